@@ -3,20 +3,67 @@
 //! Commands:
 //! - `download` — fetch market data from Yahoo Finance and cache as Parquet
 //! - `run` — execute a backtest from a TOML config file or named preset
+//!   (`--profile` prints where the engine spent its time)
 //! - `cache status` — report cache size, symbol count, date ranges
 //! - `cache clean` — remove symbols not accessed recently
+//! - `cache compact` — merge each symbol's cache parts into one
+//! - `promote` — re-run the top saved runs through the promotion ladder
+//! - `history lineage` — print a config's ancestry from a YOLO history file
+//! - `history compact` — rewrite a YOLO history file under a write preset
+//! - `config validate` — check a TOML config without running it
+//! - `config show` — print the effective settings and where each came from
+//! - `leaderboard refresh` — re-test a saved session's leaderboard entries
+//!   on data that has grown since the session ran
+//! - `holdout evaluate` — run a saved session's leaderboards once on the
+//!   holdout window it withheld from discovery
+//! - `verify-run` — re-run saved runs from the cache and report the first
+//!   place each departs from its manifest
+//! - `corpus run` — run a directory of configs and save a summary snapshot
+//! - `corpus diff` — compare two snapshots, exiting with 1 when results drifted
+//! - `inspect` — print a saved run's summary, optionally recomputed over a
+//!   date window (`--from` / `--to`)
+//! - `signal-check` — evaluate a config on the latest cached bar of each
+//!   symbol and print the entry it would place, without a backtest
+//! - `paper init` — start paper-trading promoted champions (or a config)
+//! - `paper update` — advance each paper book over the bars since its last
+//!   update and print open positions with unrealized PnL
+//!
+//! `run` exits with a code per failure category: 3 data load, 4 invalid
+//! config, 5 engine invariant or inconsistent result, 6 artifact I/O (1 for
+//! anything else).
+//! `config validate` exits with 4 when the config has errors.
+//!
+//! Cache and results directories, default symbols, dates and execution
+//! preset come from `~/.config/trendlab/config.toml` and `TRENDLAB_*` env
+//! vars when not given as flags (see `trendlab_runner::settings`).
 
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
 use chrono::NaiveDate;
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use trendlab_core::components::composition::StrategyPreset;
+use trendlab_core::data::cache::format_size;
 use trendlab_core::data::{
-    download_symbols, CircuitBreaker, ParquetCache, StdoutProgress, YahooProvider,
+    download_symbols, BarInterval, CircuitBreaker, DownloadOutcome, ParquetCache, StdoutProgress,
+    YahooProvider,
+};
+use trendlab_core::domain::FullHash;
+use trendlab_core::engine::{EngineTimings, WarmupReport};
+use trendlab_core::fingerprint::TradingMode;
+use trendlab_runner::runner::RunErrorKind;
+use trendlab_runner::verify::verify_run_dir;
+use trendlab_runner::{
+    check_signals, diff_corpora, lineage, load_artifacts, load_bars, load_snapshot, save_snapshot,
+    scan_artifacts, snapshot_partitions, top_by_sharpe, BacktestConfig, BacktestResult,
+    CheckOutcome, CorpusOutcome, FitnessMetric, HistoryEntry, HoldoutError, IssueSeverity,
+    LoadOptions, PaperChampion, PaperEvent, PaperState, PaperStore, PromotionCandidate,
+    PromotionConfig, PromotionReport, RDistribution, RefreshConfig, RetentionPolicy, RetentionRule,
+    RiskProfile, Settings, SliceError, Sourced, StoredLeaderboard, StyleReport, TailMetrics,
+    TrendLab, ValidationReport, WriteFilter, WritePreset, YoloHistory, YoloResult,
+    DEFAULT_DRIFT_TOLERANCE, DEFAULT_SNAPSHOT_MAX_BYTES, DEFAULT_STALE_AFTER_DAYS,
+    DEFAULT_TOLERANCE, LEADERBOARDS_DIR, SNAPSHOT_DIR,
 };
-use trendlab_runner::runner::run_single_backtest;
-use trendlab_runner::{save_artifacts, BacktestConfig, BacktestResult, LoadOptions};
 
 #[derive(Parser)]
 #[command(
@@ -32,25 +79,26 @@ struct Cli {
 enum Commands {
     /// Download market data from Yahoo Finance and cache as Parquet.
     Download {
-        /// Symbols to download (e.g., SPY QQQ AAPL).
-        #[arg(required = true)]
+        /// Symbols to download (e.g., SPY QQQ AAPL). Defaults to the symbols
+        /// setting.
         symbols: Vec<String>,
 
-        /// Start date (YYYY-MM-DD). Defaults to 10 years ago.
+        /// Start date (YYYY-MM-DD). Defaults to the start_date setting, else
+        /// 10 years ago.
         #[arg(long)]
         start: Option<String>,
 
-        /// End date (YYYY-MM-DD). Defaults to today.
+        /// End date (YYYY-MM-DD). Defaults to the end_date setting, else today.
         #[arg(long)]
         end: Option<String>,
 
-        /// Force re-download even if cached.
+        /// Force re-download even if cached or completed by an earlier run.
         #[arg(long, default_value_t = false)]
         force: bool,
 
-        /// Cache directory. Defaults to ./data.
-        #[arg(long, default_value = "data")]
-        cache_dir: PathBuf,
+        /// Cache directory. Defaults to the cache_dir setting (./data).
+        #[arg(long)]
+        cache_dir: Option<PathBuf>,
     },
     /// Execute a backtest from a TOML config file or named preset.
     Run {
@@ -62,15 +110,18 @@ enum Commands {
         #[arg(long)]
         preset: Option<String>,
 
-        /// Symbol (required with --preset).
+        /// Symbol for --preset. Defaults to the first of the symbols
+        /// setting, else SPY.
         #[arg(long)]
         symbol: Option<String>,
 
-        /// Start date (YYYY-MM-DD). Defaults to 5 years ago.
+        /// Start date (YYYY-MM-DD) for --preset. Defaults to the start_date
+        /// setting, else 2020-01-02.
         #[arg(long)]
         start: Option<String>,
 
-        /// End date (YYYY-MM-DD). Defaults to today.
+        /// End date (YYYY-MM-DD) for --preset. Defaults to the end_date
+        /// setting, else 2024-12-31.
         #[arg(long)]
         end: Option<String>,
 
@@ -82,28 +133,560 @@ enum Commands {
         #[arg(long, default_value_t = false)]
         synthetic: bool,
 
-        /// Cache directory. Defaults to ./data.
-        #[arg(long, default_value = "data")]
-        cache_dir: PathBuf,
+        /// Fail the run if the bars contain severe anomalies (bad ticks, OHLC violations).
+        #[arg(long, default_value_t = false)]
+        strict_data: bool,
+
+        /// Refresh (or, offline, flag) cached data ending more than this many
+        /// days before the end date. 0 disables the check.
+        #[arg(long, default_value_t = DEFAULT_STALE_AFTER_DAYS)]
+        stale_after_days: u32,
+
+        /// Cache directory. Defaults to the cache_dir setting (./data).
+        #[arg(long)]
+        cache_dir: Option<PathBuf>,
+
+        /// Output directory for result JSON. Defaults to the results_dir
+        /// setting (./results).
+        #[arg(long)]
+        output_dir: Option<PathBuf>,
+
+        /// Print a per-phase engine timing breakdown after the run.
+        #[arg(long, default_value_t = false)]
+        profile: bool,
+
+        /// Bar interval: daily or weekly. Overrides the config's interval.
+        #[arg(long)]
+        interval: Option<BarInterval>,
 
-        /// Output directory for result JSON.
-        #[arg(long, default_value = "results")]
-        output_dir: PathBuf,
+        /// Risk profile whose position cap, drawdown kill switch and required
+        /// filter apply to the run: balanced, conservative, aggressive,
+        /// trend-options. Overrides the config's risk_profile.
+        #[arg(long)]
+        risk_profile: Option<RiskProfile>,
     },
     /// Cache management commands.
     Cache {
         #[command(subcommand)]
         action: CacheAction,
     },
+    /// Re-run the top saved runs through the promotion ladder
+    /// (walk-forward, execution MC, bootstrap).
+    Promote(PromoteArgs),
+    /// YOLO history commands.
+    History {
+        #[command(subcommand)]
+        action: HistoryAction,
+    },
+    /// Backtest config commands.
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Saved YOLO leaderboard commands.
+    Leaderboard {
+        #[command(subcommand)]
+        action: LeaderboardAction,
+    },
+    /// Saved YOLO session holdout commands.
+    Holdout {
+        #[command(subcommand)]
+        action: HoldoutAction,
+    },
+    /// Saved run directory commands.
+    Results {
+        #[command(subcommand)]
+        action: ResultsAction,
+    },
+    /// Re-run a saved run from the cache and check it reproduces its manifest.
+    VerifyRun(VerifyRunArgs),
+    /// Re-run a library of configs and compare the results across engine
+    /// changes.
+    Corpus {
+        #[command(subcommand)]
+        action: CorpusAction,
+    },
+    /// Print a saved run's summary, with metrics recomputed over a date
+    /// window when --from or --to is given.
+    Inspect(InspectArgs),
+    /// Evaluate a config's signal and filter on the latest completed bar and
+    /// report the entry order and initial stop it would place.
+    SignalCheck(SignalCheckArgs),
+    /// Paper-trading commands: hypothetical live positions for champions.
+    Paper {
+        #[command(subcommand)]
+        action: PaperAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum PaperAction {
+    /// Start paper books for the promoted entries of a promotion report, or
+    /// for a config on its symbols. Champions already tracked are skipped.
+    Init(PaperInitArgs),
+    /// Process the bars since each book's last update, append the actions to
+    /// its journal and print open positions.
+    Update(PaperUpdateArgs),
+}
+
+#[derive(Args)]
+struct PaperInitArgs {
+    /// Promotion report to take promoted champions from. Defaults to
+    /// promotion_report.json in the results directory.
+    #[arg(long, conflicts_with = "config")]
+    report: Option<PathBuf>,
+
+    /// TOML config to paper trade instead of a promotion report.
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Comma-separated symbols for --config. Defaults to the config's symbol.
+    #[arg(long, value_delimiter = ',', requires = "config")]
+    symbols: Vec<String>,
+
+    /// Trading mode for report champions: long_only, short_only, long_short.
+    #[arg(long, default_value = "long_only", conflicts_with = "config")]
+    trading_mode: String,
+
+    /// First bar date to trade (YYYY-MM-DD). Defaults to today.
+    #[arg(long)]
+    since: Option<String>,
+
+    /// Results directory holding the paper books. Defaults to the
+    /// results_dir setting (./results).
+    #[arg(long)]
+    results_dir: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct PaperUpdateArgs {
+    /// Fetch bars missing from the cache's tail before updating. Without it
+    /// only cached data is read.
+    #[arg(long, default_value_t = false)]
+    refresh: bool,
+
+    /// Process a bar dated today, which may still be forming.
+    #[arg(long, default_value_t = false)]
+    include_today: bool,
+
+    /// Days of history loaded for indicators.
+    #[arg(long, default_value_t = 730)]
+    history_days: u32,
+
+    /// Print the updated states as JSON instead of a table.
+    #[arg(long, default_value_t = false)]
+    json: bool,
+
+    /// Results directory holding the paper books. Defaults to the
+    /// results_dir setting (./results).
+    #[arg(long)]
+    results_dir: Option<PathBuf>,
+
+    /// Cache directory. Defaults to the cache_dir setting (./data).
+    #[arg(long)]
+    cache_dir: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum HoldoutAction {
+    /// Run the session's leaderboard configs on its holdout window. A
+    /// session's holdout is evaluated only once.
+    Evaluate(HoldoutArgs),
+}
+
+#[derive(Args)]
+struct HoldoutArgs {
+    /// Saved session directory (or its session.json).
+    session: PathBuf,
+
+    /// YOLO history JSONL to append holdout records to.
+    #[arg(long)]
+    history: Option<PathBuf>,
+
+    /// Cache directory. Defaults to the cache_dir setting (./data).
+    #[arg(long)]
+    cache_dir: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum ResultsAction {
+    /// Delete saved runs that no retention rule keeps. Runs referenced by a
+    /// session, leaderboard file or promotion report, the top runs of each
+    /// symbol and recent runs are kept.
+    Prune(PruneArgs),
+}
+
+#[derive(Args)]
+struct PruneArgs {
+    /// Results directory holding the run directories. Defaults to the
+    /// results_dir setting (./results).
+    #[arg(long)]
+    dir: Option<PathBuf>,
+
+    /// Runs kept per symbol, best first by --metric.
+    #[arg(long, default_value_t = 10)]
+    keep_top: usize,
+
+    /// Metric ranking a symbol's runs: sharpe, sortino, calmar, cagr,
+    /// win-rate, profit-factor or max-drawdown.
+    #[arg(long, default_value = "sharpe")]
+    metric: FitnessMetric,
+
+    /// Keep runs saved within this many days. 0 disables the rule.
+    #[arg(long, default_value_t = 30)]
+    keep_days: u64,
+
+    /// Actually delete (without this flag, only previews what would be removed).
+    #[arg(long, default_value_t = false)]
+    confirm: bool,
+
+    /// Also delete runs that sessions, leaderboard files or promotion
+    /// reports reference, unless another rule keeps them.
+    #[arg(long, default_value_t = false)]
+    force: bool,
+}
+
+#[derive(Args)]
+struct VerifyRunArgs {
+    /// Run artifact directory (holding manifest.json), or with --all a
+    /// results directory of them.
+    run_dir: PathBuf,
+
+    /// Verify every saved run under the results directory.
+    #[arg(long, default_value_t = false)]
+    all: bool,
+
+    /// Largest difference in PnL or a metric still counted as a match
+    /// (relative above magnitude 1).
+    #[arg(long, default_value_t = DEFAULT_TOLERANCE)]
+    tolerance: f64,
+
+    /// Refuse to re-run when any symbol's cached data changed since the run,
+    /// even outside the run's date range.
+    #[arg(long, default_value_t = false)]
+    as_of: bool,
+
+    /// Re-run against the partitions `cache snapshot` copied into each run's
+    /// directory instead of the cache.
+    #[arg(long, default_value_t = false)]
+    from_snapshot: bool,
+
+    /// Cache directory. Defaults to the cache_dir setting (./data).
+    #[arg(long)]
+    cache_dir: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum CorpusAction {
+    /// Run every TOML config under a directory and save their metrics and
+    /// trade counts as corpus.json in the output directory.
+    Run(CorpusRunArgs),
+    /// Compare two corpus snapshots config by config. Exits with 1 when any
+    /// config drifted beyond the tolerance.
+    Diff(CorpusDiffArgs),
+}
+
+#[derive(Args)]
+struct CorpusRunArgs {
+    /// Directory of TOML configs, searched recursively.
+    #[arg(long)]
+    configs: PathBuf,
+
+    /// Directory to write corpus.json to.
+    #[arg(long)]
+    out: PathBuf,
+
+    /// Worker threads (0 = all cores).
+    #[arg(long, default_value_t = 0)]
+    jobs: usize,
+
+    /// Offline mode: no network access.
+    #[arg(long, default_value_t = false)]
+    offline: bool,
+
+    /// Use synthetic data as fallback.
+    #[arg(long, default_value_t = false)]
+    synthetic: bool,
+
+    /// Refresh (or, offline, flag) cached data ending more than this many
+    /// days before the end date. 0 disables the check.
+    #[arg(long, default_value_t = DEFAULT_STALE_AFTER_DAYS)]
+    stale_after_days: u32,
+
+    /// Cache directory. Defaults to the cache_dir setting (./data).
+    #[arg(long)]
+    cache_dir: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct CorpusDiffArgs {
+    /// Earlier snapshot: a `corpus run` output directory or its corpus.json.
+    a: PathBuf,
+
+    /// Later snapshot, compared against the first.
+    b: PathBuf,
+
+    /// Largest absolute metric move still counted as no drift.
+    #[arg(long, default_value_t = DEFAULT_DRIFT_TOLERANCE)]
+    tolerance: f64,
+
+    /// Print the diff as JSON instead of a report.
+    #[arg(long, default_value_t = false)]
+    json: bool,
+}
+
+#[derive(Args)]
+struct InspectArgs {
+    /// Run artifact directory (holding manifest.json).
+    run_dir: PathBuf,
+
+    /// First date of the window (YYYY-MM-DD). Defaults to the run's start.
+    #[arg(long)]
+    from: Option<String>,
+
+    /// Last date of the window (YYYY-MM-DD). Defaults to the run's end.
+    #[arg(long)]
+    to: Option<String>,
+}
+
+#[derive(Args)]
+struct SignalCheckArgs {
+    /// Path to TOML config file.
+    #[arg(long)]
+    config: PathBuf,
+
+    /// Comma-separated symbols. Defaults to the config's symbol.
+    #[arg(long, value_delimiter = ',')]
+    symbols: Vec<String>,
+
+    /// Fetch bars missing from the cache's tail before evaluating. Without
+    /// it only cached data is read.
+    #[arg(long, default_value_t = false)]
+    refresh: bool,
+
+    /// Evaluate a bar dated today, which may still be forming.
+    #[arg(long, default_value_t = false)]
+    include_today: bool,
+
+    /// Flag symbols whose last bar is more than this many days old.
+    #[arg(long, default_value_t = DEFAULT_STALE_AFTER_DAYS)]
+    stale_after_days: u32,
+
+    /// Days of history to download for symbols not yet cached (--refresh).
+    #[arg(long, default_value_t = 730)]
+    history_days: u32,
+
+    /// Print the checks as JSON instead of a table.
+    #[arg(long, default_value_t = false)]
+    json: bool,
+
+    /// Cache directory. Defaults to the cache_dir setting (./data).
+    #[arg(long)]
+    cache_dir: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum LeaderboardAction {
+    /// Re-run entries whose data ends before the latest available bar and
+    /// flag those whose fitness degraded.
+    Refresh(RefreshArgs),
+    /// Print a symbol's persistent leaderboard file.
+    Show(ShowArgs),
+}
+
+#[derive(Args)]
+struct ShowArgs {
+    /// Symbol whose leaderboard to print.
+    symbol: String,
+
+    /// Leaderboard directory (holding symbol=<SYMBOL>.json files).
+    /// Defaults to sessions/leaderboards under the results_dir setting.
+    #[arg(long)]
+    dir: Option<PathBuf>,
+
+    /// Number of entries to print.
+    #[arg(long, default_value_t = 20)]
+    top: usize,
+
+    /// YOLO history JSONL to replay and compare against the file.
+    #[arg(long)]
+    history: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct RefreshArgs {
+    /// Saved session directory (or its session.json).
+    session: PathBuf,
+
+    /// YOLO history JSONL to append re-test records to.
+    #[arg(long)]
+    history: Option<PathBuf>,
+
+    /// Fraction of the previous fitness an entry may lose before it is
+    /// flagged as degraded.
+    #[arg(long, default_value_t = 0.3)]
+    max_degradation: f64,
+
+    /// Last date to load (YYYY-MM-DD). Defaults to today.
+    #[arg(long)]
+    end: Option<String>,
+
+    /// Offline mode: re-test against the cache only.
+    #[arg(long, default_value_t = false)]
+    offline: bool,
+
+    /// Extend cached data ending more than this many days before the end
+    /// date. 0 disables the check.
+    #[arg(long, default_value_t = DEFAULT_STALE_AFTER_DAYS)]
+    stale_after_days: u32,
+
+    /// Cache directory. Defaults to the cache_dir setting (./data).
+    #[arg(long)]
+    cache_dir: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Check a TOML config for errors without loading data or running it.
+    Validate {
+        /// Path to a TOML config file.
+        path: PathBuf,
+
+        /// Also check that the config's symbols are in the cache.
+        #[arg(long, default_value_t = false)]
+        offline: bool,
+
+        /// Cache directory. Defaults to the cache_dir setting (./data).
+        #[arg(long)]
+        cache_dir: Option<PathBuf>,
+    },
+    /// Print the effective settings (config file, TRENDLAB_* env vars,
+    /// built-in defaults) and where each value came from.
+    Show,
+}
+
+#[derive(Subcommand)]
+enum HistoryAction {
+    /// Print a config's ancestry back to its original random sample.
+    Lineage {
+        /// Full hash of the config, or a unique prefix. A config hash prefix
+        /// selects the most recent config with that structure.
+        hash: String,
+
+        /// YOLO history JSONL file.
+        #[arg(long)]
+        history: PathBuf,
+    },
+
+    /// Rewrite a history file keeping only what a write preset keeps.
+    Compact {
+        /// YOLO history JSONL file to read.
+        history: PathBuf,
+
+        /// File to write the compacted history to.
+        #[arg(long)]
+        output: PathBuf,
+
+        /// Write preset: everything, leaderboard-only, top-quartile or
+        /// errors-and-warnings.
+        #[arg(long)]
+        preset: WritePreset,
+
+        /// Move equity curves and trades of entries over this many KB to
+        /// side files next to the output.
+        #[arg(long)]
+        entry_budget_kb: Option<usize>,
+
+        /// Leaderboard slots per symbol for the leaderboard-only preset.
+        #[arg(long, default_value_t = 500)]
+        leaderboard_size: usize,
+    },
+}
+
+#[derive(Args)]
+struct PromoteArgs {
+    /// Results directory holding saved run manifests.
+    results_dir: Option<PathBuf>,
+
+    /// YOLO history JSONL to read candidates from (instead of, or as well as, manifests).
+    #[arg(long)]
+    history: Option<PathBuf>,
+
+    /// Number of candidates to promote, best recorded Sharpe first.
+    #[arg(long, default_value_t = 10)]
+    top: usize,
+
+    /// Promotion config TOML. The flags below override its values.
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Minimum Sharpe to attempt walk-forward.
+    #[arg(long)]
+    sharpe_threshold: Option<f64>,
+
+    /// Minimum walk-forward degradation ratio to reach execution MC.
+    #[arg(long)]
+    degradation_threshold: Option<f64>,
+
+    /// Walk-forward folds.
+    #[arg(long)]
+    folds: Option<usize>,
+
+    /// Execution Monte Carlo samples.
+    #[arg(long)]
+    mc_samples: Option<usize>,
+
+    /// Bootstrap resamples.
+    #[arg(long)]
+    bootstrap_resamples: Option<usize>,
+
+    /// Trading mode for candidates that don't record one: long_only, short_only, long_short.
+    #[arg(long, default_value = "long_only")]
+    trading_mode: String,
+
+    /// Fraction of equity per position.
+    #[arg(long, default_value_t = 1.0)]
+    position_size: f64,
+
+    /// Worker threads (0 = all cores).
+    #[arg(long, default_value_t = 0)]
+    jobs: usize,
+
+    /// Offline mode: no network access.
+    #[arg(long, default_value_t = false)]
+    offline: bool,
+
+    /// Use synthetic data as fallback.
+    #[arg(long, default_value_t = false)]
+    synthetic: bool,
+
+    /// Refresh (or, offline, flag) cached data ending more than this many
+    /// days before the end date. 0 disables the check.
+    #[arg(long, default_value_t = DEFAULT_STALE_AFTER_DAYS)]
+    stale_after_days: u32,
+
+    /// Cache directory. Defaults to the cache_dir setting (./data).
+    #[arg(long)]
+    cache_dir: Option<PathBuf>,
+
+    /// Recompute every ladder level instead of reusing results stored under
+    /// robustness_cache/ in the results directory.
+    #[arg(long, default_value_t = false)]
+    no_cache: bool,
+
+    /// JSON report path. Defaults to promotion_report.json in the results
+    /// directory (or the working directory with --history only).
+    #[arg(long)]
+    output: Option<PathBuf>,
 }
 
 #[derive(Subcommand)]
 enum CacheAction {
     /// Report cache size, symbol count, and date ranges.
     Status {
-        /// Cache directory. Defaults to ./data.
-        #[arg(long, default_value = "data")]
-        cache_dir: PathBuf,
+        /// Cache directory. Defaults to the cache_dir setting (./data).
+        #[arg(long)]
+        cache_dir: Option<PathBuf>,
     },
     /// Remove cached symbols not accessed within the given number of days.
     Clean {
@@ -111,18 +694,47 @@ enum CacheAction {
         #[arg(long)]
         unused_days: u64,
 
-        /// Cache directory. Defaults to ./data.
-        #[arg(long, default_value = "data")]
-        cache_dir: PathBuf,
+        /// Cache directory. Defaults to the cache_dir setting (./data).
+        #[arg(long)]
+        cache_dir: Option<PathBuf>,
 
         /// Actually delete (without this flag, only previews what would be removed).
         #[arg(long, default_value_t = false)]
         confirm: bool,
     },
+    /// Merge each symbol's cache parts into one, dropping overlapped bars and
+    /// leftovers of interrupted writes.
+    Compact {
+        /// Symbols to compact. Defaults to every cached symbol.
+        symbols: Vec<String>,
+
+        /// Bar interval: daily or weekly.
+        #[arg(long, default_value = "daily")]
+        interval: BarInterval,
+
+        /// Cache directory. Defaults to the cache_dir setting (./data).
+        #[arg(long)]
+        cache_dir: Option<PathBuf>,
+    },
+    /// Copy the cache partitions a saved run read into its artifact
+    /// directory, so `verify-run --from-snapshot` needs no shared cache.
+    Snapshot {
+        /// Run artifact directory (holding manifest.json).
+        run_dir: PathBuf,
+
+        /// Refuse when the partitions add up to more than this many MB.
+        #[arg(long, default_value_t = DEFAULT_SNAPSHOT_MAX_BYTES / (1024 * 1024))]
+        max_mb: u64,
+
+        /// Cache directory. Defaults to the cache_dir setting (./data).
+        #[arg(long)]
+        cache_dir: Option<PathBuf>,
+    },
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    let settings = Settings::load()?;
 
     match cli.command {
         Commands::Download {
@@ -131,7 +743,14 @@ fn main() -> Result<()> {
             end,
             force,
             cache_dir,
-        } => run_download(symbols, start, end, force, cache_dir),
+        } => run_download(
+            symbols,
+            start,
+            end,
+            force,
+            settings.cache_dir.or_flag(cache_dir),
+            &settings,
+        ),
         Commands::Run {
             config,
             preset,
@@ -140,18 +759,92 @@ fn main() -> Result<()> {
             end,
             offline,
             synthetic,
+            strict_data,
+            stale_after_days,
             cache_dir,
             output_dir,
+            profile,
+            interval,
+            risk_profile,
         } => run_backtest_cmd(
-            config, preset, symbol, start, end, offline, synthetic, cache_dir, output_dir,
+            config,
+            preset,
+            symbol,
+            start,
+            end,
+            offline,
+            synthetic,
+            strict_data,
+            stale_after_days,
+            settings.cache_dir.or_flag(cache_dir),
+            settings.results_dir.or_flag(output_dir),
+            profile,
+            interval,
+            risk_profile,
+            &settings,
         ),
         Commands::Cache { action } => match action {
-            CacheAction::Status { cache_dir } => run_cache_status(&cache_dir),
+            CacheAction::Status { cache_dir } => {
+                run_cache_status(&settings.cache_dir.or_flag(cache_dir))
+            }
             CacheAction::Clean {
                 unused_days,
                 cache_dir,
                 confirm,
-            } => run_cache_clean(&cache_dir, unused_days, confirm),
+            } => run_cache_clean(&settings.cache_dir.or_flag(cache_dir), unused_days, confirm),
+            CacheAction::Compact {
+                symbols,
+                interval,
+                cache_dir,
+            } => run_cache_compact(&settings.cache_dir.or_flag(cache_dir), &symbols, interval),
+            CacheAction::Snapshot {
+                run_dir,
+                max_mb,
+                cache_dir,
+            } => run_cache_snapshot(&settings.cache_dir.or_flag(cache_dir), &run_dir, max_mb),
+        },
+        Commands::Promote(args) => run_promote_cmd(args, &settings),
+        Commands::History { action } => match action {
+            HistoryAction::Lineage { hash, history } => run_history_lineage(&hash, &history),
+            HistoryAction::Compact {
+                history,
+                output,
+                preset,
+                entry_budget_kb,
+                leaderboard_size,
+            } => run_history_compact(&history, &output, preset, entry_budget_kb, leaderboard_size),
+        },
+        Commands::Config { action } => match action {
+            ConfigAction::Validate {
+                path,
+                offline,
+                cache_dir,
+            } => run_config_validate(&path, offline, &settings.cache_dir.or_flag(cache_dir)),
+            ConfigAction::Show => {
+                run_config_show(&settings);
+                Ok(())
+            }
+        },
+        Commands::Leaderboard { action } => match action {
+            LeaderboardAction::Refresh(args) => run_leaderboard_refresh(args, &settings),
+            LeaderboardAction::Show(args) => run_leaderboard_show(args, &settings),
+        },
+        Commands::Holdout { action } => match action {
+            HoldoutAction::Evaluate(args) => run_holdout_evaluate(args, &settings),
+        },
+        Commands::Results { action } => match action {
+            ResultsAction::Prune(args) => run_results_prune(args, &settings),
+        },
+        Commands::VerifyRun(args) => run_verify_cmd(args, &settings),
+        Commands::Corpus { action } => match action {
+            CorpusAction::Run(args) => run_corpus_cmd(args, &settings),
+            CorpusAction::Diff(args) => run_corpus_diff(args),
+        },
+        Commands::Inspect(args) => run_inspect(args),
+        Commands::SignalCheck(args) => run_signal_check(args, &settings),
+        Commands::Paper { action } => match action {
+            PaperAction::Init(args) => run_paper_init(args, &settings),
+            PaperAction::Update(args) => run_paper_update(args, &settings),
         },
     }
 }
@@ -162,17 +855,33 @@ fn run_download(
     end: Option<String>,
     force: bool,
     cache_dir: PathBuf,
+    settings: &Settings,
 ) -> Result<()> {
-    let start_date = start
+    let symbols = if symbols.is_empty() {
+        settings.symbols.value.clone()
+    } else {
+        symbols
+    };
+    if symbols.is_empty() {
+        bail!("no symbols given and no symbols setting (config file or TRENDLAB_SYMBOLS)");
+    }
+
+    let start = start
         .as_deref()
         .map(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d"))
-        .transpose()?
+        .transpose()?;
+    let start_date = settings
+        .start_date
+        .or_flag_opt(start)
         .unwrap_or_else(|| chrono::Local::now().date_naive() - chrono::Duration::days(365 * 10));
 
-    let end_date = end
+    let end = end
         .as_deref()
         .map(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d"))
-        .transpose()?
+        .transpose()?;
+    let end_date = settings
+        .end_date
+        .or_flag_opt(end)
         .unwrap_or_else(|| chrono::Local::now().date_naive());
 
     let circuit_breaker = Arc::new(CircuitBreaker::default_provider());
@@ -183,13 +892,42 @@ fn run_download(
     let sym_refs: Vec<&str> = symbols.iter().map(|s| s.as_str()).collect();
 
     let summary = download_symbols(
-        &provider, &cache, &sym_refs, start_date, end_date, force, &progress,
+        &provider,
+        &cache,
+        &sym_refs,
+        start_date,
+        end_date,
+        force,
+        settings.drop_partial_last_bar.value,
+        &progress,
     );
 
-    if !summary.all_succeeded() {
+    for outcome in &summary.outcomes {
+        if let DownloadOutcome::PartialData {
+            covered_start,
+            covered_end,
+            bars,
+            expected_bars,
+        } = &outcome.outcome
+        {
+            println!(
+                "  PARTIAL: {}: {bars}/{expected_bars} bars, covers {covered_start} to {covered_end}",
+                outcome.symbol
+            );
+        }
+    }
+    for (symbol, report) in summary.corrected() {
+        println!("  CORRECTED: {symbol}: {report}");
+    }
+
+    if summary.has_failures() {
         for (sym, err) in &summary.errors {
             eprintln!("Error for {sym}: {err}");
         }
+        eprintln!(
+            "\nRetry the failed symbols with:\n  trendlab download {} --start {start_date} --end {end_date}",
+            summary.failed_symbols().join(" ")
+        );
         std::process::exit(1);
     }
 
@@ -205,8 +943,14 @@ fn run_backtest_cmd(
     end: Option<String>,
     offline: bool,
     synthetic: bool,
+    strict_data: bool,
+    stale_after_days: u32,
     cache_dir: PathBuf,
     output_dir: PathBuf,
+    profile: bool,
+    interval: Option<BarInterval>,
+    risk_profile: Option<RiskProfile>,
+    settings: &Settings,
 ) -> Result<()> {
     // Validate mutually exclusive options
     if config_path.is_some() && preset_name.is_some() {
@@ -216,220 +960,1142 @@ fn run_backtest_cmd(
         bail!("one of --config or --preset is required");
     }
 
+    let lab = TrendLab::new(settings)
+        .with_cache_dir(cache_dir)
+        .with_results_dir(&output_dir)
+        .with_offline(offline)
+        .with_synthetic(synthetic)
+        .with_strict_data(strict_data)
+        .with_stale_after_days((stale_after_days > 0).then_some(stale_after_days));
+
     // Build BacktestConfig
-    let backtest_config = if let Some(path) = config_path {
+    let mut backtest_config = if let Some(path) = config_path {
         BacktestConfig::from_file(&path)?
     } else {
         let preset_name = preset_name.unwrap();
-        let sym = symbol.as_deref().unwrap_or("SPY");
-        build_config_from_preset(&preset_name, sym, start.as_deref(), end.as_deref())?
-    };
-
-    // Build load options
-    let start_date = NaiveDate::parse_from_str(&backtest_config.backtest.start_date, "%Y-%m-%d")?;
-    let end_date = NaiveDate::parse_from_str(&backtest_config.backtest.end_date, "%Y-%m-%d")?;
-    let opts = LoadOptions {
-        start: start_date,
-        end: end_date,
-        offline,
-        synthetic,
-        force: false,
+        let sym = symbol
+            .or_else(|| settings.symbols.value.first().cloned())
+            .unwrap_or_else(|| "SPY".to_string());
+        let start = preset_date(start, &settings.start_date, (2020, 1, 2))?;
+        let end = preset_date(end, &settings.end_date, (2024, 12, 31))?;
+        lab.preset_config(&preset_name, &sym, start..=end)?
     };
-
-    // Set up cache + provider
-    let cache = ParquetCache::new(&cache_dir);
-    let circuit_breaker = Arc::new(CircuitBreaker::default_provider());
-    let provider = YahooProvider::new(circuit_breaker);
-
-    let provider_ref: Option<&dyn trendlab_core::data::provider::DataProvider> =
-        if offline { None } else { Some(&provider) };
+    if let Some(interval) = interval {
+        backtest_config.backtest.interval = interval;
+    }
+    if risk_profile.is_some() {
+        backtest_config.risk_profile = risk_profile;
+    }
+    let report = backtest_config.validate(None);
+    print_validation_report(&report);
+    if report.has_errors() {
+        std::process::exit(exit_code(RunErrorKind::ConfigInvalid));
+    }
 
     // Run backtest
-    let result = run_single_backtest(&backtest_config, &cache, provider_ref, &opts)?;
+    let result = match lab.run(&backtest_config) {
+        Ok(result) => result,
+        Err(err) => {
+            eprintln!("Error: {err}");
+            if err.is_retryable() {
+                eprintln!("\nThis failure may be transient; re-running may succeed.");
+            }
+            std::process::exit(exit_code(err.kind()));
+        }
+    };
 
     // Print summary
     print_summary(&result);
+    if profile {
+        print_profile(&result.timings);
+    }
 
     // Save full artifact set (manifest.json, trades.csv, equity.csv)
-    let run_dir = save_artifacts(&result, &output_dir)?;
+    let run_dir = match lab.save(&result) {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("Error: saving artifacts: {err}");
+            std::process::exit(exit_code(err.kind()));
+        }
+    };
     println!("Artifacts saved to: {}", run_dir.display());
 
     Ok(())
 }
 
-fn build_config_from_preset(
-    name: &str,
-    symbol: &str,
-    start: Option<&str>,
-    end: Option<&str>,
-) -> Result<BacktestConfig> {
-    let preset = match name {
-        "donchian_trend" => StrategyPreset::DonchianTrend,
-        "bollinger_breakout" => StrategyPreset::BollingerBreakout,
-        "ma_crossover" => StrategyPreset::MaCrossoverTrend,
-        "momentum_roc" => StrategyPreset::MomentumRoc,
-        "supertrend" => StrategyPreset::SupertrendSystem,
-        _ => bail!(
-            "unknown preset '{name}'. Valid: donchian_trend, bollinger_breakout, ma_crossover, momentum_roc, supertrend"
-        ),
-    };
-
-    let strategy_config = preset.to_config();
-    let start_date = start.unwrap_or("2020-01-02");
-    let end_date = end.unwrap_or("2024-12-31");
-
-    // Build a TOML string and parse it — ensures the config goes through the same path
-    let toml_str = format!(
-        r#"[backtest]
-symbol = "{symbol}"
-start_date = "{start_date}"
-end_date = "{end_date}"
-initial_capital = 100000.0
-trading_mode = "long_only"
-position_size_pct = 1.0
-
-[signal]
-type = "{sig_type}"
-{sig_params}
-
-[position_manager]
-type = "{pm_type}"
-{pm_params}
-
-[execution_model]
-type = "{exec_type}"
-{exec_params}
-
-[signal_filter]
-type = "{filter_type}"
-{filter_params}
-"#,
-        sig_type = strategy_config.signal.component_type,
-        sig_params = format_params("signal", &strategy_config.signal.params),
-        pm_type = strategy_config.position_manager.component_type,
-        pm_params = format_params("position_manager", &strategy_config.position_manager.params),
-        exec_type = strategy_config.execution_model.component_type,
-        exec_params = format_params("execution_model", &strategy_config.execution_model.params),
-        filter_type = strategy_config.signal_filter.component_type,
-        filter_params = format_params("signal_filter", &strategy_config.signal_filter.params),
-    );
-
-    Ok(BacktestConfig::from_toml(&toml_str)?)
+/// Process exit code for a failed `run`, by error category.
+fn run_config_validate(path: &Path, offline: bool, cache_dir: &Path) -> Result<()> {
+    let config = BacktestConfig::from_file(path)?;
+    let cache = offline.then(|| ParquetCache::new(cache_dir));
+    let report = config.validate(cache.as_ref());
+    print_validation_report(&report);
+
+    let errors = report.errors().count();
+    let warnings = report.warnings().count();
+    if errors > 0 {
+        eprintln!(
+            "\n{}: {errors} error(s), {warnings} warning(s)",
+            path.display()
+        );
+        std::process::exit(exit_code(RunErrorKind::ConfigInvalid));
+    }
+    println!("{}: ok ({warnings} warning(s))", path.display());
+    Ok(())
 }
 
-fn format_params(section: &str, params: &std::collections::BTreeMap<String, f64>) -> String {
-    if params.is_empty() {
-        return String::new();
+/// Print each issue to stderr, with component hints indented beneath.
+fn print_validation_report(report: &ValidationReport) {
+    for issue in &report.issues {
+        let label = match issue.severity {
+            IssueSeverity::Error => "error",
+            IssueSeverity::Warning => "warning",
+        };
+        eprintln!("{label}: {issue}");
+        if let Some(hint) = &issue.hint {
+            eprintln!("  hint: {}", hint.replace('\n', "\n        "));
+        }
     }
-    let pairs: Vec<String> = params.iter().map(|(k, v)| format!("{k} = {v}")).collect();
-    format!("[{section}.params]\n{}", pairs.join("\n"))
 }
 
-fn run_cache_status(cache_dir: &Path) -> Result<()> {
-    if !cache_dir.exists() {
-        println!("Cache directory does not exist: {}", cache_dir.display());
-        return Ok(());
+fn exit_code(kind: RunErrorKind) -> i32 {
+    match kind {
+        RunErrorKind::DataLoad => 3,
+        RunErrorKind::ConfigInvalid => 4,
+        RunErrorKind::EngineInvariant | RunErrorKind::InconsistentResult => 5,
+        RunErrorKind::ArtifactIo => 6,
     }
+}
 
-    let mut total_size: u64 = 0;
-    let mut symbol_count = 0;
-
-    let entries = std::fs::read_dir(cache_dir)?;
-    let mut rows: Vec<(String, String, String, u64)> = Vec::new();
-
-    for entry in entries {
-        let entry = entry?;
-        let name = entry.file_name().to_string_lossy().to_string();
-        if !name.starts_with("symbol=") {
-            continue;
-        }
-
-        let symbol = name.trim_start_matches("symbol=").to_string();
-        symbol_count += 1;
-
-        // Read metadata
-        let meta_path = entry.path().join("meta.json");
-        let (date_range, bar_count) = if let Ok(content) = std::fs::read_to_string(&meta_path) {
-            if let Ok(meta) =
-                serde_json::from_str::<trendlab_core::data::cache::CacheMeta>(&content)
-            {
-                (
-                    format!("{} to {}", meta.start_date, meta.end_date),
-                    meta.bar_count,
-                )
-            } else {
-                ("(corrupt meta)".into(), 0)
-            }
-        } else {
-            ("(no meta)".into(), 0)
-        };
-
-        // Calculate directory size
-        let dir_size = dir_size(&entry.path());
-        total_size += dir_size;
-
-        rows.push((symbol, date_range, format!("{bar_count} bars"), dir_size));
+fn run_promote_cmd(args: PromoteArgs, settings: &Settings) -> Result<()> {
+    if args.results_dir.is_none() && args.history.is_none() {
+        bail!("a results directory or --history is required");
     }
+    let trading_mode = parse_trading_mode(&args.trading_mode)?;
 
-    if symbol_count == 0 {
-        println!("Cache is empty: {}", cache_dir.display());
-        return Ok(());
+    let mut promotion = match &args.config {
+        Some(path) => PromotionConfig::from_file(path)?,
+        None => PromotionConfig::default(),
+    };
+    if let Some(v) = args.sharpe_threshold {
+        promotion.wf_sharpe_threshold = v;
+    }
+    if let Some(v) = args.degradation_threshold {
+        promotion.wf_degradation_threshold = v;
+    }
+    if let Some(v) = args.folds {
+        promotion.wf_config.n_folds = v;
+    }
+    if let Some(v) = args.mc_samples {
+        promotion.mc_config.n_samples = v;
+    }
+    if let Some(v) = args.bootstrap_resamples {
+        promotion.bootstrap_config.n_resamples = v;
     }
 
-    rows.sort_by(|a, b| a.0.cmp(&b.0));
+    // Gather candidates
+    let mut candidates = Vec::new();
+    if let Some(dir) = &args.results_dir {
+        candidates.extend(
+            scan_artifacts(dir)
+                .iter()
+                .filter_map(PromotionCandidate::from_artifact),
+        );
+    }
+    if let Some(path) = &args.history {
+        candidates.extend(
+            read_history(path)?
+                .iter()
+                .map(PromotionCandidate::from_history),
+        );
+    }
+    let found = candidates.len();
+    let candidates = top_by_sharpe(candidates, args.top);
+    if candidates.is_empty() {
+        bail!("no saved runs found to promote");
+    }
+    println!(
+        "Promoting top {} of {found} saved runs...",
+        candidates.len()
+    );
 
-    println!("Cache: {}", cache_dir.display());
-    println!("Symbols: {symbol_count}");
-    println!("Total size: {}", format_size(total_size));
-    println!();
-    println!("{:<8} {:<25} {:<12} {:>10}", "Symbol", "Date Range", "Bars", "Size");
-    println!("{}", "-".repeat(58));
-    for (sym, range, bars, size) in &rows {
-        println!("{:<8} {:<25} {:<12} {:>10}", sym, range, bars, format_size(*size));
+    let lab = TrendLab::new(settings)
+        .with_cache_dir(settings.cache_dir.or_flag(args.cache_dir.clone()))
+        .with_results_dir(settings.results_dir.or_flag(args.results_dir.clone()))
+        .with_offline(args.offline)
+        .with_synthetic(args.synthetic)
+        .with_stale_after_days((args.stale_after_days > 0).then_some(args.stale_after_days))
+        .with_jobs(args.jobs)
+        .with_robustness_cache(!args.no_cache);
+
+    let report = lab.promote_batch(&candidates, &promotion, trading_mode, args.position_size);
+    print_promotion_report(&report);
+    if let Some(cache) = lab.robustness_cache() {
+        let stats = cache.stats();
+        println!(
+            "Robustness cache: {} level(s) reused, {} computed ({})",
+            stats.hits,
+            stats.misses,
+            cache.dir().display()
+        );
     }
 
+    let output = args.output.unwrap_or_else(|| {
+        args.results_dir
+            .unwrap_or_default()
+            .join("promotion_report.json")
+    });
+    std::fs::write(&output, serde_json::to_string_pretty(&report)?)?;
+    println!("Report saved to: {}", output.display());
+
     Ok(())
 }
 
-fn run_cache_clean(cache_dir: &Path, unused_days: u64, confirm: bool) -> Result<()> {
-    if !cache_dir.exists() {
-        println!("Cache directory does not exist: {}", cache_dir.display());
-        return Ok(());
+fn parse_trading_mode(mode: &str) -> Result<TradingMode> {
+    Ok(match mode {
+        "long_only" => TradingMode::LongOnly,
+        "short_only" => TradingMode::ShortOnly,
+        "long_short" => TradingMode::LongShort,
+        other => bail!("unknown trading mode '{other}' (long_only, short_only, long_short)"),
+    })
+}
+
+/// Read every entry of a history file, warning about torn lines.
+fn read_history(path: &Path) -> Result<Vec<HistoryEntry>> {
+    // Read back everything: the write filter only applies to appends
+    let history = YoloHistory::new(path.to_path_buf(), WriteFilter::default());
+    let read = history.read_all_checked()?;
+    if read.torn_lines > 0 {
+        eprintln!(
+            "warning: skipped {} torn line(s) in {}",
+            read.torn_lines,
+            path.display()
+        );
+    }
+    Ok(read.records)
+}
+
+fn run_history_compact(
+    path: &Path,
+    output: &Path,
+    preset: WritePreset,
+    entry_budget_kb: Option<usize>,
+    leaderboard_size: usize,
+) -> Result<()> {
+    if !path.exists() {
+        bail!("history file not found: {}", path.display());
+    }
+    // Appending would mix the result into an existing history (or the source)
+    if output.exists() {
+        bail!("output file already exists: {}", output.display());
     }
 
-    let cutoff = chrono::Local::now().naive_local()
-        - chrono::Duration::days(unused_days as i64);
+    let source = YoloHistory::new(path.to_path_buf(), WriteFilter::unfiltered());
+    let dest = YoloHistory::new(output.to_path_buf(), WriteFilter::unfiltered())
+        .with_entry_budget(entry_budget_kb.map(|kb| kb * 1024));
+    let report = source.compact_into(&dest, preset, leaderboard_size)?;
+    println!(
+        "Kept {} of {} entries ({preset}): {} -> {}",
+        report.kept,
+        report.kept + report.dropped,
+        format_size(report.bytes_before),
+        format_size(report.bytes_after)
+    );
+    println!("Wrote {}", output.display());
+    Ok(())
+}
 
-    let entries = std::fs::read_dir(cache_dir)?;
-    let mut to_remove: Vec<(String, PathBuf)> = Vec::new();
+fn run_history_lineage(hash: &str, path: &Path) -> Result<()> {
+    let entries = read_history(path)?;
+    let start = resolve_history_hash(&entries, hash)?;
 
-    for entry in entries {
-        let entry = entry?;
-        let name = entry.file_name().to_string_lossy().to_string();
-        if !name.starts_with("symbol=") {
+    println!("Lineage of {} (newest first):", &start.as_hex()[..12]);
+    for (generation, step) in lineage(&entries, &start).iter().enumerate() {
+        let short = &step.full_hash.as_hex()[..12];
+        let Some(entry) = step.entry else {
+            println!("{generation:>3}  {short}  (not in history; filtered out on write)");
             continue;
+        };
+        let config = &entry.fingerprint.strategy_config;
+        println!(
+            "{generation:>3}  {short}  {} + {} + {} + {}  fitness {:.3} ({})",
+            config.signal.structural_name(),
+            config.position_manager.structural_name(),
+            config.execution_model.structural_name(),
+            config.signal_filter.structural_name(),
+            entry.fitness_score,
+            entry.fingerprint.symbol
+        );
+        match &entry.provenance {
+            Some(provenance) => {
+                for change in &provenance.changes {
+                    println!("       {change}");
+                }
+            }
+            None => println!("       original random sample"),
+        }
+    }
+    Ok(())
+}
+
+fn run_leaderboard_refresh(args: RefreshArgs, settings: &Settings) -> Result<()> {
+    let mut session = YoloResult::load(&args.session)?;
+    let end = match &args.end {
+        Some(s) => NaiveDate::parse_from_str(s, "%Y-%m-%d")?,
+        None => chrono::Utc::now().date_naive(),
+    };
+    let mut symbols: Vec<&str> = session.leaderboards.keys().map(String::as_str).collect();
+    symbols.sort();
+    if symbols.is_empty() {
+        bail!("session has no leaderboards to refresh");
+    }
+
+    let opts = LoadOptions {
+        start: session.config.start_date,
+        end,
+        offline: args.offline,
+        synthetic: false,
+        force: false,
+        strict: false,
+        stale_after_days: (args.stale_after_days > 0).then_some(args.stale_after_days),
+        interval: Default::default(),
+    };
+    let cache = ParquetCache::new(settings.cache_dir.or_flag(args.cache_dir.clone()));
+    let provider =
+        (!args.offline).then(|| YahooProvider::new(Arc::new(CircuitBreaker::default_provider())));
+    let provider_ref = provider
+        .as_ref()
+        .map(|p| p as &dyn trendlab_core::data::provider::DataProvider);
+    let data = load_bars(&symbols, &cache, provider_ref, None, &opts)?;
+
+    // Untouched entries keep their artifacts across the re-save
+    session.rehydrate_artifacts()?;
+    let config = RefreshConfig {
+        max_degradation: args.max_degradation,
+        ..RefreshConfig::from_yolo(&session.config)
+    };
+    let report = session.refresh(&data, &config, chrono::Utc::now().naive_utc());
+
+    for refreshed in &report.refreshed {
+        let r = &refreshed.record;
+        println!(
+            "{:<8} {}  {} -> {}  fitness {:.3} -> {:.3} ({:+.3}){}",
+            refreshed.symbol,
+            &refreshed.full_hash.as_hex()[..12],
+            r.previous_end_date,
+            r.end_date,
+            r.fitness_before,
+            r.fitness_after,
+            r.delta(),
+            if r.degraded { "  DEGRADED" } else { "" }
+        );
+    }
+    for (symbol, hash, err) in &report.failed {
+        println!(
+            "{symbol:<8} {}  re-test failed: {err}",
+            &hash.as_hex()[..12]
+        );
+    }
+    println!(
+        "{} re-tested ({} degraded), {} already up to date, {} failed",
+        report.refreshed.len(),
+        report.degraded().count(),
+        report.up_to_date,
+        report.failed.len()
+    );
+    if report.refreshed.is_empty() {
+        return Ok(());
+    }
+
+    let path = session.save(&session_dir(&args.session))?;
+    println!("Session saved to: {}", path.display());
+
+    if let Some(history_path) = args.history {
+        let history = YoloHistory::new(history_path, WriteFilter::default());
+        for refreshed in &report.refreshed {
+            history.append_refresh(refreshed)?;
         }
+    }
+    Ok(())
+}
+
+fn run_leaderboard_show(args: ShowArgs, settings: &Settings) -> Result<()> {
+    let dir = args.dir.clone().unwrap_or_else(|| {
+        settings
+            .results_dir
+            .value
+            .join("sessions")
+            .join(LEADERBOARDS_DIR)
+    });
+    let Some(board) = StoredLeaderboard::load(&dir, &args.symbol)? else {
+        bail!("no leaderboard for {} in {}", args.symbol, dir.display());
+    };
 
-        let symbol = name.trim_start_matches("symbol=").to_string();
-        let meta_path = entry.path().join("meta.json");
+    println!(
+        "{} leaderboard: {} entries, ranked by {:?}",
+        board.symbol(),
+        board.len(),
+        board.fitness_metric()
+    );
+    println!(
+        "{:>4}  {:<12}  {:<32}  {:>8}  {:>7}  {:>8}  {:>8}  {:>6}  {:>6}  source",
+        "#", "hash", "signal / pm", "fitness", "sharpe", "cagr", "max dd", "trades", "q"
+    );
+    for (rank, entry) in board.entries().iter().take(args.top).enumerate() {
+        let components = format!(
+            "{} / {}",
+            entry.config.signal.component_type, entry.config.position_manager.component_type
+        );
+        let q = entry
+            .fdr
+            .map_or_else(|| "-".to_string(), |f| format!("{:.3}", f.q_value));
+        let source = match (&entry.artifact_dir, &entry.origin) {
+            (Some(dir), _) => dir.display().to_string(),
+            (None, Some(origin)) => format!("{} #{}", origin.session_id, origin.iteration),
+            (None, None) => "history".to_string(),
+        };
+        println!(
+            "{:>4}  {:<12}  {:<32}  {:>8.3}  {:>7.2}  {:>7.1}%  {:>7.1}%  {:>6}  {:>6}  {}",
+            rank + 1,
+            &entry.full_hash().as_hex()[..12],
+            components,
+            entry.fitness_score,
+            entry.metrics.sharpe,
+            entry.metrics.cagr * 100.0,
+            entry.metrics.max_drawdown * 100.0,
+            entry.trade_count,
+            q,
+            source
+        );
+    }
 
-        let should_remove = if let Ok(content) = std::fs::read_to_string(&meta_path) {
-            if let Ok(meta) =
-                serde_json::from_str::<trendlab_core::data::cache::CacheMeta>(&content)
-            {
-                meta.cached_at < cutoff
-            } else {
-                false // don't remove if we can't parse metadata
+    if let Some(history_path) = args.history {
+        let history = YoloHistory::new(history_path, WriteFilter::default()).read_all()?;
+        let replayed = StoredLeaderboard::from_history(
+            board.symbol(),
+            &history,
+            board.len().max(1),
+            board.fitness_metric(),
+        );
+        let divergence = board.divergence(&replayed);
+        if divergence.is_consistent() {
+            println!("Consistent with the history replay");
+        } else {
+            println!(
+                "Diverges from the history replay: {} only in the file, {} only in the \
+                 history, {} with a different fitness",
+                divergence.only_in_file.len(),
+                divergence.only_in_history.len(),
+                divergence.fitness_mismatch.len()
+            );
+            for (label, hashes) in [
+                ("file only", &divergence.only_in_file),
+                ("history only", &divergence.only_in_history),
+                ("fitness differs", &divergence.fitness_mismatch),
+            ] {
+                for hash in hashes {
+                    println!("  {label:<16} {}", &hash[..12]);
+                }
             }
+        }
+    }
+    Ok(())
+}
+
+/// Directory of a session given as its directory or its session.json.
+fn session_dir(session: &Path) -> PathBuf {
+    if session.is_dir() {
+        session.to_path_buf()
+    } else {
+        session.parent().map(Path::to_path_buf).unwrap_or_default()
+    }
+}
+
+fn run_holdout_evaluate(args: HoldoutArgs, settings: &Settings) -> Result<()> {
+    let mut session = YoloResult::load(&args.session)?;
+    let Some(holdout_start) = session.holdout_start else {
+        bail!(HoldoutError::NotConfigured);
+    };
+    if let Some(ref report) = session.holdout {
+        bail!(HoldoutError::AlreadyEvaluated(report.evaluated_at));
+    }
+    let mut symbols: Vec<&str> = session.leaderboards.keys().map(String::as_str).collect();
+    symbols.sort();
+
+    // The cache only: the holdout is the bars the session withheld
+    let opts = LoadOptions {
+        start: holdout_start,
+        end: session.config.end_date,
+        offline: true,
+        synthetic: false,
+        force: false,
+        strict: false,
+        stale_after_days: None,
+        interval: Default::default(),
+    };
+    let cache = ParquetCache::new(settings.cache_dir.or_flag(args.cache_dir.clone()));
+    let data = load_bars(&symbols, &cache, None, None, &opts)?;
+
+    session.rehydrate_artifacts()?;
+    let report = session
+        .evaluate_holdout(&data, chrono::Utc::now().naive_utc())?
+        .clone();
+
+    println!(
+        "Holdout {} to {} (discovery ended before {holdout_start})",
+        report.holdout_start, report.holdout_end
+    );
+    for evaluated in &report.evaluated {
+        let r = &evaluated.record;
+        println!(
+            "{:<8} {}  fitness {:.3} -> {:.3}  trades {:>4}  degradation {}",
+            evaluated.symbol,
+            &evaluated.full_hash.as_hex()[..12],
+            r.discovery_fitness,
+            r.holdout_fitness,
+            r.trade_count,
+            r.degradation_ratio
+                .map_or("n/a".to_string(), |ratio| format!("{ratio:.2}"))
+        );
+    }
+    for (symbol, hash, err) in &report.failed {
+        println!(
+            "{symbol:<8} {}  holdout run failed: {err}",
+            &hash.as_hex()[..12]
+        );
+    }
+    println!(
+        "{} evaluated, {} failed, median degradation ratio {}",
+        report.evaluated.len(),
+        report.failed.len(),
+        report
+            .median_degradation_ratio()
+            .map_or("n/a".to_string(), |ratio| format!("{ratio:.2}"))
+    );
+
+    let path = session.save(&session_dir(&args.session))?;
+    println!("Session saved to: {}", path.display());
+
+    if let Some(history_path) = args.history {
+        let history = YoloHistory::new(history_path, WriteFilter::default());
+        for evaluated in &report.evaluated {
+            history.append_holdout(evaluated)?;
+        }
+    }
+    Ok(())
+}
+
+fn run_verify_cmd(args: VerifyRunArgs, settings: &Settings) -> Result<()> {
+    let shared = ParquetCache::new(settings.cache_dir.or_flag(args.cache_dir.clone()));
+    let verify = |run_dir: &Path| {
+        let snapshot;
+        let cache = if args.from_snapshot {
+            snapshot = ParquetCache::new(run_dir.join(SNAPSHOT_DIR));
+            &snapshot
         } else {
-            false
+            &shared
+        };
+        verify_run_dir(run_dir, cache, args.tolerance, args.as_of)
+    };
+    if !args.all {
+        let report = verify(&args.run_dir)?;
+        match &report.divergence {
+            None => println!(
+                "{}: reproduced ({} trades)",
+                report.symbol, report.trade_count
+            ),
+            Some(divergence) => bail!("{}: diverged at {divergence}", report.symbol),
+        }
+        return Ok(());
+    }
+
+    let runs = scan_artifacts(&args.run_dir);
+    if runs.is_empty() {
+        bail!("no saved runs found in {}", args.run_dir.display());
+    }
+    println!("{:<32} {:<8} {:>7}  Result", "Run", "Symbol", "Trades");
+    let mut failed = 0;
+    for run in &runs {
+        let name = run
+            .dir
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let (trades, outcome) = match verify(&run.dir) {
+            Ok(report) => (
+                report.trade_count.to_string(),
+                report.divergence.map(|d| format!("DIVERGED: {d}")),
+            ),
+            Err(err) => ("-".to_string(), Some(format!("ERROR: {err}"))),
+        };
+        if outcome.is_some() {
+            failed += 1;
+        }
+        let outcome = outcome.unwrap_or_else(|| "reproduced".to_string());
+        println!("{name:<32} {:<8} {trades:>7}  {outcome}", run.symbol);
+    }
+    println!("{} of {} runs reproduced", runs.len() - failed, runs.len());
+    if failed > 0 {
+        bail!("{failed} run(s) did not reproduce");
+    }
+    Ok(())
+}
+
+fn run_corpus_cmd(args: CorpusRunArgs, settings: &Settings) -> Result<()> {
+    let lab = TrendLab::new(settings)
+        .with_cache_dir(settings.cache_dir.or_flag(args.cache_dir))
+        .with_offline(args.offline)
+        .with_synthetic(args.synthetic)
+        .with_stale_after_days((args.stale_after_days > 0).then_some(args.stale_after_days))
+        .with_jobs(args.jobs);
+    let snapshot = lab.run_corpus(&args.configs)?;
+
+    println!(
+        "{:<40} {:<8} {:>7} {:>9}",
+        "Config", "Symbol", "Trades", "Sharpe"
+    );
+    for entry in &snapshot.entries {
+        match &entry.outcome {
+            CorpusOutcome::Ran(run) => println!(
+                "{:<40} {:<8} {:>7} {:>9}",
+                entry.name,
+                run.symbol,
+                run.trade_count,
+                corpus_metric(run.metrics.get("sharpe").copied().flatten())
+            ),
+            CorpusOutcome::Failed { error } => println!("{:<40} FAILED: {error}", entry.name),
+        }
+    }
+    let path = save_snapshot(&snapshot, &args.out)?;
+    println!(
+        "{} configs, {} failed; saved to {}",
+        snapshot.entries.len(),
+        snapshot.failed(),
+        path.display()
+    );
+    Ok(())
+}
+
+fn run_corpus_diff(args: CorpusDiffArgs) -> Result<()> {
+    let diff = diff_corpora(
+        &load_snapshot(&args.a)?,
+        &load_snapshot(&args.b)?,
+        args.tolerance,
+    );
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+    } else {
+        println!(
+            "Compared {} configs (tolerance {:e})",
+            diff.compared, diff.tolerance
+        );
+        for changed in &diff.changed {
+            println!("{}:", changed.name);
+            if let Some((from, to)) = &changed.status {
+                println!("  status: {from} -> {to}");
+            }
+            if let Some((from, to)) = changed.trade_count {
+                println!("  trades: {from} -> {to}");
+            }
+            for metric in &changed.metrics {
+                let delta = metric
+                    .delta()
+                    .map_or("n/a".to_string(), |d| format!("{d:+.6}"));
+                println!(
+                    "  {}: {} -> {} ({delta})",
+                    metric.metric,
+                    corpus_metric(metric.a),
+                    corpus_metric(metric.b)
+                );
+            }
+            if changed.data_changed {
+                println!("  (read different bars: the data changed too)");
+            }
+        }
+        for name in &diff.only_in_a {
+            println!("only in {}: {name}", args.a.display());
+        }
+        for name in &diff.only_in_b {
+            println!("only in {}: {name}", args.b.display());
+        }
+        println!("\nMax absolute drift:");
+        for (metric, drift) in &diff.max_drift {
+            println!("  {metric:<14} {drift:.3e}");
+        }
+    }
+    if diff.has_drift() {
+        bail!(
+            "corpora differ: {} changed, {} removed, {} added",
+            diff.changed.len(),
+            diff.only_in_a.len(),
+            diff.only_in_b.len()
+        );
+    }
+    if !args.json {
+        println!("No drift beyond {:e}", diff.tolerance);
+    }
+    Ok(())
+}
+
+fn corpus_metric(value: Option<f64>) -> String {
+    value.map_or("n/a".to_string(), |v| format!("{v:.6}"))
+}
+
+fn run_signal_check(args: SignalCheckArgs, settings: &Settings) -> Result<()> {
+    let backtest_config = BacktestConfig::from_file(&args.config)?;
+    let report = backtest_config.validate(None);
+    print_validation_report(&report);
+    if report.has_errors() {
+        std::process::exit(exit_code(RunErrorKind::ConfigInvalid));
+    }
+
+    let mut symbols: Vec<String> = args
+        .symbols
+        .iter()
+        .map(|s| s.trim().to_uppercase())
+        .filter(|s| !s.is_empty())
+        .collect();
+    if symbols.is_empty() {
+        symbols.push(backtest_config.backtest.symbol.clone());
+    }
+    let symbol_refs: Vec<&str> = symbols.iter().map(String::as_str).collect();
+
+    let today = chrono::Utc::now().date_naive();
+    let opts = LoadOptions {
+        start: today - chrono::Duration::days(i64::from(args.history_days)),
+        end: today,
+        offline: !args.refresh,
+        synthetic: false,
+        force: false,
+        strict: false,
+        // Any missing tail is worth fetching when refreshing
+        stale_after_days: args.refresh.then_some(1),
+        interval: backtest_config.backtest.interval,
+    };
+    let cache = ParquetCache::new(settings.cache_dir.or_flag(args.cache_dir.clone()));
+    let provider = args.refresh.then(|| {
+        YahooProvider::new(Arc::new(CircuitBreaker::default_provider()))
+            .with_interval(opts.interval)
+    });
+    let provider_ref = provider
+        .as_ref()
+        .map(|p| p as &dyn trendlab_core::data::provider::DataProvider);
+    let mut aligned = load_bars(&symbol_refs, &cache, provider_ref, None, &opts)?.aligned;
+
+    // A bar dated today may still be forming
+    if !args.include_today {
+        let keep = aligned.dates.partition_point(|d| *d < today);
+        aligned.dates.truncate(keep);
+        for bars in aligned.bars.values_mut() {
+            bars.truncate(keep);
+        }
+    }
+
+    let checks = check_signals(
+        &backtest_config.to_strategy_config(),
+        backtest_config.trading_mode(),
+        &aligned,
+        &symbol_refs,
+        today,
+        args.stale_after_days,
+    )?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&checks)?);
+        return Ok(());
+    }
+    println!(
+        "{:<8} {:<10} {:>5} {:>10}  Result",
+        "Symbol", "Last bar", "Age", "Close"
+    );
+    for check in &checks {
+        let mut result = check.outcome.to_string();
+        if let CheckOutcome::Entry(plan) = &check.outcome {
+            if let Some(risk) = plan.risk_per_share() {
+                result.push_str(&format!(" (risk {risk:.2}/share)"));
+            }
+        }
+        println!(
+            "{:<8} {} {:>4}d {:>10.2}  {result}{}",
+            check.symbol,
+            check.last_bar,
+            check.age_days,
+            check.close,
+            if check.stale { "  STALE" } else { "" }
+        );
+    }
+    for symbol in &symbols {
+        if !checks.iter().any(|c| &c.symbol == symbol) {
+            println!("{symbol:<8} no bars before {today}");
+        }
+    }
+    Ok(())
+}
+
+fn run_paper_init(args: PaperInitArgs, settings: &Settings) -> Result<()> {
+    let results_dir = settings.results_dir.or_flag(args.results_dir.clone());
+    let since = match &args.since {
+        Some(s) => NaiveDate::parse_from_str(s, "%Y-%m-%d")?,
+        None => chrono::Utc::now().date_naive(),
+    };
+
+    let champions: Vec<PaperChampion> = match &args.config {
+        Some(path) => {
+            let backtest_config = BacktestConfig::from_file(path)?;
+            let mut symbols: Vec<String> = args
+                .symbols
+                .iter()
+                .map(|s| s.trim().to_uppercase())
+                .filter(|s| !s.is_empty())
+                .collect();
+            if symbols.is_empty() {
+                symbols.push(backtest_config.backtest.symbol.clone());
+            }
+            symbols
+                .iter()
+                .map(|symbol| {
+                    PaperChampion::new(
+                        symbol,
+                        backtest_config.to_strategy_config(),
+                        backtest_config.trading_mode(),
+                        backtest_config.backtest.interval,
+                        since,
+                    )
+                })
+                .collect()
+        }
+        None => {
+            let path = args
+                .report
+                .clone()
+                .unwrap_or_else(|| results_dir.join("promotion_report.json"));
+            let trading_mode = parse_trading_mode(&args.trading_mode)?;
+            let report: PromotionReport =
+                serde_json::from_str(&std::fs::read_to_string(&path).map_err(|e| {
+                    anyhow!("cannot read promotion report {}: {e}", path.display())
+                })?)?;
+            report
+                .promoted()
+                .map(|entry| {
+                    PaperChampion::new(
+                        &entry.symbol,
+                        entry.config.clone(),
+                        trading_mode,
+                        BarInterval::Daily,
+                        since,
+                    )
+                })
+                .collect()
+        }
+    };
+    if champions.is_empty() {
+        bail!("no promoted champions to paper trade");
+    }
+
+    let store = PaperStore::new(&results_dir);
+    for champion in champions {
+        if store.contains(&champion.id) {
+            println!("{:<24} already tracked", champion.id);
+            continue;
+        }
+        let id = champion.id.clone();
+        store.save(&PaperState::new(champion), &[])?;
+        println!("{id:<24} trading from {since}");
+    }
+    println!("Paper books in: {}", store.dir().display());
+    Ok(())
+}
+
+fn run_paper_update(args: PaperUpdateArgs, settings: &Settings) -> Result<()> {
+    let store = PaperStore::new(&settings.results_dir.or_flag(args.results_dir.clone()));
+    let mut states = store.load_all()?;
+    if states.is_empty() {
+        bail!(
+            "no paper books in {} (run `paper init`)",
+            store.dir().display()
+        );
+    }
+
+    let today = chrono::Utc::now().date_naive();
+    let cache = ParquetCache::new(settings.cache_dir.or_flag(args.cache_dir.clone()));
+    for state in &mut states {
+        let champion = &state.champion;
+        let opts = LoadOptions {
+            start: today - chrono::Duration::days(i64::from(args.history_days)),
+            end: today,
+            offline: !args.refresh,
+            synthetic: false,
+            force: false,
+            strict: false,
+            // Any missing tail is worth fetching when refreshing
+            stale_after_days: args.refresh.then_some(1),
+            interval: champion.interval,
         };
+        let provider = args.refresh.then(|| {
+            YahooProvider::new(Arc::new(CircuitBreaker::default_provider()))
+                .with_interval(opts.interval)
+        });
+        let provider_ref = provider
+            .as_ref()
+            .map(|p| p as &dyn trendlab_core::data::provider::DataProvider);
+        let mut aligned = load_bars(
+            &[champion.symbol.as_str()],
+            &cache,
+            provider_ref,
+            None,
+            &opts,
+        )?
+        .aligned;
+
+        // A bar dated today may still be forming
+        if !args.include_today {
+            let keep = aligned.dates.partition_point(|d| *d < today);
+            aligned.dates.truncate(keep);
+            for bars in aligned.bars.values_mut() {
+                bars.truncate(keep);
+            }
+        }
+
+        let journal = state.update(&aligned)?;
+        store.save(state, &journal)?;
+        if !args.json {
+            for entry in &journal {
+                if !matches!(entry.event, PaperEvent::Marked { .. }) {
+                    println!(
+                        "{} {} {}",
+                        state.champion.id,
+                        entry.date,
+                        paper_event(&entry.event)
+                    );
+                }
+            }
+        }
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&states)?);
+        return Ok(());
+    }
+    println!(
+        "{:<24} {:<10} {:<6} {:>10} {:>10} {:>10} {:>9} {:>10}",
+        "Champion", "Through", "Side", "Entry", "Close", "Stop", "Unreal%", "Realized"
+    );
+    for state in &states {
+        let book = &state.book;
+        let through = book.through.map_or("-".to_string(), |d| d.to_string());
+        match &book.position {
+            Some(p) => println!(
+                "{:<24} {:<10} {:<6} {:>10.2} {:>10.2} {:>10} {:>8.2}% {:>10.2}",
+                state.champion.id,
+                through,
+                format!("{:?}", p.side),
+                p.entry_price,
+                p.last_close,
+                p.stop.map_or("-".to_string(), |s| format!("{s:.2}")),
+                p.unrealized_return() * 100.0,
+                book.realized_pnl,
+            ),
+            None => println!(
+                "{:<24} {:<10} {:<6} {:>10} {:>10} {:>10} {:>9} {:>10.2}",
+                state.champion.id,
+                through,
+                if book.pending_entry.is_some() {
+                    "order"
+                } else {
+                    "flat"
+                },
+                "-",
+                "-",
+                "-",
+                "-",
+                book.realized_pnl,
+            ),
+        }
+    }
+    Ok(())
+}
+
+fn paper_event(event: &PaperEvent) -> String {
+    match event {
+        PaperEvent::OrderPlaced { side, order_type } => {
+            format!("order placed: {side:?} {order_type:?}")
+        }
+        PaperEvent::OrderExpired => "order expired".to_string(),
+        PaperEvent::Entered { side, price, .. } => format!("entered {side:?} at {price:.2}"),
+        PaperEvent::StopMoved { stop } => format!("stop moved to {stop:.2}"),
+        PaperEvent::TargetMoved { target } => format!("target moved to {target:.2}"),
+        PaperEvent::ExitScheduled => "exit scheduled for next open".to_string(),
+        PaperEvent::Exited { price, reason, pnl } => {
+            format!("exited at {price:.2} ({reason:?}), pnl {pnl:+.2}")
+        }
+        PaperEvent::Marked { .. } => "marked".to_string(),
+    }
+}
+
+/// Resolve a full hash prefix, falling back to the newest config whose
+/// structural config hash has that prefix.
+fn resolve_history_hash(entries: &[HistoryEntry], hash: &str) -> Result<FullHash> {
+    let prefix = hash.trim().to_lowercase();
+    if prefix.is_empty() {
+        bail!("a config hash is required");
+    }
+    let matches: HashSet<&FullHash> = entries
+        .iter()
+        .map(|e| &e.fingerprint.full_hash)
+        .filter(|h| h.as_hex().starts_with(&prefix))
+        .collect();
+    match matches.len() {
+        1 => return Ok(matches.into_iter().next().unwrap().clone()),
+        0 => {}
+        n => bail!("'{prefix}' matches {n} configs; use a longer prefix"),
+    }
+    entries
+        .iter()
+        .rev()
+        .find(|e| e.fingerprint.config_hash.as_hex().starts_with(&prefix))
+        .map(|e| e.fingerprint.full_hash.clone())
+        .ok_or_else(|| anyhow!("no config in the history matches '{prefix}'"))
+}
 
-        if should_remove {
-            to_remove.push((symbol, entry.path()));
+fn print_promotion_report(report: &PromotionReport) {
+    println!();
+    println!(
+        "{:<3} {:<8} {:<22} {:>7} {:>7} {:<16} {:<4} {:<6} {:<14} Failing criterion",
+        "#", "Symbol", "Signal", "Sharpe", "Re-run", "Level", "FDR", "Cached", "vs B&H"
+    );
+    for (i, e) in report.entries.iter().enumerate() {
+        let rerun = e.sharpe.map_or("-".to_string(), |s| format!("{s:.3}"));
+        let level = e.level.map_or("-".to_string(), |l| l.to_string());
+        let fdr = match e.fdr_significant {
+            Some(true) => "yes",
+            Some(false) => "no",
+            None => "-",
+        };
+        // Number of ladder levels reused from the robustness cache
+        let cached = match e.from_cache.len() {
+            0 => "-".to_string(),
+            n => n.to_string(),
+        };
+        // Lead over buy-and-hold on the benchmark metric
+        let vs_held = e
+            .robustness
+            .as_ref()
+            .and_then(|r| r.benchmark.as_ref())
+            .map_or("-".to_string(), |b| {
+                format!("{} {}", b.metric.label(), b.metric.format_margin(b.margin))
+            });
+        println!(
+            "{:<3} {:<8} {:<22} {:>7.3} {rerun:>7} {level:<16} {fdr:<4} {cached:<6} {vs_held:<14} {}",
+            i + 1,
+            e.symbol,
+            e.config.signal.component_type,
+            e.recorded_sharpe,
+            e.failing_criterion.as_deref().unwrap_or("-"),
+        );
+        let walk_forward = e.robustness.as_ref().and_then(|r| r.walk_forward.as_ref());
+        if let Some(worst) = walk_forward.and_then(|wf| wf.worst_fold()) {
+            println!(
+                "    walk-forward: {} splits, worst {} (OOS Sharpe {:.3})",
+                walk_forward.map_or(0, |wf| wf.fold_results.len()),
+                if worst.label.is_empty() {
+                    "-"
+                } else {
+                    &worst.label
+                },
+                worst.oos_sharpe
+            );
+        }
+        if let Some(sv) = e.robustness.as_ref().and_then(|r| r.signal_value.as_ref()) {
+            println!(
+                "    shuffle test: Sharpe beats {:.0}%, return {:.0}% of {} shuffled variants",
+                sv.sharpe_percentile, sv.total_return_percentile, sv.variants
+            );
         }
+        if let Some(ps) = e.robustness.as_ref().and_then(|r| r.sensitivity.as_ref()) {
+            match &ps.cliff {
+                Some(cliff) => println!("    parameter cliff: {cliff}"),
+                None => println!(
+                    "    sensitivity: no cliff in {} nudged reruns",
+                    ps.probes.len()
+                ),
+            }
+        }
+    }
+    println!();
+    println!(
+        "{} of {} candidates promoted",
+        report.promoted().count(),
+        report.entries.len()
+    );
+}
+
+/// A preset run's date: the flag, else the setting, else `default` (y, m, d).
+fn preset_date(
+    flag: Option<String>,
+    setting: &Sourced<Option<NaiveDate>>,
+    default: (i32, u32, u32),
+) -> Result<NaiveDate> {
+    let flag = flag
+        .as_deref()
+        .map(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d"))
+        .transpose()?;
+    let (y, m, d) = default;
+    Ok(setting
+        .or_flag_opt(flag)
+        .unwrap_or_else(|| NaiveDate::from_ymd_opt(y, m, d).unwrap()))
+}
+
+fn run_config_show(settings: &Settings) {
+    for (key, value, source) in settings.rows() {
+        println!("{key:<17} {value:<30} {source}");
+    }
+}
+
+fn run_cache_status(cache_dir: &Path) -> Result<()> {
+    if !cache_dir.exists() {
+        println!("Cache directory does not exist: {}", cache_dir.display());
+        return Ok(());
+    }
+
+    let entries = ParquetCache::new(cache_dir).entries()?;
+    if entries.is_empty() {
+        println!("Cache is empty: {}", cache_dir.display());
+        return Ok(());
+    }
+    let total_size: u64 = entries.iter().map(|e| e.size_bytes).sum();
+
+    println!("Cache: {}", cache_dir.display());
+    println!("Symbols: {}", entries.len());
+    println!("Total size: {}", format_size(total_size));
+    println!();
+    println!(
+        "{:<8} {:<25} {:<12} {:>10}",
+        "Symbol", "Date Range", "Bars", "Size"
+    );
+    println!("{}", "-".repeat(58));
+    for entry in &entries {
+        println!(
+            "{:<8} {:<25} {:<12} {:>10}",
+            entry.symbol,
+            entry.date_range_label(),
+            format!("{} bars", entry.bar_count()),
+            format_size(entry.size_bytes)
+        );
+    }
+
+    Ok(())
+}
+
+fn run_cache_clean(cache_dir: &Path, unused_days: u64, confirm: bool) -> Result<()> {
+    if !cache_dir.exists() {
+        println!("Cache directory does not exist: {}", cache_dir.display());
+        return Ok(());
     }
 
+    let cutoff = chrono::Local::now().naive_local() - chrono::Duration::days(unused_days as i64);
+
+    let cache = ParquetCache::new(cache_dir);
+    let to_remove = cache.unused_since(cutoff)?;
+
     if to_remove.is_empty() {
         println!("No symbols older than {unused_days} days to remove.");
         return Ok(());
@@ -439,9 +2105,8 @@ fn run_cache_clean(cache_dir: &Path, unused_days: u64, confirm: bool) -> Result<
         "Found {} symbol(s) not accessed in {unused_days} days:",
         to_remove.len()
     );
-    for (sym, path) in &to_remove {
-        let size = dir_size(path);
-        println!("  {sym} ({})", format_size(size));
+    for entry in &to_remove {
+        println!("  {} ({})", entry.symbol, format_size(entry.size_bytes));
     }
 
     if !confirm {
@@ -450,35 +2115,138 @@ fn run_cache_clean(cache_dir: &Path, unused_days: u64, confirm: bool) -> Result<
         return Ok(());
     }
 
-    for (sym, path) in &to_remove {
-        std::fs::remove_dir_all(path)?;
-        println!("Removed: {sym}");
+    for entry in &to_remove {
+        cache.remove_symbol(&entry.symbol)?;
+        println!("Removed: {}", entry.symbol);
     }
 
     println!("Done. Removed {} symbol(s).", to_remove.len());
     Ok(())
 }
 
-fn dir_size(path: &Path) -> u64 {
-    let mut size = 0u64;
-    if let Ok(entries) = std::fs::read_dir(path) {
-        for entry in entries.flatten() {
-            if let Ok(meta) = entry.metadata() {
-                size += meta.len();
+fn run_cache_compact(cache_dir: &Path, symbols: &[String], interval: BarInterval) -> Result<()> {
+    let cache = ParquetCache::new(cache_dir).with_interval(interval);
+    let symbols: Vec<String> = if symbols.is_empty() {
+        cache.entries()?.into_iter().map(|e| e.symbol).collect()
+    } else {
+        symbols.to_vec()
+    };
+    if symbols.is_empty() {
+        println!("Cache is empty: {}", cache_dir.display());
+        return Ok(());
+    }
+
+    println!(
+        "{:<8} {:>6} {:>10} {:>9} {:>8}",
+        "Symbol", "Parts", "Bars", "Shadowed", "Removed"
+    );
+    let mut failed = 0;
+    for symbol in &symbols {
+        match cache.compact(symbol) {
+            Ok(r) => println!(
+                "{:<8} {:>6} {:>10} {:>9} {:>8}",
+                r.symbol,
+                format!("{}->{}", r.parts_before, r.parts_after),
+                r.bars,
+                r.shadowed_bars,
+                r.files_removed
+            ),
+            Err(e) => {
+                failed += 1;
+                println!("{symbol:<8} skipped: {e}");
             }
         }
     }
-    size
+    if failed == symbols.len() {
+        bail!("no symbol was compacted");
+    }
+    Ok(())
 }
 
-fn format_size(bytes: u64) -> String {
-    if bytes < 1024 {
-        format!("{bytes} B")
-    } else if bytes < 1024 * 1024 {
-        format!("{:.1} KB", bytes as f64 / 1024.0)
-    } else {
-        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+fn run_cache_snapshot(cache_dir: &Path, run_dir: &Path, max_mb: u64) -> Result<()> {
+    let saved = load_artifacts(run_dir)?;
+    let cache = ParquetCache::new(cache_dir);
+    let report = snapshot_partitions(
+        &saved.data_vintage,
+        &cache,
+        run_dir,
+        Some(max_mb * 1024 * 1024),
+    )?;
+    println!(
+        "Copied {} partition(s), {} file(s), {} to {}",
+        report.partitions,
+        report.files,
+        format_size(report.bytes),
+        report.dir.display()
+    );
+    Ok(())
+}
+
+fn run_results_prune(args: PruneArgs, settings: &Settings) -> Result<()> {
+    let dir = settings.results_dir.or_flag(args.dir.clone());
+    let policy = RetentionPolicy {
+        keep_top: args.keep_top,
+        metric: args.metric,
+        keep_days: (args.keep_days > 0).then_some(args.keep_days),
+        force: args.force,
+    };
+    let plan = trendlab_runner::plan_prune(&dir, &policy, chrono::Local::now().naive_local())?;
+
+    let total = plan.kept.len() + plan.delete.len();
+    println!(
+        "{total} run(s) in {}, {} reference file(s) read",
+        dir.display(),
+        plan.reference_files
+    );
+    for rule in RetentionRule::ALL {
+        let (runs, bytes) = plan.kept_by(rule);
+        println!(
+            "  kept, {:<11} {runs:>6}  {:>10}",
+            rule.label(),
+            format_size(bytes)
+        );
+    }
+    println!(
+        "  delete            {:>6}  {:>10}",
+        plan.delete.len(),
+        format_size(plan.bytes_to_reclaim())
+    );
+    if plan.forced > 0 {
+        println!(
+            "  {} referenced run(s) deleted because of --force",
+            plan.forced
+        );
+    }
+    if !plan.unreadable.is_empty() {
+        println!(
+            "  {} run(s) with an unreadable manifest kept",
+            plan.unreadable.len()
+        );
+    }
+
+    if plan.delete.is_empty() {
+        println!("Nothing to delete.");
+        return Ok(());
+    }
+    if !args.confirm {
+        println!();
+        println!("Dry run — pass --confirm to actually delete.");
+        return Ok(());
+    }
+
+    let outcome = trendlab_runner::execute_prune(&plan);
+    for (dir, e) in &outcome.failed {
+        eprintln!("Failed to delete {}: {e}", dir.display());
+    }
+    println!(
+        "Done. Deleted {} run(s), reclaimed {}.",
+        outcome.deleted,
+        format_size(outcome.bytes_reclaimed)
+    );
+    if !outcome.failed.is_empty() {
+        bail!("{} run(s) could not be deleted", outcome.failed.len());
     }
+    Ok(())
 }
 
 fn print_summary(result: &BacktestResult) {
@@ -489,12 +2257,29 @@ fn print_summary(result: &BacktestResult) {
         "Period:         {} to {}",
         result.start_date, result.end_date
     );
+    if let Some(truncated) = &result.truncated_range {
+        println!("WARNING:        {truncated}");
+    }
     println!(
-        "Bars:           {} ({} warmup)",
-        result.bar_count, result.warmup_bars
+        "Bars:           {} {} ({} warmup)",
+        result.bar_count, result.interval, result.warmup_bars
     );
+    if let Some(warmup) = &result.warmup {
+        print_warmup(warmup);
+    }
+    if let Some(profile) = result.inputs.as_ref().and_then(|i| i.risk_profile) {
+        println!("Risk profile:   {}", profile.label());
+    }
     println!("Signals:        {}", result.signal_count);
-    println!("Trades:         {}", result.metrics.trade_count);
+    let open_at_end = result.trades.iter().filter(|t| t.liquidated_at_end).count();
+    if open_at_end > 0 {
+        println!(
+            "Trades:         {} ({open_at_end} open at end of data)",
+            result.metrics.trade_count
+        );
+    } else {
+        println!("Trades:         {}", result.metrics.trade_count);
+    }
     println!();
     println!("--- Performance ---");
     println!(
@@ -515,6 +2300,30 @@ fn print_summary(result: &BacktestResult) {
     println!("Max Consec Win: {}", result.metrics.max_consecutive_wins);
     println!("Max Consec Loss:{}", result.metrics.max_consecutive_losses);
     println!("Avg Lose Streak:{:.1}", result.metrics.avg_losing_streak);
+    if !result.exposure_curve.is_empty() {
+        let m = &result.metrics;
+        println!();
+        println!("--- Exposure ---");
+        println!("Time in Market: {:.1}%", m.time_in_market * 100.0);
+        println!(
+            "Avg Exposure:   {:.1}% (when invested)",
+            m.avg_exposure * 100.0
+        );
+        println!("Invested Return:{:.2}%", m.invested_return * 100.0);
+        println!("Idle Return:    {:.2}%", m.idle_return * 100.0);
+        println!("Exp-Adj CAGR:   {:.2}%", m.exposure_adjusted_cagr * 100.0);
+    }
+    if let Some(adjusted) = &result.open_position_adjusted {
+        println!();
+        println!("--- Without End-of-Data Trades ---");
+        println!("Trades:         {}", adjusted.trade_count);
+        println!("Win Rate:       {:.1}%", adjusted.win_rate * 100.0);
+        println!("Profit Factor:  {:.2}", adjusted.profit_factor);
+    }
+    print_r_distribution(&result.r_distribution);
+    if let Some(style) = &result.style {
+        print_style(style);
+    }
     if result.has_synthetic {
         println!();
         println!("WARNING: Results based on SYNTHETIC data");
@@ -522,6 +2331,148 @@ fn print_summary(result: &BacktestResult) {
     for warn in &result.data_quality_warnings {
         println!("WARNING: {warn}");
     }
+    for warn in &result.consistency_warnings {
+        println!("WARNING: inconsistent result: {warn}");
+    }
+    println!();
+}
+
+fn run_inspect(args: InspectArgs) -> Result<()> {
+    let result = load_artifacts(&args.run_dir)?;
+    if args.from.is_none() && args.to.is_none() {
+        print_summary(&result);
+        return Ok(());
+    }
+    let parse = |s: &Option<String>| {
+        s.as_deref()
+            .map(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d"))
+            .transpose()
+    };
+    let (from, to) = (parse(&args.from)?, parse(&args.to)?);
+    let (Some(&first), Some(&last)) = (result.dates.first(), result.dates.last()) else {
+        bail!(SliceError::NoDates);
+    };
+    let slice = result.slice(from.unwrap_or(first), to.unwrap_or(last))?;
+
+    println!();
+    println!(
+        "Window:         {} to {} (bars {}..={} of {}, run {} to {})",
+        slice.result.start_date,
+        slice.result.end_date,
+        slice.first_bar,
+        slice.last_bar,
+        result.bar_count,
+        result.start_date,
+        result.end_date
+    );
+    print_summary(&slice.result);
+    if let Some(tail) = &slice.result.tail_metrics {
+        print_tail(tail);
+    }
+    if !slice.prorated.is_empty() {
+        println!("--- Boundary Trades (PnL pro-rated by bars in window) ---");
+        for p in &slice.prorated {
+            let trade = &slice.result.trades[p.trade];
+            let cut = match (p.cut_entry, p.cut_exit) {
+                (true, true) => "spans window",
+                (true, false) => "entered before window",
+                _ => "open at window end",
+            };
+            println!(
+                "  {} → {}  {:>5.1}% of its bars  net {:>10.2}  ({cut})",
+                trade.entry_date,
+                trade.exit_date,
+                p.share * 100.0,
+                trade.net_pnl
+            );
+        }
+        println!();
+    }
+    Ok(())
+}
+
+fn print_tail(tail: &TailMetrics) {
+    let pct = |v: Option<f64>| v.map_or("n/a".to_string(), |v| format!("{:.2}%", v * 100.0));
+    let num = |v: Option<f64>| v.map_or("n/a".to_string(), |v| format!("{v:.3}"));
+    println!("--- Tail Risk ({} returns) ---", tail.sample_size);
+    println!("CVaR 95:        {}", pct(tail.cvar_95));
+    println!("CVaR 99:        {}", pct(tail.cvar_99));
+    println!("Skewness:       {}", num(tail.skewness));
+    println!("Kurtosis:       {}", num(tail.kurtosis));
+    if let Some(worst) = tail.worst_days.first() {
+        println!(
+            "Worst day:      {} {:.2}%",
+            worst.date,
+            worst.daily_return * 100.0
+        );
+    }
+    println!();
+}
+
+fn print_profile(timings: &EngineTimings) {
+    let total = timings.total().as_secs_f64();
+    println!("--- Engine Profile ---");
+    println!("{:<18} {:>10} {:>7}", "Phase", "ms", "share");
+    for (phase, elapsed) in timings.phases() {
+        let secs = elapsed.as_secs_f64();
+        let share = if total > 0.0 {
+            secs / total * 100.0
+        } else {
+            0.0
+        };
+        println!("{phase:<18} {:>10.3} {share:>6.1}%", secs * 1000.0);
+    }
+    println!("{:<18} {:>10.3} {:>6.1}%", "total", total * 1000.0, 100.0);
+    println!();
+}
+
+fn print_style(style: &StyleReport) {
     println!();
+    println!("--- Style ({} days) ---", style.observations);
+    for b in &style.betas {
+        println!("Beta {:<10} {:.3}", b.factor, b.beta);
+    }
+    println!("R²:             {:.3}", style.r_squared);
+    println!("Alpha (ann.):   {:.2}%", style.alpha * 100.0);
+    println!("Residual Sharpe:{:.3}", style.residual_sharpe);
+}
+
+fn print_warmup(warmup: &WarmupReport) {
+    let source = match warmup.override_bars {
+        Some(_) => "override".to_string(),
+        None if warmup.multiplier > 1.0 => format!("lookbacks x{}", warmup.multiplier),
+        None => "lookbacks".to_string(),
+    };
+    println!(
+        "Warmup:         {} bars ({source}), {} tradable",
+        warmup.applied, warmup.tradable_bars
+    );
+    for c in &warmup.contributions {
+        println!("  {:<12} {:>5} -> {}", c.indicator, c.lookback, c.bars);
+    }
 }
 
+fn print_r_distribution(r: &RDistribution) {
+    println!();
+    if r.trade_count == 0 {
+        println!(
+            "R-Multiples:    n/a ({} trades without an initial stop)",
+            r.excluded_trades
+        );
+        return;
+    }
+    println!(
+        "R-Multiples:    {} trades ({} excluded, no initial stop)",
+        r.trade_count, r.excluded_trades
+    );
+    println!("Mean R:         {:+.2}", r.mean_r);
+    println!("Median R:       {:+.2}", r.median_r);
+    println!("Expectancy:     {:+.2}R", r.expectancy_r);
+    println!("Stopped ~-1R:   {:.1}%", r.pct_near_minus_one * 100.0);
+    println!("Scratched ~0R:  {:.1}%", r.pct_scratch * 100.0);
+    let max = r.histogram.iter().copied().max().unwrap_or(0).max(1);
+    for (i, &count) in r.histogram.iter().enumerate() {
+        let bar = "#".repeat((count * 30).div_ceil(max));
+        println!("  {:>8} {:>4} {bar}", RDistribution::bin_label(i), count);
+    }
+}
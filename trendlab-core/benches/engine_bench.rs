@@ -2,10 +2,12 @@
 //!
 //! Benchmarks:
 //! 1. Bar event loop (full backtest iteration)
-//! 2. Order book operations (submit, fill, cancel/replace, OCO)
+//! 2. Order book operations (submit, fill, cancel/replace, OCO, active lookup)
 //! 3. Execution fill simulation (trigger checks, fill price computation)
 //! 4. Indicator precompute (SMA, EMA, ATR, Donchian, Bollinger batch)
 //! 5. Position manager state machine (sequential PM on_bar calls)
+//! 6. Full backtest with strategy composition
+//! 7. Accounting (applying fills in f64 vs fixed-point micro-units)
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 use std::collections::HashMap;
@@ -19,12 +21,12 @@ use trendlab_core::components::signal::NullSignal;
 use trendlab_core::data::align::AlignedData;
 use trendlab_core::data::provider::RawBar;
 use trendlab_core::domain::{
-    Bar, Instrument, MarketStatus, OcoGroup, OcoGroupId, Order, OrderId, OrderSide, OrderStatus,
-    OrderType, Position,
+    Bar, Fill, FillPhase, Instrument, MarketStatus, OcoGroup, OcoGroupId, Order, OrderId,
+    OrderSide, OrderStatus, OrderType, Portfolio, Position,
 };
 use trendlab_core::engine::order_book::OrderBook;
 use trendlab_core::engine::precompute::precompute_indicators;
-use trendlab_core::engine::{run_backtest, EngineConfig, ExecutionEngine};
+use trendlab_core::engine::{apply_fills, run_backtest, EngineConfig, ExecutionEngine};
 use trendlab_core::fingerprint::TradingMode;
 use trendlab_core::indicators::{Atr, Bollinger, Donchian, Ema, Sma};
 
@@ -60,7 +62,7 @@ fn make_bars(n: usize) -> Vec<Bar> {
             let high = close + 1.5;
             let low = close - 1.5;
             Bar {
-                symbol: "BENCH".to_string(),
+                symbol: "BENCH".into(),
                 date: base_date + chrono::Duration::days(i as i64),
                 open,
                 high,
@@ -132,6 +134,36 @@ fn make_order(id: u64, order_type: OrderType) -> Order {
     }
 }
 
+fn donchian_atr_config() -> trendlab_core::fingerprint::StrategyConfig {
+    trendlab_core::fingerprint::StrategyConfig {
+        signal: trendlab_core::fingerprint::ComponentConfig {
+            component_type: "donchian_breakout".into(),
+            params: [("entry_lookback".to_string(), 50.0)].into_iter().collect(),
+            children: Vec::new(),
+        },
+        position_manager: trendlab_core::fingerprint::ComponentConfig {
+            component_type: "atr_trailing".into(),
+            params: [
+                ("atr_period".to_string(), 14.0),
+                ("multiplier".to_string(), 3.0),
+            ]
+            .into_iter()
+            .collect(),
+            children: Vec::new(),
+        },
+        execution_model: trendlab_core::fingerprint::ComponentConfig {
+            component_type: "next_bar_open".into(),
+            params: [("preset".to_string(), 1.0)].into_iter().collect(),
+            children: Vec::new(),
+        },
+        signal_filter: trendlab_core::fingerprint::ComponentConfig {
+            component_type: "no_filter".into(),
+            params: Default::default(),
+            children: Vec::new(),
+        },
+    }
+}
+
 // ── 1. Bar Event Loop ────────────────────────────────────────────────
 
 fn bench_bar_loop(c: &mut Criterion) {
@@ -278,6 +310,44 @@ fn bench_order_book(c: &mut Criterion) {
         });
     });
 
+    // Per-bar active lookup against a long history: 100k cancelled orders
+    // plus one live stop. The linear scan is the pre-index implementation.
+    let history = 100_000u64;
+    let mut book = OrderBook::new();
+    let mut all_orders: HashMap<OrderId, Order> = HashMap::new();
+    for i in 0..=history {
+        let order = make_order(
+            i,
+            OrderType::StopMarket {
+                trigger_price: 95.0,
+            },
+        );
+        book.submit(order.clone());
+        let mut stored = order;
+        if i < history {
+            book.cancel(OrderId(i), 0, "replaced").unwrap();
+            stored.status = OrderStatus::Cancelled {
+                reason: "replaced".to_string(),
+            };
+        }
+        all_orders.insert(OrderId(i), stored);
+    }
+
+    group.bench_function("active_lookup_100k_history/indexed", |b| {
+        b.iter(|| black_box(book.active_orders_for_symbol(black_box("BENCH"))));
+    });
+
+    group.bench_function("active_lookup_100k_history/linear_scan", |b| {
+        b.iter(|| {
+            let symbol = black_box("BENCH");
+            let active: Vec<&Order> = all_orders
+                .values()
+                .filter(|o| o.is_active() && o.symbol == symbol)
+                .collect();
+            black_box(active)
+        });
+    });
+
     group.finish();
 }
 
@@ -295,7 +365,7 @@ fn bench_execution(c: &mut Criterion) {
 
     group.bench_function("start_of_bar_10_moo", |b| {
         let bar = Bar {
-            symbol: "BENCH".to_string(),
+            symbol: "BENCH".into(),
             date: chrono::NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
             open: 100.0,
             high: 105.0,
@@ -324,7 +394,7 @@ fn bench_execution(c: &mut Criterion) {
 
     group.bench_function("intrabar_10_stops", |b| {
         let bar = Bar {
-            symbol: "BENCH".to_string(),
+            symbol: "BENCH".into(),
             date: chrono::NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
             open: 100.0,
             high: 105.0,
@@ -443,7 +513,7 @@ fn bench_pm_state_machine(c: &mut Criterion) {
             // Simulate a position held from bar 20 to end
             let mut pos = Position::new_long("BENCH".into(), bars[20].close, 100.0, 20);
             for t in 21..bars.len() {
-                pos.tick_bar();
+                pos.tick_bar(MarketStatus::Open);
                 pos.update_mark(bars[t].close);
                 let intent = pm.on_bar(
                     black_box(&pos),
@@ -478,29 +548,7 @@ fn bench_full_composition(c: &mut Criterion) {
     let mut group = c.benchmark_group("full_composition");
 
     let aligned = make_aligned(1260);
-    let config = trendlab_core::fingerprint::StrategyConfig {
-        signal: trendlab_core::fingerprint::ComponentConfig {
-            component_type: "donchian_breakout".into(),
-            params: [("entry_lookback".to_string(), 50.0)].into_iter().collect(),
-        },
-        position_manager: trendlab_core::fingerprint::ComponentConfig {
-            component_type: "atr_trailing".into(),
-            params: [
-                ("atr_period".to_string(), 14.0),
-                ("multiplier".to_string(), 3.0),
-            ]
-            .into_iter()
-            .collect(),
-        },
-        execution_model: trendlab_core::fingerprint::ComponentConfig {
-            component_type: "next_bar_open".into(),
-            params: [("preset".to_string(), 1.0)].into_iter().collect(),
-        },
-        signal_filter: trendlab_core::fingerprint::ComponentConfig {
-            component_type: "no_filter".into(),
-            params: Default::default(),
-        },
-    };
+    let config = donchian_atr_config();
 
     let comp = build_composition(&config, TradingMode::LongOnly).unwrap();
     let engine_config = EngineConfig::new(100_000.0, 0);
@@ -522,6 +570,78 @@ fn bench_full_composition(c: &mut Criterion) {
     group.finish();
 }
 
+// ── 7. Accounting ────────────────────────────────────────────────────
+
+fn bench_accounting(c: &mut Criterion) {
+    let mut group = c.benchmark_group("accounting");
+
+    // 10k alternating buys and sells across 50 symbols, costs off the cent grid
+    let date = chrono::NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+    let fills: Vec<Fill> = (0..10_000usize)
+        .map(|i| {
+            let side = if i % 2 == 0 {
+                OrderSide::Buy
+            } else {
+                OrderSide::Sell
+            };
+            let price = 100.0 + (i as f64 * 0.1).sin() * 10.0;
+            Fill {
+                order_id: OrderId(i as u64),
+                bar_index: i / 2,
+                date,
+                symbol: format!("SYM{}", (i / 2) % 50),
+                side,
+                price,
+                quantity: (1 + i % 37) as f64,
+                commission: price * 1e-4,
+                slippage: price * 3e-5,
+                phase: FillPhase::StartOfBar,
+                trigger_margin: None,
+                decision_source: None,
+            }
+        })
+        .collect();
+
+    for precise in [false, true] {
+        let name = if precise { "fixed_point" } else { "f64" };
+        group.bench_function(format!("apply_10k_fills/{name}"), |b| {
+            b.iter(|| {
+                let mut portfolio = Portfolio::new(100_000.0);
+                portfolio.precise_accounting = precise;
+                apply_fills(black_box(&fills), &mut portfolio);
+                black_box(portfolio.cash)
+            });
+        });
+    }
+
+    // The same full backtest with precise accounting off and on
+    let aligned = make_aligned(1260);
+    let comp = build_composition(&donchian_atr_config(), TradingMode::LongOnly).unwrap();
+    for precise in [false, true] {
+        let mut execution = trendlab_core::engine::ExecutionConfig::from_preset(
+            trendlab_core::components::execution::ExecutionPreset::Realistic,
+        );
+        execution.precise_accounting = precise;
+        let config = EngineConfig::with_execution(100_000.0, 0, execution);
+        let name = if precise { "fixed_point" } else { "f64" };
+        group.bench_function(format!("donchian_atr_1260_bars/{name}"), |b| {
+            b.iter(|| {
+                run_backtest(
+                    black_box(&aligned),
+                    black_box(&comp.indicators),
+                    black_box(&config),
+                    comp.signal.as_ref(),
+                    comp.filter.as_ref(),
+                    comp.execution.as_ref(),
+                    comp.pm.as_ref(),
+                )
+            });
+        });
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_bar_loop,
@@ -530,5 +650,6 @@ criterion_group!(
     bench_indicators,
     bench_pm_state_machine,
     bench_full_composition,
+    bench_accounting,
 );
 criterion_main!(benches);
@@ -5,7 +5,9 @@
 //!
 //! - `StrategyComposition`: fully assembled strategy ready for the engine.
 //! - `build_composition`: factory orchestrator that builds all four components.
-//! - `check_compatibility`: static compatibility rules (warnings, not errors).
+//! - `check_compatibility`: checks a config against the `COMPATIBILITY_RULES`
+//!   table. Warnings never block a config; the sampler resamples configs that
+//!   break an incompatible rule.
 //! - `StrategyPreset`: named presets for common strategy archetypes.
 
 use std::collections::BTreeMap;
@@ -19,7 +21,9 @@ use super::factory::{
 use super::filter::SignalFilter;
 use super::indicator::Indicator;
 use super::pm::PositionManager;
+use super::sampler::ComponentSlot;
 use super::signal::SignalGenerator;
+use crate::engine::compute_warmup;
 
 // ─── StrategyComposition ────────────────────────────────────────────
 
@@ -36,10 +40,136 @@ pub struct StrategyComposition {
 
 // ─── Compatibility check ────────────────────────────────────────────
 
+/// Component types in one slot that a rule matches.
+#[derive(Debug, Clone, Copy)]
+pub struct ComponentMatch {
+    pub slot: ComponentSlot,
+    pub types: &'static [&'static str],
+}
+
+impl ComponentMatch {
+    const fn new(slot: ComponentSlot, types: &'static [&'static str]) -> Self {
+        Self { slot, types }
+    }
+
+    fn matches(&self, config: &StrategyConfig) -> bool {
+        let component_type = self.slot.component(config).component_type.as_str();
+        self.types.contains(&component_type)
+    }
+}
+
+/// What a compatibility rule checks.
+#[derive(Debug, Clone, Copy)]
+pub enum RuleCheck {
+    /// When `when` matches, `then` must match too.
+    Requires {
+        when: ComponentMatch,
+        then: ComponentMatch,
+    },
+    /// When `when` matches, `then` must not.
+    Forbids {
+        when: ComponentMatch,
+        then: ComponentMatch,
+    },
+    /// At least `min_post_warmup_bars` of the context's bars must remain after
+    /// the config's indicator warmup. Skipped when the bar count is unknown.
+    PostWarmupBars,
+}
+
+/// How a broken rule is treated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleSeverity {
+    /// Reported only.
+    Warning,
+    /// Reported, and the sampler resamples rather than emit the config.
+    Incompatible,
+}
+
+/// One entry of the compatibility table.
+#[derive(Debug, Clone, Copy)]
+pub struct CompatibilityRule {
+    /// Stable identifier, used as the key of YOLO's per-rule counts.
+    pub id: &'static str,
+    pub severity: RuleSeverity,
+    pub check: RuleCheck,
+    pub message: &'static str,
+}
+
+const BREAKOUT_SIGNALS: &[&str] = &[
+    "breakout_52w",
+    "donchian_breakout",
+    "bollinger_breakout",
+    "keltner_breakout",
+];
+
+/// PMs that can close a position without a stop being hit.
+const FORCE_EXIT_PMS: &[&str] = &["since_entry_trailing", "max_holding_period", "composite"];
+
+/// Every compatibility rule, checked in order.
+pub const COMPATIBILITY_RULES: &[CompatibilityRule] = &[
+    CompatibilityRule {
+        id: "stop_entry_needs_breakout",
+        severity: RuleSeverity::Warning,
+        check: RuleCheck::Requires {
+            when: ComponentMatch::new(ComponentSlot::ExecutionModel, &["stop_entry"]),
+            then: ComponentMatch::new(ComponentSlot::Signal, BREAKOUT_SIGNALS),
+        },
+        message:
+            "stop_entry with non-breakout signal: stop entry will use fallback trigger (high+tick)",
+    },
+    CompatibilityRule {
+        id: "limit_entry_with_breakout",
+        severity: RuleSeverity::Warning,
+        check: RuleCheck::Forbids {
+            when: ComponentMatch::new(ComponentSlot::ExecutionModel, &["limit_entry"]),
+            then: ComponentMatch::new(ComponentSlot::Signal, BREAKOUT_SIGNALS),
+        },
+        message:
+            "limit_entry with breakout signal: limit entry ignores breakout_level; consider stop_entry",
+    },
+    CompatibilityRule {
+        id: "close_on_signal_needs_forced_exit",
+        severity: RuleSeverity::Incompatible,
+        check: RuleCheck::Requires {
+            when: ComponentMatch::new(ComponentSlot::ExecutionModel, &["close_on_signal"]),
+            then: ComponentMatch::new(ComponentSlot::PositionManager, FORCE_EXIT_PMS),
+        },
+        message: "close_on_signal with a stop-only PM: positions entered at the close can only \
+                  leave on a stop; pair it with a PM that forces exits",
+    },
+    CompatibilityRule {
+        id: "min_post_warmup_bars",
+        severity: RuleSeverity::Incompatible,
+        check: RuleCheck::PostWarmupBars,
+        message: "warmup leaves too few tradable bars",
+    },
+];
+
+/// Dataset facts a config is checked against.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompatibilityContext {
+    /// Bars the config will run on, if known.
+    pub bar_count: Option<usize>,
+    /// Bars that must remain after warmup.
+    pub min_post_warmup_bars: usize,
+}
+
+impl CompatibilityContext {
+    /// Largest indicator lookback that still leaves `min_post_warmup_bars`,
+    /// or None when the bar count is unknown.
+    pub fn max_lookback(&self) -> Option<usize> {
+        self.bar_count
+            .map(|bars| bars.saturating_sub(self.min_post_warmup_bars))
+    }
+}
+
 /// Result of a compatibility check between strategy components.
 #[derive(Debug, Clone)]
 pub struct CompatibilityResult {
+    /// Messages of every broken rule, whatever its severity.
     pub warnings: Vec<String>,
+    /// Ids of broken rules with [`RuleSeverity::Incompatible`].
+    pub incompatible: Vec<&'static str>,
 }
 
 impl CompatibilityResult {
@@ -47,40 +177,61 @@ impl CompatibilityResult {
     pub fn is_clean(&self) -> bool {
         self.warnings.is_empty()
     }
-}
 
-/// Static compatibility rules between components.
-/// Returns warnings (not errors) — all combinations are allowed.
-pub fn check_compatibility(config: &StrategyConfig) -> CompatibilityResult {
-    let mut warnings = Vec::new();
-
-    let sig = &config.signal.component_type;
-    let exec = &config.execution_model.component_type;
-
-    let breakout_signals = [
-        "breakout_52w",
-        "donchian_breakout",
-        "bollinger_breakout",
-        "keltner_breakout",
-    ];
-
-    // stop_entry + non-breakout signal
-    if exec == "stop_entry" && !breakout_signals.contains(&sig.as_str()) {
-        warnings.push(
-            "stop_entry with non-breakout signal: stop entry will use fallback trigger (high+tick)"
-                .into(),
-        );
+    /// True if no incompatible rule is broken; warnings are allowed.
+    pub fn is_compatible(&self) -> bool {
+        self.incompatible.is_empty()
     }
+}
 
-    // limit_entry + breakout signal
-    if exec == "limit_entry" && breakout_signals.contains(&sig.as_str()) {
-        warnings.push(
-            "limit_entry with breakout signal: limit entry ignores breakout_level; consider stop_entry"
-                .into(),
-        );
+/// Check `config` against every rule in [`COMPATIBILITY_RULES`].
+///
+/// Never blocks construction: callers decide what an incompatible result
+/// means (the sampler resamples, config validation reports it).
+pub fn check_compatibility(
+    config: &StrategyConfig,
+    context: &CompatibilityContext,
+) -> CompatibilityResult {
+    let mut result = CompatibilityResult {
+        warnings: Vec::new(),
+        incompatible: Vec::new(),
+    };
+    for rule in COMPATIBILITY_RULES {
+        let violation = match rule.check {
+            RuleCheck::Requires { when, then } => {
+                (when.matches(config) && !then.matches(config)).then(|| rule.message.to_string())
+            }
+            RuleCheck::Forbids { when, then } => {
+                (when.matches(config) && then.matches(config)).then(|| rule.message.to_string())
+            }
+            RuleCheck::PostWarmupBars => context.bar_count.and_then(|bars| {
+                let warmup = config_warmup(config);
+                let left = bars.saturating_sub(warmup);
+                (left < context.min_post_warmup_bars).then(|| {
+                    format!(
+                        "{}: warmup of {warmup} bars leaves {left} of {bars}, need {}",
+                        rule.message, context.min_post_warmup_bars
+                    )
+                })
+            }),
+        };
+        if let Some(message) = violation {
+            result.warnings.push(message);
+            if rule.severity == RuleSeverity::Incompatible {
+                result.incompatible.push(rule.id);
+            }
+        }
     }
+    result
+}
 
-    CompatibilityResult { warnings }
+/// Bars of indicator warmup `config` needs before its first signal.
+fn config_warmup(config: &StrategyConfig) -> usize {
+    compute_warmup(&required_indicators(
+        &config.signal,
+        &config.signal_filter,
+        &config.position_manager,
+    ))
 }
 
 // ─── build_composition ──────────────────────────────────────────────
@@ -139,36 +290,44 @@ impl StrategyPreset {
                 signal: ComponentConfig {
                     component_type: "donchian_breakout".into(),
                     params: btree(&[("entry_lookback", 50.0)]),
+                    children: Vec::new(),
                 },
                 position_manager: ComponentConfig {
                     component_type: "atr_trailing".into(),
                     params: btree(&[("atr_period", 14.0), ("multiplier", 3.0)]),
+                    children: Vec::new(),
                 },
                 execution_model: ComponentConfig {
                     component_type: "stop_entry".into(),
                     params: btree(&[("preset", 1.0)]),
+                    children: Vec::new(),
                 },
                 signal_filter: ComponentConfig {
                     component_type: "no_filter".into(),
                     params: BTreeMap::new(),
+                    children: Vec::new(),
                 },
             },
             Self::BollingerBreakout => StrategyConfig {
                 signal: ComponentConfig {
                     component_type: "bollinger_breakout".into(),
                     params: btree(&[("period", 20.0), ("std_multiplier", 2.0)]),
+                    children: Vec::new(),
                 },
                 position_manager: ComponentConfig {
                     component_type: "percent_trailing".into(),
                     params: btree(&[("trail_pct", 0.05)]),
+                    children: Vec::new(),
                 },
                 execution_model: ComponentConfig {
                     component_type: "next_bar_open".into(),
                     params: btree(&[("preset", 1.0)]),
+                    children: Vec::new(),
                 },
                 signal_filter: ComponentConfig {
                     component_type: "adx_filter".into(),
                     params: btree(&[("period", 14.0), ("threshold", 25.0)]),
+                    children: Vec::new(),
                 },
             },
             Self::MaCrossoverTrend => StrategyConfig {
@@ -179,24 +338,29 @@ impl StrategyPreset {
                         ("slow_period", 50.0),
                         ("ma_type", 0.0),
                     ]),
+                    children: Vec::new(),
                 },
                 position_manager: ComponentConfig {
                     component_type: "chandelier".into(),
                     params: btree(&[("atr_period", 22.0), ("multiplier", 3.0)]),
+                    children: Vec::new(),
                 },
                 execution_model: ComponentConfig {
                     component_type: "next_bar_open".into(),
                     params: btree(&[("preset", 1.0)]),
+                    children: Vec::new(),
                 },
                 signal_filter: ComponentConfig {
                     component_type: "ma_regime".into(),
                     params: btree(&[("period", 200.0), ("direction", 0.0)]),
+                    children: Vec::new(),
                 },
             },
             Self::MomentumRoc => StrategyConfig {
                 signal: ComponentConfig {
                     component_type: "roc_momentum".into(),
                     params: btree(&[("period", 12.0), ("threshold_pct", 0.0)]),
+                    children: Vec::new(),
                 },
                 position_manager: ComponentConfig {
                     component_type: "time_decay".into(),
@@ -205,32 +369,39 @@ impl StrategyPreset {
                         ("decay_per_bar", 0.005),
                         ("min_pct", 0.02),
                     ]),
+                    children: Vec::new(),
                 },
                 execution_model: ComponentConfig {
                     component_type: "next_bar_open".into(),
                     params: btree(&[("preset", 1.0)]),
+                    children: Vec::new(),
                 },
                 signal_filter: ComponentConfig {
                     component_type: "volatility_filter".into(),
                     params: btree(&[("period", 14.0), ("min_pct", 0.5), ("max_pct", 5.0)]),
+                    children: Vec::new(),
                 },
             },
             Self::SupertrendSystem => StrategyConfig {
                 signal: ComponentConfig {
                     component_type: "supertrend".into(),
                     params: btree(&[("period", 10.0), ("multiplier", 3.0)]),
+                    children: Vec::new(),
                 },
                 position_manager: ComponentConfig {
                     component_type: "breakeven_then_trail".into(),
                     params: btree(&[("breakeven_trigger_pct", 0.02), ("trail_pct", 0.03)]),
+                    children: Vec::new(),
                 },
                 execution_model: ComponentConfig {
                     component_type: "next_bar_open".into(),
                     params: btree(&[("preset", 1.0)]),
+                    children: Vec::new(),
                 },
                 signal_filter: ComponentConfig {
                     component_type: "no_filter".into(),
                     params: BTreeMap::new(),
+                    children: Vec::new(),
                 },
             },
         }
@@ -246,6 +417,33 @@ impl StrategyPreset {
             Self::SupertrendSystem,
         ]
     }
+
+    /// Name used on the command line and in the embedding API.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::DonchianTrend => "donchian_trend",
+            Self::BollingerBreakout => "bollinger_breakout",
+            Self::MaCrossoverTrend => "ma_crossover",
+            Self::MomentumRoc => "momentum_roc",
+            Self::SupertrendSystem => "supertrend",
+        }
+    }
+}
+
+impl std::str::FromStr for StrategyPreset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.to_ascii_lowercase();
+        Self::all()
+            .iter()
+            .copied()
+            .find(|p| p.label() == name)
+            .ok_or_else(|| {
+                let valid: Vec<&str> = Self::all().iter().map(|p| p.label()).collect();
+                format!("unknown preset '{s}'. Valid: {}", valid.join(", "))
+            })
+    }
 }
 
 // ─── Tests ──────────────────────────────────────────────────────────
@@ -306,6 +504,16 @@ mod tests {
         assert_eq!(comp.filter.name(), "no_filter");
     }
 
+    #[test]
+    fn preset_labels_round_trip() {
+        for &preset in StrategyPreset::all() {
+            assert_eq!(preset.label().parse::<StrategyPreset>(), Ok(preset));
+        }
+        assert_eq!("Momentum_ROC".parse(), Ok(StrategyPreset::MomentumRoc));
+        let err = "turtle".parse::<StrategyPreset>().unwrap_err();
+        assert!(err.contains("donchian_trend, bollinger_breakout"), "{err}");
+    }
+
     // ── Compatibility checks ────────────────────────────────────
 
     #[test]
@@ -314,21 +522,25 @@ mod tests {
             signal: ComponentConfig {
                 component_type: "ma_crossover".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
             execution_model: ComponentConfig {
                 component_type: "stop_entry".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
             position_manager: ComponentConfig {
                 component_type: "no_op".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
             signal_filter: ComponentConfig {
                 component_type: "no_filter".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
         };
-        let result = check_compatibility(&config);
+        let result = check_compatibility(&config, &CompatibilityContext::default());
         assert!(!result.is_clean());
         assert_eq!(result.warnings.len(), 1);
         assert!(result.warnings[0].contains("stop_entry with non-breakout signal"));
@@ -340,30 +552,102 @@ mod tests {
             signal: ComponentConfig {
                 component_type: "donchian_breakout".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
             execution_model: ComponentConfig {
                 component_type: "limit_entry".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
             position_manager: ComponentConfig {
                 component_type: "no_op".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
             signal_filter: ComponentConfig {
                 component_type: "no_filter".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
         };
-        let result = check_compatibility(&config);
+        let result = check_compatibility(&config, &CompatibilityContext::default());
         assert!(!result.is_clean());
         assert_eq!(result.warnings.len(), 1);
         assert!(result.warnings[0].contains("limit_entry with breakout signal"));
     }
 
+    fn with_components(signal: &str, pm: &str, exec: &str) -> StrategyConfig {
+        let mut config = StrategyPreset::DonchianTrend.to_config();
+        for (component, component_type) in [
+            (&mut config.signal, signal),
+            (&mut config.position_manager, pm),
+            (&mut config.execution_model, exec),
+        ] {
+            component.component_type = component_type.into();
+            component.params.clear();
+        }
+        config
+    }
+
+    #[test]
+    fn compat_rule_ids_are_unique() {
+        let mut ids: Vec<&str> = COMPATIBILITY_RULES.iter().map(|r| r.id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), COMPATIBILITY_RULES.len());
+    }
+
+    #[test]
+    fn compat_close_on_signal_needs_forced_exit_pm() {
+        let context = CompatibilityContext::default();
+        let stop_only = with_components("tsmom", "atr_trailing", "close_on_signal");
+        let result = check_compatibility(&stop_only, &context);
+        assert_eq!(
+            result.incompatible,
+            vec!["close_on_signal_needs_forced_exit"]
+        );
+
+        let forced = with_components("tsmom", "max_holding_period", "close_on_signal");
+        assert!(check_compatibility(&forced, &context).is_clean());
+    }
+
+    #[test]
+    fn compat_warnings_do_not_make_a_config_incompatible() {
+        let config = with_components("ma_crossover", "atr_trailing", "stop_entry");
+        let result = check_compatibility(&config, &CompatibilityContext::default());
+        assert!(!result.is_clean());
+        assert!(result.is_compatible());
+    }
+
+    #[test]
+    fn compat_post_warmup_bars_uses_the_dataset_length() {
+        let mut config = with_components("breakout_52w", "atr_trailing", "stop_entry");
+        config.signal.params.insert("lookback".into(), 252.0);
+        let short = CompatibilityContext {
+            bar_count: Some(300),
+            min_post_warmup_bars: 60,
+        };
+        let result = check_compatibility(&config, &short);
+        assert_eq!(result.incompatible, vec!["min_post_warmup_bars"]);
+        assert!(
+            result.warnings[0].contains("of 300, need 60"),
+            "{:?}",
+            result.warnings
+        );
+
+        let long = CompatibilityContext {
+            bar_count: Some(1000),
+            ..short
+        };
+        assert!(check_compatibility(&config, &long).is_clean());
+        // Unknown length: the rule does not apply
+        assert!(check_compatibility(&config, &CompatibilityContext::default()).is_clean());
+    }
+
     #[test]
     fn compat_clean_combo_no_warnings() {
         let config = StrategyPreset::DonchianTrend.to_config();
-        let result = check_compatibility(&config);
+        let result = check_compatibility(&config, &CompatibilityContext::default());
         assert!(result.is_clean());
     }
 
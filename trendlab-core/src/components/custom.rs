@@ -0,0 +1,365 @@
+//! Custom components — signals, position managers and filters registered at
+//! runtime by downstream crates.
+//!
+//! The built-in tables in `registry` are fixed at compile time. A crate with
+//! its own `SignalGenerator` (or PM, or filter) registers a constructor under
+//! a new component type instead of forking the factory:
+//!
+//! ```ignore
+//! ComponentRegistry::register_signal(
+//!     CustomSignal::new("my_signal", vec![lookback_range], |config| {
+//!         Box::new(MySignal::from_config(config))
+//!     })
+//!     .with_indicators(|config| vec![Box::new(Sma::new(lookback(config)))]),
+//! )?;
+//! ```
+//!
+//! Registered types join the registry's specs, so the factory validates their
+//! params, builds them after the built-ins, and lists them in unknown-type
+//! errors. The sampler only draws them from a pool built with
+//! `ComponentPool::with_registered`, and only those with a positive weight.
+//! Configs store the component type verbatim, so both fingerprint hashes tell
+//! custom components apart from built-ins and from each other.
+//!
+//! Registrations are process-wide and permanent; register once at startup.
+
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::fingerprint::ComponentConfig;
+
+use super::factory::FactoryError;
+use super::filter::SignalFilter;
+use super::indicator::Indicator;
+use super::pm::PositionManager;
+use super::registry::{ComponentKind, ComponentSpec, ParamSpec};
+use super::sampler::ParamRange;
+use super::signal::SignalGenerator;
+
+type Build<T> = Arc<dyn Fn(&ComponentConfig) -> Box<T> + Send + Sync>;
+type Indicators = Arc<dyn Fn(&ComponentConfig) -> Vec<Box<dyn Indicator>> + Send + Sync>;
+
+/// A custom signal type.
+pub type CustomSignal = CustomComponent<dyn SignalGenerator>;
+/// A custom position manager type.
+pub type CustomPm = CustomComponent<dyn PositionManager>;
+/// A custom filter type.
+pub type CustomFilter = CustomComponent<dyn SignalFilter>;
+
+/// A component type supplied from outside the crate.
+pub struct CustomComponent<T: ?Sized> {
+    component_type: String,
+    params: Vec<ParamRange>,
+    weight: f64,
+    build: Build<T>,
+    indicators: Option<Indicators>,
+}
+
+impl<T: ?Sized> CustomComponent<T> {
+    /// `params` are the params the type accepts (and the sampler explores);
+    /// `build` turns a validated config into the runtime component.
+    pub fn new(
+        component_type: impl Into<String>,
+        params: Vec<ParamRange>,
+        build: impl Fn(&ComponentConfig) -> Box<T> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            component_type: component_type.into(),
+            params,
+            weight: 0.0,
+            build: Arc::new(build),
+            indicators: None,
+        }
+    }
+
+    /// Sampler selection weight. The default of 0 keeps the type out of
+    /// sampled pools; it can still be named in configs.
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Indicators the component reads, precomputed like a built-in's.
+    pub fn with_indicators(
+        mut self,
+        indicators: impl Fn(&ComponentConfig) -> Vec<Box<dyn Indicator>> + Send + Sync + 'static,
+    ) -> Self {
+        self.indicators = Some(Arc::new(indicators));
+        self
+    }
+
+    /// Leak the metadata into a `'static` spec. Registrations are permanent,
+    /// so each successful one leaks once.
+    fn leak_spec(&self) -> &'static ComponentSpec {
+        let params: Vec<ParamSpec> = self
+            .params
+            .iter()
+            .map(|r| ParamSpec {
+                name: Box::leak(r.name.clone().into_boxed_str()),
+                default: r.default,
+                min: r.min,
+                max: r.max,
+            })
+            .collect();
+        Box::leak(Box::new(ComponentSpec {
+            component_type: Box::leak(self.component_type.clone().into_boxed_str()),
+            params: Box::leak(params.into_boxed_slice()),
+            extra_params: &[],
+            weight: self.weight,
+            primary: None,
+        }))
+    }
+}
+
+struct Registered<T: ?Sized> {
+    spec: &'static ComponentSpec,
+    build: Build<T>,
+    indicators: Option<Indicators>,
+}
+
+impl<T: ?Sized> Registered<T> {
+    fn find<'a>(entries: &'a [Self], component_type: &str) -> Option<&'a Self> {
+        entries
+            .iter()
+            .find(|e| e.spec.component_type == component_type)
+    }
+}
+
+/// Process-wide table of custom component types.
+#[derive(Default)]
+pub struct ComponentRegistry {
+    signals: Vec<Registered<dyn SignalGenerator>>,
+    position_managers: Vec<Registered<dyn PositionManager>>,
+    filters: Vec<Registered<dyn SignalFilter>>,
+}
+
+fn global() -> &'static RwLock<ComponentRegistry> {
+    static REGISTRY: OnceLock<RwLock<ComponentRegistry>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+fn read() -> std::sync::RwLockReadGuard<'static, ComponentRegistry> {
+    global().read().unwrap_or_else(|e| e.into_inner())
+}
+
+fn write() -> std::sync::RwLockWriteGuard<'static, ComponentRegistry> {
+    global().write().unwrap_or_else(|e| e.into_inner())
+}
+
+impl ComponentRegistry {
+    /// Register a custom signal type.
+    pub fn register_signal(component: CustomSignal) -> Result<(), FactoryError> {
+        register(ComponentKind::Signal, component, |r| &mut r.signals)
+    }
+
+    /// Register a custom position manager type.
+    pub fn register_pm(component: CustomPm) -> Result<(), FactoryError> {
+        register(ComponentKind::PositionManager, component, |r| {
+            &mut r.position_managers
+        })
+    }
+
+    /// Register a custom filter type.
+    pub fn register_filter(component: CustomFilter) -> Result<(), FactoryError> {
+        register(ComponentKind::SignalFilter, component, |r| &mut r.filters)
+    }
+
+    /// Specs of the custom types of `kind`, in registration order.
+    pub fn specs(kind: ComponentKind) -> Vec<&'static ComponentSpec> {
+        let registry = read();
+        match kind {
+            ComponentKind::Signal => registry.signals.iter().map(|e| e.spec).collect(),
+            ComponentKind::PositionManager => {
+                registry.position_managers.iter().map(|e| e.spec).collect()
+            }
+            ComponentKind::SignalFilter => registry.filters.iter().map(|e| e.spec).collect(),
+            ComponentKind::ExecutionModel => Vec::new(),
+        }
+    }
+
+    /// Build a custom signal, or None if the type is not registered.
+    pub(crate) fn build_signal(config: &ComponentConfig) -> Option<Box<dyn SignalGenerator>> {
+        let build = Registered::find(&read().signals, &config.component_type)
+            .map(|e| Arc::clone(&e.build))?;
+        Some(build(config))
+    }
+
+    /// Build a custom position manager, or None if the type is not registered.
+    pub(crate) fn build_pm(config: &ComponentConfig) -> Option<Box<dyn PositionManager>> {
+        let build = Registered::find(&read().position_managers, &config.component_type)
+            .map(|e| Arc::clone(&e.build))?;
+        Some(build(config))
+    }
+
+    /// Build a custom filter, or None if the type is not registered.
+    pub(crate) fn build_filter(config: &ComponentConfig) -> Option<Box<dyn SignalFilter>> {
+        let build = Registered::find(&read().filters, &config.component_type)
+            .map(|e| Arc::clone(&e.build))?;
+        Some(build(config))
+    }
+
+    /// Indicators a custom component declared (empty for unknown types).
+    pub(crate) fn indicators(
+        kind: ComponentKind,
+        config: &ComponentConfig,
+    ) -> Vec<Box<dyn Indicator>> {
+        let indicators = {
+            let registry = read();
+            let name = &config.component_type;
+            match kind {
+                ComponentKind::Signal => {
+                    Registered::find(&registry.signals, name).and_then(|e| e.indicators.clone())
+                }
+                ComponentKind::PositionManager => {
+                    Registered::find(&registry.position_managers, name)
+                        .and_then(|e| e.indicators.clone())
+                }
+                ComponentKind::SignalFilter => {
+                    Registered::find(&registry.filters, name).and_then(|e| e.indicators.clone())
+                }
+                ComponentKind::ExecutionModel => None,
+            }
+        };
+        indicators.map(|f| f(config)).unwrap_or_default()
+    }
+}
+
+/// Add `component` to the table `slot` picks, rejecting empty names and names
+/// already taken by a built-in or an earlier registration.
+fn register<T: ?Sized>(
+    kind: ComponentKind,
+    component: CustomComponent<T>,
+    slot: impl FnOnce(&mut ComponentRegistry) -> &mut Vec<Registered<T>>,
+) -> Result<(), FactoryError> {
+    let name = component.component_type.as_str();
+    if name.trim().is_empty() {
+        return Err(FactoryError::InvalidCustomType {
+            kind,
+            name: name.to_string(),
+        });
+    }
+    if kind.find(name).is_some() {
+        return Err(FactoryError::DuplicateType {
+            kind,
+            name: name.to_string(),
+        });
+    }
+
+    let mut registry = write();
+    let entries = slot(&mut registry);
+    // Re-check under the write lock: another thread may have registered it.
+    if Registered::find(entries, name).is_some() {
+        return Err(FactoryError::DuplicateType {
+            kind,
+            name: name.to_string(),
+        });
+    }
+    entries.push(Registered {
+        spec: component.leak_spec(),
+        build: component.build,
+        indicators: component.indicators,
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::signal::SignalEvent;
+    use crate::components::IndicatorValues;
+    use crate::domain::BarSeries;
+    use std::collections::BTreeMap;
+
+    struct Never;
+
+    impl SignalGenerator for Never {
+        fn name(&self) -> &str {
+            "never"
+        }
+
+        fn warmup_bars(&self) -> usize {
+            0
+        }
+
+        fn evaluate(
+            &self,
+            _: &dyn BarSeries,
+            _: usize,
+            _: &IndicatorValues,
+        ) -> Option<SignalEvent> {
+            None
+        }
+    }
+
+    fn never(name: &str) -> CustomSignal {
+        let range = ParamRange {
+            name: "window".into(),
+            default: 5.0,
+            min: 2.0,
+            max: 10.0,
+        };
+        CustomSignal::new(name, vec![range], |_| Box::new(Never))
+    }
+
+    fn config(name: &str) -> ComponentConfig {
+        ComponentConfig {
+            component_type: name.into(),
+            params: BTreeMap::from([("window".to_string(), 4.0)]),
+            children: vec![],
+        }
+    }
+
+    #[test]
+    fn registered_signal_is_listed_and_built() {
+        ComponentRegistry::register_signal(never("custom_test_never")).unwrap();
+
+        let spec = ComponentKind::Signal.find("custom_test_never").unwrap();
+        assert_eq!(spec.params[0].name, "window");
+        assert!(ComponentKind::Signal
+            .type_names()
+            .contains(&"custom_test_never"));
+        assert!(ComponentRegistry::specs(ComponentKind::PositionManager)
+            .iter()
+            .all(|s| s.component_type != "custom_test_never"));
+
+        let signal = ComponentRegistry::build_signal(&config("custom_test_never")).unwrap();
+        assert_eq!(signal.name(), "never");
+        assert!(ComponentRegistry::indicators(ComponentKind::Signal, &config("x")).is_empty());
+    }
+
+    #[test]
+    fn rejects_builtin_duplicate_and_empty_names() {
+        assert!(matches!(
+            ComponentRegistry::register_signal(never("donchian_breakout")),
+            Err(FactoryError::DuplicateType { .. })
+        ));
+        ComponentRegistry::register_signal(never("custom_test_twice")).unwrap();
+        assert!(matches!(
+            ComponentRegistry::register_signal(never("custom_test_twice")),
+            Err(FactoryError::DuplicateType { .. })
+        ));
+        assert!(matches!(
+            ComponentRegistry::register_signal(never("  ")),
+            Err(FactoryError::InvalidCustomType { .. })
+        ));
+    }
+
+    #[test]
+    fn only_weighted_types_join_a_registered_pool() {
+        use crate::components::ComponentPool;
+
+        ComponentRegistry::register_signal(never("custom_test_sampled").with_weight(1.0)).unwrap();
+        ComponentRegistry::register_signal(never("custom_test_unsampled")).unwrap();
+
+        let names = |pool: &ComponentPool| -> Vec<String> {
+            pool.signals
+                .iter()
+                .map(|v| v.component_type.clone())
+                .collect()
+        };
+        let default = names(&ComponentPool::default_pool());
+        let extended = names(&ComponentPool::default_pool().with_registered());
+        assert!(!default.iter().any(|n| n.starts_with("custom_test")));
+        assert!(extended.contains(&"custom_test_sampled".to_string()));
+        assert!(!extended.contains(&"custom_test_unsampled".to_string()));
+    }
+}
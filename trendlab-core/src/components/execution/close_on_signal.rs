@@ -3,21 +3,53 @@
 //! Entry orders are Market-On-Close (MOC): signal at bar T → MOC order fills
 //! at T's close (end-of-bar phase). This gives the fastest possible execution
 //! but requires the signal to fire during intrabar evaluation or at start-of-bar.
+//!
+//! With a [`FlipPolicy`] other than `Ignore`, an opposite-direction signal
+//! while in a position closes it at the next bar's open (and, for
+//! stop-and-reverse, opens the reverse position at that same open).
 
 use crate::components::signal::SignalEvent;
 use crate::domain::{Bar, Instrument, OrderType};
 
-use super::{ExecutionModel, ExecutionPreset, GapPolicy, PathPolicy};
+use crate::engine::execution::{CommissionModel, RegulatoryFees};
+
+use super::{ExecutionModel, ExecutionPreset, FlipPolicy, GapPolicy, PathPolicy};
 
 /// Market-On-Close entry: signal evaluated → MOC fill at bar close.
 #[derive(Debug, Clone)]
 pub struct CloseOnSignalModel {
     preset: ExecutionPreset,
+    /// Overrides the preset's bps commission when set.
+    commission: Option<CommissionModel>,
+    regulatory_fees: Option<RegulatoryFees>,
+    flip: FlipPolicy,
 }
 
 impl CloseOnSignalModel {
     pub fn new(preset: ExecutionPreset) -> Self {
-        Self { preset }
+        Self {
+            preset,
+            commission: None,
+            regulatory_fees: None,
+            flip: FlipPolicy::Ignore,
+        }
+    }
+
+    /// Exit (or stop-and-reverse) when the signal flips direction.
+    pub fn with_flip_policy(mut self, flip: FlipPolicy) -> Self {
+        self.flip = flip;
+        self
+    }
+
+    /// Replace the preset's bps commission and optionally add regulatory fees.
+    pub fn with_commission(
+        mut self,
+        commission: Option<CommissionModel>,
+        regulatory_fees: Option<RegulatoryFees>,
+    ) -> Self {
+        self.commission = commission;
+        self.regulatory_fees = regulatory_fees;
+        self
     }
 }
 
@@ -56,6 +88,20 @@ impl ExecutionModel for CloseOnSignalModel {
     fn commission_bps(&self) -> f64 {
         self.preset.commission_bps()
     }
+
+    fn commission_model(&self) -> CommissionModel {
+        self.commission.clone().unwrap_or(CommissionModel::Bps {
+            rate: self.commission_bps(),
+        })
+    }
+
+    fn regulatory_fees(&self) -> Option<RegulatoryFees> {
+        self.regulatory_fees
+    }
+
+    fn flip_policy(&self) -> FlipPolicy {
+        self.flip
+    }
 }
 
 #[cfg(test)]
@@ -104,6 +150,16 @@ mod tests {
         assert_eq!(CloseOnSignalModel::default().name(), "close_on_signal");
     }
 
+    #[test]
+    fn flip_policy_defaults_to_ignore() {
+        assert_eq!(
+            CloseOnSignalModel::default().flip_policy(),
+            FlipPolicy::Ignore
+        );
+        let model = CloseOnSignalModel::default().with_flip_policy(FlipPolicy::StopAndReverse);
+        assert_eq!(model.flip_policy(), FlipPolicy::StopAndReverse);
+    }
+
     #[test]
     fn frictionless_preset() {
         let model = CloseOnSignalModel::new(ExecutionPreset::Frictionless);
@@ -6,23 +6,44 @@
 //!
 //! This model captures "buy the dip" entries that wait for a pullback.
 
-use crate::components::signal::{SignalDirection, SignalEvent};
+use crate::components::signal::{SignalDirection, SignalEvent, REFERENCE_PRICE};
 use crate::domain::instrument::{round_to_tick, OrderSide};
 use crate::domain::{Bar, Instrument, OrderType};
 
+use crate::engine::execution::{CommissionModel, RegulatoryFees};
+
 use super::{ExecutionModel, ExecutionPreset, GapPolicy, PathPolicy};
 
 /// Limit-entry model: buy/sell limit at offset from reference price.
 #[derive(Debug, Clone)]
 pub struct LimitEntryModel {
     preset: ExecutionPreset,
+    /// Overrides the preset's bps commission when set.
+    commission: Option<CommissionModel>,
+    regulatory_fees: Option<RegulatoryFees>,
     /// Offset in basis points from the reference price. Default: 25 bps.
     offset_bps: f64,
 }
 
 impl LimitEntryModel {
     pub fn new(preset: ExecutionPreset, offset_bps: f64) -> Self {
-        Self { preset, offset_bps }
+        Self {
+            preset,
+            offset_bps,
+            commission: None,
+            regulatory_fees: None,
+        }
+    }
+
+    /// Replace the preset's bps commission and optionally add regulatory fees.
+    pub fn with_commission(
+        mut self,
+        commission: Option<CommissionModel>,
+        regulatory_fees: Option<RegulatoryFees>,
+    ) -> Self {
+        self.commission = commission;
+        self.regulatory_fees = regulatory_fees;
+        self
     }
 
     /// Default: 25 bps offset with realistic friction.
@@ -50,7 +71,7 @@ impl ExecutionModel for LimitEntryModel {
     ) -> OrderType {
         let reference_price = signal
             .metadata
-            .get("reference_price")
+            .get(REFERENCE_PRICE)
             .copied()
             .unwrap_or(bar.close);
 
@@ -89,6 +110,16 @@ impl ExecutionModel for LimitEntryModel {
     fn commission_bps(&self) -> f64 {
         self.preset.commission_bps()
     }
+
+    fn commission_model(&self) -> CommissionModel {
+        self.commission.clone().unwrap_or(CommissionModel::Bps {
+            rate: self.commission_bps(),
+        })
+    }
+
+    fn regulatory_fees(&self) -> Option<RegulatoryFees> {
+        self.regulatory_fees
+    }
 }
 
 #[cfg(test)]
@@ -2,17 +2,21 @@
 //!
 //! The execution model specifies what order type to use for entries,
 //! the path policy for intrabar ambiguity, gap handling, and friction
-//! parameters (slippage, commission).
+//! parameters (slippage, commission, regulatory fees). Per-symbol
+//! [`ExecutionProfile`]s override the friction for individual symbols.
 
 pub mod close_on_signal;
 pub mod limit_entry;
 pub mod next_bar_open;
+pub mod profile;
 pub mod stop_entry;
 
 use crate::domain::{Bar, Instrument, OrderType};
+use crate::engine::execution::{CommissionModel, RegulatoryFees};
 
 use super::signal::SignalEvent;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Intrabar path policy for resolving ambiguous bars.
 ///
@@ -41,13 +45,47 @@ pub enum GapPolicy {
     FillAtWorst,
 }
 
+/// What to do when the signal fires against an open position.
+///
+/// Lets flip-style signals (Supertrend, Parabolic SAR) manage their own exits
+/// instead of relying on a position manager.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlipPolicy {
+    /// Opposite signals are ignored while a position is open (default).
+    #[default]
+    Ignore,
+    /// Close the position at the next bar's open.
+    ExitOnly,
+    /// Close the position and open the reverse one, both at the next bar's open.
+    StopAndReverse,
+}
+
+impl FlipPolicy {
+    /// Param key enabling flip exits (>= 0.5 = on).
+    pub const PARAM_CLOSE_ON_FLIP: &'static str = "close_on_signal_flip";
+    /// Param key enabling stop-and-reverse (>= 0.5 = on, implies flip exits).
+    pub const PARAM_REVERSE_ON_FLIP: &'static str = "reverse_on_flip";
+
+    /// Decode from execution params. Missing keys leave flips ignored.
+    pub fn from_params(params: &BTreeMap<String, f64>) -> Self {
+        let on = |k: &str| params.get(k).is_some_and(|&v| v >= 0.5);
+        if on(Self::PARAM_REVERSE_ON_FLIP) {
+            Self::StopAndReverse
+        } else if on(Self::PARAM_CLOSE_ON_FLIP) {
+            Self::ExitOnly
+        } else {
+            Self::Ignore
+        }
+    }
+}
+
 /// Trait for execution models.
 ///
 /// The execution model determines:
 /// 1. What order type to use for entries (market, stop, limit, close-on-signal)
 /// 2. Path policy for intrabar ambiguity
 /// 3. Gap policy for gap-through fills
-/// 4. Friction parameters (slippage bps, commission bps)
+/// 4. Friction parameters (slippage bps, commission model, regulatory fees)
 pub trait ExecutionModel: Send + Sync {
     /// Human-readable name (e.g., "next_bar_open", "stop_entry").
     fn name(&self) -> &str;
@@ -71,6 +109,23 @@ pub trait ExecutionModel: Send + Sync {
 
     /// Commission in basis points per side.
     fn commission_bps(&self) -> f64;
+
+    /// Commission model charged per fill. Defaults to `commission_bps()` in bps.
+    fn commission_model(&self) -> CommissionModel {
+        CommissionModel::Bps {
+            rate: self.commission_bps(),
+        }
+    }
+
+    /// Sell-side regulatory fees, if enabled.
+    fn regulatory_fees(&self) -> Option<RegulatoryFees> {
+        None
+    }
+
+    /// Handling of opposite-direction signals while a position is open.
+    fn flip_policy(&self) -> FlipPolicy {
+        FlipPolicy::Ignore
+    }
 }
 
 /// Named execution presets bundling path policy, slippage, and commission.
@@ -87,6 +142,22 @@ pub enum ExecutionPreset {
 }
 
 impl ExecutionPreset {
+    pub const ALL: [Self; 4] = [
+        Self::Frictionless,
+        Self::Realistic,
+        Self::Hostile,
+        Self::Optimistic,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Frictionless => "frictionless",
+            Self::Realistic => "realistic",
+            Self::Hostile => "hostile",
+            Self::Optimistic => "optimistic",
+        }
+    }
+
     pub fn path_policy(self) -> PathPolicy {
         match self {
             Self::Frictionless => PathPolicy::Deterministic,
@@ -121,10 +192,26 @@ impl ExecutionPreset {
     }
 }
 
+impl std::str::FromStr for ExecutionPreset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|p| p.label() == s)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(|p| p.label()).collect();
+                format!("unknown execution preset '{s}' ({})", names.join(", "))
+            })
+    }
+}
+
 // Re-export concrete models.
 pub use close_on_signal::CloseOnSignalModel;
 pub use limit_entry::LimitEntryModel;
 pub use next_bar_open::NextBarOpenModel;
+pub use profile::{ExecutionProfile, ExecutionProfiles, LiquidityBucket};
 pub use stop_entry::StopEntryModel;
 
 #[cfg(test)]
@@ -140,6 +227,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn preset_labels_round_trip() {
+        for preset in ExecutionPreset::ALL {
+            assert_eq!(preset.label().parse::<ExecutionPreset>(), Ok(preset));
+        }
+        assert_eq!("Hostile".parse(), Ok(ExecutionPreset::Hostile));
+        assert!("lenient".parse::<ExecutionPreset>().is_err());
+    }
+
     #[test]
     fn worst_case_is_default_for_realistic() {
         assert_eq!(
@@ -155,4 +251,14 @@ mod tests {
         let deser: ExecutionPreset = serde_json::from_str(&json).unwrap();
         assert_eq!(preset, deser);
     }
+
+    #[test]
+    fn flip_policy_from_params() {
+        let mut params = BTreeMap::new();
+        assert_eq!(FlipPolicy::from_params(&params), FlipPolicy::Ignore);
+        params.insert(FlipPolicy::PARAM_CLOSE_ON_FLIP.to_string(), 1.0);
+        assert_eq!(FlipPolicy::from_params(&params), FlipPolicy::ExitOnly);
+        params.insert(FlipPolicy::PARAM_REVERSE_ON_FLIP.to_string(), 1.0);
+        assert_eq!(FlipPolicy::from_params(&params), FlipPolicy::StopAndReverse);
+    }
 }
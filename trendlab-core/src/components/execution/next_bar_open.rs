@@ -6,17 +6,37 @@
 use crate::components::signal::SignalEvent;
 use crate::domain::{Bar, Instrument, OrderType};
 
+use crate::engine::execution::{CommissionModel, RegulatoryFees};
+
 use super::{ExecutionModel, ExecutionPreset, GapPolicy, PathPolicy};
 
 /// Market-On-Open entry: signal evaluated at bar T → MOO order fills at T+1.
 #[derive(Debug, Clone)]
 pub struct NextBarOpenModel {
     preset: ExecutionPreset,
+    /// Overrides the preset's bps commission when set.
+    commission: Option<CommissionModel>,
+    regulatory_fees: Option<RegulatoryFees>,
 }
 
 impl NextBarOpenModel {
     pub fn new(preset: ExecutionPreset) -> Self {
-        Self { preset }
+        Self {
+            preset,
+            commission: None,
+            regulatory_fees: None,
+        }
+    }
+
+    /// Replace the preset's bps commission and optionally add regulatory fees.
+    pub fn with_commission(
+        mut self,
+        commission: Option<CommissionModel>,
+        regulatory_fees: Option<RegulatoryFees>,
+    ) -> Self {
+        self.commission = commission;
+        self.regulatory_fees = regulatory_fees;
+        self
     }
 
     /// Default: realistic friction.
@@ -60,6 +80,16 @@ impl ExecutionModel for NextBarOpenModel {
     fn commission_bps(&self) -> f64 {
         self.preset.commission_bps()
     }
+
+    fn commission_model(&self) -> CommissionModel {
+        self.commission.clone().unwrap_or(CommissionModel::Bps {
+            rate: self.commission_bps(),
+        })
+    }
+
+    fn regulatory_fees(&self) -> Option<RegulatoryFees> {
+        self.regulatory_fees
+    }
 }
 
 #[cfg(test)]
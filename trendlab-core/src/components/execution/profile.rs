@@ -0,0 +1,180 @@
+//! Per-symbol execution profiles.
+//!
+//! A session applies one sampled [`ExecutionPreset`] to every symbol, but a
+//! large-cap ETF and a small cap do not trade at the same friction. An
+//! [`ExecutionProfiles`] table overrides the preset, slippage, or commission
+//! for a symbol, either by name or by the liquidity bucket its average dollar
+//! volume falls into.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::ExecutionPreset;
+use crate::data::provider::RawBar;
+use crate::engine::execution::{CommissionModel, ExecutionConfig};
+
+/// Friction overrides for the symbols a profile applies to.
+///
+/// Unset fields keep what the strategy's execution model asked for.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionProfile {
+    /// Label recorded with every run that used the profile.
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<ExecutionPreset>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slippage_bps: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commission: Option<CommissionModel>,
+}
+
+impl ExecutionProfile {
+    /// Preset a run under this profile uses in place of `sampled`.
+    pub fn preset_for(&self, sampled: ExecutionPreset) -> ExecutionPreset {
+        self.preset.unwrap_or(sampled)
+    }
+
+    /// Build the execution config for a run whose strategy asked for
+    /// `sampled` with execution-model `params`.
+    ///
+    /// Commission and limit-fill params on the strategy apply first; the
+    /// profile's own slippage and commission then override them.
+    pub fn execution_config(
+        &self,
+        sampled: ExecutionPreset,
+        params: &BTreeMap<String, f64>,
+    ) -> ExecutionConfig {
+        let mut config = ExecutionConfig::from_preset(self.preset_for(sampled)).with_params(params);
+        if let Some(bps) = self.slippage_bps {
+            config.cost_model.slippage_bps = bps;
+        }
+        if let Some(ref commission) = self.commission {
+            config.cost_model.commission = commission.clone();
+        }
+        config
+    }
+}
+
+/// Profile for symbols whose average dollar volume is at least
+/// `min_avg_dollar_volume`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiquidityBucket {
+    pub min_avg_dollar_volume: f64,
+    pub profile: ExecutionProfile,
+}
+
+/// Symbol and liquidity-bucket mapping to execution profiles.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionProfiles {
+    /// Profiles for named symbols. These win over any bucket.
+    #[serde(default)]
+    pub symbols: BTreeMap<String, ExecutionProfile>,
+    /// Liquidity buckets. A symbol falls into the bucket with the highest
+    /// threshold its average dollar volume reaches.
+    #[serde(default)]
+    pub buckets: Vec<LiquidityBucket>,
+}
+
+impl ExecutionProfiles {
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty() && self.buckets.is_empty()
+    }
+
+    /// Profile for `symbol`, if any. Without a dollar volume only the
+    /// symbol mapping is consulted.
+    pub fn resolve(
+        &self,
+        symbol: &str,
+        avg_dollar_volume: Option<f64>,
+    ) -> Option<&ExecutionProfile> {
+        if let Some(profile) = self.symbols.get(symbol) {
+            return Some(profile);
+        }
+        let adv = avg_dollar_volume?;
+        self.buckets
+            .iter()
+            .filter(|b| adv >= b.min_avg_dollar_volume)
+            .max_by(|a, b| a.min_avg_dollar_volume.total_cmp(&b.min_avg_dollar_volume))
+            .map(|b| &b.profile)
+    }
+}
+
+/// Mean of close × volume over bars with a close (alignment fills gaps with
+/// NaN), or None if there are none.
+pub fn average_dollar_volume(bars: &[RawBar]) -> Option<f64> {
+    let (sum, n) = bars
+        .iter()
+        .filter(|b| b.close.is_finite())
+        .fold((0.0, 0usize), |(sum, n), b| {
+            (sum + b.close * b.volume as f64, n + 1)
+        });
+    (n > 0).then(|| sum / n as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(name: &str, slippage_bps: f64) -> ExecutionProfile {
+        ExecutionProfile {
+            name: name.into(),
+            preset: None,
+            slippage_bps: Some(slippage_bps),
+            commission: None,
+        }
+    }
+
+    fn profiles() -> ExecutionProfiles {
+        ExecutionProfiles {
+            symbols: BTreeMap::from([("SPY".to_string(), profile("etf", 1.0))]),
+            buckets: vec![
+                LiquidityBucket {
+                    min_avg_dollar_volume: 0.0,
+                    profile: profile("small_cap", 50.0),
+                },
+                LiquidityBucket {
+                    min_avg_dollar_volume: 1e9,
+                    profile: profile("large_cap", 3.0),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn symbol_mapping_wins_over_buckets() {
+        let p = profiles();
+        assert_eq!(p.resolve("SPY", Some(10.0)).unwrap().name, "etf");
+        assert_eq!(p.resolve("SPY", None).unwrap().name, "etf");
+    }
+
+    #[test]
+    fn highest_reached_bucket_applies() {
+        let p = profiles();
+        assert_eq!(p.resolve("AAPL", Some(5e9)).unwrap().name, "large_cap");
+        assert_eq!(p.resolve("TINY", Some(2e6)).unwrap().name, "small_cap");
+        assert!(p.resolve("TINY", None).is_none());
+        assert!(ExecutionProfiles::default()
+            .resolve("SPY", Some(1e9))
+            .is_none());
+    }
+
+    #[test]
+    fn overrides_apply_on_top_of_the_sampled_preset() {
+        let mut p = profile("small_cap", 50.0);
+        p.commission = Some(CommissionModel::PerTrade { flat: 1.0 });
+        let config = p.execution_config(ExecutionPreset::Realistic, &BTreeMap::new());
+        assert_eq!(config.cost_model.slippage_bps, 50.0);
+        assert_eq!(
+            config.cost_model.commission,
+            CommissionModel::PerTrade { flat: 1.0 }
+        );
+        assert_eq!(config.path_policy, ExecutionPreset::Realistic.path_policy());
+
+        p.preset = Some(ExecutionPreset::Frictionless);
+        assert_eq!(
+            p.preset_for(ExecutionPreset::Hostile),
+            ExecutionPreset::Frictionless
+        );
+    }
+}
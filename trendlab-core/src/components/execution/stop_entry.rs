@@ -5,23 +5,60 @@
 //! For Short signals: sell-stop at breakout level (price must fall to trigger).
 //!
 //! If the signal metadata does not contain a "breakout_level", falls back to
-//! bar.high + tick_size (Long) or bar.low - tick_size (Short).
+//! one tick beyond the signal bar's range: "signal_bar_high" / "signal_bar_low"
+//! when published, else the high / low of the bar passed in. The optional
+//! `offset_ticks` param moves the trigger further in the signal's direction,
+//! in whole ticks of the instrument.
 
-use crate::components::signal::{SignalDirection, SignalEvent};
+use crate::components::signal::{
+    SignalDirection, SignalEvent, BREAKOUT_LEVEL, SIGNAL_BAR_HIGH, SIGNAL_BAR_LOW,
+};
 use crate::domain::instrument::{round_to_tick, OrderSide};
 use crate::domain::{Bar, Instrument, OrderType};
 
+use crate::engine::execution::{CommissionModel, RegulatoryFees};
+
 use super::{ExecutionModel, ExecutionPreset, GapPolicy, PathPolicy};
 
 /// Stop-entry model: buy/sell stop at signal's breakout level.
 #[derive(Debug, Clone)]
 pub struct StopEntryModel {
     preset: ExecutionPreset,
+    /// Overrides the preset's bps commission when set.
+    commission: Option<CommissionModel>,
+    regulatory_fees: Option<RegulatoryFees>,
+    /// Extra ticks beyond the breakout level. Default: 0.
+    offset_ticks: u32,
 }
 
 impl StopEntryModel {
+    /// Param name for the trigger offset in ticks (rounded to a whole tick count).
+    pub const PARAM_OFFSET_TICKS: &'static str = "offset_ticks";
+
     pub fn new(preset: ExecutionPreset) -> Self {
-        Self { preset }
+        Self {
+            preset,
+            commission: None,
+            regulatory_fees: None,
+            offset_ticks: 0,
+        }
+    }
+
+    /// Place triggers `offset_ticks` beyond the breakout level.
+    pub fn with_offset_ticks(mut self, offset_ticks: u32) -> Self {
+        self.offset_ticks = offset_ticks;
+        self
+    }
+
+    /// Replace the preset's bps commission and optionally add regulatory fees.
+    pub fn with_commission(
+        mut self,
+        commission: Option<CommissionModel>,
+        regulatory_fees: Option<RegulatoryFees>,
+    ) -> Self {
+        self.commission = commission;
+        self.regulatory_fees = regulatory_fees;
+        self
     }
 }
 
@@ -42,21 +79,21 @@ impl ExecutionModel for StopEntryModel {
         bar: &Bar,
         instrument: &Instrument,
     ) -> OrderType {
-        let trigger_price = if let Some(&level) = signal.metadata.get("breakout_level") {
-            level
-        } else {
+        let tick = instrument.tick_size;
+        let offset = f64::from(self.offset_ticks) * tick;
+        let meta = |key: &str| signal.metadata.get(key).copied();
+
+        let trigger_price = match (signal.direction, meta(BREAKOUT_LEVEL)) {
+            (SignalDirection::Long, Some(level)) => level + offset,
+            (SignalDirection::Short, Some(level)) => level - offset,
             // Fallback: one tick above high (long) or below low (short)
-            match signal.direction {
-                SignalDirection::Long => round_to_tick(
-                    bar.high + instrument.tick_size,
-                    instrument.tick_size,
-                    OrderSide::Buy,
-                ),
-                SignalDirection::Short => round_to_tick(
-                    bar.low - instrument.tick_size,
-                    instrument.tick_size,
-                    OrderSide::Sell,
-                ),
+            (SignalDirection::Long, None) => {
+                let high = meta(SIGNAL_BAR_HIGH).unwrap_or(bar.high);
+                round_to_tick(high + tick + offset, tick, OrderSide::Buy)
+            }
+            (SignalDirection::Short, None) => {
+                let low = meta(SIGNAL_BAR_LOW).unwrap_or(bar.low);
+                round_to_tick(low - tick - offset, tick, OrderSide::Sell)
             }
         };
 
@@ -78,6 +115,16 @@ impl ExecutionModel for StopEntryModel {
     fn commission_bps(&self) -> f64 {
         self.preset.commission_bps()
     }
+
+    fn commission_model(&self) -> CommissionModel {
+        self.commission.clone().unwrap_or(CommissionModel::Bps {
+            rate: self.commission_bps(),
+        })
+    }
+
+    fn regulatory_fees(&self) -> Option<RegulatoryFees> {
+        self.regulatory_fees
+    }
 }
 
 #[cfg(test)]
@@ -178,6 +225,43 @@ mod tests {
         }
     }
 
+    fn trigger(model: &StopEntryModel, signal: &SignalEvent) -> f64 {
+        match model.entry_order_type(signal, &make_bar(), &Instrument::us_equity("SPY")) {
+            OrderType::StopMarket { trigger_price } => trigger_price,
+            other => panic!("expected StopMarket, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn offset_ticks_move_trigger_beyond_breakout_level() {
+        let model = StopEntryModel::default().with_offset_ticks(3);
+
+        let mut meta = HashMap::new();
+        meta.insert(BREAKOUT_LEVEL.into(), 106.0);
+        let long = make_signal(SignalDirection::Long, meta.clone());
+        assert!((trigger(&model, &long) - 106.03).abs() < 1e-9);
+
+        let short = make_signal(SignalDirection::Short, meta);
+        assert!((trigger(&model, &short) - 105.97).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fallback_prefers_published_signal_bar_range() {
+        let model = StopEntryModel::default();
+        let mut meta = HashMap::new();
+        meta.insert(SIGNAL_BAR_HIGH.into(), 104.5);
+        meta.insert(SIGNAL_BAR_LOW.into(), 98.5);
+
+        let long = make_signal(SignalDirection::Long, meta.clone());
+        assert!((trigger(&model, &long) - 104.51).abs() < 1e-9);
+        let short = make_signal(SignalDirection::Short, meta);
+        assert!((trigger(&model, &short) - 98.49).abs() < 1e-9);
+
+        let offset = StopEntryModel::default().with_offset_ticks(2);
+        let bare = make_signal(SignalDirection::Long, HashMap::new());
+        assert!((trigger(&offset, &bare) - 105.03).abs() < 1e-9);
+    }
+
     #[test]
     fn name_is_correct() {
         assert_eq!(StopEntryModel::default().name(), "stop_entry");
@@ -3,45 +3,187 @@
 //! Four factory functions (`create_signal`, `create_pm`, `create_execution`,
 //! `create_filter`) plus a `required_indicators` resolver that inspects configs
 //! to determine which indicators need precomputing.
+//!
+//! Component types and param names are checked against the registry (see
+//! `registry`) before construction, so a misspelled type or param is an error
+//! that names what would have been valid rather than a silent default.
+//! Types registered at runtime (see `custom`) are built after the built-ins.
 
 use std::collections::HashSet;
 
+use crate::engine::execution::{CommissionModel, RegulatoryFees};
 use crate::fingerprint::ComponentConfig;
 use crate::indicators::{
-    Adx, Aroon, Atr, Bollinger, Donchian, Ema, Keltner, Momentum, ParabolicSar, Roc, Sma,
-    Supertrend,
+    Adx, Aroon, Atr, Bollinger, DirectionalIndicator, Donchian, Ema, Ichimoku, Keltner, Momentum,
+    ParabolicSar, Roc, Sma, Supertrend,
 };
 
+use super::custom::ComponentRegistry;
 use super::execution::{
-    CloseOnSignalModel, ExecutionModel, ExecutionPreset, LimitEntryModel, NextBarOpenModel,
-    StopEntryModel,
+    CloseOnSignalModel, ExecutionModel, ExecutionPreset, FlipPolicy, LimitEntryModel,
+    NextBarOpenModel, StopEntryModel,
 };
 use super::filter::{
     AdxFilter, MaRegimeFilter, NoFilter, RegimeDirection, SignalFilter, VolatilityFilter,
 };
 use super::indicator::Indicator;
 use super::pm::{
-    AtrTrailing, BreakevenThenTrail, Chandelier, FixedStopLoss, FrozenReference, MaxHoldingPeriod,
-    NoOpPm, PercentTrailing, PositionManager, SinceEntryTrailing, TimeDecay,
+    AtrTrailing, BreakevenThenTrail, Chandelier, CompositePm, DonchianExit, FixedStopLoss,
+    FrozenReference, HoldingClock, MaxHoldingPeriod, NoOpPm, PercentTrailing, PositionManager,
+    SinceEntryTrailing, TimeDecay,
 };
+use super::registry::{ComponentKind, ParamSpec, ENSEMBLE_SIGNAL};
 use super::signal::{
-    AroonCrossover, BollingerBreakout, Breakout52w, DonchianBreakout, KeltnerBreakout, MaCrossover,
-    MaType, ParabolicSarSignal, RocMomentum, SignalGenerator, SupertrendSignal, Tsmom,
+    AdxTrend, AroonCrossover, BollingerBreakout, Breakout52w, DonchianBreakout, EnsembleSignal,
+    IchimokuCloud, KeltnerBreakout, MaCrossover, MaType, ParabolicSarSignal, RocMomentum,
+    SignalGenerator, SqueezeBreakout, StrengthAggregation, SupertrendSignal, Tsmom,
 };
 
 // ─── Error type ──────────────────────────────────────────────────────
 
 /// Errors that can occur during component construction.
+///
+/// Unknown-type errors carry the valid types, and param errors the accepted
+/// params with their ranges; [`FactoryError::hint`] formats them for display.
 #[derive(Debug, thiserror::Error)]
 pub enum FactoryError {
-    #[error("Unknown signal type: {0}")]
-    UnknownSignal(String),
-    #[error("Unknown position manager type: {0}")]
-    UnknownPm(String),
-    #[error("Unknown execution model type: {0}")]
-    UnknownExecution(String),
-    #[error("Unknown filter type: {0}")]
-    UnknownFilter(String),
+    #[error("Unknown signal type: {name} (valid: {})", valid.join(", "))]
+    UnknownSignal {
+        name: String,
+        valid: Vec<&'static str>,
+    },
+    #[error("Unknown position manager type: {name} (valid: {})", valid.join(", "))]
+    UnknownPm {
+        name: String,
+        valid: Vec<&'static str>,
+    },
+    #[error("Composite position manager has no children")]
+    EmptyComposite,
+    #[error("Ensemble signal has no children")]
+    EmptyEnsemble,
+    #[error("Unknown execution model type: {name} (valid: {})", valid.join(", "))]
+    UnknownExecution {
+        name: String,
+        valid: Vec<&'static str>,
+    },
+    #[error("Unknown filter type: {name} (valid: {})", valid.join(", "))]
+    UnknownFilter {
+        name: String,
+        valid: Vec<&'static str>,
+    },
+    #[error(
+        "Unknown parameter '{param}' for {kind} {component} (accepted: {})",
+        param_names(accepted)
+    )]
+    UnknownParam {
+        kind: ComponentKind,
+        component: String,
+        param: String,
+        accepted: Vec<ParamSpec>,
+    },
+    #[error("Invalid value {value} for parameter '{param}' of {kind} {component} (must be finite and non-negative)")]
+    InvalidParam {
+        kind: ComponentKind,
+        component: String,
+        param: String,
+        value: f64,
+        accepted: Vec<ParamSpec>,
+    },
+    #[error("Cannot register {kind} type '{name}': the type is already registered")]
+    DuplicateType { kind: ComponentKind, name: String },
+    #[error("Cannot register {kind} type '{name}': the type name is empty")]
+    InvalidCustomType { kind: ComponentKind, name: String },
+}
+
+impl FactoryError {
+    /// Multi-line listing of what would have been valid, one entry per line
+    /// (params with their typical range and default). None for errors that
+    /// carry no alternatives.
+    pub fn hint(&self) -> Option<String> {
+        let (header, lines): (String, Vec<String>) = match self {
+            Self::UnknownSignal { valid, .. }
+            | Self::UnknownPm { valid, .. }
+            | Self::UnknownExecution { valid, .. }
+            | Self::UnknownFilter { valid, .. } => (
+                "valid types:".to_string(),
+                valid.iter().map(|v| v.to_string()).collect(),
+            ),
+            Self::UnknownParam {
+                kind,
+                component,
+                accepted,
+                ..
+            }
+            | Self::InvalidParam {
+                kind,
+                component,
+                accepted,
+                ..
+            } => (
+                format!("accepted params for {kind} {component}:"),
+                if accepted.is_empty() {
+                    vec!["(none)".to_string()]
+                } else {
+                    accepted.iter().map(|p| p.to_string()).collect()
+                },
+            ),
+            Self::EmptyComposite
+            | Self::EmptyEnsemble
+            | Self::DuplicateType { .. }
+            | Self::InvalidCustomType { .. } => return None,
+        };
+        let mut hint = header;
+        for line in lines {
+            hint.push_str("\n  ");
+            hint.push_str(&line);
+        }
+        Some(hint)
+    }
+}
+
+fn param_names(accepted: &[ParamSpec]) -> String {
+    if accepted.is_empty() {
+        return "none".to_string();
+    }
+    accepted
+        .iter()
+        .map(|p| p.name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Check a config's type and params against the registry.
+fn check_component(kind: ComponentKind, config: &ComponentConfig) -> Result<(), FactoryError> {
+    let name = &config.component_type;
+    let Some(spec) = kind.find(name) else {
+        let (name, valid) = (name.clone(), kind.type_names());
+        return Err(match kind {
+            ComponentKind::Signal => FactoryError::UnknownSignal { name, valid },
+            ComponentKind::PositionManager => FactoryError::UnknownPm { name, valid },
+            ComponentKind::ExecutionModel => FactoryError::UnknownExecution { name, valid },
+            ComponentKind::SignalFilter => FactoryError::UnknownFilter { name, valid },
+        });
+    };
+    for (param, &value) in &config.params {
+        if !kind.accepts(spec, param) {
+            return Err(FactoryError::UnknownParam {
+                kind,
+                component: name.clone(),
+                param: param.clone(),
+                accepted: kind.accepted_params(spec),
+            });
+        }
+        if !value.is_finite() || value < 0.0 {
+            return Err(FactoryError::InvalidParam {
+                kind,
+                component: name.clone(),
+                param: param.clone(),
+                value,
+                accepted: kind.accepted_params(spec),
+            });
+        }
+    }
+    Ok(())
 }
 
 // ─── Helpers ─────────────────────────────────────────────────────────
@@ -65,6 +207,7 @@ fn param_usize(config: &ComponentConfig, name: &str, default: usize) -> usize {
 
 /// Create a signal generator from a `ComponentConfig`.
 pub fn create_signal(config: &ComponentConfig) -> Result<Box<dyn SignalGenerator>, FactoryError> {
+    check_component(ComponentKind::Signal, config)?;
     match config.component_type.as_str() {
         "breakout_52w" => {
             let lookback = param_usize(config, "lookback", 252);
@@ -127,7 +270,49 @@ pub fn create_signal(config: &ComponentConfig) -> Result<Box<dyn SignalGenerator
             let period = param_usize(config, "period", 25);
             Ok(Box::new(AroonCrossover::new(period)))
         }
-        other => Err(FactoryError::UnknownSignal(other.to_string())),
+        "ichimoku_cloud" => {
+            let tenkan = param_usize(config, "tenkan", 9);
+            let kijun = param_usize(config, "kijun", 26);
+            let senkou_b = param_usize(config, "senkou_b", 52);
+            Ok(Box::new(IchimokuCloud::new(tenkan, kijun, senkou_b)))
+        }
+        "adx_trend" => {
+            let period = param_usize(config, "period", 14);
+            let threshold = param(config, "threshold", 25.0);
+            Ok(Box::new(AdxTrend::new(period, threshold)))
+        }
+        "squeeze_breakout" => {
+            let period = param_usize(config, "period", 20);
+            let std_multiplier = param(config, "std_multiplier", 2.0);
+            let keltner_multiplier = param(config, "keltner_multiplier", 1.5);
+            let percentile_lookback = param_usize(config, "percentile_lookback", 120);
+            let percentile = param(config, "percentile", 20.0);
+            let min_squeeze_bars = param_usize(config, "min_squeeze_bars", 5);
+            Ok(Box::new(SqueezeBreakout::new(
+                period,
+                std_multiplier,
+                keltner_multiplier,
+                percentile_lookback,
+                percentile,
+                min_squeeze_bars,
+            )))
+        }
+        ENSEMBLE_SIGNAL => {
+            if config.children.is_empty() {
+                return Err(FactoryError::EmptyEnsemble);
+            }
+            let children = config
+                .children
+                .iter()
+                .map(create_signal)
+                .collect::<Result<Vec<_>, _>>()?;
+            let k = param_usize(config, EnsembleSignal::PARAM_K, children.len());
+            let aggregation =
+                StrengthAggregation::from_param(param(config, StrengthAggregation::PARAM, 0.0));
+            Ok(Box::new(EnsembleSignal::new(children, k, aggregation)))
+        }
+        other => Ok(ComponentRegistry::build_signal(config)
+            .unwrap_or_else(|| unreachable!("signal '{other}' is registered but not built"))),
     }
 }
 
@@ -135,6 +320,7 @@ pub fn create_signal(config: &ComponentConfig) -> Result<Box<dyn SignalGenerator
 
 /// Create a position manager from a `ComponentConfig`.
 pub fn create_pm(config: &ComponentConfig) -> Result<Box<dyn PositionManager>, FactoryError> {
+    check_component(ComponentKind::PositionManager, config)?;
     match config.component_type.as_str() {
         "atr_trailing" => {
             let atr_period = param_usize(config, "atr_period", 14);
@@ -150,6 +336,10 @@ pub fn create_pm(config: &ComponentConfig) -> Result<Box<dyn PositionManager>, F
             let multiplier = param(config, "multiplier", 3.0);
             Ok(Box::new(Chandelier::new(atr_period, multiplier)))
         }
+        "donchian_exit" => {
+            let exit_lookback = param_usize(config, "exit_lookback", 20);
+            Ok(Box::new(DonchianExit::new(exit_lookback)))
+        }
         "fixed_stop_loss" => {
             let stop_pct = param(config, "stop_pct", 0.02);
             Ok(Box::new(FixedStopLoss::new(stop_pct)))
@@ -166,11 +356,10 @@ pub fn create_pm(config: &ComponentConfig) -> Result<Box<dyn PositionManager>, F
             let initial_pct = param(config, "initial_pct", 0.10);
             let decay_per_bar = param(config, "decay_per_bar", 0.005);
             let min_pct = param(config, "min_pct", 0.02);
-            Ok(Box::new(TimeDecay::new(
-                initial_pct,
-                decay_per_bar,
-                min_pct,
-            )))
+            Ok(Box::new(
+                TimeDecay::new(initial_pct, decay_per_bar, min_pct)
+                    .with_clock(HoldingClock::from_params(&config.params)),
+            ))
         }
         "frozen_reference" => {
             let exit_pct = param(config, "exit_pct", 0.05);
@@ -182,10 +371,26 @@ pub fn create_pm(config: &ComponentConfig) -> Result<Box<dyn PositionManager>, F
         }
         "max_holding_period" => {
             let max_bars = param_usize(config, "max_bars", 20);
-            Ok(Box::new(MaxHoldingPeriod::new(max_bars)))
+            Ok(Box::new(
+                MaxHoldingPeriod::new(max_bars)
+                    .with_clock(HoldingClock::from_params(&config.params)),
+            ))
+        }
+        "composite" => {
+            if config.children.is_empty() {
+                return Err(FactoryError::EmptyComposite);
+            }
+            let children = config
+                .children
+                .iter()
+                .map(create_pm)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Box::new(CompositePm::new(children)))
         }
         "no_op" => Ok(Box::new(NoOpPm)),
-        other => Err(FactoryError::UnknownPm(other.to_string())),
+        other => Ok(ComponentRegistry::build_pm(config).unwrap_or_else(|| {
+            unreachable!("position manager '{other}' is registered but not built")
+        })),
     }
 }
 
@@ -202,17 +407,39 @@ fn decode_preset(config: &ComponentConfig) -> ExecutionPreset {
 }
 
 /// Create an execution model from a `ComponentConfig`.
+///
+/// Optional commission params (see `engine::execution::commission`) replace
+/// the preset's bps commission. `close_on_signal` also reads its flip policy
+/// (see [`FlipPolicy::from_params`]).
 pub fn create_execution(config: &ComponentConfig) -> Result<Box<dyn ExecutionModel>, FactoryError> {
+    check_component(ComponentKind::ExecutionModel, config)?;
     let preset = decode_preset(config);
+    let commission = CommissionModel::from_params(&config.params);
+    let fees = RegulatoryFees::from_params(&config.params);
     match config.component_type.as_str() {
-        "next_bar_open" => Ok(Box::new(NextBarOpenModel::new(preset))),
-        "stop_entry" => Ok(Box::new(StopEntryModel::new(preset))),
-        "close_on_signal" => Ok(Box::new(CloseOnSignalModel::new(preset))),
+        "next_bar_open" => Ok(Box::new(
+            NextBarOpenModel::new(preset).with_commission(commission, fees),
+        )),
+        "stop_entry" => {
+            let offset_ticks = param(config, StopEntryModel::PARAM_OFFSET_TICKS, 0.0);
+            Ok(Box::new(
+                StopEntryModel::new(preset)
+                    .with_commission(commission, fees)
+                    .with_offset_ticks(offset_ticks.round().max(0.0) as u32),
+            ))
+        }
+        "close_on_signal" => Ok(Box::new(
+            CloseOnSignalModel::new(preset)
+                .with_commission(commission, fees)
+                .with_flip_policy(FlipPolicy::from_params(&config.params)),
+        )),
         "limit_entry" => {
             let offset_bps = param(config, "offset_bps", 25.0);
-            Ok(Box::new(LimitEntryModel::new(preset, offset_bps)))
+            Ok(Box::new(
+                LimitEntryModel::new(preset, offset_bps).with_commission(commission, fees),
+            ))
         }
-        other => Err(FactoryError::UnknownExecution(other.to_string())),
+        other => unreachable!("execution model '{other}' is registered but not built"),
     }
 }
 
@@ -220,6 +447,7 @@ pub fn create_execution(config: &ComponentConfig) -> Result<Box<dyn ExecutionMod
 
 /// Create a signal filter from a `ComponentConfig`.
 pub fn create_filter(config: &ComponentConfig) -> Result<Box<dyn SignalFilter>, FactoryError> {
+    check_component(ComponentKind::SignalFilter, config)?;
     match config.component_type.as_str() {
         "no_filter" => Ok(Box::new(NoFilter)),
         "adx_filter" => {
@@ -241,9 +469,12 @@ pub fn create_filter(config: &ComponentConfig) -> Result<Box<dyn SignalFilter>,
             let period = param_usize(config, "period", 14);
             let min_pct = param(config, "min_pct", 0.5);
             let max_pct = param(config, "max_pct", 5.0);
-            Ok(Box::new(VolatilityFilter::new(period, min_pct, max_pct)))
+            Ok(Box::new(
+                VolatilityFilter::new(period, min_pct, max_pct).with_params(&config.params),
+            ))
         }
-        other => Err(FactoryError::UnknownFilter(other.to_string())),
+        other => Ok(ComponentRegistry::build_filter(config)
+            .unwrap_or_else(|| unreachable!("filter '{other}' is registered but not built"))),
     }
 }
 
@@ -261,15 +492,49 @@ pub fn required_indicators(
     let mut seen = HashSet::new();
     let mut indicators: Vec<Box<dyn Indicator>> = Vec::new();
 
-    // Helper closure: add an indicator if its name hasn't been seen yet.
+    // Helper closure: add an indicator unless every series it outputs is
+    // already produced by an earlier one.
     let mut add = |ind: Box<dyn Indicator>| {
-        let key = ind.name().to_string();
-        if seen.insert(key) {
+        let mut fresh = false;
+        for output in ind.outputs() {
+            fresh |= seen.insert(output.to_string());
+        }
+        if fresh {
             indicators.push(ind);
         }
     };
 
     // ── Signal indicators ────────────────────────────────────────
+    signal_indicators(signal, &mut add);
+
+    // ── Filter indicators ────────────────────────────────────────
+    match filter.component_type.as_str() {
+        "adx_filter" => {
+            let period = param_usize(filter, "period", 14);
+            add(Box::new(Adx::new(period)));
+        }
+        "ma_regime" => {
+            let period = param_usize(filter, "period", 200);
+            add(Box::new(Sma::new(period)));
+        }
+        "volatility_filter" => {
+            let period = param_usize(filter, "period", 14);
+            add(Box::new(Atr::new(period)));
+        }
+        // no_filter needs nothing; custom filters declare their own.
+        _ => ComponentRegistry::indicators(ComponentKind::SignalFilter, filter)
+            .into_iter()
+            .for_each(&mut add),
+    }
+
+    // ── PM indicators (ATR and channel PMs) ──────────────────────
+    pm_indicators(pm, &mut add);
+
+    indicators
+}
+
+/// Add the indicators a signal needs, recursing into ensemble children.
+fn signal_indicators(signal: &ComponentConfig, add: &mut dyn FnMut(Box<dyn Indicator>)) {
     match signal.component_type.as_str() {
         "breakout_52w" => {
             let lookback = param_usize(signal, "lookback", 252);
@@ -323,30 +588,43 @@ pub fn required_indicators(
         }
         "aroon_crossover" => {
             let period = param_usize(signal, "period", 25);
-            add(Box::new(Aroon::up(period)));
-            add(Box::new(Aroon::down(period)));
+            add(Box::new(Aroon::both(period)));
         }
-        _ => {} // Unknown signal — no indicators to add.
-    }
-
-    // ── Filter indicators ────────────────────────────────────────
-    match filter.component_type.as_str() {
-        "adx_filter" => {
-            let period = param_usize(filter, "period", 14);
+        "ichimoku_cloud" => {
+            let tenkan = param_usize(signal, "tenkan", 9);
+            let kijun = param_usize(signal, "kijun", 26);
+            let senkou_b = param_usize(signal, "senkou_b", 52);
+            add(Box::new(Ichimoku::tenkan(tenkan)));
+            add(Box::new(Ichimoku::kijun(kijun)));
+            add(Box::new(Ichimoku::span_a(tenkan, kijun)));
+            add(Box::new(Ichimoku::span_b(senkou_b, kijun)));
+        }
+        "adx_trend" => {
+            let period = param_usize(signal, "period", 14);
             add(Box::new(Adx::new(period)));
+            add(Box::new(DirectionalIndicator::plus(period)));
+            add(Box::new(DirectionalIndicator::minus(period)));
         }
-        "ma_regime" => {
-            let period = param_usize(filter, "period", 200);
-            add(Box::new(Sma::new(period)));
+        "squeeze_breakout" => {
+            let period = param_usize(signal, "period", 20);
+            let std_multiplier = param(signal, "std_multiplier", 2.0);
+            let keltner_multiplier = param(signal, "keltner_multiplier", 1.5);
+            add(Box::new(Bollinger::bands(period, std_multiplier)));
+            add(Box::new(Keltner::bands(period, period, keltner_multiplier)));
         }
-        "volatility_filter" => {
-            let period = param_usize(filter, "period", 14);
-            add(Box::new(Atr::new(period)));
+        ENSEMBLE_SIGNAL => {
+            for child in &signal.children {
+                signal_indicators(child, add);
+            }
         }
-        _ => {} // no_filter or unknown — nothing needed.
+        _ => ComponentRegistry::indicators(ComponentKind::Signal, signal)
+            .into_iter()
+            .for_each(add),
     }
+}
 
-    // ── PM indicators (ATR-dependent PMs) ────────────────────────
+/// Add the indicators a PM needs, recursing into composite children.
+fn pm_indicators(pm: &ComponentConfig, add: &mut dyn FnMut(Box<dyn Indicator>)) {
     match pm.component_type.as_str() {
         "atr_trailing" => {
             let atr_period = param_usize(pm, "atr_period", 14);
@@ -356,10 +634,20 @@ pub fn required_indicators(
             let atr_period = param_usize(pm, "atr_period", 22);
             add(Box::new(Atr::new(atr_period)));
         }
-        _ => {} // Other PMs don't need precomputed indicators.
+        "donchian_exit" => {
+            let exit_lookback = param_usize(pm, "exit_lookback", 20);
+            add(Box::new(Donchian::channel(exit_lookback)));
+        }
+        "composite" => {
+            for child in &pm.children {
+                pm_indicators(child, add);
+            }
+        }
+        // Other built-in PMs need nothing; custom PMs declare their own.
+        _ => ComponentRegistry::indicators(ComponentKind::PositionManager, pm)
+            .into_iter()
+            .for_each(add),
     }
-
-    indicators
 }
 
 // ─── Tests ───────────────────────────────────────────────────────────
@@ -378,6 +666,7 @@ mod tests {
         ComponentConfig {
             component_type: component_type.to_string(),
             params: p,
+            children: Vec::new(),
         }
     }
 
@@ -480,12 +769,51 @@ mod tests {
         assert_eq!(sig.name(), "aroon_crossover");
     }
 
+    #[test]
+    fn signal_ichimoku_cloud() {
+        let sig = create_signal(&bare("ichimoku_cloud")).unwrap();
+        assert_eq!(sig.name(), "ichimoku_cloud");
+        assert_eq!(sig.warmup_bars(), 78);
+    }
+
+    #[test]
+    fn signal_adx_trend() {
+        let sig = create_signal(&config("adx_trend", &[("period", 10.0)])).unwrap();
+        assert_eq!(sig.name(), "adx_trend");
+        assert_eq!(sig.warmup_bars(), 21);
+    }
+
+    #[test]
+    fn signal_squeeze_breakout() {
+        let sig = create_signal(&bare("squeeze_breakout")).unwrap();
+        assert_eq!(sig.name(), "squeeze_breakout");
+        assert_eq!(sig.warmup_bars(), 144);
+
+        let indicators = required_indicators(
+            &bare("squeeze_breakout"),
+            &bare("no_filter"),
+            &bare("no_op"),
+        );
+        let outputs: Vec<&str> = indicators.iter().flat_map(|i| i.outputs()).collect();
+        assert_eq!(
+            outputs,
+            [
+                "bollinger_upper_20_2",
+                "bollinger_middle_20_2",
+                "bollinger_lower_20_2",
+                "keltner_upper_20_20_1.5",
+                "keltner_middle_20_20_1.5",
+                "keltner_lower_20_20_1.5",
+            ]
+        );
+    }
+
     #[test]
     fn signal_unknown_returns_error() {
         let result = create_signal(&bare("bogus_signal"));
         assert!(result.is_err());
         match result.err().unwrap() {
-            FactoryError::UnknownSignal(name) => assert_eq!(name, "bogus_signal"),
+            FactoryError::UnknownSignal { name, .. } => assert_eq!(name, "bogus_signal"),
             other => panic!("expected UnknownSignal, got {:?}", other),
         }
     }
@@ -510,6 +838,12 @@ mod tests {
         assert_eq!(pm.name(), "chandelier_exit");
     }
 
+    #[test]
+    fn pm_donchian_exit() {
+        let pm = create_pm(&bare("donchian_exit")).unwrap();
+        assert_eq!(pm.name(), "donchian_exit");
+    }
+
     #[test]
     fn pm_fixed_stop_loss() {
         let pm = create_pm(&bare("fixed_stop_loss")).unwrap();
@@ -552,12 +886,89 @@ mod tests {
         assert_eq!(pm.name(), "no_op");
     }
 
+    fn composite(children: Vec<ComponentConfig>) -> ComponentConfig {
+        ComponentConfig {
+            children,
+            ..bare("composite")
+        }
+    }
+
+    #[test]
+    fn pm_composite() {
+        let pm = create_pm(&composite(vec![
+            config("fixed_stop_loss", &[("stop_pct", 0.05)]),
+            bare("atr_trailing"),
+        ]))
+        .unwrap();
+        assert_eq!(pm.name(), "composite");
+    }
+
+    #[test]
+    fn pm_composite_rejects_empty_and_unknown_children() {
+        match create_pm(&bare("composite")).err().unwrap() {
+            FactoryError::EmptyComposite => {}
+            other => panic!("expected EmptyComposite, got {:?}", other),
+        }
+        match create_pm(&composite(vec![bare("bogus_pm")])).err().unwrap() {
+            FactoryError::UnknownPm { name, .. } => assert_eq!(name, "bogus_pm"),
+            other => panic!("expected UnknownPm, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn required_indicators_recurses_into_composite_pm() {
+        let pm = composite(vec![
+            config("atr_trailing", &[("atr_period", 10.0)]),
+            config("chandelier", &[("atr_period", 22.0)]),
+        ]);
+        let inds = required_indicators(&bare("donchian_breakout"), &bare("no_filter"), &pm);
+        let names: HashSet<String> = inds.iter().map(|i| i.name().to_string()).collect();
+        assert!(names.contains("atr_10"));
+        assert!(names.contains("atr_22"));
+    }
+
+    #[test]
+    fn signal_ensemble_builds_children_and_their_indicators() {
+        let signal = ComponentConfig {
+            children: vec![
+                config("donchian_breakout", &[("entry_lookback", 20.0)]),
+                config("supertrend", &[("period", 30.0)]),
+            ],
+            ..bare("ensemble")
+        };
+        let sig = create_signal(&signal).unwrap();
+        assert_eq!(sig.name(), "ensemble");
+        assert_eq!(sig.warmup_bars(), 31);
+
+        let inds = required_indicators(&signal, &bare("no_filter"), &bare("no_op"));
+        let outputs: HashSet<&str> = inds.iter().flat_map(|i| i.outputs()).collect();
+        assert!(outputs.contains("donchian_upper_20"));
+        assert!(outputs.contains("supertrend_30_3"));
+
+        assert!(matches!(
+            create_signal(&bare("ensemble")),
+            Err(FactoryError::EmptyEnsemble)
+        ));
+    }
+
+    #[test]
+    fn required_indicators_donchian_exit_uses_its_own_lookback() {
+        // Turtle 20/10: entry channel and exit channel are separate series
+        let signal = config("donchian_breakout", &[("entry_lookback", 20.0)]);
+        let pm = config("donchian_exit", &[("exit_lookback", 10.0)]);
+        let inds = required_indicators(&signal, &bare("no_filter"), &pm);
+        let outputs: HashSet<&str> = inds.iter().flat_map(|i| i.outputs()).collect();
+        assert!(outputs.contains("donchian_upper_20"));
+        assert!(outputs.contains("donchian_lower_10"));
+        assert!(outputs.contains("donchian_upper_10"));
+    }
+
     #[test]
     fn pm_unknown_returns_error() {
         let result = create_pm(&bare("bogus_pm"));
         assert!(result.is_err());
         match result.err().unwrap() {
-            FactoryError::UnknownPm(name) => assert_eq!(name, "bogus_pm"),
+            FactoryError::UnknownPm { name, .. } => assert_eq!(name, "bogus_pm"),
             other => panic!("expected UnknownPm, got {:?}", other),
         }
     }
@@ -580,6 +991,10 @@ mod tests {
     fn exec_close_on_signal() {
         let ex = create_execution(&bare("close_on_signal")).unwrap();
         assert_eq!(ex.name(), "close_on_signal");
+        assert_eq!(ex.flip_policy(), FlipPolicy::Ignore);
+
+        let ex = create_execution(&config("close_on_signal", &[("reverse_on_flip", 1.0)])).unwrap();
+        assert_eq!(ex.flip_policy(), FlipPolicy::StopAndReverse);
     }
 
     #[test]
@@ -593,7 +1008,7 @@ mod tests {
         let result = create_execution(&bare("bogus_exec"));
         assert!(result.is_err());
         match result.err().unwrap() {
-            FactoryError::UnknownExecution(name) => assert_eq!(name, "bogus_exec"),
+            FactoryError::UnknownExecution { name, .. } => assert_eq!(name, "bogus_exec"),
             other => panic!("expected UnknownExecution, got {:?}", other),
         }
     }
@@ -606,6 +1021,32 @@ mod tests {
         assert_eq!(ex.commission_bps(), 0.0);
     }
 
+    #[test]
+    fn exec_commission_params_override_preset() {
+        let c = config(
+            "stop_entry",
+            &[
+                ("commission_model", 1.0),
+                ("commission_rate", 0.005),
+                ("commission_min", 1.0),
+                ("regulatory_fees", 1.0),
+            ],
+        );
+        let ex = create_execution(&c).unwrap();
+        assert_eq!(
+            ex.commission_model(),
+            CommissionModel::PerShare {
+                rate: 0.005,
+                min: 1.0
+            }
+        );
+        assert_eq!(ex.regulatory_fees(), Some(RegulatoryFees::default()));
+
+        let plain = create_execution(&bare("stop_entry")).unwrap();
+        assert_eq!(plain.commission_model(), CommissionModel::Bps { rate: 5.0 });
+        assert!(plain.regulatory_fees().is_none());
+    }
+
     #[test]
     fn exec_preset_realistic_default() {
         let c = bare("next_bar_open");
@@ -664,7 +1105,7 @@ mod tests {
         let result = create_filter(&bare("bogus_filter"));
         assert!(result.is_err());
         match result.err().unwrap() {
-            FactoryError::UnknownFilter(name) => assert_eq!(name, "bogus_filter"),
+            FactoryError::UnknownFilter { name, .. } => assert_eq!(name, "bogus_filter"),
             other => panic!("expected UnknownFilter, got {:?}", other),
         }
     }
@@ -740,14 +1181,46 @@ mod tests {
         let filter = bare("no_filter");
         let pm = bare("no_op");
 
+        // One multi-output instance fills both bands.
         let inds = required_indicators(&signal, &filter, &pm);
-        assert_eq!(inds.len(), 2);
+        assert_eq!(inds.len(), 1);
 
-        let names: HashSet<String> = inds.iter().map(|i| i.name().to_string()).collect();
+        let names: HashSet<String> = inds[0].outputs().iter().map(|n| n.to_string()).collect();
         assert!(names.contains("aroon_up_25"));
         assert!(names.contains("aroon_down_25"));
     }
 
+    #[test]
+    fn required_indicators_ichimoku_cloud() {
+        let signal = bare("ichimoku_cloud"); // four lines at 9/26/52
+        let filter = bare("no_filter");
+        let pm = bare("no_op");
+
+        let inds = required_indicators(&signal, &filter, &pm);
+        assert_eq!(inds.len(), 4);
+
+        let names: HashSet<String> = inds.iter().map(|i| i.name().to_string()).collect();
+        assert!(names.contains("ichimoku_tenkan_9"));
+        assert!(names.contains("ichimoku_kijun_26"));
+        assert!(names.contains("ichimoku_span_a_9_26"));
+        assert!(names.contains("ichimoku_span_b_52_26"));
+    }
+
+    #[test]
+    fn required_indicators_adx_trend_shares_adx_with_filter() {
+        let signal = bare("adx_trend"); // adx_14 + plus_di_14 + minus_di_14
+        let filter = bare("adx_filter"); // adx_14 (dedup)
+        let pm = bare("no_op");
+
+        let inds = required_indicators(&signal, &filter, &pm);
+        assert_eq!(inds.len(), 3);
+
+        let names: HashSet<String> = inds.iter().map(|i| i.name().to_string()).collect();
+        assert!(names.contains("adx_14"));
+        assert!(names.contains("plus_di_14"));
+        assert!(names.contains("minus_di_14"));
+    }
+
     #[test]
     fn required_indicators_no_filter_no_pm_indicators() {
         let signal = bare("tsmom"); // momentum_20
@@ -840,4 +1313,133 @@ mod tests {
         let pm = create_pm(&config("max_holding_period", &[("max_bars", 50.0)])).unwrap();
         assert_eq!(pm.name(), "max_holding_period");
     }
+
+    #[test]
+    fn time_based_pms_accept_void_bar_switch() {
+        let switch = [(HoldingClock::PARAM_COUNT_VOID_BARS, 1.0)];
+        assert!(create_pm(&config("max_holding_period", &switch)).is_ok());
+        assert!(create_pm(&config("time_decay", &switch)).is_ok());
+        assert!(matches!(
+            create_pm(&config("atr_trailing", &switch)),
+            Err(FactoryError::UnknownParam { .. })
+        ));
+    }
+
+    // ── Registry validation ──────────────────────────────────────
+
+    #[test]
+    fn every_registered_type_builds_with_defaults() {
+        use super::super::registry::ComponentKind::*;
+        let with_defaults = |spec: &super::super::registry::ComponentSpec| {
+            let params: Vec<(&str, f64)> =
+                spec.params.iter().map(|p| (p.name, p.default)).collect();
+            config(spec.component_type, &params)
+        };
+        for spec in Signal.all_specs() {
+            if spec.component_type == "ensemble" {
+                continue;
+            }
+            create_signal(&with_defaults(spec)).unwrap();
+        }
+        for spec in PositionManager.all_specs() {
+            if spec.component_type == "composite" {
+                continue;
+            }
+            create_pm(&with_defaults(spec)).unwrap();
+        }
+        for spec in ExecutionModel.all_specs() {
+            create_execution(&with_defaults(spec)).unwrap();
+        }
+        for spec in SignalFilter.all_specs() {
+            create_filter(&with_defaults(spec)).unwrap();
+        }
+    }
+
+    #[test]
+    fn unknown_type_lists_valid_types() {
+        let err = create_filter(&bare("sma_regime")).err().unwrap();
+        let msg = err.to_string();
+        assert!(msg.starts_with("Unknown filter type: sma_regime (valid: no_filter, adx_filter"));
+        let hint = err.hint().unwrap();
+        assert!(hint.starts_with("valid types:\n  no_filter\n  adx_filter"));
+
+        let FactoryError::UnknownPm { valid, .. } = create_pm(&bare("bogus")).err().unwrap() else {
+            panic!("expected UnknownPm");
+        };
+        assert!(valid.contains(&"no_op"));
+    }
+
+    #[test]
+    fn unknown_param_lists_accepted_params_with_ranges() {
+        let err = create_signal(&config(
+            "donchian_breakout",
+            &[("entry_lookback", 50.0), ("exit_lookback", 20.0)],
+        ))
+        .err()
+        .unwrap();
+        match &err {
+            FactoryError::UnknownParam {
+                param, accepted, ..
+            } => {
+                assert_eq!(param, "exit_lookback");
+                assert_eq!(accepted[0].name, "entry_lookback");
+                // Entry governance params are accepted by every signal
+                assert!(accepted.iter().any(|p| p.name == "cooldown_bars"));
+            }
+            other => panic!("expected UnknownParam, got {other:?}"),
+        }
+        assert!(err
+            .to_string()
+            .contains("'exit_lookback' for signal donchian_breakout (accepted: entry_lookback,"));
+        let hint = err.hint().unwrap();
+        assert!(hint.starts_with(
+            "accepted params for signal donchian_breakout:\n  entry_lookback (10..=200, default 50)"
+        ));
+    }
+
+    #[test]
+    fn composite_children_are_checked() {
+        let err = create_pm(&composite(vec![config(
+            "fixed_stop_loss",
+            &[("stop", 0.05)],
+        )]))
+        .err()
+        .unwrap();
+        assert!(
+            matches!(err, FactoryError::UnknownParam { ref component, .. } if component == "fixed_stop_loss")
+        );
+    }
+
+    #[test]
+    fn negative_or_nan_param_is_invalid() {
+        for value in [-1.0, f64::NAN] {
+            let err = create_pm(&config("percent_trailing", &[("trail_pct", value)]))
+                .err()
+                .unwrap();
+            assert!(matches!(err, FactoryError::InvalidParam { .. }), "{value}");
+            assert!(err
+                .hint()
+                .unwrap()
+                .contains("trail_pct (0.01..=0.15, default 0.05)"));
+        }
+    }
+
+    #[test]
+    fn shared_execution_params_are_accepted() {
+        create_execution(&config(
+            "next_bar_open",
+            &[
+                ("commission_model", 3.0),
+                ("commission_tier_0_above", 0.0),
+                ("commission_tier_0_rate", 0.005),
+                ("regulatory_fees", 1.0),
+            ],
+        ))
+        .unwrap();
+        let err = create_execution(&config("next_bar_open", &[("reverse_on_flip", 1.0)]))
+            .err()
+            .unwrap();
+        assert!(matches!(err, FactoryError::UnknownParam { .. }));
+        create_execution(&config("close_on_signal", &[("reverse_on_flip", 1.0)])).unwrap();
+    }
 }
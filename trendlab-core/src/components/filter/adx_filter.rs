@@ -5,7 +5,7 @@
 
 use crate::components::indicator::IndicatorValues;
 use crate::components::signal::{FilterVerdict, SignalEvaluation, SignalEvent};
-use crate::domain::Bar;
+use crate::domain::BarSeries;
 use std::collections::HashMap;
 
 use super::SignalFilter;
@@ -45,7 +45,7 @@ impl SignalFilter for AdxFilter {
     fn evaluate(
         &self,
         signal: &SignalEvent,
-        _bars: &[Bar],
+        _bars: &dyn BarSeries,
         bar_index: usize,
         indicators: &IndicatorValues,
     ) -> SignalEvaluation {
@@ -109,7 +109,7 @@ mod tests {
         let mut adx_vals = vec![f64::NAN; 15];
         adx_vals[10] = 30.0; // above threshold
         let iv = make_indicators("adx_14", adx_vals);
-        let eval = filter.evaluate(&signal, &[], 10, &iv);
+        let eval = filter.evaluate(&signal, &Vec::new(), 10, &iv);
         assert!(eval.verdict.is_passed());
         assert_eq!(eval.filter_state["adx_value"], 30.0);
     }
@@ -121,7 +121,7 @@ mod tests {
         let mut adx_vals = vec![f64::NAN; 15];
         adx_vals[10] = 18.0; // below threshold
         let iv = make_indicators("adx_14", adx_vals);
-        let eval = filter.evaluate(&signal, &[], 10, &iv);
+        let eval = filter.evaluate(&signal, &Vec::new(), 10, &iv);
         assert!(!eval.verdict.is_passed());
         assert_eq!(eval.verdict, FilterVerdict::FilteredByAdx);
         assert_eq!(eval.filter_state["adx_value"], 18.0);
@@ -134,7 +134,7 @@ mod tests {
         let mut adx_vals = vec![f64::NAN; 15];
         adx_vals[10] = 25.0;
         let iv = make_indicators("adx_14", adx_vals);
-        let eval = filter.evaluate(&signal, &[], 10, &iv);
+        let eval = filter.evaluate(&signal, &Vec::new(), 10, &iv);
         assert!(eval.verdict.is_passed());
     }
 
@@ -144,7 +144,7 @@ mod tests {
         let signal = make_signal();
         let adx_vals = vec![f64::NAN; 15];
         let iv = make_indicators("adx_14", adx_vals);
-        let eval = filter.evaluate(&signal, &[], 10, &iv);
+        let eval = filter.evaluate(&signal, &Vec::new(), 10, &iv);
         assert!(!eval.verdict.is_passed());
     }
 
@@ -153,7 +153,7 @@ mod tests {
         let filter = AdxFilter::new(14, 25.0);
         let signal = make_signal();
         let iv = IndicatorValues::new();
-        let eval = filter.evaluate(&signal, &[], 10, &iv);
+        let eval = filter.evaluate(&signal, &Vec::new(), 10, &iv);
         assert!(!eval.verdict.is_passed());
     }
 
@@ -164,7 +164,7 @@ mod tests {
         let mut adx_vals = vec![f64::NAN; 15];
         adx_vals[10] = 30.0;
         let iv = make_indicators("adx_14", adx_vals);
-        let eval = filter.evaluate(&signal, &[], 10, &iv);
+        let eval = filter.evaluate(&signal, &Vec::new(), 10, &iv);
         assert_eq!(eval.filter_state["adx_value"], 30.0);
         assert_eq!(eval.filter_state["threshold"], 25.0);
     }
@@ -5,7 +5,7 @@
 
 use crate::components::indicator::IndicatorValues;
 use crate::components::signal::{FilterVerdict, SignalEvaluation, SignalEvent};
-use crate::domain::Bar;
+use crate::domain::BarSeries;
 use std::collections::HashMap;
 
 use super::SignalFilter;
@@ -53,12 +53,12 @@ impl SignalFilter for MaRegimeFilter {
     fn evaluate(
         &self,
         signal: &SignalEvent,
-        bars: &[Bar],
+        bars: &dyn BarSeries,
         bar_index: usize,
         indicators: &IndicatorValues,
     ) -> SignalEvaluation {
         let close = if bar_index < bars.len() {
-            bars[bar_index].close
+            bars.bar(bar_index).close
         } else {
             f64::NAN
         };
@@ -96,7 +96,7 @@ impl SignalFilter for MaRegimeFilter {
 mod tests {
     use super::*;
     use crate::components::signal::SignalDirection;
-    use crate::domain::SignalEventId;
+    use crate::domain::{Bar, SignalEventId};
     use chrono::NaiveDate;
 
     fn make_signal() -> SignalEvent {
@@ -117,7 +117,7 @@ mod tests {
             .iter()
             .enumerate()
             .map(|(i, &close)| Bar {
-                symbol: "SPY".to_string(),
+                symbol: "SPY".into(),
                 date: base_date + chrono::Duration::days(i as i64),
                 open: close - 0.5,
                 high: close + 2.0,
@@ -2,15 +2,22 @@
 //!
 //! Filters evaluate signal events and produce `SignalEvaluation` records.
 //! A pass-through "no filter" is the default.
+//!
+//! Filters may also review the position manager's intents during PM
+//! maintenance (see [`SignalFilter::review_intent`]), e.g. to hold stops
+//! steady through a volatility spike. Each override is recorded as an
+//! `IntentIntervention`.
 
 pub mod adx_filter;
 pub mod ma_regime;
 pub mod volatility;
 
-use crate::domain::Bar;
+use crate::domain::BarSeries;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use super::indicator::IndicatorValues;
+use super::pm::OrderIntent;
 use super::signal::{FilterVerdict, SignalEvaluation, SignalEvent};
 
 /// Trait for signal filters.
@@ -21,6 +28,7 @@ use super::signal::{FilterVerdict, SignalEvaluation, SignalEvent};
 ///
 /// # Architecture invariant
 /// Filters must not reference portfolio state — they evaluate market conditions only.
+/// The intent hook sees the managed position's current stop and nothing else.
 pub trait SignalFilter: Send + Sync {
     /// Human-readable name (e.g., "adx_filter", "no_filter").
     fn name(&self) -> &str;
@@ -31,10 +39,48 @@ pub trait SignalFilter: Send + Sync {
     fn evaluate(
         &self,
         signal: &SignalEvent,
-        bars: &[Bar],
+        bars: &dyn BarSeries,
         bar_index: usize,
         indicators: &IndicatorValues,
     ) -> SignalEvaluation;
+
+    /// Review the position manager's intent for an open position.
+    ///
+    /// Called after PM maintenance with the ratchet-clamped intent and the
+    /// position's current stop. Return an override to transform or veto the
+    /// intent; the default leaves it alone. The engine re-applies the ratchet
+    /// to overrides, so a filter can hold or tighten a stop but never loosen it.
+    fn review_intent(
+        &self,
+        _intent: &OrderIntent,
+        _current_stop: Option<f64>,
+        _bars: &dyn BarSeries,
+        _bar_index: usize,
+        _indicators: &IndicatorValues,
+    ) -> Option<IntentOverride> {
+        None
+    }
+}
+
+/// A filter's replacement for a PM intent, from [`SignalFilter::review_intent`].
+#[derive(Debug, Clone)]
+pub struct IntentOverride {
+    pub intent: OrderIntent,
+    /// Snapshot of the filter's state when it intervened.
+    pub filter_state: HashMap<String, f64>,
+}
+
+/// Diagnostic record of a filter changing a PM intent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntentIntervention {
+    pub symbol: String,
+    pub bar_index: usize,
+    pub filter_name: String,
+    /// The PM's intent after the ratchet.
+    pub original: OrderIntent,
+    /// What the engine applied instead.
+    pub applied: OrderIntent,
+    pub filter_state: HashMap<String, f64>,
 }
 
 /// No-op filter — always passes signals through.
@@ -48,7 +94,7 @@ impl SignalFilter for NoFilter {
     fn evaluate(
         &self,
         signal: &SignalEvent,
-        _bars: &[Bar],
+        _bars: &dyn BarSeries,
         _bar_index: usize,
         _indicators: &IndicatorValues,
     ) -> SignalEvaluation {
@@ -89,7 +135,7 @@ mod tests {
         let filter = NoFilter;
         let signal = make_signal();
         let iv = IndicatorValues::new();
-        let eval = filter.evaluate(&signal, &[], 0, &iv);
+        let eval = filter.evaluate(&signal, &Vec::new(), 0, &iv);
         assert!(eval.verdict.is_passed());
         assert_eq!(eval.filter_name, "no_filter");
         assert_eq!(eval.signal_event_id, signal.id);
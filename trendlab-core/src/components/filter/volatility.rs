@@ -3,13 +3,18 @@
 //! Passes signals when volatility (ATR as % of price) is within
 //! the specified range. Rejects in extremely low-vol (no movement)
 //! or extremely high-vol (erratic) environments.
+//!
+//! Optionally freezes stops: above `freeze_stops_above_atr_pct`, PM stop
+//! adjustments are held back so a single bad print cannot drag the stop up
+//! into the noise. The stop tightens on the first calmer bar.
 
 use crate::components::indicator::IndicatorValues;
+use crate::components::pm::{IntentAction, OrderIntent};
 use crate::components::signal::{FilterVerdict, SignalEvaluation, SignalEvent};
-use crate::domain::Bar;
-use std::collections::HashMap;
+use crate::domain::BarSeries;
+use std::collections::{BTreeMap, HashMap};
 
-use super::SignalFilter;
+use super::{IntentOverride, SignalFilter};
 
 /// ATR-based volatility filter.
 ///
@@ -20,6 +25,8 @@ pub struct VolatilityFilter {
     pub period: usize,
     pub min_pct: f64,
     pub max_pct: f64,
+    /// Hold stop adjustments while volatility exceeds this %. `None` never freezes.
+    pub freeze_stops_above_pct: Option<f64>,
     indicator_key: String,
 }
 
@@ -32,6 +39,7 @@ impl VolatilityFilter {
             period,
             min_pct,
             max_pct,
+            freeze_stops_above_pct: None,
             indicator_key: format!("atr_{period}"),
         }
     }
@@ -39,6 +47,38 @@ impl VolatilityFilter {
     pub fn default_params() -> Self {
         Self::new(14, 0.5, 5.0)
     }
+
+    /// Param key for the stop-freeze threshold; 0 or absent disables it.
+    pub const PARAM_FREEZE_STOPS: &'static str = "freeze_stops_above_atr_pct";
+
+    /// Freeze stops while volatility exceeds `pct` (ATR as % of close).
+    pub fn with_freeze_stops_above(mut self, pct: f64) -> Self {
+        self.freeze_stops_above_pct = Some(pct);
+        self
+    }
+
+    /// Apply the stop-freeze threshold carried in filter params, if positive.
+    pub fn with_params(self, params: &BTreeMap<String, f64>) -> Self {
+        match params.get(Self::PARAM_FREEZE_STOPS) {
+            Some(&pct) if pct > 0.0 => self.with_freeze_stops_above(pct),
+            _ => self,
+        }
+    }
+
+    /// ATR, close, and ATR as % of close at `bar_index`, if all are valid.
+    fn volatility(
+        &self,
+        bars: &dyn BarSeries,
+        bar_index: usize,
+        indicators: &IndicatorValues,
+    ) -> Option<(f64, f64, f64)> {
+        let close = bars.get(bar_index)?.close;
+        let atr = indicators.get(&self.indicator_key, bar_index)?;
+        if atr.is_nan() || close.is_nan() || close <= 0.0 {
+            return None;
+        }
+        Some((atr, close, (atr / close) * 100.0))
+    }
 }
 
 impl SignalFilter for VolatilityFilter {
@@ -49,21 +89,12 @@ impl SignalFilter for VolatilityFilter {
     fn evaluate(
         &self,
         signal: &SignalEvent,
-        bars: &[Bar],
+        bars: &dyn BarSeries,
         bar_index: usize,
         indicators: &IndicatorValues,
     ) -> SignalEvaluation {
-        let close = if bar_index < bars.len() {
-            bars[bar_index].close
-        } else {
-            f64::NAN
-        };
-
-        let atr_value = indicators.get(&self.indicator_key, bar_index);
-
-        let (verdict, filter_state) = match atr_value {
-            Some(atr) if !atr.is_nan() && !close.is_nan() && close > 0.0 => {
-                let vol_pct = (atr / close) * 100.0;
+        let (verdict, filter_state) = match self.volatility(bars, bar_index, indicators) {
+            Some((atr, close, vol_pct)) => {
                 let mut state = HashMap::new();
                 state.insert("atr_value".into(), atr);
                 state.insert("close".into(), close);
@@ -74,7 +105,7 @@ impl SignalFilter for VolatilityFilter {
                     (FilterVerdict::FilteredByVolatility, state)
                 }
             }
-            _ => (FilterVerdict::FilteredByVolatility, HashMap::new()),
+            None => (FilterVerdict::FilteredByVolatility, HashMap::new()),
         };
 
         SignalEvaluation {
@@ -84,13 +115,54 @@ impl SignalFilter for VolatilityFilter {
             filter_state,
         }
     }
+
+    /// Hold the current stop while volatility is above the freeze threshold.
+    ///
+    /// The first stop of a position is always placed, and a target carried
+    /// alongside a frozen stop still goes through.
+    fn review_intent(
+        &self,
+        intent: &OrderIntent,
+        current_stop: Option<f64>,
+        bars: &dyn BarSeries,
+        bar_index: usize,
+        indicators: &IndicatorValues,
+    ) -> Option<IntentOverride> {
+        let threshold = self.freeze_stops_above_pct?;
+        let current_stop = current_stop?;
+        if intent.action != IntentAction::AdjustStop || intent.stop_price == Some(current_stop) {
+            return None;
+        }
+        let (atr, close, vol_pct) = self.volatility(bars, bar_index, indicators)?;
+        if vol_pct <= threshold {
+            return None;
+        }
+
+        let frozen = match intent.target_price {
+            Some(_) => OrderIntent {
+                action: IntentAction::AdjustTarget,
+                stop_price: None,
+                ..intent.clone()
+            },
+            None => OrderIntent::hold(),
+        };
+        let mut filter_state = HashMap::new();
+        filter_state.insert("atr_value".into(), atr);
+        filter_state.insert("close".into(), close);
+        filter_state.insert("volatility_pct".into(), vol_pct);
+        filter_state.insert("held_stop".into(), current_stop);
+        Some(IntentOverride {
+            intent: frozen,
+            filter_state,
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::components::signal::SignalDirection;
-    use crate::domain::SignalEventId;
+    use crate::domain::{Bar, SignalEventId};
     use chrono::NaiveDate;
 
     fn make_signal() -> SignalEvent {
@@ -111,7 +183,7 @@ mod tests {
             .iter()
             .enumerate()
             .map(|(i, &close)| Bar {
-                symbol: "SPY".to_string(),
+                symbol: "SPY".into(),
                 date: base_date + chrono::Duration::days(i as i64),
                 open: close - 0.5,
                 high: close + 2.0,
@@ -223,6 +295,74 @@ mod tests {
         assert_eq!(eval.filter_state["volatility_pct"], 2.0);
     }
 
+    fn spike_indicators() -> IndicatorValues {
+        // vol_pct 2% at bar 4, 10% at bar 5
+        let mut atr_vals = vec![f64::NAN; 10];
+        atr_vals[4] = 2.0;
+        atr_vals[5] = 10.0;
+        make_indicators("atr_14", atr_vals)
+    }
+
+    #[test]
+    fn freeze_holds_stop_above_threshold() {
+        let filter = VolatilityFilter::new(14, 0.5, 5.0).with_freeze_stops_above(4.0);
+        let bars = make_bars_with_close(&[100.0; 10]);
+        let iv = spike_indicators();
+        let tighten = OrderIntent::adjust_stop(97.0);
+
+        let review = filter
+            .review_intent(&tighten, Some(95.0), &bars, 5, &iv)
+            .expect("stop frozen on the spike bar");
+        assert_eq!(review.intent, OrderIntent::hold());
+        assert_eq!(review.filter_state["volatility_pct"], 10.0);
+        assert_eq!(review.filter_state["held_stop"], 95.0);
+
+        // Calm bar, first stop, and non-stop intents pass untouched
+        assert!(filter
+            .review_intent(&tighten, Some(95.0), &bars, 4, &iv)
+            .is_none());
+        assert!(filter
+            .review_intent(&tighten, None, &bars, 5, &iv)
+            .is_none());
+        let exit = OrderIntent::force_exit();
+        assert!(filter
+            .review_intent(&exit, Some(95.0), &bars, 5, &iv)
+            .is_none());
+    }
+
+    #[test]
+    fn freeze_keeps_bracket_target() {
+        let filter = VolatilityFilter::new(14, 0.5, 5.0).with_freeze_stops_above(4.0);
+        let bars = make_bars_with_close(&[100.0; 10]);
+        let bracket = OrderIntent {
+            target_price: Some(110.0),
+            ..OrderIntent::adjust_stop(97.0)
+        };
+        let review = filter
+            .review_intent(&bracket, Some(95.0), &bars, 5, &spike_indicators())
+            .unwrap();
+        assert_eq!(review.intent.action, IntentAction::AdjustTarget);
+        assert_eq!(review.intent.stop_price, None);
+        assert_eq!(review.intent.target_price, Some(110.0));
+    }
+
+    #[test]
+    fn freeze_disabled_by_default() {
+        let mut params = BTreeMap::new();
+        let filter = VolatilityFilter::default_params().with_params(&params);
+        assert_eq!(filter.freeze_stops_above_pct, None);
+        let bars = make_bars_with_close(&[100.0; 10]);
+        let tighten = OrderIntent::adjust_stop(97.0);
+        let iv = spike_indicators();
+        assert!(filter
+            .review_intent(&tighten, Some(95.0), &bars, 5, &iv)
+            .is_none());
+
+        params.insert(VolatilityFilter::PARAM_FREEZE_STOPS.into(), 4.0);
+        let filter = VolatilityFilter::default_params().with_params(&params);
+        assert_eq!(filter.freeze_stops_above_pct, Some(4.0));
+    }
+
     #[test]
     fn name_is_correct() {
         assert_eq!(
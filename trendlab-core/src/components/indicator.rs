@@ -27,6 +27,22 @@ pub trait Indicator: Send + Sync {
     /// Returns a `Vec<f64>` of the same length as `bars`.
     /// The first `lookback()` values should be `f64::NAN`.
     fn compute(&self, bars: &[Bar]) -> Vec<f64>;
+
+    /// Names of the series this indicator produces, in `compute_all` order.
+    ///
+    /// Single-series indicators produce one series stored under `name()`.
+    /// Multi-output indicators (e.g. a full Donchian channel) list one key per
+    /// band, using the same keys as their per-band instances.
+    fn outputs(&self) -> Vec<&str> {
+        vec![self.name()]
+    }
+
+    /// Compute every output series in one pass, parallel to `outputs()`.
+    ///
+    /// Each series follows the same length and warmup rules as `compute`.
+    fn compute_all(&self, bars: &[Bar]) -> Vec<Vec<f64>> {
+        vec![self.compute(bars)]
+    }
 }
 
 /// Container for precomputed indicator values.
@@ -59,6 +75,11 @@ impl IndicatorValues {
         self.series.get(name).map(|v| v.as_slice())
     }
 
+    /// Names of the stored series, in no particular order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.series.keys().map(|k| k.as_str())
+    }
+
     /// Number of indicator series stored.
     pub fn len(&self) -> usize {
         self.series.len()
@@ -9,18 +9,22 @@
 //! Plus the indicator trait for precomputed numeric series.
 
 pub mod composition;
+pub mod custom;
 pub mod execution;
 pub mod factory;
 pub mod filter;
 pub mod indicator;
 pub mod pm;
+pub mod registry;
 pub mod sampler;
 pub mod signal;
 
 pub use composition::{
-    build_composition, check_compatibility, CompatibilityResult, StrategyComposition,
-    StrategyPreset,
+    build_composition, check_compatibility, CompatibilityContext, CompatibilityResult,
+    CompatibilityRule, ComponentMatch, RuleCheck, RuleSeverity, StrategyComposition,
+    StrategyPreset, COMPATIBILITY_RULES,
 };
+pub use custom::{ComponentRegistry, CustomComponent, CustomFilter, CustomPm, CustomSignal};
 pub use execution::{
     CloseOnSignalModel, ExecutionModel, ExecutionPreset, GapPolicy, LimitEntryModel,
     NextBarOpenModel, PathPolicy, StopEntryModel,
@@ -31,9 +35,14 @@ pub use factory::{
 pub use filter::SignalFilter;
 pub use indicator::{Indicator, IndicatorValues};
 pub use pm::{
-    AtrTrailing, BreakevenThenTrail, Chandelier, FixedStopLoss, FrozenReference, IntentAction,
-    MaxHoldingPeriod, NoOpPm, OrderIntent, PercentTrailing, PositionManager, SinceEntryTrailing,
-    TimeDecay,
+    AtrTrailing, BreakevenThenTrail, Chandelier, CompositePm, DonchianExit, FixedStopLoss,
+    FrozenReference, HoldingClock, IntentAction, MaxHoldingPeriod, NoOpPm, OrderIntent,
+    PercentTrailing, PositionManager, SinceEntryTrailing, TimeDecay,
+};
+pub use registry::{ComponentKind, ComponentSpec, ParamSpec, PrimaryParam};
+pub use sampler::{
+    default_composite_children, mutate_composition, sample_compatible_composition,
+    sample_composition, CompatibleSample, ComponentPool, ComponentSlot, ComponentVariant, GridAxis,
+    ParamGrid, ParamRange, MAX_RESAMPLES,
 };
-pub use sampler::{sample_composition, ComponentPool, ComponentVariant, ParamRange};
 pub use signal::{FilterVerdict, SignalDirection, SignalEvaluation, SignalEvent, SignalGenerator};
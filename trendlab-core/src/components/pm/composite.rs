@@ -0,0 +1,291 @@
+//! Composite — chains several position managers and merges their intents.
+//!
+//! Each child is evaluated on every bar, in order. Intents are merged as:
+//! - Any `ForceExit` wins outright.
+//! - Otherwise the tightest `AdjustStop` is chosen (highest for longs, lowest
+//!   for shorts). Candidates that would loosen the current stop are discarded,
+//!   so the merged stop never violates the ratchet invariant.
+//! - Conflicting targets resolve to the one nearest the bar close.
+
+use crate::components::indicator::IndicatorValues;
+use crate::domain::{Bar, MarketStatus, Position, PositionSide};
+
+use super::{IntentAction, OrderIntent, PositionManager};
+
+/// Composite position manager — tightest-stop-wins over an ordered list of children.
+pub struct CompositePm {
+    children: Vec<Box<dyn PositionManager>>,
+}
+
+impl CompositePm {
+    pub fn new(children: Vec<Box<dyn PositionManager>>) -> Self {
+        assert!(
+            !children.is_empty(),
+            "composite PM needs at least one child"
+        );
+        Self { children }
+    }
+
+    /// Names of the wrapped children, in evaluation order.
+    pub fn child_names(&self) -> Vec<&str> {
+        self.children.iter().map(|c| c.name()).collect()
+    }
+}
+
+impl PositionManager for CompositePm {
+    fn name(&self) -> &str {
+        "composite"
+    }
+
+    fn on_bar(
+        &self,
+        position: &Position,
+        bar: &Bar,
+        bar_index: usize,
+        market_status: MarketStatus,
+        indicators: &IndicatorValues,
+    ) -> OrderIntent {
+        let intents: Vec<OrderIntent> = self
+            .children
+            .iter()
+            .map(|c| c.on_bar(position, bar, bar_index, market_status, indicators))
+            .collect();
+        merge_intents(&intents, position, bar.close)
+    }
+
+    fn time_expired(&self, position: &Position) -> bool {
+        self.children.iter().any(|c| c.time_expired(position))
+    }
+}
+
+/// Merge child intents into a single intent for `position`.
+fn merge_intents(intents: &[OrderIntent], position: &Position, close: f64) -> OrderIntent {
+    if intents.iter().any(|i| i.action == IntentAction::ForceExit) {
+        return OrderIntent::force_exit();
+    }
+
+    let tighter = |a: f64, b: f64| match position.side {
+        PositionSide::Long => a > b,
+        PositionSide::Short => a < b,
+        PositionSide::Flat => false,
+    };
+
+    let mut stop: Option<f64> = None;
+    for price in intents
+        .iter()
+        .filter(|i| i.action == IntentAction::AdjustStop)
+        .filter_map(|i| i.stop_price)
+        .filter(|p| p.is_finite())
+    {
+        // Drop candidates that would loosen the existing stop
+        if let Some(cur) = position.current_stop {
+            if tighter(cur, price) {
+                continue;
+            }
+        }
+        if stop.map_or(true, |s| tighter(price, s)) {
+            stop = Some(price);
+        }
+    }
+
+    let target = intents
+        .iter()
+        .filter(|i| i.action == IntentAction::AdjustTarget)
+        .filter_map(|i| i.target_price)
+        .filter(|p| p.is_finite())
+        .min_by(|a, b| (a - close).abs().total_cmp(&(b - close).abs()));
+
+    if position.side == PositionSide::Flat {
+        return OrderIntent::hold();
+    }
+
+    match (stop, target) {
+        (Some(s), t) => OrderIntent {
+            target_price: t,
+            ..OrderIntent::adjust_stop(s)
+        },
+        (None, Some(t)) => OrderIntent {
+            action: IntentAction::AdjustTarget,
+            stop_price: None,
+            target_price: Some(t),
+        },
+        (None, None) => OrderIntent::hold(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::pm::{FixedStopLoss, MaxHoldingPeriod, PercentTrailing};
+    use chrono::NaiveDate;
+
+    fn make_bar(close: f64) -> Bar {
+        Bar {
+            symbol: "SPY".into(),
+            date: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+            open: close - 0.5,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+            volume: 1000,
+            adj_close: close,
+        }
+    }
+
+    /// Test child that always emits the same intent.
+    struct Fixed(OrderIntent);
+
+    impl PositionManager for Fixed {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        fn on_bar(
+            &self,
+            _position: &Position,
+            _bar: &Bar,
+            _bar_index: usize,
+            _market_status: MarketStatus,
+            _indicators: &IndicatorValues,
+        ) -> OrderIntent {
+            self.0.clone()
+        }
+    }
+
+    fn fixed(intent: OrderIntent) -> Box<dyn PositionManager> {
+        Box::new(Fixed(intent))
+    }
+
+    fn target(price: f64) -> OrderIntent {
+        OrderIntent {
+            action: IntentAction::AdjustTarget,
+            stop_price: None,
+            target_price: Some(price),
+        }
+    }
+
+    fn eval(pm: &CompositePm, pos: &Position, close: f64) -> OrderIntent {
+        let iv = IndicatorValues::new();
+        pm.on_bar(pos, &make_bar(close), 5, MarketStatus::Open, &iv)
+    }
+
+    #[test]
+    fn long_picks_highest_stop() {
+        let pm = CompositePm::new(vec![
+            fixed(OrderIntent::adjust_stop(95.0)),
+            fixed(OrderIntent::adjust_stop(98.0)),
+            fixed(OrderIntent::adjust_stop(96.0)),
+        ]);
+        let pos = Position::new_long("SPY".into(), 100.0, 100.0, 0);
+        let intent = eval(&pm, &pos, 102.0);
+        assert_eq!(intent.action, IntentAction::AdjustStop);
+        assert_eq!(intent.stop_price, Some(98.0));
+    }
+
+    #[test]
+    fn short_picks_lowest_stop() {
+        let pm = CompositePm::new(vec![
+            fixed(OrderIntent::adjust_stop(105.0)),
+            fixed(OrderIntent::adjust_stop(102.0)),
+        ]);
+        let pos = Position::new_short("SPY".into(), 100.0, 100.0, 0);
+        let intent = eval(&pm, &pos, 99.0);
+        assert_eq!(intent.stop_price, Some(102.0));
+    }
+
+    #[test]
+    fn merged_stop_never_loosens_when_children_disagree() {
+        // Long: one child tightens, one loosens, one holds. Over many bars with
+        // the stop ratcheting, the merged stop must be monotonically non-decreasing.
+        let mut pos = Position::new_long("SPY".into(), 100.0, 100.0, 0);
+        pos.current_stop = Some(97.0);
+        let pm = CompositePm::new(vec![
+            fixed(OrderIntent::adjust_stop(90.0)),
+            fixed(OrderIntent::hold()),
+            Box::new(PercentTrailing::new(0.05)),
+        ]);
+        let closes = [100.0, 104.0, 101.0, 99.0, 108.0, 103.0, 96.0];
+        for &close in &closes {
+            pos.highest_price_since_entry = pos.highest_price_since_entry.max(close + 1.0);
+            let intent = eval(&pm, &pos, close);
+            if let Some(stop) = intent.stop_price {
+                assert!(
+                    stop >= pos.current_stop.unwrap(),
+                    "stop loosened from {:?} to {stop}",
+                    pos.current_stop
+                );
+                pos.current_stop = Some(stop);
+            }
+        }
+        assert!(pos.current_stop.unwrap() > 97.0);
+
+        // Short: every child wants a looser stop → hold, never loosen
+        let mut pos = Position::new_short("SPY".into(), 100.0, 100.0, 0);
+        pos.current_stop = Some(103.0);
+        let pm = CompositePm::new(vec![
+            fixed(OrderIntent::adjust_stop(110.0)),
+            fixed(OrderIntent::adjust_stop(104.0)),
+        ]);
+        let intent = eval(&pm, &pos, 99.0);
+        assert_eq!(intent.action, IntentAction::Hold);
+        assert!(intent.stop_price.is_none());
+    }
+
+    #[test]
+    fn non_finite_stops_are_ignored() {
+        let pm = CompositePm::new(vec![
+            fixed(OrderIntent::adjust_stop(f64::INFINITY)),
+            fixed(OrderIntent::adjust_stop(f64::NAN)),
+            fixed(OrderIntent::adjust_stop(94.0)),
+        ]);
+        let pos = Position::new_long("SPY".into(), 100.0, 100.0, 0);
+        assert_eq!(eval(&pm, &pos, 100.0).stop_price, Some(94.0));
+    }
+
+    #[test]
+    fn force_exit_wins() {
+        let pm = CompositePm::new(vec![
+            fixed(OrderIntent::adjust_stop(99.0)),
+            Box::new(MaxHoldingPeriod::new(3)),
+        ]);
+        let mut pos = Position::new_long("SPY".into(), 100.0, 100.0, 0);
+        pos.bars_held_open = 3;
+        assert_eq!(eval(&pm, &pos, 101.0).action, IntentAction::ForceExit);
+    }
+
+    #[test]
+    fn nearest_target_wins() {
+        let pm = CompositePm::new(vec![fixed(target(120.0)), fixed(target(108.0))]);
+        let pos = Position::new_long("SPY".into(), 100.0, 100.0, 0);
+        let intent = eval(&pm, &pos, 105.0);
+        assert_eq!(intent.action, IntentAction::AdjustTarget);
+        assert_eq!(intent.target_price, Some(108.0));
+    }
+
+    #[test]
+    fn stop_and_target_are_both_carried() {
+        let pm = CompositePm::new(vec![
+            Box::new(FixedStopLoss::new(0.05)),
+            fixed(target(115.0)),
+        ]);
+        let pos = Position::new_long("SPY".into(), 100.0, 100.0, 0);
+        let intent = eval(&pm, &pos, 101.0);
+        assert_eq!(intent.action, IntentAction::AdjustStop);
+        assert_eq!(intent.stop_price, Some(95.0));
+        assert_eq!(intent.target_price, Some(115.0));
+    }
+
+    #[test]
+    fn all_hold_yields_hold() {
+        let pm = CompositePm::new(vec![fixed(OrderIntent::hold()), fixed(OrderIntent::hold())]);
+        let pos = Position::new_long("SPY".into(), 100.0, 100.0, 0);
+        assert_eq!(eval(&pm, &pos, 100.0).action, IntentAction::Hold);
+        assert_eq!(pm.child_names(), vec!["fixed", "fixed"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one child")]
+    fn rejects_empty_children() {
+        CompositePm::new(Vec::new());
+    }
+}
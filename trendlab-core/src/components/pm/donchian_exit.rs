@@ -0,0 +1,139 @@
+//! Donchian exit — stop at the opposite Donchian channel band.
+//!
+//! For longs: stop = lowest low of the last `exit_lookback` bars.
+//! For shorts: stop = highest high of the last `exit_lookback` bars.
+//!
+//! The exit half of the classic turtle system: paired with a
+//! `donchian_breakout` entry on a longer lookback (20/10, 55/20), the entry
+//! channel and the exit channel are set independently. The exit lives in the
+//! PM rather than on the signal, so the signal still only decides entries and
+//! the exit composes with any signal (or inside a composite PM).
+//!
+//! The channel at bar t covers bars t-N+1..=t, and the stop applies to bar
+//! t+1, so the position exits on the first bar trading below the prior N-bar
+//! low. The channel only drops when a bar trades through it, which triggers
+//! the stop, so the stop never loosens while the position is open.
+
+use crate::components::indicator::IndicatorValues;
+use crate::domain::{Bar, MarketStatus, Position, PositionSide};
+
+use super::{OrderIntent, PositionManager};
+
+/// Donchian channel exit position manager.
+#[derive(Debug, Clone)]
+pub struct DonchianExit {
+    /// Channel lookback for the exit (bars).
+    pub exit_lookback: usize,
+    lower_key: String,
+    upper_key: String,
+}
+
+impl DonchianExit {
+    pub fn new(exit_lookback: usize) -> Self {
+        assert!(exit_lookback >= 1, "exit_lookback must be >= 1");
+        Self {
+            exit_lookback,
+            lower_key: format!("donchian_lower_{exit_lookback}"),
+            upper_key: format!("donchian_upper_{exit_lookback}"),
+        }
+    }
+}
+
+impl PositionManager for DonchianExit {
+    fn name(&self) -> &str {
+        "donchian_exit"
+    }
+
+    fn on_bar(
+        &self,
+        position: &Position,
+        _bar: &Bar,
+        bar_index: usize,
+        _market_status: MarketStatus,
+        indicators: &IndicatorValues,
+    ) -> OrderIntent {
+        let key = match position.side {
+            PositionSide::Long => &self.lower_key,
+            PositionSide::Short => &self.upper_key,
+            PositionSide::Flat => return OrderIntent::hold(),
+        };
+        match indicators.get(key, bar_index) {
+            Some(stop) if !stop.is_nan() => OrderIntent::adjust_stop(stop),
+            _ => OrderIntent::hold(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::pm::IntentAction;
+    use chrono::NaiveDate;
+
+    fn make_bar(close: f64) -> Bar {
+        Bar {
+            symbol: "SPY".into(),
+            date: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+            open: close - 0.5,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+            volume: 1000,
+            adj_close: close,
+        }
+    }
+
+    fn make_indicators(
+        lookback: usize,
+        bar_index: usize,
+        lower: f64,
+        upper: f64,
+    ) -> IndicatorValues {
+        let mut iv = IndicatorValues::new();
+        let mut lower_series = vec![f64::NAN; bar_index + 1];
+        let mut upper_series = vec![f64::NAN; bar_index + 1];
+        lower_series[bar_index] = lower;
+        upper_series[bar_index] = upper;
+        iv.insert(format!("donchian_lower_{lookback}"), lower_series);
+        iv.insert(format!("donchian_upper_{lookback}"), upper_series);
+        iv
+    }
+
+    #[test]
+    fn long_stop_at_channel_low() {
+        let pm = DonchianExit::new(10);
+        let pos = Position::new_long("SPY".into(), 100.0, 100.0, 0);
+        let iv = make_indicators(10, 5, 95.0, 112.0);
+        let intent = pm.on_bar(&pos, &make_bar(110.0), 5, MarketStatus::Open, &iv);
+        assert_eq!(intent.stop_price, Some(95.0));
+    }
+
+    #[test]
+    fn short_stop_at_channel_high() {
+        let pm = DonchianExit::new(10);
+        let pos = Position::new_short("SPY".into(), 100.0, 100.0, 0);
+        let iv = make_indicators(10, 5, 85.0, 104.0);
+        let intent = pm.on_bar(&pos, &make_bar(90.0), 5, MarketStatus::Open, &iv);
+        assert_eq!(intent.stop_price, Some(104.0));
+    }
+
+    #[test]
+    fn holds_during_channel_warmup() {
+        let pm = DonchianExit::new(10);
+        let pos = Position::new_long("SPY".into(), 100.0, 100.0, 0);
+        let iv = make_indicators(10, 5, 95.0, 112.0);
+        // Bar 3 is NaN in both series
+        let intent = pm.on_bar(&pos, &make_bar(100.0), 3, MarketStatus::Open, &iv);
+        assert_eq!(intent.action, IntentAction::Hold);
+        // A different lookback's channel is not read
+        let intent =
+            DonchianExit::new(20).on_bar(&pos, &make_bar(100.0), 5, MarketStatus::Open, &iv);
+        assert_eq!(intent.action, IntentAction::Hold);
+    }
+
+    #[test]
+    #[should_panic(expected = "exit_lookback must be >= 1")]
+    fn rejects_zero_lookback() {
+        DonchianExit::new(0);
+    }
+}
@@ -59,7 +59,7 @@ mod tests {
 
     fn make_bar(close: f64) -> Bar {
         Bar {
-            symbol: "SPY".to_string(),
+            symbol: "SPY".into(),
             date: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
             open: close - 0.5,
             high: close + 1.0,
@@ -115,9 +115,11 @@ mod tests {
             highest_price_since_entry: 100.0,
             lowest_price_since_entry: 100.0,
             bars_held: 0,
+            bars_held_open: 0,
             unrealized_pnl: 0.0,
             realized_pnl: 0.0,
             current_stop: None,
+            initial_stop: None,
         };
         let bar = make_bar(100.0);
         let iv = IndicatorValues::new();
@@ -58,7 +58,7 @@ mod tests {
 
     fn make_bar(close: f64) -> Bar {
         Bar {
-            symbol: "SPY".to_string(),
+            symbol: "SPY".into(),
             date: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
             open: close - 0.5,
             high: close + 1.0,
@@ -1,24 +1,35 @@
 //! Max holding period — force exit after N bars regardless of price.
 //!
-//! A pure time-based exit. On void bars, `bars_held` still increments
-//! (via `Position::tick_bar()`), so void bars count toward the limit.
+//! A pure time-based exit. By default only open bars count toward the
+//! limit; with [`HoldingClock::AllBars`] void bars count too, and a limit
+//! reached during a void stretch exits at the next valid bar's open.
 
 use crate::components::indicator::IndicatorValues;
 use crate::domain::{Bar, MarketStatus, Position};
 
-use super::{OrderIntent, PositionManager};
+use super::{HoldingClock, OrderIntent, PositionManager};
 
 /// Max holding period position manager.
 #[derive(Debug, Clone)]
 pub struct MaxHoldingPeriod {
     /// Maximum number of bars to hold before force exit.
     pub max_bars: usize,
+    /// Which bars count toward `max_bars`.
+    pub clock: HoldingClock,
 }
 
 impl MaxHoldingPeriod {
     pub fn new(max_bars: usize) -> Self {
         assert!(max_bars > 0, "max_bars must be > 0");
-        Self { max_bars }
+        Self {
+            max_bars,
+            clock: HoldingClock::default(),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: HoldingClock) -> Self {
+        self.clock = clock;
+        self
     }
 }
 
@@ -35,12 +46,16 @@ impl PositionManager for MaxHoldingPeriod {
         _market_status: MarketStatus,
         _indicators: &IndicatorValues,
     ) -> OrderIntent {
-        if position.bars_held >= self.max_bars {
+        if self.time_expired(position) {
             OrderIntent::force_exit()
         } else {
             OrderIntent::hold()
         }
     }
+
+    fn time_expired(&self, position: &Position) -> bool {
+        self.clock.bars_held(position) >= self.max_bars
+    }
 }
 
 #[cfg(test)]
@@ -50,7 +65,7 @@ mod tests {
 
     fn make_bar(close: f64) -> Bar {
         Bar {
-            symbol: "SPY".to_string(),
+            symbol: "SPY".into(),
             date: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
             open: close - 0.5,
             high: close + 1.0,
@@ -65,7 +80,7 @@ mod tests {
     fn holds_before_max() {
         let pm = MaxHoldingPeriod::new(10);
         let mut pos = Position::new_long("SPY".into(), 100.0, 100.0, 0);
-        pos.bars_held = 9;
+        pos.bars_held_open = 9;
         let bar = make_bar(110.0);
         let iv = IndicatorValues::new();
         let intent = pm.on_bar(&pos, &bar, 9, MarketStatus::Open, &iv);
@@ -76,7 +91,7 @@ mod tests {
     fn exits_at_max() {
         let pm = MaxHoldingPeriod::new(10);
         let mut pos = Position::new_long("SPY".into(), 100.0, 100.0, 0);
-        pos.bars_held = 10;
+        pos.bars_held_open = 10;
         let bar = make_bar(110.0);
         let iv = IndicatorValues::new();
         let intent = pm.on_bar(&pos, &bar, 10, MarketStatus::Open, &iv);
@@ -87,7 +102,7 @@ mod tests {
     fn exits_past_max() {
         let pm = MaxHoldingPeriod::new(5);
         let mut pos = Position::new_long("SPY".into(), 100.0, 100.0, 0);
-        pos.bars_held = 20;
+        pos.bars_held_open = 20;
         let bar = make_bar(110.0);
         let iv = IndicatorValues::new();
         let intent = pm.on_bar(&pos, &bar, 20, MarketStatus::Open, &iv);
@@ -98,10 +113,27 @@ mod tests {
     fn works_for_shorts() {
         let pm = MaxHoldingPeriod::new(3);
         let mut pos = Position::new_short("SPY".into(), 100.0, 100.0, 0);
-        pos.bars_held = 3;
+        pos.bars_held_open = 3;
         let bar = make_bar(90.0);
         let iv = IndicatorValues::new();
         let intent = pm.on_bar(&pos, &bar, 3, MarketStatus::Open, &iv);
         assert_eq!(intent.action, super::super::IntentAction::ForceExit);
     }
+
+    #[test]
+    fn clock_selects_counter() {
+        let mut pos = Position::new_long("SPY".into(), 100.0, 100.0, 0);
+        pos.bars_held = 10;
+        pos.bars_held_open = 4;
+        let bar = make_bar(110.0);
+        let iv = IndicatorValues::new();
+
+        let open_bars = MaxHoldingPeriod::new(5);
+        let intent = open_bars.on_bar(&pos, &bar, 10, MarketStatus::Open, &iv);
+        assert_eq!(intent.action, super::super::IntentAction::Hold);
+
+        let all_bars = MaxHoldingPeriod::new(5).with_clock(HoldingClock::AllBars);
+        let intent = all_bars.on_bar(&pos, &bar, 10, MarketStatus::Open, &iv);
+        assert_eq!(intent.action, super::super::IntentAction::ForceExit);
+    }
 }
@@ -8,6 +8,7 @@
 //!
 //! - [`AtrTrailing`] — ATR-based trailing stop
 //! - [`Chandelier`] — chandelier exit (ATR from highest high since entry)
+//! - [`DonchianExit`] — stop at the opposite Donchian channel band
 //! - [`PercentTrailing`] — fixed percentage trailing stop
 //! - [`SinceEntryTrailing`] — drawdown-from-peak condition exit
 //! - [`FrozenReference`] — stop frozen at entry price, never moves
@@ -15,10 +16,16 @@
 //! - [`MaxHoldingPeriod`] — force exit after N bars
 //! - [`FixedStopLoss`] — simple fixed stop below entry
 //! - [`BreakevenThenTrail`] — move to breakeven, then trail
+//! - [`CompositePm`] — chains several PMs, tightest stop wins
+//!
+//! Time-based PMs count holding time on a [`HoldingClock`]: open bars by
+//! default, or every bar with `count_void_bars = 1`.
 
 pub mod atr_trailing;
 pub mod breakeven_then_trail;
 pub mod chandelier;
+pub mod composite;
+pub mod donchian_exit;
 pub mod fixed_stop_loss;
 pub mod frozen_reference;
 pub mod max_holding_period;
@@ -29,6 +36,8 @@ pub mod time_decay;
 pub use atr_trailing::AtrTrailing;
 pub use breakeven_then_trail::BreakevenThenTrail;
 pub use chandelier::Chandelier;
+pub use composite::CompositePm;
+pub use donchian_exit::DonchianExit;
 pub use fixed_stop_loss::FixedStopLoss;
 pub use frozen_reference::FrozenReference;
 pub use max_holding_period::MaxHoldingPeriod;
@@ -36,11 +45,49 @@ pub use percent_trailing::PercentTrailing;
 pub use since_entry_trailing::SinceEntryTrailing;
 pub use time_decay::TimeDecay;
 
+use std::collections::BTreeMap;
+
 use crate::domain::{Bar, MarketStatus, Position};
 
 use super::indicator::IndicatorValues;
 use serde::{Deserialize, Serialize};
 
+/// Which bars a time-based PM counts toward the holding period.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HoldingClock {
+    /// Only bars where the symbol traded (default), so a void stretch on a
+    /// thinly traded symbol doesn't age the position.
+    #[default]
+    OpenBars,
+    /// Every aligned bar, void bars included.
+    AllBars,
+}
+
+impl HoldingClock {
+    /// Param key counting void bars toward the holding period (>= 0.5 = on).
+    pub const PARAM_COUNT_VOID_BARS: &'static str = "count_void_bars";
+
+    /// Decode from PM params. A missing key counts open bars only.
+    pub fn from_params(params: &BTreeMap<String, f64>) -> Self {
+        if params
+            .get(Self::PARAM_COUNT_VOID_BARS)
+            .is_some_and(|&v| v >= 0.5)
+        {
+            Self::AllBars
+        } else {
+            Self::OpenBars
+        }
+    }
+
+    /// Bars `position` has been held on this clock.
+    pub fn bars_held(self, position: &Position) -> usize {
+        match self {
+            Self::OpenBars => position.bars_held_open,
+            Self::AllBars => position.bars_held,
+        }
+    }
+}
+
 /// What action the position manager wants to take.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum IntentAction {
@@ -48,7 +95,7 @@ pub enum IntentAction {
     Hold,
     /// Adjust the stop price (tighten only — ratchet invariant).
     AdjustStop,
-    /// Adjust the take-profit target.
+    /// Adjust the take-profit target, OCO-linked with the stop.
     AdjustTarget,
     /// Force exit on the next bar (max holding period, time decay converged, etc.).
     ForceExit,
@@ -58,12 +105,13 @@ pub enum IntentAction {
 ///
 /// Translated into cancel/replace orders on the order book. The cancel/replace
 /// is atomic: no "stopless window" between cancellation and new order placement.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OrderIntent {
     pub action: IntentAction,
     /// New stop price (only meaningful when action is AdjustStop).
     pub stop_price: Option<f64>,
-    /// New target price (only meaningful when action is AdjustTarget).
+    /// New target price. Honored with AdjustTarget, and alongside the stop
+    /// with AdjustStop (so one intent can place a full exit bracket).
     pub target_price: Option<f64>,
 }
 
@@ -99,8 +147,11 @@ impl OrderIntent {
 /// - PMs operate after post-bar mark-to-market, emitting intents for the NEXT bar.
 /// - PMs must obey the ratchet invariant: stops may tighten but never loosen.
 /// - On void bars (`MarketStatus::Closed`), the engine does NOT call `on_bar`.
-///   Time-based counters (bars_held) are already incremented by `Position::tick_bar()`.
-/// - Time-based exits that expire during void bars emit on the next valid bar.
+///   `Position::tick_bar()` still advances `bars_held`, but not `bars_held_open`;
+///   time-based PMs pick one through their [`HoldingClock`].
+/// - Time-based exits that expire during void bars exit at the next valid
+///   bar's open: the engine asks [`PositionManager::time_expired`] on void
+///   bars and submits the `MarketOnOpen` exit itself.
 pub trait PositionManager: Send + Sync {
     /// Human-readable name (e.g., "atr_trailing", "chandelier_exit").
     fn name(&self) -> &str;
@@ -114,6 +165,14 @@ pub trait PositionManager: Send + Sync {
         market_status: MarketStatus,
         indicators: &IndicatorValues,
     ) -> OrderIntent;
+
+    /// Whether `position` has reached this PM's time limit, past which
+    /// `on_bar` force-exits whatever the price. Reads only the position's
+    /// holding counts, so the engine may ask on void bars. PMs without a
+    /// time limit keep the default.
+    fn time_expired(&self, _position: &Position) -> bool {
+        false
+    }
 }
 
 /// No-op position manager — always holds. Used as default in tests
@@ -155,6 +214,14 @@ mod tests {
         assert_eq!(intent.stop_price, Some(95.0));
     }
 
+    #[test]
+    fn holding_clock_from_params() {
+        let mut params = BTreeMap::new();
+        assert_eq!(HoldingClock::from_params(&params), HoldingClock::OpenBars);
+        params.insert(HoldingClock::PARAM_COUNT_VOID_BARS.into(), 1.0);
+        assert_eq!(HoldingClock::from_params(&params), HoldingClock::AllBars);
+    }
+
     #[test]
     fn force_exit_intent() {
         let intent = OrderIntent::force_exit();
@@ -176,7 +243,7 @@ mod tests {
         let pm = NoOpPm;
         let pos = Position::new_long("SPY".into(), 100.0, 100.0, 0);
         let bar = Bar {
-            symbol: "SPY".to_string(),
+            symbol: "SPY".into(),
             date: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
             open: 100.0,
             high: 105.0,
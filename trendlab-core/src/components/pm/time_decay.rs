@@ -1,15 +1,19 @@
 //! Time decay stop — the stop tightens over time.
 //!
 //! The effective stop distance starts at `initial_pct` and decays by
-//! `decay_per_bar` each bar, with a floor at `min_pct`.
+//! `decay_per_bar` each bar held (open bars by default, see
+//! [`HoldingClock`]), with a floor at `min_pct`.
 //!
 //! For longs: raw_stop = close * (1 - effective_pct).
 //! The ratchet in the engine ensures the absolute stop level never drops.
+//!
+//! There is no time limit: decay accrued over void bars only tightens the
+//! stop placed at the next valid bar's close.
 
 use crate::components::indicator::IndicatorValues;
 use crate::domain::{Bar, MarketStatus, Position, PositionSide};
 
-use super::{OrderIntent, PositionManager};
+use super::{HoldingClock, OrderIntent, PositionManager};
 
 /// Time decay stop position manager.
 #[derive(Debug, Clone)]
@@ -20,6 +24,8 @@ pub struct TimeDecay {
     pub decay_per_bar: f64,
     /// Minimum stop distance (floor). The stop never gets closer than this.
     pub min_pct: f64,
+    /// Which bars advance the decay.
+    pub clock: HoldingClock,
 }
 
 impl TimeDecay {
@@ -33,9 +39,15 @@ impl TimeDecay {
             initial_pct,
             decay_per_bar,
             min_pct,
+            clock: HoldingClock::default(),
         }
     }
 
+    pub fn with_clock(mut self, clock: HoldingClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Compute the effective percentage at the current bars_held.
     fn effective_pct(&self, bars_held: usize) -> f64 {
         let raw = self.initial_pct - (bars_held as f64 * self.decay_per_bar);
@@ -56,7 +68,7 @@ impl PositionManager for TimeDecay {
         _market_status: MarketStatus,
         _indicators: &IndicatorValues,
     ) -> OrderIntent {
-        let pct = self.effective_pct(position.bars_held);
+        let pct = self.effective_pct(self.clock.bars_held(position));
 
         let stop = match position.side {
             PositionSide::Long => bar.close * (1.0 - pct),
@@ -75,7 +87,7 @@ mod tests {
 
     fn make_bar(close: f64) -> Bar {
         Bar {
-            symbol: "SPY".to_string(),
+            symbol: "SPY".into(),
             date: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
             open: close - 0.5,
             high: close + 1.0,
@@ -113,17 +125,17 @@ mod tests {
 
         // Bar 0: stop = 100 * (1 - 0.10) = 90
         let mut pos = Position::new_long("SPY".into(), 100.0, 100.0, 0);
-        pos.bars_held = 0;
+        pos.bars_held_open = 0;
         let intent = pm.on_bar(&pos, &bar, 0, MarketStatus::Open, &iv);
         assert_eq!(intent.stop_price, Some(90.0));
 
         // Bar 4: stop = 100 * (1 - 0.06) = 94 (tighter)
-        pos.bars_held = 4;
+        pos.bars_held_open = 4;
         let intent = pm.on_bar(&pos, &bar, 4, MarketStatus::Open, &iv);
         assert_eq!(intent.stop_price, Some(94.0));
 
         // Bar 8: stop = 100 * (1 - 0.02) = 98 (at floor)
-        pos.bars_held = 8;
+        pos.bars_held_open = 8;
         let intent = pm.on_bar(&pos, &bar, 8, MarketStatus::Open, &iv);
         assert_eq!(intent.stop_price, Some(98.0));
     }
@@ -136,13 +148,13 @@ mod tests {
 
         // Bar 0: stop = 100 * (1 + 0.10) = 110
         let mut pos = Position::new_short("SPY".into(), 100.0, 100.0, 0);
-        pos.bars_held = 0;
+        pos.bars_held_open = 0;
         let intent = pm.on_bar(&pos, &bar, 0, MarketStatus::Open, &iv);
         let stop = intent.stop_price.unwrap();
         assert!((stop - 110.0).abs() < 1e-10, "expected ~110, got {stop}");
 
         // Bar 4: stop = 100 * (1 + 0.06) = 106 (tighter for short)
-        pos.bars_held = 4;
+        pos.bars_held_open = 4;
         let intent = pm.on_bar(&pos, &bar, 4, MarketStatus::Open, &iv);
         let stop = intent.stop_price.unwrap();
         assert!((stop - 106.0).abs() < 1e-10, "expected ~106, got {stop}");
@@ -154,12 +166,12 @@ mod tests {
         let iv = IndicatorValues::new();
         // At bar 80, effective_pct = 0.02, so stop = 100 * 0.98 = 98
         let mut pos = Position::new_long("SPY".into(), 100.0, 100.0, 0);
-        pos.bars_held = 80;
+        pos.bars_held_open = 80;
         let intent = pm.on_bar(&pos, &make_bar(100.0), 80, MarketStatus::Open, &iv);
         assert_eq!(intent.stop_price, Some(98.0));
 
         // At bar 200, still 98 (floor)
-        pos.bars_held = 200;
+        pos.bars_held_open = 200;
         let intent = pm.on_bar(&pos, &make_bar(100.0), 200, MarketStatus::Open, &iv);
         assert_eq!(intent.stop_price, Some(98.0));
     }
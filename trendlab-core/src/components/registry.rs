@@ -0,0 +1,633 @@
+//! Component registry — the single list of component types and their params.
+//!
+//! Both the factory (to validate configs and explain what would have been
+//! valid) and `ComponentPool::default_pool` (to sample from) are built from
+//! these tables, so the two cannot drift apart. Each component lists the
+//! params the sampler explores; shared params (entry governance and warmup on
+//! signals, commission and fees on execution models) and a few component-specific
+//! switches are accepted but never sampled.
+//!
+//! Custom types registered at runtime (see `custom`) follow the built-in ones
+//! in `all_specs`, and accept the same shared params.
+
+use std::fmt;
+
+use crate::domain::instrument::{PARAM_CONTRACT_MULTIPLIER, PARAM_QUANTIZATION};
+use crate::engine::entry_governance::{
+    PARAM_COOLDOWN_BARS, PARAM_ENTRY_WINDOW_BARS, PARAM_MAX_ENTRIES_PER_WINDOW,
+    PARAM_REQUIRE_SIGNAL_RESET,
+};
+use crate::engine::execution::commission::{
+    is_tier_param, PARAM_COMMISSION_FLAT, PARAM_COMMISSION_MIN, PARAM_COMMISSION_MODEL,
+    PARAM_COMMISSION_RATE, PARAM_REGULATORY_FEES, PARAM_SEC_FEE_RATE, PARAM_TAF_MAX,
+    PARAM_TAF_PER_SHARE,
+};
+use crate::engine::execution::fill_price::PARAM_PRECISE_ACCOUNTING;
+use crate::engine::execution::limit_fill::{
+    PARAM_LIMIT_FILL, PARAM_LIMIT_FILL_PROB, PARAM_LIMIT_FILL_TICKS,
+};
+use crate::engine::risk_guard::{PARAM_MAX_DRAWDOWN_HALT, PARAM_MAX_POSITION_PCT};
+use crate::engine::warmup::{PARAM_WARMUP_MULTIPLIER, PARAM_WARMUP_OVERRIDE};
+
+use super::custom::ComponentRegistry;
+use super::execution::{FlipPolicy, StopEntryModel};
+use super::filter::VolatilityFilter;
+use super::pm::HoldingClock;
+use super::signal::{EnsembleSignal, StrengthAggregation};
+
+/// A numeric component parameter: its default and its typical range.
+///
+/// The range is what the sampler explores; the factory only rejects values
+/// that are negative or non-finite.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParamSpec {
+    pub name: &'static str,
+    pub default: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl ParamSpec {
+    const fn new(name: &'static str, default: f64, min: f64, max: f64) -> Self {
+        Self {
+            name,
+            default,
+            min,
+            max,
+        }
+    }
+}
+
+impl fmt::Display for ParamSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}..={}, default {})",
+            self.name, self.min, self.max, self.default
+        )
+    }
+}
+
+/// The param a component's behavior hinges on most: a signal's lookback, a
+/// position manager's stop distance. Sensitivity probes perturb it to find
+/// configs that sit on a cliff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrimaryParam {
+    pub name: &'static str,
+    /// A whole number of bars, perturbed in integer steps.
+    pub bars: bool,
+}
+
+impl PrimaryParam {
+    /// A continuous param, such as a stop percentage or ATR multiple.
+    pub const fn value(name: &'static str) -> Self {
+        Self { name, bars: false }
+    }
+
+    /// A param counted in bars, such as a lookback.
+    pub const fn bars(name: &'static str) -> Self {
+        Self { name, bars: true }
+    }
+}
+
+/// One component type.
+#[derive(Debug, Clone, Copy)]
+pub struct ComponentSpec {
+    pub component_type: &'static str,
+    /// Params explored by the sampler.
+    pub params: &'static [ParamSpec],
+    /// Params accepted by this type only, never sampled.
+    pub extra_params: &'static [ParamSpec],
+    /// Sampler selection weight.
+    pub weight: f64,
+    /// The sampled param sensitivity probes perturb, if the type has one.
+    pub primary: Option<PrimaryParam>,
+}
+
+impl ComponentSpec {
+    const fn new(component_type: &'static str, params: &'static [ParamSpec], weight: f64) -> Self {
+        Self {
+            component_type,
+            params,
+            extra_params: &[],
+            weight,
+            primary: None,
+        }
+    }
+
+    const fn with_primary(self, primary: PrimaryParam) -> Self {
+        Self {
+            primary: Some(primary),
+            ..self
+        }
+    }
+
+    /// The primary param and its spec.
+    pub fn primary_param(&self) -> Option<(PrimaryParam, &'static ParamSpec)> {
+        let primary = self.primary?;
+        let spec = self.params.iter().find(|p| p.name == primary.name)?;
+        Some((primary, spec))
+    }
+}
+
+/// Which of the four strategy components a spec describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComponentKind {
+    Signal,
+    PositionManager,
+    ExecutionModel,
+    SignalFilter,
+}
+
+impl ComponentKind {
+    /// Sampled types of this kind, in sampler order.
+    pub fn specs(self) -> &'static [ComponentSpec] {
+        match self {
+            Self::Signal => SIGNALS,
+            Self::PositionManager => POSITION_MANAGERS,
+            Self::ExecutionModel => EXECUTION_MODELS,
+            Self::SignalFilter => FILTERS,
+        }
+    }
+
+    /// Params every type of this kind accepts.
+    pub fn shared_params(self) -> &'static [ParamSpec] {
+        match self {
+            Self::Signal => SIGNAL_SHARED_PARAMS,
+            Self::ExecutionModel => EXECUTION_SHARED_PARAMS,
+            Self::PositionManager | Self::SignalFilter => &[],
+        }
+    }
+
+    /// Every type the factory builds: sampled types, then unsampled ones,
+    /// then custom types registered at runtime (see [`ComponentRegistry`]).
+    pub fn all_specs(self) -> impl Iterator<Item = &'static ComponentSpec> {
+        let unsampled: &'static [ComponentSpec] = match self {
+            Self::Signal => UNSAMPLED_SIGNALS,
+            Self::PositionManager => UNSAMPLED_POSITION_MANAGERS,
+            _ => &[],
+        };
+        self.specs()
+            .iter()
+            .chain(unsampled)
+            .chain(ComponentRegistry::specs(self))
+    }
+
+    pub fn find(self, component_type: &str) -> Option<&'static ComponentSpec> {
+        self.all_specs()
+            .find(|s| s.component_type == component_type)
+    }
+
+    pub fn type_names(self) -> Vec<&'static str> {
+        self.all_specs().map(|s| s.component_type).collect()
+    }
+
+    /// Everything `spec` accepts: sampled, type-specific, then shared params.
+    pub fn accepted_params(self, spec: &ComponentSpec) -> Vec<ParamSpec> {
+        spec.params
+            .iter()
+            .chain(spec.extra_params)
+            .chain(self.shared_params())
+            .copied()
+            .collect()
+    }
+
+    /// Whether `spec` accepts a param called `name`.
+    pub fn accepts(self, spec: &ComponentSpec, name: &str) -> bool {
+        self.accepted_params(spec).iter().any(|p| p.name == name)
+            || (self == Self::ExecutionModel && is_tier_param(name))
+    }
+}
+
+impl fmt::Display for ComponentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Signal => "signal",
+            Self::PositionManager => "position manager",
+            Self::ExecutionModel => "execution model",
+            Self::SignalFilter => "filter",
+        })
+    }
+}
+
+// ─── Signals ─────────────────────────────────────────────────────────
+
+pub const SIGNALS: &[ComponentSpec] = &[
+    ComponentSpec::new(
+        "donchian_breakout",
+        &[ParamSpec::new("entry_lookback", 50.0, 10.0, 200.0)],
+        2.0,
+    )
+    .with_primary(PrimaryParam::bars("entry_lookback")),
+    ComponentSpec::new(
+        "bollinger_breakout",
+        &[
+            ParamSpec::new("period", 20.0, 10.0, 50.0),
+            ParamSpec::new("std_multiplier", 2.0, 1.0, 3.0),
+        ],
+        2.0,
+    )
+    .with_primary(PrimaryParam::bars("period")),
+    ComponentSpec::new(
+        "breakout_52w",
+        &[
+            ParamSpec::new("lookback", 252.0, 50.0, 504.0),
+            ParamSpec::new("threshold_pct", 0.0, 0.0, 5.0),
+        ],
+        1.0,
+    )
+    .with_primary(PrimaryParam::bars("lookback")),
+    ComponentSpec::new(
+        "keltner_breakout",
+        &[
+            ParamSpec::new("ema_period", 20.0, 10.0, 50.0),
+            ParamSpec::new("atr_period", 10.0, 5.0, 30.0),
+            ParamSpec::new("multiplier", 1.5, 0.5, 3.0),
+        ],
+        1.5,
+    )
+    .with_primary(PrimaryParam::bars("ema_period")),
+    ComponentSpec::new(
+        "supertrend",
+        &[
+            ParamSpec::new("period", 10.0, 5.0, 30.0),
+            ParamSpec::new("multiplier", 3.0, 1.0, 5.0),
+        ],
+        2.0,
+    )
+    .with_primary(PrimaryParam::bars("period")),
+    ComponentSpec::new(
+        "parabolic_sar",
+        &[
+            ParamSpec::new("af_start", 0.02, 0.01, 0.05),
+            ParamSpec::new("af_step", 0.02, 0.01, 0.05),
+            ParamSpec::new("af_max", 0.20, 0.10, 0.40),
+        ],
+        1.5,
+    ),
+    ComponentSpec::new(
+        "ma_crossover",
+        &[
+            ParamSpec::new("fast_period", 10.0, 5.0, 30.0),
+            ParamSpec::new("slow_period", 50.0, 20.0, 200.0),
+            ParamSpec::new("ma_type", 0.0, 0.0, 1.0),
+        ],
+        2.0,
+    )
+    .with_primary(PrimaryParam::bars("slow_period")),
+    ComponentSpec::new("tsmom", &[ParamSpec::new("lookback", 20.0, 5.0, 60.0)], 1.0)
+        .with_primary(PrimaryParam::bars("lookback")),
+    ComponentSpec::new(
+        "roc_momentum",
+        &[
+            ParamSpec::new("period", 12.0, 5.0, 30.0),
+            ParamSpec::new("threshold_pct", 0.0, 0.0, 5.0),
+        ],
+        1.0,
+    )
+    .with_primary(PrimaryParam::bars("period")),
+    ComponentSpec::new(
+        "aroon_crossover",
+        &[ParamSpec::new("period", 25.0, 10.0, 50.0)],
+        1.0,
+    )
+    .with_primary(PrimaryParam::bars("period")),
+    ComponentSpec::new(
+        "ichimoku_cloud",
+        &[
+            ParamSpec::new("tenkan", 9.0, 5.0, 20.0),
+            ParamSpec::new("kijun", 26.0, 15.0, 60.0),
+            ParamSpec::new("senkou_b", 52.0, 30.0, 120.0),
+        ],
+        1.0,
+    )
+    .with_primary(PrimaryParam::bars("kijun")),
+    ComponentSpec::new(
+        "adx_trend",
+        &[
+            ParamSpec::new("period", 14.0, 7.0, 30.0),
+            ParamSpec::new("threshold", 25.0, 15.0, 40.0),
+        ],
+        1.0,
+    )
+    .with_primary(PrimaryParam::bars("period")),
+    ComponentSpec::new(
+        "squeeze_breakout",
+        &[
+            ParamSpec::new("period", 20.0, 10.0, 40.0),
+            ParamSpec::new("std_multiplier", 2.0, 1.5, 3.0),
+            ParamSpec::new("keltner_multiplier", 1.5, 1.0, 2.5),
+            ParamSpec::new("percentile_lookback", 120.0, 50.0, 252.0),
+            ParamSpec::new("percentile", 20.0, 5.0, 40.0),
+            ParamSpec::new("min_squeeze_bars", 5.0, 1.0, 20.0),
+        ],
+        1.0,
+    )
+    .with_primary(PrimaryParam::bars("period")),
+];
+
+/// Component type of the ensemble signal, whose children are listed separately.
+pub const ENSEMBLE_SIGNAL: &str = "ensemble";
+
+/// Built by the factory but sampled only from pools that opt in (see
+/// `ComponentPool::with_signal_ensembles`), at this spec's weight.
+pub const UNSAMPLED_SIGNALS: &[ComponentSpec] = &[ComponentSpec {
+    component_type: ENSEMBLE_SIGNAL,
+    params: &[],
+    extra_params: &[
+        ParamSpec::new(EnsembleSignal::PARAM_K, 2.0, 1.0, 5.0),
+        ParamSpec::new(StrengthAggregation::PARAM, 0.0, 0.0, 1.0),
+    ],
+    weight: 0.3,
+    primary: None,
+}];
+
+/// Post-exit cooldown, the one governance param the sampler explores.
+pub const COOLDOWN_BARS: ParamSpec = ParamSpec::new(PARAM_COOLDOWN_BARS, 0.0, 0.0, 20.0);
+
+/// Entry governance, warmup and risk guard params, accepted by every signal.
+pub const SIGNAL_SHARED_PARAMS: &[ParamSpec] = &[
+    COOLDOWN_BARS,
+    ParamSpec::new(PARAM_MAX_ENTRIES_PER_WINDOW, 0.0, 0.0, 10.0),
+    ParamSpec::new(PARAM_ENTRY_WINDOW_BARS, 20.0, 1.0, 100.0),
+    ParamSpec::new(PARAM_REQUIRE_SIGNAL_RESET, 0.0, 0.0, 1.0),
+    ParamSpec::new(PARAM_WARMUP_MULTIPLIER, 1.0, 1.0, 5.0),
+    ParamSpec::new(PARAM_WARMUP_OVERRIDE, 0.0, 0.0, 1000.0),
+    ParamSpec::new(PARAM_MAX_POSITION_PCT, 0.0, 0.0, 1.0),
+    ParamSpec::new(PARAM_MAX_DRAWDOWN_HALT, 0.0, 0.0, 1.0),
+];
+
+// ─── Position managers ───────────────────────────────────────────────
+
+/// Component type of the composite PM, whose children are listed separately.
+pub const COMPOSITE_PM: &str = "composite";
+
+/// Time-based PMs: count void bars toward the holding period (see [`HoldingClock`]).
+const COUNT_VOID_BARS: ParamSpec =
+    ParamSpec::new(HoldingClock::PARAM_COUNT_VOID_BARS, 0.0, 0.0, 1.0);
+
+pub const POSITION_MANAGERS: &[ComponentSpec] = &[
+    ComponentSpec::new(
+        "atr_trailing",
+        &[
+            ParamSpec::new("atr_period", 14.0, 5.0, 30.0),
+            ParamSpec::new("multiplier", 3.0, 1.0, 5.0),
+        ],
+        3.0,
+    )
+    .with_primary(PrimaryParam::value("multiplier")),
+    ComponentSpec::new(
+        "percent_trailing",
+        &[ParamSpec::new("trail_pct", 0.05, 0.01, 0.15)],
+        2.0,
+    )
+    .with_primary(PrimaryParam::value("trail_pct")),
+    ComponentSpec::new(
+        "chandelier",
+        &[
+            ParamSpec::new("atr_period", 22.0, 10.0, 30.0),
+            ParamSpec::new("multiplier", 3.0, 1.5, 5.0),
+        ],
+        2.0,
+    )
+    .with_primary(PrimaryParam::value("multiplier")),
+    ComponentSpec::new(
+        "donchian_exit",
+        &[ParamSpec::new("exit_lookback", 20.0, 5.0, 100.0)],
+        1.5,
+    )
+    .with_primary(PrimaryParam::bars("exit_lookback")),
+    ComponentSpec::new(
+        "fixed_stop_loss",
+        &[ParamSpec::new("stop_pct", 0.02, 0.005, 0.10)],
+        1.5,
+    )
+    .with_primary(PrimaryParam::value("stop_pct")),
+    ComponentSpec::new(
+        "breakeven_then_trail",
+        &[
+            ParamSpec::new("breakeven_trigger_pct", 0.02, 0.005, 0.05),
+            ParamSpec::new("trail_pct", 0.03, 0.01, 0.10),
+        ],
+        1.5,
+    )
+    .with_primary(PrimaryParam::value("trail_pct")),
+    ComponentSpec {
+        component_type: "time_decay",
+        params: &[
+            ParamSpec::new("initial_pct", 0.10, 0.03, 0.20),
+            ParamSpec::new("decay_per_bar", 0.005, 0.001, 0.02),
+            ParamSpec::new("min_pct", 0.02, 0.005, 0.05),
+        ],
+        extra_params: &[COUNT_VOID_BARS],
+        weight: 1.0,
+        primary: Some(PrimaryParam::value("initial_pct")),
+    },
+    ComponentSpec::new(
+        "frozen_reference",
+        &[ParamSpec::new("exit_pct", 0.05, 0.01, 0.15)],
+        1.0,
+    )
+    .with_primary(PrimaryParam::value("exit_pct")),
+    ComponentSpec::new(
+        "since_entry_trailing",
+        &[ParamSpec::new("exit_pct", 0.05, 0.01, 0.15)],
+        1.0,
+    )
+    .with_primary(PrimaryParam::value("exit_pct")),
+    ComponentSpec {
+        component_type: "max_holding_period",
+        params: &[ParamSpec::new("max_bars", 20.0, 5.0, 60.0)],
+        extra_params: &[COUNT_VOID_BARS],
+        weight: 0.5,
+        primary: Some(PrimaryParam::bars("max_bars")),
+    },
+    ComponentSpec::new(COMPOSITE_PM, &[], 0.3),
+];
+
+/// Built by the factory but never sampled.
+pub const UNSAMPLED_POSITION_MANAGERS: &[ComponentSpec] = &[ComponentSpec::new("no_op", &[], 0.0)];
+
+// ─── Execution models ────────────────────────────────────────────────
+
+const PRESET: ParamSpec = ParamSpec::new("preset", 1.0, 0.0, 3.0);
+
+pub const EXECUTION_MODELS: &[ComponentSpec] = &[
+    ComponentSpec::new("next_bar_open", &[PRESET], 3.0),
+    ComponentSpec {
+        component_type: "stop_entry",
+        params: &[PRESET],
+        extra_params: &[ParamSpec::new(
+            StopEntryModel::PARAM_OFFSET_TICKS,
+            0.0,
+            0.0,
+            10.0,
+        )],
+        weight: 2.0,
+        primary: None,
+    },
+    ComponentSpec {
+        component_type: "close_on_signal",
+        params: &[PRESET],
+        extra_params: &[
+            ParamSpec::new(FlipPolicy::PARAM_CLOSE_ON_FLIP, 0.0, 0.0, 1.0),
+            ParamSpec::new(FlipPolicy::PARAM_REVERSE_ON_FLIP, 0.0, 0.0, 1.0),
+        ],
+        weight: 1.0,
+        primary: None,
+    },
+    ComponentSpec::new(
+        "limit_entry",
+        &[PRESET, ParamSpec::new("offset_bps", 25.0, 5.0, 100.0)],
+        1.0,
+    ),
+];
+
+/// Commission, regulatory fee, quantization, limit fill and accounting
+/// params, accepted by every execution model (plus the
+/// `commission_tier_<i>_above` / `_rate` pairs of a tiered schedule).
+pub const EXECUTION_SHARED_PARAMS: &[ParamSpec] = &[
+    ParamSpec::new(PARAM_COMMISSION_MODEL, 0.0, 0.0, 3.0),
+    ParamSpec::new(PARAM_COMMISSION_RATE, 0.0, 0.0, 50.0),
+    ParamSpec::new(PARAM_COMMISSION_MIN, 0.0, 0.0, 10.0),
+    ParamSpec::new(PARAM_COMMISSION_FLAT, 0.0, 0.0, 20.0),
+    ParamSpec::new(PARAM_REGULATORY_FEES, 0.0, 0.0, 1.0),
+    ParamSpec::new(PARAM_SEC_FEE_RATE, 27.80 / 1_000_000.0, 0.0, 0.001),
+    ParamSpec::new(PARAM_TAF_PER_SHARE, 0.000166, 0.0, 0.001),
+    ParamSpec::new(PARAM_TAF_MAX, 8.30, 0.0, 20.0),
+    ParamSpec::new(PARAM_QUANTIZATION, 0.0, 0.0, 2.0),
+    ParamSpec::new(PARAM_CONTRACT_MULTIPLIER, 1.0, 1.0, 100.0),
+    ParamSpec::new(PARAM_LIMIT_FILL, 0.0, 0.0, 2.0),
+    ParamSpec::new(PARAM_LIMIT_FILL_TICKS, 1.0, 0.0, 10.0),
+    ParamSpec::new(PARAM_LIMIT_FILL_PROB, 0.5, 0.0, 1.0),
+    ParamSpec::new(PARAM_PRECISE_ACCOUNTING, 0.0, 0.0, 1.0),
+];
+
+// ─── Filters ─────────────────────────────────────────────────────────
+
+pub const FILTERS: &[ComponentSpec] = &[
+    ComponentSpec::new("no_filter", &[], 3.0),
+    ComponentSpec::new(
+        "adx_filter",
+        &[
+            ParamSpec::new("period", 14.0, 7.0, 28.0),
+            ParamSpec::new("threshold", 25.0, 15.0, 40.0),
+        ],
+        2.0,
+    ),
+    ComponentSpec::new(
+        "ma_regime",
+        &[
+            ParamSpec::new("period", 200.0, 50.0, 400.0),
+            ParamSpec::new("direction", 0.0, 0.0, 1.0),
+        ],
+        1.5,
+    ),
+    ComponentSpec {
+        component_type: "volatility_filter",
+        params: &[
+            ParamSpec::new("period", 14.0, 7.0, 28.0),
+            ParamSpec::new("min_pct", 0.5, 0.1, 2.0),
+            ParamSpec::new("max_pct", 5.0, 2.0, 10.0),
+        ],
+        extra_params: &[ParamSpec::new(
+            VolatilityFilter::PARAM_FREEZE_STOPS,
+            0.0,
+            0.0,
+            10.0,
+        )],
+        weight: 1.5,
+        primary: None,
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn type_names_are_unique_per_kind() {
+        for kind in [
+            ComponentKind::Signal,
+            ComponentKind::PositionManager,
+            ComponentKind::ExecutionModel,
+            ComponentKind::SignalFilter,
+        ] {
+            let mut names = kind.type_names();
+            let n = names.len();
+            names.sort_unstable();
+            names.dedup();
+            assert_eq!(names.len(), n, "duplicate {kind} type");
+        }
+    }
+
+    #[test]
+    fn defaults_lie_within_ranges() {
+        let all = [
+            SIGNALS,
+            UNSAMPLED_SIGNALS,
+            POSITION_MANAGERS,
+            EXECUTION_MODELS,
+            FILTERS,
+        ];
+        for spec in all.iter().flat_map(|s| s.iter()) {
+            for p in spec.params.iter().chain(spec.extra_params) {
+                assert!(
+                    p.min <= p.default && p.default <= p.max,
+                    "{}.{}",
+                    spec.component_type,
+                    p.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn primary_params_are_sampled() {
+        for spec in SIGNALS.iter().chain(POSITION_MANAGERS) {
+            let Some(primary) = spec.primary else {
+                continue;
+            };
+            let (_, param) = spec
+                .primary_param()
+                .unwrap_or_else(|| panic!("{}.{} not sampled", spec.component_type, primary.name));
+            if primary.bars {
+                assert_eq!(param.default.fract(), 0.0, "{}", spec.component_type);
+            }
+        }
+        // Every signal but parabolic SAR has a lookback
+        let without: Vec<_> = SIGNALS
+            .iter()
+            .filter(|s| s.primary.is_none())
+            .map(|s| s.component_type)
+            .collect();
+        assert_eq!(without, ["parabolic_sar"]);
+        let pm = ComponentKind::PositionManager.find("atr_trailing").unwrap();
+        assert_eq!(pm.primary, Some(PrimaryParam::value("multiplier")));
+    }
+
+    #[test]
+    fn accepts_shared_and_type_specific_params() {
+        let kind = ComponentKind::ExecutionModel;
+        let close = kind.find("close_on_signal").unwrap();
+        let next = kind.find("next_bar_open").unwrap();
+        assert!(kind.accepts(close, FlipPolicy::PARAM_REVERSE_ON_FLIP));
+        assert!(!kind.accepts(next, FlipPolicy::PARAM_REVERSE_ON_FLIP));
+        assert!(kind.accepts(next, PARAM_COMMISSION_RATE));
+        assert!(kind.accepts(next, "commission_tier_2_rate"));
+        assert!(!kind.accepts(next, "commission_tier_x_rate"));
+
+        let kind = ComponentKind::Signal;
+        let donchian = kind.find("donchian_breakout").unwrap();
+        assert!(kind.accepts(donchian, PARAM_COOLDOWN_BARS));
+        assert!(!kind.accepts(donchian, "exit_lookback"));
+    }
+
+    #[test]
+    fn param_spec_display_shows_range_and_default() {
+        assert_eq!(
+            SIGNALS[0].params[0].to_string(),
+            "entry_lookback (10..=200, default 50)"
+        );
+    }
+}
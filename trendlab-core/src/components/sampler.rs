@@ -3,11 +3,29 @@
 //! Two controls:
 //! - `jitter_pct` (0.0 to 1.0): how much to randomize parameter values
 //! - `structural_explore` (0.0 to 1.0): probability of picking non-default component types
+//!
+//! `ParamGrid` is the deterministic counterpart: it expands a base config over
+//! evenly spaced parameter values drawn from the same `ParamRange`s.
+//!
+//! `mutate_composition` samples around a known config instead of the defaults,
+//! and records the parent it came from.
+//!
+//! `sample_compatible_composition` conditions lookback ranges on the dataset
+//! length and resamples draws that break an incompatible rule of the
+//! compatibility table (see `composition::COMPATIBILITY_RULES`).
 
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
+use std::fmt;
 
-use crate::fingerprint::{ComponentConfig, StrategyConfig};
+use crate::fingerprint::{ComponentConfig, Provenance, StrategyConfig};
+
+use super::composition::{check_compatibility, CompatibilityContext};
+use super::custom::ComponentRegistry;
+use super::registry::{
+    ComponentKind, ComponentSpec, ParamSpec, COMPOSITE_PM, COOLDOWN_BARS, ENSEMBLE_SIGNAL,
+};
 
 /// Range for a numeric parameter.
 #[derive(Debug, Clone)]
@@ -18,6 +36,33 @@ pub struct ParamRange {
     pub max: f64,
 }
 
+impl ParamRange {
+    /// `steps` evenly spaced values from `min` to `max` inclusive.
+    ///
+    /// `steps <= 1` yields just the default.
+    pub fn grid_values(&self, steps: usize) -> Vec<f64> {
+        if steps <= 1 {
+            return vec![self.default];
+        }
+        let step = (self.max - self.min) / (steps - 1) as f64;
+        (0..steps).map(|i| self.min + step * i as f64).collect()
+    }
+
+    /// Step for nudging the value by hand: a twentieth of the range, whole
+    /// bars for lookbacks and the cooldown, and 1 for enum codes.
+    pub fn nudge_step(&self) -> f64 {
+        let name = self.name.as_str();
+        let step = (self.max - self.min) / 20.0;
+        if DISCRETE_PARAMS.contains(&name) {
+            1.0
+        } else if LOOKBACK_PARAMS.contains(&name) || name == COOLDOWN_BARS.name {
+            step.round().max(1.0)
+        } else {
+            step
+        }
+    }
+}
+
 /// A component variant with its parameter ranges.
 #[derive(Debug, Clone)]
 pub struct ComponentVariant {
@@ -34,437 +79,319 @@ pub struct ComponentPool {
     pub position_managers: Vec<ComponentVariant>,
     pub execution_models: Vec<ComponentVariant>,
     pub filters: Vec<ComponentVariant>,
+    /// Post-exit cooldown (bars), explored alongside structural choices.
+    pub cooldown_bars: ParamRange,
+}
+
+impl From<&ParamSpec> for ParamRange {
+    fn from(spec: &ParamSpec) -> Self {
+        Self {
+            name: spec.name.into(),
+            default: spec.default,
+            min: spec.min,
+            max: spec.max,
+        }
+    }
+}
+
+impl From<&ComponentSpec> for ComponentVariant {
+    fn from(spec: &ComponentSpec) -> Self {
+        Self {
+            component_type: spec.component_type.into(),
+            param_ranges: spec.params.iter().map(ParamRange::from).collect(),
+            weight: spec.weight,
+        }
+    }
 }
 
 impl ComponentPool {
-    /// Default pool with all 10 signals, 9 PMs, 4 executions, 4 filters.
+    /// Default pool with all 13 signals, 11 PMs, 4 executions, 4 filters,
+    /// built from the component registry. Composite PM children are sampled
+    /// separately (see `sample_composite_children`).
     pub fn default_pool() -> Self {
+        let variants =
+            |kind: ComponentKind| kind.specs().iter().map(ComponentVariant::from).collect();
         Self {
-            signals: vec![
-                ComponentVariant {
-                    component_type: "donchian_breakout".into(),
-                    param_ranges: vec![ParamRange {
-                        name: "entry_lookback".into(),
-                        default: 50.0,
-                        min: 10.0,
-                        max: 200.0,
-                    }],
-                    weight: 2.0,
-                },
-                ComponentVariant {
-                    component_type: "bollinger_breakout".into(),
-                    param_ranges: vec![
-                        ParamRange {
-                            name: "period".into(),
-                            default: 20.0,
-                            min: 10.0,
-                            max: 50.0,
-                        },
-                        ParamRange {
-                            name: "std_multiplier".into(),
-                            default: 2.0,
-                            min: 1.0,
-                            max: 3.0,
-                        },
-                    ],
-                    weight: 2.0,
-                },
-                ComponentVariant {
-                    component_type: "breakout_52w".into(),
-                    param_ranges: vec![
-                        ParamRange {
-                            name: "lookback".into(),
-                            default: 252.0,
-                            min: 50.0,
-                            max: 504.0,
-                        },
-                        ParamRange {
-                            name: "threshold_pct".into(),
-                            default: 0.0,
-                            min: 0.0,
-                            max: 5.0,
-                        },
-                    ],
-                    weight: 1.0,
-                },
-                ComponentVariant {
-                    component_type: "keltner_breakout".into(),
-                    param_ranges: vec![
-                        ParamRange {
-                            name: "ema_period".into(),
-                            default: 20.0,
-                            min: 10.0,
-                            max: 50.0,
-                        },
-                        ParamRange {
-                            name: "atr_period".into(),
-                            default: 10.0,
-                            min: 5.0,
-                            max: 30.0,
-                        },
-                        ParamRange {
-                            name: "multiplier".into(),
-                            default: 1.5,
-                            min: 0.5,
-                            max: 3.0,
-                        },
-                    ],
-                    weight: 1.5,
-                },
-                ComponentVariant {
-                    component_type: "supertrend".into(),
-                    param_ranges: vec![
-                        ParamRange {
-                            name: "period".into(),
-                            default: 10.0,
-                            min: 5.0,
-                            max: 30.0,
-                        },
-                        ParamRange {
-                            name: "multiplier".into(),
-                            default: 3.0,
-                            min: 1.0,
-                            max: 5.0,
-                        },
-                    ],
-                    weight: 2.0,
-                },
-                ComponentVariant {
-                    component_type: "parabolic_sar".into(),
-                    param_ranges: vec![
-                        ParamRange {
-                            name: "af_start".into(),
-                            default: 0.02,
-                            min: 0.01,
-                            max: 0.05,
-                        },
-                        ParamRange {
-                            name: "af_step".into(),
-                            default: 0.02,
-                            min: 0.01,
-                            max: 0.05,
-                        },
-                        ParamRange {
-                            name: "af_max".into(),
-                            default: 0.20,
-                            min: 0.10,
-                            max: 0.40,
-                        },
-                    ],
-                    weight: 1.5,
-                },
-                ComponentVariant {
-                    component_type: "ma_crossover".into(),
-                    param_ranges: vec![
-                        ParamRange {
-                            name: "fast_period".into(),
-                            default: 10.0,
-                            min: 5.0,
-                            max: 30.0,
-                        },
-                        ParamRange {
-                            name: "slow_period".into(),
-                            default: 50.0,
-                            min: 20.0,
-                            max: 200.0,
-                        },
-                        ParamRange {
-                            name: "ma_type".into(),
-                            default: 0.0,
-                            min: 0.0,
-                            max: 1.0,
-                        },
-                    ],
-                    weight: 2.0,
-                },
-                ComponentVariant {
-                    component_type: "tsmom".into(),
-                    param_ranges: vec![ParamRange {
-                        name: "lookback".into(),
-                        default: 20.0,
-                        min: 5.0,
-                        max: 60.0,
-                    }],
-                    weight: 1.0,
-                },
-                ComponentVariant {
-                    component_type: "roc_momentum".into(),
-                    param_ranges: vec![
-                        ParamRange {
-                            name: "period".into(),
-                            default: 12.0,
-                            min: 5.0,
-                            max: 30.0,
-                        },
-                        ParamRange {
-                            name: "threshold_pct".into(),
-                            default: 0.0,
-                            min: 0.0,
-                            max: 5.0,
-                        },
-                    ],
-                    weight: 1.0,
-                },
-                ComponentVariant {
-                    component_type: "aroon_crossover".into(),
-                    param_ranges: vec![ParamRange {
-                        name: "period".into(),
-                        default: 25.0,
-                        min: 10.0,
-                        max: 50.0,
-                    }],
-                    weight: 1.0,
-                },
-            ],
-            position_managers: vec![
-                ComponentVariant {
-                    component_type: "atr_trailing".into(),
-                    param_ranges: vec![
-                        ParamRange {
-                            name: "atr_period".into(),
-                            default: 14.0,
-                            min: 5.0,
-                            max: 30.0,
-                        },
-                        ParamRange {
-                            name: "multiplier".into(),
-                            default: 3.0,
-                            min: 1.0,
-                            max: 5.0,
-                        },
-                    ],
-                    weight: 3.0,
-                },
-                ComponentVariant {
-                    component_type: "percent_trailing".into(),
-                    param_ranges: vec![ParamRange {
-                        name: "trail_pct".into(),
-                        default: 0.05,
-                        min: 0.01,
-                        max: 0.15,
-                    }],
-                    weight: 2.0,
-                },
-                ComponentVariant {
-                    component_type: "chandelier".into(),
-                    param_ranges: vec![
-                        ParamRange {
-                            name: "atr_period".into(),
-                            default: 22.0,
-                            min: 10.0,
-                            max: 30.0,
-                        },
-                        ParamRange {
-                            name: "multiplier".into(),
-                            default: 3.0,
-                            min: 1.5,
-                            max: 5.0,
-                        },
-                    ],
-                    weight: 2.0,
-                },
-                ComponentVariant {
-                    component_type: "fixed_stop_loss".into(),
-                    param_ranges: vec![ParamRange {
-                        name: "stop_pct".into(),
-                        default: 0.02,
-                        min: 0.005,
-                        max: 0.10,
-                    }],
-                    weight: 1.5,
-                },
-                ComponentVariant {
-                    component_type: "breakeven_then_trail".into(),
-                    param_ranges: vec![
-                        ParamRange {
-                            name: "breakeven_trigger_pct".into(),
-                            default: 0.02,
-                            min: 0.005,
-                            max: 0.05,
-                        },
-                        ParamRange {
-                            name: "trail_pct".into(),
-                            default: 0.03,
-                            min: 0.01,
-                            max: 0.10,
-                        },
-                    ],
-                    weight: 1.5,
-                },
-                ComponentVariant {
-                    component_type: "time_decay".into(),
-                    param_ranges: vec![
-                        ParamRange {
-                            name: "initial_pct".into(),
-                            default: 0.10,
-                            min: 0.03,
-                            max: 0.20,
-                        },
-                        ParamRange {
-                            name: "decay_per_bar".into(),
-                            default: 0.005,
-                            min: 0.001,
-                            max: 0.02,
-                        },
-                        ParamRange {
-                            name: "min_pct".into(),
-                            default: 0.02,
-                            min: 0.005,
-                            max: 0.05,
-                        },
-                    ],
-                    weight: 1.0,
-                },
-                ComponentVariant {
-                    component_type: "frozen_reference".into(),
-                    param_ranges: vec![ParamRange {
-                        name: "exit_pct".into(),
-                        default: 0.05,
-                        min: 0.01,
-                        max: 0.15,
-                    }],
-                    weight: 1.0,
-                },
-                ComponentVariant {
-                    component_type: "since_entry_trailing".into(),
-                    param_ranges: vec![ParamRange {
-                        name: "exit_pct".into(),
-                        default: 0.05,
-                        min: 0.01,
-                        max: 0.15,
-                    }],
-                    weight: 1.0,
-                },
-                ComponentVariant {
-                    component_type: "max_holding_period".into(),
-                    param_ranges: vec![ParamRange {
-                        name: "max_bars".into(),
-                        default: 20.0,
-                        min: 5.0,
-                        max: 60.0,
-                    }],
-                    weight: 0.5,
-                },
-            ],
-            execution_models: vec![
-                ComponentVariant {
-                    component_type: "next_bar_open".into(),
-                    param_ranges: vec![ParamRange {
-                        name: "preset".into(),
-                        default: 1.0,
-                        min: 0.0,
-                        max: 3.0,
-                    }],
-                    weight: 3.0,
-                },
-                ComponentVariant {
-                    component_type: "stop_entry".into(),
-                    param_ranges: vec![ParamRange {
-                        name: "preset".into(),
-                        default: 1.0,
-                        min: 0.0,
-                        max: 3.0,
-                    }],
-                    weight: 2.0,
-                },
-                ComponentVariant {
-                    component_type: "close_on_signal".into(),
-                    param_ranges: vec![ParamRange {
-                        name: "preset".into(),
-                        default: 1.0,
-                        min: 0.0,
-                        max: 3.0,
-                    }],
-                    weight: 1.0,
-                },
-                ComponentVariant {
-                    component_type: "limit_entry".into(),
-                    param_ranges: vec![
-                        ParamRange {
-                            name: "preset".into(),
-                            default: 1.0,
-                            min: 0.0,
-                            max: 3.0,
-                        },
-                        ParamRange {
-                            name: "offset_bps".into(),
-                            default: 25.0,
-                            min: 5.0,
-                            max: 100.0,
-                        },
-                    ],
-                    weight: 1.0,
-                },
-            ],
-            filters: vec![
-                ComponentVariant {
-                    component_type: "no_filter".into(),
-                    param_ranges: vec![],
-                    weight: 3.0,
-                },
-                ComponentVariant {
-                    component_type: "adx_filter".into(),
-                    param_ranges: vec![
-                        ParamRange {
-                            name: "period".into(),
-                            default: 14.0,
-                            min: 7.0,
-                            max: 28.0,
-                        },
-                        ParamRange {
-                            name: "threshold".into(),
-                            default: 25.0,
-                            min: 15.0,
-                            max: 40.0,
-                        },
-                    ],
-                    weight: 2.0,
-                },
-                ComponentVariant {
-                    component_type: "ma_regime".into(),
-                    param_ranges: vec![
-                        ParamRange {
-                            name: "period".into(),
-                            default: 200.0,
-                            min: 50.0,
-                            max: 400.0,
-                        },
-                        ParamRange {
-                            name: "direction".into(),
-                            default: 0.0,
-                            min: 0.0,
-                            max: 1.0,
-                        },
-                    ],
-                    weight: 1.5,
-                },
-                ComponentVariant {
-                    component_type: "volatility_filter".into(),
-                    param_ranges: vec![
-                        ParamRange {
-                            name: "period".into(),
-                            default: 14.0,
-                            min: 7.0,
-                            max: 28.0,
-                        },
-                        ParamRange {
-                            name: "min_pct".into(),
-                            default: 0.5,
-                            min: 0.1,
-                            max: 2.0,
-                        },
-                        ParamRange {
-                            name: "max_pct".into(),
-                            default: 5.0,
-                            min: 2.0,
-                            max: 10.0,
-                        },
-                    ],
-                    weight: 1.5,
-                },
-            ],
+            signals: variants(ComponentKind::Signal),
+            position_managers: variants(ComponentKind::PositionManager),
+            execution_models: variants(ComponentKind::ExecutionModel),
+            filters: variants(ComponentKind::SignalFilter),
+            cooldown_bars: ParamRange::from(&COOLDOWN_BARS),
+        }
+    }
+
+    /// The pool plus every custom signal, PM and filter registered with a
+    /// positive weight (see [`ComponentRegistry`]).
+    pub fn with_registered(mut self) -> Self {
+        let registered = |kind: ComponentKind| {
+            ComponentRegistry::specs(kind)
+                .into_iter()
+                .filter(|spec| spec.weight > 0.0)
+                .map(ComponentVariant::from)
+                .collect::<Vec<_>>()
+        };
+        self.signals.extend(registered(ComponentKind::Signal));
+        self.position_managers
+            .extend(registered(ComponentKind::PositionManager));
+        self.filters.extend(registered(ComponentKind::SignalFilter));
+        self
+    }
+
+    /// The pool plus 2-of-2 signal ensembles (see [`ENSEMBLE_SIGNAL`]),
+    /// picked at the ensemble spec's low weight when exploring. Children are
+    /// two distinct signals drawn from the rest of the pool.
+    pub fn with_signal_ensembles(mut self) -> Self {
+        if let Some(spec) = ComponentKind::Signal.find(ENSEMBLE_SIGNAL) {
+            self.signals.push(ComponentVariant::from(spec));
+        }
+        self
+    }
+
+    /// Copy of the pool with lookback ranges capped so a config leaves
+    /// `context.min_post_warmup_bars` after warmup.
+    ///
+    /// Ranges whose minimum is already over the cap are left alone; the
+    /// compatibility check rejects what they produce. Without a bar count the
+    /// pool is returned unchanged.
+    pub fn conditioned_on(&self, context: &CompatibilityContext) -> Self {
+        let mut pool = self.clone();
+        let Some(cap) = context.max_lookback() else {
+            return pool;
+        };
+        let cap = cap as f64;
+        for variant in pool
+            .signals
+            .iter_mut()
+            .chain(pool.position_managers.iter_mut())
+            .chain(pool.filters.iter_mut())
+        {
+            for range in &mut variant.param_ranges {
+                if LOOKBACK_PARAMS.contains(&range.name.as_str()) && range.min <= cap {
+                    range.max = range.max.min(cap);
+                    range.default = range.default.min(cap);
+                }
+            }
+        }
+        pool
+    }
+
+    /// Range of `param` on `slot`'s component type `component_type`, or the
+    /// cooldown range for the cooldown param. None for components not in the
+    /// pool and params without a range.
+    pub fn param_range(
+        &self,
+        slot: ComponentSlot,
+        component_type: &str,
+        param: &str,
+    ) -> Option<&ParamRange> {
+        if param == self.cooldown_bars.name {
+            return Some(&self.cooldown_bars);
+        }
+        slot.variants(self)
+            .iter()
+            .find(|v| v.component_type == component_type)?
+            .param_ranges
+            .iter()
+            .find(|r| r.name == param)
+    }
+}
+
+/// Parameters holding enum codes rather than continuous values.
+const DISCRETE_PARAMS: [&str; 3] = ["ma_type", "preset", "direction"];
+
+/// Parameters measured in bars of history, capped by `conditioned_on`.
+const LOOKBACK_PARAMS: &[&str] = &[
+    "entry_lookback",
+    "exit_lookback",
+    "lookback",
+    "period",
+    "fast_period",
+    "slow_period",
+    "ema_period",
+    "atr_period",
+    "tenkan",
+    "kijun",
+    "senkou_b",
+];
+
+/// Draws `sample_compatible_composition` makes beyond the first before it
+/// keeps an incompatible config.
+pub const MAX_RESAMPLES: usize = 20;
+
+/// Hard-stop child every sampled composite PM starts with.
+const COMPOSITE_FLOOR_PM: &str = "fixed_stop_loss";
+
+/// Which of the four strategy components a grid axis varies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ComponentSlot {
+    Signal,
+    PositionManager,
+    ExecutionModel,
+    SignalFilter,
+}
+
+impl ComponentSlot {
+    pub const ALL: [ComponentSlot; 4] = [
+        Self::Signal,
+        Self::PositionManager,
+        Self::ExecutionModel,
+        Self::SignalFilter,
+    ];
+
+    /// Config key of the slot, e.g. `position_manager`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Signal => "signal",
+            Self::PositionManager => "position_manager",
+            Self::ExecutionModel => "execution_model",
+            Self::SignalFilter => "signal_filter",
+        }
+    }
+
+    pub fn component<'a>(&self, config: &'a StrategyConfig) -> &'a ComponentConfig {
+        match self {
+            Self::Signal => &config.signal,
+            Self::PositionManager => &config.position_manager,
+            Self::ExecutionModel => &config.execution_model,
+            Self::SignalFilter => &config.signal_filter,
         }
     }
+
+    pub fn component_mut<'a>(&self, config: &'a mut StrategyConfig) -> &'a mut ComponentConfig {
+        match self {
+            Self::Signal => &mut config.signal,
+            Self::PositionManager => &mut config.position_manager,
+            Self::ExecutionModel => &mut config.execution_model,
+            Self::SignalFilter => &mut config.signal_filter,
+        }
+    }
+
+    fn variants<'a>(&self, pool: &'a ComponentPool) -> &'a [ComponentVariant] {
+        match self {
+            Self::Signal => &pool.signals,
+            Self::PositionManager => &pool.position_managers,
+            Self::ExecutionModel => &pool.execution_models,
+            Self::SignalFilter => &pool.filters,
+        }
+    }
+}
+
+/// One varied parameter of a grid.
+#[derive(Debug, Clone)]
+pub struct GridAxis {
+    pub slot: ComponentSlot,
+    pub param: String,
+    pub values: Vec<f64>,
+}
+
+/// Cartesian parameter grid around a base `StrategyConfig`.
+///
+/// Component types never change; only the listed parameters do. Keep grids
+/// small — the point count is the product of all axis lengths.
+#[derive(Debug, Clone, Default)]
+pub struct ParamGrid {
+    pub axes: Vec<GridAxis>,
+}
+
+impl ParamGrid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an axis with explicit values.
+    pub fn with_axis(mut self, slot: ComponentSlot, param: &str, values: Vec<f64>) -> Self {
+        self.axes.push(GridAxis {
+            slot,
+            param: param.to_string(),
+            values,
+        });
+        self
+    }
+
+    /// Build axes for every continuous parameter of `base`'s components in
+    /// `slots`, using the pool's ranges with `steps` points each.
+    ///
+    /// Components not found in the pool and discrete parameters are skipped.
+    pub fn from_pool(
+        pool: &ComponentPool,
+        base: &StrategyConfig,
+        slots: &[ComponentSlot],
+        steps: usize,
+    ) -> Self {
+        let mut grid = Self::new();
+        for &slot in slots {
+            let component_type = &slot.component(base).component_type;
+            let Some(variant) = slot
+                .variants(pool)
+                .iter()
+                .find(|v| &v.component_type == component_type)
+            else {
+                continue;
+            };
+            for range in &variant.param_ranges {
+                if DISCRETE_PARAMS.contains(&range.name.as_str()) {
+                    continue;
+                }
+                grid = grid.with_axis(slot, &range.name, range.grid_values(steps));
+            }
+        }
+        grid
+    }
+
+    /// Number of grid points before deduplication.
+    pub fn len(&self) -> usize {
+        if self.axes.is_empty() {
+            return 1;
+        }
+        self.axes.iter().map(|a| a.values.len()).product()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Expand the grid into concrete configs.
+    ///
+    /// Applies the same discrete rounding and cross-parameter fixes as
+    /// `sample_composition`, then drops duplicates (e.g. fast/slow swaps that
+    /// collapse onto the same point). An empty grid yields just `base`.
+    pub fn expand(&self, base: &StrategyConfig) -> Vec<StrategyConfig> {
+        let mut configs = vec![base.clone()];
+        for axis in &self.axes {
+            let mut next = Vec::with_capacity(configs.len() * axis.values.len());
+            for config in &configs {
+                for &value in &axis.values {
+                    let mut point = config.clone();
+                    axis.slot
+                        .component_mut(&mut point)
+                        .params
+                        .insert(axis.param.clone(), value);
+                    next.push(point);
+                }
+            }
+            configs = next;
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        configs
+            .into_iter()
+            .map(|mut config| {
+                config.signal = fix_cross_param_constraints(round_discrete_params(
+                    config.signal,
+                    &DISCRETE_PARAMS,
+                ));
+                config.position_manager = fix_cross_param_constraints(config.position_manager);
+                config.signal_filter = fix_cross_param_constraints(round_discrete_params(
+                    config.signal_filter,
+                    &DISCRETE_PARAMS,
+                ));
+                config.execution_model =
+                    round_discrete_params(config.execution_model, &DISCRETE_PARAMS);
+                fix_cross_component_constraints(config)
+            })
+            .filter(|config| seen.insert(config.full_hash()))
+            .collect()
+    }
 }
 
 /// Sample a random StrategyConfig from the component pool.
@@ -499,12 +426,120 @@ pub fn sample_composition<R: Rng>(
     let pm = fix_cross_param_constraints(pm);
     let filter = fix_cross_param_constraints(filter);
 
-    StrategyConfig {
+    // Cooldown is explored at the same rate as structural changes
+    let mut signal = signal;
+    if explore_prob > 0.0 && rng.gen::<f64>() < explore_prob {
+        let range = &pool.cooldown_bars;
+        let bars = (range.min + rng.gen::<f64>() * (range.max - range.min)).round();
+        if bars > 0.0 {
+            signal.params.insert(range.name.clone(), bars);
+        }
+    }
+
+    fix_cross_component_constraints(StrategyConfig {
         signal,
         position_manager: pm,
         execution_model: execution,
         signal_filter: filter,
+    })
+}
+
+/// A config from [`sample_compatible_composition`].
+#[derive(Debug, Clone)]
+pub struct CompatibleSample {
+    pub config: StrategyConfig,
+    /// Id of each incompatible rule broken by a rejected draw, once per draw
+    /// and rule.
+    pub rejections: Vec<&'static str>,
+}
+
+/// Sample like [`sample_composition`] from `pool` conditioned on `context`,
+/// resampling any draw that breaks an incompatible rule.
+///
+/// After [`MAX_RESAMPLES`] rejected draws the next draw is kept as is, so a
+/// context no config can satisfy still yields a config.
+pub fn sample_compatible_composition<R: Rng>(
+    pool: &ComponentPool,
+    rng: &mut R,
+    jitter_pct: f64,
+    structural_explore: f64,
+    context: &CompatibilityContext,
+) -> CompatibleSample {
+    let pool = pool.conditioned_on(context);
+    let mut rejections = Vec::new();
+    let mut config = sample_composition(&pool, rng, jitter_pct, structural_explore);
+    for _ in 0..MAX_RESAMPLES {
+        let check = check_compatibility(&config, context);
+        if check.is_compatible() {
+            break;
+        }
+        rejections.extend(check.incompatible);
+        config = sample_composition(&pool, rng, jitter_pct, structural_explore);
     }
+    CompatibleSample { config, rejections }
+}
+
+/// Mutate a known config, typically a leaderboard champion.
+///
+/// Component types are kept; every parameter with a known range moves from
+/// the parent's value by up to `jitter_pct / 2` of that range (clamped).
+/// Returns the child with its [`Provenance`].
+pub fn mutate_composition<R: Rng>(
+    pool: &ComponentPool,
+    parent: &StrategyConfig,
+    rng: &mut R,
+    jitter_pct: f64,
+) -> (StrategyConfig, Provenance) {
+    let jitter = jitter_pct.clamp(0.0, 1.0);
+    let mut child = parent.clone();
+    for slot in [
+        ComponentSlot::Signal,
+        ComponentSlot::PositionManager,
+        ComponentSlot::ExecutionModel,
+        ComponentSlot::SignalFilter,
+    ] {
+        let variants = slot.variants(pool);
+        let component = slot.component_mut(&mut child);
+        *component = mutate_component(rng, component, variants, &pool.cooldown_bars, jitter);
+    }
+    let child = fix_cross_component_constraints(child);
+    let provenance = Provenance::new(parent, &child);
+    (child, provenance)
+}
+
+fn mutate_component<R: Rng>(
+    rng: &mut R,
+    component: &ComponentConfig,
+    variants: &[ComponentVariant],
+    cooldown: &ParamRange,
+    jitter: f64,
+) -> ComponentConfig {
+    let mut config = component.clone();
+    let ranges = variants
+        .iter()
+        .find(|v| v.component_type == config.component_type)
+        .map_or(&[][..], |v| &v.param_ranges[..]);
+    for (name, value) in config.params.iter_mut() {
+        let range = if *name == cooldown.name {
+            cooldown
+        } else if let Some(range) = ranges.iter().find(|r| r.name == *name) {
+            range
+        } else {
+            continue;
+        };
+        let spread = range.max - range.min;
+        let offset = (rng.gen::<f64>() - 0.5) * spread * jitter;
+        *value = (*value + offset).clamp(range.min, range.max);
+        if *name == cooldown.name {
+            *value = value.round();
+        }
+    }
+    config.children = component
+        .children
+        .iter()
+        .map(|child| mutate_component(rng, child, variants, cooldown, jitter))
+        .collect();
+    fix_cross_param_constraints(round_discrete_params(config, &DISCRETE_PARAMS))
 }
 
 fn sample_component<R: Rng>(
@@ -521,7 +556,18 @@ fn sample_component<R: Rng>(
         &variants[0]
     };
 
-    // Sample params with jitter
+    let mut config = sample_params(rng, variant, jitter);
+    if variant.component_type == COMPOSITE_PM {
+        config.children = sample_composite_children(rng, variants, jitter);
+    }
+    if variant.component_type == ENSEMBLE_SIGNAL {
+        config.children = sample_ensemble_children(rng, variants, jitter);
+    }
+    config
+}
+
+/// Sample a variant's params with jitter around the defaults.
+fn sample_params<R: Rng>(rng: &mut R, variant: &ComponentVariant, jitter: f64) -> ComponentConfig {
     let mut params = BTreeMap::new();
     for range in &variant.param_ranges {
         let value = if jitter < 1e-10 {
@@ -538,9 +584,88 @@ fn sample_component<R: Rng>(
     ComponentConfig {
         component_type: variant.component_type.clone(),
         params,
+        children: Vec::new(),
     }
 }
 
+/// Children for a sampled composite PM: a fixed stop-loss floor plus one
+/// weighted-selected trailing PM, both jittered like any other component.
+fn sample_composite_children<R: Rng>(
+    rng: &mut R,
+    variants: &[ComponentVariant],
+    jitter: f64,
+) -> Vec<ComponentConfig> {
+    let trailing: Vec<ComponentVariant> = variants
+        .iter()
+        .filter(|v| v.component_type != COMPOSITE_PM && v.component_type != COMPOSITE_FLOOR_PM)
+        .cloned()
+        .collect();
+    let mut children = Vec::with_capacity(2);
+    if let Some(floor) = variants
+        .iter()
+        .find(|v| v.component_type == COMPOSITE_FLOOR_PM)
+    {
+        children.push(sample_params(rng, floor, jitter));
+    }
+    if !trailing.is_empty() {
+        let variant = weighted_select(rng, &trailing);
+        children.push(fix_cross_param_constraints(sample_params(
+            rng, variant, jitter,
+        )));
+    }
+    children
+}
+
+/// Children for a sampled ensemble signal: two distinct weighted-selected
+/// signals, jittered like any other component.
+fn sample_ensemble_children<R: Rng>(
+    rng: &mut R,
+    variants: &[ComponentVariant],
+    jitter: f64,
+) -> Vec<ComponentConfig> {
+    let mut pool: Vec<ComponentVariant> = variants
+        .iter()
+        .filter(|v| v.component_type != ENSEMBLE_SIGNAL)
+        .cloned()
+        .collect();
+    let mut children = Vec::with_capacity(2);
+    while children.len() < 2 && !pool.is_empty() {
+        let variant = weighted_select(rng, &pool).clone();
+        pool.retain(|v| v.component_type != variant.component_type);
+        children.push(fix_cross_param_constraints(round_discrete_params(
+            sample_params(rng, &variant, jitter),
+            &DISCRETE_PARAMS,
+        )));
+    }
+    children
+}
+
+/// Default children for a composite PM built from `variants` at default params:
+/// the fixed stop-loss floor plus the first (highest-weight) trailing PM.
+///
+/// Used where a composite is picked without sampling (e.g. the TUI strategy panel).
+pub fn default_composite_children(variants: &[ComponentVariant]) -> Vec<ComponentConfig> {
+    let floor = variants
+        .iter()
+        .find(|v| v.component_type == COMPOSITE_FLOOR_PM);
+    let trailing = variants
+        .iter()
+        .find(|v| v.component_type != COMPOSITE_PM && v.component_type != COMPOSITE_FLOOR_PM);
+    floor
+        .into_iter()
+        .chain(trailing)
+        .map(|v| ComponentConfig {
+            component_type: v.component_type.clone(),
+            params: v
+                .param_ranges
+                .iter()
+                .map(|r| (r.name.clone(), r.default))
+                .collect(),
+            children: Vec::new(),
+        })
+        .collect()
+}
+
 fn weighted_select<'a, R: Rng>(
     rng: &mut R,
     variants: &'a [ComponentVariant],
@@ -604,12 +729,142 @@ fn fix_cross_param_constraints(mut config: ComponentConfig) -> ComponentConfig {
     config
 }
 
+/// Enforce constraints between the signal and the position manager.
+///
+/// - `donchian_breakout` + `donchian_exit` (directly or as a composite child):
+///   exit_lookback must be < entry_lookback, compared as the whole bars the
+///   factory uses. A violating exit is halved from the entry lookback (the
+///   turtle 20/10 ratio).
+fn fix_cross_component_constraints(mut config: StrategyConfig) -> StrategyConfig {
+    if config.signal.component_type == "donchian_breakout" {
+        let entry = config
+            .signal
+            .params
+            .get("entry_lookback")
+            .copied()
+            .unwrap_or(50.0)
+            .trunc();
+        fix_donchian_exit_lookback(&mut config.position_manager, entry);
+    }
+    config
+}
+
+fn fix_donchian_exit_lookback(pm: &mut ComponentConfig, entry: f64) {
+    if pm.component_type == "donchian_exit" {
+        let exit = pm.params.get("exit_lookback").copied().unwrap_or(20.0);
+        if exit.trunc() >= entry {
+            pm.params
+                .insert("exit_lookback".into(), (entry / 2.0).trunc().max(1.0));
+        }
+    }
+    for child in &mut pm.children {
+        fix_donchian_exit_lookback(child, entry);
+    }
+}
+
+/// A cross-parameter constraint a config breaks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstraintViolation {
+    pub slot: ComponentSlot,
+    /// e.g. "ma_crossover slow_period must be greater than fast_period (fast 30, slow 20)".
+    pub message: String,
+}
+
+impl fmt::Display for ConstraintViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.slot.name(), self.message)
+    }
+}
+
+/// Cross-parameter constraints `config` breaks, in slot order.
+///
+/// The checks behind `fix_cross_param_constraints` and
+/// `fix_cross_component_constraints`, reported instead of repaired: the
+/// factory constructors assert on them, so a hand-edited config must pass
+/// before it is built. Composite children are checked too.
+pub fn constraint_violations(config: &StrategyConfig) -> Vec<ConstraintViolation> {
+    let mut violations = Vec::new();
+    for slot in ComponentSlot::ALL {
+        let mut messages = Vec::new();
+        component_violations(slot.component(config), &mut messages);
+        if slot == ComponentSlot::PositionManager
+            && config.signal.component_type == "donchian_breakout"
+        {
+            let entry = param_or(&config.signal, "entry_lookback", 50.0).trunc();
+            donchian_exit_violations(&config.position_manager, entry, &mut messages);
+        }
+        violations.extend(
+            messages
+                .into_iter()
+                .map(|message| ConstraintViolation { slot, message }),
+        );
+    }
+    violations
+}
+
+fn param_or(config: &ComponentConfig, name: &str, default: f64) -> f64 {
+    config.params.get(name).copied().unwrap_or(default)
+}
+
+fn component_violations(config: &ComponentConfig, out: &mut Vec<String>) {
+    let name = config.component_type.as_str();
+    match name {
+        "ma_crossover" => {
+            let fast = param_or(config, "fast_period", 10.0);
+            let slow = param_or(config, "slow_period", 50.0);
+            if slow <= fast {
+                out.push(format!(
+                    "{name} slow_period must be greater than fast_period (fast {fast}, slow {slow})"
+                ));
+            }
+        }
+        "volatility_filter" => {
+            let min_pct = param_or(config, "min_pct", 0.5);
+            let max_pct = param_or(config, "max_pct", 5.0);
+            if max_pct < min_pct {
+                out.push(format!(
+                    "{name} max_pct must be at least min_pct (min {min_pct}, max {max_pct})"
+                ));
+            }
+        }
+        "time_decay" => {
+            let initial = param_or(config, "initial_pct", 0.10);
+            let min = param_or(config, "min_pct", 0.02);
+            if min >= initial {
+                out.push(format!(
+                    "{name} min_pct must be below initial_pct (min {min}, initial {initial})"
+                ));
+            }
+        }
+        _ => {}
+    }
+    for child in &config.children {
+        component_violations(child, out);
+    }
+}
+
+fn donchian_exit_violations(pm: &ComponentConfig, entry: f64, out: &mut Vec<String>) {
+    if pm.component_type == "donchian_exit" {
+        let exit = param_or(pm, "exit_lookback", 20.0);
+        if exit.trunc() >= entry {
+            out.push(format!(
+                "donchian_exit exit_lookback must be below the donchian_breakout \
+                 entry_lookback (exit {exit}, entry {entry})"
+            ));
+        }
+    }
+    for child in &pm.children {
+        donchian_exit_violations(child, entry, out);
+    }
+}
+
 // ─── Tests ───────────────────────────────────────────────────────────
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::components::factory::{create_execution, create_filter, create_pm, create_signal};
+    use crate::engine::entry_governance::PARAM_COOLDOWN_BARS;
     use rand::rngs::StdRng;
     use rand::SeedableRng;
 
@@ -768,6 +1023,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cooldown_is_explored_only_when_exploring() {
+        let pool = ComponentPool::default_pool();
+        let mut rng = StdRng::seed_from_u64(999);
+
+        for _ in 0..200 {
+            let config = sample_composition(&pool, &mut rng, 0.5, 0.0);
+            assert!(!config.signal.params.contains_key(PARAM_COOLDOWN_BARS));
+        }
+
+        let cooldowns: Vec<f64> = (0..200)
+            .filter_map(|_| {
+                sample_composition(&pool, &mut rng, 0.5, 1.0)
+                    .signal
+                    .params
+                    .get(PARAM_COOLDOWN_BARS)
+                    .copied()
+            })
+            .collect();
+        assert!(cooldowns.len() > 100);
+        for &bars in &cooldowns {
+            assert!((1.0..=20.0).contains(&bars) && bars.fract() == 0.0);
+        }
+    }
+
     // ── Parameter bounds are always respected ────────────────────
 
     #[test]
@@ -863,8 +1143,8 @@ mod tests {
     #[test]
     fn default_pool_has_correct_variant_counts() {
         let pool = ComponentPool::default_pool();
-        assert_eq!(pool.signals.len(), 10, "Expected 10 signals");
-        assert_eq!(pool.position_managers.len(), 9, "Expected 9 PMs");
+        assert_eq!(pool.signals.len(), 13, "Expected 13 signals");
+        assert_eq!(pool.position_managers.len(), 11, "Expected 11 PMs");
         assert_eq!(
             pool.execution_models.len(),
             4,
@@ -873,6 +1153,74 @@ mod tests {
         assert_eq!(pool.filters.len(), 4, "Expected 4 filters");
     }
 
+    // ── Composite PMs get buildable children ─────────────────────
+
+    #[test]
+    fn sampled_composite_pm_has_buildable_children() {
+        let pool = ComponentPool::default_pool();
+        let mut rng = StdRng::seed_from_u64(1578);
+
+        let mut composites = 0;
+        for _ in 0..2000 {
+            let config = sample_composition(&pool, &mut rng, 1.0, 1.0);
+            let pm = &config.position_manager;
+            if pm.component_type != "composite" {
+                assert!(pm.children.is_empty());
+                continue;
+            }
+            composites += 1;
+            assert_eq!(pm.children.len(), 2);
+            assert_eq!(pm.children[0].component_type, "fixed_stop_loss");
+            for child in &pm.children {
+                assert_ne!(child.component_type, "composite");
+                check_bounds(child, &pool.position_managers);
+            }
+            create_pm(pm).unwrap();
+        }
+        // Low weight: present, but rare
+        assert!(composites > 0 && composites < 200, "got {composites}");
+    }
+
+    #[test]
+    fn signal_ensembles_are_opt_in_and_buildable() {
+        let mut rng = StdRng::seed_from_u64(1651);
+        let plain = ComponentPool::default_pool();
+        assert!(plain.signals.iter().all(|v| v.component_type != "ensemble"));
+
+        let pool = ComponentPool::default_pool().with_signal_ensembles();
+        let mut ensembles = 0;
+        for _ in 0..2000 {
+            let config = sample_composition(&pool, &mut rng, 1.0, 1.0);
+            let signal = &config.signal;
+            if signal.component_type != "ensemble" {
+                assert!(signal.children.is_empty());
+                continue;
+            }
+            ensembles += 1;
+            assert_eq!(signal.children.len(), 2);
+            assert_ne!(
+                signal.children[0].component_type,
+                signal.children[1].component_type
+            );
+            for child in &signal.children {
+                check_bounds(child, &pool.signals);
+            }
+            assert_eq!(create_signal(signal).unwrap().name(), "ensemble");
+        }
+        // Low weight: present, but rare
+        assert!(ensembles > 0 && ensembles < 200, "got {ensembles}");
+    }
+
+    #[test]
+    fn default_composite_children_use_defaults() {
+        let pool = ComponentPool::default_pool();
+        let children = default_composite_children(&pool.position_managers);
+        let types: Vec<&str> = children.iter().map(|c| c.component_type.as_str()).collect();
+        assert_eq!(types, vec!["fixed_stop_loss", "atr_trailing"]);
+        assert_eq!(children[0].params["stop_pct"], 0.02);
+        assert_eq!(children[1].params["atr_period"], 14.0);
+    }
+
     // ── Weighted selection respects weights ──────────────────────
 
     #[test]
@@ -906,4 +1254,317 @@ mod tests {
             heavy_count,
         );
     }
+
+    // ── Parameter grid ─────────────────────────────────────────
+
+    fn base_config() -> StrategyConfig {
+        let pool = ComponentPool::default_pool();
+        let mut rng = StdRng::seed_from_u64(0);
+        sample_composition(&pool, &mut rng, 0.0, 0.0)
+    }
+
+    #[test]
+    fn grid_values_span_range() {
+        let range = ParamRange {
+            name: "period".into(),
+            default: 20.0,
+            min: 10.0,
+            max: 30.0,
+        };
+        assert_eq!(range.grid_values(3), vec![10.0, 20.0, 30.0]);
+        assert_eq!(range.grid_values(1), vec![20.0]);
+    }
+
+    #[test]
+    fn grid_expands_cartesian_product() {
+        let base = base_config();
+        let grid = ParamGrid::new()
+            .with_axis(
+                ComponentSlot::Signal,
+                "entry_lookback",
+                vec![20.0, 50.0, 100.0],
+            )
+            .with_axis(ComponentSlot::PositionManager, "multiplier", vec![2.0, 3.0]);
+        assert_eq!(grid.len(), 6);
+
+        let configs = grid.expand(&base);
+        assert_eq!(configs.len(), 6);
+        for config in &configs {
+            assert_eq!(config.config_hash(), base.config_hash());
+        }
+        assert!(configs
+            .iter()
+            .any(|c| c.signal.params["entry_lookback"] == 100.0));
+    }
+
+    #[test]
+    fn empty_grid_yields_base() {
+        let base = base_config();
+        assert_eq!(ParamGrid::new().expand(&base), vec![base]);
+    }
+
+    #[test]
+    fn grid_from_pool_skips_discrete_and_passes_factories() {
+        let pool = ComponentPool::default_pool();
+        let mut base = base_config();
+        base.signal = ComponentConfig {
+            component_type: "ma_crossover".into(),
+            params: BTreeMap::from([
+                ("fast_period".into(), 10.0),
+                ("slow_period".into(), 50.0),
+                ("ma_type".into(), 0.0),
+            ]),
+            children: Vec::new(),
+        };
+
+        let grid = ParamGrid::from_pool(&pool, &base, &[ComponentSlot::Signal], 3);
+        assert_eq!(grid.axes.len(), 2, "ma_type must not become an axis");
+
+        for config in grid.expand(&base) {
+            create_signal(&config.signal).unwrap();
+            assert!(config.signal.params["slow_period"] > config.signal.params["fast_period"]);
+        }
+    }
+
+    fn donchian_exit_lookbacks(pm: &ComponentConfig, out: &mut Vec<f64>) {
+        if pm.component_type == "donchian_exit" {
+            out.push(pm.params["exit_lookback"]);
+        }
+        for child in &pm.children {
+            donchian_exit_lookbacks(child, out);
+        }
+    }
+
+    #[test]
+    fn donchian_exit_lookback_stays_below_entry() {
+        let pool = ComponentPool::default_pool();
+        let mut rng = StdRng::seed_from_u64(11);
+        let mut pairs = 0;
+        for _ in 0..2000 {
+            let config = sample_composition(&pool, &mut rng, 1.0, 1.0);
+            if config.signal.component_type != "donchian_breakout" {
+                continue;
+            }
+            let entry = config.signal.params["entry_lookback"] as usize;
+            let mut exits = Vec::new();
+            donchian_exit_lookbacks(&config.position_manager, &mut exits);
+            for exit in exits {
+                assert!((exit as usize) < entry, "exit {exit} >= entry {entry}");
+                pairs += 1;
+            }
+        }
+        assert!(pairs > 0, "no donchian entry/exit pair sampled");
+
+        // Grid: violating points are pulled under the entry, and both
+        // lookbacks feed the fingerprint
+        let mut base = base_config();
+        base.position_manager = ComponentConfig {
+            component_type: "donchian_exit".into(),
+            params: BTreeMap::from([("exit_lookback".into(), 10.0)]),
+            children: Vec::new(),
+        };
+        let grid = ParamGrid::new()
+            .with_axis(ComponentSlot::Signal, "entry_lookback", vec![20.0, 55.0])
+            .with_axis(
+                ComponentSlot::PositionManager,
+                "exit_lookback",
+                vec![10.0, 20.0, 60.0],
+            );
+        let configs = grid.expand(&base);
+        let points: Vec<(f64, f64)> = configs
+            .iter()
+            .map(|c| {
+                (
+                    c.signal.params["entry_lookback"],
+                    c.position_manager.params["exit_lookback"],
+                )
+            })
+            .collect();
+        // (20, 20) and (20, 60) both collapse onto (20, 10); (55, 60) becomes (55, 27)
+        assert_eq!(
+            points,
+            vec![(20.0, 10.0), (55.0, 10.0), (55.0, 20.0), (55.0, 27.0)]
+        );
+        let hashes: std::collections::HashSet<_> = configs.iter().map(|c| c.full_hash()).collect();
+        assert_eq!(hashes.len(), configs.len());
+    }
+
+    // ── Hand edits ──────────────────────────────────────────────
+
+    #[test]
+    fn nudge_steps_keep_whole_bars_and_enum_codes() {
+        let pool = ComponentPool::default_pool();
+        let range = |slot, component, param| pool.param_range(slot, component, param).unwrap();
+
+        let lookback = range(ComponentSlot::Signal, "donchian_breakout", "entry_lookback");
+        let step = lookback.nudge_step();
+        assert_eq!(step.fract(), 0.0);
+        assert!(step >= 1.0);
+        assert_eq!(
+            range(ComponentSlot::Signal, "ma_crossover", "ma_type").nudge_step(),
+            1.0
+        );
+        let multiplier = range(ComponentSlot::PositionManager, "atr_trailing", "multiplier");
+        assert_eq!(
+            multiplier.nudge_step(),
+            (multiplier.max - multiplier.min) / 20.0
+        );
+        assert!(pool
+            .param_range(ComponentSlot::Signal, "donchian_breakout", "multiplier")
+            .is_none());
+    }
+
+    #[test]
+    fn constraint_violations_name_the_broken_rule() {
+        let component = |component_type: &str, params: &[(&str, f64)]| ComponentConfig {
+            component_type: component_type.into(),
+            params: params.iter().map(|&(k, v)| (k.to_string(), v)).collect(),
+            children: Vec::new(),
+        };
+        let mut config = base_config();
+        config.signal = component("donchian_breakout", &[("entry_lookback", 50.0)]);
+        config.position_manager = component("donchian_exit", &[("exit_lookback", 20.0)]);
+        assert!(constraint_violations(&config).is_empty());
+
+        config
+            .position_manager
+            .params
+            .insert("exit_lookback".into(), 50.0);
+        let violations = constraint_violations(&config);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].slot, ComponentSlot::PositionManager);
+        assert!(violations[0].message.contains("exit 50, entry 50"));
+
+        config.signal = component(
+            "ma_crossover",
+            &[("fast_period", 30.0), ("slow_period", 20.0)],
+        );
+        config.position_manager = component("composite", &[]);
+        config.position_manager.children = vec![component(
+            "time_decay",
+            &[("initial_pct", 0.05), ("min_pct", 0.05)],
+        )];
+        let violations: Vec<String> = constraint_violations(&config)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            violations,
+            vec![
+                "signal: ma_crossover slow_period must be greater than fast_period \
+                 (fast 30, slow 20)",
+                "position_manager: time_decay min_pct must be below initial_pct \
+                 (min 0.05, initial 0.05)",
+            ]
+        );
+
+        // What the repair functions produce passes
+        config.signal = fix_cross_param_constraints(config.signal);
+        config.position_manager.children[0] =
+            fix_cross_param_constraints(config.position_manager.children[0].clone());
+        assert!(constraint_violations(&config).is_empty());
+    }
+
+    // ── Mutation around a parent ────────────────────────────────
+
+    // ── Compatibility-aware sampling ──
+
+    fn short_dataset() -> CompatibilityContext {
+        CompatibilityContext {
+            bar_count: Some(300),
+            min_post_warmup_bars: 60,
+        }
+    }
+
+    #[test]
+    fn conditioned_pool_caps_lookbacks() {
+        let pool = ComponentPool::default_pool().conditioned_on(&short_dataset());
+        let breakout = pool
+            .signals
+            .iter()
+            .find(|v| v.component_type == "breakout_52w")
+            .unwrap();
+        let lookback = &breakout.param_ranges[0];
+        assert_eq!(lookback.max, 240.0);
+        assert_eq!(lookback.default, 240.0);
+        // Non-lookback params keep their ranges
+        assert_eq!(breakout.param_ranges[1].max, 5.0);
+
+        let unbounded =
+            ComponentPool::default_pool().conditioned_on(&CompatibilityContext::default());
+        assert_eq!(unbounded.signals[2].param_ranges[0].max, 504.0);
+    }
+
+    #[test]
+    fn compatible_samples_pass_incompatible_rules() {
+        let pool = ComponentPool::default_pool();
+        let context = short_dataset();
+        let mut rejected = 0;
+        for seed in 0..200 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let sample = sample_compatible_composition(&pool, &mut rng, 1.0, 1.0, &context);
+            let check = check_compatibility(&sample.config, &context);
+            assert!(check.is_compatible(), "seed {seed}: {:?}", check.warnings);
+            rejected += sample.rejections.len();
+        }
+        assert!(rejected > 0, "full exploration should hit a rule");
+    }
+
+    #[test]
+    fn unsatisfiable_context_stops_after_max_resamples() {
+        let pool = ComponentPool::default_pool();
+        let context = CompatibilityContext {
+            bar_count: Some(10),
+            min_post_warmup_bars: 60,
+        };
+        let mut rng = StdRng::seed_from_u64(7);
+        let sample = sample_compatible_composition(&pool, &mut rng, 0.5, 0.5, &context);
+        let rejected_for_warmup = sample
+            .rejections
+            .iter()
+            .filter(|r| **r == "min_post_warmup_bars")
+            .count();
+        assert_eq!(rejected_for_warmup, MAX_RESAMPLES);
+    }
+
+    #[test]
+    fn mutation_keeps_structure_and_bounds() {
+        let pool = ComponentPool::default_pool();
+        let mut rng = StdRng::seed_from_u64(77);
+
+        for _ in 0..300 {
+            let parent = sample_composition(&pool, &mut rng, 1.0, 1.0);
+            let (child, provenance) = mutate_composition(&pool, &parent, &mut rng, 0.3);
+
+            assert_eq!(child.config_hash(), parent.config_hash());
+            assert_eq!(provenance.parent_full_hash, parent.full_hash());
+            assert_eq!(provenance.changes, parent.diff(&child));
+            check_bounds(&child.signal, &pool.signals);
+            check_bounds(&child.position_manager, &pool.position_managers);
+            check_bounds(&child.signal_filter, &pool.filters);
+            create_signal(&child.signal).unwrap();
+            create_pm(&child.position_manager).unwrap();
+            create_execution(&child.execution_model).unwrap();
+            create_filter(&child.signal_filter).unwrap();
+        }
+    }
+
+    #[test]
+    fn mutation_stays_near_parent() {
+        let pool = ComponentPool::default_pool();
+        let parent = base_config();
+        let mut rng = StdRng::seed_from_u64(5);
+
+        let (same, provenance) = mutate_composition(&pool, &parent, &mut rng, 0.0);
+        assert_eq!(same, parent);
+        assert!(provenance.changes.is_empty());
+
+        let range = &pool.signals[0].param_ranges[0];
+        let spread = range.max - range.min;
+        for _ in 0..100 {
+            let (child, _) = mutate_composition(&pool, &parent, &mut rng, 0.2);
+            let delta = child.signal.params[&range.name] - parent.signal.params[&range.name];
+            assert!(delta.abs() <= spread * 0.1 + 1e-9);
+        }
+    }
 }
@@ -0,0 +1,237 @@
+//! ADX trend signal - ADX rises through a threshold, direction from the DIs.
+//!
+//! Uses precomputed `adx_{period}`, `plus_di_{period}` and `minus_di_{period}`
+//! indicators. Fires on the bar ADX crosses above `threshold`: Long when +DI
+//! is above -DI, Short when -DI is above +DI.
+
+use crate::components::indicator::IndicatorValues;
+use crate::domain::{BarSeries, SignalEventId};
+
+use super::{
+    SignalDirection, SignalEvent, SignalGenerator, REFERENCE_PRICE, SIGNAL_BAR_HIGH, SIGNAL_BAR_LOW,
+};
+use std::collections::HashMap;
+
+/// ADX trend-strength signal.
+///
+/// Fires when a trend starts to strengthen rather than on every bar of a
+/// strong trend. Strength is ADX / 100, capped at 1.0.
+#[derive(Debug, Clone)]
+pub struct AdxTrend {
+    pub period: usize,
+    pub threshold: f64,
+    adx_key: String,
+    plus_key: String,
+    minus_key: String,
+}
+
+impl AdxTrend {
+    pub fn new(period: usize, threshold: f64) -> Self {
+        assert!(period >= 1, "period must be >= 1");
+        Self {
+            period,
+            threshold,
+            adx_key: format!("adx_{period}"),
+            plus_key: format!("plus_di_{period}"),
+            minus_key: format!("minus_di_{period}"),
+        }
+    }
+
+    pub fn default_params() -> Self {
+        Self::new(14, 25.0)
+    }
+}
+
+impl SignalGenerator for AdxTrend {
+    fn name(&self) -> &str {
+        "adx_trend"
+    }
+
+    fn warmup_bars(&self) -> usize {
+        2 * self.period + 1 // ADX lookback, plus the previous bar for the cross
+    }
+
+    fn evaluate(
+        &self,
+        bars: &dyn BarSeries,
+        bar_index: usize,
+        indicators: &IndicatorValues,
+    ) -> Option<SignalEvent> {
+        if bar_index < self.warmup_bars() || bar_index == 0 {
+            return None;
+        }
+
+        let bar = bars.bar(bar_index);
+        if bar.close.is_nan() {
+            return None;
+        }
+
+        let adx = indicators.get(&self.adx_key, bar_index)?;
+        let prev_adx = indicators.get(&self.adx_key, bar_index - 1)?;
+        let plus_di = indicators.get(&self.plus_key, bar_index)?;
+        let minus_di = indicators.get(&self.minus_key, bar_index)?;
+
+        if adx.is_nan() || prev_adx.is_nan() || plus_di.is_nan() || minus_di.is_nan() {
+            return None;
+        }
+
+        if !(adx > self.threshold && prev_adx <= self.threshold) {
+            return None;
+        }
+
+        let direction = if plus_di > minus_di {
+            SignalDirection::Long
+        } else if minus_di > plus_di {
+            SignalDirection::Short
+        } else {
+            return None;
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("adx".into(), adx);
+        metadata.insert("plus_di".into(), plus_di);
+        metadata.insert("minus_di".into(), minus_di);
+        metadata.insert(REFERENCE_PRICE.into(), bar.close);
+        metadata.insert(SIGNAL_BAR_HIGH.into(), bar.high);
+        metadata.insert(SIGNAL_BAR_LOW.into(), bar.low);
+
+        Some(SignalEvent {
+            id: SignalEventId(0),
+            bar_index,
+            date: bar.date,
+            symbol: bar.symbol.to_string(),
+            direction,
+            strength: (adx / 100.0).min(1.0),
+            metadata,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::indicator::Indicator;
+    use crate::domain::Bar;
+    use crate::indicators::{Adx, DirectionalIndicator};
+    use chrono::NaiveDate;
+
+    fn make_bars(closes: &[f64]) -> Vec<Bar> {
+        let base_date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| Bar {
+                symbol: "SPY".into(),
+                date: base_date + chrono::Duration::days(i as i64),
+                open: close - 0.5,
+                high: close + 1.0,
+                low: close - 1.0,
+                close,
+                volume: 1000,
+                adj_close: close,
+            })
+            .collect()
+    }
+
+    fn compute(sig: &AdxTrend, bars: &[Bar]) -> IndicatorValues {
+        let mut iv = IndicatorValues::new();
+        let indicators: [Box<dyn Indicator>; 3] = [
+            Box::new(Adx::new(sig.period)),
+            Box::new(DirectionalIndicator::plus(sig.period)),
+            Box::new(DirectionalIndicator::minus(sig.period)),
+        ];
+        for ind in indicators {
+            iv.insert(ind.name().to_string(), ind.compute(bars));
+        }
+        iv
+    }
+
+    fn fires(sig: &AdxTrend, bars: &[Bar]) -> Vec<(usize, SignalDirection)> {
+        let iv = compute(sig, bars);
+        (0..bars.len())
+            .filter_map(|i| sig.evaluate(&bars, i, &iv).map(|e| (i, e.direction)))
+            .collect()
+    }
+
+    /// Oscillates around 100 for `range` bars, then trends by `slope` per bar.
+    fn range_then_trend(range: usize, trend: usize, slope: f64) -> Vec<f64> {
+        (0..range + trend)
+            .map(|i| {
+                if i < range {
+                    100.0 + if i % 2 == 0 { 1.5 } else { -1.5 }
+                } else {
+                    100.0 + slope * (i - range + 1) as f64
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fires_long_once_when_uptrend_strengthens() {
+        let sig = AdxTrend::new(5, 25.0);
+        let bars = make_bars(&range_then_trend(30, 20, 2.0));
+        let fired = fires(&sig, &bars);
+        assert_eq!(fired.len(), 1, "{fired:?}");
+        assert_eq!(fired[0].1, SignalDirection::Long);
+        assert!(fired[0].0 >= 30);
+    }
+
+    #[test]
+    fn fires_short_when_downtrend_strengthens() {
+        let sig = AdxTrend::new(5, 25.0);
+        let bars = make_bars(&range_then_trend(30, 20, -2.0));
+        let fired = fires(&sig, &bars);
+        assert_eq!(fired.len(), 1, "{fired:?}");
+        assert_eq!(fired[0].1, SignalDirection::Short);
+    }
+
+    #[test]
+    fn no_fire_in_ranging_market() {
+        let sig = AdxTrend::new(5, 25.0);
+        let bars = make_bars(&range_then_trend(60, 0, 0.0));
+        assert!(fires(&sig, &bars).is_empty());
+    }
+
+    #[test]
+    fn warmup_guard() {
+        let sig = AdxTrend::new(5, 0.0);
+        let bars = make_bars(&range_then_trend(0, 30, 2.0));
+        let iv = compute(&sig, &bars);
+        for i in 0..sig.warmup_bars() {
+            assert!(sig.evaluate(&bars, i, &iv).is_none());
+        }
+    }
+
+    #[test]
+    fn nan_guard() {
+        let sig = AdxTrend::new(5, 25.0);
+        let mut bars = make_bars(&range_then_trend(30, 20, 2.0));
+        let (fire_bar, _) = fires(&sig, &bars)[0];
+        let iv = compute(&sig, &bars);
+        bars[fire_bar].close = f64::NAN;
+        assert!(sig.evaluate(&bars, fire_bar, &iv).is_none());
+    }
+
+    #[test]
+    fn metadata_correctness() {
+        let sig = AdxTrend::new(5, 25.0);
+        let bars = make_bars(&range_then_trend(30, 20, 2.0));
+        let iv = compute(&sig, &bars);
+        let (fire_bar, _) = fires(&sig, &bars)[0];
+        let event = sig.evaluate(&bars, fire_bar, &iv).unwrap();
+        let m = &event.metadata;
+        assert_eq!(m["adx"], iv.get("adx_5", fire_bar).unwrap());
+        assert!(m["adx"] > 25.0);
+        assert!(m["plus_di"] > m["minus_di"]);
+        assert_eq!(m["reference_price"], bars[fire_bar].close);
+        assert_eq!(m["signal_bar_high"], bars[fire_bar].high);
+        assert!((event.strength - m["adx"] / 100.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn name_and_warmup() {
+        let sig = AdxTrend::default_params();
+        assert_eq!(sig.name(), "adx_trend");
+        assert_eq!(sig.warmup_bars(), 29);
+    }
+}
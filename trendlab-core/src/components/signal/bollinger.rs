@@ -4,9 +4,12 @@
 //! the lookback `period`). This is a volatility-adjusted breakout signal.
 
 use crate::components::indicator::IndicatorValues;
-use crate::domain::{Bar, SignalEventId};
+use crate::domain::{BarSeries, SignalEventId};
 
-use super::{SignalDirection, SignalEvent, SignalGenerator};
+use super::{
+    SignalDirection, SignalEvent, SignalGenerator, BREAKOUT_LEVEL, REFERENCE_PRICE,
+    SIGNAL_BAR_HIGH, SIGNAL_BAR_LOW,
+};
 use std::collections::HashMap;
 
 /// Bollinger Band upper-channel breakout signal.
@@ -50,7 +53,7 @@ impl SignalGenerator for BollingerBreakout {
 
     fn evaluate(
         &self,
-        bars: &[Bar],
+        bars: &dyn BarSeries,
         bar_index: usize,
         indicators: &IndicatorValues,
     ) -> Option<SignalEvent> {
@@ -58,7 +61,7 @@ impl SignalGenerator for BollingerBreakout {
             return None;
         }
 
-        let bar = &bars[bar_index];
+        let bar = bars.bar(bar_index);
         if bar.close.is_nan() {
             return None;
         }
@@ -70,15 +73,16 @@ impl SignalGenerator for BollingerBreakout {
 
         if bar.close > bollinger_upper {
             let mut metadata = HashMap::new();
-            metadata.insert("breakout_level".into(), bollinger_upper);
-            metadata.insert("reference_price".into(), bar.close);
-            metadata.insert("signal_bar_low".into(), bar.low);
+            metadata.insert(BREAKOUT_LEVEL.into(), bollinger_upper);
+            metadata.insert(REFERENCE_PRICE.into(), bar.close);
+            metadata.insert(SIGNAL_BAR_HIGH.into(), bar.high);
+            metadata.insert(SIGNAL_BAR_LOW.into(), bar.low);
 
             Some(SignalEvent {
                 id: SignalEventId(0),
                 bar_index,
                 date: bar.date,
-                symbol: bar.symbol.clone(),
+                symbol: bar.symbol.to_string(),
                 direction: SignalDirection::Long,
                 strength: 1.0,
                 metadata,
@@ -92,6 +96,7 @@ impl SignalGenerator for BollingerBreakout {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::Bar;
     use chrono::NaiveDate;
 
     fn make_bars(n: usize, breakout_at: Option<usize>) -> Vec<Bar> {
@@ -100,7 +105,7 @@ mod tests {
             .map(|i| {
                 let close = if breakout_at == Some(i) { 120.0 } else { 100.0 };
                 Bar {
-                    symbol: "SPY".to_string(),
+                    symbol: "SPY".into(),
                     date: base_date + chrono::Duration::days(i as i64),
                     open: close - 0.5,
                     high: if breakout_at == Some(i) { 121.0 } else { 102.0 },
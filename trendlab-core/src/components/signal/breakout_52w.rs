@@ -1,9 +1,12 @@
 //! 52-week breakout signal — price exceeds the N-day high times a threshold.
 
 use crate::components::indicator::IndicatorValues;
-use crate::domain::{Bar, SignalEventId};
+use crate::domain::{BarSeries, SignalEventId};
 
-use super::{SignalDirection, SignalEvent, SignalGenerator};
+use super::{
+    SignalDirection, SignalEvent, SignalGenerator, BREAKOUT_LEVEL, REFERENCE_PRICE,
+    SIGNAL_BAR_HIGH, SIGNAL_BAR_LOW,
+};
 use std::collections::HashMap;
 
 /// 52-week (N-day) breakout signal.
@@ -43,7 +46,7 @@ impl SignalGenerator for Breakout52w {
 
     fn evaluate(
         &self,
-        bars: &[Bar],
+        bars: &dyn BarSeries,
         bar_index: usize,
         indicators: &IndicatorValues,
     ) -> Option<SignalEvent> {
@@ -51,7 +54,7 @@ impl SignalGenerator for Breakout52w {
             return None;
         }
 
-        let bar = &bars[bar_index];
+        let bar = bars.bar(bar_index);
         if bar.close.is_nan() {
             return None;
         }
@@ -64,15 +67,16 @@ impl SignalGenerator for Breakout52w {
         let threshold = upper * (1.0 + self.threshold_pct / 100.0);
         if bar.close > threshold {
             let mut metadata = HashMap::new();
-            metadata.insert("breakout_level".into(), upper);
-            metadata.insert("reference_price".into(), bar.close);
-            metadata.insert("signal_bar_low".into(), bar.low);
+            metadata.insert(BREAKOUT_LEVEL.into(), upper);
+            metadata.insert(REFERENCE_PRICE.into(), bar.close);
+            metadata.insert(SIGNAL_BAR_HIGH.into(), bar.high);
+            metadata.insert(SIGNAL_BAR_LOW.into(), bar.low);
 
             Some(SignalEvent {
                 id: SignalEventId(0),
                 bar_index,
                 date: bar.date,
-                symbol: bar.symbol.clone(),
+                symbol: bar.symbol.to_string(),
                 direction: SignalDirection::Long,
                 strength: 1.0,
                 metadata,
@@ -86,6 +90,7 @@ impl SignalGenerator for Breakout52w {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::Bar;
     use chrono::NaiveDate;
 
     fn make_bars_with_breakout(n: usize, breakout_at: usize) -> Vec<Bar> {
@@ -94,7 +99,7 @@ mod tests {
             .map(|i| {
                 let close = if i == breakout_at { 120.0 } else { 100.0 };
                 Bar {
-                    symbol: "SPY".to_string(),
+                    symbol: "SPY".into(),
                     date: base_date + chrono::Duration::days(i as i64),
                     open: close - 0.5,
                     high: if i == breakout_at { 121.0 } else { 102.0 },
@@ -4,9 +4,12 @@
 //! This is the classic turtle/channel-breakout signal with no threshold buffer.
 
 use crate::components::indicator::IndicatorValues;
-use crate::domain::{Bar, SignalEventId};
+use crate::domain::{BarSeries, SignalEventId};
 
-use super::{SignalDirection, SignalEvent, SignalGenerator};
+use super::{
+    SignalDirection, SignalEvent, SignalGenerator, BREAKOUT_LEVEL, REFERENCE_PRICE,
+    SIGNAL_BAR_HIGH, SIGNAL_BAR_LOW,
+};
 use std::collections::HashMap;
 
 /// Donchian channel breakout signal.
@@ -45,7 +48,7 @@ impl SignalGenerator for DonchianBreakout {
 
     fn evaluate(
         &self,
-        bars: &[Bar],
+        bars: &dyn BarSeries,
         bar_index: usize,
         indicators: &IndicatorValues,
     ) -> Option<SignalEvent> {
@@ -53,7 +56,7 @@ impl SignalGenerator for DonchianBreakout {
             return None;
         }
 
-        let bar = &bars[bar_index];
+        let bar = bars.bar(bar_index);
         if bar.close.is_nan() {
             return None;
         }
@@ -65,15 +68,16 @@ impl SignalGenerator for DonchianBreakout {
 
         if bar.close > donchian_upper {
             let mut metadata = HashMap::new();
-            metadata.insert("breakout_level".into(), donchian_upper);
-            metadata.insert("reference_price".into(), bar.close);
-            metadata.insert("signal_bar_low".into(), bar.low);
+            metadata.insert(BREAKOUT_LEVEL.into(), donchian_upper);
+            metadata.insert(REFERENCE_PRICE.into(), bar.close);
+            metadata.insert(SIGNAL_BAR_HIGH.into(), bar.high);
+            metadata.insert(SIGNAL_BAR_LOW.into(), bar.low);
 
             Some(SignalEvent {
                 id: SignalEventId(0),
                 bar_index,
                 date: bar.date,
-                symbol: bar.symbol.clone(),
+                symbol: bar.symbol.to_string(),
                 direction: SignalDirection::Long,
                 strength: 1.0,
                 metadata,
@@ -87,6 +91,7 @@ impl SignalGenerator for DonchianBreakout {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::Bar;
     use chrono::NaiveDate;
 
     fn make_bars(n: usize, breakout_at: Option<usize>) -> Vec<Bar> {
@@ -95,7 +100,7 @@ mod tests {
             .map(|i| {
                 let close = if breakout_at == Some(i) { 120.0 } else { 100.0 };
                 Bar {
-                    symbol: "SPY".to_string(),
+                    symbol: "SPY".into(),
                     date: base_date + chrono::Duration::days(i as i64),
                     open: close - 0.5,
                     high: if breakout_at == Some(i) { 121.0 } else { 102.0 },
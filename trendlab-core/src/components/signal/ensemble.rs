@@ -0,0 +1,319 @@
+//! Ensemble — fires when K of N child signals agree on the same bar.
+//!
+//! Every child is evaluated on every bar. The ensemble fires in a direction
+//! when at least `k` children fired that way on this bar. If both directions
+//! reach `k` (possible when `k <= n / 2`) the children disagree and nothing
+//! fires.
+//!
+//! The emitted event carries the standard bar metadata, plus one
+//! `ensemble_child_<i>` key per agreeing child (its index in the child list,
+//! valued at that child's strength) and [`ENSEMBLE_AGREEING`]. When agreeing
+//! children report a [`BREAKOUT_LEVEL`], the ensemble reports the last level
+//! crossed: the highest for longs, the lowest for shorts.
+
+use std::collections::HashMap;
+
+use crate::components::indicator::IndicatorValues;
+use crate::domain::{BarSeries, SignalEventId};
+
+use super::{
+    SignalDirection, SignalEvent, SignalGenerator, BREAKOUT_LEVEL, REFERENCE_PRICE,
+    SIGNAL_BAR_HIGH, SIGNAL_BAR_LOW,
+};
+
+/// Metadata key: number of children that agreed.
+pub const ENSEMBLE_AGREEING: &str = "ensemble_agreeing";
+/// Metadata key prefix: `ensemble_child_<i>` for each agreeing child.
+pub const ENSEMBLE_CHILD_PREFIX: &str = "ensemble_child_";
+
+/// How the strengths of the agreeing children combine.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StrengthAggregation {
+    #[default]
+    Mean,
+    Min,
+}
+
+impl StrengthAggregation {
+    /// Param selecting the aggregation: 0 = mean, 1 = min.
+    pub const PARAM: &'static str = "strength_agg";
+
+    pub fn from_param(value: f64) -> Self {
+        if value.round() == 1.0 {
+            Self::Min
+        } else {
+            Self::Mean
+        }
+    }
+
+    fn combine(self, strengths: &[f64]) -> f64 {
+        match self {
+            Self::Mean => strengths.iter().sum::<f64>() / strengths.len() as f64,
+            Self::Min => strengths.iter().copied().fold(f64::INFINITY, f64::min),
+        }
+    }
+}
+
+/// K-of-N agreement over child signal generators.
+pub struct EnsembleSignal {
+    children: Vec<Box<dyn SignalGenerator>>,
+    k: usize,
+    aggregation: StrengthAggregation,
+}
+
+impl EnsembleSignal {
+    /// Param holding the number of children that must agree.
+    pub const PARAM_K: &'static str = "k";
+
+    /// `k` is clamped to `1..=children.len()`.
+    pub fn new(
+        children: Vec<Box<dyn SignalGenerator>>,
+        k: usize,
+        aggregation: StrengthAggregation,
+    ) -> Self {
+        assert!(
+            !children.is_empty(),
+            "ensemble signal needs at least one child"
+        );
+        let k = k.clamp(1, children.len());
+        Self {
+            children,
+            k,
+            aggregation,
+        }
+    }
+
+    /// Names of the wrapped children, in evaluation order.
+    pub fn child_names(&self) -> Vec<&str> {
+        self.children.iter().map(|c| c.name()).collect()
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+}
+
+impl SignalGenerator for EnsembleSignal {
+    fn name(&self) -> &str {
+        "ensemble"
+    }
+
+    fn warmup_bars(&self) -> usize {
+        self.children
+            .iter()
+            .map(|c| c.warmup_bars())
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn evaluate(
+        &self,
+        bars: &dyn BarSeries,
+        bar_index: usize,
+        indicators: &IndicatorValues,
+    ) -> Option<SignalEvent> {
+        let fired: Vec<(usize, SignalEvent)> = self
+            .children
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| c.evaluate(bars, bar_index, indicators).map(|e| (i, e)))
+            .collect();
+        let count = |dir| fired.iter().filter(|(_, e)| e.direction == dir).count();
+        let (longs, shorts) = (count(SignalDirection::Long), count(SignalDirection::Short));
+        let direction = match (longs >= self.k, shorts >= self.k) {
+            (true, false) => SignalDirection::Long,
+            (false, true) => SignalDirection::Short,
+            _ => return None,
+        };
+
+        let agreeing: Vec<&(usize, SignalEvent)> = fired
+            .iter()
+            .filter(|(_, e)| e.direction == direction)
+            .collect();
+        let strengths: Vec<f64> = agreeing.iter().map(|(_, e)| e.strength).collect();
+        let bar = bars.bar(bar_index);
+
+        let mut metadata = HashMap::new();
+        metadata.insert(REFERENCE_PRICE.into(), bar.close);
+        metadata.insert(SIGNAL_BAR_HIGH.into(), bar.high);
+        metadata.insert(SIGNAL_BAR_LOW.into(), bar.low);
+        metadata.insert(ENSEMBLE_AGREEING.into(), agreeing.len() as f64);
+        for (i, event) in &agreeing {
+            metadata.insert(format!("{ENSEMBLE_CHILD_PREFIX}{i}"), event.strength);
+        }
+        let levels = agreeing
+            .iter()
+            .filter_map(|(_, e)| e.metadata.get(BREAKOUT_LEVEL).copied());
+        let level = match direction {
+            SignalDirection::Long => levels.reduce(f64::max),
+            SignalDirection::Short => levels.reduce(f64::min),
+        };
+        if let Some(level) = level {
+            metadata.insert(BREAKOUT_LEVEL.into(), level);
+        }
+
+        Some(SignalEvent {
+            id: SignalEventId(0),
+            bar_index,
+            date: bar.date,
+            symbol: bar.symbol.to_string(),
+            direction,
+            strength: self.aggregation.combine(&strengths),
+            metadata,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Bar;
+    use chrono::NaiveDate;
+
+    fn make_bars(n: usize) -> Vec<Bar> {
+        (0..n)
+            .map(|i| Bar {
+                symbol: "SPY".into(),
+                date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()
+                    + chrono::Duration::days(i as i64),
+                open: 100.0,
+                high: 101.0,
+                low: 99.0,
+                close: 100.0,
+                volume: 1000,
+                adj_close: 100.0,
+            })
+            .collect()
+    }
+
+    /// Test child firing `direction` on the listed bars.
+    struct Scripted {
+        fires: Vec<(usize, SignalDirection)>,
+        strength: f64,
+        level: Option<f64>,
+        warmup: usize,
+    }
+
+    impl Scripted {
+        fn boxed(fires: &[(usize, SignalDirection)], strength: f64) -> Box<dyn SignalGenerator> {
+            Box::new(Self {
+                fires: fires.to_vec(),
+                strength,
+                level: None,
+                warmup: 0,
+            })
+        }
+    }
+
+    impl SignalGenerator for Scripted {
+        fn name(&self) -> &str {
+            "scripted"
+        }
+
+        fn warmup_bars(&self) -> usize {
+            self.warmup
+        }
+
+        fn evaluate(
+            &self,
+            bars: &dyn BarSeries,
+            bar_index: usize,
+            _indicators: &IndicatorValues,
+        ) -> Option<SignalEvent> {
+            let &(_, direction) = self.fires.iter().find(|(b, _)| *b == bar_index)?;
+            let mut metadata = HashMap::new();
+            if let Some(level) = self.level {
+                metadata.insert(BREAKOUT_LEVEL.into(), level);
+            }
+            Some(SignalEvent {
+                id: SignalEventId(0),
+                bar_index,
+                date: bars.bar(bar_index).date,
+                symbol: "SPY".into(),
+                direction,
+                strength: self.strength,
+                metadata,
+            })
+        }
+    }
+
+    fn fire_bars(signal: &EnsembleSignal, bars: &[Bar]) -> Vec<SignalEvent> {
+        let iv = IndicatorValues::new();
+        (0..bars.len())
+            .filter_map(|i| signal.evaluate(&bars, i, &iv))
+            .collect()
+    }
+
+    #[test]
+    fn disagreeing_children_never_fire() {
+        use SignalDirection::*;
+        let bars = make_bars(10);
+        // Opposite directions on bar 3, single-child fires on bars 5 and 7
+        let ensemble = EnsembleSignal::new(
+            vec![
+                Scripted::boxed(&[(3, Long), (5, Long)], 1.0),
+                Scripted::boxed(&[(3, Short), (7, Short)], 1.0),
+            ],
+            2,
+            StrengthAggregation::Mean,
+        );
+        assert!(fire_bars(&ensemble, &bars).is_empty());
+    }
+
+    #[test]
+    fn agreeing_children_fire_once_with_metadata() {
+        use SignalDirection::*;
+        let bars = make_bars(10);
+        let ensemble = EnsembleSignal::new(
+            vec![
+                Scripted::boxed(&[(2, Long), (6, Short)], 0.8),
+                Box::new(Scripted {
+                    fires: vec![(6, Short)],
+                    strength: 0.4,
+                    level: Some(98.5),
+                    warmup: 4,
+                }),
+            ],
+            2,
+            StrengthAggregation::Mean,
+        );
+        assert_eq!(ensemble.warmup_bars(), 4);
+
+        let events = fire_bars(&ensemble, &bars);
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.bar_index, 6);
+        assert_eq!(event.direction, Short);
+        assert!((event.strength - 0.6).abs() < 1e-12);
+        assert_eq!(event.metadata[ENSEMBLE_AGREEING], 2.0);
+        assert_eq!(event.metadata["ensemble_child_0"], 0.8);
+        assert_eq!(event.metadata["ensemble_child_1"], 0.4);
+        assert_eq!(event.metadata[BREAKOUT_LEVEL], 98.5);
+        assert_eq!(event.metadata[REFERENCE_PRICE], 100.0);
+    }
+
+    #[test]
+    fn k_of_n_counts_agreement_and_aggregates_min() {
+        use SignalDirection::*;
+        let bars = make_bars(10);
+        let children = || {
+            vec![
+                Scripted::boxed(&[(4, Long)], 0.9),
+                Scripted::boxed(&[(4, Long)], 0.5),
+                Scripted::boxed(&[(4, Short), (8, Long)], 0.7),
+            ]
+        };
+        let two_of_three = EnsembleSignal::new(children(), 2, StrengthAggregation::Min);
+        let events = fire_bars(&two_of_three, &bars);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].direction, Long);
+        assert_eq!(events[0].strength, 0.5);
+        assert!(!events[0].metadata.contains_key("ensemble_child_2"));
+
+        // One vote each way: a tie is disagreement
+        let one_of_three = EnsembleSignal::new(children(), 1, StrengthAggregation::Min);
+        let events = fire_bars(&one_of_three, &bars);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].bar_index, 8);
+    }
+}
@@ -0,0 +1,284 @@
+//! Ichimoku cloud signal - Tenkan/Kijun cross confirmed by price outside the cloud.
+//!
+//! Uses precomputed `ichimoku_tenkan_{t}`, `ichimoku_kijun_{k}`,
+//! `ichimoku_span_a_{t}_{k}` and `ichimoku_span_b_{s}_{k}` indicators.
+//! Fires Long on the bar where Tenkan is above Kijun and the close is above
+//! the cloud, having not been both on the previous bar. Short mirrors it below
+//! the cloud. Whichever of the cross and the cloud break comes second fires.
+
+use crate::components::indicator::IndicatorValues;
+use crate::domain::{BarSeries, SignalEventId};
+
+use super::{
+    SignalDirection, SignalEvent, SignalGenerator, REFERENCE_PRICE, SIGNAL_BAR_HIGH, SIGNAL_BAR_LOW,
+};
+use std::collections::HashMap;
+
+/// Ichimoku cloud signal.
+///
+/// The cloud at bar t is Senkou span A/B as plotted on t, i.e. built from
+/// bars up to t - kijun. Strength is always 1.0.
+#[derive(Debug, Clone)]
+pub struct IchimokuCloud {
+    pub tenkan: usize,
+    pub kijun: usize,
+    pub senkou_b: usize,
+    tenkan_key: String,
+    kijun_key: String,
+    span_a_key: String,
+    span_b_key: String,
+}
+
+/// Per-bar Ichimoku readings.
+struct Lines {
+    tenkan: f64,
+    kijun: f64,
+    span_a: f64,
+    span_b: f64,
+}
+
+impl Lines {
+    fn cloud_top(&self) -> f64 {
+        self.span_a.max(self.span_b)
+    }
+
+    fn cloud_bottom(&self) -> f64 {
+        self.span_a.min(self.span_b)
+    }
+
+    fn bullish(&self, close: f64) -> bool {
+        self.tenkan > self.kijun && close > self.cloud_top()
+    }
+
+    fn bearish(&self, close: f64) -> bool {
+        self.tenkan < self.kijun && close < self.cloud_bottom()
+    }
+}
+
+impl IchimokuCloud {
+    pub fn new(tenkan: usize, kijun: usize, senkou_b: usize) -> Self {
+        assert!(
+            tenkan >= 1 && kijun >= 1 && senkou_b >= 1,
+            "periods must be >= 1"
+        );
+        Self {
+            tenkan,
+            kijun,
+            senkou_b,
+            tenkan_key: format!("ichimoku_tenkan_{tenkan}"),
+            kijun_key: format!("ichimoku_kijun_{kijun}"),
+            span_a_key: format!("ichimoku_span_a_{tenkan}_{kijun}"),
+            span_b_key: format!("ichimoku_span_b_{senkou_b}_{kijun}"),
+        }
+    }
+
+    pub fn default_params() -> Self {
+        Self::new(9, 26, 52)
+    }
+
+    fn lines(&self, indicators: &IndicatorValues, bar_index: usize) -> Option<Lines> {
+        let lines = Lines {
+            tenkan: indicators.get(&self.tenkan_key, bar_index)?,
+            kijun: indicators.get(&self.kijun_key, bar_index)?,
+            span_a: indicators.get(&self.span_a_key, bar_index)?,
+            span_b: indicators.get(&self.span_b_key, bar_index)?,
+        };
+        let any_nan = lines.tenkan.is_nan()
+            || lines.kijun.is_nan()
+            || lines.span_a.is_nan()
+            || lines.span_b.is_nan();
+        (!any_nan).then_some(lines)
+    }
+}
+
+impl SignalGenerator for IchimokuCloud {
+    fn name(&self) -> &str {
+        "ichimoku_cloud"
+    }
+
+    fn warmup_bars(&self) -> usize {
+        // Span lookback (longest period - 1, displaced by kijun), plus the
+        // previous bar for crossover detection
+        self.tenkan.max(self.kijun).max(self.senkou_b) + self.kijun
+    }
+
+    fn evaluate(
+        &self,
+        bars: &dyn BarSeries,
+        bar_index: usize,
+        indicators: &IndicatorValues,
+    ) -> Option<SignalEvent> {
+        if bar_index < self.warmup_bars() || bar_index == 0 {
+            return None;
+        }
+
+        let bar = bars.bar(bar_index);
+        let prev_close = bars.bar(bar_index - 1).close;
+        if bar.close.is_nan() || prev_close.is_nan() {
+            return None;
+        }
+
+        let now = self.lines(indicators, bar_index)?;
+        let prev = self.lines(indicators, bar_index - 1)?;
+
+        let direction = if now.bullish(bar.close) && !prev.bullish(prev_close) {
+            SignalDirection::Long
+        } else if now.bearish(bar.close) && !prev.bearish(prev_close) {
+            SignalDirection::Short
+        } else {
+            return None;
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("tenkan".into(), now.tenkan);
+        metadata.insert("kijun".into(), now.kijun);
+        metadata.insert("senkou_a".into(), now.span_a);
+        metadata.insert("senkou_b".into(), now.span_b);
+        metadata.insert("cloud_top".into(), now.cloud_top());
+        metadata.insert("cloud_bottom".into(), now.cloud_bottom());
+        metadata.insert(REFERENCE_PRICE.into(), bar.close);
+        metadata.insert(SIGNAL_BAR_HIGH.into(), bar.high);
+        metadata.insert(SIGNAL_BAR_LOW.into(), bar.low);
+
+        Some(SignalEvent {
+            id: SignalEventId(0),
+            bar_index,
+            date: bar.date,
+            symbol: bar.symbol.to_string(),
+            direction,
+            strength: 1.0,
+            metadata,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::indicator::Indicator;
+    use crate::domain::Bar;
+    use crate::indicators::Ichimoku;
+    use chrono::NaiveDate;
+
+    fn make_bars(closes: &[f64]) -> Vec<Bar> {
+        let base_date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| Bar {
+                symbol: "SPY".into(),
+                date: base_date + chrono::Duration::days(i as i64),
+                open: close - 0.5,
+                high: close + 1.0,
+                low: close - 1.0,
+                close,
+                volume: 1000,
+                adj_close: close,
+            })
+            .collect()
+    }
+
+    fn compute(sig: &IchimokuCloud, bars: &[Bar]) -> IndicatorValues {
+        let mut iv = IndicatorValues::new();
+        let lines = [
+            Ichimoku::tenkan(sig.tenkan),
+            Ichimoku::kijun(sig.kijun),
+            Ichimoku::span_a(sig.tenkan, sig.kijun),
+            Ichimoku::span_b(sig.senkou_b, sig.kijun),
+        ];
+        for line in lines {
+            iv.insert(line.name().to_string(), line.compute(bars));
+        }
+        iv
+    }
+
+    fn fires(sig: &IchimokuCloud, bars: &[Bar]) -> Vec<(usize, SignalDirection)> {
+        let iv = compute(sig, bars);
+        (0..bars.len())
+            .filter_map(|i| sig.evaluate(&bars, i, &iv).map(|e| (i, e.direction)))
+            .collect()
+    }
+
+    /// Flat at 100 for `flat` bars, then a steady trend of `slope` per bar.
+    fn flat_then_trend(flat: usize, trend: usize, slope: f64) -> Vec<f64> {
+        (0..flat + trend)
+            .map(|i| 100.0 + slope * i.saturating_sub(flat) as f64)
+            .collect()
+    }
+
+    #[test]
+    fn fires_long_once_when_uptrend_clears_cloud() {
+        let sig = IchimokuCloud::new(3, 5, 10);
+        let bars = make_bars(&flat_then_trend(30, 20, 2.0));
+        let fired = fires(&sig, &bars);
+        assert_eq!(fired.len(), 1, "{fired:?}");
+        assert_eq!(fired[0].1, SignalDirection::Long);
+        assert!(fired[0].0 > 30);
+    }
+
+    #[test]
+    fn fires_short_when_downtrend_breaks_below_cloud() {
+        let sig = IchimokuCloud::new(3, 5, 10);
+        let bars = make_bars(&flat_then_trend(30, 20, -2.0));
+        let fired = fires(&sig, &bars);
+        assert_eq!(fired.len(), 1, "{fired:?}");
+        assert_eq!(fired[0].1, SignalDirection::Short);
+    }
+
+    #[test]
+    fn no_fire_in_ranging_market() {
+        let sig = IchimokuCloud::new(3, 5, 10);
+        // Bar-to-bar chop: every window spans the same range, so Tenkan and
+        // Kijun sit on the same midpoint and never cross
+        let closes: Vec<f64> = (0..80)
+            .map(|i| 100.0 + if i % 2 == 0 { 1.5 } else { -1.5 })
+            .collect();
+        let bars = make_bars(&closes);
+        assert!(fires(&sig, &bars).is_empty());
+    }
+
+    #[test]
+    fn warmup_guard() {
+        let sig = IchimokuCloud::new(3, 5, 10);
+        let bars = make_bars(&flat_then_trend(5, 20, 2.0));
+        let iv = compute(&sig, &bars);
+        for i in 0..sig.warmup_bars() {
+            assert!(sig.evaluate(&bars, i, &iv).is_none());
+        }
+    }
+
+    #[test]
+    fn nan_guard() {
+        let sig = IchimokuCloud::new(3, 5, 10);
+        let mut bars = make_bars(&flat_then_trend(30, 20, 2.0));
+        let (fire_bar, _) = fires(&sig, &bars)[0];
+        let iv = compute(&sig, &bars);
+        bars[fire_bar].close = f64::NAN;
+        assert!(sig.evaluate(&bars, fire_bar, &iv).is_none());
+    }
+
+    #[test]
+    fn metadata_correctness() {
+        let sig = IchimokuCloud::new(3, 5, 10);
+        let bars = make_bars(&flat_then_trend(30, 20, 2.0));
+        let iv = compute(&sig, &bars);
+        let (fire_bar, _) = fires(&sig, &bars)[0];
+        let event = sig.evaluate(&bars, fire_bar, &iv).unwrap();
+        let m = &event.metadata;
+        assert_eq!(m["tenkan"], iv.get("ichimoku_tenkan_3", fire_bar).unwrap());
+        assert_eq!(m["cloud_top"], m["senkou_a"].max(m["senkou_b"]));
+        assert_eq!(m["cloud_bottom"], m["senkou_a"].min(m["senkou_b"]));
+        assert!(m["reference_price"] > m["cloud_top"]);
+        assert!(m["tenkan"] > m["kijun"]);
+        assert_eq!(m["signal_bar_low"], bars[fire_bar].low);
+        assert_eq!(event.strength, 1.0);
+    }
+
+    #[test]
+    fn name_and_warmup() {
+        let sig = IchimokuCloud::default_params();
+        assert_eq!(sig.name(), "ichimoku_cloud");
+        // Span B lookback 51 + 26, plus one bar for the crossover
+        assert_eq!(sig.warmup_bars(), 78);
+    }
+}
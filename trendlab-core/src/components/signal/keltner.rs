@@ -5,9 +5,12 @@
 //! all of which are encoded in the indicator key.
 
 use crate::components::indicator::IndicatorValues;
-use crate::domain::{Bar, SignalEventId};
+use crate::domain::{BarSeries, SignalEventId};
 
-use super::{SignalDirection, SignalEvent, SignalGenerator};
+use super::{
+    SignalDirection, SignalEvent, SignalGenerator, BREAKOUT_LEVEL, REFERENCE_PRICE,
+    SIGNAL_BAR_HIGH, SIGNAL_BAR_LOW,
+};
 use std::collections::HashMap;
 
 /// Keltner channel breakout signal.
@@ -55,7 +58,7 @@ impl SignalGenerator for KeltnerBreakout {
 
     fn evaluate(
         &self,
-        bars: &[Bar],
+        bars: &dyn BarSeries,
         bar_index: usize,
         indicators: &IndicatorValues,
     ) -> Option<SignalEvent> {
@@ -63,7 +66,7 @@ impl SignalGenerator for KeltnerBreakout {
             return None;
         }
 
-        let bar = &bars[bar_index];
+        let bar = bars.bar(bar_index);
         if bar.close.is_nan() {
             return None;
         }
@@ -75,15 +78,16 @@ impl SignalGenerator for KeltnerBreakout {
 
         if bar.close > upper {
             let mut metadata = HashMap::new();
-            metadata.insert("breakout_level".into(), upper);
-            metadata.insert("reference_price".into(), bar.close);
-            metadata.insert("signal_bar_low".into(), bar.low);
+            metadata.insert(BREAKOUT_LEVEL.into(), upper);
+            metadata.insert(REFERENCE_PRICE.into(), bar.close);
+            metadata.insert(SIGNAL_BAR_HIGH.into(), bar.high);
+            metadata.insert(SIGNAL_BAR_LOW.into(), bar.low);
 
             Some(SignalEvent {
                 id: SignalEventId(0),
                 bar_index,
                 date: bar.date,
-                symbol: bar.symbol.clone(),
+                symbol: bar.symbol.to_string(),
                 direction: SignalDirection::Long,
                 strength: 1.0,
                 metadata,
@@ -97,6 +101,7 @@ impl SignalGenerator for KeltnerBreakout {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::Bar;
     use chrono::NaiveDate;
 
     fn base_date() -> NaiveDate {
@@ -110,7 +115,7 @@ mod tests {
             .map(|i| {
                 let close = if breakout_at == Some(i) { 120.0 } else { 100.0 };
                 Bar {
-                    symbol: "SPY".to_string(),
+                    symbol: "SPY".into(),
                     date: base_date() + chrono::Duration::days(i as i64),
                     open: close - 0.5,
                     high: if breakout_at == Some(i) { 121.0 } else { 102.0 },
@@ -4,9 +4,11 @@
 //! Fires Short when the fast MA crosses below the slow MA (death cross).
 
 use crate::components::indicator::IndicatorValues;
-use crate::domain::{Bar, SignalEventId};
+use crate::domain::{BarSeries, SignalEventId};
 
-use super::{SignalDirection, SignalEvent, SignalGenerator};
+use super::{
+    SignalDirection, SignalEvent, SignalGenerator, REFERENCE_PRICE, SIGNAL_BAR_HIGH, SIGNAL_BAR_LOW,
+};
 use std::collections::HashMap;
 
 /// Moving average type selector.
@@ -81,7 +83,7 @@ impl SignalGenerator for MaCrossover {
 
     fn evaluate(
         &self,
-        bars: &[Bar],
+        bars: &dyn BarSeries,
         bar_index: usize,
         indicators: &IndicatorValues,
     ) -> Option<SignalEvent> {
@@ -90,7 +92,7 @@ impl SignalGenerator for MaCrossover {
             return None;
         }
 
-        let bar = &bars[bar_index];
+        let bar = bars.bar(bar_index);
 
         // NaN guard: current bar close.
         if bar.close.is_nan() {
@@ -109,8 +111,9 @@ impl SignalGenerator for MaCrossover {
         }
 
         let mut metadata = HashMap::new();
-        metadata.insert("reference_price".into(), bar.close);
-        metadata.insert("signal_bar_low".into(), bar.low);
+        metadata.insert(REFERENCE_PRICE.into(), bar.close);
+        metadata.insert(SIGNAL_BAR_HIGH.into(), bar.high);
+        metadata.insert(SIGNAL_BAR_LOW.into(), bar.low);
 
         // Long signal (golden cross): fast crosses above slow.
         // Current bar: fast > slow. Previous bar: fast <= slow.
@@ -119,7 +122,7 @@ impl SignalGenerator for MaCrossover {
                 id: SignalEventId(0),
                 bar_index,
                 date: bar.date,
-                symbol: bar.symbol.clone(),
+                symbol: bar.symbol.to_string(),
                 direction: SignalDirection::Long,
                 strength: 1.0,
                 metadata,
@@ -133,7 +136,7 @@ impl SignalGenerator for MaCrossover {
                 id: SignalEventId(0),
                 bar_index,
                 date: bar.date,
-                symbol: bar.symbol.clone(),
+                symbol: bar.symbol.to_string(),
                 direction: SignalDirection::Short,
                 strength: 1.0,
                 metadata,
@@ -147,6 +150,7 @@ impl SignalGenerator for MaCrossover {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::Bar;
     use chrono::NaiveDate;
 
     fn base_date() -> NaiveDate {
@@ -155,7 +159,7 @@ mod tests {
 
     fn make_bar(index: usize, close: f64, low: f64) -> Bar {
         Bar {
-            symbol: "AAPL".to_string(),
+            symbol: "AAPL".into(),
             date: base_date() + chrono::Duration::days(index as i64),
             open: close - 0.5,
             high: close + 1.0,
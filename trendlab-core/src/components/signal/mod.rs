@@ -3,25 +3,51 @@
 //! Signals are portfolio-agnostic: they receive bar history and indicator values,
 //! never portfolio or position state. Signal events are immutable once emitted —
 //! they describe a market event, not a downstream decision.
-
+//!
+//! # Metadata contract
+//!
+//! Every signal event carries these [`SignalEvent::metadata`] keys:
+//! - [`REFERENCE_PRICE`] — close of the signal bar
+//! - [`SIGNAL_BAR_HIGH`] / [`SIGNAL_BAR_LOW`] — range of the signal bar
+//!
+//! Level-crossing signals (Donchian, Bollinger, Keltner, squeeze, 52-week
+//! breakout, Supertrend, Parabolic SAR) also carry [`BREAKOUT_LEVEL`], the exact price
+//! whose crossing fired the signal. Stop-entry execution places its trigger
+//! there. Crossover and momentum signals (MA crossover, Aroon, ROC, TSMOM,
+//! Ichimoku, ADX trend) have no such level and omit it. An ensemble (see
+//! [`ensemble`]) carries the level of its agreeing children, if any.
+
+pub mod adx_trend;
 pub mod aroon;
 pub mod bollinger;
 pub mod breakout_52w;
 pub mod donchian;
+pub mod ensemble;
+pub mod ichimoku;
 pub mod keltner;
 pub mod ma_crossover;
 pub mod parabolic_sar;
 pub mod roc_momentum;
+pub mod squeeze;
 pub mod supertrend;
 pub mod tsmom;
 
-use crate::domain::{Bar, SignalEventId};
+use crate::domain::{BarSeries, SignalEventId};
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use super::indicator::IndicatorValues;
 
+/// Metadata key: the price level whose crossing fired the signal.
+pub const BREAKOUT_LEVEL: &str = "breakout_level";
+/// Metadata key: close of the signal bar.
+pub const REFERENCE_PRICE: &str = "reference_price";
+/// Metadata key: high of the signal bar.
+pub const SIGNAL_BAR_HIGH: &str = "signal_bar_high";
+/// Metadata key: low of the signal bar.
+pub const SIGNAL_BAR_LOW: &str = "signal_bar_low";
+
 /// Directional intent of a signal.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SignalDirection {
@@ -66,7 +92,7 @@ pub trait SignalGenerator: Send + Sync {
     /// The implementation must only use data from `bars[0..=bar_index]`.
     fn evaluate(
         &self,
-        bars: &[Bar],
+        bars: &dyn BarSeries,
         bar_index: usize,
         indicators: &IndicatorValues,
     ) -> Option<SignalEvent>;
@@ -93,6 +119,11 @@ pub enum FilterVerdict {
     FilteredByRegime,
     FilteredByVolatility,
     FilteredByCustom(String),
+    /// Blocked by entry governance (cooldown, entry cap, or signal reset).
+    Cooldown,
+    /// Passed the filter, but the position size rounded down to zero under
+    /// the instrument's quantization (e.g. less cash than one share).
+    BelowMinimumSize,
 }
 
 impl FilterVerdict {
@@ -116,7 +147,7 @@ impl SignalGenerator for NullSignal {
 
     fn evaluate(
         &self,
-        _bars: &[Bar],
+        _bars: &dyn BarSeries,
         _bar_index: usize,
         _indicators: &IndicatorValues,
     ) -> Option<SignalEvent> {
@@ -125,14 +156,18 @@ impl SignalGenerator for NullSignal {
 }
 
 // Re-export concrete signal types.
+pub use adx_trend::AdxTrend;
 pub use aroon::AroonCrossover;
 pub use bollinger::BollingerBreakout;
 pub use breakout_52w::Breakout52w;
 pub use donchian::DonchianBreakout;
+pub use ensemble::{EnsembleSignal, StrengthAggregation};
+pub use ichimoku::IchimokuCloud;
 pub use keltner::KeltnerBreakout;
 pub use ma_crossover::{MaCrossover, MaType};
 pub use parabolic_sar::ParabolicSarSignal;
 pub use roc_momentum::RocMomentum;
+pub use squeeze::SqueezeBreakout;
 pub use supertrend::SupertrendSignal;
 pub use tsmom::Tsmom;
 
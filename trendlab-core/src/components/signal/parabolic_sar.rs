@@ -4,9 +4,12 @@
 //! Fires Short when SAR flips from below close to above close (bullish-to-bearish).
 
 use crate::components::indicator::IndicatorValues;
-use crate::domain::{Bar, SignalEventId};
+use crate::domain::{BarSeries, SignalEventId};
 
-use super::{SignalDirection, SignalEvent, SignalGenerator};
+use super::{
+    SignalDirection, SignalEvent, SignalGenerator, BREAKOUT_LEVEL, REFERENCE_PRICE,
+    SIGNAL_BAR_HIGH, SIGNAL_BAR_LOW,
+};
 use std::collections::HashMap;
 
 /// Parabolic SAR flip signal generator.
@@ -56,7 +59,7 @@ impl SignalGenerator for ParabolicSarSignal {
 
     fn evaluate(
         &self,
-        bars: &[Bar],
+        bars: &dyn BarSeries,
         bar_index: usize,
         indicators: &IndicatorValues,
     ) -> Option<SignalEvent> {
@@ -65,8 +68,8 @@ impl SignalGenerator for ParabolicSarSignal {
             return None;
         }
 
-        let bar = &bars[bar_index];
-        let prev_bar = &bars[bar_index - 1];
+        let bar = bars.bar(bar_index);
+        let prev_bar = bars.bar(bar_index - 1);
 
         // NaN guard: current bar close.
         if bar.close.is_nan() {
@@ -87,9 +90,10 @@ impl SignalGenerator for ParabolicSarSignal {
         }
 
         let mut metadata = HashMap::new();
-        metadata.insert("breakout_level".into(), sar);
-        metadata.insert("reference_price".into(), bar.close);
-        metadata.insert("signal_bar_low".into(), bar.low);
+        metadata.insert(BREAKOUT_LEVEL.into(), sar);
+        metadata.insert(REFERENCE_PRICE.into(), bar.close);
+        metadata.insert(SIGNAL_BAR_HIGH.into(), bar.high);
+        metadata.insert(SIGNAL_BAR_LOW.into(), bar.low);
 
         // Long signal: SAR flips from above to below close.
         // Current bar: SAR < close. Previous bar: SAR >= close.
@@ -98,7 +102,7 @@ impl SignalGenerator for ParabolicSarSignal {
                 id: SignalEventId(0),
                 bar_index,
                 date: bar.date,
-                symbol: bar.symbol.clone(),
+                symbol: bar.symbol.to_string(),
                 direction: SignalDirection::Long,
                 strength: 1.0,
                 metadata,
@@ -112,7 +116,7 @@ impl SignalGenerator for ParabolicSarSignal {
                 id: SignalEventId(0),
                 bar_index,
                 date: bar.date,
-                symbol: bar.symbol.clone(),
+                symbol: bar.symbol.to_string(),
                 direction: SignalDirection::Short,
                 strength: 1.0,
                 metadata,
@@ -126,6 +130,7 @@ impl SignalGenerator for ParabolicSarSignal {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::Bar;
     use chrono::NaiveDate;
 
     fn base_date() -> NaiveDate {
@@ -134,7 +139,7 @@ mod tests {
 
     fn make_bar(index: usize, close: f64, low: f64) -> Bar {
         Bar {
-            symbol: "SPY".to_string(),
+            symbol: "SPY".into(),
             date: base_date() + chrono::Duration::days(index as i64),
             open: close - 0.5,
             high: close + 1.0,
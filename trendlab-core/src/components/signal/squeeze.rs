@@ -0,0 +1,374 @@
+//! Squeeze breakout signal — a volatility contraction confirmed by a Keltner
+//! channel breakout.
+//!
+//! Uses precomputed Bollinger bands (`bollinger_{upper,middle,lower}_{period}_{std}`)
+//! and Keltner bands (`keltner_{upper,lower}_{period}_{period}_{mult}`).
+//! A bar is *compressed* when its Bollinger width, `(upper - lower) / middle`,
+//! ranks at or below `percentile` among the last `percentile_lookback` widths.
+//! The signal fires on the bar whose close leaves the Keltner channel (Long
+//! above the upper band, Short below the lower) when a run of at least
+//! `min_squeeze_bars` consecutive compressed bars ended within the last
+//! `period` bars.
+
+use crate::components::indicator::IndicatorValues;
+use crate::domain::{BarSeries, SignalEventId};
+
+use super::{
+    SignalDirection, SignalEvent, SignalGenerator, BREAKOUT_LEVEL, REFERENCE_PRICE,
+    SIGNAL_BAR_HIGH, SIGNAL_BAR_LOW,
+};
+use std::collections::HashMap;
+
+/// Metadata key: consecutive compressed bars before the breakout bar.
+pub const SQUEEZE_BARS: &str = "squeeze_bars";
+/// Metadata key: Bollinger width percentile (0–100) on the last compressed bar.
+pub const WIDTH_PERCENTILE: &str = "width_percentile";
+
+/// Bollinger squeeze with Keltner breakout confirmation.
+///
+/// Both channels share `period` (Keltner uses it for the EMA and the ATR).
+/// Only the first close outside the channel fires, so a breakout that keeps
+/// running produces one event. Strength is 1.0.
+#[derive(Debug, Clone)]
+pub struct SqueezeBreakout {
+    pub period: usize,
+    pub std_multiplier: f64,
+    pub keltner_multiplier: f64,
+    pub percentile_lookback: usize,
+    pub percentile: f64,
+    pub min_squeeze_bars: usize,
+    bb_upper_key: String,
+    bb_middle_key: String,
+    bb_lower_key: String,
+    kc_upper_key: String,
+    kc_lower_key: String,
+}
+
+impl SqueezeBreakout {
+    pub fn new(
+        period: usize,
+        std_multiplier: f64,
+        keltner_multiplier: f64,
+        percentile_lookback: usize,
+        percentile: f64,
+        min_squeeze_bars: usize,
+    ) -> Self {
+        assert!(period >= 2, "period must be >= 2");
+        assert!(std_multiplier > 0.0, "std_multiplier must be > 0");
+        assert!(keltner_multiplier > 0.0, "keltner_multiplier must be > 0");
+        assert!(percentile_lookback >= 2, "percentile_lookback must be >= 2");
+        assert!(
+            percentile > 0.0 && percentile <= 100.0,
+            "percentile must be in (0, 100]"
+        );
+        assert!(min_squeeze_bars >= 1, "min_squeeze_bars must be >= 1");
+        Self {
+            period,
+            std_multiplier,
+            keltner_multiplier,
+            percentile_lookback,
+            percentile,
+            min_squeeze_bars,
+            bb_upper_key: format!("bollinger_upper_{period}_{std_multiplier}"),
+            bb_middle_key: format!("bollinger_middle_{period}_{std_multiplier}"),
+            bb_lower_key: format!("bollinger_lower_{period}_{std_multiplier}"),
+            kc_upper_key: format!("keltner_upper_{period}_{period}_{keltner_multiplier}"),
+            kc_lower_key: format!("keltner_lower_{period}_{period}_{keltner_multiplier}"),
+        }
+    }
+
+    pub fn default_params() -> Self {
+        Self::new(20, 2.0, 1.5, 120, 20.0, 5)
+    }
+
+    /// Normalized Bollinger width at `i`, or None while any band is missing.
+    fn width(&self, indicators: &IndicatorValues, i: usize) -> Option<f64> {
+        let upper = indicators.get(&self.bb_upper_key, i)?;
+        let middle = indicators.get(&self.bb_middle_key, i)?;
+        let lower = indicators.get(&self.bb_lower_key, i)?;
+        let width = (upper - lower) / middle;
+        (width.is_finite() && middle > 0.0).then_some(width)
+    }
+
+    /// Percent of the last `percentile_lookback` widths (ending at `i`) that
+    /// are at or below the width at `i`.
+    fn width_percentile(&self, indicators: &IndicatorValues, i: usize) -> Option<f64> {
+        let start = (i + 1).checked_sub(self.percentile_lookback)?;
+        let current = self.width(indicators, i)?;
+        let mut at_or_below = 0usize;
+        for j in start..=i {
+            if self.width(indicators, j)? <= current {
+                at_or_below += 1;
+            }
+        }
+        Some(100.0 * at_or_below as f64 / self.percentile_lookback as f64)
+    }
+
+    fn compressed(&self, indicators: &IndicatorValues, i: usize) -> Option<f64> {
+        self.width_percentile(indicators, i)
+            .filter(|&pct| pct <= self.percentile)
+    }
+
+    /// The latest squeeze ending within `period` bars before `bar_index`:
+    /// its length in bars and the width percentile on its last bar.
+    ///
+    /// The Bollinger bands widen as soon as price starts to move, often a
+    /// few bars before the close clears the Keltner channel, so the squeeze
+    /// may end slightly before the breakout bar.
+    fn squeeze_before(
+        &self,
+        indicators: &IndicatorValues,
+        bar_index: usize,
+    ) -> Option<(usize, f64)> {
+        let earliest = bar_index.saturating_sub(self.period);
+        let end = (earliest..bar_index)
+            .rev()
+            .find(|&i| self.compressed(indicators, i).is_some())?;
+        let last = self.compressed(indicators, end)?;
+        let run = (0..=end)
+            .rev()
+            .take_while(|&i| self.compressed(indicators, i).is_some())
+            .count();
+        Some((run, last))
+    }
+}
+
+impl SignalGenerator for SqueezeBreakout {
+    fn name(&self) -> &str {
+        "squeeze_breakout"
+    }
+
+    fn warmup_bars(&self) -> usize {
+        // Longest channel lookback (Keltner's ATR: `period`), then a full
+        // percentile window and the minimum squeeze before the breakout bar.
+        self.period + self.percentile_lookback + self.min_squeeze_bars - 1
+    }
+
+    fn evaluate(
+        &self,
+        bars: &dyn BarSeries,
+        bar_index: usize,
+        indicators: &IndicatorValues,
+    ) -> Option<SignalEvent> {
+        if bar_index < self.warmup_bars() || bar_index == 0 {
+            return None;
+        }
+
+        let bar = bars.bar(bar_index);
+        let prev_close = bars.bar(bar_index - 1).close;
+        if bar.close.is_nan() || prev_close.is_nan() {
+            return None;
+        }
+
+        let upper = indicators.get(&self.kc_upper_key, bar_index)?;
+        let lower = indicators.get(&self.kc_lower_key, bar_index)?;
+        let prev_upper = indicators.get(&self.kc_upper_key, bar_index - 1)?;
+        let prev_lower = indicators.get(&self.kc_lower_key, bar_index - 1)?;
+        if upper.is_nan() || lower.is_nan() || prev_upper.is_nan() || prev_lower.is_nan() {
+            return None;
+        }
+
+        // Only the first close outside the channel is a breakout.
+        let inside_before = prev_close <= prev_upper && prev_close >= prev_lower;
+        let (direction, level) = if bar.close > upper {
+            (SignalDirection::Long, upper)
+        } else if bar.close < lower {
+            (SignalDirection::Short, lower)
+        } else {
+            return None;
+        };
+        if !inside_before {
+            return None;
+        }
+
+        let (squeeze_bars, width_percentile) = self.squeeze_before(indicators, bar_index)?;
+        if squeeze_bars < self.min_squeeze_bars {
+            return None;
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert(BREAKOUT_LEVEL.into(), level);
+        metadata.insert(REFERENCE_PRICE.into(), bar.close);
+        metadata.insert(SIGNAL_BAR_HIGH.into(), bar.high);
+        metadata.insert(SIGNAL_BAR_LOW.into(), bar.low);
+        metadata.insert(SQUEEZE_BARS.into(), squeeze_bars as f64);
+        metadata.insert(WIDTH_PERCENTILE.into(), width_percentile);
+
+        Some(SignalEvent {
+            id: SignalEventId(0),
+            bar_index,
+            date: bar.date,
+            symbol: bar.symbol.to_string(),
+            direction,
+            strength: 1.0,
+            metadata,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::indicator::Indicator;
+    use crate::domain::Bar;
+    use crate::indicators::{Bollinger, Keltner};
+    use chrono::NaiveDate;
+
+    const CHOP: usize = 150;
+    const SQUEEZE: usize = 30;
+
+    fn make_bars(closes: &[f64]) -> Vec<Bar> {
+        let base_date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| Bar {
+                symbol: "SPY".into(),
+                date: base_date + chrono::Duration::days(i as i64),
+                open: close,
+                high: close + 0.25,
+                low: close - 0.25,
+                close,
+                volume: 1000,
+                adj_close: close,
+            })
+            .collect()
+    }
+
+    /// Close swinging around 100 by `amplitude(i)`, alternating sides.
+    fn swing(i: usize, amplitude: f64) -> f64 {
+        100.0 + if i % 2 == 0 { amplitude } else { -amplitude }
+    }
+
+    /// Chop around 100 with slowly widening swings, a squeeze whose swings
+    /// shrink every bar, then a steady trend of `slope` per bar. The first
+    /// trend bar is index `CHOP + SQUEEZE`.
+    fn squeeze_then_trend(slope: f64) -> Vec<Bar> {
+        let chop = (0..CHOP).map(|i| swing(i, 3.0 + 2.0 * i as f64 / CHOP as f64));
+        let squeeze = (0..SQUEEZE).map(|i| swing(i, 0.85f64.powi(i as i32)));
+        let trend = (1..=40).map(|i| 100.0 + slope * i as f64);
+        make_bars(&chop.chain(squeeze).chain(trend).collect::<Vec<_>>())
+    }
+
+    fn compute(sig: &SqueezeBreakout, bars: &[Bar]) -> IndicatorValues {
+        let mut iv = IndicatorValues::new();
+        let indicators: [Box<dyn Indicator>; 2] = [
+            Box::new(Bollinger::bands(sig.period, sig.std_multiplier)),
+            Box::new(Keltner::bands(
+                sig.period,
+                sig.period,
+                sig.keltner_multiplier,
+            )),
+        ];
+        for ind in indicators {
+            for (name, values) in ind.outputs().into_iter().zip(ind.compute_all(bars)) {
+                iv.insert(name.to_string(), values);
+            }
+        }
+        iv
+    }
+
+    fn fires(sig: &SqueezeBreakout, bars: &[Bar]) -> Vec<SignalEvent> {
+        let iv = compute(sig, bars);
+        (0..bars.len())
+            .filter_map(|i| sig.evaluate(&bars, i, &iv))
+            .collect()
+    }
+
+    fn test_signal(min_squeeze_bars: usize) -> SqueezeBreakout {
+        SqueezeBreakout::new(10, 2.0, 1.5, 100, 20.0, min_squeeze_bars)
+    }
+
+    #[test]
+    fn default_params() {
+        let sig = SqueezeBreakout::default_params();
+        assert_eq!(sig.name(), "squeeze_breakout");
+        assert_eq!(sig.period, 20);
+        assert_eq!(sig.percentile_lookback, 120);
+        assert_eq!(sig.min_squeeze_bars, 5);
+        assert_eq!(sig.kc_upper_key, "keltner_upper_20_20_1.5");
+        assert_eq!(sig.bb_middle_key, "bollinger_middle_20_2");
+    }
+
+    #[test]
+    fn warmup_covers_channel_percentile_window_and_squeeze() {
+        assert_eq!(test_signal(5).warmup_bars(), 10 + 100 + 5 - 1);
+        assert_eq!(SqueezeBreakout::default_params().warmup_bars(), 144);
+    }
+
+    #[test]
+    fn fires_once_at_the_breakout_bar_and_never_in_the_chop() {
+        let sig = test_signal(5);
+        let bars = squeeze_then_trend(1.0);
+        let iv = compute(&sig, &bars);
+        let events = fires(&sig, &bars);
+
+        assert_eq!(
+            events.len(),
+            1,
+            "{:?}",
+            events.iter().map(|e| e.bar_index).collect::<Vec<_>>()
+        );
+        let event = &events[0];
+        assert_eq!(event.direction, SignalDirection::Long);
+
+        // The breakout bar is the first trend bar closing above the channel.
+        let upper = &iv.get_series("keltner_upper_10_10_1.5").unwrap();
+        let breakout = (CHOP + SQUEEZE..bars.len())
+            .find(|&i| bars[i].close > upper[i])
+            .unwrap();
+        assert_eq!(event.bar_index, breakout);
+        assert!(breakout < CHOP + SQUEEZE + 5, "breakout at {breakout}");
+        assert!(events.iter().all(|e| e.bar_index >= CHOP + SQUEEZE));
+    }
+
+    #[test]
+    fn squeeze_shorter_than_minimum_does_not_fire() {
+        let sig = test_signal(SQUEEZE + 10);
+        assert!(fires(&sig, &squeeze_then_trend(1.0)).is_empty());
+    }
+
+    #[test]
+    fn downside_breakout_fires_short() {
+        let events = fires(&test_signal(5), &squeeze_then_trend(-1.0));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].direction, SignalDirection::Short);
+    }
+
+    #[test]
+    fn metadata_reports_squeeze_duration_and_width_percentile() {
+        let sig = test_signal(5);
+        let bars = squeeze_then_trend(1.0);
+        let iv = compute(&sig, &bars);
+        let event = &fires(&sig, &bars)[0];
+        let i = event.bar_index;
+
+        let upper = iv.get_series("keltner_upper_10_10_1.5").unwrap();
+        assert_eq!(event.metadata[BREAKOUT_LEVEL], upper[i]);
+        assert_eq!(event.metadata[REFERENCE_PRICE], bars[i].close);
+        let squeeze = event.metadata[SQUEEZE_BARS];
+        assert!(
+            squeeze >= 5.0 && squeeze <= (SQUEEZE + 1) as f64,
+            "{squeeze}"
+        );
+        let pct = event.metadata[WIDTH_PERCENTILE];
+        assert!(pct > 0.0 && pct <= sig.percentile, "{pct}");
+    }
+
+    #[test]
+    fn nan_guard() {
+        let sig = test_signal(5);
+        let mut bars = squeeze_then_trend(1.0);
+        let iv = compute(&sig, &bars);
+        let i = fires(&sig, &bars)[0].bar_index;
+        bars[i].close = f64::NAN;
+        assert!(sig.evaluate(&bars, i, &iv).is_none());
+        assert!(sig.evaluate(&bars, i, &IndicatorValues::new()).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "percentile must be in (0, 100]")]
+    fn rejects_out_of_range_percentile() {
+        SqueezeBreakout::new(20, 2.0, 1.5, 120, 0.0, 5);
+    }
+}
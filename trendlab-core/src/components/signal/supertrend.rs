@@ -9,9 +9,12 @@
 //! - Short: supertrend transitions from below close to above close (uptrend -> downtrend)
 
 use crate::components::indicator::IndicatorValues;
-use crate::domain::{Bar, SignalEventId};
+use crate::domain::{BarSeries, SignalEventId};
 
-use super::{SignalDirection, SignalEvent, SignalGenerator};
+use super::{
+    SignalDirection, SignalEvent, SignalGenerator, BREAKOUT_LEVEL, REFERENCE_PRICE,
+    SIGNAL_BAR_HIGH, SIGNAL_BAR_LOW,
+};
 use std::collections::HashMap;
 
 /// Supertrend direction flip signal.
@@ -57,7 +60,7 @@ impl SignalGenerator for SupertrendSignal {
 
     fn evaluate(
         &self,
-        bars: &[Bar],
+        bars: &dyn BarSeries,
         bar_index: usize,
         indicators: &IndicatorValues,
     ) -> Option<SignalEvent> {
@@ -70,8 +73,8 @@ impl SignalGenerator for SupertrendSignal {
             return None;
         }
 
-        let bar_cur = &bars[bar_index];
-        let bar_prev = &bars[bar_index - 1];
+        let bar_cur = bars.bar(bar_index);
+        let bar_prev = bars.bar(bar_index - 1);
 
         // NaN guard: both bars' close values must be valid.
         if bar_cur.close.is_nan() || bar_prev.close.is_nan() {
@@ -107,15 +110,16 @@ impl SignalGenerator for SupertrendSignal {
 
         direction.map(|dir| {
             let mut metadata = HashMap::new();
-            metadata.insert("breakout_level".into(), st_cur);
-            metadata.insert("reference_price".into(), close_cur);
-            metadata.insert("signal_bar_low".into(), bar_cur.low);
+            metadata.insert(BREAKOUT_LEVEL.into(), st_cur);
+            metadata.insert(REFERENCE_PRICE.into(), close_cur);
+            metadata.insert(SIGNAL_BAR_HIGH.into(), bar_cur.high);
+            metadata.insert(SIGNAL_BAR_LOW.into(), bar_cur.low);
 
             SignalEvent {
                 id: SignalEventId(0),
                 bar_index,
                 date: bar_cur.date,
-                symbol: bar_cur.symbol.clone(),
+                symbol: bar_cur.symbol.to_string(),
                 direction: dir,
                 strength: 1.0,
                 metadata,
@@ -127,6 +131,7 @@ impl SignalGenerator for SupertrendSignal {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::Bar;
     use chrono::NaiveDate;
 
     fn base_date() -> NaiveDate {
@@ -139,7 +144,7 @@ mod tests {
             .iter()
             .enumerate()
             .map(|(i, &close)| Bar {
-                symbol: "AAPL".to_string(),
+                symbol: "AAPL".into(),
                 date: base_date() + chrono::Duration::days(i as i64),
                 open: close - 0.5,
                 high: close + 1.0,
@@ -4,9 +4,11 @@
 //! Fires Long when momentum > 0, Short when momentum < 0.
 
 use crate::components::indicator::IndicatorValues;
-use crate::domain::{Bar, SignalEventId};
+use crate::domain::{BarSeries, SignalEventId};
 
-use super::{SignalDirection, SignalEvent, SignalGenerator};
+use super::{
+    SignalDirection, SignalEvent, SignalGenerator, REFERENCE_PRICE, SIGNAL_BAR_HIGH, SIGNAL_BAR_LOW,
+};
 use std::collections::HashMap;
 
 /// Time-series momentum signal.
@@ -44,7 +46,7 @@ impl SignalGenerator for Tsmom {
 
     fn evaluate(
         &self,
-        bars: &[Bar],
+        bars: &dyn BarSeries,
         bar_index: usize,
         indicators: &IndicatorValues,
     ) -> Option<SignalEvent> {
@@ -52,7 +54,7 @@ impl SignalGenerator for Tsmom {
             return None;
         }
 
-        let bar = &bars[bar_index];
+        let bar = bars.bar(bar_index);
         if bar.close.is_nan() {
             return None;
         }
@@ -74,14 +76,15 @@ impl SignalGenerator for Tsmom {
 
         let mut metadata = HashMap::new();
         metadata.insert("momentum_value".into(), momentum);
-        metadata.insert("reference_price".into(), bar.close);
-        metadata.insert("signal_bar_low".into(), bar.low);
+        metadata.insert(REFERENCE_PRICE.into(), bar.close);
+        metadata.insert(SIGNAL_BAR_HIGH.into(), bar.high);
+        metadata.insert(SIGNAL_BAR_LOW.into(), bar.low);
 
         Some(SignalEvent {
             id: SignalEventId(0),
             bar_index,
             date: bar.date,
-            symbol: bar.symbol.clone(),
+            symbol: bar.symbol.to_string(),
             direction,
             strength,
             metadata,
@@ -92,6 +95,7 @@ impl SignalGenerator for Tsmom {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::Bar;
     use chrono::NaiveDate;
 
     fn make_bars(n: usize) -> Vec<Bar> {
@@ -100,7 +104,7 @@ mod tests {
             .map(|i| {
                 let close = 100.0 + i as f64;
                 Bar {
-                    symbol: "SPY".to_string(),
+                    symbol: "SPY".into(),
                     date: base_date + chrono::Duration::days(i as i64),
                     open: close - 0.5,
                     high: close + 2.0,
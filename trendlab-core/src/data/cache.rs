@@ -1,6 +1,20 @@
 //! Parquet cache layer with Hive-style partitioning.
 //!
-//! Layout: `{cache_dir}/symbol={SYMBOL}/{year}.parquet`
+//! Layout: `{cache_dir}/symbol={SYMBOL}/part-{NNNN}.parquet` for daily bars,
+//! and `{cache_dir}/symbol={SYMBOL}/interval={code}/part-{NNNN}.parquet` for
+//! other intervals (see [`ParquetCache::with_interval`]). A `parts.json`
+//! manifest in each partition lists its parts, oldest first, with their date
+//! ranges.
+//!
+//! Each write appends a new part and then rewrites the manifest, so existing
+//! parts are never touched and the manifest rename is the commit point: a
+//! part left behind by an interrupted write is not listed and is ignored.
+//! Readers merge the listed parts, and where parts overlap the newest part's
+//! bar wins. [`ParquetCache::compact`] merges the parts into one and removes
+//! orphans.
+//!
+//! Partitions in the older one-file-per-year layout (`{year}.parquet`, no
+//! manifest) still load, and are migrated into a single part on first write.
 //!
 //! Features:
 //! - Atomic writes (write to .tmp, rename into place)
@@ -8,12 +22,17 @@
 //! - Integrity validation on load (schema check, row count > 0)
 //! - Quarantine for corrupt files ({filename}.quarantined)
 //! - Metadata sidecar per symbol (hash, date range, source)
+//! - Ingest report sidecar: corrections made by the latest ingest
+//! - Listing and cleanup of cached symbols ([`ParquetCache::entries`])
+//! - Partition vintages for as-of reproduction ([`ParquetCache::vintage`])
 
+use super::ingest::IngestReport;
+use super::interval::BarInterval;
 use super::provider::{DataError, RawBar};
-use chrono::{Datelike, NaiveDate};
+use chrono::{NaiveDate, NaiveDateTime};
 use polars::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -29,31 +48,114 @@ pub struct CacheMeta {
     pub cached_at: chrono::NaiveDateTime,
 }
 
+/// When a symbol partition was last written, and a hash of what it holds.
+///
+/// Two vintages of the same partition with the same `content_hash` hold the
+/// same bars, whatever their `cached_at`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartitionVintage {
+    pub symbol: String,
+    /// Interval of the partition the bars were read from. Daily for bars
+    /// resampled from the daily partition on load.
+    #[serde(default)]
+    pub interval: BarInterval,
+    /// From the metadata sidecar; None when it is missing or unreadable.
+    pub cached_at: Option<NaiveDateTime>,
+    /// BLAKE3 over the merged bars, computed as [`CacheMeta::data_hash`] is.
+    pub content_hash: String,
+}
+
+/// File name of the part manifest inside a symbol partition.
+pub const PARTS_MANIFEST: &str = "parts.json";
+
+/// File name of the ingest report sidecar inside a symbol partition.
+pub const INGEST_REPORT: &str = "ingest.json";
+
+/// One Parquet part of a symbol partition.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartInfo {
+    /// File name inside the partition directory.
+    pub file: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub bar_count: usize,
+}
+
+/// The parts of a symbol partition, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PartManifest {
+    pub parts: Vec<PartInfo>,
+}
+
+/// Outcome of [`ParquetCache::compact`] for one symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompactReport {
+    pub symbol: String,
+    pub parts_before: usize,
+    pub parts_after: usize,
+    /// Bars in the merged series.
+    pub bars: usize,
+    /// Stored bars dropped because a newer part had the same date.
+    pub shadowed_bars: usize,
+    /// Unlisted part files (and temp files) deleted.
+    pub files_removed: usize,
+}
+
 /// The Parquet cache.
 pub struct ParquetCache {
     cache_dir: PathBuf,
+    interval: BarInterval,
 }
 
 impl ParquetCache {
     pub fn new(cache_dir: impl Into<PathBuf>) -> Self {
         Self {
             cache_dir: cache_dir.into(),
+            interval: BarInterval::Daily,
+        }
+    }
+
+    /// The same cache, reading and writing bars of `interval`.
+    pub fn with_interval(&self, interval: BarInterval) -> Self {
+        Self {
+            cache_dir: self.cache_dir.clone(),
+            interval,
         }
     }
 
+    /// Interval of the bars this cache reads and writes.
+    pub fn interval(&self) -> BarInterval {
+        self.interval
+    }
+
     /// Root directory of the cache.
     pub fn cache_dir(&self) -> &Path {
         &self.cache_dir
     }
 
-    /// Directory for a specific symbol: `{cache_dir}/symbol={SYMBOL}/`
+    /// Directory for a specific symbol: `{cache_dir}/symbol={SYMBOL}/`, plus
+    /// `interval={code}/` for non-daily bars.
     fn symbol_dir(&self, symbol: &str) -> PathBuf {
-        self.cache_dir.join(format!("symbol={symbol}"))
+        let dir = self.cache_dir.join(format!("symbol={symbol}"));
+        match self.interval {
+            BarInterval::Daily => dir,
+            other => dir.join(format!("interval={}", other.code())),
+        }
     }
 
-    /// Path to the Parquet file for a symbol+year: `{cache_dir}/symbol={SYMBOL}/{year}.parquet`
-    fn year_path(&self, symbol: &str, year: i32) -> PathBuf {
-        self.symbol_dir(symbol).join(format!("{year}.parquet"))
+    /// Directory of a symbol's partition for this cache's interval.
+    pub fn partition_dir(&self, symbol: &str) -> PathBuf {
+        self.symbol_dir(symbol)
+    }
+
+    /// Path to the ingest report sidecar of a symbol: `{symbol_dir}/ingest.json`
+    fn ingest_report_path(&self, symbol: &str) -> PathBuf {
+        self.symbol_dir(symbol).join(INGEST_REPORT)
+    }
+
+    /// Path to the part manifest of a symbol: `{symbol_dir}/parts.json`
+    fn parts_path(&self, symbol: &str) -> PathBuf {
+        self.symbol_dir(symbol).join(PARTS_MANIFEST)
     }
 
     /// Path to the metadata sidecar for a symbol.
@@ -63,8 +165,10 @@ impl ParquetCache {
 
     /// Write bars for a symbol to the cache.
     ///
-    /// Groups bars by year and writes one Parquet file per year.
-    /// Writes are atomic: write to .tmp then rename.
+    /// The bars are appended as a new part; where they overlap cached dates
+    /// they replace the cached bars. A partition still in the one-file-per-year
+    /// layout is migrated first. The metadata sidecar describes the merged
+    /// series afterwards.
     pub fn write(&self, symbol: &str, bars: &[RawBar]) -> Result<(), DataError> {
         if bars.is_empty() {
             return Err(DataError::CacheError("no bars to cache".into()));
@@ -74,96 +178,193 @@ impl ParquetCache {
         fs::create_dir_all(&sym_dir)
             .map_err(|e| DataError::CacheError(format!("failed to create dir: {e}")))?;
 
-        // Group bars by year
-        let mut by_year: HashMap<i32, Vec<&RawBar>> = HashMap::new();
-        for bar in bars {
-            by_year.entry(bar.date.year()).or_default().push(bar);
-        }
-
-        // Write each year partition
-        for (year, year_bars) in &by_year {
-            let df = bars_to_dataframe(year_bars)?;
-            let path = self.year_path(symbol, *year);
-            let tmp_path = path.with_extension("parquet.tmp");
+        let mut manifest = self.migrate_legacy(symbol)?;
+        let refs: Vec<&RawBar> = bars.iter().collect();
+        let part = self.write_part(symbol, next_part_number(&sym_dir, &manifest), refs)?;
+        manifest.parts.push(part);
+        self.write_manifest(symbol, &manifest)?;
 
-            write_parquet(&df, &tmp_path)?;
+        self.write_meta(symbol, &self.load(symbol)?)
+    }
 
-            // Atomic rename
-            fs::rename(&tmp_path, &path).map_err(|e| {
-                // Clean up temp file on rename failure
-                let _ = fs::remove_file(&tmp_path);
-                DataError::CacheError(format!("atomic rename failed: {e}"))
-            })?;
+    /// Load all cached bars for a symbol, sorted by date ascending.
+    ///
+    /// Parts are merged in manifest order; a date present in several parts
+    /// takes the bar from the newest one.
+    pub fn load(&self, symbol: &str) -> Result<Vec<RawBar>, DataError> {
+        let sym_dir = self.symbol_dir(symbol);
+        if !sym_dir.exists() {
+            return Err(DataError::NoCachedData {
+                symbol: symbol.to_string(),
+            });
         }
 
-        // Write metadata sidecar
-        let meta = CacheMeta {
-            symbol: symbol.to_string(),
-            start_date: bars.first().unwrap().date,
-            end_date: bars.last().unwrap().date,
-            bar_count: bars.len(),
-            data_hash: blake3::hash(
-                &serde_json::to_vec(bars)
-                    .map_err(|e| DataError::CacheError(format!("hash serialization: {e}")))?,
-            )
-            .to_hex()
-            .to_string(),
-            source: "ingest".to_string(),
-            cached_at: chrono::Local::now().naive_local(),
+        let all_bars = match self.parts(symbol) {
+            Some(manifest) => {
+                let mut merged = BTreeMap::new();
+                for part in &manifest.parts {
+                    let path = sym_dir.join(&part.file);
+                    if !path.exists() {
+                        eprintln!("WARNING: missing cache part {}", path.display());
+                        continue;
+                    }
+                    for bar in load_or_quarantine(&path) {
+                        merged.insert(bar.date, bar);
+                    }
+                }
+                merged.into_values().collect()
+            }
+            None => self.load_legacy(&sym_dir)?,
         };
-        let meta_json = serde_json::to_string_pretty(&meta)
-            .map_err(|e| DataError::CacheError(format!("meta serialization: {e}")))?;
-        fs::write(self.meta_path(symbol), meta_json)
-            .map_err(|e| DataError::CacheError(format!("meta write: {e}")))?;
 
-        Ok(())
+        if all_bars.is_empty() {
+            return Err(DataError::NoCachedData {
+                symbol: symbol.to_string(),
+            });
+        }
+        Ok(all_bars)
     }
 
-    /// Load all cached bars for a symbol, sorted by date ascending.
-    pub fn load(&self, symbol: &str) -> Result<Vec<RawBar>, DataError> {
+    /// The part manifest of a symbol, or None for an empty partition or one
+    /// still in the one-file-per-year layout.
+    pub fn parts(&self, symbol: &str) -> Option<PartManifest> {
+        let content = fs::read_to_string(self.parts_path(symbol)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Merge a symbol's parts into a single part, dropping bars shadowed by a
+    /// newer part, and delete part files the manifest does not list
+    /// (leftovers of interrupted writes). The merged series is unchanged.
+    pub fn compact(&self, symbol: &str) -> Result<CompactReport, DataError> {
         let sym_dir = self.symbol_dir(symbol);
         if !sym_dir.exists() {
             return Err(DataError::NoCachedData {
                 symbol: symbol.to_string(),
             });
         }
+        let mut manifest = self.migrate_legacy(symbol)?;
+        let parts_before = manifest.parts.len();
+        let stored: usize = manifest.parts.iter().map(|p| p.bar_count).sum();
+        let merged = self.load(symbol)?;
+
+        if parts_before > 1 {
+            let number = next_part_number(&sym_dir, &manifest);
+            let part = self.write_part(symbol, number, merged.iter().collect())?;
+            manifest = PartManifest { parts: vec![part] };
+            self.write_manifest(symbol, &manifest)?;
+        }
 
-        let mut all_bars = Vec::new();
-
+        let mut files_removed = 0;
         let entries =
             fs::read_dir(&sym_dir).map_err(|e| DataError::CacheError(format!("read dir: {e}")))?;
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let listed = manifest.parts.iter().any(|p| p.file == name);
+            if part_number(&name).is_some() && !listed {
+                fs::remove_file(entry.path())
+                    .map_err(|e| DataError::CacheError(format!("remove {name}: {e}")))?;
+                files_removed += 1;
+            }
+        }
 
-        for entry in entries {
-            let entry = entry.map_err(|e| DataError::CacheError(format!("dir entry: {e}")))?;
-            let path = entry.path();
+        Ok(CompactReport {
+            symbol: symbol.to_string(),
+            parts_before,
+            parts_after: manifest.parts.len(),
+            bars: merged.len(),
+            shadowed_bars: stored.saturating_sub(merged.len()),
+            files_removed,
+        })
+    }
 
-            // Skip non-parquet files (meta.json, .quarantined, etc)
-            if path.extension().and_then(|e| e.to_str()) != Some("parquet") {
-                continue;
-            }
+    /// Load the one-file-per-year data of a partition without a manifest.
+    fn load_legacy(&self, sym_dir: &Path) -> Result<Vec<RawBar>, DataError> {
+        let mut all_bars = Vec::new();
+        for path in legacy_files(sym_dir)? {
+            all_bars.extend(load_or_quarantine(&path));
+        }
+        all_bars.sort_by_key(|b| b.date);
+        Ok(all_bars)
+    }
 
-            match load_and_validate_parquet(&path) {
-                Ok(bars) => all_bars.extend(bars),
-                Err(e) => {
-                    // Quarantine the corrupt file
-                    let quarantine = path.with_extension("parquet.quarantined");
-                    eprintln!(
-                        "WARNING: quarantining corrupt cache file {}: {e}",
-                        path.display()
-                    );
-                    let _ = fs::rename(&path, &quarantine);
-                }
-            }
+    /// The partition's manifest, first moving one-file-per-year data into a
+    /// single part. Empty for a new partition.
+    fn migrate_legacy(&self, symbol: &str) -> Result<PartManifest, DataError> {
+        if let Some(manifest) = self.parts(symbol) {
+            return Ok(manifest);
+        }
+        let sym_dir = self.symbol_dir(symbol);
+        let legacy = legacy_files(&sym_dir)?;
+        let bars = self.load_legacy(&sym_dir)?;
+        if bars.is_empty() {
+            return Ok(PartManifest::default());
         }
 
-        if all_bars.is_empty() {
-            return Err(DataError::NoCachedData {
-                symbol: symbol.to_string(),
-            });
+        let part = self.write_part(symbol, 1, bars.iter().collect())?;
+        let manifest = PartManifest { parts: vec![part] };
+        self.write_manifest(symbol, &manifest)?;
+        for path in legacy {
+            let _ = fs::remove_file(path);
         }
+        Ok(manifest)
+    }
 
-        all_bars.sort_by_key(|b| b.date);
-        Ok(all_bars)
+    /// Write `bars` as part `number`. Atomic: written to .tmp, then renamed.
+    fn write_part(
+        &self,
+        symbol: &str,
+        number: u32,
+        mut bars: Vec<&RawBar>,
+    ) -> Result<PartInfo, DataError> {
+        bars.sort_by_key(|b| b.date);
+        let file = part_file_name(number);
+        let path = self.symbol_dir(symbol).join(&file);
+        let tmp_path = path.with_extension("parquet.tmp");
+
+        write_parquet(&bars_to_dataframe(&bars)?, &tmp_path)?;
+        fs::rename(&tmp_path, &path).map_err(|e| {
+            // Clean up temp file on rename failure
+            let _ = fs::remove_file(&tmp_path);
+            DataError::CacheError(format!("atomic rename failed: {e}"))
+        })?;
+
+        Ok(PartInfo {
+            file,
+            start_date: bars[0].date,
+            end_date: bars[bars.len() - 1].date,
+            bar_count: bars.len(),
+        })
+    }
+
+    /// Replace the part manifest atomically.
+    fn write_manifest(&self, symbol: &str, manifest: &PartManifest) -> Result<(), DataError> {
+        let json = serde_json::to_string_pretty(manifest)
+            .map_err(|e| DataError::CacheError(format!("manifest serialization: {e}")))?;
+        let path = self.parts_path(symbol);
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, json)
+            .map_err(|e| DataError::CacheError(format!("manifest write: {e}")))?;
+        fs::rename(&tmp_path, &path)
+            .map_err(|e| DataError::CacheError(format!("atomic rename failed: {e}")))
+    }
+
+    /// Write the metadata sidecar for the merged series `bars`.
+    fn write_meta(&self, symbol: &str, bars: &[RawBar]) -> Result<(), DataError> {
+        let meta = CacheMeta {
+            symbol: symbol.to_string(),
+            start_date: bars.first().unwrap().date,
+            end_date: bars.last().unwrap().date,
+            bar_count: bars.len(),
+            data_hash: content_hash(bars)?,
+            source: "ingest".to_string(),
+            cached_at: chrono::Local::now().naive_local(),
+        };
+        let meta_json = serde_json::to_string_pretty(&meta)
+            .map_err(|e| DataError::CacheError(format!("meta serialization: {e}")))?;
+        fs::write(self.meta_path(symbol), meta_json)
+            .map_err(|e| DataError::CacheError(format!("meta write: {e}")))?;
+
+        Ok(())
     }
 
     /// Check if a symbol has cached data and return its metadata.
@@ -173,6 +374,80 @@ impl ParquetCache {
         serde_json::from_str(&content).ok()
     }
 
+    /// Record the report of the ingest whose bars were just written. A clean
+    /// report removes the sidecar, so it always describes the latest ingest.
+    pub fn write_ingest_report(
+        &self,
+        symbol: &str,
+        report: &IngestReport,
+    ) -> Result<(), DataError> {
+        let path = self.ingest_report_path(symbol);
+        if report.is_clean() {
+            return match fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                    Err(DataError::CacheError(format!("ingest report remove: {e}")))
+                }
+                _ => Ok(()),
+            };
+        }
+        let json = serde_json::to_string_pretty(report)
+            .map_err(|e| DataError::CacheError(format!("ingest report serialization: {e}")))?;
+        fs::write(path, json)
+            .map_err(|e| DataError::CacheError(format!("ingest report write: {e}")))
+    }
+
+    /// Corrections made by the latest ingest into a symbol's partition; None
+    /// when it needed none (or the sidecar is unreadable).
+    pub fn ingest_report(&self, symbol: &str) -> Option<IngestReport> {
+        let content = fs::read_to_string(self.ingest_report_path(symbol)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Vintage of a symbol's partition, loading and hashing its bars.
+    pub fn vintage(&self, symbol: &str) -> Result<PartitionVintage, DataError> {
+        self.vintage_of(symbol, &self.load(symbol)?)
+    }
+
+    /// Vintage of a symbol's partition whose merged bars the caller has
+    /// already loaded.
+    pub fn vintage_of(&self, symbol: &str, bars: &[RawBar]) -> Result<PartitionVintage, DataError> {
+        Ok(PartitionVintage {
+            symbol: symbol.to_string(),
+            interval: self.interval,
+            cached_at: self.get_meta(symbol).map(|m| m.cached_at),
+            content_hash: content_hash(bars)?,
+        })
+    }
+
+    /// Files a symbol's bars are read from, plus its manifest and sidecars:
+    /// everything needed to load the partition elsewhere. Orphaned
+    /// and quarantined files are left out.
+    pub fn partition_files(&self, symbol: &str) -> Result<Vec<PathBuf>, DataError> {
+        let sym_dir = self.symbol_dir(symbol);
+        if !sym_dir.exists() {
+            return Err(DataError::NoCachedData {
+                symbol: symbol.to_string(),
+            });
+        }
+        let mut files = match self.parts(symbol) {
+            Some(manifest) => {
+                let mut files = vec![self.parts_path(symbol)];
+                files.extend(manifest.parts.iter().map(|p| sym_dir.join(&p.file)));
+                files
+            }
+            None => legacy_files(&sym_dir)?,
+        };
+        let meta = self.meta_path(symbol);
+        if meta.exists() {
+            files.push(meta);
+        }
+        let report = self.ingest_report_path(symbol);
+        if report.exists() {
+            files.push(report);
+        }
+        Ok(files)
+    }
+
     /// Check which symbols have cached data, and their date ranges.
     pub fn status(&self, symbols: &[&str]) -> Vec<CacheStatus> {
         symbols
@@ -206,6 +481,119 @@ impl ParquetCache {
             }
         }
     }
+
+    /// Every symbol directory in the cache, sorted by symbol.
+    ///
+    /// Metadata is read at this cache's interval; sizes cover the whole
+    /// symbol directory. A missing cache directory lists as empty.
+    pub fn entries(&self) -> Result<Vec<CacheEntry>, DataError> {
+        if !self.cache_dir.exists() {
+            return Ok(Vec::new());
+        }
+        let dir_entries = fs::read_dir(&self.cache_dir)
+            .map_err(|e| DataError::CacheError(format!("read dir: {e}")))?;
+
+        let mut entries = Vec::new();
+        for entry in dir_entries {
+            let entry = entry.map_err(|e| DataError::CacheError(format!("dir entry: {e}")))?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Some(symbol) = name.strip_prefix("symbol=") else {
+                continue;
+            };
+            let meta = match fs::read_to_string(self.meta_path(symbol)) {
+                Err(_) => EntryMeta::Missing,
+                Ok(content) => match serde_json::from_str(&content) {
+                    Ok(meta) => EntryMeta::Valid(meta),
+                    Err(_) => EntryMeta::Corrupt,
+                },
+            };
+            entries.push(CacheEntry {
+                symbol: symbol.to_string(),
+                size_bytes: dir_size(&entry.path()),
+                meta,
+            });
+        }
+        entries.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        Ok(entries)
+    }
+
+    /// Entries last written before `cutoff`. Entries without readable
+    /// metadata are never included.
+    pub fn unused_since(&self, cutoff: NaiveDateTime) -> Result<Vec<CacheEntry>, DataError> {
+        let mut entries = self.entries()?;
+        entries.retain(|e| e.meta().is_some_and(|m| m.cached_at < cutoff));
+        Ok(entries)
+    }
+
+    /// Delete everything cached for `symbol`, at every interval.
+    pub fn remove_symbol(&self, symbol: &str) -> Result<(), DataError> {
+        fs::remove_dir_all(self.cache_dir.join(format!("symbol={symbol}")))
+            .map_err(|e| DataError::CacheError(format!("remove {symbol}: {e}")))
+    }
+}
+
+/// A symbol directory found by [`ParquetCache::entries`].
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    pub symbol: String,
+    pub meta: EntryMeta,
+    /// Bytes on disk under the symbol directory, all intervals included.
+    pub size_bytes: u64,
+}
+
+impl CacheEntry {
+    pub fn meta(&self) -> Option<&CacheMeta> {
+        match &self.meta {
+            EntryMeta::Valid(meta) => Some(meta),
+            EntryMeta::Missing | EntryMeta::Corrupt => None,
+        }
+    }
+
+    /// "start to end", or why the range is unknown.
+    pub fn date_range_label(&self) -> String {
+        match &self.meta {
+            EntryMeta::Valid(meta) => format!("{} to {}", meta.start_date, meta.end_date),
+            EntryMeta::Missing => "(no meta)".into(),
+            EntryMeta::Corrupt => "(corrupt meta)".into(),
+        }
+    }
+
+    pub fn bar_count(&self) -> usize {
+        self.meta().map_or(0, |m| m.bar_count)
+    }
+}
+
+/// State of a cache entry's metadata sidecar.
+#[derive(Debug, Clone)]
+pub enum EntryMeta {
+    Valid(CacheMeta),
+    Missing,
+    Corrupt,
+}
+
+/// Total size of the files under `path`.
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(t) if t.is_dir() => dir_size(&entry.path()),
+            _ => entry.metadata().map_or(0, |m| m.len()),
+        })
+        .sum()
+}
+
+/// Human-readable byte count: B, KB or MB.
+pub fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{bytes} B")
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
 }
 
 /// Cache status for a single symbol.
@@ -229,6 +617,77 @@ pub enum CoverageResult {
     },
 }
 
+/// BLAKE3 over the JSON form of a merged series.
+fn content_hash(bars: &[RawBar]) -> Result<String, DataError> {
+    let json = serde_json::to_vec(bars)
+        .map_err(|e| DataError::CacheError(format!("hash serialization: {e}")))?;
+    Ok(blake3::hash(&json).to_hex().to_string())
+}
+
+// ── Part helpers ────────────────────────────────────────────────────
+
+/// `part-0001.parquet` for part 1.
+fn part_file_name(number: u32) -> String {
+    format!("part-{number:04}.parquet")
+}
+
+/// Number of a part file or its temp file, None for any other name.
+fn part_number(name: &str) -> Option<u32> {
+    let rest = name.strip_prefix("part-")?;
+    let digits = rest
+        .strip_suffix(".parquet")
+        .or_else(|| rest.strip_suffix(".parquet.tmp"))?;
+    digits.parse().ok()
+}
+
+/// `*.parquet` files of a partition in the one-file-per-year layout. Part
+/// files (orphans of a first write interrupted before its manifest) and
+/// non-parquet files (meta.json, .quarantined, etc) are skipped.
+fn legacy_files(sym_dir: &Path) -> Result<Vec<PathBuf>, DataError> {
+    let mut files = Vec::new();
+    let entries =
+        fs::read_dir(sym_dir).map_err(|e| DataError::CacheError(format!("read dir: {e}")))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| DataError::CacheError(format!("dir entry: {e}")))?;
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if path.extension().and_then(|e| e.to_str()) == Some("parquet")
+            && part_number(&name).is_none()
+        {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+/// One past the highest part number listed or present on disk, so a new
+/// part never reuses the name of an orphan.
+fn next_part_number(sym_dir: &Path, manifest: &PartManifest) -> u32 {
+    let on_disk = fs::read_dir(sym_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|e| part_number(&e.file_name().to_string_lossy()));
+    let listed = manifest.parts.iter().filter_map(|p| part_number(&p.file));
+    on_disk.chain(listed).max().unwrap_or(0) + 1
+}
+
+/// Load a Parquet file, quarantining it (and returning no bars) if corrupt.
+fn load_or_quarantine(path: &Path) -> Vec<RawBar> {
+    match load_and_validate_parquet(path) {
+        Ok(bars) => bars,
+        Err(e) => {
+            let quarantine = path.with_extension("parquet.quarantined");
+            eprintln!(
+                "WARNING: quarantining corrupt cache file {}: {e}",
+                path.display()
+            );
+            let _ = fs::rename(path, &quarantine);
+            Vec::new()
+        }
+    }
+}
+
 // ── Parquet I/O helpers ─────────────────────────────────────────────
 
 /// Convert raw bars to a Polars DataFrame.
@@ -415,6 +874,29 @@ mod tests {
         let _ = fs::remove_dir_all(&dir);
     }
 
+    #[test]
+    fn intervals_are_cached_separately() {
+        let dir = temp_cache_dir();
+        let daily = ParquetCache::new(&dir);
+        let weekly = daily.with_interval(BarInterval::Weekly);
+
+        daily.write("SPY", &sample_bars()).unwrap();
+        assert!(weekly.load("SPY").is_err());
+
+        let week = BarInterval::Weekly.resample(&sample_bars());
+        weekly.write("SPY", &week).unwrap();
+        assert!(dir
+            .join("symbol=SPY/interval=1wk/part-0001.parquet")
+            .exists());
+        assert_eq!(weekly.load("SPY").unwrap().len(), 1);
+        assert_eq!(weekly.get_meta("SPY").unwrap().bar_count, 1);
+        // Daily bars still load from the original layout
+        assert_eq!(daily.load("SPY").unwrap().len(), 2);
+        assert_eq!(daily.get_meta("SPY").unwrap().bar_count, 2);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn load_nonexistent_returns_error() {
         let dir = temp_cache_dir();
@@ -444,6 +926,44 @@ mod tests {
         let _ = fs::remove_dir_all(&dir);
     }
 
+    #[test]
+    fn vintage_hashes_the_merged_partition() {
+        let dir = temp_cache_dir();
+        let cache = ParquetCache::new(&dir);
+
+        cache.write("SPY", &sample_bars()).unwrap();
+        let before = cache.vintage("SPY").unwrap();
+        let meta = cache.get_meta("SPY").unwrap();
+        assert_eq!(before.content_hash, meta.data_hash);
+        assert_eq!(before.cached_at, Some(meta.cached_at));
+        assert_eq!(before.interval, BarInterval::Daily);
+
+        let files = cache.partition_files("SPY").unwrap();
+        let names: Vec<String> = files
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, ["parts.json", "part-0001.parquet", "meta.json"]);
+
+        // Rewriting the same bars leaves the content hash alone
+        cache.write("SPY", &sample_bars()[1..]).unwrap();
+        assert_eq!(
+            cache.vintage("SPY").unwrap().content_hash,
+            before.content_hash
+        );
+
+        let mut revised = sample_bars();
+        revised[1].close = 102.5;
+        cache.write("SPY", &revised[1..]).unwrap();
+        assert_ne!(
+            cache.vintage("SPY").unwrap().content_hash,
+            before.content_hash
+        );
+        assert!(cache.partition_files("QQQ").is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn cache_status_query() {
         let dir = temp_cache_dir();
@@ -481,4 +1001,203 @@ mod tests {
 
         let _ = fs::remove_dir_all(&dir);
     }
+
+    #[test]
+    fn entries_list_symbols_with_meta_state() {
+        let dir = temp_cache_dir();
+        let cache = ParquetCache::new(&dir);
+        assert!(ParquetCache::new(dir.join("missing"))
+            .entries()
+            .unwrap()
+            .is_empty());
+
+        cache.write("SPY", &sample_bars()).unwrap();
+        fs::create_dir_all(dir.join("symbol=QQQ")).unwrap();
+        fs::create_dir_all(dir.join("symbol=IWM")).unwrap();
+        fs::write(dir.join("symbol=IWM/meta.json"), "{").unwrap();
+        fs::create_dir_all(dir.join("not_a_symbol")).unwrap();
+
+        let entries = cache.entries().unwrap();
+        let symbols: Vec<&str> = entries.iter().map(|e| e.symbol.as_str()).collect();
+        assert_eq!(symbols, ["IWM", "QQQ", "SPY"]);
+        assert_eq!(entries[0].date_range_label(), "(corrupt meta)");
+        assert_eq!(entries[1].date_range_label(), "(no meta)");
+        assert_eq!(entries[2].date_range_label(), "2024-01-02 to 2024-01-03");
+        assert_eq!(entries[2].bar_count(), 2);
+        assert!(entries[2].size_bytes > 0);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn unused_entries_can_be_removed() {
+        let dir = temp_cache_dir();
+        let cache = ParquetCache::new(&dir);
+        cache.write("SPY", &sample_bars()).unwrap();
+        fs::create_dir_all(dir.join("symbol=QQQ")).unwrap();
+
+        let past = chrono::Local::now().naive_local() - chrono::Duration::days(1);
+        assert!(cache.unused_since(past).unwrap().is_empty());
+        let future = chrono::Local::now().naive_local() + chrono::Duration::days(1);
+        let stale = cache.unused_since(future).unwrap();
+        assert_eq!(stale.len(), 1, "entries without meta are kept");
+
+        cache.remove_symbol(&stale[0].symbol).unwrap();
+        let left: Vec<String> = cache
+            .entries()
+            .unwrap()
+            .into_iter()
+            .map(|e| e.symbol)
+            .collect();
+        assert_eq!(left, ["QQQ"]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    fn closes(bars: &[RawBar]) -> Vec<(NaiveDate, f64)> {
+        bars.iter().map(|b| (b.date, b.close)).collect()
+    }
+
+    fn bar(day: u32, close: f64) -> RawBar {
+        RawBar {
+            date: NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+            open: close,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+            volume: 1000,
+            adj_close: close,
+        }
+    }
+
+    #[test]
+    fn appends_are_parts_and_newest_part_wins_overlaps() {
+        let dir = temp_cache_dir();
+        let cache = ParquetCache::new(&dir);
+
+        cache.write("SPY", &[bar(2, 101.0), bar(3, 102.0)]).unwrap();
+        cache.write("SPY", &[bar(3, 200.0), bar(4, 201.0)]).unwrap();
+        let parts = cache.parts("SPY").unwrap().parts;
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[1].file, "part-0002.parquet");
+        assert_eq!(parts[1].start_date, bar(3, 0.0).date);
+
+        let loaded: Vec<f64> = cache.load("SPY").unwrap().iter().map(|b| b.close).collect();
+        assert_eq!(loaded, [101.0, 200.0, 201.0]);
+        assert_eq!(cache.get_meta("SPY").unwrap().bar_count, 3);
+
+        let report = cache.compact("SPY").unwrap();
+        assert_eq!((report.parts_before, report.parts_after), (2, 1));
+        assert_eq!(report.shadowed_bars, 1);
+        assert_eq!(report.files_removed, 2);
+        let loaded: Vec<f64> = cache.load("SPY").unwrap().iter().map(|b| b.close).collect();
+        assert_eq!(loaded, [101.0, 200.0, 201.0]);
+        assert_eq!(cache.parts("SPY").unwrap().parts[0].bar_count, 3);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn interrupted_append_leaves_an_ignored_orphan() {
+        let dir = temp_cache_dir();
+        let cache = ParquetCache::new(&dir);
+        cache.write("SPY", &sample_bars()).unwrap();
+
+        // A crash after the part was written, before the manifest listed it,
+        // and one mid-write
+        let sym_dir = dir.join("symbol=SPY");
+        let orphan = bar(5, 500.0);
+        write_parquet(
+            &bars_to_dataframe(&[&orphan]).unwrap(),
+            &sym_dir.join("part-0002.parquet"),
+        )
+        .unwrap();
+        fs::write(sym_dir.join("part-0003.parquet.tmp"), "partial").unwrap();
+
+        assert_eq!(closes(&cache.load("SPY").unwrap()), closes(&sample_bars()));
+        cache.write("SPY", &[bar(4, 103.0)]).unwrap();
+        assert_eq!(
+            cache.parts("SPY").unwrap().parts[1].file,
+            "part-0004.parquet"
+        );
+        assert_eq!(cache.load("SPY").unwrap().len(), 3);
+
+        let report = cache.compact("SPY").unwrap();
+        assert_eq!(report.files_removed, 4);
+        let mut left: Vec<String> = fs::read_dir(&sym_dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        left.sort();
+        assert_eq!(left, ["meta.json", "part-0005.parquet", "parts.json"]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn year_files_load_and_migrate_on_first_write() {
+        let dir = temp_cache_dir();
+        let cache = ParquetCache::new(&dir);
+        let sym_dir = dir.join("symbol=SPY");
+        fs::create_dir_all(&sym_dir).unwrap();
+        let bars = sample_bars();
+        let refs: Vec<&RawBar> = bars.iter().collect();
+        write_parquet(
+            &bars_to_dataframe(&refs).unwrap(),
+            &sym_dir.join("2024.parquet"),
+        )
+        .unwrap();
+
+        assert_eq!(closes(&cache.load("SPY").unwrap()), closes(&bars));
+        assert!(cache.parts("SPY").is_none());
+
+        cache.write("SPY", &[bar(4, 103.0)]).unwrap();
+        assert!(!sym_dir.join("2024.parquet").exists());
+        let files: Vec<String> = cache
+            .parts("SPY")
+            .unwrap()
+            .parts
+            .into_iter()
+            .map(|p| p.file)
+            .collect();
+        assert_eq!(files, ["part-0001.parquet", "part-0002.parquet"]);
+        assert_eq!(cache.load("SPY").unwrap().len(), 3);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn data_hash_follows_the_merged_series_not_the_layout() {
+        let dir = temp_cache_dir();
+        let whole = ParquetCache::new(dir.join("whole"));
+        let pieces = ParquetCache::new(dir.join("pieces"));
+
+        whole
+            .write("SPY", &[bar(2, 101.0), bar(3, 200.0), bar(4, 201.0)])
+            .unwrap();
+        pieces
+            .write("SPY", &[bar(2, 101.0), bar(3, 102.0)])
+            .unwrap();
+        pieces
+            .write("SPY", &[bar(3, 200.0), bar(4, 201.0)])
+            .unwrap();
+
+        let hash = |c: &ParquetCache| c.get_meta("SPY").unwrap().data_hash;
+        assert_eq!(hash(&whole), hash(&pieces));
+        pieces.compact("SPY").unwrap();
+        assert_eq!(hash(&whole), hash(&pieces));
+        assert_eq!(
+            closes(&whole.load("SPY").unwrap()),
+            closes(&pieces.load("SPY").unwrap())
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn sizes_are_human_readable() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(2048), "2.0 KB");
+        assert_eq!(format_size(3 * 1024 * 1024), "3.0 MB");
+    }
 }
@@ -1,8 +1,9 @@
 //! Circuit breaker for data provider rate limiting and IP bans.
 //!
-//! When the provider returns HTTP 403 (IP ban) or repeated 429 (rate limit),
-//! the circuit breaker trips and refuses all subsequent requests for a cooldown
-//! period (default 30 minutes).
+//! When the provider returns HTTP 403 (IP ban), or consecutive symbols keep
+//! failing after their retries (e.g. persistent 429 rate limits), the circuit
+//! breaker trips and refuses all subsequent requests for a cooldown period
+//! (default 30 minutes).
 
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
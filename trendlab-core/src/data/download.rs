@@ -1,13 +1,114 @@
 //! Download orchestrator — coordinates multi-symbol downloads with progress reporting.
+//!
+//! Batches degrade gracefully:
+//! - Transient errors (rate limits, network) are retried per symbol with backoff
+//!   from the provider's `RetryPolicy`.
+//! - Each completed symbol is recorded in a resume manifest in the cache
+//!   directory, so re-running an interrupted batch skips it unless forced.
+//! - A response covering noticeably less than the requested range is recorded
+//!   as `PartialData` with the range actually covered, not as plain success.
+//! - Every fetched symbol's ingest corrections (duplicates, misordered or
+//!   out-of-range rows, a partial last bar) are kept in the summary.
 
-use super::cache::ParquetCache;
-use super::ingest;
-use super::provider::{DataError, DataProvider, DownloadProgress};
-use chrono::NaiveDate;
+use super::cache::{CoverageResult, ParquetCache};
+use super::ingest::{self, IngestOptions, IngestReport};
+use super::provider::{fetch_with_retry, DataError, DataProvider, DownloadProgress, RawBar};
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Resume manifest file name, stored at the cache root.
+const MANIFEST_FILE: &str = "download_manifest.json";
+
+/// A response is partial when it has fewer than this fraction of the bars the
+/// requested range implies (leaves room for holidays and half-days).
+const PARTIAL_COVERAGE_THRESHOLD: f64 = 0.9;
+
+/// Trading days per weekday (~252 sessions out of ~261 weekdays a year).
+const SESSIONS_PER_WEEKDAY: f64 = 252.0 / 261.0;
+
+/// Final outcome for one symbol in a batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DownloadOutcome {
+    /// Fetched, ingested and cached over the full requested range.
+    Downloaded { bars: usize },
+    /// Cache already covered the requested range.
+    Cached,
+    /// Completed by an earlier run of the same batch (resume manifest).
+    Resumed,
+    /// Fetched and cached, but the provider returned less history than requested.
+    PartialData {
+        covered_start: NaiveDate,
+        covered_end: NaiveDate,
+        bars: usize,
+        expected_bars: usize,
+    },
+    /// Failed after all retry attempts.
+    Failed { error: String, attempts: u32 },
+}
+
+impl DownloadOutcome {
+    /// Whether this outcome left usable data in the cache.
+    pub fn is_completed(&self) -> bool {
+        !matches!(self, Self::Failed { .. })
+    }
+}
+
+/// Outcome for a named symbol.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolOutcome {
+    pub symbol: String,
+    pub outcome: DownloadOutcome,
+}
+
+/// Symbols completed for one requested date range, persisted between runs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DownloadManifest {
+    pub start: Option<NaiveDate>,
+    pub end: Option<NaiveDate>,
+    pub completed: BTreeMap<String, DownloadOutcome>,
+}
+
+impl DownloadManifest {
+    fn path(cache: &ParquetCache) -> PathBuf {
+        cache.cache_dir().join(MANIFEST_FILE)
+    }
+
+    /// Load the manifest for `[start, end]`. A missing or unreadable manifest,
+    /// or one recorded for a different range, yields an empty one.
+    pub fn load(cache: &ParquetCache, start: NaiveDate, end: NaiveDate) -> Self {
+        let manifest = std::fs::read_to_string(Self::path(cache))
+            .ok()
+            .and_then(|json| serde_json::from_str::<Self>(&json).ok());
+        match manifest {
+            Some(m) if m.start == Some(start) && m.end == Some(end) => m,
+            _ => Self {
+                start: Some(start),
+                end: Some(end),
+                completed: BTreeMap::new(),
+            },
+        }
+    }
+
+    /// Persist the manifest. Failures are non-fatal: resume is best-effort.
+    pub fn save(&self, cache: &ParquetCache) {
+        if let Ok(json) = serde_json::to_string_pretty(self) {
+            let _ = std::fs::create_dir_all(cache.cache_dir());
+            let _ = std::fs::write(Self::path(cache), json);
+        }
+    }
+}
 
 /// Download multiple symbols, running them through the ingest pipeline and caching.
 ///
-/// Returns a summary of successes and failures.
+/// Symbols already covered by the cache, or completed by an earlier run over the
+/// same range, are skipped unless `force` is set. A partially cached symbol
+/// appends only the bars outside its cached range; a forced download rewrites
+/// the whole range. `drop_partial_last_bar`
+/// drops a last bar from a session still trading (see [`IngestOptions`]).
+/// Returns a per-symbol summary.
+#[allow(clippy::too_many_arguments)]
 pub fn download_symbols(
     provider: &dyn DataProvider,
     cache: &ParquetCache,
@@ -15,83 +116,688 @@ pub fn download_symbols(
     start: NaiveDate,
     end: NaiveDate,
     force: bool,
+    drop_partial_last_bar: bool,
     progress: &dyn DownloadProgress,
 ) -> DownloadSummary {
     let total = symbols.len();
-    let mut succeeded = 0;
-    let mut failed = 0;
-    let mut errors: Vec<(String, DataError)> = Vec::new();
+    let mut summary = DownloadSummary {
+        total,
+        ..Default::default()
+    };
+    let mut manifest = DownloadManifest::load(cache, start, end);
+    if force {
+        manifest.completed.clear();
+    }
 
     for (i, symbol) in symbols.iter().enumerate() {
         progress.on_start(symbol, i, total);
 
-        // Skip if cache is fresh and not forcing
+        // Skip if cache is fresh or this batch already completed it
+        let coverage = cache.covers_range(symbol, start, end);
         if !force {
-            if let super::cache::CoverageResult::FullyCovered =
-                cache.covers_range(symbol, start, end)
-            {
+            let skipped = if manifest.completed.contains_key(*symbol) {
+                Some(DownloadOutcome::Resumed)
+            } else if coverage == CoverageResult::FullyCovered {
+                Some(DownloadOutcome::Cached)
+            } else {
+                None
+            };
+            if let Some(outcome) = skipped {
                 progress.on_complete(symbol, i, total, &Ok(()));
-                succeeded += 1;
+                summary.record(symbol, outcome, None);
                 continue;
             }
         }
 
-        let result = download_single(provider, cache, symbol, start, end);
-        progress.on_complete(symbol, i, total, &result);
+        // Bail out early if circuit breaker tripped
+        if !provider.is_available() {
+            let result = Err(DataError::CircuitBreakerTripped);
+            let outcome = DownloadOutcome::Failed {
+                error: DataError::CircuitBreakerTripped.to_string(),
+                attempts: 0,
+            };
+            progress.on_complete(symbol, i, total, &result);
+            summary.record(symbol, outcome, result.err());
+            continue;
+        }
 
+        let opts =
+            IngestOptions::daily(start, end).with_drop_partial_last_bar(drop_partial_last_bar);
+        let cached = match coverage {
+            CoverageResult::PartiallyCovered {
+                cached_start,
+                cached_end,
+            } if !force => Some((cached_start, cached_end)),
+            _ => None,
+        };
+        let (result, attempts) = download_single(provider, cache, symbol, &opts, cached, progress);
         match result {
-            Ok(()) => succeeded += 1,
-            Err(e) => {
-                errors.push((symbol.to_string(), e));
-                failed += 1;
+            Ok((outcome, report)) => {
+                summary.ingest_reports.insert(symbol.to_string(), report);
+                progress.on_complete(symbol, i, total, &Ok(()));
+                manifest
+                    .completed
+                    .insert(symbol.to_string(), outcome.clone());
+                manifest.save(cache);
+                summary.record(symbol, outcome, None);
             }
-        }
-
-        // Bail out early if circuit breaker tripped
-        if !provider.is_available() {
-            for sym in &symbols[(i + 1)..total] {
-                errors.push((sym.to_string(), DataError::CircuitBreakerTripped));
-                failed += 1;
+            Err(e) => {
+                let outcome = DownloadOutcome::Failed {
+                    error: e.to_string(),
+                    attempts,
+                };
+                let result = Err(e);
+                progress.on_complete(symbol, i, total, &result);
+                summary.record(symbol, outcome, result.err());
             }
-            break;
         }
     }
 
-    progress.on_batch_complete(succeeded, failed, total);
-
-    DownloadSummary {
-        total,
-        succeeded,
-        failed,
-        errors,
-    }
+    // Partial symbols still landed in the cache; callers report them separately
+    progress.on_batch_complete(summary.succeeded + summary.partial, summary.failed, total);
+    summary
 }
 
-/// Download a single symbol: fetch → ingest → cache.
+/// Download a single symbol: fetch (with retries) → ingest → cache.
+///
+/// With the `cached` date range of a partial cache, only bars outside it are
+/// appended, so a refresh does not store another copy of the history.
+/// Returns the outcome with the ingest's corrections, and the number of
+/// fetch attempts made.
 fn download_single(
     provider: &dyn DataProvider,
     cache: &ParquetCache,
     symbol: &str,
-    start: NaiveDate,
-    end: NaiveDate,
-) -> Result<(), DataError> {
-    let fetch_result = provider.fetch(symbol, start, end)?;
-    let ingest_result = ingest::ingest(fetch_result.bars)?;
-    cache.write(symbol, &ingest_result.bars)?;
-    Ok(())
+    opts: &IngestOptions,
+    cached: Option<(NaiveDate, NaiveDate)>,
+    progress: &dyn DownloadProgress,
+) -> (Result<(DownloadOutcome, IngestReport), DataError>, u32) {
+    let (start, end) = opts.range.expect("downloads request a range");
+    let (fetched, attempts) = fetch_with_retry(provider, symbol, start, end, Some(progress));
+    let result = fetched.and_then(|fetch_result| {
+        let ingest_result = ingest::ingest_with(fetch_result.bars, opts)?;
+        match cached {
+            None => cache.write(symbol, &ingest_result.bars)?,
+            Some((first, last)) => {
+                let new_bars: Vec<RawBar> = ingest_result
+                    .bars
+                    .iter()
+                    .filter(|b| b.date < first || b.date > last)
+                    .cloned()
+                    .collect();
+                if !new_bars.is_empty() {
+                    cache.write(symbol, &new_bars)?;
+                }
+            }
+        }
+        cache.write_ingest_report(symbol, &ingest_result.report)?;
+        let outcome = classify_coverage(&ingest_result.bars, start, end);
+        Ok((outcome, ingest_result.report))
+    });
+    (result, attempts)
+}
+
+/// Compare returned bars against the range requested.
+fn classify_coverage(bars: &[RawBar], start: NaiveDate, end: NaiveDate) -> DownloadOutcome {
+    let expected_bars = expected_sessions(start, end);
+    let (Some(first), Some(last)) = (bars.first(), bars.last()) else {
+        return DownloadOutcome::Downloaded { bars: 0 };
+    };
+    if (bars.len() as f64) < expected_bars as f64 * PARTIAL_COVERAGE_THRESHOLD {
+        DownloadOutcome::PartialData {
+            covered_start: first.date,
+            covered_end: last.date,
+            bars: bars.len(),
+            expected_bars,
+        }
+    } else {
+        DownloadOutcome::Downloaded { bars: bars.len() }
+    }
+}
+
+/// Approximate number of trading sessions in `[start, end]`.
+fn expected_sessions(start: NaiveDate, end: NaiveDate) -> usize {
+    let weekdays = start
+        .iter_days()
+        .take_while(|d| *d <= end)
+        .filter(|d| !matches!(d.weekday(), Weekday::Sat | Weekday::Sun))
+        .count();
+    (weekdays as f64 * SESSIONS_PER_WEEKDAY).round() as usize
 }
 
 /// Summary of a batch download operation.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct DownloadSummary {
     pub total: usize,
+    /// Symbols with full data: downloaded, already cached, or resumed.
     pub succeeded: usize,
+    /// Symbols cached with less history than requested.
+    pub partial: usize,
     pub failed: usize,
     pub errors: Vec<(String, DataError)>,
+    /// Per-symbol outcomes in batch order.
+    pub outcomes: Vec<SymbolOutcome>,
+    /// Ingest corrections for each symbol fetched in this batch.
+    pub ingest_reports: BTreeMap<String, IngestReport>,
 }
 
 impl DownloadSummary {
     pub fn all_succeeded(&self) -> bool {
-        self.failed == 0
+        self.failed == 0 && self.partial == 0
+    }
+
+    /// Whether any symbol finally failed after retries.
+    pub fn has_failures(&self) -> bool {
+        self.failed > 0
+    }
+
+    /// Symbols whose fetched rows needed corrections, with their reports.
+    pub fn corrected(&self) -> impl Iterator<Item = (&str, &IngestReport)> {
+        self.ingest_reports
+            .iter()
+            .filter(|(_, report)| !report.is_clean())
+            .map(|(symbol, report)| (symbol.as_str(), report))
+    }
+
+    /// Symbols that failed, in batch order — the set to retry.
+    pub fn failed_symbols(&self) -> Vec<&str> {
+        self.outcomes
+            .iter()
+            .filter(|o| !o.outcome.is_completed())
+            .map(|o| o.symbol.as_str())
+            .collect()
+    }
+
+    fn record(&mut self, symbol: &str, outcome: DownloadOutcome, error: Option<DataError>) {
+        match &outcome {
+            DownloadOutcome::Failed { .. } => self.failed += 1,
+            DownloadOutcome::PartialData { .. } => self.partial += 1,
+            _ => self.succeeded += 1,
+        }
+        if let Some(e) = error {
+            self.errors.push((symbol.to_string(), e));
+        }
+        self.outcomes.push(SymbolOutcome {
+            symbol: symbol.to_string(),
+            outcome,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::circuit_breaker::CircuitBreaker;
+    use crate::data::provider::{DataSource, FetchResult, RetryPolicy};
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Mutex;
+
+    static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    fn temp_cache() -> ParquetCache {
+        let id = TEST_COUNTER.fetch_add(1, Ordering::SeqCst);
+        let dir = std::env::temp_dir().join(format!(
+            "trendlab_download_test_{}_{id}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        ParquetCache::new(dir)
+    }
+
+    fn d(y: i32, m: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, day).unwrap()
+    }
+
+    fn weekday_bars(start: NaiveDate, end: NaiveDate) -> Vec<RawBar> {
+        start
+            .iter_days()
+            .take_while(|day| *day <= end)
+            .filter(|day| !matches!(day.weekday(), Weekday::Sat | Weekday::Sun))
+            .map(|date| RawBar {
+                date,
+                open: 100.0,
+                high: 101.0,
+                low: 99.0,
+                close: 100.5,
+                volume: 1_000,
+                adj_close: 100.5,
+            })
+            .collect()
+    }
+
+    /// Provider scripted per symbol: a queue of errors, then bars from
+    /// `first_bar`, or the crafted `rows` when given. With a `breaker` it
+    /// keeps the same circuit breaker books as `YahooProvider`.
+    struct ScriptedProvider {
+        failures: Mutex<HashMap<String, Vec<DataError>>>,
+        first_bar: HashMap<String, NaiveDate>,
+        rows: HashMap<String, Vec<RawBar>>,
+        calls: Mutex<HashMap<String, u32>>,
+        max_attempts: u32,
+        breaker: Option<CircuitBreaker>,
+    }
+
+    impl ScriptedProvider {
+        fn new() -> Self {
+            Self {
+                failures: Mutex::new(HashMap::new()),
+                first_bar: HashMap::new(),
+                rows: HashMap::new(),
+                calls: Mutex::new(HashMap::new()),
+                max_attempts: 3,
+                breaker: None,
+            }
+        }
+
+        fn fail(self, symbol: &str, errors: Vec<DataError>) -> Self {
+            self.failures
+                .lock()
+                .unwrap()
+                .insert(symbol.to_string(), errors);
+            self
+        }
+
+        fn calls(&self, symbol: &str) -> u32 {
+            self.calls.lock().unwrap().get(symbol).copied().unwrap_or(0)
+        }
+    }
+
+    impl DataProvider for ScriptedProvider {
+        fn name(&self) -> &str {
+            "scripted"
+        }
+
+        fn fetch(
+            &self,
+            symbol: &str,
+            start: NaiveDate,
+            end: NaiveDate,
+        ) -> Result<FetchResult, DataError> {
+            *self
+                .calls
+                .lock()
+                .unwrap()
+                .entry(symbol.to_string())
+                .or_default() += 1;
+            if let Some(queue) = self.failures.lock().unwrap().get_mut(symbol) {
+                if !queue.is_empty() {
+                    return Err(queue.remove(0));
+                }
+            }
+            if let Some(breaker) = &self.breaker {
+                breaker.record_success();
+            }
+            let first = self.first_bar.get(symbol).copied().unwrap_or(start);
+            let bars = match self.rows.get(symbol) {
+                Some(rows) => rows.clone(),
+                None => weekday_bars(first, end),
+            };
+            Ok(FetchResult {
+                symbol: symbol.to_string(),
+                bars,
+                source: DataSource::YahooFinance,
+            })
+        }
+
+        fn is_available(&self) -> bool {
+            self.breaker.as_ref().map_or(true, |b| b.is_allowed())
+        }
+
+        fn retry_policy(&self) -> RetryPolicy {
+            RetryPolicy {
+                max_attempts: self.max_attempts,
+                base_delay: std::time::Duration::ZERO,
+                max_delay: std::time::Duration::ZERO,
+            }
+        }
+
+        fn record_exhausted(&self, _error: &DataError) {
+            if let Some(breaker) = &self.breaker {
+                breaker.record_failure();
+            }
+        }
+    }
+
+    struct Silent;
+
+    impl DownloadProgress for Silent {
+        fn on_start(&self, _: &str, _: usize, _: usize) {}
+        fn on_complete(&self, _: &str, _: usize, _: usize, _: &Result<(), DataError>) {}
+        fn on_batch_complete(&self, _: usize, _: usize, _: usize) {}
+    }
+
+    #[test]
+    fn transient_errors_are_retried() {
+        let cache = temp_cache();
+        let provider = ScriptedProvider::new().fail(
+            "SPY",
+            vec![
+                DataError::RateLimited {
+                    retry_after_secs: 5,
+                },
+                DataError::NetworkUnreachable("reset".into()),
+            ],
+        );
+
+        let summary = download_symbols(
+            &provider,
+            &cache,
+            &["SPY"],
+            d(2024, 1, 1),
+            d(2024, 3, 29),
+            false,
+            true,
+            &Silent,
+        );
+
+        assert!(summary.all_succeeded());
+        assert_eq!(provider.calls("SPY"), 3);
+        assert!(matches!(
+            summary.outcomes[0].outcome,
+            DownloadOutcome::Downloaded { .. }
+        ));
+    }
+
+    #[test]
+    fn rate_limited_retries_do_not_trip_the_breaker_for_the_batch() {
+        let cache = temp_cache();
+        let rate_limited = (0..4)
+            .map(|_| DataError::RateLimited {
+                retry_after_secs: 0,
+            })
+            .collect();
+        let mut provider = ScriptedProvider::new().fail("SPY", rate_limited);
+        provider.max_attempts = 5;
+        provider.breaker = Some(CircuitBreaker::default_provider());
+
+        let summary = download_symbols(
+            &provider,
+            &cache,
+            &["SPY", "QQQ", "IWM", "DIA"],
+            d(2024, 1, 1),
+            d(2024, 3, 29),
+            false,
+            true,
+            &Silent,
+        );
+
+        // Four 429s on one symbol exceed the breaker's threshold of three,
+        // but they were retried, so only exhausted symbols count
+        assert!(summary.all_succeeded(), "{:?}", summary.outcomes);
+        assert_eq!(summary.succeeded, 4);
+        assert_eq!(provider.calls("SPY"), 5);
+        for symbol in ["QQQ", "IWM", "DIA"] {
+            assert_eq!(provider.calls(symbol), 1);
+        }
+        assert!(provider.is_available());
+    }
+
+    #[test]
+    fn consecutive_exhausted_symbols_trip_the_breaker() {
+        let cache = temp_cache();
+        let failing = || {
+            (0..3)
+                .map(|_| DataError::Other("HTTP 502".into()))
+                .collect()
+        };
+        let mut provider = ScriptedProvider::new()
+            .fail("A", failing())
+            .fail("B", failing())
+            .fail("C", failing());
+        provider.breaker = Some(CircuitBreaker::default_provider());
+
+        let summary = download_symbols(
+            &provider,
+            &cache,
+            &["A", "B", "C", "SPY"],
+            d(2024, 1, 1),
+            d(2024, 3, 29),
+            false,
+            true,
+            &Silent,
+        );
+
+        assert_eq!(summary.failed, 4);
+        assert_eq!(provider.calls("SPY"), 0);
+        assert_eq!(
+            summary.outcomes[3].outcome,
+            DownloadOutcome::Failed {
+                error: DataError::CircuitBreakerTripped.to_string(),
+                attempts: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn exhausted_retries_fail_only_that_symbol() {
+        let cache = temp_cache();
+        let provider = ScriptedProvider::new().fail(
+            "BAD",
+            (0..3)
+                .map(|_| DataError::Other("HTTP 502".into()))
+                .collect(),
+        );
+
+        let summary = download_symbols(
+            &provider,
+            &cache,
+            &["SPY", "BAD", "QQQ"],
+            d(2024, 1, 1),
+            d(2024, 3, 29),
+            false,
+            true,
+            &Silent,
+        );
+
+        assert!(summary.has_failures());
+        assert_eq!(summary.succeeded, 2);
+        assert_eq!(summary.failed_symbols(), vec!["BAD"]);
+        assert_eq!(
+            summary.outcomes[1].outcome,
+            DownloadOutcome::Failed {
+                error: "data error: HTTP 502".into(),
+                attempts: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn permanent_errors_are_not_retried() {
+        let cache = temp_cache();
+        let provider = ScriptedProvider::new().fail(
+            "NOPE",
+            vec![DataError::SymbolNotFound {
+                symbol: "NOPE".into(),
+            }],
+        );
+
+        let summary = download_symbols(
+            &provider,
+            &cache,
+            &["NOPE"],
+            d(2024, 1, 1),
+            d(2024, 3, 29),
+            false,
+            true,
+            &Silent,
+        );
+
+        assert_eq!(provider.calls("NOPE"), 1);
+        assert_eq!(summary.failed, 1);
+    }
+
+    #[test]
+    fn short_history_is_partial_data() {
+        let cache = temp_cache();
+        let mut provider = ScriptedProvider::new();
+        provider.first_bar.insert("IPO".to_string(), d(2024, 3, 1));
+
+        let summary = download_symbols(
+            &provider,
+            &cache,
+            &["IPO"],
+            d(2024, 1, 1),
+            d(2024, 3, 29),
+            false,
+            true,
+            &Silent,
+        );
+
+        assert_eq!(summary.partial, 1);
+        assert!(!summary.all_succeeded());
+        assert!(!summary.has_failures());
+        match &summary.outcomes[0].outcome {
+            DownloadOutcome::PartialData {
+                covered_start,
+                covered_end,
+                ..
+            } => {
+                assert_eq!(*covered_start, d(2024, 3, 1));
+                assert_eq!(*covered_end, d(2024, 3, 29));
+            }
+            other => panic!("expected PartialData, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn ingest_corrections_are_summarized_and_cached() {
+        let cache = temp_cache();
+        let (start, end) = (d(2024, 1, 1), d(2024, 3, 29));
+        let mut rows = weekday_bars(start, end);
+        let mut repeat = rows[10].clone();
+        repeat.close = 100.75;
+        repeat.adj_close = 100.75;
+        rows.insert(11, repeat);
+        rows.swap(3, 4);
+        rows.push(RawBar {
+            date: d(2024, 4, 1),
+            ..rows[0].clone()
+        });
+        let mut provider = ScriptedProvider::new();
+        provider.rows.insert("SPY".to_string(), rows);
+        let clean = ScriptedProvider::new();
+
+        let summary = download_symbols(
+            &provider,
+            &cache,
+            &["SPY"],
+            start,
+            end,
+            false,
+            true,
+            &Silent,
+        );
+
+        let expected = IngestReport {
+            duplicates_removed: 1,
+            duplicate_dates: vec![d(2024, 1, 15)],
+            out_of_order: 1,
+            out_of_range: vec![d(2024, 4, 1)],
+            partial_bar: None,
+        };
+        let corrected: Vec<_> = summary.corrected().collect();
+        assert_eq!(corrected, [("SPY", &expected)]);
+        assert_eq!(cache.ingest_report("SPY"), Some(expected));
+        let cached = cache.load("SPY").unwrap();
+        assert_eq!(cached.len(), weekday_bars(start, end).len());
+        assert!(cached.windows(2).all(|w| w[0].date < w[1].date));
+        assert_eq!(cached.last().unwrap().date, end);
+        assert_eq!(cached[10].close, 100.75);
+
+        // A clean re-download clears the cached report
+        let summary = download_symbols(&clean, &cache, &["SPY"], start, end, true, true, &Silent);
+        assert_eq!(summary.corrected().count(), 0);
+        assert!(summary.ingest_reports["SPY"].is_clean());
+        assert_eq!(cache.ingest_report("SPY"), None);
+
+        let _ = std::fs::remove_dir_all(cache.cache_dir());
+    }
+
+    #[test]
+    fn rerun_resumes_completed_symbols() {
+        let cache = temp_cache();
+        let mut provider = ScriptedProvider::new().fail(
+            "BAD",
+            (0..3)
+                .map(|_| DataError::Other("HTTP 502".into()))
+                .collect(),
+        );
+        // Partial history is never FullyCovered, so only the manifest can skip it
+        provider.first_bar.insert("IPO".to_string(), d(2024, 3, 1));
+        let symbols = ["IPO", "BAD"];
+        let (start, end) = (d(2024, 1, 1), d(2024, 3, 29));
+
+        let first = download_symbols(
+            &provider, &cache, &symbols, start, end, false, true, &Silent,
+        );
+        assert_eq!(first.failed_symbols(), vec!["BAD"]);
+
+        let second = download_symbols(
+            &provider, &cache, &symbols, start, end, false, true, &Silent,
+        );
+        assert_eq!(second.outcomes[0].outcome, DownloadOutcome::Resumed);
+        assert!(!second.has_failures());
+        assert_eq!(provider.calls("IPO"), 1);
+
+        let forced = download_symbols(&provider, &cache, &symbols, start, end, true, true, &Silent);
+        assert_eq!(provider.calls("IPO"), 2);
+        assert!(matches!(
+            forced.outcomes[0].outcome,
+            DownloadOutcome::PartialData { .. }
+        ));
+
+        let _ = std::fs::remove_dir_all(cache.cache_dir());
+    }
+
+    #[test]
+    fn partial_cache_appends_only_the_missing_tail() {
+        let cache = temp_cache();
+        cache
+            .write("SPY", &weekday_bars(d(2024, 1, 1), d(2024, 2, 29)))
+            .unwrap();
+        let provider = ScriptedProvider::new();
+        let (start, end) = (d(2024, 1, 1), d(2024, 3, 29));
+
+        let summary = download_symbols(
+            &provider,
+            &cache,
+            &["SPY"],
+            start,
+            end,
+            false,
+            true,
+            &Silent,
+        );
+        assert!(summary.all_succeeded());
+
+        let tail = weekday_bars(d(2024, 3, 1), end).len();
+        let parts = cache.parts("SPY").unwrap().parts;
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[1].bar_count, tail);
+        assert_eq!(parts[1].start_date, d(2024, 3, 1));
+        assert_eq!(
+            cache.load("SPY").unwrap().len(),
+            weekday_bars(start, end).len()
+        );
+
+        let _ = std::fs::remove_dir_all(cache.cache_dir());
+    }
+
+    #[test]
+    fn retry_delay_honors_rate_limit_hint_and_cap() {
+        let policy = RetryPolicy {
+            max_attempts: 4,
+            base_delay: std::time::Duration::from_millis(500),
+            max_delay: std::time::Duration::from_secs(10),
+        };
+        let network = DataError::NetworkUnreachable("x".into());
+        assert_eq!(policy.delay_for(1, &network).as_millis(), 500);
+        assert_eq!(policy.delay_for(3, &network).as_millis(), 2000);
+
+        let limited = DataError::RateLimited {
+            retry_after_secs: 60,
+        };
+        assert_eq!(policy.delay_for(1, &limited).as_secs(), 10);
     }
 }
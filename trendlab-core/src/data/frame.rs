@@ -0,0 +1,227 @@
+//! Columnar bar storage.
+//!
+//! A `BarFrame` holds one symbol's series as contiguous OHLCV columns, with
+//! the symbol name stored once and the date axis shared between all frames
+//! built from the same `AlignedData`. The engine runs on frames: position
+//! managers get a per-bar view from [`BarFrame::bar`], and signals and filters
+//! read history through the [`BarSeries`] impl, so no `Vec<Bar>` of the whole
+//! run is ever held.
+//!
+//! Cached series are compressed Parquet parts merged and aligned before a
+//! run, so frames are filled from `AlignedData` rather than mapped from disk.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+
+use super::align::AlignedData;
+use super::provider::RawBar;
+use crate::domain::{Bar, BarSeries};
+
+/// One symbol's bars as contiguous columns.
+#[derive(Debug, Clone)]
+pub struct BarFrame {
+    symbol: Arc<str>,
+    dates: Arc<[NaiveDate]>,
+    open: Vec<f64>,
+    high: Vec<f64>,
+    low: Vec<f64>,
+    close: Vec<f64>,
+    volume: Vec<u64>,
+    adj_close: Vec<f64>,
+}
+
+impl BarFrame {
+    /// Build a frame from row-oriented bars, taking dates from the bars.
+    pub fn from_raw(symbol: &str, bars: &[RawBar]) -> Self {
+        let dates: Arc<[NaiveDate]> = bars.iter().map(|b| b.date).collect();
+        Self::with_dates(Arc::from(symbol), dates, bars)
+    }
+
+    /// Build one frame per symbol of `aligned`.
+    ///
+    /// Frames whose bar dates match the aligned timeline (always the case for
+    /// `align_symbols` output) share a single date column.
+    pub fn from_aligned(aligned: &AlignedData) -> HashMap<String, BarFrame> {
+        let shared: Arc<[NaiveDate]> = Arc::from(aligned.dates.as_slice());
+        aligned
+            .bars
+            .iter()
+            .map(|(symbol, bars)| {
+                let on_timeline = bars.iter().map(|b| b.date).eq(shared.iter().copied());
+                let frame = if on_timeline {
+                    Self::with_dates(Arc::from(symbol.as_str()), Arc::clone(&shared), bars)
+                } else {
+                    Self::from_raw(symbol, bars)
+                };
+                (symbol.clone(), frame)
+            })
+            .collect()
+    }
+
+    fn with_dates(symbol: Arc<str>, dates: Arc<[NaiveDate]>, bars: &[RawBar]) -> Self {
+        Self {
+            symbol,
+            dates,
+            open: bars.iter().map(|b| b.open).collect(),
+            high: bars.iter().map(|b| b.high).collect(),
+            low: bars.iter().map(|b| b.low).collect(),
+            close: bars.iter().map(|b| b.close).collect(),
+            volume: bars.iter().map(|b| b.volume).collect(),
+            adj_close: bars.iter().map(|b| b.adj_close).collect(),
+        }
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    pub fn len(&self) -> usize {
+        self.close.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.close.is_empty()
+    }
+
+    pub fn dates(&self) -> &[NaiveDate] {
+        &self.dates
+    }
+
+    pub fn open(&self) -> &[f64] {
+        &self.open
+    }
+
+    pub fn high(&self) -> &[f64] {
+        &self.high
+    }
+
+    pub fn low(&self) -> &[f64] {
+        &self.low
+    }
+
+    pub fn close(&self) -> &[f64] {
+        &self.close
+    }
+
+    pub fn volume(&self) -> &[u64] {
+        &self.volume
+    }
+
+    pub fn adj_close(&self) -> &[f64] {
+        &self.adj_close
+    }
+
+    /// Bar `index` as a domain `Bar`. Does not allocate.
+    ///
+    /// # Panics
+    /// If `index >= self.len()`.
+    pub fn bar(&self, index: usize) -> Bar {
+        Bar {
+            symbol: Arc::clone(&self.symbol),
+            date: self.dates[index],
+            open: self.open[index],
+            high: self.high[index],
+            low: self.low[index],
+            close: self.close[index],
+            volume: self.volume[index],
+            adj_close: self.adj_close[index],
+        }
+    }
+
+    /// Whether bar `index` is a void (all-NaN) bar, without building it.
+    pub fn is_void(&self, index: usize) -> bool {
+        self.open[index].is_nan()
+            || self.high[index].is_nan()
+            || self.low[index].is_nan()
+            || self.close[index].is_nan()
+            || self.adj_close[index].is_nan()
+    }
+
+    /// Materialize the whole series for components that take `&[Bar]`
+    /// (indicator precompute).
+    pub fn to_bars(&self) -> Vec<Bar> {
+        (0..self.len()).map(|i| self.bar(i)).collect()
+    }
+}
+
+impl BarSeries for BarFrame {
+    fn len(&self) -> usize {
+        BarFrame::len(self)
+    }
+
+    fn bar(&self, index: usize) -> Bar {
+        BarFrame::bar(self, index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::align::align_symbols;
+
+    fn raw(day: u32, close: f64) -> RawBar {
+        RawBar {
+            date: NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+            open: close - 1.0,
+            high: close + 1.0,
+            low: close - 2.0,
+            close,
+            volume: 1_000 + u64::from(day),
+            adj_close: close,
+        }
+    }
+
+    #[test]
+    fn views_match_row_bars() {
+        let bars = vec![raw(2, 100.0), raw(3, 101.5), raw(4, 99.0)];
+        let frame = BarFrame::from_raw("SPY", &bars);
+        assert_eq!(frame.len(), 3);
+        assert_eq!(frame.symbol(), "SPY");
+        assert_eq!(frame.close(), &[100.0, 101.5, 99.0]);
+        for (i, raw) in bars.iter().enumerate() {
+            let bar = frame.bar(i);
+            assert_eq!(&*bar.symbol, "SPY");
+            assert_eq!(bar.date, raw.date);
+            assert_eq!(bar.open, raw.open);
+            assert_eq!(bar.high, raw.high);
+            assert_eq!(bar.low, raw.low);
+            assert_eq!(bar.close, raw.close);
+            assert_eq!(bar.volume, raw.volume);
+            assert_eq!(bar.adj_close, raw.adj_close);
+        }
+    }
+
+    #[test]
+    fn views_share_the_symbol() {
+        let frame = BarFrame::from_raw("SPY", &[raw(2, 100.0), raw(3, 101.0)]);
+        let bars = frame.to_bars();
+        assert!(Arc::ptr_eq(&bars[0].symbol, &bars[1].symbol));
+    }
+
+    #[test]
+    fn series_view_bounds_checks() {
+        let frame = BarFrame::from_raw("SPY", &[raw(2, 100.0), raw(3, 101.0)]);
+        let series: &dyn BarSeries = &frame;
+        assert_eq!(series.len(), 2);
+        assert_eq!(series.get(1).map(|b| b.close), Some(101.0));
+        assert!(series.get(2).is_none());
+    }
+
+    #[test]
+    fn aligned_frames_share_dates_and_keep_void_bars() {
+        let mut input = HashMap::new();
+        input.insert("SPY".to_string(), vec![raw(2, 100.0), raw(3, 101.0)]);
+        input.insert("QQQ".to_string(), vec![raw(3, 200.0)]);
+        let aligned = align_symbols(input);
+
+        let frames = BarFrame::from_aligned(&aligned);
+        let (spy, qqq) = (&frames["SPY"], &frames["QQQ"]);
+        assert!(Arc::ptr_eq(&spy.dates, &qqq.dates));
+        assert_eq!(qqq.len(), 2);
+        assert!(qqq.is_void(0));
+        assert!(qqq.bar(0).is_void());
+        assert!(!qqq.is_void(1));
+    }
+}
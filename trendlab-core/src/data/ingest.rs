@@ -2,42 +2,210 @@
 //!
 //! Raw data from any source (Yahoo, CSV, Parquet import) passes through this
 //! pipeline before being cached:
-//! 1. Schema validation (OHLCV sanity)
-//! 2. Sort by date ascending
-//! 3. Deduplicate (keep last row per date)
-//! 4. Corporate action adjustment (split-adjust all OHLC columns)
-//! 5. Anomaly detection
+//! 1. Sort by date ascending
+//! 2. Deduplicate (keep last row per date)
+//! 3. Quarantine bars outside the requested range
+//! 4. Drop the partial bar of a session still trading at fetch time
+//! 5. Schema validation (OHLCV sanity)
+//! 6. Corporate action adjustment (split-adjust all OHLC columns)
+//!
+//! Steps 1–4 correct what providers get wrong: Yahoo occasionally repeats a
+//! date, and serves a bar for "today" that changes on the next fetch. Every
+//! correction is counted in the [`IngestReport`], which the cache keeps
+//! beside the partition so later loads can warn about it.
+
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike, Weekday};
+use serde::{Deserialize, Serialize};
 
 use super::provider::{DataError, RawBar};
 
+/// Hour (UTC) by which the US equity session has closed: 4pm New York is
+/// 20:00 UTC in summer and 21:00 in winter.
+const SESSION_CLOSE_UTC_HOUR: u32 = 21;
+
+/// What the ingest pipeline may correct, beyond sorting and deduplicating.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IngestOptions {
+    /// Requested `(start, end)`. Bars outside it are quarantined.
+    pub range: Option<(NaiveDate, NaiveDate)>,
+    /// Drop the last bar when it is dated on `open_session`.
+    pub drop_partial_last_bar: bool,
+    /// Session still trading when the bars were fetched, whose bar is
+    /// incomplete. See [`open_session_at`].
+    pub open_session: Option<NaiveDate>,
+}
+
+impl Default for IngestOptions {
+    fn default() -> Self {
+        Self {
+            range: None,
+            drop_partial_last_bar: true,
+            open_session: None,
+        }
+    }
+}
+
+impl IngestOptions {
+    /// Options for daily bars over `[start, end]` fetched just now.
+    pub fn daily(start: NaiveDate, end: NaiveDate) -> Self {
+        Self {
+            range: Some((start, end)),
+            open_session: open_session_at(chrono::Utc::now().naive_utc()),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_drop_partial_last_bar(mut self, drop: bool) -> Self {
+        self.drop_partial_last_bar = drop;
+        self
+    }
+}
+
+/// The session that may still be trading at `now` (UTC): today on a weekday
+/// before the close, else none.
+///
+/// Before the open there is no bar for today yet, so nothing is dropped.
+pub fn open_session_at(now: NaiveDateTime) -> Option<NaiveDate> {
+    let weekend = matches!(now.weekday(), Weekday::Sat | Weekday::Sun);
+    (!weekend && now.hour() < SESSION_CLOSE_UTC_HOUR).then(|| now.date())
+}
+
+/// Corrections the ingest pipeline made to a provider's rows.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IngestReport {
+    /// Rows removed because a later row had the same date.
+    pub duplicates_removed: usize,
+    /// Each date that arrived more than once, ascending.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub duplicate_dates: Vec<NaiveDate>,
+    /// Rows dated before the row that preceded them.
+    #[serde(default)]
+    pub out_of_order: usize,
+    /// Dates of quarantined bars, outside the requested range.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub out_of_range: Vec<NaiveDate>,
+    /// Date of the incomplete last bar that was dropped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partial_bar: Option<NaiveDate>,
+}
+
+impl IngestReport {
+    /// Number of corrections: removed or quarantined rows, misordered rows,
+    /// and a dropped partial bar.
+    pub fn corrections(&self) -> usize {
+        self.duplicates_removed
+            + self.out_of_order
+            + self.out_of_range.len()
+            + usize::from(self.partial_bar.is_some())
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.corrections() == 0
+    }
+}
+
+impl fmt::Display for IngestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if self.duplicates_removed > 0 {
+            parts.push(format!(
+                "{} duplicate rows removed",
+                self.duplicates_removed
+            ));
+        }
+        if self.out_of_order > 0 {
+            parts.push(format!("{} rows out of order", self.out_of_order));
+        }
+        if !self.out_of_range.is_empty() {
+            parts.push(format!(
+                "{} bars outside the requested range quarantined",
+                self.out_of_range.len()
+            ));
+        }
+        if let Some(date) = self.partial_bar {
+            parts.push(format!("partial bar {date} dropped"));
+        }
+        if parts.is_empty() {
+            f.write_str("no corrections")
+        } else {
+            f.write_str(&parts.join(", "))
+        }
+    }
+}
+
 /// Result of the ingest pipeline.
 #[derive(Debug)]
 pub struct IngestResult {
     /// Validated, sorted, deduplicated, adjusted bars.
     pub bars: Vec<RawBar>,
-    /// Number of bars removed as duplicates.
-    pub duplicates_removed: usize,
+    /// Bars outside the requested range, set aside unadjusted.
+    pub quarantined: Vec<RawBar>,
+    /// Corrections made on the way to `bars`.
+    pub report: IngestReport,
     /// Number of bars with OHLCV anomalies (but not removed).
     pub anomalies_detected: usize,
     /// Per-bar adjustment ratios (adj_close / close).
     pub adjustment_ratios: Vec<f64>,
 }
 
+/// Run the full ingest pipeline on raw bars, without a requested range or
+/// an open session.
+pub fn ingest(bars: Vec<RawBar>) -> Result<IngestResult, DataError> {
+    ingest_with(bars, &IngestOptions::default())
+}
+
 /// Run the full ingest pipeline on raw bars.
-pub fn ingest(mut bars: Vec<RawBar>) -> Result<IngestResult, DataError> {
+///
+/// Corrections may leave no bars (e.g. a fetch holding only today's partial
+/// bar); callers treat that as nothing fetched.
+pub fn ingest_with(bars: Vec<RawBar>, opts: &IngestOptions) -> Result<IngestResult, DataError> {
     if bars.is_empty() {
         return Err(DataError::ValidationError("no bars to ingest".into()));
     }
-
-    // Step 1: Sort by date ascending
-    bars.sort_by_key(|b| b.date);
+    // Step 1: Sort by date ascending (stable, so duplicates keep arrival order)
+    let mut report = IngestReport {
+        out_of_order: bars.windows(2).filter(|w| w[1].date < w[0].date).count(),
+        ..Default::default()
+    };
+    let mut sorted = bars;
+    sorted.sort_by_key(|b| b.date);
 
     // Step 2: Deduplicate (keep last row per date)
-    let len_before = bars.len();
-    bars.dedup_by_key(|b| b.date);
-    let duplicates_removed = len_before - bars.len();
+    let mut bars: Vec<RawBar> = Vec::with_capacity(sorted.len());
+    for bar in sorted {
+        match bars.last_mut() {
+            Some(last) if last.date == bar.date => {
+                if report.duplicate_dates.last() != Some(&bar.date) {
+                    report.duplicate_dates.push(bar.date);
+                }
+                report.duplicates_removed += 1;
+                *last = bar;
+            }
+            _ => bars.push(bar),
+        }
+    }
+
+    // Step 3: Quarantine bars outside the requested range
+    let mut quarantined = Vec::new();
+    if let Some((start, end)) = opts.range {
+        let (kept, outside): (Vec<RawBar>, Vec<RawBar>) = bars
+            .into_iter()
+            .partition(|b| start <= b.date && b.date <= end);
+        report.out_of_range = outside.iter().map(|b| b.date).collect();
+        bars = kept;
+        quarantined = outside;
+    }
+
+    // Step 4: Drop the open session's partial bar
+    let last_date = bars.last().map(|b| b.date);
+    if opts.drop_partial_last_bar && opts.open_session.is_some() && last_date == opts.open_session {
+        bars.pop();
+        report.partial_bar = last_date;
+    }
 
-    // Step 3: Validate OHLCV sanity
+    // Step 5: Validate OHLCV sanity
     let mut anomalies_detected = 0;
     for bar in &bars {
         if !validate_bar(bar) {
@@ -45,12 +213,13 @@ pub fn ingest(mut bars: Vec<RawBar>) -> Result<IngestResult, DataError> {
         }
     }
 
-    // Step 4: Corporate action adjustment
+    // Step 6: Corporate action adjustment
     let adjustment_ratios = adjust_corporate_actions(&mut bars);
 
     Ok(IngestResult {
         bars,
-        duplicates_removed,
+        quarantined,
+        report,
         anomalies_detected,
         adjustment_ratios,
     })
@@ -146,7 +315,72 @@ mod tests {
         ];
         let result = ingest(bars).unwrap();
         assert_eq!(result.bars.len(), 2);
-        assert_eq!(result.duplicates_removed, 1);
+        assert_eq!(result.report.duplicates_removed, 1);
+        // The later row wins
+        assert_eq!(result.bars[0].close, 101.5);
+        assert_eq!(result.bars[0].volume, 1100);
+    }
+
+    fn d(date: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn ingest_corrects_and_reports_provider_rows() {
+        let bar = |date, close: f64| {
+            make_bar(date, (close, close + 1.0, close - 1.0, close), 1000, close)
+        };
+        let bars = vec![
+            bar("2023-12-29", 99.0), // before the range
+            bar("2024-01-03", 102.0),
+            bar("2024-01-02", 101.0), // out of order
+            bar("2024-01-03", 103.0), // duplicate, later row kept
+            bar("2024-01-04", 104.0),
+            bar("2024-01-05", 105.5), // today's partial bar
+        ];
+        let opts = IngestOptions {
+            range: Some((d("2024-01-02"), d("2024-01-05"))),
+            drop_partial_last_bar: true,
+            open_session: Some(d("2024-01-05")),
+        };
+        let result = ingest_with(bars.clone(), &opts).unwrap();
+
+        let closes: Vec<(NaiveDate, f64)> = result.bars.iter().map(|b| (b.date, b.close)).collect();
+        assert_eq!(
+            closes,
+            [
+                (d("2024-01-02"), 101.0),
+                (d("2024-01-03"), 103.0),
+                (d("2024-01-04"), 104.0)
+            ]
+        );
+        assert_eq!(result.quarantined.len(), 1);
+        assert_eq!(
+            result.report,
+            IngestReport {
+                duplicates_removed: 1,
+                duplicate_dates: vec![d("2024-01-03")],
+                out_of_order: 1,
+                out_of_range: vec![d("2023-12-29")],
+                partial_bar: Some(d("2024-01-05")),
+            }
+        );
+        assert_eq!(result.report.corrections(), 4);
+
+        // Configured off, the partial bar stays
+        let kept = ingest_with(bars, &opts.clone().with_drop_partial_last_bar(false)).unwrap();
+        assert_eq!(kept.bars.last().unwrap().date, d("2024-01-05"));
+        assert_eq!(kept.report.partial_bar, None);
+    }
+
+    #[test]
+    fn open_session_only_before_a_weekday_close() {
+        let at = |date: &str, hour| d(date).and_hms_opt(hour, 0, 0).unwrap();
+        // Friday
+        assert_eq!(open_session_at(at("2024-01-05", 15)), Some(d("2024-01-05")));
+        assert_eq!(open_session_at(at("2024-01-05", 22)), None);
+        // Saturday
+        assert_eq!(open_session_at(at("2024-01-06", 15)), None);
     }
 
     #[test]
@@ -0,0 +1,177 @@
+//! Bar intervals.
+//!
+//! Bars are daily unless a [`BarInterval`] says otherwise. Weekly bars keep the
+//! date-only timestamp of daily bars: each is dated by the first session of
+//! its ISO week. Signals and indicators count bars, not days, so they run
+//! unchanged on any interval; only annualization needs to know the interval.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use super::provider::RawBar;
+
+/// Time span covered by one bar.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BarInterval {
+    #[default]
+    Daily,
+    Weekly,
+}
+
+impl BarInterval {
+    /// Bars per year, for annualizing returns and ratios.
+    pub fn periods_per_year(&self) -> f64 {
+        match self {
+            Self::Daily => 252.0,
+            Self::Weekly => 52.0,
+        }
+    }
+
+    /// Interval code used by Yahoo's chart API and the cache layout.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Daily => "1d",
+            Self::Weekly => "1wk",
+        }
+    }
+
+    /// Guess the interval of a date axis from its median spacing.
+    ///
+    /// Daily series have a median gap of one day (weekends and holidays are
+    /// the exception), weekly series of seven. Fewer than two dates are daily.
+    pub fn infer(dates: &[NaiveDate]) -> Self {
+        let mut gaps: Vec<i64> = dates.windows(2).map(|w| (w[1] - w[0]).num_days()).collect();
+        if gaps.is_empty() {
+            return Self::Daily;
+        }
+        let mid = gaps.len() / 2;
+        let (_, median, _) = gaps.select_nth_unstable(mid);
+        if *median >= 5 {
+            Self::Weekly
+        } else {
+            Self::Daily
+        }
+    }
+
+    /// Aggregate daily bars into bars of this interval.
+    ///
+    /// Weekly bars open at the week's first open, close at its last close,
+    /// and take the high/low extremes and summed volume. Input must be sorted
+    /// by date. Daily input is returned as is.
+    pub fn resample(&self, daily: &[RawBar]) -> Vec<RawBar> {
+        match self {
+            Self::Daily => daily.to_vec(),
+            Self::Weekly => {
+                let mut out: Vec<RawBar> = Vec::new();
+                let mut week = None;
+                for bar in daily {
+                    let key = (bar.date.iso_week().year(), bar.date.iso_week().week());
+                    match out.last_mut() {
+                        Some(agg) if week == Some(key) => {
+                            agg.high = agg.high.max(bar.high);
+                            agg.low = agg.low.min(bar.low);
+                            agg.close = bar.close;
+                            agg.adj_close = bar.adj_close;
+                            agg.volume += bar.volume;
+                        }
+                        _ => {
+                            out.push(bar.clone());
+                            week = Some(key);
+                        }
+                    }
+                }
+                out
+            }
+        }
+    }
+}
+
+impl fmt::Display for BarInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Daily => "daily",
+            Self::Weekly => "weekly",
+        })
+    }
+}
+
+impl FromStr for BarInterval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "daily" | "1d" => Ok(Self::Daily),
+            "weekly" | "1wk" => Ok(Self::Weekly),
+            other => Err(format!("unknown bar interval '{other}' (daily, weekly)")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(date: NaiveDate, open: f64, high: f64, low: f64, close: f64) -> RawBar {
+        RawBar {
+            date,
+            open,
+            high,
+            low,
+            close,
+            volume: 100,
+            adj_close: close,
+        }
+    }
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, d).unwrap()
+    }
+
+    #[test]
+    fn weekly_resample_aggregates_each_iso_week() {
+        // Tue 2 .. Fri 5, then Mon 8 .. Tue 9
+        let daily = vec![
+            bar(day(2), 10.0, 11.0, 9.0, 10.5),
+            bar(day(3), 10.5, 13.0, 10.0, 12.0),
+            bar(day(5), 12.0, 12.5, 8.5, 9.0),
+            bar(day(8), 9.0, 10.0, 8.0, 9.5),
+            bar(day(9), 9.5, 11.0, 9.0, 10.0),
+        ];
+        let weekly = BarInterval::Weekly.resample(&daily);
+        assert_eq!(weekly.len(), 2);
+
+        let w1 = &weekly[0];
+        assert_eq!(w1.date, day(2));
+        assert_eq!((w1.open, w1.high, w1.low, w1.close), (10.0, 13.0, 8.5, 9.0));
+        assert_eq!(w1.adj_close, 9.0);
+        assert_eq!(w1.volume, 300);
+
+        let w2 = &weekly[1];
+        assert_eq!(w2.date, day(8));
+        assert_eq!((w2.open, w2.high, w2.low, w2.close), (9.0, 11.0, 8.0, 10.0));
+    }
+
+    #[test]
+    fn infer_from_median_spacing() {
+        let daily: Vec<NaiveDate> = [2, 3, 4, 5, 8, 9, 10].map(day).to_vec();
+        assert_eq!(BarInterval::infer(&daily), BarInterval::Daily);
+        let weekly: Vec<NaiveDate> = [1, 8, 16, 22, 29].map(day).to_vec();
+        assert_eq!(BarInterval::infer(&weekly), BarInterval::Weekly);
+        assert_eq!(BarInterval::infer(&[day(2)]), BarInterval::Daily);
+    }
+
+    #[test]
+    fn parse_and_serde_names() {
+        assert_eq!("weekly".parse::<BarInterval>(), Ok(BarInterval::Weekly));
+        assert_eq!("1d".parse::<BarInterval>(), Ok(BarInterval::Daily));
+        assert!("hourly".parse::<BarInterval>().is_err());
+        assert_eq!(
+            serde_json::to_string(&BarInterval::Weekly).unwrap(),
+            r#""weekly""#
+        );
+    }
+}
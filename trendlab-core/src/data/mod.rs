@@ -5,23 +5,35 @@
 //! - Ingest pipeline (validation, corporate action adjustment)
 //! - Parquet cache with Hive-style partitioning
 //! - Multi-symbol time alignment
+//! - Columnar per-symbol bar storage for the engine
+//! - Bar intervals (daily, weekly) and resampling of daily bars
 //! - Universe configuration (sector/ticker hierarchy)
-//! - Download orchestration with progress reporting
+//! - Download orchestration with progress reporting, retries and resume
 
 pub mod align;
 pub mod cache;
 pub mod circuit_breaker;
 pub mod download;
+pub mod frame;
 pub mod ingest;
+pub mod interval;
 pub mod provider;
 pub mod universe;
 pub mod yahoo;
 
-pub use cache::{CacheStatus, CoverageResult, ParquetCache};
+pub use cache::{
+    CacheEntry, CacheStatus, CompactReport, CoverageResult, EntryMeta, ParquetCache, PartInfo,
+    PartManifest, PartitionVintage,
+};
 pub use circuit_breaker::CircuitBreaker;
-pub use download::{download_symbols, DownloadSummary};
+pub use download::{
+    download_symbols, DownloadManifest, DownloadOutcome, DownloadSummary, SymbolOutcome,
+};
+pub use frame::BarFrame;
+pub use interval::BarInterval;
 pub use provider::{
-    DataError, DataProvider, DataSource, DownloadProgress, FetchResult, RawBar, StdoutProgress,
+    fetch_with_retry, DataError, DataProvider, DataSource, DownloadProgress, FetchResult, RawBar,
+    RetryPolicy, StdoutProgress,
 };
 pub use universe::Universe;
 pub use yahoo::YahooProvider;
@@ -5,6 +5,7 @@
 
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use thiserror::Error;
 
 /// Raw daily OHLCV bar from a data provider (before validation/adjustment).
@@ -58,6 +59,65 @@ pub enum DataError {
     Other(String),
 }
 
+impl DataError {
+    /// Whether retrying the same request later might succeed.
+    ///
+    /// Rate limits, network failures and unclassified HTTP errors are transient.
+    /// A tripped circuit breaker is not: it already encodes "stop asking".
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Self::RateLimited { .. } | Self::NetworkUnreachable(_) | Self::Other(_)
+        )
+    }
+}
+
+/// Per-symbol retry schedule for transient provider errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts per symbol, including the first (minimum 1).
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles on each subsequent retry.
+    pub base_delay: Duration,
+    /// Upper bound on any single delay, including provider Retry-After hints.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Single attempt, no retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+        }
+    }
+
+    /// Delay before retry number `retry` (1-based), honoring a rate-limit hint.
+    pub fn delay_for(&self, retry: u32, error: &DataError) -> Duration {
+        let backoff = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)));
+        let hinted = match error {
+            DataError::RateLimited { retry_after_secs } => {
+                backoff.max(Duration::from_secs(*retry_after_secs))
+            }
+            _ => backoff,
+        };
+        hinted.min(self.max_delay)
+    }
+}
+
 /// Result of a successful data fetch for a single symbol.
 #[derive(Debug, Clone)]
 pub struct FetchResult {
@@ -94,6 +154,58 @@ pub trait DataProvider: Send + Sync {
 
     /// Check if the provider is currently available (not rate-limited, not blocked).
     fn is_available(&self) -> bool;
+
+    /// Retry schedule used by [`fetch_with_retry`] for this provider.
+    fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy::default()
+    }
+
+    /// Called by [`fetch_with_retry`] when a symbol still fails with a
+    /// transient error after its last attempt. Providers with a circuit
+    /// breaker count these rather than attempts that are about to be
+    /// retried, so one rate-limited symbol can't trip it on its own.
+    fn record_exhausted(&self, _error: &DataError) {}
+}
+
+/// Fetch a symbol, retrying transient errors per the provider's `RetryPolicy`.
+///
+/// Stops early when the provider becomes unavailable (circuit breaker open).
+/// A transient error left after the last attempt is reported to the
+/// provider via [`DataProvider::record_exhausted`]. Returns the final result
+/// and the number of attempts made.
+pub fn fetch_with_retry(
+    provider: &dyn DataProvider,
+    symbol: &str,
+    start: NaiveDate,
+    end: NaiveDate,
+    progress: Option<&dyn DownloadProgress>,
+) -> (Result<FetchResult, DataError>, u32) {
+    let policy = provider.retry_policy();
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 1;
+
+    loop {
+        if !provider.is_available() {
+            return (Err(DataError::CircuitBreakerTripped), attempt - 1);
+        }
+
+        let result = provider.fetch(symbol, start, end);
+        match &result {
+            Err(e) if e.is_transient() && attempt < max_attempts => {
+                let delay = policy.delay_for(attempt, e);
+                if let Some(p) = progress {
+                    p.on_retry(symbol, attempt + 1, delay, e);
+                }
+                std::thread::sleep(delay);
+                attempt += 1;
+            }
+            Err(e) if e.is_transient() => {
+                provider.record_exhausted(e);
+                return (result, attempt);
+            }
+            _ => return (result, attempt),
+        }
+    }
 }
 
 /// Progress callback for multi-symbol operations.
@@ -106,6 +218,9 @@ pub trait DownloadProgress: Send {
 
     /// Called when the entire batch is done.
     fn on_batch_complete(&self, succeeded: usize, failed: usize, total: usize);
+
+    /// Called before retrying a symbol after a transient error.
+    fn on_retry(&self, _symbol: &str, _attempt: u32, _delay: Duration, _error: &DataError) {}
 }
 
 /// Simple progress reporter that prints to stdout.
@@ -132,4 +247,11 @@ impl DownloadProgress for StdoutProgress {
     fn on_batch_complete(&self, succeeded: usize, failed: usize, total: usize) {
         println!("\nDownload complete: {succeeded}/{total} succeeded, {failed} failed");
     }
+
+    fn on_retry(&self, symbol: &str, attempt: u32, delay: Duration, error: &DataError) {
+        println!(
+            "  RETRY: {symbol} (attempt {attempt}) in {:.1}s after: {error}",
+            delay.as_secs_f64()
+        );
+    }
 }
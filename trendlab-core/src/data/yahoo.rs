@@ -1,13 +1,16 @@
 //! Yahoo Finance data provider.
 //!
-//! Fetches daily OHLCV bars from Yahoo's v8 chart API. Handles rate limiting,
-//! retries with exponential backoff, response parsing, and the circuit breaker.
+//! Fetches OHLCV bars (daily unless configured with
+//! [`YahooProvider::with_interval`]) from Yahoo's v8 chart API. Handles rate limiting,
+//! response parsing, and the circuit breaker. Retries with exponential backoff
+//! are configured here (`RetryPolicy`) and driven by `fetch_with_retry`.
 //!
 //! Yahoo Finance has no official API and is subject to unannounced format changes.
 //! The CSV import path is the primary fallback when Yahoo is unavailable.
 
 use super::circuit_breaker::CircuitBreaker;
-use super::provider::{DataError, DataProvider, DataSource, FetchResult, RawBar};
+use super::interval::BarInterval;
+use super::provider::{DataError, DataProvider, DataSource, FetchResult, RawBar, RetryPolicy};
 use chrono::NaiveDate;
 use serde::Deserialize;
 use std::sync::Arc;
@@ -61,8 +64,8 @@ struct AdjCloseData {
 pub struct YahooProvider {
     client: reqwest::blocking::Client,
     circuit_breaker: Arc<CircuitBreaker>,
-    max_retries: u32,
-    base_delay: Duration,
+    retry_policy: RetryPolicy,
+    interval: BarInterval,
 }
 
 impl YahooProvider {
@@ -76,18 +79,31 @@ impl YahooProvider {
         Self {
             client,
             circuit_breaker,
-            max_retries: 3,
-            base_delay: Duration::from_millis(500),
+            retry_policy: RetryPolicy::default(),
+            interval: BarInterval::Daily,
         }
     }
 
-    /// Build the chart API URL for a symbol and date range.
-    fn chart_url(symbol: &str, start: NaiveDate, end: NaiveDate) -> String {
+    /// Request bars of `interval` instead of daily bars.
+    pub fn with_interval(mut self, interval: BarInterval) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Override the per-symbol retry schedule (attempts and backoff).
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Build the chart API URL for a symbol, date range, and bar interval.
+    fn chart_url(symbol: &str, start: NaiveDate, end: NaiveDate, interval: BarInterval) -> String {
         let start_ts = start.and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp();
         let end_ts = end.and_hms_opt(23, 59, 59).unwrap().and_utc().timestamp();
+        let interval = interval.code();
         format!(
             "https://query2.finance.yahoo.com/v8/finance/chart/{symbol}\
-             ?period1={start_ts}&period2={end_ts}&interval=1d\
+             ?period1={start_ts}&period2={end_ts}&interval={interval}\
              &includeAdjustedClose=true"
         )
     }
@@ -179,8 +195,13 @@ impl YahooProvider {
         Ok(bars)
     }
 
-    /// Execute a single HTTP request with retry and circuit breaker logic.
-    fn fetch_with_retry(
+    /// Execute a single HTTP request with circuit breaker bookkeeping.
+    ///
+    /// Retries are driven by the caller (`fetch_with_retry`) using
+    /// `retry_policy()`, so a rate-limited symbol backs off as a unit. Only a
+    /// symbol that exhausts its retries counts toward the breaker (see
+    /// `record_exhausted`); a 403 trips it at once.
+    fn fetch_once(
         &self,
         symbol: &str,
         start: NaiveDate,
@@ -190,77 +211,50 @@ impl YahooProvider {
             return Err(DataError::CircuitBreakerTripped);
         }
 
-        let url = Self::chart_url(symbol, start, end);
-        let mut last_error = None;
-
-        for attempt in 0..=self.max_retries {
-            if attempt > 0 {
-                let delay = self.base_delay * 2u32.pow(attempt - 1);
-                std::thread::sleep(delay);
-            }
-
-            if !self.circuit_breaker.is_allowed() {
-                return Err(DataError::CircuitBreakerTripped);
-            }
-
-            match self.client.get(&url).send() {
-                Ok(resp) => {
-                    let status = resp.status();
-
-                    if status == reqwest::StatusCode::FORBIDDEN {
-                        // IP ban — immediately trip the circuit breaker
-                        self.circuit_breaker.trip();
-                        return Err(DataError::CircuitBreakerTripped);
-                    }
-
-                    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                        self.circuit_breaker.record_failure();
-                        let retry_after = resp
-                            .headers()
-                            .get("retry-after")
-                            .and_then(|v| v.to_str().ok())
-                            .and_then(|v| v.parse::<u64>().ok())
-                            .unwrap_or(60);
-                        last_error = Some(DataError::RateLimited {
-                            retry_after_secs: retry_after,
-                        });
-                        continue;
-                    }
-
-                    if status == reqwest::StatusCode::UNAUTHORIZED {
-                        return Err(DataError::AuthenticationRequired(
-                            "Yahoo Finance requires authentication".into(),
-                        ));
-                    }
+        let url = Self::chart_url(symbol, start, end, self.interval);
+        let resp = self
+            .client
+            .get(&url)
+            .send()
+            .map_err(|e| DataError::NetworkUnreachable(e.to_string()))?;
+        let status = resp.status();
+
+        if status == reqwest::StatusCode::FORBIDDEN {
+            // IP ban — immediately trip the circuit breaker
+            self.circuit_breaker.trip();
+            return Err(DataError::CircuitBreakerTripped);
+        }
 
-                    if !status.is_success() {
-                        self.circuit_breaker.record_failure();
-                        last_error = Some(DataError::Other(format!("HTTP {status} for {symbol}")));
-                        continue;
-                    }
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = resp
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(60);
+            return Err(DataError::RateLimited {
+                retry_after_secs: retry_after,
+            });
+        }
 
-                    // Parse the JSON response
-                    let chart: ChartResponse = resp.json().map_err(|e| {
-                        DataError::ResponseFormatChanged(format!(
-                            "failed to parse response for {symbol}: {e}"
-                        ))
-                    })?;
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(DataError::AuthenticationRequired(
+                "Yahoo Finance requires authentication".into(),
+            ));
+        }
 
-                    let bars = Self::parse_response(symbol, chart)?;
-                    self.circuit_breaker.record_success();
-                    return Ok(bars);
-                }
-                Err(e) => {
-                    if e.is_connect() || e.is_timeout() {
-                        last_error = Some(DataError::NetworkUnreachable(e.to_string()));
-                        continue;
-                    }
-                    return Err(DataError::NetworkUnreachable(e.to_string()));
-                }
-            }
+        if !status.is_success() {
+            return Err(DataError::Other(format!("HTTP {status} for {symbol}")));
         }
 
-        Err(last_error.unwrap_or_else(|| DataError::Other("max retries exceeded".into())))
+        // Parse the JSON response
+        let chart: ChartResponse = resp.json().map_err(|e| {
+            DataError::ResponseFormatChanged(format!("failed to parse response for {symbol}: {e}"))
+        })?;
+
+        let bars = Self::parse_response(symbol, chart)?;
+        self.circuit_breaker.record_success();
+        Ok(bars)
     }
 }
 
@@ -275,7 +269,7 @@ impl DataProvider for YahooProvider {
         start: NaiveDate,
         end: NaiveDate,
     ) -> Result<FetchResult, DataError> {
-        let bars = self.fetch_with_retry(symbol, start, end)?;
+        let bars = self.fetch_once(symbol, start, end)?;
         Ok(FetchResult {
             symbol: symbol.to_string(),
             bars,
@@ -286,4 +280,12 @@ impl DataProvider for YahooProvider {
     fn is_available(&self) -> bool {
         self.circuit_breaker.is_allowed()
     }
+
+    fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy
+    }
+
+    fn record_exhausted(&self, _error: &DataError) {
+        self.circuit_breaker.record_failure();
+    }
 }
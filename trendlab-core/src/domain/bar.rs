@@ -1,5 +1,7 @@
 //! Bar — the fundamental market data unit.
 
+use std::sync::Arc;
+
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
@@ -7,9 +9,12 @@ use serde::{Deserialize, Serialize};
 ///
 /// All OHLC columns are split-adjusted (Phase 4 applies adjustment ratios).
 /// The `adj_close` column is retained for reference but all calculations use adjusted OHLC.
+///
+/// The symbol is shared: every bar of a series points at the same `Arc<str>`,
+/// so building or cloning bars never allocates.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Bar {
-    pub symbol: String,
+    pub symbol: Arc<str>,
     pub date: NaiveDate,
     pub open: f64,
     pub high: f64,
@@ -44,6 +49,61 @@ impl Bar {
     }
 }
 
+/// A symbol's bar history, indexed by bar.
+///
+/// Signals and filters read bars through this view rather than a `&[Bar]`,
+/// so the engine can keep its series in columnar storage
+/// (`data::frame::BarFrame`) and hand out one bar at a time.
+pub trait BarSeries {
+    /// Number of bars in the series.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Bar `index`.
+    ///
+    /// # Panics
+    /// If `index >= self.len()`.
+    fn bar(&self, index: usize) -> Bar;
+
+    /// Bar `index`, or `None` past the end of the series.
+    fn get(&self, index: usize) -> Option<Bar> {
+        (index < self.len()).then(|| self.bar(index))
+    }
+}
+
+impl BarSeries for [Bar] {
+    fn len(&self) -> usize {
+        <[Bar]>::len(self)
+    }
+
+    fn bar(&self, index: usize) -> Bar {
+        self[index].clone()
+    }
+}
+
+impl BarSeries for Vec<Bar> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn bar(&self, index: usize) -> Bar {
+        self[index].clone()
+    }
+}
+
+impl<S: BarSeries + ?Sized> BarSeries for &S {
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    fn bar(&self, index: usize) -> Bar {
+        (**self).bar(index)
+    }
+}
+
 /// Whether the market is open or closed for a symbol on a given bar.
 ///
 /// A bar with all-NaN OHLCV fields (produced by multi-symbol alignment) has
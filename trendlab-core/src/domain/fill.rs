@@ -4,6 +4,7 @@ use super::ids::OrderId;
 use super::instrument::OrderSide;
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// Which phase of the bar loop produced this fill.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -16,6 +17,67 @@ pub enum FillPhase {
     EndOfBar,
 }
 
+/// Which engine decision placed the order behind a fill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionSource {
+    /// Entry order from a signal that passed the filter and governance.
+    SignalEntry,
+    /// Exit at the next open because an opposite signal fired.
+    SignalFlipExit,
+    /// The position manager's stop leg triggered.
+    PmStopExit,
+    /// The position manager asked for an exit at the next open.
+    PmForceExit,
+    /// The position manager's take-profit leg filled.
+    PmTargetExit,
+    /// The risk guard's drawdown kill switch flattened the position at the
+    /// next open (see `engine::risk_guard`).
+    RiskGuardFlatten,
+    /// No fill: positions still open when the run ends, marked at the last
+    /// close (see `engine::attribution` and `engine::trade_extraction`).
+    EndOfRunLiquidation,
+}
+
+impl DecisionSource {
+    pub const ALL: [DecisionSource; 7] = [
+        DecisionSource::SignalEntry,
+        DecisionSource::SignalFlipExit,
+        DecisionSource::PmStopExit,
+        DecisionSource::PmForceExit,
+        DecisionSource::PmTargetExit,
+        DecisionSource::RiskGuardFlatten,
+        DecisionSource::EndOfRunLiquidation,
+    ];
+
+    /// Short label for tables.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::SignalEntry => "entry",
+            Self::SignalFlipExit => "flip",
+            Self::PmStopExit => "stop",
+            Self::PmForceExit => "force",
+            Self::PmTargetExit => "target",
+            Self::RiskGuardFlatten => "risk",
+            Self::EndOfRunLiquidation => "open",
+        }
+    }
+}
+
+impl fmt::Display for DecisionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::SignalEntry => "signal_entry",
+            Self::SignalFlipExit => "signal_flip_exit",
+            Self::PmStopExit => "pm_stop_exit",
+            Self::PmForceExit => "pm_force_exit",
+            Self::PmTargetExit => "pm_target_exit",
+            Self::RiskGuardFlatten => "risk_guard_flatten",
+            Self::EndOfRunLiquidation => "end_of_run_liquidation",
+        })
+    }
+}
+
 /// Record of an order being filled (fully or partially).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Fill {
@@ -30,6 +92,14 @@ pub struct Fill {
     pub slippage: f64,
     /// Which bar-loop phase produced this fill.
     pub phase: FillPhase,
+    /// For stop/limit fills, how far the bar traded past the trigger as a
+    /// fraction of the trigger price (see `trigger::trigger_margin`).
+    #[serde(default)]
+    pub trigger_margin: Option<f64>,
+    /// Decision behind the order. Stamped by the bar loop; None for fills
+    /// made outside it.
+    #[serde(default)]
+    pub decision_source: Option<DecisionSource>,
 }
 
 impl Fill {
@@ -60,6 +130,8 @@ mod tests {
             commission: 5.0,
             slippage: 2.0,
             phase: FillPhase::StartOfBar,
+            trigger_margin: None,
+            decision_source: None,
         };
         // Buy: cost = 100*50 + 5 + 2 = 5007
         assert_eq!(fill.net_amount(), 5007.0);
@@ -78,8 +150,18 @@ mod tests {
             commission: 5.0,
             slippage: 2.0,
             phase: FillPhase::Intrabar,
+            trigger_margin: None,
+            decision_source: None,
         };
         // Sell: proceeds = 110*50 - 5 - 2 = 5493
         assert_eq!(fill.net_amount(), 5493.0);
     }
+
+    #[test]
+    fn decision_source_display_matches_serde() {
+        for source in DecisionSource::ALL {
+            let json = serde_json::to_string(&source).unwrap();
+            assert_eq!(json, format!("\"{source}\""));
+        }
+    }
 }
@@ -1,7 +1,101 @@
-//! Instrument metadata and tick rounding.
+//! Instrument metadata, tick rounding, and quantity quantization.
+//!
+//! Quantization is the rule for which order quantities an instrument can
+//! trade: any amount, whole shares, or whole contracts. Strategy configs
+//! carry it as flat params on the execution model (see
+//! [`Quantization::from_params`]) so it enters `full_hash` fingerprints;
+//! configs without it trade whole shares.
+//!
+//! Contract quantities are kept in underlying units (contracts × multiplier)
+//! from order creation on, so fills, positions, equity and trade PnL all
+//! carry the contract multiplier without a separate scaling step.
+
+use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
+/// Selects the quantization rule: 0 = whole shares, 1 = fractional, 2 = contracts.
+pub const PARAM_QUANTIZATION: &str = "quantization";
+/// Underlying units per contract when trading contracts.
+pub const PARAM_CONTRACT_MULTIPLIER: &str = "contract_multiplier";
+
+/// Quantities an instrument can trade.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "quantization", rename_all = "snake_case")]
+pub enum Quantization {
+    /// Any positive quantity (fractional shares).
+    Fractional,
+    /// Whole shares only.
+    #[default]
+    WholeShares,
+    /// Whole contracts of `multiplier` underlying units each (futures).
+    Contracts { multiplier: f64 },
+}
+
+impl Quantization {
+    /// Smallest tradable step in underlying units; `None` when fractional.
+    pub fn increment(&self) -> Option<f64> {
+        match self {
+            Self::Fractional => None,
+            Self::WholeShares => Some(1.0),
+            Self::Contracts { multiplier } => Some(*multiplier),
+        }
+    }
+
+    /// Underlying units per contract (1 for shares).
+    pub fn contract_multiplier(&self) -> f64 {
+        match self {
+            Self::Contracts { multiplier } => *multiplier,
+            _ => 1.0,
+        }
+    }
+
+    /// Round a quantity in underlying units down to a tradable one. Zero
+    /// when not even one increment fits.
+    pub fn round_down(&self, quantity: f64) -> f64 {
+        if !quantity.is_finite() || quantity <= 0.0 {
+            return 0.0;
+        }
+        match self.increment() {
+            // The epsilon keeps 99.99999999 lots (float noise) at 100
+            Some(step) if step > 0.0 => (quantity / step + 1e-9).floor() * step,
+            _ => quantity,
+        }
+    }
+
+    /// Decode from execution-model params. `None` if the params don't set
+    /// a quantization, which means whole shares.
+    pub fn from_params(params: &BTreeMap<String, f64>) -> Option<Self> {
+        let rule = match params.get(PARAM_QUANTIZATION)?.round() as u8 {
+            1 => Self::Fractional,
+            2 => Self::Contracts {
+                multiplier: params
+                    .get(PARAM_CONTRACT_MULTIPLIER)
+                    .copied()
+                    .unwrap_or(1.0),
+            },
+            _ => Self::WholeShares,
+        };
+        Some(rule)
+    }
+
+    /// Encode into execution-model params (inverse of [`Self::from_params`]).
+    pub fn write_params(&self, params: &mut BTreeMap<String, f64>) {
+        match self {
+            Self::WholeShares => {
+                params.insert(PARAM_QUANTIZATION.into(), 0.0);
+            }
+            Self::Fractional => {
+                params.insert(PARAM_QUANTIZATION.into(), 1.0);
+            }
+            Self::Contracts { multiplier } => {
+                params.insert(PARAM_QUANTIZATION.into(), 2.0);
+                params.insert(PARAM_CONTRACT_MULTIPLIER.into(), *multiplier);
+            }
+        }
+    }
+}
+
 /// Asset classification.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AssetClass {
@@ -19,6 +113,8 @@ pub struct Instrument {
     pub lot_size: f64,
     pub currency: String,
     pub asset_class: AssetClass,
+    #[serde(default)]
+    pub quantization: Quantization,
 }
 
 impl Instrument {
@@ -30,6 +126,7 @@ impl Instrument {
             lot_size: 1.0,
             currency: "USD".into(),
             asset_class: AssetClass::Equity,
+            quantization: Quantization::WholeShares,
         }
     }
 
@@ -41,8 +138,23 @@ impl Instrument {
             lot_size: 1.0,
             currency: "USD".into(),
             asset_class: AssetClass::Etf,
+            quantization: Quantization::WholeShares,
         }
     }
+
+    /// Replace the quantization rule. Contracts mark the instrument a future.
+    pub fn with_quantization(mut self, quantization: Quantization) -> Self {
+        if matches!(quantization, Quantization::Contracts { .. }) {
+            self.asset_class = AssetClass::Future;
+        }
+        self.quantization = quantization;
+        self
+    }
+
+    /// Round a desired quantity down to what the instrument can trade.
+    pub fn quantize(&self, quantity: f64) -> f64 {
+        self.quantization.round_down(quantity)
+    }
 }
 
 /// Side-aware tick rounding.
@@ -104,6 +216,31 @@ mod tests {
         assert_eq!(round_to_lot(153.7, 100.0), 100.0);
     }
 
+    #[test]
+    fn quantization_rounds_down_to_the_increment() {
+        assert_eq!(Quantization::WholeShares.round_down(123.7), 123.0);
+        assert_eq!(Quantization::Fractional.round_down(123.7), 123.7);
+        let es = Quantization::Contracts { multiplier: 50.0 };
+        assert_eq!(es.round_down(149.9), 100.0);
+        assert_eq!(es.round_down(49.9), 0.0);
+        assert_eq!(Quantization::WholeShares.round_down(0.4), 0.0);
+        assert_eq!(Quantization::WholeShares.round_down(2.9999999999999), 3.0);
+    }
+
+    #[test]
+    fn quantization_params_roundtrip() {
+        let mut params = BTreeMap::new();
+        assert_eq!(Quantization::from_params(&params), None);
+        for rule in [
+            Quantization::WholeShares,
+            Quantization::Fractional,
+            Quantization::Contracts { multiplier: 50.0 },
+        ] {
+            rule.write_params(&mut params);
+            assert_eq!(Quantization::from_params(&params), Some(rule));
+        }
+    }
+
     #[test]
     fn instrument_serialization_roundtrip() {
         let inst = Instrument::us_equity("AAPL");
@@ -0,0 +1,133 @@
+//! Micros — fixed-point monetary amounts in millionths of a currency unit.
+//!
+//! Used by precise accounting (see `ExecutionConfig::precise_accounting`):
+//! fill prices, costs and cash are rounded to the micro-unit grid and summed
+//! as integers, so the result no longer depends on the order or number of
+//! fills. Amounts convert back to `f64` at the boundary; any grid value
+//! below about two billion units round-trips exactly.
+
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+/// Micro-units per currency unit.
+pub const MICROS_PER_UNIT: i64 = 1_000_000;
+
+/// A monetary amount in micro-units.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Micros(pub i64);
+
+impl Micros {
+    pub const ZERO: Self = Self(0);
+
+    /// Nearest micro-unit amount to `value` (halves away from zero).
+    pub fn from_f64(value: f64) -> Self {
+        Self((value * MICROS_PER_UNIT as f64).round() as i64)
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / MICROS_PER_UNIT as f64
+    }
+
+    /// This amount (a price) times `quantity`, itself rounded to the
+    /// micro-unit grid, with the product rounded to the nearest micro-unit.
+    pub fn times(self, quantity: f64) -> Self {
+        let quantity = Micros::from_f64(quantity).0 as i128;
+        let product = self.0 as i128 * quantity;
+        let scale = MICROS_PER_UNIT as i128;
+        // Round half away from zero, like `f64::round`
+        let rounded = (product.abs() + scale / 2) / scale * product.signum();
+        Self(rounded as i64)
+    }
+}
+
+/// `value` rounded to the micro-unit grid.
+pub fn round_to_micros(value: f64) -> f64 {
+    Micros::from_f64(value).to_f64()
+}
+
+impl Add for Micros {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Micros {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl Neg for Micros {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+impl AddAssign for Micros {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl SubAssign for Micros {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Sum for Micros {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_values_round_trip_exactly() {
+        for value in [
+            0.0,
+            0.01,
+            100.05,
+            -2.5,
+            123_456.789_012,
+            1_999_999_999.999_999,
+        ] {
+            let micros = Micros::from_f64(value);
+            assert_eq!(Micros::from_f64(micros.to_f64()), micros);
+            assert_eq!(round_to_micros(value), micros.to_f64());
+        }
+        assert_eq!(Micros::from_f64(100.05), Micros(100_050_000));
+        assert_eq!(Micros::from_f64(-0.000_000_5), Micros(-1));
+    }
+
+    #[test]
+    fn times_rounds_the_product_once() {
+        let price = Micros::from_f64(100.05);
+        assert_eq!(price.times(100.0), Micros::from_f64(10_005.0));
+        // 0.000_003 * 0.5 = 0.000_001_5 rounds away from zero
+        assert_eq!(Micros(3).times(0.5), Micros(2));
+        assert_eq!(Micros(-3).times(0.5), Micros(-2));
+        assert_eq!(
+            Micros::from_f64(33.33).times(3.7),
+            Micros::from_f64(123.321)
+        );
+    }
+
+    #[test]
+    fn sums_are_exact_where_f64_is_not() {
+        let tenth = Micros::from_f64(0.1);
+        let exact: Micros = vec![tenth; 10].into_iter().sum();
+        assert_eq!(exact, Micros::from_f64(1.0));
+        let float: f64 = vec![0.1; 10].into_iter().sum();
+        assert_ne!(float, 1.0);
+    }
+}
@@ -5,7 +5,7 @@ use super::instrument::OrderSide;
 use serde::{Deserialize, Serialize};
 
 /// What kind of order and its price parameters.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum OrderType {
     /// Fill at next bar's open price.
     MarketOnOpen,
@@ -24,6 +24,18 @@ pub enum OrderType {
     },
 }
 
+impl OrderType {
+    /// The limit price of a limit or stop-limit order.
+    pub fn limit_price(&self) -> Option<f64> {
+        match self {
+            OrderType::Limit { limit_price } | OrderType::StopLimit { limit_price, .. } => {
+                Some(*limit_price)
+            }
+            _ => None,
+        }
+    }
+}
+
 /// Order lifecycle states.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OrderStatus {
@@ -1,19 +1,25 @@
 //! Portfolio — aggregate state of cash + all open positions.
 
 use super::position::Position;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Aggregate portfolio state.
 ///
 /// Tracks cash, open positions, and accumulated costs. The equity accounting
 /// identity must hold at every bar: `equity == cash + sum(position market values)`.
+///
+/// Positions are keyed in symbol order, so equity sums (and the position
+/// sizes derived from them) come out bit-identical from run to run.
 #[derive(Debug, Clone)]
 pub struct Portfolio {
     pub cash: f64,
     pub initial_capital: f64,
-    pub positions: HashMap<String, Position>,
+    pub positions: BTreeMap<String, Position>,
     pub total_commission: f64,
     pub total_slippage: f64,
+    /// Settle fills in fixed-point micro-units (see [`super::money`]), so
+    /// cash, realized PnL and cost totals are exact sums of the fills.
+    pub precise_accounting: bool,
 }
 
 impl Portfolio {
@@ -21,9 +27,10 @@ impl Portfolio {
         Self {
             cash: initial_capital,
             initial_capital,
-            positions: HashMap::new(),
+            positions: BTreeMap::new(),
             total_commission: 0.0,
             total_slippage: 0.0,
+            precise_accounting: false,
         }
     }
 
@@ -40,6 +47,17 @@ impl Portfolio {
         self.cash + position_value
     }
 
+    /// Gross exposure: the summed absolute market value of every position.
+    pub fn gross_exposure(&self, prices: &HashMap<String, f64>) -> f64 {
+        self.positions
+            .iter()
+            .map(|(sym, pos)| {
+                let price = prices.get(sym).copied().unwrap_or(pos.avg_entry_price);
+                pos.market_value(price).abs()
+            })
+            .sum()
+    }
+
     /// Whether a symbol has an open position.
     pub fn has_position(&self, symbol: &str) -> bool {
         self.positions.get(symbol).is_some_and(|p| !p.is_flat())
@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::MarketStatus;
+
 /// Direction of a position.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PositionSide {
@@ -27,12 +29,18 @@ pub struct Position {
     pub lowest_price_since_entry: f64,
     /// Number of bars the position has been held (incremented each bar including void bars).
     pub bars_held: usize,
+    /// Number of open-market bars the position has been held (void bars excluded).
+    #[serde(default)]
+    pub bars_held_open: usize,
     /// Current unrealized PnL based on last mark-to-market price.
     pub unrealized_pnl: f64,
     /// Accumulated realized PnL from partial exits.
     pub realized_pnl: f64,
     /// Current stop price (set by position manager, used by ratchet invariant check).
     pub current_stop: Option<f64>,
+    /// First stop the position manager placed; defines 1R for R-multiple stats.
+    #[serde(default)]
+    pub initial_stop: Option<f64>,
 }
 
 impl Position {
@@ -46,9 +54,11 @@ impl Position {
             highest_price_since_entry: entry_price,
             lowest_price_since_entry: entry_price,
             bars_held: 0,
+            bars_held_open: 0,
             unrealized_pnl: 0.0,
             realized_pnl: 0.0,
             current_stop: None,
+            initial_stop: None,
         }
     }
 
@@ -62,9 +72,11 @@ impl Position {
             highest_price_since_entry: entry_price,
             lowest_price_since_entry: entry_price,
             bars_held: 0,
+            bars_held_open: 0,
             unrealized_pnl: 0.0,
             realized_pnl: 0.0,
             current_stop: None,
+            initial_stop: None,
         }
     }
 
@@ -87,14 +99,25 @@ impl Position {
         };
     }
 
-    /// Increment the bars-held counter (called every bar, including void bars).
-    pub fn tick_bar(&mut self) {
+    /// Advance the holding counters (called every bar, including void bars).
+    ///
+    /// `bars_held` counts every bar; `bars_held_open` only bars where the
+    /// symbol traded.
+    pub fn tick_bar(&mut self, status: MarketStatus) {
         self.bars_held += 1;
+        if status == MarketStatus::Open {
+            self.bars_held_open += 1;
+        }
     }
 
-    /// Market value at the given price.
+    /// Market value at the given price: negative for shorts, whose sale
+    /// proceeds are already in cash and must be bought back.
     pub fn market_value(&self, price: f64) -> f64 {
-        self.quantity * price
+        match self.side {
+            PositionSide::Long => self.quantity * price,
+            PositionSide::Short => -self.quantity * price,
+            PositionSide::Flat => 0.0,
+        }
     }
 }
 
@@ -129,9 +152,21 @@ mod tests {
     #[test]
     fn bars_held_increments() {
         let mut pos = Position::new_long("SPY".into(), 100.0, 100.0, 0);
-        pos.tick_bar();
-        pos.tick_bar();
-        pos.tick_bar();
+        pos.tick_bar(MarketStatus::Open);
+        pos.tick_bar(MarketStatus::Open);
+        pos.tick_bar(MarketStatus::Open);
         assert_eq!(pos.bars_held, 3);
+        assert_eq!(pos.bars_held_open, 3);
+    }
+
+    #[test]
+    fn void_bars_only_advance_bars_held() {
+        let mut pos = Position::new_long("SPY".into(), 100.0, 100.0, 0);
+        pos.tick_bar(MarketStatus::Open);
+        pos.tick_bar(MarketStatus::Closed);
+        pos.tick_bar(MarketStatus::Closed);
+        pos.tick_bar(MarketStatus::Open);
+        assert_eq!(pos.bars_held, 4);
+        assert_eq!(pos.bars_held_open, 2);
     }
 }
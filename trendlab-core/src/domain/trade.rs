@@ -1,5 +1,6 @@
 //! TradeRecord — a completed round-trip trade with full traceability.
 
+use super::fill::DecisionSource;
 use super::ids::SignalEventId;
 use super::position::PositionSide;
 use chrono::NaiveDate;
@@ -19,11 +20,19 @@ pub struct TradeRecord {
     pub entry_bar: usize,
     pub entry_date: NaiveDate,
     pub entry_price: f64,
+    /// Trigger margin of a stop/limit entry fill (see `Fill::trigger_margin`).
+    #[serde(default)]
+    pub entry_trigger_margin: Option<f64>,
 
     // ── Exit ──
     pub exit_bar: usize,
     pub exit_date: NaiveDate,
     pub exit_price: f64,
+    /// Bar on which a time limit came due (see `PositionManager::time_expired`).
+    /// The exit fills at the next open bar, so `exit_bar` is later, by more
+    /// than one bar when the market was closed in between.
+    #[serde(default)]
+    pub expiry_bar: Option<usize>,
 
     // ── Size ──
     pub quantity: f64,
@@ -43,6 +52,23 @@ pub struct TradeRecord {
     /// Maximum favorable excursion (best unrealized gain during the trade).
     pub mfe: f64,
 
+    // ── Risk ──
+    /// First stop placed by the position manager (None if it never set one).
+    #[serde(default)]
+    pub initial_stop_price: Option<f64>,
+
+    // ── Attribution ──
+    /// Decision behind the entry fill.
+    #[serde(default)]
+    pub entry_source: Option<DecisionSource>,
+    /// Decision behind the exit fill.
+    #[serde(default)]
+    pub exit_source: Option<DecisionSource>,
+    /// Closed by the end of the data rather than by the strategy: the
+    /// position was still open after the last bar and is marked there.
+    #[serde(default)]
+    pub liquidated_at_end: bool,
+
     // ── Signal traceability ──
     pub signal_id: Option<SignalEventId>,
     pub signal_type: Option<String>,
@@ -63,6 +89,22 @@ impl TradeRecord {
     pub fn is_winner(&self) -> bool {
         self.net_pnl > 0.0
     }
+
+    /// Net PnL expressed in multiples of the initial risk (entry to initial stop).
+    ///
+    /// None if no initial stop was recorded or the stop is on the wrong side of entry.
+    pub fn r_multiple(&self) -> Option<f64> {
+        let stop = self.initial_stop_price?;
+        let risk_per_unit = match self.side {
+            PositionSide::Long => self.entry_price - stop,
+            PositionSide::Short => stop - self.entry_price,
+            PositionSide::Flat => return None,
+        };
+        if risk_per_unit <= 0.0 || self.quantity == 0.0 {
+            return None;
+        }
+        Some(self.net_pnl / (risk_per_unit * self.quantity))
+    }
 }
 
 #[cfg(test)]
@@ -76,6 +118,7 @@ mod tests {
             entry_bar: 4,
             entry_date: NaiveDate::from_ymd_opt(2024, 1, 5).unwrap(),
             entry_price: 100.0,
+            entry_trigger_margin: None,
             exit_bar: 8,
             exit_date: NaiveDate::from_ymd_opt(2024, 1, 11).unwrap(),
             exit_price: 110.0,
@@ -87,6 +130,11 @@ mod tests {
             bars_held: 4,
             mae: -50.0,
             mfe: 600.0,
+            initial_stop_price: Some(95.0),
+            entry_source: None,
+            exit_source: None,
+            liquidated_at_end: false,
+            expiry_bar: None,
             signal_id: Some(SignalEventId(1)),
             signal_type: Some("donchian_breakout".into()),
             pm_type: Some("atr_trailing".into()),
@@ -107,6 +155,19 @@ mod tests {
         assert!(sample_trade().is_winner());
     }
 
+    #[test]
+    fn r_multiple_uses_initial_stop_distance() {
+        let mut trade = sample_trade();
+        // Risk = (100 - 95) * 50 = 250; net = 485 → 1.94R
+        assert!((trade.r_multiple().unwrap() - 1.94).abs() < 1e-10);
+
+        trade.initial_stop_price = None;
+        assert!(trade.r_multiple().is_none());
+
+        trade.initial_stop_price = Some(101.0); // stop above a long entry
+        assert!(trade.r_multiple().is_none());
+    }
+
     #[test]
     fn trade_serialization_roundtrip() {
         let trade = sample_trade();
@@ -0,0 +1,197 @@
+//! PnL attribution — which engine decisions a run's PnL came from.
+//!
+//! Post-processes fills after the bar loop, like trade extraction. Each fill
+//! books its PnL on its own bar under its [`DecisionSource`]:
+//!
+//! - A fill that opens or adds to a position books its commission and
+//!   slippage (negative), whatever its source.
+//! - A fill that reduces a position books the gross PnL of the quantity it
+//!   closes against the average entry, less its own costs.
+//!
+//! Positions still open at the end book their unrealized PnL at the final
+//! marks under [`DecisionSource::EndOfRunLiquidation`] on the last bar. The
+//! buckets therefore sum to the net PnL of every closed trade plus the open
+//! positions' PnL at the final marks.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::instrument::OrderSide;
+use crate::domain::{DecisionSource, Fill};
+
+/// PnL booked on one bar, by decision source.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttributedBar {
+    pub bar_index: usize,
+    pub date: NaiveDate,
+    pub pnl: BTreeMap<DecisionSource, f64>,
+}
+
+/// Per-bar PnL split by decision source. Bars without fills are omitted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PnlAttribution {
+    pub bars: Vec<AttributedBar>,
+}
+
+impl PnlAttribution {
+    pub fn is_empty(&self) -> bool {
+        self.bars.is_empty()
+    }
+
+    /// PnL per source over the whole run.
+    pub fn totals(&self) -> BTreeMap<DecisionSource, f64> {
+        let mut totals = BTreeMap::new();
+        for bar in &self.bars {
+            for (&source, &pnl) in &bar.pnl {
+                *totals.entry(source).or_insert(0.0) += pnl;
+            }
+        }
+        totals
+    }
+
+    /// Sum over every source and bar.
+    pub fn total(&self) -> f64 {
+        self.totals().values().sum()
+    }
+
+    fn book(&mut self, bar_index: usize, date: NaiveDate, source: DecisionSource, pnl: f64) {
+        let bar = match self.bars.last_mut() {
+            Some(bar) if bar.bar_index == bar_index => bar,
+            _ => {
+                self.bars.push(AttributedBar {
+                    bar_index,
+                    date,
+                    pnl: BTreeMap::new(),
+                });
+                self.bars.last_mut().unwrap()
+            }
+        };
+        *bar.pnl.entry(source).or_insert(0.0) += pnl;
+    }
+}
+
+/// Signed quantity and average entry price of a position being replayed.
+#[derive(Default)]
+struct Holding {
+    quantity: f64,
+    avg_price: f64,
+}
+
+/// Attribute the PnL of `fills` (in bar order) to their decision sources.
+///
+/// `final_marks` are the prices open positions are marked at after the last
+/// bar, which is `last_bar`. Fills without a source count as entries when
+/// they open a position and as forced exits when they reduce one.
+pub fn attribute_pnl(
+    fills: &[Fill],
+    final_marks: &HashMap<String, f64>,
+    last_bar: Option<(usize, NaiveDate)>,
+) -> PnlAttribution {
+    let mut attribution = PnlAttribution::default();
+    let mut holdings: BTreeMap<&str, Holding> = BTreeMap::new();
+
+    for fill in fills {
+        let holding = holdings.entry(&fill.symbol).or_default();
+        let signed = match fill.side {
+            OrderSide::Buy => fill.quantity,
+            OrderSide::Sell => -fill.quantity,
+        };
+        let costs = fill.commission + fill.slippage;
+
+        if holding.quantity == 0.0 || holding.quantity.signum() == signed.signum() {
+            let total = holding.quantity.abs() + fill.quantity;
+            holding.avg_price =
+                (holding.avg_price * holding.quantity.abs() + fill.price * fill.quantity) / total;
+            holding.quantity += signed;
+            let source = fill.decision_source.unwrap_or(DecisionSource::SignalEntry);
+            attribution.book(fill.bar_index, fill.date, source, -costs);
+        } else {
+            // Closing quantity beyond the position is ignored, as in apply_fills
+            let closed = fill.quantity.min(holding.quantity.abs());
+            let gross = (fill.price - holding.avg_price) * closed * holding.quantity.signum();
+            holding.quantity += closed * signed.signum();
+            if holding.quantity.abs() <= 1e-10 {
+                *holding = Holding::default();
+            }
+            let source = fill.decision_source.unwrap_or(DecisionSource::PmForceExit);
+            attribution.book(fill.bar_index, fill.date, source, gross - costs);
+        }
+    }
+
+    if let Some((bar_index, date)) = last_bar {
+        for (symbol, holding) in holdings.iter().filter(|(_, h)| h.quantity != 0.0) {
+            let mark = final_marks
+                .get(*symbol)
+                .copied()
+                .unwrap_or(holding.avg_price);
+            let unrealized = (mark - holding.avg_price) * holding.quantity;
+            attribution.book(
+                bar_index,
+                date,
+                DecisionSource::EndOfRunLiquidation,
+                unrealized,
+            );
+        }
+    }
+
+    attribution
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::fill::FillPhase;
+    use crate::domain::ids::OrderId;
+
+    fn day(bar: usize) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, 1).unwrap() + chrono::Days::new(bar as u64)
+    }
+
+    fn fill(bar: usize, side: OrderSide, price: f64, source: DecisionSource) -> Fill {
+        Fill {
+            order_id: OrderId(bar as u64),
+            bar_index: bar,
+            date: day(bar),
+            symbol: "SPY".into(),
+            side,
+            price,
+            quantity: 10.0,
+            commission: 1.0,
+            slippage: 0.5,
+            phase: FillPhase::StartOfBar,
+            trigger_margin: None,
+            decision_source: Some(source),
+        }
+    }
+
+    #[test]
+    fn exits_book_gross_less_their_own_costs() {
+        let fills = [
+            fill(1, OrderSide::Buy, 100.0, DecisionSource::SignalEntry),
+            fill(5, OrderSide::Sell, 110.0, DecisionSource::PmStopExit),
+            fill(6, OrderSide::Sell, 120.0, DecisionSource::SignalEntry),
+            fill(9, OrderSide::Buy, 115.0, DecisionSource::PmTargetExit),
+        ];
+        let attribution = attribute_pnl(&fills, &HashMap::new(), Some((10, day(10))));
+        let totals = attribution.totals();
+        assert_eq!(totals[&DecisionSource::SignalEntry], -3.0);
+        assert_eq!(totals[&DecisionSource::PmStopExit], 98.5);
+        assert_eq!(totals[&DecisionSource::PmTargetExit], 48.5);
+        assert!(!totals.contains_key(&DecisionSource::EndOfRunLiquidation));
+        assert_eq!(attribution.bars.len(), 4);
+        assert_eq!(attribution.bars[1].date, day(5));
+    }
+
+    #[test]
+    fn open_positions_are_marked_on_the_last_bar() {
+        let fills = [fill(1, OrderSide::Sell, 100.0, DecisionSource::SignalEntry)];
+        let marks = HashMap::from([("SPY".to_string(), 90.0)]);
+        let attribution = attribute_pnl(&fills, &marks, Some((20, day(20))));
+        let last = attribution.bars.last().unwrap();
+        assert_eq!(last.bar_index, 20);
+        assert_eq!(last.pnl[&DecisionSource::EndOfRunLiquidation], 100.0);
+        assert_eq!(attribution.total(), 98.5);
+    }
+}
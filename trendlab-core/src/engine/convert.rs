@@ -2,17 +2,20 @@
 //!
 //! The data pipeline uses `RawBar` (no symbol field); the engine uses `domain::Bar`
 //! (with symbol). This module bridges the gap with a one-time conversion before the
-//! bar loop begins.
+//! bar loop begins, in which every bar of a symbol shares one symbol allocation.
 
 use crate::data::align::AlignedData;
 use crate::data::provider::RawBar;
 use crate::domain::Bar;
 use std::collections::HashMap;
+use std::sync::Arc;
 
-/// Convert a single `RawBar` + symbol name into a domain `Bar`.
-pub fn raw_to_bar(raw: &RawBar, symbol: &str) -> Bar {
+/// Convert a single `RawBar` + symbol into a domain `Bar`.
+///
+/// The bar shares `symbol` rather than allocating its own copy.
+pub fn raw_to_bar(raw: &RawBar, symbol: &Arc<str>) -> Bar {
     Bar {
-        symbol: symbol.to_string(),
+        symbol: Arc::clone(symbol),
         date: raw.date,
         open: raw.open,
         high: raw.high,
@@ -32,7 +35,11 @@ pub fn aligned_to_bars(aligned: &AlignedData) -> HashMap<String, Vec<Bar>> {
         .bars
         .iter()
         .map(|(symbol, raw_bars)| {
-            let bars: Vec<Bar> = raw_bars.iter().map(|raw| raw_to_bar(raw, symbol)).collect();
+            let shared: Arc<str> = Arc::from(symbol.as_str());
+            let bars = raw_bars
+                .iter()
+                .map(|raw| raw_to_bar(raw, &shared))
+                .collect();
             (symbol.clone(), bars)
         })
         .collect()
@@ -54,8 +61,8 @@ mod tests {
             volume: 1000,
             adj_close: 103.0,
         };
-        let bar = raw_to_bar(&raw, "SPY");
-        assert_eq!(bar.symbol, "SPY");
+        let bar = raw_to_bar(&raw, &Arc::from("SPY"));
+        assert_eq!(&*bar.symbol, "SPY");
         assert_eq!(bar.date, raw.date);
         assert_eq!(bar.open, 100.0);
         assert_eq!(bar.high, 105.0);
@@ -76,7 +83,7 @@ mod tests {
             volume: 0,
             adj_close: f64::NAN,
         };
-        let bar = raw_to_bar(&raw, "QQQ");
+        let bar = raw_to_bar(&raw, &Arc::from("QQQ"));
         assert!(bar.is_void());
     }
 
@@ -116,9 +123,33 @@ mod tests {
 
         let bars = aligned_to_bars(&aligned);
         assert_eq!(bars.len(), 2);
-        assert_eq!(bars["SPY"][0].symbol, "SPY");
+        assert_eq!(&*bars["SPY"][0].symbol, "SPY");
         assert_eq!(bars["SPY"][0].close, 103.0);
-        assert_eq!(bars["QQQ"][0].symbol, "QQQ");
+        assert_eq!(&*bars["QQQ"][0].symbol, "QQQ");
         assert_eq!(bars["QQQ"][0].close, 205.0);
     }
+
+    #[test]
+    fn aligned_to_bars_shares_the_symbol_across_a_series() {
+        let raw = |day| RawBar {
+            date: NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+            open: 100.0,
+            high: 105.0,
+            low: 99.0,
+            close: 103.0,
+            volume: 1000,
+            adj_close: 103.0,
+        };
+        let aligned = AlignedData {
+            dates: vec![
+                NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 1, 3).unwrap(),
+            ],
+            bars: HashMap::from([("SPY".to_string(), vec![raw(2), raw(3)])]),
+            symbols: vec!["SPY".to_string()],
+        };
+
+        let bars = aligned_to_bars(&aligned);
+        assert!(Arc::ptr_eq(&bars["SPY"][0].symbol, &bars["SPY"][1].symbol));
+    }
 }
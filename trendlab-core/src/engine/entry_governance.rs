@@ -0,0 +1,304 @@
+//! Entry governance — cooldown, entry caps, and signal re-arming.
+//!
+//! Breakout signals keep firing while a trend runs, so a stop-out is often
+//! followed by a re-entry on the very next bar. Governance blocks such entries:
+//! - `cooldown_bars_after_exit`: no entry in the same symbol and direction for
+//!   N bars after an exit.
+//! - `max_entries_per_window`: at most K entries per symbol within any rolling
+//!   `entry_window_bars` window.
+//! - `require_signal_reset`: after an exit, the signal must go quiet for at
+//!   least one bar before it can enter again.
+//!
+//! Blocked signals are recorded as `FilterVerdict::Cooldown` evaluations.
+//! Strategy configs carry the rules as flat params on the signal component
+//! (see [`EntryGovernance::from_params`]), so they enter `full_hash`
+//! fingerprints. Configs without them are ungoverned.
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use crate::components::signal::SignalDirection;
+use crate::domain::PositionSide;
+
+// ─── Param keys ──────────────────────────────────────────────────────
+
+/// Bars after an exit during which same-direction entries are blocked.
+pub const PARAM_COOLDOWN_BARS: &str = "cooldown_bars";
+/// Maximum entries per symbol within the rolling window (0 = unlimited).
+pub const PARAM_MAX_ENTRIES_PER_WINDOW: &str = "max_entries_per_window";
+/// Length of the rolling entry window in bars.
+pub const PARAM_ENTRY_WINDOW_BARS: &str = "entry_window_bars";
+/// Requires a quiet signal bar after an exit when >= 0.5.
+pub const PARAM_REQUIRE_SIGNAL_RESET: &str = "require_signal_reset";
+
+/// Filter name recorded on evaluations blocked by governance.
+pub const GOVERNANCE_FILTER_NAME: &str = "entry_governance";
+
+/// Default rolling window for the entry cap.
+const DEFAULT_ENTRY_WINDOW_BARS: usize = 20;
+
+// ─── Rules ───────────────────────────────────────────────────────────
+
+/// Entry governance rules. The default blocks nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EntryGovernance {
+    pub cooldown_bars_after_exit: usize,
+    /// `None` = unlimited.
+    pub max_entries_per_window: Option<usize>,
+    pub entry_window_bars: usize,
+    pub require_signal_reset: bool,
+}
+
+impl Default for EntryGovernance {
+    fn default() -> Self {
+        Self {
+            cooldown_bars_after_exit: 0,
+            max_entries_per_window: None,
+            entry_window_bars: DEFAULT_ENTRY_WINDOW_BARS,
+            require_signal_reset: false,
+        }
+    }
+}
+
+impl EntryGovernance {
+    /// True when any rule can block an entry.
+    pub fn is_active(&self) -> bool {
+        self.cooldown_bars_after_exit > 0
+            || self.max_entries_per_window.is_some()
+            || self.require_signal_reset
+    }
+
+    /// Decode from signal params. Missing keys leave the rule off.
+    pub fn from_params(params: &BTreeMap<String, f64>) -> Self {
+        let get = |k: &str| params.get(k).copied().filter(|v| v.is_finite());
+        let count = |k: &str| get(k).map(|v| v.round().max(0.0) as usize);
+        let d = Self::default();
+        Self {
+            cooldown_bars_after_exit: count(PARAM_COOLDOWN_BARS).unwrap_or(0),
+            max_entries_per_window: count(PARAM_MAX_ENTRIES_PER_WINDOW).filter(|&k| k > 0),
+            entry_window_bars: count(PARAM_ENTRY_WINDOW_BARS)
+                .filter(|&w| w > 0)
+                .unwrap_or(d.entry_window_bars),
+            require_signal_reset: get(PARAM_REQUIRE_SIGNAL_RESET).is_some_and(|v| v >= 0.5),
+        }
+    }
+
+    /// Encode into signal params (inverse of [`Self::from_params`]).
+    ///
+    /// Only active rules are written, so ungoverned configs hash as before.
+    pub fn write_params(&self, params: &mut BTreeMap<String, f64>) {
+        if self.cooldown_bars_after_exit > 0 {
+            params.insert(
+                PARAM_COOLDOWN_BARS.into(),
+                self.cooldown_bars_after_exit as f64,
+            );
+        }
+        if let Some(k) = self.max_entries_per_window {
+            params.insert(PARAM_MAX_ENTRIES_PER_WINDOW.into(), k as f64);
+            params.insert(
+                PARAM_ENTRY_WINDOW_BARS.into(),
+                self.entry_window_bars as f64,
+            );
+        }
+        if self.require_signal_reset {
+            params.insert(PARAM_REQUIRE_SIGNAL_RESET.into(), 1.0);
+        }
+    }
+}
+
+// ─── Runtime tracking ────────────────────────────────────────────────
+
+/// Per-run governance state: recent exits, entries, and re-arm status per symbol.
+#[derive(Debug, Default)]
+pub struct EntryGovernor {
+    rules: EntryGovernance,
+    /// Bar and direction of the most recent exit per symbol.
+    last_exit: HashMap<String, (usize, SignalDirection)>,
+    /// Entry bars per symbol within the rolling window.
+    entries: HashMap<String, VecDeque<usize>>,
+    /// Symbols waiting for a quiet signal bar before re-arming.
+    disarmed: HashSet<String>,
+}
+
+impl EntryGovernor {
+    pub fn new(rules: EntryGovernance) -> Self {
+        Self {
+            rules,
+            ..Self::default()
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.rules.is_active()
+    }
+
+    /// Record that the `side` position in `symbol` closed on `bar`.
+    pub fn record_exit(&mut self, symbol: &str, bar: usize, side: PositionSide) {
+        let direction = match side {
+            PositionSide::Long => SignalDirection::Long,
+            PositionSide::Short => SignalDirection::Short,
+            PositionSide::Flat => return,
+        };
+        self.last_exit.insert(symbol.to_string(), (bar, direction));
+        if self.rules.require_signal_reset {
+            self.disarmed.insert(symbol.to_string());
+        }
+    }
+
+    /// Record an entry order submitted for `symbol` on `bar`.
+    pub fn record_entry(&mut self, symbol: &str, bar: usize) {
+        if self.rules.max_entries_per_window.is_some() {
+            self.entries
+                .entry(symbol.to_string())
+                .or_default()
+                .push_back(bar);
+        }
+    }
+
+    /// Record a bar on which `symbol` was flat and its signal did not fire.
+    pub fn record_quiet_bar(&mut self, symbol: &str) {
+        self.disarmed.remove(symbol);
+    }
+
+    /// Check whether an entry may be taken.
+    ///
+    /// Returns `None` when allowed, otherwise the state that blocked it
+    /// (recorded as the evaluation's `filter_state`).
+    pub fn check(
+        &mut self,
+        symbol: &str,
+        direction: SignalDirection,
+        bar: usize,
+    ) -> Option<HashMap<String, f64>> {
+        if self.rules.cooldown_bars_after_exit > 0 {
+            if let Some(&(exit_bar, exit_dir)) = self.last_exit.get(symbol) {
+                let since = bar.saturating_sub(exit_bar);
+                if exit_dir == direction && since <= self.rules.cooldown_bars_after_exit {
+                    return Some(HashMap::from([
+                        ("bars_since_exit".to_string(), since as f64),
+                        (
+                            PARAM_COOLDOWN_BARS.to_string(),
+                            self.rules.cooldown_bars_after_exit as f64,
+                        ),
+                    ]));
+                }
+            }
+        }
+
+        if let Some(max) = self.rules.max_entries_per_window {
+            let window = self.rules.entry_window_bars;
+            let recent = self.entries.entry(symbol.to_string()).or_default();
+            while recent
+                .front()
+                .is_some_and(|&b| bar.saturating_sub(b) >= window)
+            {
+                recent.pop_front();
+            }
+            if recent.len() >= max {
+                return Some(HashMap::from([
+                    ("entries_in_window".to_string(), recent.len() as f64),
+                    (PARAM_MAX_ENTRIES_PER_WINDOW.to_string(), max as f64),
+                ]));
+            }
+        }
+
+        if self.disarmed.contains(symbol) {
+            return Some(HashMap::from([(
+                PARAM_REQUIRE_SIGNAL_RESET.to_string(),
+                1.0,
+            )]));
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn governor(rules: EntryGovernance) -> EntryGovernor {
+        EntryGovernor::new(rules)
+    }
+
+    #[test]
+    fn default_is_inactive_and_blocks_nothing() {
+        let rules = EntryGovernance::default();
+        assert!(!rules.is_active());
+        let mut g = governor(rules);
+        g.record_exit("SPY", 10, PositionSide::Long);
+        assert!(g.check("SPY", SignalDirection::Long, 11).is_none());
+    }
+
+    #[test]
+    fn cooldown_blocks_same_direction_for_n_bars() {
+        let mut g = governor(EntryGovernance {
+            cooldown_bars_after_exit: 3,
+            ..Default::default()
+        });
+        g.record_exit("SPY", 10, PositionSide::Long);
+        for bar in 10..=13 {
+            let state = g.check("SPY", SignalDirection::Long, bar).unwrap();
+            assert_eq!(state["bars_since_exit"], (bar - 10) as f64);
+        }
+        assert!(g.check("SPY", SignalDirection::Long, 14).is_none());
+        // Opposite direction and other symbols are unaffected
+        assert!(g.check("SPY", SignalDirection::Short, 11).is_none());
+        assert!(g.check("QQQ", SignalDirection::Long, 11).is_none());
+    }
+
+    #[test]
+    fn entry_cap_uses_rolling_window() {
+        let mut g = governor(EntryGovernance {
+            max_entries_per_window: Some(2),
+            entry_window_bars: 10,
+            ..Default::default()
+        });
+        g.record_entry("SPY", 0);
+        g.record_entry("SPY", 5);
+        let state = g.check("SPY", SignalDirection::Long, 9).unwrap();
+        assert_eq!(state["entries_in_window"], 2.0);
+        // Bar 0 falls out of the window at bar 10
+        assert!(g.check("SPY", SignalDirection::Long, 10).is_none());
+    }
+
+    #[test]
+    fn signal_reset_rearms_after_quiet_bar() {
+        let mut g = governor(EntryGovernance {
+            require_signal_reset: true,
+            ..Default::default()
+        });
+        assert!(g.check("SPY", SignalDirection::Long, 5).is_none());
+        g.record_exit("SPY", 10, PositionSide::Long);
+        assert!(g.check("SPY", SignalDirection::Long, 11).is_some());
+        assert!(g.check("SPY", SignalDirection::Short, 12).is_some());
+        g.record_quiet_bar("SPY");
+        assert!(g.check("SPY", SignalDirection::Long, 13).is_none());
+    }
+
+    #[test]
+    fn params_roundtrip() {
+        let rules = EntryGovernance {
+            cooldown_bars_after_exit: 5,
+            max_entries_per_window: Some(3),
+            entry_window_bars: 40,
+            require_signal_reset: true,
+        };
+        let mut params = BTreeMap::new();
+        rules.write_params(&mut params);
+        assert_eq!(EntryGovernance::from_params(&params), rules);
+    }
+
+    #[test]
+    fn inactive_rules_write_no_params() {
+        let mut params = BTreeMap::new();
+        EntryGovernance::default().write_params(&mut params);
+        assert!(params.is_empty());
+        assert_eq!(
+            EntryGovernance::from_params(&params),
+            EntryGovernance::default()
+        );
+    }
+}
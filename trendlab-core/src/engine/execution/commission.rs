@@ -0,0 +1,380 @@
+//! Commission models — basis points, per-share, per-trade, and tiered schedules,
+//! plus optional sell-side regulatory fees (SEC Section 31 and FINRA TAF).
+//!
+//! Commission models are charged per fill. A partially filled order therefore
+//! pays any minimum or flat fee once per fill, as a real broker would.
+//!
+//! Strategy configs carry commission settings as flat numeric params on the
+//! execution model (see [`CommissionModel::from_params`]), so they flow into
+//! `full_hash` fingerprints without any schema change. Configs that omit them
+//! keep the preset's basis-point commission.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::instrument::OrderSide;
+
+// ─── Param keys ──────────────────────────────────────────────────────
+
+/// Selects the commission model: 0 = bps, 1 = per-share, 2 = per-trade, 3 = tiered.
+pub const PARAM_COMMISSION_MODEL: &str = "commission_model";
+/// Bps rate (model 0) or per-share dollar rate (model 1).
+pub const PARAM_COMMISSION_RATE: &str = "commission_rate";
+/// Per-fill minimum for the per-share model.
+pub const PARAM_COMMISSION_MIN: &str = "commission_min";
+/// Flat per-fill fee for the per-trade model.
+pub const PARAM_COMMISSION_FLAT: &str = "commission_flat";
+/// Enables sell-side regulatory fees when >= 0.5.
+pub const PARAM_REGULATORY_FEES: &str = "regulatory_fees";
+/// SEC fee rate as a fraction of sell notional.
+pub const PARAM_SEC_FEE_RATE: &str = "sec_fee_rate";
+/// FINRA TAF per share sold.
+pub const PARAM_TAF_PER_SHARE: &str = "taf_per_share";
+/// FINRA TAF cap per fill.
+pub const PARAM_TAF_MAX: &str = "taf_max";
+
+fn tier_above_key(i: usize) -> String {
+    format!("commission_tier_{i}_above")
+}
+
+fn tier_rate_key(i: usize) -> String {
+    format!("commission_tier_{i}_rate")
+}
+
+/// Whether `name` is one of the numbered tier keys of a tiered schedule.
+pub fn is_tier_param(name: &str) -> bool {
+    name.strip_prefix("commission_tier_")
+        .and_then(|rest| {
+            rest.strip_suffix("_above")
+                .or_else(|| rest.strip_suffix("_rate"))
+        })
+        .is_some_and(|i| !i.is_empty() && i.bytes().all(|b| b.is_ascii_digit()))
+}
+
+// ─── Commission models ───────────────────────────────────────────────
+
+/// One band of a tiered per-share schedule.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CommissionTier {
+    /// Shares in the fill beyond this count are charged at `rate`.
+    pub above: f64,
+    /// Dollars per share within this band.
+    pub rate: f64,
+}
+
+/// How broker commission is charged on a fill.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "model", rename_all = "snake_case")]
+pub enum CommissionModel {
+    /// Basis points of fill notional.
+    Bps { rate: f64 },
+    /// Dollars per share, with a per-fill minimum.
+    PerShare {
+        rate: f64,
+        #[serde(default)]
+        min: f64,
+    },
+    /// Flat dollars per fill.
+    PerTrade { flat: f64 },
+    /// Marginal per-share bands by fill size, like tax brackets. The
+    /// lowest band applies from the first share.
+    Tiered { schedule: Vec<CommissionTier> },
+}
+
+impl CommissionModel {
+    /// Commission in dollars for a fill of `quantity` shares at `fill_price`.
+    pub fn compute(&self, fill_price: f64, quantity: f64) -> f64 {
+        if quantity <= 0.0 {
+            return 0.0;
+        }
+        match self {
+            Self::Bps { rate } => fill_price * quantity * (rate / 10_000.0),
+            Self::PerShare { rate, min } => (quantity * rate).max(*min),
+            Self::PerTrade { flat } => *flat,
+            Self::Tiered { schedule } => {
+                let mut tiers = schedule.clone();
+                tiers.sort_by(|a, b| a.above.total_cmp(&b.above));
+                let mut total = 0.0;
+                for (i, tier) in tiers.iter().enumerate() {
+                    let lo = if i == 0 { 0.0 } else { tier.above };
+                    let hi = tiers.get(i + 1).map_or(f64::INFINITY, |t| t.above);
+                    let shares = quantity.min(hi) - lo;
+                    if shares <= 0.0 {
+                        break;
+                    }
+                    total += shares * tier.rate;
+                }
+                total
+            }
+        }
+    }
+
+    /// Copy with every dollar amount and rate multiplied by `factor`.
+    ///
+    /// Used by execution Monte Carlo to perturb non-bps schedules.
+    pub fn scaled(&self, factor: f64) -> Self {
+        match self {
+            Self::Bps { rate } => Self::Bps {
+                rate: rate * factor,
+            },
+            Self::PerShare { rate, min } => Self::PerShare {
+                rate: rate * factor,
+                min: min * factor,
+            },
+            Self::PerTrade { flat } => Self::PerTrade {
+                flat: flat * factor,
+            },
+            Self::Tiered { schedule } => Self::Tiered {
+                schedule: schedule
+                    .iter()
+                    .map(|t| CommissionTier {
+                        above: t.above,
+                        rate: t.rate * factor,
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    /// Decode from execution-model params. Returns `None` when no
+    /// `commission_model` key is present (the preset's bps rate applies).
+    pub fn from_params(params: &BTreeMap<String, f64>) -> Option<Self> {
+        let get = |k: &str| params.get(k).copied().unwrap_or(0.0);
+        let model = match params.get(PARAM_COMMISSION_MODEL)?.round() as u8 {
+            1 => Self::PerShare {
+                rate: get(PARAM_COMMISSION_RATE),
+                min: get(PARAM_COMMISSION_MIN),
+            },
+            2 => Self::PerTrade {
+                flat: get(PARAM_COMMISSION_FLAT),
+            },
+            3 => {
+                let schedule = (0..)
+                    .map_while(|i| {
+                        let above = params.get(&tier_above_key(i))?;
+                        let rate = params.get(&tier_rate_key(i))?;
+                        Some(CommissionTier {
+                            above: *above,
+                            rate: *rate,
+                        })
+                    })
+                    .collect();
+                Self::Tiered { schedule }
+            }
+            _ => Self::Bps {
+                rate: get(PARAM_COMMISSION_RATE),
+            },
+        };
+        Some(model)
+    }
+
+    /// Encode into execution-model params (inverse of [`Self::from_params`]).
+    pub fn write_params(&self, params: &mut BTreeMap<String, f64>) {
+        match self {
+            Self::Bps { rate } => {
+                params.insert(PARAM_COMMISSION_MODEL.into(), 0.0);
+                params.insert(PARAM_COMMISSION_RATE.into(), *rate);
+            }
+            Self::PerShare { rate, min } => {
+                params.insert(PARAM_COMMISSION_MODEL.into(), 1.0);
+                params.insert(PARAM_COMMISSION_RATE.into(), *rate);
+                params.insert(PARAM_COMMISSION_MIN.into(), *min);
+            }
+            Self::PerTrade { flat } => {
+                params.insert(PARAM_COMMISSION_MODEL.into(), 2.0);
+                params.insert(PARAM_COMMISSION_FLAT.into(), *flat);
+            }
+            Self::Tiered { schedule } => {
+                params.insert(PARAM_COMMISSION_MODEL.into(), 3.0);
+                for (i, tier) in schedule.iter().enumerate() {
+                    params.insert(tier_above_key(i), tier.above);
+                    params.insert(tier_rate_key(i), tier.rate);
+                }
+            }
+        }
+    }
+}
+
+// ─── Regulatory fees ─────────────────────────────────────────────────
+
+/// US equity sell-side regulatory fees. Buys are never charged.
+///
+/// Each fee is rounded up to the next cent per fill, matching how brokers
+/// pass them through.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RegulatoryFees {
+    /// SEC Section 31 fee as a fraction of sell notional ($27.80 per million).
+    pub sec_fee_rate: f64,
+    /// FINRA Trading Activity Fee per share sold.
+    pub taf_per_share: f64,
+    /// FINRA TAF cap per fill.
+    pub taf_max: f64,
+}
+
+impl Default for RegulatoryFees {
+    fn default() -> Self {
+        Self {
+            sec_fee_rate: 27.80 / 1_000_000.0,
+            taf_per_share: 0.000166,
+            taf_max: 8.30,
+        }
+    }
+}
+
+impl RegulatoryFees {
+    /// Regulatory fees in dollars for a fill. Zero for buys.
+    pub fn compute(&self, fill_price: f64, quantity: f64, side: OrderSide) -> f64 {
+        if side == OrderSide::Buy || quantity <= 0.0 {
+            return 0.0;
+        }
+        let sec = round_up_cents(fill_price * quantity * self.sec_fee_rate);
+        let taf = round_up_cents((quantity * self.taf_per_share).min(self.taf_max));
+        sec + taf
+    }
+
+    /// Decode from execution-model params. Returns `None` unless
+    /// `regulatory_fees` is set; individual rates fall back to the defaults.
+    pub fn from_params(params: &BTreeMap<String, f64>) -> Option<Self> {
+        if params.get(PARAM_REGULATORY_FEES).copied().unwrap_or(0.0) < 0.5 {
+            return None;
+        }
+        let d = Self::default();
+        let get = |k: &str, default: f64| params.get(k).copied().unwrap_or(default);
+        Some(Self {
+            sec_fee_rate: get(PARAM_SEC_FEE_RATE, d.sec_fee_rate),
+            taf_per_share: get(PARAM_TAF_PER_SHARE, d.taf_per_share),
+            taf_max: get(PARAM_TAF_MAX, d.taf_max),
+        })
+    }
+
+    /// Encode into execution-model params (inverse of [`Self::from_params`]).
+    pub fn write_params(&self, params: &mut BTreeMap<String, f64>) {
+        params.insert(PARAM_REGULATORY_FEES.into(), 1.0);
+        params.insert(PARAM_SEC_FEE_RATE.into(), self.sec_fee_rate);
+        params.insert(PARAM_TAF_PER_SHARE.into(), self.taf_per_share);
+        params.insert(PARAM_TAF_MAX.into(), self.taf_max);
+    }
+}
+
+/// Round a dollar amount up to the next cent, tolerating float noise.
+fn round_up_cents(amount: f64) -> f64 {
+    ((amount * 100.0) - 1e-9).ceil().max(0.0) / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn per_share_minimum_dominates_small_orders() {
+        let model = CommissionModel::PerShare {
+            rate: 0.005,
+            min: 1.0,
+        };
+        // 10 shares * $0.005 = $0.05 → floored to $1.00
+        assert!((model.compute(50.0, 10.0) - 1.0).abs() < 1e-12);
+        // 199 shares = $0.995 → still the minimum
+        assert!((model.compute(50.0, 199.0) - 1.0).abs() < 1e-12);
+        // 1000 shares = $5.00 → rate dominates
+        assert!((model.compute(50.0, 1000.0) - 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn per_trade_is_flat_and_zero_quantity_is_free() {
+        let model = CommissionModel::PerTrade { flat: 4.95 };
+        assert_eq!(model.compute(10.0, 1.0), 4.95);
+        assert_eq!(model.compute(10.0, 10_000.0), 4.95);
+        assert_eq!(model.compute(10.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn tiered_schedule_is_marginal() {
+        let model = CommissionModel::Tiered {
+            schedule: vec![
+                CommissionTier {
+                    above: 500.0,
+                    rate: 0.002,
+                },
+                CommissionTier {
+                    above: 0.0,
+                    rate: 0.005,
+                },
+            ],
+        };
+        // 300 shares all in the first band
+        assert!((model.compute(10.0, 300.0) - 1.5).abs() < 1e-12);
+        // 800 shares: 500 * 0.005 + 300 * 0.002 = 2.5 + 0.6
+        assert!((model.compute(10.0, 800.0) - 3.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn regulatory_fees_sell_side_only() {
+        let fees = RegulatoryFees::default();
+        assert_eq!(fees.compute(100.0, 100.0, OrderSide::Buy), 0.0);
+        // SEC: $10,000 * 27.80e-6 = $0.278 → $0.28; TAF: 100 * 0.000166 = $0.0166 → $0.02
+        assert!((fees.compute(100.0, 100.0, OrderSide::Sell) - 0.30).abs() < 1e-12);
+        // TAF capped at $8.30 for very large fills
+        let big = fees.compute(1.0, 1_000_000.0, OrderSide::Sell);
+        assert!((big - (27.80 + 8.30)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn params_roundtrip() {
+        let models = [
+            CommissionModel::Bps { rate: 3.0 },
+            CommissionModel::PerShare {
+                rate: 0.005,
+                min: 1.0,
+            },
+            CommissionModel::PerTrade { flat: 2.5 },
+            CommissionModel::Tiered {
+                schedule: vec![
+                    CommissionTier {
+                        above: 0.0,
+                        rate: 0.0035,
+                    },
+                    CommissionTier {
+                        above: 300_000.0,
+                        rate: 0.002,
+                    },
+                ],
+            },
+        ];
+        for model in models {
+            let mut params = BTreeMap::new();
+            model.write_params(&mut params);
+            assert_eq!(CommissionModel::from_params(&params), Some(model));
+        }
+
+        let mut params = BTreeMap::new();
+        RegulatoryFees::default().write_params(&mut params);
+        assert_eq!(
+            RegulatoryFees::from_params(&params),
+            Some(RegulatoryFees::default())
+        );
+    }
+
+    #[test]
+    fn absent_params_decode_to_none() {
+        let mut params = BTreeMap::new();
+        params.insert("preset".to_string(), 1.0);
+        assert_eq!(CommissionModel::from_params(&params), None);
+        assert_eq!(RegulatoryFees::from_params(&params), None);
+    }
+
+    #[test]
+    fn scaled_multiplies_dollar_amounts() {
+        let model = CommissionModel::PerShare {
+            rate: 0.005,
+            min: 1.0,
+        };
+        assert_eq!(
+            model.scaled(2.0),
+            CommissionModel::PerShare {
+                rate: 0.01,
+                min: 2.0
+            }
+        );
+    }
+}
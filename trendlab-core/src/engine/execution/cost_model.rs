@@ -1,43 +1,76 @@
-//! Cost model — slippage and commission calculation.
+//! Cost model — slippage, commission, and regulatory fee calculation.
 //!
 //! Slippage is directional: buyers pay more (higher price), sellers receive less (lower price).
-//! Commission is symmetric per-side in basis points.
+//! Commission is per fill under a [`CommissionModel`] (bps by default).
+//! Regulatory fees, when enabled, are charged on sells only.
 //! Tick rounding is applied after slippage: buy rounds up, sell rounds down.
 
+use std::collections::BTreeMap;
+
 use crate::components::execution::ExecutionPreset;
 use crate::domain::instrument::{round_to_tick, OrderSide};
 
-/// Cost model for execution friction (slippage + commission).
+use super::commission::{CommissionModel, RegulatoryFees};
+
+/// Cost model for execution friction (slippage + commission + fees).
 ///
-/// Current implementation uses fixed basis points. The struct design allows
-/// future replacement with ATR-scaled or distribution-sampled models.
+/// Slippage uses fixed basis points. The struct design allows future
+/// replacement with ATR-scaled or distribution-sampled models.
 #[derive(Debug, Clone)]
 pub struct CostModel {
     /// Slippage in basis points, applied directionally.
     pub slippage_bps: f64,
-    /// Commission in basis points per side.
-    pub commission_bps: f64,
+    /// Broker commission charged on every fill.
+    pub commission: CommissionModel,
+    /// Sell-side SEC/TAF fees. `None` disables them.
+    pub regulatory_fees: Option<RegulatoryFees>,
 }
 
 impl CostModel {
     pub fn new(slippage_bps: f64, commission_bps: f64) -> Self {
         Self {
             slippage_bps,
-            commission_bps,
+            commission: CommissionModel::Bps {
+                rate: commission_bps,
+            },
+            regulatory_fees: None,
         }
     }
 
     pub fn from_preset(preset: ExecutionPreset) -> Self {
-        Self {
-            slippage_bps: preset.slippage_bps(),
-            commission_bps: preset.commission_bps(),
-        }
+        Self::new(preset.slippage_bps(), preset.commission_bps())
     }
 
     pub fn frictionless() -> Self {
         Self::new(0.0, 0.0)
     }
 
+    /// Replace the commission model.
+    pub fn with_commission(mut self, commission: CommissionModel) -> Self {
+        self.commission = commission;
+        self
+    }
+
+    /// Enable or disable sell-side regulatory fees.
+    pub fn with_regulatory_fees(mut self, fees: Option<RegulatoryFees>) -> Self {
+        self.regulatory_fees = fees;
+        self
+    }
+
+    /// Apply commission settings carried in execution-model params.
+    ///
+    /// Params without commission keys leave the model untouched, so
+    /// preset-only configs keep their bps commission.
+    pub fn with_params(mut self, params: &BTreeMap<String, f64>) -> Self {
+        if let Some(commission) = CommissionModel::from_params(params) {
+            self.commission = commission;
+        }
+        if let Some(fees) = RegulatoryFees::from_params(params) {
+            self.regulatory_fees = Some(fees);
+        }
+        self
+    }
+
     /// Apply slippage to a raw fill price.
     ///
     /// Directional: buyers get a worse (higher) price, sellers get a worse (lower) price.
@@ -82,11 +115,15 @@ impl CostModel {
         (ticked, slip_amount.max(0.0))
     }
 
-    /// Compute commission for a fill.
-    ///
-    /// `commission = fill_price * quantity * (commission_bps / 10_000)`
+    /// Compute broker commission for a fill (excludes regulatory fees).
     pub fn compute_commission(&self, fill_price: f64, quantity: f64) -> f64 {
-        fill_price * quantity * (self.commission_bps / 10_000.0)
+        self.commission.compute(fill_price, quantity)
+    }
+
+    /// Compute sell-side regulatory fees for a fill. Zero for buys or when disabled.
+    pub fn compute_regulatory_fees(&self, fill_price: f64, quantity: f64, side: OrderSide) -> f64 {
+        self.regulatory_fees
+            .map_or(0.0, |fees| fees.compute(fill_price, quantity, side))
     }
 }
 
@@ -133,7 +170,7 @@ mod tests {
     fn realistic_preset() {
         let cost = CostModel::from_preset(ExecutionPreset::Realistic);
         assert_eq!(cost.slippage_bps, 5.0);
-        assert_eq!(cost.commission_bps, 5.0);
+        assert_eq!(cost.commission, CommissionModel::Bps { rate: 5.0 });
     }
 
     #[test]
@@ -144,7 +181,36 @@ mod tests {
 
         assert!(hostile.slippage_bps > realistic.slippage_bps);
         assert!(realistic.slippage_bps > frictionless.slippage_bps);
-        assert!(hostile.commission_bps > realistic.commission_bps);
+        assert!(
+            hostile.compute_commission(100.0, 100.0) > realistic.compute_commission(100.0, 100.0)
+        );
+    }
+
+    #[test]
+    fn params_without_commission_keys_keep_preset_bps() {
+        let mut params = BTreeMap::new();
+        params.insert("preset".to_string(), 1.0);
+        let cost = CostModel::from_preset(ExecutionPreset::Realistic).with_params(&params);
+        assert_eq!(cost.commission, CommissionModel::Bps { rate: 5.0 });
+        assert!(cost.regulatory_fees.is_none());
+    }
+
+    #[test]
+    fn params_override_commission_model() {
+        let mut params = BTreeMap::new();
+        CommissionModel::PerShare {
+            rate: 0.005,
+            min: 1.0,
+        }
+        .write_params(&mut params);
+        RegulatoryFees::default().write_params(&mut params);
+        let cost = CostModel::from_preset(ExecutionPreset::Realistic).with_params(&params);
+        assert_eq!(cost.compute_commission(100.0, 10.0), 1.0);
+        assert_eq!(
+            cost.compute_regulatory_fees(100.0, 10.0, OrderSide::Buy),
+            0.0
+        );
+        assert!(cost.compute_regulatory_fees(100.0, 10.0, OrderSide::Sell) > 0.0);
     }
 
     #[test]
@@ -1,12 +1,17 @@
 //! Fill price computation — applies cost model to raw trigger prices.
 //!
 //! Takes a raw fill price from the trigger module, applies slippage,
-//! tick rounding, and commission to produce the final fill parameters.
+//! tick rounding, commission, and sell-side regulatory fees to produce the
+//! final fill parameters.
 
 use crate::domain::instrument::{Instrument, OrderSide};
+use crate::domain::money::round_to_micros;
 
 use super::cost_model::CostModel;
 
+/// Param enabling precise (fixed-point) accounting: 1 = on, absent or 0 = off.
+pub const PARAM_PRECISE_ACCOUNTING: &str = "precise_accounting";
+
 /// The fully computed fill price with all costs.
 #[derive(Debug, Clone)]
 pub struct ComputedFill {
@@ -14,13 +19,25 @@ pub struct ComputedFill {
     pub price: f64,
     /// Dollar amount of slippage applied.
     pub slippage: f64,
-    /// Dollar amount of commission.
+    /// Dollar amount of commission, including any regulatory fees.
     pub commission: f64,
 }
 
+impl ComputedFill {
+    /// Price and costs rounded to the micro-unit grid, as settled under
+    /// precise accounting.
+    pub fn on_micro_grid(self) -> Self {
+        Self {
+            price: round_to_micros(self.price),
+            slippage: round_to_micros(self.slippage),
+            commission: round_to_micros(self.commission),
+        }
+    }
+}
+
 /// Compute the final fill price from a raw trigger price.
 ///
-/// Applies: slippage (directional) → tick rounding → commission calculation.
+/// Applies: slippage (directional) → tick rounding → commission + regulatory fees.
 pub fn compute_fill(
     raw_price: f64,
     side: OrderSide,
@@ -30,7 +47,8 @@ pub fn compute_fill(
 ) -> ComputedFill {
     let (price, slippage) =
         cost_model.apply_slippage_with_tick(raw_price, side, quantity, instrument.tick_size);
-    let commission = cost_model.compute_commission(price, quantity);
+    let commission = cost_model.compute_commission(price, quantity)
+        + cost_model.compute_regulatory_fees(price, quantity, side);
 
     ComputedFill {
         price,
@@ -42,6 +60,7 @@ pub fn compute_fill(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::engine::execution::commission::{CommissionModel, RegulatoryFees};
 
     #[test]
     fn frictionless_fill() {
@@ -75,6 +94,58 @@ mod tests {
         assert!(fill.slippage > 0.0);
     }
 
+    #[test]
+    fn minimum_commission_dominates_small_orders() {
+        let cost = CostModel::frictionless().with_commission(CommissionModel::PerShare {
+            rate: 0.005,
+            min: 1.0,
+        });
+        let inst = Instrument::us_equity("SPY");
+        let small = compute_fill(100.0, OrderSide::Buy, 5.0, &inst, &cost);
+        assert_eq!(small.commission, 1.0);
+        // Same order expressed as bps would cost a fraction of a cent per share
+        let bps = compute_fill(100.0, OrderSide::Buy, 5.0, &inst, &CostModel::new(0.0, 1.0));
+        assert!(bps.commission < small.commission);
+        let large = compute_fill(100.0, OrderSide::Buy, 1000.0, &inst, &cost);
+        assert!((large.commission - 5.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn regulatory_fees_added_on_sells_only() {
+        let cost = CostModel::frictionless()
+            .with_commission(CommissionModel::PerShare {
+                rate: 0.005,
+                min: 1.0,
+            })
+            .with_regulatory_fees(Some(RegulatoryFees::default()));
+        let inst = Instrument::us_equity("SPY");
+        let buy = compute_fill(100.0, OrderSide::Buy, 100.0, &inst, &cost);
+        let sell = compute_fill(100.0, OrderSide::Sell, 100.0, &inst, &cost);
+        assert_eq!(buy.commission, 1.0);
+        // $1.00 minimum + $0.28 SEC + $0.02 TAF
+        assert!((sell.commission - 1.30).abs() < 1e-10);
+    }
+
+    #[test]
+    fn micro_grid_rounds_price_and_costs() {
+        let fill = ComputedFill {
+            price: 100.05,
+            slippage: 0.012_345_67,
+            commission: 1.000_000_4,
+        }
+        .on_micro_grid();
+        assert_eq!(fill.price, 100.05);
+        assert_eq!(fill.slippage, 0.012_346);
+        assert_eq!(fill.commission, 1.0);
+
+        let cost = CostModel::new(3.0, 7.0);
+        let inst = Instrument::us_equity("SPY");
+        let raw = compute_fill(123.456_789, OrderSide::Buy, 33.0, &inst, &cost);
+        let settled = raw.clone().on_micro_grid();
+        assert_eq!(settled.commission, round_to_micros(raw.commission));
+        assert!((settled.commission - raw.commission).abs() <= 0.5e-6);
+    }
+
     #[test]
     fn buy_always_pays_more() {
         let cost = CostModel::new(10.0, 0.0);
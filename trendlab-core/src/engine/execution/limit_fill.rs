@@ -0,0 +1,177 @@
+//! Limit fill policy — how much a bar must trade through a limit to fill it.
+//!
+//! A bar whose low exactly equals a buy limit "touched" it, but the order was
+//! most likely behind others in the queue at that price and never filled.
+//! The policy decides whether touch-only bars fill limit orders (including
+//! the limit leg of stop-limits); bars that trade through the limit by at
+//! least one tick always fill.
+//!
+//! The policy is encoded into execution-model params (see
+//! [`LimitFillPolicy::from_params`]) so it enters `full_hash` fingerprints.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::instrument::OrderSide;
+use crate::domain::{Bar, OrderId};
+
+/// Param selecting the policy: 0 = fill on touch, 1 = require penetration,
+/// 2 = probabilistic on touch.
+pub const PARAM_LIMIT_FILL: &str = "limit_fill";
+/// Ticks a bar must trade through the limit under `RequirePenetration`.
+pub const PARAM_LIMIT_FILL_TICKS: &str = "limit_fill_ticks";
+/// Fill probability of a touch-only bar under `ProbabilisticOnTouch`.
+pub const PARAM_LIMIT_FILL_PROB: &str = "limit_fill_prob";
+
+/// Whether a bar that reaches a limit price fills the order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "policy", rename_all = "snake_case")]
+pub enum LimitFillPolicy {
+    /// Any bar that reaches the limit fills it.
+    #[default]
+    AlwaysFillOnTouch,
+    /// The bar must trade `ticks` beyond the limit.
+    RequirePenetration { ticks: u32 },
+    /// A touch-only bar fills with probability `p`, drawn from the order id
+    /// and bar index so reruns agree.
+    ProbabilisticOnTouch { p: f64 },
+}
+
+impl LimitFillPolicy {
+    /// Decode from execution-model params. Returns `None` when no
+    /// `limit_fill` key is present.
+    pub fn from_params(params: &BTreeMap<String, f64>) -> Option<Self> {
+        let policy = match params.get(PARAM_LIMIT_FILL)?.round() as u8 {
+            1 => Self::RequirePenetration {
+                ticks: params
+                    .get(PARAM_LIMIT_FILL_TICKS)
+                    .map_or(1, |t| t.round().max(0.0) as u32),
+            },
+            2 => Self::ProbabilisticOnTouch {
+                p: params
+                    .get(PARAM_LIMIT_FILL_PROB)
+                    .map_or(0.5, |p| p.clamp(0.0, 1.0)),
+            },
+            _ => Self::AlwaysFillOnTouch,
+        };
+        Some(policy)
+    }
+
+    /// Encode into execution-model params (inverse of [`Self::from_params`]).
+    pub fn write_params(&self, params: &mut BTreeMap<String, f64>) {
+        match self {
+            Self::AlwaysFillOnTouch => {
+                params.insert(PARAM_LIMIT_FILL.into(), 0.0);
+            }
+            Self::RequirePenetration { ticks } => {
+                params.insert(PARAM_LIMIT_FILL.into(), 1.0);
+                params.insert(PARAM_LIMIT_FILL_TICKS.into(), *ticks as f64);
+            }
+            Self::ProbabilisticOnTouch { p } => {
+                params.insert(PARAM_LIMIT_FILL.into(), 2.0);
+                params.insert(PARAM_LIMIT_FILL_PROB.into(), *p);
+            }
+        }
+    }
+
+    /// Whether `bar`, which reached the `limit` of an order on `side`, fills it.
+    ///
+    /// Penetration is how far the bar traded beyond the limit: `limit - low`
+    /// for buys, `high - limit` for sells.
+    pub fn fills(
+        &self,
+        side: OrderSide,
+        limit: f64,
+        bar: &Bar,
+        tick_size: f64,
+        order_id: OrderId,
+        bar_index: usize,
+    ) -> bool {
+        let penetration = match side {
+            OrderSide::Buy => limit - bar.low,
+            OrderSide::Sell => bar.high - limit,
+        };
+        // Half a tick of tolerance absorbs float noise in tick multiples
+        let reaches = |ticks: f64| penetration >= ticks * tick_size - tick_size * 0.5;
+        match *self {
+            Self::AlwaysFillOnTouch => true,
+            Self::RequirePenetration { ticks } => ticks == 0 || reaches(ticks as f64),
+            Self::ProbabilisticOnTouch { p } => reaches(1.0) || touch_draw(order_id, bar_index) < p,
+        }
+    }
+}
+
+/// Uniform draw in `[0, 1)` from the order id and bar index (SplitMix64).
+fn touch_draw(order_id: OrderId, bar_index: usize) -> f64 {
+    let mut z = order_id
+        .0
+        .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        .wrapping_add(bar_index as u64);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn bar(low: f64, high: f64) -> Bar {
+        Bar {
+            symbol: "SPY".into(),
+            date: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+            open: 100.0,
+            high,
+            low,
+            close: 100.0,
+            volume: 1_000_000,
+            adj_close: 100.0,
+        }
+    }
+
+    #[test]
+    fn penetration_counts_ticks_beyond_the_limit() {
+        let policy = LimitFillPolicy::RequirePenetration { ticks: 2 };
+        let fills = |b: &Bar, side| policy.fills(side, 98.0, b, 0.01, OrderId(1), 0);
+        assert!(!fills(&bar(98.0, 101.0), OrderSide::Buy));
+        assert!(!fills(&bar(97.99, 101.0), OrderSide::Buy));
+        assert!(fills(&bar(97.98, 101.0), OrderSide::Buy));
+        assert!(!fills(&bar(95.0, 98.01), OrderSide::Sell));
+        assert!(fills(&bar(95.0, 98.02), OrderSide::Sell));
+    }
+
+    #[test]
+    fn probabilistic_touch_is_deterministic_and_near_p() {
+        let policy = LimitFillPolicy::ProbabilisticOnTouch { p: 0.3 };
+        let touch = bar(98.0, 101.0);
+        let filled = (0..10_000)
+            .filter(|&i| policy.fills(OrderSide::Buy, 98.0, &touch, 0.01, OrderId(i), 7))
+            .count();
+        assert!((2_700..3_300).contains(&filled), "{filled}");
+        let once = policy.fills(OrderSide::Buy, 98.0, &touch, 0.01, OrderId(5), 7);
+        let again = policy.fills(OrderSide::Buy, 98.0, &touch, 0.01, OrderId(5), 7);
+        assert_eq!(once, again);
+        // Trading through the limit always fills
+        let through = bar(97.5, 101.0);
+        let never = LimitFillPolicy::ProbabilisticOnTouch { p: 0.0 };
+        assert!(never.fills(OrderSide::Buy, 98.0, &through, 0.01, OrderId(5), 7));
+        assert!(!never.fills(OrderSide::Buy, 98.0, &touch, 0.01, OrderId(5), 7));
+    }
+
+    #[test]
+    fn params_round_trip() {
+        for policy in [
+            LimitFillPolicy::AlwaysFillOnTouch,
+            LimitFillPolicy::RequirePenetration { ticks: 3 },
+            LimitFillPolicy::ProbabilisticOnTouch { p: 0.25 },
+        ] {
+            let mut params = BTreeMap::new();
+            policy.write_params(&mut params);
+            assert_eq!(LimitFillPolicy::from_params(&params), Some(policy));
+        }
+        assert_eq!(LimitFillPolicy::from_params(&BTreeMap::new()), None);
+    }
+}
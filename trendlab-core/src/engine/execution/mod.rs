@@ -9,13 +9,17 @@
 //! - `process_intrabar`: stop/limit triggers with path policy resolution
 //! - `process_end_of_bar`: MOC fills
 
+pub mod commission;
 pub mod cost_model;
 pub mod fill_price;
+pub mod limit_fill;
 pub mod liquidity;
 pub mod path_policy;
 pub mod trigger;
 
+pub use commission::{CommissionModel, CommissionTier, RegulatoryFees};
 pub use cost_model::CostModel;
+pub use limit_fill::LimitFillPolicy;
 pub use liquidity::{LiquidityPolicy, RemainderPolicy};
 
 use crate::components::execution::{ExecutionPreset, GapPolicy, PathPolicy};
@@ -24,10 +28,10 @@ use crate::domain::position::PositionSide;
 use crate::domain::{Bar, Fill, FillPhase, OrderId, OrderStatus, OrderType};
 use crate::engine::order_book::OrderBook;
 
-use self::fill_price::compute_fill;
-use self::trigger::{check_trigger, TriggerResult};
+use self::fill_price::{compute_fill, ComputedFill, PARAM_PRECISE_ACCOUNTING};
+use self::trigger::{check_trigger, trigger_margin, TriggerResult};
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Configuration for the execution engine.
 #[derive(Debug, Clone)]
@@ -36,6 +40,11 @@ pub struct ExecutionConfig {
     pub path_policy: PathPolicy,
     pub gap_policy: GapPolicy,
     pub liquidity: Option<LiquidityPolicy>,
+    /// Whether bars that only touch a limit price fill it.
+    pub limit_fill: LimitFillPolicy,
+    /// Round fill prices and costs to the micro-unit grid and settle them
+    /// in fixed point (see [`crate::domain::money`]).
+    pub precise_accounting: bool,
 }
 
 impl ExecutionConfig {
@@ -45,12 +54,32 @@ impl ExecutionConfig {
             path_policy: preset.path_policy(),
             gap_policy: preset.gap_policy(),
             liquidity: None,
+            limit_fill: LimitFillPolicy::default(),
+            precise_accounting: false,
         }
     }
 
     pub fn frictionless() -> Self {
         Self::from_preset(ExecutionPreset::Frictionless)
     }
+
+    /// Apply commission settings from execution-model params to the cost model.
+    pub fn with_commission_params(mut self, params: &BTreeMap<String, f64>) -> Self {
+        self.cost_model = self.cost_model.with_params(params);
+        self
+    }
+
+    /// Apply commission, limit-fill and accounting settings from
+    /// execution-model params.
+    pub fn with_params(mut self, params: &BTreeMap<String, f64>) -> Self {
+        if let Some(limit_fill) = LimitFillPolicy::from_params(params) {
+            self.limit_fill = limit_fill;
+        }
+        if let Some(&flag) = params.get(PARAM_PRECISE_ACCOUNTING) {
+            self.precise_accounting = flag > 0.5;
+        }
+        self.with_commission_params(params)
+    }
 }
 
 /// The execution engine: computes fill prices and manages order execution.
@@ -82,8 +111,9 @@ impl ExecutionEngine {
     ) -> Vec<Fill> {
         let mut fills = Vec::new();
 
-        // Collect active MOO and Immediate orders
-        let active: Vec<(OrderId, String)> = order_book
+        // Collect active MOO and Immediate orders, in submission order so an
+        // exit always fills before a reversal entry at the same open
+        let mut active: Vec<(OrderId, String)> = order_book
             .active_orders()
             .iter()
             .filter(|o| {
@@ -94,6 +124,7 @@ impl ExecutionEngine {
             })
             .map(|o| (o.id, o.symbol.clone()))
             .collect();
+        active.sort_by_key(|(id, _)| id.0);
 
         for (order_id, symbol) in active {
             let Some(bar) = bars.get(symbol.as_str()) else {
@@ -118,13 +149,13 @@ impl ExecutionEngine {
                 continue;
             }
 
-            let computed = compute_fill(
+            let computed = self.settle(compute_fill(
                 bar.open,
                 order.side,
                 qty,
                 &instrument,
                 &self.config.cost_model,
-            );
+            ));
 
             let fill = Fill {
                 order_id,
@@ -137,10 +168,12 @@ impl ExecutionEngine {
                 commission: computed.commission,
                 slippage: computed.slippage,
                 phase: FillPhase::StartOfBar,
+                trigger_margin: None,
+                decision_source: None,
             };
 
             // Record fill in order book (handles OCO, bracket activation)
-            let _ = order_book.record_fill(order_id, qty, bar_index);
+            self.record_fill(order_book, order_id, qty, bar_index);
             fills.push(fill);
         }
 
@@ -229,7 +262,28 @@ impl ExecutionEngine {
                     _ => continue, // cancelled by OCO
                 };
 
-                let result = check_trigger(order, bar, self.config.gap_policy);
+                let mut result = check_trigger(order, bar, self.config.gap_policy);
+                if let (TriggerResult::Fill { .. }, Some(limit)) =
+                    (&result, order.order_type.limit_price())
+                {
+                    let fills = self.config.limit_fill.fills(
+                        order.side,
+                        limit,
+                        bar,
+                        instrument.tick_size,
+                        order_id,
+                        bar_index,
+                    );
+                    if !fills {
+                        // A stop-limit that only touched its limit still triggers
+                        result = match (&order.order_type, &order.status) {
+                            (OrderType::StopLimit { .. }, OrderStatus::Pending) => {
+                                TriggerResult::StopTriggeredLimitPending
+                            }
+                            _ => TriggerResult::NoTrigger,
+                        };
+                    }
+                }
                 match result {
                     TriggerResult::Fill { fill_price, .. } => {
                         let qty = self.effective_fill_qty(order.remaining_quantity(), bar.volume);
@@ -237,13 +291,13 @@ impl ExecutionEngine {
                             continue;
                         }
 
-                        let computed = compute_fill(
+                        let computed = self.settle(compute_fill(
                             fill_price,
                             order.side,
                             qty,
                             &instrument,
                             &self.config.cost_model,
-                        );
+                        ));
 
                         let fill = Fill {
                             order_id,
@@ -256,6 +310,8 @@ impl ExecutionEngine {
                             commission: computed.commission,
                             slippage: computed.slippage,
                             phase: FillPhase::Intrabar,
+                            trigger_margin: trigger_margin(order, bar),
+                            decision_source: None,
                         };
 
                         // For stops: trigger first, then fill
@@ -265,7 +321,7 @@ impl ExecutionEngine {
                             let _ = order_book.trigger(order_id, bar_index);
                         }
 
-                        let _ = order_book.record_fill(order_id, qty, bar_index);
+                        self.record_fill(order_book, order_id, qty, bar_index);
                         fills.push(fill);
                     }
                     TriggerResult::StopTriggeredLimitPending => {
@@ -323,13 +379,13 @@ impl ExecutionEngine {
                 continue;
             }
 
-            let computed = compute_fill(
+            let computed = self.settle(compute_fill(
                 bar.close,
                 order.side,
                 qty,
                 &instrument,
                 &self.config.cost_model,
-            );
+            ));
 
             let fill = Fill {
                 order_id,
@@ -342,15 +398,50 @@ impl ExecutionEngine {
                 commission: computed.commission,
                 slippage: computed.slippage,
                 phase: FillPhase::EndOfBar,
+                trigger_margin: None,
+                decision_source: None,
             };
 
-            let _ = order_book.record_fill(order_id, qty, bar_index);
+            self.record_fill(order_book, order_id, qty, bar_index);
             fills.push(fill);
         }
 
         fills
     }
 
+    /// A computed fill as it settles: on the micro-unit grid under precise
+    /// accounting, unchanged otherwise.
+    fn settle(&self, computed: ComputedFill) -> ComputedFill {
+        if self.config.precise_accounting {
+            computed.on_micro_grid()
+        } else {
+            computed
+        }
+    }
+
+    /// Record a fill in the order book. Under `RemainderPolicy::Cancel` the
+    /// unfilled remainder of a partial fill is cancelled; otherwise the
+    /// order stays working for the next bar.
+    fn record_fill(
+        &self,
+        order_book: &mut OrderBook,
+        order_id: OrderId,
+        qty: f64,
+        bar_index: usize,
+    ) {
+        let fully_filled = order_book
+            .record_fill(order_id, qty, bar_index)
+            .unwrap_or(true);
+        let cancel_remainder = self
+            .config
+            .liquidity
+            .as_ref()
+            .is_some_and(|p| p.remainder == RemainderPolicy::Cancel);
+        if !fully_filled && cancel_remainder {
+            let _ = order_book.cancel(order_id, bar_index, "liquidity remainder cancelled");
+        }
+    }
+
     /// Apply liquidity constraint to desired quantity. Returns effective fill qty.
     fn effective_fill_qty(&self, desired_qty: f64, bar_volume: u64) -> f64 {
         match &self.config.liquidity {
@@ -466,6 +557,33 @@ mod tests {
         assert!(book.get(OrderId(1)).unwrap().is_active()); // still pending
     }
 
+    #[test]
+    fn liquidity_cap_carries_or_cancels_remainder() {
+        let thin = |remainder| {
+            ExecutionEngine::new(ExecutionConfig {
+                liquidity: Some(LiquidityPolicy::new(0.00004, remainder)),
+                ..ExecutionConfig::frictionless()
+            })
+        };
+        let b = bar(100.0, 105.0, 98.0, 103.0);
+        let mut bars = HashMap::new();
+        bars.insert("SPY", &b);
+
+        for (remainder, still_active) in [
+            (RemainderPolicy::Carry, true),
+            (RemainderPolicy::Cancel, false),
+        ] {
+            let mut book = OrderBook::new();
+            book.submit(make_order(1, OrderSide::Buy, OrderType::MarketOnOpen));
+            let fills =
+                thin(remainder).process_start_of_bar(&mut book, &bars, &default_instruments(), 0);
+            assert!((fills[0].quantity - 40.0).abs() < 1e-9);
+            let order = book.get(OrderId(1)).unwrap();
+            assert!(order.filled_quantity < order.quantity);
+            assert_eq!(order.is_active(), still_active, "{remainder:?}");
+        }
+    }
+
     // ── Intrabar tests ──────────────────────────────────────────────
 
     #[test]
@@ -499,6 +617,8 @@ mod tests {
             path_policy: PathPolicy::WorstCase,
             gap_policy: GapPolicy::FillAtOpen,
             liquidity: None,
+            limit_fill: LimitFillPolicy::default(),
+            precise_accounting: false,
         };
         let engine = ExecutionEngine::new(config);
         let mut book = OrderBook::new();
@@ -543,6 +663,77 @@ mod tests {
         assert_eq!(fills[0].price, 98.0);
     }
 
+    #[test]
+    fn limit_touched_at_the_low_needs_penetration_to_fill() {
+        let b = bar(100.0, 105.0, 98.0, 103.0);
+        let mut bars = HashMap::new();
+        bars.insert("SPY", &b);
+        let positions = HashMap::new();
+
+        for (limit_fill, filled) in [
+            (LimitFillPolicy::AlwaysFillOnTouch, true),
+            (LimitFillPolicy::RequirePenetration { ticks: 1 }, false),
+        ] {
+            let engine = ExecutionEngine::new(ExecutionConfig {
+                limit_fill,
+                ..ExecutionConfig::frictionless()
+            });
+            let mut book = OrderBook::new();
+            book.submit(make_order(
+                1,
+                OrderSide::Buy,
+                OrderType::Limit { limit_price: 98.0 },
+            ));
+            let fills =
+                engine.process_intrabar(&mut book, &bars, &default_instruments(), 0, &positions);
+            assert_eq!(fills.len(), usize::from(filled), "{limit_fill:?}");
+            assert_eq!(book.get(OrderId(1)).unwrap().is_active(), !filled);
+        }
+    }
+
+    #[test]
+    fn stop_limit_touching_its_limit_still_triggers() {
+        let engine = ExecutionEngine::new(ExecutionConfig {
+            limit_fill: LimitFillPolicy::RequirePenetration { ticks: 1 },
+            ..ExecutionConfig::frictionless()
+        });
+        let mut book = OrderBook::new();
+        book.submit(make_order(
+            1,
+            OrderSide::Buy,
+            OrderType::StopLimit {
+                trigger_price: 104.0,
+                limit_price: 105.0,
+            },
+        ));
+
+        // High reaches the stop and the low trades well through the limit
+        let b = bar(100.0, 106.0, 99.0, 103.0);
+        let mut bars = HashMap::new();
+        bars.insert("SPY", &b);
+        let fills =
+            engine.process_intrabar(&mut book, &bars, &default_instruments(), 0, &HashMap::new());
+        assert_eq!(fills.len(), 1);
+
+        // Bar whose low sits exactly on the limit: triggered, not filled
+        let mut book = OrderBook::new();
+        book.submit(make_order(
+            1,
+            OrderSide::Buy,
+            OrderType::StopLimit {
+                trigger_price: 104.0,
+                limit_price: 105.0,
+            },
+        ));
+        let b = bar(105.5, 106.0, 105.0, 105.5);
+        let mut bars = HashMap::new();
+        bars.insert("SPY", &b);
+        let fills =
+            engine.process_intrabar(&mut book, &bars, &default_instruments(), 0, &HashMap::new());
+        assert!(fills.is_empty());
+        assert_eq!(book.get(OrderId(1)).unwrap().status, OrderStatus::Triggered);
+    }
+
     #[test]
     fn intrabar_worst_case_stop_before_tp() {
         let engine = ExecutionEngine::new(ExecutionConfig {
@@ -550,6 +741,8 @@ mod tests {
             path_policy: PathPolicy::WorstCase,
             gap_policy: GapPolicy::FillAtOpen,
             liquidity: None,
+            limit_fill: LimitFillPolicy::default(),
+            precise_accounting: false,
         });
         let mut book = OrderBook::new();
 
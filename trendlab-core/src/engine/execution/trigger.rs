@@ -178,6 +178,38 @@ fn check_stop_limit(
     }
 }
 
+/// How far `bar` traded past the price that fills `order`, as a fraction of
+/// that price.
+///
+/// Buy stop: `(high - trigger) / trigger`. Sell stop: `(trigger - low) / trigger`.
+/// Limits mirror this against the limit price, and stop-limits are measured on
+/// the limit leg that actually fills. A margin near zero marks a borderline
+/// fill that a slightly different tape would not have produced. `None` for
+/// market orders and void bars.
+pub fn trigger_margin(order: &Order, bar: &Bar) -> Option<f64> {
+    if bar.is_void() {
+        return None;
+    }
+    let (level, penetration) = match (&order.order_type, order.side) {
+        (OrderType::StopMarket { trigger_price }, OrderSide::Buy) => {
+            (*trigger_price, bar.high - trigger_price)
+        }
+        (OrderType::StopMarket { trigger_price }, OrderSide::Sell) => {
+            (*trigger_price, trigger_price - bar.low)
+        }
+        (
+            OrderType::Limit { limit_price } | OrderType::StopLimit { limit_price, .. },
+            OrderSide::Buy,
+        ) => (*limit_price, limit_price - bar.low),
+        (
+            OrderType::Limit { limit_price } | OrderType::StopLimit { limit_price, .. },
+            OrderSide::Sell,
+        ) => (*limit_price, bar.high - limit_price),
+        _ => return None,
+    };
+    (level > 0.0).then(|| penetration.max(0.0) / level)
+}
+
 /// Resolve gap-through fill price for a sell stop.
 ///
 /// When price gaps down through a sell stop (open < trigger):
@@ -624,6 +656,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn trigger_margin_measures_penetration() {
+        let b = bar(100.0, 106.0, 98.0, 103.0);
+        let buy_stop = make_order(
+            OrderSide::Buy,
+            OrderType::StopMarket {
+                trigger_price: 105.0,
+            },
+        );
+        let margin = trigger_margin(&buy_stop, &b).unwrap();
+        assert!((margin - 1.0 / 105.0).abs() < 1e-12);
+
+        let sell_stop = make_order(
+            OrderSide::Sell,
+            OrderType::StopMarket {
+                trigger_price: 98.0,
+            },
+        );
+        assert_eq!(trigger_margin(&sell_stop, &b), Some(0.0));
+
+        let sell_limit = make_order(OrderSide::Sell, OrderType::Limit { limit_price: 104.0 });
+        let margin = trigger_margin(&sell_limit, &b).unwrap();
+        assert!((margin - 2.0 / 104.0).abs() < 1e-12);
+
+        let market = make_order(OrderSide::Buy, OrderType::MarketOnOpen);
+        assert_eq!(trigger_margin(&market, &b), None);
+        assert_eq!(trigger_margin(&buy_stop, &void_bar()), None);
+    }
+
     #[test]
     fn buy_limit_triggers_at_exact_level() {
         let order = make_order(OrderSide::Buy, OrderType::Limit { limit_price: 98.0 });
@@ -5,34 +5,50 @@
 //! 2. Intrabar: simulate trigger checks for stop/limit orders
 //! 3. End-of-bar: fill MOC orders
 //! 4. Post-bar: mark-to-market, equity accounting, PM maintenance orders
+//!    (PM intents pass the ratchet, then the filter's intent review)
+//!
+//! Every order is registered with the decision that placed it, and its fills
+//! carry that [`DecisionSource`] for PnL attribution.
 
-use crate::components::execution::ExecutionModel;
-use crate::components::filter::SignalFilter;
+use crate::components::execution::{ExecutionModel, FlipPolicy};
+use crate::components::filter::{IntentIntervention, SignalFilter};
 use crate::components::indicator::Indicator;
 use crate::components::pm::{IntentAction, OrderIntent, PositionManager};
-use crate::components::signal::{SignalDirection, SignalGenerator};
+use crate::components::signal::{
+    FilterVerdict, SignalDirection, SignalEvaluation, SignalGenerator,
+};
 use crate::data::align::AlignedData;
-use crate::domain::{Bar, Fill, MarketStatus, Order, OrderStatus, OrderType, PositionSide};
+use crate::data::frame::BarFrame;
+use crate::domain::{
+    Bar, DecisionSource, Fill, MarketStatus, Order, OrderId, OrderStatus, OrderType, PositionSide,
+};
 use crate::engine::execution::ExecutionEngine;
 use crate::engine::portfolio_update::apply_fills;
-use crate::engine::stickiness::compute_stickiness;
+use crate::engine::stickiness::compute_stickiness_report;
 
-use super::convert::aligned_to_bars;
-use super::precompute::{compute_warmup, precompute_indicators};
-use super::state::{EngineConfig, EngineState, RunResult};
-use super::trade_extraction::extract_trades;
+use super::attribution::attribute_pnl;
+use super::entry_governance::{EntryGovernor, GOVERNANCE_FILTER_NAME};
+use super::precompute::{precompute_frame_indicators, IndicatorCache};
+use super::portfolio_snapshot::{PortfolioSnapshot, SnapshotRecorder};
+use super::replay::ReplayRecorder;
+use super::state::{EngineConfig, EngineState, RatchetClamp, RunResult};
+use super::timings::{EngineTimings, PhaseClock};
+use super::trade_extraction::{extract_trades, EndOfData};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Data quality threshold: warn if void bar rate exceeds this fraction.
 const VOID_BAR_RATE_THRESHOLD: f64 = 0.10;
 
+/// Filter name recorded on evaluations whose entry sized to zero.
+pub const SIZING_FILTER_NAME: &str = "sizing";
+
 /// Run a backtest on aligned data.
 ///
 /// This is the main entry point for the engine. It:
-/// 1. Converts `AlignedData` to per-symbol `Vec<Bar>`
+/// 1. Converts `AlignedData` to per-symbol columnar `BarFrame`s
 /// 2. Precomputes all indicators per symbol
-/// 3. Computes warmup length from indicator lookbacks
+/// 3. Computes warmup length from indicator lookbacks and the warmup policy
 /// 4. Runs the four-phase bar loop
 /// 5. Returns `RunResult`
 pub fn run_backtest(
@@ -44,32 +60,105 @@ pub fn run_backtest(
     execution_model: &dyn ExecutionModel,
     position_manager: &dyn PositionManager,
 ) -> RunResult {
-    // Step 1: Convert RawBar → Bar
-    let bars_by_symbol = aligned_to_bars(aligned);
+    run_backtest_inner(
+        aligned,
+        indicators,
+        config,
+        signal_generator,
+        signal_filter,
+        execution_model,
+        position_manager,
+        None,
+    )
+}
+
+/// Run a backtest, serving indicator series from a shared cache.
+///
+/// Identical to [`run_backtest`] except that indicators already present in
+/// `cache` are not recomputed. The cache must belong to this exact `aligned`
+/// dataset.
+#[allow(clippy::too_many_arguments)]
+pub fn run_backtest_cached(
+    aligned: &AlignedData,
+    indicators: &[Box<dyn Indicator>],
+    config: &EngineConfig,
+    signal_generator: &dyn SignalGenerator,
+    signal_filter: &dyn SignalFilter,
+    execution_model: &dyn ExecutionModel,
+    position_manager: &dyn PositionManager,
+    cache: &mut IndicatorCache,
+) -> RunResult {
+    run_backtest_inner(
+        aligned,
+        indicators,
+        config,
+        signal_generator,
+        signal_filter,
+        execution_model,
+        position_manager,
+        Some(cache),
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_backtest_inner(
+    aligned: &AlignedData,
+    indicators: &[Box<dyn Indicator>],
+    config: &EngineConfig,
+    signal_generator: &dyn SignalGenerator,
+    signal_filter: &dyn SignalFilter,
+    execution_model: &dyn ExecutionModel,
+    position_manager: &dyn PositionManager,
+    cache: Option<&mut IndicatorCache>,
+) -> RunResult {
+    let mut timings = EngineTimings {
+        runs: 1,
+        ..Default::default()
+    };
+    let mut clock = PhaseClock::new(config.record_timings);
+
+    // Step 1: Convert RawBar rows → columnar frames
+    let frames = BarFrame::from_aligned(aligned);
     let symbols: Vec<&str> = aligned.symbols.iter().map(|s| s.as_str()).collect();
     let num_bars = aligned.dates.len();
 
     // Step 2: Precompute indicators
-    let indicator_values = precompute_indicators(&bars_by_symbol, indicators);
+    let indicator_values = precompute_frame_indicators(&frames, indicators, cache);
+    clock.lap(&mut timings.precompute);
 
     // Step 3: Compute warmup
-    let indicator_warmup = compute_warmup(indicators);
-    let warmup_bars = config.warmup_bars.max(indicator_warmup);
+    let warmup = config.warmup.report(indicators, config.warmup_bars, num_bars);
+    let warmup_bars = warmup.applied;
 
     // Step 4: Initialize engine state and execution engine
     let mut state = EngineState::new(config.initial_capital);
+    state.portfolio.precise_accounting = config.execution_config.precise_accounting;
     let execution_engine = ExecutionEngine::new(config.execution_config.clone());
     let mut equity_curve = Vec::with_capacity(num_bars);
+    let mut exposure_curve = Vec::with_capacity(num_bars);
     let mut all_fills: Vec<Fill> = Vec::new();
+    let mut governor = EntryGovernor::new(config.entry_governance);
+    let mut peak_equity = config.initial_capital;
+    let mut halted = false;
+    let flip_policy = execution_model.flip_policy();
+    let mut replay = config.record_replay.then(ReplayRecorder::default);
+    let mut snapshots = SnapshotRecorder::new(config.portfolio_snapshots);
 
     // Step 5: Run the bar loop
     for t in 0..num_bars {
         state.bar_index = t;
+        let fills_before = all_fills.len();
+
+        // Per-bar views of every symbol's frame
+        let bars_at_t: HashMap<&str, Bar> = symbols
+            .iter()
+            .map(|&symbol| (symbol, frames[symbol].bar(t)))
+            .collect();
 
         // Determine market status per symbol for this bar
         let mut market_status: HashMap<&str, MarketStatus> = HashMap::new();
         for &symbol in &symbols {
-            let bar = &bars_by_symbol[symbol][t];
+            let bar = &bars_at_t[symbol];
             let status = if bar.is_void() {
                 MarketStatus::Closed
             } else {
@@ -81,12 +170,19 @@ pub fn run_backtest(
         // Build per-bar map for execution engine: HashMap<&str, &Bar>
         let mut bar_map: HashMap<&str, &Bar> = HashMap::new();
         for &symbol in &symbols {
-            let bar = &bars_by_symbol[symbol][t];
+            let bar = &bars_at_t[symbol];
             if market_status[symbol] == MarketStatus::Open {
                 bar_map.insert(symbol, bar);
             }
         }
 
+        // Snapshot open sides so exits during this bar can be detected
+        let sides_at_open = if governor.is_active() {
+            state.position_sides()
+        } else {
+            HashMap::new()
+        };
+
         // ─── Phase 1: Start-of-bar ───
         // Activate day orders, fill MOO and MarketImmediate orders.
         let start_fills = execution_engine.process_start_of_bar(
@@ -96,6 +192,7 @@ pub fn run_backtest(
             t,
         );
         apply_fills(&start_fills, &mut state.portfolio);
+        clock.lap(&mut timings.start_of_bar);
 
         // ─── Phase 2: Intrabar ───
         // Check stop/limit triggers against bar's high/low range.
@@ -108,6 +205,7 @@ pub fn run_backtest(
             &position_sides,
         );
         apply_fills(&intrabar_fills, &mut state.portfolio);
+        clock.lap(&mut timings.intrabar);
 
         // ─── Phase 3: End-of-bar ───
         // Fill MOC orders at bar's close.
@@ -118,16 +216,30 @@ pub fn run_backtest(
             t,
         );
         apply_fills(&eob_fills, &mut state.portfolio);
+        clock.lap(&mut timings.end_of_bar);
 
-        // Collect all fills from this bar
-        all_fills.extend(start_fills);
-        all_fills.extend(intrabar_fills);
-        all_fills.extend(eob_fills);
+        // Record exits (including reversals) for entry governance
+        for (symbol, &side) in &sides_at_open {
+            let side_now = state.portfolio.get_position(symbol).map(|p| p.side);
+            if side_now != Some(side) {
+                governor.record_exit(symbol, t, side);
+            }
+        }
+
+        // Collect all fills from this bar, tagged with their order's decision
+        for mut fill in start_fills
+            .into_iter()
+            .chain(intrabar_fills)
+            .chain(eob_fills)
+        {
+            fill.decision_source = state.order_sources.get(&fill.order_id).copied();
+            all_fills.push(fill);
+        }
 
         // ─── Phase 4: Post-bar ───
         // Mark-to-market, update position statistics, equity accounting.
         for &symbol in &symbols {
-            let bar = &bars_by_symbol[symbol][t];
+            let bar = &bars_at_t[symbol];
             let status = market_status[symbol];
 
             // Track bar counts for data quality
@@ -141,7 +253,7 @@ pub fn run_backtest(
 
             // Update positions
             if let Some(pos) = state.portfolio.get_position_mut(symbol) {
-                pos.tick_bar(); // Always increment, even on void bars
+                pos.tick_bar(status); // Every bar; void bars skip the open-bar count
 
                 match status {
                     MarketStatus::Open => {
@@ -152,7 +264,7 @@ pub fn run_backtest(
                     MarketStatus::Closed => {
                         // Void bar: equity carries forward at last valid close.
                         // No mark-to-market update, no PnL change.
-                        // PM time counters already incremented via tick_bar().
+                        // PM time counters already advanced via tick_bar().
                     }
                 }
             }
@@ -164,12 +276,36 @@ pub fn run_backtest(
         }
 
         // Equity accounting: build current prices for equity calculation
-        let prices = build_current_prices(&bars_by_symbol, &state.last_valid_close, &symbols, t);
+        let prices = build_current_prices(&frames, &state.last_valid_close, &symbols, t);
         let equity = state.verify_equity(&prices);
         equity_curve.push(equity);
+        let gross = state.portfolio.gross_exposure(&prices);
+        exposure_curve.push(match (gross > 0.0, equity > 0.0) {
+            (false, _) => 0.0,
+            (true, true) => gross / equity,
+            // A wiped-out account still holding positions counts as fully invested
+            (true, false) => 1.0,
+        });
+        if let Some(recorder) = snapshots.as_mut() {
+            if recorder.wants(t, num_bars, all_fills.len() > fills_before) {
+                recorder.push(PortfolioSnapshot::capture(
+                    t,
+                    aligned.dates[t],
+                    &state.portfolio,
+                    &prices,
+                    equity,
+                ));
+            }
+        }
+        peak_equity = peak_equity.max(equity);
+        if !halted && config.risk_guard.drawdown_breached(peak_equity, equity) {
+            halted = true;
+            cancel_pending_entries(&mut state, t);
+        }
 
         // Warmup check: skip signal evaluation and PM during warmup
         if t < warmup_bars {
+            clock.lap(&mut timings.post_bar);
             continue;
         }
 
@@ -177,18 +313,51 @@ pub fn run_backtest(
             state.warmup_complete = true;
         }
 
+        // ─── Risk guard ───
+        // Past the drawdown limit: flatten at the next open, enter nothing
+        if halted {
+            for &symbol in &symbols {
+                if market_status[symbol] == MarketStatus::Closed {
+                    continue;
+                }
+                let held = state
+                    .portfolio
+                    .get_position(symbol)
+                    .filter(|p| !p.is_flat())
+                    .map(|p| (p.side, p.quantity));
+                if let Some((side, quantity)) = held {
+                    apply_pm_intent(
+                        &OrderIntent::force_exit(),
+                        DecisionSource::RiskGuardFlatten,
+                        symbol,
+                        side,
+                        quantity,
+                        &mut state,
+                        t,
+                    );
+                }
+            }
+        }
+
         // ─── Signal evaluation ───
+        // Symbols whose position is being closed by a signal flip this bar
+        let mut flipping: HashSet<&str> = HashSet::new();
         for &symbol in &symbols {
-            if market_status[symbol] == MarketStatus::Closed {
+            if halted || market_status[symbol] == MarketStatus::Closed {
                 continue;
             }
 
-            // Skip if already in a position for this symbol
-            if state.portfolio.has_position(symbol) {
+            // Skip if already in a position for this symbol, unless the
+            // execution model exits on opposite signals
+            let held = state
+                .portfolio
+                .get_position(symbol)
+                .map(|p| (p.side, p.quantity));
+            if held.is_some() && flip_policy == FlipPolicy::Ignore {
                 continue;
             }
 
-            let bars = &bars_by_symbol[symbol];
+            let bars = &frames[symbol];
             let indicators_for_symbol = indicator_values
                 .get(symbol)
                 .expect("indicator values must exist for all symbols");
@@ -196,13 +365,47 @@ pub fn run_backtest(
             // 1. Evaluate signal
             let mut signal = match signal_generator.evaluate(bars, t, indicators_for_symbol) {
                 Some(s) => s,
-                None => continue,
+                None => {
+                    if held.is_none() {
+                        governor.record_quiet_bar(symbol);
+                    }
+                    continue;
+                }
             };
 
+            // Only an opposite-direction signal acts on an open position
+            if let Some((side, _)) = held {
+                let opposite = match side {
+                    PositionSide::Long => SignalDirection::Short,
+                    PositionSide::Short => SignalDirection::Long,
+                    PositionSide::Flat => continue,
+                };
+                if signal.direction != opposite {
+                    continue;
+                }
+            }
+
             // Assign real signal ID
             signal.id = state.id_gen.next_signal_event_id();
             state.signal_count += 1;
 
+            // Flip exit: close at next open, regardless of trading mode or filters
+            if let Some((side, quantity)) = held {
+                apply_pm_intent(
+                    &OrderIntent::force_exit(),
+                    DecisionSource::SignalFlipExit,
+                    symbol,
+                    side,
+                    quantity,
+                    &mut state,
+                    t,
+                );
+                flipping.insert(symbol);
+                if flip_policy != FlipPolicy::StopAndReverse {
+                    continue;
+                }
+            }
+
             // 2. Trading mode filter
             match config.trading_mode {
                 crate::fingerprint::TradingMode::LongOnly => {
@@ -218,7 +421,18 @@ pub fn run_backtest(
                 crate::fingerprint::TradingMode::LongShort => {}
             }
 
-            // 3. Apply signal filter
+            // 3. Entry governance: cooldown, entry cap, signal reset
+            if let Some(filter_state) = governor.check(symbol, signal.direction, t) {
+                state.signal_evaluations.push(SignalEvaluation {
+                    signal_event_id: signal.id,
+                    filter_name: GOVERNANCE_FILTER_NAME.to_string(),
+                    verdict: FilterVerdict::Cooldown,
+                    filter_state,
+                });
+                continue;
+            }
+
+            // 4. Apply signal filter
             let evaluation = signal_filter.evaluate(&signal, bars, t, indicators_for_symbol);
             let passed = evaluation.verdict.is_passed();
             state.signal_evaluations.push(evaluation);
@@ -227,31 +441,59 @@ pub fn run_backtest(
                 continue;
             }
 
-            // 4. Determine entry order type from execution model
+            // 5. Determine entry order type from execution model
             let instrument = config
                 .instruments
                 .get(symbol)
                 .cloned()
                 .unwrap_or_else(|| crate::domain::Instrument::us_equity(symbol));
-            let bar = &bars[t];
-            let order_type = execution_model.entry_order_type(&signal, bar, &instrument);
-
-            // 5. Calculate quantity
-            let equity = state.portfolio.cash; // simplified: use cash as sizing base
-            let position_value = equity * config.position_size_pct;
-            let quantity = if bar.close > 0.0 {
-                (position_value / bar.close).floor().max(1.0)
+            let bar = &bars_at_t[symbol];
+            // A reversal enters at the same open the flip exit fills at
+            let order_type = if held.is_some() {
+                OrderType::MarketOnOpen
             } else {
-                continue;
+                execution_model.entry_order_type(&signal, bar, &instrument)
             };
 
-            // 6. Determine order side
+            // 6. Calculate quantity
+            let mut equity = state.portfolio.cash; // simplified: use cash as sizing base
+            if let Some((side, qty)) = held {
+                // Reversal: size off the cash left once the position is closed
+                equity += match side {
+                    PositionSide::Short => -qty * bar.close,
+                    _ => qty * bar.close,
+                };
+            }
+            if bar.close <= 0.0 {
+                continue;
+            }
+            let size_pct = config.risk_guard.position_size(config.position_size_pct);
+            let desired = equity * size_pct / bar.close;
+            let quantity = instrument.quantize(desired);
+            if quantity <= 0.0 {
+                state.signal_evaluations.push(SignalEvaluation {
+                    signal_event_id: signal.id,
+                    filter_name: SIZING_FILTER_NAME.to_string(),
+                    verdict: FilterVerdict::BelowMinimumSize,
+                    filter_state: HashMap::from([
+                        ("desired_quantity".to_string(), desired),
+                        (
+                            "increment".to_string(),
+                            instrument.quantization.increment().unwrap_or(0.0),
+                        ),
+                        ("price".to_string(), bar.close),
+                    ]),
+                });
+                continue;
+            }
+
+            // 7. Determine order side
             let order_side = match signal.direction {
                 SignalDirection::Long => crate::domain::OrderSide::Buy,
                 SignalDirection::Short => crate::domain::OrderSide::Sell,
             };
 
-            // 7. Create and submit entry order
+            // 8. Create and submit entry order
             let order_id = state.id_gen.next_order_id();
             let order = Order {
                 id: order_id,
@@ -267,6 +509,10 @@ pub fn run_backtest(
                 activated_bar: None,
             };
             state.order_book.submit(order);
+            state
+                .order_sources
+                .insert(order_id, DecisionSource::SignalEntry);
+            governor.record_entry(symbol, t);
 
             // Track the entry signal for this symbol
             state.entry_signals.insert(symbol.to_string(), signal);
@@ -276,11 +522,20 @@ pub fn run_backtest(
         // For each symbol with an open position and Open status:
         //   1. Call position_manager.on_bar(position, bar, status, indicators)
         //   2. Enforce ratchet invariant
-        //   3. Translate OrderIntent into cancel/replace on order book
+        //   3. Let the filter review the intent (re-ratcheted, recorded)
+        //   4. Translate OrderIntent into cancel/replace on order book
+        // On void bars only a time limit is checked (see schedule_time_expiry).
         for &symbol in &symbols {
+            if halted {
+                continue; // flatten queued
+            }
             if market_status[symbol] == MarketStatus::Closed {
+                schedule_time_expiry(position_manager, symbol, &mut state, t);
                 continue; // void bar: no PM evaluation
             }
+            if flipping.contains(symbol) {
+                continue; // flip exit already queued
+            }
 
             // Check if there's an open position. We need to clone the relevant
             // data to avoid borrow conflicts with state.
@@ -296,7 +551,8 @@ pub fn run_backtest(
                 None => continue,
             };
 
-            let bar = &bars_by_symbol[symbol][t];
+            let bars = &frames[symbol];
+            let bar = &bars_at_t[symbol];
             let indicators_for_symbol = indicator_values
                 .get(symbol)
                 .expect("indicator values must exist for all symbols");
@@ -310,25 +566,103 @@ pub fn run_backtest(
             );
 
             // Track PM calls for stickiness diagnostics
-            state.pm_calls_total += 1;
-            if raw_intent.action != IntentAction::Hold {
-                state.pm_calls_active += 1;
-            }
+            state
+                .pm_calls
+                .entry((symbol.to_string(), pos_snapshot.entry_bar))
+                .or_default()
+                .record(raw_intent.action != IntentAction::Hold);
 
             // Enforce ratchet invariant
-            let intent = enforce_ratchet(&raw_intent, &pos_snapshot);
+            let mut intent = enforce_ratchet(&raw_intent, &pos_snapshot);
+            if let (IntentAction::AdjustStop, Some(requested), Some(applied)) =
+                (raw_intent.action, raw_intent.stop_price, intent.stop_price)
+            {
+                record_ratchet_clamp(&mut state, symbol, t, side, requested, applied);
+            }
+
+            // Filter review: an override is ratcheted too, so it cannot loosen
+            if let Some(review) = signal_filter.review_intent(
+                &intent,
+                pos_snapshot.current_stop,
+                bars,
+                t,
+                indicators_for_symbol,
+            ) {
+                let applied = enforce_ratchet(&review.intent, &pos_snapshot);
+                if applied != intent {
+                    state.intent_interventions.push(IntentIntervention {
+                        symbol: symbol.to_string(),
+                        bar_index: t,
+                        filter_name: signal_filter.name().to_string(),
+                        original: intent,
+                        applied: applied.clone(),
+                        filter_state: review.filter_state,
+                    });
+                    intent = applied;
+                }
+            }
+
+            if intent.action == IntentAction::ForceExit
+                && position_manager.time_expired(&pos_snapshot)
+            {
+                state
+                    .time_expiries
+                    .entry((symbol.to_string(), pos_snapshot.entry_bar))
+                    .or_insert(t);
+            }
 
             // Translate intent into order book operations
-            apply_pm_intent(&intent, symbol, side, pos_snapshot.quantity, &mut state, t);
+            apply_pm_intent(
+                &intent,
+                DecisionSource::PmForceExit,
+                symbol,
+                side,
+                pos_snapshot.quantity,
+                &mut state,
+                t,
+            );
         }
+
+        if let Some(recorder) = replay.as_mut() {
+            for &symbol in &symbols {
+                recorder.record(
+                    &bars_at_t[symbol],
+                    t,
+                    state.portfolio.get_position(symbol),
+                    &state.order_book.active_orders_for_symbol(symbol),
+                    equity,
+                    &indicator_values[symbol],
+                );
+            }
+        }
+        clock.lap(&mut timings.post_bar);
     }
 
+    // Marks used for the last equity point; positions still open are
+    // closed and attributed at them
+    let final_marks = num_bars.checked_sub(1).map(|last| {
+        let marks = build_current_prices(&frames, &state.last_valid_close, &symbols, last);
+        (last, marks)
+    });
+
     // Extract round-trip trades from fills
-    let all_trades = extract_trades(&all_fills, &bars_by_symbol, &state.entry_signals);
+    let end_of_data = final_marks.as_ref().map(|(last, marks)| EndOfData {
+        bar_index: *last,
+        date: aligned.dates[*last],
+        marks,
+    });
+    let mut all_trades = extract_trades(&all_fills, &frames, &state.entry_signals, end_of_data);
+    for trade in &mut all_trades {
+        let key = (trade.symbol.clone(), trade.entry_bar);
+        trade.initial_stop_price = state.initial_stops.get(&key).copied();
+        trade.expiry_bar = state.time_expiries.get(&key).copied();
+    }
+    clock.lap(&mut timings.trade_extraction);
 
     // Build result
     let void_bar_rates = state.void_bar_rates();
     let mut data_quality_warnings = Vec::new();
+    // In symbol order, like every per-symbol output of the run
     for (symbol, &rate) in &void_bar_rates {
         if rate > VOID_BAR_RATE_THRESHOLD {
             data_quality_warnings.push(format!(
@@ -340,20 +674,52 @@ pub fn run_backtest(
     }
 
     let final_equity = *equity_curve.last().unwrap_or(&config.initial_capital);
-    let stickiness = compute_stickiness(&all_trades, state.pm_calls_total, state.pm_calls_active);
+    let open_bars_held = state
+        .portfolio
+        .positions
+        .values()
+        .filter(|pos| !pos.is_flat())
+        .map(|pos| ((pos.symbol.clone(), pos.entry_bar), pos.bars_held))
+        .collect();
+    // Open positions count through `open_bars_held`, not their end-of-data trades
+    let closed = all_trades
+        .iter()
+        .take_while(|t| !t.liquidated_at_end)
+        .count();
+    let stickiness =
+        compute_stickiness_report(&all_trades[..closed], &state.pm_calls, &open_bars_held);
+
+    let attribution = match &final_marks {
+        Some((last, marks)) => {
+            attribute_pnl(&all_fills, marks, Some((*last, aligned.dates[*last])))
+        }
+        None => attribute_pnl(&all_fills, &HashMap::new(), None),
+    };
+
+    let replay = replay.map(|r| r.finish(&all_trades)).unwrap_or_default();
+    let portfolio_snapshots = snapshots.map(SnapshotRecorder::finish).unwrap_or_default();
 
     RunResult {
         equity_curve,
+        exposure_curve,
         fills: all_fills,
+        order_audit: state.order_book.take_audit_trail(),
         trades: all_trades,
         final_equity,
         bar_count: num_bars,
         warmup_bars,
+        warmup,
         void_bar_rates,
         data_quality_warnings,
         stickiness,
         signal_count: state.signal_count,
         signal_evaluations: state.signal_evaluations,
+        intent_interventions: state.intent_interventions,
+        ratchet_clamps: state.ratchet_clamps,
+        attribution,
+        timings,
+        replay,
+        portfolio_snapshots,
     }
 }
 
@@ -361,40 +727,85 @@ pub fn run_backtest(
 ///
 /// For longs: stops may only go UP (tighter = higher stop).
 /// For shorts: stops may only go DOWN (tighter = lower stop).
-/// In debug builds, a violation triggers a debug_assert. In release, silently clamps.
-fn enforce_ratchet(intent: &OrderIntent, position: &crate::domain::Position) -> OrderIntent {
+/// A loosening stop is clamped to the position's current stop; the caller
+/// records the clamp as a [`RatchetClamp`]. Paper trading applies the same
+/// rule to its positions.
+pub fn enforce_ratchet(intent: &OrderIntent, position: &crate::domain::Position) -> OrderIntent {
     match intent.action {
         IntentAction::AdjustStop => {
             let new_stop = match intent.stop_price {
                 Some(p) => p,
                 None => return OrderIntent::hold(),
             };
-
-            match position.side {
-                PositionSide::Long => {
-                    let clamped = match position.current_stop {
-                        Some(cur) => new_stop.max(cur),
-                        None => new_stop,
-                    };
-                    OrderIntent::adjust_stop(clamped)
-                }
-                PositionSide::Short => {
-                    let clamped = match position.current_stop {
-                        Some(cur) => new_stop.min(cur),
-                        None => new_stop,
-                    };
-                    OrderIntent::adjust_stop(clamped)
-                }
-                PositionSide::Flat => OrderIntent::hold(),
+            if position.side == PositionSide::Flat {
+                return OrderIntent::hold();
+            }
+            OrderIntent {
+                stop_price: Some(tighter_stop(position.side, new_stop, position.current_stop)),
+                ..intent.clone()
             }
         }
         _ => intent.clone(),
     }
 }
 
+/// The tighter of `stop` and `reference` for a position on `side`.
+fn tighter_stop(side: PositionSide, stop: f64, reference: Option<f64>) -> f64 {
+    match (side, reference) {
+        (PositionSide::Long, Some(reference)) => stop.max(reference),
+        (PositionSide::Short, Some(reference)) => stop.min(reference),
+        _ => stop,
+    }
+}
+
+/// Trigger price of the active stop order protecting the open position in
+/// `symbol`. Stop orders left over from an earlier position are ignored.
+fn working_stop(
+    state: &EngineState,
+    symbol: &str,
+    exit_side: crate::domain::OrderSide,
+) -> Option<f64> {
+    let entry_bar = state.portfolio.get_position(symbol)?.entry_bar;
+    let order = state
+        .stop_order_ids
+        .get(symbol)
+        .and_then(|&id| state.order_book.get_order(id))
+        .filter(|o| o.is_active() && o.side == exit_side && o.created_bar >= entry_bar)?;
+    match order.order_type {
+        OrderType::StopMarket { trigger_price } => Some(trigger_price),
+        _ => None,
+    }
+}
+
+/// Record a clamped stop adjustment; nothing when the stop was kept as asked.
+fn record_ratchet_clamp(
+    state: &mut EngineState,
+    symbol: &str,
+    bar_index: usize,
+    side: PositionSide,
+    requested: f64,
+    applied: f64,
+) {
+    if applied != requested {
+        state.ratchet_clamps.push(RatchetClamp {
+            symbol: symbol.to_string(),
+            bar_index,
+            side,
+            requested,
+            applied,
+        });
+    }
+}
+
 /// Translate a PM intent into order book operations.
+///
+/// The stop and take-profit legs of a position form one OCO group: filling
+/// either cancels the other. Adjusting a leg is a cancel/replace, which keeps
+/// the replacement in the group. A forced exit is attributed to `exit_source`.
+#[allow(clippy::too_many_arguments)]
 fn apply_pm_intent(
     intent: &OrderIntent,
+    exit_source: DecisionSource,
     symbol: &str,
     side: PositionSide,
     quantity: f64,
@@ -409,60 +820,61 @@ fn apply_pm_intent(
 
     match intent.action {
         IntentAction::Hold => { /* nothing */ }
-        IntentAction::AdjustStop => {
-            let stop_price = match intent.stop_price {
-                Some(p) => p,
-                None => return,
-            };
-
-            // Update position's current_stop
-            if let Some(pos) = state.portfolio.get_position_mut(symbol) {
-                pos.current_stop = Some(stop_price);
-            }
-
-            let new_order_id = state.id_gen.next_order_id();
-            let new_stop_order = Order {
-                id: new_order_id,
-                symbol: symbol.to_string(),
-                side: exit_side,
-                order_type: OrderType::StopMarket {
-                    trigger_price: stop_price,
-                },
-                quantity,
-                filled_quantity: 0.0,
-                status: OrderStatus::Pending,
-                created_bar: bar_index,
-                parent_id: None,
-                oco_group_id: None,
-                activated_bar: None,
-            };
-
-            if let Some(&old_id) = state.stop_order_ids.get(symbol) {
-                // Cancel/replace existing stop — check if old order is still active
-                let old_is_active = state
-                    .order_book
-                    .get_order(old_id)
-                    .is_some_and(|o| o.is_active());
-
-                if old_is_active {
-                    let _ = state
-                        .order_book
-                        .cancel_replace(old_id, new_stop_order, bar_index);
-                } else {
-                    // Old stop already filled/cancelled, submit fresh
-                    state.order_book.submit(new_stop_order);
+        IntentAction::AdjustStop | IntentAction::AdjustTarget => {
+            let stop_price = intent
+                .stop_price
+                .filter(|_| intent.action == IntentAction::AdjustStop);
+            if let Some(requested) = stop_price {
+                // The working stop order is the last word: never replace it
+                // with a looser one, whatever the intent was checked against
+                let stop_price =
+                    tighter_stop(side, requested, working_stop(state, symbol, exit_side));
+                record_ratchet_clamp(state, symbol, bar_index, side, requested, stop_price);
+                // Update position's current_stop; the first stop also defines 1R
+                if let Some(pos) = state.portfolio.get_position_mut(symbol) {
+                    pos.current_stop = Some(stop_price);
+                    if pos.initial_stop.is_none() {
+                        pos.initial_stop = Some(stop_price);
+                        state
+                            .initial_stops
+                            .insert((symbol.to_string(), pos.entry_bar), stop_price);
+                    }
                 }
-            } else {
-                // First stop placement
-                state.order_book.submit(new_stop_order);
+                let order_type = OrderType::StopMarket {
+                    trigger_price: stop_price,
+                };
+                place_exit_leg(
+                    ExitLeg::Stop,
+                    symbol,
+                    exit_side,
+                    order_type,
+                    quantity,
+                    state,
+                    bar_index,
+                );
+            }
+            if let Some(target_price) = intent.target_price {
+                let order_type = OrderType::Limit {
+                    limit_price: target_price,
+                };
+                place_exit_leg(
+                    ExitLeg::Target,
+                    symbol,
+                    exit_side,
+                    order_type,
+                    quantity,
+                    state,
+                    bar_index,
+                );
             }
-            state
-                .stop_order_ids
-                .insert(symbol.to_string(), new_order_id);
         }
         IntentAction::ForceExit => {
-            // Cancel existing stop if any
-            if let Some(&old_id) = state.stop_order_ids.get(symbol) {
+            // Cancel the bracket legs, if any
+            let legs = [
+                state.stop_order_ids.remove(symbol),
+                state.target_order_ids.remove(symbol),
+            ];
+            for old_id in legs.into_iter().flatten() {
                 let old_is_active = state
                     .order_book
                     .get_order(old_id)
@@ -471,7 +883,6 @@ fn apply_pm_intent(
                     let _ = state.order_book.cancel(old_id, bar_index, "PM force exit");
                 }
             }
-            state.stop_order_ids.remove(symbol);
 
             // Place MOO exit order for next bar
             let exit_order_id = state.id_gen.next_order_id();
@@ -489,11 +900,133 @@ fn apply_pm_intent(
                 activated_bar: None,
             };
             state.order_book.submit(exit_order);
+            state.order_sources.insert(exit_order_id, exit_source);
         }
-        IntentAction::AdjustTarget => {
-            // Deferred: target management is not in the MVP PM set.
+    }
+}
+
+/// Void-bar half of a PM's time limit: `on_bar` is not called while the
+/// market is closed, so a limit that comes due here is caught by asking
+/// [`PositionManager::time_expired`] and queuing the force exit directly. Its
+/// `MarketOnOpen` order waits out the void bars and fills at the next open,
+/// not a bar after it. Queued once per position.
+fn schedule_time_expiry(
+    position_manager: &dyn PositionManager,
+    symbol: &str,
+    state: &mut EngineState,
+    bar_index: usize,
+) {
+    let Some(pos) = state
+        .portfolio
+        .get_position(symbol)
+        .filter(|p| !p.is_flat())
+    else {
+        return;
+    };
+    let key = (symbol.to_string(), pos.entry_bar);
+    if state.time_expiries.contains_key(&key) || !position_manager.time_expired(pos) {
+        return;
+    }
+    let (side, quantity) = (pos.side, pos.quantity);
+    state.time_expiries.insert(key, bar_index);
+    apply_pm_intent(
+        &OrderIntent::force_exit(),
+        DecisionSource::PmForceExit,
+        symbol,
+        side,
+        quantity,
+        state,
+        bar_index,
+    );
+}
+
+/// Cancel every active entry order, e.g. a stop entry still waiting for its
+/// trigger when the risk guard halts the run.
+fn cancel_pending_entries(state: &mut EngineState, bar_index: usize) {
+    let entries: Vec<OrderId> = state
+        .order_book
+        .active_orders()
+        .into_iter()
+        .filter(|o| state.order_sources.get(&o.id) == Some(&DecisionSource::SignalEntry))
+        .map(|o| o.id)
+        .collect();
+    for id in entries {
+        let _ = state.order_book.cancel(id, bar_index, "risk guard halt");
+    }
+}
+
+/// One side of a position's exit bracket.
+#[derive(Clone, Copy)]
+enum ExitLeg {
+    Stop,
+    Target,
+}
+
+/// Place or move one exit leg.
+///
+/// An active leg is cancel/replaced (keeping its OCO membership). A new leg
+/// is OCO-linked with the other leg if that one is still working; a lone leg
+/// is submitted standalone.
+fn place_exit_leg(
+    leg: ExitLeg,
+    symbol: &str,
+    exit_side: crate::domain::OrderSide,
+    order_type: OrderType,
+    quantity: f64,
+    state: &mut EngineState,
+    bar_index: usize,
+) {
+    let new_order_id = state.id_gen.next_order_id();
+    let new_order = Order {
+        id: new_order_id,
+        symbol: symbol.to_string(),
+        side: exit_side,
+        order_type,
+        quantity,
+        filled_quantity: 0.0,
+        status: OrderStatus::Pending,
+        created_bar: bar_index,
+        parent_id: None,
+        oco_group_id: None,
+        activated_bar: None,
+    };
+
+    let (own_ids, other_ids) = match leg {
+        ExitLeg::Stop => (&state.stop_order_ids, &state.target_order_ids),
+        ExitLeg::Target => (&state.target_order_ids, &state.stop_order_ids),
+    };
+    let active = |ids: &HashMap<String, crate::domain::OrderId>| {
+        ids.get(symbol)
+            .and_then(|&id| state.order_book.get_order(id))
+            .filter(|o| o.is_active())
+            .map(|o| (o.id, o.oco_group_id))
+    };
+    let own = active(own_ids);
+    let sibling = active(other_ids);
+
+    match (own, sibling) {
+        (Some((old_id, _)), _) => {
+            let _ = state
+                .order_book
+                .cancel_replace(old_id, new_order, bar_index);
         }
+        (None, Some((sibling_id, group))) => {
+            let group_id = group.unwrap_or_else(|| {
+                let group_id = state.id_gen.next_oco_group_id();
+                let _ = state.order_book.join_oco_group(sibling_id, group_id);
+                group_id
+            });
+            state.order_book.submit_oco_leg(new_order, group_id);
+        }
+        (None, None) => state.order_book.submit(new_order),
     }
+
+    let (ids, source) = match leg {
+        ExitLeg::Stop => (&mut state.stop_order_ids, DecisionSource::PmStopExit),
+        ExitLeg::Target => (&mut state.target_order_ids, DecisionSource::PmTargetExit),
+    };
+    ids.insert(symbol.to_string(), new_order_id);
+    state.order_sources.insert(new_order_id, source);
 }
 
 /// Build a price map for equity calculation at bar index `t`.
@@ -501,16 +1034,16 @@ fn apply_pm_intent(
 /// For open markets: use the bar's close price.
 /// For closed/void markets: use the last valid close (carry forward).
 fn build_current_prices(
-    bars_by_symbol: &HashMap<String, Vec<Bar>>,
+    frames: &HashMap<String, BarFrame>,
     last_valid_close: &HashMap<String, f64>,
     symbols: &[&str],
     bar_index: usize,
 ) -> HashMap<String, f64> {
     let mut prices = HashMap::new();
     for &symbol in symbols {
-        let bar = &bars_by_symbol[symbol][bar_index];
-        let price = if !bar.close.is_nan() {
-            bar.close
+        let close = frames[symbol].close()[bar_index];
+        let price = if !close.is_nan() {
+            close
         } else {
             // Void bar: use last valid close
             last_valid_close.get(symbol).copied().unwrap_or(0.0)
@@ -523,12 +1056,13 @@ fn build_current_prices(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::components::execution::NextBarOpenModel;
+    use crate::components::execution::{NextBarOpenModel, PathPolicy};
     use crate::components::filter::NoFilter;
     use crate::components::pm::NoOpPm;
     use crate::components::signal::NullSignal;
     use crate::data::align::AlignedData;
     use crate::data::provider::RawBar;
+    use crate::domain::{BarSeries, Instrument, Quantization};
     use chrono::NaiveDate;
 
     fn make_aligned_single(bars: Vec<RawBar>) -> AlignedData {
@@ -627,6 +1161,30 @@ mod tests {
         assert_eq!(result.warmup_bars, 5);
     }
 
+    #[test]
+    fn backtest_warmup_policy_scales_lookbacks_and_reports_them() {
+        let aligned = make_aligned_single(simple_bars(10));
+        let indicators: Vec<Box<dyn Indicator>> = vec![Box::new(crate::indicators::Sma::new(3))]; // lookback = 2
+        let mut config = EngineConfig::new(100_000.0, 0);
+        config.warmup.multiplier = 3.0;
+
+        let result = run_backtest(
+            &aligned,
+            &indicators,
+            &config,
+            &NullSignal,
+            &NoFilter,
+            &NextBarOpenModel::default(),
+            &NoOpPm,
+        );
+
+        assert_eq!(result.warmup_bars, 6);
+        assert_eq!(result.warmup.applied, 6);
+        assert_eq!(result.warmup.tradable_bars, 4);
+        assert_eq!(result.warmup.contributions[0].indicator, "sma_3");
+        assert_eq!(result.warmup.contributions[0].lookback, 2);
+    }
+
     #[test]
     fn backtest_void_bar_handling() {
         let base_date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
@@ -847,4 +1405,918 @@ mod tests {
         let result = enforce_ratchet(&intent, &pos);
         assert_eq!(result.action, IntentAction::ForceExit);
     }
+
+    #[test]
+    fn atr_trailing_stop_never_loosens_on_a_volatility_spike() {
+        use crate::components::pm::AtrTrailing;
+        use crate::indicators::Atr;
+
+        // Steady 0.5/bar climb with ~1.2-point ranges; bar 30 spikes 30
+        // points above its close, so ATR jumps and close - 3 * ATR drops
+        let bars: Vec<RawBar> = (0..40)
+            .map(|i| {
+                let close = 100.0 + 0.5 * i as f64;
+                let high = if i == 30 { close + 30.0 } else { close + 0.5 };
+                bar(i, close - 0.2, high, close - 0.7, close)
+            })
+            .collect();
+        let mut config = EngineConfig::new(100_000.0, 0);
+        config.record_replay = true;
+        let indicators: Vec<Box<dyn Indicator>> = vec![Box::new(Atr::new(14))];
+        let result = run_backtest(
+            &make_aligned_single(bars),
+            &indicators,
+            &config,
+            &LongAt(16),
+            &NoFilter,
+            &NextBarOpenModel::default(),
+            &AtrTrailing::new(14, 3.0),
+        );
+
+        // The working stop order only ever moves up
+        let position = &result.replay.positions[0];
+        let working: Vec<f64> = position
+            .bars
+            .iter()
+            .filter_map(|b| {
+                b.orders.iter().find_map(|o| match o.order_type {
+                    OrderType::StopMarket { trigger_price } => Some(trigger_price),
+                    _ => None,
+                })
+            })
+            .collect();
+        assert!(working.len() > 10, "{working:?}");
+        assert!(working.windows(2).all(|w| w[1] >= w[0]), "{working:?}");
+
+        // The spike's loosened stop was clamped and recorded
+        let first = result.ratchet_clamps.first().expect("a clamp on the spike");
+        assert_eq!(first.bar_index, 30);
+        assert_eq!(first.side, PositionSide::Long);
+        assert!(first.requested < first.applied - 1.0, "{first:?}");
+        assert!(result
+            .ratchet_clamps
+            .iter()
+            .all(|c| c.symbol == "SPY" && c.requested < c.applied));
+    }
+
+    #[test]
+    fn stop_adjustment_is_clamped_to_the_working_stop_order() {
+        let mut state = EngineState::new(100_000.0);
+        state.portfolio.positions.insert(
+            "SPY".into(),
+            crate::domain::Position::new_long("SPY".into(), 100.0, 100.0, 3),
+        );
+        let adjust = |state: &mut EngineState, stop: f64, bar_index: usize| {
+            apply_pm_intent(
+                &OrderIntent::adjust_stop(stop),
+                DecisionSource::PmForceExit,
+                "SPY",
+                PositionSide::Long,
+                100.0,
+                state,
+                bar_index,
+            );
+        };
+        adjust(&mut state, 95.0, 4);
+        assert!(state.ratchet_clamps.is_empty());
+
+        // The position lost track of its stop, but the order book did not
+        state
+            .portfolio
+            .get_position_mut("SPY")
+            .unwrap()
+            .current_stop = None;
+        adjust(&mut state, 90.0, 5);
+
+        let stop = state.order_book.get(state.stop_order_ids["SPY"]).unwrap();
+        assert!(matches!(
+            stop.order_type,
+            OrderType::StopMarket { trigger_price } if trigger_price == 95.0
+        ));
+        assert_eq!(
+            state.portfolio.get_position("SPY").unwrap().current_stop,
+            Some(95.0)
+        );
+        assert_eq!(
+            state.ratchet_clamps,
+            vec![RatchetClamp {
+                symbol: "SPY".into(),
+                bar_index: 5,
+                side: PositionSide::Long,
+                requested: 90.0,
+                applied: 95.0,
+            }]
+        );
+
+        // Tightening passes through untouched
+        adjust(&mut state, 97.0, 6);
+        assert_eq!(state.ratchet_clamps.len(), 1);
+    }
+
+    #[test]
+    fn apply_pm_intent_records_initial_stop_once() {
+        let mut state = EngineState::new(100_000.0);
+        state.portfolio.positions.insert(
+            "SPY".into(),
+            crate::domain::Position::new_long("SPY".into(), 100.0, 100.0, 3),
+        );
+
+        let first = OrderIntent::adjust_stop(95.0);
+        apply_pm_intent(
+            &first,
+            DecisionSource::PmForceExit,
+            "SPY",
+            PositionSide::Long,
+            100.0,
+            &mut state,
+            4,
+        );
+        let second = OrderIntent::adjust_stop(97.0);
+        apply_pm_intent(
+            &second,
+            DecisionSource::PmForceExit,
+            "SPY",
+            PositionSide::Long,
+            100.0,
+            &mut state,
+            5,
+        );
+
+        let pos = state.portfolio.get_position("SPY").unwrap();
+        assert_eq!(pos.current_stop, Some(97.0));
+        assert_eq!(pos.initial_stop, Some(95.0));
+        assert_eq!(state.initial_stops[&("SPY".to_string(), 3)], 95.0);
+    }
+
+    /// Test signal that fires long on every bar.
+    struct AlwaysLong;
+
+    impl SignalGenerator for AlwaysLong {
+        fn name(&self) -> &str {
+            "always_long"
+        }
+
+        fn warmup_bars(&self) -> usize {
+            0
+        }
+
+        fn evaluate(
+            &self,
+            bars: &dyn BarSeries,
+            bar_index: usize,
+            _indicators: &crate::components::indicator::IndicatorValues,
+        ) -> Option<crate::components::signal::SignalEvent> {
+            Some(crate::components::signal::SignalEvent {
+                id: crate::domain::ids::SignalEventId(0),
+                bar_index,
+                date: bars.bar(bar_index).date,
+                symbol: bars.bar(bar_index).symbol.to_string(),
+                direction: SignalDirection::Long,
+                strength: 1.0,
+                metadata: HashMap::new(),
+            })
+        }
+    }
+
+    fn run_always_long(config: &EngineConfig) -> RunResult {
+        let aligned = make_aligned_single(simple_bars(30));
+        let indicators: Vec<Box<dyn Indicator>> = vec![];
+        run_backtest(
+            &aligned,
+            &indicators,
+            config,
+            &AlwaysLong,
+            &NoFilter,
+            &NextBarOpenModel::default(),
+            &crate::components::pm::MaxHoldingPeriod::new(2),
+        )
+    }
+
+    #[test]
+    fn stickiness_report_scores_every_position() {
+        let result = run_always_long(&EngineConfig::new(100_000.0, 0));
+        let report = &result.stickiness;
+        assert!(!result.trades.is_empty());
+
+        // One score per closed trade, in trade order, then any still-open
+        // position (whose end-of-data trade comes last)
+        let closed: Vec<_> = result
+            .trades
+            .iter()
+            .filter(|t| !t.liquidated_at_end)
+            .collect();
+        for (trade, pos) in closed.iter().zip(&report.positions) {
+            assert_eq!(
+                (pos.entry_bar, pos.exit_bar),
+                (trade.entry_bar, Some(trade.exit_bar))
+            );
+            assert!(pos.pm_calls.total > 0);
+        }
+        assert_eq!(report.positions.len(), result.trades.len());
+
+        // The run's exit trigger rate is the per-position calls summed
+        let calls = report.pm_calls();
+        let run = report.run.as_ref().unwrap();
+        assert_eq!(
+            run.exit_trigger_rate,
+            calls.active as f64 / calls.total as f64
+        );
+    }
+
+    #[test]
+    fn account_too_small_for_one_share_skips_entries() {
+        // 50 of cash against a ~100 share price
+        let result = run_always_long(&EngineConfig::new(50.0, 0));
+        assert!(result.trades.is_empty());
+        let rejected: Vec<_> = result
+            .signal_evaluations
+            .iter()
+            .filter(|e| e.verdict == FilterVerdict::BelowMinimumSize)
+            .collect();
+        assert!(!rejected.is_empty());
+        assert!(rejected.iter().all(|e| e.filter_name == SIZING_FILTER_NAME
+            && e.filter_state["desired_quantity"] < 1.0
+            && e.filter_state["increment"] == 1.0));
+        assert!(result.equity_curve.iter().all(|&e| e == 50.0));
+
+        // Fractional shares let the same account trade
+        let mut config = EngineConfig::new(50.0, 0);
+        config.instruments.insert(
+            "SPY".into(),
+            Instrument::us_equity("SPY").with_quantization(Quantization::Fractional),
+        );
+        let fractional = run_always_long(&config);
+        assert!(!fractional.trades.is_empty());
+        assert!(fractional.trades.iter().all(|t| t.quantity < 1.0));
+    }
+
+    #[test]
+    fn contracts_size_in_whole_multiples_and_scale_pnl() {
+        // 7,000 of cash buys 70 shares at ~100, but only one 50-unit contract
+        let mut config = EngineConfig::new(7_000.0, 0);
+        config.instruments.insert(
+            "SPY".into(),
+            Instrument::us_equity("SPY")
+                .with_quantization(Quantization::Contracts { multiplier: 50.0 }),
+        );
+        let result = run_always_long(&config);
+        let trade = result.trades.first().expect("one contract fits");
+        assert_eq!(trade.quantity, 50.0);
+        let points = trade.exit_price - trade.entry_price;
+        assert!((trade.gross_pnl - points * 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn cooldown_blocks_reentry_after_exit() {
+        let ungoverned = run_always_long(&EngineConfig::new(100_000.0, 0));
+        assert!(ungoverned
+            .signal_evaluations
+            .iter()
+            .all(|e| e.verdict != FilterVerdict::Cooldown));
+
+        let mut config = EngineConfig::new(100_000.0, 0);
+        config.entry_governance.cooldown_bars_after_exit = 5;
+        let governed = run_always_long(&config);
+
+        let blocked: Vec<_> = governed
+            .signal_evaluations
+            .iter()
+            .filter(|e| e.verdict == FilterVerdict::Cooldown)
+            .collect();
+        assert!(!blocked.is_empty());
+        assert!(blocked
+            .iter()
+            .all(|e| e.filter_name == GOVERNANCE_FILTER_NAME
+                && e.filter_state["bars_since_exit"] <= 5.0));
+        assert!(governed.trades.len() < ungoverned.trades.len());
+
+        // Entries resume only after the cooldown has elapsed
+        let mut trades = governed.trades.iter();
+        let mut prev = trades.next().unwrap();
+        for trade in trades {
+            assert!(trade.entry_bar > prev.exit_bar + 5);
+            prev = trade;
+        }
+    }
+
+    #[test]
+    fn risk_guard_caps_size_and_halts_after_drawdown() {
+        let full = run_always_long(&EngineConfig::new(100_000.0, 0));
+        let mut config = EngineConfig::new(100_000.0, 0);
+        config.risk_guard.max_position_pct = Some(0.5);
+        let capped = run_always_long(&config);
+        assert!(capped.trades[0].quantity < full.trades[0].quantity * 0.6);
+
+        // A steady 2%-a-bar slide trips a 10% kill switch
+        let base_date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        let bars = (0..30)
+            .map(|i| {
+                let close = 100.0 * 0.98f64.powi(i);
+                RawBar {
+                    date: base_date + chrono::Duration::days(i as i64),
+                    open: close * 1.005,
+                    high: close * 1.01,
+                    low: close * 0.99,
+                    close,
+                    volume: 1000,
+                    adj_close: close,
+                }
+            })
+            .collect();
+        let mut config = EngineConfig::new(100_000.0, 0);
+        config.risk_guard.max_drawdown = Some(0.1);
+        let result = run_backtest(
+            &make_aligned_single(bars),
+            &[],
+            &config,
+            &AlwaysLong,
+            &NoFilter,
+            &NextBarOpenModel::default(),
+            &crate::components::pm::MaxHoldingPeriod::new(100),
+        );
+
+        let last = result.trades.last().expect("one guarded trade");
+        assert_eq!(result.trades.len(), 1);
+        assert_eq!(last.exit_source, Some(DecisionSource::RiskGuardFlatten));
+        assert!(!last.liquidated_at_end);
+        // Flat from the halt on: equity stops moving
+        let after = &result.equity_curve[last.exit_bar..];
+        assert!(after.iter().all(|&e| e == after[0]));
+        assert!(after[0] < 90_000.0);
+    }
+
+    #[test]
+    fn timings_recorded_only_when_enabled() {
+        let mut config = EngineConfig::new(100_000.0, 0);
+        let timed = run_always_long(&config);
+        assert_eq!(timed.timings.runs, 1);
+        assert!(timed.timings.total() > std::time::Duration::ZERO);
+
+        config.record_timings = false;
+        let untimed = run_always_long(&config);
+        assert_eq!(untimed.timings.total(), std::time::Duration::ZERO);
+        // Timing never changes results
+        assert_eq!(untimed.equity_curve, timed.equity_curve);
+    }
+
+    #[test]
+    fn replay_covers_each_trade_window() {
+        let mut config = EngineConfig::new(100_000.0, 0);
+        assert!(run_always_long(&config).replay.is_empty());
+
+        config.record_replay = true;
+        let result = run_always_long(&config);
+        assert!(!result.trades.is_empty());
+        for trade in result.trades.iter().filter(|t| !t.liquidated_at_end) {
+            let replay = result
+                .replay
+                .for_trade(&trade.symbol, trade.entry_bar)
+                .unwrap();
+            let first = replay.bars.first().unwrap();
+            let exit = &replay.bars[replay.exit_index()];
+            assert_eq!(first.bar_index, trade.entry_bar);
+            assert_eq!(exit.bar_index, trade.exit_bar);
+            assert!(exit.exit);
+            assert_eq!(exit.equity, result.equity_curve[trade.exit_bar]);
+            assert_eq!(replay.net_pnl, Some(trade.net_pnl));
+        }
+    }
+
+    #[test]
+    fn attribution_sums_to_the_equity_change() {
+        let config = EngineConfig::with_execution(
+            100_000.0,
+            0,
+            crate::engine::ExecutionConfig::from_preset(
+                crate::components::execution::ExecutionPreset::Realistic,
+            ),
+        );
+        let result = run_always_long(&config);
+        let totals = result.attribution.totals();
+        // Entries cost money; max-holding exits are forced exits
+        assert!(totals[&DecisionSource::SignalEntry] < 0.0);
+        assert!(totals.contains_key(&DecisionSource::PmForceExit));
+        // Always long: a position is still open on the last bar
+        assert!(totals.contains_key(&DecisionSource::EndOfRunLiquidation));
+        // Long only, so the equity change is the run's total PnL
+        let change = result.final_equity - config.initial_capital;
+        assert!(
+            (result.attribution.total() - change).abs() < 1e-6,
+            "{} vs {change}",
+            result.attribution.total()
+        );
+
+        // The open position's end-of-data trade completes the ledger
+        let net: f64 = result.trades.iter().map(|t| t.net_pnl).sum();
+        assert!((net - change).abs() < 1e-6, "{net} vs {change}");
+
+        let last = result.trades.last().unwrap();
+        assert!(last.liquidated_at_end);
+        assert_eq!(last.exit_source, Some(DecisionSource::EndOfRunLiquidation));
+        assert_eq!(last.exit_bar, result.bar_count - 1);
+        for trade in &result.trades {
+            assert_eq!(trade.entry_source, Some(DecisionSource::SignalEntry));
+            if !trade.liquidated_at_end {
+                assert_eq!(trade.exit_source, Some(DecisionSource::PmForceExit));
+            }
+        }
+        assert!(result.fills.iter().all(|f| f.decision_source.is_some()));
+    }
+
+    #[test]
+    fn precise_accounting_settles_fills_exactly() {
+        use crate::domain::money::{round_to_micros, Micros};
+        use crate::domain::OrderSide;
+
+        let realistic = crate::engine::ExecutionConfig::from_preset(
+            crate::components::execution::ExecutionPreset::Realistic,
+        );
+        let float = run_always_long(&EngineConfig::with_execution(
+            100_000.0,
+            0,
+            realistic.clone(),
+        ));
+        let config = EngineConfig::with_execution(
+            100_000.0,
+            0,
+            crate::engine::ExecutionConfig {
+                precise_accounting: true,
+                ..realistic
+            },
+        );
+        let result = run_always_long(&config);
+        let on_grid = |v: f64| v == round_to_micros(v);
+        assert!(result
+            .fills
+            .iter()
+            .all(|f| on_grid(f.price) && on_grid(f.commission) && on_grid(f.slippage)));
+        assert!((result.final_equity - float.final_equity).abs() < 0.01);
+
+        // Flat after each exit, equity is the capital plus the exact sum of
+        // every fill's cash flow so far
+        let mut checked = 0;
+        for trade in result.trades.iter().filter(|t| !t.liquidated_at_end) {
+            if result.exposure_curve[trade.exit_bar] != 0.0 {
+                continue;
+            }
+            let flows: Micros = result
+                .fills
+                .iter()
+                .filter(|f| f.bar_index <= trade.exit_bar)
+                .map(|f| {
+                    let gross = Micros::from_f64(f.price).times(f.quantity);
+                    let costs = Micros::from_f64(f.commission) + Micros::from_f64(f.slippage);
+                    match f.side {
+                        OrderSide::Buy => -(gross + costs),
+                        OrderSide::Sell => gross - costs,
+                    }
+                })
+                .sum();
+            let cash = Micros::from_f64(config.initial_capital) + flows;
+            assert_eq!(result.equity_curve[trade.exit_bar], cash.to_f64());
+            checked += 1;
+        }
+        assert!(checked > 0);
+    }
+
+    /// Test signal that fires long once, on the given bar.
+    struct LongAt(usize);
+
+    impl SignalGenerator for LongAt {
+        fn name(&self) -> &str {
+            "long_at"
+        }
+
+        fn warmup_bars(&self) -> usize {
+            0
+        }
+
+        fn evaluate(
+            &self,
+            bars: &dyn BarSeries,
+            bar_index: usize,
+            indicators: &crate::components::indicator::IndicatorValues,
+        ) -> Option<crate::components::signal::SignalEvent> {
+            if bar_index == self.0 {
+                AlwaysLong.evaluate(bars, bar_index, indicators)
+            } else {
+                None
+            }
+        }
+    }
+
+    #[test]
+    fn exposure_curve_is_zero_until_the_entry_fill() {
+        let aligned = make_aligned_single(simple_bars(20));
+        let config = EngineConfig::new(100_000.0, 0);
+        let indicators: Vec<Box<dyn Indicator>> = vec![];
+
+        // Signal on bar 9's close, filled at bar 10's open and held to the end
+        let result = run_backtest(
+            &aligned,
+            &indicators,
+            &config,
+            &LongAt(9),
+            &NoFilter,
+            &NextBarOpenModel::default(),
+            &NoOpPm,
+        );
+
+        assert_eq!(result.exposure_curve.len(), 20);
+        assert!(result.exposure_curve[..10].iter().all(|&e| e == 0.0));
+        assert!(result.exposure_curve[10..].iter().all(|&e| e > 0.0));
+    }
+
+    /// Test PM holding a fixed bracket around the entry price.
+    struct FixedBracket {
+        stop_pct: f64,
+        target_pct: f64,
+    }
+
+    impl PositionManager for FixedBracket {
+        fn name(&self) -> &str {
+            "fixed_bracket"
+        }
+
+        fn on_bar(
+            &self,
+            position: &crate::domain::Position,
+            _bar: &Bar,
+            _bar_index: usize,
+            _market_status: MarketStatus,
+            _indicators: &crate::components::indicator::IndicatorValues,
+        ) -> OrderIntent {
+            let entry = position.avg_entry_price;
+            OrderIntent {
+                target_price: Some(entry * (1.0 + self.target_pct)),
+                ..OrderIntent::adjust_stop(entry * (1.0 - self.stop_pct))
+            }
+        }
+    }
+
+    fn bar(day: i64, open: f64, high: f64, low: f64, close: f64) -> RawBar {
+        RawBar {
+            date: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap() + chrono::Duration::days(day),
+            open,
+            high,
+            low,
+            close,
+            volume: 1000,
+            adj_close: close,
+        }
+    }
+
+    /// Enter at bar 1's open (100), bracket 95 / 105 placed after bar 1, then
+    /// `exit_bar` at bar 2 and a slide through the stop level afterwards.
+    fn run_bracket(exit_bar: RawBar, path_policy: PathPolicy) -> RunResult {
+        let bars = vec![
+            bar(0, 100.0, 101.0, 99.0, 100.0),
+            bar(1, 100.0, 101.0, 99.0, 100.0),
+            exit_bar,
+            bar(3, 100.0, 100.0, 90.0, 90.0),
+            bar(4, 90.0, 90.0, 80.0, 80.0),
+        ];
+        let mut config = EngineConfig::new(100_000.0, 0);
+        config.execution_config.path_policy = path_policy;
+        let indicators: Vec<Box<dyn Indicator>> = vec![];
+        run_backtest(
+            &make_aligned_single(bars),
+            &indicators,
+            &config,
+            &LongAt(0),
+            &NoFilter,
+            &NextBarOpenModel::default(),
+            &FixedBracket {
+                stop_pct: 0.05,
+                target_pct: 0.05,
+            },
+        )
+    }
+
+    fn exit_fills(result: &RunResult) -> Vec<(usize, f64)> {
+        result
+            .fills
+            .iter()
+            .filter(|f| f.side == crate::domain::OrderSide::Sell)
+            .map(|f| (f.bar_index, f.price))
+            .collect()
+    }
+
+    #[test]
+    fn take_profit_fill_cancels_stop_in_same_bar() {
+        let result = run_bracket(bar(2, 100.0, 106.0, 99.0, 104.0), PathPolicy::WorstCase);
+        // Target fills at 105; the stop never fires on the later slide to 80
+        assert_eq!(exit_fills(&result), vec![(2, 105.0)]);
+        assert_eq!(result.trades.len(), 1);
+        assert_eq!(result.trades[0].exit_bar, 2);
+    }
+
+    #[test]
+    fn worst_case_picks_stop_on_ambiguous_bar() {
+        // Both 95 and 105 inside the bar's range
+        let ambiguous = bar(2, 100.0, 106.0, 94.0, 100.0);
+        let worst = run_bracket(ambiguous.clone(), PathPolicy::WorstCase);
+        assert_eq!(exit_fills(&worst), vec![(2, 95.0)]);
+        let best = run_bracket(ambiguous, PathPolicy::BestCase);
+        assert_eq!(exit_fills(&best), vec![(2, 105.0)]);
+    }
+
+    #[test]
+    fn bracket_exits_are_attributed_to_their_leg() {
+        let ambiguous = bar(2, 100.0, 106.0, 94.0, 100.0);
+        for (policy, source, pnl) in [
+            (PathPolicy::WorstCase, DecisionSource::PmStopExit, -5.0),
+            (PathPolicy::BestCase, DecisionSource::PmTargetExit, 5.0),
+        ] {
+            let result = run_bracket(ambiguous.clone(), policy);
+            assert_eq!(result.trades[0].exit_source, Some(source));
+            let bar = &result.attribution.bars[1];
+            assert_eq!(bar.bar_index, 2);
+            let quantity = result.trades[0].quantity;
+            assert_eq!(bar.pnl[&source], pnl * quantity);
+        }
+    }
+
+    #[test]
+    fn bracket_legs_share_oco_group_across_adjustments() {
+        let mut state = EngineState::new(100_000.0);
+        state.portfolio.positions.insert(
+            "SPY".into(),
+            crate::domain::Position::new_long("SPY".into(), 100.0, 100.0, 3),
+        );
+        let bracket = OrderIntent {
+            target_price: Some(110.0),
+            ..OrderIntent::adjust_stop(95.0)
+        };
+        apply_pm_intent(
+            &bracket,
+            DecisionSource::PmForceExit,
+            "SPY",
+            PositionSide::Long,
+            100.0,
+            &mut state,
+            4,
+        );
+        let retarget = OrderIntent {
+            action: IntentAction::AdjustTarget,
+            stop_price: None,
+            target_price: Some(108.0),
+        };
+        apply_pm_intent(
+            &retarget,
+            DecisionSource::PmForceExit,
+            "SPY",
+            PositionSide::Long,
+            100.0,
+            &mut state,
+            5,
+        );
+
+        let stop = state.order_book.get(state.stop_order_ids["SPY"]).unwrap();
+        let target = state.order_book.get(state.target_order_ids["SPY"]).unwrap();
+        assert!(matches!(
+            target.order_type,
+            OrderType::Limit { limit_price } if limit_price == 108.0
+        ));
+        let group = stop.oco_group_id.expect("stop is in an OCO group");
+        assert_eq!(target.oco_group_id, Some(group));
+        let members = &state.order_book.get_oco_group(group).unwrap().order_ids;
+        assert_eq!(members, &vec![stop.id, target.id]);
+        // The target adjustment left the stop alone
+        assert_eq!(
+            state.portfolio.get_position("SPY").unwrap().current_stop,
+            Some(95.0)
+        );
+
+        // Force exit cancels both legs
+        apply_pm_intent(
+            &OrderIntent::force_exit(),
+            DecisionSource::PmForceExit,
+            "SPY",
+            PositionSide::Long,
+            100.0,
+            &mut state,
+            6,
+        );
+        assert!(state
+            .order_book
+            .active_orders_for_symbol("SPY")
+            .iter()
+            .all(|o| matches!(o.order_type, OrderType::MarketOnOpen)));
+        assert!(state.target_order_ids.is_empty());
+    }
+
+    /// Enter at bar 1's open and hold through a 10-bar void gap (bars 4..14)
+    /// under an 8-bar max holding period on the given clock.
+    fn run_through_void_gap(clock: crate::components::pm::HoldingClock) -> RunResult {
+        run_void_gap_with(&crate::components::pm::MaxHoldingPeriod::new(8).with_clock(clock))
+    }
+
+    fn run_void_gap_with(position_manager: &dyn PositionManager) -> RunResult {
+        let mut bars = simple_bars(30);
+        for b in &mut bars[4..14] {
+            *b = RawBar {
+                open: f64::NAN,
+                high: f64::NAN,
+                low: f64::NAN,
+                close: f64::NAN,
+                volume: 0,
+                adj_close: f64::NAN,
+                ..b.clone()
+            };
+        }
+        let indicators: Vec<Box<dyn Indicator>> = vec![];
+        run_backtest(
+            &make_aligned_single(bars),
+            &indicators,
+            &EngineConfig::new(100_000.0, 0),
+            &LongAt(0),
+            &NoFilter,
+            &NextBarOpenModel::default(),
+            position_manager,
+        )
+    }
+
+    #[test]
+    fn max_holding_counts_open_bars_across_void_gap() {
+        let result = run_through_void_gap(crate::components::pm::HoldingClock::OpenBars);
+        // Open bars 1-3 and 14-18 make 8; the exit fills at bar 19's open
+        assert_eq!(exit_fills(&result), vec![(19, 118.5)]);
+        assert_eq!(result.trades[0].expiry_bar, Some(18));
+    }
+
+    #[test]
+    fn max_holding_counts_void_bars_when_configured() {
+        let result = run_through_void_gap(crate::components::pm::HoldingClock::AllBars);
+        // The limit expires on void bar 8; the exit waits out the gap and
+        // fills at bar 14's open, the first valid bar
+        assert_eq!(exit_fills(&result), vec![(14, 113.5)]);
+        let trade = &result.trades[0];
+        assert_eq!((trade.expiry_bar, trade.exit_bar), (Some(8), 14));
+        // Held exactly the configured limit when it came due
+        assert_eq!(trade.expiry_bar.unwrap() - trade.entry_bar + 1, 8);
+        assert_eq!(trade.exit_source, Some(DecisionSource::PmForceExit));
+    }
+
+    /// Test PM recording the bars `on_bar` is called on.
+    struct RecordingPm {
+        inner: crate::components::pm::MaxHoldingPeriod,
+        calls: std::sync::Mutex<Vec<usize>>,
+    }
+
+    impl PositionManager for RecordingPm {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        fn on_bar(
+            &self,
+            position: &crate::domain::Position,
+            bar: &Bar,
+            bar_index: usize,
+            market_status: MarketStatus,
+            indicators: &crate::components::indicator::IndicatorValues,
+        ) -> OrderIntent {
+            self.calls.lock().unwrap().push(bar_index);
+            self.inner.on_bar(position, bar, bar_index, market_status, indicators)
+        }
+
+        fn time_expired(&self, position: &crate::domain::Position) -> bool {
+            self.inner.time_expired(position)
+        }
+    }
+
+    #[test]
+    fn void_bar_expiry_exits_without_calling_on_bar() {
+        let pm = RecordingPm {
+            inner: crate::components::pm::MaxHoldingPeriod::new(8)
+                .with_clock(crate::components::pm::HoldingClock::AllBars),
+            calls: Default::default(),
+        };
+        let result = run_void_gap_with(&pm);
+        assert_eq!(exit_fills(&result), vec![(14, 113.5)]);
+        // Only the open bars before the gap; the position is flat from bar 14
+        assert_eq!(*pm.calls.lock().unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn pm_without_time_limit_waits_for_open_bars() {
+        // NoOpPm never expires: the position rides the gap to the end of data
+        let result = run_void_gap_with(&NoOpPm);
+        assert!(exit_fills(&result).is_empty());
+        assert!(result.trades.iter().all(|t| t.expiry_bar.is_none()));
+    }
+
+    /// Test PM trailing its stop a fixed fraction below each close.
+    struct TrailBelowClose(f64);
+
+    impl PositionManager for TrailBelowClose {
+        fn name(&self) -> &str {
+            "trail_below_close"
+        }
+
+        fn on_bar(
+            &self,
+            _position: &crate::domain::Position,
+            bar: &Bar,
+            _bar_index: usize,
+            _market_status: MarketStatus,
+            _indicators: &crate::components::indicator::IndicatorValues,
+        ) -> OrderIntent {
+            OrderIntent::adjust_stop(bar.close * (1.0 - self.0))
+        }
+    }
+
+    #[test]
+    fn volatility_filter_freezes_stop_on_spike_bar() {
+        // Enter at bar 2's open; bar 3 is a 13-point spike, bar 4 is calm
+        let bars = vec![
+            bar(0, 100.0, 101.0, 99.0, 100.0),
+            bar(1, 100.0, 101.0, 99.0, 100.0),
+            bar(2, 100.0, 101.0, 99.0, 100.0),
+            bar(3, 100.0, 112.0, 99.0, 110.0),
+            bar(4, 110.0, 111.0, 109.0, 110.0),
+            bar(5, 110.0, 110.0, 100.0, 101.0),
+        ];
+        let indicators: Vec<Box<dyn Indicator>> = vec![Box::new(crate::indicators::Atr::new(1))];
+        let filter = crate::components::filter::VolatilityFilter::new(1, 0.0, 100.0)
+            .with_freeze_stops_above(5.0);
+        let result = run_backtest(
+            &make_aligned_single(bars),
+            &indicators,
+            &EngineConfig::new(100_000.0, 0),
+            &LongAt(1),
+            &filter,
+            &NextBarOpenModel::default(),
+            &TrailBelowClose(0.05),
+        );
+
+        // The spike bar's tightening to 104.5 is held at the initial 95 stop
+        assert_eq!(result.intent_interventions.len(), 1);
+        let held = &result.intent_interventions[0];
+        assert_eq!(held.bar_index, 3);
+        assert_eq!(held.filter_name, "volatility_filter");
+        assert_eq!(held.original, OrderIntent::adjust_stop(110.0 * 0.95));
+        assert_eq!(held.applied, OrderIntent::hold());
+        assert_eq!(held.filter_state["held_stop"], 95.0);
+
+        // The calm bar applies it, and bar 5 stops out at 104.5
+        assert_eq!(exit_fills(&result), vec![(5, 110.0 * 0.95)]);
+        assert_eq!(result.trades[0].initial_stop_price, Some(95.0));
+    }
+
+    #[test]
+    fn filter_override_cannot_loosen_stop() {
+        struct Loosen;
+
+        impl SignalFilter for Loosen {
+            fn name(&self) -> &str {
+                "loosen"
+            }
+
+            fn evaluate(
+                &self,
+                signal: &crate::components::signal::SignalEvent,
+                bars: &dyn BarSeries,
+                bar_index: usize,
+                indicators: &crate::components::indicator::IndicatorValues,
+            ) -> SignalEvaluation {
+                NoFilter.evaluate(signal, bars, bar_index, indicators)
+            }
+
+            fn review_intent(
+                &self,
+                _intent: &OrderIntent,
+                current_stop: Option<f64>,
+                _bars: &dyn BarSeries,
+                _bar_index: usize,
+                _indicators: &crate::components::indicator::IndicatorValues,
+            ) -> Option<crate::components::filter::IntentOverride> {
+                Some(crate::components::filter::IntentOverride {
+                    intent: OrderIntent::adjust_stop(current_stop? - 50.0),
+                    filter_state: HashMap::new(),
+                })
+            }
+        }
+
+        let indicators: Vec<Box<dyn Indicator>> = vec![];
+        let result = run_backtest(
+            &make_aligned_single(simple_bars(10)),
+            &indicators,
+            &EngineConfig::new(100_000.0, 0),
+            &LongAt(0),
+            &Loosen,
+            &NextBarOpenModel::default(),
+            &TrailBelowClose(0.05),
+        );
+
+        // The initial stop (101 * 0.95 after bar 1) is placed untouched;
+        // every later loosening is clamped back to it
+        let initial = 101.0 * 0.95;
+        assert_eq!(result.intent_interventions.len(), 8);
+        for i in &result.intent_interventions {
+            assert_eq!(i.applied, OrderIntent::adjust_stop(initial));
+            assert!(i.original.stop_price.unwrap() > initial);
+        }
+    }
 }
@@ -8,22 +8,40 @@
 //! 3. End-of-bar: fill MOC orders
 //! 4. Post-bar: mark-to-market, equity accounting, PM maintenance orders
 
+pub mod attribution;
 pub mod convert;
+pub mod entry_governance;
 pub mod execution;
 pub mod loop_runner;
 pub mod order_book;
+pub mod portfolio_snapshot;
 pub mod portfolio_update;
 pub mod precompute;
+pub mod replay;
+pub mod risk_guard;
 pub mod state;
 pub mod stickiness;
+pub mod timings;
 pub mod trade_extraction;
+pub mod warmup;
 
+pub use attribution::{attribute_pnl, AttributedBar, PnlAttribution};
 pub use convert::{aligned_to_bars, raw_to_bar};
+pub use entry_governance::{EntryGovernance, EntryGovernor};
 pub use execution::{
-    CostModel, ExecutionConfig, ExecutionEngine, LiquidityPolicy, RemainderPolicy,
+    CommissionModel, CommissionTier, CostModel, ExecutionConfig, ExecutionEngine, LimitFillPolicy,
+    LiquidityPolicy, RegulatoryFees, RemainderPolicy,
 };
-pub use loop_runner::run_backtest;
+pub use loop_runner::{enforce_ratchet, run_backtest, run_backtest_cached, SIZING_FILTER_NAME};
 pub use order_book::{OrderBook, OrderBookError};
+pub use portfolio_snapshot::{PortfolioSnapshot, PositionSnapshot, SnapshotLog, SnapshotMode};
 pub use portfolio_update::apply_fills;
-pub use precompute::{compute_warmup, precompute_indicators};
-pub use state::{EngineConfig, EngineState, RunResult};
+pub use precompute::{
+    compute_warmup, precompute_frame_indicators, precompute_indicators,
+    precompute_indicators_cached, IndicatorCache,
+};
+pub use replay::{OrderSnapshot, PositionReplay, ReplayBar, ReplayLog};
+pub use risk_guard::RiskGuard;
+pub use state::{EngineConfig, EngineState, RatchetClamp, RunResult};
+pub use timings::EngineTimings;
+pub use warmup::{WarmupContribution, WarmupPolicy, WarmupReport};
@@ -1,7 +1,8 @@
 //! Order book state machine — manages order lifecycle, OCO groups, and brackets.
 //!
 //! The order book is the central registry for all orders. It manages:
-//! - Order storage and lookup (active + historical)
+//! - Order storage and lookup (active + historical), with a per-symbol index
+//!   of active orders so per-bar scans never touch historical orders
 //! - State transitions (Pending → Triggered → Filled / Cancelled / Expired)
 //! - OCO enforcement (one fill cancels all siblings)
 //! - Bracket activation (children activate only after entry fills)
@@ -14,7 +15,7 @@
 use crate::domain::{
     BracketOrder, OcoGroup, OcoGroupId, Order, OrderAuditEntry, OrderId, OrderStatus, OrderType,
 };
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use thiserror::Error;
 
 /// Errors from order book operations.
@@ -42,6 +43,10 @@ pub struct OrderBook {
     /// All active/historical orders keyed by ID.
     orders: HashMap<OrderId, Order>,
 
+    /// IDs of active (Pending/Triggered) orders per symbol, kept in step with
+    /// every status transition.
+    active_by_symbol: BTreeMap<String, BTreeSet<OrderId>>,
+
     /// Bracket children waiting for entry fill.
     /// Key: entry order ID. Value: child orders (stop-loss, optional take-profit).
     dormant: HashMap<OrderId, Vec<Order>>,
@@ -60,6 +65,7 @@ impl OrderBook {
     pub fn new() -> Self {
         Self {
             orders: HashMap::new(),
+            active_by_symbol: BTreeMap::new(),
             dormant: HashMap::new(),
             brackets: HashMap::new(),
             oco_groups: HashMap::new(),
@@ -80,7 +86,9 @@ impl OrderBook {
             order.status == OrderStatus::Pending,
             "submitted order must be Pending"
         );
-        self.orders.insert(order.id, order);
+        let id = order.id;
+        self.orders.insert(id, order);
+        self.index_active(id);
     }
 
     /// Submit a bracket order group: entry + stop_loss + optional take_profit.
@@ -127,7 +135,9 @@ impl OrderBook {
         self.oco_groups.insert(oco_group_id, oco_group);
 
         // Place entry order
-        self.orders.insert(entry.id, entry);
+        let entry_id = entry.id;
+        self.orders.insert(entry_id, entry);
+        self.index_active(entry_id);
     }
 
     /// Record a fill on an order, updating filled_quantity and transitioning
@@ -168,6 +178,7 @@ impl OrderBook {
                 bar_index,
                 "filled",
             );
+            self.unindex(order_id);
         }
 
         // OCO cancellation: if this order is in an OCO group and fully filled,
@@ -255,6 +266,7 @@ impl OrderBook {
         let order = self.orders.get_mut(&order_id).unwrap();
         order.status = new_status.clone();
         self.record_audit(order_id, from, new_status, bar_index, reason);
+        self.unindex(order_id);
 
         // If this was a bracket entry, clean up dormant children
         if let Some(children) = self.dormant.remove(&order_id) {
@@ -322,6 +334,7 @@ impl OrderBook {
         let old_order = self.orders.get_mut(&old_id).unwrap();
         old_order.status = cancel_status.clone();
         self.record_audit(old_id, from, cancel_status, bar_index, "replaced");
+        self.unindex(old_id);
 
         // Update OCO group membership: swap old ID for new ID
         if let Some(group_id) = oco_group_id {
@@ -336,7 +349,9 @@ impl OrderBook {
         }
 
         // Submit replacement
-        self.orders.insert(new_order.id, new_order);
+        let new_id = new_order.id;
+        self.orders.insert(new_id, new_order);
+        self.index_active(new_id);
 
         Ok(())
     }
@@ -359,6 +374,7 @@ impl OrderBook {
         let order = self.orders.get_mut(&order_id).unwrap();
         order.status = OrderStatus::Expired;
         self.record_audit(order_id, from, OrderStatus::Expired, bar_index, "expired");
+        self.unindex(order_id);
         Ok(())
     }
 
@@ -367,17 +383,24 @@ impl OrderBook {
         self.orders.get(&id)
     }
 
-    /// Get all active orders (Pending or Triggered).
+    /// Get all active orders (Pending or Triggered), by symbol then ID.
     pub fn active_orders(&self) -> Vec<&Order> {
-        self.orders.values().filter(|o| o.is_active()).collect()
+        self.active_by_symbol
+            .values()
+            .flatten()
+            .map(|id| &self.orders[id])
+            .collect()
     }
 
-    /// Get active orders for a specific symbol.
+    /// Get active orders for a specific symbol, in ID order.
+    ///
+    /// Served from the active index: cost is proportional to the symbol's
+    /// active orders, not to the size of the book's history.
     pub fn active_orders_for_symbol(&self, symbol: &str) -> Vec<&Order> {
-        self.orders
-            .values()
-            .filter(|o| o.is_active() && o.symbol == symbol)
-            .collect()
+        self.active_by_symbol
+            .get(symbol)
+            .map(|ids| ids.iter().map(|id| &self.orders[id]).collect())
+            .unwrap_or_default()
     }
 
     /// Get the bracket info for an entry order (if it's a bracket entry).
@@ -390,6 +413,39 @@ impl OrderBook {
         self.oco_groups.get(&group_id)
     }
 
+    /// Submit an order as a leg of OCO group `group_id`, creating the group if
+    /// it doesn't exist yet. The order's `oco_group_id` is overwritten.
+    ///
+    /// Used for exit brackets on an already-open position, where legs arrive
+    /// one at a time (stop first, target when the PM defines one).
+    pub fn submit_oco_leg(&mut self, mut order: Order, group_id: OcoGroupId) {
+        order.oco_group_id = Some(group_id);
+        self.add_to_oco_group(order.id, group_id);
+        self.submit(order);
+    }
+
+    /// Move an active, ungrouped order into OCO group `group_id`, creating
+    /// the group if it doesn't exist yet.
+    pub fn join_oco_group(
+        &mut self,
+        order_id: OrderId,
+        group_id: OcoGroupId,
+    ) -> Result<(), OrderBookError> {
+        let order = self
+            .orders
+            .get_mut(&order_id)
+            .ok_or(OrderBookError::OrderNotFound(order_id))?;
+        if !order.is_active() {
+            return Err(OrderBookError::OrderNotActive(
+                order_id,
+                format!("{:?}", order.status),
+            ));
+        }
+        order.oco_group_id = Some(group_id);
+        self.add_to_oco_group(order_id, group_id);
+        Ok(())
+    }
+
     /// Register a standalone OCO group (not part of a bracket).
     ///
     /// Use this when two orders are OCO-linked but not created via `submit_bracket`.
@@ -403,14 +459,19 @@ impl OrderBook {
         &self.audit_trail
     }
 
+    /// Move the audit trail out, leaving it empty.
+    pub fn take_audit_trail(&mut self) -> Vec<OrderAuditEntry> {
+        std::mem::take(&mut self.audit_trail)
+    }
+
     /// Whether there are any active orders.
     pub fn has_active_orders(&self) -> bool {
-        self.orders.values().any(|o| o.is_active())
+        !self.active_by_symbol.is_empty()
     }
 
     /// Count of active orders.
     pub fn active_count(&self) -> usize {
-        self.orders.values().filter(|o| o.is_active()).count()
+        self.active_by_symbol.values().map(BTreeSet::len).sum()
     }
 
     /// Whether a given order is dormant (bracket child waiting for entry fill).
@@ -463,6 +524,7 @@ impl OrderBook {
                         bar_index,
                         "OCO sibling filled",
                     );
+                    self.unindex(sibling_id);
                 }
             }
         }
@@ -476,6 +538,7 @@ impl OrderBook {
                 let child_id = child.id;
                 child.activated_bar = Some(bar_index);
                 self.orders.insert(child_id, child);
+                self.index_active(child_id);
                 self.record_audit(
                     child_id,
                     OrderStatus::Pending, // dormant → active (still Pending status)
@@ -487,6 +550,40 @@ impl OrderBook {
         }
     }
 
+    /// Add an order to the active index if it is active.
+    fn add_to_oco_group(&mut self, order_id: OrderId, group_id: OcoGroupId) {
+        self.oco_groups
+            .entry(group_id)
+            .or_insert_with(|| OcoGroup {
+                id: group_id,
+                order_ids: Vec::new(),
+            })
+            .order_ids
+            .push(order_id);
+    }
+
+    fn index_active(&mut self, order_id: OrderId) {
+        if let Some(order) = self.orders.get(&order_id).filter(|o| o.is_active()) {
+            self.active_by_symbol
+                .entry(order.symbol.clone())
+                .or_default()
+                .insert(order_id);
+        }
+    }
+
+    /// Remove an order from the active index after a terminal transition.
+    fn unindex(&mut self, order_id: OrderId) {
+        let Some(order) = self.orders.get(&order_id) else {
+            return;
+        };
+        if let Some(ids) = self.active_by_symbol.get_mut(&order.symbol) {
+            ids.remove(&order_id);
+            if ids.is_empty() {
+                self.active_by_symbol.remove(&order.symbol);
+            }
+        }
+    }
+
     /// Record an audit entry.
     fn record_audit(
         &mut self,
@@ -1186,6 +1283,34 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn oco_legs_join_group_one_at_a_time() {
+        let mut book = OrderBook::new();
+        book.submit(stop_sell(1, 95.0, 100.0));
+        book.join_oco_group(OrderId(1), OcoGroupId(90)).unwrap();
+        let tp = make_order(
+            2,
+            "SPY",
+            OrderSide::Sell,
+            OrderType::Limit { limit_price: 110.0 },
+            100.0,
+        );
+        book.submit_oco_leg(tp, OcoGroupId(90));
+
+        let group = book.get_oco_group(OcoGroupId(90)).unwrap();
+        assert_eq!(group.order_ids, vec![OrderId(1), OrderId(2)]);
+        assert_eq!(
+            book.get(OrderId(2)).unwrap().oco_group_id,
+            Some(OcoGroupId(90))
+        );
+
+        book.record_fill(OrderId(2), 100.0, 4).unwrap();
+        assert!(matches!(
+            book.get(OrderId(1)).unwrap().status,
+            OrderStatus::Cancelled { .. }
+        ));
+    }
+
     #[test]
     fn cancel_replace_filled_order_fails() {
         let mut book = OrderBook::new();
@@ -1400,4 +1525,88 @@ mod tests {
         assert_eq!(active.len(), 1);
         assert_eq!(active[0].id, OrderId(2));
     }
+
+    // ── Active index ───────────────────────────────────────────────────
+
+    /// The per-symbol index must agree with a full scan of the book.
+    fn assert_index_matches_scan(book: &OrderBook) {
+        let mut scanned: Vec<OrderId> = book
+            .orders
+            .values()
+            .filter(|o| o.is_active())
+            .map(|o| o.id)
+            .collect();
+        scanned.sort();
+        let mut indexed: Vec<OrderId> = book.active_orders().iter().map(|o| o.id).collect();
+        indexed.sort();
+        assert_eq!(indexed, scanned);
+        assert_eq!(book.active_count(), scanned.len());
+        assert_eq!(book.has_active_orders(), !scanned.is_empty());
+        assert!(book.active_by_symbol.values().all(|ids| !ids.is_empty()));
+    }
+
+    #[test]
+    fn active_index_tracks_every_transition() {
+        let mut book = OrderBook::new();
+        book.submit(moo_buy(1, 100.0));
+        book.submit(stop_sell(2, 95.0, 100.0));
+        book.submit(make_order(
+            3,
+            "QQQ",
+            OrderSide::Buy,
+            OrderType::MarketOnOpen,
+            10.0,
+        ));
+        book.submit(limit_buy(4, 90.0, 50.0));
+        assert_index_matches_scan(&book);
+
+        book.record_fill(OrderId(1), 40.0, 1).unwrap(); // partial stays active
+        assert_index_matches_scan(&book);
+        book.record_fill(OrderId(1), 60.0, 1).unwrap();
+        book.trigger(OrderId(2), 1).unwrap();
+        book.expire(OrderId(3), 1).unwrap();
+        assert_index_matches_scan(&book);
+
+        book.cancel_replace(OrderId(2), stop_sell(5, 96.0, 100.0), 2)
+            .unwrap();
+        book.cancel(OrderId(4), 2, "test").unwrap();
+        assert_index_matches_scan(&book);
+        assert_eq!(book.active_orders_for_symbol("SPY").len(), 1);
+        assert!(book.active_orders_for_symbol("QQQ").is_empty());
+
+        // Bracket: children enter the index only on activation; OCO
+        // cancellation removes the sibling
+        let mut stop = stop_sell(11, 90.0, 100.0);
+        stop.oco_group_id = Some(OcoGroupId(1));
+        let mut tp = make_order(
+            12,
+            "SPY",
+            OrderSide::Sell,
+            OrderType::Limit { limit_price: 120.0 },
+            100.0,
+        );
+        tp.oco_group_id = Some(OcoGroupId(1));
+        book.submit_bracket(moo_buy(10, 100.0), stop, Some(tp), OcoGroupId(1));
+        assert_index_matches_scan(&book);
+        book.record_fill(OrderId(10), 100.0, 3).unwrap();
+        assert_index_matches_scan(&book);
+        book.trigger(OrderId(11), 4).unwrap();
+        book.record_fill(OrderId(11), 100.0, 4).unwrap();
+        assert_index_matches_scan(&book);
+        assert!(!book.get(OrderId(12)).unwrap().is_active());
+    }
+
+    #[test]
+    fn active_lookup_ignores_history() {
+        let mut book = OrderBook::new();
+        for id in 0..1_000 {
+            book.submit(stop_sell(id, 95.0, 100.0));
+            book.cancel(OrderId(id), 0, "replaced").unwrap();
+        }
+        book.submit(stop_sell(1_000, 95.0, 100.0));
+        let active = book.active_orders_for_symbol("SPY");
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].id, OrderId(1_000));
+        assert_eq!(book.active_by_symbol["SPY"].len(), 1);
+    }
 }
@@ -0,0 +1,257 @@
+//! Per-bar portfolio snapshots — cash, positions and exposure behind equity.
+//!
+//! The equity curve keeps one number per bar; a snapshot keeps what it was
+//! made of (cash, each position's quantity and mark-to-market value, gross
+//! and net exposure), so cash drift or an exposure spike can be traced to a
+//! bar and the fills behind it. Recorded after the post-bar mark-to-market,
+//! with the same marks the equity curve uses.
+//!
+//! Off by default (`EngineConfig::portfolio_snapshots`): a snapshot with its
+//! positions on every bar is several times the size of the equity curve.
+//! [`SnapshotMode::OnChange`] keeps the first and last bar and every bar with
+//! a fill; cash and quantities only move on fills, so consecutive snapshots
+//! still reconcile against the fills between them.
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{Portfolio, PositionSide};
+
+/// Which bars get a portfolio snapshot.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotMode {
+    /// No snapshots (default).
+    #[default]
+    Off,
+    /// The first and last bar, and every bar with a fill.
+    OnChange,
+    /// Every bar.
+    EveryBar,
+}
+
+impl SnapshotMode {
+    pub fn is_enabled(self) -> bool {
+        self != Self::Off
+    }
+}
+
+/// One open position as it was marked at the end of a bar.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionSnapshot {
+    pub symbol: String,
+    pub side: PositionSide,
+    pub quantity: f64,
+    /// Price the position was marked at (last valid close).
+    pub mark: f64,
+    /// Value the equity identity counts for the position (`quantity * mark`,
+    /// negative for shorts).
+    pub market_value: f64,
+}
+
+/// The portfolio at the end of one bar.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioSnapshot {
+    pub bar_index: usize,
+    pub date: NaiveDate,
+    pub cash: f64,
+    /// Sum of the positions' `market_value`.
+    pub market_value: f64,
+    /// Equity recorded in the equity curve for this bar.
+    pub equity: f64,
+    /// Long plus short position value.
+    pub gross_exposure: f64,
+    /// Long minus short position value.
+    pub net_exposure: f64,
+    /// Open positions, sorted by symbol.
+    pub positions: Vec<PositionSnapshot>,
+}
+
+impl PortfolioSnapshot {
+    /// Snapshot `portfolio`, marking each position at `prices` (its entry
+    /// price when missing, as `Portfolio::equity` does).
+    pub fn capture(
+        bar_index: usize,
+        date: NaiveDate,
+        portfolio: &Portfolio,
+        prices: &HashMap<String, f64>,
+        equity: f64,
+    ) -> Self {
+        let mut positions: Vec<PositionSnapshot> = portfolio
+            .positions
+            .values()
+            .filter(|pos| !pos.is_flat())
+            .map(|pos| {
+                let mark = prices
+                    .get(&pos.symbol)
+                    .copied()
+                    .unwrap_or(pos.avg_entry_price);
+                PositionSnapshot {
+                    symbol: pos.symbol.clone(),
+                    side: pos.side,
+                    quantity: pos.quantity,
+                    mark,
+                    market_value: pos.market_value(mark),
+                }
+            })
+            .collect();
+        positions.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+
+        let value_on = |side: PositionSide| -> f64 {
+            positions
+                .iter()
+                .filter(|p| p.side == side)
+                .map(|p| p.market_value.abs())
+                .sum()
+        };
+        let (long, short) = (value_on(PositionSide::Long), value_on(PositionSide::Short));
+        Self {
+            bar_index,
+            date,
+            cash: portfolio.cash,
+            market_value: positions.iter().map(|p| p.market_value).sum(),
+            equity,
+            gross_exposure: long + short,
+            net_exposure: long - short,
+            positions,
+        }
+    }
+
+    /// `cash + market_value - equity`: zero up to rounding when the equity
+    /// accounting identity holds.
+    pub fn accounting_error(&self) -> f64 {
+        self.cash + self.market_value - self.equity
+    }
+
+    /// The open position in `symbol`, if any.
+    pub fn position(&self, symbol: &str) -> Option<&PositionSnapshot> {
+        self.positions.iter().find(|p| p.symbol == symbol)
+    }
+}
+
+/// Snapshots of a run, in bar order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotLog {
+    pub snapshots: Vec<PortfolioSnapshot>,
+}
+
+impl SnapshotLog {
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    /// The snapshot taken at `bar_index`, if that bar was recorded.
+    pub fn at_bar(&self, bar_index: usize) -> Option<&PortfolioSnapshot> {
+        self.snapshots
+            .binary_search_by_key(&bar_index, |s| s.bar_index)
+            .ok()
+            .map(|i| &self.snapshots[i])
+    }
+}
+
+/// Accumulates [`PortfolioSnapshot`]s as the bar loop runs.
+#[derive(Debug)]
+pub(crate) struct SnapshotRecorder {
+    mode: SnapshotMode,
+    snapshots: Vec<PortfolioSnapshot>,
+}
+
+impl SnapshotRecorder {
+    /// A recorder for `mode`, or None when snapshots are off.
+    pub fn new(mode: SnapshotMode) -> Option<Self> {
+        mode.is_enabled().then(|| Self {
+            mode,
+            snapshots: Vec::new(),
+        })
+    }
+
+    /// Whether bar `bar_index` of `num_bars` is recorded, given whether any
+    /// order filled on it.
+    pub fn wants(&self, bar_index: usize, num_bars: usize, filled: bool) -> bool {
+        match self.mode {
+            SnapshotMode::Off => false,
+            SnapshotMode::EveryBar => true,
+            SnapshotMode::OnChange => filled || bar_index == 0 || bar_index + 1 == num_bars,
+        }
+    }
+
+    pub fn push(&mut self, snapshot: PortfolioSnapshot) {
+        self.snapshots.push(snapshot);
+    }
+
+    pub fn finish(self) -> SnapshotLog {
+        SnapshotLog {
+            snapshots: self.snapshots,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Position;
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, 2).unwrap()
+    }
+
+    #[test]
+    fn capture_splits_long_and_short_exposure() {
+        let mut portfolio = Portfolio::new(50_000.0);
+        portfolio.positions.insert(
+            "SPY".into(),
+            Position::new_long("SPY".into(), 100.0, 400.0, 0),
+        );
+        portfolio.positions.insert(
+            "QQQ".into(),
+            Position::new_short("QQQ".into(), 20.0, 300.0, 0),
+        );
+        let prices = HashMap::from([("SPY".to_string(), 410.0)]);
+        let equity = portfolio.equity(&prices);
+
+        let snapshot = PortfolioSnapshot::capture(3, date(), &portfolio, &prices, equity);
+        // QQQ has no price: marked at its entry, like Portfolio::equity
+        let symbols: Vec<&str> = snapshot
+            .positions
+            .iter()
+            .map(|p| p.symbol.as_str())
+            .collect();
+        assert_eq!(symbols, ["QQQ", "SPY"]);
+        assert_eq!(snapshot.position("QQQ").unwrap().mark, 300.0);
+        assert_eq!(snapshot.market_value, 41_000.0 - 6_000.0);
+        assert_eq!(snapshot.gross_exposure, 47_000.0);
+        assert_eq!(snapshot.net_exposure, 35_000.0);
+        assert_eq!(snapshot.accounting_error(), 0.0);
+    }
+
+    #[test]
+    fn on_change_keeps_endpoints_and_fill_bars() {
+        assert!(SnapshotRecorder::new(SnapshotMode::Off).is_none());
+        let recorder = SnapshotRecorder::new(SnapshotMode::OnChange).unwrap();
+        let kept: Vec<usize> = (0..10).filter(|&t| recorder.wants(t, 10, t == 4)).collect();
+        assert_eq!(kept, [0, 4, 9]);
+
+        let every = SnapshotRecorder::new(SnapshotMode::EveryBar).unwrap();
+        assert!((0..10).all(|t| every.wants(t, 10, false)));
+    }
+
+    #[test]
+    fn at_bar_finds_recorded_bars_only() {
+        let portfolio = Portfolio::new(1_000.0);
+        let log = SnapshotLog {
+            snapshots: [0, 4, 9]
+                .map(|t| {
+                    PortfolioSnapshot::capture(t, date(), &portfolio, &HashMap::new(), 1_000.0)
+                })
+                .to_vec(),
+        };
+        assert_eq!(log.at_bar(4).unwrap().bar_index, 4);
+        assert!(log.at_bar(5).is_none());
+    }
+}
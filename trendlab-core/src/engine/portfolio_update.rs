@@ -2,8 +2,13 @@
 //!
 //! Handles position creation, position closure, realized PnL calculation,
 //! and cash accounting after fills.
+//!
+//! Under precise accounting (`Portfolio::precise_accounting`) every amount
+//! is settled in fixed-point micro-units, so cash and PnL no longer pick up
+//! rounding error that depends on the order and number of fills.
 
 use crate::domain::instrument::OrderSide;
+use crate::domain::money::Micros;
 use crate::domain::position::{Position, PositionSide};
 use crate::domain::{Fill, Portfolio};
 
@@ -16,27 +21,88 @@ use crate::domain::{Fill, Portfolio};
 /// The equity accounting identity (`equity == cash + positions`) must hold
 /// after every call.
 pub fn apply_fills(fills: &[Fill], portfolio: &mut Portfolio) {
+    let money = Money {
+        precise: portfolio.precise_accounting,
+    };
     for fill in fills {
         match fill.side {
-            OrderSide::Buy => apply_buy_fill(fill, portfolio),
-            OrderSide::Sell => apply_sell_fill(fill, portfolio),
+            OrderSide::Buy => apply_buy_fill(fill, portfolio, money),
+            OrderSide::Sell => apply_sell_fill(fill, portfolio, money),
+        }
+        portfolio.total_commission = money.add(portfolio.total_commission, fill.commission);
+        portfolio.total_slippage = money.add(portfolio.total_slippage, fill.slippage);
+    }
+}
+
+/// Arithmetic on monetary amounts: plain `f64` by default, micro-unit
+/// integers under precise accounting. Every precise result lies on the
+/// micro-unit grid, where it converts to and from `f64` exactly.
+#[derive(Clone, Copy)]
+struct Money {
+    precise: bool,
+}
+
+impl Money {
+    fn add(self, a: f64, b: f64) -> f64 {
+        if self.precise {
+            (Micros::from_f64(a) + Micros::from_f64(b)).to_f64()
+        } else {
+            a + b
+        }
+    }
+
+    fn sub(self, a: f64, b: f64) -> f64 {
+        if self.precise {
+            (Micros::from_f64(a) - Micros::from_f64(b)).to_f64()
+        } else {
+            a - b
+        }
+    }
+
+    fn times(self, price: f64, quantity: f64) -> f64 {
+        if self.precise {
+            Micros::from_f64(price).times(quantity).to_f64()
+        } else {
+            price * quantity
+        }
+    }
+
+    /// Volume-weighted average price of `total_cost` over `total_qty`.
+    fn average(self, total_cost: f64, total_qty: f64) -> f64 {
+        let avg = total_cost / total_qty;
+        if self.precise {
+            Micros::from_f64(avg).to_f64()
+        } else {
+            avg
+        }
+    }
+
+    /// Net cost of a buy fill, or net proceeds of a sell fill.
+    fn net_amount(self, fill: &Fill) -> f64 {
+        if !self.precise {
+            return fill.net_amount();
+        }
+        let gross = Micros::from_f64(fill.price).times(fill.quantity);
+        let costs = Micros::from_f64(fill.commission) + Micros::from_f64(fill.slippage);
+        match fill.side {
+            OrderSide::Buy => gross + costs,
+            OrderSide::Sell => gross - costs,
         }
-        portfolio.total_commission += fill.commission;
-        portfolio.total_slippage += fill.slippage;
+        .to_f64()
     }
 }
 
 /// Apply a buy fill: deduct cost from cash, create or add to position.
-fn apply_buy_fill(fill: &Fill, portfolio: &mut Portfolio) {
-    let cost = fill.net_amount(); // gross + commission + slippage
-    portfolio.cash -= cost;
+fn apply_buy_fill(fill: &Fill, portfolio: &mut Portfolio, money: Money) {
+    let cost = money.net_amount(fill); // gross + commission + slippage
+    portfolio.cash = money.sub(portfolio.cash, cost);
 
     if let Some(pos) = portfolio.positions.get_mut(&fill.symbol) {
         if pos.side == PositionSide::Short {
             // Covering a short position (reducing)
             let covered_qty = fill.quantity.min(pos.quantity);
-            let realized = (pos.avg_entry_price - fill.price) * covered_qty;
-            pos.realized_pnl += realized;
+            let realized = money.times(money.sub(pos.avg_entry_price, fill.price), covered_qty);
+            pos.realized_pnl = money.add(pos.realized_pnl, realized);
             pos.quantity -= covered_qty;
 
             if pos.quantity <= 1e-10 {
@@ -45,9 +111,12 @@ fn apply_buy_fill(fill: &Fill, portfolio: &mut Portfolio) {
             }
         } else if pos.side == PositionSide::Long {
             // Adding to a long position (averaging in)
-            let total_cost = pos.avg_entry_price * pos.quantity + fill.price * fill.quantity;
+            let total_cost = money.add(
+                money.times(pos.avg_entry_price, pos.quantity),
+                money.times(fill.price, fill.quantity),
+            );
             let total_qty = pos.quantity + fill.quantity;
-            pos.avg_entry_price = total_cost / total_qty;
+            pos.avg_entry_price = money.average(total_cost, total_qty);
             pos.quantity = total_qty;
         } else {
             // Flat → open new long
@@ -73,16 +142,16 @@ fn apply_buy_fill(fill: &Fill, portfolio: &mut Portfolio) {
 }
 
 /// Apply a sell fill: add proceeds to cash, reduce or close position.
-fn apply_sell_fill(fill: &Fill, portfolio: &mut Portfolio) {
-    let proceeds = fill.net_amount(); // gross - commission - slippage
-    portfolio.cash += proceeds;
+fn apply_sell_fill(fill: &Fill, portfolio: &mut Portfolio, money: Money) {
+    let proceeds = money.net_amount(fill); // gross - commission - slippage
+    portfolio.cash = money.add(portfolio.cash, proceeds);
 
     if let Some(pos) = portfolio.positions.get_mut(&fill.symbol) {
         if pos.side == PositionSide::Long {
             // Selling from a long position (reducing)
             let sold_qty = fill.quantity.min(pos.quantity);
-            let realized = (fill.price - pos.avg_entry_price) * sold_qty;
-            pos.realized_pnl += realized;
+            let realized = money.times(money.sub(fill.price, pos.avg_entry_price), sold_qty);
+            pos.realized_pnl = money.add(pos.realized_pnl, realized);
             pos.quantity -= sold_qty;
 
             if pos.quantity <= 1e-10 {
@@ -91,9 +160,12 @@ fn apply_sell_fill(fill: &Fill, portfolio: &mut Portfolio) {
             }
         } else if pos.side == PositionSide::Short {
             // Adding to a short position
-            let total_cost = pos.avg_entry_price * pos.quantity + fill.price * fill.quantity;
+            let total_cost = money.add(
+                money.times(pos.avg_entry_price, pos.quantity),
+                money.times(fill.price, fill.quantity),
+            );
             let total_qty = pos.quantity + fill.quantity;
-            pos.avg_entry_price = total_cost / total_qty;
+            pos.avg_entry_price = money.average(total_cost, total_qty);
             pos.quantity = total_qty;
         } else {
             // Flat → open new short
@@ -123,6 +195,7 @@ mod tests {
     use super::*;
     use crate::domain::fill::FillPhase;
     use crate::domain::ids::OrderId;
+    use crate::domain::money::round_to_micros;
     use chrono::NaiveDate;
 
     fn buy_fill(symbol: &str, price: f64, qty: f64) -> Fill {
@@ -137,6 +210,8 @@ mod tests {
             commission: 0.0,
             slippage: 0.0,
             phase: FillPhase::StartOfBar,
+            trigger_margin: None,
+            decision_source: None,
         }
     }
 
@@ -152,6 +227,8 @@ mod tests {
             commission: 0.0,
             slippage: 0.0,
             phase: FillPhase::Intrabar,
+            trigger_margin: None,
+            decision_source: None,
         }
     }
 
@@ -268,4 +345,106 @@ mod tests {
         // All cash now: 90000 + 105*100 = 100500
         assert!((equity_after - 100_500.0).abs() < 1e-10);
     }
+
+    /// Round trips across many symbols, priced and costed on the
+    /// micro-unit grid the way precise accounting settles fills.
+    fn round_trips(n: usize) -> Vec<Fill> {
+        let mut fills = Vec::with_capacity(2 * n);
+        for i in 0..n {
+            let symbol = format!("S{i}");
+            let qty = (1 + i % 17) as f64;
+            let entry = round_to_micros(50.0 + (i % 997) as f64 * 0.37 + (i % 13) as f64 * 1e-6);
+            let exit = round_to_micros(entry * (0.9 + (i % 23) as f64 * 0.01));
+            let mut buy = buy_fill(&symbol, entry, qty);
+            buy.commission = round_to_micros(0.35 + (i % 29) as f64 * 0.001_37);
+            let mut sell = sell_fill(&symbol, exit, qty);
+            sell.slippage = round_to_micros(exit * qty * 3e-4);
+            fills.push(buy);
+            fills.push(sell);
+        }
+        fills
+    }
+
+    /// Initial capital plus every fill's cash flow, in integer micro-units.
+    fn exact_cash(initial: f64, fills: &[Fill]) -> Micros {
+        let flows: Micros = fills
+            .iter()
+            .map(|f| {
+                let gross = Micros(Micros::from_f64(f.price).0 * f.quantity as i64);
+                let costs = Micros::from_f64(f.commission) + Micros::from_f64(f.slippage);
+                match f.side {
+                    OrderSide::Buy => -(gross + costs),
+                    OrderSide::Sell => gross - costs,
+                }
+            })
+            .sum();
+        Micros::from_f64(initial) + flows
+    }
+
+    fn settle(fills: &[Fill], precise: bool) -> Portfolio {
+        let mut portfolio = Portfolio::new(1_000_000.0);
+        portfolio.precise_accounting = precise;
+        apply_fills(fills, &mut portfolio);
+        portfolio
+    }
+
+    fn realized(portfolio: &Portfolio) -> Micros {
+        portfolio
+            .positions
+            .values()
+            .map(|p| Micros::from_f64(p.realized_pnl))
+            .sum()
+    }
+
+    #[test]
+    fn float_accounting_drifts_with_fill_order() {
+        let fills = round_trips(10_000);
+        let reversed: Vec<Fill> = fills.iter().rev().cloned().collect();
+        let exact = exact_cash(1_000_000.0, &fills).to_f64();
+
+        let forward = settle(&fills, false);
+        let backward = settle(&reversed, false);
+        assert_ne!(forward.cash, backward.cash);
+        assert_ne!(forward.cash, exact);
+        // The drift is tiny, but it is there
+        assert!((forward.cash - exact).abs() < 1e-3);
+    }
+
+    #[test]
+    fn precise_accounting_is_exact_in_any_order() {
+        let fills = round_trips(10_000);
+        let reversed: Vec<Fill> = fills.iter().rev().cloned().collect();
+        let exact = exact_cash(1_000_000.0, &fills);
+
+        let forward = settle(&fills, true);
+        let backward = settle(&reversed, true);
+        assert_eq!(forward.cash, exact.to_f64());
+        assert_eq!(backward.cash, exact.to_f64());
+        assert_eq!(forward.total_commission, backward.total_commission);
+        assert_eq!(forward.total_slippage, backward.total_slippage);
+        assert_eq!(realized(&forward), realized(&backward));
+
+        // Every position is closed: cash moved by realized PnL less costs
+        let costs =
+            Micros::from_f64(forward.total_commission) + Micros::from_f64(forward.total_slippage);
+        assert_eq!(
+            exact - Micros::from_f64(1_000_000.0),
+            realized(&forward) - costs
+        );
+    }
+
+    #[test]
+    fn float_mode_matches_plain_arithmetic() {
+        let mut portfolio = Portfolio::new(100_000.0);
+        let mut buy = buy_fill("SPY", 100.123_456_7, 33.0);
+        buy.commission = 0.123_456_7;
+        apply_fills(&[buy.clone()], &mut portfolio);
+        assert_eq!(portfolio.cash, 100_000.0 - buy.net_amount());
+
+        let mut precise = Portfolio::new(100_000.0);
+        precise.precise_accounting = true;
+        apply_fills(&[buy], &mut precise);
+        assert_eq!(precise.cash, round_to_micros(precise.cash));
+        assert!((precise.cash - portfolio.cash).abs() < 1e-4);
+    }
 }
@@ -2,9 +2,13 @@
 //!
 //! All indicators are computed once before the bar loop begins.
 //! Results are stored in `IndicatorValues` containers, one per symbol.
+//! Multi-output indicators fill every series they list in `outputs()` from a
+//! single `compute_all` pass.
 
 use crate::components::indicator::{Indicator, IndicatorValues};
+use crate::data::frame::BarFrame;
 use crate::domain::Bar;
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 /// Precompute all indicators for all symbols before the bar loop.
@@ -15,27 +19,176 @@ pub fn precompute_indicators(
     bars_by_symbol: &HashMap<String, Vec<Bar>>,
     indicators: &[Box<dyn Indicator>],
 ) -> HashMap<String, IndicatorValues> {
-    let mut result = HashMap::new();
+    bars_by_symbol
+        .iter()
+        .map(|(symbol, bars)| (symbol.clone(), symbol_values(symbol, bars, indicators)))
+        .collect()
+}
+
+/// Same as [`precompute_indicators`], on the engine's columnar frames.
+///
+/// Rows are materialized for one symbol at a time and dropped once its
+/// series are computed, so a run never holds a `Vec<Bar>` for every symbol.
+/// With a `cache`, a symbol whose series are all cached is not materialized
+/// at all.
+pub fn precompute_frame_indicators(
+    frames: &HashMap<String, BarFrame>,
+    indicators: &[Box<dyn Indicator>],
+    cache: Option<&mut IndicatorCache>,
+) -> HashMap<String, IndicatorValues> {
+    match cache {
+        Some(cache) => frames
+            .iter()
+            .map(|(symbol, frame)| {
+                let rows = || Cow::Owned(frame.to_bars());
+                let iv = symbol_values_cached(symbol, frame.len(), rows, indicators, cache);
+                (symbol.clone(), iv)
+            })
+            .collect(),
+        None => frames
+            .iter()
+            .map(|(symbol, frame)| {
+                let bars = frame.to_bars();
+                (symbol.clone(), symbol_values(symbol, &bars, indicators))
+            })
+            .collect(),
+    }
+}
 
-    for (symbol, bars) in bars_by_symbol {
-        let mut iv = IndicatorValues::new();
-        for indicator in indicators {
-            let series = indicator.compute(bars);
+fn symbol_values(symbol: &str, bars: &[Bar], indicators: &[Box<dyn Indicator>]) -> IndicatorValues {
+    let mut iv = IndicatorValues::new();
+    for indicator in indicators {
+        let outputs = indicator.outputs();
+        let all = indicator.compute_all(bars);
+        debug_assert_eq!(
+            all.len(),
+            outputs.len(),
+            "indicator '{}' produced {} series for {} outputs",
+            indicator.name(),
+            all.len(),
+            outputs.len()
+        );
+        for (name, series) in outputs.into_iter().zip(all) {
             debug_assert_eq!(
                 series.len(),
                 bars.len(),
                 "indicator '{}' produced {} values for {} bars (symbol={})",
-                indicator.name(),
+                name,
                 series.len(),
                 bars.len(),
                 symbol
             );
-            iv.insert(indicator.name(), series);
+            iv.insert(name, series);
         }
-        result.insert(symbol.clone(), iv);
+    }
+    iv
+}
+
+/// Indicator series memoized across runs on the same bar data.
+///
+/// Output names encode their parameters (`sma_50`, `atr_14`), so a
+/// `(symbol, name)` key identifies a series uniquely for one dataset. A cache
+/// must never be shared between different bar slices — create one per slice.
+#[derive(Debug, Default)]
+pub struct IndicatorCache {
+    series: HashMap<(String, String), Vec<f64>>,
+    hits: usize,
+    misses: usize,
+}
+
+impl IndicatorCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lookups served from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits
     }
 
-    result
+    /// Lookups that had to compute the series.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Number of cached series.
+    pub fn len(&self) -> usize {
+        self.series.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.series.is_empty()
+    }
+
+    /// Drop every cached series. Hit and miss counts are kept.
+    pub fn clear(&mut self) {
+        self.series.clear();
+    }
+}
+
+/// Same as [`precompute_indicators`], but serves repeated indicators from `cache`.
+///
+/// Used when many parameter variants run on one dataset (grid search,
+/// walk-forward optimization): an ATR(14) shared by every grid point is
+/// computed once.
+pub fn precompute_indicators_cached(
+    bars_by_symbol: &HashMap<String, Vec<Bar>>,
+    indicators: &[Box<dyn Indicator>],
+    cache: &mut IndicatorCache,
+) -> HashMap<String, IndicatorValues> {
+    bars_by_symbol
+        .iter()
+        .map(|(symbol, bars)| {
+            let rows = || Cow::Borrowed(bars.as_slice());
+            let iv = symbol_values_cached(symbol, bars.len(), rows, indicators, cache);
+            (symbol.clone(), iv)
+        })
+        .collect()
+}
+
+/// One symbol's series through `cache`. `rows` is only called on a miss.
+fn symbol_values_cached<'a>(
+    symbol: &str,
+    len: usize,
+    rows: impl Fn() -> Cow<'a, [Bar]>,
+    indicators: &[Box<dyn Indicator>],
+    cache: &mut IndicatorCache,
+) -> IndicatorValues {
+    let mut bars: Option<Cow<'a, [Bar]>> = None;
+    let mut iv = IndicatorValues::new();
+    for indicator in indicators {
+        let outputs = indicator.outputs();
+        let cached: Option<Vec<Vec<f64>>> = outputs
+            .iter()
+            .map(|name| {
+                cache
+                    .series
+                    .get(&(symbol.to_string(), name.to_string()))
+                    .filter(|series| series.len() == len)
+                    .cloned()
+            })
+            .collect();
+        let all = match cached {
+            Some(all) => {
+                cache.hits += 1;
+                all
+            }
+            None => {
+                cache.misses += 1;
+                let all = indicator.compute_all(bars.get_or_insert_with(&rows));
+                for (name, series) in outputs.iter().zip(&all) {
+                    cache
+                        .series
+                        .insert((symbol.to_string(), name.to_string()), series.clone());
+                }
+                all
+            }
+        };
+        for (name, series) in outputs.into_iter().zip(all) {
+            iv.insert(name, series);
+        }
+    }
+    iv
 }
 
 /// Compute the warmup length from a set of indicators.
@@ -49,7 +202,8 @@ pub fn compute_warmup(indicators: &[Box<dyn Indicator>]) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::indicators::{make_bars, Ema, Sma};
+    use crate::indicators::{make_bars, Aroon, Donchian, Ema, Sma};
+    use std::time::Instant;
 
     #[test]
     fn precompute_single_symbol_single_indicator() {
@@ -122,4 +276,115 @@ mod tests {
         let indicators: Vec<Box<dyn Indicator>> = vec![];
         assert_eq!(compute_warmup(&indicators), 0);
     }
+
+    #[test]
+    fn cached_precompute_reuses_series() {
+        let bars = make_bars(&[10.0, 11.0, 12.0, 13.0, 14.0]);
+        let mut bars_by_symbol = HashMap::new();
+        bars_by_symbol.insert("SPY".to_string(), bars);
+
+        let mut cache = IndicatorCache::new();
+        let first: Vec<Box<dyn Indicator>> = vec![Box::new(Sma::new(3))];
+        let second: Vec<Box<dyn Indicator>> = vec![Box::new(Sma::new(3)), Box::new(Ema::new(3))];
+
+        let a = precompute_indicators_cached(&bars_by_symbol, &first, &mut cache);
+        let b = precompute_indicators_cached(&bars_by_symbol, &second, &mut cache);
+
+        assert_eq!(cache.misses(), 2);
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(a["SPY"].get("sma_3", 4), b["SPY"].get("sma_3", 4));
+
+        let uncached = precompute_indicators(&bars_by_symbol, &second);
+        assert_eq!(uncached["SPY"].get("ema_3", 4), b["SPY"].get("ema_3", 4));
+    }
+
+    #[test]
+    fn multi_output_indicator_fills_every_band_key() {
+        let bars = make_bars(&[10.0, 12.0, 11.0, 14.0, 13.0, 15.0]);
+        let mut bars_by_symbol = HashMap::new();
+        bars_by_symbol.insert("SPY".to_string(), bars);
+
+        let channel: Vec<Box<dyn Indicator>> =
+            vec![Box::new(Donchian::channel(3)), Box::new(Aroon::both(2))];
+        let per_band: Vec<Box<dyn Indicator>> = vec![
+            Box::new(Donchian::upper(3)),
+            Box::new(Donchian::lower(3)),
+            Box::new(Aroon::up(2)),
+            Box::new(Aroon::down(2)),
+        ];
+        let a = precompute_indicators(&bars_by_symbol, &channel);
+        let b = precompute_indicators(&bars_by_symbol, &per_band);
+
+        assert_eq!(a["SPY"].len(), 4);
+        for ind in &per_band {
+            let name = ind.name();
+            let (x, y) = (a["SPY"].get(name, 5), b["SPY"].get(name, 5));
+            assert!(x.is_some() && x == y, "{name}: {x:?} vs {y:?}");
+        }
+
+        // The cache stores each band and serves the whole channel on a hit.
+        let mut cache = IndicatorCache::new();
+        precompute_indicators_cached(&bars_by_symbol, &channel, &mut cache);
+        let c = precompute_indicators_cached(&bars_by_symbol, &channel, &mut cache);
+        assert_eq!(cache.len(), 4);
+        assert_eq!((cache.misses(), cache.hits()), (2, 2));
+        assert_eq!(
+            c["SPY"].get("donchian_lower_3", 5),
+            b["SPY"].get("donchian_lower_3", 5)
+        );
+    }
+
+    /// Benchmark: a Donchian-heavy composition (both bands over several
+    /// lookbacks) precomputes faster from channel instances than from
+    /// per-band instances, with identical series.
+    #[test]
+    fn donchian_channels_precompute_faster_than_per_band_instances() {
+        let closes: Vec<f64> = (0..5_000)
+            .map(|i| 100.0 + 20.0 * (i as f64 * 0.05).sin() + (i % 11) as f64)
+            .collect();
+        let mut bars_by_symbol = HashMap::new();
+        bars_by_symbol.insert("SPY".to_string(), make_bars(&closes));
+
+        let periods = [20, 55, 100, 252];
+        let per_band: Vec<Box<dyn Indicator>> = periods
+            .iter()
+            .flat_map(|&p| -> [Box<dyn Indicator>; 2] {
+                [Box::new(Donchian::upper(p)), Box::new(Donchian::lower(p))]
+            })
+            .collect();
+        let channels: Vec<Box<dyn Indicator>> = periods
+            .iter()
+            .map(|&p| Box::new(Donchian::channel(p)) as Box<dyn Indicator>)
+            .collect();
+
+        let best_of = |indicators: &[Box<dyn Indicator>]| {
+            (0..3)
+                .map(|_| {
+                    let start = Instant::now();
+                    let values = precompute_indicators(&bars_by_symbol, indicators);
+                    (start.elapsed(), values)
+                })
+                .min_by_key(|(elapsed, _)| *elapsed)
+                .unwrap()
+        };
+        let (per_band_time, per_band_values) = best_of(&per_band);
+        let (channel_time, channel_values) = best_of(&channels);
+
+        let (old, new) = (&per_band_values["SPY"], &channel_values["SPY"]);
+        assert_eq!(old.len(), new.len());
+        for name in old.names() {
+            let (x, y) = (old.get_series(name).unwrap(), new.get_series(name).unwrap());
+            assert!(
+                x.iter()
+                    .zip(y)
+                    .all(|(a, b)| a == b || (a.is_nan() && b.is_nan())),
+                "{name} differs"
+            );
+        }
+        assert!(
+            channel_time < per_band_time,
+            "channel {channel_time:?} vs per-band {per_band_time:?}"
+        );
+    }
 }
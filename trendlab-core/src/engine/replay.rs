@@ -0,0 +1,315 @@
+//! Bar-by-bar replay of each position's holding window.
+//!
+//! While a position is open the engine records, at the end of every bar, the
+//! bar's prices, the stop the position manager left in place, the working
+//! orders, portfolio equity and every indicator value. The bar a position
+//! closes on is recorded too, carrying the stop that was working into it, so
+//! a replay shows exactly which level the exit went through. Disabled via
+//! `EngineConfig::record_replay` (YOLO sweeps keep it off: the log is several
+//! times the size of the equity curve).
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::components::indicator::IndicatorValues;
+use crate::domain::{Bar, Order, OrderSide, OrderType, Position, PositionSide, TradeRecord};
+
+/// A working order as it stood at the end of a bar.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderSnapshot {
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub quantity: f64,
+}
+
+impl OrderSnapshot {
+    pub fn from_order(order: &Order) -> Self {
+        Self {
+            side: order.side,
+            order_type: order.order_type.clone(),
+            quantity: order.remaining_quantity(),
+        }
+    }
+
+    /// Short label such as "sell stop @ 98.50".
+    pub fn describe(&self) -> String {
+        let side = match self.side {
+            OrderSide::Buy => "buy",
+            OrderSide::Sell => "sell",
+        };
+        match &self.order_type {
+            OrderType::MarketOnOpen => format!("{side} MOO"),
+            OrderType::MarketOnClose => format!("{side} MOC"),
+            OrderType::MarketImmediate => format!("{side} market"),
+            OrderType::StopMarket { trigger_price } => format!("{side} stop @ {trigger_price:.2}"),
+            OrderType::Limit { limit_price } => format!("{side} limit @ {limit_price:.2}"),
+            OrderType::StopLimit {
+                trigger_price,
+                limit_price,
+            } => format!("{side} stop {trigger_price:.2} / limit {limit_price:.2}"),
+        }
+    }
+}
+
+/// One recorded bar of a position's holding window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayBar {
+    pub bar_index: usize,
+    pub date: NaiveDate,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    /// Stop level in force after the position manager ran on this bar (on
+    /// the exit bar: the stop that was working into it).
+    pub stop: Option<f64>,
+    /// Orders still working for the symbol at the end of the bar.
+    pub orders: Vec<OrderSnapshot>,
+    /// Portfolio equity at the bar's close.
+    pub equity: f64,
+    /// Every finite indicator value for the symbol at this bar.
+    pub indicators: BTreeMap<String, f64>,
+    /// True on the bar the position closed.
+    #[serde(default)]
+    pub exit: bool,
+}
+
+/// The recorded holding window of one position.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionReplay {
+    pub symbol: String,
+    pub side: PositionSide,
+    pub entry_bar: usize,
+    /// Net PnL of the position's trades (None while the position is open).
+    #[serde(default)]
+    pub net_pnl: Option<f64>,
+    pub bars: Vec<ReplayBar>,
+}
+
+impl PositionReplay {
+    /// Names of the indicators recorded on any bar, sorted.
+    pub fn indicator_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .bars
+            .iter()
+            .flat_map(|b| b.indicators.keys().cloned())
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    pub fn is_loser(&self) -> bool {
+        self.net_pnl.is_some_and(|pnl| pnl < 0.0)
+    }
+
+    /// Index into `bars` of the exit bar (the last bar if still open).
+    pub fn exit_index(&self) -> usize {
+        self.bars
+            .iter()
+            .position(|b| b.exit)
+            .unwrap_or(self.bars.len().saturating_sub(1))
+    }
+}
+
+/// Replays of every position a run opened, in the order they opened.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReplayLog {
+    pub positions: Vec<PositionReplay>,
+}
+
+impl ReplayLog {
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// The replay of the position a trade closed, keyed like stickiness
+    /// scores by symbol and entry bar.
+    pub fn for_trade(&self, symbol: &str, entry_bar: usize) -> Option<&PositionReplay> {
+        self.positions
+            .iter()
+            .find(|p| p.symbol == symbol && p.entry_bar == entry_bar)
+    }
+}
+
+/// Accumulates [`PositionReplay`]s as the bar loop runs.
+#[derive(Debug, Default)]
+pub(crate) struct ReplayRecorder {
+    open: HashMap<String, PositionReplay>,
+    closed: Vec<PositionReplay>,
+}
+
+impl ReplayRecorder {
+    /// Record one symbol's end-of-bar state. `position` is the symbol's
+    /// position after PM maintenance, if any.
+    pub fn record(
+        &mut self,
+        bar: &Bar,
+        bar_index: usize,
+        position: Option<&Position>,
+        orders: &[&Order],
+        equity: f64,
+        indicators: &IndicatorValues,
+    ) {
+        let symbol: &str = &bar.symbol;
+        let position = position.filter(|p| !p.is_flat());
+        let entry_bar = position.map(|p| p.entry_bar);
+
+        let closed = self
+            .open
+            .get(symbol)
+            .is_some_and(|open| Some(open.entry_bar) != entry_bar);
+        if closed {
+            let mut replay = self.open.remove(symbol).expect("checked above");
+            let stop = replay.bars.last().and_then(|b| b.stop);
+            let mut exit = snapshot(bar, bar_index, stop, orders, equity, indicators);
+            exit.exit = true;
+            replay.bars.push(exit);
+            self.closed.push(replay);
+        }
+
+        let Some(pos) = position else { return };
+        if bar.is_void() {
+            return;
+        }
+        let replay = self
+            .open
+            .entry(symbol.to_string())
+            .or_insert_with(|| PositionReplay {
+                symbol: symbol.to_string(),
+                side: pos.side,
+                entry_bar: pos.entry_bar,
+                net_pnl: None,
+                bars: Vec::new(),
+            });
+        replay.bars.push(snapshot(
+            bar,
+            bar_index,
+            pos.current_stop,
+            orders,
+            equity,
+            indicators,
+        ));
+    }
+
+    /// Close out the log, attaching each closed position's trade PnL.
+    /// Positions still open end on their last bar.
+    pub fn finish(mut self, trades: &[TradeRecord]) -> ReplayLog {
+        let mut positions = self.closed;
+        for replay in &mut positions {
+            // Partial exits split one position into several trades
+            replay.net_pnl = trades
+                .iter()
+                .filter(|t| t.symbol == replay.symbol && t.entry_bar == replay.entry_bar)
+                .map(|t| t.net_pnl)
+                .reduce(|a, b| a + b);
+        }
+        positions.extend(self.open.drain().map(|(_, replay)| replay));
+        positions.sort_by(|a, b| (a.entry_bar, &a.symbol).cmp(&(b.entry_bar, &b.symbol)));
+        ReplayLog { positions }
+    }
+}
+
+fn snapshot(
+    bar: &Bar,
+    bar_index: usize,
+    stop: Option<f64>,
+    orders: &[&Order],
+    equity: f64,
+    indicators: &IndicatorValues,
+) -> ReplayBar {
+    ReplayBar {
+        bar_index,
+        date: bar.date,
+        open: bar.open,
+        high: bar.high,
+        low: bar.low,
+        close: bar.close,
+        stop,
+        orders: orders
+            .iter()
+            .map(|o| OrderSnapshot::from_order(o))
+            .collect(),
+        equity,
+        indicators: indicators
+            .names()
+            .filter_map(|name| {
+                let value = indicators.get(name, bar_index)?;
+                value.is_finite().then(|| (name.to_string(), value))
+            })
+            .collect(),
+        exit: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn bar(day: u32, close: f64) -> Bar {
+        Bar {
+            symbol: Arc::from("SPY"),
+            date: NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+            open: close,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+            volume: 1000,
+            adj_close: close,
+        }
+    }
+
+    #[test]
+    fn records_holding_window_and_exit_bar() {
+        let mut indicators = IndicatorValues::new();
+        indicators.insert("atr_14", vec![f64::NAN, 2.0, 2.1, 2.2]);
+        let mut recorder = ReplayRecorder::default();
+        let mut pos = Position::new_long("SPY".into(), 10.0, 100.0, 1);
+
+        recorder.record(&bar(1, 100.0), 0, None, &[], 1000.0, &indicators);
+        pos.current_stop = Some(95.0);
+        recorder.record(&bar(2, 101.0), 1, Some(&pos), &[], 1010.0, &indicators);
+        pos.current_stop = Some(97.0);
+        recorder.record(&bar(3, 103.0), 2, Some(&pos), &[], 1030.0, &indicators);
+        recorder.record(&bar(4, 96.0), 3, None, &[], 970.0, &indicators);
+
+        let log = recorder.finish(&[]);
+        let replay = log.for_trade("SPY", 1).unwrap();
+        let stops: Vec<_> = replay.bars.iter().map(|b| b.stop).collect();
+        assert_eq!(stops, vec![Some(95.0), Some(97.0), Some(97.0)]);
+        assert_eq!(replay.exit_index(), 2);
+        assert!(replay.bars[2].exit);
+        assert_eq!(replay.indicator_names(), vec!["atr_14".to_string()]);
+        assert!(log.for_trade("SPY", 2).is_none());
+    }
+
+    #[test]
+    fn open_position_ends_on_its_last_bar() {
+        let indicators = IndicatorValues::new();
+        let mut recorder = ReplayRecorder::default();
+        let pos = Position::new_long("SPY".into(), 10.0, 100.0, 0);
+        recorder.record(&bar(1, 100.0), 0, Some(&pos), &[], 1000.0, &indicators);
+        recorder.record(&bar(2, 101.0), 1, Some(&pos), &[], 1010.0, &indicators);
+
+        let log = recorder.finish(&[]);
+        let replay = log.for_trade("SPY", 0).unwrap();
+        assert_eq!(replay.bars.len(), 2);
+        assert_eq!(replay.exit_index(), 1);
+        assert!(replay.bars.iter().all(|b| !b.exit));
+    }
+
+    #[test]
+    fn order_snapshot_labels() {
+        let stop = OrderSnapshot {
+            side: OrderSide::Sell,
+            order_type: OrderType::StopMarket {
+                trigger_price: 98.5,
+            },
+            quantity: 10.0,
+        };
+        assert_eq!(stop.describe(), "sell stop @ 98.50");
+    }
+}
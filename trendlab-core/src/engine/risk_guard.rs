@@ -0,0 +1,120 @@
+//! Risk guard — a position size cap and a max-drawdown kill switch.
+//!
+//! - `max_position_pct`: caps the fraction of equity a single entry may use,
+//!   whatever `EngineConfig::position_size_pct` asks for.
+//! - `max_drawdown`: once equity closes this fraction below its running peak,
+//!   every open position is flattened at the next open (decision source
+//!   `RiskGuardFlatten`), pending entries are cancelled, and the run takes no
+//!   further entries.
+//!
+//! Like entry governance, the rules travel as flat params on the signal
+//! component (see [`RiskGuard::from_params`]), so they enter `full_hash`
+//! fingerprints. Configs without them are unguarded.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+// ─── Param keys ──────────────────────────────────────────────────────
+
+/// Largest fraction of equity a single entry may use (0 or absent = no cap).
+pub const PARAM_MAX_POSITION_PCT: &str = "max_position_pct";
+/// Drawdown from peak equity that halts the run (0 or absent = never halts).
+pub const PARAM_MAX_DRAWDOWN_HALT: &str = "max_drawdown_halt";
+
+// ─── Rules ───────────────────────────────────────────────────────────
+
+/// Risk guard rules. The default guards nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RiskGuard {
+    /// `None` = position size is not capped.
+    pub max_position_pct: Option<f64>,
+    /// Fraction of peak equity, e.g. 0.15 halts at a 15% drawdown.
+    /// `None` = no kill switch.
+    pub max_drawdown: Option<f64>,
+}
+
+impl RiskGuard {
+    /// True when either rule is set.
+    pub fn is_active(&self) -> bool {
+        self.max_position_pct.is_some() || self.max_drawdown.is_some()
+    }
+
+    /// Decode from signal params. Missing or non-positive values leave the rule off.
+    pub fn from_params(params: &BTreeMap<String, f64>) -> Self {
+        let get = |k: &str| params.get(k).copied().filter(|v| v.is_finite() && *v > 0.0);
+        Self {
+            max_position_pct: get(PARAM_MAX_POSITION_PCT),
+            max_drawdown: get(PARAM_MAX_DRAWDOWN_HALT),
+        }
+    }
+
+    /// Encode into signal params (inverse of [`Self::from_params`]).
+    ///
+    /// Rules that are off are removed, so an unguarded config hashes as before.
+    pub fn write_params(&self, params: &mut BTreeMap<String, f64>) {
+        for (key, value) in [
+            (PARAM_MAX_POSITION_PCT, self.max_position_pct),
+            (PARAM_MAX_DRAWDOWN_HALT, self.max_drawdown),
+        ] {
+            match value {
+                Some(v) => params.insert(key.into(), v),
+                None => params.remove(key),
+            };
+        }
+    }
+
+    /// Fraction of equity an entry uses under the cap.
+    pub fn position_size(&self, position_size_pct: f64) -> f64 {
+        match self.max_position_pct {
+            Some(cap) => position_size_pct.min(cap),
+            None => position_size_pct,
+        }
+    }
+
+    /// True once `equity` has fallen `max_drawdown` or more below `peak`.
+    pub fn drawdown_breached(&self, peak: f64, equity: f64) -> bool {
+        self.max_drawdown
+            .is_some_and(|limit| peak > 0.0 && equity <= peak * (1.0 - limit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_is_inactive_and_guards_nothing() {
+        let guard = RiskGuard::default();
+        assert!(!guard.is_active());
+        assert_eq!(guard.position_size(1.0), 1.0);
+        assert!(!guard.drawdown_breached(100.0, 1.0));
+    }
+
+    #[test]
+    fn caps_size_and_halts_at_the_drawdown_limit() {
+        let guard = RiskGuard {
+            max_position_pct: Some(0.5),
+            max_drawdown: Some(0.2),
+        };
+        assert_eq!(guard.position_size(1.0), 0.5);
+        assert_eq!(guard.position_size(0.3), 0.3);
+        assert!(!guard.drawdown_breached(100.0, 80.1));
+        assert!(guard.drawdown_breached(100.0, 80.0));
+    }
+
+    #[test]
+    fn params_round_trip_and_off_rules_are_removed() {
+        let guard = RiskGuard {
+            max_position_pct: Some(0.5),
+            max_drawdown: Some(0.15),
+        };
+        let mut params = BTreeMap::new();
+        guard.write_params(&mut params);
+        assert_eq!(RiskGuard::from_params(&params), guard);
+
+        RiskGuard::default().write_params(&mut params);
+        assert!(params.is_empty());
+    }
+}
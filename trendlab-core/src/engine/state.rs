@@ -1,20 +1,35 @@
 //! Engine configuration, mutable state, and run result types.
 
+use crate::components::filter::IntentIntervention;
 use crate::components::signal::{SignalEvaluation, SignalEvent};
 use crate::domain::ids::IdGen;
-use crate::domain::{Fill, Instrument, OrderId, Portfolio, TradeRecord};
+use crate::domain::{
+    DecisionSource, Fill, Instrument, OrderAuditEntry, OrderId, Portfolio, PositionSide,
+    TradeRecord,
+};
+use crate::engine::attribution::PnlAttribution;
+use crate::engine::entry_governance::EntryGovernance;
 use crate::engine::execution::ExecutionConfig;
 use crate::engine::order_book::OrderBook;
-use crate::engine::stickiness::StickinessMetrics;
+use crate::engine::portfolio_snapshot::{SnapshotLog, SnapshotMode};
+use crate::engine::replay::ReplayLog;
+use crate::engine::risk_guard::RiskGuard;
+use crate::engine::stickiness::{PmCallCounts, StickinessReport};
+use crate::engine::timings::EngineTimings;
+use crate::engine::warmup::{WarmupPolicy, WarmupReport};
 use crate::fingerprint::TradingMode;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
 /// Configuration for a single backtest run.
 #[derive(Debug, Clone)]
 pub struct EngineConfig {
     pub initial_capital: f64,
-    /// Computed from max indicator lookback. No signals/orders during warmup.
+    /// Minimum warmup; the indicator lookbacks can extend it. No
+    /// signals/orders during warmup.
     pub warmup_bars: usize,
+    /// Lookback multiplier and override (default: plain lookbacks).
+    pub warmup: WarmupPolicy,
     pub trading_mode: TradingMode,
     /// Execution engine configuration (path policy, gap policy, cost model, liquidity).
     pub execution_config: ExecutionConfig,
@@ -22,6 +37,16 @@ pub struct EngineConfig {
     pub instruments: HashMap<String, Instrument>,
     /// Fraction of equity to allocate per position (default 1.0 = 100%).
     pub position_size_pct: f64,
+    /// Cooldown and re-entry rules (default: ungoverned).
+    pub entry_governance: EntryGovernance,
+    /// Position size cap and drawdown kill switch (default: unguarded).
+    pub risk_guard: RiskGuard,
+    /// Record per-phase wall times in `RunResult::timings` (default true).
+    pub record_timings: bool,
+    /// Record each position's bars in `RunResult::replay` (default false).
+    pub record_replay: bool,
+    /// Which bars get a `RunResult::portfolio_snapshots` entry (default off).
+    pub portfolio_snapshots: SnapshotMode,
 }
 
 impl EngineConfig {
@@ -29,10 +54,16 @@ impl EngineConfig {
         Self {
             initial_capital,
             warmup_bars,
+            warmup: WarmupPolicy::default(),
             trading_mode: TradingMode::LongOnly,
             execution_config: ExecutionConfig::frictionless(),
             instruments: HashMap::new(),
             position_size_pct: 1.0,
+            entry_governance: EntryGovernance::default(),
+            risk_guard: RiskGuard::default(),
+            record_timings: true,
+            record_replay: false,
+            portfolio_snapshots: SnapshotMode::Off,
         }
     }
 
@@ -45,10 +76,16 @@ impl EngineConfig {
         Self {
             initial_capital,
             warmup_bars,
+            warmup: WarmupPolicy::default(),
             trading_mode: TradingMode::LongOnly,
             execution_config,
             instruments: HashMap::new(),
             position_size_pct: 1.0,
+            entry_governance: EntryGovernance::default(),
+            risk_guard: RiskGuard::default(),
+            record_timings: true,
+            record_replay: false,
+            portfolio_snapshots: SnapshotMode::Off,
         }
     }
 }
@@ -68,16 +105,26 @@ pub struct EngineState {
     pub total_bar_counts: HashMap<String, usize>,
     /// Active stop order ID per symbol, for PM cancel/replace.
     pub stop_order_ids: HashMap<String, OrderId>,
-    /// Total PM on_bar calls made (for stickiness diagnostics).
-    pub pm_calls_total: usize,
-    /// PM calls that returned AdjustStop or ForceExit (non-Hold).
-    pub pm_calls_active: usize,
+    /// Active take-profit order ID per symbol. OCO-linked with the stop.
+    pub target_order_ids: HashMap<String, OrderId>,
+    /// PM on_bar calls per (symbol, entry_bar) position (for stickiness diagnostics).
+    pub pm_calls: HashMap<(String, usize), PmCallCounts>,
     /// Total signals fired during the run.
     pub signal_count: usize,
     /// Records of all signal filter evaluations (for diagnostics).
     pub signal_evaluations: Vec<SignalEvaluation>,
+    /// PM intents changed by the filter's intent review (for diagnostics).
+    pub intent_interventions: Vec<IntentIntervention>,
+    /// Stop adjustments the engine clamped to keep the ratchet (for diagnostics).
+    pub ratchet_clamps: Vec<RatchetClamp>,
     /// Maps symbol -> last entry signal (for reference by downstream components).
     pub entry_signals: HashMap<String, SignalEvent>,
+    /// First stop placed per (symbol, entry_bar), stamped onto trades as 1R.
+    pub initial_stops: HashMap<(String, usize), f64>,
+    /// Bar a time limit came due per (symbol, entry_bar), stamped onto trades.
+    pub time_expiries: HashMap<(String, usize), usize>,
+    /// Decision behind each submitted order, stamped onto its fills.
+    pub order_sources: HashMap<OrderId, DecisionSource>,
 }
 
 impl EngineState {
@@ -92,11 +139,16 @@ impl EngineState {
             void_bar_counts: HashMap::new(),
             total_bar_counts: HashMap::new(),
             stop_order_ids: HashMap::new(),
-            pm_calls_total: 0,
-            pm_calls_active: 0,
+            target_order_ids: HashMap::new(),
+            pm_calls: HashMap::new(),
             signal_count: 0,
             signal_evaluations: Vec::new(),
+            intent_interventions: Vec::new(),
+            ratchet_clamps: Vec::new(),
             entry_signals: HashMap::new(),
+            initial_stops: HashMap::new(),
+            time_expiries: HashMap::new(),
+            order_sources: HashMap::new(),
         }
     }
 
@@ -118,8 +170,9 @@ impl EngineState {
                 })
                 .sum();
             let expected = self.portfolio.cash + position_value;
+            // Relative, so large accounts don't trip on float rounding
             assert!(
-                (equity - expected).abs() < 1e-10,
+                (equity - expected).abs() <= 1e-10 * expected.abs().max(1.0),
                 "equity accounting violated: equity={equity}, cash={} + positions={position_value} = {expected}",
                 self.portfolio.cash
             );
@@ -129,7 +182,7 @@ impl EngineState {
     }
 
     /// Compute void bar rates per symbol. Returns rate as fraction (0.0 to 1.0).
-    pub fn void_bar_rates(&self) -> HashMap<String, f64> {
+    pub fn void_bar_rates(&self) -> BTreeMap<String, f64> {
         self.total_bar_counts
             .iter()
             .map(|(sym, &total)| {
@@ -160,8 +213,13 @@ impl EngineState {
 pub struct RunResult {
     /// Equity value at each bar close.
     pub equity_curve: Vec<f64>,
+    /// Gross exposure at each bar close, as a fraction of that bar's equity
+    /// (0.0 while flat, 1.0 when positions are open on non-positive equity).
+    pub exposure_curve: Vec<f64>,
     /// All fills generated during the run.
     pub fills: Vec<Fill>,
+    /// Every order state transition, in the order it happened.
+    pub order_audit: Vec<OrderAuditEntry>,
     /// Completed round-trip trades.
     pub trades: Vec<TradeRecord>,
     /// Final equity value.
@@ -170,16 +228,49 @@ pub struct RunResult {
     pub bar_count: usize,
     /// Number of warmup bars skipped.
     pub warmup_bars: usize,
+    /// How `warmup_bars` was derived, per indicator.
+    pub warmup: WarmupReport,
     /// Void bar rate per symbol (fraction 0.0 to 1.0).
-    pub void_bar_rates: HashMap<String, f64>,
+    pub void_bar_rates: BTreeMap<String, f64>,
     /// Data quality warnings (e.g., "SPY: 12% void bars exceeds 10% threshold").
     pub data_quality_warnings: Vec<String>,
-    /// Stickiness diagnostics (None if no trades completed).
-    pub stickiness: Option<StickinessMetrics>,
+    /// Stickiness diagnostics: run metrics (None if no trades completed)
+    /// and a score per position.
+    pub stickiness: StickinessReport,
     /// Total signals fired during the run.
     pub signal_count: usize,
     /// All signal filter evaluations (for diagnostics).
     pub signal_evaluations: Vec<SignalEvaluation>,
+    /// PM intents the filter held back or replaced (for diagnostics).
+    pub intent_interventions: Vec<IntentIntervention>,
+    /// Stop adjustments that would have loosened a stop, clamped by the
+    /// engine (for diagnostics).
+    pub ratchet_clamps: Vec<RatchetClamp>,
+    /// Per-bar PnL by the decision behind each fill.
+    pub attribution: PnlAttribution,
+    /// Per-phase wall times (all zero when `record_timings` is off).
+    pub timings: EngineTimings,
+    /// Bar-by-bar replay of each position (empty unless `record_replay`).
+    pub replay: ReplayLog,
+    /// Cash, positions and exposure per recorded bar (empty unless
+    /// `portfolio_snapshots` is enabled).
+    pub portfolio_snapshots: SnapshotLog,
+}
+
+/// Diagnostic record of the engine refusing to loosen a stop.
+///
+/// Stops may only tighten: up for longs, down for shorts. A requested stop
+/// on the loose side of the position's current stop or of its working stop
+/// order is clamped to the tighter of the two.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RatchetClamp {
+    pub symbol: String,
+    pub bar_index: usize,
+    pub side: PositionSide,
+    /// Stop price the intent asked for.
+    pub requested: f64,
+    /// Stop price the engine kept instead.
+    pub applied: f64,
 }
 
 #[cfg(test)]
@@ -192,6 +283,9 @@ mod tests {
         assert_eq!(config.initial_capital, 100_000.0);
         assert_eq!(config.warmup_bars, 20);
         assert_eq!(config.trading_mode, TradingMode::LongOnly);
+        assert!(config.record_timings);
+        assert!(!config.record_replay);
+        assert_eq!(config.portfolio_snapshots, SnapshotMode::Off);
     }
 
     #[test]
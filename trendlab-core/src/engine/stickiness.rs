@@ -3,13 +3,27 @@
 //! Stickiness is the primary failure mode of trend-following backtests: positions
 //! that never exit because the stop keeps chasing the price. These metrics
 //! quantify the problem and flag pathological configurations.
+//!
+//! A [`StickinessReport`] pairs the run-level [`StickinessMetrics`] (what the
+//! leaderboards aggregate) with a [`PositionStickiness`] score per position,
+//! so a run's numbers can be traced to the trades behind them. Every PM call
+//! belongs to exactly one position, so the run's exit trigger rate is the
+//! per-position call counts summed.
+
+use std::collections::{HashMap, HashSet};
 
 use serde::{Deserialize, Serialize};
 
 use crate::domain::TradeRecord;
 
+/// Holding period (bars) beyond which a run or position is pathological.
+pub const PATHOLOGICAL_HOLDING_BARS: f64 = 100.0;
+
+/// Exit trigger rate below which a run or position is pathological.
+pub const PATHOLOGICAL_EXIT_TRIGGER_RATE: f64 = 0.05;
+
 /// Stickiness metrics computed for a backtest run.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StickinessMetrics {
     /// Median holding period in bars.
     pub median_holding_bars: f64,
@@ -57,22 +71,184 @@ pub fn compute_stickiness(
         0.0
     };
 
-    let reference_chase_ratio = if exit_trigger_rate > 0.0 {
-        (1.0 / exit_trigger_rate).min(100.0)
-    } else {
-        100.0 // max cap when exit never triggers
-    };
-
     Some(StickinessMetrics {
         median_holding_bars,
         p95_holding_bars,
         pct_over_60_bars,
         pct_over_120_bars,
         exit_trigger_rate,
-        reference_chase_ratio,
+        reference_chase_ratio: chase_ratio(exit_trigger_rate),
     })
 }
 
+/// Inverse of an exit trigger rate, capped at 100.0.
+fn chase_ratio(exit_trigger_rate: f64) -> f64 {
+    if exit_trigger_rate > 0.0 {
+        (1.0 / exit_trigger_rate).min(100.0)
+    } else {
+        100.0 // max cap when exit never triggers
+    }
+}
+
+/// PM `on_bar` calls made for one position.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PmCallCounts {
+    pub total: usize,
+    /// Calls that returned AdjustStop, AdjustTarget, or ForceExit (non-Hold).
+    pub active: usize,
+}
+
+impl PmCallCounts {
+    pub fn record(&mut self, active: bool) {
+        self.total += 1;
+        if active {
+            self.active += 1;
+        }
+    }
+
+    /// Fraction of calls that were active (0.0 without calls).
+    pub fn exit_trigger_rate(&self) -> f64 {
+        if self.total > 0 {
+            self.active as f64 / self.total as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Stickiness score of a single position.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionStickiness {
+    pub symbol: String,
+    pub entry_bar: usize,
+    /// None for a position still open at the end of the run.
+    pub exit_bar: Option<usize>,
+    pub bars_held: usize,
+    pub pm_calls: PmCallCounts,
+    /// `pm_calls.active / pm_calls.total`.
+    pub exit_trigger_rate: f64,
+    /// Inverse of the exit trigger rate, capped at 100.0 (as for the run).
+    pub reference_chase_ratio: f64,
+    /// Held past [`PATHOLOGICAL_HOLDING_BARS`], or the PM was called and its
+    /// exit trigger rate stayed below [`PATHOLOGICAL_EXIT_TRIGGER_RATE`].
+    pub is_chasing: bool,
+}
+
+impl PositionStickiness {
+    pub fn new(
+        symbol: &str,
+        entry_bar: usize,
+        exit_bar: Option<usize>,
+        bars_held: usize,
+        pm_calls: PmCallCounts,
+    ) -> Self {
+        let exit_trigger_rate = pm_calls.exit_trigger_rate();
+        Self {
+            symbol: symbol.to_string(),
+            entry_bar,
+            exit_bar,
+            bars_held,
+            pm_calls,
+            exit_trigger_rate,
+            reference_chase_ratio: chase_ratio(exit_trigger_rate),
+            is_chasing: bars_held as f64 > PATHOLOGICAL_HOLDING_BARS
+                || (pm_calls.total > 0 && exit_trigger_rate < PATHOLOGICAL_EXIT_TRIGGER_RATE),
+        }
+    }
+}
+
+/// Run-level stickiness plus the per-position scores behind it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StickinessReport {
+    /// The metrics stored on the result and aggregated by the leaderboards.
+    /// None if no trades completed.
+    pub run: Option<StickinessMetrics>,
+    /// Closed trades in trade order, then positions still open at the end.
+    pub positions: Vec<PositionStickiness>,
+}
+
+impl StickinessReport {
+    /// PM calls summed over all positions: the run's exit trigger rate.
+    pub fn pm_calls(&self) -> PmCallCounts {
+        self.positions
+            .iter()
+            .fold(PmCallCounts::default(), |acc, p| PmCallCounts {
+                total: acc.total + p.pm_calls.total,
+                active: acc.active + p.pm_calls.active,
+            })
+    }
+
+    /// Fraction of PM calls that held, leaving the reference level frozen.
+    pub fn frozen_reference_rate(&self) -> f64 {
+        let calls = self.pm_calls();
+        if calls.total > 0 {
+            1.0 - calls.exit_trigger_rate()
+        } else {
+            0.0
+        }
+    }
+
+    /// Positions flagged as chasing.
+    pub fn chasing(&self) -> impl Iterator<Item = &PositionStickiness> {
+        self.positions.iter().filter(|p| p.is_chasing)
+    }
+}
+
+/// Build the stickiness report of a run.
+///
+/// `pm_calls` is keyed by `(symbol, entry_bar)`. Keys without a completed
+/// trade are positions still open at the end; `open_bars_held` gives their
+/// holding periods (missing keys count as 0 bars).
+pub fn compute_stickiness_report(
+    trades: &[TradeRecord],
+    pm_calls: &HashMap<(String, usize), PmCallCounts>,
+    open_bars_held: &HashMap<(String, usize), usize>,
+) -> StickinessReport {
+    let calls_for = |symbol: &str, entry_bar: usize| {
+        pm_calls
+            .get(&(symbol.to_string(), entry_bar))
+            .copied()
+            .unwrap_or_default()
+    };
+
+    let mut positions: Vec<PositionStickiness> = trades
+        .iter()
+        .map(|t| {
+            PositionStickiness::new(
+                &t.symbol,
+                t.entry_bar,
+                Some(t.exit_bar),
+                t.bars_held,
+                calls_for(&t.symbol, t.entry_bar),
+            )
+        })
+        .collect();
+
+    let closed: HashSet<(&str, usize)> = trades
+        .iter()
+        .map(|t| (t.symbol.as_str(), t.entry_bar))
+        .collect();
+    let mut open: Vec<&(String, usize)> = pm_calls
+        .keys()
+        .filter(|(symbol, entry_bar)| !closed.contains(&(symbol.as_str(), *entry_bar)))
+        .collect();
+    open.sort();
+    positions.extend(open.into_iter().map(|key| {
+        let bars_held = open_bars_held.get(key).copied().unwrap_or(0);
+        PositionStickiness::new(&key.0, key.1, None, bars_held, pm_calls[key])
+    }));
+
+    let report = StickinessReport {
+        run: None,
+        positions,
+    };
+    let calls = report.pm_calls();
+    StickinessReport {
+        run: compute_stickiness(trades, calls.total, calls.active),
+        ..report
+    }
+}
+
 /// Compute the p-th percentile of a sorted slice using linear interpolation.
 fn percentile(sorted: &[f64], p: f64) -> f64 {
     let n = sorted.len();
@@ -102,6 +278,7 @@ mod tests {
             entry_bar: 0,
             entry_date: date,
             entry_price: 100.0,
+            entry_trigger_margin: None,
             exit_bar: bars_held,
             exit_date: date,
             exit_price: 105.0,
@@ -113,6 +290,11 @@ mod tests {
             bars_held,
             mae: -50.0,
             mfe: 600.0,
+            initial_stop_price: None,
+            entry_source: None,
+            exit_source: None,
+            liquidated_at_end: false,
+            expiry_bar: None,
             signal_id: None,
             signal_type: None,
             pm_type: None,
@@ -172,6 +354,58 @@ mod tests {
         assert!((m.reference_chase_ratio - 100.0).abs() < 1e-10);
     }
 
+    fn calls(total: usize, active: usize) -> PmCallCounts {
+        PmCallCounts { total, active }
+    }
+
+    #[test]
+    fn report_scores_trades_then_open_positions() {
+        let mut first = make_trade(20);
+        first.entry_bar = 10;
+        first.exit_bar = 30;
+        let mut second = make_trade(150);
+        second.entry_bar = 40;
+        second.exit_bar = 190;
+
+        let mut pm_calls = HashMap::new();
+        pm_calls.insert(("SPY".to_string(), 10), calls(20, 10));
+        pm_calls.insert(("SPY".to_string(), 40), calls(150, 3));
+        pm_calls.insert(("SPY".to_string(), 200), calls(30, 0));
+        let open_bars_held = HashMap::from([(("SPY".to_string(), 200), 30)]);
+
+        let report = compute_stickiness_report(&[first, second], &pm_calls, &open_bars_held);
+        let entries: Vec<(usize, Option<usize>)> = report
+            .positions
+            .iter()
+            .map(|p| (p.entry_bar, p.exit_bar))
+            .collect();
+        assert_eq!(entries, vec![(10, Some(30)), (40, Some(190)), (200, None)]);
+
+        let [first, second, open] = &report.positions[..] else {
+            panic!("three positions");
+        };
+        assert!((first.exit_trigger_rate - 0.5).abs() < 1e-10);
+        assert!((first.reference_chase_ratio - 2.0).abs() < 1e-10);
+        assert!(!first.is_chasing);
+        assert!(second.is_chasing, "held past 100 bars, 2% trigger rate");
+        assert!(open.is_chasing, "PM never moved the reference");
+        assert_eq!(open.bars_held, 30);
+        assert_eq!(report.chasing().count(), 2);
+
+        // Run metrics come from the per-position counts summed: 13 of 200
+        assert_eq!(report.pm_calls(), calls(200, 13));
+        let run = report.run.as_ref().unwrap();
+        assert!((run.exit_trigger_rate - 0.065).abs() < 1e-10);
+        assert!((report.frozen_reference_rate() - 0.935).abs() < 1e-10);
+    }
+
+    #[test]
+    fn report_without_trades_has_no_run_metrics() {
+        let report = compute_stickiness_report(&[], &HashMap::new(), &HashMap::new());
+        assert_eq!(report, StickinessReport::default());
+        assert_eq!(report.frozen_reference_rate(), 0.0);
+    }
+
     #[test]
     fn chase_ratio_capped() {
         let trades = vec![make_trade(10)];
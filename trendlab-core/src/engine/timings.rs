@@ -0,0 +1,125 @@
+//! Per-phase wall-clock timings for the bar loop.
+//!
+//! Each phase's time is accumulated across all bars, so a run's timings show
+//! where the engine spends its time (indicator precompute vs order-book work
+//! vs signal/PM evaluation). Timings are summed across runs with
+//! [`EngineTimings::merge`]. Disabled via `EngineConfig::record_timings`, in
+//! which case no clock is ever read.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Cumulative wall time per engine phase.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct EngineTimings {
+    /// Bar conversion and indicator precompute.
+    pub precompute: Duration,
+    /// Start-of-bar: market status, day-order activation, MOO fills.
+    pub start_of_bar: Duration,
+    /// Intrabar stop/limit trigger checks.
+    pub intrabar: Duration,
+    /// End-of-bar MOC fills.
+    pub end_of_bar: Duration,
+    /// Post-bar: mark-to-market, equity, signal evaluation, PM maintenance.
+    pub post_bar: Duration,
+    /// Round-trip trade extraction from fills.
+    pub trade_extraction: Duration,
+    /// Number of runs summed into these timings.
+    pub runs: usize,
+}
+
+impl EngineTimings {
+    /// Sum of all phases.
+    pub fn total(&self) -> Duration {
+        self.phases().iter().map(|&(_, d)| d).sum()
+    }
+
+    /// Phases in loop order, with display labels.
+    pub fn phases(&self) -> [(&'static str, Duration); 6] {
+        [
+            ("precompute", self.precompute),
+            ("start-of-bar", self.start_of_bar),
+            ("intrabar", self.intrabar),
+            ("end-of-bar", self.end_of_bar),
+            ("post-bar", self.post_bar),
+            ("trade extraction", self.trade_extraction),
+        ]
+    }
+
+    /// Add another run's (or aggregate's) timings into this one.
+    pub fn merge(&mut self, other: &EngineTimings) {
+        self.precompute += other.precompute;
+        self.start_of_bar += other.start_of_bar;
+        self.intrabar += other.intrabar;
+        self.end_of_bar += other.end_of_bar;
+        self.post_bar += other.post_bar;
+        self.trade_extraction += other.trade_extraction;
+        self.runs += other.runs;
+    }
+}
+
+/// Lap clock that charges elapsed time to phase slots. A disabled clock
+/// never reads the system time.
+pub(crate) struct PhaseClock {
+    last: Option<Instant>,
+}
+
+impl PhaseClock {
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self {
+            last: enabled.then(Instant::now),
+        }
+    }
+
+    /// Charge the time since the previous lap to `slot`.
+    #[inline]
+    pub(crate) fn lap(&mut self, slot: &mut Duration) {
+        if let Some(last) = &mut self.last {
+            let now = Instant::now();
+            *slot += now - *last;
+            *last = now;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_clock_records_nothing() {
+        let mut clock = PhaseClock::new(false);
+        let mut slot = Duration::ZERO;
+        std::thread::sleep(Duration::from_millis(2));
+        clock.lap(&mut slot);
+        assert_eq!(slot, Duration::ZERO);
+    }
+
+    #[test]
+    fn enabled_clock_charges_each_lap_to_its_slot() {
+        let mut clock = PhaseClock::new(true);
+        let (mut a, mut b) = (Duration::ZERO, Duration::ZERO);
+        std::thread::sleep(Duration::from_millis(2));
+        clock.lap(&mut a);
+        clock.lap(&mut b);
+        assert!(a >= Duration::from_millis(2));
+        assert!(b < a);
+    }
+
+    #[test]
+    fn merge_sums_phases_and_runs() {
+        let one = EngineTimings {
+            precompute: Duration::from_millis(3),
+            post_bar: Duration::from_millis(5),
+            runs: 1,
+            ..Default::default()
+        };
+        let mut total = EngineTimings::default();
+        total.merge(&one);
+        total.merge(&one);
+        assert_eq!(total.runs, 2);
+        assert_eq!(total.precompute, Duration::from_millis(6));
+        assert_eq!(total.total(), Duration::from_millis(16));
+    }
+}
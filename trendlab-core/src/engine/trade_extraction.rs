@@ -2,36 +2,163 @@
 //!
 //! Post-processes fills after the bar loop completes. Pure function:
 //! fills + bar data + signal map → trade records.
+//!
+//! Positions still open after the last bar become end-of-data trades,
+//! marked like the final equity point: exit at the last mark, no exit
+//! costs, [`DecisionSource::EndOfRunLiquidation`] as the exit source and
+//! `liquidated_at_end` set. Metrics that should only see exits the
+//! strategy chose filter on that flag.
 
 use crate::components::signal::SignalEvent;
 use crate::domain::instrument::OrderSide;
 use crate::domain::position::PositionSide;
-use crate::domain::{Bar, Fill, TradeRecord};
+use crate::domain::{BarSeries, DecisionSource, Fill, TradeRecord};
+use chrono::NaiveDate;
 use std::collections::HashMap;
 
+/// Where positions still open after the last bar are closed.
+#[derive(Debug, Clone, Copy)]
+pub struct EndOfData<'a> {
+    pub bar_index: usize,
+    pub date: NaiveDate,
+    /// Final mark per symbol (the prices of the last equity point).
+    pub marks: &'a HashMap<String, f64>,
+}
+
+/// The closing leg of a round trip.
+struct ExitLeg {
+    bar_index: usize,
+    date: NaiveDate,
+    /// Quantity-weighted average over the leg's fills.
+    price: f64,
+    commission: f64,
+    slippage: f64,
+    source: Option<DecisionSource>,
+    liquidated_at_end: bool,
+}
+
 /// State for an open trade being tracked during extraction.
+///
+/// An order the liquidity cap fills over several bars arrives as several
+/// fills. Same-side fills scale into the entry and opposite-side fills
+/// reduce `held`; the trade closes when `held` reaches zero.
 struct OpenTrade {
     symbol: String,
     side: PositionSide,
     entry_bar: usize,
-    entry_date: chrono::NaiveDate,
-    entry_price: f64,
+    entry_date: NaiveDate,
+    entry_trigger_margin: Option<f64>,
+    entry_source: Option<DecisionSource>,
+    /// Total quantity entered, at an average of `entry_price`.
     quantity: f64,
+    entry_price: f64,
     entry_commission: f64,
     entry_slippage: f64,
+    /// Quantity still held.
+    held: f64,
+    /// Average price of the quantity sold so far.
+    exit_price: f64,
+    exit_commission: f64,
+    exit_slippage: f64,
+}
+
+/// Quantity-weighted average of two prices. Exact when either side is empty.
+fn blend(price: f64, quantity: f64, add_price: f64, add_quantity: f64) -> f64 {
+    if quantity <= 0.0 {
+        return add_price;
+    }
+    if add_quantity <= 0.0 {
+        return price;
+    }
+    (price * quantity + add_price * add_quantity) / (quantity + add_quantity)
+}
+
+impl OpenTrade {
+    fn open(fill: &Fill) -> Self {
+        Self {
+            symbol: fill.symbol.clone(),
+            side: match fill.side {
+                OrderSide::Buy => PositionSide::Long,
+                OrderSide::Sell => PositionSide::Short,
+            },
+            entry_bar: fill.bar_index,
+            entry_date: fill.date,
+            entry_trigger_margin: fill.trigger_margin,
+            entry_source: fill.decision_source,
+            quantity: fill.quantity,
+            entry_price: fill.price,
+            entry_commission: fill.commission,
+            entry_slippage: fill.slippage,
+            held: fill.quantity,
+            exit_price: 0.0,
+            exit_commission: 0.0,
+            exit_slippage: 0.0,
+        }
+    }
+
+    /// Whether `fill` adds to the trade rather than reducing it.
+    fn adds(&self, fill: &Fill) -> bool {
+        matches!(
+            (self.side, fill.side),
+            (PositionSide::Long, OrderSide::Buy) | (PositionSide::Short, OrderSide::Sell)
+        )
+    }
+
+    fn scale_in(&mut self, fill: &Fill) {
+        self.entry_price = blend(self.entry_price, self.quantity, fill.price, fill.quantity);
+        self.quantity += fill.quantity;
+        self.held += fill.quantity;
+        self.entry_commission += fill.commission;
+        self.entry_slippage += fill.slippage;
+    }
+
+    /// Reduce by `fill`, up to the quantity held (like the portfolio).
+    /// True once the trade is flat.
+    fn reduce(&mut self, fill: &Fill) -> bool {
+        let closed = fill.quantity.min(self.held);
+        self.exit_price = blend(
+            self.exit_price,
+            self.quantity - self.held,
+            fill.price,
+            closed,
+        );
+        self.held -= closed;
+        self.exit_commission += fill.commission;
+        self.exit_slippage += fill.slippage;
+        self.held <= 1e-10
+    }
+
+    /// The exit leg of a trade `last` made flat.
+    fn exit_leg(&self, last: &Fill) -> ExitLeg {
+        ExitLeg {
+            bar_index: last.bar_index,
+            date: last.date,
+            price: self.exit_price,
+            commission: self.exit_commission,
+            slippage: self.exit_slippage,
+            source: last.decision_source,
+            liquidated_at_end: false,
+        }
+    }
 }
 
 /// Extract round-trip trades from fills and bar data.
 ///
 /// Groups fills by symbol in chronological order. When a buy fill arrives with
-/// no open long for that symbol, it's an entry. When a sell fill arrives closing
-/// an open long, it's an exit (and vice versa for shorts).
+/// no open long for that symbol, it's an entry. Sell fills reduce an open
+/// long, which closes once fully sold (and vice versa for shorts). Entry and
+/// exit prices are quantity-weighted over each leg's fills.
 ///
 /// MAE/MFE are computed by walking bar data between entry and exit.
-pub fn extract_trades(
+///
+/// With `end`, trades still open after the last fill are closed there as
+/// end-of-data trades, appended after the closed trades in symbol order.
+/// Without it they are dropped.
+pub fn extract_trades<B: BarSeries>(
     fills: &[Fill],
-    bars_by_symbol: &HashMap<String, Vec<Bar>>,
+    bars_by_symbol: &HashMap<String, B>,
     entry_signals: &HashMap<String, SignalEvent>,
+    end: Option<EndOfData<'_>>,
 ) -> Vec<TradeRecord> {
     let mut trades = Vec::new();
     let mut open_trades: HashMap<String, OpenTrade> = HashMap::new();
@@ -39,74 +166,83 @@ pub fn extract_trades(
     for fill in fills {
         let symbol = &fill.symbol;
 
-        if let Some(open) = open_trades.get(symbol) {
-            // Check if this fill closes the open trade
-            let is_exit = match open.side {
-                PositionSide::Long => fill.side == OrderSide::Sell,
-                PositionSide::Short => fill.side == OrderSide::Buy,
-                PositionSide::Flat => false,
-            };
-
-            if is_exit {
+        if let Some(open) = open_trades.get_mut(symbol) {
+            if open.adds(fill) {
+                open.scale_in(fill);
+            } else if open.reduce(fill) {
                 let open = open_trades.remove(symbol).unwrap();
                 let trade = build_trade_record(
                     &open,
-                    fill,
-                    bars_by_symbol.get(symbol),
+                    &open.exit_leg(fill),
+                    bars_by_symbol.get(symbol).map(|b| b as &dyn BarSeries),
                     entry_signals.get(symbol),
                 );
                 trades.push(trade);
-                continue;
             }
+            continue;
         }
 
         // This fill opens a new trade
-        let side = match fill.side {
-            OrderSide::Buy => PositionSide::Long,
-            OrderSide::Sell => PositionSide::Short,
-        };
+        open_trades.insert(symbol.clone(), OpenTrade::open(fill));
+    }
 
-        open_trades.insert(
-            symbol.clone(),
-            OpenTrade {
-                symbol: symbol.clone(),
-                side,
-                entry_bar: fill.bar_index,
-                entry_date: fill.date,
-                entry_price: fill.price,
-                quantity: fill.quantity,
-                entry_commission: fill.commission,
-                entry_slippage: fill.slippage,
-            },
-        );
+    if let Some(end) = end {
+        let mut still_open: Vec<OpenTrade> = open_trades.into_values().collect();
+        still_open.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        for open in still_open {
+            // The rest of the position is marked without exit costs
+            let mark = end
+                .marks
+                .get(&open.symbol)
+                .copied()
+                .unwrap_or(open.entry_price);
+            let exit = ExitLeg {
+                bar_index: end.bar_index,
+                date: end.date,
+                price: blend(open.exit_price, open.quantity - open.held, mark, open.held),
+                commission: open.exit_commission,
+                slippage: open.exit_slippage,
+                source: Some(DecisionSource::EndOfRunLiquidation),
+                liquidated_at_end: true,
+            };
+            trades.push(build_trade_record(
+                &open,
+                &exit,
+                bars_by_symbol
+                    .get(&open.symbol)
+                    .map(|b| b as &dyn BarSeries),
+                entry_signals.get(&open.symbol),
+            ));
+        }
     }
 
     trades
 }
 
-/// Build a TradeRecord from an open trade and an exit fill.
+/// Build a TradeRecord from an open trade and its exit.
 fn build_trade_record(
     open: &OpenTrade,
-    exit_fill: &Fill,
-    bars: Option<&Vec<Bar>>,
+    exit: &ExitLeg,
+    bars: Option<&dyn BarSeries>,
     signal: Option<&SignalEvent>,
 ) -> TradeRecord {
+    let entry_price = open.entry_price;
     let gross_pnl = match open.side {
-        PositionSide::Long => (exit_fill.price - open.entry_price) * open.quantity,
-        PositionSide::Short => (open.entry_price - exit_fill.price) * open.quantity,
+        PositionSide::Long => (exit.price - entry_price) * open.quantity,
+        PositionSide::Short => (entry_price - exit.price) * open.quantity,
         PositionSide::Flat => 0.0,
     };
 
-    let commission = open.entry_commission + exit_fill.commission;
-    let slippage = open.entry_slippage + exit_fill.slippage;
+    let commission = open.entry_commission + exit.commission;
+    let slippage = open.entry_slippage + exit.slippage;
     let net_pnl = gross_pnl - commission - slippage;
-    let bars_held = exit_fill.bar_index.saturating_sub(open.entry_bar);
+    let bars_held = exit.bar_index.saturating_sub(open.entry_bar);
 
     let (mae, mfe) = compute_mae_mfe(
         bars,
         open.entry_bar,
-        exit_fill.bar_index,
-        open.entry_price,
+        exit.bar_index,
+        entry_price,
         open.quantity,
         open.side,
     );
@@ -116,10 +252,11 @@ fn build_trade_record(
         side: open.side,
         entry_bar: open.entry_bar,
         entry_date: open.entry_date,
-        entry_price: open.entry_price,
-        exit_bar: exit_fill.bar_index,
-        exit_date: exit_fill.date,
-        exit_price: exit_fill.price,
+        entry_price,
+        entry_trigger_margin: open.entry_trigger_margin,
+        exit_bar: exit.bar_index,
+        exit_date: exit.date,
+        exit_price: exit.price,
         quantity: open.quantity,
         gross_pnl,
         commission,
@@ -128,6 +265,11 @@ fn build_trade_record(
         bars_held,
         mae,
         mfe,
+        initial_stop_price: None, // Stamped by the engine from recorded initial stops
+        entry_source: open.entry_source,
+        exit_source: exit.source,
+        liquidated_at_end: exit.liquidated_at_end,
+        expiry_bar: None, // Stamped by the engine from recorded time expiries
         signal_id: signal.map(|s| s.id),
         signal_type: None, // Set by runner from composition info
         pm_type: None,
@@ -139,7 +281,7 @@ fn build_trade_record(
 /// Compute Maximum Adverse Excursion and Maximum Favorable Excursion
 /// by walking bar data between entry and exit.
 fn compute_mae_mfe(
-    bars: Option<&Vec<Bar>>,
+    bars: Option<&dyn BarSeries>,
     entry_bar: usize,
     exit_bar: usize,
     entry_price: f64,
@@ -157,7 +299,7 @@ fn compute_mae_mfe(
     let start = entry_bar.min(bars.len());
     let end = (exit_bar + 1).min(bars.len());
 
-    for bar in &bars[start..end] {
+    for bar in (start..end).map(|i| bars.bar(i)) {
         if bar.is_void() {
             continue;
         }
@@ -197,7 +339,7 @@ mod tests {
     use super::*;
     use crate::domain::fill::FillPhase;
     use crate::domain::ids::{OrderId, SignalEventId};
-    use chrono::NaiveDate;
+    use crate::domain::Bar;
 
     fn make_bars(prices: &[(f64, f64, f64, f64)]) -> Vec<Bar> {
         let base = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
@@ -217,6 +359,10 @@ mod tests {
             .collect()
     }
 
+    fn no_bars() -> HashMap<String, Vec<Bar>> {
+        HashMap::new()
+    }
+
     fn buy_fill(symbol: &str, bar: usize, price: f64, qty: f64) -> Fill {
         Fill {
             order_id: OrderId(bar as u64),
@@ -229,6 +375,8 @@ mod tests {
             commission: 0.0,
             slippage: 0.0,
             phase: FillPhase::StartOfBar,
+            trigger_margin: None,
+            decision_source: None,
         }
     }
 
@@ -244,12 +392,14 @@ mod tests {
             commission: 0.0,
             slippage: 0.0,
             phase: FillPhase::Intrabar,
+            trigger_margin: None,
+            decision_source: None,
         }
     }
 
     #[test]
     fn zero_fills_produces_zero_trades() {
-        let trades = extract_trades(&[], &HashMap::new(), &HashMap::new());
+        let trades = extract_trades(&[], &no_bars(), &HashMap::new(), None);
         assert!(trades.is_empty());
     }
 
@@ -271,7 +421,7 @@ mod tests {
         let mut bars_map = HashMap::new();
         bars_map.insert("SPY".to_string(), bars);
 
-        let trades = extract_trades(&fills, &bars_map, &HashMap::new());
+        let trades = extract_trades(&fills, &bars_map, &HashMap::new(), None);
 
         assert_eq!(trades.len(), 1);
         let t = &trades[0];
@@ -306,7 +456,7 @@ mod tests {
         let mut bars_map = HashMap::new();
         bars_map.insert("SPY".to_string(), bars);
 
-        let trades = extract_trades(&fills, &bars_map, &HashMap::new());
+        let trades = extract_trades(&fills, &bars_map, &HashMap::new(), None);
 
         assert_eq!(trades.len(), 1);
         let t = &trades[0];
@@ -325,7 +475,7 @@ mod tests {
             sell_fill("SPY", 8, 108.0, 50.0),
         ];
 
-        let trades = extract_trades(&fills, &HashMap::new(), &HashMap::new());
+        let trades = extract_trades(&fills, &no_bars(), &HashMap::new(), None);
 
         assert_eq!(trades.len(), 2);
         assert_eq!(trades[0].entry_bar, 1);
@@ -343,7 +493,7 @@ mod tests {
             sell_fill("QQQ", 5, 210.0, 25.0),
         ];
 
-        let trades = extract_trades(&fills, &HashMap::new(), &HashMap::new());
+        let trades = extract_trades(&fills, &no_bars(), &HashMap::new(), None);
 
         assert_eq!(trades.len(), 2);
         let spy_trade = trades.iter().find(|t| t.symbol == "SPY").unwrap();
@@ -363,7 +513,7 @@ mod tests {
         exit.slippage = 2.0;
 
         let fills = vec![entry, exit];
-        let trades = extract_trades(&fills, &HashMap::new(), &HashMap::new());
+        let trades = extract_trades(&fills, &no_bars(), &HashMap::new(), None);
 
         assert_eq!(trades.len(), 1);
         let t = &trades[0];
@@ -389,7 +539,7 @@ mod tests {
         let mut bars_map = HashMap::new();
         bars_map.insert("SPY".to_string(), bars);
 
-        let trades = extract_trades(&fills, &bars_map, &HashMap::new());
+        let trades = extract_trades(&fills, &bars_map, &HashMap::new(), None);
 
         assert_eq!(trades.len(), 1);
         let t = &trades[0];
@@ -415,7 +565,7 @@ mod tests {
         let mut bars_map = HashMap::new();
         bars_map.insert("SPY".to_string(), bars);
 
-        let trades = extract_trades(&fills, &bars_map, &HashMap::new());
+        let trades = extract_trades(&fills, &bars_map, &HashMap::new(), None);
 
         assert_eq!(trades.len(), 1);
         let t = &trades[0];
@@ -444,7 +594,7 @@ mod tests {
             },
         );
 
-        let trades = extract_trades(&fills, &HashMap::new(), &signals);
+        let trades = extract_trades(&fills, &no_bars(), &signals, None);
 
         assert_eq!(trades.len(), 1);
         let t = &trades[0];
@@ -455,10 +605,86 @@ mod tests {
     fn unmatched_entry_produces_no_trade() {
         // Only an entry, no exit
         let fills = vec![buy_fill("SPY", 2, 100.0, 50.0)];
-        let trades = extract_trades(&fills, &HashMap::new(), &HashMap::new());
+        let trades = extract_trades(&fills, &no_bars(), &HashMap::new(), None);
         assert!(trades.is_empty());
     }
 
+    #[test]
+    fn open_trades_close_at_end_of_data() {
+        let fills = vec![
+            buy_fill("SPY", 1, 100.0, 50.0),
+            sell_fill("SPY", 3, 104.0, 50.0),
+            buy_fill("SPY", 4, 105.0, 50.0),
+            sell_fill("QQQ", 2, 200.0, 10.0),
+        ];
+        let marks = HashMap::from([("SPY".to_string(), 111.0)]);
+        let end = EndOfData {
+            bar_index: 6,
+            date: NaiveDate::from_ymd_opt(2024, 1, 8).unwrap(),
+            marks: &marks,
+        };
+
+        let trades = extract_trades(&fills, &no_bars(), &HashMap::new(), Some(end));
+
+        assert_eq!(trades.len(), 3);
+        assert!(!trades[0].liquidated_at_end);
+        // Open trades follow the closed ones, by symbol
+        let (qqq, spy) = (&trades[1], &trades[2]);
+        assert_eq!((qqq.symbol.as_str(), spy.symbol.as_str()), ("QQQ", "SPY"));
+        assert!(qqq.liquidated_at_end && spy.liquidated_at_end);
+        assert_eq!(spy.exit_source, Some(DecisionSource::EndOfRunLiquidation));
+        assert_eq!((spy.exit_bar, spy.exit_price, spy.bars_held), (6, 111.0, 2));
+        assert!((spy.net_pnl - 300.0).abs() < 1e-10);
+        // No mark for the symbol: closed flat at the entry price
+        assert_eq!(qqq.side, PositionSide::Short);
+        assert_eq!(qqq.net_pnl, 0.0);
+    }
+
+    #[test]
+    fn partial_fills_aggregate_into_one_round_trip() {
+        // A long filled over two bars, sold over two more, then a short
+        // half covered when the data ends
+        let mut fills = vec![
+            buy_fill("SPY", 1, 100.0, 30.0),
+            buy_fill("SPY", 2, 104.0, 10.0),
+            sell_fill("SPY", 4, 110.0, 25.0),
+            sell_fill("SPY", 5, 112.0, 15.0),
+            sell_fill("SPY", 6, 115.0, 20.0),
+            buy_fill("SPY", 7, 111.0, 10.0),
+        ];
+        for fill in &mut fills {
+            fill.commission = 1.0;
+        }
+        let marks = HashMap::from([("SPY".to_string(), 109.0)]);
+        let end = EndOfData {
+            bar_index: 8,
+            date: NaiveDate::from_ymd_opt(2024, 1, 10).unwrap(),
+            marks: &marks,
+        };
+
+        let trades = extract_trades(&fills, &no_bars(), &HashMap::new(), Some(end));
+
+        assert_eq!(trades.len(), 2);
+        let long = &trades[0];
+        assert_eq!((long.entry_bar, long.exit_bar, long.quantity), (1, 5, 40.0));
+        assert!((long.entry_price - 101.0).abs() < 1e-10);
+        assert!((long.exit_price - 110.75).abs() < 1e-10);
+        // Sold for 2750 + 1680, bought for 3000 + 1040
+        assert!((long.gross_pnl - 390.0).abs() < 1e-10);
+        assert_eq!(long.commission, 4.0);
+
+        let short = &trades[1];
+        assert!(short.liquidated_at_end);
+        assert_eq!(
+            (short.entry_bar, short.exit_bar, short.quantity),
+            (6, 8, 20.0)
+        );
+        // Covered 10 at 111, the other 10 marked at 109
+        assert!((short.exit_price - 110.0).abs() < 1e-10);
+        assert!((short.gross_pnl - 100.0).abs() < 1e-10);
+        assert_eq!(short.commission, 2.0);
+    }
+
     #[test]
     fn losing_trade_has_negative_pnl() {
         let fills = vec![
@@ -466,7 +692,7 @@ mod tests {
             sell_fill("SPY", 5, 90.0, 50.0),
         ];
 
-        let trades = extract_trades(&fills, &HashMap::new(), &HashMap::new());
+        let trades = extract_trades(&fills, &no_bars(), &HashMap::new(), None);
 
         assert_eq!(trades.len(), 1);
         let t = &trades[0];
@@ -0,0 +1,207 @@
+//! Warmup policy — how many leading bars the engine skips, and why.
+//!
+//! By default the warmup is the longest indicator lookback. Recursive
+//! indicators (EMA, supertrend, ADX) produce values after their nominal
+//! period but need several times that to forget their seed, so a config can
+//! scale every lookback by `multiplier` or replace the warmup outright with
+//! `override_bars`. Like entry governance, the policy travels as flat params
+//! on the signal component (see [`WarmupPolicy::from_params`]) and enters
+//! `full_hash` fingerprints. Configs without them use the plain lookbacks.
+//!
+//! The engine records what it applied in a [`WarmupReport`]: each
+//! indicator's contribution, the warmup, and the bars left to trade.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::components::indicator::Indicator;
+
+/// Scales every indicator lookback (>= 1).
+pub const PARAM_WARMUP_MULTIPLIER: &str = "warmup_multiplier";
+/// Fixed warmup in bars, replacing the computed one.
+pub const PARAM_WARMUP_OVERRIDE: &str = "warmup_override";
+
+/// How the warmup is derived from indicator lookbacks. The default uses
+/// the lookbacks as they are.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WarmupPolicy {
+    /// Factor applied to each lookback, rounded up. Values below 1 are
+    /// treated as 1: the warmup never ends before an indicator has output.
+    pub multiplier: f64,
+    /// Warmup in bars, used instead of the scaled lookbacks and the
+    /// engine's explicit minimum. Signals still stay silent until their
+    /// own indicators have values.
+    pub override_bars: Option<usize>,
+}
+
+impl Default for WarmupPolicy {
+    fn default() -> Self {
+        Self {
+            multiplier: 1.0,
+            override_bars: None,
+        }
+    }
+}
+
+impl WarmupPolicy {
+    /// Decode from signal params. Missing keys leave the default.
+    pub fn from_params(params: &BTreeMap<String, f64>) -> Self {
+        let get = |k: &str| params.get(k).copied().filter(|v| v.is_finite());
+        Self {
+            multiplier: get(PARAM_WARMUP_MULTIPLIER).map_or(1.0, |m| m.max(1.0)),
+            override_bars: get(PARAM_WARMUP_OVERRIDE).map(|v| v.round().max(0.0) as usize),
+        }
+    }
+
+    /// Encode into signal params (inverse of [`Self::from_params`]).
+    ///
+    /// Only non-default settings are written, so existing configs hash as
+    /// before.
+    pub fn write_params(&self, params: &mut BTreeMap<String, f64>) {
+        if self.multiplier > 1.0 {
+            params.insert(PARAM_WARMUP_MULTIPLIER.into(), self.multiplier);
+        }
+        if let Some(bars) = self.override_bars {
+            params.insert(PARAM_WARMUP_OVERRIDE.into(), bars as f64);
+        }
+    }
+
+    /// Warmup for `indicators` over a run of `bar_count` bars. `minimum`
+    /// is the engine's explicit warmup floor (`EngineConfig::warmup_bars`).
+    pub fn report(
+        &self,
+        indicators: &[Box<dyn Indicator>],
+        minimum: usize,
+        bar_count: usize,
+    ) -> WarmupReport {
+        let multiplier = self.multiplier.max(1.0);
+        let contributions: Vec<WarmupContribution> = indicators
+            .iter()
+            .map(|i| WarmupContribution {
+                indicator: i.name().to_string(),
+                lookback: i.lookback(),
+                bars: (i.lookback() as f64 * multiplier).ceil() as usize,
+            })
+            .collect();
+        let computed = contributions.iter().map(|c| c.bars).max().unwrap_or(0);
+        let applied = self.override_bars.unwrap_or(computed.max(minimum));
+        WarmupReport {
+            contributions,
+            multiplier,
+            override_bars: self.override_bars,
+            minimum,
+            applied,
+            tradable_bars: bar_count.saturating_sub(applied),
+        }
+    }
+}
+
+/// One indicator's share of the warmup.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WarmupContribution {
+    pub indicator: String,
+    /// Nominal lookback.
+    pub lookback: usize,
+    /// Lookback after the multiplier.
+    pub bars: usize,
+}
+
+/// The warmup a run applied and where it came from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WarmupReport {
+    /// Per-indicator lookbacks, in composition order.
+    pub contributions: Vec<WarmupContribution>,
+    pub multiplier: f64,
+    pub override_bars: Option<usize>,
+    /// Explicit engine floor (0 unless set on `EngineConfig`).
+    pub minimum: usize,
+    /// Bars skipped before signals are evaluated.
+    pub applied: usize,
+    /// Bars left after the warmup.
+    pub tradable_bars: usize,
+}
+
+impl Default for WarmupReport {
+    fn default() -> Self {
+        WarmupPolicy::default().report(&[], 0, 0)
+    }
+}
+
+impl WarmupReport {
+    /// The indicator that set the warmup, unless an override or the engine
+    /// floor did.
+    pub fn binding(&self) -> Option<&WarmupContribution> {
+        if self.override_bars.is_some() {
+            return None;
+        }
+        self.contributions
+            .iter()
+            .filter(|c| c.bars == self.applied)
+            .max_by_key(|c| c.lookback)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{Ema, Sma};
+
+    fn indicators() -> Vec<Box<dyn Indicator>> {
+        vec![Box::new(Ema::new(200)), Box::new(Sma::new(20))]
+    }
+
+    #[test]
+    fn default_policy_uses_max_lookback() {
+        let report = WarmupPolicy::default().report(&indicators(), 0, 2000);
+        assert_eq!(report.applied, 199);
+        assert_eq!(report.tradable_bars, 1801);
+        assert_eq!(report.contributions.len(), 2);
+        assert_eq!(report.binding().unwrap().indicator, "ema_200");
+    }
+
+    #[test]
+    fn multiplier_scales_each_lookback_and_override_replaces_it() {
+        let scaled = WarmupPolicy {
+            multiplier: 1.5,
+            override_bars: None,
+        }
+        .report(&indicators(), 0, 250);
+        assert_eq!(scaled.contributions[0].bars, 299);
+        assert_eq!(scaled.contributions[1].bars, 29);
+        assert_eq!(scaled.applied, 299);
+        assert_eq!(scaled.tradable_bars, 0);
+
+        let fixed = WarmupPolicy {
+            multiplier: 1.0,
+            override_bars: Some(30),
+        }
+        .report(&indicators(), 50, 250);
+        assert_eq!(fixed.applied, 30);
+        assert!(fixed.binding().is_none());
+
+        // The engine floor wins over shorter lookbacks
+        let floored = WarmupPolicy::default().report(&indicators(), 250, 300);
+        assert_eq!(floored.applied, 250);
+        assert!(floored.binding().is_none());
+    }
+
+    #[test]
+    fn params_round_trip_and_default_writes_nothing() {
+        let mut params = BTreeMap::new();
+        WarmupPolicy::default().write_params(&mut params);
+        assert!(params.is_empty());
+
+        let policy = WarmupPolicy {
+            multiplier: 3.0,
+            override_bars: Some(400),
+        };
+        policy.write_params(&mut params);
+        assert_eq!(WarmupPolicy::from_params(&params), policy);
+
+        // Multipliers below 1 would end the warmup before indicators have output
+        params.insert(PARAM_WARMUP_MULTIPLIER.into(), 0.5);
+        assert_eq!(WarmupPolicy::from_params(&params).multiplier, 1.0);
+    }
+}
@@ -4,11 +4,14 @@
 //! - `ConfigHash`: structural identity (component types only, no parameter values).
 //! - `FullHash`: exact identity (component types + all parameter values).
 //! - `RunFingerprint`: complete record of a backtest run for the JSONL history.
+//! - `Provenance`: the parent a mutated config descends from, and what changed.
 
+use crate::components::execution::ExecutionProfile;
 use crate::domain::{ConfigHash, DatasetHash, FullHash, RunId};
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
 
 /// Configuration of a single component (signal, PM, execution model, or filter).
 ///
@@ -17,6 +20,25 @@ use std::collections::BTreeMap;
 pub struct ComponentConfig {
     pub component_type: String,
     pub params: BTreeMap<String, f64>,
+    /// Nested components for composite types (e.g. a `composite` PM).
+    ///
+    /// Omitted from serialization when empty so hashes of non-composite
+    /// configs are unchanged.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<ComponentConfig>,
+}
+
+impl ComponentConfig {
+    /// Structural name: the component type, with child types in order for composites.
+    ///
+    /// `"atr_trailing"` for a leaf, `"composite(fixed_stop_loss,atr_trailing)"` for a composite.
+    pub fn structural_name(&self) -> String {
+        if self.children.is_empty() {
+            return self.component_type.clone();
+        }
+        let children: Vec<String> = self.children.iter().map(|c| c.structural_name()).collect();
+        format!("{}({})", self.component_type, children.join(","))
+    }
 }
 
 /// Complete strategy configuration: four components.
@@ -40,10 +62,10 @@ impl StrategyConfig {
     pub fn config_hash(&self) -> ConfigHash {
         let structural = format!(
             "{}+{}+{}+{}",
-            self.signal.component_type,
-            self.position_manager.component_type,
-            self.execution_model.component_type,
-            self.signal_filter.component_type,
+            self.signal.structural_name(),
+            self.position_manager.structural_name(),
+            self.execution_model.structural_name(),
+            self.signal_filter.structural_name(),
         );
         ConfigHash::from_bytes(structural.as_bytes())
     }
@@ -56,6 +78,140 @@ impl StrategyConfig {
         let json = serde_json::to_string(self).expect("StrategyConfig must serialize");
         FullHash::from_bytes(json.as_bytes())
     }
+
+    /// Changes that turn `self` into `other`, component by component.
+    ///
+    /// A component whose type (or composite child structure) differs is
+    /// reported as one `Component` change; otherwise each differing parameter
+    /// is reported, including those of composite children.
+    pub fn diff(&self, other: &StrategyConfig) -> Vec<ConfigChange> {
+        let mut changes = Vec::new();
+        let slots = [
+            ("signal", &self.signal, &other.signal),
+            (
+                "position_manager",
+                &self.position_manager,
+                &other.position_manager,
+            ),
+            (
+                "execution_model",
+                &self.execution_model,
+                &other.execution_model,
+            ),
+            ("signal_filter", &self.signal_filter, &other.signal_filter),
+        ];
+        for (slot, from, to) in slots {
+            diff_component(slot, from, to, &mut changes);
+        }
+        changes
+    }
+}
+
+fn diff_component(
+    slot: &str,
+    from: &ComponentConfig,
+    to: &ComponentConfig,
+    out: &mut Vec<ConfigChange>,
+) {
+    let (from_name, to_name) = (from.structural_name(), to.structural_name());
+    if from_name != to_name {
+        out.push(ConfigChange::Component {
+            slot: slot.to_string(),
+            from: from_name,
+            to: to_name,
+        });
+        return;
+    }
+    let keys: BTreeSet<&String> = from.params.keys().chain(to.params.keys()).collect();
+    for key in keys {
+        let (a, b) = (from.params.get(key).copied(), to.params.get(key).copied());
+        if a != b {
+            out.push(ConfigChange::Param {
+                slot: slot.to_string(),
+                component: from.component_type.clone(),
+                param: key.clone(),
+                from: a,
+                to: b,
+            });
+        }
+    }
+    for (i, (a, b)) in from.children.iter().zip(&to.children).enumerate() {
+        diff_component(&format!("{slot}.children[{i}]"), a, b, out);
+    }
+}
+
+/// One difference between two strategy configs (see [`StrategyConfig::diff`]).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ConfigChange {
+    /// The component in `slot` was replaced by a different type.
+    Component {
+        slot: String,
+        from: String,
+        to: String,
+    },
+    /// A parameter changed value, or is set on only one side.
+    Param {
+        slot: String,
+        component: String,
+        param: String,
+        from: Option<f64>,
+        to: Option<f64>,
+    },
+}
+
+impl fmt::Display for ConfigChange {
+    /// `"atr_trailing multiplier 3.0 → 2.6"` or `"signal: donchian_breakout → keltner_breakout"`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Component { slot, from, to } => write!(f, "{slot}: {from} → {to}"),
+            Self::Param {
+                component,
+                param,
+                from,
+                to,
+                ..
+            } => write!(
+                f,
+                "{component} {param} {} → {}",
+                format_param(*from),
+                format_param(*to)
+            ),
+        }
+    }
+}
+
+/// Whole numbers keep one decimal; others are trimmed to four.
+fn format_param(value: Option<f64>) -> String {
+    match value {
+        None => "unset".into(),
+        Some(v) if v.fract() == 0.0 => format!("{v:.1}"),
+        Some(v) => {
+            let s = format!("{v:.4}");
+            s.trim_end_matches('0').to_string()
+        }
+    }
+}
+
+/// Where a mutated config came from.
+///
+/// Parents are identified by `full_hash`: `config_hash` is structural, so a
+/// parameter-only mutation shares it with its parent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Provenance {
+    pub parent_full_hash: FullHash,
+    pub parent_config_hash: ConfigHash,
+    /// Changes from the parent to the child.
+    pub changes: Vec<ConfigChange>,
+}
+
+impl Provenance {
+    pub fn new(parent: &StrategyConfig, child: &StrategyConfig) -> Self {
+        Self {
+            parent_full_hash: parent.full_hash(),
+            parent_config_hash: parent.config_hash(),
+            changes: parent.diff(child),
+        }
+    }
 }
 
 /// Trading mode: which directions are allowed.
@@ -86,6 +242,12 @@ pub struct RunFingerprint {
 
     // ── Components ──
     pub strategy_config: StrategyConfig,
+    /// Per-symbol friction override the run used, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_profile: Option<ExecutionProfile>,
+    /// Name of the risk profile whose overrides `strategy_config` carries, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk_profile: Option<String>,
 
     // ── Derived hashes ──
     pub config_hash: ConfigHash,
@@ -107,6 +269,7 @@ mod tests {
                     m.insert("exit_lookback".into(), 20.0);
                     m
                 },
+                children: Vec::new(),
             },
             position_manager: ComponentConfig {
                 component_type: "atr_trailing".into(),
@@ -116,14 +279,17 @@ mod tests {
                     m.insert("multiplier".into(), 3.0);
                     m
                 },
+                children: Vec::new(),
             },
             execution_model: ComponentConfig {
                 component_type: "next_bar_open".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
             signal_filter: ComponentConfig {
                 component_type: "no_filter".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
         }
     }
@@ -175,6 +341,140 @@ mod tests {
         assert_eq!(config.full_hash(), deser.full_hash());
     }
 
+    #[test]
+    fn leaf_config_omits_children_from_json() {
+        let json = serde_json::to_string(&sample_config()).unwrap();
+        assert!(!json.contains("children"));
+        let deser: StrategyConfig = serde_json::from_str(&json).unwrap();
+        assert!(deser.position_manager.children.is_empty());
+    }
+
+    #[test]
+    fn composite_children_affect_both_hashes() {
+        let leaf = |t: &str| ComponentConfig {
+            component_type: t.into(),
+            params: BTreeMap::new(),
+            children: Vec::new(),
+        };
+        let mut c1 = sample_config();
+        c1.position_manager = ComponentConfig {
+            component_type: "composite".into(),
+            params: BTreeMap::new(),
+            children: vec![leaf("fixed_stop_loss"), leaf("atr_trailing")],
+        };
+        let mut c2 = c1.clone();
+        c2.position_manager.children[1] = leaf("chandelier");
+
+        assert_eq!(
+            c1.position_manager.structural_name(),
+            "composite(fixed_stop_loss,atr_trailing)"
+        );
+        assert_ne!(c1.config_hash(), c2.config_hash());
+        assert_ne!(c1.full_hash(), c2.full_hash());
+
+        let json = serde_json::to_string(&c1).unwrap();
+        let deser: StrategyConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(c1, deser);
+    }
+
+    #[test]
+    fn ensemble_child_params_affect_full_hash_only() {
+        let child = |lookback: f64| ComponentConfig {
+            component_type: "donchian_breakout".into(),
+            params: [("entry_lookback".to_string(), lookback)].into(),
+            children: Vec::new(),
+        };
+        let mut c1 = sample_config();
+        c1.signal = ComponentConfig {
+            component_type: "ensemble".into(),
+            params: BTreeMap::new(),
+            children: vec![child(20.0), child(55.0)],
+        };
+        let mut c2 = c1.clone();
+        c2.signal.children[1].params.insert("entry_lookback".into(), 60.0);
+
+        assert_eq!(c1.config_hash(), c2.config_hash());
+        assert_ne!(c1.full_hash(), c2.full_hash());
+    }
+
+    #[test]
+    fn diff_reports_param_changes() {
+        let parent = sample_config();
+        let mut child = sample_config();
+        child
+            .position_manager
+            .params
+            .insert("multiplier".into(), 2.6);
+        child.signal.params.remove("exit_lookback");
+
+        assert!(parent.diff(&parent).is_empty());
+        let changes = parent.diff(&child);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(
+            changes[0].to_string(),
+            "donchian_breakout exit_lookback 20.0 → unset"
+        );
+        assert_eq!(changes[1].to_string(), "atr_trailing multiplier 3.0 → 2.6");
+    }
+
+    #[test]
+    fn diff_reports_component_swaps_without_params() {
+        let parent = sample_config();
+        let mut child = sample_config();
+        child.signal.component_type = "keltner_breakout".into();
+        child.signal.params.insert("entry_lookback".into(), 10.0);
+
+        assert_eq!(
+            parent.diff(&child),
+            vec![ConfigChange::Component {
+                slot: "signal".into(),
+                from: "donchian_breakout".into(),
+                to: "keltner_breakout".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_descends_into_composite_children() {
+        let leaf = |m: f64| ComponentConfig {
+            component_type: "atr_trailing".into(),
+            params: BTreeMap::from([("multiplier".to_string(), m)]),
+            children: Vec::new(),
+        };
+        let mut parent = sample_config();
+        parent.position_manager = ComponentConfig {
+            component_type: "composite".into(),
+            params: BTreeMap::new(),
+            children: vec![leaf(3.0)],
+        };
+        let mut child = parent.clone();
+        child.position_manager.children[0] = leaf(3.25);
+
+        let changes = parent.diff(&child);
+        assert_eq!(changes.len(), 1);
+        assert!(matches!(
+            &changes[0],
+            ConfigChange::Param { slot, .. } if slot == "position_manager.children[0]"
+        ));
+        assert_eq!(changes[0].to_string(), "atr_trailing multiplier 3.0 → 3.25");
+    }
+
+    #[test]
+    fn provenance_records_parent_hashes() {
+        let parent = sample_config();
+        let mut child = sample_config();
+        child.signal.params.insert("entry_lookback".into(), 55.0);
+
+        let provenance = Provenance::new(&parent, &child);
+        assert_eq!(provenance.parent_full_hash, parent.full_hash());
+        assert_eq!(provenance.parent_config_hash, child.config_hash());
+        assert_eq!(provenance.changes.len(), 1);
+
+        let json = serde_json::to_string(&provenance).unwrap();
+        let deser: Provenance = serde_json::from_str(&json).unwrap();
+        assert_eq!(provenance, deser);
+    }
+
     #[test]
     fn trading_mode_serialization() {
         let mode = TradingMode::LongOnly;
@@ -9,6 +9,8 @@
 //! 6. ADX = Wilder-smoothed DX
 //!
 //! Lookback: 2 * period (period for DI smoothing, then period for ADX smoothing).
+//!
+//! +DI and -DI are also exposed on their own (see [`DirectionalIndicator`]).
 
 use crate::components::indicator::Indicator;
 use crate::domain::Bar;
@@ -40,75 +42,139 @@ impl Indicator for Adx {
     }
 
     fn compute(&self, bars: &[Bar]) -> Vec<f64> {
-        let n = bars.len();
-        let result = vec![f64::NAN; n];
+        let (plus_di, minus_di) = directional_indicators(bars, self.period);
+
+        // Step 5-6: DX from the two DIs, Wilder-smoothed to get ADX
+        let dx: Vec<f64> = plus_di
+            .iter()
+            .zip(&minus_di)
+            .map(|(&plus, &minus)| {
+                let di_sum = plus + minus;
+                if di_sum == 0.0 {
+                    0.0
+                } else {
+                    100.0 * (plus - minus).abs() / di_sum
+                }
+            })
+            .collect();
+        wilder_smooth(&dx, self.period)
+    }
+}
+
+/// Which directional indicator to compute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiSide {
+    Plus,
+    Minus,
+}
+
+/// +DI or -DI, the directional indicators behind ADX.
+///
+/// Lookback: period.
+#[derive(Debug, Clone)]
+pub struct DirectionalIndicator {
+    period: usize,
+    side: DiSide,
+    name: String,
+}
 
-        if n < 2 {
-            return result;
+impl DirectionalIndicator {
+    pub fn plus(period: usize) -> Self {
+        assert!(period >= 1, "DI period must be >= 1");
+        Self {
+            period,
+            side: DiSide::Plus,
+            name: format!("plus_di_{period}"),
         }
+    }
 
-        // Step 1: Compute +DM and -DM
-        let mut plus_dm = vec![f64::NAN; n];
-        let mut minus_dm = vec![f64::NAN; n];
-
-        for i in 1..n {
-            let high_diff = bars[i].high - bars[i - 1].high;
-            let low_diff = bars[i - 1].low - bars[i].low;
-
-            if bars[i].high.is_nan()
-                || bars[i].low.is_nan()
-                || bars[i - 1].high.is_nan()
-                || bars[i - 1].low.is_nan()
-            {
-                plus_dm[i] = f64::NAN;
-                minus_dm[i] = f64::NAN;
-                continue;
-            }
+    pub fn minus(period: usize) -> Self {
+        assert!(period >= 1, "DI period must be >= 1");
+        Self {
+            period,
+            side: DiSide::Minus,
+            name: format!("minus_di_{period}"),
+        }
+    }
+}
 
-            if high_diff > low_diff && high_diff > 0.0 {
-                plus_dm[i] = high_diff;
-            } else {
-                plus_dm[i] = 0.0;
-            }
+impl Indicator for DirectionalIndicator {
+    fn name(&self) -> &str {
+        &self.name
+    }
 
-            if low_diff > high_diff && low_diff > 0.0 {
-                minus_dm[i] = low_diff;
-            } else {
-                minus_dm[i] = 0.0;
-            }
+    fn lookback(&self) -> usize {
+        self.period
+    }
+
+    fn compute(&self, bars: &[Bar]) -> Vec<f64> {
+        let (plus_di, minus_di) = directional_indicators(bars, self.period);
+        match self.side {
+            DiSide::Plus => plus_di,
+            DiSide::Minus => minus_di,
         }
+    }
+}
 
-        // Step 2: Wilder smooth +DM, -DM, and TR
-        let tr = true_range(bars);
-        let smooth_tr = wilder_smooth(&tr, self.period);
-        let smooth_plus_dm = wilder_smooth(&plus_dm, self.period);
-        let smooth_minus_dm = wilder_smooth(&minus_dm, self.period);
-
-        // Step 3-4: Compute +DI and -DI, then DX
-        let mut dx = vec![f64::NAN; n];
-        for i in 0..n {
-            if smooth_tr[i].is_nan()
-                || smooth_plus_dm[i].is_nan()
-                || smooth_minus_dm[i].is_nan()
-                || smooth_tr[i] == 0.0
-            {
-                continue;
-            }
+/// +DI and -DI series (steps 1-4). NaN where the smoothed true range is
+/// missing or zero.
+fn directional_indicators(bars: &[Bar], period: usize) -> (Vec<f64>, Vec<f64>) {
+    let n = bars.len();
+    let mut plus_di = vec![f64::NAN; n];
+    let mut minus_di = vec![f64::NAN; n];
+
+    if n < 2 {
+        return (plus_di, minus_di);
+    }
 
-            let plus_di = 100.0 * smooth_plus_dm[i] / smooth_tr[i];
-            let minus_di = 100.0 * smooth_minus_dm[i] / smooth_tr[i];
-            let di_sum = plus_di + minus_di;
+    // Step 1: Compute +DM and -DM
+    let mut plus_dm = vec![f64::NAN; n];
+    let mut minus_dm = vec![f64::NAN; n];
 
-            if di_sum == 0.0 {
-                dx[i] = 0.0;
-            } else {
-                dx[i] = 100.0 * (plus_di - minus_di).abs() / di_sum;
-            }
+    for i in 1..n {
+        let high_diff = bars[i].high - bars[i - 1].high;
+        let low_diff = bars[i - 1].low - bars[i].low;
+
+        if bars[i].high.is_nan()
+            || bars[i].low.is_nan()
+            || bars[i - 1].high.is_nan()
+            || bars[i - 1].low.is_nan()
+        {
+            continue;
         }
 
-        // Step 5-6: Wilder smooth DX to get ADX
-        wilder_smooth(&dx, self.period)
+        plus_dm[i] = if high_diff > low_diff && high_diff > 0.0 {
+            high_diff
+        } else {
+            0.0
+        };
+        minus_dm[i] = if low_diff > high_diff && low_diff > 0.0 {
+            low_diff
+        } else {
+            0.0
+        };
+    }
+
+    // Step 2: Wilder smooth +DM, -DM, and TR
+    let tr = true_range(bars);
+    let smooth_tr = wilder_smooth(&tr, period);
+    let smooth_plus_dm = wilder_smooth(&plus_dm, period);
+    let smooth_minus_dm = wilder_smooth(&minus_dm, period);
+
+    // Step 3-4: +DI and -DI
+    for i in 0..n {
+        if smooth_tr[i].is_nan()
+            || smooth_plus_dm[i].is_nan()
+            || smooth_minus_dm[i].is_nan()
+            || smooth_tr[i] == 0.0
+        {
+            continue;
+        }
+        plus_di[i] = 100.0 * smooth_plus_dm[i] / smooth_tr[i];
+        minus_di[i] = 100.0 * smooth_minus_dm[i] / smooth_tr[i];
     }
+
+    (plus_di, minus_di)
 }
 
 #[cfg(test)]
@@ -121,7 +187,7 @@ mod tests {
         data.iter()
             .enumerate()
             .map(|(i, &(open, high, low, close))| Bar {
-                symbol: "TEST".to_string(),
+                symbol: "TEST".into(),
                 date: base_date + chrono::Duration::days(i as i64),
                 open,
                 high,
@@ -193,4 +259,24 @@ mod tests {
         let result = adx.compute(&bars);
         assert!(result.iter().all(|v| v.is_nan()));
     }
+
+    #[test]
+    fn di_follows_trend_direction() {
+        let data: Vec<_> = (0..20)
+            .map(|i| {
+                let base = 100.0 + i as f64 * 2.0;
+                (base - 1.0, base + 2.0, base - 2.0, base + 1.0)
+            })
+            .collect();
+        let bars = make_ohlc_bars(&data);
+        let plus = DirectionalIndicator::plus(5).compute(&bars);
+        let minus = DirectionalIndicator::minus(5).compute(&bars);
+
+        assert!(plus[4].is_nan());
+        assert!(!plus[5].is_nan());
+        assert!(plus[19] > minus[19]);
+        assert_eq!(minus[19], 0.0);
+        assert_eq!(DirectionalIndicator::minus(14).lookback(), 14);
+        assert_eq!(DirectionalIndicator::plus(14).name(), "plus_di_14");
+    }
 }
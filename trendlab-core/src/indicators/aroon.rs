@@ -2,7 +2,8 @@
 //!
 //! Aroon Up = 100 * (period - bars_since_highest_high) / period
 //! Aroon Down = 100 * (period - bars_since_lowest_low) / period
-//! Two bands (separate Indicator instances).
+//! Two bands, either as separate per-band instances or both at once from
+//! [`Aroon::both`].
 //! Lookback: period.
 
 use crate::components::indicator::Indicator;
@@ -18,28 +19,83 @@ pub enum AroonBand {
 #[derive(Debug, Clone)]
 pub struct Aroon {
     period: usize,
-    band: AroonBand,
+    /// `None` = both bands.
+    band: Option<AroonBand>,
     name: String,
+    outputs: Vec<String>,
 }
 
 impl Aroon {
     pub fn up(period: usize) -> Self {
         assert!(period >= 1, "Aroon period must be >= 1");
+        let name = format!("aroon_up_{period}");
         Self {
             period,
-            band: AroonBand::Up,
-            name: format!("aroon_up_{period}"),
+            band: Some(AroonBand::Up),
+            outputs: vec![name.clone()],
+            name,
         }
     }
 
     pub fn down(period: usize) -> Self {
         assert!(period >= 1, "Aroon period must be >= 1");
+        let name = format!("aroon_down_{period}");
         Self {
             period,
-            band: AroonBand::Down,
-            name: format!("aroon_down_{period}"),
+            band: Some(AroonBand::Down),
+            outputs: vec![name.clone()],
+            name,
         }
     }
+
+    /// Both bands from a single window scan, stored as `aroon_up_{period}` and
+    /// `aroon_down_{period}`. `compute` returns Aroon Up.
+    pub fn both(period: usize) -> Self {
+        assert!(period >= 1, "Aroon period must be >= 1");
+        Self {
+            period,
+            band: None,
+            name: format!("aroon_{period}"),
+            outputs: vec![format!("aroon_up_{period}"), format!("aroon_down_{period}")],
+        }
+    }
+
+    fn both_bands(&self, bars: &[Bar]) -> Vec<Vec<f64>> {
+        let n = bars.len();
+        let mut up = vec![f64::NAN; n];
+        let mut down = vec![f64::NAN; n];
+
+        for i in self.period..n {
+            let window = &bars[i - self.period..=i];
+            let mut max_val = f64::NEG_INFINITY;
+            let mut min_val = f64::INFINITY;
+            let (mut max_offset, mut min_offset) = (0, 0);
+            let (mut high_nan, mut low_nan) = (false, false);
+
+            // Most recent extreme wins ties, as in the per-band scan.
+            for (j, bar) in window.iter().enumerate() {
+                high_nan |= bar.high.is_nan();
+                low_nan |= bar.low.is_nan();
+                if bar.high >= max_val {
+                    max_val = bar.high;
+                    max_offset = j;
+                }
+                if bar.low <= min_val {
+                    min_val = bar.low;
+                    min_offset = j;
+                }
+            }
+
+            if !high_nan {
+                up[i] = 100.0 * max_offset as f64 / self.period as f64;
+            }
+            if !low_nan {
+                down[i] = 100.0 * min_offset as f64 / self.period as f64;
+            }
+        }
+
+        vec![up, down]
+    }
 }
 
 impl Indicator for Aroon {
@@ -51,7 +107,22 @@ impl Indicator for Aroon {
         self.period
     }
 
+    fn outputs(&self) -> Vec<&str> {
+        self.outputs.iter().map(String::as_str).collect()
+    }
+
+    fn compute_all(&self, bars: &[Bar]) -> Vec<Vec<f64>> {
+        match self.band {
+            Some(_) => vec![self.compute(bars)],
+            None => self.both_bands(bars),
+        }
+    }
+
     fn compute(&self, bars: &[Bar]) -> Vec<f64> {
+        let band = match self.band {
+            Some(band) => band,
+            None => return self.both_bands(bars).swap_remove(0),
+        };
         let n = bars.len();
         let mut result = vec![f64::NAN; n];
 
@@ -64,7 +135,7 @@ impl Indicator for Aroon {
             let window = &bars[start..=i];
 
             // Check for NaN
-            let has_nan = match self.band {
+            let has_nan = match band {
                 AroonBand::Up => window.iter().any(|b| b.high.is_nan()),
                 AroonBand::Down => window.iter().any(|b| b.low.is_nan()),
             };
@@ -74,7 +145,7 @@ impl Indicator for Aroon {
                 continue;
             }
 
-            match self.band {
+            match band {
                 AroonBand::Up => {
                     // Find the index of the highest high in the window
                     // (most recent if tied)
@@ -113,7 +184,7 @@ impl Indicator for Aroon {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::indicators::{assert_approx, DEFAULT_EPSILON};
+    use crate::indicators::{assert_approx, assert_same_series, DEFAULT_EPSILON};
     use chrono::NaiveDate;
 
     fn make_ohlc_bars(data: &[(f64, f64, f64, f64)]) -> Vec<Bar> {
@@ -121,7 +192,7 @@ mod tests {
         data.iter()
             .enumerate()
             .map(|(i, &(open, high, low, close))| Bar {
-                symbol: "TEST".to_string(),
+                symbol: "TEST".into(),
                 date: base_date + chrono::Duration::days(i as i64),
                 open,
                 high,
@@ -222,6 +293,22 @@ mod tests {
         assert!(result[2].is_nan());
     }
 
+    #[test]
+    fn both_matches_per_band_outputs() {
+        let mut bars = crate::indicators::wavy_bars(200);
+        bars[40].high = f64::NAN;
+        bars[90].low = f64::NAN;
+
+        let both = Aroon::both(25);
+        let (up, down) = (Aroon::up(25), Aroon::down(25));
+        assert_eq!(both.outputs(), vec![up.name(), down.name()]);
+
+        let all = both.compute_all(&bars);
+        assert_eq!(all.len(), 2);
+        assert_same_series(&all[0], &up.compute(&bars));
+        assert_same_series(&all[1], &down.compute(&bars));
+    }
+
     #[test]
     fn aroon_lookback() {
         assert_eq!(Aroon::up(25).lookback(), 25);
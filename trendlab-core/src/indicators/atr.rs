@@ -157,7 +157,7 @@ mod tests {
         data.iter()
             .enumerate()
             .map(|(i, &(open, high, low, close))| Bar {
-                symbol: "TEST".to_string(),
+                symbol: "TEST".into(),
                 date: base_date + chrono::Duration::days(i as i64),
                 open,
                 high,
@@ -1,6 +1,7 @@
 //! Bollinger Bands — moving average +/- standard deviation multiplier.
 //!
-//! Three bands (separate Indicator instances):
+//! Three bands, either as separate per-band instances or all at once from
+//! [`Bollinger::bands`]:
 //! - Middle: SMA(close, period)
 //! - Upper: middle + mult * stddev(close, period)
 //! - Lower: middle - mult * stddev(close, period)
@@ -23,40 +24,99 @@ pub enum BollingerBand {
 pub struct Bollinger {
     period: usize,
     multiplier: f64,
-    band: BollingerBand,
+    /// `None` = all three bands.
+    band: Option<BollingerBand>,
     name: String,
+    outputs: Vec<String>,
 }
 
 impl Bollinger {
     pub fn upper(period: usize, multiplier: f64) -> Self {
         assert!(period >= 1, "Bollinger period must be >= 1");
+        let name = format!("bollinger_upper_{period}_{multiplier}");
         Self {
             period,
             multiplier,
-            band: BollingerBand::Upper,
-            name: format!("bollinger_upper_{period}_{multiplier}"),
+            band: Some(BollingerBand::Upper),
+            outputs: vec![name.clone()],
+            name,
         }
     }
 
     pub fn middle(period: usize, multiplier: f64) -> Self {
         assert!(period >= 1, "Bollinger period must be >= 1");
+        let name = format!("bollinger_middle_{period}_{multiplier}");
         Self {
             period,
             multiplier,
-            band: BollingerBand::Middle,
-            name: format!("bollinger_middle_{period}_{multiplier}"),
+            band: Some(BollingerBand::Middle),
+            outputs: vec![name.clone()],
+            name,
         }
     }
 
     pub fn lower(period: usize, multiplier: f64) -> Self {
         assert!(period >= 1, "Bollinger period must be >= 1");
+        let name = format!("bollinger_lower_{period}_{multiplier}");
         Self {
             period,
             multiplier,
-            band: BollingerBand::Lower,
-            name: format!("bollinger_lower_{period}_{multiplier}"),
+            band: Some(BollingerBand::Lower),
+            outputs: vec![name.clone()],
+            name,
         }
     }
+
+    /// All three bands in one pass (mean and stddev computed once per bar),
+    /// stored under the upper, middle and lower band keys in that order.
+    /// `compute` returns the upper band.
+    pub fn bands(period: usize, multiplier: f64) -> Self {
+        assert!(period >= 1, "Bollinger period must be >= 1");
+        Self {
+            period,
+            multiplier,
+            band: None,
+            name: format!("bollinger_{period}_{multiplier}"),
+            outputs: vec![
+                format!("bollinger_upper_{period}_{multiplier}"),
+                format!("bollinger_middle_{period}_{multiplier}"),
+                format!("bollinger_lower_{period}_{multiplier}"),
+            ],
+        }
+    }
+
+    fn all_bands(&self, bars: &[Bar]) -> Vec<Vec<f64>> {
+        let n = bars.len();
+        let mut upper = vec![f64::NAN; n];
+        let mut middle = vec![f64::NAN; n];
+        let mut lower = vec![f64::NAN; n];
+
+        if n >= self.period {
+            for i in (self.period - 1)..n {
+                let window = &bars[i + 1 - self.period..=i];
+                if window.iter().any(|bar| bar.close.is_nan()) {
+                    continue;
+                }
+                let sum = window.iter().fold(0.0, |acc, bar| acc + bar.close);
+                let mean = sum / self.period as f64;
+                let variance: f64 = window
+                    .iter()
+                    .map(|bar| {
+                        let diff = bar.close - mean;
+                        diff * diff
+                    })
+                    .sum::<f64>()
+                    / self.period as f64;
+                let stddev = variance.sqrt();
+
+                upper[i] = mean + self.multiplier * stddev;
+                middle[i] = mean;
+                lower[i] = mean - self.multiplier * stddev;
+            }
+        }
+
+        vec![upper, middle, lower]
+    }
 }
 
 impl Indicator for Bollinger {
@@ -68,7 +128,22 @@ impl Indicator for Bollinger {
         self.period.saturating_sub(1)
     }
 
+    fn outputs(&self) -> Vec<&str> {
+        self.outputs.iter().map(String::as_str).collect()
+    }
+
+    fn compute_all(&self, bars: &[Bar]) -> Vec<Vec<f64>> {
+        match self.band {
+            Some(_) => vec![self.compute(bars)],
+            None => self.all_bands(bars),
+        }
+    }
+
     fn compute(&self, bars: &[Bar]) -> Vec<f64> {
+        let band = match self.band {
+            Some(band) => band,
+            None => return self.all_bands(bars).swap_remove(0),
+        };
         let n = bars.len();
         let mut result = vec![f64::NAN; n];
 
@@ -98,7 +173,7 @@ impl Indicator for Bollinger {
 
             let mean = sum / self.period as f64;
 
-            match self.band {
+            match band {
                 BollingerBand::Middle => {
                     result[i] = mean;
                 }
@@ -114,7 +189,7 @@ impl Indicator for Bollinger {
                         / self.period as f64;
                     let stddev = variance.sqrt();
 
-                    result[i] = match self.band {
+                    result[i] = match band {
                         BollingerBand::Upper => mean + self.multiplier * stddev,
                         BollingerBand::Lower => mean - self.multiplier * stddev,
                         _ => unreachable!(),
@@ -130,7 +205,7 @@ impl Indicator for Bollinger {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::indicators::{assert_approx, make_bars, DEFAULT_EPSILON};
+    use crate::indicators::{assert_approx, assert_same_series, make_bars, DEFAULT_EPSILON};
 
     #[test]
     fn bollinger_middle_is_sma() {
@@ -187,6 +262,27 @@ mod tests {
         assert!(result[3].is_nan()); // window includes NaN bar 2
     }
 
+    #[test]
+    fn bands_match_per_band_outputs() {
+        let mut bars = crate::indicators::wavy_bars(200);
+        bars[80].close = f64::NAN;
+
+        let bands = Bollinger::bands(20, 2.0);
+        let per_band = [
+            Bollinger::upper(20, 2.0),
+            Bollinger::middle(20, 2.0),
+            Bollinger::lower(20, 2.0),
+        ];
+        let names: Vec<&str> = per_band.iter().map(|b| b.name()).collect();
+        assert_eq!(bands.outputs(), names);
+
+        let all = bands.compute_all(&bars);
+        assert_eq!(all.len(), 3);
+        for (series, single) in all.iter().zip(&per_band) {
+            assert_same_series(series, &single.compute(&bars));
+        }
+    }
+
     #[test]
     fn bollinger_lookback() {
         assert_eq!(Bollinger::upper(20, 2.0).lookback(), 19);
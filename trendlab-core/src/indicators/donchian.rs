@@ -1,11 +1,14 @@
 //! Donchian Channel — highest high / lowest low over a lookback window.
 //!
-//! Produces two series (exposed as separate Indicator instances):
+//! Produces two series, either as separate per-band instances or both at once
+//! from [`Donchian::channel`]:
 //! - Upper: max(high[t-period+1..=t])
 //! - Lower: min(low[t-period+1..=t])
 //!
 //! Lookback: period - 1.
 
+use std::collections::VecDeque;
+
 use crate::components::indicator::Indicator;
 use crate::domain::Bar;
 
@@ -19,27 +22,99 @@ pub enum DonchianBand {
 #[derive(Debug, Clone)]
 pub struct Donchian {
     period: usize,
-    band: DonchianBand,
+    /// `None` = full channel (both bands).
+    band: Option<DonchianBand>,
     name: String,
+    outputs: Vec<String>,
 }
 
 impl Donchian {
     pub fn upper(period: usize) -> Self {
         assert!(period >= 1, "Donchian period must be >= 1");
+        let name = format!("donchian_upper_{period}");
         Self {
             period,
-            band: DonchianBand::Upper,
-            name: format!("donchian_upper_{period}"),
+            band: Some(DonchianBand::Upper),
+            outputs: vec![name.clone()],
+            name,
         }
     }
 
     pub fn lower(period: usize) -> Self {
         assert!(period >= 1, "Donchian period must be >= 1");
+        let name = format!("donchian_lower_{period}");
         Self {
             period,
-            band: DonchianBand::Lower,
-            name: format!("donchian_lower_{period}"),
+            band: Some(DonchianBand::Lower),
+            outputs: vec![name.clone()],
+            name,
+        }
+    }
+
+    /// Both bands in one pass, stored as `donchian_upper_{period}` and
+    /// `donchian_lower_{period}`. `compute` returns the upper band.
+    pub fn channel(period: usize) -> Self {
+        assert!(period >= 1, "Donchian period must be >= 1");
+        Self {
+            period,
+            band: None,
+            name: format!("donchian_{period}"),
+            outputs: vec![
+                format!("donchian_upper_{period}"),
+                format!("donchian_lower_{period}"),
+            ],
+        }
+    }
+
+    /// Sliding max of highs and min of lows using monotonic deques, O(n).
+    /// A window containing a NaN yields NaN.
+    fn channel_bands(&self, bars: &[Bar]) -> (Vec<f64>, Vec<f64>) {
+        let n = bars.len();
+        let mut upper = vec![f64::NAN; n];
+        let mut lower = vec![f64::NAN; n];
+        let mut max_idx: VecDeque<usize> = VecDeque::new();
+        let mut min_idx: VecDeque<usize> = VecDeque::new();
+        let mut last_nan_high: Option<usize> = None;
+        let mut last_nan_low: Option<usize> = None;
+
+        for (i, bar) in bars.iter().enumerate() {
+            let start = (i + 1).saturating_sub(self.period);
+            while max_idx.front().is_some_and(|&j| j < start) {
+                max_idx.pop_front();
+            }
+            while min_idx.front().is_some_and(|&j| j < start) {
+                min_idx.pop_front();
+            }
+
+            if bar.high.is_nan() {
+                last_nan_high = Some(i);
+            } else {
+                while max_idx.back().is_some_and(|&j| bars[j].high <= bar.high) {
+                    max_idx.pop_back();
+                }
+                max_idx.push_back(i);
+            }
+            if bar.low.is_nan() {
+                last_nan_low = Some(i);
+            } else {
+                while min_idx.back().is_some_and(|&j| bars[j].low >= bar.low) {
+                    min_idx.pop_back();
+                }
+                min_idx.push_back(i);
+            }
+
+            if i + 1 < self.period {
+                continue;
+            }
+            if !last_nan_high.is_some_and(|j| j >= start) {
+                upper[i] = bars[max_idx[0]].high;
+            }
+            if !last_nan_low.is_some_and(|j| j >= start) {
+                lower[i] = bars[min_idx[0]].low;
+            }
         }
+
+        (upper, lower)
     }
 }
 
@@ -52,7 +127,25 @@ impl Indicator for Donchian {
         self.period.saturating_sub(1)
     }
 
+    fn outputs(&self) -> Vec<&str> {
+        self.outputs.iter().map(String::as_str).collect()
+    }
+
+    fn compute_all(&self, bars: &[Bar]) -> Vec<Vec<f64>> {
+        match self.band {
+            Some(_) => vec![self.compute(bars)],
+            None => {
+                let (upper, lower) = self.channel_bands(bars);
+                vec![upper, lower]
+            }
+        }
+    }
+
     fn compute(&self, bars: &[Bar]) -> Vec<f64> {
+        let band = match self.band {
+            Some(band) => band,
+            None => return self.channel_bands(bars).0,
+        };
         let n = bars.len();
         let mut result = vec![f64::NAN; n];
 
@@ -64,7 +157,7 @@ impl Indicator for Donchian {
             let start = i + 1 - self.period;
             let window = &bars[start..=i];
 
-            match self.band {
+            match band {
                 DonchianBand::Upper => {
                     let mut max_val = f64::NEG_INFINITY;
                     let mut has_nan = false;
@@ -103,7 +196,7 @@ impl Indicator for Donchian {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::indicators::{assert_approx, DEFAULT_EPSILON};
+    use crate::indicators::{assert_approx, assert_same_series, DEFAULT_EPSILON};
     use chrono::NaiveDate;
 
     fn make_ohlc_bars(data: &[(f64, f64, f64, f64)]) -> Vec<Bar> {
@@ -111,7 +204,7 @@ mod tests {
         data.iter()
             .enumerate()
             .map(|(i, &(open, high, low, close))| Bar {
-                symbol: "TEST".to_string(),
+                symbol: "TEST".into(),
                 date: base_date + chrono::Duration::days(i as i64),
                 open,
                 high,
@@ -183,6 +276,26 @@ mod tests {
         assert!(lower.compute(&bars)[2].is_nan());
     }
 
+    #[test]
+    fn channel_matches_per_band_outputs() {
+        let mut bars = crate::indicators::wavy_bars(200);
+        bars[50].high = f64::NAN;
+        bars[120].low = f64::NAN;
+
+        for period in [1, 3, 20, 55] {
+            let channel = Donchian::channel(period);
+            let upper = Donchian::upper(period);
+            let lower = Donchian::lower(period);
+            assert_eq!(channel.outputs(), vec![upper.name(), lower.name()]);
+
+            let all = channel.compute_all(&bars);
+            assert_eq!(all.len(), 2);
+            assert_same_series(&all[0], &upper.compute(&bars));
+            assert_same_series(&all[1], &lower.compute(&bars));
+            assert_same_series(&channel.compute(&bars), &all[0]);
+        }
+    }
+
     #[test]
     fn donchian_lookback() {
         assert_eq!(Donchian::upper(20).lookback(), 19);
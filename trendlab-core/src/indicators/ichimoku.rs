@@ -0,0 +1,191 @@
+//! Ichimoku Kinko Hyo — range midpoints and the cloud.
+//!
+//! - Tenkan-sen: (highest high + lowest low) / 2 over `tenkan` bars
+//! - Kijun-sen: the same midpoint over `kijun` bars
+//! - Senkou span A: (Tenkan + Kijun) / 2, plotted `kijun` bars ahead
+//! - Senkou span B: the midpoint over `senkou_b` bars, plotted `kijun` bars ahead
+//!
+//! The spans are displaced forward, so the cloud at bar t is built from bars
+//! up to t - kijun only. Four lines (separate Indicator instances).
+//! Lookback: period - 1 for Tenkan and Kijun; the longest span period - 1,
+//! plus the `kijun` displacement, for the spans.
+
+use crate::components::indicator::Indicator;
+use crate::domain::Bar;
+
+/// Which Ichimoku line to compute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IchimokuLine {
+    Tenkan,
+    Kijun,
+    SpanA,
+    SpanB,
+}
+
+#[derive(Debug, Clone)]
+pub struct Ichimoku {
+    line: IchimokuLine,
+    /// Midpoint period (Tenkan, Kijun, span B) or the Tenkan period (span A).
+    period: usize,
+    /// Kijun period, the spans' forward displacement. Unused by Tenkan/Kijun.
+    displacement: usize,
+    name: String,
+}
+
+impl Ichimoku {
+    pub fn tenkan(period: usize) -> Self {
+        assert!(period >= 1, "Ichimoku period must be >= 1");
+        Self {
+            line: IchimokuLine::Tenkan,
+            period,
+            displacement: 0,
+            name: format!("ichimoku_tenkan_{period}"),
+        }
+    }
+
+    pub fn kijun(period: usize) -> Self {
+        assert!(period >= 1, "Ichimoku period must be >= 1");
+        Self {
+            line: IchimokuLine::Kijun,
+            period,
+            displacement: 0,
+            name: format!("ichimoku_kijun_{period}"),
+        }
+    }
+
+    pub fn span_a(tenkan: usize, kijun: usize) -> Self {
+        assert!(tenkan >= 1 && kijun >= 1, "Ichimoku period must be >= 1");
+        Self {
+            line: IchimokuLine::SpanA,
+            period: tenkan,
+            displacement: kijun,
+            name: format!("ichimoku_span_a_{tenkan}_{kijun}"),
+        }
+    }
+
+    pub fn span_b(senkou_b: usize, kijun: usize) -> Self {
+        assert!(senkou_b >= 1 && kijun >= 1, "Ichimoku period must be >= 1");
+        Self {
+            line: IchimokuLine::SpanB,
+            period: senkou_b,
+            displacement: kijun,
+            name: format!("ichimoku_span_b_{senkou_b}_{kijun}"),
+        }
+    }
+}
+
+impl Indicator for Ichimoku {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn lookback(&self) -> usize {
+        match self.line {
+            IchimokuLine::Tenkan | IchimokuLine::Kijun => self.period - 1,
+            IchimokuLine::SpanA => self.period.max(self.displacement) - 1 + self.displacement,
+            IchimokuLine::SpanB => self.period - 1 + self.displacement,
+        }
+    }
+
+    fn compute(&self, bars: &[Bar]) -> Vec<f64> {
+        let unshifted = match self.line {
+            IchimokuLine::Tenkan | IchimokuLine::Kijun => return midpoint(bars, self.period),
+            IchimokuLine::SpanA => {
+                let tenkan = midpoint(bars, self.period);
+                let kijun = midpoint(bars, self.displacement);
+                tenkan
+                    .iter()
+                    .zip(&kijun)
+                    .map(|(t, k)| (t + k) / 2.0)
+                    .collect()
+            }
+            IchimokuLine::SpanB => midpoint(bars, self.period),
+        };
+
+        // Plot forward: bar t gets the value computed on bar t - displacement
+        let n = bars.len();
+        let mut result = vec![f64::NAN; n];
+        for i in self.displacement..n {
+            result[i] = unshifted[i - self.displacement];
+        }
+        result
+    }
+}
+
+/// (highest high + lowest low) / 2 over the last `period` bars, NaN if any
+/// bar in the window is missing its high or low.
+fn midpoint(bars: &[Bar], period: usize) -> Vec<f64> {
+    let n = bars.len();
+    let mut result = vec![f64::NAN; n];
+    if n < period {
+        return result;
+    }
+
+    for i in (period - 1)..n {
+        let window = &bars[i + 1 - period..=i];
+        if window.iter().any(|b| b.high.is_nan() || b.low.is_nan()) {
+            continue;
+        }
+        let high = window
+            .iter()
+            .map(|b| b.high)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let low = window.iter().map(|b| b.low).fold(f64::INFINITY, f64::min);
+        result[i] = (high + low) / 2.0;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{assert_approx, make_bars, DEFAULT_EPSILON};
+
+    #[test]
+    fn tenkan_is_range_midpoint() {
+        // make_bars: high = max(open, close) + 1, low = min(open, close) - 1
+        let bars = make_bars(&[10.0, 12.0, 11.0, 15.0]);
+        let tenkan = Ichimoku::tenkan(3).compute(&bars);
+        assert!(tenkan[1].is_nan());
+        // Bars 0..=2: high 13, low 9
+        assert_approx(tenkan[2], 11.0, DEFAULT_EPSILON);
+        // Bars 1..=3: high 16, low 9
+        assert_approx(tenkan[3], 12.5, DEFAULT_EPSILON);
+    }
+
+    #[test]
+    fn spans_are_displaced_forward() {
+        let closes: Vec<f64> = (0..20).map(|i| 100.0 + i as f64).collect();
+        let bars = make_bars(&closes);
+        let tenkan = Ichimoku::tenkan(3).compute(&bars);
+        let kijun = Ichimoku::kijun(5).compute(&bars);
+        let span_a = Ichimoku::span_a(3, 5).compute(&bars);
+        let span_b = Ichimoku::span_b(8, 5).compute(&bars);
+        let mid_8 = Ichimoku::kijun(8).compute(&bars);
+
+        assert_eq!(Ichimoku::span_a(3, 5).lookback(), 9);
+        assert_eq!(Ichimoku::span_b(8, 5).lookback(), 12);
+        assert!(span_a[8].is_nan());
+        assert_approx(span_a[9], (tenkan[4] + kijun[4]) / 2.0, DEFAULT_EPSILON);
+        assert!(span_b[11].is_nan());
+        assert_approx(span_b[15], mid_8[10], DEFAULT_EPSILON);
+    }
+
+    #[test]
+    fn spans_never_look_ahead() {
+        let closes: Vec<f64> = (0..30).map(|i| 100.0 + (i as f64).sin() * 5.0).collect();
+        let bars = make_bars(&closes);
+        let full = Ichimoku::span_b(10, 6).compute(&bars);
+        let truncated = Ichimoku::span_b(10, 6).compute(&bars[..20]);
+        for i in 0..20 {
+            assert!(full[i] == truncated[i] || (full[i].is_nan() && truncated[i].is_nan()));
+        }
+    }
+
+    #[test]
+    fn names_include_periods() {
+        assert_eq!(Ichimoku::tenkan(9).name(), "ichimoku_tenkan_9");
+        assert_eq!(Ichimoku::span_a(9, 26).name(), "ichimoku_span_a_9_26");
+        assert_eq!(Ichimoku::span_b(52, 26).name(), "ichimoku_span_b_52_26");
+    }
+}
@@ -1,6 +1,7 @@
 //! Keltner Channel — EMA +/- ATR multiplier.
 //!
-//! Three bands (separate Indicator instances):
+//! Three bands, either as separate per-band instances or all at once from
+//! [`Keltner::bands`]:
 //! - Middle: EMA(close, ema_period)
 //! - Upper: middle + mult * ATR(atr_period)
 //! - Lower: middle - mult * ATR(atr_period)
@@ -25,40 +26,85 @@ pub struct Keltner {
     ema_period: usize,
     atr_period: usize,
     multiplier: f64,
-    band: KeltnerBand,
+    /// `None` = all three bands.
+    band: Option<KeltnerBand>,
     name: String,
+    outputs: Vec<String>,
 }
 
 impl Keltner {
     pub fn upper(ema_period: usize, atr_period: usize, multiplier: f64) -> Self {
+        let name = format!("keltner_upper_{ema_period}_{atr_period}_{multiplier}");
         Self {
             ema_period,
             atr_period,
             multiplier,
-            band: KeltnerBand::Upper,
-            name: format!("keltner_upper_{ema_period}_{atr_period}_{multiplier}"),
+            band: Some(KeltnerBand::Upper),
+            outputs: vec![name.clone()],
+            name,
         }
     }
 
     pub fn middle(ema_period: usize, atr_period: usize, multiplier: f64) -> Self {
+        let name = format!("keltner_middle_{ema_period}_{atr_period}_{multiplier}");
         Self {
             ema_period,
             atr_period,
             multiplier,
-            band: KeltnerBand::Middle,
-            name: format!("keltner_middle_{ema_period}_{atr_period}_{multiplier}"),
+            band: Some(KeltnerBand::Middle),
+            outputs: vec![name.clone()],
+            name,
         }
     }
 
     pub fn lower(ema_period: usize, atr_period: usize, multiplier: f64) -> Self {
+        let name = format!("keltner_lower_{ema_period}_{atr_period}_{multiplier}");
         Self {
             ema_period,
             atr_period,
             multiplier,
-            band: KeltnerBand::Lower,
-            name: format!("keltner_lower_{ema_period}_{atr_period}_{multiplier}"),
+            band: Some(KeltnerBand::Lower),
+            outputs: vec![name.clone()],
+            name,
         }
     }
+
+    /// All three bands in one pass (EMA and ATR computed once), stored under
+    /// the upper, middle and lower band keys in that order. `compute` returns
+    /// the upper band.
+    pub fn bands(ema_period: usize, atr_period: usize, multiplier: f64) -> Self {
+        Self {
+            ema_period,
+            atr_period,
+            multiplier,
+            band: None,
+            name: format!("keltner_{ema_period}_{atr_period}_{multiplier}"),
+            outputs: vec![
+                format!("keltner_upper_{ema_period}_{atr_period}_{multiplier}"),
+                format!("keltner_middle_{ema_period}_{atr_period}_{multiplier}"),
+                format!("keltner_lower_{ema_period}_{atr_period}_{multiplier}"),
+            ],
+        }
+    }
+
+    fn all_bands(&self, bars: &[Bar]) -> Vec<Vec<f64>> {
+        let n = bars.len();
+        let closes: Vec<f64> = bars.iter().map(|b| b.close).collect();
+        let middle = ema_of_series(&closes, self.ema_period);
+        let atr_values = wilder_smooth(&true_range(bars), self.atr_period);
+
+        let mut upper = vec![f64::NAN; n];
+        let mut lower = vec![f64::NAN; n];
+        for i in 0..n {
+            if middle[i].is_nan() || atr_values[i].is_nan() {
+                continue;
+            }
+            upper[i] = middle[i] + self.multiplier * atr_values[i];
+            lower[i] = middle[i] - self.multiplier * atr_values[i];
+        }
+
+        vec![upper, middle, lower]
+    }
 }
 
 impl Indicator for Keltner {
@@ -70,7 +116,22 @@ impl Indicator for Keltner {
         (self.ema_period.saturating_sub(1)).max(self.atr_period)
     }
 
+    fn outputs(&self) -> Vec<&str> {
+        self.outputs.iter().map(String::as_str).collect()
+    }
+
+    fn compute_all(&self, bars: &[Bar]) -> Vec<Vec<f64>> {
+        match self.band {
+            Some(_) => vec![self.compute(bars)],
+            None => self.all_bands(bars),
+        }
+    }
+
     fn compute(&self, bars: &[Bar]) -> Vec<f64> {
+        let band = match self.band {
+            Some(band) => band,
+            None => return self.all_bands(bars).swap_remove(0),
+        };
         let n = bars.len();
 
         // Compute EMA of closes
@@ -78,7 +139,7 @@ impl Indicator for Keltner {
         let ema_values = ema_of_series(&closes, self.ema_period);
 
         // For middle band, just return EMA
-        if self.band == KeltnerBand::Middle {
+        if band == KeltnerBand::Middle {
             return ema_values;
         }
 
@@ -92,7 +153,7 @@ impl Indicator for Keltner {
             if ema_values[i].is_nan() || atr_values[i].is_nan() {
                 continue;
             }
-            result[i] = match self.band {
+            result[i] = match band {
                 KeltnerBand::Upper => ema_values[i] + self.multiplier * atr_values[i],
                 KeltnerBand::Lower => ema_values[i] - self.multiplier * atr_values[i],
                 KeltnerBand::Middle => unreachable!(),
@@ -106,7 +167,7 @@ impl Indicator for Keltner {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::indicators::{assert_approx, DEFAULT_EPSILON};
+    use crate::indicators::{assert_approx, assert_same_series, DEFAULT_EPSILON};
     use chrono::NaiveDate;
 
     fn make_ohlc_bars(data: &[(f64, f64, f64, f64)]) -> Vec<Bar> {
@@ -114,7 +175,7 @@ mod tests {
         data.iter()
             .enumerate()
             .map(|(i, &(open, high, low, close))| Bar {
-                symbol: "TEST".to_string(),
+                symbol: "TEST".into(),
                 date: base_date + chrono::Duration::days(i as i64),
                 open,
                 high,
@@ -198,6 +259,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn bands_match_per_band_outputs() {
+        let bars = crate::indicators::wavy_bars(200);
+        let bands = Keltner::bands(20, 10, 1.5);
+        let per_band = [
+            Keltner::upper(20, 10, 1.5),
+            Keltner::middle(20, 10, 1.5),
+            Keltner::lower(20, 10, 1.5),
+        ];
+        let names: Vec<&str> = per_band.iter().map(|k| k.name()).collect();
+        assert_eq!(bands.outputs(), names);
+
+        let all = bands.compute_all(&bars);
+        assert_eq!(all.len(), 3);
+        for (series, single) in all.iter().zip(&per_band) {
+            assert_same_series(series, &single.compute(&bars));
+        }
+    }
+
     #[test]
     fn keltner_lookback() {
         // ema_period=20, atr_period=14 → lookback = max(19, 14) = 19
@@ -1,12 +1,14 @@
 //! Concrete indicator implementations.
 //!
-//! All 13 indicators implement the `Indicator` trait from `components::indicator`.
+//! All 14 indicators implement the `Indicator` trait from `components::indicator`.
 //! They are precomputed once before the bar loop and fed per-bar into the event loop
 //! via `IndicatorValues`.
 //!
-//! Multi-series indicators (Donchian, Bollinger, Keltner, Aroon) are exposed as
-//! separate named instances per band, keeping the single-series `Indicator` trait
-//! unchanged.
+//! Multi-series indicators (Donchian, Bollinger, Keltner, Aroon, Ichimoku, and
+//! ADX's +DI/-DI) are exposed as separate named instances per band. Donchian,
+//! Bollinger, Keltner and Aroon also offer a multi-output instance that fills
+//! every band in one pass via `Indicator::outputs` / `compute_all`, stored under
+//! the same per-band keys.
 
 pub mod adx;
 pub mod aroon;
@@ -14,6 +16,7 @@ pub mod atr;
 pub mod bollinger;
 pub mod donchian;
 pub mod ema;
+pub mod ichimoku;
 pub mod keltner;
 pub mod momentum;
 pub mod parabolic_sar;
@@ -22,12 +25,13 @@ pub mod rsi;
 pub mod sma;
 pub mod supertrend;
 
-pub use adx::Adx;
+pub use adx::{Adx, DiSide, DirectionalIndicator};
 pub use aroon::{Aroon, AroonBand};
 pub use atr::Atr;
 pub use bollinger::{Bollinger, BollingerBand};
 pub use donchian::{Donchian, DonchianBand};
 pub use ema::Ema;
+pub use ichimoku::{Ichimoku, IchimokuLine};
 pub use keltner::{Keltner, KeltnerBand};
 pub use momentum::Momentum;
 pub use parabolic_sar::ParabolicSar;
@@ -52,7 +56,7 @@ pub fn make_bars(closes: &[f64]) -> Vec<crate::domain::Bar> {
             let high = open.max(close) + 1.0;
             let low = open.min(close) - 1.0;
             Bar {
-                symbol: "TEST".to_string(),
+                symbol: "TEST".into(),
                 date: base_date + chrono::Duration::days(i as i64),
                 open,
                 high,
@@ -75,6 +79,28 @@ pub fn assert_approx(actual: f64, expected: f64, epsilon: f64) {
     );
 }
 
+/// Deterministic oscillating bars for comparing multi-output indicators
+/// against their per-band instances.
+#[cfg(test)]
+pub fn wavy_bars(n: usize) -> Vec<crate::domain::Bar> {
+    let closes: Vec<f64> = (0..n)
+        .map(|i| 100.0 + 10.0 * (i as f64 * 0.3).sin() + (i % 7) as f64)
+        .collect();
+    make_bars(&closes)
+}
+
+/// Assert two series are identical, treating NaN as equal to NaN.
+#[cfg(test)]
+pub fn assert_same_series(actual: &[f64], expected: &[f64]) {
+    assert_eq!(actual.len(), expected.len());
+    for (i, (a, e)) in actual.iter().zip(expected).enumerate() {
+        assert!(
+            a == e || (a.is_nan() && e.is_nan()),
+            "bar {i}: actual={a}, expected={e}"
+        );
+    }
+}
+
 /// Default epsilon for indicator tests.
 #[cfg(test)]
 pub const DEFAULT_EPSILON: f64 = 1e-10;
@@ -97,6 +97,8 @@ mod tests {
         require_sync::<components::AtrTrailing>();
         require_send::<components::Chandelier>();
         require_sync::<components::Chandelier>();
+        require_send::<components::DonchianExit>();
+        require_sync::<components::DonchianExit>();
         require_send::<components::PercentTrailing>();
         require_sync::<components::PercentTrailing>();
         require_send::<components::SinceEntryTrailing>();
@@ -135,6 +137,12 @@ mod tests {
         require_sync::<components::signal::RocMomentum>();
         require_send::<components::signal::AroonCrossover>();
         require_sync::<components::signal::AroonCrossover>();
+        require_send::<components::signal::IchimokuCloud>();
+        require_sync::<components::signal::IchimokuCloud>();
+        require_send::<components::signal::AdxTrend>();
+        require_sync::<components::signal::AdxTrend>();
+        require_send::<components::signal::SqueezeBreakout>();
+        require_sync::<components::signal::SqueezeBreakout>();
         require_send::<components::signal::NullSignal>();
         require_sync::<components::signal::NullSignal>();
 
@@ -152,13 +160,13 @@ mod tests {
     /// Architecture contract: SignalGenerator trait does NOT accept Portfolio.
     ///
     /// This is enforced by the trait signature itself — `evaluate()` takes
-    /// `&[Bar]`, `usize`, and `&IndicatorValues`, with no portfolio parameter.
+    /// `&dyn BarSeries`, `usize`, and `&IndicatorValues`, with no portfolio parameter.
     /// If someone adds a portfolio parameter, the trait changes and all
     /// implementations break. This test documents the contract explicitly.
     #[test]
     fn signal_generator_trait_has_no_portfolio_parameter() {
         // The trait signature is:
-        //   fn evaluate(&self, bars: &[Bar], bar_index: usize, indicators: &IndicatorValues)
+        //   fn evaluate(&self, bars: &dyn BarSeries, bar_index: usize, indicators: &IndicatorValues)
         //       -> Option<SignalEvent>;
         //
         // If this compiles, signals cannot see portfolio state.
@@ -168,7 +176,7 @@ mod tests {
         // trait signature is ever modified to include portfolio state.
         fn _check_trait_object_builds(
             sig: &dyn components::SignalGenerator,
-            bars: &[domain::Bar],
+            bars: &dyn domain::BarSeries,
             indicators: &components::IndicatorValues,
         ) -> Option<components::SignalEvent> {
             sig.evaluate(bars, 0, indicators)
@@ -181,7 +189,7 @@ mod tests {
         fn _check_trait_object_builds(
             filter: &dyn components::SignalFilter,
             signal: &components::SignalEvent,
-            bars: &[domain::Bar],
+            bars: &dyn domain::BarSeries,
             indicators: &components::IndicatorValues,
         ) -> components::SignalEvaluation {
             filter.evaluate(signal, bars, 0, indicators)
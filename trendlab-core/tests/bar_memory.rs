@@ -0,0 +1,246 @@
+//! Memory benchmark for the engine's bars on a 50-symbol x 6000-bar dataset.
+//!
+//! A counting global allocator measures the live heap bytes each layout
+//! holds once built, and the peak heap while the engine precomputes and
+//! runs. Reference numbers (x86_64, run with `--nocapture`):
+//!
+//! | layout                                  | live bytes | allocations |
+//! |-----------------------------------------|------------|-------------|
+//! | `Vec<Bar>` with a `String` symbol/bar   | ~25.5 MB   | ~300,100    |
+//! | `Vec<Bar>` via `aligned_to_bars`        | ~21.6 MB   | ~150        |
+//! | `BarFrame::from_aligned` (engine)       | ~14.4 MB   | ~400        |
+//!
+//! | engine phase (peak heap)                | rows       | frames      |
+//! |-----------------------------------------|------------|-------------|
+//! | bars + Supertrend/ATR precompute        | ~26.5 MB   | ~19.7 MB    |
+//! | full `run_backtest` (Supertrend, no PM) | —          | ~19.7 MB    |
+//!
+//! Frames store OHLCV as columns with one symbol and one shared date axis,
+//! and precompute materializes rows for one symbol at a time, so a run
+//! peaks below what the row layout held for its bars and indicators alone.
+//! The run uses no position manager: a trailing stop re-places an order
+//! every bar, and the order audit would dwarf the bar storage measured here.
+//! The string layout's requested bytes understate it: every per-bar
+//! `String` is its own heap block, which the allocator rounds to at least
+//! 32 bytes, so it really held ~34 MB.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use chrono::NaiveDate;
+use trendlab_core::components::execution::NextBarOpenModel;
+use trendlab_core::components::filter::NoFilter;
+use trendlab_core::components::indicator::Indicator;
+use trendlab_core::components::pm::NoOpPm;
+use trendlab_core::components::signal::SupertrendSignal;
+use trendlab_core::data::align::AlignedData;
+use trendlab_core::data::frame::BarFrame;
+use trendlab_core::data::provider::RawBar;
+use trendlab_core::engine::{
+    aligned_to_bars, precompute_frame_indicators, precompute_indicators, run_backtest, EngineConfig,
+};
+use trendlab_core::indicators::{Atr, Supertrend};
+
+struct Counting;
+
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let live = BYTES.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+        PEAK.fetch_max(live, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+const SYMBOLS: usize = 50;
+const BARS: usize = 6000;
+
+/// The previous bar layout, with an owned symbol per bar.
+#[allow(dead_code)]
+struct StringBar {
+    symbol: String,
+    date: NaiveDate,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: u64,
+    adj_close: f64,
+}
+
+fn dataset() -> AlignedData {
+    let start = NaiveDate::from_ymd_opt(2000, 1, 3).unwrap();
+    let dates: Vec<NaiveDate> = (0..BARS)
+        .map(|i| start + chrono::Duration::days(i as i64))
+        .collect();
+    let symbols: Vec<String> = (0..SYMBOLS).map(|i| format!("SYM{i:02}")).collect();
+    let bars = symbols
+        .iter()
+        .enumerate()
+        .map(|(s, symbol)| {
+            let series = dates
+                .iter()
+                .enumerate()
+                .map(|(i, &date)| {
+                    let close = 100.0 + s as f64 + (i as f64 * 0.01).sin() * 10.0;
+                    RawBar {
+                        date,
+                        open: close - 0.5,
+                        high: close + 1.0,
+                        low: close - 1.0,
+                        close,
+                        volume: 1_000_000,
+                        adj_close: close,
+                    }
+                })
+                .collect();
+            (symbol.clone(), series)
+        })
+        .collect::<HashMap<_, Vec<RawBar>>>();
+    AlignedData {
+        dates,
+        bars,
+        symbols,
+    }
+}
+
+/// Heap bytes still held by `f`'s result, and allocations made building it.
+fn measure<T>(f: impl FnOnce() -> T) -> (T, usize, usize) {
+    let (bytes, allocations) = (
+        BYTES.load(Ordering::Relaxed),
+        ALLOCATIONS.load(Ordering::Relaxed),
+    );
+    let value = f();
+    (
+        value,
+        BYTES.load(Ordering::Relaxed) - bytes,
+        ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+    )
+}
+
+/// Peak heap bytes above the starting level while `f` runs.
+fn measure_peak<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let bytes = BYTES.load(Ordering::Relaxed);
+    PEAK.store(bytes, Ordering::Relaxed);
+    let value = f();
+    (value, PEAK.load(Ordering::Relaxed) - bytes)
+}
+
+fn indicators() -> Vec<Box<dyn Indicator>> {
+    vec![Box::new(Supertrend::new(10, 3.0)), Box::new(Atr::new(14))]
+}
+
+// A single test: the counters are process-wide, so measurements must not
+// overlap with other tests' allocations.
+#[test]
+fn bar_storage_memory_50_symbols_x_6000_bars() {
+    let aligned = dataset();
+    let total_bars = SYMBOLS * BARS;
+
+    let (string_bars, string_bytes, string_allocs) = measure(|| {
+        aligned
+            .bars
+            .iter()
+            .map(|(symbol, raw)| {
+                let bars: Vec<StringBar> = raw
+                    .iter()
+                    .map(|r| StringBar {
+                        symbol: symbol.to_string(),
+                        date: r.date,
+                        open: r.open,
+                        high: r.high,
+                        low: r.low,
+                        close: r.close,
+                        volume: r.volume,
+                        adj_close: r.adj_close,
+                    })
+                    .collect();
+                (symbol.clone(), bars)
+            })
+            .collect::<HashMap<_, _>>()
+    });
+    drop(string_bars);
+
+    let (bars, bar_bytes, bar_allocs) = measure(|| aligned_to_bars(&aligned));
+    let (frames, frame_bytes, frame_allocs) = measure(|| BarFrame::from_aligned(&aligned));
+
+    println!("String-symbol bars: {string_bytes} bytes, {string_allocs} allocations");
+    println!("Shared-symbol bars: {bar_bytes} bytes, {bar_allocs} allocations");
+    println!("Columnar frames:    {frame_bytes} bytes, {frame_allocs} allocations");
+
+    assert!(string_allocs >= total_bars, "one String per bar");
+    assert!(bar_allocs < SYMBOLS * 20, "no per-bar allocations");
+    assert!(frame_allocs < SYMBOLS * 20, "no per-bar allocations");
+    assert!(bar_bytes < string_bytes);
+    assert!(frame_bytes < bar_bytes * 3 / 4);
+
+    // Both layouts carry the aligned data
+    for (symbol, raw) in &aligned.bars {
+        let series = &bars[symbol];
+        let frame = &frames[symbol];
+        assert_eq!(series.len(), raw.len());
+        assert_eq!(frame.len(), raw.len());
+        for i in [0, BARS / 2, BARS - 1] {
+            assert_eq!(series[i].date, raw[i].date);
+            assert_eq!(series[i].close, raw[i].close);
+            assert_eq!(&*series[i].symbol, symbol.as_str());
+            let view = frame.bar(i);
+            assert_eq!(view.date, raw[i].date);
+            assert_eq!(view.close, raw[i].close);
+            assert_eq!(&*view.symbol, symbol.as_str());
+        }
+    }
+    drop(bars);
+    drop(frames);
+
+    // Bars plus indicator precompute, as the engine held them before and now
+    let indicators = indicators();
+    let (rows, rows_peak) = measure_peak(|| {
+        let bars = aligned_to_bars(&aligned);
+        let values = precompute_indicators(&bars, &indicators);
+        (bars, values)
+    });
+    drop(rows);
+    let (framed, frames_peak) = measure_peak(|| {
+        let frames = BarFrame::from_aligned(&aligned);
+        let values = precompute_frame_indicators(&frames, &indicators, None);
+        (frames, values)
+    });
+    drop(framed);
+
+    let (result, run_peak) = measure_peak(|| {
+        run_backtest(
+            &aligned,
+            &indicators,
+            &EngineConfig::new(100_000.0, 0),
+            &SupertrendSignal::new(10, 3.0),
+            &NoFilter,
+            &NextBarOpenModel::default(),
+            &NoOpPm,
+        )
+    });
+
+    println!("Rows + precompute peak:   {rows_peak} bytes");
+    println!("Frames + precompute peak: {frames_peak} bytes");
+    println!("run_backtest peak:        {run_peak} bytes");
+
+    assert!(result.signal_count > 0);
+    assert!(frames_peak < rows_peak);
+    assert!(
+        run_peak < rows_peak,
+        "a run must never hold per-bar rows for every symbol"
+    );
+}
@@ -7,14 +7,19 @@
 //! 4. Trading mode correctly filters signals (ShortOnly blocks Long signals)
 //! 5. Signal evaluations are recorded when signals pass trading mode filter
 //! 6. All presets produce non-empty indicator sets
+//! 7. Turtle 20/10: donchian_exit trades on its own lookback
 
 use chrono::NaiveDate;
 use std::collections::HashMap;
 use trendlab_core::components::composition::{build_composition, StrategyPreset};
+use trendlab_core::components::indicator::Indicator;
+use trendlab_core::components::{create_execution, create_filter, create_pm, create_signal};
 use trendlab_core::data::align::AlignedData;
 use trendlab_core::data::provider::RawBar;
+use trendlab_core::domain::Bar;
 use trendlab_core::engine::{run_backtest, EngineConfig};
-use trendlab_core::fingerprint::TradingMode;
+use trendlab_core::fingerprint::{ComponentConfig, TradingMode};
+use trendlab_core::indicators::Donchian;
 
 // ──────────────────────────────────────────────
 // Helpers
@@ -365,3 +370,103 @@ fn presets_have_indicators() {
         );
     }
 }
+
+// ──────────────────────────────────────────────
+// 7. Turtle 20/10: decoupled entry and exit lookbacks
+// ──────────────────────────────────────────────
+
+/// Donchian upper channel lagged by one bar, so bar i's value is the highest
+/// high of bars i-N..i-1 (see `signal_test.rs`: the unlagged channel includes
+/// the current high and `donchian_breakout` can never fire on it).
+struct PriorDonchianUpper(Donchian);
+
+impl Indicator for PriorDonchianUpper {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    fn lookback(&self) -> usize {
+        self.0.lookback() + 1
+    }
+
+    fn compute(&self, bars: &[Bar]) -> Vec<f64> {
+        let mut values = self.0.compute(bars);
+        values.pop();
+        values.insert(0, f64::NAN);
+        values
+    }
+}
+
+/// Flat at 100 for 30 bars, up 2 per bar to 160 (bar 59), then down 2 per
+/// bar. High/low are close +/- 1.
+fn make_turtle_rawbars() -> Vec<RawBar> {
+    let base_date = NaiveDate::from_ymd_opt(2023, 1, 2).unwrap();
+    (0..90)
+        .map(|i| {
+            let close = match i {
+                0..=29 => 100.0,
+                30..=59 => 100.0 + 2.0 * (i - 29) as f64,
+                _ => 160.0 - 2.0 * (i - 59) as f64,
+            };
+            RawBar {
+                date: base_date + chrono::Duration::days(i as i64),
+                open: close,
+                high: close + 1.0,
+                low: close - 1.0,
+                close,
+                volume: 1000,
+                adj_close: close,
+            }
+        })
+        .collect()
+}
+
+fn component(component_type: &str, params: &[(&str, f64)]) -> ComponentConfig {
+    ComponentConfig {
+        component_type: component_type.to_string(),
+        params: params.iter().map(|&(k, v)| (k.to_string(), v)).collect(),
+        children: Vec::new(),
+    }
+}
+
+/// Trades of a 20-bar breakout entry with a `donchian_exit` on `exit_lookback`,
+/// frictionless, as (entry_bar, exit_bar, exit_price).
+fn turtle_trades(exit_lookback: usize) -> Vec<(usize, usize, f64)> {
+    let aligned = make_aligned(make_turtle_rawbars());
+    let signal = create_signal(&component("donchian_breakout", &[("entry_lookback", 20.0)]));
+    let pm_config = component("donchian_exit", &[("exit_lookback", exit_lookback as f64)]);
+    let execution = create_execution(&component("next_bar_open", &[("preset", 0.0)]));
+    let filter = create_filter(&component("no_filter", &[]));
+    // Long-only, so the exit reads only the lower band (the full channel's
+    // upper band would replace the lagged entry channel at a 20-bar exit)
+    let indicators: Vec<Box<dyn Indicator>> = vec![
+        Box::new(PriorDonchianUpper(Donchian::upper(20))),
+        Box::new(Donchian::lower(exit_lookback)),
+    ];
+
+    let result = run_backtest(
+        &aligned,
+        &indicators,
+        &EngineConfig::new(100_000.0, 0),
+        signal.unwrap().as_ref(),
+        filter.unwrap().as_ref(),
+        execution.unwrap().as_ref(),
+        create_pm(&pm_config).unwrap().as_ref(),
+    );
+    result
+        .trades
+        .iter()
+        .map(|t| (t.entry_bar, t.exit_bar, t.exit_price))
+        .collect()
+}
+
+#[test]
+fn turtle_20_10_trade_boundaries() {
+    // Bar 30 closes above the prior 20-bar high (101) and fills at bar 31's
+    // open. From the peak the 10-bar low trails 149 (bar 54's low), first
+    // traded through on bar 64 (low 149).
+    assert_eq!(turtle_trades(10), vec![(31, 64, 149.0)]);
+
+    // Same entry, exit on the 20-bar low (139, bar 49's low): five bars later
+    assert_eq!(turtle_trades(20), vec![(31, 69, 139.0)]);
+}
@@ -23,7 +23,7 @@ use trendlab_core::data::provider::RawBar;
 use trendlab_core::domain::ids::{OcoGroupId, OrderId};
 use trendlab_core::domain::instrument::{Instrument, OrderSide};
 use trendlab_core::domain::order::{Order, OrderStatus, OrderType};
-use trendlab_core::domain::Bar;
+use trendlab_core::domain::{Bar, MarketStatus};
 use trendlab_core::engine::execution::{ExecutionConfig, ExecutionEngine};
 use trendlab_core::engine::portfolio_update::apply_fills;
 use trendlab_core::engine::state::EngineState;
@@ -185,7 +185,7 @@ fn run_e2e_backtest(preset: ExecutionPreset) -> (f64, Vec<f64>, usize) {
 
         // ─── Phase 4: Post-bar ───
         if let Some(pos) = state.portfolio.get_position_mut("SPY") {
-            pos.tick_bar();
+            pos.tick_bar(MarketStatus::Open);
             pos.update_mark(bar.close);
         }
         state.last_valid_close.insert("SPY".into(), bar.close);
@@ -392,7 +392,7 @@ fn e2e_spy_with_stop_loss_bracket() {
 
         // Post-bar
         if let Some(pos) = state.portfolio.get_position_mut("SPY") {
-            pos.tick_bar();
+            pos.tick_bar(MarketStatus::Open);
             pos.update_mark(bar.close);
         }
         state.last_valid_close.insert("SPY".into(), bar.close);
@@ -5,18 +5,27 @@
 //! 2. Warmup: indicator lookback respected, no activity before warmup
 //! 3. Equity accounting: equity == cash + positions at every bar
 //! 4. Precomputed-vs-naive: indicator values match when computed via engine
+//! 5. Signal flip exits: exit-only and stop-and-reverse on Supertrend flips
+//! 6. Fill golden snapshot: a long multi-symbol run with trailing stops
+//! 7. Cross-run determinism: byte-identical multi-symbol runs under
+//!    different HashMap seeds
+//! 8. Portfolio snapshots: equity identity and reconciliation against fills
 
 use chrono::NaiveDate;
 use std::collections::HashMap;
-use trendlab_core::components::execution::NextBarOpenModel;
+use trendlab_core::components::execution::{
+    CloseOnSignalModel, ExecutionPreset, FlipPolicy, NextBarOpenModel,
+};
 use trendlab_core::components::filter::NoFilter;
 use trendlab_core::components::indicator::Indicator;
-use trendlab_core::components::pm::NoOpPm;
-use trendlab_core::components::signal::NullSignal;
+use trendlab_core::components::pm::{AtrTrailing, NoOpPm};
+use trendlab_core::components::signal::{NullSignal, SupertrendSignal};
 use trendlab_core::data::align::AlignedData;
 use trendlab_core::data::provider::RawBar;
-use trendlab_core::engine::{run_backtest, EngineConfig};
-use trendlab_core::indicators::{Ema, Sma};
+use trendlab_core::domain::{DecisionSource, OrderSide, PositionSide};
+use trendlab_core::engine::{run_backtest, EngineConfig, RunResult, SnapshotMode};
+use trendlab_core::fingerprint::TradingMode;
+use trendlab_core::indicators::{Atr, Ema, Sma, Supertrend};
 
 /// Helper: create aligned data for a single symbol.
 fn make_aligned_single(symbol: &str, bars: Vec<RawBar>) -> AlignedData {
@@ -469,7 +478,7 @@ fn precomputed_indicators_match_direct_computation() {
     let domain_bars: Vec<Bar> = raw_bars
         .iter()
         .map(|r| Bar {
-            symbol: "SPY".to_string(),
+            symbol: "SPY".into(),
             date: r.date,
             open: r.open,
             high: r.high,
@@ -527,3 +536,399 @@ fn precomputed_indicators_match_direct_computation() {
         }
     }
 }
+
+// ──────────────────────────────────────────────
+// Signal flip exits
+// ──────────────────────────────────────────────
+
+/// Helper: N bars following a sine wave, so Supertrend flips repeatedly.
+fn wave_bars(n: usize) -> Vec<RawBar> {
+    let base_date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+    (0..n)
+        .map(|i| {
+            let close = 100.0 + 20.0 * (i as f64 * std::f64::consts::TAU / 40.0).sin();
+            RawBar {
+                date: base_date + chrono::Duration::days(i as i64),
+                open: close,
+                high: close + 1.0,
+                low: close - 1.0,
+                close,
+                volume: 1000,
+                adj_close: close,
+            }
+        })
+        .collect()
+}
+
+fn run_supertrend_flips(flip: FlipPolicy, mode: TradingMode) -> RunResult {
+    let aligned = make_aligned_single("SPY", wave_bars(200));
+    let indicators: Vec<Box<dyn Indicator>> = vec![Box::new(Supertrend::new(10, 3.0))];
+    let mut config = EngineConfig::new(100_000.0, 0);
+    config.trading_mode = mode;
+    run_backtest(
+        &aligned,
+        &indicators,
+        &config,
+        &SupertrendSignal::new(10, 3.0),
+        &NoFilter,
+        &CloseOnSignalModel::new(ExecutionPreset::Frictionless).with_flip_policy(flip),
+        &NoOpPm,
+    )
+}
+
+#[test]
+fn flips_ignored_by_default() {
+    let result = run_supertrend_flips(FlipPolicy::Ignore, TradingMode::LongShort);
+    // Without a PM nothing ever exits: the first position is held to the end
+    assert_eq!(result.trades.len(), 1);
+    assert!(result.trades[0].liquidated_at_end);
+    assert!(result.signal_count > 0);
+}
+
+#[test]
+fn stop_and_reverse_alternates_with_continuous_exposure() {
+    let result = run_supertrend_flips(FlipPolicy::StopAndReverse, TradingMode::LongShort);
+    let trades = &result.trades;
+    assert!(
+        trades.len() >= 3,
+        "expected several flips, got {}",
+        trades.len()
+    );
+
+    for pair in trades.windows(2) {
+        let (prev, next) = (&pair[0], &pair[1]);
+        assert_ne!(prev.side, next.side, "sides must alternate");
+        // No gap and no overlap: the reverse opens at the exit's open
+        assert_eq!(prev.exit_bar, next.entry_bar);
+        assert_eq!(prev.exit_price, next.entry_price);
+    }
+
+    // Each exit fills before the reversal entry on the same bar
+    for trade in trades.iter().skip(1) {
+        let on_bar: Vec<_> = result
+            .fills
+            .iter()
+            .filter(|f| f.bar_index == trade.entry_bar)
+            .collect();
+        assert_eq!(on_bar.len(), 2);
+    }
+
+    // Flip exits are the signal's decision, not the PM's; the last position
+    // is still open when the data ends
+    let (last, closed) = trades.split_last().unwrap();
+    assert!(closed
+        .iter()
+        .all(|t| t.exit_source == Some(DecisionSource::SignalFlipExit)));
+    assert_eq!(last.exit_source, Some(DecisionSource::EndOfRunLiquidation));
+}
+
+#[test]
+fn exit_only_closes_without_reversing() {
+    let result = run_supertrend_flips(FlipPolicy::ExitOnly, TradingMode::LongShort);
+    let trades = &result.trades;
+    assert!(trades.len() >= 2);
+    for pair in trades.windows(2) {
+        assert!(
+            pair[1].entry_bar > pair[0].exit_bar,
+            "exit-only must leave a gap"
+        );
+    }
+}
+
+#[test]
+fn long_only_flip_exits_longs_without_shorting() {
+    let result = run_supertrend_flips(FlipPolicy::StopAndReverse, TradingMode::LongOnly);
+    assert!(!result.trades.is_empty());
+    assert!(result.trades.iter().all(|t| t.side == PositionSide::Long));
+}
+
+// ──────────────────────────────────────────────
+// Fill golden snapshot
+// ──────────────────────────────────────────────
+
+/// FNV-1a digest of every fill, sorted by (bar, order id).
+fn fills_digest(result: &RunResult) -> u64 {
+    let mut fills: Vec<_> = result.fills.iter().collect();
+    fills.sort_by_key(|f| (f.bar_index, f.order_id.0));
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut feed = |bytes: &[u8]| {
+        for &b in bytes {
+            hash ^= u64::from(b);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    };
+    for f in fills {
+        feed(&f.order_id.0.to_le_bytes());
+        feed(&(f.bar_index as u64).to_le_bytes());
+        feed(f.symbol.as_bytes());
+        feed(&f.price.to_bits().to_le_bytes());
+        feed(&f.quantity.to_bits().to_le_bytes());
+    }
+    hash
+}
+
+/// Three phase-shifted waves over `n` bars.
+fn three_waves(n: usize) -> AlignedData {
+    let symbols = ["AAA", "BBB", "CCC"];
+    let base_date = NaiveDate::from_ymd_opt(2010, 1, 4).unwrap();
+    let mut bar_map = HashMap::new();
+    for (k, symbol) in symbols.iter().enumerate() {
+        let bars = (0..n)
+            .map(|i| {
+                let x = i as f64 * std::f64::consts::TAU / (37.0 + 11.0 * k as f64);
+                let close = 100.0 + 15.0 * x.sin() + 0.01 * i as f64;
+                RawBar {
+                    date: base_date + chrono::Duration::days(i as i64),
+                    open: close - 0.4,
+                    high: close + 1.2,
+                    low: close - 1.3,
+                    close,
+                    volume: 1_000_000,
+                    adj_close: close,
+                }
+            })
+            .collect();
+        bar_map.insert(symbol.to_string(), bars);
+    }
+    AlignedData {
+        dates: (0..n)
+            .map(|i| base_date + chrono::Duration::days(i as i64))
+            .collect(),
+        bars: bar_map,
+        symbols: symbols.iter().map(|s| s.to_string()).collect(),
+    }
+}
+
+/// Long/short Supertrend with an ATR trailing stop, 30% per position.
+fn run_three_waves(aligned: &AlignedData) -> RunResult {
+    let indicators: Vec<Box<dyn Indicator>> =
+        vec![Box::new(Supertrend::new(10, 3.0)), Box::new(Atr::new(14))];
+    let mut config = EngineConfig::new(300_000.0, 0);
+    config.trading_mode = TradingMode::LongShort;
+    config.position_size_pct = 0.3;
+    run_backtest(
+        aligned,
+        &indicators,
+        &config,
+        &SupertrendSignal::new(10, 3.0),
+        &NoFilter,
+        &NextBarOpenModel::default(),
+        &AtrTrailing::new(14, 2.0),
+    )
+}
+
+/// Three phase-shifted waves over 3,000 bars. The trailing stop is
+/// cancel/replaced on every held bar, so the book accumulates thousands of
+/// historical orders.
+#[test]
+fn long_multi_symbol_run_matches_fill_snapshot() {
+    let result = run_three_waves(&three_waves(3_000));
+
+    // Locked before the order book gained its per-symbol active index.
+    // If this breaks, fill behaviour changed: investigate before updating.
+    assert_eq!(result.fills.len(), 773);
+    assert_eq!(fills_digest(&result), 0x884b_4cf5_ff07_4c79);
+}
+
+// ──────────────────────────────────────────────
+// Cross-run determinism
+// ──────────────────────────────────────────────
+
+/// Everything a run sequences, serialized: fills, the order audit trail,
+/// trades, attribution, void-bar rates and warnings, and equity bits.
+fn run_transcript(result: &RunResult) -> String {
+    let equity_bits: Vec<u64> = result.equity_curve.iter().map(|e| e.to_bits()).collect();
+    serde_json::json!({
+        "fills": result.fills,
+        "order_audit": result.order_audit,
+        "trades": result.trades,
+        "attribution": result.attribution,
+        "void_bar_rates": result.void_bar_rates,
+        "warnings": result.data_quality_warnings,
+        "equity_bits": equity_bits,
+    })
+    .to_string()
+}
+
+/// The same multi-symbol config run on separate threads (so every HashMap
+/// gets freshly seeded keys and a different iteration order) must produce a
+/// byte-identical transcript.
+#[test]
+fn multi_symbol_runs_are_byte_identical_across_hash_seeds() {
+    let run = || {
+        std::thread::spawn(|| {
+            let mut aligned = three_waves(1_000);
+            // Void stretches over the 10% threshold on two symbols, so the
+            // run reports more than one warning
+            for symbol in ["BBB", "CCC"] {
+                let bars = aligned.bars.get_mut(symbol).unwrap();
+                for bar in &mut bars[300..420] {
+                    *bar = void_bar(bar.date);
+                }
+            }
+            run_transcript(&run_three_waves(&aligned))
+        })
+        .join()
+        .unwrap()
+    };
+
+    let first = run();
+    assert!(first.contains("void bars exceeds"));
+    for _ in 0..4 {
+        assert_eq!(run(), first);
+    }
+}
+
+// ──────────────────────────────────────────────
+// Portfolio snapshots
+// ──────────────────────────────────────────────
+
+/// Two phase-shifted waves, long/short with commissions and a trailing stop,
+/// so snapshots see entries, stop exits, reversals and costs.
+fn run_with_snapshots(mode: SnapshotMode) -> RunResult {
+    let base_date = NaiveDate::from_ymd_opt(2020, 1, 2).unwrap();
+    let n = 400;
+    let mut bar_map = HashMap::new();
+    for (k, symbol) in ["AAA", "BBB"].iter().enumerate() {
+        let bars = (0..n)
+            .map(|i| {
+                let x = i as f64 * std::f64::consts::TAU / (29.0 + 13.0 * k as f64);
+                let close = 100.0 + 12.0 * x.sin();
+                RawBar {
+                    date: base_date + chrono::Duration::days(i as i64),
+                    open: close - 0.3,
+                    high: close + 1.1,
+                    low: close - 1.2,
+                    close,
+                    volume: 1_000_000,
+                    adj_close: close,
+                }
+            })
+            .collect();
+        bar_map.insert(symbol.to_string(), bars);
+    }
+    let aligned = AlignedData {
+        dates: (0..n)
+            .map(|i| base_date + chrono::Duration::days(i as i64))
+            .collect(),
+        bars: bar_map,
+        symbols: vec!["AAA".into(), "BBB".into()],
+    };
+
+    let indicators: Vec<Box<dyn Indicator>> =
+        vec![Box::new(Supertrend::new(10, 3.0)), Box::new(Atr::new(14))];
+    let mut config = EngineConfig::new(200_000.0, 0);
+    config.trading_mode = TradingMode::LongShort;
+    config.position_size_pct = 0.4;
+    config.portfolio_snapshots = mode;
+    run_backtest(
+        &aligned,
+        &indicators,
+        &config,
+        &SupertrendSignal::new(10, 3.0),
+        &NoFilter,
+        &NextBarOpenModel::default(),
+        &AtrTrailing::new(14, 2.0),
+    )
+}
+
+/// Signed quantity held in `symbol` (short positions negative).
+fn signed_quantity(snapshot: &trendlab_core::engine::PortfolioSnapshot, symbol: &str) -> f64 {
+    snapshot.position(symbol).map_or(0.0, |p| match p.side {
+        PositionSide::Short => -p.quantity,
+        _ => p.quantity,
+    })
+}
+
+#[test]
+fn snapshots_are_off_by_default() {
+    assert!(run_with_snapshots(SnapshotMode::Off)
+        .portfolio_snapshots
+        .is_empty());
+}
+
+#[test]
+fn snapshots_hold_the_equity_identity_and_reconcile_with_fills() {
+    for mode in [SnapshotMode::EveryBar, SnapshotMode::OnChange] {
+        let result = run_with_snapshots(mode);
+        let snapshots = &result.portfolio_snapshots.snapshots;
+        assert!(result.fills.len() > 10, "{mode:?}: too few fills to test");
+
+        for s in snapshots {
+            assert!(
+                s.accounting_error().abs() <= 1e-9,
+                "{mode:?} bar {}: cash {} + positions {} != equity {}",
+                s.bar_index,
+                s.cash,
+                s.market_value,
+                s.equity
+            );
+            assert_eq!(s.equity, result.equity_curve[s.bar_index]);
+            assert!(s.gross_exposure >= s.net_exposure.abs());
+        }
+
+        // Cash and quantity moves between consecutive snapshots are exactly
+        // the fills in between
+        for pair in snapshots.windows(2) {
+            let (prev, next) = (&pair[0], &pair[1]);
+            let between: Vec<_> = result
+                .fills
+                .iter()
+                .filter(|f| f.bar_index > prev.bar_index && f.bar_index <= next.bar_index)
+                .collect();
+            let cash_flow: f64 = between
+                .iter()
+                .map(|f| match f.side {
+                    OrderSide::Buy => -f.net_amount(),
+                    OrderSide::Sell => f.net_amount(),
+                })
+                .sum();
+            assert!(
+                (next.cash - prev.cash - cash_flow).abs() < 1e-6,
+                "{mode:?}: cash moved {} over bars {}..={}, fills explain {cash_flow}",
+                next.cash - prev.cash,
+                prev.bar_index + 1,
+                next.bar_index
+            );
+            for symbol in ["AAA", "BBB"] {
+                let traded: f64 = between
+                    .iter()
+                    .filter(|f| f.symbol == symbol)
+                    .map(|f| match f.side {
+                        OrderSide::Buy => f.quantity,
+                        OrderSide::Sell => -f.quantity,
+                    })
+                    .sum();
+                let moved = signed_quantity(next, symbol) - signed_quantity(prev, symbol);
+                assert!(
+                    (moved - traded).abs() < 1e-9,
+                    "{mode:?} {symbol}: quantity moved {moved} over bars {}..={}, fills {traded}",
+                    prev.bar_index + 1,
+                    next.bar_index
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn on_change_snapshots_are_the_fill_bars_of_every_bar_snapshots() {
+    let every = run_with_snapshots(SnapshotMode::EveryBar);
+    let sparse = run_with_snapshots(SnapshotMode::OnChange);
+    assert_eq!(every.portfolio_snapshots.len(), every.bar_count);
+
+    let mut fill_bars: Vec<usize> = sparse.fills.iter().map(|f| f.bar_index).collect();
+    fill_bars.extend([0, sparse.bar_count - 1]);
+    fill_bars.sort_unstable();
+    fill_bars.dedup();
+    let recorded: Vec<usize> = sparse
+        .portfolio_snapshots
+        .snapshots
+        .iter()
+        .map(|s| s.bar_index)
+        .collect();
+    assert_eq!(recorded, fill_bars);
+    for s in &sparse.portfolio_snapshots.snapshots {
+        assert_eq!(every.portfolio_snapshots.at_bar(s.bar_index), Some(s));
+    }
+}
@@ -14,7 +14,7 @@ use trendlab_core::domain::order::{Order, OrderStatus, OrderType};
 use trendlab_core::domain::position::PositionSide;
 use trendlab_core::domain::{Bar, Portfolio};
 use trendlab_core::engine::execution::{
-    CostModel, ExecutionConfig, ExecutionEngine, LiquidityPolicy, RemainderPolicy,
+    CostModel, ExecutionConfig, ExecutionEngine, LimitFillPolicy, LiquidityPolicy, RemainderPolicy,
 };
 use trendlab_core::engine::order_book::OrderBook;
 use trendlab_core::engine::portfolio_update::apply_fills;
@@ -77,6 +77,8 @@ fn gap_through_sell_stop_fill_at_open_policy() {
         path_policy: PathPolicy::WorstCase,
         gap_policy: GapPolicy::FillAtOpen,
         liquidity: None,
+        limit_fill: LimitFillPolicy::default(),
+        precise_accounting: false,
     };
     let engine = ExecutionEngine::new(config);
     let mut book = OrderBook::new();
@@ -104,6 +106,8 @@ fn gap_through_sell_stop_fill_at_trigger_policy() {
         path_policy: PathPolicy::WorstCase,
         gap_policy: GapPolicy::FillAtTrigger,
         liquidity: None,
+        limit_fill: LimitFillPolicy::default(),
+        precise_accounting: false,
     };
     let engine = ExecutionEngine::new(config);
     let mut book = OrderBook::new();
@@ -131,6 +135,8 @@ fn gap_through_sell_stop_fill_at_worst_policy() {
         path_policy: PathPolicy::WorstCase,
         gap_policy: GapPolicy::FillAtWorst,
         liquidity: None,
+        limit_fill: LimitFillPolicy::default(),
+        precise_accounting: false,
     };
     let engine = ExecutionEngine::new(config);
     let mut book = OrderBook::new();
@@ -160,6 +166,8 @@ fn gap_through_buy_stop_fill_at_open() {
         path_policy: PathPolicy::WorstCase,
         gap_policy: GapPolicy::FillAtOpen,
         liquidity: None,
+        limit_fill: LimitFillPolicy::default(),
+        precise_accounting: false,
     };
     let engine = ExecutionEngine::new(config);
     let mut book = OrderBook::new();
@@ -190,6 +198,8 @@ fn ambiguous_bar_worst_case_stop_fills_before_tp() {
         path_policy: PathPolicy::WorstCase,
         gap_policy: GapPolicy::FillAtOpen,
         liquidity: None,
+        limit_fill: LimitFillPolicy::default(),
+        precise_accounting: false,
     };
     let engine = ExecutionEngine::new(config);
     let mut book = OrderBook::new();
@@ -241,6 +251,8 @@ fn ambiguous_bar_best_case_tp_fills_before_stop() {
         path_policy: PathPolicy::BestCase,
         gap_policy: GapPolicy::FillAtOpen,
         liquidity: None,
+        limit_fill: LimitFillPolicy::default(),
+        precise_accounting: false,
     };
     let engine = ExecutionEngine::new(config);
     let mut book = OrderBook::new();
@@ -556,6 +568,8 @@ fn liquidity_constraint_limits_fill_quantity() {
         path_policy: PathPolicy::WorstCase,
         gap_policy: GapPolicy::FillAtOpen,
         liquidity: Some(LiquidityPolicy::new(0.01, RemainderPolicy::Cancel)), // 1% participation
+        limit_fill: LimitFillPolicy::default(),
+        precise_accounting: false,
     };
     let engine = ExecutionEngine::new(config);
     let mut book = OrderBook::new();
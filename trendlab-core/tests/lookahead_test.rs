@@ -31,7 +31,7 @@ fn make_test_bars(n: usize) -> Vec<Bar> {
         let low = open.min(close) - 2.0;
 
         bars.push(Bar {
-            symbol: "TEST".to_string(),
+            symbol: "TEST".into(),
             date: base_date + chrono::Duration::days(i as i64),
             open,
             high,
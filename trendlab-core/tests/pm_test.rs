@@ -17,7 +17,7 @@ use trendlab_core::domain::{Bar, MarketStatus, Position, PositionSide};
 
 fn make_bar_at(date: NaiveDate, open: f64, high: f64, low: f64, close: f64) -> Bar {
     Bar {
-        symbol: "SPY".to_string(),
+        symbol: "SPY".into(),
         date,
         open,
         high,
@@ -68,7 +68,7 @@ fn assert_ratchet_long_rising(pm: &dyn PositionManager, indicators: &IndicatorVa
         let close = 100.0 + i as f64; // steadily rising
         let bar = make_bar(close);
         pos.update_mark(close);
-        pos.tick_bar();
+        pos.tick_bar(MarketStatus::Open);
 
         let intent = pm.on_bar(&pos, &bar, i, MarketStatus::Open, indicators);
         if let Some(raw_stop) = intent.stop_price {
@@ -99,7 +99,7 @@ fn assert_ratchet_short_falling(pm: &dyn PositionManager, indicators: &Indicator
         let close = 100.0 - i as f64 * 0.5; // steadily falling
         let bar = make_bar(close);
         pos.update_mark(close);
-        pos.tick_bar();
+        pos.tick_bar(MarketStatus::Open);
 
         let intent = pm.on_bar(&pos, &bar, i, MarketStatus::Open, indicators);
         if let Some(raw_stop) = intent.stop_price {
@@ -137,7 +137,7 @@ fn assert_ratchet_long_vshape(pm: &dyn PositionManager, indicators: &IndicatorVa
         };
         let bar = make_bar(close);
         pos.update_mark(close);
-        pos.tick_bar();
+        pos.tick_bar(MarketStatus::Open);
 
         let intent = pm.on_bar(&pos, &bar, i, MarketStatus::Open, indicators);
         if let Some(raw_stop) = intent.stop_price {
@@ -170,7 +170,7 @@ fn assert_ratchet_short_vshape(pm: &dyn PositionManager, indicators: &IndicatorV
         };
         let bar = make_bar(close);
         pos.update_mark(close);
-        pos.tick_bar();
+        pos.tick_bar(MarketStatus::Open);
 
         let intent = pm.on_bar(&pos, &bar, i, MarketStatus::Open, indicators);
         if let Some(raw_stop) = intent.stop_price {
@@ -208,7 +208,7 @@ fn assert_ratchet_long_gap(pm: &dyn PositionManager, indicators: &IndicatorValue
     for (i, &close) in prices.iter().enumerate() {
         let bar = make_bar(close);
         pos.update_mark(close);
-        pos.tick_bar();
+        pos.tick_bar(MarketStatus::Open);
 
         let intent = pm.on_bar(&pos, &bar, i, MarketStatus::Open, indicators);
         if let Some(raw_stop) = intent.stop_price {
@@ -332,7 +332,7 @@ fn ratchet_atr_trailing_atr_expansion() {
         let close = 100.0 + i as f64; // rising
         let bar = make_bar(close);
         pos.update_mark(close);
-        pos.tick_bar();
+        pos.tick_bar(MarketStatus::Open);
 
         let intent = pm.on_bar(&pos, &bar, i, MarketStatus::Open, &iv);
         if let Some(raw_stop) = intent.stop_price {
@@ -368,7 +368,7 @@ fn ratchet_chandelier_atr_expansion() {
         let close = 100.0 + i as f64;
         let bar = make_bar(close);
         pos.update_mark(close);
-        pos.tick_bar();
+        pos.tick_bar(MarketStatus::Open);
 
         let intent = pm.on_bar(&pos, &bar, i, MarketStatus::Open, &iv);
         if let Some(raw_stop) = intent.stop_price {
@@ -409,7 +409,7 @@ fn chandelier_not_trapped_by_reversal() {
         let close = 100.0 + i as f64 * 2.5;
         let bar = make_bar(close);
         pos.update_mark(close);
-        pos.tick_bar();
+        pos.tick_bar(MarketStatus::Open);
 
         let intent = pm.on_bar(&pos, &bar, i, MarketStatus::Open, &iv);
         if let Some(raw_stop) = intent.stop_price {
@@ -424,7 +424,7 @@ fn chandelier_not_trapped_by_reversal() {
         let close = 150.0 - (i - 20) as f64 * 2.0;
         let bar = make_bar(close);
         pos.update_mark(close);
-        pos.tick_bar();
+        pos.tick_bar(MarketStatus::Open);
 
         let intent = pm.on_bar(&pos, &bar, i, MarketStatus::Open, &iv);
         if let Some(raw_stop) = intent.stop_price {
@@ -467,7 +467,7 @@ fn frozen_reference_emits_adjust_exactly_once() {
         let close = 100.0 + i as f64;
         let bar = make_bar(close);
         pos.update_mark(close);
-        pos.tick_bar();
+        pos.tick_bar(MarketStatus::Open);
 
         let intent = pm.on_bar(&pos, &bar, i, MarketStatus::Open, &iv);
         if intent.action == IntentAction::AdjustStop {
@@ -494,13 +494,13 @@ fn time_decay_converges_to_min_pct() {
 
     // At bar 80 the effective pct should be exactly min_pct (0.02)
     let mut pos = Position::new_long("SPY".into(), 100.0, 100.0, 0);
-    pos.bars_held = 80;
+    pos.bars_held_open = 80;
     let bar = make_bar(100.0);
     let intent = pm.on_bar(&pos, &bar, 80, MarketStatus::Open, &iv);
     assert_eq!(intent.stop_price, Some(98.0)); // 100 * (1 - 0.02)
 
     // At bar 200, still at min_pct
-    pos.bars_held = 200;
+    pos.bars_held_open = 200;
     let intent = pm.on_bar(&pos, &bar, 200, MarketStatus::Open, &iv);
     assert_eq!(intent.stop_price, Some(98.0));
 }
@@ -518,12 +518,12 @@ fn max_holding_fires_at_exact_boundary() {
 
     // Bar 19: still holds
     let mut pos = Position::new_long("SPY".into(), 100.0, 100.0, 0);
-    pos.bars_held = 19;
+    pos.bars_held_open = 19;
     let intent = pm.on_bar(&pos, &bar, 19, MarketStatus::Open, &iv);
     assert_eq!(intent.action, IntentAction::Hold);
 
     // Bar 20: fires
-    pos.bars_held = 20;
+    pos.bars_held_open = 20;
     let intent = pm.on_bar(&pos, &bar, 20, MarketStatus::Open, &iv);
     assert_eq!(intent.action, IntentAction::ForceExit);
 }
@@ -535,7 +535,7 @@ fn max_holding_fires_for_shorts() {
     let bar = make_bar(90.0);
 
     let mut pos = Position::new_short("SPY".into(), 100.0, 100.0, 0);
-    pos.bars_held = 10;
+    pos.bars_held_open = 10;
     let intent = pm.on_bar(&pos, &bar, 10, MarketStatus::Open, &iv);
     assert_eq!(intent.action, IntentAction::ForceExit);
 }
@@ -743,9 +743,11 @@ fn flat_position_always_holds() {
         highest_price_since_entry: 100.0,
         lowest_price_since_entry: 100.0,
         bars_held: 0,
+        bars_held_open: 0,
         unrealized_pnl: 0.0,
         realized_pnl: 0.0,
         current_stop: None,
+        initial_stop: None,
     };
     let bar = make_bar(100.0);
     let iv = IndicatorValues::new();
@@ -790,7 +792,7 @@ fn noop_pm_always_holds_extended() {
         let close = 100.0 + (i as f64 * 0.5);
         let bar = make_bar(close);
         pos.update_mark(close);
-        pos.tick_bar();
+        pos.tick_bar(MarketStatus::Open);
 
         let intent = pm.on_bar(&pos, &bar, i, MarketStatus::Open, &iv);
         assert_eq!(
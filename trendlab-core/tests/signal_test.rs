@@ -1,4 +1,4 @@
-//! Integration tests for all 10 signal generators.
+//! Integration tests for all 13 signal generators.
 //!
 //! Tests:
 //! 1. Each signal produces non-empty output on 252 bars of synthetic data.
@@ -6,13 +6,18 @@
 //!    signals for overlapping bars.
 //! 3. NaN injection: no signal fires on NaN bars; signals still fire on valid bars.
 //! 4. Portfolio agnosticism: compile-time invariant (comment-only).
+//! 5. Metadata contract: every event carries the documented keys, and a stop
+//!    entry triggers exactly at the published breakout level plus its offset.
 
 use chrono::NaiveDate;
 use std::collections::BTreeMap;
-use trendlab_core::components::factory::{create_signal, required_indicators};
+use trendlab_core::components::factory::{create_execution, create_signal, required_indicators};
 use trendlab_core::components::indicator::{Indicator, IndicatorValues};
-use trendlab_core::components::signal::SignalGenerator;
-use trendlab_core::domain::Bar;
+use trendlab_core::components::signal::{
+    SignalDirection, SignalGenerator, BREAKOUT_LEVEL, REFERENCE_PRICE, SIGNAL_BAR_HIGH,
+    SIGNAL_BAR_LOW,
+};
+use trendlab_core::domain::{Bar, Instrument, OrderType};
 use trendlab_core::fingerprint::ComponentConfig;
 
 // ──────────────────────────────────────────────
@@ -28,6 +33,7 @@ fn config(component_type: &str, params: &[(&str, f64)]) -> ComponentConfig {
     ComponentConfig {
         component_type: component_type.to_string(),
         params: btree(params),
+        children: Vec::new(),
     }
 }
 
@@ -36,6 +42,7 @@ fn no_filter_config() -> ComponentConfig {
     ComponentConfig {
         component_type: "no_filter".to_string(),
         params: BTreeMap::new(),
+        children: Vec::new(),
     }
 }
 
@@ -44,6 +51,7 @@ fn no_op_pm_config() -> ComponentConfig {
     ComponentConfig {
         component_type: "no_op".to_string(),
         params: BTreeMap::new(),
+        children: Vec::new(),
     }
 }
 
@@ -55,7 +63,7 @@ fn no_op_pm_config() -> ComponentConfig {
 /// The test uses a shifted Donchian series for these signals.
 const DONCHIAN_SIGNALS: &[&str] = &["breakout_52w", "donchian_breakout"];
 
-/// All 10 signal configs with short-enough lookback periods to fire within 252 bars.
+/// All 13 signal configs with short-enough lookback periods to fire within 252 bars.
 fn signal_configs() -> Vec<(&'static str, ComponentConfig)> {
     vec![
         (
@@ -118,6 +126,33 @@ fn signal_configs() -> Vec<(&'static str, ComponentConfig)> {
             "aroon_crossover",
             config("aroon_crossover", &[("period", 10.0)]),
         ),
+        (
+            "ichimoku_cloud",
+            config(
+                "ichimoku_cloud",
+                &[("tenkan", 5.0), ("kijun", 10.0), ("senkou_b", 20.0)],
+            ),
+        ),
+        // The noise-free V keeps ADX near 100 except after the reversal,
+        // where it dips to ~65 and climbs back through 70
+        (
+            "adx_trend",
+            config("adx_trend", &[("period", 10.0), ("threshold", 70.0)]),
+        ),
+        // The V's turn is the only compression: the band narrows while its
+        // window straddles the bottom, and the rally breaks out of it
+        (
+            "squeeze_breakout",
+            config(
+                "squeeze_breakout",
+                &[
+                    ("period", 10.0),
+                    ("percentile_lookback", 30.0),
+                    ("percentile", 20.0),
+                    ("min_squeeze_bars", 1.0),
+                ],
+            ),
+        ),
     ]
 }
 
@@ -143,7 +178,7 @@ fn trending_bars(n: usize) -> Vec<Bar> {
                 200.0 - (inflection as f64) * 1.0 + bars_since * 3.0
             };
             Bar {
-                symbol: "TEST".to_string(),
+                symbol: "TEST".into(),
                 date: base_date + chrono::Duration::days(i as i64),
                 open: close - 0.5,
                 high: close + 1.5,
@@ -173,9 +208,11 @@ fn compute_indicators_for_signal(
     let mut iv = IndicatorValues::new();
     let needs_lag = DONCHIAN_SIGNALS.contains(&signal_name);
 
-    for ind in indicators {
-        let values = ind.compute(bars);
-        let final_values = if needs_lag && ind.name().starts_with("donchian_upper") {
+    for (name, values) in indicators
+        .iter()
+        .flat_map(|ind| ind.outputs().into_iter().zip(ind.compute_all(bars)))
+    {
+        let final_values = if needs_lag && name.starts_with("donchian_upper") {
             // Shift values forward by one: value[i] = original[i-1].
             // This means bar i gets the donchian upper from bar i-1,
             // which does not include bar i's high.
@@ -187,7 +224,7 @@ fn compute_indicators_for_signal(
         } else {
             values
         };
-        iv.insert(ind.name().to_string(), final_values);
+        iv.insert(name, final_values);
     }
     iv
 }
@@ -197,8 +234,9 @@ fn compute_indicators_for_signal(
 fn compute_indicators(bars: &[Bar], indicators: &[Box<dyn Indicator>]) -> IndicatorValues {
     let mut iv = IndicatorValues::new();
     for ind in indicators {
-        let values = ind.compute(bars);
-        iv.insert(ind.name().to_string(), values);
+        for (name, values) in ind.outputs().into_iter().zip(ind.compute_all(bars)) {
+            iv.insert(name, values);
+        }
     }
     iv
 }
@@ -210,7 +248,7 @@ fn evaluate_all(
     indicators: &IndicatorValues,
 ) -> Vec<usize> {
     (0..bars.len())
-        .filter(|&i| signal.evaluate(bars, i, indicators).is_some())
+        .filter(|&i| signal.evaluate(&bars, i, indicators).is_some())
         .collect()
 }
 
@@ -224,7 +262,7 @@ fn evaluate_pattern(
 ) -> Vec<Option<bool>> {
     (0..bars.len())
         .map(|i| {
-            signal.evaluate(bars, i, indicators).map(|evt| {
+            signal.evaluate(&bars, i, indicators).map(|evt| {
                 evt.direction == trendlab_core::components::signal::SignalDirection::Long
             })
         })
@@ -351,7 +389,7 @@ fn nan_bars_produce_no_signal() {
 /// ```rust,ignore
 /// fn evaluate(
 ///     &self,
-///     bars: &[Bar],
+///     bars: &dyn BarSeries,
 ///     bar_index: usize,
 ///     indicators: &IndicatorValues,
 /// ) -> Option<SignalEvent>;
@@ -364,7 +402,7 @@ fn nan_bars_produce_no_signal() {
 #[test]
 fn portfolio_agnosticism_is_enforced_by_trait_signature() {
     // The SignalGenerator::evaluate method takes only:
-    //   &self, bars: &[Bar], bar_index: usize, indicators: &IndicatorValues
+    //   &self, bars: &dyn BarSeries, bar_index: usize, indicators: &IndicatorValues
     // No portfolio, no position, no account state.
     //
     // This is a compile-time invariant. If the trait signature changes to
@@ -375,7 +413,92 @@ fn portfolio_agnosticism_is_enforced_by_trait_signature() {
 }
 
 // ──────────────────────────────────────────────
-// 5. Per-signal smoke tests (verbose names)
+// 5. Metadata contract
+// ──────────────────────────────────────────────
+
+/// Signals that enter on a price level and publish it as `breakout_level`.
+const LEVEL_SIGNALS: &[&str] = &[
+    "breakout_52w",
+    "donchian_breakout",
+    "bollinger_breakout",
+    "keltner_breakout",
+    "supertrend",
+    "parabolic_sar",
+    "squeeze_breakout",
+];
+
+#[test]
+fn every_signal_event_carries_the_contract_keys() {
+    let bars = trending_bars(252);
+
+    for (name, cfg) in signal_configs() {
+        let signal = create_signal(&cfg).unwrap();
+        let indicators = required_indicators(&cfg, &no_filter_config(), &no_op_pm_config());
+        let iv = compute_indicators_for_signal(&bars, &indicators, name);
+
+        for i in evaluate_all(signal.as_ref(), &bars, &iv) {
+            let meta = &signal.evaluate(&bars, i, &iv).unwrap().metadata;
+            assert_eq!(
+                meta.get(REFERENCE_PRICE),
+                Some(&bars[i].close),
+                "{name} at {i}"
+            );
+            assert_eq!(
+                meta.get(SIGNAL_BAR_HIGH),
+                Some(&bars[i].high),
+                "{name} at {i}"
+            );
+            assert_eq!(
+                meta.get(SIGNAL_BAR_LOW),
+                Some(&bars[i].low),
+                "{name} at {i}"
+            );
+            assert_eq!(
+                meta.contains_key(BREAKOUT_LEVEL),
+                LEVEL_SIGNALS.contains(&name),
+                "{name} at {i}: breakout_level presence"
+            );
+        }
+    }
+}
+
+#[test]
+fn stop_entry_triggers_at_published_breakout_level_plus_offset() {
+    let bars = trending_bars(252);
+    let instrument = Instrument::us_equity("TEST");
+    let offset_ticks = 2.0;
+    let execution = create_execution(&config("stop_entry", &[("offset_ticks", offset_ticks)]))
+        .expect("stop_entry with offset_ticks");
+
+    for (name, cfg) in signal_configs() {
+        if !LEVEL_SIGNALS.contains(&name) {
+            continue;
+        }
+        let signal = create_signal(&cfg).unwrap();
+        let indicators = required_indicators(&cfg, &no_filter_config(), &no_op_pm_config());
+        let iv = compute_indicators_for_signal(&bars, &indicators, name);
+
+        for i in evaluate_all(signal.as_ref(), &bars, &iv) {
+            let event = signal.evaluate(&bars, i, &iv).unwrap();
+            let level = event.metadata[BREAKOUT_LEVEL];
+            let offset = offset_ticks * instrument.tick_size;
+            let expected = match event.direction {
+                SignalDirection::Long => level + offset,
+                SignalDirection::Short => level - offset,
+            };
+            match execution.entry_order_type(&event, &bars[i], &instrument) {
+                OrderType::StopMarket { trigger_price } => assert!(
+                    (trigger_price - expected).abs() < 1e-9,
+                    "{name} at {i}: trigger {trigger_price}, expected {expected}"
+                ),
+                other => panic!("{name}: expected StopMarket, got {other:?}"),
+            }
+        }
+    }
+}
+
+// ──────────────────────────────────────────────
+// 6. Per-signal smoke tests (verbose names)
 // ──────────────────────────────────────────────
 // These are individual named tests so CI output shows exactly which signal
 // failed, rather than a single parametric test.
@@ -477,3 +600,29 @@ signal_smoke_test!(
     "aroon_crossover",
     &[("period", 10.0)]
 );
+
+signal_smoke_test!(
+    smoke_ichimoku_cloud,
+    "ichimoku_cloud",
+    "ichimoku_cloud",
+    &[("tenkan", 5.0), ("kijun", 10.0), ("senkou_b", 20.0)]
+);
+
+signal_smoke_test!(
+    smoke_adx_trend,
+    "adx_trend",
+    "adx_trend",
+    &[("period", 10.0), ("threshold", 70.0)]
+);
+
+signal_smoke_test!(
+    smoke_squeeze_breakout,
+    "squeeze_breakout",
+    "squeeze_breakout",
+    &[
+        ("period", 10.0),
+        ("percentile_lookback", 30.0),
+        ("percentile", 20.0),
+        ("min_squeeze_bars", 1.0)
+    ]
+);
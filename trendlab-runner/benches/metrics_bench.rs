@@ -0,0 +1,102 @@
+//! Criterion benchmarks for performance metrics.
+//!
+//! `compute` (one pass over the curve and one over the trades) against the
+//! per-metric functions called one by one, on a 1M-bar synthetic curve.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use chrono::NaiveDate;
+use trendlab_core::domain::{PositionSide, TradeRecord};
+use trendlab_runner::metrics::{self, PerformanceMetrics, TRADING_DAYS_PER_YEAR};
+
+const BARS: usize = 1_000_000;
+const TRADES: usize = 10_000;
+
+// ── Helpers ──────────────────────────────────────────────────────────
+
+fn make_curve(n: usize) -> Vec<f64> {
+    (0..n)
+        .map(|i| {
+            let t = i as f64;
+            100_000.0 * (1.0 + t * 1e-6) * (1.0 + 0.05 * (t * 0.01).sin() + 0.01 * (t * 0.7).sin())
+        })
+        .collect()
+}
+
+fn make_trades(n: usize) -> Vec<TradeRecord> {
+    let date = NaiveDate::from_ymd_opt(2020, 1, 2).unwrap();
+    (0..n)
+        .map(|i| {
+            let net_pnl = 500.0 * (i as f64 * 0.37).sin();
+            TradeRecord {
+                symbol: "SPY".into(),
+                side: PositionSide::Long,
+                entry_bar: i * 100,
+                entry_date: date,
+                entry_price: 100.0,
+                entry_trigger_margin: None,
+                exit_bar: i * 100 + 50,
+                exit_date: date,
+                exit_price: 100.0 + net_pnl / 100.0,
+                quantity: 100.0,
+                gross_pnl: net_pnl,
+                commission: 0.0,
+                slippage: 0.0,
+                net_pnl,
+                bars_held: 50,
+                mae: 0.0,
+                mfe: 0.0,
+                initial_stop_price: None,
+                entry_source: None,
+                exit_source: None,
+                liquidated_at_end: false,
+                expiry_bar: None,
+                signal_id: None,
+                signal_type: None,
+                pm_type: None,
+                execution_model: None,
+                filter_type: None,
+            }
+        })
+        .collect()
+}
+
+/// Every metric from its own function: one or more passes each.
+fn per_metric(curve: &[f64], trades: &[TradeRecord]) -> f64 {
+    let days = curve.len();
+    let ppy = TRADING_DAYS_PER_YEAR;
+    metrics::total_return(curve)
+        + metrics::cagr(curve, days, ppy)
+        + metrics::sharpe_ratio(curve, 0.0, ppy)
+        + metrics::sortino_ratio(curve, 0.0, ppy)
+        + metrics::calmar_ratio(curve, days, ppy)
+        + metrics::max_drawdown(curve)
+        + metrics::win_rate(trades)
+        + metrics::profit_factor(trades)
+        + metrics::turnover(trades, metrics::average_equity(curve).unwrap(), days, ppy)
+        + metrics::max_consecutive_wins(trades) as f64
+        + metrics::max_consecutive_losses(trades) as f64
+        + metrics::avg_losing_streak(trades)
+}
+
+// ── Metrics ──────────────────────────────────────────────────────────
+
+fn bench_metrics(c: &mut Criterion) {
+    let mut group = c.benchmark_group("performance_metrics");
+    group.sample_size(20);
+
+    let curve = make_curve(BARS);
+    let trades = make_trades(TRADES);
+
+    group.bench_function("compute_1m_bars", |b| {
+        b.iter(|| PerformanceMetrics::compute(black_box(&curve), black_box(&trades), 100_000.0));
+    });
+    group.bench_function("per_metric_1m_bars", |b| {
+        b.iter(|| per_metric(black_box(&curve), black_box(&trades)));
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_metrics);
+criterion_main!(benches);
@@ -0,0 +1,485 @@
+//! Adaptive exploration — YOLO sliders that steer themselves.
+//!
+//! With an [`AdaptiveConfig`] on the session, the [`AdaptiveController`]
+//! picks `jitter_pct` and `structural_explore` for every iteration from what
+//! the session has observed so far:
+//!
+//! - **Explore**: best fitness has not improved for `plateau_iterations`.
+//!   `structural_explore` rises by `explore_step` (up to `max_explore`), and
+//!   again for every further `plateau_iterations` without a gain.
+//! - **Exploit**: at least `exploit_insertion_rate` of the last
+//!   `insertion_window` iterations added a leaderboard entry. Jitter and
+//!   `structural_explore` shrink by `exploit_scale`, and iterations mutate
+//!   the top cross-symbol champions with probability `exploit_pct`.
+//! - **Baseline**: the configured slider values. Exploit falls back to it
+//!   when insertions dry up, Explore when fitness improves again, and every
+//!   mode when the dataset hash changes (new data arrived).
+//!
+//! The controller has no randomness of its own: the same seed and the same
+//! iteration outcomes produce the same transitions. Each transition is
+//! carried into the next history checkpoint.
+
+use std::collections::VecDeque;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::yolo::YoloTunables;
+
+/// Adaptive exploration settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdaptiveConfig {
+    /// Iterations without a best-fitness gain before each explore step.
+    pub plateau_iterations: usize,
+    /// `structural_explore` added per explore step.
+    pub explore_step: f64,
+    /// Ceiling for `structural_explore` while exploring.
+    pub max_explore: f64,
+    /// Trailing window, in iterations, for the insertion rate.
+    pub insertion_window: usize,
+    /// Insertion rate over the window that switches to exploit mode.
+    pub exploit_insertion_rate: f64,
+    /// Factor applied to the baseline jitter and `structural_explore` while
+    /// exploiting.
+    pub exploit_scale: f64,
+    /// Probability that an exploiting iteration mutates a champion.
+    pub exploit_pct: f64,
+}
+
+impl Default for AdaptiveConfig {
+    fn default() -> Self {
+        Self {
+            plateau_iterations: 200,
+            explore_step: 0.1,
+            max_explore: 0.9,
+            insertion_window: 50,
+            exploit_insertion_rate: 0.2,
+            exploit_scale: 0.5,
+            exploit_pct: 0.8,
+        }
+    }
+}
+
+/// What the controller is currently doing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExplorationMode {
+    /// Configured slider values.
+    #[default]
+    Baseline,
+    /// Raised `structural_explore` after a best-fitness plateau.
+    Explore { steps: usize },
+    /// Tightened sliders, mutating around the top champions.
+    Exploit { champions: usize },
+}
+
+impl ExplorationMode {
+    /// Short status line, e.g. "exploit (around 3 champions)".
+    pub fn describe(&self) -> String {
+        match self {
+            ExplorationMode::Baseline => "baseline".to_string(),
+            ExplorationMode::Explore { steps: 1 } => "explore (1 step up)".to_string(),
+            ExplorationMode::Explore { steps } => format!("explore ({steps} steps up)"),
+            ExplorationMode::Exploit { champions: 1 } => "exploit (around 1 champion)".to_string(),
+            ExplorationMode::Exploit { champions } => {
+                format!("exploit (around {champions} champions)")
+            }
+        }
+    }
+}
+
+impl fmt::Display for ExplorationMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+/// Why the controller changed mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransitionReason {
+    Plateau,
+    FitnessImproved,
+    InsertionsRising,
+    InsertionsFalling,
+    DatasetChanged,
+    BaselineChanged,
+}
+
+/// One mode change, as logged in history checkpoints.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModeTransition {
+    pub iteration: usize,
+    pub from: ExplorationMode,
+    pub to: ExplorationMode,
+    pub reason: TransitionReason,
+    /// Slider values in effect from the next iteration.
+    pub tunables: YoloTunables,
+}
+
+/// Chooses the slider values and exploit probability for each iteration.
+///
+/// Per iteration: call [`observe_dataset`](Self::observe_dataset) before
+/// sampling, then [`end_iteration`](Self::end_iteration) once the results
+/// are in.
+#[derive(Debug)]
+pub struct AdaptiveController {
+    config: AdaptiveConfig,
+    baseline: YoloTunables,
+    dataset_hash: String,
+    mode: ExplorationMode,
+    /// Per-iteration "added a leaderboard entry" flags since the last
+    /// transition, capped at the insertion window.
+    recent: VecDeque<bool>,
+    recent_hits: usize,
+    /// `since_improvement` at the last explore step.
+    explored_at: usize,
+}
+
+impl AdaptiveController {
+    pub fn new(config: AdaptiveConfig, baseline: YoloTunables, dataset_hash: &str) -> Self {
+        Self {
+            config,
+            baseline,
+            dataset_hash: dataset_hash.to_string(),
+            mode: ExplorationMode::Baseline,
+            recent: VecDeque::new(),
+            recent_hits: 0,
+            explored_at: 0,
+        }
+    }
+
+    pub fn mode(&self) -> ExplorationMode {
+        self.mode
+    }
+
+    /// Slider values the modes adjust from.
+    pub fn baseline(&self) -> YoloTunables {
+        self.baseline
+    }
+
+    /// Slider values for the current mode.
+    pub fn tunables(&self) -> YoloTunables {
+        let mut tunables = self.baseline;
+        match self.mode {
+            ExplorationMode::Baseline => {}
+            ExplorationMode::Explore { steps } => {
+                let raised =
+                    self.baseline.structural_explore + steps as f64 * self.config.explore_step;
+                let ceiling = self
+                    .config
+                    .max_explore
+                    .max(self.baseline.structural_explore);
+                tunables.structural_explore = raised.min(ceiling).clamp(0.0, 1.0);
+            }
+            ExplorationMode::Exploit { .. } => {
+                let scale = self.config.exploit_scale.clamp(0.0, 1.0);
+                tunables.jitter_pct *= scale;
+                tunables.structural_explore *= scale;
+            }
+        }
+        tunables
+    }
+
+    /// Probability that an iteration mutates a champion: `exploit_pct` while
+    /// exploiting, otherwise `configured`.
+    pub fn exploit_pct(&self, configured: f64) -> f64 {
+        match self.mode {
+            ExplorationMode::Exploit { .. } => self.config.exploit_pct,
+            _ => configured,
+        }
+    }
+
+    /// Reset to the baseline if the data changed since the last call.
+    pub fn observe_dataset(
+        &mut self,
+        iteration: usize,
+        dataset_hash: &str,
+    ) -> Option<ModeTransition> {
+        if dataset_hash == self.dataset_hash {
+            return None;
+        }
+        self.dataset_hash = dataset_hash.to_string();
+        self.transition(
+            iteration,
+            ExplorationMode::Baseline,
+            TransitionReason::DatasetChanged,
+        )
+    }
+
+    /// Adopt new baseline slider values (e.g. moved by the user mid-session).
+    /// Drops back to baseline mode, since the old adjustments were relative
+    /// to the previous values.
+    pub fn set_baseline(
+        &mut self,
+        iteration: usize,
+        baseline: YoloTunables,
+    ) -> Option<ModeTransition> {
+        if baseline == self.baseline {
+            return None;
+        }
+        self.baseline = baseline;
+        let transition = self.transition(
+            iteration,
+            ExplorationMode::Baseline,
+            TransitionReason::BaselineChanged,
+        );
+        // Report the change even when already at baseline: the sliders moved
+        transition.or_else(|| {
+            Some(ModeTransition {
+                iteration,
+                from: self.mode,
+                to: self.mode,
+                reason: TransitionReason::BaselineChanged,
+                tunables: self.tunables(),
+            })
+        })
+    }
+
+    /// Close out `iteration`. `inserted` is whether any leaderboard accepted
+    /// an entry, `since_improvement` the convergence tracker's count of
+    /// iterations without a best-fitness gain, and `champions` how many
+    /// champions an exploiting iteration could mutate.
+    pub fn end_iteration(
+        &mut self,
+        iteration: usize,
+        inserted: bool,
+        since_improvement: usize,
+        champions: usize,
+    ) -> Option<ModeTransition> {
+        let window = self.config.insertion_window.max(1);
+        self.recent.push_back(inserted);
+        self.recent_hits += usize::from(inserted);
+        while self.recent.len() > window {
+            if self.recent.pop_front() == Some(true) {
+                self.recent_hits -= 1;
+            }
+        }
+        let window_full = self.recent.len() == window;
+        let insertion_rate = self.recent_hits as f64 / window as f64;
+
+        if since_improvement < self.explored_at {
+            self.explored_at = 0;
+        }
+        let plateau = self.config.plateau_iterations > 0
+            && since_improvement - self.explored_at >= self.config.plateau_iterations;
+
+        let (next, reason) = match self.mode {
+            _ if plateau => {
+                self.explored_at = since_improvement;
+                let steps = match self.mode {
+                    ExplorationMode::Explore { steps } => steps + 1,
+                    _ => 1,
+                };
+                (
+                    ExplorationMode::Explore { steps },
+                    TransitionReason::Plateau,
+                )
+            }
+            ExplorationMode::Explore { .. } if since_improvement == 0 => {
+                (ExplorationMode::Baseline, TransitionReason::FitnessImproved)
+            }
+            ExplorationMode::Exploit { .. }
+                if window_full && insertion_rate < self.config.exploit_insertion_rate =>
+            {
+                (
+                    ExplorationMode::Baseline,
+                    TransitionReason::InsertionsFalling,
+                )
+            }
+            ExplorationMode::Exploit { .. } => {
+                // Track the champion count without logging a transition
+                self.mode = ExplorationMode::Exploit { champions };
+                return None;
+            }
+            _ if window_full
+                && champions > 0
+                && insertion_rate >= self.config.exploit_insertion_rate =>
+            {
+                (
+                    ExplorationMode::Exploit { champions },
+                    TransitionReason::InsertionsRising,
+                )
+            }
+            _ => return None,
+        };
+        self.transition(iteration, next, reason)
+    }
+
+    fn transition(
+        &mut self,
+        iteration: usize,
+        to: ExplorationMode,
+        reason: TransitionReason,
+    ) -> Option<ModeTransition> {
+        if to == self.mode {
+            return None;
+        }
+        let from = std::mem::replace(&mut self.mode, to);
+        // Each mode is judged on its own iterations
+        self.recent.clear();
+        self.recent_hits = 0;
+        Some(ModeTransition {
+            iteration,
+            from,
+            to,
+            reason,
+            tunables: self.tunables(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn baseline() -> YoloTunables {
+        YoloTunables {
+            jitter_pct: 0.5,
+            structural_explore: 0.3,
+        }
+    }
+
+    fn controller() -> AdaptiveController {
+        AdaptiveController::new(
+            AdaptiveConfig {
+                plateau_iterations: 10,
+                insertion_window: 4,
+                exploit_insertion_rate: 0.5,
+                ..AdaptiveConfig::default()
+            },
+            baseline(),
+            "hash-a",
+        )
+    }
+
+    /// Feed (inserted, since_improvement) outcomes with 3 champions and
+    /// return the transitions.
+    fn run(c: &mut AdaptiveController, outcomes: &[(bool, usize)]) -> Vec<ModeTransition> {
+        outcomes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &(inserted, since))| c.end_iteration(i, inserted, since, 3))
+            .collect()
+    }
+
+    #[test]
+    fn insertions_switch_to_exploit_and_back() {
+        let mut c = controller();
+        let mut outcomes = vec![(true, 0), (false, 0), (true, 0), (false, 0)];
+        outcomes.extend([(false, 1), (false, 2), (false, 3), (true, 4)]);
+        let transitions = run(&mut c, &outcomes);
+
+        assert_eq!(transitions.len(), 2);
+        assert_eq!(transitions[0].iteration, 3);
+        assert_eq!(transitions[0].to, ExplorationMode::Exploit { champions: 3 });
+        assert_eq!(transitions[0].reason, TransitionReason::InsertionsRising);
+        assert!((transitions[0].tunables.jitter_pct - 0.25).abs() < 1e-12);
+        assert!((transitions[0].tunables.structural_explore - 0.15).abs() < 1e-12);
+
+        // The window restarts on entering exploit: 1 of 4 is below the rate
+        assert_eq!(transitions[1].iteration, 7);
+        assert_eq!(transitions[1].to, ExplorationMode::Baseline);
+        assert_eq!(transitions[1].reason, TransitionReason::InsertionsFalling);
+        assert_eq!(c.tunables(), baseline());
+    }
+
+    #[test]
+    fn exploit_mode_raises_champion_probability() {
+        let mut c = controller();
+        assert_eq!(c.exploit_pct(0.1), 0.1);
+        run(&mut c, &[(true, 0); 4]);
+        assert_eq!(c.exploit_pct(0.1), 0.8);
+        assert_eq!(c.mode().describe(), "exploit (around 3 champions)");
+        c.end_iteration(4, true, 0, 5);
+        assert_eq!(c.mode(), ExplorationMode::Exploit { champions: 5 });
+    }
+
+    #[test]
+    fn no_champions_means_no_exploit() {
+        let mut c = controller();
+        for i in 0..8 {
+            assert!(c.end_iteration(i, true, 0, 0).is_none());
+        }
+        assert_eq!(c.mode(), ExplorationMode::Baseline);
+    }
+
+    #[test]
+    fn plateau_steps_up_exploration_until_fitness_improves() {
+        let mut c = controller();
+        let outcomes: Vec<(bool, usize)> =
+            (1..=25).map(|s| (false, s)).chain([(false, 0)]).collect();
+        let transitions = run(&mut c, &outcomes);
+
+        let modes: Vec<_> = transitions.iter().map(|t| (t.iteration, t.to)).collect();
+        assert_eq!(
+            modes,
+            vec![
+                (9, ExplorationMode::Explore { steps: 1 }),
+                (19, ExplorationMode::Explore { steps: 2 }),
+                (25, ExplorationMode::Baseline),
+            ]
+        );
+        assert!((transitions[0].tunables.structural_explore - 0.4).abs() < 1e-12);
+        assert!((transitions[1].tunables.structural_explore - 0.5).abs() < 1e-12);
+        assert_eq!(transitions[1].tunables.jitter_pct, 0.5);
+        assert_eq!(transitions[2].reason, TransitionReason::FitnessImproved);
+    }
+
+    #[test]
+    fn exploration_is_capped() {
+        let mut c = controller();
+        let outcomes: Vec<(bool, usize)> = (1..=200).map(|s| (false, s)).collect();
+        run(&mut c, &outcomes);
+        assert_eq!(c.mode(), ExplorationMode::Explore { steps: 20 });
+        assert_eq!(c.tunables().structural_explore, 0.9);
+    }
+
+    #[test]
+    fn new_data_resets_to_baseline() {
+        let mut c = controller();
+        run(&mut c, &[(true, 0); 4]);
+        assert!(c.observe_dataset(4, "hash-a").is_none());
+
+        let reset = c.observe_dataset(5, "hash-b").unwrap();
+        assert_eq!(reset.from, ExplorationMode::Exploit { champions: 3 });
+        assert_eq!(reset.to, ExplorationMode::Baseline);
+        assert_eq!(reset.reason, TransitionReason::DatasetChanged);
+        assert_eq!(c.tunables(), baseline());
+        // Already at baseline: nothing more to reset
+        assert!(c.observe_dataset(6, "hash-c").is_none());
+    }
+
+    #[test]
+    fn moved_sliders_become_the_new_baseline() {
+        let mut c = controller();
+        let moved = YoloTunables {
+            jitter_pct: 0.2,
+            structural_explore: 0.6,
+        };
+        assert!(c.set_baseline(0, baseline()).is_none());
+        let change = c.set_baseline(1, moved).unwrap();
+        assert_eq!(change.reason, TransitionReason::BaselineChanged);
+        assert_eq!(change.tunables, moved);
+    }
+
+    #[test]
+    fn same_outcomes_same_transitions() {
+        let outcomes: Vec<(bool, usize)> = (0..300)
+            .map(|i| (i % 7 < 3, if i % 97 == 0 { 0 } else { i % 97 }))
+            .collect();
+        let a = run(&mut controller(), &outcomes);
+        let b = run(&mut controller(), &outcomes);
+        assert!(!a.is_empty());
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn config_defaults_fill_in_from_json() {
+        let config: AdaptiveConfig =
+            serde_json::from_str(r#"{"plateau_iterations": 500}"#).unwrap();
+        assert_eq!(config.plateau_iterations, 500);
+        assert_eq!(config.exploit_scale, 0.5);
+    }
+}
@@ -0,0 +1,317 @@
+//! Trade journal — user notes and tags on runs and their trades.
+//!
+//! A results directory keeps one [`ANNOTATIONS_FILE`] for every run under it.
+//! Runs are keyed by run id (the config's full hash) and symbol, the pair
+//! that identifies a run across sessions and saved artifacts; a run's trades
+//! are keyed by their entry bar, which the trade list and the bar replay
+//! share. Keeping notes outside the artifact directories means they survive
+//! re-running, re-saving or pruning a run, and show up again when the same
+//! config is seen on the same symbol.
+//!
+//! Saves take the history's [`FileLock`] and rename a temp file over the
+//! annotations file, so readers never see a partial write and a crash
+//! mid-save leaves the previous notes intact.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use trendlab_core::domain::FullHash;
+
+use crate::history::FileLock;
+
+/// File name of the annotations inside a results directory.
+pub const ANNOTATIONS_FILE: &str = "annotations.json";
+
+/// A tag from the journal's fixed set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AnnotationTag {
+    /// Results look too good, or rest on too little.
+    Suspicious,
+    /// One trade carries most of the run's PnL.
+    LuckyTrade,
+    /// Entry filled on a gap through the trigger.
+    GapEntry,
+    /// Bad or missing bars distort the result.
+    DataIssue,
+    /// Worth another look later.
+    Revisit,
+}
+
+impl AnnotationTag {
+    pub const ALL: [AnnotationTag; 5] = [
+        AnnotationTag::Suspicious,
+        AnnotationTag::LuckyTrade,
+        AnnotationTag::GapEntry,
+        AnnotationTag::DataIssue,
+        AnnotationTag::Revisit,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AnnotationTag::Suspicious => "suspicious",
+            AnnotationTag::LuckyTrade => "lucky-trade",
+            AnnotationTag::GapEntry => "gap-entry",
+            AnnotationTag::DataIssue => "data-issue",
+            AnnotationTag::Revisit => "revisit",
+        }
+    }
+}
+
+impl fmt::Display for AnnotationTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// A note and tags on a run or a trade.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Annotation {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub tags: BTreeSet<AnnotationTag>,
+}
+
+impl Annotation {
+    /// No note text (blank counts as none) and no tags.
+    pub fn is_empty(&self) -> bool {
+        self.note.trim().is_empty() && self.tags.is_empty()
+    }
+
+    /// Tags joined with `;`, as in the CSV exports.
+    pub fn tags_label(&self) -> String {
+        self.tags
+            .iter()
+            .map(|t| t.label())
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+}
+
+/// Notes on one run and its trades.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunAnnotations {
+    /// Hex full hash of the run's config.
+    pub run_id: String,
+    pub symbol: String,
+    /// Note and tags on the run itself.
+    #[serde(default, skip_serializing_if = "Annotation::is_empty")]
+    pub run: Annotation,
+    /// Notes on trades, by entry bar.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub trades: BTreeMap<usize, Annotation>,
+}
+
+impl RunAnnotations {
+    /// The annotation of the trade entered on `entry_bar`, if any.
+    pub fn trade(&self, entry_bar: usize) -> Option<&Annotation> {
+        self.trades.get(&entry_bar)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.run.is_empty() && self.trades.is_empty()
+    }
+}
+
+/// Every annotation of a results directory, as stored in [`ANNOTATIONS_FILE`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Annotations {
+    /// Sorted by run id, then symbol. Runs without notes are dropped.
+    #[serde(default)]
+    pub runs: Vec<RunAnnotations>,
+}
+
+impl Annotations {
+    /// Load the annotations of the results directory `dir`; empty if it has
+    /// none yet.
+    pub fn load(dir: &Path) -> io::Result<Self> {
+        let path = dir.join(ANNOTATIONS_FILE);
+        let file = match File::open(&path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e),
+        };
+        serde_json::from_reader(BufReader::new(file))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Write the annotations to `dir`'s [`ANNOTATIONS_FILE`], replacing it.
+    pub fn save(&self, dir: &Path) -> io::Result<PathBuf> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(ANNOTATIONS_FILE);
+        let _lock = FileLock::acquire(&path)?;
+        let tmp = dir.join(format!("{ANNOTATIONS_FILE}.tmp"));
+        let mut writer = BufWriter::new(File::create(&tmp)?);
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writer.flush()?;
+        drop(writer);
+        fs::rename(&tmp, &path)?;
+        Ok(path)
+    }
+
+    /// Notes on the run of `run_id` on `symbol`.
+    pub fn get(&self, run_id: &FullHash, symbol: &str) -> Option<&RunAnnotations> {
+        self.position(&run_id.as_hex(), symbol)
+            .ok()
+            .map(|i| &self.runs[i])
+    }
+
+    /// The run's own annotation, if it has one.
+    pub fn run(&self, run_id: &FullHash, symbol: &str) -> Option<&Annotation> {
+        self.get(run_id, symbol)
+            .map(|r| &r.run)
+            .filter(|a| !a.is_empty())
+    }
+
+    /// The annotation of the run's trade entered on `entry_bar`, if any.
+    pub fn trade(&self, run_id: &FullHash, symbol: &str, entry_bar: usize) -> Option<&Annotation> {
+        self.get(run_id, symbol)?.trade(entry_bar)
+    }
+
+    /// Whether the run or any of its trades carries a note or tag.
+    pub fn is_annotated(&self, run_id: &FullHash, symbol: &str) -> bool {
+        self.get(run_id, symbol).is_some()
+    }
+
+    /// Replace the run's own annotation; an empty one removes it.
+    pub fn set_run(&mut self, run_id: &FullHash, symbol: &str, annotation: Annotation) {
+        self.update(run_id, symbol, |run| run.run = annotation);
+    }
+
+    /// Replace the annotation of the run's trade entered on `entry_bar`; an
+    /// empty one removes it.
+    pub fn set_trade(
+        &mut self,
+        run_id: &FullHash,
+        symbol: &str,
+        entry_bar: usize,
+        annotation: Annotation,
+    ) {
+        self.update(run_id, symbol, |run| {
+            if annotation.is_empty() {
+                run.trades.remove(&entry_bar);
+            } else {
+                run.trades.insert(entry_bar, annotation);
+            }
+        });
+    }
+
+    fn update(&mut self, run_id: &FullHash, symbol: &str, edit: impl FnOnce(&mut RunAnnotations)) {
+        let run_id = run_id.as_hex();
+        let index = match self.position(&run_id, symbol) {
+            Ok(i) => i,
+            Err(i) => {
+                let run = RunAnnotations {
+                    run_id,
+                    symbol: symbol.to_string(),
+                    ..RunAnnotations::default()
+                };
+                self.runs.insert(i, run);
+                i
+            }
+        };
+        edit(&mut self.runs[index]);
+        if self.runs[index].is_empty() {
+            self.runs.remove(index);
+        }
+    }
+
+    fn position(&self, run_id: &str, symbol: &str) -> Result<usize, usize> {
+        self.runs
+            .binary_search_by(|r| (r.run_id.as_str(), r.symbol.as_str()).cmp(&(run_id, symbol)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn hash(seed: &str) -> FullHash {
+        FullHash::from_bytes(seed.as_bytes())
+    }
+
+    fn note(text: &str, tags: &[AnnotationTag]) -> Annotation {
+        Annotation {
+            note: text.into(),
+            tags: tags.iter().copied().collect(),
+        }
+    }
+
+    #[test]
+    fn notes_are_kept_per_run_symbol_and_trade() {
+        let mut annotations = Annotations::default();
+        let (a, b) = (hash("a"), hash("b"));
+        annotations.set_run(
+            &a,
+            "SPY",
+            note("one lucky trade", &[AnnotationTag::Suspicious]),
+        );
+        annotations.set_trade(&a, "SPY", 42, note("", &[AnnotationTag::GapEntry]));
+        annotations.set_run(&b, "SPY", note("fine", &[]));
+
+        assert_eq!(annotations.run(&a, "SPY").unwrap().note, "one lucky trade");
+        assert!(annotations.run(&a, "QQQ").is_none());
+        assert_eq!(
+            annotations.trade(&a, "SPY", 42).unwrap().tags_label(),
+            "gap-entry"
+        );
+        assert!(annotations.trade(&a, "SPY", 43).is_none());
+        assert!(annotations.is_annotated(&b, "SPY"));
+        assert_eq!(annotations.runs.len(), 2);
+
+        // A run with only trade notes stays annotated without a run note
+        annotations.set_run(&a, "SPY", Annotation::default());
+        assert!(annotations.run(&a, "SPY").is_none());
+        assert!(annotations.is_annotated(&a, "SPY"));
+
+        // Clearing the last note drops the run
+        annotations.set_trade(&a, "SPY", 42, note("  ", &[]));
+        assert!(!annotations.is_annotated(&a, "SPY"));
+        assert_eq!(annotations.runs.len(), 1);
+    }
+
+    #[test]
+    fn save_replaces_the_file_and_load_round_trips() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(
+            Annotations::load(dir.path()).unwrap(),
+            Annotations::default()
+        );
+
+        let mut annotations = Annotations::default();
+        annotations.set_run(
+            &hash("a"),
+            "SPY",
+            note("check 2020", &[AnnotationTag::Revisit]),
+        );
+        annotations.set_trade(
+            &hash("a"),
+            "SPY",
+            7,
+            note("gap up", &[AnnotationTag::GapEntry]),
+        );
+        let path = annotations.save(dir.path()).unwrap();
+        assert_eq!(path, dir.path().join(ANNOTATIONS_FILE));
+        assert!(!dir.path().join("annotations.json.tmp").exists());
+        assert_eq!(Annotations::load(dir.path()).unwrap(), annotations);
+
+        annotations.set_run(&hash("a"), "SPY", Annotation::default());
+        annotations.set_trade(&hash("a"), "SPY", 7, Annotation::default());
+        annotations.save(dir.path()).unwrap();
+        assert!(Annotations::load(dir.path()).unwrap().runs.is_empty());
+    }
+
+    #[test]
+    fn corrupt_file_is_an_error() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(ANNOTATIONS_FILE), "{ not json").unwrap();
+        let err = Annotations::load(dir.path()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,551 @@
+//! Embedding API — drive TrendLab from your own programs.
+//!
+//! [`TrendLab`] is the supported entry point for research scripts and other
+//! tools: it is built from [`Settings`] and hides the data provider, bar
+//! cache, [`LoadOptions`] and artifact layout that the lower-level functions
+//! take explicitly. The CLI's `run`, `promote` and `corpus run` commands go
+//! through it.
+//!
+//! This module follows semver: within a release series, methods are only
+//! added, never removed or changed in signature, and new options arrive as
+//! new `with_*` builders with defaults matching the previous behaviour. The
+//! free functions elsewhere in the crate remain available but may change
+//! between minor releases.
+//!
+//! Offline with synthetic fallback, nothing touches the network:
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use trendlab_runner::{Settings, TrendLab};
+//!
+//! let dir = tempfile::tempdir().unwrap();
+//! let lab = TrendLab::new(&Settings::default())
+//!     .with_cache_dir(dir.path().join("data"))
+//!     .with_results_dir(dir.path().join("results"))
+//!     .with_offline(true)
+//!     .with_synthetic(true);
+//!
+//! let start = NaiveDate::from_ymd_opt(2022, 1, 3).unwrap();
+//! let end = NaiveDate::from_ymd_opt(2023, 12, 29).unwrap();
+//! let result = lab.run_preset("donchian_trend", "SPY", start..=end).unwrap();
+//! assert!(result.has_synthetic);
+//!
+//! let run_dir = lab.save(&result).unwrap();
+//! assert!(run_dir.join("manifest.json").is_file());
+//! ```
+
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use rayon::prelude::*;
+
+use trendlab_core::components::composition::StrategyPreset;
+use trendlab_core::components::execution::ExecutionPreset;
+use trendlab_core::components::sampler::ParamGrid;
+use trendlab_core::data::{BarInterval, CircuitBreaker, ParquetCache, YahooProvider};
+use trendlab_core::fingerprint::{StrategyConfig, TradingMode};
+
+use crate::config::{BacktestConfig, ConfigError};
+use crate::corpus::{run_corpus, CorpusError, CorpusSnapshot};
+use crate::data_loader::{load_bars, LoadOptions, DEFAULT_STALE_AFTER_DAYS};
+use crate::export::save_artifacts;
+use crate::fdr::FdrFamily;
+use crate::interop::write_results_arrow;
+use crate::promotion::{promote, promote_cached, PromotionConfig, RobustnessResult};
+use crate::promotion_batch::{
+    run_promotion_batch, BatchSettings, PromotionCandidate, PromotionReport,
+};
+use crate::robustness_cache::RobustnessCache;
+use crate::runner::{
+    decode_execution_preset, run_backtest_from_data, run_single_backtest, BacktestResult, RunError,
+};
+use crate::settings::Settings;
+use crate::yolo::{run_yolo_from_cache, YoloConfig, YoloError, YoloResult};
+
+/// One point of a [`TrendLab::sweep`].
+#[derive(Debug)]
+pub struct SweepPoint {
+    pub config: StrategyConfig,
+    pub result: Result<BacktestResult, RunError>,
+}
+
+/// High-level handle for running backtests, sweeps, YOLO sessions and the
+/// promotion ladder. See the [module docs](self) for the stability promise.
+#[derive(Debug, Clone)]
+pub struct TrendLab {
+    cache_dir: PathBuf,
+    results_dir: PathBuf,
+    execution_preset: Option<ExecutionPreset>,
+    symbols: Vec<String>,
+    offline: bool,
+    synthetic: bool,
+    strict_data: bool,
+    stale_after_days: Option<u32>,
+    jobs: usize,
+    robustness_cache: Option<RobustnessCache>,
+    arrow_export: bool,
+}
+
+impl TrendLab {
+    /// Handle using the cache and results directories, execution preset and
+    /// default symbols of `settings`. Online, without synthetic fallback.
+    pub fn new(settings: &Settings) -> Self {
+        let results_dir = settings.results_dir.value.clone();
+        Self {
+            cache_dir: settings.cache_dir.value.clone(),
+            robustness_cache: Some(RobustnessCache::in_results_dir(&results_dir)),
+            results_dir,
+            execution_preset: settings.execution_preset.value,
+            symbols: settings.symbols.value.clone(),
+            offline: false,
+            synthetic: false,
+            strict_data: false,
+            stale_after_days: Some(DEFAULT_STALE_AFTER_DAYS),
+            jobs: 0,
+            arrow_export: settings.arrow_export.value,
+        }
+    }
+
+    /// Bar cache directory.
+    pub fn with_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = dir.into();
+        self
+    }
+
+    /// Directory for saved runs and the robustness cache.
+    pub fn with_results_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.results_dir = dir.into();
+        if self.robustness_cache.is_some() {
+            self.robustness_cache = Some(RobustnessCache::in_results_dir(&self.results_dir));
+        }
+        self
+    }
+
+    /// Never make network requests; a cache miss fails unless synthetic
+    /// fallback is on.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Generate synthetic bars when real data is unavailable.
+    pub fn with_synthetic(mut self, synthetic: bool) -> Self {
+        self.synthetic = synthetic;
+        self
+    }
+
+    /// Fail on data with too many severe anomalies.
+    pub fn with_strict_data(mut self, strict: bool) -> Self {
+        self.strict_data = strict;
+        self
+    }
+
+    /// Refresh (or, offline, flag) cached data ending more than this many
+    /// days before the requested end. None disables the check.
+    pub fn with_stale_after_days(mut self, days: Option<u32>) -> Self {
+        self.stale_after_days = days;
+        self
+    }
+
+    /// Worker threads for sweeps, batch promotion and corpus runs; 0 uses
+    /// all cores.
+    pub fn with_jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs;
+        self
+    }
+
+    /// Reuse promotion-ladder levels stored under the results directory
+    /// (on by default).
+    pub fn with_robustness_cache(mut self, enabled: bool) -> Self {
+        self.robustness_cache = enabled.then(|| RobustnessCache::in_results_dir(&self.results_dir));
+        self
+    }
+
+    /// Also save each run's bars and trades as Arrow IPC files (see
+    /// [`crate::interop`]).
+    pub fn with_arrow_export(mut self, enabled: bool) -> Self {
+        self.arrow_export = enabled;
+        self
+    }
+
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    pub fn results_dir(&self) -> &Path {
+        &self.results_dir
+    }
+
+    /// The robustness cache, when enabled; its stats cover every promotion
+    /// run through this handle.
+    pub fn robustness_cache(&self) -> Option<&RobustnessCache> {
+        self.robustness_cache.as_ref()
+    }
+
+    /// Backtest config for a named [`StrategyPreset`] on `symbol`, with the
+    /// settings' execution preset when one is set.
+    pub fn preset_config(
+        &self,
+        name: &str,
+        symbol: &str,
+        range: RangeInclusive<NaiveDate>,
+    ) -> Result<BacktestConfig, ConfigError> {
+        let preset: StrategyPreset = name.parse().map_err(ConfigError::UnknownPreset)?;
+        let mut strategy_config = preset.to_config();
+        if let Some(execution) = self.execution_preset {
+            strategy_config
+                .execution_model
+                .params
+                .insert("preset".into(), execution as u8 as f64);
+        }
+
+        // Build a TOML string and parse it — ensures the config goes through the same path
+        let toml_str = format!(
+            r#"[backtest]
+symbol = "{symbol}"
+start_date = "{start_date}"
+end_date = "{end_date}"
+initial_capital = 100000.0
+trading_mode = "long_only"
+position_size_pct = 1.0
+
+[signal]
+type = "{sig_type}"
+{sig_params}
+
+[position_manager]
+type = "{pm_type}"
+{pm_params}
+
+[execution_model]
+type = "{exec_type}"
+{exec_params}
+
+[signal_filter]
+type = "{filter_type}"
+{filter_params}
+"#,
+            start_date = range.start(),
+            end_date = range.end(),
+            sig_type = strategy_config.signal.component_type,
+            sig_params = format_params("signal", &strategy_config.signal.params),
+            pm_type = strategy_config.position_manager.component_type,
+            pm_params = format_params("position_manager", &strategy_config.position_manager.params),
+            exec_type = strategy_config.execution_model.component_type,
+            exec_params = format_params("execution_model", &strategy_config.execution_model.params),
+            filter_type = strategy_config.signal_filter.component_type,
+            filter_params = format_params("signal_filter", &strategy_config.signal_filter.params),
+        );
+        BacktestConfig::from_toml(&toml_str)
+    }
+
+    /// Run one backtest, loading its bars through the cache.
+    pub fn run(&self, config: &BacktestConfig) -> Result<BacktestResult, RunError> {
+        let (start, end) = config_range(config)?;
+        let opts = self.load_options(start, end, config.backtest.interval);
+        let provider = self.provider(opts.interval);
+        run_single_backtest(
+            config,
+            &self.cache(),
+            provider.as_ref().map(|p| p as _),
+            &opts,
+        )
+    }
+
+    /// Run a named preset on `symbol` (see [`Self::preset_config`]).
+    pub fn run_preset(
+        &self,
+        name: &str,
+        symbol: &str,
+        range: RangeInclusive<NaiveDate>,
+    ) -> Result<BacktestResult, RunError> {
+        self.run(&self.preset_config(name, symbol, range)?)
+    }
+
+    /// Run every point of `grid` around `config`'s strategy, in grid order.
+    ///
+    /// Bars are loaded once. A point that fails to run is reported in its
+    /// [`SweepPoint`]; only a config or data error fails the whole sweep.
+    ///
+    /// ```
+    /// # use chrono::NaiveDate;
+    /// # use trendlab_runner::{Settings, TrendLab};
+    /// use trendlab_core::components::sampler::{ComponentSlot, ParamGrid};
+    ///
+    /// # let dir = tempfile::tempdir().unwrap();
+    /// let lab = TrendLab::new(&Settings::default())
+    ///     .with_cache_dir(dir.path())
+    ///     .with_offline(true)
+    ///     .with_synthetic(true);
+    /// let start = NaiveDate::from_ymd_opt(2022, 1, 3).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2023, 12, 29).unwrap();
+    /// let config = lab.preset_config("donchian_trend", "SPY", start..=end).unwrap();
+    ///
+    /// let grid = ParamGrid::new().with_axis(
+    ///     ComponentSlot::Signal,
+    ///     "entry_lookback",
+    ///     vec![20.0, 40.0, 60.0],
+    /// );
+    /// let points = lab.sweep(&config, &grid).unwrap();
+    /// assert_eq!(points.len(), 3);
+    /// assert!(points.iter().all(|p| p.result.is_ok()));
+    /// ```
+    pub fn sweep(
+        &self,
+        config: &BacktestConfig,
+        grid: &ParamGrid,
+    ) -> Result<Vec<SweepPoint>, RunError> {
+        config.validate(None).into_result()?;
+        let (start, end) = config_range(config)?;
+        let symbol = config.backtest.symbol.as_str();
+        let opts = self.load_options(start, end, config.backtest.interval);
+        let provider = self.provider(opts.interval);
+        let loaded = load_bars(
+            &[symbol],
+            &self.cache(),
+            provider.as_ref().map(|p| p as _),
+            None,
+            &opts,
+        )
+        .map_err(|source| RunError::DataLoad {
+            symbol: symbol.to_string(),
+            source,
+        })?;
+
+        let run = |strategy_config: StrategyConfig| {
+            let result = run_backtest_from_data(
+                &strategy_config,
+                &loaded.aligned,
+                symbol,
+                config.trading_mode(),
+                config.backtest.initial_capital,
+                config.backtest.position_size_pct,
+                decode_execution_preset(&strategy_config.execution_model.params),
+                &loaded.dataset_hash,
+                loaded.has_synthetic,
+            )
+            .map(|mut result| {
+                result.data_vintage = loaded.vintage.clone();
+                result
+            });
+            SweepPoint {
+                config: strategy_config,
+                result,
+            }
+        };
+        let points = grid.expand(&config.to_strategy_config());
+        Ok(match self.jobs {
+            0 => points.into_par_iter().map(run).collect(),
+            1 => points.into_iter().map(run).collect(),
+            jobs => rayon::ThreadPoolBuilder::new()
+                .num_threads(jobs)
+                .build()
+                .expect("failed to build Rayon thread pool")
+                .install(|| points.into_par_iter().map(run).collect()),
+        })
+    }
+
+    /// Run a YOLO session over `symbols` (the settings' symbols when empty)
+    /// and `config`'s date range, until its iteration limit.
+    pub fn discover(
+        &self,
+        config: &YoloConfig,
+        symbols: &[String],
+    ) -> Result<YoloResult, YoloError> {
+        let symbols = if symbols.is_empty() {
+            &self.symbols
+        } else {
+            symbols
+        };
+        let opts = self.load_options(config.start_date, config.end_date, BarInterval::default());
+        let provider = self.provider(opts.interval);
+        run_yolo_from_cache(
+            config,
+            symbols,
+            &self.cache(),
+            provider.as_ref().map(|p| p as _),
+            &opts,
+            None,
+            None,
+            None,
+        )
+    }
+
+    /// Run the promotion ladder on a finished run.
+    ///
+    /// The run's bars are reloaded for its symbols and date range, so a
+    /// cache that has changed since the run changes what the ladder sees.
+    /// Runs without recorded inputs are promoted long-only at full size.
+    /// Levels come from the robustness cache when it is enabled.
+    pub fn promote(
+        &self,
+        result: &BacktestResult,
+        promotion_config: &PromotionConfig,
+    ) -> Result<RobustnessResult, RunError> {
+        let parse = |date: &str| {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|e| ConfigError::Parse(format!("run date '{date}': {e}")))
+        };
+        let opts = self.load_options(
+            parse(&result.start_date)?,
+            parse(&result.end_date)?,
+            result.interval,
+        );
+        let inputs = result.inputs.as_ref();
+        let symbols: Vec<&str> = match inputs {
+            Some(inputs) if !inputs.symbols.is_empty() => {
+                inputs.symbols.iter().map(String::as_str).collect()
+            }
+            _ => vec![result.symbol.as_str()],
+        };
+        let provider = self.provider(opts.interval);
+        let loaded = load_bars(
+            &symbols,
+            &self.cache(),
+            provider.as_ref().map(|p| p as _),
+            None,
+            &opts,
+        )
+        .map_err(|source| RunError::DataLoad {
+            symbol: result.symbol.clone(),
+            source,
+        })?;
+
+        let trading_mode = inputs.map_or(TradingMode::LongOnly, |i| i.trading_mode);
+        let position_size_pct = inputs.map_or(1.0, |i| i.position_size_pct);
+        let preset = inputs.map_or_else(
+            || decode_execution_preset(&result.config.execution_model.params),
+            |i| i.execution_preset,
+        );
+        let mut fdr_family = FdrFamily::new();
+        Ok(match &self.robustness_cache {
+            Some(cache) => {
+                promote_cached(
+                    result,
+                    &result.config,
+                    &loaded.aligned,
+                    &result.symbol,
+                    trading_mode,
+                    result.initial_capital,
+                    position_size_pct,
+                    preset,
+                    &loaded.dataset_hash,
+                    promotion_config,
+                    &mut fdr_family,
+                    cache,
+                )
+                .0
+            }
+            None => promote(
+                result,
+                &result.config,
+                &loaded.aligned,
+                &result.symbol,
+                trading_mode,
+                result.initial_capital,
+                position_size_pct,
+                preset,
+                &loaded.dataset_hash,
+                promotion_config,
+                &mut fdr_family,
+            ),
+        })
+    }
+
+    /// Re-run and promote saved candidates (see [`run_promotion_batch`]).
+    /// `trading_mode` applies to candidates that don't record one.
+    pub fn promote_batch(
+        &self,
+        candidates: &[PromotionCandidate],
+        promotion_config: &PromotionConfig,
+        trading_mode: TradingMode,
+        position_size_pct: f64,
+    ) -> PromotionReport {
+        let settings = BatchSettings {
+            promotion: promotion_config.clone(),
+            trading_mode,
+            position_size_pct,
+            jobs: self.jobs,
+            robustness_cache: self.robustness_cache.clone(),
+        };
+        // Dates come from each candidate
+        let today = chrono::Local::now().date_naive();
+        let opts = self.load_options(today, today, BarInterval::default());
+        let provider = self.provider(opts.interval);
+        run_promotion_batch(
+            candidates,
+            &self.cache(),
+            provider.as_ref().map(|p| p as _),
+            &opts,
+            &settings,
+        )
+    }
+
+    /// Run every TOML config under `configs_dir` through [`Self::run`] and
+    /// collect their summaries (see [`run_corpus`]). Bars come through the
+    /// cache, so configs sharing data load it once from disk.
+    pub fn run_corpus(&self, configs_dir: &Path) -> Result<CorpusSnapshot, CorpusError> {
+        run_corpus(configs_dir, self.jobs, |config| self.run(config))
+    }
+
+    /// Save `result`'s artifact set in a new directory under the results
+    /// directory and return its path. With Arrow export on, the set also
+    /// holds `results.arrow` and `trades.arrow`.
+    pub fn save(&self, result: &BacktestResult) -> Result<PathBuf, RunError> {
+        let artifact_io = |path: &Path, err: anyhow::Error| RunError::ArtifactIo {
+            path: path.display().to_string(),
+            reason: format!("{err:#}"),
+        };
+        let run_dir = save_artifacts(result, &self.results_dir)
+            .map_err(|err| artifact_io(&self.results_dir, err))?;
+        if self.arrow_export {
+            write_results_arrow(result, &run_dir).map_err(|err| artifact_io(&run_dir, err))?;
+        }
+        Ok(run_dir)
+    }
+
+    fn cache(&self) -> ParquetCache {
+        ParquetCache::new(&self.cache_dir)
+    }
+
+    /// No network client exists at all when offline.
+    fn provider(&self, interval: BarInterval) -> Option<YahooProvider> {
+        (!self.offline).then(|| {
+            YahooProvider::new(Arc::new(CircuitBreaker::default_provider())).with_interval(interval)
+        })
+    }
+
+    fn load_options(&self, start: NaiveDate, end: NaiveDate, interval: BarInterval) -> LoadOptions {
+        LoadOptions {
+            start,
+            end,
+            offline: self.offline,
+            synthetic: self.synthetic,
+            force: false,
+            strict: self.strict_data,
+            stale_after_days: self.stale_after_days,
+            interval,
+        }
+    }
+}
+
+fn config_range(config: &BacktestConfig) -> Result<(NaiveDate, NaiveDate), ConfigError> {
+    let parse = |date: &str| {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|e| ConfigError::Parse(format!("date '{date}': {e}")))
+    };
+    Ok((
+        parse(&config.backtest.start_date)?,
+        parse(&config.backtest.end_date)?,
+    ))
+}
+
+fn format_params(section: &str, params: &std::collections::BTreeMap<String, f64>) -> String {
+    if params.is_empty() {
+        return String::new();
+    }
+    let pairs: Vec<String> = params.iter().map(|(k, v)| format!("{k} = {v}")).collect();
+    format!("[{section}.params]\n{}", pairs.join("\n"))
+}
@@ -24,6 +24,7 @@ use crate::metrics::daily_returns;
 
 /// Configuration for block bootstrap.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct BootstrapConfig {
     /// Number of bootstrap resamples (default 1000).
     pub n_resamples: usize,
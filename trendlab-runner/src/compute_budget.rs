@@ -0,0 +1,270 @@
+//! YOLO compute accounting — what a discovery session cost.
+//!
+//! A [`ComputeBudget`] follows a session run by run: bars simulated, engine
+//! time (summed from each run's phase timings) and indicator-cache hits and
+//! misses, per symbol and in total. Optional [`ComputeLimits`] end the session
+//! once it has simulated enough bars or run long enough, and every session
+//! records why it ended as a [`StopReason`].
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::runner::BacktestResult;
+
+/// Limits that end a YOLO session before `max_iterations`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ComputeLimits {
+    /// Stop once this many bars have been simulated, over all symbols.
+    pub max_bars: Option<u64>,
+    /// Stop once the session has run this many minutes.
+    pub max_minutes: Option<f64>,
+}
+
+/// Why a YOLO session ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    /// `max_iterations` reached.
+    MaxIterations,
+    /// The caller's cancel flag was set.
+    Cancelled,
+    /// `PlateauAction::Stop` fired.
+    Plateau,
+    /// Every symbol was quarantined.
+    AllQuarantined,
+    /// [`ComputeLimits::max_bars`] reached.
+    BarBudget,
+    /// [`ComputeLimits::max_minutes`] reached.
+    TimeBudget,
+}
+
+impl StopReason {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            StopReason::MaxIterations => "iteration limit reached",
+            StopReason::Cancelled => "cancelled",
+            StopReason::Plateau => "plateaued",
+            StopReason::AllQuarantined => "every symbol quarantined",
+            StopReason::BarBudget => "bar budget spent",
+            StopReason::TimeBudget => "time budget spent",
+        }
+    }
+}
+
+/// Compute spent on a set of backtests.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ComputeUsage {
+    /// Completed backtests.
+    pub runs: usize,
+    /// Bars simulated by the completed backtests.
+    pub bars: u64,
+    /// Engine time, summed over the runs' phase timings.
+    pub engine_secs: f64,
+    /// Indicator lookups served from the cache.
+    pub cache_hits: usize,
+    /// Indicator lookups that had to compute the series.
+    pub cache_misses: usize,
+}
+
+impl ComputeUsage {
+    /// Usage of one completed backtest that made the given cache lookups.
+    pub fn of_run(result: &BacktestResult, cache_hits: usize, cache_misses: usize) -> Self {
+        Self {
+            runs: 1,
+            bars: result.equity_curve.len() as u64,
+            engine_secs: result.timings.total().as_secs_f64(),
+            cache_hits,
+            cache_misses,
+        }
+    }
+
+    pub fn add(&mut self, other: &ComputeUsage) {
+        self.runs += other.runs;
+        self.bars += other.bars;
+        self.engine_secs += other.engine_secs;
+        self.cache_hits += other.cache_hits;
+        self.cache_misses += other.cache_misses;
+    }
+
+    /// Share of indicator lookups served from the cache, if any were made.
+    pub fn cache_hit_rate(&self) -> Option<f64> {
+        let lookups = self.cache_hits + self.cache_misses;
+        (lookups > 0).then(|| self.cache_hits as f64 / lookups as f64)
+    }
+
+    /// Bars simulated per second of engine time.
+    pub fn bars_per_sec(&self) -> Option<f64> {
+        (self.engine_secs > 0.0).then(|| self.bars as f64 / self.engine_secs)
+    }
+
+    /// One-line summary, e.g. "1,260 runs, 317,520 bars, 4.2s engine, cache 81% hit".
+    pub fn describe(&self) -> String {
+        let mut out = format!(
+            "{} runs, {} bars, {:.1}s engine",
+            group_thousands(self.runs as u64),
+            group_thousands(self.bars),
+            self.engine_secs
+        );
+        if let Some(rate) = self.cache_hit_rate() {
+            out.push_str(&format!(", cache {:.0}% hit", rate * 100.0));
+        }
+        out
+    }
+}
+
+/// Format a count with comma thousands separators.
+fn group_thousands(n: u64) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Compute accounting for a YOLO session.
+///
+/// Per iteration: call [`record`](Self::record) for every symbol's run, then
+/// [`end_iteration`](Self::end_iteration) once.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ComputeBudget {
+    /// Usage over the whole session.
+    pub total: ComputeUsage,
+    /// Usage per symbol; sums to `total`.
+    pub by_symbol: BTreeMap<String, ComputeUsage>,
+    /// Usage of the most recently ended iteration.
+    pub last_iteration: ComputeUsage,
+    /// Iterations ended so far.
+    pub iterations: usize,
+    #[serde(skip)]
+    current: ComputeUsage,
+}
+
+impl ComputeBudget {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add one run on `symbol` to the current iteration.
+    pub fn record(&mut self, symbol: &str, usage: &ComputeUsage) {
+        self.total.add(usage);
+        self.by_symbol
+            .entry(symbol.to_string())
+            .or_default()
+            .add(usage);
+        self.current.add(usage);
+    }
+
+    /// Close the current iteration and return its usage.
+    pub fn end_iteration(&mut self) -> ComputeUsage {
+        self.last_iteration = std::mem::take(&mut self.current);
+        self.iterations += 1;
+        self.last_iteration
+    }
+
+    /// The limit that `elapsed` wall time and the usage so far have reached.
+    pub fn exhausted(&self, limits: &ComputeLimits, elapsed: Duration) -> Option<StopReason> {
+        if limits.max_bars.is_some_and(|max| self.total.bars >= max) {
+            return Some(StopReason::BarBudget);
+        }
+        if limits
+            .max_minutes
+            .is_some_and(|max| elapsed.as_secs_f64() >= max * 60.0)
+        {
+            return Some(StopReason::TimeBudget);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(bars: u64, engine_secs: f64, cache_hits: usize, cache_misses: usize) -> ComputeUsage {
+        ComputeUsage {
+            runs: 1,
+            bars,
+            engine_secs,
+            cache_hits,
+            cache_misses,
+        }
+    }
+
+    #[test]
+    fn totals_sum_iterations_and_symbols() {
+        let mut budget = ComputeBudget::new();
+        budget.record("SPY", &usage(100, 0.5, 0, 2));
+        budget.record("QQQ", &usage(80, 0.25, 1, 1));
+        let first = budget.end_iteration();
+        budget.record("SPY", &usage(100, 0.5, 2, 0));
+        let second = budget.end_iteration();
+
+        assert_eq!(first.bars, 180);
+        assert_eq!(first.runs, 2);
+        assert_eq!(second.bars, 100);
+        assert_eq!(budget.last_iteration, second);
+        assert_eq!(budget.iterations, 2);
+
+        let mut by_iteration = first;
+        by_iteration.add(&second);
+        assert_eq!(budget.total, by_iteration);
+        let mut by_symbol = ComputeUsage::default();
+        for symbol_usage in budget.by_symbol.values() {
+            by_symbol.add(symbol_usage);
+        }
+        assert_eq!(budget.total, by_symbol);
+        assert_eq!(budget.by_symbol["SPY"].bars, 200);
+        assert_eq!(budget.total.cache_hit_rate(), Some(0.5));
+        assert_eq!(budget.total.bars_per_sec(), Some(224.0));
+    }
+
+    #[test]
+    fn limits_trigger_once_reached() {
+        let limits = ComputeLimits {
+            max_bars: Some(250),
+            max_minutes: Some(1.0),
+        };
+        let mut budget = ComputeBudget::new();
+        budget.record("SPY", &usage(249, 0.1, 0, 1));
+        assert_eq!(budget.exhausted(&limits, Duration::from_secs(59)), None);
+        assert_eq!(
+            budget.exhausted(&limits, Duration::from_secs(60)),
+            Some(StopReason::TimeBudget)
+        );
+        budget.record("SPY", &usage(1, 0.1, 1, 0));
+        assert_eq!(
+            budget.exhausted(&limits, Duration::ZERO),
+            Some(StopReason::BarBudget)
+        );
+        assert_eq!(
+            budget.exhausted(&ComputeLimits::default(), Duration::MAX),
+            None
+        );
+    }
+
+    #[test]
+    fn describe_groups_counts() {
+        let u = ComputeUsage {
+            runs: 1_260,
+            bars: 317_520,
+            engine_secs: 4.2,
+            cache_hits: 81,
+            cache_misses: 19,
+        };
+        assert_eq!(
+            u.describe(),
+            "1,260 runs, 317,520 bars, 4.2s engine, cache 81% hit"
+        );
+        assert_eq!(
+            ComputeUsage::default().describe(),
+            "0 runs, 0 bars, 0.0s engine"
+        );
+    }
+}
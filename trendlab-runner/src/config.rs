@@ -1,11 +1,33 @@
 //! TOML config parsing — loads strategy configurations from TOML files.
+//!
+//! [`BacktestConfig::validate`] is a pre-flight check: it reports every
+//! problem it can find without loading bars or running the engine.
 
+use chrono::{Datelike, NaiveDate, Weekday};
 use serde::Deserialize;
 use std::collections::BTreeMap;
+use std::fmt;
 use std::path::Path;
 
+use trendlab_core::components::composition::{check_compatibility, CompatibilityContext};
+use trendlab_core::components::factory::{
+    create_execution, create_filter, create_pm, create_signal, required_indicators, FactoryError,
+};
+use trendlab_core::components::sampler::constraint_violations;
+use trendlab_core::data::cache::ParquetCache;
+use trendlab_core::data::interval::BarInterval;
+use trendlab_core::domain::Quantization;
+use trendlab_core::engine::execution::fill_price::PARAM_PRECISE_ACCOUNTING;
+use trendlab_core::engine::execution::{CommissionModel, LimitFillPolicy, RegulatoryFees};
+use trendlab_core::engine::{EntryGovernance, WarmupPolicy};
 use trendlab_core::fingerprint::{ComponentConfig, StrategyConfig, TradingMode};
 
+use crate::consistency::ConsistencyCheck;
+use crate::risk_profile::RiskProfile;
+
+/// Date format for `start_date` / `end_date`.
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
 /// Top-level backtest configuration from a TOML file.
 #[derive(Debug, Deserialize)]
 pub struct BacktestConfig {
@@ -15,8 +37,78 @@ pub struct BacktestConfig {
     pub execution_model: ComponentSection,
     #[serde(default = "default_no_filter")]
     pub signal_filter: ComponentSection,
+    /// Optional commission override. When omitted, the execution preset's
+    /// bps commission applies.
+    #[serde(default)]
+    pub commission: Option<CommissionSection>,
+    /// Optional cooldown / re-entry rules. When omitted, entries are ungoverned.
+    ///
+    /// ```toml
+    /// [entry_governance]
+    /// cooldown_bars_after_exit = 5
+    /// max_entries_per_window = 2
+    /// entry_window_bars = 20
+    /// require_signal_reset = true
+    /// ```
+    #[serde(default)]
+    pub entry_governance: Option<EntryGovernance>,
+    /// Optional extra factors for style analysis. The symbol's own
+    /// buy-and-hold is always the first factor.
+    ///
+    /// ```toml
+    /// [style]
+    /// factors = ["TLT", "data/momentum.csv"]
+    /// ```
+    #[serde(default)]
+    pub style: Option<StyleSection>,
+    /// Optional quantity rule. When omitted, orders trade whole shares.
+    ///
+    /// ```toml
+    /// [instrument]
+    /// quantization = "contracts"   # whole_shares | fractional | contracts
+    /// multiplier = 50
+    /// ```
+    #[serde(default)]
+    pub instrument: Option<Quantization>,
+    /// Optional limit fill policy. When omitted, any bar that touches a
+    /// limit price fills it.
+    ///
+    /// ```toml
+    /// [limit_fill]
+    /// policy = "require_penetration"   # always_fill_on_touch | require_penetration | probabilistic_on_touch
+    /// ticks = 1
+    /// ```
+    #[serde(default)]
+    pub limit_fill: Option<LimitFillPolicy>,
+    /// Optional warmup scaling and tradable-bar floor. When omitted, the
+    /// warmup is the longest indicator lookback and at least
+    /// [`DEFAULT_MIN_TRADABLE_BARS`] bars must remain after it.
+    #[serde(default)]
+    pub warmup: Option<WarmupSection>,
+    /// Optional risk profile whose guards and required filter are applied on
+    /// top of the components (see [`RiskProfile::to_overrides`]). Must come
+    /// before the first table.
+    ///
+    /// ```toml
+    /// risk_profile = "Conservative"
+    /// ```
+    #[serde(default)]
+    pub risk_profile: Option<RiskProfile>,
+    /// Optional post-run consistency check. When omitted, debug builds check
+    /// strictly and release builds don't check (see [`ConsistencyCheck`]).
+    ///
+    /// ```toml
+    /// [consistency]
+    /// mode = "lenient"   # strict | lenient | off
+    /// ```
+    #[serde(default)]
+    pub consistency: Option<ConsistencyCheck>,
 }
 
+/// Bars a single run must have left after its warmup unless `[warmup]`
+/// says otherwise.
+pub const DEFAULT_MIN_TRADABLE_BARS: usize = 50;
+
 /// General backtest parameters.
 #[derive(Debug, Deserialize)]
 pub struct BacktestSection {
@@ -29,17 +121,100 @@ pub struct BacktestSection {
     pub trading_mode: String,
     #[serde(default = "default_position_size")]
     pub position_size_pct: f64,
+    /// Bar interval: "daily" (default) or "weekly".
+    #[serde(default)]
+    pub interval: BarInterval,
+    /// Settle fills, cash and PnL in fixed-point micro-units instead of
+    /// `f64`, so results don't drift with the order or number of fills.
+    #[serde(default)]
+    pub precise_accounting: bool,
 }
 
 /// A component (signal, PM, execution, filter) section in TOML.
+///
+/// Composite components list their children as an array of tables:
+///
+/// ```toml
+/// [position_manager]
+/// type = "composite"
+///
+/// [[position_manager.children]]
+/// type = "fixed_stop_loss"
+/// params = { stop_pct = 0.05 }
+///
+/// [[position_manager.children]]
+/// type = "atr_trailing"
+/// ```
 #[derive(Debug, Deserialize)]
 pub struct ComponentSection {
     #[serde(rename = "type")]
     pub component_type: String,
     #[serde(default)]
     pub params: BTreeMap<String, f64>,
+    #[serde(default)]
+    pub children: Vec<ComponentSection>,
+}
+
+impl ComponentSection {
+    /// Convert to a `ComponentConfig`, recursing into children.
+    pub fn to_component_config(&self) -> ComponentConfig {
+        ComponentConfig {
+            component_type: self.component_type.clone(),
+            params: self.params.clone(),
+            children: self
+                .children
+                .iter()
+                .map(ComponentSection::to_component_config)
+                .collect(),
+        }
+    }
 }
 
+/// The `[commission]` section: a tagged commission model plus fee toggle.
+///
+/// ```toml
+/// [commission]
+/// model = "per_share"   # bps | per_share | per_trade | tiered
+/// rate = 0.005
+/// min = 1.0
+/// regulatory_fees = true
+/// ```
+#[derive(Debug, Deserialize)]
+pub struct CommissionSection {
+    #[serde(flatten)]
+    pub model: CommissionModel,
+    /// Charge SEC Section 31 and FINRA TAF fees on sells.
+    #[serde(default)]
+    pub regulatory_fees: bool,
+}
+
+/// The `[style]` section: factor symbols (loaded like backtest data) and
+/// `date,return` CSV files (paths ending in `.csv`).
+#[derive(Debug, Deserialize)]
+pub struct StyleSection {
+    #[serde(default)]
+    pub factors: Vec<String>,
+}
+
+/// The `[warmup]` section: lookback scaling and the tradable-bar floor.
+///
+/// ```toml
+/// [warmup]
+/// multiplier = 3.0          # scale every indicator lookback (EMA convergence)
+/// override_bars = 300       # or fix the warmup outright
+/// min_tradable_bars = 100   # error when fewer bars remain (default 50)
+/// ```
+#[derive(Debug, Deserialize)]
+pub struct WarmupSection {
+    #[serde(flatten)]
+    pub policy: WarmupPolicy,
+    #[serde(default = "default_min_tradable_bars")]
+    pub min_tradable_bars: usize,
+}
+
+fn default_min_tradable_bars() -> usize {
+    DEFAULT_MIN_TRADABLE_BARS
+}
 fn default_capital() -> f64 {
     100_000.0
 }
@@ -53,6 +228,7 @@ fn default_no_filter() -> ComponentSection {
     ComponentSection {
         component_type: "no_filter".to_string(),
         params: BTreeMap::new(),
+        children: Vec::new(),
     }
 }
 
@@ -69,25 +245,191 @@ impl BacktestConfig {
     }
 
     /// Convert to a StrategyConfig for the factory system.
+    ///
+    /// `[commission]`, `[instrument]` and `[limit_fill]` sections and the
+    /// `precise_accounting` flag are encoded into the execution model's
+    /// params and `[entry_governance]` and the `[warmup]` policy into the
+    /// signal's params, so all of them participate
+    /// in config fingerprints. `min_tradable_bars` doesn't change results and stays out.
+    /// The risk profile's overrides are applied last.
     pub fn to_strategy_config(&self) -> StrategyConfig {
-        StrategyConfig {
-            signal: ComponentConfig {
-                component_type: self.signal.component_type.clone(),
-                params: self.signal.params.clone(),
-            },
-            position_manager: ComponentConfig {
-                component_type: self.position_manager.component_type.clone(),
-                params: self.position_manager.params.clone(),
-            },
+        let mut execution_params = self.execution_model.params.clone();
+        if let Some(commission) = &self.commission {
+            commission.model.write_params(&mut execution_params);
+            if commission.regulatory_fees {
+                RegulatoryFees::default().write_params(&mut execution_params);
+            }
+        }
+        if let Some(quantization) = &self.instrument {
+            quantization.write_params(&mut execution_params);
+        }
+        if let Some(limit_fill) = &self.limit_fill {
+            limit_fill.write_params(&mut execution_params);
+        }
+        if self.backtest.precise_accounting {
+            execution_params.insert(PARAM_PRECISE_ACCOUNTING.into(), 1.0);
+        }
+
+        let mut signal = self.signal.to_component_config();
+        if let Some(governance) = &self.entry_governance {
+            governance.write_params(&mut signal.params);
+        }
+        if let Some(warmup) = &self.warmup {
+            warmup.policy.write_params(&mut signal.params);
+        }
+
+        let mut config = StrategyConfig {
+            signal,
+            position_manager: self.position_manager.to_component_config(),
             execution_model: ComponentConfig {
-                component_type: self.execution_model.component_type.clone(),
-                params: self.execution_model.params.clone(),
-            },
-            signal_filter: ComponentConfig {
-                component_type: self.signal_filter.component_type.clone(),
-                params: self.signal_filter.params.clone(),
+                params: execution_params,
+                ..self.execution_model.to_component_config()
             },
+            signal_filter: self.signal_filter.to_component_config(),
+        };
+        if let Some(profile) = self.risk_profile {
+            profile.to_overrides().apply(&mut config);
         }
+        config
+    }
+
+    /// Pre-flight check without running anything.
+    ///
+    /// Checks date parsing and order, capital and sizing, cross-parameter
+    /// constraints, runs each of the four factory constructors (component
+    /// types and param ranges), and
+    /// warns when the indicator warmup doesn't fit the date range. With
+    /// `offline_cache`, also checks that the symbol and any style factor
+    /// symbols are cached. Collects every issue instead of stopping at the
+    /// first.
+    pub fn validate(&self, offline_cache: Option<&ParquetCache>) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        let section = &self.backtest;
+
+        let start = parse_date(&mut report, "backtest.start_date", &section.start_date);
+        let end = parse_date(&mut report, "backtest.end_date", &section.end_date);
+        let range = match (start, end) {
+            (Some(start), Some(end)) if start >= end => {
+                report.error(
+                    "backtest.end_date",
+                    format!("end date {end} is not after start date {start}"),
+                );
+                None
+            }
+            (Some(start), Some(end)) => Some((start, end)),
+            _ => None,
+        };
+        if !matches!(
+            section.trading_mode.as_str(),
+            "long_only" | "short_only" | "long_short"
+        ) {
+            report.warning(
+                "backtest.trading_mode",
+                format!(
+                    "unknown trading mode '{}', using long_only",
+                    section.trading_mode
+                ),
+            );
+        }
+        if !(section.initial_capital.is_finite() && section.initial_capital > 0.0) {
+            report.error(
+                "backtest.initial_capital",
+                format!("must be positive, got {}", section.initial_capital),
+            );
+        }
+        if !(section.position_size_pct.is_finite() && section.position_size_pct > 0.0) {
+            report.error(
+                "backtest.position_size_pct",
+                format!("must be positive, got {}", section.position_size_pct),
+            );
+        }
+
+        if let Some(Quantization::Contracts { multiplier }) = self.instrument {
+            if !(multiplier.is_finite() && multiplier > 0.0) {
+                report.error(
+                    "instrument.multiplier",
+                    format!("must be positive, got {multiplier}"),
+                );
+            }
+        }
+
+        if let Some(LimitFillPolicy::ProbabilisticOnTouch { p }) = self.limit_fill {
+            if !(0.0..=1.0).contains(&p) {
+                report.error("limit_fill.p", format!("must be between 0 and 1, got {p}"));
+            }
+        }
+
+        if let Some(consistency) = &self.consistency {
+            let tolerance = consistency.tolerance;
+            if !(tolerance.is_finite() && tolerance >= 0.0) {
+                report.error(
+                    "consistency.tolerance",
+                    format!("must be zero or more, got {tolerance}"),
+                );
+            }
+        }
+
+        if let Some(warmup) = &self.warmup {
+            let multiplier = warmup.policy.multiplier;
+            if !(multiplier.is_finite() && multiplier >= 1.0) {
+                report.error(
+                    "warmup.multiplier",
+                    format!("must be at least 1, got {multiplier}"),
+                );
+            }
+        }
+
+        let config = self.to_strategy_config();
+        let components_ok = check_strategy(&mut report, &config);
+        if components_ok {
+            // Warmup against the date range is checked below, with the
+            // range's own wording
+            for warning in check_compatibility(&config, &CompatibilityContext::default()).warnings {
+                report.warning("execution_model", warning);
+            }
+        }
+
+        if let (true, Some((start, end))) = (components_ok, range) {
+            let indicators = required_indicators(
+                &config.signal,
+                &config.signal_filter,
+                &config.position_manager,
+            );
+            let days = weekdays_between(start, end);
+            let warmup = WarmupPolicy::from_params(&config.signal.params)
+                .report(&indicators, 0, days)
+                .applied;
+            // Only an estimate: the run checks min_tradable_bars on the loaded bars
+            if warmup >= days {
+                report.warning(
+                    "backtest.start_date",
+                    format!("warmup of {warmup} bars exceeds the ~{days} trading days in range"),
+                );
+            } else if warmup * 2 > days {
+                report.warning(
+                    "backtest.start_date",
+                    format!(
+                        "warmup of {warmup} bars uses most of the ~{days} trading days in range"
+                    ),
+                );
+            }
+        }
+
+        if let Some(cache) = offline_cache {
+            let factors = self.style.iter().flat_map(|s| &s.factors);
+            let symbols =
+                std::iter::once(&section.symbol).chain(factors.filter(|f| !f.ends_with(".csv")));
+            for symbol in symbols {
+                if cache.get_meta(symbol).is_none() {
+                    report.error(
+                        "backtest.symbol",
+                        format!("'{symbol}' is not in the cache and offline mode cannot fetch it"),
+                    );
+                }
+            }
+        }
+
+        report
     }
 
     /// Parse the trading mode string.
@@ -99,6 +441,178 @@ impl BacktestConfig {
             _ => TradingMode::LongOnly,
         }
     }
+
+    /// The `[consistency]` section, or the build's default check.
+    pub fn consistency_check(&self) -> ConsistencyCheck {
+        self.consistency.unwrap_or_default()
+    }
+
+    /// Bars a run must have left after its warmup.
+    pub fn min_tradable_bars(&self) -> usize {
+        self.warmup
+            .as_ref()
+            .map_or(DEFAULT_MIN_TRADABLE_BARS, |w| w.min_tradable_bars)
+    }
+}
+
+pub(crate) fn parse_date(report: &mut ValidationReport, field: &str, value: &str) -> Option<NaiveDate> {
+    match NaiveDate::parse_from_str(value, DATE_FORMAT) {
+        Ok(date) => Some(date),
+        Err(err) => {
+            report.error(field, format!("'{value}' is not a YYYY-MM-DD date ({err})"));
+            None
+        }
+    }
+}
+
+/// Weekdays in `[start, end]`, an upper bound on the bars the range can hold.
+fn weekdays_between(start: NaiveDate, end: NaiveDate) -> usize {
+    start
+        .iter_days()
+        .take_while(|d| *d <= end)
+        .filter(|d| !matches!(d.weekday(), Weekday::Sat | Weekday::Sun))
+        .count()
+}
+
+/// How serious a [`ConfigIssue`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueSeverity {
+    /// The run would fail or be meaningless.
+    Error,
+    /// The run works but probably not as intended.
+    Warning,
+}
+
+/// One problem found by [`BacktestConfig::validate`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigIssue {
+    pub severity: IssueSeverity,
+    /// TOML path of the offending section or key, e.g. `backtest.end_date`.
+    pub field: String,
+    pub message: String,
+    /// Valid alternatives, for component errors (see [`FactoryError::hint`]).
+    pub hint: Option<String>,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// All issues found by [`BacktestConfig::validate`], in check order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    pub issues: Vec<ConfigIssue>,
+}
+
+impl ValidationReport {
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &ConfigIssue> {
+        self.issues
+            .iter()
+            .filter(|i| i.severity == IssueSeverity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &ConfigIssue> {
+        self.issues
+            .iter()
+            .filter(|i| i.severity == IssueSeverity::Warning)
+    }
+
+    /// `Err` with the whole report if any issue is an error.
+    pub fn into_result(self) -> Result<Self, ConfigError> {
+        if self.has_errors() {
+            Err(ConfigError::Invalid(self))
+        } else {
+            Ok(self)
+        }
+    }
+
+    fn push(&mut self, issue: ConfigIssue) {
+        self.issues.push(issue);
+    }
+
+    pub(crate) fn error(&mut self, field: &str, message: String) {
+        self.push(ConfigIssue {
+            severity: IssueSeverity::Error,
+            field: field.to_string(),
+            message,
+            hint: None,
+        });
+    }
+
+    fn warning(&mut self, field: &str, message: String) {
+        self.push(ConfigIssue {
+            severity: IssueSeverity::Warning,
+            field: field.to_string(),
+            message,
+            hint: None,
+        });
+    }
+}
+
+/// Errors only, joined with `; `.
+/// Check a strategy config on its own: no cross-parameter constraint is
+/// broken (see [`constraint_violations`]) and each component builds.
+///
+/// [`BacktestConfig::validate`] runs the same checks on its strategy.
+pub fn validate_strategy(config: &StrategyConfig) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    check_strategy(&mut report, config);
+    report
+}
+
+/// Report broken constraints, then component errors; true if neither.
+///
+/// Constructors assert on the constraints, so they only run once the
+/// constraints hold.
+fn check_strategy(report: &mut ValidationReport, config: &StrategyConfig) -> bool {
+    let violations = constraint_violations(config);
+    for violation in &violations {
+        report.error(violation.slot.name(), violation.message.clone());
+    }
+    if !violations.is_empty() {
+        return false;
+    }
+    let built = [
+        ("signal", create_signal(&config.signal).err()),
+        (
+            "position_manager",
+            create_pm(&config.position_manager).err(),
+        ),
+        (
+            "execution_model",
+            create_execution(&config.execution_model).err(),
+        ),
+        ("signal_filter", create_filter(&config.signal_filter).err()),
+    ];
+    let mut ok = true;
+    for (field, err) in built {
+        if let Some(err) = err {
+            ok = false;
+            report.push(ConfigIssue {
+                severity: IssueSeverity::Error,
+                field: field.to_string(),
+                message: err.to_string(),
+                hint: err.hint(),
+            });
+        }
+    }
+    ok
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let errors: Vec<String> = self.errors().map(|i| i.to_string()).collect();
+        match errors.len() {
+            1 => write!(f, "{}", errors[0]),
+            n => write!(f, "{n} errors: {}", errors.join("; ")),
+        }
+    }
 }
 
 /// Config loading errors.
@@ -108,11 +622,47 @@ pub enum ConfigError {
     Io(String),
     #[error("TOML parse error: {0}")]
     Parse(String),
+    #[error(transparent)]
+    Component(#[from] FactoryError),
+    /// A strategy preset name that does not exist; the message lists the valid ones.
+    #[error("{0}")]
+    UnknownPreset(String),
+    /// Pre-flight validation found errors; the report includes warnings too.
+    #[error("{0}")]
+    Invalid(ValidationReport),
+    /// Too few bars remain after the warmup for the run to mean anything.
+    #[error(
+        "only {tradable_bars} of {bar_count} bars remain after a {warmup}-bar warmup \
+         (minimum {minimum})"
+    )]
+    InsufficientBars {
+        bar_count: usize,
+        warmup: usize,
+        tradable_bars: usize,
+        minimum: usize,
+    },
+}
+
+impl ConfigError {
+    /// Valid alternatives for a component error (see [`FactoryError::hint`]).
+    ///
+    /// For a validation report, the hint of the first error that has one.
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            Self::Component(err) => err.hint(),
+            Self::Invalid(report) => report.errors().find_map(|i| i.hint.clone()),
+            Self::InsufficientBars { .. } => Some(
+                "extend the date range, or lower [warmup] min_tradable_bars or multiplier".into(),
+            ),
+            Self::Io(_) | Self::Parse(_) | Self::UnknownPreset(_) => None,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use trendlab_core::engine::RiskGuard;
 
     const FULL_TOML: &str = r#"
 [backtest]
@@ -214,6 +764,204 @@ type = "next_bar_open"
         assert_eq!(config.backtest.position_size_pct, 1.0);
     }
 
+    #[test]
+    fn bps_only_config_has_no_commission_params() {
+        let config = BacktestConfig::from_toml(FULL_TOML).unwrap();
+        assert!(config.commission.is_none());
+        let sc = config.to_strategy_config();
+        assert!(sc.execution_model.params.is_empty());
+    }
+
+    #[test]
+    fn per_share_commission_section() {
+        let toml = format!(
+            "{FULL_TOML}\n[commission]\nmodel = \"per_share\"\nrate = 0.005\nmin = 1\nregulatory_fees = true\n"
+        );
+        let config = BacktestConfig::from_toml(&toml).unwrap();
+        let section = config.commission.as_ref().unwrap();
+        assert_eq!(
+            section.model,
+            CommissionModel::PerShare {
+                rate: 0.005,
+                min: 1.0
+            }
+        );
+        assert!(section.regulatory_fees);
+
+        let sc = config.to_strategy_config();
+        let params = &sc.execution_model.params;
+        assert_eq!(
+            CommissionModel::from_params(params),
+            Some(section.model.clone())
+        );
+        assert_eq!(
+            RegulatoryFees::from_params(params),
+            Some(RegulatoryFees::default())
+        );
+
+        // Commission settings change the fingerprint
+        let plain = BacktestConfig::from_toml(FULL_TOML)
+            .unwrap()
+            .to_strategy_config();
+        assert_ne!(sc.full_hash(), plain.full_hash());
+    }
+
+    #[test]
+    fn instrument_section_enters_the_fingerprint() {
+        let toml =
+            format!("{FULL_TOML}\n[instrument]\nquantization = \"contracts\"\nmultiplier = 50\n");
+        let config = BacktestConfig::from_toml(&toml).unwrap();
+        let rule = Quantization::Contracts { multiplier: 50.0 };
+        assert_eq!(config.instrument, Some(rule));
+        let report = config.validate(None);
+        assert!(report.errors().all(|e| e.field != "instrument.multiplier"));
+
+        let sc = config.to_strategy_config();
+        assert_eq!(
+            Quantization::from_params(&sc.execution_model.params),
+            Some(rule)
+        );
+        let plain = BacktestConfig::from_toml(FULL_TOML)
+            .unwrap()
+            .to_strategy_config();
+        assert_ne!(sc.full_hash(), plain.full_hash());
+
+        let fractional = format!("{FULL_TOML}\n[instrument]\nquantization = \"fractional\"\n");
+        let sc = BacktestConfig::from_toml(&fractional)
+            .unwrap()
+            .to_strategy_config();
+        assert_eq!(
+            Quantization::from_params(&sc.execution_model.params),
+            Some(Quantization::Fractional)
+        );
+    }
+
+    #[test]
+    fn limit_fill_section_enters_the_fingerprint() {
+        let toml =
+            format!("{FULL_TOML}\n[limit_fill]\npolicy = \"require_penetration\"\nticks = 2\n");
+        let config = BacktestConfig::from_toml(&toml).unwrap();
+        let policy = LimitFillPolicy::RequirePenetration { ticks: 2 };
+        assert_eq!(config.limit_fill, Some(policy));
+
+        let sc = config.to_strategy_config();
+        assert_eq!(
+            LimitFillPolicy::from_params(&sc.execution_model.params),
+            Some(policy)
+        );
+        let plain = BacktestConfig::from_toml(FULL_TOML)
+            .unwrap()
+            .to_strategy_config();
+        assert_ne!(sc.full_hash(), plain.full_hash());
+
+        let toml =
+            format!("{FULL_TOML}\n[limit_fill]\npolicy = \"probabilistic_on_touch\"\np = 1.5\n");
+        let report = BacktestConfig::from_toml(&toml).unwrap().validate(None);
+        assert!(report.errors().any(|e| e.field == "limit_fill.p"));
+    }
+
+    #[test]
+    fn precise_accounting_enters_the_fingerprint() {
+        let plain = BacktestConfig::from_toml(FULL_TOML).unwrap();
+        assert!(!plain.backtest.precise_accounting);
+        let plain = plain.to_strategy_config();
+        assert!(!plain
+            .execution_model
+            .params
+            .contains_key(PARAM_PRECISE_ACCOUNTING));
+
+        let toml = FULL_TOML.replace(
+            "position_size_pct = 0.5",
+            "position_size_pct = 0.5\nprecise_accounting = true",
+        );
+        let sc = BacktestConfig::from_toml(&toml)
+            .unwrap()
+            .to_strategy_config();
+        assert_eq!(
+            sc.execution_model.params.get(PARAM_PRECISE_ACCOUNTING),
+            Some(&1.0)
+        );
+        assert_ne!(sc.full_hash(), plain.full_hash());
+    }
+
+    #[test]
+    fn entry_governance_section() {
+        let toml = format!(
+            "{FULL_TOML}\n[entry_governance]\ncooldown_bars_after_exit = 5\nrequire_signal_reset = true\n"
+        );
+        let config = BacktestConfig::from_toml(&toml).unwrap();
+        let governance = config.entry_governance.unwrap();
+        assert_eq!(governance.cooldown_bars_after_exit, 5);
+        assert!(governance.max_entries_per_window.is_none());
+        assert!(governance.require_signal_reset);
+
+        let sc = config.to_strategy_config();
+        assert_eq!(EntryGovernance::from_params(&sc.signal.params), governance);
+        assert_eq!(sc.signal.params["entry_lookback"], 50.0);
+
+        // Governance changes the fingerprint
+        let plain = BacktestConfig::from_toml(FULL_TOML)
+            .unwrap()
+            .to_strategy_config();
+        assert_ne!(sc.full_hash(), plain.full_hash());
+    }
+
+    #[test]
+    fn risk_profile_key_applies_overrides() {
+        let toml = format!("risk_profile = \"Conservative\"\n{FULL_TOML}");
+        let config = BacktestConfig::from_toml(&toml).unwrap();
+        assert_eq!(config.risk_profile, Some(RiskProfile::Conservative));
+
+        let sc = config.to_strategy_config();
+        let overrides = RiskProfile::Conservative.to_overrides();
+        assert_eq!(RiskGuard::from_params(&sc.signal.params), overrides.guard);
+        assert_eq!(Some(sc.signal_filter), overrides.required_filter);
+    }
+
+    #[test]
+    fn tiered_commission_section() {
+        let toml = format!(
+            "{FULL_TOML}\n[commission]\nmodel = \"tiered\"\nschedule = [{{ above = 0, rate = 0.0035 }}, {{ above = 300000, rate = 0.002 }}]\n"
+        );
+        let config = BacktestConfig::from_toml(&toml).unwrap();
+        let section = config.commission.unwrap();
+        match section.model {
+            CommissionModel::Tiered { schedule } => {
+                assert_eq!(schedule.len(), 2);
+                assert_eq!(schedule[1].above, 300_000.0);
+            }
+            other => panic!("expected Tiered, got {other:?}"),
+        }
+        assert!(!section.regulatory_fees);
+    }
+
+    #[test]
+    fn composite_position_manager_section() {
+        let toml = MINIMAL_TOML.replace(
+            "[position_manager]\ntype = \"atr_trailing\"\nparams = { atr_period = 14.0 }\n",
+            r#"[position_manager]
+type = "composite"
+
+[[position_manager.children]]
+type = "fixed_stop_loss"
+params = { stop_pct = 0.05 }
+
+[[position_manager.children]]
+type = "atr_trailing"
+params = { atr_period = 10.0 }
+"#,
+        );
+        let config = BacktestConfig::from_toml(&toml).unwrap();
+        let strategy = config.to_strategy_config();
+        let pm = &strategy.position_manager;
+        assert_eq!(pm.component_type, "composite");
+        assert_eq!(pm.children.len(), 2);
+        assert_eq!(pm.children[0].component_type, "fixed_stop_loss");
+        assert_eq!(pm.children[1].params["atr_period"], 10.0);
+        assert!(pm.children.iter().all(|c| c.children.is_empty()));
+        assert!(strategy.signal.children.is_empty());
+    }
+
     #[test]
     fn trading_mode_parsing() {
         // long_only
@@ -247,4 +995,123 @@ type = "next_bar_open"
         let msg = err.to_string();
         assert!(msg.contains("TOML parse error"));
     }
+
+    #[test]
+    fn validate_surfaces_factory_details() {
+        let report = BacktestConfig::from_toml(MINIMAL_TOML)
+            .unwrap()
+            .validate(None);
+        assert!(report.issues.is_empty(), "{:?}", report.issues);
+
+        // FULL_TOML has an unknown signal param and an unknown filter type;
+        // both are reported.
+        let config = BacktestConfig::from_toml(FULL_TOML).unwrap();
+        let report = config.validate(None);
+        let errors: Vec<&ConfigIssue> = report.errors().collect();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].field, "signal");
+        assert!(errors[0]
+            .message
+            .starts_with("Unknown parameter 'exit_lookback' for signal donchian_breakout"));
+        assert!(errors[0]
+            .hint
+            .as_ref()
+            .unwrap()
+            .contains("entry_lookback (10..=200, default 50)"));
+        assert_eq!(errors[1].field, "signal_filter");
+        assert!(errors[1]
+            .message
+            .starts_with("Unknown filter type: sma_regime"));
+        assert!(errors[1].hint.as_ref().unwrap().contains("\n  ma_regime"));
+
+        let err = report.into_result().unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("2 errors: signal: Unknown parameter"));
+        assert!(err.hint().unwrap().contains("entry_lookback"));
+    }
+
+    #[test]
+    fn validate_strategy_reports_constraints_before_building() {
+        let mut config = BacktestConfig::from_toml(MINIMAL_TOML)
+            .unwrap()
+            .to_strategy_config();
+        assert!(validate_strategy(&config).issues.is_empty());
+
+        // The constructor would assert on this
+        config.signal = ComponentConfig {
+            component_type: "ma_crossover".into(),
+            params: [("fast_period".into(), 50.0), ("slow_period".into(), 20.0)].into(),
+            children: Vec::new(),
+        };
+        let report = validate_strategy(&config);
+        assert_eq!(
+            report.to_string(),
+            "signal: ma_crossover slow_period must be greater than fast_period \
+             (fast 50, slow 20)"
+        );
+    }
+
+    #[test]
+    fn validate_collects_backtest_section_issues() {
+        let toml = MINIMAL_TOML.replace("2020-01-01", "2024-13-01").replace(
+            "symbol = \"SPY\"",
+            "symbol = \"SPY\"\ntrading_mode = \"both\"\ninitial_capital = 0.0",
+        );
+        let report = BacktestConfig::from_toml(&toml).unwrap().validate(None);
+        let fields: Vec<(&str, IssueSeverity)> = report
+            .issues
+            .iter()
+            .map(|i| (i.field.as_str(), i.severity))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("backtest.start_date", IssueSeverity::Error),
+                ("backtest.trading_mode", IssueSeverity::Warning),
+                ("backtest.initial_capital", IssueSeverity::Error),
+            ]
+        );
+
+        let toml = MINIMAL_TOML.replace("2023-12-31", "2019-06-30");
+        let report = BacktestConfig::from_toml(&toml).unwrap().validate(None);
+        assert_eq!(
+            report.to_string(),
+            "backtest.end_date: end date 2019-06-30 is not after start date 2020-01-01"
+        );
+    }
+
+    #[test]
+    fn validate_checks_warmup_against_range() {
+        // 50-bar Donchian in a ~3 week range
+        let toml = MINIMAL_TOML.replace("2023-12-31", "2020-01-21");
+        let report = BacktestConfig::from_toml(&toml).unwrap().validate(None);
+        assert!(!report.has_errors());
+        let warnings: Vec<&ConfigIssue> = report.warnings().collect();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0]
+            .message
+            .starts_with("warmup of 49 bars exceeds the ~15 trading days in range"));
+
+        // ~4 months: runs, but most of it is warmup
+        let toml = MINIMAL_TOML.replace("2023-12-31", "2020-04-30");
+        let report = BacktestConfig::from_toml(&toml).unwrap().validate(None);
+        assert!(!report.has_errors());
+        assert!(report.issues[0].message.contains("uses most of"));
+    }
+
+    #[test]
+    fn validate_checks_symbols_in_offline_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ParquetCache::new(dir.path());
+        let toml = format!("{MINIMAL_TOML}\n[style]\nfactors = [\"TLT\", \"data/momentum.csv\"]\n");
+        let config = BacktestConfig::from_toml(&toml).unwrap();
+
+        assert!(!config.validate(None).has_errors());
+        let report = config.validate(Some(&cache));
+        let messages: Vec<&str> = report.errors().map(|i| i.message.as_str()).collect();
+        assert_eq!(messages.len(), 2);
+        assert!(messages[0].starts_with("'SPY' is not in the cache"));
+        assert!(messages[1].starts_with("'TLT' is not in the cache"));
+    }
 }
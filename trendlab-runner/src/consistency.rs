@@ -0,0 +1,596 @@
+//! Post-run consistency check — do the trades, fills and equity curve agree?
+//!
+//! Three invariants hold for every engine run:
+//!
+//! - **Equity reconciles.** Gross PnL of the closed trades, plus the
+//!   mark-to-market of positions still open at the end (their end-of-data
+//!   trades), minus the commission and slippage of every fill, equals the
+//!   change in equity over the run.
+//! - **Fills map to legs.** Every fill belongs to the entry or exit leg of
+//!   exactly one trade, and every leg except an end-of-data exit has fills
+//!   adding up to the trade's quantity and price.
+//! - **Trades don't overlap.** Two trades on the same symbol never hold at
+//!   the same time; a reversal may exit and enter on the same bar.
+//!
+//! The runner checks them after the engine returns (see
+//! [`ConsistencyCheck::apply`]). Debug builds check every run; release
+//! builds only check when the config asks for it with a `[consistency]`
+//! section.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use trendlab_core::domain::{Fill, OrderSide, PositionSide, TradeRecord};
+
+use crate::runner::RunError;
+
+/// Default equity tolerance, as a fraction of initial capital.
+pub const DEFAULT_TOLERANCE: f64 = 1e-6;
+
+/// What a failed check does to the run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsistencyMode {
+    /// Skip the check.
+    Off,
+    /// Fail the run with [`RunError::InconsistentResult`].
+    Strict,
+    /// Keep the run and record each violation in
+    /// `BacktestResult::consistency_warnings`.
+    Lenient,
+}
+
+/// The `[consistency]` config section.
+///
+/// ```toml
+/// [consistency]
+/// mode = "lenient"     # strict (default) | lenient | off
+/// tolerance = 1e-6     # equity tolerance as a fraction of initial capital
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ConsistencyCheck {
+    #[serde(default = "default_mode")]
+    pub mode: ConsistencyMode,
+    #[serde(default = "default_tolerance")]
+    pub tolerance: f64,
+}
+
+fn default_mode() -> ConsistencyMode {
+    ConsistencyMode::Strict
+}
+
+fn default_tolerance() -> f64 {
+    DEFAULT_TOLERANCE
+}
+
+impl Default for ConsistencyCheck {
+    /// Strict in debug builds, off in release builds.
+    fn default() -> Self {
+        Self {
+            mode: if cfg!(debug_assertions) {
+                ConsistencyMode::Strict
+            } else {
+                ConsistencyMode::Off
+            },
+            tolerance: DEFAULT_TOLERANCE,
+        }
+    }
+}
+
+impl ConsistencyCheck {
+    pub fn strict() -> Self {
+        Self {
+            mode: ConsistencyMode::Strict,
+            tolerance: DEFAULT_TOLERANCE,
+        }
+    }
+
+    pub fn lenient() -> Self {
+        Self {
+            mode: ConsistencyMode::Lenient,
+            tolerance: DEFAULT_TOLERANCE,
+        }
+    }
+
+    /// Check a run's output under this mode.
+    ///
+    /// Returns the violations to record as warnings: always empty unless the
+    /// mode is lenient. Strict mode turns any violation into
+    /// [`RunError::InconsistentResult`].
+    pub fn apply(
+        &self,
+        symbol: &str,
+        initial_capital: f64,
+        equity_curve: &[f64],
+        fills: &[Fill],
+        trades: &[TradeRecord],
+    ) -> Result<Vec<Inconsistency>, RunError> {
+        if self.mode == ConsistencyMode::Off {
+            return Ok(Vec::new());
+        }
+        let found = check_consistency(initial_capital, equity_curve, fills, trades, self.tolerance);
+        match self.mode {
+            ConsistencyMode::Strict if !found.is_empty() => Err(RunError::InconsistentResult {
+                symbol: symbol.to_string(),
+                details: found.iter().map(ToString::to_string).collect(),
+            }),
+            ConsistencyMode::Lenient => Ok(found),
+            _ => Ok(Vec::new()),
+        }
+    }
+}
+
+/// Which end of a trade a fill belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeLeg {
+    Entry,
+    Exit,
+}
+
+impl fmt::Display for TradeLeg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Entry => "entry",
+            Self::Exit => "exit",
+        })
+    }
+}
+
+/// One broken invariant.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Inconsistency {
+    /// Trade PnL and costs don't add up to the equity change.
+    EquityMismatch {
+        /// Closed gross PnL + open mark-to-market - fill costs.
+        expected: f64,
+        /// Final equity minus initial capital.
+        actual: f64,
+    },
+    /// A fill that is no trade's entry or exit.
+    UnmatchedFill {
+        symbol: String,
+        bar_index: usize,
+        side: OrderSide,
+        quantity: f64,
+    },
+    /// A trade leg whose fills are missing or don't add up to it.
+    MissingFill {
+        symbol: String,
+        bar_index: usize,
+        leg: TradeLeg,
+    },
+    /// A trade entered before the previous trade on the symbol exited.
+    OverlappingTrades {
+        symbol: String,
+        exit_bar: usize,
+        next_entry_bar: usize,
+    },
+}
+
+impl fmt::Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EquityMismatch { expected, actual } => write!(
+                f,
+                "equity changed by {actual:.4} but trades and costs account for {expected:.4}"
+            ),
+            Self::UnmatchedFill {
+                symbol,
+                bar_index,
+                side,
+                quantity,
+            } => write!(
+                f,
+                "{side:?} fill of {quantity} {symbol} at bar {bar_index} belongs to no trade"
+            ),
+            Self::MissingFill {
+                symbol,
+                bar_index,
+                leg,
+            } => write!(f, "{symbol} trade {leg} at bar {bar_index} has no matching fills"),
+            Self::OverlappingTrades {
+                symbol,
+                exit_bar,
+                next_entry_bar,
+            } => write!(
+                f,
+                "{symbol} trade entered at bar {next_entry_bar} before the previous one exited at bar {exit_bar}"
+            ),
+        }
+    }
+}
+
+/// Check the three invariants, returning every violation found.
+///
+/// `tolerance` is a fraction of `initial_capital`. Fills and trades may
+/// cover several symbols.
+pub fn check_consistency(
+    initial_capital: f64,
+    equity_curve: &[f64],
+    fills: &[Fill],
+    trades: &[TradeRecord],
+    tolerance: f64,
+) -> Vec<Inconsistency> {
+    let mut found = Vec::new();
+
+    let pnl: f64 = trades.iter().map(|t| t.gross_pnl).sum();
+    let costs: f64 = fills.iter().map(|f| f.commission + f.slippage).sum();
+    let expected = pnl - costs;
+    let actual = equity_curve.last().copied().unwrap_or(initial_capital) - initial_capital;
+    if (expected - actual).abs() > tolerance * initial_capital.abs().max(1.0) {
+        found.push(Inconsistency::EquityMismatch { expected, actual });
+    }
+
+    found.extend(match_legs(fills, trades));
+    found.extend(overlaps(trades));
+    found
+}
+
+/// Claim each trade leg's fills, reporting fills and legs left over.
+///
+/// A leg is one or more same-side fills on the trade's symbol between its
+/// entry and exit bars: several when the liquidity cap split an order over
+/// bars. Entry legs start on the entry bar and closing exit legs end on the
+/// exit bar; both add up to the trade's quantity at its price.
+fn match_legs(fills: &[Fill], trades: &[TradeRecord]) -> Vec<Inconsistency> {
+    let mut queues: HashMap<(&str, bool), VecDeque<&Fill>> = HashMap::new();
+    for fill in fills {
+        let buy = fill.side == OrderSide::Buy;
+        queues
+            .entry((fill.symbol.as_str(), buy))
+            .or_default()
+            .push_back(fill);
+    }
+
+    // Trades on a symbol never overlap, so entry order is fill order
+    let mut ordered: Vec<&TradeRecord> = trades.iter().collect();
+    ordered.sort_by_key(|t| (t.entry_bar, t.exit_bar));
+
+    let mut found = Vec::new();
+    let mut stray = Vec::new();
+    for trade in ordered {
+        let entry_buy = match trade.side {
+            PositionSide::Long => true,
+            PositionSide::Short => false,
+            PositionSide::Flat => continue,
+        };
+        let mut claim = |buy: bool| {
+            let queue = queues.get_mut(&(trade.symbol.as_str(), buy));
+            claim_leg(
+                queue,
+                trade.entry_bar,
+                trade.exit_bar,
+                trade.quantity,
+                &mut stray,
+            )
+        };
+
+        let entry = claim(entry_buy);
+        let entry_ok = entry.first().map(|f| f.bar_index) == Some(trade.entry_bar)
+            && fills_leg(&entry, trade.quantity, trade.entry_price);
+        let exit = claim(!entry_buy);
+        let exit_ok = trade.liquidated_at_end
+            || (exit.last().map(|f| f.bar_index) == Some(trade.exit_bar)
+                && fills_leg(&exit, trade.quantity, trade.exit_price));
+
+        for (ok, leg, bar_index) in [
+            (entry_ok, TradeLeg::Entry, trade.entry_bar),
+            (exit_ok, TradeLeg::Exit, trade.exit_bar),
+        ] {
+            if !ok {
+                found.push(Inconsistency::MissingFill {
+                    symbol: trade.symbol.clone(),
+                    bar_index,
+                    leg,
+                });
+            }
+        }
+    }
+
+    stray.extend(queues.into_values().flatten());
+    stray.sort_by(|a, b| (a.bar_index, &a.symbol).cmp(&(b.bar_index, &b.symbol)));
+    found.extend(stray.into_iter().map(|f| Inconsistency::UnmatchedFill {
+        symbol: f.symbol.clone(),
+        bar_index: f.bar_index,
+        side: f.side,
+        quantity: f.quantity,
+    }));
+    found
+}
+
+/// Take fills from bars `from..=to` off the front of `queue` until they add
+/// up to `quantity`. Fills before `from` belong to no later trade and go
+/// to `stray`.
+fn claim_leg<'a>(
+    queue: Option<&mut VecDeque<&'a Fill>>,
+    from: usize,
+    to: usize,
+    quantity: f64,
+    stray: &mut Vec<&'a Fill>,
+) -> Vec<&'a Fill> {
+    let Some(queue) = queue else {
+        return Vec::new();
+    };
+    while let Some(fill) = queue.pop_front() {
+        if fill.bar_index >= from {
+            queue.push_front(fill);
+            break;
+        }
+        stray.push(fill);
+    }
+
+    let mut claimed = Vec::new();
+    let mut filled = 0.0;
+    while filled < quantity && !same(filled, quantity) {
+        match queue.front() {
+            Some(fill) if fill.bar_index <= to => {
+                filled += fill.quantity;
+                claimed.extend(queue.pop_front());
+            }
+            _ => break,
+        }
+    }
+    claimed
+}
+
+/// Whether `leg` adds up to `quantity` at an average of `price`.
+fn fills_leg(leg: &[&Fill], quantity: f64, price: f64) -> bool {
+    let filled: f64 = leg.iter().map(|f| f.quantity).sum();
+    let value: f64 = leg.iter().map(|f| f.price * f.quantity).sum();
+    !leg.is_empty() && same(filled, quantity) && same(value / filled, price)
+}
+
+fn same(a: f64, b: f64) -> bool {
+    (a - b).abs() <= 1e-9 * a.abs().max(b.abs()).max(1.0)
+}
+
+/// Trades on the same symbol whose holding periods overlap.
+fn overlaps(trades: &[TradeRecord]) -> Vec<Inconsistency> {
+    let mut by_symbol: HashMap<&str, Vec<&TradeRecord>> = HashMap::new();
+    for trade in trades {
+        by_symbol.entry(&trade.symbol).or_default().push(trade);
+    }
+    let mut symbols: Vec<&str> = by_symbol.keys().copied().collect();
+    symbols.sort_unstable();
+
+    let mut found = Vec::new();
+    for symbol in symbols {
+        let held = by_symbol.get_mut(symbol).unwrap();
+        held.sort_by_key(|t| (t.entry_bar, t.exit_bar));
+        for pair in held.windows(2) {
+            if pair[1].entry_bar < pair[0].exit_bar {
+                found.push(Inconsistency::OverlappingTrades {
+                    symbol: symbol.to_string(),
+                    exit_bar: pair[0].exit_bar,
+                    next_entry_bar: pair[1].entry_bar,
+                });
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use trendlab_core::domain::{FillPhase, OrderId};
+
+    fn date(bar: usize) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, 1).unwrap() + chrono::Duration::days(bar as i64)
+    }
+
+    fn fill(bar: usize, side: OrderSide, price: f64, quantity: f64) -> Fill {
+        Fill {
+            order_id: OrderId(bar as u64),
+            bar_index: bar,
+            date: date(bar),
+            symbol: "SPY".into(),
+            side,
+            price,
+            quantity,
+            commission: 1.0,
+            slippage: 0.5,
+            phase: FillPhase::StartOfBar,
+            trigger_margin: None,
+            decision_source: None,
+        }
+    }
+
+    fn trade(entry: &Fill, exit_bar: usize, exit_price: f64, liquidated: bool) -> TradeRecord {
+        let side = match entry.side {
+            OrderSide::Buy => PositionSide::Long,
+            OrderSide::Sell => PositionSide::Short,
+        };
+        let sign = if side == PositionSide::Long {
+            1.0
+        } else {
+            -1.0
+        };
+        let gross_pnl = sign * (exit_price - entry.price) * entry.quantity;
+        let costs = if liquidated { 1.5 } else { 3.0 };
+        TradeRecord {
+            symbol: entry.symbol.clone(),
+            side,
+            entry_bar: entry.bar_index,
+            entry_date: entry.date,
+            entry_price: entry.price,
+            entry_trigger_margin: None,
+            exit_bar,
+            exit_date: date(exit_bar),
+            exit_price,
+            quantity: entry.quantity,
+            gross_pnl,
+            commission: costs * 2.0 / 3.0,
+            slippage: costs / 3.0,
+            net_pnl: gross_pnl - costs,
+            bars_held: exit_bar - entry.bar_index,
+            mae: 0.0,
+            mfe: 0.0,
+            initial_stop_price: None,
+            entry_source: None,
+            exit_source: None,
+            liquidated_at_end: liquidated,
+            expiry_bar: None,
+            signal_id: None,
+            signal_type: None,
+            pm_type: None,
+            execution_model: None,
+            filter_type: None,
+        }
+    }
+
+    /// A long from bar 1 to 4, a reversal into a short closed at bar 7, and
+    /// a long still open at bar 9, with the equity curve they imply.
+    fn consistent_run() -> (Vec<f64>, Vec<Fill>, Vec<TradeRecord>) {
+        let fills = vec![
+            fill(1, OrderSide::Buy, 100.0, 10.0),
+            fill(4, OrderSide::Sell, 110.0, 10.0),
+            fill(4, OrderSide::Sell, 110.0, 10.0),
+            fill(7, OrderSide::Buy, 105.0, 10.0),
+            fill(8, OrderSide::Buy, 104.0, 10.0),
+        ];
+        let trades = vec![
+            trade(&fills[0], 4, 110.0, false),
+            trade(&fills[2], 7, 105.0, false),
+            trade(&fills[4], 9, 108.0, true),
+        ];
+        // +100 long, +50 short, +40 open mark, minus 5 fills at 1.5 each
+        let equity = vec![10_000.0, 10_000.0, 10_100.0, 10_182.5];
+        (equity, fills, trades)
+    }
+
+    fn check(equity: &[f64], fills: &[Fill], trades: &[TradeRecord]) -> Vec<Inconsistency> {
+        check_consistency(10_000.0, equity, fills, trades, DEFAULT_TOLERANCE)
+    }
+
+    #[test]
+    fn consistent_run_passes() {
+        let (equity, fills, trades) = consistent_run();
+        assert_eq!(check(&equity, &fills, &trades), vec![]);
+    }
+
+    #[test]
+    fn equity_drift_is_caught() {
+        let (mut equity, fills, trades) = consistent_run();
+        *equity.last_mut().unwrap() += 1.0;
+        assert!(matches!(
+            check(&equity, &fills, &trades)[..],
+            [Inconsistency::EquityMismatch { expected, actual }]
+                if (expected - 182.5).abs() < 1e-9 && (actual - 183.5).abs() < 1e-9
+        ));
+
+        // Within tolerance of 1e-6 of capital (0.01)
+        *equity.last_mut().unwrap() -= 0.995;
+        assert_eq!(check(&equity, &fills, &trades), vec![]);
+
+        // A trade whose PnL was misstated is caught the same way
+        let (equity, fills, mut trades) = consistent_run();
+        trades[1].gross_pnl += 25.0;
+        assert!(matches!(
+            check(&equity, &fills, &trades)[..],
+            [Inconsistency::EquityMismatch { .. }]
+        ));
+    }
+
+    #[test]
+    fn fills_without_legs_and_legs_without_fills_are_caught() {
+        // A stray fill: its costs also unbalance the equity
+        let (equity, mut fills, trades) = consistent_run();
+        fills.push(fill(5, OrderSide::Buy, 107.0, 3.0));
+        let found = check(&equity, &fills, &trades);
+        assert_eq!(found.len(), 2);
+        assert!(matches!(found[0], Inconsistency::EquityMismatch { .. }));
+        assert_eq!(
+            found[1],
+            Inconsistency::UnmatchedFill {
+                symbol: "SPY".into(),
+                bar_index: 5,
+                side: OrderSide::Buy,
+                quantity: 3.0,
+            }
+        );
+
+        // The short's exit fill moved a bar early: the leg no longer ends on
+        // its exit bar
+        let (equity, mut fills, trades) = consistent_run();
+        fills[3].bar_index = 6;
+        assert_eq!(
+            check(&equity, &fills, &trades),
+            vec![Inconsistency::MissingFill {
+                symbol: "SPY".into(),
+                bar_index: 7,
+                leg: TradeLeg::Exit,
+            }]
+        );
+
+        // A partial exit fill: the leg falls short of the trade's quantity
+        let (equity, mut fills, trades) = consistent_run();
+        fills[1].quantity = 6.0;
+        assert!(
+            check(&equity, &fills, &trades).contains(&Inconsistency::MissingFill {
+                symbol: "SPY".into(),
+                bar_index: 4,
+                leg: TradeLeg::Exit,
+            })
+        );
+
+        // One fill claimed as two trades' entry: the second finds none
+        let (equity, fills, mut trades) = consistent_run();
+        let mut twin = trades[0].clone();
+        twin.gross_pnl = 0.0;
+        twin.exit_bar = 1;
+        twin.liquidated_at_end = true;
+        trades.insert(1, twin);
+        let found = check(&equity, &fills, &trades);
+        assert!(found.contains(&Inconsistency::MissingFill {
+            symbol: "SPY".into(),
+            bar_index: 1,
+            leg: TradeLeg::Entry,
+        }));
+    }
+
+    #[test]
+    fn overlapping_trades_are_caught() {
+        let (equity, fills, mut trades) = consistent_run();
+        // Bar 4 is a same-bar reversal and fine; an exit at bar 5 is not
+        trades[0].exit_bar = 5;
+        let found = check(&equity, &fills, &trades);
+        assert!(found.contains(&Inconsistency::OverlappingTrades {
+            symbol: "SPY".into(),
+            exit_bar: 5,
+            next_entry_bar: 4,
+        }));
+    }
+
+    #[test]
+    fn modes_decide_between_error_and_warnings() {
+        let (mut equity, fills, trades) = consistent_run();
+        *equity.last_mut().unwrap() += 50.0;
+
+        let err = ConsistencyCheck::strict()
+            .apply("SPY", 10_000.0, &equity, &fills, &trades)
+            .unwrap_err();
+        assert!(matches!(
+            &err,
+            RunError::InconsistentResult { symbol, details } if symbol == "SPY" && details.len() == 1
+        ));
+        assert!(err.to_string().contains("equity changed by 232.5000"));
+
+        let warnings = ConsistencyCheck::lenient()
+            .apply("SPY", 10_000.0, &equity, &fills, &trades)
+            .unwrap();
+        assert_eq!(warnings.len(), 1);
+
+        let off = ConsistencyCheck {
+            mode: ConsistencyMode::Off,
+            tolerance: DEFAULT_TOLERANCE,
+        };
+        assert!(off
+            .apply("SPY", 10_000.0, &equity, &fills, &trades)
+            .unwrap()
+            .is_empty());
+    }
+}
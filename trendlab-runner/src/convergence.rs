@@ -0,0 +1,332 @@
+//! YOLO convergence tracking — best fitness over time and plateau detection.
+//!
+//! A long session either keeps finding better configs or settles on a
+//! plateau. The tracker follows per-symbol best fitness iteration by
+//! iteration, samples it into a convergence series, and flags a plateau once
+//! no symbol's best has improved for `plateau_window` iterations. What happens
+//! then is up to [`PlateauAction`]: report it, stop the session, or push
+//! `structural_explore` up to search further afield.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+/// Default iterations between convergence samples.
+pub const DEFAULT_SAMPLE_EVERY: usize = 100;
+/// Default trailing window for the new-entry rate.
+pub const DEFAULT_RATE_WINDOW: usize = 100;
+/// Default plateau window. The default action only reports the plateau.
+pub const DEFAULT_PLATEAU_WINDOW: usize = 1_000;
+
+/// What to do when the session plateaus.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PlateauAction {
+    /// Only report the plateau in progress updates.
+    Report,
+    /// End the session.
+    Stop,
+    /// Raise `structural_explore` by `step` (clamped to 1.0) and keep going.
+    BoostExplore { step: f64 },
+}
+
+/// Convergence tracking settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConvergenceConfig {
+    /// Iterations between convergence samples (and history checkpoints).
+    pub sample_every: usize,
+    /// Trailing window, in iterations, for the new-entry rate.
+    pub rate_window: usize,
+    /// Iterations without a best-fitness improvement that count as a plateau.
+    /// `None` disables plateau detection.
+    pub plateau_window: Option<usize>,
+    pub on_plateau: PlateauAction,
+}
+
+impl Default for ConvergenceConfig {
+    fn default() -> Self {
+        Self {
+            sample_every: DEFAULT_SAMPLE_EVERY,
+            rate_window: DEFAULT_RATE_WINDOW,
+            plateau_window: Some(DEFAULT_PLATEAU_WINDOW),
+            on_plateau: PlateauAction::Report,
+        }
+    }
+}
+
+/// One sample of the convergence series.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConvergencePoint {
+    pub iteration: usize,
+    /// Best fitness so far per symbol (symbols with no valid result omitted).
+    pub best_fitness: BTreeMap<String, f64>,
+    /// Fraction of trailing-window iterations that added a leaderboard entry.
+    pub new_entry_rate: f64,
+}
+
+/// Current convergence status, carried in progress updates.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConvergenceState {
+    /// Iteration of the most recent best-fitness improvement.
+    pub last_improvement: Option<usize>,
+    pub iterations_since_improvement: usize,
+    pub new_entry_rate: f64,
+    pub plateaued: bool,
+}
+
+impl ConvergenceState {
+    /// Short status line, e.g. "plateaued for 1,200 iterations".
+    pub fn describe(&self) -> String {
+        let quiet = group_thousands(self.iterations_since_improvement);
+        if self.plateaued {
+            format!("plateaued for {quiet} iterations")
+        } else if let Some(at) = self.last_improvement {
+            format!("improving (last gain at #{at}, {quiet} iterations ago)")
+        } else {
+            "no valid results yet".to_string()
+        }
+    }
+}
+
+/// Format a count with comma thousands separators.
+fn group_thousands(n: usize) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Follows best fitness across iterations and detects plateaus.
+///
+/// Per iteration: call [`observe`](Self::observe) for every leaderboard-worthy
+/// result, then [`end_iteration`](Self::end_iteration) once.
+#[derive(Debug)]
+pub struct ConvergenceTracker {
+    config: ConvergenceConfig,
+    best: BTreeMap<String, f64>,
+    improved: bool,
+    /// Per-iteration "added a leaderboard entry" flags over the rate window.
+    recent: VecDeque<bool>,
+    recent_hits: usize,
+    last_improvement: Option<usize>,
+    since_improvement: usize,
+    /// `since_improvement` when the plateau last fired (or 0 after a gain).
+    armed_at: usize,
+    series: Vec<ConvergencePoint>,
+}
+
+impl ConvergenceTracker {
+    pub fn new(config: ConvergenceConfig) -> Self {
+        Self {
+            config,
+            best: BTreeMap::new(),
+            improved: false,
+            recent: VecDeque::new(),
+            recent_hits: 0,
+            last_improvement: None,
+            since_improvement: 0,
+            armed_at: 0,
+            series: Vec::new(),
+        }
+    }
+
+    /// Record a valid result's fitness for `symbol`.
+    pub fn observe(&mut self, symbol: &str, fitness: f64) {
+        if !fitness.is_finite() {
+            return;
+        }
+        match self.best.get_mut(symbol) {
+            Some(best) if fitness <= *best => {}
+            Some(best) => {
+                *best = fitness;
+                self.improved = true;
+            }
+            None => {
+                self.best.insert(symbol.to_string(), fitness);
+                self.improved = true;
+            }
+        }
+    }
+
+    /// Close out `iteration`. `inserted` is whether any leaderboard accepted
+    /// an entry during it.
+    ///
+    /// Returns the configured action when a plateau fires. With a window of
+    /// N, it fires after N quiet iterations and again every N after that
+    /// until fitness improves.
+    pub fn end_iteration(&mut self, iteration: usize, inserted: bool) -> Option<PlateauAction> {
+        if std::mem::take(&mut self.improved) {
+            self.last_improvement = Some(iteration);
+            self.since_improvement = 0;
+            self.armed_at = 0;
+        } else {
+            self.since_improvement += 1;
+        }
+
+        self.recent.push_back(inserted);
+        self.recent_hits += usize::from(inserted);
+        while self.recent.len() > self.config.rate_window.max(1) {
+            if self.recent.pop_front() == Some(true) {
+                self.recent_hits -= 1;
+            }
+        }
+
+        if self.config.sample_every > 0 && iteration % self.config.sample_every == 0 {
+            self.record_point(iteration);
+        }
+
+        let window = self.config.plateau_window?;
+        if window > 0 && self.since_improvement - self.armed_at >= window {
+            self.armed_at = self.since_improvement;
+            return Some(self.config.on_plateau);
+        }
+        None
+    }
+
+    /// Current status for progress updates.
+    pub fn state(&self) -> ConvergenceState {
+        ConvergenceState {
+            last_improvement: self.last_improvement,
+            iterations_since_improvement: self.since_improvement,
+            new_entry_rate: self.new_entry_rate(),
+            plateaued: self
+                .config
+                .plateau_window
+                .is_some_and(|w| w > 0 && self.since_improvement >= w),
+        }
+    }
+
+    /// Most recently recorded sample, if any.
+    pub fn last_point(&self) -> Option<&ConvergencePoint> {
+        self.series.last()
+    }
+
+    /// Finish the series with a sample at `last_iteration` (if not already
+    /// recorded) and return it.
+    pub fn finish(mut self, last_iteration: Option<usize>) -> Vec<ConvergencePoint> {
+        if let Some(it) = last_iteration {
+            if self.series.last().map_or(true, |p| p.iteration != it) {
+                self.record_point(it);
+            }
+        }
+        self.series
+    }
+
+    fn new_entry_rate(&self) -> f64 {
+        if self.recent.is_empty() {
+            0.0
+        } else {
+            self.recent_hits as f64 / self.recent.len() as f64
+        }
+    }
+
+    fn record_point(&mut self, iteration: usize) {
+        self.series.push(ConvergencePoint {
+            iteration,
+            best_fitness: self.best.clone(),
+            new_entry_rate: self.new_entry_rate(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker(window: usize, action: PlateauAction) -> ConvergenceTracker {
+        ConvergenceTracker::new(ConvergenceConfig {
+            sample_every: 10,
+            rate_window: 4,
+            plateau_window: Some(window),
+            on_plateau: action,
+        })
+    }
+
+    /// Feed a rigged fitness sequence (one value per iteration) and return the
+    /// iterations on which the plateau fired.
+    fn run(t: &mut ConvergenceTracker, fitness: &[f64]) -> Vec<usize> {
+        let mut fired = Vec::new();
+        for (i, &f) in fitness.iter().enumerate() {
+            t.observe("SPY", f);
+            if t.end_iteration(i, false).is_some() {
+                fired.push(i);
+            }
+        }
+        fired
+    }
+
+    #[test]
+    fn plateau_fires_after_window_without_improvement() {
+        let mut t = tracker(5, PlateauAction::Stop);
+        // Improves through iteration 3, then flat
+        let fitness = [0.1, 0.2, 0.3, 0.4, 0.4, 0.35, 0.4, 0.1, 0.4, 0.2];
+        let fired = run(&mut t, &fitness);
+        assert_eq!(fired, vec![8]);
+        let state = t.state();
+        assert!(state.plateaued);
+        assert_eq!(state.last_improvement, Some(3));
+        assert_eq!(state.iterations_since_improvement, 6);
+    }
+
+    #[test]
+    fn improvement_resets_plateau() {
+        let mut t = tracker(3, PlateauAction::Report);
+        let fitness = [1.0, 0.5, 0.5, 0.5, 2.0, 1.0, 1.0];
+        let fired = run(&mut t, &fitness);
+        assert_eq!(fired, vec![3]);
+        assert!(!t.state().plateaued);
+        assert_eq!(t.state().last_improvement, Some(4));
+    }
+
+    #[test]
+    fn plateau_refires_every_window() {
+        let mut t = tracker(2, PlateauAction::BoostExplore { step: 0.1 });
+        let fitness = [1.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+        assert_eq!(run(&mut t, &fitness), vec![2, 4]);
+    }
+
+    #[test]
+    fn no_window_never_fires() {
+        let mut t = ConvergenceTracker::new(ConvergenceConfig {
+            plateau_window: None,
+            ..ConvergenceConfig::default()
+        });
+        assert!(run(&mut t, &[1.0; 5_000]).is_empty());
+        assert!(!t.state().plateaued);
+    }
+
+    #[test]
+    fn series_samples_best_fitness_and_entry_rate() {
+        let mut t = tracker(100, PlateauAction::Report);
+        for i in 0..25 {
+            t.observe("SPY", i as f64);
+            t.observe("QQQ", 1.0);
+            t.end_iteration(i, i % 2 == 0);
+        }
+        let series = t.finish(Some(24));
+        let iterations: Vec<usize> = series.iter().map(|p| p.iteration).collect();
+        assert_eq!(iterations, vec![0, 10, 20, 24]);
+        assert_eq!(series[1].best_fitness["SPY"], 10.0);
+        assert_eq!(series[1].best_fitness["QQQ"], 1.0);
+        // Window of 4 over iterations 21..=24: 22 and 24 inserted
+        assert_eq!(series[3].new_entry_rate, 0.5);
+    }
+
+    #[test]
+    fn describe_formats_plateau() {
+        let state = ConvergenceState {
+            last_improvement: Some(850),
+            iterations_since_improvement: 1200,
+            new_entry_rate: 0.0,
+            plateaued: true,
+        };
+        assert_eq!(state.describe(), "plateaued for 1,200 iterations");
+        assert_eq!(group_thousands(1_234_567), "1,234,567");
+        assert_eq!(group_thousands(999), "999");
+    }
+}
@@ -0,0 +1,538 @@
+//! Corpus runs — re-run a library of saved configs and diff the results.
+//!
+//! [`run_corpus`] runs every TOML config under a directory and records a
+//! compact [`CorpusSnapshot`]: per config, its headline metrics and trade
+//! count, or the error that stopped it. Snapshots are written as stable JSON
+//! (entries sorted by config path, keys in fixed order, no timestamps), so
+//! one taken before an engine change and one after can be kept under
+//! version control and compared.
+//!
+//! [`diff_corpora`] compares two snapshots config by config. A config
+//! counts as changed when its trade count moved, a metric moved by more than
+//! the tolerance, or it ran in one snapshot and failed in the other; the
+//! [`CorpusDiff`] also keeps the largest absolute move of each metric over
+//! every config, changed or not.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::config::BacktestConfig;
+use crate::runner::{BacktestResult, RunError};
+use crate::verify::metric_values;
+
+/// File a snapshot is saved as inside its output directory.
+pub const CORPUS_FILE: &str = "corpus.json";
+
+/// Schema version written into new snapshots.
+pub const CORPUS_SCHEMA_VERSION: u32 = 1;
+
+/// Default largest absolute metric move still counted as no drift.
+pub const DEFAULT_DRIFT_TOLERANCE: f64 = 1e-9;
+
+/// Why a corpus could not be run, saved or loaded.
+#[derive(Debug, Error)]
+pub enum CorpusError {
+    #[error("no TOML configs found in {}", .0.display())]
+    NoConfigs(PathBuf),
+    #[error("{path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("{path}: {source}")]
+    Json {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error(
+        "{path}: schema version {version} is newer than this build reads ({CORPUS_SCHEMA_VERSION})"
+    )]
+    UnsupportedVersion { path: PathBuf, version: u32 },
+}
+
+/// Results of one corpus run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CorpusSnapshot {
+    pub schema_version: u32,
+    /// Sorted by config name.
+    pub entries: Vec<CorpusEntry>,
+}
+
+impl CorpusSnapshot {
+    /// Snapshot of `entries`, sorted by name.
+    pub fn new(mut entries: Vec<CorpusEntry>) -> Self {
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Self {
+            schema_version: CORPUS_SCHEMA_VERSION,
+            entries,
+        }
+    }
+
+    pub fn failed(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| matches!(e.outcome, CorpusOutcome::Failed { .. }))
+            .count()
+    }
+}
+
+/// One config of a corpus and how its run went.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CorpusEntry {
+    /// Config path relative to the configs directory, `/`-separated.
+    pub name: String,
+    #[serde(flatten)]
+    pub outcome: CorpusOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum CorpusOutcome {
+    Ran(RunSummary),
+    /// The config did not parse or validate, or the run failed.
+    Failed {
+        error: String,
+    },
+}
+
+/// What a corpus keeps of a run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunSummary {
+    pub config_hash: String,
+    pub symbol: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub dataset_hash: String,
+    pub trade_count: usize,
+    /// Headline metrics by name; None for a non-finite value.
+    pub metrics: BTreeMap<String, Option<f64>>,
+}
+
+impl RunSummary {
+    pub fn from_result(result: &BacktestResult) -> Self {
+        Self {
+            config_hash: result.config.full_hash().as_hex(),
+            symbol: result.symbol.clone(),
+            start_date: result.start_date.clone(),
+            end_date: result.end_date.clone(),
+            dataset_hash: result.dataset_hash.clone(),
+            trade_count: result.trades.len(),
+            metrics: metric_values(&result.metrics)
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value.is_finite().then_some(value)))
+                .collect(),
+        }
+    }
+}
+
+/// TOML configs under `dir`, recursively, sorted by path.
+pub fn corpus_configs(dir: &Path) -> Result<Vec<PathBuf>, CorpusError> {
+    let mut configs = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries = fs::read_dir(&dir).map_err(|source| io_error(&dir, source))?;
+        for entry in entries {
+            let path = entry.map_err(|source| io_error(&dir, source))?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "toml") {
+                configs.push(path);
+            }
+        }
+    }
+    if configs.is_empty() {
+        return Err(CorpusError::NoConfigs(dir.to_path_buf()));
+    }
+    configs.sort();
+    Ok(configs)
+}
+
+/// Run every config under `configs_dir` with `run`.
+///
+/// A config that fails to parse, validate or run is recorded as
+/// [`CorpusOutcome::Failed`] rather than failing the corpus. `jobs` as for
+/// [`crate::TrendLab::with_jobs`]: 0 uses the global Rayon pool, 1 runs
+/// sequentially.
+pub fn run_corpus<F>(configs_dir: &Path, jobs: usize, run: F) -> Result<CorpusSnapshot, CorpusError>
+where
+    F: Fn(&BacktestConfig) -> Result<BacktestResult, RunError> + Sync,
+{
+    let configs = corpus_configs(configs_dir)?;
+    let run_one = |path: &PathBuf| CorpusEntry {
+        name: config_name(configs_dir, path),
+        outcome: match run_config(path, &run) {
+            Ok(result) => CorpusOutcome::Ran(RunSummary::from_result(&result)),
+            Err(error) => CorpusOutcome::Failed { error },
+        },
+    };
+    let entries = match jobs {
+        0 => configs.par_iter().map(run_one).collect(),
+        1 => configs.iter().map(run_one).collect(),
+        jobs => rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build()
+            .expect("failed to build Rayon thread pool")
+            .install(|| configs.par_iter().map(run_one).collect()),
+    };
+    Ok(CorpusSnapshot::new(entries))
+}
+
+fn run_config<F>(path: &Path, run: &F) -> Result<BacktestResult, String>
+where
+    F: Fn(&BacktestConfig) -> Result<BacktestResult, RunError>,
+{
+    let config = BacktestConfig::from_file(path).map_err(|e| e.to_string())?;
+    config
+        .validate(None)
+        .into_result()
+        .map_err(|e| e.to_string())?;
+    run(&config).map_err(|e| e.to_string())
+}
+
+fn config_name(root: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Write `snapshot` to `out_dir`/[`CORPUS_FILE`], creating the directory.
+pub fn save_snapshot(snapshot: &CorpusSnapshot, out_dir: &Path) -> Result<PathBuf, CorpusError> {
+    fs::create_dir_all(out_dir).map_err(|source| io_error(out_dir, source))?;
+    let path = out_dir.join(CORPUS_FILE);
+    let mut json = serde_json::to_string_pretty(snapshot).map_err(|source| CorpusError::Json {
+        path: path.clone(),
+        source,
+    })?;
+    json.push('\n');
+    fs::write(&path, json).map_err(|source| io_error(&path, source))?;
+    Ok(path)
+}
+
+/// Load a snapshot from its directory or its [`CORPUS_FILE`].
+pub fn load_snapshot(path: &Path) -> Result<CorpusSnapshot, CorpusError> {
+    let path = if path.is_dir() {
+        path.join(CORPUS_FILE)
+    } else {
+        path.to_path_buf()
+    };
+    let json = fs::read_to_string(&path).map_err(|source| io_error(&path, source))?;
+    let snapshot: CorpusSnapshot =
+        serde_json::from_str(&json).map_err(|source| CorpusError::Json {
+            path: path.clone(),
+            source,
+        })?;
+    if snapshot.schema_version > CORPUS_SCHEMA_VERSION {
+        return Err(CorpusError::UnsupportedVersion {
+            path,
+            version: snapshot.schema_version,
+        });
+    }
+    Ok(snapshot)
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> CorpusError {
+    CorpusError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+/// A metric's value in both snapshots.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricDelta {
+    pub metric: String,
+    pub a: Option<f64>,
+    pub b: Option<f64>,
+}
+
+impl MetricDelta {
+    /// `b - a`; None unless both are finite.
+    pub fn delta(&self) -> Option<f64> {
+        Some(self.b? - self.a?)
+    }
+
+    /// Moved by more than `tolerance`, or finite in only one snapshot.
+    pub fn drifted(&self, tolerance: f64) -> bool {
+        match (self.a, self.b) {
+            (Some(a), Some(b)) => (b - a).abs() > tolerance,
+            (None, None) => false,
+            _ => true,
+        }
+    }
+}
+
+/// How one config's result changed between the snapshots.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigDiff {
+    pub name: String,
+    /// Trade counts in `a` and `b`, when they differ.
+    pub trade_count: Option<(usize, usize)>,
+    /// Metrics that moved by more than the tolerance.
+    pub metrics: Vec<MetricDelta>,
+    /// Status in `a` and `b` ("ran" or the error), when the config ran in
+    /// one snapshot and failed in the other, or failed differently.
+    pub status: Option<(String, String)>,
+    /// The run read different bars: the change may be the data's, not the
+    /// engine's.
+    pub data_changed: bool,
+}
+
+/// Comparison of two corpus snapshots.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CorpusDiff {
+    pub tolerance: f64,
+    /// Configs in both snapshots.
+    pub compared: usize,
+    /// Configs whose result changed beyond the tolerance, by name.
+    pub changed: Vec<ConfigDiff>,
+    pub only_in_a: Vec<String>,
+    pub only_in_b: Vec<String>,
+    /// Largest absolute move of each metric over configs that ran in both.
+    pub max_drift: BTreeMap<String, f64>,
+}
+
+impl CorpusDiff {
+    /// Any config changed, or the snapshots hold different configs.
+    pub fn has_drift(&self) -> bool {
+        !self.changed.is_empty() || !self.only_in_a.is_empty() || !self.only_in_b.is_empty()
+    }
+}
+
+/// Compare snapshot `b` against `a`, config by config.
+pub fn diff_corpora(a: &CorpusSnapshot, b: &CorpusSnapshot, tolerance: f64) -> CorpusDiff {
+    let by_name = |s: &CorpusSnapshot| -> BTreeMap<String, CorpusOutcome> {
+        s.entries
+            .iter()
+            .map(|e| (e.name.clone(), e.outcome.clone()))
+            .collect()
+    };
+    let (a, b) = (by_name(a), by_name(b));
+
+    let mut diff = CorpusDiff {
+        tolerance,
+        compared: 0,
+        changed: Vec::new(),
+        only_in_a: a.keys().filter(|n| !b.contains_key(*n)).cloned().collect(),
+        only_in_b: b.keys().filter(|n| !a.contains_key(*n)).cloned().collect(),
+        max_drift: BTreeMap::new(),
+    };
+    for (name, outcome_a) in &a {
+        let Some(outcome_b) = b.get(name) else {
+            continue;
+        };
+        diff.compared += 1;
+        let config_diff = match (outcome_a, outcome_b) {
+            (CorpusOutcome::Ran(ra), CorpusOutcome::Ran(rb)) => {
+                for delta in metric_deltas(ra, rb) {
+                    if let Some(d) = delta.delta() {
+                        let max = diff.max_drift.entry(delta.metric).or_default();
+                        *max = max.max(d.abs());
+                    }
+                }
+                let metrics: Vec<_> = metric_deltas(ra, rb)
+                    .filter(|d| d.drifted(tolerance))
+                    .collect();
+                let trade_count =
+                    (ra.trade_count != rb.trade_count).then_some((ra.trade_count, rb.trade_count));
+                (trade_count.is_some() || !metrics.is_empty()).then(|| ConfigDiff {
+                    name: name.clone(),
+                    trade_count,
+                    metrics,
+                    status: None,
+                    data_changed: ra.dataset_hash != rb.dataset_hash,
+                })
+            }
+            (sa, sb) => (sa != sb).then(|| ConfigDiff {
+                name: name.clone(),
+                trade_count: None,
+                metrics: Vec::new(),
+                status: Some((status_label(sa), status_label(sb))),
+                data_changed: false,
+            }),
+        };
+        diff.changed.extend(config_diff);
+    }
+    diff
+}
+
+/// Every metric of either summary, in name order.
+fn metric_deltas<'a>(
+    a: &'a RunSummary,
+    b: &'a RunSummary,
+) -> impl Iterator<Item = MetricDelta> + 'a {
+    let mut names: Vec<&String> = a.metrics.keys().chain(b.metrics.keys()).collect();
+    names.sort();
+    names.dedup();
+    names.into_iter().map(|name| MetricDelta {
+        metric: name.clone(),
+        a: a.metrics.get(name).copied().flatten(),
+        b: b.metrics.get(name).copied().flatten(),
+    })
+}
+
+fn status_label(outcome: &CorpusOutcome) -> String {
+    match outcome {
+        CorpusOutcome::Ran(_) => "ran".to_string(),
+        CorpusOutcome::Failed { error } => format!("failed: {error}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ran(name: &str, trades: usize, sharpe: f64) -> CorpusEntry {
+        CorpusEntry {
+            name: name.into(),
+            outcome: CorpusOutcome::Ran(RunSummary {
+                config_hash: format!("hash-{name}"),
+                symbol: "SPY".into(),
+                start_date: "2020-01-02".into(),
+                end_date: "2023-12-29".into(),
+                dataset_hash: "data".into(),
+                trade_count: trades,
+                metrics: [
+                    ("sharpe".to_string(), Some(sharpe)),
+                    ("cagr".to_string(), Some(0.08)),
+                    ("profit_factor".to_string(), None),
+                ]
+                .into_iter()
+                .collect(),
+            }),
+        }
+    }
+
+    fn corpus(sharpes: &[f64]) -> CorpusSnapshot {
+        CorpusSnapshot::new(
+            sharpes
+                .iter()
+                .enumerate()
+                .map(|(i, &s)| ran(&format!("config_{i:02}.toml"), 10 + i, s))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn identical_corpora_have_no_drift() {
+        let a = corpus(&[0.5, 1.0, 1.5]);
+        let diff = diff_corpora(&a, &a.clone(), DEFAULT_DRIFT_TOLERANCE);
+        assert!(!diff.has_drift());
+        assert_eq!(diff.compared, 3);
+        assert_eq!(diff.max_drift["sharpe"], 0.0);
+        // Non-finite in both: not a drift, and no drift value
+        assert!(!diff.max_drift.contains_key("profit_factor"));
+    }
+
+    #[test]
+    fn injected_change_is_localized_to_its_config() {
+        let a = corpus(&[0.5, 1.0, 1.5, 2.0]);
+        let mut b = a.clone();
+        let CorpusOutcome::Ran(summary) = &mut b.entries[2].outcome else {
+            unreachable!()
+        };
+        summary.trade_count += 1;
+        summary.metrics.insert("sharpe".into(), Some(1.25));
+
+        let diff = diff_corpora(&a, &b, 1e-6);
+        assert!(diff.has_drift());
+        assert_eq!(diff.changed.len(), 1);
+        let changed = &diff.changed[0];
+        assert_eq!(changed.name, "config_02.toml");
+        assert_eq!(changed.trade_count, Some((12, 13)));
+        assert_eq!(changed.metrics.len(), 1);
+        assert_eq!(changed.metrics[0].metric, "sharpe");
+        assert_eq!(changed.metrics[0].delta(), Some(-0.25));
+        assert!(!changed.data_changed);
+        assert_eq!(diff.max_drift["sharpe"], 0.25);
+        assert_eq!(diff.max_drift["cagr"], 0.0);
+    }
+
+    #[test]
+    fn moves_within_tolerance_count_toward_max_drift_only() {
+        let a = corpus(&[1.0]);
+        let b = corpus(&[1.0 + 1e-8]);
+        let diff = diff_corpora(&a, &b, 1e-6);
+        assert!(!diff.has_drift());
+        assert!(diff.max_drift["sharpe"] > 0.0);
+        assert!(diff_corpora(&a, &b, 1e-10).has_drift());
+    }
+
+    #[test]
+    fn added_removed_and_failing_configs_are_reported() {
+        let a = corpus(&[1.0, 2.0]);
+        let mut b = corpus(&[1.0]);
+        b.entries[0].outcome = CorpusOutcome::Failed {
+            error: "unknown signal type".into(),
+        };
+        b.entries.push(ran("new.toml", 3, 0.1));
+
+        let diff = diff_corpora(&a, &b, DEFAULT_DRIFT_TOLERANCE);
+        assert_eq!(diff.only_in_a, vec!["config_01.toml"]);
+        assert_eq!(diff.only_in_b, vec!["new.toml"]);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(
+            diff.changed[0].status,
+            Some(("ran".into(), "failed: unknown signal type".into()))
+        );
+    }
+
+    #[test]
+    fn a_finite_metric_becoming_non_finite_drifts() {
+        let a = corpus(&[1.0]);
+        let mut b = a.clone();
+        let CorpusOutcome::Ran(summary) = &mut b.entries[0].outcome else {
+            unreachable!()
+        };
+        summary.metrics.insert("sharpe".into(), None);
+        let diff = diff_corpora(&a, &b, DEFAULT_DRIFT_TOLERANCE);
+        assert_eq!(diff.changed[0].metrics[0].delta(), None);
+        assert!(!diff.max_drift.contains_key("sharpe"));
+    }
+
+    #[test]
+    fn snapshots_round_trip_as_stable_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut entries = corpus(&[1.0, 2.0]).entries;
+        entries.reverse();
+        entries.push(CorpusEntry {
+            name: "broken.toml".into(),
+            outcome: CorpusOutcome::Failed {
+                error: "bad".into(),
+            },
+        });
+        let snapshot = CorpusSnapshot::new(entries);
+        assert_eq!(snapshot.entries[0].name, "broken.toml");
+
+        let path = save_snapshot(&snapshot, &dir.path().join("corpus_A")).unwrap();
+        let first = fs::read_to_string(&path).unwrap();
+        assert!(first.contains("\"status\": \"failed\""));
+        assert!(first.contains("\"profit_factor\": null"));
+        assert_eq!(load_snapshot(path.parent().unwrap()).unwrap(), snapshot);
+
+        save_snapshot(&load_snapshot(&path).unwrap(), &dir.path().join("corpus_B")).unwrap();
+        let second = fs::read_to_string(dir.path().join("corpus_B").join(CORPUS_FILE)).unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn newer_schema_versions_are_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut snapshot = corpus(&[1.0]);
+        snapshot.schema_version = CORPUS_SCHEMA_VERSION + 1;
+        save_snapshot(&snapshot, dir.path()).unwrap();
+        assert!(matches!(
+            load_snapshot(dir.path()),
+            Err(CorpusError::UnsupportedVersion { .. })
+        ));
+    }
+}
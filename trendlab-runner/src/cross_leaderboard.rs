@@ -8,23 +8,38 @@
 //! Designed for incremental update: as new per-symbol results arrive during a YOLO
 //! session, the aggregates are recomputed efficiently.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 
-use trendlab_core::domain::{ConfigHash, FullHash};
-use trendlab_core::fingerprint::StrategyConfig;
+use trendlab_core::domain::{ConfigHash, FullHash, TradeRecord};
+use trendlab_core::fingerprint::{Provenance, StrategyConfig};
 
-use trendlab_core::engine::stickiness::StickinessMetrics;
+use trendlab_core::engine::stickiness::{
+    StickinessMetrics, PATHOLOGICAL_EXIT_TRIGGER_RATE, PATHOLOGICAL_HOLDING_BARS,
+};
 
+use crate::execution_mc::FrictionSensitivity;
+use crate::fdr::{FdrStanding, QValues};
+use crate::holdout::HoldoutRecord;
 use crate::metrics::PerformanceMetrics;
-use crate::promotion::RobustnessResult;
+use crate::overlap::{
+    self, downsample_returns, OverlapAnalysis, OverlapConfig, OverlapInput, RETURN_SAMPLE_POINTS,
+};
+use crate::promotion::{PromotionLevel, RobustnessResult};
+use crate::refresh::{RetestRecord, RetestStatus};
 use crate::risk_profile::RankingMetric;
+use crate::sparkline::{equity_sparkline, SPARKLINE_POINTS};
 use crate::tail_metrics::{compute_tail_metrics, TailMetrics};
 
 /// Aggregated stickiness metrics across multiple symbols.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Built from each symbol's run-level `StickinessMetrics` (the `stickiness`
+/// of its `BacktestResult`, also `run` in its diagnostics.json): `avg_*` is
+/// the plain mean over symbols, `worst_*` the largest holding period and
+/// chase ratio and the smallest exit trigger rate. See [`Self::from_runs`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AggregatedStickiness {
     pub avg_median_holding_bars: f64,
     pub worst_median_holding_bars: f64,
@@ -37,11 +52,35 @@ pub struct AggregatedStickiness {
     pub is_pathological: bool,
 }
 
+impl AggregatedStickiness {
+    /// Aggregate per-symbol run metrics. None for an empty slice.
+    pub fn from_runs(runs: &[&StickinessMetrics]) -> Option<Self> {
+        if runs.is_empty() {
+            return None;
+        }
+        let n = runs.len() as f64;
+        let mean = |f: fn(&StickinessMetrics) -> f64| runs.iter().map(|s| f(s)).sum::<f64>() / n;
+        let max = |f: fn(&StickinessMetrics) -> f64| runs.iter().map(|s| f(s)).fold(0.0, f64::max);
+
+        Some(Self {
+            avg_median_holding_bars: mean(|s| s.median_holding_bars),
+            worst_median_holding_bars: max(|s| s.median_holding_bars),
+            avg_exit_trigger_rate: mean(|s| s.exit_trigger_rate),
+            worst_exit_trigger_rate: runs.iter().map(|s| s.exit_trigger_rate).fold(1.0, f64::min),
+            avg_reference_chase_ratio: mean(|s| s.reference_chase_ratio),
+            worst_reference_chase_ratio: max(|s| s.reference_chase_ratio),
+            symbol_count: runs.len(),
+            is_pathological: runs.iter().any(|s| is_pathological_stickiness(s)),
+        })
+    }
+}
+
 /// Check if stickiness metrics indicate a pathological configuration.
 ///
 /// Pathological = median holding > 100 bars OR exit trigger rate < 0.05.
 pub fn is_pathological_stickiness(s: &StickinessMetrics) -> bool {
-    s.median_holding_bars > 100.0 || s.exit_trigger_rate < 0.05
+    s.median_holding_bars > PATHOLOGICAL_HOLDING_BARS
+        || s.exit_trigger_rate < PATHOLOGICAL_EXIT_TRIGGER_RATE
 }
 
 /// A single entry in the cross-symbol leaderboard.
@@ -63,8 +102,24 @@ pub struct CrossSymbolEntry {
     pub worst_max_drawdown: f64,
     pub avg_trade_count: f64,
 
+    // ── Correlation adjustment ──
+    /// Independent bets among the per-symbol returns (see
+    /// [`overlap::effective_bets`]). None with fewer than two symbols.
+    #[serde(default)]
+    pub effective_bets: Option<f64>,
+    /// `avg_sharpe` scaled by `sqrt(effective_bets / symbols)`: a config whose
+    /// symbols move together counts as fewer bets. Non-positive averages are
+    /// left as they are, so redundancy never makes a loser look better.
+    /// None on entries saved before the adjustment existed.
+    #[serde(default)]
+    pub adjusted_sharpe: Option<f64>,
+
     // ── Tail risk ──
     pub tail_metrics: Option<TailMetrics>,
+    /// Mean of the per-symbol equity curves (each rebased to 1.0), downsampled
+    /// for leaderboard sparklines.
+    #[serde(default)]
+    pub equity_sparkline: Vec<f64>,
 
     // ── Per-symbol breakdown ──
     pub symbol_count: usize,
@@ -73,6 +128,9 @@ pub struct CrossSymbolEntry {
     /// Per-symbol equity curves for tail metric recomputation.
     #[serde(skip)]
     pub(crate) symbol_equity_curves: HashMap<String, Vec<f64>>,
+    /// Per-symbol `(entry_bar, exit_bar)` trade spans for overlap analysis.
+    #[serde(skip)]
+    pub(crate) symbol_trade_spans: HashMap<String, Vec<(usize, usize)>>,
 
     // ── Stickiness ──
     #[serde(default)]
@@ -80,11 +138,29 @@ pub struct CrossSymbolEntry {
     /// Per-symbol stickiness for aggregation.
     #[serde(skip)]
     pub(crate) symbol_stickiness: HashMap<String, StickinessMetrics>,
+    /// Latest re-test per symbol (see [`crate::refresh`]).
+    #[serde(default)]
+    pub symbol_retests: HashMap<String, RetestRecord>,
+    /// Holdout evaluation per symbol (see [`crate::holdout`]).
+    #[serde(default)]
+    pub symbol_holdouts: HashMap<String, HoldoutRecord>,
+    /// Execution profile name per symbol, for symbols that ran under one.
+    #[serde(default)]
+    pub symbol_execution_profiles: HashMap<String, String>,
+    /// FDR standing per symbol, in that symbol's family.
+    #[serde(default)]
+    pub symbol_fdr: HashMap<String, FdrStanding>,
 
     // ── Robustness (promotion ladder) ──
     #[serde(default)]
     pub robustness: Option<RobustnessResult>,
 
+    // ── Overlap (set by `analyze_overlap`, top-N entries only) ──
+    #[serde(default)]
+    pub cluster_id: Option<usize>,
+    #[serde(default)]
+    pub diversification_score: Option<f64>,
+
     // ── Flags ──
     pub has_catastrophic: bool,
 
@@ -92,14 +168,98 @@ pub struct CrossSymbolEntry {
     pub session_id: String,
     pub timestamp: NaiveDateTime,
     pub iteration: usize,
+    /// Parent config, when this config was mutated from a champion.
+    #[serde(default)]
+    pub provenance: Option<Provenance>,
+}
+
+impl CrossSymbolEntry {
+    /// The average Sharpe that ranks this entry under `aggregation`.
+    pub fn aggregate_sharpe(&self, aggregation: CrossAggregation) -> f64 {
+        match aggregation {
+            CrossAggregation::Mean => self.avg_sharpe,
+            CrossAggregation::CorrelationAdjusted => {
+                self.adjusted_sharpe.unwrap_or(self.avg_sharpe)
+            }
+        }
+    }
+
+    /// Degraded if any symbol degraded on re-test, survived if every symbol
+    /// was re-tested, never re-tested otherwise.
+    pub fn retest_status(&self) -> RetestStatus {
+        if self.symbol_retests.values().any(|r| r.degraded) {
+            RetestStatus::Degraded
+        } else if !self.symbol_metrics.is_empty()
+            && self
+                .symbol_metrics
+                .keys()
+                .all(|s| self.symbol_retests.contains_key(s))
+        {
+            RetestStatus::Survived
+        } else {
+            RetestStatus::NeverRetested
+        }
+    }
+
+    /// Mean holdout degradation ratio over the symbols that have one.
+    pub fn holdout_degradation_ratio(&self) -> Option<f64> {
+        let ratios: Vec<f64> = self
+            .symbol_holdouts
+            .values()
+            .filter_map(|h| h.degradation_ratio)
+            .collect();
+        (!ratios.is_empty()).then(|| ratios.iter().sum::<f64>() / ratios.len() as f64)
+    }
+
+    /// The symbol standing with the highest q-value: a champion survives
+    /// FDR only if it does on every symbol with a p-value.
+    pub fn weakest_fdr(&self) -> Option<&FdrStanding> {
+        self.symbol_fdr
+            .values()
+            .max_by(|a, b| a.q_value.total_cmp(&b.q_value))
+    }
+
+    /// True when member runs used different execution profiles. A symbol
+    /// without a profile differs from every profiled one.
+    pub fn has_mixed_execution_profiles(&self) -> bool {
+        let mut profiles = self
+            .symbol_metrics
+            .keys()
+            .map(|s| self.symbol_execution_profiles.get(s));
+        match profiles.next() {
+            Some(first) => profiles.any(|p| p != first),
+            None => false,
+        }
+    }
+}
+
+/// How per-symbol Sharpe ratios combine into the score that ranks a config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrossAggregation {
+    /// Plain mean over symbols (`avg_sharpe`).
+    #[default]
+    Mean,
+    /// Mean discounted for correlated symbols (`adjusted_sharpe`), so three
+    /// tech ETFs count as less evidence than three unrelated assets.
+    CorrelationAdjusted,
 }
 
 /// Cross-symbol leaderboard: top N strategy configs ranked across all symbols.
-#[derive(Debug)]
+///
+/// Serialized sessions keep the aggregates but not the per-symbol equity
+/// curves, trade spans, or stickiness behind them, so a reloaded leaderboard
+/// is for display rather than further inserts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CrossSymbolLeaderboard {
     entries: HashMap<FullHash, CrossSymbolEntry>,
     max_size: usize,
     catastrophic_threshold: f64,
+    /// Aggregate behind avg-Sharpe ranking and trimming.
+    #[serde(default)]
+    aggregation: CrossAggregation,
+    /// Ranking order of the last overlap analysis.
+    overlap_order: Vec<FullHash>,
 }
 
 impl CrossSymbolLeaderboard {
@@ -108,9 +268,21 @@ impl CrossSymbolLeaderboard {
             entries: HashMap::with_capacity(max_size.min(1024)),
             max_size,
             catastrophic_threshold,
+            aggregation: CrossAggregation::default(),
+            overlap_order: Vec::new(),
         }
     }
 
+    /// Rank and trim by `aggregation` instead of the plain mean.
+    pub fn with_aggregation(mut self, aggregation: CrossAggregation) -> Self {
+        self.aggregation = aggregation;
+        self
+    }
+
+    pub fn aggregation(&self) -> CrossAggregation {
+        self.aggregation
+    }
+
     /// Insert or update a result for a (config, symbol) pair.
     ///
     /// If the `full_hash` already exists, the new symbol's metrics are merged
@@ -142,17 +314,28 @@ impl CrossSymbolLeaderboard {
                 hit_rate: 0.0,
                 worst_max_drawdown: 0.0,
                 avg_trade_count: 0.0,
+                effective_bets: None,
+                adjusted_sharpe: None,
                 tail_metrics: None,
+                equity_sparkline: Vec::new(),
                 symbol_count: 0,
                 symbol_metrics: HashMap::new(),
                 symbol_equity_curves: HashMap::new(),
+                symbol_trade_spans: HashMap::new(),
                 avg_stickiness: None,
                 symbol_stickiness: HashMap::new(),
+                symbol_retests: HashMap::new(),
+                symbol_holdouts: HashMap::new(),
+                symbol_execution_profiles: HashMap::new(),
+                symbol_fdr: HashMap::new(),
                 robustness: None,
+                cluster_id: None,
+                diversification_score: None,
                 has_catastrophic: false,
                 session_id: session_id.to_string(),
                 timestamp,
                 iteration,
+                provenance: None,
             });
 
         // Store per-symbol data
@@ -177,12 +360,13 @@ impl CrossSymbolLeaderboard {
     /// Get all entries sorted by the specified ranking metric.
     ///
     /// For `RankingMetric::Composite`, the caller must provide composite scores
-    /// via `get_ranked_by_scores()` instead. This method uses the raw metric value.
+    /// via `get_ranked_by_scores()` instead. This method uses the raw metric value,
+    /// except that avg Sharpe follows the leaderboard's [`CrossAggregation`].
     pub fn get_ranked(&self, metric: RankingMetric) -> Vec<&CrossSymbolEntry> {
         let mut entries: Vec<&CrossSymbolEntry> = self.entries.values().collect();
         entries.sort_by(|a, b| {
-            let va = extract_ranking_metric(a, metric);
-            let vb = extract_ranking_metric(b, metric);
+            let va = self.ranking_value(a, metric);
+            let vb = self.ranking_value(b, metric);
             vb.partial_cmp(&va).unwrap_or(std::cmp::Ordering::Equal)
         });
         entries
@@ -214,13 +398,191 @@ impl CrossSymbolLeaderboard {
         }
     }
 
+    /// Record the execution profile a symbol's run used (None for the
+    /// sampled preset alone).
+    pub fn set_execution_profile(
+        &mut self,
+        full_hash: &FullHash,
+        symbol: &str,
+        profile: Option<&str>,
+    ) {
+        if let Some(entry) = self.entries.get_mut(full_hash) {
+            match profile {
+                Some(name) => {
+                    entry
+                        .symbol_execution_profiles
+                        .insert(symbol.to_string(), name.to_string());
+                }
+                None => {
+                    entry.symbol_execution_profiles.remove(symbol);
+                }
+            }
+        }
+    }
+
+    /// Replace a symbol's result after a re-test and recompute aggregates.
+    pub fn set_retest(
+        &mut self,
+        full_hash: &FullHash,
+        symbol: &str,
+        metrics: PerformanceMetrics,
+        equity_curve: &[f64],
+        record: RetestRecord,
+    ) {
+        if let Some(entry) = self.entries.get_mut(full_hash) {
+            entry.symbol_metrics.insert(symbol.to_string(), metrics);
+            entry
+                .symbol_equity_curves
+                .insert(symbol.to_string(), equity_curve.to_vec());
+            entry.symbol_retests.insert(symbol.to_string(), record);
+            recompute_aggregates(entry, self.catastrophic_threshold);
+        }
+    }
+
+    /// Record a symbol's holdout evaluation. Aggregates stay the discovery
+    /// ones.
+    pub fn set_holdout(&mut self, full_hash: &FullHash, symbol: &str, record: HoldoutRecord) {
+        if let Some(entry) = self.entries.get_mut(full_hash) {
+            entry.symbol_holdouts.insert(symbol.to_string(), record);
+        }
+    }
+
+    /// Record a symbol's FDR standing for a strategy configuration.
+    pub fn set_fdr(&mut self, full_hash: &FullHash, symbol: &str, standing: FdrStanding) {
+        if let Some(entry) = self.entries.get_mut(full_hash) {
+            entry.symbol_fdr.insert(symbol.to_string(), standing);
+        }
+    }
+
+    /// Recompute every entry's standing on `symbol` against the symbol
+    /// family's current q-values.
+    pub fn refresh_fdr(&mut self, symbol: &str, q_values: &QValues, alpha: f64) {
+        for entry in self.entries.values_mut() {
+            if let Some(standing) = entry.symbol_fdr.get_mut(symbol) {
+                *standing = q_values.standing(standing.p_value, alpha);
+            }
+        }
+    }
+
+    /// Set per-symbol trades, used for trade overlap analysis.
+    pub fn set_trades(&mut self, full_hash: &FullHash, symbol: &str, trades: &[TradeRecord]) {
+        if let Some(entry) = self.entries.get_mut(full_hash) {
+            let spans = trades.iter().map(|t| (t.entry_bar, t.exit_bar)).collect();
+            entry.symbol_trade_spans.insert(symbol.to_string(), spans);
+        }
+    }
+
     /// Set robustness result for a strategy configuration.
-    pub fn set_robustness(&mut self, full_hash: &FullHash, robustness: RobustnessResult) {
+    ///
+    /// A friction ramp already on the entry is kept unless `robustness`
+    /// brings its own.
+    pub fn set_robustness(&mut self, full_hash: &FullHash, mut robustness: RobustnessResult) {
         if let Some(entry) = self.entries.get_mut(full_hash) {
+            if robustness.friction.is_none() {
+                robustness.friction = entry.robustness.take().and_then(|r| r.friction);
+            }
             entry.robustness = Some(robustness);
         }
     }
 
+    /// Record where a strategy configuration came from. The first provenance
+    /// recorded for a config is kept.
+    pub fn set_provenance(&mut self, full_hash: &FullHash, provenance: Provenance) {
+        if let Some(entry) = self.entries.get_mut(full_hash) {
+            entry.provenance.get_or_insert(provenance);
+        }
+    }
+
+    /// Attach a friction ramp to a strategy configuration.
+    ///
+    /// Without a prior robustness result (promotion disabled), one is created
+    /// at Level 1 with no gate failure to carry it.
+    pub fn set_friction(&mut self, full_hash: &FullHash, friction: FrictionSensitivity) {
+        if let Some(entry) = self.entries.get_mut(full_hash) {
+            let robustness = entry.robustness.get_or_insert(RobustnessResult {
+                level_reached: PromotionLevel::Level1CheapPass,
+                walk_forward: None,
+                execution_mc: None,
+                bootstrap: None,
+                borderline: None,
+                gate_failure: None,
+                friction: None,
+                benchmark: None,
+                signal_value: None,
+                sensitivity: None,
+            });
+            robustness.friction = Some(friction);
+        }
+    }
+
+    /// Analyze trade overlap and return correlation among the top-N entries.
+    ///
+    /// Annotates those entries with a cluster id and diversification score
+    /// (all other entries are cleared) and remembers the ranking for
+    /// [`Self::diversified_top`].
+    pub fn analyze_overlap(
+        &mut self,
+        metric: RankingMetric,
+        config: &OverlapConfig,
+    ) -> OverlapAnalysis {
+        let ranked: Vec<FullHash> = self
+            .get_ranked(metric)
+            .into_iter()
+            .take(config.top_n)
+            .map(|e| e.full_hash.clone())
+            .collect();
+
+        let analysis = {
+            let inputs: Vec<OverlapInput<'_>> = ranked
+                .iter()
+                .map(|h| {
+                    let e = &self.entries[h];
+                    OverlapInput {
+                        full_hash: &e.full_hash,
+                        equity_curves: &e.symbol_equity_curves,
+                        trade_spans: &e.symbol_trade_spans,
+                    }
+                })
+                .collect();
+            overlap::analyze(&inputs, config.correlation_threshold)
+        };
+
+        for entry in self.entries.values_mut() {
+            entry.cluster_id = None;
+            entry.diversification_score = None;
+        }
+        for (i, hash) in analysis.full_hashes.iter().enumerate() {
+            if let Some(entry) = self.entries.get_mut(hash) {
+                entry.cluster_id = Some(analysis.cluster_ids[i]);
+                entry.diversification_score = Some(analysis.diversification_scores[i]);
+            }
+        }
+        self.overlap_order = ranked;
+        analysis
+    }
+
+    /// Best entry per cluster, in ranking order, up to `n` entries.
+    ///
+    /// Uses the ranking and clusters from the last [`Self::analyze_overlap`];
+    /// before any analysis, every entry is its own cluster (ranked by avg Sharpe).
+    pub fn diversified_top(&self, n: usize) -> Vec<&CrossSymbolEntry> {
+        if self.overlap_order.is_empty() {
+            return self
+                .get_ranked(RankingMetric::AvgSharpe)
+                .into_iter()
+                .take(n)
+                .collect();
+        }
+
+        let mut seen = HashSet::new();
+        self.overlap_order
+            .iter()
+            .filter_map(|h| self.entries.get(h))
+            .filter(|e| e.cluster_id.map_or(true, |c| seen.insert(c)))
+            .take(n)
+            .collect()
+    }
+
     pub fn entries(&self) -> &HashMap<FullHash, CrossSymbolEntry> {
         &self.entries
     }
@@ -235,7 +597,7 @@ impl CrossSymbolLeaderboard {
 
     /// Trim to max_size by removing lowest-ranked entries.
     ///
-    /// Uses avg_sharpe as the default trimming metric.
+    /// Uses avg Sharpe (under the leaderboard's aggregation) as the trimming metric.
     pub fn trim(&mut self) {
         if self.entries.len() <= self.max_size {
             return;
@@ -244,7 +606,7 @@ impl CrossSymbolLeaderboard {
         let mut ranked: Vec<(FullHash, f64)> = self
             .entries
             .iter()
-            .map(|(h, e)| (h.clone(), e.avg_sharpe))
+            .map(|(h, e)| (h.clone(), e.aggregate_sharpe(self.aggregation)))
             .collect();
         ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
 
@@ -258,6 +620,13 @@ impl CrossSymbolLeaderboard {
             self.entries.remove(&hash);
         }
     }
+
+    fn ranking_value(&self, entry: &CrossSymbolEntry, metric: RankingMetric) -> f64 {
+        match metric {
+            RankingMetric::AvgSharpe => entry.aggregate_sharpe(self.aggregation),
+            _ => extract_ranking_metric(entry, metric),
+        }
+    }
 }
 
 /// Extract a ranking metric value from a cross-symbol entry.
@@ -267,6 +636,12 @@ pub fn extract_ranking_metric(entry: &CrossSymbolEntry, metric: RankingMetric) -
         RankingMetric::MinSharpe => entry.min_sharpe,
         RankingMetric::GeoMeanCagr => entry.geo_mean_cagr,
         RankingMetric::HitRate => entry.hit_rate,
+        // Entries without enough history for CVaR rank last
+        RankingMetric::Cvar95 => entry
+            .tail_metrics
+            .as_ref()
+            .and_then(|t| t.cvar_95)
+            .unwrap_or(f64::NEG_INFINITY),
         // Phase 11 stub: use avg_sharpe as placeholder for OOS Sharpe
         RankingMetric::MeanOosSharpe => entry.avg_sharpe,
         // Composite requires external scores; fall back to avg_sharpe
@@ -310,6 +685,49 @@ fn recompute_aggregates(entry: &mut CrossSymbolEntry, catastrophic_threshold: f6
 
     // Tail metrics: compute from pooled equity curves
     recompute_tail_metrics(entry);
+    recompute_sparkline(entry);
+    recompute_correlation_adjustment(entry);
+}
+
+/// Effective bets among the per-symbol returns (curves truncated to the
+/// shortest, then downsampled) and the Sharpe discounted by them.
+fn recompute_correlation_adjustment(entry: &mut CrossSymbolEntry) {
+    let mut symbols: Vec<&String> = entry.symbol_equity_curves.keys().collect();
+    symbols.sort();
+    let curves: Vec<&Vec<f64>> = symbols
+        .iter()
+        .map(|s| &entry.symbol_equity_curves[*s])
+        .collect();
+    let len = curves.iter().map(|eq| eq.len()).min().unwrap_or(0);
+    let returns: Vec<Vec<f64>> = curves
+        .iter()
+        .map(|eq| downsample_returns(&eq[..len], RETURN_SAMPLE_POINTS))
+        .collect();
+
+    entry.effective_bets = overlap::effective_bets(&returns);
+    entry.adjusted_sharpe = Some(match entry.effective_bets {
+        Some(bets) if entry.avg_sharpe > 0.0 => {
+            entry.avg_sharpe * (bets / returns.len() as f64).sqrt()
+        }
+        _ => entry.avg_sharpe,
+    });
+}
+
+/// Average the rebased per-symbol equity curves (truncated to the shortest)
+/// and downsample the result.
+fn recompute_sparkline(entry: &mut CrossSymbolEntry) {
+    let mut symbols: Vec<&String> = entry.symbol_equity_curves.keys().collect();
+    symbols.sort();
+    let curves: Vec<&Vec<f64>> = symbols
+        .iter()
+        .map(|s| &entry.symbol_equity_curves[*s])
+        .filter(|eq| eq.first().is_some_and(|&v| v > 0.0))
+        .collect();
+    let len = curves.iter().map(|eq| eq.len()).min().unwrap_or(0);
+    let mean: Vec<f64> = (0..len)
+        .map(|i| curves.iter().map(|eq| eq[i] / eq[0]).sum::<f64>() / curves.len() as f64)
+        .collect();
+    entry.equity_sparkline = equity_sparkline(&mean, SPARKLINE_POINTS);
 }
 
 /// Compute tail metrics from pooled equity curve returns across all symbols.
@@ -343,43 +761,14 @@ fn recompute_tail_metrics(entry: &mut CrossSymbolEntry) {
 
 /// Recompute aggregated stickiness from per-symbol stickiness data.
 fn recompute_stickiness(entry: &mut CrossSymbolEntry) {
-    if entry.symbol_stickiness.is_empty() {
-        entry.avg_stickiness = None;
-        return;
-    }
-
-    let sticks: Vec<&StickinessMetrics> = entry.symbol_stickiness.values().collect();
-    let n = sticks.len() as f64;
-
-    let avg_median_holding_bars = sticks.iter().map(|s| s.median_holding_bars).sum::<f64>() / n;
-    let worst_median_holding_bars = sticks
-        .iter()
-        .map(|s| s.median_holding_bars)
-        .fold(0.0_f64, f64::max);
-    let avg_exit_trigger_rate = sticks.iter().map(|s| s.exit_trigger_rate).sum::<f64>() / n;
-    let worst_exit_trigger_rate = sticks
-        .iter()
-        .map(|s| s.exit_trigger_rate)
-        .fold(1.0_f64, f64::min);
-    let avg_reference_chase_ratio =
-        sticks.iter().map(|s| s.reference_chase_ratio).sum::<f64>() / n;
-    let worst_reference_chase_ratio = sticks
-        .iter()
-        .map(|s| s.reference_chase_ratio)
-        .fold(0.0_f64, f64::max);
-
-    let is_pathological = sticks.iter().any(|s| is_pathological_stickiness(s));
-
-    entry.avg_stickiness = Some(AggregatedStickiness {
-        avg_median_holding_bars,
-        worst_median_holding_bars,
-        avg_exit_trigger_rate,
-        worst_exit_trigger_rate,
-        avg_reference_chase_ratio,
-        worst_reference_chase_ratio,
-        symbol_count: sticks.len(),
-        is_pathological,
-    });
+    // Symbol order, so the float sums do not depend on map iteration order
+    let mut symbols: Vec<&String> = entry.symbol_stickiness.keys().collect();
+    symbols.sort();
+    let runs: Vec<&StickinessMetrics> = symbols
+        .into_iter()
+        .map(|s| &entry.symbol_stickiness[s])
+        .collect();
+    entry.avg_stickiness = AggregatedStickiness::from_runs(&runs);
 }
 
 /// Geometric mean of CAGR values across symbols.
@@ -444,18 +833,22 @@ mod tests {
                     m.insert("lookback".into(), lookback);
                     m
                 },
+                children: Vec::new(),
             },
             position_manager: ComponentConfig {
                 component_type: "atr_trailing".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
             execution_model: ComponentConfig {
                 component_type: "next_bar_open".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
             signal_filter: ComponentConfig {
                 component_type: "no_filter".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
         }
     }
@@ -479,6 +872,11 @@ mod tests {
             max_consecutive_wins: 4,
             max_consecutive_losses: 3,
             avg_losing_streak: 1.5,
+            time_in_market: 0.0,
+            avg_exposure: 0.0,
+            invested_return: 0.0,
+            idle_return: 0.0,
+            exposure_adjusted_cagr: 0.0,
         }
     }
 
@@ -550,6 +948,49 @@ mod tests {
         assert!((entry.worst_max_drawdown - (-0.12)).abs() < 1e-10);
     }
 
+    fn make_stickiness(median: f64, rate: f64) -> StickinessMetrics {
+        StickinessMetrics {
+            median_holding_bars: median,
+            p95_holding_bars: median * 2.0,
+            pct_over_60_bars: 0.0,
+            pct_over_120_bars: 0.0,
+            exit_trigger_rate: rate,
+            reference_chase_ratio: (1.0 / rate).min(100.0),
+        }
+    }
+
+    #[test]
+    fn stickiness_aggregates_per_symbol_run_metrics() {
+        let mut lb = CrossSymbolLeaderboard::new(100, -0.5);
+        let config = make_config("donchian", 50.0);
+        let eq = make_equity(253, 0.001);
+        let spy = make_stickiness(20.0, 0.5);
+        let qqq = make_stickiness(120.0, 0.25);
+        for (symbol, stickiness) in [("SPY", &spy), ("QQQ", &qqq)] {
+            let metrics = make_metrics(1.0, 0.05, 0.05, -0.1);
+            lb.insert_result(symbol, metrics, &eq, &config, "s1", 0, ts());
+            lb.set_stickiness(&config.full_hash(), symbol, stickiness.clone());
+        }
+
+        let agg = lb.entries()[&config.full_hash()]
+            .avg_stickiness
+            .clone()
+            .unwrap();
+        assert_eq!(
+            Some(&agg),
+            AggregatedStickiness::from_runs(&[&qqq, &spy]).as_ref()
+        );
+        assert!((agg.avg_median_holding_bars - 70.0).abs() < 1e-10);
+        assert!((agg.worst_median_holding_bars - 120.0).abs() < 1e-10);
+        assert!((agg.avg_exit_trigger_rate - 0.375).abs() < 1e-10);
+        assert!((agg.worst_exit_trigger_rate - 0.25).abs() < 1e-10);
+        assert!((agg.avg_reference_chase_ratio - 3.0).abs() < 1e-10);
+        assert!((agg.worst_reference_chase_ratio - 4.0).abs() < 1e-10);
+        assert_eq!(agg.symbol_count, 2);
+        assert!(agg.is_pathological, "QQQ median hold exceeds 100 bars");
+        assert_eq!(AggregatedStickiness::from_runs(&[]), None);
+    }
+
     #[test]
     fn hit_rate_partial_profitability() {
         let mut lb = CrossSymbolLeaderboard::new(100, -0.5);
@@ -733,6 +1174,98 @@ mod tests {
         assert!((hit - 1.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn friction_survives_later_robustness() {
+        let config = make_config("bollinger", 20.0);
+        let eq = make_equity(253, 0.001);
+        let mut lb = CrossSymbolLeaderboard::new(100, -0.5);
+        lb.insert_result(
+            "SPY",
+            make_metrics(1.0, 0.1, 0.1, -0.1),
+            &eq,
+            &config,
+            "s1",
+            0,
+            ts(),
+        );
+        let hash = config.full_hash();
+
+        let friction = FrictionSensitivity::from_points("SPY", Vec::new(), 5.0);
+        lb.set_friction(&hash, friction);
+        let robustness = lb.entries()[&hash].robustness.as_ref().unwrap();
+        assert_eq!(robustness.level_reached, PromotionLevel::Level1CheapPass);
+        assert!(robustness.gate_failure.is_none());
+
+        lb.set_robustness(
+            &hash,
+            RobustnessResult {
+                level_reached: PromotionLevel::Level2WalkForward,
+                walk_forward: None,
+                execution_mc: None,
+                bootstrap: None,
+                borderline: None,
+                gate_failure: None,
+                friction: None,
+                benchmark: None,
+                signal_value: None,
+                sensitivity: None,
+            },
+        );
+        let robustness = lb.entries()[&hash].robustness.as_ref().unwrap();
+        assert_eq!(robustness.level_reached, PromotionLevel::Level2WalkForward);
+        assert_eq!(robustness.friction.as_ref().unwrap().symbol, "SPY");
+    }
+
+    #[test]
+    fn first_provenance_is_kept() {
+        let parent = make_config("donchian", 50.0);
+        let other = make_config("donchian", 40.0);
+        let child = make_config("donchian", 55.0);
+        let eq = make_equity(253, 0.001);
+        let mut lb = CrossSymbolLeaderboard::new(100, -0.5);
+        lb.insert_result(
+            "SPY",
+            make_metrics(1.0, 0.1, 0.1, -0.1),
+            &eq,
+            &child,
+            "s1",
+            0,
+            ts(),
+        );
+        let hash = child.full_hash();
+
+        lb.set_provenance(&hash, Provenance::new(&parent, &child));
+        lb.set_provenance(&hash, Provenance::new(&other, &child));
+        let provenance = lb.entries()[&hash].provenance.as_ref().unwrap();
+        assert_eq!(provenance.parent_full_hash, parent.full_hash());
+        assert_eq!(provenance.changes.len(), 1);
+    }
+
+    #[test]
+    fn sparkline_averages_rebased_symbol_curves() {
+        let config = make_config("bollinger", 20.0);
+        let mut lb = CrossSymbolLeaderboard::new(100, -0.5);
+        let up = make_equity(253, 0.001);
+        let flat = vec![50_000.0; 253];
+        for (symbol, eq) in [("SPY", &up), ("QQQ", &flat)] {
+            lb.insert_result(
+                symbol,
+                make_metrics(1.0, 0.1, 0.1, -0.1),
+                eq,
+                &config,
+                "s1",
+                0,
+                ts(),
+            );
+        }
+
+        let spark = &lb.entries()[&config.full_hash()].equity_sparkline;
+        assert_eq!(spark.len(), SPARKLINE_POINTS);
+        assert!((spark[0] - 1.0).abs() < 1e-12);
+        let expected_last = (1.0 + up[252] / up[0]) / 2.0;
+        assert!((spark[SPARKLINE_POINTS - 1] - expected_last).abs() < 1e-12);
+    }
+
     #[test]
     fn trim_removes_worst() {
         let mut lb = CrossSymbolLeaderboard::new(2, -0.5);
@@ -822,6 +1355,29 @@ mod tests {
         assert!((ranked[2].avg_sharpe - 1.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn cvar_ranking_puts_short_history_last() {
+        let mut lb = CrossSymbolLeaderboard::new(100, -0.5);
+        let calm = make_config("donchian", 50.0);
+        let wild = make_config("donchian", 100.0);
+        let short = make_config("bollinger", 20.0);
+
+        let runs = [
+            (&calm, wavy_equity(300, 0.0, 0.5)),
+            (&wild, wavy_equity(300, 0.0, 2.0)),
+            (&short, make_equity(50, 0.001)),
+        ];
+        for (i, (config, eq)) in runs.iter().enumerate() {
+            let metrics = make_metrics(1.0, 0.05, 0.05, -0.05);
+            lb.insert_result("SPY", metrics, eq, config, "s1", i, ts());
+        }
+
+        let ranked = lb.get_ranked(RankingMetric::Cvar95);
+        assert_eq!(ranked[0].config, calm);
+        assert_eq!(ranked[1].config, wild);
+        assert!(ranked[2].tail_metrics.is_none());
+    }
+
     #[test]
     fn empty_leaderboard() {
         let lb = CrossSymbolLeaderboard::new(100, -0.5);
@@ -829,4 +1385,108 @@ mod tests {
         assert_eq!(lb.len(), 0);
         assert!(lb.get_ranked(RankingMetric::AvgSharpe).is_empty());
     }
+
+    fn wavy_equity(n: usize, phase: f64, scale: f64) -> Vec<f64> {
+        let mut eq = vec![100_000.0];
+        for t in 1..n {
+            let r = scale * 0.01 * ((t as f64) * 0.7 + phase).sin();
+            eq.push(eq.last().unwrap() * (1.0 + r));
+        }
+        eq
+    }
+
+    /// Equity whose returns follow Walsh function `k`: curves with different
+    /// `k` have exactly uncorrelated returns.
+    fn walsh_equity(k: u32) -> Vec<f64> {
+        let mut eq = vec![100_000.0];
+        for t in 0..RETURN_SAMPLE_POINTS {
+            let sign = if (t >> k) % 2 == 0 { 1.0 } else { -1.0 };
+            eq.push(eq.last().unwrap() * (1.0 + 0.01 * sign));
+        }
+        eq
+    }
+
+    #[test]
+    fn correlation_adjustment_separates_redundant_from_independent_symbols() {
+        let mut lb = CrossSymbolLeaderboard::new(100, -0.5);
+        let redundant = make_config("donchian", 50.0);
+        let independent = make_config("keltner", 20.0);
+        let loser = make_config("bollinger", 20.0);
+
+        for (i, symbol) in ["QQQ", "XLK", "SMH"].iter().enumerate() {
+            let metrics = make_metrics(1.2, 0.05, 0.05, -0.05);
+            lb.insert_result(symbol, metrics, &walsh_equity(0), &redundant, "s1", 0, ts());
+            let metrics = make_metrics(1.2, 0.05, 0.05, -0.05);
+            let eq = walsh_equity(i as u32);
+            lb.insert_result(symbol, metrics, &eq, &independent, "s1", 1, ts());
+            let metrics = make_metrics(-0.5, -0.05, -0.05, -0.1);
+            lb.insert_result(symbol, metrics, &walsh_equity(0), &loser, "s1", 2, ts());
+        }
+
+        let entry = |c: &StrategyConfig| &lb.entries()[&c.full_hash()];
+        let (r, i, l) = (entry(&redundant), entry(&independent), entry(&loser));
+        assert!((r.avg_sharpe - i.avg_sharpe).abs() < 1e-12);
+        assert!((r.effective_bets.unwrap() - 1.0).abs() < 1e-9);
+        assert!((i.effective_bets.unwrap() - 3.0).abs() < 1e-9);
+        assert!((r.adjusted_sharpe.unwrap() - 1.2 / 3f64.sqrt()).abs() < 1e-9);
+        assert!((i.adjusted_sharpe.unwrap() - 1.2).abs() < 1e-9);
+        // Redundancy does not soften a losing average
+        assert_eq!(l.adjusted_sharpe, Some(l.avg_sharpe));
+
+        let lb = lb.with_aggregation(CrossAggregation::CorrelationAdjusted);
+        let ranked = lb.get_ranked(RankingMetric::AvgSharpe);
+        assert_eq!(ranked[0].config, independent);
+        assert_eq!(ranked[1].config, redundant);
+
+        // A single symbol has nothing to correlate with
+        let mut single = CrossSymbolLeaderboard::new(100, -0.5);
+        let metrics = make_metrics(1.0, 0.05, 0.05, -0.05);
+        single.insert_result("SPY", metrics, &walsh_equity(0), &redundant, "s1", 0, ts());
+        let e = &single.entries()[&redundant.full_hash()];
+        assert!(e.effective_bets.is_none());
+        assert_eq!(e.adjusted_sharpe, Some(1.0));
+    }
+
+    #[test]
+    fn diversified_top_keeps_best_per_cluster() {
+        let mut lb = CrossSymbolLeaderboard::new(100, -0.5);
+        let same_a = make_config("donchian", 50.0);
+        let same_b = make_config("donchian", 55.0);
+        let different = make_config("keltner", 20.0);
+
+        // Two configs riding the same move, one uncorrelated
+        let curves = [
+            (&same_a, 3.0, wavy_equity(120, 0.0, 1.0)),
+            (&same_b, 2.0, wavy_equity(120, 0.0, 1.5)),
+            (&different, 1.0, wavy_equity(120, 1.6, 1.0)),
+        ];
+        for (config, sharpe, eq) in &curves {
+            lb.insert_result(
+                "SPY",
+                make_metrics(*sharpe, 0.1, 0.1, -0.05),
+                eq,
+                config,
+                "s1",
+                0,
+                ts(),
+            );
+        }
+        lb.set_trades(&same_a.full_hash(), "SPY", &[]);
+
+        // Before analysis every entry stands alone
+        assert_eq!(lb.diversified_top(10).len(), 3);
+
+        let analysis = lb.analyze_overlap(RankingMetric::AvgSharpe, &OverlapConfig::default());
+        assert_eq!(analysis.cluster_ids, vec![0, 0, 1]);
+
+        let top = lb.diversified_top(10);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].full_hash, same_a.full_hash());
+        assert_eq!(top[1].full_hash, different.full_hash());
+
+        let b = &lb.entries()[&same_b.full_hash()];
+        assert_eq!(b.cluster_id, Some(0));
+        assert!(b.diversification_score.unwrap() < top[1].diversification_score.unwrap());
+        assert_eq!(lb.diversified_top(1).len(), 1);
+    }
 }
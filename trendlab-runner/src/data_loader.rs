@@ -7,31 +7,97 @@
 //! 3. If no data and `--synthetic` → generate synthetic bars (tagged)
 //! 4. Otherwise → fail with a clear error
 //!
+//! With `offline` set, step 2 is skipped entirely: the provider is never
+//! touched, and every cache miss is reported in a single
+//! `LoadError::OfflineCacheMiss`.
+//!
+//! A cache hit whose data ends more than `stale_after_days` before the
+//! requested end is stale. Online, the missing tail is downloaded and merged
+//! into the cache. Offline (or when the refresh fails), the symbol is loaded
+//! as-is and flagged with a [`TruncatedRange`] and a `TruncatedRange`
+//! data quality warning, so a short backtest never passes silently.
+//!
+//! Bars are daily unless `LoadOptions::interval` says otherwise. Other
+//! intervals are cached separately (see `ParquetCache::with_interval`); on a
+//! miss they are built from the symbol's cached daily bars before anything is
+//! downloaded. The provider is expected to serve the requested interval.
+//!
 //! Synthetic data is a developer-only debug mode. Results produced on
 //! synthetic data are tagged and cannot enter the all-time leaderboard.
+//!
+//! Every symbol's bars are scanned for anomalies (see `data_quality`) before
+//! alignment. Under `strict`, severe anomalies fail the load. Corrections the
+//! ingest pipeline made when a partition was last written (duplicate,
+//! misordered or out-of-range rows, a dropped partial bar) are read back from
+//! the cache and reported alongside them.
 
 use chrono::{Datelike, NaiveDate};
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use thiserror::Error;
+use trendlab_core::components::execution::profile::average_dollar_volume;
 use trendlab_core::data::{
     align::{align_symbols, AlignedData},
-    cache::ParquetCache,
-    provider::{DataError, DataProvider, DataSource, DownloadProgress, RawBar},
+    cache::{ParquetCache, PartitionVintage},
+    ingest::{ingest_with, IngestOptions, IngestReport},
+    interval::BarInterval,
+    provider::{fetch_with_retry, DataError, DataProvider, DataSource, DownloadProgress, RawBar},
+};
+
+use crate::data_quality::{
+    ingest_warnings, scan_bars, AnomalyKind, AnomalyThresholds, DataQualityWarning,
 };
+use crate::walk_forward::slice_aligned_data;
+
+/// Default staleness threshold: a week covers weekends and holidays.
+pub const DEFAULT_STALE_AFTER_DAYS: u32 = 7;
 
 /// Errors from the data loading layer.
 #[derive(Debug, Error)]
 pub enum LoadError {
     #[error(
-        "no cached data for '{symbol}' and no network access (use --synthetic for synthetic data)"
+        "offline and no cached data for {} (use --synthetic for synthetic data)",
+        format_misses(.missing)
     )]
-    NoCachedDataOffline { symbol: String },
+    OfflineCacheMiss { missing: Vec<CacheMiss> },
 
     #[error("no cached data for '{symbol}' and download failed: {reason}")]
     DownloadFailed { symbol: String, reason: String },
 
     #[error("data error: {0}")]
     Data(#[from] DataError),
+
+    #[error("'{symbol}' has {count} severe data anomalies (first: {first})")]
+    BadData {
+        symbol: String,
+        count: usize,
+        first: String,
+    },
+
+    #[error("'{symbol}' is not in the loaded data")]
+    NotLoaded { symbol: String },
+}
+
+/// A symbol and date range that was requested offline but not cached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheMiss {
+    pub symbol: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl std::fmt::Display for CacheMiss {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' ({}..{})", self.symbol, self.start, self.end)
+    }
+}
+
+fn format_misses(missing: &[CacheMiss]) -> String {
+    missing
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 /// Options controlling how bars are loaded.
@@ -44,10 +110,37 @@ pub struct LoadOptions {
     /// If true, never make network requests.
     pub offline: bool,
     /// If true, generate synthetic bars when real data is unavailable.
-    /// Mutually exclusive with `offline` (enforced at call site).
+    /// Never implied by `offline`: an offline cache miss is an error unless
+    /// this is set.
     pub synthetic: bool,
     /// Force re-download even if cached.
     pub force: bool,
+    /// Fail with `LoadError::BadData` when a symbol has more severe anomalies
+    /// than `AnomalyThresholds::max_severe`.
+    pub strict: bool,
+    /// Treat cached data ending more than this many days before `end` as
+    /// stale. `None` disables the check.
+    pub stale_after_days: Option<u32>,
+    /// Bar interval to load. Defaults to daily.
+    pub interval: BarInterval,
+}
+
+/// Cached data that stops short of the requested end date.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TruncatedRange {
+    /// Last date actually covered.
+    pub data_end: NaiveDate,
+    pub requested_end: NaiveDate,
+}
+
+impl std::fmt::Display for TruncatedRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "data ends {} (requested {})",
+            self.data_end, self.requested_end
+        )
+    }
 }
 
 /// Result of loading bars, including data source provenance.
@@ -61,6 +154,86 @@ pub struct LoadedData {
     pub dataset_hash: String,
     /// Whether any symbol used synthetic data.
     pub has_synthetic: bool,
+    /// Anomalies found while scanning each symbol's bars.
+    pub data_quality_warnings: Vec<DataQualityWarning>,
+    /// Symbols served from a stale cache that could not be refreshed.
+    pub truncated_ranges: BTreeMap<String, TruncatedRange>,
+    /// Mean close × volume per bar for each symbol, for liquidity buckets.
+    pub avg_dollar_volume: HashMap<String, f64>,
+    /// Vintage of each cache partition the bars came from, by symbol.
+    /// Synthetic symbols have none.
+    pub vintage: Vec<PartitionVintage>,
+}
+
+impl LoadedData {
+    /// Data quality warnings for a single symbol.
+    pub fn warnings_for<'a>(
+        &'a self,
+        symbol: &'a str,
+    ) -> impl Iterator<Item = &'a DataQualityWarning> + 'a {
+        self.data_quality_warnings
+            .iter()
+            .filter(move |w| w.symbol == symbol)
+    }
+
+    /// Truncation flag for a single symbol, if its data ends early.
+    pub fn truncated_range(&self, symbol: &str) -> Option<&TruncatedRange> {
+        self.truncated_ranges.get(symbol)
+    }
+
+    /// Average dollar volume of a single symbol, if it has any valid bars.
+    pub fn avg_dollar_volume(&self, symbol: &str) -> Option<f64> {
+        self.avg_dollar_volume.get(symbol).copied()
+    }
+
+    /// Split at bar `boundary` into the bars before it and the bars from it
+    /// on, so a caller can hand out one half without the other.
+    ///
+    /// Each half gets its own dataset hash and dollar volumes. Dated warnings
+    /// go with the half their bar falls in; symbol-wide ones go with both.
+    /// Truncation flags describe the end of the data and stay with the later
+    /// half.
+    pub fn split_at(&self, boundary: usize) -> (LoadedData, LoadedData) {
+        let end = self.aligned.dates.len();
+        let mut before = self.slice(0, boundary);
+        before.truncated_ranges.clear();
+        (before, self.slice(boundary, end))
+    }
+
+    fn slice(&self, start: usize, end: usize) -> LoadedData {
+        let aligned = slice_aligned_data(&self.aligned, start, end);
+        let dataset_hash = compute_dataset_hash(&aligned);
+        let range = aligned
+            .dates
+            .first()
+            .copied()
+            .zip(aligned.dates.last().copied());
+        let data_quality_warnings = self
+            .data_quality_warnings
+            .iter()
+            .filter(|w| match (w.date, range) {
+                (Some(date), Some((first, last))) => first <= date && date <= last,
+                (Some(_), None) => false,
+                (None, _) => true,
+            })
+            .cloned()
+            .collect();
+        let avg_dollar_volume = aligned
+            .bars
+            .iter()
+            .filter_map(|(symbol, bars)| Some((symbol.clone(), average_dollar_volume(bars)?)))
+            .collect();
+        LoadedData {
+            aligned,
+            sources: self.sources.clone(),
+            dataset_hash,
+            has_synthetic: self.has_synthetic,
+            data_quality_warnings,
+            truncated_ranges: self.truncated_ranges.clone(),
+            avg_dollar_volume,
+            vintage: self.vintage.clone(),
+        }
+    }
 }
 
 /// Load bars for a set of symbols from the cache, with fallback to download or synthetic.
@@ -76,47 +249,103 @@ pub fn load_bars(
     let mut all_bars: HashMap<String, Vec<RawBar>> = HashMap::new();
     let mut sources: HashMap<String, DataSource> = HashMap::new();
     let mut has_synthetic = false;
+    let mut offline_misses = Vec::new();
+    let mut truncated_ranges = BTreeMap::new();
+    let mut vintage = Vec::new();
+    let mut ingest_reports: HashMap<String, IngestReport> = HashMap::new();
+
+    // Offline means the provider is never consulted, whatever the caller passed
+    let provider = if opts.offline { None } else { provider };
+    let daily_cache = cache;
+    let cache = &cache.with_interval(opts.interval);
 
     for (i, symbol) in symbols.iter().enumerate() {
         let total = symbols.len();
 
         // Step 1: Try cache
         if !opts.force {
-            if let Ok(bars) = cache.load(symbol) {
+            if let Ok(mut bars) = cache.load(symbol) {
                 if let Some(p) = progress {
                     p.on_start(symbol, i, total);
                     p.on_complete(symbol, i, total, &Ok(()));
                 }
+                let mut source = DataSource::Cache;
+                if let Some(data_end) = stale_end(cache, symbol, &bars, opts) {
+                    // Online: fetch the missing tail and merge it into the cache
+                    if let Some(prov) = provider.filter(|p| p.is_available()) {
+                        if let Some(merged) =
+                            extend_cached(prov, cache, symbol, &bars, data_end, opts, progress)?
+                        {
+                            bars = merged;
+                            source = DataSource::YahooFinance;
+                        }
+                    }
+                    if let Some(data_end) = stale_end(cache, symbol, &bars, opts) {
+                        truncated_ranges.insert(
+                            symbol.to_string(),
+                            TruncatedRange {
+                                data_end,
+                                requested_end: opts.end,
+                            },
+                        );
+                    }
+                }
+                vintage.push(cache.vintage_of(symbol, &bars)?);
+                if let Some(report) = cache.ingest_report(symbol) {
+                    ingest_reports.insert(symbol.to_string(), report);
+                }
                 all_bars.insert(symbol.to_string(), bars);
-                sources.insert(symbol.to_string(), DataSource::Cache);
+                sources.insert(symbol.to_string(), source);
                 continue;
             }
-        }
 
-        // Step 2: Try download (if not offline and provider available)
-        if !opts.offline {
-            if let Some(prov) = provider {
-                if prov.is_available() {
+            // Longer intervals can be built from cached daily bars
+            if opts.interval != BarInterval::Daily {
+                if let Ok(daily) = daily_cache.load(symbol) {
                     if let Some(p) = progress {
                         p.on_start(symbol, i, total);
+                        p.on_complete(symbol, i, total, &Ok(()));
+                    }
+                    vintage.push(daily_cache.vintage_of(symbol, &daily)?);
+                    if let Some(report) = daily_cache.ingest_report(symbol) {
+                        ingest_reports.insert(symbol.to_string(), report);
                     }
-                    match prov.fetch(symbol, opts.start, opts.end) {
-                        Ok(fetch_result) => {
-                            let ingested = trendlab_core::data::ingest::ingest(fetch_result.bars)?;
-                            cache.write(symbol, &ingested.bars)?;
-                            if let Some(p) = progress {
-                                p.on_complete(symbol, i, total, &Ok(()));
-                            }
-                            all_bars.insert(symbol.to_string(), ingested.bars);
-                            sources.insert(symbol.to_string(), DataSource::YahooFinance);
-                            continue;
+                    all_bars.insert(symbol.to_string(), opts.interval.resample(&daily));
+                    sources.insert(symbol.to_string(), DataSource::Cache);
+                    continue;
+                }
+            }
+        }
+
+        // Step 2: Try download (provider is `None` when offline)
+        if let Some(prov) = provider {
+            if prov.is_available() {
+                if let Some(p) = progress {
+                    p.on_start(symbol, i, total);
+                }
+                let (fetched, _attempts) =
+                    fetch_with_retry(prov, symbol, opts.start, opts.end, progress);
+                match fetched {
+                    Ok(fetch_result) => {
+                        let ingest_opts = ingest_options(opts.start, opts.end, opts.interval);
+                        let ingested = ingest_with(fetch_result.bars, &ingest_opts)?;
+                        cache.write(symbol, &ingested.bars)?;
+                        cache.write_ingest_report(symbol, &ingested.report)?;
+                        ingest_reports.insert(symbol.to_string(), ingested.report);
+                        // A forced download merges into what was cached
+                        vintage.push(cache.vintage(symbol)?);
+                        if let Some(p) = progress {
+                            p.on_complete(symbol, i, total, &Ok(()));
                         }
-                        Err(e) => {
-                            if let Some(p) = progress {
-                                p.on_complete(symbol, i, total, &Err(e));
-                            }
-                            // Fall through to synthetic or error
+                        all_bars.insert(symbol.to_string(), ingested.bars);
+                        sources.insert(symbol.to_string(), DataSource::YahooFinance);
+                        continue;
+                    }
+                    Err(e) => {
+                        if let Some(p) = progress {
+                            p.on_complete(symbol, i, total, &Err(e));
                         }
+                        // Fall through to synthetic or error
                     }
                 }
             }
@@ -127,18 +356,23 @@ pub fn load_bars(
             eprintln!(
                 "WARNING: generating synthetic data for {symbol} — results will be tagged as synthetic"
             );
-            let bars = generate_synthetic_bars(symbol, opts.start, opts.end);
+            let bars = opts
+                .interval
+                .resample(&generate_synthetic_bars(symbol, opts.start, opts.end));
             all_bars.insert(symbol.to_string(), bars);
             sources.insert(symbol.to_string(), DataSource::Synthetic);
             has_synthetic = true;
             continue;
         }
 
-        // Step 4: Fail
+        // Step 4: Fail (offline misses are collected so all are reported at once)
         if opts.offline {
-            return Err(LoadError::NoCachedDataOffline {
+            offline_misses.push(CacheMiss {
                 symbol: symbol.to_string(),
+                start: opts.start,
+                end: opts.end,
             });
+            continue;
         }
         return Err(LoadError::DownloadFailed {
             symbol: symbol.to_string(),
@@ -146,11 +380,55 @@ pub fn load_bars(
         });
     }
 
+    if !offline_misses.is_empty() {
+        return Err(LoadError::OfflineCacheMiss {
+            missing: offline_misses,
+        });
+    }
+
+    // Scan for anomalies before alignment adds void bars
+    let thresholds = AnomalyThresholds::default();
+    let mut data_quality_warnings = Vec::new();
+    for symbol in symbols {
+        let Some(bars) = all_bars.get(*symbol) else {
+            continue;
+        };
+        let warnings = scan_bars(symbol, bars, &thresholds);
+        if opts.strict {
+            let severe: Vec<&DataQualityWarning> =
+                warnings.iter().filter(|w| w.kind.is_severe()).collect();
+            if severe.len() > thresholds.max_severe {
+                return Err(LoadError::BadData {
+                    symbol: symbol.to_string(),
+                    count: severe.len(),
+                    first: severe[0].to_string(),
+                });
+            }
+        }
+        data_quality_warnings.extend(warnings);
+        if let Some(report) = ingest_reports.get(*symbol) {
+            data_quality_warnings.extend(ingest_warnings(symbol, report));
+        }
+        if let Some(truncated) = truncated_ranges.get(*symbol) {
+            data_quality_warnings.push(DataQualityWarning::new(
+                AnomalyKind::TruncatedRange,
+                Some(truncated.data_end),
+                *symbol,
+                truncated.to_string(),
+            ));
+        }
+    }
+
     // Align all symbols to a common timeline
     let aligned = align_symbols(all_bars);
 
     // Compute deterministic dataset hash
     let dataset_hash = compute_dataset_hash(&aligned);
+    let avg_dollar_volume = aligned
+        .bars
+        .iter()
+        .filter_map(|(symbol, bars)| Some((symbol.clone(), average_dollar_volume(bars)?)))
+        .collect();
 
     if let Some(p) = progress {
         let succeeded = sources.len();
@@ -163,14 +441,83 @@ pub fn load_bars(
         sources,
         dataset_hash,
         has_synthetic,
+        data_quality_warnings,
+        truncated_ranges,
+        avg_dollar_volume,
+        vintage,
     })
 }
 
+/// End of the cached data if it falls more than `stale_after_days` short of
+/// the requested end. Uses the cache metadata, falling back to the last bar.
+fn stale_end(
+    cache: &ParquetCache,
+    symbol: &str,
+    bars: &[RawBar],
+    opts: &LoadOptions,
+) -> Option<NaiveDate> {
+    let threshold = opts.stale_after_days?;
+    let data_end = cache
+        .get_meta(symbol)
+        .map(|m| m.end_date)
+        .or_else(|| bars.last().map(|b| b.date))?;
+    ((opts.end - data_end).num_days() > i64::from(threshold)).then_some(data_end)
+}
+
+/// Download bars after `data_end` and append them to the cache as a new part
+/// holding only those bars. Returns the extended series, or `None` if the
+/// fetch fails or adds no bars.
+fn extend_cached(
+    provider: &dyn DataProvider,
+    cache: &ParquetCache,
+    symbol: &str,
+    cached: &[RawBar],
+    data_end: NaiveDate,
+    opts: &LoadOptions,
+    progress: Option<&dyn DownloadProgress>,
+) -> Result<Option<Vec<RawBar>>, LoadError> {
+    let from = data_end + chrono::Duration::days(1);
+    let (fetched, _attempts) = fetch_with_retry(provider, symbol, from, opts.end, progress);
+    let fetched = match fetched {
+        Ok(fetch_result) if !fetch_result.bars.is_empty() => fetch_result.bars,
+        _ => return Ok(None),
+    };
+    let ingested = ingest_with(fetched, &ingest_options(from, opts.end, opts.interval))?;
+
+    let last_cached = cached.last().map(|b| b.date);
+    let new_bars: Vec<RawBar> = ingested
+        .bars
+        .into_iter()
+        .filter(|b| last_cached.map_or(true, |last| b.date > last))
+        .collect();
+    if new_bars.is_empty() {
+        return Ok(None);
+    }
+    cache.write(symbol, &new_bars)?;
+    cache.write_ingest_report(symbol, &ingested.report)?;
+    let mut merged = cached.to_vec();
+    merged.extend(new_bars);
+    Ok(Some(merged))
+}
+
+/// Ingest options for bars over `[start, end]` fetched just now. Only daily
+/// bars are matched against the session still trading.
+fn ingest_options(start: NaiveDate, end: NaiveDate, interval: BarInterval) -> IngestOptions {
+    let opts = IngestOptions::daily(start, end);
+    match interval {
+        BarInterval::Daily => opts,
+        BarInterval::Weekly => IngestOptions {
+            open_session: None,
+            ..opts
+        },
+    }
+}
+
 /// Compute a deterministic BLAKE3 hash over all bar data.
 ///
 /// The hash covers dates and all OHLCV values in sorted symbol order,
 /// ensuring it's identical regardless of HashMap iteration order.
-fn compute_dataset_hash(aligned: &AlignedData) -> String {
+pub fn compute_dataset_hash(aligned: &AlignedData) -> String {
     let mut hasher = blake3::Hasher::new();
 
     // Sort symbols for deterministic ordering
@@ -295,6 +642,9 @@ mod tests {
             offline: false,
             synthetic: false,
             force: false,
+            strict: false,
+            stale_after_days: None,
+            interval: Default::default(),
         };
 
         let loaded = load_bars(&["SPY"], &cache, None, None, &opts).unwrap();
@@ -318,12 +668,24 @@ mod tests {
             offline: true,
             synthetic: false,
             force: false,
+            strict: false,
+            stale_after_days: None,
+            interval: Default::default(),
         };
 
-        let result = load_bars(&["SPY"], &cache, None, None, &opts);
-        assert!(result.is_err());
+        let result = load_bars(&["SPY", "QQQ"], &cache, None, None, &opts);
         let err = result.unwrap_err();
         assert!(err.to_string().contains("no cached data"));
+        // Every miss is reported, not just the first
+        match err {
+            LoadError::OfflineCacheMiss { missing } => {
+                let symbols: Vec<&str> = missing.iter().map(|m| m.symbol.as_str()).collect();
+                assert_eq!(symbols, ["SPY", "QQQ"]);
+                assert_eq!(missing[0].start, opts.start);
+                assert_eq!(missing[0].end, opts.end);
+            }
+            other => panic!("expected OfflineCacheMiss, got {other}"),
+        }
 
         let _ = std::fs::remove_dir_all(&dir);
     }
@@ -339,6 +701,9 @@ mod tests {
             offline: false,
             synthetic: true,
             force: false,
+            strict: false,
+            stale_after_days: None,
+            interval: Default::default(),
         };
 
         let loaded = load_bars(&["FAKE"], &cache, None, None, &opts).unwrap();
@@ -400,6 +765,9 @@ mod tests {
             offline: false,
             synthetic: false,
             force: false,
+            strict: false,
+            stale_after_days: None,
+            interval: Default::default(),
         };
 
         let loaded1 = load_bars(&["SPY"], &cache, None, None, &opts).unwrap();
@@ -410,6 +778,38 @@ mod tests {
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    #[test]
+    fn anomalies_are_reported_per_symbol() {
+        let dir = temp_cache_dir();
+        let cache = ParquetCache::new(&dir);
+        let mut bars = sample_bars();
+        bars[1].high = 98.0; // high < low
+
+        cache.write("SPY", &bars).unwrap();
+
+        let mut opts = LoadOptions {
+            start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            end: NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+            offline: true,
+            synthetic: false,
+            force: false,
+            strict: false,
+            stale_after_days: None,
+            interval: Default::default(),
+        };
+
+        let loaded = load_bars(&["SPY"], &cache, None, None, &opts).unwrap();
+        assert_eq!(loaded.data_quality_warnings.len(), 1);
+        assert_eq!(loaded.warnings_for("SPY").count(), 1);
+        assert_eq!(loaded.warnings_for("QQQ").count(), 0);
+
+        opts.strict = true;
+        let err = load_bars(&["SPY"], &cache, None, None, &opts).unwrap_err();
+        assert!(matches!(err, LoadError::BadData { count: 1, .. }));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn multi_symbol_alignment_via_loader() {
         let dir = temp_cache_dir();
@@ -454,6 +854,9 @@ mod tests {
             offline: false,
             synthetic: false,
             force: false,
+            strict: false,
+            stale_after_days: None,
+            interval: Default::default(),
         };
 
         let loaded = load_bars(&["SPY", "QQQ"], &cache, None, None, &opts).unwrap();
@@ -467,4 +870,254 @@ mod tests {
 
         let _ = std::fs::remove_dir_all(&dir);
     }
+
+    /// Provider serving synthetic bars for whatever range is requested,
+    /// recording each request.
+    struct TailProvider {
+        requests: std::sync::Mutex<Vec<(NaiveDate, NaiveDate)>>,
+    }
+
+    impl DataProvider for TailProvider {
+        fn name(&self) -> &str {
+            "tail"
+        }
+
+        fn fetch(
+            &self,
+            symbol: &str,
+            start: NaiveDate,
+            end: NaiveDate,
+        ) -> Result<trendlab_core::data::provider::FetchResult, DataError> {
+            self.requests.lock().unwrap().push((start, end));
+            Ok(trendlab_core::data::provider::FetchResult {
+                symbol: symbol.to_string(),
+                bars: generate_synthetic_bars(symbol, start, end),
+                source: DataSource::YahooFinance,
+            })
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    fn stale_opts(offline: bool, end: NaiveDate) -> LoadOptions {
+        LoadOptions {
+            start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            end,
+            offline,
+            synthetic: false,
+            force: false,
+            strict: false,
+            stale_after_days: Some(DEFAULT_STALE_AFTER_DAYS),
+            interval: Default::default(),
+        }
+    }
+
+    #[test]
+    fn stale_cache_is_extended_online() {
+        let dir = temp_cache_dir();
+        let cache = ParquetCache::new(&dir);
+        cache.write("SPY", &sample_bars()).unwrap(); // ends 2024-01-03
+        let end = NaiveDate::from_ymd_opt(2024, 3, 29).unwrap();
+        let provider = TailProvider {
+            requests: Default::default(),
+        };
+
+        let loaded = load_bars(
+            &["SPY"],
+            &cache,
+            Some(&provider),
+            None,
+            &stale_opts(false, end),
+        )
+        .unwrap();
+
+        // Only the missing tail is fetched
+        let requests = provider.requests.lock().unwrap().clone();
+        assert_eq!(
+            requests,
+            vec![(NaiveDate::from_ymd_opt(2024, 1, 4).unwrap(), end)]
+        );
+        let bars = &loaded.aligned.bars["SPY"];
+        assert_eq!(bars[0].date, sample_bars()[0].date);
+        assert_eq!(bars.last().unwrap().date, end);
+        assert_eq!(loaded.sources["SPY"], DataSource::YahooFinance);
+        assert!(loaded.truncated_range("SPY").is_none());
+        assert_eq!(cache.get_meta("SPY").unwrap().end_date, end);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn tail_refresh_appends_only_the_new_bars() {
+        let dir = temp_cache_dir();
+        let cache = ParquetCache::new(&dir);
+        cache.write("SPY", &sample_bars()).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 3, 29).unwrap();
+        let provider = TailProvider {
+            requests: Default::default(),
+        };
+
+        let loaded = load_bars(
+            &["SPY"],
+            &cache,
+            Some(&provider),
+            None,
+            &stale_opts(false, end),
+        )
+        .unwrap();
+
+        let new_bars = loaded.aligned.bars["SPY"].len() - sample_bars().len();
+        assert!(new_bars > 0);
+        let parts = cache.parts("SPY").unwrap().parts;
+        assert_eq!(parts.len(), 2);
+        let newest = parts.last().unwrap();
+        assert_eq!(newest.bar_count, new_bars);
+        assert_eq!(
+            newest.start_date,
+            NaiveDate::from_ymd_opt(2024, 1, 4).unwrap()
+        );
+        assert_eq!(
+            cache.load("SPY").unwrap().len(),
+            loaded.aligned.bars["SPY"].len()
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn stale_cache_is_flagged_offline() {
+        let dir = temp_cache_dir();
+        let cache = ParquetCache::new(&dir);
+        cache.write("SPY", &sample_bars()).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 3, 29).unwrap();
+
+        let loaded = load_bars(&["SPY"], &cache, None, None, &stale_opts(true, end)).unwrap();
+
+        let truncated = loaded.truncated_range("SPY").unwrap();
+        assert_eq!(
+            truncated.data_end,
+            NaiveDate::from_ymd_opt(2024, 1, 3).unwrap()
+        );
+        assert_eq!(truncated.requested_end, end);
+        assert_eq!(loaded.sources["SPY"], DataSource::Cache);
+        let warning = loaded
+            .warnings_for("SPY")
+            .find(|w| w.kind == AnomalyKind::TruncatedRange)
+            .unwrap();
+        assert_eq!(
+            warning.details,
+            "data ends 2024-01-03 (requested 2024-03-29)"
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn ingest_corrections_surface_as_warnings_on_later_loads() {
+        let dir = temp_cache_dir();
+        let cache = ParquetCache::new(&dir);
+        let day = |d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+        let bar = |d, close: f64| RawBar {
+            date: day(d),
+            open: close,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+            volume: 1000,
+            adj_close: close,
+        };
+        let fetched = vec![
+            bar(3, 102.0),
+            bar(2, 101.0), // out of order
+            bar(3, 102.5), // duplicate: the later row wins
+            bar(4, 103.0),
+            bar(5, 103.7), // today's partial bar
+            bar(10, 99.0), // beyond the requested end
+        ];
+        let ingest_opts = IngestOptions {
+            range: Some((day(1), day(8))),
+            drop_partial_last_bar: true,
+            open_session: Some(day(5)),
+        };
+        let ingested = ingest_with(fetched, &ingest_opts).unwrap();
+        cache.write("SPY", &ingested.bars).unwrap();
+        cache.write_ingest_report("SPY", &ingested.report).unwrap();
+
+        let opts = stale_opts(true, day(8));
+        let loaded = load_bars(&["SPY"], &cache, None, None, &opts).unwrap();
+        let closes: Vec<(NaiveDate, f64)> = loaded.aligned.bars["SPY"]
+            .iter()
+            .map(|b| (b.date, b.close))
+            .collect();
+        assert_eq!(closes, [(day(2), 101.0), (day(3), 102.5), (day(4), 103.0)]);
+        let corrections: Vec<(Option<NaiveDate>, &str)> = loaded
+            .warnings_for("SPY")
+            .filter(|w| w.kind == AnomalyKind::IngestCorrection)
+            .map(|w| (w.date, w.details.as_str()))
+            .collect();
+        assert_eq!(
+            corrections,
+            [
+                (Some(day(3)), "duplicate rows; kept the last"),
+                (None, "1 rows arrived out of date order; sorted"),
+                (
+                    Some(day(10)),
+                    "bar outside the requested range; quarantined"
+                ),
+                (
+                    Some(day(5)),
+                    "partial bar of a session still trading; dropped"
+                ),
+            ]
+        );
+
+        // A clean ingest clears the record
+        let clean = ingest_with(vec![bar(5, 104.0)], &IngestOptions::default()).unwrap();
+        cache.write("SPY", &clean.bars).unwrap();
+        cache.write_ingest_report("SPY", &clean.report).unwrap();
+        let loaded = load_bars(&["SPY"], &cache, None, None, &opts).unwrap();
+        assert_eq!(loaded.warnings_for("SPY").count(), 0);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn recent_cache_within_threshold_is_not_stale() {
+        let dir = temp_cache_dir();
+        let cache = ParquetCache::new(&dir);
+        cache.write("SPY", &sample_bars()).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 1, 8).unwrap();
+
+        let loaded = load_bars(&["SPY"], &cache, None, None, &stale_opts(true, end)).unwrap();
+        assert!(loaded.truncated_ranges.is_empty());
+        assert_eq!(loaded.warnings_for("SPY").count(), 0);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn split_halves_share_no_bars() {
+        let dir = temp_cache_dir();
+        let cache = ParquetCache::new(&dir);
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 3, 29).unwrap();
+        cache
+            .write("SPY", &generate_synthetic_bars("SPY", start, end))
+            .unwrap();
+        let loaded = load_bars(&["SPY"], &cache, None, None, &stale_opts(true, end)).unwrap();
+
+        let n = loaded.aligned.dates.len();
+        let (discovery, holdout) = loaded.split_at(40);
+        assert_eq!(discovery.aligned.dates.len(), 40);
+        assert_eq!(holdout.aligned.dates.len(), n - 40);
+        assert_eq!(discovery.aligned.bars["SPY"].len(), 40);
+        assert!(discovery.aligned.dates.last() < holdout.aligned.dates.first());
+        assert_ne!(discovery.dataset_hash, loaded.dataset_hash);
+        assert_ne!(discovery.dataset_hash, holdout.dataset_hash);
+        assert!(discovery.avg_dollar_volume("SPY").is_some());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
@@ -0,0 +1,464 @@
+//! Bar-data anomaly detection.
+//!
+//! Scans each symbol's bars at load time for patterns that usually indicate
+//! bad data rather than real market moves:
+//! - single-bar price spikes that reverse on the next bar (bad ticks)
+//! - runs of identical closes (stale data)
+//! - zero-volume bars on an otherwise liquid symbol
+//! - OHLC violations (high < low, open/close outside the bar's range)
+//!
+//! Corrections the ingest pipeline made when the bars were cached (see
+//! [`ingest_warnings`]) are reported the same way.
+//!
+//! Each finding becomes a structured [`DataQualityWarning`] that travels with
+//! the loaded data into every `BacktestResult` for that symbol.
+
+use std::fmt;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer, Serialize};
+use trendlab_core::data::ingest::IngestReport;
+use trendlab_core::data::provider::RawBar;
+
+/// Category of a data quality warning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AnomalyKind {
+    /// Single-bar move beyond the sigma threshold that reverses next bar.
+    PriceSpike,
+    /// Run of identical closes longer than the stale-run threshold.
+    StalePrice,
+    /// Zero volume on a symbol that normally trades.
+    ZeroVolume,
+    /// High below low, or open/close outside the high-low range.
+    OhlcViolation,
+    /// Void-bar rate above the engine's threshold.
+    VoidBarRate,
+    /// Cached data ends well before the requested end date.
+    TruncatedRange,
+    /// Provider rows the ingest pipeline corrected when caching: duplicates,
+    /// misordered or out-of-range rows, a partial last bar.
+    IngestCorrection,
+    /// Free-form warning (e.g. loaded from an older artifact).
+    Other,
+}
+
+impl AnomalyKind {
+    /// Severe anomalies corrupt prices directly and can fail a strict load.
+    pub fn is_severe(&self) -> bool {
+        matches!(self, AnomalyKind::PriceSpike | AnomalyKind::OhlcViolation)
+    }
+}
+
+impl fmt::Display for AnomalyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            AnomalyKind::PriceSpike => "price spike",
+            AnomalyKind::StalePrice => "stale price",
+            AnomalyKind::ZeroVolume => "zero volume",
+            AnomalyKind::OhlcViolation => "OHLC violation",
+            AnomalyKind::VoidBarRate => "void bar rate",
+            AnomalyKind::TruncatedRange => "truncated range",
+            AnomalyKind::IngestCorrection => "ingest correction",
+            AnomalyKind::Other => "other",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A single data quality finding for one symbol.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataQualityWarning {
+    pub kind: AnomalyKind,
+    /// Date of the offending bar (start of the run for stale prices).
+    /// None for symbol-wide warnings such as void-bar rate.
+    pub date: Option<NaiveDate>,
+    pub symbol: String,
+    pub details: String,
+}
+
+impl DataQualityWarning {
+    pub fn new(
+        kind: AnomalyKind,
+        date: Option<NaiveDate>,
+        symbol: impl Into<String>,
+        details: impl Into<String>,
+    ) -> Self {
+        Self {
+            kind,
+            date,
+            symbol: symbol.into(),
+            details: details.into(),
+        }
+    }
+}
+
+impl fmt::Display for DataQualityWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.date {
+            Some(date) => write!(
+                f,
+                "{} {date}: {} — {}",
+                self.symbol, self.kind, self.details
+            ),
+            None => write!(f, "{}: {} — {}", self.symbol, self.kind, self.details),
+        }
+    }
+}
+
+/// Deserialize warnings that may be either structured or legacy bare strings.
+///
+/// Artifacts written before structured warnings stored `Vec<String>`.
+pub(crate) fn deserialize_warnings<'de, D>(
+    deserializer: D,
+) -> Result<Vec<DataQualityWarning>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        Structured(DataQualityWarning),
+        Legacy(String),
+    }
+
+    let items: Vec<Repr> = Vec::deserialize(deserializer)?;
+    Ok(items
+        .into_iter()
+        .map(|r| match r {
+            Repr::Structured(w) => w,
+            Repr::Legacy(s) => DataQualityWarning::new(AnomalyKind::Other, None, "", s),
+        })
+        .collect())
+}
+
+/// Thresholds for the anomaly scan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnomalyThresholds {
+    /// A close-to-close move beyond this many robust sigmas is a spike candidate.
+    pub spike_sigma: f64,
+    /// Runs of identical closes longer than this many bars are flagged stale.
+    pub stale_run: usize,
+    /// Median volume at or above which a symbol counts as liquid.
+    pub liquid_volume: u64,
+    /// Under strict loading, more severe anomalies than this fail the load.
+    pub max_severe: usize,
+}
+
+impl Default for AnomalyThresholds {
+    fn default() -> Self {
+        Self {
+            spike_sigma: 8.0,
+            stale_run: 5,
+            liquid_volume: 10_000,
+            max_severe: 0,
+        }
+    }
+}
+
+/// Scan one symbol's bars (sorted ascending, no void bars) for anomalies.
+pub fn scan_bars(
+    symbol: &str,
+    bars: &[RawBar],
+    thresholds: &AnomalyThresholds,
+) -> Vec<DataQualityWarning> {
+    let mut warnings = Vec::new();
+    let valid: Vec<&RawBar> = bars
+        .iter()
+        .filter(|b| {
+            b.open.is_finite() && b.high.is_finite() && b.low.is_finite() && b.close.is_finite()
+        })
+        .collect();
+
+    scan_ohlc(symbol, &valid, &mut warnings);
+    scan_spikes(symbol, &valid, thresholds.spike_sigma, &mut warnings);
+    scan_stale(symbol, &valid, thresholds.stale_run, &mut warnings);
+    scan_zero_volume(symbol, &valid, thresholds.liquid_volume, &mut warnings);
+
+    warnings.sort_by_key(|w| w.date);
+    warnings
+}
+
+/// One warning per correction in `report`, dated where the correction is.
+pub fn ingest_warnings(symbol: &str, report: &IngestReport) -> Vec<DataQualityWarning> {
+    let warning = |date, details: String| {
+        DataQualityWarning::new(AnomalyKind::IngestCorrection, date, symbol, details)
+    };
+    let mut warnings: Vec<DataQualityWarning> = report
+        .duplicate_dates
+        .iter()
+        .map(|&date| warning(Some(date), "duplicate rows; kept the last".into()))
+        .collect();
+    if report.out_of_order > 0 {
+        warnings.push(warning(
+            None,
+            format!(
+                "{} rows arrived out of date order; sorted",
+                report.out_of_order
+            ),
+        ));
+    }
+    warnings.extend(report.out_of_range.iter().map(|&date| {
+        warning(
+            Some(date),
+            "bar outside the requested range; quarantined".into(),
+        )
+    }));
+    if let Some(date) = report.partial_bar {
+        warnings.push(warning(
+            Some(date),
+            "partial bar of a session still trading; dropped".into(),
+        ));
+    }
+    warnings
+}
+
+fn scan_ohlc(symbol: &str, bars: &[&RawBar], out: &mut Vec<DataQualityWarning>) {
+    for bar in bars {
+        let details = if bar.high < bar.low {
+            format!("high {:.4} < low {:.4}", bar.high, bar.low)
+        } else if bar.open > bar.high || bar.open < bar.low {
+            format!(
+                "open {:.4} outside [{:.4}, {:.4}]",
+                bar.open, bar.low, bar.high
+            )
+        } else if bar.close > bar.high || bar.close < bar.low {
+            format!(
+                "close {:.4} outside [{:.4}, {:.4}]",
+                bar.close, bar.low, bar.high
+            )
+        } else {
+            continue;
+        };
+        out.push(DataQualityWarning::new(
+            AnomalyKind::OhlcViolation,
+            Some(bar.date),
+            symbol,
+            details,
+        ));
+    }
+}
+
+/// Flag bars whose close-to-close log return exceeds `sigma` robust standard
+/// deviations and is at least half reversed by the following bar.
+///
+/// Sigma is estimated from the interquartile range so a handful of bad ticks
+/// cannot inflate it enough to hide themselves.
+fn scan_spikes(symbol: &str, bars: &[&RawBar], sigma: f64, out: &mut Vec<DataQualityWarning>) {
+    if bars.len() < 3 {
+        return;
+    }
+    let returns: Vec<f64> = bars
+        .windows(2)
+        .map(|w| (w[1].close / w[0].close).ln())
+        .collect();
+    if returns.iter().any(|r| !r.is_finite()) {
+        return;
+    }
+
+    let mut sorted = returns.clone();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let robust_sd = (quantile(&sorted, 0.75) - quantile(&sorted, 0.25)) / 1.349;
+    if robust_sd <= 0.0 {
+        return;
+    }
+    let limit = sigma * robust_sd;
+
+    for i in 0..returns.len() - 1 {
+        let r = returns[i];
+        let next = returns[i + 1];
+        if r.abs() > limit && r.signum() != next.signum() && next.abs() >= r.abs() * 0.5 {
+            let bar = bars[i + 1];
+            out.push(DataQualityWarning::new(
+                AnomalyKind::PriceSpike,
+                Some(bar.date),
+                symbol,
+                format!(
+                    "close {:.4} moved {:+.1}% ({:.1} sigma) and reversed next bar",
+                    bar.close,
+                    (r.exp() - 1.0) * 100.0,
+                    r.abs() / robust_sd
+                ),
+            ));
+        }
+    }
+}
+
+fn scan_stale(symbol: &str, bars: &[&RawBar], max_run: usize, out: &mut Vec<DataQualityWarning>) {
+    let mut run_start = 0;
+    for i in 1..=bars.len() {
+        let continues = i < bars.len() && bars[i].close == bars[run_start].close;
+        if continues {
+            continue;
+        }
+        let run_len = i - run_start;
+        if run_len > max_run {
+            out.push(DataQualityWarning::new(
+                AnomalyKind::StalePrice,
+                Some(bars[run_start].date),
+                symbol,
+                format!(
+                    "close unchanged at {:.4} for {run_len} bars",
+                    bars[run_start].close
+                ),
+            ));
+        }
+        run_start = i;
+    }
+}
+
+fn scan_zero_volume(
+    symbol: &str,
+    bars: &[&RawBar],
+    liquid_volume: u64,
+    out: &mut Vec<DataQualityWarning>,
+) {
+    if bars.is_empty() {
+        return;
+    }
+    let mut volumes: Vec<u64> = bars.iter().map(|b| b.volume).collect();
+    volumes.sort_unstable();
+    let median_volume = volumes[volumes.len() / 2];
+    if median_volume < liquid_volume {
+        return;
+    }
+    for bar in bars.iter().filter(|b| b.volume == 0) {
+        out.push(DataQualityWarning::new(
+            AnomalyKind::ZeroVolume,
+            Some(bar.date),
+            symbol,
+            format!("zero volume (median {median_volume})"),
+        ));
+    }
+}
+
+/// Linear-interpolated quantile of an ascending-sorted slice.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let lo = pos.floor() as usize;
+    let hi = pos.ceil() as usize;
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Gently trending bars with a small deterministic wiggle.
+    fn clean_bars(n: usize) -> Vec<RawBar> {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        (0..n)
+            .map(|i| {
+                let close = 100.0 + i as f64 * 0.1 + if i % 2 == 0 { 0.3 } else { -0.3 };
+                RawBar {
+                    date: start + chrono::Duration::days(i as i64),
+                    open: close - 0.1,
+                    high: close + 1.0,
+                    low: close - 1.0,
+                    close,
+                    volume: 1_000_000,
+                    adj_close: close,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn clean_data_has_no_warnings() {
+        let bars = clean_bars(100);
+        assert!(scan_bars("SPY", &bars, &AnomalyThresholds::default()).is_empty());
+    }
+
+    #[test]
+    fn detects_reversing_spike() {
+        let mut bars = clean_bars(100);
+        bars[50].close *= 1.5;
+        bars[50].high = bars[50].close + 1.0;
+        let warnings = scan_bars("SPY", &bars, &AnomalyThresholds::default());
+        let spikes: Vec<_> = warnings
+            .iter()
+            .filter(|w| w.kind == AnomalyKind::PriceSpike)
+            .collect();
+        assert_eq!(spikes.len(), 1);
+        assert_eq!(spikes[0].date, Some(bars[50].date));
+    }
+
+    #[test]
+    fn persistent_jump_is_not_a_spike() {
+        let mut bars = clean_bars(100);
+        for bar in bars.iter_mut().skip(50) {
+            bar.open *= 1.5;
+            bar.high *= 1.5;
+            bar.low *= 1.5;
+            bar.close *= 1.5;
+        }
+        let warnings = scan_bars("SPY", &bars, &AnomalyThresholds::default());
+        assert!(warnings.iter().all(|w| w.kind != AnomalyKind::PriceSpike));
+    }
+
+    #[test]
+    fn detects_stale_run() {
+        let mut bars = clean_bars(40);
+        for bar in bars.iter_mut().skip(10).take(8) {
+            bar.close = 105.0;
+            bar.open = 105.0;
+            bar.high = 105.5;
+            bar.low = 104.5;
+        }
+        let warnings = scan_bars("SPY", &bars, &AnomalyThresholds::default());
+        let stale: Vec<_> = warnings
+            .iter()
+            .filter(|w| w.kind == AnomalyKind::StalePrice)
+            .collect();
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].date, Some(bars[10].date));
+        assert!(stale[0].details.contains("8 bars"));
+    }
+
+    #[test]
+    fn detects_zero_volume_on_liquid_symbol_only() {
+        let mut bars = clean_bars(20);
+        bars[5].volume = 0;
+        let warnings = scan_bars("SPY", &bars, &AnomalyThresholds::default());
+        assert_eq!(
+            warnings
+                .iter()
+                .filter(|w| w.kind == AnomalyKind::ZeroVolume)
+                .count(),
+            1
+        );
+
+        for bar in &mut bars {
+            bar.volume = 100;
+        }
+        bars[5].volume = 0;
+        let warnings = scan_bars("TINY", &bars, &AnomalyThresholds::default());
+        assert!(warnings.iter().all(|w| w.kind != AnomalyKind::ZeroVolume));
+    }
+
+    #[test]
+    fn detects_ohlc_violation() {
+        let mut bars = clean_bars(20);
+        bars[3].high = bars[3].low - 1.0;
+        let warnings = scan_bars("SPY", &bars, &AnomalyThresholds::default());
+        assert!(warnings
+            .iter()
+            .any(|w| w.kind == AnomalyKind::OhlcViolation && w.date == Some(bars[3].date)));
+        assert!(AnomalyKind::OhlcViolation.is_severe());
+        assert!(!AnomalyKind::StalePrice.is_severe());
+    }
+
+    #[test]
+    fn legacy_string_warnings_deserialize() {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            #[serde(deserialize_with = "deserialize_warnings")]
+            warnings: Vec<DataQualityWarning>,
+        }
+        let json = r#"{"warnings": ["SPY: 12% void bars", {"kind": "StalePrice", "date": "2024-01-02", "symbol": "QQQ", "details": "x"}]}"#;
+        let w: Wrapper = serde_json::from_str(json).unwrap();
+        assert_eq!(w.warnings.len(), 2);
+        assert_eq!(w.warnings[0].kind, AnomalyKind::Other);
+        assert_eq!(w.warnings[0].details, "SPY: 12% void bars");
+        assert_eq!(w.warnings[1].kind, AnomalyKind::StalePrice);
+    }
+}
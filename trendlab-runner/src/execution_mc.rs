@@ -1,27 +1,47 @@
-//! Execution Monte Carlo — sensitivity analysis for slippage, commission, and
-//! path policy.
+//! Execution Monte Carlo — sensitivity analysis for slippage, commission,
+//! path policy, and limit fill policy.
 //!
 //! Samples execution parameters from uniform distributions, runs backtests with
 //! each sample, and computes a stability score that rewards high median performance
 //! with low variance.
+//!
+//! The friction ramp complements the random sampling: it reruns a config at
+//! evenly spaced slippage levels with commissions fixed, and reports where
+//! the strategy's Sharpe reaches zero.
+//!
+//! An optional volume shock adds liquidity to the sampled dimensions: each
+//! sample is rerun a second time with its bar volumes haircut under a
+//! participation cap, so large orders fill partially and their remainders
+//! carry or cancel.
+//!
+//! The trigger-margin MC targets stop and limit entries instead of prices:
+//! entries whose bar only just crossed the trigger are randomly treated as
+//! unfilled, and metrics are recomputed without them.
 
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use trendlab_core::components::execution::{GapPolicy, PathPolicy};
+use trendlab_core::components::execution::{ExecutionPreset, GapPolicy, PathPolicy};
 use trendlab_core::data::align::AlignedData;
-use trendlab_core::engine::execution::CostModel;
-use trendlab_core::engine::ExecutionConfig;
+use trendlab_core::domain::{PositionSide, TradeRecord};
+use trendlab_core::engine::execution::{
+    CommissionModel, CostModel, LimitFillPolicy, LiquidityPolicy, RegulatoryFees, RemainderPolicy,
+};
+use trendlab_core::engine::{ExecutionConfig, IndicatorCache};
 use trendlab_core::fingerprint::{StrategyConfig, TradingMode};
 
-use crate::runner::{run_backtest_with_exec_config, RunError};
+use crate::metrics::PerformanceMetrics;
+use crate::runner::{
+    run_backtest_with_exec_config, run_backtest_with_exec_config_cached, BacktestResult, RunError,
+};
 
 // ─── Configuration ───────────────────────────────────────────────────
 
 /// Configuration for execution Monte Carlo sampling.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ExecutionMcConfig {
     /// Number of MC samples (default 20).
     pub n_samples: usize,
@@ -29,8 +49,19 @@ pub struct ExecutionMcConfig {
     pub slippage_range: (f64, f64),
     /// Commission range in basis points: (min, max). Uniform sampling.
     pub commission_range: (f64, f64),
+    /// Multiplier range for non-bps commission models (per-share, per-trade,
+    /// tiered): (min, max). Replaces `commission_range` for those strategies.
+    #[serde(default = "default_commission_scale_range")]
+    pub commission_scale_range: (f64, f64),
     /// Path policies to sample from.
     pub path_policies: Vec<PathPolicy>,
+    /// Limit fill policies to sample from, to see how much a limit-entry
+    /// strategy leans on touch fills. Empty keeps the strategy's own policy.
+    #[serde(default)]
+    pub limit_fill_policies: Vec<LimitFillPolicy>,
+    /// Liquidity shock rerun for every sample. `None` samples prices only.
+    #[serde(default)]
+    pub volume_shock: Option<VolumeShockConfig>,
     /// RNG seed for reproducibility.
     pub seed: u64,
 }
@@ -41,26 +72,155 @@ impl Default for ExecutionMcConfig {
             n_samples: 20,
             slippage_range: (0.0, 30.0),
             commission_range: (0.0, 20.0),
-            path_policies: vec![PathPolicy::Deterministic, PathPolicy::WorstCase, PathPolicy::BestCase],
+            commission_scale_range: default_commission_scale_range(),
+            path_policies: vec![
+                PathPolicy::Deterministic,
+                PathPolicy::WorstCase,
+                PathPolicy::BestCase,
+            ],
+            limit_fill_policies: Vec::new(),
+            volume_shock: None,
+            seed: 42,
+        }
+    }
+}
+
+fn default_commission_scale_range() -> (f64, f64) {
+    (0.5, 2.0)
+}
+
+/// Random liquidity shock applied on top of each price sample.
+///
+/// Capping fills at `max_participation` of volume scaled by a multiplier
+/// `m` is the same as capping them at `max_participation * m` of the
+/// recorded volume, so trials run through the execution engine with that
+/// tighter cap and the bar data (and any volume indicators) stay untouched.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VolumeShockConfig {
+    /// Multiplier on every bar's volume: (min, max). Uniform sampling.
+    pub volume_multiplier_range: (f64, f64),
+    /// Largest fill as a fraction of the (shocked) bar volume (default 0.10).
+    pub max_participation: f64,
+    /// What happens to the unfilled part of a capped order.
+    pub remainder: RemainderPolicy,
+}
+
+impl Default for VolumeShockConfig {
+    fn default() -> Self {
+        Self {
+            volume_multiplier_range: (0.3, 0.7),
+            max_participation: 0.10,
+            remainder: RemainderPolicy::Carry,
+        }
+    }
+}
+
+impl VolumeShockConfig {
+    /// Liquidity policy for a trial with volumes scaled by `multiplier`.
+    pub fn policy(&self, multiplier: f64) -> LiquidityPolicy {
+        let participation = (self.max_participation * multiplier).clamp(0.0, 1.0);
+        LiquidityPolicy::new(participation, self.remainder)
+    }
+}
+
+/// Slippage ramp for friction sensitivity analysis.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FrictionRampConfig {
+    /// Highest slippage tested, in basis points (default 50).
+    pub max_slippage_bps: f64,
+    /// Distance between ramp points, in basis points (default 5).
+    pub step_bps: f64,
+}
+
+impl Default for FrictionRampConfig {
+    fn default() -> Self {
+        Self {
+            max_slippage_bps: 50.0,
+            step_bps: 5.0,
+        }
+    }
+}
+
+impl FrictionRampConfig {
+    /// Slippage levels from 0 to `max_slippage_bps` inclusive. A non-positive
+    /// step yields the frictionless level only.
+    pub fn levels(&self) -> Vec<f64> {
+        if !self.step_bps.is_finite() || self.step_bps <= 0.0 || self.max_slippage_bps <= 0.0 {
+            return vec![0.0];
+        }
+        let steps = (self.max_slippage_bps / self.step_bps + 1e-9).floor() as usize;
+        (0..=steps).map(|i| i as f64 * self.step_bps).collect()
+    }
+}
+
+/// Configuration for the trigger-margin Monte Carlo.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TriggerMarginMcConfig {
+    /// Trigger margin (penetration as a fraction of the trigger price) below
+    /// which an entry counts as borderline (default 0.002 = 20 bps). May be
+    /// written as `"20bps"` (see [`crate::metrics::fraction`]).
+    #[serde(with = "crate::metrics::fraction")]
+    pub epsilon: f64,
+    /// Number of trials (default 200).
+    pub n_trials: usize,
+    /// RNG seed for reproducibility.
+    pub seed: u64,
+}
+
+impl Default for TriggerMarginMcConfig {
+    fn default() -> Self {
+        Self {
+            epsilon: 0.002,
+            n_trials: 200,
             seed: 42,
         }
     }
 }
 
+impl TriggerMarginMcConfig {
+    /// Probability that an entry with this trigger margin is treated as
+    /// unfilled: 1 at zero margin, falling linearly to 0 at `epsilon`.
+    /// Entries without a margin (market orders) are never dropped.
+    pub fn drop_probability(&self, margin: Option<f64>) -> f64 {
+        match margin {
+            Some(m) if self.epsilon > 0.0 && m < self.epsilon => {
+                (1.0 - m.max(0.0) / self.epsilon).clamp(0.0, 1.0)
+            }
+            _ => 0.0,
+        }
+    }
+}
+
 // ─── Result types ────────────────────────────────────────────────────
 
 /// A single MC sample: execution parameters and resulting metrics.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct McSample {
     pub slippage_bps: f64,
+    /// Sampled bps commission; 0.0 when the strategy uses a non-bps model.
     pub commission_bps: f64,
+    /// Multiplier applied to a non-bps commission model; 1.0 for bps.
+    #[serde(default = "default_commission_scale")]
+    pub commission_scale: f64,
     pub path_policy: PathPolicy,
+    #[serde(default)]
+    pub limit_fill: LimitFillPolicy,
+    /// Volume multiplier of a volume-shock trial; `None` for price samples.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume_multiplier: Option<f64>,
     pub sharpe: f64,
     pub cagr: f64,
     pub max_drawdown: f64,
     pub trade_count: usize,
 }
 
+fn default_commission_scale() -> f64 {
+    1.0
+}
+
 /// Stability score: summarizes the distribution of outcomes across MC samples.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StabilityScore {
@@ -79,10 +239,115 @@ pub struct StabilityScore {
 /// Complete result of execution MC.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionMcResult {
+    /// Price-friction samples.
     pub samples: Vec<McSample>,
+    /// Sharpe distribution across `samples`.
+    pub stability: StabilityScore,
+    /// Volume-shock trials, one per price sample with the same friction.
+    /// Empty when the volume shock is disabled.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub volume_samples: Vec<McSample>,
+    /// Sharpe distribution across `volume_samples`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume_stability: Option<StabilityScore>,
+    /// Sharpe distribution across price and volume samples pooled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub combined_stability: Option<StabilityScore>,
+}
+
+impl ExecutionMcResult {
+    /// The combined score when a volume shock ran, else the price-only one.
+    pub fn overall_stability(&self) -> &StabilityScore {
+        self.combined_stability.as_ref().unwrap_or(&self.stability)
+    }
+}
+
+/// One trigger-margin trial: how many borderline entries were dropped and
+/// the metrics without them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriggerMarginTrial {
+    pub dropped: usize,
+    pub sharpe: f64,
+    pub cagr: f64,
+    pub max_drawdown: f64,
+    pub trade_count: usize,
+}
+
+/// Robustness of a strategy to borderline stop/limit entry fills.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriggerMarginMcResult {
+    /// Borderline threshold the trials used.
+    pub epsilon: f64,
+    /// Trades in the original backtest.
+    pub trade_count: usize,
+    /// Trades whose entry margin was below `epsilon`.
+    pub borderline_count: usize,
+    /// Net PnL of the borderline trades.
+    pub borderline_pnl: f64,
+    /// Share of total net PnL earned by borderline trades. `None` when the
+    /// backtest did not make money overall.
+    pub borderline_profit_fraction: Option<f64>,
+    /// Sharpe of the original backtest, with every trade filled.
+    pub baseline_sharpe: f64,
+    pub trials: Vec<TriggerMarginTrial>,
+    /// Sharpe distribution across trials.
     pub stability: StabilityScore,
 }
 
+/// Sharpe at one slippage level of a friction ramp.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FrictionPoint {
+    pub slippage_bps: f64,
+    pub sharpe: f64,
+}
+
+/// How a strategy's Sharpe decays as slippage rises, commissions held fixed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrictionSensitivity {
+    /// Symbol the ramp was run on.
+    pub symbol: String,
+    /// Ramp points in ascending slippage order.
+    pub points: Vec<FrictionPoint>,
+    /// Least-squares line through the points: Sharpe per bps of slippage.
+    pub fit_slope: f64,
+    /// Least-squares line through the points: Sharpe at zero slippage.
+    pub fit_intercept: f64,
+    /// Slippage (bps) at which Sharpe reaches zero.
+    ///
+    /// Interpolated between the first pair of points straddling zero, or
+    /// extrapolated from the fitted line when the ramp never gets there.
+    /// `None` if Sharpe does not fall with slippage.
+    pub break_even_bps: Option<f64>,
+    /// Slippage of the realistic execution preset.
+    pub preset_slippage_bps: f64,
+    /// Local Sharpe change per bps of slippage around the realistic preset.
+    pub slope_at_preset: f64,
+}
+
+impl FrictionSensitivity {
+    /// Fit the Sharpe-vs-slippage curve. `points` must be sorted by slippage;
+    /// points with a non-finite Sharpe are dropped.
+    pub fn from_points(symbol: &str, points: Vec<FrictionPoint>, preset_slippage_bps: f64) -> Self {
+        let points: Vec<FrictionPoint> = points
+            .into_iter()
+            .filter(|p| p.sharpe.is_finite())
+            .collect();
+        let (fit_slope, fit_intercept) = least_squares(&points);
+        let break_even_bps = break_even(&points, fit_slope, fit_intercept);
+        let slope_at_preset = local_slope(&points, preset_slippage_bps).unwrap_or(fit_slope);
+
+        Self {
+            symbol: symbol.to_string(),
+            points,
+            fit_slope,
+            fit_intercept,
+            break_even_bps,
+            preset_slippage_bps,
+            slope_at_preset,
+        }
+    }
+}
+
 /// Errors from execution MC.
 #[derive(Debug, Error)]
 pub enum McError {
@@ -99,6 +364,12 @@ pub enum McError {
 // ─── MC execution ────────────────────────────────────────────────────
 
 /// Run execution Monte Carlo: sample execution parameters and run backtests.
+///
+/// Strategies whose execution params select a per-share, per-trade, or tiered
+/// commission model have that model scaled by a sampled multiplier instead of
+/// being replaced by a bps rate. Regulatory fees are held fixed. With
+/// `mc_config.volume_shock` set, every sample is rerun once more with a
+/// sampled volume haircut and the participation cap.
 #[allow(clippy::too_many_arguments)]
 pub fn run_execution_mc(
     strategy_config: &StrategyConfig,
@@ -112,22 +383,270 @@ pub fn run_execution_mc(
 ) -> Result<ExecutionMcResult, McError> {
     let mut rng = StdRng::seed_from_u64(mc_config.seed);
     let mut samples = Vec::with_capacity(mc_config.n_samples);
+    let mut volume_samples = Vec::new();
+
+    let exec_params = &strategy_config.execution_model.params;
+    let base_commission = CommissionModel::from_params(exec_params)
+        .filter(|m| !matches!(m, CommissionModel::Bps { .. }));
+    let regulatory_fees = RegulatoryFees::from_params(exec_params);
+    let base_limit_fill = LimitFillPolicy::from_params(exec_params).unwrap_or_default();
+    let precise_accounting = ExecutionConfig::frictionless()
+        .with_params(exec_params)
+        .precise_accounting;
 
     for i in 0..mc_config.n_samples {
         let slippage_bps = rng.gen_range(mc_config.slippage_range.0..=mc_config.slippage_range.1);
-        let commission_bps =
-            rng.gen_range(mc_config.commission_range.0..=mc_config.commission_range.1);
+        let (commission, commission_bps, commission_scale) = match &base_commission {
+            Some(model) => {
+                let (lo, hi) = mc_config.commission_scale_range;
+                let scale = rng.gen_range(lo..=hi);
+                (model.scaled(scale), 0.0, scale)
+            }
+            None => {
+                let bps =
+                    rng.gen_range(mc_config.commission_range.0..=mc_config.commission_range.1);
+                (CommissionModel::Bps { rate: bps }, bps, 1.0)
+            }
+        };
         let policy_idx = rng.gen_range(0..mc_config.path_policies.len());
         let path_policy = mc_config.path_policies[policy_idx];
+        let limit_fill = if mc_config.limit_fill_policies.is_empty() {
+            base_limit_fill
+        } else {
+            let idx = rng.gen_range(0..mc_config.limit_fill_policies.len());
+            mc_config.limit_fill_policies[idx]
+        };
 
         let exec_config = ExecutionConfig {
-            cost_model: CostModel::new(slippage_bps, commission_bps),
+            cost_model: CostModel::new(slippage_bps, 0.0)
+                .with_commission(commission)
+                .with_regulatory_fees(regulatory_fees),
             path_policy,
             gap_policy: GapPolicy::FillAtOpen, // realistic default
             liquidity: None,
+            limit_fill,
+            precise_accounting,
+        };
+
+        let shock = mc_config.volume_shock.as_ref().map(|shock| {
+            let (lo, hi) = shock.volume_multiplier_range;
+            let multiplier = rng.gen_range(lo..=hi);
+            let mut shocked = exec_config.clone();
+            shocked.liquidity = Some(shock.policy(multiplier));
+            (multiplier, shocked)
+        });
+
+        let run = |exec_config| {
+            run_backtest_with_exec_config(
+                strategy_config,
+                aligned,
+                symbol,
+                trading_mode,
+                initial_capital,
+                position_size_pct,
+                exec_config,
+                dataset_hash,
+                false,
+            )
+            .map_err(|e| McError::BacktestFailed {
+                sample: i,
+                source: e,
+            })
+        };
+        let sample = |result: BacktestResult, volume_multiplier| McSample {
+            slippage_bps,
+            commission_bps,
+            commission_scale,
+            path_policy,
+            limit_fill,
+            volume_multiplier,
+            sharpe: result.metrics.sharpe,
+            cagr: result.metrics.cagr,
+            max_drawdown: result.metrics.max_drawdown,
+            trade_count: result.metrics.trade_count,
         };
 
-        let result = run_backtest_with_exec_config(
+        samples.push(sample(run(exec_config)?, None));
+        if let Some((multiplier, shocked)) = shock {
+            volume_samples.push(sample(run(shocked)?, Some(multiplier)));
+        }
+    }
+
+    if samples.is_empty() {
+        return Err(McError::NoSamples);
+    }
+
+    let stability = compute_stability(&samples);
+    let (volume_stability, combined_stability) = if volume_samples.is_empty() {
+        (None, None)
+    } else {
+        let pooled: Vec<McSample> = samples.iter().chain(&volume_samples).cloned().collect();
+        (
+            Some(compute_stability(&volume_samples)),
+            Some(compute_stability(&pooled)),
+        )
+    };
+
+    Ok(ExecutionMcResult {
+        samples,
+        stability,
+        volume_samples,
+        volume_stability,
+        combined_stability,
+    })
+}
+
+// ─── Trigger-margin MC ───────────────────────────────────────────────
+
+/// Run the trigger-margin Monte Carlo on a finished backtest.
+///
+/// Each trial drops every borderline entry with
+/// [`TriggerMarginMcConfig::drop_probability`] and removes the dropped
+/// trades from the equity curve: marked to market against `closes` while
+/// open, their net PnL once closed. `closes` must be aligned with
+/// `result.equity_curve`. Sizing of the remaining trades is left as it was,
+/// so this is a first-order estimate rather than a rerun.
+pub fn run_trigger_margin_mc(
+    result: &BacktestResult,
+    closes: &[f64],
+    config: &TriggerMarginMcConfig,
+) -> Result<TriggerMarginMcResult, McError> {
+    trigger_margin_trials(
+        &result.trades,
+        &result.equity_curve,
+        closes,
+        result.initial_capital,
+        result.metrics.sharpe,
+        config,
+    )
+}
+
+fn trigger_margin_trials(
+    trades: &[TradeRecord],
+    equity_curve: &[f64],
+    closes: &[f64],
+    initial_capital: f64,
+    baseline_sharpe: f64,
+    config: &TriggerMarginMcConfig,
+) -> Result<TriggerMarginMcResult, McError> {
+    if config.n_trials == 0 {
+        return Err(McError::NoSamples);
+    }
+    let mut rng = StdRng::seed_from_u64(config.seed);
+
+    let drop_p: Vec<f64> = trades
+        .iter()
+        .map(|t| config.drop_probability(t.entry_trigger_margin))
+        .collect();
+    let borderline: Vec<usize> = (0..drop_p.len()).filter(|&i| drop_p[i] > 0.0).collect();
+    let borderline_pnl: f64 = borderline.iter().map(|&i| trades[i].net_pnl).sum();
+    let total_pnl: f64 = trades.iter().map(|t| t.net_pnl).sum();
+    let borderline_profit_fraction = (total_pnl > 0.0).then(|| borderline_pnl / total_pnl);
+
+    let closes = carry_forward(closes);
+    let mut trials = Vec::with_capacity(config.n_trials);
+    for _ in 0..config.n_trials {
+        let mut equity = equity_curve.to_vec();
+        let mut kept = Vec::with_capacity(trades.len());
+        let mut dropped = 0;
+        for (trade, &p) in trades.iter().zip(&drop_p) {
+            if p > 0.0 && rng.gen::<f64>() < p {
+                remove_trade(&mut equity, trade, &closes);
+                dropped += 1;
+            } else {
+                kept.push(trade.clone());
+            }
+        }
+        let metrics = PerformanceMetrics::compute(&equity, &kept, initial_capital);
+        trials.push(TriggerMarginTrial {
+            dropped,
+            sharpe: metrics.sharpe,
+            cagr: metrics.cagr,
+            max_drawdown: metrics.max_drawdown,
+            trade_count: kept.len(),
+        });
+    }
+
+    let stability = stability_from_sharpes(trials.iter().map(|t| t.sharpe).collect());
+    Ok(TriggerMarginMcResult {
+        epsilon: config.epsilon,
+        trade_count: trades.len(),
+        borderline_count: borderline.len(),
+        borderline_pnl,
+        borderline_profit_fraction,
+        baseline_sharpe,
+        trials,
+        stability,
+    })
+}
+
+/// Subtract a trade's contribution from the equity curve.
+fn remove_trade(equity: &mut [f64], trade: &TradeRecord, closes: &[f64]) {
+    let direction = match trade.side {
+        PositionSide::Long => 1.0,
+        PositionSide::Short => -1.0,
+        PositionSide::Flat => return,
+    };
+    let exit = trade.exit_bar.min(equity.len());
+    for (i, eq) in equity
+        .iter_mut()
+        .enumerate()
+        .take(exit)
+        .skip(trade.entry_bar)
+    {
+        if let Some(&close) = closes.get(i).filter(|c| c.is_finite()) {
+            *eq -= direction * (close - trade.entry_price) * trade.quantity;
+        }
+    }
+    for eq in equity.iter_mut().skip(exit) {
+        *eq -= trade.net_pnl;
+    }
+}
+
+/// Replace non-finite closes (void bars) with the last finite one.
+fn carry_forward(closes: &[f64]) -> Vec<f64> {
+    let mut last = f64::NAN;
+    closes
+        .iter()
+        .map(|&c| {
+            if c.is_finite() {
+                last = c;
+            }
+            last
+        })
+        .collect()
+}
+
+// ─── Friction ramp ───────────────────────────────────────────────────
+
+/// Run the friction ramp: rerun a config at each slippage level of
+/// `ramp_config` and fit the Sharpe-vs-slippage curve.
+///
+/// Commission, regulatory fees, and path/gap policy come from the realistic
+/// preset plus the strategy's execution params and stay fixed across the
+/// ramp. Indicator series are computed once and reused for every level.
+#[allow(clippy::too_many_arguments)]
+pub fn run_friction_ramp(
+    strategy_config: &StrategyConfig,
+    aligned: &AlignedData,
+    symbol: &str,
+    ramp_config: &FrictionRampConfig,
+    trading_mode: TradingMode,
+    initial_capital: f64,
+    position_size_pct: f64,
+    dataset_hash: &str,
+) -> Result<FrictionSensitivity, McError> {
+    let preset = ExecutionPreset::Realistic;
+    let base =
+        ExecutionConfig::from_preset(preset).with_params(&strategy_config.execution_model.params);
+    let mut cache = IndicatorCache::new();
+    let mut points = Vec::new();
+
+    for (i, slippage_bps) in ramp_config.levels().into_iter().enumerate() {
+        let mut exec_config = base.clone();
+        exec_config.cost_model.slippage_bps = slippage_bps;
+
+        let result = run_backtest_with_exec_config_cached(
             strategy_config,
             aligned,
             symbol,
@@ -136,37 +655,95 @@ pub fn run_execution_mc(
             position_size_pct,
             exec_config,
             dataset_hash,
-            false,
+            &mut cache,
         )
         .map_err(|e| McError::BacktestFailed {
             sample: i,
             source: e,
         })?;
 
-        samples.push(McSample {
+        points.push(FrictionPoint {
             slippage_bps,
-            commission_bps,
-            path_policy,
             sharpe: result.metrics.sharpe,
-            cagr: result.metrics.cagr,
-            max_drawdown: result.metrics.max_drawdown,
-            trade_count: result.metrics.trade_count,
         });
     }
 
-    if samples.is_empty() {
+    let sensitivity = FrictionSensitivity::from_points(symbol, points, preset.slippage_bps());
+    if sensitivity.points.is_empty() {
         return Err(McError::NoSamples);
     }
+    Ok(sensitivity)
+}
 
-    let stability = compute_stability(&samples);
+/// Least-squares slope and intercept of Sharpe on slippage.
+fn least_squares(points: &[FrictionPoint]) -> (f64, f64) {
+    let n = points.len() as f64;
+    if points.is_empty() {
+        return (0.0, 0.0);
+    }
+    let mean_x = points.iter().map(|p| p.slippage_bps).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.sharpe).sum::<f64>() / n;
+    let sxx: f64 = points
+        .iter()
+        .map(|p| (p.slippage_bps - mean_x).powi(2))
+        .sum();
+    if sxx < 1e-15 {
+        return (0.0, mean_y);
+    }
+    let sxy: f64 = points
+        .iter()
+        .map(|p| (p.slippage_bps - mean_x) * (p.sharpe - mean_y))
+        .sum();
+    let slope = sxy / sxx;
+    (slope, mean_y - slope * mean_x)
+}
+
+/// First zero crossing of the ramp, falling back to the fitted line.
+fn break_even(points: &[FrictionPoint], slope: f64, intercept: f64) -> Option<f64> {
+    let first = points.first()?;
+    if first.sharpe <= 0.0 {
+        return Some(first.slippage_bps);
+    }
+    for w in points.windows(2) {
+        if w[1].sharpe <= 0.0 {
+            let frac = w[0].sharpe / (w[0].sharpe - w[1].sharpe);
+            return Some(w[0].slippage_bps + frac * (w[1].slippage_bps - w[0].slippage_bps));
+        }
+    }
+    if slope >= 0.0 {
+        return None;
+    }
+    // Never crossed: the break-even lies beyond the ramp
+    let last = points.last().map_or(0.0, |p| p.slippage_bps);
+    Some((-intercept / slope).max(last))
+}
 
-    Ok(ExecutionMcResult { samples, stability })
+/// Slope between the ramp points bracketing `x` (central difference when `x`
+/// falls on a point). `None` with fewer than two points.
+fn local_slope(points: &[FrictionPoint], x: f64) -> Option<f64> {
+    if points.len() < 2 {
+        return None;
+    }
+    let lo = points.iter().rposition(|p| p.slippage_bps < x).unwrap_or(0);
+    let hi = points
+        .iter()
+        .position(|p| p.slippage_bps > x)
+        .unwrap_or(points.len() - 1);
+    let (a, b) = if lo < hi {
+        (points[lo], points[hi])
+    } else {
+        (points[points.len() - 2], points[points.len() - 1])
+    };
+    Some((b.sharpe - a.sharpe) / (b.slippage_bps - a.slippage_bps))
 }
 
 // ─── Stability scoring ───────────────────────────────────────────────
 
 fn compute_stability(samples: &[McSample]) -> StabilityScore {
-    let mut sharpes: Vec<f64> = samples.iter().map(|s| s.sharpe).collect();
+    stability_from_sharpes(samples.iter().map(|s| s.sharpe).collect())
+}
+
+fn stability_from_sharpes(mut sharpes: Vec<f64>) -> StabilityScore {
     sharpes.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
     let n = sharpes.len();
@@ -285,14 +862,281 @@ mod tests {
         assert_eq!(config.n_samples, 20);
         assert_eq!(config.slippage_range, (0.0, 30.0));
         assert_eq!(config.commission_range, (0.0, 20.0));
+        assert_eq!(config.commission_scale_range, (0.5, 2.0));
         assert_eq!(config.path_policies.len(), 3);
     }
 
+    #[test]
+    fn config_without_scale_range_deserializes() {
+        let json = r#"{"n_samples":5,"slippage_range":[0.0,10.0],"commission_range":[0.0,5.0],
+            "path_policies":["WorstCase"],"seed":7}"#;
+        let config: ExecutionMcConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.commission_scale_range, (0.5, 2.0));
+    }
+
+    fn ramp(sharpes: &[f64]) -> Vec<FrictionPoint> {
+        sharpes
+            .iter()
+            .enumerate()
+            .map(|(i, &sharpe)| FrictionPoint {
+                slippage_bps: i as f64 * 5.0,
+                sharpe,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn friction_ramp_levels_default() {
+        let levels = FrictionRampConfig::default().levels();
+        assert_eq!(levels.len(), 11);
+        assert_eq!(levels[0], 0.0);
+        assert_eq!(levels[10], 50.0);
+        let degenerate = FrictionRampConfig {
+            max_slippage_bps: 50.0,
+            step_bps: 0.0,
+        };
+        assert_eq!(degenerate.levels(), vec![0.0]);
+    }
+
+    #[test]
+    fn friction_break_even_interpolates_crossing() {
+        // Sharpe falls 0.1 per bps: 1.0, 0.5, 0.0, -0.5, ...
+        let points = ramp(&[1.0, 0.5, 0.0, -0.5, -1.0]);
+        let f = FrictionSensitivity::from_points("SPY", points, 5.0);
+        assert!((f.fit_slope + 0.1).abs() < 1e-10);
+        assert!((f.fit_intercept - 1.0).abs() < 1e-10);
+        assert!((f.break_even_bps.unwrap() - 10.0).abs() < 1e-10);
+        assert!((f.slope_at_preset + 0.1).abs() < 1e-10);
+
+        let between = FrictionSensitivity::from_points("SPY", ramp(&[1.0, 0.6, -0.2]), 5.0);
+        assert!((between.break_even_bps.unwrap() - 8.75).abs() < 1e-10);
+    }
+
+    #[test]
+    fn friction_break_even_extrapolates_beyond_ramp() {
+        let f = FrictionSensitivity::from_points("SPY", ramp(&[2.0, 1.9, 1.8]), 5.0);
+        assert!((f.break_even_bps.unwrap() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn friction_break_even_edge_cases() {
+        let losing = FrictionSensitivity::from_points("SPY", ramp(&[-0.2, -0.4]), 5.0);
+        assert_eq!(losing.break_even_bps, Some(0.0));
+
+        let flat = FrictionSensitivity::from_points("SPY", ramp(&[1.0, 1.0, 1.0]), 5.0);
+        assert_eq!(flat.break_even_bps, None);
+        assert!(flat.slope_at_preset.abs() < 1e-12);
+
+        let nan = FrictionSensitivity::from_points("SPY", ramp(&[1.0, f64::NAN, 0.0]), 5.0);
+        assert_eq!(nan.points.len(), 2);
+    }
+
+    #[test]
+    fn friction_slope_at_preset_is_local() {
+        // Steep at low slippage, flat later: the local slope differs from the fit
+        let f = FrictionSensitivity::from_points("SPY", ramp(&[2.0, 1.0, 0.9, 0.8, 0.7]), 5.0);
+        assert!((f.slope_at_preset + 0.11).abs() < 1e-10);
+        assert!(f.fit_slope > f.slope_at_preset);
+    }
+
+    #[test]
+    fn trigger_margin_drop_probability() {
+        let config = TriggerMarginMcConfig::default();
+        assert_eq!(config.drop_probability(None), 0.0);
+        assert_eq!(config.drop_probability(Some(0.0)), 1.0);
+        assert!((config.drop_probability(Some(0.001)) - 0.5).abs() < 1e-12);
+        assert_eq!(config.drop_probability(Some(0.002)), 0.0);
+        assert_eq!(config.drop_probability(Some(0.05)), 0.0);
+    }
+
+    #[test]
+    fn remove_trade_marks_to_market_then_realizes() {
+        // Long 10 @ 100, open bars 1..3, closed at bar 3 for +48 net
+        let trade = make_trade(PositionSide::Long, 1, 3, 100.0, 10.0, 48.0, None);
+        let closes = [100.0, 102.0, 101.0, 105.0, 106.0];
+        let mut equity = vec![1000.0, 1020.0, 1010.0, 1048.0, 1048.0];
+        remove_trade(&mut equity, &trade, &closes);
+        assert_eq!(equity, vec![1000.0; 5]);
+    }
+
+    #[test]
+    fn remove_trade_skips_void_closes() {
+        let trade = make_trade(PositionSide::Short, 0, 2, 50.0, 2.0, 4.0, None);
+        let closes = carry_forward(&[f64::NAN, 48.0, f64::NAN]);
+        let mut equity = vec![1000.0, 1004.0, 1004.0];
+        remove_trade(&mut equity, &trade, &closes);
+        assert_eq!(equity, vec![1000.0, 1000.0, 1000.0]);
+    }
+
+    #[test]
+    fn trigger_margin_mc_reports_borderline_profit() {
+        // Two trades: a clean market entry (+100) and a zero-margin stop entry (+300)
+        let trades = vec![
+            make_trade(PositionSide::Long, 1, 2, 100.0, 1.0, 100.0, None),
+            make_trade(PositionSide::Long, 3, 4, 100.0, 1.0, 300.0, Some(0.0)),
+        ];
+        let equity = vec![1000.0, 1000.0, 1100.0, 1100.0, 1400.0, 1400.0];
+        let closes = vec![100.0; 6];
+        let config = TriggerMarginMcConfig {
+            n_trials: 10,
+            ..TriggerMarginMcConfig::default()
+        };
+        let r = trigger_margin_trials(&trades, &equity, &closes, 1000.0, 2.0, &config).unwrap();
+        assert_eq!(r.borderline_count, 1);
+        assert!((r.borderline_pnl - 300.0).abs() < 1e-12);
+        assert!((r.borderline_profit_fraction.unwrap() - 0.75).abs() < 1e-12);
+        // Zero margin: dropped in every trial
+        assert!(r
+            .trials
+            .iter()
+            .all(|t| t.dropped == 1 && t.trade_count == 1));
+        assert_eq!(r.trials.len(), 10);
+        assert!(!r.stability.all_different);
+    }
+
+    #[test]
+    fn trigger_margin_mc_losing_strategy_has_no_fraction() {
+        let long = PositionSide::Long;
+        let trades = vec![make_trade(long, 1, 2, 100.0, 1.0, -50.0, Some(0.001))];
+        let equity = vec![1000.0, 1000.0, 950.0];
+        let r = trigger_margin_trials(
+            &trades,
+            &equity,
+            &[100.0; 3],
+            1000.0,
+            -1.0,
+            &TriggerMarginMcConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(r.borderline_profit_fraction, None);
+        let zero = TriggerMarginMcConfig {
+            n_trials: 0,
+            ..TriggerMarginMcConfig::default()
+        };
+        assert!(trigger_margin_trials(&trades, &equity, &[], 1000.0, -1.0, &zero).is_err());
+    }
+
+    fn make_trade(
+        side: PositionSide,
+        entry_bar: usize,
+        exit_bar: usize,
+        entry_price: f64,
+        quantity: f64,
+        net_pnl: f64,
+        entry_trigger_margin: Option<f64>,
+    ) -> TradeRecord {
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        TradeRecord {
+            symbol: "SPY".into(),
+            side,
+            entry_bar,
+            entry_date: date,
+            entry_price,
+            entry_trigger_margin,
+            exit_bar,
+            exit_date: date,
+            exit_price: entry_price,
+            quantity,
+            gross_pnl: net_pnl,
+            commission: 0.0,
+            slippage: 0.0,
+            net_pnl,
+            bars_held: exit_bar - entry_bar,
+            mae: 0.0,
+            mfe: 0.0,
+            initial_stop_price: None,
+            entry_source: None,
+            exit_source: None,
+            liquidated_at_end: false,
+            expiry_bar: None,
+            signal_id: None,
+            signal_type: None,
+            pm_type: None,
+            execution_model: None,
+            filter_type: None,
+        }
+    }
+
+    /// MC with a volume shock on two years of synthetic bars whose volume
+    /// is replaced by `volume`.
+    fn volume_shocked_mc(volume: u64) -> ExecutionMcResult {
+        use crate::data_loader::{load_bars, LoadOptions};
+        use chrono::NaiveDate;
+        use trendlab_core::components::composition::StrategyPreset;
+        use trendlab_core::data::ParquetCache;
+
+        let opts = LoadOptions {
+            start: NaiveDate::from_ymd_opt(2022, 1, 3).unwrap(),
+            end: NaiveDate::from_ymd_opt(2023, 12, 29).unwrap(),
+            offline: true,
+            synthetic: true,
+            force: false,
+            strict: false,
+            stale_after_days: None,
+            interval: Default::default(),
+        };
+        let cache = ParquetCache::new(std::env::temp_dir().join("trendlab-volume-shock-none"));
+        let mut loaded = load_bars(&["SYN"], &cache, None, None, &opts).unwrap();
+        for bar in loaded.aligned.bars.get_mut("SYN").unwrap() {
+            bar.volume = volume;
+        }
+        let mc_config = ExecutionMcConfig {
+            n_samples: 12,
+            limit_fill_policies: Vec::new(),
+            volume_shock: Some(VolumeShockConfig::default()),
+            ..ExecutionMcConfig::default()
+        };
+        run_execution_mc(
+            &StrategyPreset::MomentumRoc.to_config(),
+            &loaded.aligned,
+            "SYN",
+            &mc_config,
+            TradingMode::LongOnly,
+            100_000.0,
+            1.0,
+            &loaded.dataset_hash,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn volume_shock_degrades_stability_on_thin_volume() {
+        let deep = volume_shocked_mc(1_000_000_000);
+        let thin = volume_shocked_mc(2_000);
+        assert_eq!(thin.volume_samples.len(), thin.samples.len());
+        assert!(thin.volume_samples.iter().all(|s| s
+            .volume_multiplier
+            .is_some_and(|m| (0.3..=0.7).contains(&m))));
+
+        // The cap never binds on deep volume: shocked trials match their
+        // price samples exactly
+        for (price, shocked) in deep.samples.iter().zip(&deep.volume_samples) {
+            assert_eq!(price.sharpe, shocked.sharpe);
+        }
+        let deep_ratio = deep.overall_stability().stability_ratio;
+        let thin_ratio = thin.overall_stability().stability_ratio;
+        assert!(
+            thin_ratio < deep_ratio,
+            "thin {thin_ratio:.3} should be less stable than deep {deep_ratio:.3}"
+        );
+    }
+
+    #[test]
+    fn volume_shock_scales_the_participation_cap() {
+        let shock = VolumeShockConfig::default();
+        let policy = shock.policy(0.5);
+        assert!((policy.max_participation - 0.05).abs() < 1e-12);
+        assert_eq!(policy.remainder, RemainderPolicy::Carry);
+    }
+
     fn make_sample(sharpe: f64) -> McSample {
         McSample {
             slippage_bps: 5.0,
             commission_bps: 5.0,
+            commission_scale: 1.0,
             path_policy: PathPolicy::WorstCase,
+            limit_fill: LimitFillPolicy::default(),
+            volume_multiplier: None,
             sharpe,
             cagr: 0.1,
             max_drawdown: -0.1,
@@ -5,15 +5,32 @@
 //! - **CSV**: trade tape and equity curve for external analysis tools
 //! - **Markdown**: human-readable single-run reports and side-by-side comparisons
 //!
+//! Artifact directories also carry the equity curve as Parquet, so viewers can
+//! list runs from manifest headline stats alone (`scan_artifacts`) and load a
+//! curve only when it is first needed (`load_equity_curve`).
+//!
 //! All persisted artifacts include a `schema_version` field. Unknown versions
 //! are rejected on load.
 
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Context, Result};
-use trendlab_core::domain::TradeRecord;
-
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use trendlab_core::domain::{PositionSide, TradeRecord};
+use trendlab_core::engine::stickiness::{PositionStickiness, StickinessMetrics, StickinessReport};
+use trendlab_core::engine::{
+    PnlAttribution, PortfolioSnapshot, PositionSnapshot, RatchetClamp, ReplayLog, SnapshotLog,
+};
+use trendlab_core::fingerprint::StrategyConfig;
+
+use crate::annotations::RunAnnotations;
+use crate::metrics::{PerformanceMetrics, RDistribution, TradeDistributions};
 use crate::runner::{BacktestResult, SCHEMA_VERSION};
+use crate::style::StyleReport;
+use crate::tail_metrics::TailMetrics;
 
 // ─── JSON export ────────────────────────────────────────────────────
 
@@ -27,11 +44,11 @@ pub fn import_json(json: &str) -> Result<BacktestResult> {
     let result: BacktestResult =
         serde_json::from_str(json).context("failed to deserialize BacktestResult from JSON")?;
     if result.schema_version > SCHEMA_VERSION {
-        bail!(
-            "unsupported schema version {} (max supported: {})",
-            result.schema_version,
-            SCHEMA_VERSION
-        );
+        return Err(UnsupportedSchema {
+            found: result.schema_version,
+            max: SCHEMA_VERSION,
+        }
+        .into());
     }
     Ok(result)
 }
@@ -42,12 +59,30 @@ pub fn import_json(json: &str) -> Result<BacktestResult> {
 ///
 /// Columns: symbol, side, entry_bar, entry_date, entry_price, exit_bar,
 /// exit_date, exit_price, quantity, gross_pnl, commission, slippage, net_pnl,
-/// bars_held, mae, mfe, signal_type, pm_type, execution_model, filter_type
+/// bars_held, mae, mfe, signal_type, pm_type, execution_model, filter_type,
+/// entry_trigger_margin, entry_source, exit_source, liquidated_at_end
+///
+/// `exit_source` is the exit reason; positions still open when the data
+/// ended read `end_of_run_liquidation` with `liquidated_at_end` true.
 pub fn export_trades_csv(trades: &[TradeRecord]) -> Result<String> {
+    trades_csv(trades, None)
+}
+
+/// Export a trade list as [`export_trades_csv`] does, plus `note` and `tags`
+/// columns from each trade's journal annotation (see [`crate::annotations`]).
+pub fn export_annotated_trades_csv(
+    trades: &[TradeRecord],
+    annotations: &RunAnnotations,
+) -> Result<String> {
+    trades_csv(trades, Some(annotations))
+}
+
+/// The note columns are written when `annotations` is given.
+fn trades_csv(trades: &[TradeRecord], annotations: Option<&RunAnnotations>) -> Result<String> {
     let mut wtr = csv::Writer::from_writer(vec![]);
 
     // Header
-    wtr.write_record([
+    let mut header = vec![
         "symbol",
         "side",
         "entry_bar",
@@ -68,31 +103,51 @@ pub fn export_trades_csv(trades: &[TradeRecord]) -> Result<String> {
         "pm_type",
         "execution_model",
         "filter_type",
-    ])?;
+        "entry_trigger_margin",
+        "entry_source",
+        "exit_source",
+        "liquidated_at_end",
+    ];
+    if annotations.is_some() {
+        header.extend(["note", "tags"]);
+    }
+    wtr.write_record(&header)?;
 
     for t in trades {
-        wtr.write_record([
-            &t.symbol,
-            &format!("{:?}", t.side),
-            &t.entry_bar.to_string(),
-            &t.entry_date.to_string(),
-            &format!("{:.6}", t.entry_price),
-            &t.exit_bar.to_string(),
-            &t.exit_date.to_string(),
-            &format!("{:.6}", t.exit_price),
-            &format!("{:.6}", t.quantity),
-            &format!("{:.2}", t.gross_pnl),
-            &format!("{:.2}", t.commission),
-            &format!("{:.2}", t.slippage),
-            &format!("{:.2}", t.net_pnl),
-            &t.bars_held.to_string(),
-            &format!("{:.2}", t.mae),
-            &format!("{:.2}", t.mfe),
-            t.signal_type.as_deref().unwrap_or(""),
-            t.pm_type.as_deref().unwrap_or(""),
-            t.execution_model.as_deref().unwrap_or(""),
-            t.filter_type.as_deref().unwrap_or(""),
-        ])?;
+        let mut record = vec![
+            t.symbol.clone(),
+            format!("{:?}", t.side),
+            t.entry_bar.to_string(),
+            t.entry_date.to_string(),
+            format!("{:.6}", t.entry_price),
+            t.exit_bar.to_string(),
+            t.exit_date.to_string(),
+            format!("{:.6}", t.exit_price),
+            format!("{:.6}", t.quantity),
+            format!("{:.2}", t.gross_pnl),
+            format!("{:.2}", t.commission),
+            format!("{:.2}", t.slippage),
+            format!("{:.2}", t.net_pnl),
+            t.bars_held.to_string(),
+            format!("{:.2}", t.mae),
+            format!("{:.2}", t.mfe),
+            t.signal_type.clone().unwrap_or_default(),
+            t.pm_type.clone().unwrap_or_default(),
+            t.execution_model.clone().unwrap_or_default(),
+            t.filter_type.clone().unwrap_or_default(),
+            t.entry_trigger_margin
+                .map(|m| format!("{:.6}", m))
+                .unwrap_or_default(),
+            t.entry_source.map(|s| s.to_string()).unwrap_or_default(),
+            t.exit_source.map(|s| s.to_string()).unwrap_or_default(),
+            t.liquidated_at_end.to_string(),
+        ];
+        if let Some(annotations) = annotations {
+            let note = annotations.trade(t.entry_bar);
+            record.push(note.map(|n| n.note.clone()).unwrap_or_default());
+            record.push(note.map(|n| n.tags_label()).unwrap_or_default());
+        }
+        wtr.write_record(&record)?;
     }
 
     let data = wtr.into_inner().context("failed to flush CSV writer")?;
@@ -119,6 +174,11 @@ pub fn export_equity_csv(equity_curve: &[f64]) -> Result<String> {
 /// - `manifest.json` — the full `BacktestResult`
 /// - `trades.csv` — trade tape with signal trace columns
 /// - `equity.csv` — bar-by-bar equity curve
+/// - `equity.parquet` — the same curve at full precision, for lazy loading
+/// - `diagnostics.json` — [`RunDiagnostics`], e.g. per-position stickiness,
+///   PnL attribution and, for single runs, the bar-by-bar position replay
+/// - `portfolio.parquet` — for single runs, the portfolio on every fill bar
+///   (see [`write_portfolio_parquet`])
 ///
 /// Returns the path to the created directory.
 pub fn save_artifacts(result: &BacktestResult, output_dir: &Path) -> Result<PathBuf> {
@@ -128,7 +188,14 @@ pub fn save_artifacts(result: &BacktestResult, output_dir: &Path) -> Result<Path
         chrono::Local::now().format("%Y%m%d_%H%M%S")
     );
     let run_dir = output_dir.join(dirname);
-    std::fs::create_dir_all(&run_dir)
+    write_artifacts(result, &run_dir)?;
+    Ok(run_dir)
+}
+
+/// Write the artifact set described in [`save_artifacts`] into `run_dir`,
+/// creating it if needed.
+pub fn write_artifacts(result: &BacktestResult, run_dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(run_dir)
         .with_context(|| format!("failed to create artifact dir: {}", run_dir.display()))?;
 
     // manifest.json
@@ -143,7 +210,59 @@ pub fn save_artifacts(result: &BacktestResult, output_dir: &Path) -> Result<Path
     let equity_csv = export_equity_csv(&result.equity_curve)?;
     std::fs::write(run_dir.join("equity.csv"), &equity_csv)?;
 
-    Ok(run_dir)
+    // equity.parquet
+    write_equity_parquet(&result.equity_curve, &run_dir.join(EQUITY_PARQUET))?;
+
+    // diagnostics.json
+    let diagnostics = RunDiagnostics {
+        stickiness: result.stickiness_report(),
+        attribution: result.attribution.clone(),
+        ratchet_clamps: result.ratchet_clamps.clone(),
+        replay: result.replay.clone(),
+    };
+    let json = serde_json::to_string_pretty(&diagnostics)
+        .context("failed to serialize run diagnostics")?;
+    std::fs::write(run_dir.join(DIAGNOSTICS_JSON), &json)?;
+
+    // portfolio.parquet
+    if !result.portfolio_snapshots.is_empty() {
+        write_portfolio_parquet(
+            &result.portfolio_snapshots,
+            &run_dir.join(PORTFOLIO_PARQUET),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// File name of the run diagnostics inside an artifact directory.
+pub const DIAGNOSTICS_JSON: &str = "diagnostics.json";
+
+/// Contents of `diagnostics.json`: what a run did, beyond its metrics.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunDiagnostics {
+    /// Run-level stickiness (as aggregated by the leaderboards) and the
+    /// per-position scores behind it.
+    pub stickiness: StickinessReport,
+    /// Per-bar PnL by decision source (empty for runs saved before it).
+    #[serde(default)]
+    pub attribution: PnlAttribution,
+    /// Stop updates clamped by the engine's ratchet (empty for runs saved
+    /// before it).
+    #[serde(default)]
+    pub ratchet_clamps: Vec<RatchetClamp>,
+    /// Bar-by-bar replay of each position (empty for sweep runs, which do
+    /// not record one, and for runs saved before it).
+    #[serde(default, skip_serializing_if = "ReplayLog::is_empty")]
+    pub replay: ReplayLog,
+}
+
+/// Load an artifact directory's diagnostics.json.
+pub fn load_diagnostics(dir: &Path) -> Result<RunDiagnostics> {
+    let path = dir.join(DIAGNOSTICS_JSON);
+    let json = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&json).with_context(|| format!("failed to parse {}", path.display()))
 }
 
 /// Load a `BacktestResult` from an artifact directory's manifest.json.
@@ -156,6 +275,477 @@ pub fn load_artifacts(dir: &Path) -> Result<BacktestResult> {
     import_json(&json)
 }
 
+// ─── Lazy artifact loading ──────────────────────────────────────────
+
+/// File name of the Parquet equity curve inside an artifact directory.
+pub const EQUITY_PARQUET: &str = "equity.parquet";
+
+/// Headline stats of a saved run, read from its manifest without keeping
+/// the equity curve or trade list in memory.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ArtifactSummary {
+    /// Artifact directory the summary was read from.
+    #[serde(skip)]
+    pub dir: PathBuf,
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
+    pub metrics: PerformanceMetrics,
+    pub config: StrategyConfig,
+    pub symbol: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub initial_capital: f64,
+    pub bar_count: usize,
+    /// Number of data quality warnings recorded in the manifest.
+    #[serde(
+        default,
+        rename = "data_quality_warnings",
+        deserialize_with = "count_entries"
+    )]
+    pub data_warning_count: usize,
+    pub stickiness: Option<StickinessMetrics>,
+    #[serde(default)]
+    pub position_stickiness: Vec<PositionStickiness>,
+    #[serde(default)]
+    pub r_distribution: RDistribution,
+    #[serde(default)]
+    pub trade_distributions: TradeDistributions,
+    #[serde(default)]
+    pub tail_metrics: Option<TailMetrics>,
+    #[serde(default)]
+    pub style: Option<StyleReport>,
+    /// Empty for manifests written before sparklines were recorded.
+    #[serde(default)]
+    pub equity_sparkline: Vec<f64>,
+    #[serde(default)]
+    pub attribution: PnlAttribution,
+    /// Top-level manifest fields this build does not know, written by a
+    /// newer build. They are ignored.
+    #[serde(skip)]
+    pub unknown_fields: Vec<String>,
+}
+
+fn default_schema_version() -> u32 {
+    SCHEMA_VERSION
+}
+
+/// Count the elements of a JSON array without materializing them.
+fn count_entries<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<usize, D::Error> {
+    let items: Vec<serde::de::IgnoredAny> = Vec::deserialize(deserializer)?;
+    Ok(items.len())
+}
+
+/// Top-level manifest keys this build knows how to read. Anything else in a
+/// manifest was written by a newer build and is ignored (but counted).
+const MANIFEST_FIELDS: &[&str] = &[
+    "schema_version",
+    "metrics",
+    "open_position_adjusted",
+    "trades",
+    "equity_curve",
+    "dates",
+    "exposure_curve",
+    "config",
+    "symbol",
+    "interval",
+    "start_date",
+    "end_date",
+    "initial_capital",
+    "dataset_hash",
+    "has_synthetic",
+    "signal_count",
+    "zero_trades",
+    "bar_count",
+    "warmup_bars",
+    "warmup",
+    "void_bar_rates",
+    "data_quality_warnings",
+    "stickiness",
+    "position_stickiness",
+    "r_distribution",
+    "trade_distributions",
+    "tail_metrics",
+    "truncated_range",
+    "style",
+    "equity_sparkline",
+    "attribution",
+    "inputs",
+    "consistency_warnings",
+    "data_vintage",
+    "provenance",
+];
+
+/// A manifest declaring a schema version newer than this build supports.
+#[derive(Debug, Error)]
+#[error("unsupported schema version {found} (max supported: {max})")]
+pub struct UnsupportedSchema {
+    pub found: u32,
+    pub max: u32,
+}
+
+/// Read the headline stats of an artifact directory's manifest.json.
+///
+/// The equity curve and trades in the manifest are skipped during parsing.
+/// Rejects unknown schema versions; unknown top-level fields are ignored
+/// and listed in [`ArtifactSummary::unknown_fields`].
+pub fn load_artifact_summary(dir: &Path) -> Result<ArtifactSummary> {
+    let manifest_path = dir.join("manifest.json");
+    let json = std::fs::read_to_string(&manifest_path)
+        .with_context(|| format!("failed to read {}", manifest_path.display()))?;
+    let mut summary: ArtifactSummary = serde_json::from_str(&json)
+        .with_context(|| format!("failed to parse {}", manifest_path.display()))?;
+    if summary.schema_version > SCHEMA_VERSION {
+        return Err(UnsupportedSchema {
+            found: summary.schema_version,
+            max: SCHEMA_VERSION,
+        }
+        .into());
+    }
+    // The summary parsed, so the manifest is a well-formed object
+    let keys: BTreeMap<String, serde::de::IgnoredAny> = serde_json::from_str(&json)?;
+    summary.unknown_fields = keys
+        .into_keys()
+        .filter(|k| !MANIFEST_FIELDS.contains(&k.as_str()))
+        .collect();
+    summary.dir = dir.to_path_buf();
+    Ok(summary)
+}
+
+/// Why a saved run was left out of an [`ArtifactScan`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactSkip {
+    /// Well-formed JSON this build cannot read: a newer schema version, or
+    /// required fields missing or of the wrong type.
+    Schema,
+    /// Unreadable or malformed JSON, e.g. a manifest truncated mid-write.
+    Corrupt,
+}
+
+impl ArtifactSkip {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Schema => "schema",
+            Self::Corrupt => "corrupt",
+        }
+    }
+
+    fn classify(err: &anyhow::Error) -> Self {
+        if err.downcast_ref::<UnsupportedSchema>().is_some() {
+            return Self::Schema;
+        }
+        match err.downcast_ref::<serde_json::Error>() {
+            Some(e) if e.classify() == serde_json::error::Category::Data => Self::Schema,
+            _ => Self::Corrupt,
+        }
+    }
+}
+
+/// A saved run that failed to load, with the reason.
+#[derive(Debug, Clone)]
+pub struct SkippedArtifact {
+    pub dir: PathBuf,
+    pub reason: ArtifactSkip,
+    pub error: String,
+}
+
+/// Result of scanning an output directory: the runs that loaded and a
+/// report of those that did not.
+#[derive(Debug, Clone, Default)]
+pub struct ArtifactScan {
+    pub summaries: Vec<ArtifactSummary>,
+    pub skipped: Vec<SkippedArtifact>,
+}
+
+impl ArtifactScan {
+    /// Number of skipped runs with the given reason.
+    pub fn skipped_count(&self, reason: ArtifactSkip) -> usize {
+        self.skipped.iter().filter(|s| s.reason == reason).count()
+    }
+
+    /// Total unknown manifest fields across the loaded runs.
+    pub fn unknown_field_count(&self) -> usize {
+        self.summaries.iter().map(|s| s.unknown_fields.len()).sum()
+    }
+
+    /// One-line report, e.g. "42 loaded, 3 skipped (schema), 1 skipped (corrupt)".
+    pub fn summary_line(&self) -> String {
+        let mut line = format!("{} loaded", self.summaries.len());
+        for reason in [ArtifactSkip::Schema, ArtifactSkip::Corrupt] {
+            let n = self.skipped_count(reason);
+            if n > 0 {
+                line.push_str(&format!(", {n} skipped ({})", reason.label()));
+            }
+        }
+        let unknown = self.unknown_field_count();
+        if unknown > 0 {
+            line.push_str(&format!(", {unknown} unknown fields ignored"));
+        }
+        line
+    }
+}
+
+/// Summaries of every artifact directory directly under `output_dir`,
+/// sorted by directory name (oldest first for the default naming).
+///
+/// Directories without a readable manifest are skipped; see
+/// [`scan_artifacts_report`] for the reasons. A missing `output_dir`
+/// yields an empty list.
+pub fn scan_artifacts(output_dir: &Path) -> Vec<ArtifactSummary> {
+    scan_artifacts_report(output_dir).summaries
+}
+
+/// Like [`scan_artifacts`], but records each directory whose manifest
+/// failed to load instead of dropping it silently.
+pub fn scan_artifacts_report(output_dir: &Path) -> ArtifactScan {
+    let mut scan = ArtifactScan::default();
+    let Ok(entries) = std::fs::read_dir(output_dir) else {
+        return scan;
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.join("manifest.json").is_file())
+        .collect();
+    dirs.sort();
+    for dir in dirs {
+        match load_artifact_summary(&dir) {
+            Ok(summary) => scan.summaries.push(summary),
+            Err(err) => scan.skipped.push(SkippedArtifact {
+                reason: ArtifactSkip::classify(&err),
+                error: format!("{err:#}"),
+                dir,
+            }),
+        }
+    }
+    scan
+}
+
+/// Write an equity curve as Parquet with `bar_index` and `equity` columns.
+pub fn write_equity_parquet(equity_curve: &[f64], path: &Path) -> Result<()> {
+    let bar_index: Vec<u64> = (0..equity_curve.len() as u64).collect();
+    let mut df = DataFrame::new(vec![
+        Column::new("bar_index".into(), bar_index),
+        Column::new("equity".into(), equity_curve.to_vec()),
+    ])
+    .context("failed to build equity dataframe")?;
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("failed to create {}", path.display()))?;
+    ParquetWriter::new(file)
+        .finish(&mut df)
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// Read the `equity` column of a Parquet equity curve.
+pub fn read_equity_parquet(path: &Path) -> Result<Vec<f64>> {
+    let file =
+        std::fs::File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let df = ParquetReader::new(file)
+        .with_columns(Some(vec!["equity".into()]))
+        .finish()
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let equity = df
+        .column("equity")
+        .and_then(|c| c.f64().cloned())
+        .with_context(|| format!("{}: missing f64 'equity' column", path.display()))?;
+    if equity.null_count() > 0 {
+        bail!("{}: equity column contains nulls", path.display());
+    }
+    Ok(equity.into_no_null_iter().collect())
+}
+
+/// Load a saved run's equity curve.
+///
+/// Prefers `equity.parquet`; artifacts saved before it existed fall back to
+/// the full manifest.
+pub fn load_equity_curve(dir: &Path) -> Result<Vec<f64>> {
+    let parquet = dir.join(EQUITY_PARQUET);
+    if parquet.is_file() {
+        return read_equity_parquet(&parquet);
+    }
+    Ok(load_artifacts(dir)?.equity_curve)
+}
+
+/// File name of the Parquet portfolio snapshots inside an artifact directory.
+pub const PORTFOLIO_PARQUET: &str = "portfolio.parquet";
+
+/// Write portfolio snapshots as Parquet, one row per open position.
+///
+/// Every row repeats its snapshot's `bar_index`, `date`, `cash`,
+/// `market_value`, `equity`, `gross_exposure` and `net_exposure`; the
+/// position columns (`symbol`, `side`, `quantity`, `mark`, `position_value`)
+/// are null on the single row of a snapshot with no open position.
+pub fn write_portfolio_parquet(log: &SnapshotLog, path: &Path) -> Result<()> {
+    let epoch = chrono::NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+    let rows: Vec<(&PortfolioSnapshot, Option<&PositionSnapshot>)> = log
+        .snapshots
+        .iter()
+        .flat_map(|s| -> Vec<_> {
+            if s.positions.is_empty() {
+                vec![(s, None)]
+            } else {
+                s.positions.iter().map(|p| (s, Some(p))).collect()
+            }
+        })
+        .collect();
+    let snapshot_col = |name: &str, f: fn(&PortfolioSnapshot) -> f64| {
+        Column::new(
+            name.into(),
+            rows.iter().map(|(s, _)| f(s)).collect::<Vec<f64>>(),
+        )
+    };
+    let position_col = |name: &str, f: fn(&PositionSnapshot) -> f64| {
+        Column::new(
+            name.into(),
+            rows.iter()
+                .map(|(_, p)| p.map(f))
+                .collect::<Vec<Option<f64>>>(),
+        )
+    };
+    let bar_index: Vec<u64> = rows.iter().map(|(s, _)| s.bar_index as u64).collect();
+    let dates: Vec<i32> = rows
+        .iter()
+        .map(|(s, _)| (s.date - epoch).num_days() as i32)
+        .collect();
+    let symbols: Vec<Option<&str>> = rows
+        .iter()
+        .map(|(_, p)| p.map(|p| p.symbol.as_str()))
+        .collect();
+    let sides: Vec<Option<&str>> = rows
+        .iter()
+        .map(|(_, p)| p.map(|p| side_label(p.side)))
+        .collect();
+
+    let mut df = DataFrame::new(vec![
+        Column::new("bar_index".into(), bar_index),
+        Column::new("date".into(), dates)
+            .cast(&DataType::Date)
+            .context("failed to cast snapshot dates")?,
+        snapshot_col("cash", |s| s.cash),
+        snapshot_col("market_value", |s| s.market_value),
+        snapshot_col("equity", |s| s.equity),
+        snapshot_col("gross_exposure", |s| s.gross_exposure),
+        snapshot_col("net_exposure", |s| s.net_exposure),
+        Column::new("symbol".into(), symbols),
+        Column::new("side".into(), sides),
+        position_col("quantity", |p| p.quantity),
+        position_col("mark", |p| p.mark),
+        position_col("position_value", |p| p.market_value),
+    ])
+    .context("failed to build portfolio dataframe")?;
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("failed to create {}", path.display()))?;
+    ParquetWriter::new(file)
+        .finish(&mut df)
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// Read portfolio snapshots written by [`write_portfolio_parquet`].
+pub fn read_portfolio_parquet(path: &Path) -> Result<SnapshotLog> {
+    let file =
+        std::fs::File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let df = ParquetReader::new(file)
+        .finish()
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let column = |name: &str| {
+        df.column(name)
+            .with_context(|| format!("{}: missing '{name}' column", path.display()))
+    };
+    let f64_col = |name: &str| -> Result<Float64Chunked> {
+        column(name)?
+            .f64()
+            .cloned()
+            .with_context(|| format!("{}: '{name}' is not f64", path.display()))
+    };
+    let str_col = |name: &str| -> Result<StringChunked> {
+        column(name)?
+            .str()
+            .cloned()
+            .with_context(|| format!("{}: '{name}' is not a string", path.display()))
+    };
+    let bar_index = column("bar_index")?
+        .u64()
+        .cloned()
+        .with_context(|| format!("{}: 'bar_index' is not u64", path.display()))?;
+    let dates = column("date")?
+        .date()
+        .cloned()
+        .with_context(|| format!("{}: 'date' is not a date", path.display()))?;
+    let (cash, market_value, equity) = (
+        f64_col("cash")?,
+        f64_col("market_value")?,
+        f64_col("equity")?,
+    );
+    let (gross, net) = (f64_col("gross_exposure")?, f64_col("net_exposure")?);
+    let (symbols, sides) = (str_col("symbol")?, str_col("side")?);
+    let (quantity, mark, value) = (
+        f64_col("quantity")?,
+        f64_col("mark")?,
+        f64_col("position_value")?,
+    );
+
+    let epoch = chrono::NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+    let null =
+        |name: &str, row: usize| anyhow::anyhow!("{}: null {name} at row {row}", path.display());
+    let mut snapshots: Vec<PortfolioSnapshot> = Vec::new();
+    for row in 0..df.height() {
+        let bar = bar_index.get(row).ok_or_else(|| null("bar_index", row))? as usize;
+        if snapshots.last().map_or(true, |s| s.bar_index != bar) {
+            let days = dates.get(row).ok_or_else(|| null("date", row))?;
+            snapshots.push(PortfolioSnapshot {
+                bar_index: bar,
+                date: epoch + chrono::Duration::days(days as i64),
+                cash: cash.get(row).ok_or_else(|| null("cash", row))?,
+                market_value: market_value
+                    .get(row)
+                    .ok_or_else(|| null("market_value", row))?,
+                equity: equity.get(row).ok_or_else(|| null("equity", row))?,
+                gross_exposure: gross.get(row).ok_or_else(|| null("gross_exposure", row))?,
+                net_exposure: net.get(row).ok_or_else(|| null("net_exposure", row))?,
+                positions: Vec::new(),
+            });
+        }
+        let Some(symbol) = symbols.get(row) else {
+            continue;
+        };
+        let side = match sides.get(row) {
+            Some("long") => PositionSide::Long,
+            Some("short") => PositionSide::Short,
+            other => bail!("{}: bad side {other:?} at row {row}", path.display()),
+        };
+        snapshots
+            .last_mut()
+            .unwrap()
+            .positions
+            .push(PositionSnapshot {
+                symbol: symbol.to_string(),
+                side,
+                quantity: quantity.get(row).ok_or_else(|| null("quantity", row))?,
+                mark: mark.get(row).ok_or_else(|| null("mark", row))?,
+                market_value: value.get(row).ok_or_else(|| null("position_value", row))?,
+            });
+    }
+    Ok(SnapshotLog { snapshots })
+}
+
+/// Load a saved run's portfolio snapshots; empty when the run recorded none.
+pub fn load_portfolio_snapshots(dir: &Path) -> Result<SnapshotLog> {
+    let parquet = dir.join(PORTFOLIO_PARQUET);
+    if !parquet.is_file() {
+        return Ok(SnapshotLog::default());
+    }
+    read_portfolio_parquet(&parquet)
+}
+
+pub(crate) fn side_label(side: PositionSide) -> &'static str {
+    match side {
+        PositionSide::Long => "long",
+        PositionSide::Short => "short",
+        PositionSide::Flat => "flat",
+    }
+}
+
 // ─── Markdown reports ───────────────────────────────────────────────
 
 /// Generate a Markdown report for a single backtest run.
@@ -186,6 +776,9 @@ pub fn generate_report(result: &BacktestResult) -> String {
     if result.has_synthetic {
         md.push_str("| Data | **SYNTHETIC** |\n");
     }
+    if let Some(truncated) = &result.truncated_range {
+        md.push_str(&format!("| Coverage | **{truncated}** |\n"));
+    }
     md.push('\n');
 
     // Strategy Composition
@@ -269,6 +862,64 @@ pub fn generate_report(result: &BacktestResult) -> String {
             "| Reference Chase Ratio | {:.1} |\n",
             s.reference_chase_ratio
         ));
+        let report = result.stickiness_report();
+        if !report.positions.is_empty() {
+            md.push_str(&format!(
+                "| Frozen Reference Rate | {:.1}% |\n",
+                report.frozen_reference_rate() * 100.0
+            ));
+            md.push_str(&format!(
+                "| Chasing Positions | {} of {} |\n",
+                report.chasing().count(),
+                report.positions.len()
+            ));
+        }
+        md.push('\n');
+    }
+
+    // Tail Risk
+    if let Some(ref t) = result.tail_metrics {
+        md.push_str("## Tail Risk\n\n");
+        md.push_str("| Metric | Value |\n");
+        md.push_str("| --- | --- |\n");
+        md.push_str(&format!("| Return Observations | {} |\n", t.sample_size));
+        md.push_str(&format!("| CVaR 95% | {} |\n", opt_pct(t.cvar_95)));
+        md.push_str(&format!("| CVaR 99% | {} |\n", opt_pct(t.cvar_99)));
+        md.push_str(&format!("| Skewness | {} |\n", opt_f3(t.skewness)));
+        md.push_str(&format!("| Excess Kurtosis | {} |\n", opt_f3(t.kurtosis)));
+        md.push('\n');
+
+        if !t.worst_days.is_empty() {
+            md.push_str("### Worst Days\n\n");
+            md.push_str("| Date | Return | Open Positions |\n");
+            md.push_str("| --- | --- | --- |\n");
+            for day in &t.worst_days {
+                md.push_str(&format!(
+                    "| {} | {:.2}% | {} |\n",
+                    day.date,
+                    day.daily_return * 100.0,
+                    day.concurrent_positions
+                ));
+            }
+            md.push('\n');
+        }
+    }
+
+    // Style Analysis
+    if let Some(ref style) = result.style {
+        md.push_str("## Style Analysis\n\n");
+        md.push_str("| Factor | Beta |\n");
+        md.push_str("| --- | --- |\n");
+        for b in &style.betas {
+            md.push_str(&format!("| {} | {:.3} |\n", b.factor, b.beta));
+        }
+        md.push_str(&format!("| R² | {:.3} |\n", style.r_squared));
+        md.push_str(&format!("| Alpha (ann.) | {:.2}% |\n", style.alpha * 100.0));
+        md.push_str(&format!(
+            "| Residual Sharpe | {:.3} |\n",
+            style.residual_sharpe
+        ));
+        md.push_str(&format!("| Observations | {} |\n", style.observations));
         md.push('\n');
     }
 
@@ -300,10 +951,7 @@ pub fn generate_comparison(a: &BacktestResult, b: &BacktestResult) -> String {
     md.push_str("## Composition\n\n");
     md.push_str("| Component | Strategy A | Strategy B |\n");
     md.push_str("| --- | --- | --- |\n");
-    md.push_str(&format!(
-        "| Symbol | {} | {} |\n",
-        a.symbol, b.symbol
-    ));
+    md.push_str(&format!("| Symbol | {} | {} |\n", a.symbol, b.symbol));
     md.push_str(&format!(
         "| Signal | {} | {} |\n",
         a.config.signal.component_type, b.config.signal.component_type
@@ -423,26 +1071,37 @@ pub fn generate_comparison(a: &BacktestResult, b: &BacktestResult) -> String {
 
 // ─── Helpers ────────────────────────────────────────────────────────
 
-fn format_component(
-    label: &str,
-    config: &trendlab_core::fingerprint::ComponentConfig,
-) -> String {
+fn format_component(label: &str, config: &trendlab_core::fingerprint::ComponentConfig) -> String {
     let mut s = format!("- **{label}**: `{}`", config.component_type);
     if !config.params.is_empty() {
-        let params: Vec<String> = config.params.iter().map(|(k, v)| format!("{k}={v}")).collect();
+        let params: Vec<String> = config
+            .params
+            .iter()
+            .map(|(k, v)| format!("{k}={v}"))
+            .collect();
         s.push_str(&format!(" ({})", params.join(", ")));
     }
     s.push('\n');
     s
 }
 
+/// Percentage with two decimals, or "n/a" when the run was too short.
+fn opt_pct(v: Option<f64>) -> String {
+    v.map_or_else(|| "n/a".to_string(), |v| format!("{:.2}%", v * 100.0))
+}
+
+fn opt_f3(v: Option<f64>) -> String {
+    v.map_or_else(|| "n/a".to_string(), |v| format!("{v:.3}"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use chrono::NaiveDate;
-    use std::collections::HashMap;
     use trendlab_core::domain::position::PositionSide;
-    use trendlab_core::engine::stickiness::StickinessMetrics;
+    use trendlab_core::domain::DecisionSource;
+    use trendlab_core::engine::stickiness::{PmCallCounts, StickinessMetrics};
+    use trendlab_core::engine::{AttributedBar, PositionReplay, ReplayBar};
     use trendlab_core::fingerprint::{ComponentConfig, StrategyConfig};
 
     use crate::metrics::PerformanceMetrics;
@@ -454,20 +1113,24 @@ mod tests {
             signal: ComponentConfig {
                 component_type: "donchian_breakout".into(),
                 params: [("lookback".into(), 50.0)].into_iter().collect(),
+                children: Vec::new(),
             },
             position_manager: ComponentConfig {
                 component_type: "atr_trailing".into(),
                 params: [("atr_period".into(), 14.0), ("multiplier".into(), 3.0)]
                     .into_iter()
                     .collect(),
+                children: Vec::new(),
             },
             execution_model: ComponentConfig {
                 component_type: "next_bar_open".into(),
                 params: [("preset".into(), 1.0)].into_iter().collect(),
+                children: Vec::new(),
             },
             signal_filter: ComponentConfig {
                 component_type: "no_filter".into(),
                 params: Default::default(),
+                children: Vec::new(),
             },
         }
     }
@@ -479,6 +1142,7 @@ mod tests {
             entry_bar: 55,
             entry_date: NaiveDate::from_ymd_opt(2024, 3, 15).unwrap(),
             entry_price: 450.50,
+            entry_trigger_margin: None,
             exit_bar: 72,
             exit_date: NaiveDate::from_ymd_opt(2024, 4, 10).unwrap(),
             exit_price: 468.25,
@@ -490,6 +1154,11 @@ mod tests {
             bars_held: 17,
             mae: -500.0,
             mfe: 4200.0,
+            initial_stop_price: None,
+            entry_source: None,
+            exit_source: None,
+            liquidated_at_end: false,
+            expiry_bar: None,
             signal_id: None,
             signal_type: Some("donchian_breakout".into()),
             pm_type: Some("atr_trailing".into()),
@@ -515,22 +1184,49 @@ mod tests {
                 max_consecutive_wins: 5,
                 max_consecutive_losses: 3,
                 avg_losing_streak: 1.8,
+                time_in_market: 0.0,
+                avg_exposure: 0.0,
+                invested_return: 0.0,
+                idle_return: 0.0,
+                exposure_adjusted_cagr: 0.0,
             },
+            open_position_adjusted: None,
             trades: vec![sample_trade()],
             equity_curve: vec![100_000.0, 100_500.0, 101_200.0, 103_000.0, 115_000.0],
+            dates: Vec::new(),
+            exposure_curve: Vec::new(),
             config: sample_config(),
             symbol: "SPY".into(),
+            interval: Default::default(),
             start_date: "2024-01-02".into(),
             end_date: "2024-12-31".into(),
             initial_capital: 100_000.0,
             dataset_hash: "abc123".into(),
             has_synthetic: false,
             signal_count: 30,
+            zero_trades: false,
             bar_count: 252,
             warmup_bars: 50,
-            void_bar_rates: HashMap::new(),
+            warmup: None,
+            void_bar_rates: BTreeMap::new(),
             data_quality_warnings: vec![],
             stickiness: None,
+            position_stickiness: Vec::new(),
+            r_distribution: Default::default(),
+            trade_distributions: Default::default(),
+            tail_metrics: None,
+            truncated_range: None,
+            style: None,
+            equity_sparkline: Vec::new(),
+            attribution: Default::default(),
+            ratchet_clamps: Vec::new(),
+            inputs: None,
+            consistency_warnings: Vec::new(),
+            data_vintage: Vec::new(),
+            provenance: None,
+            timings: Default::default(),
+            replay: Default::default(),
+            portfolio_snapshots: Default::default(),
         }
     }
 
@@ -591,7 +1287,7 @@ mod tests {
         let header = csv.lines().next().unwrap();
         let cols: Vec<&str> = header.split(',').collect();
 
-        assert_eq!(cols.len(), 20);
+        assert_eq!(cols.len(), 24);
         assert!(cols.contains(&"symbol"));
         assert!(cols.contains(&"side"));
         assert!(cols.contains(&"entry_bar"));
@@ -612,6 +1308,10 @@ mod tests {
         assert!(cols.contains(&"pm_type"));
         assert!(cols.contains(&"execution_model"));
         assert!(cols.contains(&"filter_type"));
+        assert!(cols.contains(&"entry_trigger_margin"));
+        assert!(cols.contains(&"entry_source"));
+        assert!(cols.contains(&"exit_source"));
+        assert!(cols.contains(&"liquidated_at_end"));
     }
 
     #[test]
@@ -630,6 +1330,28 @@ mod tests {
         assert!(row.contains("3909.50"));
     }
 
+    #[test]
+    fn csv_trades_with_annotation_columns() {
+        use crate::annotations::{Annotation, AnnotationTag};
+
+        let mut other = sample_trade();
+        other.entry_bar = 90;
+        let mut notes = RunAnnotations::default();
+        notes.trades.insert(
+            55,
+            Annotation {
+                note: "gapped, filled 2% over".into(),
+                tags: [AnnotationTag::GapEntry, AnnotationTag::LuckyTrade].into(),
+            },
+        );
+        let csv = export_annotated_trades_csv(&[sample_trade(), other], &notes).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert!(lines[0].ends_with(",liquidated_at_end,note,tags"));
+        assert!(lines[1].ends_with(",\"gapped, filled 2% over\",lucky-trade;gap-entry"));
+        assert!(lines[2].ends_with(",false,,"));
+    }
+
     #[test]
     fn csv_empty_trades() {
         let csv = export_trades_csv(&[]).unwrap();
@@ -679,11 +1401,18 @@ mod tests {
             exit_trigger_rate: 0.45,
             reference_chase_ratio: 2.2,
         });
+        let calls = |total, active| PmCallCounts { total, active };
+        result.position_stickiness = vec![
+            PositionStickiness::new("SPY", 10, Some(30), 20, calls(20, 9)),
+            PositionStickiness::new("SPY", 40, None, 150, calls(150, 1)),
+        ];
         let md = generate_report(&result);
 
         assert!(md.contains("## Stickiness Diagnostics"));
         assert!(md.contains("Median Holding"));
         assert!(md.contains("Exit Trigger Rate"));
+        assert!(md.contains("| Frozen Reference Rate | 94.1% |"));
+        assert!(md.contains("| Chasing Positions | 1 of 2 |"));
     }
 
     #[test]
@@ -693,6 +1422,51 @@ mod tests {
         assert!(!md.contains("Stickiness Diagnostics"));
     }
 
+    #[test]
+    fn markdown_report_with_worst_days() {
+        let mut result = sample_result();
+        result.tail_metrics = Some(TailMetrics {
+            cvar_95: None,
+            cvar_99: None,
+            skewness: None,
+            kurtosis: None,
+            downside_deviation_ratio: None,
+            sample_size: 40,
+            worst_days: vec![crate::tail_metrics::WorstDay {
+                date: NaiveDate::from_ymd_opt(2024, 3, 5).unwrap(),
+                daily_return: -0.0312,
+                concurrent_positions: 1,
+            }],
+        });
+        let md = generate_report(&result);
+
+        assert!(md.contains("## Tail Risk"));
+        // Too short for CVaR
+        assert!(md.contains("| CVaR 95% | n/a |"));
+        assert!(md.contains("| 2024-03-05 | -3.12% | 1 |"));
+    }
+
+    #[test]
+    fn markdown_report_with_style_analysis() {
+        let mut result = sample_result();
+        result.style = Some(StyleReport {
+            betas: vec![crate::style::FactorBeta {
+                factor: "SPY".into(),
+                beta: 0.874,
+            }],
+            r_squared: 0.91,
+            alpha: 0.012,
+            residual_sharpe: 0.35,
+            observations: 251,
+        });
+        let md = generate_report(&result);
+
+        assert!(md.contains("## Style Analysis"));
+        assert!(md.contains("| SPY | 0.874 |"));
+        assert!(md.contains("| R² | 0.910 |"));
+        assert!(md.contains("| Alpha (ann.) | 1.20% |"));
+    }
+
     // ─── Markdown comparison ────────────────────────────────────────
 
     #[test]
@@ -741,6 +1515,283 @@ mod tests {
         assert!((loaded.metrics.sharpe - result.metrics.sharpe).abs() < 1e-10);
     }
 
+    #[test]
+    fn diagnostics_carry_the_stickiness_report() {
+        let mut result = sample_result();
+        result.stickiness = Some(StickinessMetrics {
+            median_holding_bars: 20.0,
+            p95_holding_bars: 20.0,
+            pct_over_60_bars: 0.0,
+            pct_over_120_bars: 0.0,
+            exit_trigger_rate: 0.45,
+            reference_chase_ratio: 2.2,
+        });
+        result.position_stickiness = vec![PositionStickiness::new(
+            "SPY",
+            10,
+            Some(30),
+            20,
+            PmCallCounts {
+                total: 20,
+                active: 9,
+            },
+        )];
+        result.attribution = PnlAttribution {
+            bars: vec![AttributedBar {
+                bar_index: 30,
+                date: NaiveDate::from_ymd_opt(2024, 2, 14).unwrap(),
+                pnl: [(DecisionSource::PmStopExit, -125.5)].into(),
+            }],
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let run_dir = save_artifacts(&result, dir.path()).unwrap();
+
+        let diagnostics = load_diagnostics(&run_dir).unwrap();
+        assert_eq!(diagnostics.stickiness, result.stickiness_report());
+        assert_eq!(diagnostics.attribution, result.attribution);
+        let loaded = load_artifacts(&run_dir).unwrap();
+        assert_eq!(loaded.position_stickiness, result.position_stickiness);
+    }
+
+    #[test]
+    fn diagnostics_carry_the_replay_only_when_recorded() {
+        let mut result = sample_result();
+        let dir = tempfile::tempdir().unwrap();
+        let run_dir = save_artifacts(&result, dir.path()).unwrap();
+        let json = std::fs::read_to_string(run_dir.join(DIAGNOSTICS_JSON)).unwrap();
+        assert!(!json.contains("\"replay\""));
+        assert!(load_diagnostics(&run_dir).unwrap().replay.is_empty());
+
+        result.replay = ReplayLog {
+            positions: vec![PositionReplay {
+                symbol: "SPY".into(),
+                side: PositionSide::Long,
+                entry_bar: 10,
+                net_pnl: Some(-250.0),
+                bars: vec![ReplayBar {
+                    bar_index: 10,
+                    date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+                    open: 100.0,
+                    high: 102.0,
+                    low: 99.0,
+                    close: 101.0,
+                    stop: Some(96.5),
+                    orders: Vec::new(),
+                    equity: 100_100.0,
+                    indicators: [("atr_14".to_string(), 1.8)].into(),
+                    exit: false,
+                }],
+            }],
+        };
+        let run_dir = dir.path().join("replayed");
+        write_artifacts(&result, &run_dir).unwrap();
+        let diagnostics = load_diagnostics(&run_dir).unwrap();
+        assert_eq!(diagnostics.replay, result.replay);
+        // The manifest stays replay-free
+        assert!(load_artifacts(&run_dir).unwrap().replay.is_empty());
+    }
+
+    #[test]
+    fn equity_parquet_roundtrip_is_exact() {
+        let result = sample_result();
+        let dir = tempfile::tempdir().unwrap();
+        let run_dir = save_artifacts(&result, dir.path()).unwrap();
+        assert!(run_dir.join(EQUITY_PARQUET).exists());
+
+        let curve = load_equity_curve(&run_dir).unwrap();
+        assert_eq!(curve, result.equity_curve);
+    }
+
+    #[test]
+    fn load_equity_curve_falls_back_to_manifest() {
+        let result = sample_result();
+        let dir = tempfile::tempdir().unwrap();
+        let run_dir = save_artifacts(&result, dir.path()).unwrap();
+        std::fs::remove_file(run_dir.join(EQUITY_PARQUET)).unwrap();
+
+        let curve = load_equity_curve(&run_dir).unwrap();
+        assert_eq!(curve, result.equity_curve);
+    }
+
+    #[test]
+    fn portfolio_parquet_roundtrip_is_exact() {
+        let mut result = sample_result();
+        let dir = tempfile::tempdir().unwrap();
+        let run_dir = save_artifacts(&result, dir.path()).unwrap();
+        assert!(!run_dir.join(PORTFOLIO_PARQUET).exists());
+        assert!(load_portfolio_snapshots(&run_dir).unwrap().is_empty());
+
+        let snapshot = |bar_index: usize, cash: f64, positions: Vec<PositionSnapshot>| {
+            let market_value = positions.iter().map(|p| p.market_value).sum::<f64>();
+            PortfolioSnapshot {
+                bar_index,
+                date: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap()
+                    + chrono::Duration::days(bar_index as i64),
+                cash,
+                market_value,
+                equity: cash + market_value,
+                gross_exposure: market_value,
+                net_exposure: market_value / 3.0,
+                positions,
+            }
+        };
+        let position = |symbol: &str, side, quantity: f64, mark: f64| PositionSnapshot {
+            symbol: symbol.into(),
+            side,
+            quantity,
+            mark,
+            market_value: quantity * mark,
+        };
+        result.portfolio_snapshots = SnapshotLog {
+            snapshots: vec![
+                snapshot(0, 100_000.0, Vec::new()),
+                snapshot(
+                    7,
+                    20_000.1,
+                    vec![
+                        position("QQQ", PositionSide::Short, 12.5, 401.37),
+                        position("SPY", PositionSide::Long, 150.0, 512.0 / 3.0),
+                    ],
+                ),
+                snapshot(9, 99_123.456, Vec::new()),
+            ],
+        };
+        let run_dir = dir.path().join("snapshots");
+        write_artifacts(&result, &run_dir).unwrap();
+        assert_eq!(
+            load_portfolio_snapshots(&run_dir).unwrap(),
+            result.portfolio_snapshots
+        );
+        // The manifest stays snapshot-free
+        assert!(load_artifacts(&run_dir)
+            .unwrap()
+            .portfolio_snapshots
+            .is_empty());
+    }
+
+    #[test]
+    fn scan_artifacts_reads_headline_stats_only() {
+        let mut result = sample_result();
+        result.equity_sparkline = vec![100_000.0, 99_000.0, 103_000.0];
+        let dir = tempfile::tempdir().unwrap();
+        let run_dir = save_artifacts(&result, dir.path()).unwrap();
+        // Not an artifact directory: ignored
+        std::fs::create_dir(dir.path().join("scratch")).unwrap();
+
+        let summaries = scan_artifacts(dir.path());
+        assert_eq!(summaries.len(), 1);
+        let s = &summaries[0];
+        assert_eq!(s.dir, run_dir);
+        assert_eq!(s.symbol, result.symbol);
+        assert_eq!(s.config, result.config);
+        assert_eq!(s.bar_count, result.bar_count);
+        assert_eq!(s.data_warning_count, result.data_quality_warnings.len());
+        assert!((s.metrics.sharpe - result.metrics.sharpe).abs() < 1e-10);
+        assert_eq!(s.equity_sparkline, result.equity_sparkline);
+
+        assert!(scan_artifacts(&dir.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn artifact_summary_rejects_future_schema() {
+        let mut result = sample_result();
+        result.schema_version = SCHEMA_VERSION + 1;
+        let dir = tempfile::tempdir().unwrap();
+        let run_dir = save_artifacts(&result, dir.path()).unwrap();
+        assert!(load_artifact_summary(&run_dir).is_err());
+        assert!(scan_artifacts(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn scan_report_counts_skipped_runs_by_reason() {
+        let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/artifacts");
+        let scan = scan_artifacts_report(&fixtures);
+
+        let loaded: Vec<_> = scan.summaries.iter().map(|s| s.dir.clone()).collect();
+        assert_eq!(
+            loaded,
+            [
+                fixtures.join("run_01_current"),
+                fixtures.join("run_02_extra_fields")
+            ]
+        );
+        assert!(scan.summaries[0].unknown_fields.is_empty());
+        assert_eq!(
+            scan.summaries[1].unknown_fields,
+            ["benchmark", "regime_tags"]
+        );
+
+        let skipped: Vec<_> = scan
+            .skipped
+            .iter()
+            .map(|s| (s.dir.file_name().unwrap().to_str().unwrap(), s.reason))
+            .collect();
+        assert_eq!(
+            skipped,
+            [
+                ("run_03_future_schema", ArtifactSkip::Schema),
+                ("run_04_missing_metrics", ArtifactSkip::Schema),
+                ("run_05_truncated", ArtifactSkip::Corrupt),
+            ]
+        );
+        assert!(scan.skipped[0]
+            .error
+            .contains("unsupported schema version 2"));
+        assert_eq!(
+            scan.summary_line(),
+            "2 loaded, 2 skipped (schema), 1 skipped (corrupt), 2 unknown fields ignored"
+        );
+        assert_eq!(scan_artifacts(&fixtures).len(), 2);
+    }
+
+    #[test]
+    fn manifest_fields_cover_every_serialized_key() {
+        let json: serde_json::Value =
+            serde_json::from_str(&export_json(&sample_result()).unwrap()).unwrap();
+        let skipped_when_empty = [
+            "open_position_adjusted",
+            "position_stickiness",
+            "attribution",
+        ];
+        for key in json.as_object().unwrap().keys() {
+            assert!(MANIFEST_FIELDS.contains(&key.as_str()), "{key}");
+        }
+        for key in skipped_when_empty {
+            assert!(MANIFEST_FIELDS.contains(&key), "{key}");
+        }
+    }
+
+    #[test]
+    fn manifest_without_later_fields_still_loads() {
+        // A manifest as written before schema versioning and the optional
+        // diagnostics fields existed
+        let mut json: serde_json::Value =
+            serde_json::from_str(&export_json(&sample_result()).unwrap()).unwrap();
+        let manifest = json.as_object_mut().unwrap();
+        for key in [
+            "schema_version",
+            "data_quality_warnings",
+            "position_stickiness",
+            "r_distribution",
+            "trade_distributions",
+            "tail_metrics",
+            "truncated_range",
+            "style",
+            "equity_sparkline",
+            "attribution",
+        ] {
+            manifest.remove(key);
+        }
+
+        let loaded = import_json(&json.to_string()).unwrap();
+        assert_eq!(loaded.schema_version, SCHEMA_VERSION);
+        assert!(loaded.data_quality_warnings.is_empty());
+        assert!(loaded.position_stickiness.is_empty());
+        assert!(loaded.equity_sparkline.is_empty());
+        assert!(loaded.attribution.is_empty());
+        assert_eq!(loaded.trades.len(), sample_result().trades.len());
+    }
+
     // ─── Export coverage ────────────────────────────────────────────
 
     #[test]
@@ -8,6 +8,16 @@
 //! - Benjamini-Hochberg FDR correction
 //! - FDR family tracker for accumulating p-values across YOLO iterations
 //!
+//! YOLO sessions keep one [`FdrFamily`] per symbol. Every backtest the
+//! session runs on the symbol is a trial: those that close trades contribute
+//! the p-value of a t-test on their daily returns ([`returns_p_value`]), the
+//! rest count towards the family size with no p-value. Trials are persisted
+//! as [`FdrRecord`]s in the history, so with [`FdrConfig::merge_sessions`] a
+//! session can continue the families of earlier sessions on the same dataset.
+//! [`FdrFamily::q_values`] recomputes the Benjamini-Hochberg adjustment over
+//! the family as it stands, and leaderboard entries carry an [`FdrStanding`]
+//! refreshed as the family grows.
+//!
 //! Statistical caveat: the t-test on K fold-level OOS Sharpe values is a
 //! heuristic ranking tool, not a rigorous hypothesis test. The assumptions of
 //! normality, independence, and sufficient sample size are unlikely to hold.
@@ -16,6 +26,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::metrics::daily_returns;
+
 // ─── Math primitives ─────────────────────────────────────────────────
 
 /// Lanczos approximation for ln(Gamma(x)), g=7, n=9.
@@ -77,8 +89,8 @@ fn regularized_incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
     }
 
     // Compute the prefix: x^a * (1-x)^b / (a * B(a,b))
-    let ln_prefix = a * x.ln() + b * (1.0 - x).ln() - ln_gamma(a) - ln_gamma(b) + ln_gamma(a + b)
-        - a.ln();
+    let ln_prefix =
+        a * x.ln() + b * (1.0 - x).ln() - ln_gamma(a) - ln_gamma(b) + ln_gamma(a + b) - a.ln();
 
     let prefix = ln_prefix.exp();
 
@@ -114,8 +126,8 @@ fn regularized_incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
         f *= c * d;
 
         // Odd step: d_{2m+1}
-        let numerator_odd = -((a + m_f64) * (a + b + m_f64) * x)
-            / ((a + 2.0 * m_f64) * (a + 2.0 * m_f64 + 1.0));
+        let numerator_odd =
+            -((a + m_f64) * (a + b + m_f64) * x) / ((a + 2.0 * m_f64) * (a + 2.0 * m_f64 + 1.0));
 
         d = 1.0 + numerator_odd * d;
         if d.abs() < tiny {
@@ -238,74 +250,166 @@ pub struct FdrResult {
 ///
 /// Returns results sorted by raw p-value (ascending).
 pub fn benjamini_hochberg(p_values: &[(String, f64)], alpha: f64) -> Vec<FdrResult> {
-    if p_values.is_empty() {
-        return Vec::new();
-    }
-
-    let m = p_values.len();
+    let mut sorted = p_values.to_vec();
+    sorted.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+    correct_sorted(&sorted, sorted.len(), alpha)
+}
 
-    // Sort by raw p-value ascending
-    let mut indexed: Vec<(usize, &str, f64)> = p_values
+/// BH correction of p-values already sorted ascending, over a family of
+/// `trials` tests. Trials without a p-value rank after every listed one.
+fn correct_sorted(sorted: &[(String, f64)], trials: usize, alpha: f64) -> Vec<FdrResult> {
+    let raw: Vec<f64> = sorted.iter().map(|(_, p)| *p).collect();
+    sorted
         .iter()
-        .enumerate()
-        .map(|(i, (id, p))| (i, id.as_str(), *p))
-        .collect();
-    indexed.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(std::cmp::Ordering::Equal));
-
-    // Compute adjusted p-values using step-up procedure:
-    // adjusted_p_(k) = min(p_(k) * m/k, adjusted_p_(k+1))
-    // Working backwards from the largest p-value.
-    let mut adjusted: Vec<f64> = vec![0.0; m];
-    adjusted[m - 1] = indexed[m - 1].2; // largest p-value stays as-is (clamped to 1.0)
-    adjusted[m - 1] = adjusted[m - 1].min(1.0);
-
-    for k in (0..m - 1).rev() {
-        let rank = k + 1; // 1-based rank
-        let raw_p = indexed[k].2;
-        let corrected = raw_p * m as f64 / rank as f64;
-        adjusted[k] = corrected.min(adjusted[k + 1]).min(1.0);
-    }
-
-    // Build results
-    indexed
-        .iter()
-        .zip(adjusted.iter())
-        .map(|(&(_, id, raw_p), &adj_p)| FdrResult {
-            config_id: id.to_string(),
-            raw_p,
+        .zip(adjust_sorted(&raw, trials))
+        .map(|((id, raw_p), adj_p)| FdrResult {
+            config_id: id.clone(),
+            raw_p: *raw_p,
             adjusted_p: adj_p,
             significant: adj_p <= alpha,
         })
         .collect()
 }
 
+/// BH-adjusted p-values for ascending `sorted` p-values out of `trials`
+/// tests, using the step-up procedure:
+/// adjusted_p_(k) = min(p_(k) * m/k, adjusted_p_(k+1)), clamped to 1.0.
+fn adjust_sorted(sorted: &[f64], trials: usize) -> Vec<f64> {
+    let m = trials.max(sorted.len()) as f64;
+    let mut adjusted = vec![1.0; sorted.len()];
+    let mut running = 1.0_f64;
+    // Working backwards from the largest p-value
+    for k in (0..sorted.len()).rev() {
+        let rank = (k + 1) as f64;
+        running = running.min(sorted[k] * m / rank);
+        adjusted[k] = running;
+    }
+    adjusted
+}
+
+/// One-sided t-test p-value of a run's daily returns (H1: mean return > 0).
+///
+/// Like the walk-forward test this is a ranking score rather than a literal
+/// false-positive probability: daily returns are neither normal nor
+/// independent.
+pub fn returns_p_value(equity_curve: &[f64]) -> Option<f64> {
+    one_sided_t_test(&daily_returns(equity_curve))
+        .map(|t| t.p_value)
+        .filter(|p| p.is_finite())
+}
+
 // ─── FDR family tracker ──────────────────────────────────────────────
 
+/// FDR settings of a YOLO session.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FdrConfig {
+    /// Level at which leaderboard entries are marked as surviving.
+    #[serde(default = "default_fdr_alpha")]
+    pub alpha: f64,
+    /// Seed each symbol's family with the trials earlier sessions recorded
+    /// in the history on the same dataset hash. Off: families span this
+    /// session only.
+    #[serde(default)]
+    pub merge_sessions: bool,
+}
+
+impl Default for FdrConfig {
+    fn default() -> Self {
+        Self {
+            alpha: default_fdr_alpha(),
+            merge_sessions: false,
+        }
+    }
+}
+
+fn default_fdr_alpha() -> f64 {
+    0.05
+}
+
+/// A batch of trials added to one symbol's family, as persisted in the
+/// history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FdrRecord {
+    pub session_id: String,
+    pub symbol: String,
+    pub dataset_hash: String,
+    /// Iteration of the last trial in the batch.
+    pub iteration: usize,
+    /// Trials in the batch, including those without a p-value.
+    pub trials: usize,
+    /// `(full_hash hex, p-value)` of the trials that produced one.
+    pub p_values: Vec<(String, f64)>,
+}
+
+impl FdrRecord {
+    pub fn new(session_id: &str, symbol: &str, dataset_hash: &str) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            symbol: symbol.to_string(),
+            dataset_hash: dataset_hash.to_string(),
+            iteration: 0,
+            trials: 0,
+            p_values: Vec::new(),
+        }
+    }
+}
+
 /// Tracks p-values across YOLO iterations for FDR correction.
 ///
-/// The "family" is all configurations tested within one YOLO session on the
-/// same universe, date range, and execution preset. Different universes, date
-/// ranges, or execution presets constitute separate FDR families.
+/// The "family" is every trial run on one symbol within a session (or, when
+/// merged, across sessions sharing a dataset hash). Different symbols,
+/// universes, date ranges, or execution presets constitute separate FDR
+/// families. P-values are kept sorted, so the correction is one pass over
+/// the family however often it is recomputed.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FdrFamily {
     entries: Vec<(String, f64)>,
+    /// Trials that produced no p-value. They count towards the family size
+    /// and rank after every tested entry.
+    #[serde(default)]
+    untested: usize,
 }
 
 impl FdrFamily {
     pub fn new() -> Self {
         Self {
             entries: Vec::new(),
+            untested: 0,
         }
     }
 
     /// Add a p-value for a configuration.
     pub fn add(&mut self, config_id: String, p_value: f64) {
-        self.entries.push((config_id, p_value));
+        let at = self.entries.partition_point(|(_, p)| *p <= p_value);
+        self.entries.insert(at, (config_id, p_value));
+    }
+
+    /// Count `n` trials that produced no p-value.
+    pub fn add_untested(&mut self, n: usize) {
+        self.untested += n;
+    }
+
+    /// Add the trials of a persisted batch.
+    pub fn absorb(&mut self, record: &FdrRecord) {
+        for (id, p) in &record.p_values {
+            self.add(id.clone(), *p);
+        }
+        self.add_untested(record.trials.saturating_sub(record.p_values.len()));
     }
 
     /// Apply BH correction to all accumulated p-values.
     pub fn apply_correction(&self, alpha: f64) -> Vec<FdrResult> {
-        benjamini_hochberg(&self.entries, alpha)
+        correct_sorted(&self.entries, self.trials(), alpha)
+    }
+
+    /// Adjusted p-values of the family as it stands.
+    pub fn q_values(&self) -> QValues {
+        let sorted: Vec<f64> = self.entries.iter().map(|(_, p)| *p).collect();
+        QValues {
+            adjusted: adjust_sorted(&sorted, self.trials()),
+            sorted,
+            trials: self.trials(),
+        }
     }
 
     /// Number of accumulated entries.
@@ -317,6 +421,62 @@ impl FdrFamily {
     pub fn is_empty(&self) -> bool {
         self.entries.is_empty()
     }
+
+    /// Family size: entries plus untested trials.
+    pub fn trials(&self) -> usize {
+        self.entries.len() + self.untested
+    }
+}
+
+/// BH-adjusted p-values (q-values) of a family at one point in time.
+#[derive(Debug, Clone, Default)]
+pub struct QValues {
+    sorted: Vec<f64>,
+    adjusted: Vec<f64>,
+    trials: usize,
+}
+
+impl QValues {
+    /// The q-value of a p-value in the family.
+    pub fn q_value(&self, p_value: f64) -> f64 {
+        // Ties share the adjusted value of the last of them
+        let at = self.sorted.partition_point(|&p| p <= p_value);
+        let m = self.trials.max(at) as f64;
+        let own = p_value * m / at.max(1) as f64;
+        match self.adjusted.get(at) {
+            Some(&next) => own.min(next),
+            None => own,
+        }
+        .min(1.0)
+    }
+
+    /// The standing of a p-value in the family at level `alpha`.
+    pub fn standing(&self, p_value: f64, alpha: f64) -> FdrStanding {
+        let q_value = self.q_value(p_value);
+        FdrStanding {
+            p_value,
+            q_value,
+            significant: q_value <= alpha,
+            family_trials: self.trials,
+        }
+    }
+
+    pub fn trials(&self) -> usize {
+        self.trials
+    }
+}
+
+/// A leaderboard entry's place in its symbol's FDR family.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FdrStanding {
+    /// Raw p-value of the run's daily returns.
+    pub p_value: f64,
+    /// BH-adjusted p-value over the family.
+    pub q_value: f64,
+    /// `q_value` is within the session's FDR level.
+    pub significant: bool,
+    /// Family size the q-value was computed over.
+    pub family_trials: usize,
 }
 
 #[cfg(test)]
@@ -361,7 +521,10 @@ mod tests {
         for &t in &[0.5, 1.0, 2.0, 3.0] {
             let left = t_cdf(-t, df);
             let right = t_cdf(t, df);
-            assert!((left + right - 1.0).abs() < 1e-10, "t={t}: {left} + {right} != 1.0");
+            assert!(
+                (left + right - 1.0).abs() < 1e-10,
+                "t={t}: {left} + {right} != 1.0"
+            );
         }
     }
 
@@ -559,4 +722,69 @@ mod tests {
         let sig_large = results_large.iter().find(|r| r.config_id == "a").unwrap();
         assert!(sig_large.adjusted_p >= sig_small.adjusted_p);
     }
+
+    #[test]
+    fn significant_entry_loses_significance_as_trials_grow() {
+        let mut family = FdrFamily::new();
+        family.add("champion".into(), 0.004);
+        family.add("runner_up".into(), 0.2);
+        let early = family.q_values().standing(0.004, 0.05);
+        assert!(early.significant);
+        assert_eq!(early.family_trials, 2);
+
+        // More trials of the same hypothesis family, none better
+        for i in 0..8 {
+            family.add(format!("noise_{i}"), 0.6);
+        }
+        family.add_untested(5);
+        let late = family.q_values().standing(0.004, 0.05);
+        assert_eq!(late.family_trials, 15);
+        assert!((late.q_value - 0.06).abs() < 1e-12);
+        assert!(!late.significant);
+    }
+
+    #[test]
+    fn incremental_q_values_match_batch_correction() {
+        let pvals: Vec<(String, f64)> = [0.03, 0.001, 0.04, 0.5, 0.03, 0.02]
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| (format!("c{i}"), p))
+            .collect();
+        let mut family = FdrFamily::new();
+        for (id, p) in &pvals {
+            family.add(id.clone(), *p);
+        }
+        let q = family.q_values();
+        for result in benjamini_hochberg(&pvals, 0.05) {
+            assert!((q.q_value(result.raw_p) - result.adjusted_p).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn absorbed_records_count_untested_trials() {
+        let mut record = FdrRecord::new("s1", "SPY", "abc");
+        record.trials = 10;
+        record.p_values = vec![("a".into(), 0.01), ("b".into(), 0.3)];
+        let mut family = FdrFamily::new();
+        family.absorb(&record);
+        family.absorb(&record);
+        assert_eq!(family.len(), 4);
+        assert_eq!(family.trials(), 20);
+        let results = family.apply_correction(0.05);
+        // 0.01 ranks 1-2 of 20: adjusted 0.01 * 20 / 2
+        assert!((results[0].adjusted_p - 0.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn returns_p_value_ranks_steady_gains_first() {
+        let steady: Vec<f64> = (0..100).map(|i| 100.0 * 1.001_f64.powi(i)).collect();
+        let noisy: Vec<f64> = (0..100)
+            .map(|i| 100.0 + if i % 2 == 0 { 1.0 } else { -1.0 } + i as f64 * 0.001)
+            .collect();
+        let steady_p = returns_p_value(&steady).unwrap();
+        let noisy_p = returns_p_value(&noisy).unwrap();
+        assert!(steady_p < 0.001);
+        assert!(noisy_p > steady_p);
+        assert_eq!(returns_p_value(&[100.0]), None);
+    }
 }
@@ -1,5 +1,7 @@
 //! Fitness function — configurable metric selector for strategy ranking.
 
+use std::cmp::Ordering;
+
 use crate::metrics::PerformanceMetrics;
 use serde::{Deserialize, Serialize};
 
@@ -17,7 +19,32 @@ pub enum FitnessMetric {
 }
 
 impl FitnessMetric {
+    pub const ALL: [FitnessMetric; 7] = [
+        FitnessMetric::Sharpe,
+        FitnessMetric::Sortino,
+        FitnessMetric::Calmar,
+        FitnessMetric::Cagr,
+        FitnessMetric::WinRate,
+        FitnessMetric::ProfitFactor,
+        FitnessMetric::MaxDrawdown,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Sharpe => "sharpe",
+            Self::Sortino => "sortino",
+            Self::Calmar => "calmar",
+            Self::Cagr => "cagr",
+            Self::WinRate => "win-rate",
+            Self::ProfitFactor => "profit-factor",
+            Self::MaxDrawdown => "max-drawdown",
+        }
+    }
+
     /// Extract the relevant metric value from a PerformanceMetrics struct.
+    ///
+    /// A non-finite value means the metric is missing for this run; use
+    /// [`Self::compare`] to rank extracted values.
     pub fn extract(&self, metrics: &PerformanceMetrics) -> f64 {
         match self {
             Self::Sharpe => metrics.sharpe,
@@ -42,9 +69,37 @@ impl FitnessMetric {
     ///
     /// For all metrics including MaxDrawdown, `a > b` is the correct comparison:
     /// higher Sharpe/CAGR/etc. is better, and for MaxDrawdown, -0.05 > -0.20
-    /// means less negative (smaller drawdown) is better.
+    /// means less negative (smaller drawdown) is better. A finite value is
+    /// better than a missing (non-finite) one.
     pub fn is_better(&self, a: f64, b: f64) -> bool {
-        a > b
+        self.compare(a, b) == Ordering::Less
+    }
+
+    /// Total order over metric values, best first.
+    ///
+    /// Missing (non-finite) values sort last and compare equal to each other.
+    pub fn compare(&self, a: f64, b: f64) -> Ordering {
+        match (a.is_finite(), b.is_finite()) {
+            (true, true) => b.total_cmp(&a),
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => Ordering::Equal,
+        }
+    }
+}
+
+impl std::str::FromStr for FitnessMetric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_ascii_lowercase().replace('_', "-");
+        Self::ALL
+            .into_iter()
+            .find(|m| m.label() == s)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(|m| m.label()).collect();
+                format!("unknown fitness metric '{s}' ({})", names.join(", "))
+            })
     }
 }
 
@@ -67,6 +122,11 @@ mod tests {
             max_consecutive_wins: 5,
             max_consecutive_losses: 3,
             avg_losing_streak: 1.5,
+            time_in_market: 0.0,
+            avg_exposure: 0.0,
+            invested_return: 0.0,
+            idle_return: 0.0,
+            exposure_adjusted_cagr: 0.0,
         }
     }
 
@@ -92,6 +152,15 @@ mod tests {
         assert!(!FitnessMetric::MaxDrawdown.is_higher_better());
     }
 
+    #[test]
+    fn parses_labels() {
+        for metric in FitnessMetric::ALL {
+            assert_eq!(metric.label().parse::<FitnessMetric>(), Ok(metric));
+        }
+        assert_eq!("win_rate".parse(), Ok(FitnessMetric::WinRate));
+        assert!("alpha".parse::<FitnessMetric>().is_err());
+    }
+
     #[test]
     fn default_is_sharpe() {
         assert_eq!(FitnessMetric::default(), FitnessMetric::Sharpe);
@@ -109,4 +178,17 @@ mod tests {
         assert!(FitnessMetric::MaxDrawdown.is_better(-0.05, -0.20));
         assert!(!FitnessMetric::MaxDrawdown.is_better(-0.20, -0.05));
     }
+
+    #[test]
+    fn missing_values_sort_last() {
+        let metric = FitnessMetric::Sharpe;
+        let mut values = [f64::NAN, 1.0, f64::INFINITY, -2.0, f64::NEG_INFINITY, 3.0];
+        values.sort_by(|a, b| metric.compare(*a, *b));
+        assert_eq!(&values[..3], &[3.0, 1.0, -2.0]);
+        assert!(values[3..].iter().all(|v| !v.is_finite()));
+
+        assert!(metric.is_better(-5.0, f64::NAN));
+        assert!(!metric.is_better(f64::INFINITY, 1.0));
+        assert!(!metric.is_better(f64::NAN, f64::NAN));
+    }
 }
@@ -6,16 +6,54 @@
 //!
 //! The history enables meta-analysis: "which signal type contributes most to
 //! performance across all tested configurations?"
+//!
+//! Sessions also append periodic convergence checkpoints. These are wrapped
+//! as `{"checkpoint": ...}` lines, so `read_all` skips them and
+//! `read_checkpoints` reads only them. Leaderboard re-tests are written the
+//! same way as `{"refresh": ...}` lines (see `read_refreshes`), and holdout
+//! evaluations as `{"holdout": ...}` lines (see `read_holdouts`). Batches of
+//! FDR trials go in `{"fdr": ...}` lines (see `read_fdr`).
+//!
+//! Sessions run with a holdout window mark their entries as evaluated on the
+//! discovery window ([`EvalWindow::Discovery`]).
+//!
+//! Entries for configs mutated from a champion carry a `Provenance`, so
+//! `lineage` can walk a config's ancestry back to its original random sample.
+//!
+//! Instead of tuning [`WriteFilter`] thresholds, a session can name a
+//! [`WritePreset`]. With the `Everything` and `ErrorsAndWarnings` presets,
+//! failed runs are written as `{"error": ...}` lines (see `read_errors`).
+//!
+//! Entries may embed the run's equity curve and trades ([`EntryDetail`]).
+//! An entry that serializes larger than the history's entry budget moves
+//! them to a side file next to the history and keeps only a reference and a
+//! downsampled curve; [`YoloHistory::load_detail`] reads either form.
+//!
+//! Several processes may share one history directory (a CLI session next to
+//! the TUI, or two sessions on different symbols). Writers serialize through
+//! a [`FileLock`] on a `<file>.lock` sidecar, and each append is a single
+//! write of a complete line. Lines torn by a crashed writer are skipped on
+//! read and counted in [`HistoryRead::torn_lines`].
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::{self, OpenOptions};
-use std::io::{self, BufRead, Write};
+use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
 
 use serde::{Deserialize, Serialize};
 
+use crate::adaptive::{ExplorationMode, ModeTransition};
+use crate::compute_budget::ComputeUsage;
+use crate::convergence::ConvergencePoint;
+use crate::fdr::FdrRecord;
+use crate::holdout::HoldoutEntry;
 use crate::metrics::PerformanceMetrics;
-use trendlab_core::fingerprint::RunFingerprint;
+use crate::refresh::RefreshEntry;
+use crate::runner::RunErrorKind;
+use trendlab_core::domain::{FullHash, TradeRecord};
+use trendlab_core::fingerprint::{Provenance, RunFingerprint};
 
 /// A single history entry: fingerprint + metrics snapshot.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,7 +61,140 @@ pub struct HistoryEntry {
     pub fingerprint: RunFingerprint,
     pub metrics: PerformanceMetrics,
     pub trade_count: usize,
+    #[serde(with = "crate::metrics::finite_f64")]
     pub fitness_score: f64,
+    /// Downsampled equity returns (see `overlap::downsample_returns`), kept so
+    /// overlap analysis can run on history without the full curve.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub return_sample: Vec<f64>,
+    /// Parent config and changes, when the config was mutated from a
+    /// champion rather than sampled from scratch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Provenance>,
+    /// Bars the run was evaluated on.
+    #[serde(default, skip_serializing_if = "EvalWindow::is_full")]
+    pub window: EvalWindow,
+    /// Data quality warnings on the bars the run used.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub data_warnings: usize,
+    /// Equity curve and trades, when the session embeds them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<EntryDetail>,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+/// A run's full equity curve and trade list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunDetail {
+    pub equity_curve: Vec<f64>,
+    pub trades: Vec<TradeRecord>,
+}
+
+/// Where a history entry keeps its [`RunDetail`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryDetail {
+    Embedded(RunDetail),
+    /// Moved to a side file because the entry was over the entry budget.
+    Referenced {
+        /// Detail file, relative to the history file's directory.
+        path: PathBuf,
+        /// Equity curve downsampled to at most [`DETAIL_SAMPLE_POINTS`].
+        equity_sample: Vec<f64>,
+    },
+}
+
+/// Points kept from the equity curve of a referenced entry.
+pub const DETAIL_SAMPLE_POINTS: usize = 64;
+
+/// Evenly spaced points of `curve`, always keeping the first and last.
+fn downsample_curve(curve: &[f64], max_points: usize) -> Vec<f64> {
+    if curve.len() <= max_points || max_points < 2 {
+        return curve.to_vec();
+    }
+    let step = (curve.len() - 1) as f64 / (max_points - 1) as f64;
+    (0..max_points)
+        .map(|i| curve[(i as f64 * step).round() as usize])
+        .collect()
+}
+
+/// Which part of the loaded data a history entry was evaluated on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvalWindow {
+    /// Every loaded bar: the session held nothing out.
+    #[default]
+    Full,
+    /// The bars before the session's holdout boundary.
+    Discovery,
+}
+
+impl EvalWindow {
+    pub fn is_full(&self) -> bool {
+        *self == Self::Full
+    }
+}
+
+/// A convergence checkpoint written to the history file during a session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryCheckpoint {
+    pub session_id: String,
+    pub point: ConvergencePoint,
+    pub iterations_since_improvement: usize,
+    /// Adaptive exploration mode at the checkpoint, when the session has a
+    /// controller.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<ExplorationMode>,
+    /// Mode changes since the previous checkpoint.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transitions: Vec<ModeTransition>,
+    /// Compute the session had spent at the checkpoint.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compute: Option<ComputeUsage>,
+}
+
+/// On-disk wrapper that keeps checkpoint lines distinct from entries.
+#[derive(Serialize, Deserialize)]
+struct CheckpointLine {
+    checkpoint: HistoryCheckpoint,
+}
+
+/// On-disk wrapper for leaderboard re-test records.
+#[derive(Serialize, Deserialize)]
+struct RefreshLine {
+    refresh: RefreshEntry,
+}
+
+/// On-disk wrapper for holdout evaluation records.
+#[derive(Serialize, Deserialize)]
+struct HoldoutLine {
+    holdout: HoldoutEntry,
+}
+
+/// On-disk wrapper for FDR trial batches.
+#[derive(Serialize, Deserialize)]
+struct FdrLine {
+    fdr: FdrRecord,
+}
+
+/// A backtest that failed, recorded under the presets that keep errors.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunErrorEntry {
+    pub session_id: String,
+    pub iteration: usize,
+    pub symbol: String,
+    pub full_hash: FullHash,
+    pub kind: RunErrorKind,
+    pub message: String,
+}
+
+/// On-disk wrapper for failed-run records.
+#[derive(Serialize, Deserialize)]
+struct ErrorLine {
+    error: RunErrorEntry,
 }
 
 /// Criteria for whether a run should be persisted to the history file.
@@ -58,20 +229,286 @@ impl WriteFilter {
         let sharpe_ok = self.min_sharpe.map_or(true, |min| metrics.sharpe >= min);
         cagr_ok || sharpe_ok
     }
+
+    /// A filter that writes every entry. Presets select entries themselves
+    /// and run the history with this filter.
+    pub fn unfiltered() -> Self {
+        Self {
+            min_trades: 0,
+            min_cagr: None,
+            min_sharpe: None,
+        }
+    }
+}
+
+/// Named history write policies.
+///
+/// A session with a preset ignores its [`WriteFilter`] thresholds. Only
+/// runs that reach ranking (at least one trade, finite metrics) produce
+/// entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WritePreset {
+    /// Every ranked run, plus a line per failed run.
+    Everything,
+    /// Runs that entered, or improved their slot on, a per-symbol leaderboard.
+    LeaderboardOnly,
+    /// Runs whose fitness is in the top quartile of the symbol's ranked runs
+    /// so far in the session.
+    TopQuartileByFitness,
+    /// Failed runs, and runs whose bars carried data quality warnings.
+    ErrorsAndWarnings,
+}
+
+impl WritePreset {
+    pub const ALL: [WritePreset; 4] = [
+        WritePreset::Everything,
+        WritePreset::LeaderboardOnly,
+        WritePreset::TopQuartileByFitness,
+        WritePreset::ErrorsAndWarnings,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            WritePreset::Everything => "everything",
+            WritePreset::LeaderboardOnly => "leaderboard-only",
+            WritePreset::TopQuartileByFitness => "top-quartile",
+            WritePreset::ErrorsAndWarnings => "errors-and-warnings",
+        }
+    }
+
+    /// Whether failed runs are written as error lines.
+    pub fn keeps_errors(self) -> bool {
+        matches!(
+            self,
+            WritePreset::Everything | WritePreset::ErrorsAndWarnings
+        )
+    }
+}
+
+impl std::fmt::Display for WritePreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl std::str::FromStr for WritePreset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|p| p.label() == s.replace('_', "-"))
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(|p| p.label()).collect();
+                format!("unknown write preset '{s}' ({})", names.join(", "))
+            })
+    }
+}
+
+/// Running fitness distribution per symbol, for
+/// [`WritePreset::TopQuartileByFitness`].
+#[derive(Debug, Default)]
+pub struct FitnessQuartiles {
+    seen: HashMap<String, Vec<f64>>,
+}
+
+impl FitnessQuartiles {
+    /// Record `fitness` for `symbol`. True if fewer than a quarter of the
+    /// symbol's runs so far (this one included, rounded up) beat it.
+    pub fn observe(&mut self, symbol: &str, fitness: f64) -> bool {
+        let seen = self.seen.entry(symbol.to_string()).or_default();
+        let at = seen.partition_point(|&f| f < fitness);
+        seen.insert(at, fitness);
+        let better = seen.len() - seen.partition_point(|&f| f <= fitness);
+        better < seen.len().div_ceil(4)
+    }
+}
+
+/// Which of `entries` `preset` keeps when applied to a finished history.
+///
+/// `LeaderboardOnly` keeps each symbol's best `leaderboard_size` configs by
+/// fitness, and `TopQuartileByFitness` the top quartile of each symbol's
+/// entries, so the result can differ from what a live session writes.
+pub fn preset_selection(
+    entries: &[HistoryEntry],
+    preset: WritePreset,
+    leaderboard_size: usize,
+) -> Vec<bool> {
+    let mut by_symbol: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, entry) in entries.iter().enumerate() {
+        by_symbol
+            .entry(&entry.fingerprint.symbol)
+            .or_default()
+            .push(i);
+    }
+
+    let mut keep = vec![false; entries.len()];
+    for mut indices in by_symbol.into_values() {
+        indices.sort_by(|&a, &b| {
+            entries[b]
+                .fitness_score
+                .total_cmp(&entries[a].fitness_score)
+        });
+        match preset {
+            WritePreset::Everything => indices.iter().for_each(|&i| keep[i] = true),
+            WritePreset::ErrorsAndWarnings => {
+                for i in indices {
+                    keep[i] = entries[i].data_warnings > 0;
+                }
+            }
+            WritePreset::TopQuartileByFitness => {
+                for &i in &indices[..indices.len().div_ceil(4)] {
+                    keep[i] = true;
+                }
+            }
+            WritePreset::LeaderboardOnly => {
+                let mut ranked = HashSet::new();
+                for i in indices {
+                    if ranked.len() == leaderboard_size {
+                        break;
+                    }
+                    if ranked.insert(&entries[i].fingerprint.full_hash) {
+                        keep[i] = true;
+                    }
+                }
+            }
+        }
+    }
+    keep
+}
+
+/// How long [`FileLock::acquire`] waits before giving up.
+pub const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Lock files older than this are assumed to be left by a crashed writer.
+pub const STALE_LOCK_AGE: Duration = Duration::from_secs(30);
+
+/// Advisory cross-process lock on a file, held as a `<file>.lock` sidecar.
+///
+/// The sidecar is created with `create_new`, which is atomic on every
+/// platform, and removed on drop. A sidecar older than [`STALE_LOCK_AGE`] is
+/// broken, so a writer killed mid-write cannot wedge later sessions. Only
+/// writers that take the lock are serialized; readers never block.
+#[derive(Debug)]
+pub struct FileLock {
+    path: PathBuf,
+}
+
+impl FileLock {
+    /// Take the lock for `target`, waiting up to [`LOCK_TIMEOUT`].
+    pub fn acquire(target: &Path) -> io::Result<Self> {
+        let path = lock_path(target);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let start = SystemTime::now();
+        let mut backoff = Duration::from_millis(1);
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    // Holder pid, for whoever finds a stale lock
+                    let _ = write!(file, "{}", std::process::id());
+                    return Ok(Self { path });
+                }
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                    if is_stale(&path) {
+                        let _ = fs::remove_file(&path);
+                        continue;
+                    }
+                    if start.elapsed().unwrap_or_default() >= LOCK_TIMEOUT {
+                        return Err(io::Error::new(
+                            io::ErrorKind::TimedOut,
+                            format!("timed out waiting for lock {}", path.display()),
+                        ));
+                    }
+                    thread::sleep(backoff);
+                    backoff = (backoff * 2).min(Duration::from_millis(50));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Path of the sidecar lock file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn lock_path(target: &Path) -> PathBuf {
+    let mut name = target.file_name().unwrap_or_default().to_os_string();
+    name.push(".lock");
+    target.with_file_name(name)
+}
+
+fn is_stale(lock: &Path) -> bool {
+    fs::metadata(lock)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age > STALE_LOCK_AGE)
+}
+
+/// Records read from a history file, plus the number of torn lines skipped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryRead<T> {
+    pub records: Vec<T>,
+    /// Lines that are not valid JSON, typically the tail of a write cut
+    /// short by a crash. Lines of other record kinds are not counted.
+    pub torn_lines: usize,
+}
+
+/// Outcome of [`YoloHistory::compact_into`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompactReport {
+    pub kept: usize,
+    pub dropped: usize,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
 }
 
 /// JSONL history file manager.
 ///
 /// Appends filtered entries to a JSONL file. Each line is an independent JSON
 /// object, making the format resilient to partial writes and easy to stream.
+/// Handles on the same file, in this process or another, may append
+/// concurrently (see [`FileLock`]).
 pub struct YoloHistory {
     path: PathBuf,
     filter: WriteFilter,
+    fsync: bool,
+    entry_budget: Option<usize>,
 }
 
 impl YoloHistory {
     pub fn new(path: PathBuf, filter: WriteFilter) -> Self {
-        Self { path, filter }
+        Self {
+            path,
+            filter,
+            fsync: false,
+            entry_budget: None,
+        }
+    }
+
+    /// Sync each append to disk before returning. Default: off.
+    pub fn with_fsync(mut self, fsync: bool) -> Self {
+        self.fsync = fsync;
+        self
+    }
+
+    /// Move the embedded detail of entries serializing larger than `bytes`
+    /// to a side file. Default: no budget.
+    pub fn with_entry_budget(mut self, bytes: Option<usize>) -> Self {
+        self.entry_budget = bytes;
+        self
     }
 
     /// Append an entry to the history file if it passes the write filter.
@@ -82,23 +519,128 @@ impl YoloHistory {
             return Ok(false);
         }
 
-        let json = serde_json::to_string(entry)
+        let mut json = to_json(entry)?;
+        if let (Some(budget), Some(EntryDetail::Embedded(detail))) =
+            (self.entry_budget, &entry.detail)
+        {
+            if json.len() > budget {
+                let mut slim = entry.clone();
+                slim.detail = Some(self.offload(&entry.fingerprint, detail)?);
+                json = to_json(&slim)?;
+            }
+        }
+        self.append_line(&json)?;
+        Ok(true)
+    }
+
+    /// Directory detail side files are written to: `<history stem>_detail`
+    /// next to the history file.
+    pub fn detail_dir(&self) -> PathBuf {
+        let stem = self.path.file_stem().unwrap_or_default().to_string_lossy();
+        self.path.with_file_name(format!("{stem}_detail"))
+    }
+
+    /// Write `detail` to a side file and return the reference to it.
+    fn offload(&self, fingerprint: &RunFingerprint, detail: &RunDetail) -> io::Result<EntryDetail> {
+        let dir = self.detail_dir();
+        fs::create_dir_all(&dir)?;
+        let name = format!("{}.json", fingerprint.run_id.as_hex());
+        fs::write(dir.join(&name), to_json(detail)?)?;
+        Ok(EntryDetail::Referenced {
+            path: PathBuf::from(dir.file_name().unwrap_or_default()).join(name),
+            equity_sample: downsample_curve(&detail.equity_curve, DETAIL_SAMPLE_POINTS),
+        })
+    }
+
+    /// The equity curve and trades of `entry`, read from its side file when
+    /// they were moved there. `None` if the entry carries no detail.
+    pub fn load_detail(&self, entry: &HistoryEntry) -> io::Result<Option<RunDetail>> {
+        match &entry.detail {
+            None => Ok(None),
+            Some(EntryDetail::Embedded(detail)) => Ok(Some(detail.clone())),
+            Some(EntryDetail::Referenced { path, .. }) => {
+                let path = self.path.parent().unwrap_or(Path::new(".")).join(path);
+                let json = fs::read_to_string(&path)?;
+                serde_json::from_str(&json)
+                    .map(Some)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+        }
+    }
+
+    /// Append a failed-run record. Bypasses the write filter.
+    pub fn append_error(&self, entry: &RunErrorEntry) -> io::Result<()> {
+        let line = ErrorLine {
+            error: entry.clone(),
+        };
+        self.append_line(&to_json(&line)?)
+    }
+
+    /// Append a convergence checkpoint. Checkpoints bypass the write filter.
+    pub fn append_checkpoint(&self, checkpoint: &HistoryCheckpoint) -> io::Result<()> {
+        let line = CheckpointLine {
+            checkpoint: checkpoint.clone(),
+        };
+        let json = serde_json::to_string(&line)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        self.append_line(&json)
+    }
+
+    /// Append a leaderboard re-test record. Bypasses the write filter.
+    pub fn append_refresh(&self, entry: &RefreshEntry) -> io::Result<()> {
+        let line = RefreshLine {
+            refresh: entry.clone(),
+        };
+        let json = serde_json::to_string(&line)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        self.append_line(&json)
+    }
+
+    /// Append a holdout evaluation record. Bypasses the write filter.
+    pub fn append_holdout(&self, entry: &HoldoutEntry) -> io::Result<()> {
+        let line = HoldoutLine {
+            holdout: entry.clone(),
+        };
+        let json = serde_json::to_string(&line)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        self.append_line(&json)
+    }
 
+    /// Append a batch of FDR trials. Bypasses the write filter.
+    pub fn append_fdr(&self, record: &FdrRecord) -> io::Result<()> {
+        let line = FdrLine {
+            fdr: record.clone(),
+        };
+        self.append_line(&to_json(&line)?)
+    }
+
+    fn append_line(&self, json: &str) -> io::Result<()> {
         // Ensure parent directory exists
         if let Some(parent) = self.path.parent() {
             fs::create_dir_all(parent)?;
         }
 
+        let _lock = FileLock::acquire(&self.path)?;
         let mut file = OpenOptions::new()
             .create(true)
+            .read(true)
             .append(true)
             .open(&self.path)?;
 
-        writeln!(file, "{json}")?;
-        file.flush()?;
+        // The whole line goes out in one write. If a crashed writer left a
+        // torn line behind, terminate it first so this line stays intact.
+        let mut line = String::with_capacity(json.len() + 2);
+        if !ends_with_newline(&mut file)? {
+            line.push('\n');
+        }
+        line.push_str(json);
+        line.push('\n');
+        file.write_all(line.as_bytes())?;
 
-        Ok(true)
+        if self.fsync {
+            file.sync_data()?;
+        }
+        Ok(())
     }
 
     /// Get the current file size in bytes.
@@ -112,28 +654,154 @@ impl YoloHistory {
 
     /// Read all entries from the history file.
     ///
-    /// Skips malformed lines (logged but not fatal).
+    /// Skips malformed lines (not fatal); see [`YoloHistory::read_all_checked`]
+    /// for how many were torn.
     pub fn read_all(&self) -> io::Result<Vec<HistoryEntry>> {
+        self.read_all_checked().map(|read| read.records)
+    }
+
+    /// Read all entries, counting torn lines instead of silently dropping them.
+    pub fn read_all_checked(&self) -> io::Result<HistoryRead<HistoryEntry>> {
+        self.read_records(|line| serde_json::from_str::<HistoryEntry>(line).ok())
+    }
+
+    /// Read all convergence checkpoints, in write order.
+    pub fn read_checkpoints(&self) -> io::Result<Vec<HistoryCheckpoint>> {
+        self.read_records(|line| {
+            serde_json::from_str::<CheckpointLine>(line)
+                .ok()
+                .map(|parsed| parsed.checkpoint)
+        })
+        .map(|read| read.records)
+    }
+
+    /// Read all leaderboard re-test records, in write order.
+    pub fn read_refreshes(&self) -> io::Result<Vec<RefreshEntry>> {
+        self.read_records(|line| {
+            serde_json::from_str::<RefreshLine>(line)
+                .ok()
+                .map(|parsed| parsed.refresh)
+        })
+        .map(|read| read.records)
+    }
+
+    /// Read all holdout evaluation records, in write order.
+    pub fn read_holdouts(&self) -> io::Result<Vec<HoldoutEntry>> {
+        self.read_records(|line| {
+            serde_json::from_str::<HoldoutLine>(line)
+                .ok()
+                .map(|parsed| parsed.holdout)
+        })
+        .map(|read| read.records)
+    }
+
+    /// Read all FDR trial batches, in write order.
+    pub fn read_fdr(&self) -> io::Result<Vec<FdrRecord>> {
+        self.read_records(|line| {
+            serde_json::from_str::<FdrLine>(line)
+                .ok()
+                .map(|parsed| parsed.fdr)
+        })
+        .map(|read| read.records)
+    }
+
+    /// Read all failed-run records, in write order.
+    pub fn read_errors(&self) -> io::Result<Vec<RunErrorEntry>> {
+        self.read_records(|line| {
+            serde_json::from_str::<ErrorLine>(line)
+                .ok()
+                .map(|parsed| parsed.error)
+        })
+        .map(|read| read.records)
+    }
+
+    /// Rewrite this history into `dest`, keeping the entries `preset`
+    /// selects (see [`preset_selection`]) and every other record, except
+    /// failed-run lines under presets that drop errors.
+    ///
+    /// Kept entries go through `dest`'s write filter and entry budget.
+    /// Entries whose detail was moved to a side file of this history get it
+    /// back embedded when `dest` lives in another directory.
+    pub fn compact_into(
+        &self,
+        dest: &YoloHistory,
+        preset: WritePreset,
+        leaderboard_size: usize,
+    ) -> io::Result<CompactReport> {
+        let lines = self.read_records(|line| {
+            serde_json::from_str::<serde::de::IgnoredAny>(line)
+                .is_ok()
+                .then(|| line.to_string())
+        })?;
+        let mut entries = Vec::new();
+        let mut others = Vec::new();
+        for line in lines.records {
+            match serde_json::from_str::<HistoryEntry>(&line) {
+                Ok(entry) => entries.push(entry),
+                Err(_) => others.push(line),
+            }
+        }
+
+        let same_dir = self.path.parent() == dest.path.parent();
+        let keep = preset_selection(&entries, preset, leaderboard_size);
+        let mut report = CompactReport {
+            bytes_before: self.file_size_bytes()?,
+            ..CompactReport::default()
+        };
+        for (mut entry, keep) in entries.into_iter().zip(keep) {
+            if !keep {
+                report.dropped += 1;
+                continue;
+            }
+            if !same_dir && matches!(entry.detail, Some(EntryDetail::Referenced { .. })) {
+                entry.detail = self.load_detail(&entry)?.map(EntryDetail::Embedded);
+            }
+            if dest.append(&entry)? {
+                report.kept += 1;
+            } else {
+                report.dropped += 1;
+            }
+        }
+        for line in others {
+            let is_error = serde_json::from_str::<ErrorLine>(&line).is_ok();
+            if !is_error || preset.keeps_errors() {
+                dest.append_line(&line)?;
+            }
+        }
+        report.bytes_after = dest.file_size_bytes()?;
+        Ok(report)
+    }
+
+    /// Parse every line with `parse`. Lines it rejects are skipped; those
+    /// that are not JSON at all (or not UTF-8) are counted as torn.
+    fn read_records<T>(&self, parse: impl Fn(&str) -> Option<T>) -> io::Result<HistoryRead<T>> {
+        let mut read = HistoryRead {
+            records: Vec::new(),
+            torn_lines: 0,
+        };
         if !self.path.exists() {
-            return Ok(Vec::new());
+            return Ok(read);
         }
 
         let file = fs::File::open(&self.path)?;
-        let reader = io::BufReader::new(file);
-        let mut entries = Vec::new();
-
-        for line in reader.lines() {
+        for line in io::BufReader::new(file).split(b'\n') {
             let line = line?;
+            let Ok(line) = std::str::from_utf8(&line) else {
+                read.torn_lines += 1;
+                continue;
+            };
             if line.trim().is_empty() {
                 continue;
             }
-            match serde_json::from_str::<HistoryEntry>(&line) {
-                Ok(entry) => entries.push(entry),
-                Err(_) => continue, // skip malformed lines
+            match parse(line) {
+                Some(record) => read.records.push(record),
+                None if serde_json::from_str::<serde::de::IgnoredAny>(line).is_err() => {
+                    read.torn_lines += 1;
+                }
+                None => {}
             }
         }
-
-        Ok(entries)
+        Ok(read)
     }
 
     /// Path to the history file.
@@ -142,6 +810,54 @@ impl YoloHistory {
     }
 }
 
+fn to_json<T: Serialize>(value: &T) -> io::Result<String> {
+    serde_json::to_string(value).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Whether `file` is empty or ends with a newline.
+fn ends_with_newline(file: &mut fs::File) -> io::Result<bool> {
+    if file.metadata()?.len() == 0 {
+        return Ok(true);
+    }
+    let mut last = [0u8; 1];
+    file.seek(SeekFrom::End(-1))?;
+    file.read_exact(&mut last)?;
+    Ok(last[0] == b'\n')
+}
+
+/// One generation of a config's ancestry (see [`lineage`]).
+#[derive(Debug, Clone)]
+pub struct LineageStep<'a> {
+    pub full_hash: FullHash,
+    /// First history entry written for this config; `None` when the config
+    /// never made it past the write filter.
+    pub entry: Option<&'a HistoryEntry>,
+}
+
+/// Ancestry of the config with `full_hash`, from the config itself back to
+/// the original random sample.
+///
+/// Each step follows the provenance of the config's first history entry. The
+/// chain ends at an entry without provenance, or at a parent missing from
+/// the history (its step then has no entry).
+pub fn lineage<'a>(entries: &'a [HistoryEntry], full_hash: &FullHash) -> Vec<LineageStep<'a>> {
+    let mut chain = Vec::new();
+    let mut seen = HashSet::new();
+    let mut current = full_hash.clone();
+    while seen.insert(current.clone()) {
+        let entry = entries.iter().find(|e| e.fingerprint.full_hash == current);
+        chain.push(LineageStep {
+            full_hash: current.clone(),
+            entry,
+        });
+        match entry.and_then(|e| e.provenance.as_ref()) {
+            Some(provenance) => current = provenance.parent_full_hash.clone(),
+            None => break,
+        }
+    }
+    chain
+}
+
 /// Statistical summary for a component type (signal, PM, execution, filter).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComponentSummary {
@@ -246,7 +962,7 @@ mod tests {
     use chrono::NaiveDate;
     use std::collections::BTreeMap;
     use tempfile::TempDir;
-    use trendlab_core::domain::{DatasetHash, RunId};
+    use trendlab_core::domain::{DatasetHash, PositionSide, RunId};
     use trendlab_core::fingerprint::{ComponentConfig, StrategyConfig, TradingMode};
 
     fn make_fingerprint(signal_type: &str, sharpe: f64) -> (RunFingerprint, PerformanceMetrics) {
@@ -254,18 +970,22 @@ mod tests {
             signal: ComponentConfig {
                 component_type: signal_type.into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
             position_manager: ComponentConfig {
                 component_type: "atr_trailing".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
             execution_model: ComponentConfig {
                 component_type: "next_bar_open".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
             signal_filter: ComponentConfig {
                 component_type: "no_filter".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
         };
 
@@ -279,6 +999,8 @@ mod tests {
             trading_mode: TradingMode::LongOnly,
             initial_capital: 100_000.0,
             strategy_config: config.clone(),
+            execution_profile: None,
+            risk_profile: None,
             config_hash: config.config_hash(),
             full_hash: config.full_hash(),
             dataset_hash: DatasetHash::from_bytes(b"test"),
@@ -298,6 +1020,11 @@ mod tests {
             max_consecutive_wins: 4,
             max_consecutive_losses: 3,
             avg_losing_streak: 1.5,
+            time_in_market: 0.0,
+            avg_exposure: 0.0,
+            invested_return: 0.0,
+            idle_return: 0.0,
+            exposure_adjusted_cagr: 0.0,
         };
 
         (fp, metrics)
@@ -347,6 +1074,11 @@ mod tests {
             metrics,
             trade_count: 20,
             fitness_score: 1.5,
+            return_sample: Vec::new(),
+            provenance: None,
+            window: EvalWindow::Full,
+            data_warnings: 0,
+            detail: None,
         };
 
         let written = history.append(&entry).unwrap();
@@ -357,6 +1089,44 @@ mod tests {
         assert!((entries[0].fitness_score - 1.5).abs() < 1e-10);
     }
 
+    #[test]
+    fn checkpoints_are_kept_apart_from_entries() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("history.jsonl");
+        let history = YoloHistory::new(path, WriteFilter::default());
+
+        let (fp, metrics) = make_fingerprint("donchian", 1.5);
+        let entry = HistoryEntry {
+            fingerprint: fp,
+            metrics,
+            trade_count: 20,
+            fitness_score: 1.5,
+            return_sample: Vec::new(),
+            provenance: None,
+            window: EvalWindow::Full,
+            data_warnings: 0,
+            detail: None,
+        };
+        let checkpoint = HistoryCheckpoint {
+            session_id: "yolo-42-0".into(),
+            point: ConvergencePoint {
+                iteration: 100,
+                best_fitness: BTreeMap::from([("SPY".to_string(), 1.5)]),
+                new_entry_rate: 0.25,
+            },
+            iterations_since_improvement: 40,
+            mode: None,
+            transitions: Vec::new(),
+            compute: None,
+        };
+        history.append(&entry).unwrap();
+        history.append_checkpoint(&checkpoint).unwrap();
+        history.append(&entry).unwrap();
+
+        assert_eq!(history.read_all().unwrap().len(), 2);
+        assert_eq!(history.read_checkpoints().unwrap(), vec![checkpoint]);
+    }
+
     #[test]
     fn append_filtered_entry_not_written() {
         let tmp = TempDir::new().unwrap();
@@ -370,6 +1140,11 @@ mod tests {
             metrics,
             trade_count: 20,
             fitness_score: -2.0,
+            return_sample: Vec::new(),
+            provenance: None,
+            window: EvalWindow::Full,
+            data_warnings: 0,
+            detail: None,
         };
 
         let written = history.append(&entry).unwrap();
@@ -393,6 +1168,11 @@ mod tests {
             metrics,
             trade_count: 20,
             fitness_score: 1.5,
+            return_sample: Vec::new(),
+            provenance: None,
+            window: EvalWindow::Full,
+            data_warnings: 0,
+            detail: None,
         };
         history.append(&entry).unwrap();
 
@@ -413,6 +1193,11 @@ mod tests {
                 metrics,
                 trade_count: 20,
                 fitness_score: 1.0 + i as f64 * 0.5,
+                return_sample: Vec::new(),
+                provenance: None,
+                window: EvalWindow::Full,
+                data_warnings: 0,
+                detail: None,
             };
             history.append(&entry).unwrap();
         }
@@ -421,6 +1206,166 @@ mod tests {
         assert_eq!(entries.len(), 5);
     }
 
+    #[test]
+    fn concurrent_appends_through_separate_handles_lose_nothing() {
+        const THREADS: usize = 8;
+        const PER_THREAD: usize = 50;
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("history.jsonl");
+
+        let handles: Vec<_> = (0..THREADS)
+            .map(|t| {
+                let path = path.clone();
+                thread::spawn(move || {
+                    let history = YoloHistory::new(path, WriteFilter::default());
+                    for i in 0..PER_THREAD {
+                        let (fp, metrics) = make_fingerprint(&format!("signal_{t}"), 1.0);
+                        let entry = HistoryEntry {
+                            fingerprint: fp,
+                            metrics,
+                            trade_count: 20,
+                            fitness_score: i as f64,
+                            // Long lines make interleaved writes likely
+                            return_sample: vec![0.001; 2_000],
+                            provenance: None,
+                            window: EvalWindow::Full,
+                            data_warnings: 0,
+                            detail: None,
+                        };
+                        assert!(history.append(&entry).unwrap());
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), THREADS * PER_THREAD);
+        for line in contents.lines() {
+            serde_json::from_str::<HistoryEntry>(line).expect("every line parses");
+        }
+        let read = YoloHistory::new(path.clone(), WriteFilter::default())
+            .read_all_checked()
+            .unwrap();
+        assert_eq!(read.torn_lines, 0);
+        for t in 0..THREADS {
+            let mut fitness: Vec<f64> = read
+                .records
+                .iter()
+                .filter(|e| {
+                    e.fingerprint.strategy_config.signal.component_type == format!("signal_{t}")
+                })
+                .map(|e| e.fitness_score)
+                .collect();
+            fitness.sort_by(f64::total_cmp);
+            let expected: Vec<f64> = (0..PER_THREAD).map(|i| i as f64).collect();
+            assert_eq!(fitness, expected, "thread {t}");
+        }
+        assert!(!lock_path(&path).exists(), "lock released");
+    }
+
+    #[test]
+    fn torn_trailing_line_is_counted_and_next_append_stays_intact() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("history.jsonl");
+        let history = YoloHistory::new(path.clone(), WriteFilter::default()).with_fsync(true);
+
+        let (fp, metrics) = make_fingerprint("donchian", 1.5);
+        let entry = HistoryEntry {
+            fingerprint: fp,
+            metrics,
+            trade_count: 20,
+            fitness_score: 1.5,
+            return_sample: Vec::new(),
+            provenance: None,
+            window: EvalWindow::Full,
+            data_warnings: 0,
+            detail: None,
+        };
+        history.append(&entry).unwrap();
+
+        // A writer crashed halfway through a line
+        let json = serde_json::to_string(&entry).unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&json.as_bytes()[..json.len() / 2]).unwrap();
+        drop(file);
+
+        let read = history.read_all_checked().unwrap();
+        assert_eq!(read.records.len(), 1);
+        assert_eq!(read.torn_lines, 1);
+
+        history.append(&entry).unwrap();
+        let read = history.read_all_checked().unwrap();
+        assert_eq!(read.records.len(), 2);
+        assert_eq!(read.torn_lines, 1);
+
+        // Checkpoint lines are another record kind, not torn lines
+        let checkpoint = HistoryCheckpoint {
+            session_id: "s".into(),
+            point: ConvergencePoint {
+                iteration: 10,
+                best_fitness: BTreeMap::new(),
+                new_entry_rate: 0.5,
+            },
+            iterations_since_improvement: 0,
+            mode: None,
+            transitions: Vec::new(),
+            compute: None,
+        };
+        history.append_checkpoint(&checkpoint).unwrap();
+        assert_eq!(history.read_all_checked().unwrap().torn_lines, 1);
+    }
+
+    #[test]
+    fn stale_lock_is_broken() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("history.jsonl");
+        let lock = fs::File::create(lock_path(&path)).unwrap();
+        lock.set_modified(SystemTime::now() - STALE_LOCK_AGE * 2)
+            .unwrap();
+        drop(lock);
+
+        let held = FileLock::acquire(&path).unwrap();
+        assert!(held.path().exists());
+        drop(held);
+        assert!(!lock_path(&path).exists());
+    }
+
+    #[test]
+    fn return_sample_roundtrips_and_is_optional() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("history.jsonl");
+        let history = YoloHistory::new(path.clone(), WriteFilter::default());
+
+        let (fp, metrics) = make_fingerprint("donchian", 1.5);
+        let mut entry = HistoryEntry {
+            fingerprint: fp,
+            metrics,
+            trade_count: 20,
+            fitness_score: 1.5,
+            return_sample: vec![0.01, -0.02, 0.005],
+            provenance: None,
+            window: EvalWindow::Full,
+            data_warnings: 0,
+            detail: None,
+        };
+        history.append(&entry).unwrap();
+
+        // Lines written before return samples existed still parse
+        entry.return_sample.clear();
+        let legacy = serde_json::to_string(&entry).unwrap();
+        assert!(!legacy.contains("return_sample"));
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(file, "{legacy}").unwrap();
+
+        let entries = history.read_all().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].return_sample, vec![0.01, -0.02, 0.005]);
+        assert!(entries[1].return_sample.is_empty());
+    }
+
     #[test]
     fn read_nonexistent_file_returns_empty() {
         let tmp = TempDir::new().unwrap();
@@ -443,18 +1388,33 @@ mod tests {
                 metrics: m1,
                 trade_count: 20,
                 fitness_score: 1.5,
+                return_sample: Vec::new(),
+                provenance: None,
+                window: EvalWindow::Full,
+                data_warnings: 0,
+                detail: None,
             },
             HistoryEntry {
                 fingerprint: fp2,
                 metrics: m2,
                 trade_count: 20,
                 fitness_score: 2.0,
+                return_sample: Vec::new(),
+                provenance: None,
+                window: EvalWindow::Full,
+                data_warnings: 0,
+                detail: None,
             },
             HistoryEntry {
                 fingerprint: fp3,
                 metrics: m3,
                 trade_count: 20,
                 fitness_score: 1.0,
+                return_sample: Vec::new(),
+                provenance: None,
+                window: EvalWindow::Full,
+                data_warnings: 0,
+                detail: None,
             },
         ];
 
@@ -466,6 +1426,78 @@ mod tests {
         assert!((summary["bollinger"].mean_sharpe - 1.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn lineage_walks_back_to_the_random_sample() {
+        let entry = |signal: &str, parent: Option<&HistoryEntry>| {
+            let (fingerprint, metrics) = make_fingerprint(signal, 1.0);
+            let provenance = parent.map(|p| {
+                Provenance::new(&p.fingerprint.strategy_config, &fingerprint.strategy_config)
+            });
+            HistoryEntry {
+                fingerprint,
+                metrics,
+                trade_count: 20,
+                fitness_score: 1.0,
+                return_sample: Vec::new(),
+                provenance,
+                window: EvalWindow::Full,
+                data_warnings: 0,
+                detail: None,
+            }
+        };
+        let root = entry("donchian", None);
+        let parent = entry("bollinger", Some(&root));
+        let child = entry("keltner", Some(&parent));
+        let orphan = entry("supertrend", Some(&entry("aroon", None)));
+        let entries = vec![root, parent, child, orphan];
+
+        let chain = lineage(&entries, &entries[2].fingerprint.full_hash);
+        let signals: Vec<&str> = chain
+            .iter()
+            .map(|s| {
+                s.entry
+                    .unwrap()
+                    .fingerprint
+                    .strategy_config
+                    .signal
+                    .component_type
+                    .as_str()
+            })
+            .collect();
+        assert_eq!(signals, vec!["keltner", "bollinger", "donchian"]);
+
+        // A parent that never passed the write filter ends the chain
+        let chain = lineage(&entries, &entries[3].fingerprint.full_hash);
+        assert_eq!(chain.len(), 2);
+        assert!(chain[1].entry.is_none());
+
+        // Unknown hash: a single empty step
+        let unknown = FullHash::from_bytes(b"unknown");
+        let chain = lineage(&entries, &unknown);
+        assert_eq!(chain.len(), 1);
+        assert!(chain[0].entry.is_none());
+    }
+
+    #[test]
+    fn provenance_is_omitted_when_absent() {
+        let (fingerprint, metrics) = make_fingerprint("donchian", 1.0);
+        let entry = HistoryEntry {
+            fingerprint,
+            metrics,
+            trade_count: 20,
+            fitness_score: 1.0,
+            return_sample: Vec::new(),
+            provenance: None,
+            window: EvalWindow::Full,
+            data_warnings: 0,
+            detail: None,
+        };
+        let json = serde_json::to_string(&entry).unwrap();
+        assert!(!json.contains("provenance"));
+        let deser: HistoryEntry = serde_json::from_str(&json).unwrap();
+        assert!(deser.provenance.is_none());
+    }
+
     #[test]
     fn write_filter_serialization() {
         let filter = WriteFilter::default();
@@ -473,4 +1505,258 @@ mod tests {
         let deser: WriteFilter = serde_json::from_str(&json).unwrap();
         assert_eq!(deser.min_trades, 5);
     }
+
+    fn make_entry(run: &str, fitness: f64) -> HistoryEntry {
+        let (mut fingerprint, metrics) = make_fingerprint("donchian", fitness);
+        fingerprint.run_id = RunId::from_bytes(run.as_bytes());
+        HistoryEntry {
+            fingerprint,
+            metrics,
+            trade_count: 20,
+            fitness_score: fitness,
+            return_sample: Vec::new(),
+            provenance: None,
+            window: EvalWindow::Full,
+            data_warnings: 0,
+            detail: None,
+        }
+    }
+
+    /// Five years of daily equity and `trades` round trips.
+    fn make_detail(trades: usize) -> RunDetail {
+        let trade = TradeRecord {
+            symbol: "SPY".into(),
+            side: PositionSide::Long,
+            entry_bar: 55,
+            entry_date: NaiveDate::from_ymd_opt(2024, 3, 15).unwrap(),
+            entry_price: 450.50,
+            entry_trigger_margin: None,
+            exit_bar: 72,
+            exit_date: NaiveDate::from_ymd_opt(2024, 4, 10).unwrap(),
+            exit_price: 468.25,
+            quantity: 222.0,
+            gross_pnl: 3939.50,
+            commission: 20.0,
+            slippage: 10.0,
+            net_pnl: 3909.50,
+            bars_held: 17,
+            mae: -500.0,
+            mfe: 4200.0,
+            initial_stop_price: None,
+            entry_source: None,
+            exit_source: None,
+            liquidated_at_end: false,
+            expiry_bar: None,
+            signal_id: None,
+            signal_type: Some("donchian_breakout".into()),
+            pm_type: Some("atr_trailing".into()),
+            execution_model: Some("next_bar_open".into()),
+            filter_type: Some("no_filter".into()),
+        };
+        RunDetail {
+            equity_curve: (0..1260).map(|i| 100_000.0 + i as f64 * 13.7).collect(),
+            trades: vec![trade; trades],
+        }
+    }
+
+    #[test]
+    fn write_preset_parses_labels() {
+        for preset in WritePreset::ALL {
+            assert_eq!(preset.label().parse::<WritePreset>().unwrap(), preset);
+        }
+        assert_eq!(
+            "top_quartile".parse::<WritePreset>().unwrap(),
+            WritePreset::TopQuartileByFitness
+        );
+        assert!("best".parse::<WritePreset>().is_err());
+        let json = serde_json::to_string(&WritePreset::LeaderboardOnly).unwrap();
+        assert_eq!(json, "\"leaderboard_only\"");
+    }
+
+    #[test]
+    fn fitness_quartiles_track_each_symbol() {
+        let mut quartiles = FitnessQuartiles::default();
+        assert!(quartiles.observe("SPY", 1.0));
+        assert!(!quartiles.observe("SPY", 0.5));
+        assert!(quartiles.observe("SPY", 2.0));
+        assert!(!quartiles.observe("SPY", 1.5));
+        // A new symbol starts its own distribution
+        assert!(quartiles.observe("QQQ", 0.1));
+    }
+
+    #[test]
+    fn preset_selection_applies_to_a_finished_history() {
+        let mut entries: Vec<HistoryEntry> = (0..8)
+            .map(|i| make_entry(&format!("run{i}"), i as f64))
+            .collect();
+        entries[2].data_warnings = 3;
+
+        let kept = |preset, size| -> Vec<usize> {
+            let keep = preset_selection(&entries, preset, size);
+            (0..entries.len()).filter(|&i| keep[i]).collect()
+        };
+        assert_eq!(kept(WritePreset::Everything, 0).len(), 8);
+        assert_eq!(kept(WritePreset::TopQuartileByFitness, 0), vec![6, 7]);
+        assert_eq!(kept(WritePreset::ErrorsAndWarnings, 0), vec![2]);
+        // Every entry shares one config hash: one leaderboard slot
+        assert_eq!(kept(WritePreset::LeaderboardOnly, 10), vec![7]);
+    }
+
+    #[test]
+    fn oversized_entry_detail_moves_to_a_side_file() {
+        let tmp = TempDir::new().unwrap();
+        let history = YoloHistory::new(tmp.path().join("history.jsonl"), WriteFilter::default())
+            .with_entry_budget(Some(8 * 1024));
+
+        let detail = make_detail(40);
+        let mut small = make_entry("small", 1.0);
+        small.detail = Some(EntryDetail::Embedded(RunDetail {
+            equity_curve: vec![100.0, 101.0],
+            trades: Vec::new(),
+        }));
+        let mut large = make_entry("large", 2.0);
+        large.detail = Some(EntryDetail::Embedded(detail.clone()));
+        history.append(&small).unwrap();
+        history.append(&large).unwrap();
+
+        let entries = history.read_all().unwrap();
+        assert!(matches!(entries[0].detail, Some(EntryDetail::Embedded(_))));
+        let Some(EntryDetail::Referenced {
+            path,
+            equity_sample,
+        }) = &entries[1].detail
+        else {
+            panic!("large entry kept its detail inline");
+        };
+        assert!(path.starts_with("history_detail"));
+        assert_eq!(equity_sample.len(), DETAIL_SAMPLE_POINTS);
+        assert_eq!(equity_sample.first(), detail.equity_curve.first());
+        assert_eq!(equity_sample.last(), detail.equity_curve.last());
+        assert!(history.file_size_bytes().unwrap() < 8 * 1024 * 2);
+
+        let loaded = history.load_detail(&entries[1]).unwrap().unwrap();
+        assert_eq!(loaded.equity_curve, detail.equity_curve);
+        assert_eq!(loaded.trades.len(), 40);
+        assert_eq!(
+            history
+                .load_detail(&entries[0])
+                .unwrap()
+                .unwrap()
+                .trades
+                .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn error_lines_are_kept_apart_from_entries() {
+        let tmp = TempDir::new().unwrap();
+        let history = YoloHistory::new(tmp.path().join("history.jsonl"), WriteFilter::default());
+        let entry = make_entry("ok", 1.0);
+        let error = RunErrorEntry {
+            session_id: "session".into(),
+            iteration: 7,
+            symbol: "SPY".into(),
+            full_hash: entry.fingerprint.full_hash.clone(),
+            kind: RunErrorKind::EngineInvariant,
+            message: "equity went NaN".into(),
+        };
+        history.append(&entry).unwrap();
+        history.append_error(&error).unwrap();
+
+        assert_eq!(history.read_all().unwrap().len(), 1);
+        assert_eq!(history.read_errors().unwrap(), vec![error]);
+        assert_eq!(history.read_all_checked().unwrap().torn_lines, 0);
+    }
+
+    #[test]
+    fn fdr_lines_round_trip_apart_from_entries() {
+        let tmp = TempDir::new().unwrap();
+        let history = YoloHistory::new(tmp.path().join("history.jsonl"), WriteFilter::default());
+        let mut record = FdrRecord::new("session", "SPY", "dataset");
+        record.iteration = 12;
+        record.trials = 3;
+        record.p_values = vec![("abc".into(), 0.02)];
+        history.append(&make_entry("ok", 1.0)).unwrap();
+        history.append_fdr(&record).unwrap();
+
+        assert_eq!(history.read_all().unwrap().len(), 1);
+        assert_eq!(history.read_fdr().unwrap(), vec![record]);
+        assert_eq!(history.read_all_checked().unwrap().torn_lines, 0);
+    }
+
+    #[test]
+    fn compact_rewrites_a_history_under_a_preset() {
+        let tmp = TempDir::new().unwrap();
+        let source = YoloHistory::new(tmp.path().join("full.jsonl"), WriteFilter::default())
+            .with_entry_budget(Some(4 * 1024));
+        for i in 0..8 {
+            let mut entry = make_entry(&format!("run{i}"), i as f64);
+            entry.detail = Some(EntryDetail::Embedded(make_detail(5)));
+            source.append(&entry).unwrap();
+        }
+        source
+            .append_error(&RunErrorEntry {
+                session_id: "session".into(),
+                iteration: 8,
+                symbol: "SPY".into(),
+                full_hash: make_entry("x", 0.0).fingerprint.full_hash,
+                kind: RunErrorKind::DataLoad,
+                message: "no bars".into(),
+            })
+            .unwrap();
+
+        let out_dir = tmp.path().join("out");
+        fs::create_dir(&out_dir).unwrap();
+        let dest = YoloHistory::new(out_dir.join("top.jsonl"), WriteFilter::unfiltered());
+        let report = source
+            .compact_into(&dest, WritePreset::TopQuartileByFitness, 500)
+            .unwrap();
+        assert_eq!((report.kept, report.dropped), (2, 6));
+
+        // Detail is re-embedded, since the side files stay with the source
+        let entries = dest.read_all().unwrap();
+        assert_eq!(entries.len(), 2);
+        for entry in &entries {
+            assert!(matches!(entry.detail, Some(EntryDetail::Embedded(_))));
+        }
+        assert!(dest.read_errors().unwrap().is_empty());
+    }
+
+    #[test]
+    fn top_quartile_session_stays_under_a_byte_budget() {
+        const ITERATIONS: usize = 1_000;
+        const BUDGET_BYTES: u64 = 1024 * 1024;
+
+        let tmp = TempDir::new().unwrap();
+        let history = YoloHistory::new(tmp.path().join("history.jsonl"), WriteFilter::unfiltered())
+            .with_entry_budget(Some(4 * 1024));
+        let mut quartiles = FitnessQuartiles::default();
+        let mut state = 42u64;
+        let mut written = 0;
+        let mut unbudgeted_bytes = 0;
+        for i in 0..ITERATIONS {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let fitness = (state >> 11) as f64 / (1u64 << 53) as f64 * 3.0 - 1.0;
+            let mut entry = make_entry(&format!("run{i}"), fitness);
+            entry.detail = Some(EntryDetail::Embedded(make_detail(40)));
+            unbudgeted_bytes += to_json(&entry).unwrap().len() + 1;
+            if quartiles.observe("SPY", fitness) {
+                history.append(&entry).unwrap();
+                written += 1;
+            }
+        }
+
+        let size = history.file_size_bytes().unwrap();
+        assert!(
+            written > ITERATIONS / 4 && written < ITERATIONS / 2,
+            "{written}"
+        );
+        assert!(size < BUDGET_BYTES, "history is {size} bytes");
+        // The same session writing everything inline would be far larger
+        assert!(unbudgeted_bytes as u64 > 20 * BUDGET_BYTES);
+        assert_eq!(history.read_all().unwrap().len(), written);
+    }
 }
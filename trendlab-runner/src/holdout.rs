@@ -0,0 +1,327 @@
+//! Discovery/holdout embargo — a window YOLO never searches.
+//!
+//! Walk-forward inside the promotion ladder still draws its folds from the
+//! range the session searched, so the final leaderboard is fit to every bar
+//! it loaded. With a [`HoldoutConfig`] on the session, the loaded data is cut
+//! at a boundary before the first iteration (see [`LoadedData::split_at`]),
+//! and every discovery backtest only receives the bars before it.
+//!
+//! [`YoloResult::evaluate_holdout`] runs each final leaderboard config once
+//! on the bars from the boundary on — at session end, or later through
+//! `trendlab holdout evaluate` — and records a [`HoldoutRecord`] next to the
+//! discovery result. The degradation ratio is holdout Sharpe over discovery
+//! Sharpe, with the same edge cases as walk-forward. A session refuses a
+//! second evaluation: a holdout looked at twice is no longer untouched.
+//!
+//! Each evaluated config also produces a [`HoldoutEntry`] for the JSONL
+//! history (see [`crate::history::YoloHistory::append_holdout`]).
+
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use trendlab_core::domain::FullHash;
+
+use crate::data_loader::LoadedData;
+use crate::metrics::{finite_f64, PerformanceMetrics};
+use crate::runner::{decode_execution_preset, run_backtest_profiled};
+use crate::walk_forward::{compute_degradation_ratio, DegradationFlag};
+use crate::yolo::YoloResult;
+
+/// Where a session's holdout window starts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HoldoutConfig {
+    /// Trailing share of the loaded bars held out. Ignored when `start` is set.
+    #[serde(default = "default_fraction")]
+    pub fraction: f64,
+    /// First date of the holdout window.
+    #[serde(default)]
+    pub start: Option<NaiveDate>,
+    /// Evaluate the final leaderboards on the holdout when the session ends.
+    /// Off leaves it for `trendlab holdout evaluate`.
+    #[serde(default = "default_evaluate_at_end")]
+    pub evaluate_at_end: bool,
+}
+
+impl Default for HoldoutConfig {
+    fn default() -> Self {
+        Self {
+            fraction: default_fraction(),
+            start: None,
+            evaluate_at_end: default_evaluate_at_end(),
+        }
+    }
+}
+
+fn default_fraction() -> f64 {
+    0.2
+}
+
+fn default_evaluate_at_end() -> bool {
+    true
+}
+
+impl HoldoutConfig {
+    /// Index of the first holdout bar in `dates`. Both windows must keep at
+    /// least one bar.
+    pub fn boundary(&self, dates: &[NaiveDate]) -> Result<usize, HoldoutError> {
+        let boundary = match self.start {
+            Some(start) => dates.partition_point(|d| *d < start),
+            None => {
+                if !(self.fraction > 0.0 && self.fraction < 1.0) {
+                    return Err(HoldoutError::InvalidFraction(self.fraction));
+                }
+                let held = (dates.len() as f64 * self.fraction).round() as usize;
+                dates.len() - held
+            }
+        };
+        if boundary == 0 || boundary >= dates.len() {
+            return Err(HoldoutError::EmptyWindow {
+                window: if boundary == 0 {
+                    "discovery"
+                } else {
+                    "holdout"
+                },
+                bars: dates.len(),
+            });
+        }
+        Ok(boundary)
+    }
+}
+
+/// Reasons a holdout split or evaluation was refused.
+#[derive(Debug, Error)]
+pub enum HoldoutError {
+    #[error("holdout fraction {0} must be between 0 and 1")]
+    InvalidFraction(f64),
+    #[error("holdout boundary leaves the {window} window empty ({bars} bars loaded)")]
+    EmptyWindow { window: &'static str, bars: usize },
+    #[error("session was run without a holdout window")]
+    NotConfigured,
+    #[error("holdout already evaluated at {0}; a session evaluates it once")]
+    AlreadyEvaluated(NaiveDateTime),
+    #[error("holdout data starts {actual}, before the holdout boundary {boundary}")]
+    Overlap {
+        boundary: NaiveDate,
+        actual: NaiveDate,
+    },
+    #[error("no holdout bars loaded")]
+    NoData,
+}
+
+/// One leaderboard config's result on the holdout window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoldoutRecord {
+    pub evaluated_at: NaiveDateTime,
+    pub holdout_start: NaiveDate,
+    pub holdout_end: NaiveDate,
+    pub dataset_hash: String,
+    pub metrics: PerformanceMetrics,
+    pub trade_count: usize,
+    #[serde(with = "finite_f64")]
+    pub discovery_fitness: f64,
+    #[serde(with = "finite_f64")]
+    pub holdout_fitness: f64,
+    /// Holdout Sharpe / discovery Sharpe (a difference for a near-zero
+    /// discovery Sharpe, see `degradation_flag`).
+    pub degradation_ratio: Option<f64>,
+    pub degradation_flag: DegradationFlag,
+}
+
+/// History record of one holdout evaluation, written as a `{"holdout": ...}`
+/// line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoldoutEntry {
+    pub session_id: String,
+    pub symbol: String,
+    pub full_hash: FullHash,
+    pub record: HoldoutRecord,
+}
+
+/// The one holdout evaluation of a session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoldoutReport {
+    pub evaluated_at: NaiveDateTime,
+    pub holdout_start: NaiveDate,
+    pub holdout_end: NaiveDate,
+    /// One entry per evaluated config, per symbol in leaderboard order.
+    pub evaluated: Vec<HoldoutEntry>,
+    /// Configs whose holdout run failed, with the error.
+    #[serde(default)]
+    pub failed: Vec<(String, FullHash, String)>,
+}
+
+impl HoldoutReport {
+    /// Median degradation ratio over the evaluated configs that have one.
+    pub fn median_degradation_ratio(&self) -> Option<f64> {
+        let mut ratios: Vec<f64> = self
+            .evaluated
+            .iter()
+            .filter_map(|e| e.record.degradation_ratio)
+            .collect();
+        if ratios.is_empty() {
+            return None;
+        }
+        ratios.sort_by(f64::total_cmp);
+        let mid = ratios.len() / 2;
+        Some(if ratios.len() % 2 == 0 {
+            (ratios[mid - 1] + ratios[mid]) / 2.0
+        } else {
+            ratios[mid]
+        })
+    }
+}
+
+impl YoloResult {
+    /// Run every per-symbol leaderboard config once on `holdout`, record the
+    /// result on the entry and in the cross-symbol leaderboard, and keep the
+    /// report on the session.
+    ///
+    /// `holdout` must not reach back before the session's holdout boundary.
+    /// Rankings are left alone: they stay the discovery rankings.
+    pub fn evaluate_holdout(
+        &mut self,
+        holdout: &LoadedData,
+        now: NaiveDateTime,
+    ) -> Result<&HoldoutReport, HoldoutError> {
+        if let Some(ref report) = self.holdout {
+            return Err(HoldoutError::AlreadyEvaluated(report.evaluated_at));
+        }
+        let boundary = self.holdout_start.ok_or(HoldoutError::NotConfigured)?;
+        let dates = &holdout.aligned.dates;
+        let (&first, &last) = dates
+            .first()
+            .zip(dates.last())
+            .ok_or(HoldoutError::NoData)?;
+        if first < boundary {
+            return Err(HoldoutError::Overlap {
+                boundary,
+                actual: first,
+            });
+        }
+
+        let mut report = HoldoutReport {
+            evaluated_at: now,
+            holdout_start: first,
+            holdout_end: last,
+            evaluated: Vec::new(),
+            failed: Vec::new(),
+        };
+        let mut symbols: Vec<String> = self.leaderboards.keys().cloned().collect();
+        symbols.sort();
+        for symbol in symbols {
+            if !holdout.aligned.bars.contains_key(&symbol) {
+                continue;
+            }
+            let lb = self.leaderboards.get_mut(&symbol).unwrap();
+            let metric = lb.fitness_metric();
+            for entry in lb.entries_mut() {
+                let strategy_config = &entry.result.config;
+                let full_hash = strategy_config.full_hash();
+                let inputs = entry.result.inputs.as_ref();
+                let run = run_backtest_profiled(
+                    strategy_config,
+                    &holdout.aligned,
+                    &symbol,
+                    inputs.map_or(self.config.trading_mode, |i| i.trading_mode),
+                    entry.result.initial_capital,
+                    inputs.map_or(self.config.position_size_pct, |i| i.position_size_pct),
+                    inputs.map_or_else(
+                        || decode_execution_preset(&strategy_config.execution_model.params),
+                        |i| i.execution_preset,
+                    ),
+                    inputs.and_then(|i| i.execution_profile.as_ref()),
+                    &holdout.dataset_hash,
+                    holdout.has_synthetic,
+                );
+                let result = match run {
+                    Ok(result) => result,
+                    Err(err) => {
+                        report
+                            .failed
+                            .push((symbol.clone(), full_hash, err.to_string()));
+                        continue;
+                    }
+                };
+
+                let (ratio, flag) =
+                    compute_degradation_ratio(entry.result.metrics.sharpe, result.metrics.sharpe);
+                let record = HoldoutRecord {
+                    evaluated_at: now,
+                    holdout_start: first,
+                    holdout_end: last,
+                    dataset_hash: holdout.dataset_hash.clone(),
+                    trade_count: result.trades.len(),
+                    discovery_fitness: entry.fitness_score,
+                    holdout_fitness: metric.extract(&result.metrics),
+                    degradation_ratio: ratio.filter(|r| r.is_finite()),
+                    degradation_flag: flag,
+                    metrics: result.metrics,
+                };
+                entry.holdout = Some(record.clone());
+                self.cross_leaderboard
+                    .set_holdout(&full_hash, &symbol, record.clone());
+                report.evaluated.push(HoldoutEntry {
+                    session_id: entry.session_id.clone(),
+                    symbol: symbol.clone(),
+                    full_hash,
+                    record,
+                });
+            }
+        }
+
+        Ok(self.holdout.insert(report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dates(n: usize) -> Vec<NaiveDate> {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        (0..n as u64)
+            .map(|i| start + chrono::Days::new(i))
+            .collect()
+    }
+
+    #[test]
+    fn boundary_from_fraction_or_date() {
+        let d = dates(100);
+        assert_eq!(HoldoutConfig::default().boundary(&d).unwrap(), 80);
+
+        let by_date = HoldoutConfig {
+            start: Some(d[30]),
+            ..HoldoutConfig::default()
+        };
+        assert_eq!(by_date.boundary(&d).unwrap(), 30);
+
+        let late = HoldoutConfig {
+            start: Some(d[99] + chrono::Days::new(1)),
+            ..HoldoutConfig::default()
+        };
+        assert!(matches!(
+            late.boundary(&d),
+            Err(HoldoutError::EmptyWindow {
+                window: "holdout",
+                ..
+            })
+        ));
+        let all = HoldoutConfig {
+            fraction: 1.0,
+            ..HoldoutConfig::default()
+        };
+        assert!(matches!(
+            all.boundary(&d),
+            Err(HoldoutError::InvalidFraction(_))
+        ));
+    }
+
+    #[test]
+    fn config_defaults_fill_in_from_toml() {
+        let config: HoldoutConfig = toml::from_str(r#"start = "2023-07-01""#).unwrap();
+        assert_eq!(config.start, NaiveDate::from_ymd_opt(2023, 7, 1));
+        assert_eq!(config.fraction, 0.2);
+        assert!(config.evaluate_at_end);
+    }
+}
@@ -0,0 +1,282 @@
+//! Arrow interop — a run's bars and trades as typed tables for notebooks.
+//!
+//! CSV artifacts lose their types and are slow to parse for long runs. These
+//! tables keep them: dates are Arrow `Date32`, counts unsigned integers,
+//! missing values nulls. Tables are Polars DataFrames (Arrow memory) and are
+//! written as Arrow IPC files, which `polars.read_ipc`, `pyarrow.feather` and
+//! `pandas.read_feather` all open directly.
+//!
+//! An IPC file holds a single schema, so a run gets two:
+//!
+//! - `results.arrow` — one row per bar, see [`BAR_COLUMNS`]
+//! - `trades.arrow` — one row per round-trip trade, see [`TRADE_COLUMNS`]
+//!
+//! Each file's schema metadata records [`ARROW_SCHEMA_VERSION`] under
+//! `trendlab_schema_version`. Columns are only ever appended within a
+//! version; renaming, retyping or removing one bumps it.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+use polars::prelude::*;
+use trendlab_core::domain::{DecisionSource, TradeRecord};
+
+use crate::export::side_label;
+use crate::runner::BacktestResult;
+
+/// Version of the column layouts below.
+pub const ARROW_SCHEMA_VERSION: u32 = 1;
+
+/// Schema metadata key holding [`ARROW_SCHEMA_VERSION`].
+pub const SCHEMA_VERSION_KEY: &str = "trendlab_schema_version";
+
+/// File name of the per-bar table inside an artifact directory.
+pub const RESULTS_ARROW: &str = "results.arrow";
+
+/// File name of the trade table inside an artifact directory.
+pub const TRADES_ARROW: &str = "trades.arrow";
+
+/// `results.arrow` columns, in order:
+///
+/// | column | type | |
+/// |---|---|---|
+/// | `bar_index` | UInt64 | |
+/// | `date` | Date32 | null for results saved without dates |
+/// | `equity` | Float64 | |
+/// | `exposure` | Float64 | gross exposure / equity; null when not recorded |
+/// | `drawdown` | Float64 | equity over its running peak, minus 1 (≤ 0) |
+/// | `pnl_<source>` | Float64 | PnL booked on the bar per [`DecisionSource`], 0 without fills |
+///
+/// The `pnl_` columns follow [`DecisionSource::ALL`].
+pub const BAR_COLUMNS: [&str; 5] = ["bar_index", "date", "equity", "exposure", "drawdown"];
+
+/// `trades.arrow` columns, in order. The same as `trades.csv`, with `side`
+/// lower-case:
+///
+/// | column | type |
+/// |---|---|
+/// | `symbol`, `side` | Utf8 |
+/// | `entry_bar` | UInt64 |
+/// | `entry_date` | Date32 |
+/// | `entry_price` | Float64 |
+/// | `exit_bar` | UInt64 |
+/// | `exit_date` | Date32 |
+/// | `exit_price`, `quantity`, `gross_pnl`, `commission`, `slippage`, `net_pnl` | Float64 |
+/// | `bars_held` | UInt64 |
+/// | `mae`, `mfe` | Float64 |
+/// | `signal_type`, `pm_type`, `execution_model`, `filter_type` | Utf8, nullable |
+/// | `entry_trigger_margin` | Float64, nullable |
+/// | `entry_source`, `exit_source` | Utf8, nullable |
+/// | `liquidated_at_end` | Boolean |
+pub const TRADE_COLUMNS: [&str; 24] = [
+    "symbol",
+    "side",
+    "entry_bar",
+    "entry_date",
+    "entry_price",
+    "exit_bar",
+    "exit_date",
+    "exit_price",
+    "quantity",
+    "gross_pnl",
+    "commission",
+    "slippage",
+    "net_pnl",
+    "bars_held",
+    "mae",
+    "mfe",
+    "signal_type",
+    "pm_type",
+    "execution_model",
+    "filter_type",
+    "entry_trigger_margin",
+    "entry_source",
+    "exit_source",
+    "liquidated_at_end",
+];
+
+/// Name of the per-bar PnL column for `source`.
+pub fn pnl_column(source: DecisionSource) -> String {
+    format!("pnl_{source}")
+}
+
+/// The per-bar table of `result` (see [`BAR_COLUMNS`]).
+pub fn bars_frame(result: &BacktestResult) -> Result<DataFrame> {
+    let n = result.equity_curve.len();
+    let bar_index: Vec<u64> = (0..n as u64).collect();
+    let dates: Vec<Option<i32>> = (0..n)
+        .map(|i| result.dates.get(i).map(|&d| date32(d)))
+        .collect();
+    let exposure: Vec<Option<f64>> = (0..n)
+        .map(|i| result.exposure_curve.get(i).copied())
+        .collect();
+    let mut peak = f64::NEG_INFINITY;
+    let drawdown: Vec<f64> = result
+        .equity_curve
+        .iter()
+        .map(|&equity| {
+            peak = peak.max(equity);
+            if peak > 0.0 {
+                equity / peak - 1.0
+            } else {
+                0.0
+            }
+        })
+        .collect();
+
+    let mut pnl: BTreeMap<DecisionSource, Vec<f64>> = DecisionSource::ALL
+        .iter()
+        .map(|&source| (source, vec![0.0; n]))
+        .collect();
+    for bar in &result.attribution.bars {
+        for (source, &value) in &bar.pnl {
+            if let Some(cell) = pnl.get_mut(source).and_then(|c| c.get_mut(bar.bar_index)) {
+                *cell += value;
+            }
+        }
+    }
+
+    let mut columns = vec![
+        Column::new("bar_index".into(), bar_index),
+        date_column("date", dates)?,
+        Column::new("equity".into(), result.equity_curve.clone()),
+        Column::new("exposure".into(), exposure),
+        Column::new("drawdown".into(), drawdown),
+    ];
+    for source in DecisionSource::ALL {
+        let values = pnl.remove(&source).unwrap_or_default();
+        columns.push(Column::new(pnl_column(source).into(), values));
+    }
+    DataFrame::new(columns).context("failed to build bar table")
+}
+
+/// The trade table of `trades` (see [`TRADE_COLUMNS`]).
+pub fn trades_frame(trades: &[TradeRecord]) -> Result<DataFrame> {
+    let f64_col = |name: &str, f: fn(&TradeRecord) -> f64| {
+        Column::new(name.into(), trades.iter().map(f).collect::<Vec<f64>>())
+    };
+    let u64_col = |name: &str, f: fn(&TradeRecord) -> usize| {
+        Column::new(
+            name.into(),
+            trades.iter().map(|t| f(t) as u64).collect::<Vec<u64>>(),
+        )
+    };
+    let str_col = |name: &str, f: fn(&TradeRecord) -> Option<String>| {
+        Column::new(name.into(), trades.iter().map(f).collect::<Vec<_>>())
+    };
+    let date_col = |name: &str, f: fn(&TradeRecord) -> NaiveDate| {
+        date_column(name, trades.iter().map(|t| Some(date32(f(t)))).collect())
+    };
+
+    DataFrame::new(vec![
+        Column::new(
+            "symbol".into(),
+            trades.iter().map(|t| t.symbol.as_str()).collect::<Vec<_>>(),
+        ),
+        Column::new(
+            "side".into(),
+            trades
+                .iter()
+                .map(|t| side_label(t.side))
+                .collect::<Vec<_>>(),
+        ),
+        u64_col("entry_bar", |t| t.entry_bar),
+        date_col("entry_date", |t| t.entry_date)?,
+        f64_col("entry_price", |t| t.entry_price),
+        u64_col("exit_bar", |t| t.exit_bar),
+        date_col("exit_date", |t| t.exit_date)?,
+        f64_col("exit_price", |t| t.exit_price),
+        f64_col("quantity", |t| t.quantity),
+        f64_col("gross_pnl", |t| t.gross_pnl),
+        f64_col("commission", |t| t.commission),
+        f64_col("slippage", |t| t.slippage),
+        f64_col("net_pnl", |t| t.net_pnl),
+        u64_col("bars_held", |t| t.bars_held),
+        f64_col("mae", |t| t.mae),
+        f64_col("mfe", |t| t.mfe),
+        str_col("signal_type", |t| t.signal_type.clone()),
+        str_col("pm_type", |t| t.pm_type.clone()),
+        str_col("execution_model", |t| t.execution_model.clone()),
+        str_col("filter_type", |t| t.filter_type.clone()),
+        Column::new(
+            "entry_trigger_margin".into(),
+            trades
+                .iter()
+                .map(|t| t.entry_trigger_margin)
+                .collect::<Vec<_>>(),
+        ),
+        str_col("entry_source", |t| t.entry_source.map(|s| s.to_string())),
+        str_col("exit_source", |t| t.exit_source.map(|s| s.to_string())),
+        Column::new(
+            "liquidated_at_end".into(),
+            trades
+                .iter()
+                .map(|t| t.liquidated_at_end)
+                .collect::<Vec<_>>(),
+        ),
+    ])
+    .context("failed to build trade table")
+}
+
+/// Write `results.arrow` and `trades.arrow` for `result` into `run_dir`.
+pub fn write_results_arrow(result: &BacktestResult, run_dir: &Path) -> Result<()> {
+    write_arrow(&mut bars_frame(result)?, &run_dir.join(RESULTS_ARROW))?;
+    write_arrow(
+        &mut trades_frame(&result.trades)?,
+        &run_dir.join(TRADES_ARROW),
+    )
+}
+
+/// Write `df` as an Arrow IPC file stamped with [`ARROW_SCHEMA_VERSION`].
+pub fn write_arrow(df: &mut DataFrame, path: &Path) -> Result<()> {
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("failed to create {}", path.display()))?;
+    let mut writer = IpcWriter::new(file);
+    let metadata = [(
+        PlSmallStr::from_static(SCHEMA_VERSION_KEY),
+        PlSmallStr::from(ARROW_SCHEMA_VERSION.to_string()),
+    )];
+    writer.set_custom_schema_metadata(Arc::new(metadata.into_iter().collect()));
+    writer
+        .finish(df)
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Read an Arrow IPC file written by [`write_arrow`], rejecting newer
+/// schema versions.
+pub fn read_arrow(path: &Path) -> Result<DataFrame> {
+    let file =
+        std::fs::File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let mut reader = IpcReader::new(file);
+    let version = reader
+        .custom_metadata()
+        .with_context(|| format!("failed to read {}", path.display()))?
+        .and_then(|meta| meta.get(SCHEMA_VERSION_KEY).cloned())
+        .and_then(|v| v.parse::<u32>().ok());
+    match version {
+        Some(v) if v <= ARROW_SCHEMA_VERSION => {}
+        Some(v) => bail!(
+            "{}: schema version {v} is newer than supported {ARROW_SCHEMA_VERSION}",
+            path.display()
+        ),
+        None => bail!("{}: missing {SCHEMA_VERSION_KEY}", path.display()),
+    }
+    reader
+        .finish()
+        .with_context(|| format!("failed to read {}", path.display()))
+}
+
+/// Days since the Unix epoch, Arrow's `Date32` representation.
+fn date32(date: NaiveDate) -> i32 {
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+    (date - epoch).num_days() as i32
+}
+
+fn date_column(name: &str, days: Vec<Option<i32>>) -> Result<Column> {
+    Column::new(name.into(), days)
+        .cast(&DataType::Date)
+        .with_context(|| format!("failed to cast {name} to dates"))
+}
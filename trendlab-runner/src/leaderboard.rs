@@ -5,10 +5,15 @@
 //! same full_hash arrives with a better fitness score, it replaces the existing entry.
 //! If worse, it is skipped.
 
+use std::path::PathBuf;
+
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 
+use crate::fdr::{FdrStanding, QValues};
 use crate::fitness::FitnessMetric;
+use crate::holdout::HoldoutRecord;
+use crate::refresh::{RetestRecord, RetestStatus};
 use crate::runner::BacktestResult;
 use trendlab_core::domain::FullHash;
 
@@ -16,10 +21,36 @@ use trendlab_core::domain::FullHash;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LeaderboardEntry {
     pub result: BacktestResult,
+    #[serde(with = "crate::metrics::finite_f64")]
     pub fitness_score: f64,
     pub iteration: usize,
     pub session_id: String,
     pub timestamp: NaiveDateTime,
+    /// Saved artifact directory, set when the entry's session is persisted
+    /// (see [`crate::session`]). `None` if no artifacts exist for the entry.
+    #[serde(default)]
+    pub artifact_dir: Option<PathBuf>,
+    /// Latest re-test on a longer data range (see [`crate::refresh`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retest: Option<RetestRecord>,
+    /// The config's one run on the session's holdout window (see
+    /// [`crate::holdout`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holdout: Option<HoldoutRecord>,
+    /// Place in the symbol's FDR family, refreshed as the family grows
+    /// (see [`crate::fdr`]). `None` when the run produced no p-value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fdr: Option<FdrStanding>,
+}
+
+impl LeaderboardEntry {
+    pub fn retest_status(&self) -> RetestStatus {
+        match &self.retest {
+            None => RetestStatus::NeverRetested,
+            Some(r) if r.degraded => RetestStatus::Degraded,
+            Some(_) => RetestStatus::Survived,
+        }
+    }
 }
 
 /// Outcome of an insert operation.
@@ -29,12 +60,21 @@ pub enum InsertResult {
     Inserted,
     /// Replaced an existing entry with the same full_hash (better score).
     Replaced,
-    /// Skipped: duplicate with worse or equal score, or NaN fitness.
+    /// Skipped: duplicate with worse or equal score, or not good enough.
     Skipped,
+    /// Rejected without being compared against existing entries.
+    Rejected(RejectReason),
+}
+
+/// Why an entry was rejected outright.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RejectReason {
+    /// Fitness score is NaN or infinite.
+    NonFinite,
 }
 
 /// Per-symbol leaderboard: top N strategies ranked by fitness.
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SymbolLeaderboard {
     symbol: String,
     entries: Vec<LeaderboardEntry>,
@@ -58,9 +98,8 @@ impl SymbolLeaderboard {
     /// - Deduplicates by `full_hash`: replaces if better, skips if worse.
     /// - After insert, trims to `max_size` by removing the worst entry.
     pub fn insert(&mut self, entry: LeaderboardEntry) -> InsertResult {
-        // Reject NaN/Inf fitness
         if !entry.fitness_score.is_finite() {
-            return InsertResult::Skipped;
+            return InsertResult::Rejected(RejectReason::NonFinite);
         }
 
         let entry_hash = entry.result.config.full_hash();
@@ -105,6 +144,11 @@ impl SymbolLeaderboard {
         &self.entries
     }
 
+    /// Mutable access for session persistence; ranking must not change.
+    pub(crate) fn entries_mut(&mut self) -> &mut [LeaderboardEntry] {
+        &mut self.entries
+    }
+
     pub fn symbol(&self) -> &str {
         &self.symbol
     }
@@ -121,21 +165,32 @@ impl SymbolLeaderboard {
         self.fitness_metric
     }
 
+    /// Recompute every entry's FDR standing against the family's current
+    /// q-values.
+    pub fn refresh_fdr(&mut self, q_values: &QValues, alpha: f64) {
+        for entry in &mut self.entries {
+            if let Some(standing) = &mut entry.fdr {
+                *standing = q_values.standing(standing.p_value, alpha);
+            }
+        }
+    }
+
     fn find_by_hash(&self, hash: &FullHash) -> Option<usize> {
         self.entries
             .iter()
             .position(|e| e.result.config.full_hash() == *hash)
     }
 
+    /// Restore ranking after fitness scores were changed in place.
+    pub(crate) fn rerank(&mut self) {
+        self.sort_entries();
+    }
+
     fn sort_entries(&mut self) {
-        // Sort descending by fitness score (best first).
-        // FitnessMetric.is_better(a, b) = a > b for all metrics,
-        // so descending f64 order is correct.
-        self.entries.sort_by(|a, b| {
-            b.fitness_score
-                .partial_cmp(&a.fitness_score)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+        // Best first, missing scores last (see FitnessMetric::compare).
+        let metric = self.fitness_metric;
+        self.entries
+            .sort_by(|a, b| metric.compare(a.fitness_score, b.fitness_score));
     }
 }
 
@@ -144,8 +199,9 @@ impl SymbolLeaderboard {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fdr::FdrFamily;
     use crate::metrics::PerformanceMetrics;
-    use std::collections::{BTreeMap, HashMap};
+    use std::collections::BTreeMap;
     use trendlab_core::fingerprint::{ComponentConfig, StrategyConfig};
 
     fn make_config(signal_type: &str, lookback: f64) -> StrategyConfig {
@@ -157,18 +213,22 @@ mod tests {
                     m.insert("lookback".into(), lookback);
                     m
                 },
+                children: Vec::new(),
             },
             position_manager: ComponentConfig {
                 component_type: "atr_trailing".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
             execution_model: ComponentConfig {
                 component_type: "next_bar_open".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
             signal_filter: ComponentConfig {
                 component_type: "no_filter".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
         }
     }
@@ -188,6 +248,11 @@ mod tests {
             max_consecutive_wins: 3,
             max_consecutive_losses: 2,
             avg_losing_streak: 1.5,
+            time_in_market: 0.0,
+            avg_exposure: 0.0,
+            invested_return: 0.0,
+            idle_return: 0.0,
+            exposure_adjusted_cagr: 0.0,
         }
     }
 
@@ -203,27 +268,53 @@ mod tests {
             result: BacktestResult {
                 schema_version: 1,
                 metrics: metrics.clone(),
+                open_position_adjusted: None,
                 trades: vec![],
                 equity_curve: vec![100_000.0],
+                dates: Vec::new(),
+                exposure_curve: Vec::new(),
                 config,
                 symbol: "SPY".into(),
+                interval: Default::default(),
                 start_date: "2024-01-02".into(),
                 end_date: "2024-12-31".into(),
                 initial_capital: 100_000.0,
                 dataset_hash: "test".into(),
                 has_synthetic: false,
                 signal_count: 5,
+                zero_trades: false,
                 bar_count: 252,
                 warmup_bars: 50,
-                void_bar_rates: HashMap::new(),
+                warmup: None,
+                void_bar_rates: BTreeMap::new(),
                 data_quality_warnings: vec![],
                 stickiness: None,
+                position_stickiness: Vec::new(),
+                r_distribution: Default::default(),
+                trade_distributions: Default::default(),
+                tail_metrics: None,
+                truncated_range: None,
+                style: None,
+                timings: Default::default(),
+                replay: Default::default(),
+                portfolio_snapshots: Default::default(),
+                equity_sparkline: Vec::new(),
+                attribution: Default::default(),
+                ratchet_clamps: Vec::new(),
+                inputs: None,
+                consistency_warnings: Vec::new(),
+                data_vintage: Vec::new(),
+                provenance: None,
             },
             fitness_score: sharpe,
             iteration,
             session_id: "test-session".into(),
             timestamp: NaiveDateTime::parse_from_str("2024-01-01 00:00:00", "%Y-%m-%d %H:%M:%S")
                 .unwrap(),
+            artifact_dir: None,
+            retest: None,
+            holdout: None,
+            fdr: None,
         }
     }
 
@@ -319,7 +410,7 @@ mod tests {
         let mut lb = SymbolLeaderboard::new("SPY".into(), 10, FitnessMetric::Sharpe);
 
         let r = lb.insert(make_entry("donchian", 50.0, f64::NAN, 0));
-        assert_eq!(r, InsertResult::Skipped);
+        assert_eq!(r, InsertResult::Rejected(RejectReason::NonFinite));
         assert_eq!(lb.len(), 0);
     }
 
@@ -328,7 +419,7 @@ mod tests {
         let mut lb = SymbolLeaderboard::new("SPY".into(), 10, FitnessMetric::Sharpe);
 
         let r = lb.insert(make_entry("donchian", 50.0, f64::INFINITY, 0));
-        assert_eq!(r, InsertResult::Skipped);
+        assert_eq!(r, InsertResult::Rejected(RejectReason::NonFinite));
         assert_eq!(lb.len(), 0);
     }
 
@@ -351,4 +442,24 @@ mod tests {
         assert_eq!(lb.symbol(), "SPY");
         assert_eq!(lb.entries().len(), 0);
     }
+
+    #[test]
+    fn refresh_fdr_tracks_the_growing_family() {
+        let mut family = FdrFamily::new();
+        family.add("a".into(), 0.01);
+        let mut lb = SymbolLeaderboard::new("SPY".into(), 10, FitnessMetric::Sharpe);
+        let mut entry = make_entry("donchian", 50.0, 1.0, 0);
+        entry.fdr = Some(family.q_values().standing(0.01, 0.05));
+        lb.insert(entry);
+        lb.insert(make_entry("donchian", 100.0, 0.5, 1));
+        assert!(lb.entries()[0].fdr.unwrap().significant);
+
+        family.add_untested(9);
+        lb.refresh_fdr(&family.q_values(), 0.05);
+        let standing = lb.entries()[0].fdr.unwrap();
+        assert_eq!(standing.family_trials, 10);
+        assert!((standing.q_value - 0.1).abs() < 1e-12);
+        assert!(!standing.significant);
+        assert!(lb.entries()[1].fdr.is_none());
+    }
 }
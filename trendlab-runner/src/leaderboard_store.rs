@@ -0,0 +1,639 @@
+//! Persistent per-symbol leaderboards, kept across YOLO sessions.
+//!
+//! A session with a leaderboard directory keeps one file per symbol,
+//! `symbol=SPY.json`, holding the symbol's best configs from every session
+//! that wrote to it. Entries are compact ([`StoredEntry`]): config, fitness,
+//! metrics, date range and artifact path, without trades or equity curves,
+//! so the file can be listed without the history or the session.
+//!
+//! Each save takes the same [`FileLock`] the history uses, merges whatever
+//! another session wrote in the meantime, and replaces the file by renaming a
+//! temp file over it, so readers never see a partial write.
+//!
+//! At session start, [`resume`] loads the file and replays the history into
+//! a second board. Both are merged, and where they rank the same config the
+//! richer entry (session id, artifacts, FDR standing) wins. When the replay
+//! is not empty the two are compared first; a [`LeaderboardDivergence`]
+//! flags configs only one side ranks, or ranks with a different fitness.
+//! Histories written with a filtering preset, or compacted, can diverge
+//! without anything being wrong.
+
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::fdr::FdrStanding;
+use crate::fitness::FitnessMetric;
+use crate::history::{FileLock, HistoryEntry};
+use crate::leaderboard::{InsertResult, LeaderboardEntry, RejectReason};
+use crate::metrics::PerformanceMetrics;
+use trendlab_core::domain::FullHash;
+use trendlab_core::fingerprint::StrategyConfig;
+
+/// Directory of leaderboard files, next to a session's history by default.
+pub const LEADERBOARDS_DIR: &str = "leaderboards";
+
+/// Path of `symbol`'s leaderboard file under `dir`.
+pub fn stored_leaderboard_path(dir: &Path, symbol: &str) -> PathBuf {
+    dir.join(format!("symbol={symbol}.json"))
+}
+
+/// Session and iteration that produced a stored entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryOrigin {
+    pub session_id: String,
+    pub iteration: usize,
+}
+
+/// A leaderboard entry without its trades and equity curve.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredEntry {
+    pub config: StrategyConfig,
+    #[serde(with = "crate::metrics::finite_f64")]
+    pub fitness_score: f64,
+    pub metrics: PerformanceMetrics,
+    pub trade_count: usize,
+    pub start_date: String,
+    pub end_date: String,
+    pub timestamp: NaiveDateTime,
+    /// `None` for entries recovered from a history replay.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<EntryOrigin>,
+    /// Saved artifact directory, once the entry's session was saved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artifact_dir: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fdr: Option<FdrStanding>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub equity_sparkline: Vec<f64>,
+}
+
+impl StoredEntry {
+    pub fn from_entry(entry: &LeaderboardEntry) -> Self {
+        Self {
+            config: entry.result.config.clone(),
+            fitness_score: entry.fitness_score,
+            metrics: entry.result.metrics.clone(),
+            trade_count: entry.result.trades.len(),
+            start_date: entry.result.start_date.clone(),
+            end_date: entry.result.end_date.clone(),
+            timestamp: entry.timestamp,
+            origin: Some(EntryOrigin {
+                session_id: entry.session_id.clone(),
+                iteration: entry.iteration,
+            }),
+            artifact_dir: entry.artifact_dir.clone(),
+            fdr: entry.fdr,
+            equity_sparkline: entry.result.equity_sparkline.clone(),
+        }
+    }
+
+    /// Rebuild an entry from its history line, scored with `metric`.
+    pub fn from_history(entry: &HistoryEntry, metric: FitnessMetric) -> Self {
+        let fp = &entry.fingerprint;
+        Self {
+            config: fp.strategy_config.clone(),
+            fitness_score: metric.extract(&entry.metrics),
+            metrics: entry.metrics.clone(),
+            trade_count: entry.trade_count,
+            start_date: fp.start_date.to_string(),
+            end_date: fp.end_date.to_string(),
+            timestamp: fp.timestamp,
+            origin: None,
+            artifact_dir: None,
+            fdr: None,
+            equity_sparkline: Vec::new(),
+        }
+    }
+
+    pub fn full_hash(&self) -> FullHash {
+        self.config.full_hash()
+    }
+
+    /// How many of the optional fields are filled in; breaks fitness ties.
+    fn richness(&self) -> usize {
+        [
+            self.origin.is_some(),
+            self.artifact_dir.is_some(),
+            self.fdr.is_some(),
+            !self.equity_sparkline.is_empty(),
+        ]
+        .into_iter()
+        .filter(|&filled| filled)
+        .count()
+    }
+}
+
+/// Configs ranked differently by a leaderboard file and a history replay,
+/// by full hash.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LeaderboardDivergence {
+    /// Ranked in the file but not by the replay.
+    pub only_in_file: Vec<String>,
+    /// Ranked by the replay but missing from the file.
+    pub only_in_history: Vec<String>,
+    /// Ranked by both with different fitness scores.
+    pub fitness_mismatch: Vec<String>,
+}
+
+impl LeaderboardDivergence {
+    pub fn is_consistent(&self) -> bool {
+        self.only_in_file.is_empty()
+            && self.only_in_history.is_empty()
+            && self.fitness_mismatch.is_empty()
+    }
+}
+
+/// A symbol's leaderboard as persisted: bounded, deduplicated by full hash,
+/// best first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredLeaderboard {
+    symbol: String,
+    fitness_metric: FitnessMetric,
+    max_size: usize,
+    entries: Vec<StoredEntry>,
+}
+
+impl StoredLeaderboard {
+    pub fn new(symbol: String, max_size: usize, fitness_metric: FitnessMetric) -> Self {
+        Self {
+            symbol,
+            fitness_metric,
+            max_size,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Replay `symbol`'s history entries into a new leaderboard.
+    pub fn from_history(
+        symbol: &str,
+        history: &[HistoryEntry],
+        max_size: usize,
+        fitness_metric: FitnessMetric,
+    ) -> Self {
+        let mut board = Self::new(symbol.to_string(), max_size, fitness_metric);
+        for entry in history.iter().filter(|e| e.fingerprint.symbol == symbol) {
+            board.insert(StoredEntry::from_history(entry, fitness_metric));
+        }
+        board
+    }
+
+    /// Load `symbol`'s file from `dir`; `None` if there is none yet.
+    pub fn load(dir: &Path, symbol: &str) -> io::Result<Option<Self>> {
+        read_board(&stored_leaderboard_path(dir, symbol))
+    }
+
+    /// Write the leaderboard to its file under `dir`, first merging in what
+    /// is already there. Afterwards `self` holds the merged leaderboard.
+    pub fn save(&mut self, dir: &Path) -> io::Result<PathBuf> {
+        fs::create_dir_all(dir)?;
+        let path = stored_leaderboard_path(dir, &self.symbol);
+        let _lock = FileLock::acquire(&path)?;
+        if let Some(on_disk) = read_board(&path)? {
+            self.merge(&on_disk);
+        }
+
+        let mut tmp = path.clone().into_os_string();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        let mut writer = BufWriter::new(File::create(&tmp)?);
+        serde_json::to_writer(&mut writer, self)?;
+        writer.flush()?;
+        drop(writer);
+        fs::rename(&tmp, &path)?;
+        Ok(path)
+    }
+
+    /// Insert an entry with the same rules as
+    /// [`crate::leaderboard::SymbolLeaderboard::insert`], except that an
+    /// equally fit duplicate replaces one with fewer optional fields.
+    pub fn insert(&mut self, entry: StoredEntry) -> InsertResult {
+        if !entry.fitness_score.is_finite() {
+            return InsertResult::Rejected(RejectReason::NonFinite);
+        }
+
+        let metric = self.fitness_metric;
+        let hash = entry.full_hash();
+        if let Some(idx) = self.entries.iter().position(|e| e.full_hash() == hash) {
+            let existing = &self.entries[idx];
+            let better = metric.is_better(entry.fitness_score, existing.fitness_score);
+            let richer = !metric.is_better(existing.fitness_score, entry.fitness_score)
+                && entry.richness() > existing.richness();
+            if better || richer {
+                self.entries[idx] = entry;
+                self.sort_entries();
+                return InsertResult::Replaced;
+            }
+            return InsertResult::Skipped;
+        }
+
+        if self.entries.len() < self.max_size {
+            self.entries.push(entry);
+            self.sort_entries();
+            InsertResult::Inserted
+        } else if self
+            .entries
+            .last()
+            .is_some_and(|worst| metric.is_better(entry.fitness_score, worst.fitness_score))
+        {
+            self.entries.pop();
+            self.entries.push(entry);
+            self.sort_entries();
+            InsertResult::Inserted
+        } else {
+            InsertResult::Skipped
+        }
+    }
+
+    /// Insert every entry of `other`. Returns how many changed `self`.
+    pub fn merge(&mut self, other: &StoredLeaderboard) -> usize {
+        other
+            .entries
+            .iter()
+            .filter(|e| {
+                matches!(
+                    self.insert((*e).clone()),
+                    InsertResult::Inserted | InsertResult::Replaced
+                )
+            })
+            .count()
+    }
+
+    /// Re-score every entry with `metric` from its stored metrics, when the
+    /// file was ranked by a different one.
+    pub fn rescore(&mut self, metric: FitnessMetric) {
+        if metric == self.fitness_metric {
+            return;
+        }
+        self.fitness_metric = metric;
+        for entry in &mut self.entries {
+            entry.fitness_score = metric.extract(&entry.metrics);
+        }
+        self.entries.retain(|e| e.fitness_score.is_finite());
+        self.sort_entries();
+    }
+
+    /// Change the bound, dropping the worst entries if it shrank.
+    pub fn resize(&mut self, max_size: usize) {
+        self.max_size = max_size;
+        self.entries.truncate(max_size);
+    }
+
+    /// Point the entry `session_id` produced for `hash` at its saved
+    /// artifacts. Returns false if the leaderboard has no such entry.
+    pub fn set_artifact_dir(&mut self, hash: &FullHash, session_id: &str, dir: PathBuf) -> bool {
+        let Some(entry) = self.entries.iter_mut().find(|e| {
+            e.full_hash() == *hash
+                && e.origin
+                    .as_ref()
+                    .is_some_and(|o| o.session_id == session_id)
+        }) else {
+            return false;
+        };
+        entry.artifact_dir = Some(dir);
+        true
+    }
+
+    /// Compare against a leaderboard replayed from history.
+    pub fn divergence(&self, replayed: &StoredLeaderboard) -> LeaderboardDivergence {
+        let mut divergence = LeaderboardDivergence::default();
+        for entry in &self.entries {
+            let hash = entry.full_hash();
+            match replayed.entries.iter().find(|e| e.full_hash() == hash) {
+                None => divergence.only_in_file.push(hash.as_hex()),
+                Some(other) if (other.fitness_score - entry.fitness_score).abs() > 1e-9 => {
+                    divergence.fitness_mismatch.push(hash.as_hex())
+                }
+                Some(_) => {}
+            }
+        }
+        for entry in &replayed.entries {
+            let hash = entry.full_hash();
+            if !self.entries.iter().any(|e| e.full_hash() == hash) {
+                divergence.only_in_history.push(hash.as_hex());
+            }
+        }
+        divergence
+    }
+
+    pub fn entries(&self) -> &[StoredEntry] {
+        &self.entries
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    pub fn fitness_metric(&self) -> FitnessMetric {
+        self.fitness_metric
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn sort_entries(&mut self) {
+        let metric = self.fitness_metric;
+        self.entries
+            .sort_by(|a, b| metric.compare(a.fitness_score, b.fitness_score));
+    }
+}
+
+/// A symbol's leaderboard at session start, with how its file compared to
+/// the history.
+#[derive(Debug, Clone)]
+pub struct ResumedLeaderboard {
+    pub board: StoredLeaderboard,
+    /// `None` when there was no file, or the history had nothing to replay.
+    pub divergence: Option<LeaderboardDivergence>,
+}
+
+/// Load `symbol`'s leaderboard from `dir` and merge in a replay of
+/// `history`. The merged leaderboard is saved back when the replay added to
+/// it.
+pub fn resume(
+    dir: &Path,
+    symbol: &str,
+    history: &[HistoryEntry],
+    max_size: usize,
+    fitness_metric: FitnessMetric,
+) -> io::Result<ResumedLeaderboard> {
+    let replayed = StoredLeaderboard::from_history(symbol, history, max_size, fitness_metric);
+    let Some(mut board) = StoredLeaderboard::load(dir, symbol)? else {
+        let mut board = replayed;
+        if !board.is_empty() {
+            board.save(dir)?;
+        }
+        return Ok(ResumedLeaderboard {
+            board,
+            divergence: None,
+        });
+    };
+
+    board.rescore(fitness_metric);
+    board.resize(max_size);
+    let divergence = (!replayed.is_empty()).then(|| board.divergence(&replayed));
+    if board.merge(&replayed) > 0 {
+        board.save(dir)?;
+    }
+    Ok(ResumedLeaderboard { board, divergence })
+}
+
+fn read_board(path: &Path) -> io::Result<Option<StoredLeaderboard>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    serde_json::from_reader(BufReader::new(file))
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::EvalWindow;
+    use chrono::NaiveDate;
+    use std::collections::BTreeMap;
+    use tempfile::TempDir;
+    use trendlab_core::domain::{DatasetHash, RunId};
+    use trendlab_core::fingerprint::{ComponentConfig, RunFingerprint, TradingMode};
+
+    fn component(component_type: &str, lookback: Option<f64>) -> ComponentConfig {
+        ComponentConfig {
+            component_type: component_type.into(),
+            params: lookback
+                .map(|v| BTreeMap::from([("lookback".to_string(), v)]))
+                .unwrap_or_default(),
+            children: Vec::new(),
+        }
+    }
+
+    fn make_config(lookback: f64) -> StrategyConfig {
+        StrategyConfig {
+            signal: component("donchian_breakout", Some(lookback)),
+            position_manager: component("atr_trailing", None),
+            execution_model: component("next_bar_open", None),
+            signal_filter: component("no_filter", None),
+        }
+    }
+
+    fn make_metrics(sharpe: f64) -> PerformanceMetrics {
+        PerformanceMetrics {
+            total_return: 0.1,
+            cagr: 0.08,
+            sharpe,
+            sortino: sharpe * 2.0,
+            calmar: 0.5,
+            max_drawdown: -0.1,
+            win_rate: 0.5,
+            profit_factor: 1.5,
+            trade_count: 10,
+            turnover: 2.0,
+            max_consecutive_wins: 3,
+            max_consecutive_losses: 2,
+            avg_losing_streak: 1.5,
+            time_in_market: 0.0,
+            avg_exposure: 0.0,
+            invested_return: 0.0,
+            idle_return: 0.0,
+            exposure_adjusted_cagr: 0.0,
+        }
+    }
+
+    fn timestamp() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 6, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    }
+
+    fn stored(lookback: f64, sharpe: f64, session: &str) -> StoredEntry {
+        StoredEntry {
+            config: make_config(lookback),
+            fitness_score: sharpe,
+            metrics: make_metrics(sharpe),
+            trade_count: 10,
+            start_date: "2020-01-02".into(),
+            end_date: "2024-12-31".into(),
+            timestamp: timestamp(),
+            origin: Some(EntryOrigin {
+                session_id: session.into(),
+                iteration: 1,
+            }),
+            artifact_dir: None,
+            fdr: None,
+            equity_sparkline: vec![1.0, 1.1],
+        }
+    }
+
+    fn history_entry(lookback: f64, sharpe: f64) -> HistoryEntry {
+        let config = make_config(lookback);
+        HistoryEntry {
+            fingerprint: RunFingerprint {
+                run_id: RunId::from_bytes(format!("run-{lookback}").as_bytes()),
+                timestamp: timestamp(),
+                seed: 42,
+                symbol: "SPY".into(),
+                start_date: NaiveDate::from_ymd_opt(2020, 1, 2).unwrap(),
+                end_date: NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+                trading_mode: TradingMode::LongOnly,
+                initial_capital: 100_000.0,
+                execution_profile: None,
+                risk_profile: None,
+                config_hash: config.config_hash(),
+                full_hash: config.full_hash(),
+                dataset_hash: DatasetHash::from_bytes(b"test"),
+                strategy_config: config,
+            },
+            metrics: make_metrics(sharpe),
+            trade_count: 10,
+            fitness_score: sharpe,
+            return_sample: Vec::new(),
+            provenance: None,
+            window: EvalWindow::Full,
+            data_warnings: 0,
+            detail: None,
+        }
+    }
+
+    fn board(entries: Vec<StoredEntry>) -> StoredLeaderboard {
+        let mut board = StoredLeaderboard::new("SPY".into(), 3, FitnessMetric::Sharpe);
+        for entry in entries {
+            board.insert(entry);
+        }
+        board
+    }
+
+    fn lookbacks(board: &StoredLeaderboard) -> Vec<f64> {
+        board
+            .entries()
+            .iter()
+            .map(|e| e.config.signal.params["lookback"])
+            .collect()
+    }
+
+    #[test]
+    fn save_merges_what_another_session_wrote() {
+        let dir = TempDir::new().unwrap();
+        let mut first = board(vec![stored(10.0, 1.0, "a"), stored(20.0, 2.0, "a")]);
+        let path = first.save(dir.path()).unwrap();
+        assert_eq!(path, dir.path().join("symbol=SPY.json"));
+        assert!(!dir.path().join("symbol=SPY.json.tmp").exists());
+
+        // A second session that never saw the first one's entries
+        let mut second = board(vec![stored(30.0, 1.5, "b"), stored(40.0, 0.5, "b")]);
+        second.save(dir.path()).unwrap();
+        assert_eq!(lookbacks(&second), vec![20.0, 30.0, 10.0]);
+
+        let loaded = StoredLeaderboard::load(dir.path(), "SPY").unwrap().unwrap();
+        assert_eq!(lookbacks(&loaded), vec![20.0, 30.0, 10.0]);
+        assert!(StoredLeaderboard::load(dir.path(), "QQQ")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn equal_fitness_keeps_the_richer_entry() {
+        let mut board = board(vec![StoredEntry {
+            origin: None,
+            equity_sparkline: Vec::new(),
+            ..stored(10.0, 1.0, "a")
+        }]);
+        assert_eq!(board.insert(stored(10.0, 1.0, "a")), InsertResult::Replaced);
+        assert!(board.entries()[0].origin.is_some());
+        let poorer = StoredEntry {
+            artifact_dir: None,
+            origin: None,
+            ..stored(10.0, 1.0, "b")
+        };
+        assert_eq!(board.insert(poorer), InsertResult::Skipped);
+        assert_eq!(board.insert(stored(10.0, 0.9, "c")), InsertResult::Skipped);
+    }
+
+    #[test]
+    fn resume_merges_history_and_flags_divergence() {
+        let dir = TempDir::new().unwrap();
+        let mut file = board(vec![stored(10.0, 1.0, "a"), stored(20.0, 2.0, "a")]);
+        file.save(dir.path()).unwrap();
+
+        // The history knows lookback 30 and scores lookback 10 differently
+        let history = vec![
+            history_entry(10.0, 1.2),
+            history_entry(20.0, 2.0),
+            history_entry(30.0, 1.5),
+        ];
+        let resumed = resume(dir.path(), "SPY", &history, 3, FitnessMetric::Sharpe).unwrap();
+        let divergence = resumed.divergence.unwrap();
+        assert!(!divergence.is_consistent());
+        assert!(divergence.only_in_file.is_empty());
+        assert_eq!(
+            divergence.only_in_history,
+            vec![make_config(30.0).full_hash().as_hex()]
+        );
+        assert_eq!(
+            divergence.fitness_mismatch,
+            vec![make_config(10.0).full_hash().as_hex()]
+        );
+
+        // Better history scores win; the file keeps what only it had
+        assert_eq!(lookbacks(&resumed.board), vec![20.0, 30.0, 10.0]);
+        assert!(resumed.board.entries()[0].origin.is_some());
+        assert!(resumed.board.entries()[1].origin.is_none());
+        let saved = StoredLeaderboard::load(dir.path(), "SPY").unwrap().unwrap();
+        assert_eq!(lookbacks(&saved), vec![20.0, 30.0, 10.0]);
+
+        // Now consistent
+        let again = resume(dir.path(), "SPY", &history, 3, FitnessMetric::Sharpe).unwrap();
+        assert!(again.divergence.unwrap().is_consistent());
+    }
+
+    #[test]
+    fn resume_rebuilds_from_history_alone_and_rescores() {
+        let dir = TempDir::new().unwrap();
+        let history = vec![history_entry(10.0, 1.0), history_entry(20.0, 2.0)];
+        let resumed = resume(dir.path(), "SPY", &history, 3, FitnessMetric::Sharpe).unwrap();
+        assert!(resumed.divergence.is_none());
+        assert_eq!(lookbacks(&resumed.board), vec![20.0, 10.0]);
+        assert!(stored_leaderboard_path(dir.path(), "SPY").is_file());
+
+        let resumed = resume(dir.path(), "SPY", &[], 1, FitnessMetric::Sortino).unwrap();
+        assert_eq!(resumed.board.fitness_metric(), FitnessMetric::Sortino);
+        assert_eq!(resumed.board.entries()[0].fitness_score, 4.0);
+        assert_eq!(resumed.board.len(), 1);
+    }
+
+    #[test]
+    fn corrupt_file_is_not_overwritten() {
+        let dir = TempDir::new().unwrap();
+        let path = stored_leaderboard_path(dir.path(), "SPY");
+        fs::write(&path, "{not json").unwrap();
+        let err = board(vec![stored(10.0, 1.0, "a")])
+            .save(dir.path())
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{not json");
+    }
+
+    #[test]
+    fn artifact_dir_is_set_for_the_owning_session_only() {
+        let mut board = board(vec![stored(10.0, 1.0, "a")]);
+        let hash = make_config(10.0).full_hash();
+        assert!(!board.set_artifact_dir(&hash, "b", PathBuf::from("runs/x")));
+        assert!(board.set_artifact_dir(&hash, "a", PathBuf::from("runs/SPY_001")));
+        assert_eq!(
+            board.entries()[0].artifact_dir.as_deref(),
+            Some(Path::new("runs/SPY_001"))
+        );
+    }
+}
@@ -2,56 +2,214 @@
 //!
 //! This crate builds on `trendlab-core` to provide:
 //! - Data loading with cache/download/synthetic fallback
+//! - Bar-data anomaly detection (spikes, stale prices, OHLC violations)
 //! - Single-backtest runner with trade extraction and metrics
+//! - Manual variants: a result re-run with hand-edited params, recording its parent
 //! - YOLO mode (continuous auto-discovery engine)
+//! - YOLO convergence tracking and plateau detection
+//! - YOLO symbol quarantine after repeated failures, with errors counted by kind
+//! - YOLO compute accounting (bars simulated, engine time, cache hits) and budgets
+//! - YOLO session persistence and browsing (`session.json` + run artifacts)
 //! - Per-symbol and cross-symbol leaderboards
-//! - Risk profile ranking system
+//! - Persistent per-symbol leaderboard files, merged across sessions
+//! - Risk profile ranking system and engine-level risk overrides
+//! - Returns-based style analysis against factor series
 //! - Run fingerprinting and JSONL history
+//! - Arrow interop: a run's bars and trades as typed tables and IPC files
+//! - Equity sparklines (LTTB-downsampled curves for leaderboard rows)
 //! - Promotion ladder (walk-forward, execution MC, bootstrap)
-
+//! - Robustness-results cache: ladder levels reused across promote runs
+//! - Friction ramp: Sharpe vs slippage and break-even slippage
+//! - Trigger-margin MC: robustness to borderline stop/limit entry fills
+//! - Shuffle test: Level 1 entries ranked against randomized entry dates
+//! - Parameter sensitivity: primary stop and lookback nudged to find cliffs
+//! - Batch promotion of saved runs from manifests or history
+//! - Leaderboard refresh: re-test champions when the cache extends
+//! - Holdout embargo: a window withheld from discovery, evaluated once
+//! - Walk-forward optimization (per-fold parameter re-fitting)
+//! - Run verification: re-run a saved manifest and report the first divergence
+//! - Corpus runs: a library of configs re-run and diffed against an earlier snapshot
+//! - Data vintages: as-of verification and self-contained partition snapshots
+//! - Results retention: prune saved runs, keeping referenced, top and recent ones
+//! - Signal check: a config's entry decision on the latest bar, without a backtest
+//! - Result slicing: a run's metrics recomputed over a date window
+//! - Layered settings: config-file and `TRENDLAB_*` env defaults for CLI and TUI
+//! - Trade journal: user notes and tags on runs and trades (`annotations.json`)
+//! - Paper trading: hypothetical live positions and journals for champions
+//! - Embedding API: [`TrendLab`], the supported entry point for other programs
+
+pub mod adaptive;
+pub mod annotations;
+pub mod api;
 pub mod bootstrap;
+pub mod compute_budget;
 pub mod config;
+pub mod consistency;
+pub mod convergence;
+pub mod corpus;
 pub mod cross_leaderboard;
 pub mod data_loader;
+pub mod data_quality;
 pub mod execution_mc;
 pub mod export;
 pub mod fdr;
 pub mod fitness;
 pub mod history;
+pub mod holdout;
+pub mod interop;
 pub mod leaderboard;
+pub mod leaderboard_store;
 pub mod metrics;
+pub mod overlap;
+pub mod paper;
+pub mod param_sensitivity;
 pub mod promotion;
+pub mod promotion_batch;
+pub mod refresh;
+pub mod retention;
 pub mod risk_profile;
+pub mod robustness_cache;
 pub mod runner;
+pub mod session;
+pub mod settings;
+pub mod shuffle_test;
+pub mod signal_check;
+pub mod slice;
+pub mod sparkline;
+pub mod style;
 pub mod tail_metrics;
+pub mod verify;
+pub mod vintage;
 pub mod walk_forward;
+pub mod wfo;
 pub mod yolo;
 
+pub use adaptive::{
+    AdaptiveConfig, AdaptiveController, ExplorationMode, ModeTransition, TransitionReason,
+};
+pub use annotations::{Annotation, AnnotationTag, Annotations, RunAnnotations, ANNOTATIONS_FILE};
+pub use api::{SweepPoint, TrendLab};
 pub use bootstrap::{
     stationary_block_bootstrap, BootstrapConfig, BootstrapResult, ConfidenceGrade,
     CrossSymbolBootstrapResult, PerSymbolDiagnostic,
 };
-pub use config::{BacktestConfig, ConfigError};
-pub use cross_leaderboard::{AggregatedStickiness, CrossSymbolEntry, CrossSymbolLeaderboard};
-pub use data_loader::{load_bars, LoadError, LoadOptions, LoadedData};
-pub use execution_mc::{ExecutionMcConfig, ExecutionMcResult, McSample, StabilityScore};
+pub use compute_budget::{ComputeBudget, ComputeLimits, ComputeUsage, StopReason};
+pub use config::{
+    validate_strategy, BacktestConfig, ConfigError, ConfigIssue, IssueSeverity, ValidationReport,
+};
+pub use consistency::{
+    check_consistency, ConsistencyCheck, ConsistencyMode, Inconsistency, TradeLeg,
+};
+pub use convergence::{ConvergenceConfig, ConvergencePoint, ConvergenceState, PlateauAction};
+pub use corpus::{
+    diff_corpora, load_snapshot, run_corpus, save_snapshot, ConfigDiff, CorpusDiff, CorpusEntry,
+    CorpusError, CorpusOutcome, CorpusSnapshot, MetricDelta, RunSummary, CORPUS_FILE,
+    DEFAULT_DRIFT_TOLERANCE,
+};
+pub use cross_leaderboard::{
+    AggregatedStickiness, CrossAggregation, CrossSymbolEntry, CrossSymbolLeaderboard,
+};
+pub use data_loader::{
+    load_bars, CacheMiss, LoadError, LoadOptions, LoadedData, TruncatedRange,
+    DEFAULT_STALE_AFTER_DAYS,
+};
+pub use data_quality::{AnomalyKind, AnomalyThresholds, DataQualityWarning};
+pub use execution_mc::{
+    run_trigger_margin_mc, ExecutionMcConfig, ExecutionMcResult, FrictionPoint,
+    FrictionRampConfig, FrictionSensitivity, McSample, StabilityScore, TriggerMarginMcConfig,
+    TriggerMarginMcResult, TriggerMarginTrial, VolumeShockConfig,
+};
 pub use export::{
-    export_equity_csv, export_json, export_trades_csv, generate_comparison, generate_report,
-    import_json, load_artifacts, save_artifacts,
+    export_annotated_trades_csv, export_equity_csv, export_json, export_trades_csv,
+    generate_comparison, generate_report,
+    import_json, load_artifact_summary, load_artifacts, load_diagnostics, load_equity_curve,
+    load_portfolio_snapshots, read_equity_parquet, read_portfolio_parquet, save_artifacts,
+    scan_artifacts, scan_artifacts_report, write_artifacts, write_equity_parquet, write_portfolio_parquet,
+    ArtifactScan, ArtifactSkip, ArtifactSummary, RunDiagnostics, SkippedArtifact,
+    UnsupportedSchema, EQUITY_PARQUET, PORTFOLIO_PARQUET,
+};
+pub use fdr::{
+    benjamini_hochberg, returns_p_value, FdrConfig, FdrFamily, FdrRecord, FdrResult, FdrStanding,
+    QValues, TTestResult,
 };
-pub use fdr::{benjamini_hochberg, FdrFamily, FdrResult, TTestResult};
 pub use fitness::FitnessMetric;
-pub use history::{ComponentSummary, HistoryEntry, WriteFilter, YoloHistory};
-pub use leaderboard::{InsertResult, LeaderboardEntry, SymbolLeaderboard};
-pub use metrics::PerformanceMetrics;
-pub use promotion::{PromotionConfig, PromotionLevel, RobustnessResult};
-pub use risk_profile::{RankingMetric, RiskProfile};
-pub use runner::{run_backtest_from_data, run_single_backtest, BacktestResult, RunError, SCHEMA_VERSION};
-pub use tail_metrics::TailMetrics;
+pub use history::{
+    lineage, preset_selection, CompactReport, ComponentSummary, EntryDetail, EvalWindow,
+    FitnessQuartiles, HistoryCheckpoint, HistoryEntry, LineageStep, RunDetail, RunErrorEntry,
+    WriteFilter, WritePreset, YoloHistory,
+};
+pub use holdout::{HoldoutConfig, HoldoutEntry, HoldoutError, HoldoutRecord, HoldoutReport};
+pub use interop::{
+    bars_frame, read_arrow, trades_frame, write_results_arrow, ARROW_SCHEMA_VERSION, RESULTS_ARROW,
+    TRADES_ARROW,
+};
+pub use leaderboard::{InsertResult, LeaderboardEntry, RejectReason, SymbolLeaderboard};
+pub use leaderboard_store::{
+    stored_leaderboard_path, EntryOrigin, LeaderboardDivergence, ResumedLeaderboard, StoredEntry,
+    StoredLeaderboard, LEADERBOARDS_DIR,
+};
+pub use metrics::{Histogram, PerformanceMetrics, RDistribution, TradeDistributions};
+pub use overlap::{OverlapAnalysis, OverlapConfig};
+pub use paper::{
+    JournalEntry, PaperBook, PaperChampion, PaperEvent, PaperExitReason, PaperPosition, PaperState,
+    PaperStore, PAPER_DIR,
+};
+pub use param_sensitivity::{
+    run_parameter_sensitivity, ParameterCliff, ParameterProbe, ParameterSensitivity,
+    ProbedComponent, SensitivityConfig, SensitivityError,
+};
+pub use promotion::{
+    promote_cached, BenchmarkComparison, BenchmarkMetric, GateFailure, PromotionConfig,
+    PromotionLevel, RobustnessResult,
+};
+pub use promotion_batch::{
+    run_promotion_batch, top_by_sharpe, BatchSettings, PromotionCandidate, PromotionReport,
+    PromotionReportEntry,
+};
+pub use refresh::{
+    refresh_leaderboard, RefreshConfig, RefreshEntry, RefreshReport, RetestRecord, RetestStatus,
+};
+pub use retention::{
+    execute_prune, plan_prune, PruneOutcome, PrunePlan, RetentionError, RetentionPolicy,
+    RetentionRule, SavedRun,
+};
+pub use risk_profile::{RankingMetric, RiskOverrides, RiskProfile};
+pub use robustness_cache::{
+    CacheStats, CachedLevel, LevelKey, RobustnessCache, ROBUSTNESS_CACHE_DIR,
+};
+pub use runner::{
+    run_backtest_cached, run_backtest_from_data, run_backtest_profiled,
+    run_backtest_profiled_cached, run_backtest_replayed, run_modified, run_single_backtest,
+    BacktestResult, RunError, RunErrorKind, RunInputs, SCHEMA_VERSION,
+};
+pub use session::{
+    scan_sessions, SessionError, SessionSummary, SESSION_ARTIFACTS_PER_SYMBOL, SESSION_FILE,
+};
+pub use settings::{SettingSource, Settings, SettingsError, Sourced};
+pub use shuffle_test::{
+    run_shuffle_test, ShuffleTestConfig, ShuffleTestError, SignalValueScore,
+};
+pub use signal_check::{check_signals, CheckOutcome, EntryPlan, SymbolCheck};
+pub use slice::{ProratedTrade, ResultSlice, SliceError};
+pub use sparkline::{equity_sparkline, SPARKLINE_POINTS};
+pub use style::{style_analysis, FactorBeta, FactorSeries, StyleError, StyleReport};
+pub use tail_metrics::{TailMetrics, WorstDay};
+pub use verify::{
+    verify_run, verify_run_as_of, verify_run_dir, Divergence, VerifyError, VerifyReport,
+    DEFAULT_TOLERANCE,
+};
+pub use vintage::{
+    snapshot_partitions, vintage_changes, SnapshotError, SnapshotReport, VintageChange,
+    DEFAULT_SNAPSHOT_MAX_BYTES, SNAPSHOT_DIR,
+};
 pub use walk_forward::{
     DegradationFlag, WalkForwardConfig, WalkForwardResult,
 };
-pub use yolo::{run_yolo, YoloConfig, YoloProgress, YoloResult};
+pub use wfo::{ParamStability, WalkForwardOptimizer, WfoConfig, WfoFold, WfoResult};
+pub use yolo::{
+    run_yolo, run_yolo_from_cache, run_yolo_live, LeaderboardInsertion, QuarantinedSymbol, YoloConfig,
+    YoloProgress, YoloResult, YoloTunables,
+};
 
 #[cfg(test)]
 mod send_sync_checks {
@@ -78,12 +236,24 @@ mod send_sync_checks {
         assert_sync::<FitnessMetric>();
     }
 
+    #[test]
+    fn style_types_are_send_sync() {
+        assert_send::<StyleReport>();
+        assert_sync::<StyleReport>();
+        assert_send::<FactorSeries>();
+        assert_sync::<FactorSeries>();
+    }
+
     #[test]
     fn config_types_are_send_sync() {
         assert_send::<BacktestConfig>();
         assert_sync::<BacktestConfig>();
         assert_send::<LoadOptions>();
         assert_sync::<LoadOptions>();
+        assert_send::<CacheMiss>();
+        assert_sync::<CacheMiss>();
+        assert_send::<TruncatedRange>();
+        assert_sync::<TruncatedRange>();
     }
 
     #[test]
@@ -96,6 +266,12 @@ mod send_sync_checks {
     fn yolo_progress_is_send_sync() {
         assert_send::<YoloProgress>();
         assert_sync::<YoloProgress>();
+        assert_send::<YoloTunables>();
+        assert_sync::<YoloTunables>();
+        assert_send::<LeaderboardInsertion>();
+        assert_sync::<LeaderboardInsertion>();
+        assert_send::<QuarantinedSymbol>();
+        assert_sync::<QuarantinedSymbol>();
     }
 
     #[test]
@@ -116,6 +292,14 @@ mod send_sync_checks {
         assert_sync::<CrossSymbolEntry>();
     }
 
+    #[test]
+    fn overlap_types_are_send_sync() {
+        assert_send::<OverlapAnalysis>();
+        assert_sync::<OverlapAnalysis>();
+        assert_send::<OverlapConfig>();
+        assert_sync::<OverlapConfig>();
+    }
+
     #[test]
     fn cross_symbol_leaderboard_is_send_sync() {
         assert_send::<CrossSymbolLeaderboard>();
@@ -138,6 +322,8 @@ mod send_sync_checks {
     fn tail_metrics_is_send_sync() {
         assert_send::<TailMetrics>();
         assert_sync::<TailMetrics>();
+        assert_send::<WorstDay>();
+        assert_sync::<WorstDay>();
     }
 
     #[test]
@@ -152,6 +338,16 @@ mod send_sync_checks {
         assert_sync::<WriteFilter>();
     }
 
+    #[test]
+    fn convergence_types_are_send_sync() {
+        assert_send::<ConvergenceConfig>();
+        assert_sync::<ConvergenceConfig>();
+        assert_send::<ConvergenceState>();
+        assert_sync::<ConvergenceState>();
+        assert_send::<HistoryCheckpoint>();
+        assert_sync::<HistoryCheckpoint>();
+    }
+
     // ── Phase 11: Robustness types ──
 
     #[test]
@@ -184,6 +380,22 @@ mod send_sync_checks {
         assert_sync::<ExecutionMcResult>();
     }
 
+    #[test]
+    fn friction_types_are_send_sync() {
+        assert_send::<FrictionRampConfig>();
+        assert_sync::<FrictionRampConfig>();
+        assert_send::<FrictionSensitivity>();
+        assert_sync::<FrictionSensitivity>();
+    }
+
+    #[test]
+    fn trigger_margin_types_are_send_sync() {
+        assert_send::<TriggerMarginMcConfig>();
+        assert_sync::<TriggerMarginMcConfig>();
+        assert_send::<TriggerMarginMcResult>();
+        assert_sync::<TriggerMarginMcResult>();
+    }
+
     #[test]
     fn stability_score_is_send_sync() {
         assert_send::<StabilityScore>();
@@ -243,4 +455,54 @@ mod send_sync_checks {
         assert_send::<AggregatedStickiness>();
         assert_sync::<AggregatedStickiness>();
     }
+
+    #[test]
+    fn wfo_types_are_send_sync() {
+        assert_send::<WalkForwardOptimizer>();
+        assert_sync::<WalkForwardOptimizer>();
+        assert_send::<WfoResult>();
+        assert_sync::<WfoResult>();
+    }
+
+    #[test]
+    fn data_quality_warning_is_send_sync() {
+        assert_send::<DataQualityWarning>();
+        assert_sync::<DataQualityWarning>();
+    }
+
+    #[test]
+    fn artifact_summary_is_send_sync() {
+        assert_send::<ArtifactSummary>();
+        assert_sync::<ArtifactSummary>();
+    }
+
+    #[test]
+    fn promotion_batch_types_are_send_sync() {
+        assert_send::<PromotionCandidate>();
+        assert_sync::<PromotionCandidate>();
+        assert_send::<PromotionReport>();
+        assert_sync::<PromotionReport>();
+    }
+
+    #[test]
+    fn session_types_are_send_sync() {
+        assert_send::<YoloResult>();
+        assert_sync::<YoloResult>();
+        assert_send::<SessionSummary>();
+        assert_sync::<SessionSummary>();
+        assert_send::<RefreshReport>();
+        assert_sync::<RefreshReport>();
+        assert_send::<HoldoutReport>();
+        assert_sync::<HoldoutReport>();
+    }
+
+    #[test]
+    fn verify_types_are_send_sync() {
+        assert_send::<RunInputs>();
+        assert_sync::<RunInputs>();
+        assert_send::<VerifyReport>();
+        assert_sync::<VerifyReport>();
+        assert_send::<VerifyError>();
+        assert_sync::<VerifyError>();
+    }
 }
@@ -2,50 +2,180 @@
 //!
 //! Every metric is a pure function: equity curve and/or trade list in, scalar out.
 //! No dependencies on the runner, data pipeline, or engine.
+//!
+//! [`PerformanceMetrics::compute`] gets every metric from one pass over the
+//! equity curve and one over the trades; the individual functions below are
+//! the definitions it matches.
+//!
+//! Degenerate inputs (no trades, one trade, no losers, flat equity) produce
+//! well-defined values rather than NaN/Inf, so results can always be sorted
+//! and serialized. See [`PerformanceMetrics::compute`] for the conventions.
 
 use serde::{Deserialize, Serialize};
 use trendlab_core::domain::TradeRecord;
 
 /// Aggregate performance metrics for a single backtest run.
+///
+/// Non-finite values serialize as `null` and deserialize back as NaN.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerformanceMetrics {
+    #[serde(with = "finite_f64")]
     pub total_return: f64,
+    #[serde(with = "finite_f64")]
     pub cagr: f64,
+    #[serde(with = "finite_f64")]
     pub sharpe: f64,
+    #[serde(with = "finite_f64")]
     pub sortino: f64,
+    #[serde(with = "finite_f64")]
     pub calmar: f64,
+    #[serde(with = "finite_f64")]
     pub max_drawdown: f64,
+    #[serde(with = "finite_f64")]
     pub win_rate: f64,
+    #[serde(with = "finite_f64")]
     pub profit_factor: f64,
     pub trade_count: usize,
+    #[serde(with = "finite_f64")]
     pub turnover: f64,
     pub max_consecutive_wins: usize,
     pub max_consecutive_losses: usize,
+    #[serde(with = "finite_f64")]
     pub avg_losing_streak: f64,
+    /// Fraction of bars closed with a position open.
+    #[serde(default, with = "finite_f64")]
+    pub time_in_market: f64,
+    /// Mean gross exposure (fraction of equity) over the invested bars.
+    #[serde(default, with = "finite_f64")]
+    pub avg_exposure: f64,
+    /// Compounded return over bars a position was held into or opened on.
+    #[serde(default, with = "finite_f64")]
+    pub invested_return: f64,
+    /// Compounded return over the remaining, flat bars. Nonzero only when
+    /// costs or cash effects leak into flat periods.
+    #[serde(default, with = "finite_f64")]
+    pub idle_return: f64,
+    /// CAGR divided by the mean exposure over all bars: the return per unit
+    /// of capital actually put to work.
+    #[serde(default, with = "finite_f64")]
+    pub exposure_adjusted_cagr: f64,
 }
 
 impl PerformanceMetrics {
     /// Compute all metrics from an equity curve and trade list.
+    ///
+    /// Every field is finite. Conventions for degenerate inputs:
+    /// - Sharpe and Sortino are 0.0 when the return deviation is ~0 or there
+    ///   are fewer than two returns.
+    /// - Profit factor is capped at [`PROFIT_FACTOR_CAP`] (all winners) and
+    ///   is 0.0 with no trades.
+    /// - Ratios of fewer than two bars, and any value that would still come
+    ///   out NaN/Inf (e.g. non-finite equity), are 0.0.
+    ///
+    /// Every metric is in returns or fractions of equity, so scaling
+    /// `initial_capital` (with fractional sizing) leaves them unchanged.
+    /// Turnover is measured against the curve's average equity;
+    /// `initial_capital` stands in only when the curve is empty.
+    ///
+    /// Annualizes with [`TRADING_DAYS_PER_YEAR`]: the curve has one point per
+    /// daily bar. See [`Self::compute_annualized`] for other bar intervals.
     pub fn compute(equity_curve: &[f64], trades: &[TradeRecord], initial_capital: f64) -> Self {
-        let trading_days = equity_curve.len();
+        Self::compute_annualized(equity_curve, trades, initial_capital, TRADING_DAYS_PER_YEAR)
+    }
+
+    /// [`Self::compute`] for an equity curve with `periods_per_year` points
+    /// per year (52 for weekly bars).
+    pub fn compute_annualized(
+        equity_curve: &[f64],
+        trades: &[TradeRecord],
+        initial_capital: f64,
+        periods_per_year: f64,
+    ) -> Self {
+        let equity = EquityScan::new(equity_curve);
+        let closed = TradeScan::new(trades);
+        let cagr = equity.cagr(periods_per_year);
         Self {
-            total_return: total_return(equity_curve),
-            cagr: cagr(equity_curve, trading_days),
-            sharpe: sharpe_ratio(equity_curve, 0.0),
-            sortino: sortino_ratio(equity_curve, 0.0),
-            calmar: calmar_ratio(equity_curve, trading_days),
-            max_drawdown: max_drawdown(equity_curve),
-            win_rate: win_rate(trades),
-            profit_factor: profit_factor(trades),
+            total_return: finite_or_zero(equity.total_return()),
+            cagr: finite_or_zero(cagr),
+            sharpe: finite_or_zero(equity.sharpe(periods_per_year)),
+            sortino: finite_or_zero(equity.sortino(periods_per_year)),
+            calmar: finite_or_zero(calmar(cagr, equity.max_drawdown)),
+            max_drawdown: finite_or_zero(equity.max_drawdown),
+            win_rate: closed.win_rate(),
+            profit_factor: finite_or_zero(closed.profit_factor()),
             trade_count: trades.len(),
-            turnover: turnover(trades, initial_capital, trading_days),
-            max_consecutive_wins: max_consecutive_wins(trades),
-            max_consecutive_losses: max_consecutive_losses(trades),
-            avg_losing_streak: avg_losing_streak(trades),
+            turnover: finite_or_zero(turnover_of_notional(
+                closed.notional,
+                equity.average().unwrap_or(initial_capital),
+                equity.len,
+                periods_per_year,
+            )),
+            max_consecutive_wins: closed.max_win_streak,
+            max_consecutive_losses: closed.max_loss_streak,
+            avg_losing_streak: closed.avg_losing_streak(),
+            time_in_market: 0.0,
+            avg_exposure: 0.0,
+            invested_return: 0.0,
+            idle_return: 0.0,
+            exposure_adjusted_cagr: 0.0,
         }
     }
+
+    /// Fill the exposure-timing fields from the per-bar gross exposure
+    /// (fraction of equity, aligned with `equity_curve`). Without an
+    /// exposure curve they stay zero.
+    pub fn with_exposure(mut self, equity_curve: &[f64], exposure_curve: &[f64]) -> Self {
+        if exposure_curve.is_empty() || exposure_curve.len() != equity_curve.len() {
+            return self;
+        }
+        let (invested, idle) = split_returns(equity_curve, exposure_curve);
+        self.time_in_market = time_in_market(exposure_curve);
+        self.avg_exposure = finite_or_zero(avg_exposure_invested(exposure_curve));
+        self.invested_return = finite_or_zero(invested);
+        self.idle_return = finite_or_zero(idle);
+        self.exposure_adjusted_cagr =
+            finite_or_zero(exposure_adjusted_cagr(self.cagr, exposure_curve));
+        self
+    }
+
+    /// Metrics of a run that closed no trades: every value zero.
+    ///
+    /// The runner reports these for zero-trade runs instead of measuring a
+    /// position that never closed, so such runs rank as "no evidence".
+    pub fn neutral() -> Self {
+        Self::compute(&[], &[], 0.0)
+    }
+
+    /// Whether every float field is finite.
+    pub fn is_finite(&self) -> bool {
+        [
+            self.total_return,
+            self.cagr,
+            self.sharpe,
+            self.sortino,
+            self.calmar,
+            self.max_drawdown,
+            self.win_rate,
+            self.profit_factor,
+            self.turnover,
+            self.avg_losing_streak,
+            self.time_in_market,
+            self.avg_exposure,
+            self.invested_return,
+            self.idle_return,
+            self.exposure_adjusted_cagr,
+        ]
+        .iter()
+        .all(|v| v.is_finite())
+    }
 }
 
+/// Bars per year for daily data, the default annualization.
+pub const TRADING_DAYS_PER_YEAR: f64 = 252.0;
+
+/// Upper bound on [`profit_factor`], reported when there are no losing trades.
+pub const PROFIT_FACTOR_CAP: f64 = 100.0;
+
 // ─── Individual metric functions ────────────────────────────────────
 
 /// Total return as a fraction: (final - initial) / initial.
@@ -63,52 +193,61 @@ pub fn total_return(equity_curve: &[f64]) -> f64 {
 
 /// Compound Annual Growth Rate.
 ///
-/// Assumes 252 trading days per year. Returns 0.0 for single-bar or constant equity.
-pub fn cagr(equity_curve: &[f64], trading_days: usize) -> f64 {
+/// `trading_days` counts bars, of which there are `periods_per_year` per year.
+/// Returns 0.0 for single-bar or constant equity.
+pub fn cagr(equity_curve: &[f64], trading_days: usize, periods_per_year: f64) -> f64 {
     if equity_curve.len() < 2 || trading_days < 2 {
         return 0.0;
     }
-    let initial = equity_curve[0];
-    let final_eq = *equity_curve.last().unwrap();
+    growth_rate(
+        equity_curve[0],
+        *equity_curve.last().unwrap(),
+        trading_days,
+        periods_per_year,
+    )
+}
+
+/// Annualized growth from `initial` to `final_eq` over `trading_days` bars.
+fn growth_rate(initial: f64, final_eq: f64, trading_days: usize, periods_per_year: f64) -> f64 {
     if initial <= 0.0 || final_eq <= 0.0 {
         return 0.0;
     }
-    let years = trading_days as f64 / 252.0;
+    let years = trading_days as f64 / periods_per_year;
     if years <= 0.0 {
         return 0.0;
     }
     (final_eq / initial).powf(1.0 / years) - 1.0
 }
 
-/// Annualized Sharpe ratio from daily returns.
+/// Annualized Sharpe ratio from per-bar returns.
 ///
-/// Sharpe = mean(daily returns - rf) / std(daily returns) * sqrt(252).
+/// Sharpe = mean(bar returns - rf) / std(bar returns) * sqrt(periods_per_year).
 /// Returns 0.0 if variance is zero or fewer than 2 bars.
-pub fn sharpe_ratio(equity_curve: &[f64], risk_free_rate: f64) -> f64 {
+pub fn sharpe_ratio(equity_curve: &[f64], risk_free_rate: f64, periods_per_year: f64) -> f64 {
     let returns = daily_returns(equity_curve);
     if returns.len() < 2 {
         return 0.0;
     }
-    let daily_rf = risk_free_rate / 252.0;
+    let daily_rf = risk_free_rate / periods_per_year;
     let excess: Vec<f64> = returns.iter().map(|r| r - daily_rf).collect();
     let mean = mean_f64(&excess);
     let std = std_dev(&excess);
     if std < 1e-15 {
         return 0.0;
     }
-    (mean / std) * (252.0_f64).sqrt()
+    (mean / std) * periods_per_year.sqrt()
 }
 
 /// Annualized Sortino ratio (downside deviation only).
 ///
-/// Sortino = mean(daily returns - rf) / downside_std * sqrt(252).
+/// Sortino = mean(bar returns - rf) / downside_std * sqrt(periods_per_year).
 /// Returns 0.0 if no downside deviation or fewer than 2 bars.
-pub fn sortino_ratio(equity_curve: &[f64], risk_free_rate: f64) -> f64 {
+pub fn sortino_ratio(equity_curve: &[f64], risk_free_rate: f64, periods_per_year: f64) -> f64 {
     let returns = daily_returns(equity_curve);
     if returns.len() < 2 {
         return 0.0;
     }
-    let daily_rf = risk_free_rate / 252.0;
+    let daily_rf = risk_free_rate / periods_per_year;
     let excess: Vec<f64> = returns.iter().map(|r| r - daily_rf).collect();
     let mean = mean_f64(&excess);
 
@@ -124,19 +263,24 @@ pub fn sortino_ratio(equity_curve: &[f64], risk_free_rate: f64) -> f64 {
     if downside_std < 1e-15 {
         return 0.0;
     }
-    (mean / downside_std) * (252.0_f64).sqrt()
+    (mean / downside_std) * periods_per_year.sqrt()
 }
 
 /// Calmar ratio: CAGR / |max_drawdown|.
 ///
 /// Returns 0.0 if max drawdown is zero or CAGR is non-positive.
-pub fn calmar_ratio(equity_curve: &[f64], trading_days: usize) -> f64 {
-    let c = cagr(equity_curve, trading_days);
-    let dd = max_drawdown(equity_curve);
-    if dd >= 0.0 || c <= 0.0 {
+pub fn calmar_ratio(equity_curve: &[f64], trading_days: usize, periods_per_year: f64) -> f64 {
+    calmar(
+        cagr(equity_curve, trading_days, periods_per_year),
+        max_drawdown(equity_curve),
+    )
+}
+
+fn calmar(cagr: f64, max_drawdown: f64) -> f64 {
+    if max_drawdown >= 0.0 || cagr <= 0.0 {
         return 0.0;
     }
-    c / dd.abs()
+    cagr / max_drawdown.abs()
 }
 
 /// Maximum drawdown as a negative fraction (e.g., -0.15 = 15% drawdown).
@@ -174,7 +318,7 @@ pub fn win_rate(trades: &[TradeRecord]) -> f64 {
 
 /// Profit factor: gross profits / gross losses.
 ///
-/// Capped at 100.0 for edge cases (all winners, zero losses).
+/// Capped at [`PROFIT_FACTOR_CAP`] for edge cases (all winners, zero losses).
 pub fn profit_factor(trades: &[TradeRecord]) -> f64 {
     if trades.is_empty() {
         return 0.0;
@@ -191,25 +335,56 @@ pub fn profit_factor(trades: &[TradeRecord]) -> f64 {
         .sum();
 
     if gross_loss < 1e-10 {
-        return if gross_profit > 0.0 { 100.0 } else { 0.0 };
+        return if gross_profit > 0.0 {
+            PROFIT_FACTOR_CAP
+        } else {
+            0.0
+        };
     }
-    (gross_profit / gross_loss).min(100.0)
+    (gross_profit / gross_loss).min(PROFIT_FACTOR_CAP)
 }
 
 /// Annual turnover: total traded notional / average capital / years.
-pub fn turnover(trades: &[TradeRecord], initial_capital: f64, trading_days: usize) -> f64 {
-    if trades.is_empty() || initial_capital <= 0.0 || trading_days < 2 {
+pub fn turnover(
+    trades: &[TradeRecord],
+    average_capital: f64,
+    trading_days: usize,
+    periods_per_year: f64,
+) -> f64 {
+    let total_notional: f64 = trades.iter().map(traded_notional).sum();
+    turnover_of_notional(
+        total_notional,
+        average_capital,
+        trading_days,
+        periods_per_year,
+    )
+}
+
+/// Entry plus exit notional of one trade.
+fn traded_notional(trade: &TradeRecord) -> f64 {
+    trade.entry_price * trade.quantity + trade.exit_price * trade.quantity
+}
+
+fn turnover_of_notional(
+    total_notional: f64,
+    average_capital: f64,
+    trading_days: usize,
+    periods_per_year: f64,
+) -> f64 {
+    if total_notional == 0.0 || average_capital <= 0.0 || trading_days < 2 {
         return 0.0;
     }
-    let total_notional: f64 = trades
-        .iter()
-        .map(|t| t.entry_price * t.quantity + t.exit_price * t.quantity)
-        .sum();
-    let years = trading_days as f64 / 252.0;
+    let years = trading_days as f64 / periods_per_year;
     if years <= 0.0 {
         return 0.0;
     }
-    total_notional / initial_capital / years
+    total_notional / average_capital / years
+}
+
+/// Mean of the equity curve, the capital turnover is measured against.
+/// `None` for an empty curve.
+pub fn average_equity(equity_curve: &[f64]) -> Option<f64> {
+    (!equity_curve.is_empty()).then(|| mean_f64(equity_curve))
 }
 
 /// Maximum consecutive winning trades.
@@ -250,6 +425,264 @@ pub fn avg_losing_streak(trades: &[TradeRecord]) -> f64 {
     streaks.iter().sum::<usize>() as f64 / streaks.len() as f64
 }
 
+// ─── Exposure timing ────────────────────────────────────────────────
+
+/// Fraction of bars with any exposure at the close.
+pub fn time_in_market(exposure_curve: &[f64]) -> f64 {
+    if exposure_curve.is_empty() {
+        return 0.0;
+    }
+    exposure_curve.iter().filter(|&&e| e > 0.0).count() as f64 / exposure_curve.len() as f64
+}
+
+/// Mean exposure over the bars with any exposure; 0.0 if never invested.
+pub fn avg_exposure_invested(exposure_curve: &[f64]) -> f64 {
+    let invested: Vec<f64> = exposure_curve
+        .iter()
+        .copied()
+        .filter(|&e| e > 0.0)
+        .collect();
+    mean_f64(&invested)
+}
+
+/// CAGR per unit of mean exposure over all bars; 0.0 if never invested.
+pub fn exposure_adjusted_cagr(cagr: f64, exposure_curve: &[f64]) -> f64 {
+    let mean_exposure = mean_f64(exposure_curve);
+    if mean_exposure <= 0.0 {
+        return 0.0;
+    }
+    cagr / mean_exposure
+}
+
+/// Compounded returns (invested, idle) of an equity curve split by exposure.
+///
+/// The return into bar `t` is invested when a position was open at the
+/// close of `t - 1` or `t`, so entry and exit bars count as invested.
+pub fn split_returns(equity_curve: &[f64], exposure_curve: &[f64]) -> (f64, f64) {
+    let held = |t: usize| exposure_curve.get(t).is_some_and(|&e| e > 0.0);
+    let (mut invested, mut idle) = (1.0, 1.0);
+    for (t, r) in daily_returns(equity_curve).iter().enumerate() {
+        if held(t) || held(t + 1) {
+            invested *= 1.0 + r;
+        } else {
+            idle *= 1.0 + r;
+        }
+    }
+    (invested - 1.0, idle - 1.0)
+}
+
+// ─── R-multiple distribution ────────────────────────────────────────
+
+/// Upper-exclusive bin edges for the R-multiple histogram.
+///
+/// Bins are `(-inf, -2)`, `[-2, -1)`, ..., `[5, +inf)`: one more bin than edges.
+pub const R_HISTOGRAM_EDGES: [f64; 7] = [-2.0, -1.0, 0.0, 1.0, 2.0, 3.0, 5.0];
+
+/// Distribution of per-trade R multiples (net PnL / initial risk).
+///
+/// Only trades whose position manager placed a stop are included; the rest
+/// are counted in `excluded_trades`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RDistribution {
+    /// Trades with a usable initial stop.
+    pub trade_count: usize,
+    /// Trades excluded because no initial stop was recorded.
+    pub excluded_trades: usize,
+    pub mean_r: f64,
+    pub median_r: f64,
+    /// Fraction of trades closed between -1.2R and -0.8R (stop discipline).
+    pub pct_near_minus_one: f64,
+    /// Fraction of trades closed within ±0.2R (scratches).
+    pub pct_scratch: f64,
+    /// Average winning R.
+    pub avg_win_r: f64,
+    /// Average losing R (negative).
+    pub avg_loss_r: f64,
+    /// Expectancy in R: win rate × avg win R + loss rate × avg loss R.
+    pub expectancy_r: f64,
+    /// Trade counts per bin of `R_HISTOGRAM_EDGES`.
+    pub histogram: Vec<usize>,
+}
+
+impl RDistribution {
+    pub fn compute(trades: &[TradeRecord]) -> Self {
+        let r: Vec<f64> = trades
+            .iter()
+            .filter_map(|t| t.r_multiple())
+            .filter(|r| r.is_finite())
+            .collect();
+        let excluded_trades = trades.len() - r.len();
+        let mut histogram = vec![0; R_HISTOGRAM_EDGES.len() + 1];
+
+        if r.is_empty() {
+            return Self {
+                excluded_trades,
+                histogram,
+                ..Self::default()
+            };
+        }
+
+        let n = r.len() as f64;
+        for &v in &r {
+            let bin = R_HISTOGRAM_EDGES.iter().take_while(|&&e| v >= e).count();
+            histogram[bin] += 1;
+        }
+
+        let mut sorted = r.clone();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let mid = sorted.len() / 2;
+        let median_r = if sorted.len() % 2 == 0 {
+            (sorted[mid - 1] + sorted[mid]) / 2.0
+        } else {
+            sorted[mid]
+        };
+
+        let wins: Vec<f64> = r.iter().copied().filter(|&v| v > 0.0).collect();
+        let losses: Vec<f64> = r.iter().copied().filter(|&v| v < 0.0).collect();
+        let avg_win_r = mean_f64(&wins);
+        let avg_loss_r = mean_f64(&losses);
+
+        Self {
+            trade_count: r.len(),
+            excluded_trades,
+            mean_r: mean_f64(&r),
+            median_r,
+            pct_near_minus_one: r.iter().filter(|&&v| (-1.2..=-0.8).contains(&v)).count() as f64
+                / n,
+            pct_scratch: r.iter().filter(|&&v| v.abs() <= 0.2).count() as f64 / n,
+            avg_win_r,
+            avg_loss_r,
+            expectancy_r: wins.len() as f64 / n * avg_win_r + losses.len() as f64 / n * avg_loss_r,
+            histogram,
+        }
+    }
+
+    /// Human-readable label for histogram bin `i`.
+    pub fn bin_label(i: usize) -> String {
+        match (
+            i.checked_sub(1).map(|j| R_HISTOGRAM_EDGES[j]),
+            R_HISTOGRAM_EDGES.get(i),
+        ) {
+            (None, Some(hi)) => format!("<{hi}R"),
+            (Some(lo), Some(hi)) => format!("{lo}..{hi}R"),
+            (Some(lo), None) => format!(">={lo}R"),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+/// Most bins a [`Histogram`] is split into.
+pub const MAX_HISTOGRAM_BINS: usize = 20;
+
+/// Fewest values a [`Histogram`] bins; smaller samples leave it empty.
+pub const MIN_HISTOGRAM_SAMPLES: usize = 3;
+
+/// Equal-width histogram of a sample, with bins sized to the sample:
+/// Freedman–Diaconis width, or the square-root rule when the interquartile
+/// range is zero.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Histogram {
+    /// Bin edges, one more than `counts`: bin i spans `edges[i]..edges[i + 1]`
+    /// and the last bin includes its upper edge.
+    pub edges: Vec<f64>,
+    pub counts: Vec<usize>,
+    pub median: f64,
+    /// Values in the sample, binned or not.
+    pub sample_size: usize,
+}
+
+impl Histogram {
+    pub fn compute(values: &[f64]) -> Self {
+        let mut sorted: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let n = sorted.len();
+        if n < MIN_HISTOGRAM_SAMPLES {
+            return Self {
+                sample_size: n,
+                ..Self::default()
+            };
+        }
+
+        let (lo, hi) = (sorted[0], sorted[n - 1]);
+        let iqr = quantile(&sorted, 0.75) - quantile(&sorted, 0.25);
+        let fd_width = 2.0 * iqr / (n as f64).cbrt();
+        let bins = if hi == lo {
+            1
+        } else if fd_width > 0.0 {
+            ((hi - lo) / fd_width).ceil() as usize
+        } else {
+            (n as f64).sqrt().ceil() as usize
+        }
+        .clamp(1, MAX_HISTOGRAM_BINS);
+
+        let width = (hi - lo) / bins as f64;
+        let edges: Vec<f64> = (0..=bins)
+            .map(|i| if i == bins { hi } else { lo + width * i as f64 })
+            .collect();
+        let mut histogram = Self {
+            edges,
+            counts: vec![0; bins],
+            median: quantile(&sorted, 0.5),
+            sample_size: n,
+        };
+        for &v in &sorted {
+            if let Some(bin) = histogram.bin_of(v) {
+                histogram.counts[bin] += 1;
+            }
+        }
+        histogram
+    }
+
+    /// Too few values to bin.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Bin holding `value`, None outside the edges.
+    pub fn bin_of(&self, value: f64) -> Option<usize> {
+        let (&lo, &hi) = (self.edges.first()?, self.edges.last()?);
+        if !(lo..=hi).contains(&value) {
+            return None;
+        }
+        let bin = self.edges[1..].partition_point(|&e| e <= value);
+        Some(bin.min(self.counts.len() - 1))
+    }
+}
+
+/// Linear-interpolated quantile of an ascending, non-empty sample.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let (i, frac) = (pos.floor() as usize, pos.fract());
+    match sorted.get(i + 1) {
+        Some(next) => sorted[i] + (next - sorted[i]) * frac,
+        None => sorted[i],
+    }
+}
+
+/// Per-trade distributions for the summary histograms.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TradeDistributions {
+    /// Net PnL as a fraction of the entry notional.
+    pub return_pct: Histogram,
+    /// Bars from entry to exit.
+    pub holding_bars: Histogram,
+}
+
+impl TradeDistributions {
+    pub fn compute(trades: &[TradeRecord]) -> Self {
+        let returns: Vec<f64> = trades
+            .iter()
+            .filter(|t| t.entry_price * t.quantity != 0.0)
+            .map(|t| t.net_pnl / (t.entry_price * t.quantity).abs())
+            .collect();
+        let holding: Vec<f64> = trades.iter().map(|t| t.bars_held as f64).collect();
+        Self {
+            return_pct: Histogram::compute(&returns),
+            holding_bars: Histogram::compute(&holding),
+        }
+    }
+}
+
 // ─── Helpers ────────────────────────────────────────────────────────
 
 /// Compute daily returns from an equity curve.
@@ -269,6 +702,274 @@ pub fn daily_returns(equity_curve: &[f64]) -> Vec<f64> {
         .collect()
 }
 
+/// Equity-curve statistics gathered in one pass, for
+/// [`PerformanceMetrics::compute`]. Bar returns are never materialized.
+struct EquityScan {
+    len: usize,
+    first: f64,
+    last: f64,
+    equity_sum: f64,
+    max_drawdown: f64,
+    /// Bar returns seen (`len - 1` for a non-empty curve).
+    returns: usize,
+    return_sum: f64,
+    /// Welford running mean and sum of squared deviations of bar returns.
+    running_mean: f64,
+    squared_deviations: f64,
+    downside_count: usize,
+    downside_sq_sum: f64,
+}
+
+impl EquityScan {
+    fn new(equity_curve: &[f64]) -> Self {
+        let first = equity_curve.first().copied().unwrap_or(0.0);
+        let mut scan = Self {
+            len: equity_curve.len(),
+            first,
+            last: equity_curve.last().copied().unwrap_or(0.0),
+            equity_sum: 0.0,
+            max_drawdown: 0.0,
+            returns: 0,
+            return_sum: 0.0,
+            running_mean: 0.0,
+            squared_deviations: 0.0,
+            downside_count: 0,
+            downside_sq_sum: 0.0,
+        };
+        let mut peak = first;
+        let mut prev = first;
+        for (i, &eq) in equity_curve.iter().enumerate() {
+            scan.equity_sum += eq;
+
+            if eq > peak {
+                peak = eq;
+            }
+            if peak > 0.0 {
+                let dd = (eq - peak) / peak;
+                if dd < scan.max_drawdown {
+                    scan.max_drawdown = dd;
+                }
+            }
+
+            if i > 0 {
+                // Same per-bar return as `daily_returns`
+                let r = if prev > 0.0 { (eq - prev) / prev } else { 0.0 };
+                scan.returns += 1;
+                scan.return_sum += r;
+                let delta = r - scan.running_mean;
+                scan.running_mean += delta / scan.returns as f64;
+                scan.squared_deviations += delta * (r - scan.running_mean);
+                if r < 0.0 {
+                    scan.downside_count += 1;
+                    scan.downside_sq_sum += r * r;
+                }
+            }
+            prev = eq;
+        }
+        if scan.len < 2 {
+            scan.max_drawdown = 0.0;
+        }
+        scan
+    }
+
+    fn total_return(&self) -> f64 {
+        if self.len < 2 || self.first <= 0.0 {
+            return 0.0;
+        }
+        (self.last - self.first) / self.first
+    }
+
+    fn cagr(&self, periods_per_year: f64) -> f64 {
+        if self.len < 2 {
+            return 0.0;
+        }
+        growth_rate(self.first, self.last, self.len, periods_per_year)
+    }
+
+    fn mean_return(&self) -> f64 {
+        self.return_sum / self.returns as f64
+    }
+
+    fn sharpe(&self, periods_per_year: f64) -> f64 {
+        if self.returns < 2 {
+            return 0.0;
+        }
+        let std = (self.squared_deviations / (self.returns - 1) as f64).sqrt();
+        if std < 1e-15 {
+            return 0.0;
+        }
+        (self.mean_return() / std) * periods_per_year.sqrt()
+    }
+
+    fn sortino(&self, periods_per_year: f64) -> f64 {
+        if self.returns < 2 || self.downside_count == 0 {
+            return 0.0;
+        }
+        let downside_std = (self.downside_sq_sum / self.returns as f64).sqrt();
+        if downside_std < 1e-15 {
+            return 0.0;
+        }
+        (self.mean_return() / downside_std) * periods_per_year.sqrt()
+    }
+
+    fn average(&self) -> Option<f64> {
+        (self.len > 0).then(|| self.equity_sum / self.len as f64)
+    }
+}
+
+/// Trade-list statistics gathered in one pass, for
+/// [`PerformanceMetrics::compute`].
+struct TradeScan {
+    count: usize,
+    winners: usize,
+    gross_profit: f64,
+    gross_loss: f64,
+    notional: f64,
+    max_win_streak: usize,
+    max_loss_streak: usize,
+    losing_streaks: usize,
+    losing_trades: usize,
+}
+
+impl TradeScan {
+    fn new(trades: &[TradeRecord]) -> Self {
+        let mut scan = Self {
+            count: trades.len(),
+            winners: 0,
+            gross_profit: 0.0,
+            gross_loss: 0.0,
+            notional: 0.0,
+            max_win_streak: 0,
+            max_loss_streak: 0,
+            losing_streaks: 0,
+            losing_trades: 0,
+        };
+        let (mut win_streak, mut loss_streak) = (0, 0);
+        for trade in trades {
+            if trade.net_pnl > 0.0 {
+                scan.gross_profit += trade.net_pnl;
+            } else if trade.net_pnl < 0.0 {
+                scan.gross_loss += trade.net_pnl.abs();
+            }
+            scan.notional += traded_notional(trade);
+
+            if trade.is_winner() {
+                scan.winners += 1;
+                win_streak += 1;
+                loss_streak = 0;
+                scan.max_win_streak = scan.max_win_streak.max(win_streak);
+            } else {
+                scan.losing_trades += 1;
+                if loss_streak == 0 {
+                    scan.losing_streaks += 1;
+                }
+                loss_streak += 1;
+                win_streak = 0;
+                scan.max_loss_streak = scan.max_loss_streak.max(loss_streak);
+            }
+        }
+        scan
+    }
+
+    fn win_rate(&self) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        self.winners as f64 / self.count as f64
+    }
+
+    fn profit_factor(&self) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        if self.gross_loss < 1e-10 {
+            return if self.gross_profit > 0.0 {
+                PROFIT_FACTOR_CAP
+            } else {
+                0.0
+            };
+        }
+        (self.gross_profit / self.gross_loss).min(PROFIT_FACTOR_CAP)
+    }
+
+    fn avg_losing_streak(&self) -> f64 {
+        if self.losing_streaks == 0 {
+            return 0.0;
+        }
+        self.losing_trades as f64 / self.losing_streaks as f64
+    }
+}
+
+fn finite_or_zero(value: f64) -> f64 {
+    if value.is_finite() {
+        value
+    } else {
+        0.0
+    }
+}
+
+/// Serde adapter for floats that may be NaN/Inf.
+///
+/// Non-finite values serialize as `null` (JSON has no NaN) and `null`
+/// deserializes as NaN, i.e. a missing value.
+pub mod finite_f64 {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        if value.is_finite() {
+            serializer.serialize_f64(*value)
+        } else {
+            serializer.serialize_none()
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        Ok(Option::<f64>::deserialize(deserializer)?.unwrap_or(f64::NAN))
+    }
+}
+
+/// Serde for thresholds that are fractions: a number (`0.05`), a percentage
+/// (`"5%"`) or basis points (`"500bps"`), converted to the fraction when
+/// loaded. Serializes as the plain number.
+pub mod fraction {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(*value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Number(f64),
+            Text(String),
+        }
+        match Raw::deserialize(deserializer)? {
+            Raw::Number(value) => Ok(value),
+            Raw::Text(text) => parse(&text).ok_or_else(|| {
+                D::Error::custom(format!(
+                    "expected a fraction, \"<n>%\" or \"<n>bps\", got {text:?}"
+                ))
+            }),
+        }
+    }
+
+    /// Parse `"5%"`, `"500bps"` or `"0.05"` into a fraction.
+    pub fn parse(text: &str) -> Option<f64> {
+        let text = text.trim();
+        let (number, scale) = if let Some(pct) = text.strip_suffix('%') {
+            (pct, 0.01)
+        } else if let Some(bps) = text.strip_suffix("bps") {
+            (bps, 0.0001)
+        } else {
+            (text, 1.0)
+        };
+        number.trim().parse::<f64>().ok().map(|v| v * scale)
+    }
+}
+
 pub(crate) fn mean_f64(values: &[f64]) -> f64 {
     if values.is_empty() {
         return 0.0;
@@ -317,6 +1018,7 @@ mod tests {
             entry_bar: 0,
             entry_date: date,
             entry_price: 100.0,
+            entry_trigger_margin: None,
             exit_bar: 5,
             exit_date: date,
             exit_price: if net_pnl >= 0.0 {
@@ -332,6 +1034,11 @@ mod tests {
             bars_held: 5,
             mae: 0.0,
             mfe: 0.0,
+            initial_stop_price: None,
+            entry_source: None,
+            exit_source: None,
+            liquidated_at_end: false,
+            expiry_bar: None,
             signal_id: None,
             signal_type: None,
             pm_type: None,
@@ -380,19 +1087,31 @@ mod tests {
             let daily_r = (1.1_f64).powf(1.0 / 251.0);
             eq.push(eq[i - 1] * daily_r);
         }
-        let c = cagr(&eq, 252);
+        let c = cagr(&eq, 252, TRADING_DAYS_PER_YEAR);
         assert!((c - 0.1).abs() < 0.005, "CAGR should be ~10%, got {c}");
     }
 
+    #[test]
+    fn cagr_one_year_of_weekly_bars() {
+        // The same 10% over 52 weekly bars is still one year
+        let eq: Vec<f64> = (0..52)
+            .map(|i| 100_000.0 * 1.1_f64.powf(i as f64 / 51.0))
+            .collect();
+        let weekly = PerformanceMetrics::compute_annualized(&eq, &[], 100_000.0, 52.0);
+        assert!((weekly.cagr - 0.1).abs() < 0.005, "got {}", weekly.cagr);
+        let daily = PerformanceMetrics::compute(&eq, &[], 100_000.0);
+        assert!(daily.cagr > 0.5, "52 daily bars is a fifth of a year");
+    }
+
     #[test]
     fn cagr_constant_equity() {
         let eq = vec![100_000.0; 252];
-        assert_eq!(cagr(&eq, 252), 0.0);
+        assert_eq!(cagr(&eq, 252, TRADING_DAYS_PER_YEAR), 0.0);
     }
 
     #[test]
     fn cagr_single_bar() {
-        assert_eq!(cagr(&[100_000.0], 1), 0.0);
+        assert_eq!(cagr(&[100_000.0], 1, TRADING_DAYS_PER_YEAR), 0.0);
     }
 
     // ── Sharpe ──
@@ -400,7 +1119,7 @@ mod tests {
     #[test]
     fn sharpe_constant_equity_is_zero() {
         let eq = vec![100_000.0; 100];
-        assert_eq!(sharpe_ratio(&eq, 0.0), 0.0);
+        assert_eq!(sharpe_ratio(&eq, 0.0, TRADING_DAYS_PER_YEAR), 0.0);
     }
 
     #[test]
@@ -411,7 +1130,7 @@ mod tests {
             let r = if i % 2 == 0 { 1.002 } else { 1.0005 };
             eq.push(eq[i - 1] * r);
         }
-        let s = sharpe_ratio(&eq, 0.0);
+        let s = sharpe_ratio(&eq, 0.0, TRADING_DAYS_PER_YEAR);
         // Both days are positive returns, mean ≈ 0.125%, std small → high Sharpe
         assert!(
             s > 5.0,
@@ -426,12 +1145,12 @@ mod tests {
         for i in 1..253 {
             eq.push(eq[i - 1] * 1.001);
         }
-        assert_eq!(sharpe_ratio(&eq, 0.0), 0.0);
+        assert_eq!(sharpe_ratio(&eq, 0.0, TRADING_DAYS_PER_YEAR), 0.0);
     }
 
     #[test]
     fn sharpe_single_bar() {
-        assert_eq!(sharpe_ratio(&[100_000.0], 0.0), 0.0);
+        assert_eq!(sharpe_ratio(&[100_000.0], 0.0, TRADING_DAYS_PER_YEAR), 0.0);
     }
 
     // ── Sortino ──
@@ -440,7 +1159,7 @@ mod tests {
     fn sortino_no_downside_is_zero() {
         // Monotonically increasing equity
         let eq: Vec<f64> = (0..100).map(|i| 100_000.0 + i as f64 * 100.0).collect();
-        assert_eq!(sortino_ratio(&eq, 0.0), 0.0);
+        assert_eq!(sortino_ratio(&eq, 0.0, TRADING_DAYS_PER_YEAR), 0.0);
     }
 
     #[test]
@@ -456,7 +1175,7 @@ mod tests {
         for _ in 0..50 {
             eq.push(*eq.last().unwrap() * 1.002);
         }
-        let s = sortino_ratio(&eq, 0.0);
+        let s = sortino_ratio(&eq, 0.0, TRADING_DAYS_PER_YEAR);
         assert!(s > 0.0, "Sortino should be positive, got {s}");
     }
 
@@ -559,14 +1278,14 @@ mod tests {
         for _ in 0..96 {
             eq.push(*eq.last().unwrap() * 1.002);
         }
-        let c = calmar_ratio(&eq, eq.len());
+        let c = calmar_ratio(&eq, eq.len(), TRADING_DAYS_PER_YEAR);
         assert!(c > 0.0, "Calmar should be positive, got {c}");
     }
 
     #[test]
     fn calmar_no_drawdown_is_zero() {
         let eq: Vec<f64> = (0..252).map(|i| 100_000.0 + i as f64 * 100.0).collect();
-        assert_eq!(calmar_ratio(&eq, 252), 0.0);
+        assert_eq!(calmar_ratio(&eq, 252, TRADING_DAYS_PER_YEAR), 0.0);
     }
 
     // ── Consecutive wins/losses ──
@@ -634,13 +1353,37 @@ mod tests {
         let trades = vec![make_trade(500.0)]; // entry=100, exit~=110, qty=50
                                               // Total notional = 100*50 + 110*50 = 10500
                                               // initial_capital = 100k, years = 252/252 = 1
-        let t = turnover(&trades, 100_000.0, 252);
+        let t = turnover(&trades, 100_000.0, 252, TRADING_DAYS_PER_YEAR);
         assert!(t > 0.0);
     }
 
     #[test]
     fn turnover_empty() {
-        assert_eq!(turnover(&[], 100_000.0, 252), 0.0);
+        assert_eq!(turnover(&[], 100_000.0, 252, TRADING_DAYS_PER_YEAR), 0.0);
+    }
+
+    #[test]
+    fn turnover_is_measured_against_average_equity() {
+        let trades = vec![make_trade(500.0)];
+        // Equity doubles over the year: average 150k, not the initial 100k
+        let eq: Vec<f64> = (0..252)
+            .map(|i| 100_000.0 + i as f64 * 100_000.0 / 251.0)
+            .collect();
+        let m = PerformanceMetrics::compute(&eq, &trades, 100_000.0);
+        let expected = turnover(&trades, 150_000.0, 252, TRADING_DAYS_PER_YEAR);
+        assert!((m.turnover - expected).abs() < 1e-12);
+
+        // Same trades and curve at a tenth of the capital: same turnover
+        let small_trades: Vec<TradeRecord> = trades
+            .iter()
+            .map(|t| TradeRecord {
+                quantity: t.quantity / 10.0,
+                ..t.clone()
+            })
+            .collect();
+        let small_eq: Vec<f64> = eq.iter().map(|v| v / 10.0).collect();
+        let small = PerformanceMetrics::compute(&small_eq, &small_trades, 10_000.0);
+        assert!((small.turnover - m.turnover).abs() < 1e-12);
     }
 
     // ── Aggregate ──
@@ -658,6 +1401,49 @@ mod tests {
         assert!(m.sortino.is_finite());
     }
 
+    #[test]
+    fn neutral_metrics_are_zero_and_finite() {
+        let m = PerformanceMetrics::neutral();
+        assert!(m.is_finite());
+        assert_eq!(m.trade_count, 0);
+        assert_eq!(m.total_return, 0.0);
+        assert_eq!(m.sharpe, 0.0);
+        assert_eq!(m.max_drawdown, 0.0);
+    }
+
+    #[test]
+    fn exposure_timing_of_a_half_flat_run() {
+        // Flat for the first 126 of 252 bars, then fully invested
+        let exposure: Vec<f64> = (0..252).map(|t| if t < 126 { 0.0 } else { 1.0 }).collect();
+        let eq: Vec<f64> = (0..252)
+            .map(|t| 100_000.0 * 1.001_f64.powi((t as i32 - 125).max(0)))
+            .collect();
+        let m = PerformanceMetrics::compute(&eq, &[make_trade(100.0)], 100_000.0)
+            .with_exposure(&eq, &exposure);
+
+        assert_eq!(m.time_in_market, 0.5);
+        assert_eq!(m.avg_exposure, 1.0);
+        assert!((m.invested_return - m.total_return).abs() < 1e-12);
+        assert_eq!(m.idle_return, 0.0);
+        assert!(m.cagr > 0.0);
+        assert!((m.exposure_adjusted_cagr - 2.0 * m.cagr).abs() < 1e-12);
+        assert!(m.is_finite());
+
+        // Half-sized positions: same time in market, twice the adjustment
+        let half: Vec<f64> = exposure.iter().map(|e| e / 2.0).collect();
+        let m = m.with_exposure(&eq, &half);
+        assert_eq!(m.avg_exposure, 0.5);
+        assert!((m.exposure_adjusted_cagr - 4.0 * m.cagr).abs() < 1e-12);
+    }
+
+    #[test]
+    fn exposure_timing_without_an_exposure_curve_is_zero() {
+        let eq = vec![100.0, 110.0, 121.0];
+        let m = PerformanceMetrics::compute(&eq, &[], 100.0).with_exposure(&eq, &[]);
+        assert_eq!(m.time_in_market, 0.0);
+        assert_eq!(m.exposure_adjusted_cagr, 0.0);
+    }
+
     #[test]
     fn compute_all_metrics_with_trades() {
         // Use alternating returns to get non-zero Sharpe
@@ -684,6 +1470,99 @@ mod tests {
         assert!(m.avg_losing_streak.is_finite());
     }
 
+    // ── R-multiple distribution ──
+
+    fn make_r_trade(net_pnl: f64, stop: Option<f64>) -> TradeRecord {
+        TradeRecord {
+            initial_stop_price: stop,
+            entry_source: None,
+            exit_source: None,
+            liquidated_at_end: false,
+            expiry_bar: None,
+            ..make_trade(net_pnl)
+        }
+    }
+
+    #[test]
+    fn r_distribution_basic() {
+        // Entry 100, stop 98, qty 50 → 1R = $100
+        let trades = vec![
+            make_r_trade(-100.0, Some(98.0)), // -1R
+            make_r_trade(-110.0, Some(98.0)), // -1.1R
+            make_r_trade(500.0, Some(98.0)),  // +5R
+            make_r_trade(10.0, Some(98.0)),   // +0.1R
+            make_r_trade(300.0, None),        // excluded
+        ];
+        let d = RDistribution::compute(&trades);
+        assert_eq!(d.trade_count, 4);
+        assert_eq!(d.excluded_trades, 1);
+        assert!((d.mean_r - 0.75).abs() < 1e-10);
+        assert!((d.median_r - (-0.45)).abs() < 1e-10);
+        assert!((d.pct_near_minus_one - 0.5).abs() < 1e-10);
+        assert!((d.pct_scratch - 0.25).abs() < 1e-10);
+        assert!((d.expectancy_r - d.mean_r).abs() < 1e-10);
+        assert_eq!(d.histogram.iter().sum::<usize>(), 4);
+        assert_eq!(d.histogram[1], 1); // [-2, -1)
+        assert_eq!(d.histogram[2], 1); // [-1, 0)
+        assert_eq!(d.histogram[3], 1); // [0, 1)
+        assert_eq!(d.histogram[7], 1); // [5, inf)
+    }
+
+    #[test]
+    fn r_distribution_no_stops() {
+        let trades = vec![make_trade(100.0), make_trade(-50.0)];
+        let d = RDistribution::compute(&trades);
+        assert_eq!(d.trade_count, 0);
+        assert_eq!(d.excluded_trades, 2);
+        assert_eq!(d.mean_r, 0.0);
+        assert_eq!(d.histogram.len(), R_HISTOGRAM_EDGES.len() + 1);
+    }
+
+    #[test]
+    fn r_histogram_labels() {
+        assert_eq!(RDistribution::bin_label(0), "<-2R");
+        assert_eq!(RDistribution::bin_label(1), "-2..-1R");
+        assert_eq!(RDistribution::bin_label(7), ">=5R");
+    }
+
+    #[test]
+    fn histogram_bins_follow_the_sample() {
+        let values: Vec<f64> = (0..100).map(|i| i as f64).collect();
+        let h = Histogram::compute(&values);
+        // IQR 49.5 over 100 values: width 21.3, so 5 bins
+        assert_eq!(h.counts.len(), 5);
+        assert_eq!(h.edges.len(), 6);
+        assert_eq!(h.counts.iter().sum::<usize>(), 100);
+        assert_eq!(h.median, 49.5);
+        assert_eq!(h.bin_of(99.0), Some(4));
+        assert_eq!(h.bin_of(0.0), Some(0));
+        assert_eq!(h.bin_of(100.0), None);
+
+        // No spread in the middle half: the square-root rule
+        let mut flat = vec![1.0; 14];
+        flat.extend([0.0, 10.0]);
+        assert_eq!(Histogram::compute(&flat).counts.len(), 4);
+
+        let same = Histogram::compute(&[3.0, 3.0, 3.0]);
+        assert_eq!(same.counts, vec![3]);
+    }
+
+    #[test]
+    fn histogram_of_too_few_trades_is_empty() {
+        let h = Histogram::compute(&[1.0, 2.0]);
+        assert!(h.is_empty());
+        assert_eq!(h.sample_size, 2);
+        assert_eq!(h.bin_of(1.0), None);
+
+        let d = TradeDistributions::compute(&[make_trade(100.0)]);
+        assert!(d.return_pct.is_empty() && d.holding_bars.is_empty());
+        let trades = vec![make_trade(100.0), make_trade(-50.0), make_trade(250.0)];
+        let d = TradeDistributions::compute(&trades);
+        // 50 shares at 100: +2%, -1%, +5%
+        assert!((d.return_pct.median - 0.02).abs() < 1e-12);
+        assert_eq!(d.holding_bars.counts, vec![3]);
+    }
+
     // ── Daily returns helper ──
 
     #[test]
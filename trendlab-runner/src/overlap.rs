@@ -0,0 +1,388 @@
+//! Overlap analysis — detects cross-symbol champions that are the same trade.
+//!
+//! A YOLO session often crowns many configs that all ride the same move (e.g.
+//! long tech through 2023). For the top-N cross-symbol entries this module
+//! computes:
+//! - **Trade overlap**: Jaccard similarity of the (symbol, bar) pairs each
+//!   config spends in the market.
+//! - **Return correlation**: Pearson correlation of the configs' mean per-bar
+//!   returns across symbols.
+//!
+//! Entries whose correlation reaches the threshold are clustered (single
+//! linkage), and each entry gets a diversification score:
+//! `1 - mean(max(corr, 0))` against the other analyzed entries.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use trendlab_core::domain::FullHash;
+
+/// Default correlation at or above which two entries share a cluster.
+pub const DEFAULT_CORRELATION_THRESHOLD: f64 = 0.7;
+
+/// Default number of top-ranked entries to analyze.
+pub const DEFAULT_OVERLAP_TOP_N: usize = 20;
+
+/// Points kept when downsampling a return series for history persistence.
+pub const RETURN_SAMPLE_POINTS: usize = 256;
+
+/// Settings for overlap analysis.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct OverlapConfig {
+    pub top_n: usize,
+    pub correlation_threshold: f64,
+}
+
+impl Default for OverlapConfig {
+    fn default() -> Self {
+        Self {
+            top_n: DEFAULT_OVERLAP_TOP_N,
+            correlation_threshold: DEFAULT_CORRELATION_THRESHOLD,
+        }
+    }
+}
+
+/// Pairwise overlap of the analyzed entries, in ranking order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OverlapAnalysis {
+    pub full_hashes: Vec<FullHash>,
+    /// `correlation[i][j]`: return correlation of entries i and j (0.0 if undefined).
+    pub correlation: Vec<Vec<f64>>,
+    /// `trade_overlap[i][j]`: Jaccard overlap of in-market (symbol, bar) pairs.
+    pub trade_overlap: Vec<Vec<f64>>,
+    /// Cluster id per entry. Ids are assigned in ranking order, so cluster 0
+    /// holds the top entry.
+    pub cluster_ids: Vec<usize>,
+    pub diversification_scores: Vec<f64>,
+}
+
+impl OverlapAnalysis {
+    /// Number of distinct clusters.
+    pub fn cluster_count(&self) -> usize {
+        self.cluster_ids.iter().max().map_or(0, |&c| c + 1)
+    }
+}
+
+/// Per-entry inputs: mean return series and in-market spans per symbol.
+pub struct OverlapInput<'a> {
+    pub full_hash: &'a FullHash,
+    pub equity_curves: &'a HashMap<String, Vec<f64>>,
+    /// `(entry_bar, exit_bar)` per trade, per symbol.
+    pub trade_spans: &'a HashMap<String, Vec<(usize, usize)>>,
+}
+
+/// Run the overlap analysis over entries already sorted best-first.
+pub fn analyze(inputs: &[OverlapInput<'_>], correlation_threshold: f64) -> OverlapAnalysis {
+    let n = inputs.len();
+    let returns: Vec<Vec<f64>> = inputs
+        .iter()
+        .map(|i| mean_returns(i.equity_curves))
+        .collect();
+    let exposure: Vec<HashSet<(&str, usize)>> = inputs
+        .iter()
+        .map(|i| in_market_bars(i.trade_spans))
+        .collect();
+
+    let identity: Vec<Vec<f64>> = (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+    let mut correlation = identity.clone();
+    let mut trade_overlap = identity;
+    for (i, j) in pairs(n) {
+        let c = return_correlation(&returns[i], &returns[j]).unwrap_or(0.0);
+        let o = jaccard(&exposure[i], &exposure[j]);
+        correlation[i][j] = c;
+        correlation[j][i] = c;
+        trade_overlap[i][j] = o;
+        trade_overlap[j][i] = o;
+    }
+
+    let cluster_ids = cluster(&correlation, correlation_threshold);
+    let diversification_scores = (0..n)
+        .map(|i| {
+            if n < 2 {
+                return 1.0;
+            }
+            let sum: f64 = (0..n)
+                .filter(|&j| j != i)
+                .map(|j| correlation[i][j].max(0.0))
+                .sum();
+            1.0 - sum / (n - 1) as f64
+        })
+        .collect();
+
+    OverlapAnalysis {
+        full_hashes: inputs.iter().map(|i| i.full_hash.clone()).collect(),
+        correlation,
+        trade_overlap,
+        cluster_ids,
+        diversification_scores,
+    }
+}
+
+/// Single-linkage clustering: entries i and j share a cluster when
+/// `correlation[i][j] >= threshold`, transitively.
+///
+/// Cluster ids are dense and assigned in order of each cluster's first member.
+pub fn cluster(correlation: &[Vec<f64>], threshold: f64) -> Vec<usize> {
+    let n = correlation.len();
+    let mut parent: Vec<usize> = (0..n).collect();
+
+    fn find(parent: &mut [usize], mut x: usize) -> usize {
+        while parent[x] != x {
+            parent[x] = parent[parent[x]];
+            x = parent[x];
+        }
+        x
+    }
+
+    for (i, j) in pairs(n) {
+        if correlation[i][j] >= threshold {
+            let (ri, rj) = (find(&mut parent, i), find(&mut parent, j));
+            if ri != rj {
+                // Keep the lower (better-ranked) index as root
+                parent[ri.max(rj)] = ri.min(rj);
+            }
+        }
+    }
+
+    let mut ids: HashMap<usize, usize> = HashMap::new();
+    (0..n)
+        .map(|i| {
+            let root = find(&mut parent, i);
+            let next = ids.len();
+            *ids.entry(root).or_insert(next)
+        })
+        .collect()
+}
+
+/// Pearson correlation over the common prefix of two return series.
+///
+/// Returns `None` with fewer than 2 points or zero variance.
+pub fn return_correlation(a: &[f64], b: &[f64]) -> Option<f64> {
+    let len = a.len().min(b.len());
+    if len < 2 {
+        return None;
+    }
+    let (a, b) = (&a[..len], &b[..len]);
+    let mean_a = a.iter().sum::<f64>() / len as f64;
+    let mean_b = b.iter().sum::<f64>() / len as f64;
+
+    let mut cov = 0.0;
+    let mut var_a = 0.0;
+    let mut var_b = 0.0;
+    for (&x, &y) in a.iter().zip(b) {
+        let (dx, dy) = (x - mean_a, y - mean_b);
+        cov += dx * dy;
+        var_a += dx * dx;
+        var_b += dy * dy;
+    }
+    if var_a <= 0.0 || var_b <= 0.0 {
+        return None;
+    }
+    let r = cov / (var_a.sqrt() * var_b.sqrt());
+    r.is_finite().then_some(r.clamp(-1.0, 1.0))
+}
+
+/// Effective number of independent bets among return series.
+///
+/// The participation ratio of the correlation matrix's eigenvalues,
+/// `(Σλ)² / Σλ²`. Since `Σλ = trace(C) = n` and `Σλ² = Σᵢⱼ cᵢⱼ²`, no
+/// decomposition is needed: `n² / Σᵢⱼ cᵢⱼ²`. It is `n` for uncorrelated
+/// series and 1 for perfectly (anti-)correlated ones. Undefined
+/// correlations count as 0. Returns `None` with fewer than 2 series.
+pub fn effective_bets(returns: &[Vec<f64>]) -> Option<f64> {
+    let n = returns.len();
+    if n < 2 {
+        return None;
+    }
+    let off_diagonal: f64 = pairs(n)
+        .map(|(i, j)| return_correlation(&returns[i], &returns[j]).map_or(0.0, |c| c * c))
+        .sum();
+    let n = n as f64;
+    Some(n * n / (n + 2.0 * off_diagonal))
+}
+
+/// Mean per-bar simple return across symbols (bars where a symbol's prior
+/// equity is non-positive are skipped for that symbol).
+pub fn mean_returns(curves: &HashMap<String, Vec<f64>>) -> Vec<f64> {
+    let len = curves.values().map(|c| c.len()).max().unwrap_or(0);
+    if len < 2 {
+        return Vec::new();
+    }
+    let mut sums = vec![0.0; len - 1];
+    let mut counts = vec![0usize; len - 1];
+    for curve in curves.values() {
+        for (t, w) in curve.windows(2).enumerate() {
+            if w[0] > 0.0 {
+                sums[t] += (w[1] - w[0]) / w[0];
+                counts[t] += 1;
+            }
+        }
+    }
+    sums.iter()
+        .zip(&counts)
+        .map(|(&s, &c)| if c > 0 { s / c as f64 } else { 0.0 })
+        .collect()
+}
+
+/// Downsample an equity curve into at most `max_points` compounded returns.
+///
+/// Each point is the return over one contiguous bucket of bars, so
+/// correlations between downsampled series remain meaningful.
+pub fn downsample_returns(equity_curve: &[f64], max_points: usize) -> Vec<f64> {
+    if equity_curve.len() < 2 || max_points == 0 {
+        return Vec::new();
+    }
+    let bars = equity_curve.len() - 1;
+    let buckets = bars.min(max_points);
+    (0..buckets)
+        .map(|k| {
+            let start = k * bars / buckets;
+            let end = (k + 1) * bars / buckets;
+            let (a, b) = (equity_curve[start], equity_curve[end]);
+            if a > 0.0 {
+                b / a - 1.0
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+/// Index pairs `(i, j)` with `i < j < n`.
+fn pairs(n: usize) -> impl Iterator<Item = (usize, usize)> {
+    (0..n).flat_map(move |i| ((i + 1)..n).map(move |j| (i, j)))
+}
+
+fn in_market_bars(spans: &HashMap<String, Vec<(usize, usize)>>) -> HashSet<(&str, usize)> {
+    spans
+        .iter()
+        .flat_map(|(symbol, trades)| {
+            trades
+                .iter()
+                .flat_map(move |&(entry, exit)| (entry..=exit).map(move |b| (symbol.as_str(), b)))
+        })
+        .collect()
+}
+
+fn jaccard<T: Eq + std::hash::Hash>(a: &HashSet<T>, b: &HashSet<T>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve(returns: &[f64]) -> Vec<f64> {
+        let mut eq = vec![100.0];
+        for r in returns {
+            eq.push(eq.last().unwrap() * (1.0 + r));
+        }
+        eq
+    }
+
+    #[test]
+    fn correlation_of_identical_and_opposite_series() {
+        let a = [0.01, -0.02, 0.03, 0.0, -0.01];
+        let b: Vec<f64> = a.iter().map(|r| -r).collect();
+        assert!((return_correlation(&a, &a).unwrap() - 1.0).abs() < 1e-12);
+        assert!((return_correlation(&a, &b).unwrap() + 1.0).abs() < 1e-12);
+        assert!(return_correlation(&a, &[0.0; 5]).is_none());
+        assert!(return_correlation(&a[..1], &a[..1]).is_none());
+    }
+
+    #[test]
+    fn effective_bets_counts_independent_series() {
+        // Walsh sequences: zero mean, pairwise uncorrelated
+        let walsh = vec![
+            vec![0.01, -0.01, 0.01, -0.01],
+            vec![0.01, 0.01, -0.01, -0.01],
+            vec![0.01, -0.01, -0.01, 0.01],
+        ];
+        assert!((effective_bets(&walsh).unwrap() - 3.0).abs() < 1e-12);
+
+        let same = vec![walsh[0].clone(), walsh[0].clone(), walsh[0].clone()];
+        assert!((effective_bets(&same).unwrap() - 1.0).abs() < 1e-12);
+        assert!(effective_bets(&walsh[..1]).is_none());
+    }
+
+    #[test]
+    fn mean_returns_averages_across_symbols() {
+        let curves = HashMap::from([
+            ("A".to_string(), curve(&[0.02, 0.0])),
+            ("B".to_string(), curve(&[0.0, 0.04])),
+        ]);
+        let r = mean_returns(&curves);
+        assert_eq!(r.len(), 2);
+        assert!((r[0] - 0.01).abs() < 1e-12);
+        assert!((r[1] - 0.02).abs() < 1e-12);
+    }
+
+    #[test]
+    fn downsample_preserves_total_return() {
+        let eq = curve(&[0.01; 1000]);
+        let sampled = downsample_returns(&eq, 64);
+        assert_eq!(sampled.len(), 64);
+        let total: f64 = sampled.iter().map(|r| 1.0 + r).product();
+        assert!((total - eq[1000] / eq[0]).abs() < 1e-9);
+        // Short curves keep one point per bar
+        assert_eq!(downsample_returns(&eq[..5], 64).len(), 4);
+    }
+
+    #[test]
+    fn clusters_are_transitive_and_ordered_by_rank() {
+        // 0~2 and 2~3 correlate; 1 stands alone
+        let c = vec![
+            vec![1.0, 0.1, 0.8, 0.3],
+            vec![0.1, 1.0, 0.2, 0.0],
+            vec![0.8, 0.2, 1.0, 0.75],
+            vec![0.3, 0.0, 0.75, 1.0],
+        ];
+        assert_eq!(cluster(&c, 0.7), vec![0, 1, 0, 0]);
+        assert_eq!(cluster(&c, 0.9), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn analyze_groups_same_trade_and_scores_diversification() {
+        let base = [0.01, -0.02, 0.015, 0.03, -0.01, 0.02, -0.005, 0.0];
+        let scaled: Vec<f64> = base.iter().map(|r| r * 2.0).collect();
+        let other = [-0.01, 0.01, 0.02, -0.02, 0.0, -0.01, 0.03, 0.01];
+
+        let hashes: Vec<FullHash> = (0..3)
+            .map(|i| FullHash::from_bytes(format!("h{i}").as_bytes()))
+            .collect();
+        let curves = [
+            HashMap::from([("AAPL".to_string(), curve(&base))]),
+            HashMap::from([("MSFT".to_string(), curve(&scaled))]),
+            HashMap::from([("XOM".to_string(), curve(&other))]),
+        ];
+        let spans = [
+            HashMap::from([("AAPL".to_string(), vec![(0, 5)])]),
+            HashMap::from([("AAPL".to_string(), vec![(2, 7)])]),
+            HashMap::new(),
+        ];
+        let inputs: Vec<OverlapInput<'_>> = (0..3)
+            .map(|i| OverlapInput {
+                full_hash: &hashes[i],
+                equity_curves: &curves[i],
+                trade_spans: &spans[i],
+            })
+            .collect();
+
+        let analysis = analyze(&inputs, DEFAULT_CORRELATION_THRESHOLD);
+        assert_eq!(analysis.cluster_ids, vec![0, 0, 1]);
+        assert_eq!(analysis.cluster_count(), 2);
+        // Bars 2..=5 shared out of 0..=7
+        assert!((analysis.trade_overlap[0][1] - 0.5).abs() < 1e-12);
+        assert_eq!(analysis.trade_overlap[0][2], 0.0);
+        assert!(analysis.diversification_scores[2] > analysis.diversification_scores[0]);
+    }
+}
@@ -0,0 +1,959 @@
+//! Paper trading — hypothetical live positions for promoted champions.
+//!
+//! Each champion (a config on a symbol) keeps a [`PaperState`] file and an
+//! append-only journal under the results directory's [`PAPER_DIR`].
+//! [`PaperState::update`] walks the bars that arrived since the last update
+//! with the config's own components, in the engine's bar order: pending
+//! entries and forced exits fill at the open, the stop and target are checked
+//! against the bar's range, the position is marked at the close, and then
+//! the position manager (ratcheted, and reviewed by the filter) and the
+//! signal chain run on the completed bar.
+//!
+//! Every change to a book is a [`PaperEvent`], and the book only changes by
+//! applying events, so [`PaperBook::replay`] of the journal rebuilds the
+//! state an update left behind. Positions are one unit and entry orders are
+//! good for one bar. Each bar's working orders go through the engine's
+//! [`ExecutionEngine`] under the config's execution preset, so fills follow
+//! the same gap, path and limit-fill policies and pay the same slippage and
+//! commission as in a backtest. Entry governance (cooldowns, entry caps) and
+//! the risk guard are not modeled.
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use trendlab_core::components::composition::{build_composition, StrategyComposition};
+use trendlab_core::components::factory::FactoryError;
+use trendlab_core::components::indicator::IndicatorValues;
+use trendlab_core::components::pm::IntentAction;
+use trendlab_core::components::signal::SignalDirection;
+use trendlab_core::data::align::AlignedData;
+use trendlab_core::data::BarInterval;
+use trendlab_core::domain::{
+    Bar, Fill, Instrument, MarketStatus, OcoGroupId, Order, OrderId, OrderSide, OrderStatus,
+    OrderType, Position, PositionSide,
+};
+use trendlab_core::engine::{
+    aligned_to_bars, compute_warmup, enforce_ratchet, precompute_indicators, ExecutionConfig,
+    ExecutionEngine, OrderBook,
+};
+use trendlab_core::fingerprint::{StrategyConfig, TradingMode};
+
+use crate::history::FileLock;
+use crate::runner::decode_execution_preset;
+
+/// Directory inside a results directory holding the paper books.
+pub const PAPER_DIR: &str = "paper";
+
+/// A config tracked on one symbol.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaperChampion {
+    /// File stem of the state and journal: symbol and short config hash.
+    pub id: String,
+    pub symbol: String,
+    pub config: StrategyConfig,
+    pub trading_mode: TradingMode,
+    #[serde(default)]
+    pub interval: BarInterval,
+    /// First bar date the book trades on.
+    pub since: NaiveDate,
+}
+
+impl PaperChampion {
+    pub fn new(
+        symbol: &str,
+        config: StrategyConfig,
+        trading_mode: TradingMode,
+        interval: BarInterval,
+        since: NaiveDate,
+    ) -> Self {
+        let hex = config.full_hash().as_hex();
+        Self {
+            id: format!("{symbol}-{}", &hex[..hex.len().min(12)]),
+            symbol: symbol.to_string(),
+            config,
+            trading_mode,
+            interval,
+            since,
+        }
+    }
+}
+
+/// An open hypothetical position of one unit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaperPosition {
+    pub side: PositionSide,
+    pub entry_date: NaiveDate,
+    pub entry_price: f64,
+    /// Commission paid on entry.
+    #[serde(default)]
+    pub entry_commission: f64,
+    /// Close of the latest bar with prices.
+    pub last_close: f64,
+    pub highest_since_entry: f64,
+    pub lowest_since_entry: f64,
+    /// Bars since entry, void bars included.
+    pub bars_held: usize,
+    /// Bars since entry on which the market was open.
+    pub bars_held_open: usize,
+    pub stop: Option<f64>,
+    pub initial_stop: Option<f64>,
+    pub target: Option<f64>,
+}
+
+impl PaperPosition {
+    /// PnL per unit at the latest close.
+    pub fn unrealized_pnl(&self) -> f64 {
+        pnl(self.side, self.entry_price, self.last_close)
+    }
+
+    /// Unrealized PnL as a fraction of the entry price.
+    pub fn unrealized_return(&self) -> f64 {
+        self.unrealized_pnl() / self.entry_price
+    }
+
+    /// The engine position the position manager sees.
+    fn to_position(&self, symbol: &str, entry_bar: usize) -> Position {
+        let mut position = match self.side {
+            PositionSide::Short => {
+                Position::new_short(symbol.to_string(), 1.0, self.entry_price, entry_bar)
+            }
+            _ => Position::new_long(symbol.to_string(), 1.0, self.entry_price, entry_bar),
+        };
+        position.highest_price_since_entry = self.highest_since_entry;
+        position.lowest_price_since_entry = self.lowest_since_entry;
+        position.bars_held = self.bars_held;
+        position.bars_held_open = self.bars_held_open;
+        position.unrealized_pnl = self.unrealized_pnl();
+        position.current_stop = self.stop;
+        position.initial_stop = self.initial_stop;
+        position
+    }
+}
+
+/// An entry order waiting for the next bar.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingEntry {
+    pub side: PositionSide,
+    pub order_type: OrderType,
+}
+
+/// Why a paper position closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaperExitReason {
+    Stop,
+    Target,
+    /// The position manager's forced exit, filled at the next open.
+    ForceExit,
+}
+
+/// One change to a paper book.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum PaperEvent {
+    /// The signal fired on the bar's close; the order works on the next bar.
+    OrderPlaced {
+        side: PositionSide,
+        order_type: OrderType,
+    },
+    /// The pending entry did not fill on the bar.
+    OrderExpired,
+    Entered {
+        side: PositionSide,
+        price: f64,
+        #[serde(default)]
+        commission: f64,
+    },
+    StopMoved {
+        stop: f64,
+    },
+    TargetMoved {
+        target: f64,
+    },
+    /// The position manager asked to exit at the next open.
+    ExitScheduled,
+    Exited {
+        price: f64,
+        reason: PaperExitReason,
+        /// Realized PnL per unit, net of entry and exit commission.
+        pnl: f64,
+    },
+    /// The bar was processed; `close` is None on a void bar.
+    Marked {
+        close: Option<f64>,
+    },
+}
+
+/// A journal line: an event and the date of the bar it happened on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub date: NaiveDate,
+    #[serde(flatten)]
+    pub event: PaperEvent,
+}
+
+/// The part of a paper state the journal determines.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PaperBook {
+    /// Date of the last bar processed.
+    pub through: Option<NaiveDate>,
+    pub position: Option<PaperPosition>,
+    pub pending_entry: Option<PendingEntry>,
+    pub exit_pending: bool,
+    /// Sum of closed positions' PnL per unit.
+    pub realized_pnl: f64,
+    pub closed_trades: usize,
+}
+
+impl PaperBook {
+    /// Rebuild a book from its journal, oldest entry first.
+    pub fn replay<'a>(journal: impl IntoIterator<Item = &'a JournalEntry>) -> Self {
+        let mut book = Self::default();
+        for entry in journal {
+            book.apply(entry);
+        }
+        book
+    }
+
+    /// Apply one journal entry.
+    pub fn apply(&mut self, entry: &JournalEntry) {
+        match &entry.event {
+            PaperEvent::OrderPlaced { side, order_type } => {
+                self.pending_entry = Some(PendingEntry {
+                    side: *side,
+                    order_type: order_type.clone(),
+                });
+            }
+            PaperEvent::OrderExpired => self.pending_entry = None,
+            PaperEvent::Entered {
+                side,
+                price,
+                commission,
+            } => {
+                self.pending_entry = None;
+                self.position = Some(PaperPosition {
+                    side: *side,
+                    entry_date: entry.date,
+                    entry_price: *price,
+                    entry_commission: *commission,
+                    last_close: *price,
+                    highest_since_entry: *price,
+                    lowest_since_entry: *price,
+                    bars_held: 0,
+                    bars_held_open: 0,
+                    stop: None,
+                    initial_stop: None,
+                    target: None,
+                });
+            }
+            PaperEvent::StopMoved { stop } => {
+                if let Some(position) = self.position.as_mut() {
+                    position.stop = Some(*stop);
+                    position.initial_stop.get_or_insert(*stop);
+                }
+            }
+            PaperEvent::TargetMoved { target } => {
+                if let Some(position) = self.position.as_mut() {
+                    position.target = Some(*target);
+                }
+            }
+            PaperEvent::ExitScheduled => self.exit_pending = true,
+            PaperEvent::Exited { pnl, .. } => {
+                self.position = None;
+                self.exit_pending = false;
+                self.realized_pnl += pnl;
+                self.closed_trades += 1;
+            }
+            PaperEvent::Marked { close } => {
+                self.through = Some(entry.date);
+                if let Some(position) = self.position.as_mut() {
+                    position.bars_held += 1;
+                    if let Some(close) = *close {
+                        position.bars_held_open += 1;
+                        position.last_close = close;
+                        position.highest_since_entry = position.highest_since_entry.max(close);
+                        position.lowest_since_entry = position.lowest_since_entry.min(close);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A champion and its book, as saved in the state file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaperState {
+    pub champion: PaperChampion,
+    pub book: PaperBook,
+}
+
+impl PaperState {
+    pub fn new(champion: PaperChampion) -> Self {
+        Self {
+            champion,
+            book: PaperBook::default(),
+        }
+    }
+
+    /// Process the champion's bars in `aligned` dated after the last update
+    /// (and on or after `since`), returning the journal entries written.
+    pub fn update(&mut self, aligned: &AlignedData) -> Result<Vec<JournalEntry>, FactoryError> {
+        let composition = build_composition(&self.champion.config, self.champion.trading_mode)?;
+        let bars_by_symbol = aligned_to_bars(aligned);
+        let symbol = self.champion.symbol.as_str();
+        let Some(bars) = bars_by_symbol.get(symbol) else {
+            return Ok(Vec::new());
+        };
+        let indicator_values = precompute_indicators(&bars_by_symbol, &composition.indicators);
+        let indicators = &indicator_values[symbol];
+        let required = compute_warmup(&composition.indicators);
+        let params = &self.champion.config.execution_model.params;
+        let execution = ExecutionEngine::new(
+            ExecutionConfig::from_preset(decode_execution_preset(params)).with_params(params),
+        );
+
+        let mut journal = Vec::new();
+        for t in 0..bars.len() {
+            let date = bars[t].date;
+            if date < self.champion.since || self.book.through.is_some_and(|d| date <= d) {
+                continue;
+            }
+            let mut step = Step {
+                book: &mut self.book,
+                journal: &mut journal,
+                date,
+            };
+            step.run(&composition, &execution, bars, t, indicators, required);
+        }
+        Ok(journal)
+    }
+}
+
+/// One bar of an update: events are applied to the book as they are logged.
+struct Step<'a> {
+    book: &'a mut PaperBook,
+    journal: &'a mut Vec<JournalEntry>,
+    date: NaiveDate,
+}
+
+impl Step<'_> {
+    fn emit(&mut self, event: PaperEvent) {
+        let entry = JournalEntry {
+            date: self.date,
+            event,
+        };
+        self.book.apply(&entry);
+        self.journal.push(entry);
+    }
+
+    fn exit(&mut self, fill: &Fill, reason: PaperExitReason) {
+        let Some(position) = &self.book.position else {
+            return;
+        };
+        let pnl = pnl(position.side, position.entry_price, fill.price)
+            - position.entry_commission
+            - fill.commission;
+        self.emit(PaperEvent::Exited {
+            price: fill.price,
+            reason,
+            pnl,
+        });
+    }
+
+    fn run(
+        &mut self,
+        composition: &StrategyComposition,
+        execution: &ExecutionEngine,
+        bars: &[Bar],
+        t: usize,
+        indicators: &IndicatorValues,
+        required: usize,
+    ) {
+        let bar = &bars[t];
+        if bar.is_void() {
+            // Orders wait out a void bar; held positions still age
+            self.emit(PaperEvent::Marked { close: None });
+            return;
+        }
+
+        // Forced exit and pending entry at the open, then the stop and
+        // target intrabar, in the engine's phase order
+        let pending = self.book.pending_entry.clone();
+        let mut entered = false;
+        for (role, fill) in working_fills(execution, self.book, bar, t) {
+            match (role, &pending) {
+                (OrderRole::Entry, Some(pending)) => {
+                    entered = true;
+                    self.emit(PaperEvent::Entered {
+                        side: pending.side,
+                        price: fill.price,
+                        commission: fill.commission,
+                    });
+                }
+                (OrderRole::Entry, None) => {}
+                (OrderRole::ForceExit, _) => self.exit(&fill, PaperExitReason::ForceExit),
+                (OrderRole::Stop, _) => self.exit(&fill, PaperExitReason::Stop),
+                (OrderRole::Target, _) => self.exit(&fill, PaperExitReason::Target),
+            }
+        }
+        if pending.is_some() && !entered {
+            self.emit(PaperEvent::OrderExpired);
+        }
+
+        self.emit(PaperEvent::Marked {
+            close: Some(bar.close),
+        });
+        if t < required {
+            return;
+        }
+
+        // Position maintenance on the completed bar
+        if let Some(position) = self.book.position.clone() {
+            let entry_bar = bars
+                .iter()
+                .position(|b| b.date == position.entry_date)
+                .unwrap_or(t);
+            let pos = position.to_position(&bars[t].symbol, entry_bar);
+            let mut intent = enforce_ratchet(
+                &composition
+                    .pm
+                    .on_bar(&pos, bar, t, MarketStatus::Open, indicators),
+                &pos,
+            );
+            if let Some(review) =
+                composition
+                    .filter
+                    .review_intent(&intent, position.stop, &bars, t, indicators)
+            {
+                intent = enforce_ratchet(&review.intent, &pos);
+            }
+            match intent.action {
+                IntentAction::Hold => {}
+                IntentAction::AdjustStop | IntentAction::AdjustTarget => {
+                    let stop = intent
+                        .stop_price
+                        .filter(|_| intent.action == IntentAction::AdjustStop);
+                    if let Some(stop) = stop.filter(|s| Some(*s) != position.stop) {
+                        self.emit(PaperEvent::StopMoved { stop });
+                    }
+                    if let Some(target) =
+                        intent.target_price.filter(|p| Some(*p) != position.target)
+                    {
+                        self.emit(PaperEvent::TargetMoved { target });
+                    }
+                }
+                IntentAction::ForceExit => {
+                    if !self.book.exit_pending {
+                        self.emit(PaperEvent::ExitScheduled);
+                    }
+                }
+            }
+            return;
+        }
+
+        // Flat: would the signal chain place an entry for the next bar?
+        if self.book.pending_entry.is_some() {
+            return;
+        }
+        let Some(signal) = composition.signal.evaluate(&bars, t, indicators) else {
+            return;
+        };
+        let side = match (composition.trading_mode, signal.direction) {
+            (TradingMode::LongOnly, SignalDirection::Short)
+            | (TradingMode::ShortOnly, SignalDirection::Long) => return,
+            (_, SignalDirection::Long) => PositionSide::Long,
+            (_, SignalDirection::Short) => PositionSide::Short,
+        };
+        if !composition
+            .filter
+            .evaluate(&signal, &bars, t, indicators)
+            .verdict
+            .is_passed()
+        {
+            return;
+        }
+        let instrument = Instrument::us_equity(bar.symbol.as_ref());
+        let order_type = composition
+            .execution
+            .entry_order_type(&signal, bar, &instrument);
+        self.emit(PaperEvent::OrderPlaced { side, order_type });
+    }
+}
+
+/// PnL per unit of a position closed at `exit`.
+fn pnl(side: PositionSide, entry: f64, exit: f64) -> f64 {
+    match side {
+        PositionSide::Long => exit - entry,
+        PositionSide::Short => entry - exit,
+        PositionSide::Flat => 0.0,
+    }
+}
+
+/// What a working order does for the book.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OrderRole {
+    Entry,
+    ForceExit,
+    Stop,
+    Target,
+}
+
+/// Fills of the book's working orders on `bar`, in the engine's phase order.
+///
+/// The orders are rebuilt as a one-bar engine order book: a scheduled exit
+/// at the open, the pending entry, and the stop and target as one OCO
+/// group, so whichever leg the path policy trades first cancels the other.
+fn working_fills(
+    execution: &ExecutionEngine,
+    book: &PaperBook,
+    bar: &Bar,
+    bar_index: usize,
+) -> Vec<(OrderRole, Fill)> {
+    let symbol: &str = &bar.symbol;
+    let mut order_book = OrderBook::new();
+    let mut roles = Vec::new();
+    let mut submit = |role: OrderRole, side: OrderSide, order_type: OrderType, oco: bool| {
+        let id = OrderId(roles.len() as u64 + 1);
+        let order = Order {
+            id,
+            symbol: symbol.to_string(),
+            side,
+            order_type,
+            quantity: 1.0,
+            filled_quantity: 0.0,
+            status: OrderStatus::Pending,
+            created_bar: bar_index.saturating_sub(1),
+            parent_id: None,
+            oco_group_id: None,
+            activated_bar: None,
+        };
+        if oco {
+            order_book.submit_oco_leg(order, OcoGroupId(1));
+        } else {
+            order_book.submit(order);
+        }
+        roles.push((id, role));
+    };
+
+    let mut position_sides = HashMap::new();
+    if let Some(position) = &book.position {
+        position_sides.insert(symbol.to_string(), position.side);
+        let exit_side = match position.side {
+            PositionSide::Short => OrderSide::Buy,
+            _ => OrderSide::Sell,
+        };
+        if book.exit_pending {
+            submit(
+                OrderRole::ForceExit,
+                exit_side,
+                OrderType::MarketOnOpen,
+                false,
+            );
+        } else {
+            let bracket = position.stop.is_some() && position.target.is_some();
+            if let Some(trigger_price) = position.stop {
+                let order_type = OrderType::StopMarket { trigger_price };
+                submit(OrderRole::Stop, exit_side, order_type, bracket);
+            }
+            if let Some(limit_price) = position.target {
+                let order_type = OrderType::Limit { limit_price };
+                submit(OrderRole::Target, exit_side, order_type, bracket);
+            }
+        }
+    }
+    if let Some(pending) = &book.pending_entry {
+        let side = match pending.side {
+            PositionSide::Short => OrderSide::Sell,
+            _ => OrderSide::Buy,
+        };
+        submit(OrderRole::Entry, side, pending.order_type.clone(), false);
+    }
+
+    let bars = HashMap::from([(symbol, bar)]);
+    let instruments = HashMap::from([(symbol.to_string(), Instrument::us_equity(symbol))]);
+    let mut fills = execution.process_start_of_bar(&mut order_book, &bars, &instruments, bar_index);
+    fills.extend(execution.process_intrabar(
+        &mut order_book,
+        &bars,
+        &instruments,
+        bar_index,
+        &position_sides,
+    ));
+    fills.extend(execution.process_end_of_bar(&mut order_book, &bars, &instruments, bar_index));
+    fills
+        .into_iter()
+        .filter_map(|fill| {
+            let role = roles.iter().find(|(id, _)| *id == fill.order_id)?.1;
+            Some((role, fill))
+        })
+        .collect()
+}
+
+/// Paper books in a results directory's [`PAPER_DIR`]: `<id>.json` holds the
+/// state, `<id>.journal.jsonl` the journal.
+#[derive(Debug, Clone)]
+pub struct PaperStore {
+    dir: PathBuf,
+}
+
+impl PaperStore {
+    pub fn new(results_dir: &Path) -> Self {
+        Self {
+            dir: results_dir.join(PAPER_DIR),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn state_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.json"))
+    }
+
+    pub fn journal_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.journal.jsonl"))
+    }
+
+    /// Every saved state, ordered by champion id. A state behind its journal
+    /// is rebuilt from the journal.
+    pub fn load_all(&self) -> io::Result<Vec<PaperState>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut states = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            let is_state = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.ends_with(".json"));
+            if is_state {
+                let file = BufReader::new(File::open(&path)?);
+                let mut state: PaperState = serde_json::from_reader(file)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                let journal = self.read_journal(&state.champion.id)?;
+                if journal.last().map(|e| e.date) > state.book.through {
+                    state.book = PaperBook::replay(&journal);
+                }
+                states.push(state);
+            }
+        }
+        states.sort_by(|a, b| a.champion.id.cmp(&b.champion.id));
+        Ok(states)
+    }
+
+    /// Whether a champion with this id is already tracked.
+    pub fn contains(&self, id: &str) -> bool {
+        self.state_path(id).exists()
+    }
+
+    /// Append `journal` to the champion's journal, then replace its state
+    /// file. A crash between the two leaves a journal ahead of the state,
+    /// which [`PaperStore::load_all`] replays.
+    pub fn save(&self, state: &PaperState, journal: &[JournalEntry]) -> io::Result<PathBuf> {
+        fs::create_dir_all(&self.dir)?;
+        let id = &state.champion.id;
+        let path = self.state_path(id);
+        let _lock = FileLock::acquire(&path)?;
+
+        if !journal.is_empty() {
+            let mut writer = BufWriter::new(
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(self.journal_path(id))?,
+            );
+            for entry in journal {
+                serde_json::to_writer(&mut writer, entry)?;
+                writer.write_all(b"\n")?;
+            }
+            writer.flush()?;
+        }
+
+        let tmp = self.dir.join(format!("{id}.json.tmp"));
+        let mut writer = BufWriter::new(File::create(&tmp)?);
+        serde_json::to_writer_pretty(&mut writer, state)?;
+        writer.flush()?;
+        drop(writer);
+        fs::rename(&tmp, &path)?;
+        Ok(path)
+    }
+
+    /// The champion's journal, oldest entry first.
+    pub fn read_journal(&self, id: &str) -> io::Result<Vec<JournalEntry>> {
+        let file = match File::open(self.journal_path(id)) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut journal = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            journal.push(
+                serde_json::from_str(&line)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            );
+        }
+        Ok(journal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use trendlab_core::components::composition::StrategyPreset;
+    use trendlab_core::components::execution::{GapPolicy, PathPolicy};
+    use trendlab_core::data::provider::RawBar;
+    use trendlab_core::engine::{CostModel, LimitFillPolicy};
+
+    /// Two up-and-down cycles, so positions open, ratchet and close.
+    fn waves(n: usize) -> AlignedData {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let dates: Vec<NaiveDate> = (0..n)
+            .map(|i| start + chrono::Duration::days(i as i64))
+            .collect();
+        let bars = (0..n)
+            .map(|i| {
+                let x = i as f64;
+                let close = 100.0 + 20.0 * (x / 25.0).sin() + 0.05 * x;
+                let open = close - 0.4 * (x / 3.0).cos();
+                RawBar {
+                    date: dates[i],
+                    open,
+                    high: open.max(close) + 0.8,
+                    low: open.min(close) - 0.8,
+                    close,
+                    volume: 1000,
+                    adj_close: close,
+                }
+            })
+            .collect();
+        AlignedData {
+            dates,
+            bars: HashMap::from([("SPY".to_string(), bars)]),
+            symbols: vec!["SPY".to_string()],
+        }
+    }
+
+    fn prefix(aligned: &AlignedData, n: usize) -> AlignedData {
+        AlignedData {
+            dates: aligned.dates[..n].to_vec(),
+            bars: aligned
+                .bars
+                .iter()
+                .map(|(s, bars)| (s.clone(), bars[..n].to_vec()))
+                .collect(),
+            symbols: aligned.symbols.clone(),
+        }
+    }
+
+    fn champion() -> PaperChampion {
+        PaperChampion::new(
+            "SPY",
+            StrategyPreset::MomentumRoc.to_config(),
+            TradingMode::LongOnly,
+            BarInterval::Daily,
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        )
+    }
+
+    #[test]
+    fn journal_replay_matches_daily_updates() {
+        let data = waves(300);
+        let dir = tempfile::tempdir().unwrap();
+        let store = PaperStore::new(dir.path());
+
+        // Run daily: one more bar each time, persisted between updates
+        let mut state = PaperState::new(champion());
+        for n in 1..=data.dates.len() {
+            let journal = state.update(&prefix(&data, n)).unwrap();
+            store.save(&state, &journal).unwrap();
+            state = store.load_all().unwrap().remove(0);
+        }
+
+        let journal = store.read_journal(&state.champion.id).unwrap();
+        assert_eq!(PaperBook::replay(&journal), state.book);
+        assert_eq!(state.book.through, data.dates.last().copied());
+
+        let has = |f: fn(&PaperEvent) -> bool| journal.iter().any(|e| f(&e.event));
+        assert!(has(|e| matches!(e, PaperEvent::Entered { .. })));
+        assert!(has(|e| matches!(e, PaperEvent::StopMoved { .. })));
+        assert!(has(|e| matches!(e, PaperEvent::Exited { .. })));
+        assert_eq!(
+            state.book.closed_trades,
+            journal
+                .iter()
+                .filter(|e| matches!(e.event, PaperEvent::Exited { .. }))
+                .count()
+        );
+
+        // Catching up in one update writes the same journal
+        let mut caught_up = PaperState::new(champion());
+        assert_eq!(caught_up.update(&data).unwrap(), journal);
+        assert_eq!(caught_up, state);
+    }
+
+    #[test]
+    fn stops_only_tighten_and_an_update_is_idempotent() {
+        let data = waves(300);
+        let mut state = PaperState::new(champion());
+        let journal = state.update(&data).unwrap();
+        assert!(state.update(&data).unwrap().is_empty());
+
+        let mut last_stop: Option<f64> = None;
+        for entry in &journal {
+            match entry.event {
+                PaperEvent::Entered { .. } => last_stop = None,
+                PaperEvent::StopMoved { stop } => {
+                    assert!(last_stop.is_none_or(|s| stop > s), "{entry:?}");
+                    last_stop = Some(stop);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn exits_are_net_of_slippage_and_commission() {
+        // MomentumRoc runs under the realistic preset
+        let mut state = PaperState::new(champion());
+        let journal = state.update(&waves(300)).unwrap();
+        let mut entry = None;
+        let mut exits = 0;
+        for e in &journal {
+            match e.event {
+                PaperEvent::Entered {
+                    price, commission, ..
+                } => {
+                    assert!(commission > 0.0);
+                    entry = Some(price);
+                }
+                PaperEvent::Exited { price, pnl, .. } => {
+                    assert!(pnl < price - entry.unwrap(), "{e:?}");
+                    exits += 1;
+                }
+                _ => {}
+            }
+        }
+        assert!(exits > 0);
+    }
+
+    fn bar(open: f64, high: f64, low: f64, close: f64) -> Bar {
+        Bar {
+            symbol: "SPY".into(),
+            date: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+            open,
+            high,
+            low,
+            close,
+            volume: 1_000,
+            adj_close: close,
+        }
+    }
+
+    fn frictionless(adjust: impl FnOnce(&mut ExecutionConfig)) -> ExecutionEngine {
+        let mut config = ExecutionConfig::frictionless();
+        adjust(&mut config);
+        ExecutionEngine::new(config)
+    }
+
+    /// A long from 100 with the given stop and target.
+    fn held(stop: Option<f64>, target: Option<f64>) -> PaperBook {
+        let mut book = PaperBook::default();
+        book.apply(&JournalEntry {
+            date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            event: PaperEvent::Entered {
+                side: PositionSide::Long,
+                price: 100.0,
+                commission: 0.0,
+            },
+        });
+        let position = book.position.as_mut().unwrap();
+        position.stop = stop;
+        position.target = target;
+        book
+    }
+
+    fn fill_prices(fills: &[(OrderRole, Fill)]) -> Vec<(OrderRole, f64)> {
+        fills.iter().map(|(role, f)| (*role, f.price)).collect()
+    }
+
+    #[test]
+    fn pending_stop_entry_follows_the_gap_policy() {
+        let book = PaperBook {
+            pending_entry: Some(PendingEntry {
+                side: PositionSide::Long,
+                order_type: OrderType::StopMarket {
+                    trigger_price: 102.0,
+                },
+            }),
+            ..PaperBook::default()
+        };
+        let gapped = bar(105.0, 106.0, 104.0, 105.5);
+
+        let at_open = frictionless(|c| c.gap_policy = GapPolicy::FillAtOpen);
+        let fills = working_fills(&at_open, &book, &gapped, 1);
+        assert_eq!(fill_prices(&fills), [(OrderRole::Entry, 105.0)]);
+        let at_trigger = frictionless(|c| c.gap_policy = GapPolicy::FillAtTrigger);
+        let fills = working_fills(&at_trigger, &book, &gapped, 1);
+        assert_eq!(fill_prices(&fills), [(OrderRole::Entry, 102.0)]);
+
+        let below = bar(101.0, 101.5, 100.5, 101.0);
+        assert!(working_fills(&at_open, &book, &below, 1).is_empty());
+    }
+
+    #[test]
+    fn stop_and_target_on_one_bar_follow_the_path_policy() {
+        let book = held(Some(95.0), Some(110.0));
+        let both = bar(100.0, 112.0, 94.0, 101.0);
+
+        let worst = frictionless(|c| c.path_policy = PathPolicy::WorstCase);
+        let fills = working_fills(&worst, &book, &both, 1);
+        assert_eq!(fill_prices(&fills), [(OrderRole::Stop, 95.0)]);
+        let best = frictionless(|c| c.path_policy = PathPolicy::BestCase);
+        let fills = working_fills(&best, &book, &both, 1);
+        assert_eq!(fill_prices(&fills), [(OrderRole::Target, 110.0)]);
+    }
+
+    #[test]
+    fn touched_target_fills_per_the_limit_fill_policy() {
+        let book = held(None, Some(110.0));
+        let touch = bar(105.0, 110.0, 104.0, 108.0);
+
+        let on_touch = frictionless(|_| {});
+        let fills = working_fills(&on_touch, &book, &touch, 1);
+        assert_eq!(fill_prices(&fills), [(OrderRole::Target, 110.0)]);
+        let penetration = frictionless(|c| {
+            c.limit_fill = LimitFillPolicy::RequirePenetration { ticks: 1 };
+        });
+        assert!(working_fills(&penetration, &book, &touch, 1).is_empty());
+    }
+
+    #[test]
+    fn scheduled_exit_fills_at_the_open_with_costs() {
+        let mut book = held(Some(95.0), None);
+        book.exit_pending = true;
+        let execution = ExecutionEngine::new(ExecutionConfig {
+            cost_model: CostModel::new(10.0, 5.0),
+            ..ExecutionConfig::frictionless()
+        });
+        // The stop is cancelled with the scheduled exit
+        let fills = working_fills(&execution, &book, &bar(100.0, 101.0, 90.0, 92.0), 1);
+        assert_eq!(fills.len(), 1);
+        let (role, fill) = &fills[0];
+        assert_eq!(*role, OrderRole::ForceExit);
+        assert!((fill.price - 99.9).abs() < 1e-9);
+        assert!(fill.commission > 0.0);
+    }
+}
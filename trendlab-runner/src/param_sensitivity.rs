@@ -0,0 +1,628 @@
+//! Parameter sensitivity — does a config sit on a cliff?
+//!
+//! Some discovered configs are razor-balanced: moving an ATR stop multiple
+//! from 3.0 to 2.95 flips one trade from winner to loser and halves the
+//! Sharpe. The probe reruns a config with its position manager's primary
+//! stop param scaled up and down by each of [`SensitivityConfig::steps`],
+//! then does the same for its signal's primary lookback, rounded to whole
+//! bars and moved at least one bar. Which param is primary comes from the
+//! component registry ([`ComponentSpec::primary`]).
+//!
+//! A config is flagged with a [`ParameterCliff`] when a perturbation no
+//! larger than `cliff_perturbation` moves fitness by more than
+//! `max_fitness_change` of its base value.
+//!
+//! [`ComponentSpec::primary`]: trendlab_core::components::ComponentSpec::primary
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use trendlab_core::components::execution::ExecutionPreset;
+use trendlab_core::components::{ComponentKind, PrimaryParam};
+use trendlab_core::data::align::AlignedData;
+use trendlab_core::engine::IndicatorCache;
+use trendlab_core::fingerprint::{ComponentConfig, StrategyConfig, TradingMode};
+
+use crate::fitness::FitnessMetric;
+use crate::runner::{run_backtest_cached, BacktestResult, RunError};
+
+// ─── Configuration ───────────────────────────────────────────────────
+
+/// Configuration for the parameter sensitivity probe.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SensitivityConfig {
+    /// Relative perturbations, each applied up and down (default 2%, 5%
+    /// and 10%). Units: fraction of the param's value.
+    pub steps: Vec<f64>,
+    /// Metric whose change is measured (default Sharpe).
+    pub metric: FitnessMetric,
+    /// Largest perturbation that can flag a cliff (default 0.05). Larger
+    /// steps are recorded but never flag. Units: fraction.
+    #[serde(with = "crate::metrics::fraction")]
+    pub cliff_perturbation: f64,
+    /// Fitness change, relative to the base run, beyond which a small
+    /// perturbation flags a cliff (default 0.3). Units: fraction.
+    #[serde(with = "crate::metrics::fraction")]
+    pub max_fitness_change: f64,
+    /// Stop promotion of configs with a cliff (default off: record only).
+    pub reject_cliffs: bool,
+}
+
+impl Default for SensitivityConfig {
+    fn default() -> Self {
+        Self {
+            steps: vec![0.02, 0.05, 0.10],
+            metric: FitnessMetric::Sharpe,
+            cliff_perturbation: 0.05,
+            max_fitness_change: 0.3,
+            reject_cliffs: false,
+        }
+    }
+}
+
+// ─── Result types ────────────────────────────────────────────────────
+
+/// Which component's primary param a probe moved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProbedComponent {
+    Signal,
+    PositionManager,
+}
+
+impl fmt::Display for ProbedComponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Signal => "signal",
+            Self::PositionManager => "PM",
+        })
+    }
+}
+
+/// One rerun with a primary param moved.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParameterProbe {
+    pub component: ProbedComponent,
+    /// Param name, e.g. `multiplier`.
+    pub param: String,
+    pub base_value: f64,
+    pub value: f64,
+    /// `(value - base_value) / base_value`.
+    pub perturbation: f64,
+    /// Fitness of the rerun.
+    pub fitness: f64,
+    /// Fitness change relative to the base run's; None when either is
+    /// missing or the base is zero.
+    pub fitness_change: Option<f64>,
+    /// Rerun minus base run.
+    pub sharpe_delta: f64,
+    pub cagr_delta: f64,
+    pub max_drawdown_delta: f64,
+    pub trade_count: usize,
+}
+
+/// The perturbation that flagged a config.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParameterCliff {
+    pub component: ProbedComponent,
+    pub param: String,
+    pub metric: FitnessMetric,
+    /// Relative size of the perturbation.
+    pub perturbation: f64,
+    /// Relative fitness change it caused.
+    pub fitness_change: f64,
+}
+
+impl fmt::Display for ParameterCliff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {:+.1}% moves {} {:+.0}%",
+            self.component,
+            self.param,
+            self.perturbation * 100.0,
+            self.metric.label(),
+            self.fitness_change * 100.0
+        )
+    }
+}
+
+/// Fitness around a config's primary params.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParameterSensitivity {
+    pub metric: FitnessMetric,
+    /// Fitness of the unperturbed run.
+    pub base_fitness: f64,
+    /// Probes in order: position manager, then signal; smallest
+    /// perturbation first.
+    pub probes: Vec<ParameterProbe>,
+    /// The cliff-sized probe with the largest fitness change over the
+    /// threshold, if any.
+    pub cliff: Option<ParameterCliff>,
+}
+
+impl ParameterSensitivity {
+    /// Largest fitness change among probes no larger than `max_perturbation`.
+    pub fn worst_change(&self, max_perturbation: f64) -> Option<&ParameterProbe> {
+        self.probes
+            .iter()
+            .filter(|p| p.perturbation.abs() <= max_perturbation + 1e-9)
+            .filter_map(|p| Some((p, p.fitness_change?.abs())))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(p, _)| p)
+    }
+}
+
+/// Errors from the sensitivity probe.
+#[derive(Debug, Error)]
+pub enum SensitivityError {
+    #[error("neither the signal nor the position manager has a primary param")]
+    NoPrimaryParams,
+    #[error("backtest failed for {param} = {value}: {source}")]
+    BacktestFailed {
+        param: String,
+        value: f64,
+        #[source]
+        source: RunError,
+    },
+}
+
+// ─── Probe ───────────────────────────────────────────────────────────
+
+/// Probe the primary params of `strategy_config` around its finished
+/// backtest `result`.
+///
+/// Reruns use the same trading mode, capital, sizing and execution preset
+/// and share one indicator cache. A perturbed value the factory rejects
+/// (a fast period no longer below the slow one, say) is skipped.
+#[allow(clippy::too_many_arguments)]
+pub fn run_parameter_sensitivity(
+    result: &BacktestResult,
+    strategy_config: &StrategyConfig,
+    aligned: &AlignedData,
+    symbol: &str,
+    trading_mode: TradingMode,
+    initial_capital: f64,
+    position_size_pct: f64,
+    execution_preset: ExecutionPreset,
+    dataset_hash: &str,
+    config: &SensitivityConfig,
+) -> Result<ParameterSensitivity, SensitivityError> {
+    let targets: Vec<_> = [
+        (
+            ProbedComponent::PositionManager,
+            ComponentKind::PositionManager,
+        ),
+        (ProbedComponent::Signal, ComponentKind::Signal),
+    ]
+    .into_iter()
+    .filter_map(|(component, kind)| {
+        let (primary, base_value) =
+            primary_value(kind, component_config(strategy_config, component))?;
+        Some((component, primary, base_value))
+    })
+    .collect();
+    if targets.is_empty() {
+        return Err(SensitivityError::NoPrimaryParams);
+    }
+
+    let base = &result.metrics;
+    let base_fitness = config.metric.extract(base);
+    let mut cache = IndicatorCache::new();
+    let mut probes = Vec::new();
+    for (component, primary, base_value) in targets {
+        for value in perturbed_values(base_value, primary, &config.steps) {
+            let mut perturbed = strategy_config.clone();
+            component_config_mut(&mut perturbed, component)
+                .params
+                .insert(primary.name.to_string(), value);
+            let run = match run_backtest_cached(
+                &perturbed,
+                aligned,
+                symbol,
+                trading_mode,
+                initial_capital,
+                position_size_pct,
+                execution_preset,
+                dataset_hash,
+                &mut cache,
+            ) {
+                Ok(run) => run,
+                Err(RunError::ConfigInvalid(_)) => continue,
+                Err(source) => {
+                    return Err(SensitivityError::BacktestFailed {
+                        param: primary.name.to_string(),
+                        value,
+                        source,
+                    })
+                }
+            };
+            let metrics = &run.metrics;
+            let fitness = config.metric.extract(metrics);
+            probes.push(ParameterProbe {
+                component,
+                param: primary.name.to_string(),
+                base_value,
+                value,
+                perturbation: (value - base_value) / base_value,
+                fitness,
+                fitness_change: relative_change(base_fitness, fitness),
+                sharpe_delta: metrics.sharpe - base.sharpe,
+                cagr_delta: metrics.cagr - base.cagr,
+                max_drawdown_delta: metrics.max_drawdown - base.max_drawdown,
+                trade_count: metrics.trade_count,
+            });
+        }
+    }
+
+    let mut sensitivity = ParameterSensitivity {
+        metric: config.metric,
+        base_fitness,
+        probes,
+        cliff: None,
+    };
+    sensitivity.cliff = sensitivity
+        .worst_change(config.cliff_perturbation)
+        .and_then(|p| {
+            let change = p.fitness_change?;
+            (change.abs() > config.max_fitness_change).then(|| ParameterCliff {
+                component: p.component,
+                param: p.param.clone(),
+                metric: config.metric,
+                perturbation: p.perturbation,
+                fitness_change: change,
+            })
+        });
+    Ok(sensitivity)
+}
+
+/// Values of `primary` to probe around `base`: each step down then up,
+/// smallest step first.
+///
+/// Continuous params are scaled by `1 ± step`. Bar counts are rounded to
+/// the nearest whole bar and moved at least one bar, never below one;
+/// steps that land on a value already probed are dropped.
+pub fn perturbed_values(base: f64, primary: PrimaryParam, steps: &[f64]) -> Vec<f64> {
+    let mut steps: Vec<f64> = steps.iter().copied().filter(|s| *s > 0.0).collect();
+    steps.sort_by(f64::total_cmp);
+    let mut values: Vec<f64> = Vec::new();
+    for step in steps {
+        for sign in [-1.0, 1.0] {
+            let mut value = base * (1.0 + sign * step);
+            if primary.bars {
+                value = value.round();
+                if value == base {
+                    value = base + sign;
+                }
+                if value < 1.0 {
+                    continue;
+                }
+            }
+            if value > 0.0 && value != base && !values.contains(&value) {
+                values.push(value);
+            }
+        }
+    }
+    values
+}
+
+/// The primary param of `component` and its value, the registry default
+/// when the config leaves it unset.
+fn primary_value(kind: ComponentKind, component: &ComponentConfig) -> Option<(PrimaryParam, f64)> {
+    let (primary, spec) = kind.find(&component.component_type)?.primary_param()?;
+    let value = component
+        .params
+        .get(primary.name)
+        .copied()
+        .unwrap_or(spec.default);
+    (value > 0.0).then_some((primary, value))
+}
+
+fn component_config(config: &StrategyConfig, component: ProbedComponent) -> &ComponentConfig {
+    match component {
+        ProbedComponent::Signal => &config.signal,
+        ProbedComponent::PositionManager => &config.position_manager,
+    }
+}
+
+fn component_config_mut(
+    config: &mut StrategyConfig,
+    component: ProbedComponent,
+) -> &mut ComponentConfig {
+    match component {
+        ProbedComponent::Signal => &mut config.signal,
+        ProbedComponent::PositionManager => &mut config.position_manager,
+    }
+}
+
+/// `(value - base) / |base|`, when both are present and the base is not
+/// zero.
+fn relative_change(base: f64, value: f64) -> Option<f64> {
+    (base.is_finite() && value.is_finite() && base != 0.0).then(|| (value - base) / base.abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, HashMap};
+
+    use chrono::NaiveDate;
+    use trendlab_core::data::provider::RawBar;
+
+    use crate::fdr::FdrFamily;
+    use crate::promotion::{promote, GateFailure, PromotionConfig, PromotionLevel};
+    use crate::runner::run_backtest_from_data;
+
+    /// Entry fill price of the cliff series.
+    const FILL: f64 = 101.0;
+
+    /// Flat at 100, a one-point rise that triggers a long entry filled at
+    /// [`FILL`], a dip to 4.97% below the fill the next bar, a steady climb
+    /// to 130 and a slide to 110. A 5% trailing stop just survives the dip
+    /// and exits on the slide; a 4.9% one is taken out at a loss.
+    fn cliff_series() -> AlignedData {
+        let start = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        let closes: Vec<f64> = (0..60)
+            .map(|i| match i {
+                0..=10 => 100.0,
+                11 | 12 => FILL,
+                13..=49 => 100.0 + 30.0 * (i - 13) as f64 / 36.0,
+                _ => 130.0 - 20.0 * (i - 49) as f64 / 10.0,
+            })
+            .collect();
+        let bars: Vec<RawBar> = closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| {
+                let open = if i == 0 { close } else { closes[i - 1] };
+                // The fill bar's high is the fill, so the stop trails it
+                let high = if i == 12 {
+                    FILL
+                } else {
+                    open.max(close) * 1.002
+                };
+                let low = if i == 13 {
+                    FILL * (1.0 - 0.0497)
+                } else {
+                    open.min(close) * 0.998
+                };
+                RawBar {
+                    date: start + chrono::Duration::days(i as i64),
+                    open,
+                    high,
+                    low,
+                    close,
+                    volume: 1000,
+                    adj_close: close,
+                }
+            })
+            .collect();
+        AlignedData {
+            dates: bars.iter().map(|b| b.date).collect(),
+            bars: HashMap::from([("SYN".to_string(), bars)]),
+            symbols: vec!["SYN".to_string()],
+        }
+    }
+
+    fn component(component_type: &str, params: &[(&str, f64)]) -> ComponentConfig {
+        ComponentConfig {
+            component_type: component_type.into(),
+            params: params
+                .iter()
+                .map(|(k, v)| (k.to_string(), *v))
+                .collect::<BTreeMap<_, _>>(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Momentum entry (one trade only) behind a 5% trailing stop.
+    fn cliff_config() -> StrategyConfig {
+        StrategyConfig {
+            signal: component("tsmom", &[("lookback", 10.0), ("cooldown_bars", 1000.0)]),
+            position_manager: component("percent_trailing", &[("trail_pct", 0.05)]),
+            execution_model: component("next_bar_open", &[]),
+            signal_filter: component("no_filter", &[]),
+        }
+    }
+
+    fn run(config: &StrategyConfig, aligned: &AlignedData) -> BacktestResult {
+        run_backtest_from_data(
+            config,
+            aligned,
+            "SYN",
+            TradingMode::LongOnly,
+            100_000.0,
+            1.0,
+            ExecutionPreset::Frictionless,
+            "cliff",
+            false,
+        )
+        .unwrap()
+    }
+
+    fn probe(result: &BacktestResult, aligned: &AlignedData) -> ParameterSensitivity {
+        run_parameter_sensitivity(
+            result,
+            &cliff_config(),
+            aligned,
+            "SYN",
+            TradingMode::LongOnly,
+            100_000.0,
+            1.0,
+            ExecutionPreset::Frictionless,
+            "cliff",
+            &SensitivityConfig::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn stop_on_the_edge_of_a_dip_is_a_cliff() {
+        let aligned = cliff_series();
+        let result = run(&cliff_config(), &aligned);
+        assert_eq!(result.trades.len(), 1);
+        assert!((result.trades[0].entry_price - FILL).abs() < 1e-9);
+        assert!(result.trades[0].net_pnl > 0.0);
+
+        let sensitivity = probe(&result, &aligned);
+        let stop_probes: Vec<_> = sensitivity
+            .probes
+            .iter()
+            .filter(|p| p.component == ProbedComponent::PositionManager)
+            .collect();
+        assert_eq!(stop_probes.len(), 6);
+        // Tighter stops are hit by the dip and flip the trade to a loss;
+        // looser ones only exit a little later on the slide
+        for p in &stop_probes {
+            assert_eq!(p.param, "trail_pct");
+            let change = p.fitness_change.unwrap();
+            if p.perturbation < 0.0 {
+                assert!(change < -1.0, "{p:?}");
+                assert!(p.sharpe_delta < 0.0 && p.cagr_delta < 0.0, "{p:?}");
+            } else {
+                assert!(change.abs() < 0.3, "{p:?}");
+            }
+        }
+        // The lookback moves whole bars: 10 ± 1
+        let lookbacks: Vec<f64> = sensitivity
+            .probes
+            .iter()
+            .filter(|p| p.component == ProbedComponent::Signal)
+            .map(|p| p.value)
+            .collect();
+        assert_eq!(lookbacks, [9.0, 11.0]);
+
+        let cliff = sensitivity.cliff.expect("cliff flagged");
+        assert_eq!(cliff.component, ProbedComponent::PositionManager);
+        assert_eq!(cliff.param, "trail_pct");
+        assert!(cliff.perturbation < 0.0 && cliff.perturbation >= -0.05 - 1e-9);
+    }
+
+    #[test]
+    fn stop_clear_of_the_dip_is_not_a_cliff() {
+        let aligned = cliff_series();
+        let mut config = cliff_config();
+        config
+            .position_manager
+            .params
+            .insert("trail_pct".into(), 0.08);
+        let result = run(&config, &aligned);
+        let sensitivity = run_parameter_sensitivity(
+            &result,
+            &config,
+            &aligned,
+            "SYN",
+            TradingMode::LongOnly,
+            100_000.0,
+            1.0,
+            ExecutionPreset::Frictionless,
+            "cliff",
+            &SensitivityConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(sensitivity.cliff, None);
+        assert!(sensitivity
+            .probes
+            .iter()
+            .all(|p| p.fitness_change.is_some_and(|c| c.abs() < 0.3)));
+    }
+
+    #[test]
+    fn promotion_rejects_cliffs_when_asked() {
+        let aligned = cliff_series();
+        let config = cliff_config();
+        let result = run(&config, &aligned);
+        let promote_with = |reject_cliffs| {
+            let promotion_config = PromotionConfig {
+                wf_sharpe_threshold: 0.0,
+                sensitivity: Some(SensitivityConfig {
+                    reject_cliffs,
+                    ..SensitivityConfig::default()
+                }),
+                ..PromotionConfig::default()
+            };
+            promote(
+                &result,
+                &config,
+                &aligned,
+                "SYN",
+                TradingMode::LongOnly,
+                100_000.0,
+                1.0,
+                ExecutionPreset::Frictionless,
+                "cliff",
+                &promotion_config,
+                &mut FdrFamily::new(),
+            )
+        };
+
+        let rejected = promote_with(true);
+        assert_eq!(rejected.level_reached, PromotionLevel::Level1CheapPass);
+        assert!(rejected.walk_forward.is_none());
+        let Some(GateFailure::ParameterCliff { cliff, max_change }) = &rejected.gate_failure else {
+            panic!("expected a cliff, got {:?}", rejected.gate_failure);
+        };
+        assert_eq!(cliff.param, "trail_pct");
+        assert!((max_change - 0.3).abs() < 1e-12);
+
+        // Recorded but not gated: the ladder goes on to walk-forward
+        let recorded = promote_with(false);
+        assert!(recorded.sensitivity.as_ref().unwrap().cliff.is_some());
+        assert!(!matches!(
+            recorded.gate_failure,
+            Some(GateFailure::ParameterCliff { .. })
+        ));
+    }
+
+    #[test]
+    fn continuous_params_scale_both_ways() {
+        let values = perturbed_values(3.0, PrimaryParam::value("multiplier"), &[0.05, 0.02]);
+        let expected = [2.94, 3.06, 2.85, 3.15];
+        assert_eq!(values.len(), expected.len());
+        for (v, e) in values.iter().zip(expected) {
+            assert!((v - e).abs() < 1e-12, "{values:?}");
+        }
+    }
+
+    #[test]
+    fn bar_params_move_whole_bars() {
+        let steps = SensitivityConfig::default().steps;
+        // 2% of 20 rounds back to 20 and moves one bar instead; 5% lands
+        // on the same bars
+        assert_eq!(
+            perturbed_values(20.0, PrimaryParam::bars("lookback"), &steps),
+            [19.0, 21.0, 18.0, 22.0]
+        );
+        assert_eq!(
+            perturbed_values(100.0, PrimaryParam::bars("lookback"), &steps),
+            [98.0, 102.0, 95.0, 105.0, 90.0, 110.0]
+        );
+        assert_eq!(
+            perturbed_values(1.0, PrimaryParam::bars("lookback"), &steps),
+            [2.0]
+        );
+    }
+
+    #[test]
+    fn relative_change_skips_zero_and_missing_bases() {
+        assert_eq!(relative_change(1.0, 0.5), Some(-0.5));
+        assert_eq!(relative_change(-0.5, -1.0), Some(-1.0));
+        assert_eq!(relative_change(0.0, 0.1), None);
+        assert_eq!(relative_change(1.0, f64::NAN), None);
+    }
+
+    #[test]
+    fn cliff_display_names_param_and_metric() {
+        let cliff = ParameterCliff {
+            component: ProbedComponent::PositionManager,
+            param: "stop_pct".into(),
+            metric: FitnessMetric::Sharpe,
+            perturbation: -0.02,
+            fitness_change: -1.54,
+        };
+        assert_eq!(cliff.to_string(), "PM stop_pct -2.0% moves sharpe -154%");
+    }
+}
@@ -1,13 +1,21 @@
 //! Promotion ladder — sequential robustness levels for strategy candidates.
 //!
 //! Cheap candidates must "earn" expensive simulation:
-//! - **Level 1 (Cheap Pass):** single backtest passed basic filters.
+//! - **Level 1 (Cheap Pass):** single backtest passed basic filters and,
+//!   when configured, beat buy-and-hold of its symbol over the same bars,
+//!   ranked high against randomized entries (the shuffle test) and kept its
+//!   fitness when its primary params were nudged (the sensitivity probe).
 //! - **Level 2 (Walk-Forward):** OOS performance survives walk-forward validation.
-//! - **Level 3 (Execution MC + Bootstrap):** execution sensitivity is bounded; Sharpe CI is graded.
+//! - **Level 3 (Execution MC + Bootstrap):** profit does not hinge on borderline
+//!   stop/limit fills; execution sensitivity is bounded; Sharpe CI is graded.
 //!
 //! The `promote()` function orchestrates the gates: each level runs only if the
 //! previous level passed. OOS p-values are recorded into an `FdrFamily` for
-//! Benjamini-Hochberg correction across the YOLO run.
+//! Benjamini-Hochberg correction across the YOLO run. `promote_cached()` runs
+//! the same ladder but reuses level results from a [`RobustnessCache`].
+
+use std::fmt;
+use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -19,11 +27,20 @@ use trendlab_core::fingerprint::{StrategyConfig, TradingMode};
 use crate::bootstrap::{
     stationary_block_bootstrap, BootstrapConfig, BootstrapError, BootstrapResult,
 };
+use crate::config::ConfigError;
 use crate::execution_mc::{
-    run_execution_mc, ExecutionMcConfig, ExecutionMcResult, McError,
+    run_execution_mc, run_trigger_margin_mc, ExecutionMcConfig, ExecutionMcResult,
+    FrictionSensitivity, McError, TriggerMarginMcConfig, TriggerMarginMcResult,
 };
 use crate::fdr::FdrFamily;
+use crate::metrics::PerformanceMetrics;
+use crate::param_sensitivity::{
+    run_parameter_sensitivity, ParameterCliff, ParameterSensitivity, SensitivityConfig,
+};
+use crate::robustness_cache::{CachedLevel, LevelKey, RobustnessCache};
 use crate::runner::BacktestResult;
+use crate::shuffle_test::{run_shuffle_test, ShuffleTestConfig, SignalValueScore};
+use crate::style::FactorSeries;
 use crate::walk_forward::{
     run_walk_forward, DegradationFlag, WalkForwardConfig, WalkForwardError, WalkForwardResult,
 };
@@ -31,20 +48,54 @@ use crate::walk_forward::{
 // ─── Configuration ───────────────────────────────────────────────────
 
 /// Configuration for the promotion ladder.
+///
+/// Every field has a default, so a TOML file only needs the values it changes.
+///
+/// No threshold is in dollars, so a config gates runs the same way at any
+/// initial capital. Fraction thresholds may be written as `0.5`, `"50%"` or
+/// `"5000bps"` and are converted when loaded (see [`crate::metrics::fraction`]).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PromotionConfig {
     /// Minimum Sharpe from Level 1 backtest to attempt Level 2 walk-forward.
+    /// Units: annualized Sharpe.
     pub wf_sharpe_threshold: f64,
     /// Walk-forward configuration.
     pub wf_config: WalkForwardConfig,
     /// Minimum degradation ratio to pass walk-forward gate (Level 2 → 3).
+    /// Units: fraction (OOS Sharpe / IS Sharpe).
+    #[serde(with = "crate::metrics::fraction")]
     pub wf_degradation_threshold: f64,
     /// Execution Monte Carlo configuration.
     pub mc_config: ExecutionMcConfig,
+    /// Trigger-margin Monte Carlo configuration.
+    pub trigger_margin_config: TriggerMarginMcConfig,
+    /// Maximum share of net profit that may come from borderline entry fills
+    /// to enter Level 3 (default 0.5). Units: fraction of net profit.
+    #[serde(with = "crate::metrics::fraction")]
+    pub max_borderline_profit_fraction: f64,
     /// Bootstrap configuration.
     pub bootstrap_config: BootstrapConfig,
-    /// FDR significance level (default 0.05).
+    /// FDR significance level (default 0.05). Units: probability.
+    #[serde(with = "crate::metrics::fraction")]
     pub fdr_alpha: f64,
+    /// Metric the Level 1 run must beat its symbol's buy-and-hold on, over
+    /// the same bars, before walk-forward runs. None (default) skips the gate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub benchmark_metric: Option<BenchmarkMetric>,
+    /// How far the run must beat buy-and-hold on `benchmark_metric`, in that
+    /// metric's units (0.2 = a Sharpe 0.2 above buy-and-hold; default 0).
+    pub benchmark_margin: f64,
+    /// Also require a shallower max drawdown than buy-and-hold (default off).
+    pub benchmark_lower_drawdown: bool,
+    /// Shuffle test of the Level 1 entries against randomized ones, run
+    /// before walk-forward. None (default) skips it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shuffle_test: Option<ShuffleTestConfig>,
+    /// Sensitivity probe of the primary stop and lookback params, run
+    /// before walk-forward. None (default) skips it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sensitivity: Option<SensitivityConfig>,
 }
 
 impl Default for PromotionConfig {
@@ -54,8 +105,77 @@ impl Default for PromotionConfig {
             wf_config: WalkForwardConfig::default(),
             wf_degradation_threshold: 0.3,
             mc_config: ExecutionMcConfig::default(),
+            trigger_margin_config: TriggerMarginMcConfig::default(),
+            max_borderline_profit_fraction: 0.5,
             bootstrap_config: BootstrapConfig::default(),
             fdr_alpha: 0.05,
+            benchmark_metric: None,
+            benchmark_margin: 0.0,
+            benchmark_lower_drawdown: false,
+            shuffle_test: None,
+            sensitivity: None,
+        }
+    }
+}
+
+impl PromotionConfig {
+    /// Load from a TOML file.
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| ConfigError::Io(e.to_string()))?;
+        Self::from_toml(&contents)
+    }
+
+    /// Parse from a TOML string.
+    pub fn from_toml(toml_str: &str) -> Result<Self, ConfigError> {
+        toml::from_str(toml_str).map_err(|e| ConfigError::Parse(e.to_string()))
+    }
+}
+
+/// Metric the benchmark gate compares with buy-and-hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BenchmarkMetric {
+    Sharpe,
+    Sortino,
+    Cagr,
+    TotalReturn,
+    Calmar,
+}
+
+impl BenchmarkMetric {
+    pub const ALL: [BenchmarkMetric; 5] = [
+        BenchmarkMetric::Sharpe,
+        BenchmarkMetric::Sortino,
+        BenchmarkMetric::Cagr,
+        BenchmarkMetric::TotalReturn,
+        BenchmarkMetric::Calmar,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Sharpe => "Sharpe",
+            Self::Sortino => "Sortino",
+            Self::Cagr => "CAGR",
+            Self::TotalReturn => "total return",
+            Self::Calmar => "Calmar",
+        }
+    }
+
+    pub fn value(self, metrics: &PerformanceMetrics) -> f64 {
+        match self {
+            Self::Sharpe => metrics.sharpe,
+            Self::Sortino => metrics.sortino,
+            Self::Cagr => metrics.cagr,
+            Self::TotalReturn => metrics.total_return,
+            Self::Calmar => metrics.calmar,
+        }
+    }
+
+    /// A signed difference in this metric's units (percent for returns).
+    pub fn format_margin(self, margin: f64) -> String {
+        match self {
+            Self::Cagr | Self::TotalReturn => format!("{:+.1}%", margin * 100.0),
+            Self::Sharpe | Self::Sortino | Self::Calmar => format!("{margin:+.2}"),
         }
     }
 }
@@ -73,6 +193,16 @@ pub enum PromotionLevel {
     Level3ExecutionMc,
 }
 
+impl fmt::Display for PromotionLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Level1CheapPass => "L1 cheap pass",
+            Self::Level2WalkForward => "L2 walk-forward",
+            Self::Level3ExecutionMc => "L3 execution MC",
+        })
+    }
+}
+
 /// Complete robustness result from the promotion pipeline.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RobustnessResult {
@@ -84,19 +214,175 @@ pub struct RobustnessResult {
     pub execution_mc: Option<ExecutionMcResult>,
     /// Bootstrap result (None if Level 2 gate failed).
     pub bootstrap: Option<BootstrapResult>,
+    /// Trigger-margin MC result (None if walk-forward failed).
+    #[serde(default)]
+    pub borderline: Option<TriggerMarginMcResult>,
     /// Reason promotion stopped (None if reached Level 3).
     pub gate_failure: Option<GateFailure>,
+    /// Friction ramp, when enabled via `YoloConfig::friction_ramp`.
+    #[serde(default)]
+    pub friction: Option<FrictionSensitivity>,
+    /// The Level 1 run against buy-and-hold (None for zero-trade runs and
+    /// results saved before it was recorded).
+    #[serde(default)]
+    pub benchmark: Option<BenchmarkComparison>,
+    /// The Level 1 run against randomized entries (None when the shuffle
+    /// test is off or could not run).
+    #[serde(default)]
+    pub signal_value: Option<SignalValueScore>,
+    /// Fitness with the primary params nudged, with any cliff found (None
+    /// when the probe is off or could not run).
+    #[serde(default)]
+    pub sensitivity: Option<ParameterSensitivity>,
+}
+
+/// A Level 1 run next to buy-and-hold of its symbol over the same bars.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkComparison {
+    /// Buy-and-hold metrics, close to close without costs.
+    pub buy_and_hold: PerformanceMetrics,
+    /// Metric compared: `benchmark_metric`, or Sharpe when the gate is off.
+    pub metric: BenchmarkMetric,
+    /// Run minus buy-and-hold on `metric`.
+    pub margin: f64,
+    /// Run max drawdown minus buy-and-hold's; positive when the run's
+    /// drawdown was shallower.
+    pub drawdown_margin: f64,
+}
+
+impl BenchmarkComparison {
+    /// Compare `result` with buy-and-hold of `symbol` over the bars of
+    /// `aligned`. None when `aligned` has no bars for the symbol.
+    pub fn compute(
+        result: &BacktestResult,
+        aligned: &AlignedData,
+        symbol: &str,
+        metric: BenchmarkMetric,
+    ) -> Option<Self> {
+        let bars = aligned.bars.get(symbol)?;
+        let curve = FactorSeries::from_bars(symbol, bars).equity_curve();
+        let buy_and_hold = PerformanceMetrics::compute_annualized(
+            &curve,
+            &[],
+            1.0,
+            result.interval.periods_per_year(),
+        );
+        Some(Self {
+            metric,
+            margin: metric.value(&result.metrics) - metric.value(&buy_and_hold),
+            drawdown_margin: result.metrics.max_drawdown - buy_and_hold.max_drawdown,
+            buy_and_hold,
+        })
+    }
+
+    /// The run's value of `metric`.
+    pub fn strategy_value(&self) -> f64 {
+        self.metric.value(&self.buy_and_hold) + self.margin
+    }
+
+    /// Why the comparison fails `config`'s benchmark gate, if it does.
+    fn gate_failure(&self, config: &PromotionConfig) -> Option<GateFailure> {
+        if config.benchmark_metric.is_some() && self.margin < config.benchmark_margin {
+            return Some(GateFailure::BelowBenchmark {
+                metric: self.metric,
+                margin: self.margin,
+                required: config.benchmark_margin,
+            });
+        }
+        if config.benchmark_lower_drawdown && self.drawdown_margin <= 0.0 {
+            return Some(GateFailure::BenchmarkDrawdown {
+                max_drawdown: self.buy_and_hold.max_drawdown + self.drawdown_margin,
+                benchmark: self.buy_and_hold.max_drawdown,
+            });
+        }
+        None
+    }
 }
 
 /// Why promotion stopped at a particular level.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GateFailure {
+    /// The Level 1 backtest closed no trades, so there is nothing to validate.
+    ZeroTrades,
     /// Level 1 Sharpe below threshold.
     InsufficientSharpe { sharpe: f64, threshold: f64 },
+    /// Level 1 run did not beat buy-and-hold by `required` on `metric`.
+    BelowBenchmark {
+        metric: BenchmarkMetric,
+        margin: f64,
+        required: f64,
+    },
+    /// Level 1 max drawdown no shallower than buy-and-hold's.
+    BenchmarkDrawdown { max_drawdown: f64, benchmark: f64 },
+    /// Level 1 Sharpe did not rank high enough among randomized entries.
+    NoSignalValue { percentile: f64, required: f64 },
+    /// A small nudge to a primary param moved fitness by more than
+    /// `max_change`.
+    ParameterCliff {
+        cliff: ParameterCliff,
+        max_change: f64,
+    },
     /// Walk-forward degradation too high or OOS failed.
     WalkForwardFailed { reason: String },
     /// Walk-forward error (insufficient data, backtest failure, etc.).
     WalkForwardError { reason: String },
+    /// Too much of the profit came from entries that barely triggered.
+    BorderlineFills {
+        profit_fraction: f64,
+        threshold: f64,
+    },
+}
+
+impl fmt::Display for GateFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZeroTrades => f.write_str("no trades"),
+            Self::InsufficientSharpe { sharpe, threshold } => {
+                write!(f, "Sharpe {sharpe:.2} below {threshold:.2}")
+            }
+            Self::BelowBenchmark {
+                metric,
+                margin,
+                required,
+            } => write!(
+                f,
+                "{} {} vs buy-and-hold (need {})",
+                metric.label(),
+                metric.format_margin(*margin),
+                metric.format_margin(*required)
+            ),
+            Self::BenchmarkDrawdown {
+                max_drawdown,
+                benchmark,
+            } => write!(
+                f,
+                "max drawdown {:.1}% not below buy-and-hold's {:.1}%",
+                -max_drawdown * 100.0,
+                -benchmark * 100.0
+            ),
+            Self::NoSignalValue {
+                percentile,
+                required,
+            } => write!(
+                f,
+                "Sharpe beats {percentile:.0}% of shuffled entries (need {required:.0}%)"
+            ),
+            Self::ParameterCliff { cliff, max_change } => {
+                write!(f, "{cliff} (max {:.0}%)", max_change * 100.0)
+            }
+            Self::WalkForwardFailed { reason } => write!(f, "walk-forward failed: {reason}"),
+            Self::WalkForwardError { reason } => write!(f, "walk-forward error: {reason}"),
+            Self::BorderlineFills {
+                profit_fraction,
+                threshold,
+            } => write!(
+                f,
+                "{:.0}% of profit from borderline fills (max {:.0}%)",
+                profit_fraction * 100.0,
+                threshold * 100.0
+            ),
+        }
+    }
 }
 
 /// Errors from the promotion pipeline.
@@ -115,10 +401,21 @@ pub enum PromotionError {
 /// Run the promotion ladder for a strategy that passed Level 1.
 ///
 /// Gate logic:
-/// - **1 → 2:** Level 1 Sharpe >= `wf_sharpe_threshold`.
+/// - **Zero trades:** a Level 1 run without trades stops here, before any
+///   walk-forward or Monte Carlo work.
+/// - **1 → 2:** Level 1 Sharpe >= `wf_sharpe_threshold`, and (when
+///   `benchmark_metric` or `benchmark_lower_drawdown` is set) the run beats
+///   buy-and-hold of the symbol over the same bars. With `shuffle_test` set,
+///   the run is scored against randomized entries, and gated on the score
+///   when `min_sharpe_percentile` is set. With `sensitivity` set, the run is
+///   rerun with its primary params nudged, and rejected on a cliff when
+///   `reject_cliffs` is set.
 /// - **2 → 3:** Degradation ratio > `wf_degradation_threshold` (when Normal),
 ///   OOS Sharpe > 0, and p-value is recorded into `fdr_family`.
-/// - **Level 3:** Run execution MC + bootstrap. Always completes if Level 2 passes.
+/// - **Borderline fills:** share of net profit from entries whose trigger margin
+///   is below the trigger-margin epsilon must not exceed
+///   `max_borderline_profit_fraction`.
+/// - **Level 3:** Run execution MC + bootstrap.
 ///
 /// The `fdr_family` accumulates OOS p-values across all promoted strategies
 /// in the YOLO run for Benjamini-Hochberg correction.
@@ -136,6 +433,162 @@ pub fn promote(
     promotion_config: &PromotionConfig,
     fdr_family: &mut FdrFamily,
 ) -> RobustnessResult {
+    let context = RunContext {
+        symbol,
+        trading_mode,
+        initial_capital,
+        position_size_pct,
+        execution_preset,
+    };
+    let mut levels = LevelRunner::new(None, strategy_config, dataset_hash, context);
+    run_ladder(
+        result,
+        strategy_config,
+        aligned,
+        promotion_config,
+        fdr_family,
+        &mut levels,
+    )
+}
+
+/// [`promote`], reusing shuffle-test, walk-forward, trigger-margin MC,
+/// execution MC and bootstrap results from `cache` and storing the ones it
+/// computes.
+///
+/// Also returns the levels that came from the cache, in ladder order.
+#[allow(clippy::too_many_arguments)]
+pub fn promote_cached(
+    result: &BacktestResult,
+    strategy_config: &StrategyConfig,
+    aligned: &AlignedData,
+    symbol: &str,
+    trading_mode: TradingMode,
+    initial_capital: f64,
+    position_size_pct: f64,
+    execution_preset: ExecutionPreset,
+    dataset_hash: &str,
+    promotion_config: &PromotionConfig,
+    fdr_family: &mut FdrFamily,
+    cache: &RobustnessCache,
+) -> (RobustnessResult, Vec<CachedLevel>) {
+    let context = RunContext {
+        symbol,
+        trading_mode,
+        initial_capital,
+        position_size_pct,
+        execution_preset,
+    };
+    let mut levels = LevelRunner::new(Some(cache), strategy_config, dataset_hash, context);
+    let robustness = run_ladder(
+        result,
+        strategy_config,
+        aligned,
+        promotion_config,
+        fdr_family,
+        &mut levels,
+    );
+    (robustness, levels.from_cache)
+}
+
+/// Run settings every cached level depends on, besides its own config.
+#[derive(Debug, Clone, Copy, Serialize)]
+struct RunContext<'a> {
+    symbol: &'a str,
+    trading_mode: TradingMode,
+    initial_capital: f64,
+    position_size_pct: f64,
+    execution_preset: ExecutionPreset,
+}
+
+/// Runs ladder levels, through the cache when there is one.
+struct LevelRunner<'a> {
+    cache: Option<&'a RobustnessCache>,
+    config_hash: String,
+    dataset_hash: &'a str,
+    context: RunContext<'a>,
+    from_cache: Vec<CachedLevel>,
+}
+
+impl<'a> LevelRunner<'a> {
+    fn new(
+        cache: Option<&'a RobustnessCache>,
+        strategy_config: &StrategyConfig,
+        dataset_hash: &'a str,
+        context: RunContext<'a>,
+    ) -> Self {
+        Self {
+            cache,
+            config_hash: strategy_config.full_hash().as_hex(),
+            dataset_hash,
+            context,
+            from_cache: Vec::new(),
+        }
+    }
+
+    fn run<T, E>(
+        &mut self,
+        level: CachedLevel,
+        level_config: &impl Serialize,
+        compute: impl FnOnce() -> Result<T, E>,
+    ) -> Result<T, E>
+    where
+        T: Serialize + serde::de::DeserializeOwned,
+    {
+        let Some(cache) = self.cache else {
+            return compute();
+        };
+        let key = LevelKey::new(
+            &self.config_hash,
+            self.dataset_hash,
+            level,
+            &(level_config, &self.context),
+        );
+        let (result, cached) = cache.get_or_compute(&key, compute);
+        if cached {
+            self.from_cache.push(level);
+        }
+        result
+    }
+}
+
+fn run_ladder(
+    result: &BacktestResult,
+    strategy_config: &StrategyConfig,
+    aligned: &AlignedData,
+    promotion_config: &PromotionConfig,
+    fdr_family: &mut FdrFamily,
+    levels: &mut LevelRunner<'_>,
+) -> RobustnessResult {
+    let RunContext {
+        symbol,
+        trading_mode,
+        initial_capital,
+        position_size_pct,
+        execution_preset,
+    } = levels.context;
+    let dataset_hash = levels.dataset_hash;
+
+    // ── Zero trades: nothing for the later levels to measure ──
+    if result.zero_trades || result.trades.is_empty() {
+        return RobustnessResult {
+            level_reached: PromotionLevel::Level1CheapPass,
+            walk_forward: None,
+            execution_mc: None,
+            bootstrap: None,
+            borderline: None,
+            gate_failure: Some(GateFailure::ZeroTrades),
+            friction: None,
+            benchmark: None,
+            signal_value: None,
+            sensitivity: None,
+        };
+    }
+
+    let benchmark_metric = promotion_config
+        .benchmark_metric
+        .unwrap_or(BenchmarkMetric::Sharpe);
+    let benchmark = BenchmarkComparison::compute(result, aligned, symbol, benchmark_metric);
+
     // ── Gate 1 → 2: Sharpe threshold ──
     let sharpe = result.metrics.sharpe;
     if sharpe < promotion_config.wf_sharpe_threshold {
@@ -144,25 +597,138 @@ pub fn promote(
             walk_forward: None,
             execution_mc: None,
             bootstrap: None,
+            borderline: None,
             gate_failure: Some(GateFailure::InsufficientSharpe {
                 sharpe,
                 threshold: promotion_config.wf_sharpe_threshold,
             }),
+            friction: None,
+            benchmark,
+            signal_value: None,
+            sensitivity: None,
+        };
+    }
+
+    // ── Gate 1 → 2: buy-and-hold benchmark ──
+    if let Some(failure) = benchmark
+        .as_ref()
+        .and_then(|b| b.gate_failure(promotion_config))
+    {
+        return RobustnessResult {
+            level_reached: PromotionLevel::Level1CheapPass,
+            walk_forward: None,
+            execution_mc: None,
+            bootstrap: None,
+            borderline: None,
+            gate_failure: Some(failure),
+            friction: None,
+            benchmark,
+            signal_value: None,
+            sensitivity: None,
+        };
+    }
+
+    // ── Gate 1 → 2: shuffle test against randomized entries ──
+    let signal_value = promotion_config
+        .shuffle_test
+        .as_ref()
+        .and_then(|shuffle_config| {
+            levels
+                .run(CachedLevel::ShuffleTest, shuffle_config, || {
+                    run_shuffle_test(
+                        result,
+                        strategy_config,
+                        aligned,
+                        symbol,
+                        trading_mode,
+                        initial_capital,
+                        position_size_pct,
+                        execution_preset,
+                        shuffle_config,
+                    )
+                })
+                .ok()
+        });
+    if let Some((percentile, required)) = signal_value.as_ref().and_then(|score| {
+        let required = promotion_config
+            .shuffle_test
+            .as_ref()?
+            .min_sharpe_percentile?;
+        (score.sharpe_percentile < required).then_some((score.sharpe_percentile, required))
+    }) {
+        return RobustnessResult {
+            level_reached: PromotionLevel::Level1CheapPass,
+            walk_forward: None,
+            execution_mc: None,
+            bootstrap: None,
+            borderline: None,
+            gate_failure: Some(GateFailure::NoSignalValue {
+                percentile,
+                required,
+            }),
+            friction: None,
+            benchmark,
+            signal_value,
+            sensitivity: None,
+        };
+    }
+
+    // ── Gate 1 → 2: parameter cliffs ──
+    let sensitivity = promotion_config
+        .sensitivity
+        .as_ref()
+        .and_then(|sensitivity_config| {
+            levels
+                .run(CachedLevel::Sensitivity, sensitivity_config, || {
+                    run_parameter_sensitivity(
+                        result,
+                        strategy_config,
+                        aligned,
+                        symbol,
+                        trading_mode,
+                        initial_capital,
+                        position_size_pct,
+                        execution_preset,
+                        dataset_hash,
+                        sensitivity_config,
+                    )
+                })
+                .ok()
+        });
+    if let Some((cliff, max_change)) = sensitivity.as_ref().and_then(|s| {
+        let config = promotion_config.sensitivity.as_ref()?;
+        let cliff = s.cliff.as_ref().filter(|_| config.reject_cliffs)?;
+        Some((cliff.clone(), config.max_fitness_change))
+    }) {
+        return RobustnessResult {
+            level_reached: PromotionLevel::Level1CheapPass,
+            walk_forward: None,
+            execution_mc: None,
+            bootstrap: None,
+            borderline: None,
+            gate_failure: Some(GateFailure::ParameterCliff { cliff, max_change }),
+            friction: None,
+            benchmark,
+            signal_value,
+            sensitivity,
         };
     }
 
     // ── Level 2: Walk-Forward ──
-    let wf_result = match run_walk_forward(
-        strategy_config,
-        aligned,
-        symbol,
-        &promotion_config.wf_config,
-        trading_mode,
-        initial_capital,
-        position_size_pct,
-        execution_preset,
-        dataset_hash,
-    ) {
+    let wf_config = &promotion_config.wf_config;
+    let wf_result = match levels.run(CachedLevel::WalkForward, wf_config, || {
+        run_walk_forward(
+            strategy_config,
+            aligned,
+            symbol,
+            wf_config,
+            trading_mode,
+            initial_capital,
+            position_size_pct,
+            execution_preset,
+            dataset_hash,
+        )
+    }) {
         Ok(wf) => wf,
         Err(e) => {
             return RobustnessResult {
@@ -170,9 +736,14 @@ pub fn promote(
                 walk_forward: None,
                 execution_mc: None,
                 bootstrap: None,
+                borderline: None,
                 gate_failure: Some(GateFailure::WalkForwardError {
                     reason: e.to_string(),
                 }),
+                friction: None,
+                benchmark,
+                signal_value,
+                sensitivity,
             };
         }
     };
@@ -191,32 +762,82 @@ pub fn promote(
             walk_forward: Some(wf_result),
             execution_mc: None,
             bootstrap: None,
+            borderline: None,
             gate_failure: Some(GateFailure::WalkForwardFailed { reason }),
+            friction: None,
+            benchmark,
+            signal_value,
+            sensitivity,
+        };
+    }
+
+    // ── Gate 2 → 3: borderline fills ──
+    let tm_config = &promotion_config.trigger_margin_config;
+    let borderline = aligned.bars.get(symbol).and_then(|bars| {
+        levels
+            .run(CachedLevel::TriggerMargin, tm_config, || {
+                let closes: Vec<f64> = bars.iter().map(|b| b.close).collect();
+                run_trigger_margin_mc(result, &closes, tm_config)
+            })
+            .ok()
+    });
+    if let Some(fraction) = borderline
+        .as_ref()
+        .and_then(|b| b.borderline_profit_fraction)
+        .filter(|&f| f > promotion_config.max_borderline_profit_fraction)
+    {
+        return RobustnessResult {
+            level_reached: PromotionLevel::Level2WalkForward,
+            walk_forward: Some(wf_result),
+            execution_mc: None,
+            bootstrap: None,
+            borderline,
+            gate_failure: Some(GateFailure::BorderlineFills {
+                profit_fraction: fraction,
+                threshold: promotion_config.max_borderline_profit_fraction,
+            }),
+            friction: None,
+            benchmark,
+            signal_value,
+            sensitivity,
         };
     }
 
     // ── Level 3: Execution MC + Bootstrap ──
-    let mc_result = run_execution_mc(
-        strategy_config,
-        aligned,
-        symbol,
-        &promotion_config.mc_config,
-        trading_mode,
-        initial_capital,
-        position_size_pct,
-        dataset_hash,
-    )
-    .ok();
+    let mc_config = &promotion_config.mc_config;
+    let mc_result = levels
+        .run(CachedLevel::ExecutionMc, mc_config, || {
+            run_execution_mc(
+                strategy_config,
+                aligned,
+                symbol,
+                mc_config,
+                trading_mode,
+                initial_capital,
+                position_size_pct,
+                dataset_hash,
+            )
+        })
+        .ok();
 
-    let bootstrap_result =
-        stationary_block_bootstrap(&result.equity_curve, &promotion_config.bootstrap_config).ok();
+    let bootstrap_config = &promotion_config.bootstrap_config;
+    let bootstrap_result = levels
+        .run(CachedLevel::Bootstrap, bootstrap_config, || {
+            stationary_block_bootstrap(&result.equity_curve, bootstrap_config)
+        })
+        .ok();
 
     RobustnessResult {
         level_reached: PromotionLevel::Level3ExecutionMc,
         walk_forward: Some(wf_result),
         execution_mc: mc_result,
         bootstrap: bootstrap_result,
+        borderline,
         gate_failure: None,
+        friction: None,
+        benchmark,
+        signal_value,
+        sensitivity,
     }
 }
 
@@ -238,12 +859,31 @@ fn passes_wf_gate(wf: &WalkForwardResult, config: &PromotionConfig) -> bool {
             wf.mean_oos_sharpe > 0.0
         }
         DegradationFlag::NegativeIsSharpe | DegradationFlag::FailedOos => false,
-        DegradationFlag::InsufficientData => false,
+        DegradationFlag::InsufficientData | DegradationFlag::ParameterInstability => false,
     }
 }
 
-/// Human-readable reason why walk-forward gate failed.
+/// Human-readable reason why walk-forward gate failed, naming the split
+/// with the worst OOS Sharpe when the reason is out-of-sample weakness.
 fn wf_gate_failure_reason(wf: &WalkForwardResult, config: &PromotionConfig) -> String {
+    let reason = wf_gate_failure_cause(wf, config);
+    let oos_weakness = matches!(
+        wf.degradation_flag,
+        DegradationFlag::Normal | DegradationFlag::LowIsSharpe | DegradationFlag::FailedOos
+    );
+    match wf
+        .worst_fold()
+        .filter(|f| oos_weakness && !f.label.is_empty())
+    {
+        Some(worst) => format!(
+            "{reason}; worst split {} (OOS Sharpe {:.3})",
+            worst.label, worst.oos_sharpe
+        ),
+        None => reason,
+    }
+}
+
+fn wf_gate_failure_cause(wf: &WalkForwardResult, config: &PromotionConfig) -> String {
     match wf.degradation_flag {
         DegradationFlag::Normal => {
             if let Some(ratio) = wf.degradation_ratio {
@@ -268,12 +908,21 @@ fn wf_gate_failure_reason(wf: &WalkForwardResult, config: &PromotionConfig) -> S
         DegradationFlag::NegativeIsSharpe => "negative IS Sharpe".into(),
         DegradationFlag::FailedOos => "positive IS but negative OOS".into(),
         DegradationFlag::InsufficientData => "insufficient data for walk-forward".into(),
+        DegradationFlag::ParameterInstability => "optimal parameters unstable across folds".into(),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
+
+    use chrono::NaiveDate;
+    use trendlab_core::components::composition::StrategyPreset;
+    use trendlab_core::data::provider::RawBar;
+
+    use crate::runner::run_backtest_from_data;
+    use crate::walk_forward::FoldResult;
 
     // ─── PromotionLevel ordering ──────────────────────────────────
 
@@ -293,9 +942,338 @@ mod tests {
         assert!((config.fdr_alpha - 0.05).abs() < 1e-10);
         assert_eq!(config.wf_config.n_folds, 5);
         assert_eq!(config.mc_config.n_samples, 20);
+        assert_eq!(config.trigger_margin_config.n_trials, 200);
+        assert!((config.max_borderline_profit_fraction - 0.5).abs() < 1e-10);
         assert_eq!(config.bootstrap_config.n_resamples, 1000);
     }
 
+    #[test]
+    fn partial_toml_keeps_defaults() {
+        let config = PromotionConfig::from_toml(
+            r#"
+wf_sharpe_threshold = 0.5
+
+[wf_config]
+n_folds = 3
+
+[mc_config]
+n_samples = 8
+"#,
+        )
+        .unwrap();
+        assert!((config.wf_sharpe_threshold - 0.5).abs() < 1e-10);
+        assert!((config.wf_degradation_threshold - 0.3).abs() < 1e-10);
+        assert_eq!(config.wf_config.n_folds, 3);
+        assert_eq!(config.wf_config.min_total_bars, 756);
+        assert_eq!(config.mc_config.n_samples, 8);
+        assert_eq!(config.mc_config.path_policies.len(), 3);
+        assert_eq!(config.bootstrap_config.n_resamples, 1000);
+    }
+
+    #[test]
+    fn fraction_thresholds_convert_percent_and_bps_at_load() {
+        let config = PromotionConfig::from_toml(
+            r#"
+wf_degradation_threshold = "40%"
+max_borderline_profit_fraction = 0.25
+fdr_alpha = "1%"
+
+[trigger_margin_config]
+epsilon = "15bps"
+
+[sensitivity]
+max_fitness_change = "50%"
+reject_cliffs = true
+"#,
+        )
+        .unwrap();
+        let sensitivity = config.sensitivity.as_ref().unwrap();
+        assert!((sensitivity.max_fitness_change - 0.5).abs() < 1e-12);
+        assert!((sensitivity.cliff_perturbation - 0.05).abs() < 1e-12);
+        assert!(sensitivity.reject_cliffs);
+        assert!((config.wf_degradation_threshold - 0.4).abs() < 1e-12);
+        assert!((config.max_borderline_profit_fraction - 0.25).abs() < 1e-12);
+        assert!((config.fdr_alpha - 0.01).abs() < 1e-12);
+        assert!((config.trigger_margin_config.epsilon - 0.0015).abs() < 1e-12);
+
+        let err = PromotionConfig::from_toml(r#"fdr_alpha = "$500""#).unwrap_err();
+        assert!(err.to_string().contains("expected a fraction"), "{err}");
+    }
+
+    #[test]
+    fn gate_failure_display_names_the_criterion() {
+        let failure = GateFailure::InsufficientSharpe {
+            sharpe: 0.12,
+            threshold: 0.3,
+        };
+        assert_eq!(failure.to_string(), "Sharpe 0.12 below 0.30");
+        let failure = GateFailure::WalkForwardFailed {
+            reason: "negative IS Sharpe".into(),
+        };
+        assert_eq!(
+            failure.to_string(),
+            "walk-forward failed: negative IS Sharpe"
+        );
+        let failure = GateFailure::BorderlineFills {
+            profit_fraction: 0.72,
+            threshold: 0.5,
+        };
+        assert_eq!(
+            failure.to_string(),
+            "72% of profit from borderline fills (max 50%)"
+        );
+        assert_eq!(GateFailure::ZeroTrades.to_string(), "no trades");
+        let failure = GateFailure::BelowBenchmark {
+            metric: BenchmarkMetric::Sharpe,
+            margin: -0.3,
+            required: 0.1,
+        };
+        assert_eq!(
+            failure.to_string(),
+            "Sharpe -0.30 vs buy-and-hold (need +0.10)"
+        );
+        let failure = GateFailure::BelowBenchmark {
+            metric: BenchmarkMetric::Cagr,
+            margin: -0.042,
+            required: 0.0,
+        };
+        assert_eq!(
+            failure.to_string(),
+            "CAGR -4.2% vs buy-and-hold (need +0.0%)"
+        );
+        let failure = GateFailure::BenchmarkDrawdown {
+            max_drawdown: -0.25,
+            benchmark: -0.2,
+        };
+        assert_eq!(
+            failure.to_string(),
+            "max drawdown 25.0% not below buy-and-hold's 20.0%"
+        );
+        let failure = GateFailure::ParameterCliff {
+            cliff: ParameterCliff {
+                component: crate::param_sensitivity::ProbedComponent::PositionManager,
+                param: "multiplier".into(),
+                metric: crate::fitness::FitnessMetric::Sharpe,
+                perturbation: -0.0167,
+                fitness_change: -0.52,
+            },
+            max_change: 0.3,
+        };
+        assert_eq!(
+            failure.to_string(),
+            "PM multiplier -1.7% moves sharpe -52% (max 30%)"
+        );
+    }
+
+    #[test]
+    fn benchmark_gate_reads_from_toml() {
+        let config = PromotionConfig::default();
+        assert_eq!(config.benchmark_metric, None);
+        assert!(!config.benchmark_lower_drawdown);
+
+        let config = PromotionConfig::from_toml(
+            r#"
+benchmark_metric = "total_return"
+benchmark_margin = 0.05
+benchmark_lower_drawdown = true
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.benchmark_metric, Some(BenchmarkMetric::TotalReturn));
+        assert!((config.benchmark_margin - 0.05).abs() < 1e-12);
+        assert!(config.benchmark_lower_drawdown);
+    }
+
+    // ─── Benchmark gate on synthetic series ───────────────────────
+
+    /// Daily bars for "SYN" opening at the previous close.
+    fn synthetic(n: usize, close: impl Fn(f64) -> f64) -> AlignedData {
+        let start = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        let dates: Vec<NaiveDate> = (0..n)
+            .map(|i| start + chrono::Duration::days(i as i64))
+            .collect();
+        let bars = dates
+            .iter()
+            .enumerate()
+            .map(|(i, date)| {
+                let c = close(i as f64);
+                let prev = if i == 0 { c } else { close(i as f64 - 1.0) };
+                RawBar {
+                    date: *date,
+                    open: prev,
+                    high: c.max(prev) * 1.002,
+                    low: c.min(prev) * 0.998,
+                    close: c,
+                    volume: 1000,
+                    adj_close: c,
+                }
+            })
+            .collect();
+        AlignedData {
+            dates,
+            bars: HashMap::from([("SYN".to_string(), bars)]),
+            symbols: vec!["SYN".to_string()],
+        }
+    }
+
+    /// Promote a Supertrend system on `aligned` with the Sharpe benchmark
+    /// gate on. Walk-forward is set up to stop at once for lack of data.
+    fn promote_against_benchmark(aligned: &AlignedData) -> (BacktestResult, RobustnessResult) {
+        let strategy = StrategyPreset::SupertrendSystem.to_config();
+        let result = run_backtest_from_data(
+            &strategy,
+            aligned,
+            "SYN",
+            TradingMode::LongOnly,
+            100_000.0,
+            1.0,
+            ExecutionPreset::Frictionless,
+            "synthetic",
+            true,
+        )
+        .unwrap();
+        let config = PromotionConfig {
+            benchmark_metric: Some(BenchmarkMetric::Sharpe),
+            benchmark_lower_drawdown: true,
+            wf_config: WalkForwardConfig {
+                min_total_bars: 100_000,
+                ..WalkForwardConfig::default()
+            },
+            ..PromotionConfig::default()
+        };
+        let robustness = promote(
+            &result,
+            &strategy,
+            aligned,
+            "SYN",
+            TradingMode::LongOnly,
+            100_000.0,
+            1.0,
+            ExecutionPreset::Frictionless,
+            "synthetic",
+            &config,
+            &mut FdrFamily::new(),
+        );
+        (result, robustness)
+    }
+
+    #[test]
+    fn steady_trend_fails_against_buy_and_hold() {
+        // 0.15%/day with shallow 40-bar pullbacks: every exit and re-entry
+        // gives up trend that buy-and-hold keeps
+        let trend = synthetic(800, |i| {
+            100.0 * 1.0015f64.powf(i) * (1.0 + 0.03 * (i * std::f64::consts::TAU / 40.0).sin())
+        });
+        let (result, robustness) = promote_against_benchmark(&trend);
+        assert!(result.metrics.sharpe > PromotionConfig::default().wf_sharpe_threshold);
+
+        assert_eq!(robustness.level_reached, PromotionLevel::Level1CheapPass);
+        assert!(
+            matches!(
+                robustness.gate_failure,
+                Some(GateFailure::BelowBenchmark { margin, .. }) if margin < 0.0
+            ),
+            "{:?}",
+            robustness.gate_failure
+        );
+        assert!(robustness.walk_forward.is_none());
+        let benchmark = robustness.benchmark.unwrap();
+        assert!(benchmark.buy_and_hold.sharpe > result.metrics.sharpe);
+        assert!((benchmark.strategy_value() - result.metrics.sharpe).abs() < 1e-12);
+    }
+
+    #[test]
+    fn mean_reverting_series_clears_buy_and_hold() {
+        // 100-bar waves drifting down: buy-and-hold loses, the trend
+        // follower rides the up-legs
+        let wave = synthetic(800, |i| {
+            (100.0 + 20.0 * (i * std::f64::consts::TAU / 100.0).sin() - 0.02 * i)
+                * (1.0 + 0.003 * (i * 1.3).sin())
+        });
+        let (result, robustness) = promote_against_benchmark(&wave);
+
+        let benchmark = robustness.benchmark.as_ref().unwrap();
+        assert!(benchmark.buy_and_hold.total_return < 0.0);
+        assert!(benchmark.margin > 0.0);
+        assert!(benchmark.drawdown_margin > 0.0);
+        assert!((benchmark.strategy_value() - result.metrics.sharpe).abs() < 1e-12);
+        // Past the benchmark: stopped by walk-forward, not by buy-and-hold
+        assert!(
+            matches!(
+                robustness.gate_failure,
+                Some(GateFailure::WalkForwardError { .. } | GateFailure::WalkForwardFailed { .. })
+            ),
+            "{:?}",
+            robustness.gate_failure
+        );
+    }
+
+    #[test]
+    fn shuffle_gate_stops_before_walk_forward() {
+        let wave = synthetic(800, |i| {
+            (100.0 + 20.0 * (i * std::f64::consts::TAU / 100.0).sin() - 0.02 * i)
+                * (1.0 + 0.003 * (i * 1.3).sin())
+        });
+        let strategy = StrategyPreset::SupertrendSystem.to_config();
+        let result = run_backtest_from_data(
+            &strategy,
+            &wave,
+            "SYN",
+            TradingMode::LongOnly,
+            100_000.0,
+            1.0,
+            ExecutionPreset::Frictionless,
+            "synthetic",
+            true,
+        )
+        .unwrap();
+        // No run can rank above every variant by more than 100 percentiles
+        let config = PromotionConfig {
+            shuffle_test: Some(ShuffleTestConfig {
+                n_variants: 20,
+                min_sharpe_percentile: Some(100.5),
+                ..ShuffleTestConfig::default()
+            }),
+            ..PromotionConfig::default()
+        };
+        let robustness = promote(
+            &result,
+            &strategy,
+            &wave,
+            "SYN",
+            TradingMode::LongOnly,
+            100_000.0,
+            1.0,
+            ExecutionPreset::Frictionless,
+            "synthetic",
+            &config,
+            &mut FdrFamily::new(),
+        );
+
+        let score = robustness.signal_value.as_ref().unwrap();
+        assert_eq!((score.variants, score.trades), (20, result.trades.len()));
+        assert_eq!(robustness.level_reached, PromotionLevel::Level1CheapPass);
+        assert!(robustness.walk_forward.is_none());
+        match robustness.gate_failure {
+            Some(GateFailure::NoSignalValue {
+                percentile,
+                required,
+            }) => {
+                assert_eq!(percentile, score.sharpe_percentile);
+                assert_eq!(required, 100.5);
+            }
+            other => panic!("expected NoSignalValue, got {other:?}"),
+        }
+        let failure = GateFailure::NoSignalValue {
+            percentile: 62.0,
+            required: 90.0,
+        };
+        assert_eq!(
+            failure.to_string(),
+            "Sharpe beats 62% of shuffled entries (need 90%)"
+        );
+    }
+
     // ─── WF gate logic ───────────────────────────────────────────
 
     #[test]
@@ -354,6 +1332,13 @@ mod tests {
         assert!(!passes_wf_gate(&wf, &config));
     }
 
+    #[test]
+    fn wf_gate_parameter_instability_blocked() {
+        let config = PromotionConfig::default();
+        let wf = make_wf_result(DegradationFlag::ParameterInstability, Some(0.9), 0.5);
+        assert!(!passes_wf_gate(&wf, &config));
+    }
+
     // ─── Gate failure reasons ─────────────────────────────────────
 
     #[test]
@@ -372,6 +1357,27 @@ mod tests {
         assert!(reason.contains("negative IS"));
     }
 
+    #[test]
+    fn failure_reason_names_the_worst_split() {
+        let config = PromotionConfig::default();
+        let fold = |label: &str, oos_sharpe| FoldResult {
+            fold_index: 0,
+            label: label.into(),
+            is_sharpe: 1.0,
+            oos_sharpe,
+            is_trades: 10,
+            oos_trades: 4,
+            oos_zero_trades: false,
+        };
+        let mut wf = make_wf_result(DegradationFlag::FailedOos, Some(0.0), -0.2);
+        wf.fold_results = vec![fold("test: 2020", 0.4), fold("test: 2021", -0.8)];
+        let reason = wf_gate_failure_reason(&wf, &config);
+        assert_eq!(
+            reason,
+            "positive IS but negative OOS; worst split test: 2021 (OOS Sharpe -0.800)"
+        );
+    }
+
     fn make_wf_result(
         flag: DegradationFlag,
         ratio: Option<f64>,
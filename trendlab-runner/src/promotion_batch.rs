@@ -0,0 +1,420 @@
+//! Batch promotion — run the promotion ladder over saved candidates.
+//!
+//! Candidates come from saved run manifests or a YOLO history file. The top
+//! K by Sharpe are re-run from cached data (each symbol/date range loaded
+//! once, however many candidates share it), pushed through the ladder in
+//! parallel, and collected into a [`PromotionReport`] that records each
+//! candidate's level and, for rejects, the criterion that stopped it.
+//!
+//! With a [`RobustnessCache`], ladder levels already computed for the same
+//! config, data and level settings are reused, and each entry lists the
+//! levels that were.
+
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::NaiveDate;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use trendlab_core::data::align::AlignedData;
+use trendlab_core::data::cache::ParquetCache;
+use trendlab_core::data::provider::DataProvider;
+use trendlab_core::fingerprint::{StrategyConfig, TradingMode};
+
+use crate::data_loader::{load_bars, LoadOptions};
+use crate::export::ArtifactSummary;
+use crate::fdr::FdrFamily;
+use crate::history::HistoryEntry;
+use crate::promotion::{
+    promote, promote_cached, PromotionConfig, PromotionLevel, RobustnessResult,
+};
+use crate::robustness_cache::{CachedLevel, RobustnessCache};
+use crate::runner::{decode_execution_preset, run_backtest_from_data};
+
+/// A saved strategy run to re-promote.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromotionCandidate {
+    /// Where the candidate came from: artifact directory or history run ID.
+    pub source: String,
+    pub config: StrategyConfig,
+    pub symbol: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// Recorded trading mode; `None` falls back to [`BatchSettings::trading_mode`].
+    pub trading_mode: Option<TradingMode>,
+    pub initial_capital: f64,
+    /// Sharpe recorded with the saved run, used for top-K selection.
+    pub sharpe: f64,
+}
+
+impl PromotionCandidate {
+    /// Candidate from a saved manifest. None if its dates don't parse.
+    pub fn from_artifact(summary: &ArtifactSummary) -> Option<Self> {
+        let parse = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok();
+        Some(Self {
+            source: summary.dir.display().to_string(),
+            config: summary.config.clone(),
+            symbol: summary.symbol.clone(),
+            start: parse(&summary.start_date)?,
+            end: parse(&summary.end_date)?,
+            trading_mode: None,
+            initial_capital: summary.initial_capital,
+            sharpe: summary.metrics.sharpe,
+        })
+    }
+
+    /// Candidate from a YOLO history entry.
+    pub fn from_history(entry: &HistoryEntry) -> Self {
+        let fp = &entry.fingerprint;
+        Self {
+            source: format!("history:{}", &fp.run_id.as_hex()[..12]),
+            config: fp.strategy_config.clone(),
+            symbol: fp.symbol.clone(),
+            start: fp.start_date,
+            end: fp.end_date,
+            trading_mode: Some(fp.trading_mode),
+            initial_capital: fp.initial_capital,
+            sharpe: entry.metrics.sharpe,
+        }
+    }
+}
+
+/// The `k` best candidates by recorded Sharpe, one per distinct strategy,
+/// symbol, and date range.
+pub fn top_by_sharpe(mut candidates: Vec<PromotionCandidate>, k: usize) -> Vec<PromotionCandidate> {
+    candidates.sort_by(|a, b| {
+        let key = |c: &PromotionCandidate| {
+            if c.sharpe.is_nan() {
+                f64::NEG_INFINITY
+            } else {
+                c.sharpe
+            }
+        };
+        key(b).total_cmp(&key(a))
+    });
+    let mut seen = HashSet::new();
+    candidates
+        .into_iter()
+        .filter(|c| seen.insert((c.config.full_hash(), c.symbol.clone(), c.start, c.end)))
+        .take(k)
+        .collect()
+}
+
+/// Run settings shared by every candidate in a batch.
+#[derive(Debug, Clone)]
+pub struct BatchSettings {
+    pub promotion: PromotionConfig,
+    /// Trading mode for candidates that don't record one.
+    pub trading_mode: TradingMode,
+    pub position_size_pct: f64,
+    /// Worker threads; 0 uses the global Rayon pool, 1 runs sequentially.
+    pub jobs: usize,
+    /// Cache of ladder level results; None recomputes every level.
+    pub robustness_cache: Option<RobustnessCache>,
+}
+
+/// One candidate's outcome.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromotionReportEntry {
+    pub source: String,
+    pub symbol: String,
+    pub config: StrategyConfig,
+    /// Sharpe recorded with the saved run.
+    pub recorded_sharpe: f64,
+    /// Sharpe of the re-run (None if it could not run).
+    pub sharpe: Option<f64>,
+    /// Level reached (None if the candidate could not be re-run).
+    pub level: Option<PromotionLevel>,
+    /// Why the candidate stopped short of the top level.
+    pub failing_criterion: Option<String>,
+    /// Whether the walk-forward p-value survives Benjamini-Hochberg across
+    /// the batch (None without a walk-forward t-test).
+    pub fdr_significant: Option<bool>,
+    pub robustness: Option<RobustnessResult>,
+    /// Ladder levels reused from the robustness cache rather than computed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub from_cache: Vec<CachedLevel>,
+}
+
+impl PromotionReportEntry {
+    /// Reached the top of the ladder.
+    pub fn is_promoted(&self) -> bool {
+        self.level == Some(PromotionLevel::Level3ExecutionMc)
+    }
+}
+
+/// Outcome of a batch promotion, in candidate order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromotionReport {
+    pub promotion_config: PromotionConfig,
+    pub entries: Vec<PromotionReportEntry>,
+}
+
+impl PromotionReport {
+    /// Candidates that reached the top of the ladder.
+    pub fn promoted(&self) -> impl Iterator<Item = &PromotionReportEntry> {
+        self.entries.iter().filter(|e| e.is_promoted())
+    }
+}
+
+/// Data shared by all candidates on one symbol and date range.
+struct SharedData {
+    aligned: AlignedData,
+    dataset_hash: String,
+    has_synthetic: bool,
+}
+
+type DataKey = (String, NaiveDate, NaiveDate);
+
+/// Re-run and promote `candidates`.
+///
+/// Data is loaded once per symbol and date range through the usual cache
+/// path (`load_opts` supplies everything but the dates). Candidates whose
+/// data fails to load or whose backtest fails are reported without a level.
+pub fn run_promotion_batch(
+    candidates: &[PromotionCandidate],
+    cache: &ParquetCache,
+    provider: Option<&dyn DataProvider>,
+    load_opts: &LoadOptions,
+    settings: &BatchSettings,
+) -> PromotionReport {
+    let mut data: BTreeMap<DataKey, Result<SharedData, String>> = BTreeMap::new();
+    for c in candidates {
+        let key = (c.symbol.clone(), c.start, c.end);
+        if data.contains_key(&key) {
+            continue;
+        }
+        let opts = LoadOptions {
+            start: c.start,
+            end: c.end,
+            ..load_opts.clone()
+        };
+        let loaded = load_bars(&[c.symbol.as_str()], cache, provider, None, &opts)
+            .map(|l| SharedData {
+                aligned: l.aligned,
+                dataset_hash: l.dataset_hash,
+                has_synthetic: l.has_synthetic,
+            })
+            .map_err(|e| e.to_string());
+        data.insert(key, loaded);
+    }
+
+    let run = |c: &PromotionCandidate| promote_candidate(c, &data, settings);
+    let mut entries: Vec<PromotionReportEntry> = match settings.jobs {
+        0 => candidates.par_iter().map(run).collect(),
+        1 => candidates.iter().map(run).collect(),
+        jobs => rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build()
+            .expect("failed to build Rayon thread pool")
+            .install(|| candidates.par_iter().map(run).collect()),
+    };
+
+    // FDR across the whole batch
+    let mut family = FdrFamily::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let p_value = entry
+            .robustness
+            .as_ref()
+            .and_then(|r| r.walk_forward.as_ref())
+            .and_then(|wf| wf.t_test.as_ref())
+            .map(|t| t.p_value);
+        if let Some(p) = p_value {
+            family.add(i.to_string(), p);
+        }
+    }
+    for fdr in family.apply_correction(settings.promotion.fdr_alpha) {
+        if let Ok(i) = fdr.config_id.parse::<usize>() {
+            entries[i].fdr_significant = Some(fdr.significant);
+        }
+    }
+
+    PromotionReport {
+        promotion_config: settings.promotion.clone(),
+        entries,
+    }
+}
+
+fn promote_candidate(
+    c: &PromotionCandidate,
+    data: &BTreeMap<DataKey, Result<SharedData, String>>,
+    settings: &BatchSettings,
+) -> PromotionReportEntry {
+    let mut entry = PromotionReportEntry {
+        source: c.source.clone(),
+        symbol: c.symbol.clone(),
+        config: c.config.clone(),
+        recorded_sharpe: c.sharpe,
+        sharpe: None,
+        level: None,
+        failing_criterion: None,
+        fdr_significant: None,
+        robustness: None,
+        from_cache: Vec::new(),
+    };
+
+    let shared = match &data[&(c.symbol.clone(), c.start, c.end)] {
+        Ok(shared) => shared,
+        Err(e) => {
+            entry.failing_criterion = Some(format!("data: {e}"));
+            return entry;
+        }
+    };
+
+    let trading_mode = c.trading_mode.unwrap_or(settings.trading_mode);
+    let preset = decode_execution_preset(&c.config.execution_model.params);
+    let result = match run_backtest_from_data(
+        &c.config,
+        &shared.aligned,
+        &c.symbol,
+        trading_mode,
+        c.initial_capital,
+        settings.position_size_pct,
+        preset,
+        &shared.dataset_hash,
+        shared.has_synthetic,
+    ) {
+        Ok(result) => result,
+        Err(e) => {
+            entry.failing_criterion = Some(format!("backtest: {e}"));
+            return entry;
+        }
+    };
+
+    // Per-candidate family; the batch-wide correction runs afterwards
+    let mut fdr_family = FdrFamily::new();
+    let robustness = match &settings.robustness_cache {
+        Some(cache) => {
+            let (robustness, from_cache) = promote_cached(
+                &result,
+                &c.config,
+                &shared.aligned,
+                &c.symbol,
+                trading_mode,
+                c.initial_capital,
+                settings.position_size_pct,
+                preset,
+                &shared.dataset_hash,
+                &settings.promotion,
+                &mut fdr_family,
+                cache,
+            );
+            entry.from_cache = from_cache;
+            robustness
+        }
+        None => promote(
+            &result,
+            &c.config,
+            &shared.aligned,
+            &c.symbol,
+            trading_mode,
+            c.initial_capital,
+            settings.position_size_pct,
+            preset,
+            &shared.dataset_hash,
+            &settings.promotion,
+            &mut fdr_family,
+        ),
+    };
+    entry.sharpe = Some(result.metrics.sharpe);
+    entry.level = Some(robustness.level_reached);
+    entry.failing_criterion = robustness.gate_failure.as_ref().map(|g| g.to_string());
+    entry.robustness = Some(robustness);
+    entry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use trendlab_core::fingerprint::ComponentConfig;
+
+    fn make_config(lookback: f64) -> StrategyConfig {
+        let component = |component_type: &str, params: BTreeMap<String, f64>| ComponentConfig {
+            component_type: component_type.into(),
+            params,
+            children: Vec::new(),
+        };
+        StrategyConfig {
+            signal: component(
+                "donchian_breakout",
+                BTreeMap::from([("entry_lookback".to_string(), lookback)]),
+            ),
+            position_manager: component("atr_trailing", BTreeMap::new()),
+            execution_model: component("next_bar_open", BTreeMap::new()),
+            signal_filter: component("no_filter", BTreeMap::new()),
+        }
+    }
+
+    fn candidate(source: &str, symbol: &str, lookback: f64, sharpe: f64) -> PromotionCandidate {
+        PromotionCandidate {
+            source: source.into(),
+            config: make_config(lookback),
+            symbol: symbol.into(),
+            start: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+            end: NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+            trading_mode: None,
+            initial_capital: 100_000.0,
+            sharpe,
+        }
+    }
+
+    #[test]
+    fn top_by_sharpe_orders_and_dedups() {
+        let top = top_by_sharpe(
+            vec![
+                candidate("a", "SPY", 20.0, 0.8),
+                candidate("b", "SPY", 50.0, f64::NAN),
+                candidate("c", "SPY", 20.0, 1.1),
+                candidate("d", "QQQ", 20.0, 0.9),
+                candidate("e", "SPY", 30.0, 0.2),
+            ],
+            3,
+        );
+        let sources: Vec<&str> = top.iter().map(|c| c.source.as_str()).collect();
+        // "a" repeats "c" (same strategy, symbol, and range)
+        assert_eq!(sources, ["c", "d", "e"]);
+    }
+
+    #[test]
+    fn load_failure_is_reported_as_failing_criterion() {
+        let cache_dir =
+            std::env::temp_dir().join(format!("trendlab_promotion_batch_{}", std::process::id()));
+        let cache = ParquetCache::new(&cache_dir);
+        let opts = LoadOptions {
+            start: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+            end: NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+            offline: true,
+            synthetic: false,
+            force: false,
+            strict: false,
+            stale_after_days: None,
+            interval: Default::default(),
+        };
+        let settings = BatchSettings {
+            promotion: PromotionConfig::default(),
+            trading_mode: TradingMode::LongOnly,
+            position_size_pct: 1.0,
+            jobs: 1,
+            robustness_cache: None,
+        };
+
+        let report = run_promotion_batch(
+            &[candidate("a", "SPY", 20.0, 1.0)],
+            &cache,
+            None,
+            &opts,
+            &settings,
+        );
+        let entry = &report.entries[0];
+        assert_eq!(entry.level, None);
+        assert!(entry
+            .failing_criterion
+            .as_deref()
+            .unwrap()
+            .starts_with("data: "));
+        assert_eq!(report.promoted().count(), 0);
+
+        let _ = std::fs::remove_dir_all(&cache_dir);
+    }
+}
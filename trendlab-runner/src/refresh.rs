@@ -0,0 +1,307 @@
+//! Leaderboard refresh — re-test aging champions against fresh data.
+//!
+//! Leaderboard fitness is measured on whatever data the session loaded. When
+//! the cache later extends past an entry's `end_date`, [`refresh_leaderboard`]
+//! re-runs the entry's config on the extended range, replaces its result and
+//! fitness, and keeps the before/after numbers as a [`RetestRecord`]. Entries
+//! whose fitness drops by more than [`RefreshConfig::max_degradation`] are
+//! flagged as demotion candidates but stay on the board.
+//!
+//! Each re-test also produces a [`RefreshEntry`] for the JSONL history (see
+//! [`crate::history::YoloHistory::append_refresh`]).
+
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use trendlab_core::domain::FullHash;
+use trendlab_core::fingerprint::TradingMode;
+
+use crate::data_loader::LoadedData;
+use crate::leaderboard::SymbolLeaderboard;
+use crate::metrics::finite_f64;
+use crate::runner::{decode_execution_preset, run_backtest_from_data};
+use crate::yolo::{YoloConfig, YoloResult};
+
+/// Settings for re-running leaderboard entries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshConfig {
+    pub trading_mode: TradingMode,
+    pub position_size_pct: f64,
+    /// Fraction of the previous fitness an entry may lose before it is
+    /// flagged as degraded (0.3 = a Sharpe of 1.0 may fall to 0.7).
+    pub max_degradation: f64,
+}
+
+impl Default for RefreshConfig {
+    fn default() -> Self {
+        Self {
+            trading_mode: TradingMode::LongOnly,
+            position_size_pct: 1.0,
+            max_degradation: 0.3,
+        }
+    }
+}
+
+impl RefreshConfig {
+    /// Re-run with the same trading mode and sizing as the session.
+    pub fn from_yolo(config: &YoloConfig) -> Self {
+        Self {
+            trading_mode: config.trading_mode,
+            position_size_pct: config.position_size_pct,
+            ..Self::default()
+        }
+    }
+
+    /// Whether moving from `before` to `after` counts as degradation.
+    ///
+    /// A missing (non-finite) `after` always does.
+    pub fn is_degraded(&self, before: f64, after: f64) -> bool {
+        if !after.is_finite() {
+            return true;
+        }
+        after < before - self.max_degradation * before.abs()
+    }
+}
+
+/// Outcome of the latest re-test of a leaderboard entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetestRecord {
+    pub refreshed_at: NaiveDateTime,
+    /// Last bar date and dataset hash the previous fitness was measured on.
+    pub previous_end_date: String,
+    pub previous_dataset_hash: String,
+    /// Last bar date of the extended range.
+    pub end_date: String,
+    #[serde(with = "finite_f64")]
+    pub fitness_before: f64,
+    #[serde(with = "finite_f64")]
+    pub fitness_after: f64,
+    /// Fitness fell by more than the refresh threshold: a demotion candidate.
+    pub degraded: bool,
+}
+
+impl RetestRecord {
+    pub fn delta(&self) -> f64 {
+        self.fitness_after - self.fitness_before
+    }
+}
+
+/// Whether a leaderboard entry's fitness has held up on newer data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetestStatus {
+    /// Fitness is still from the range the entry was discovered on.
+    NeverRetested,
+    /// Re-tested on a longer range and within the degradation threshold.
+    Survived,
+    /// Re-tested and degraded beyond the threshold.
+    Degraded,
+}
+
+/// History record of one re-test, written as a `{"refresh": ...}` line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefreshEntry {
+    pub session_id: String,
+    pub symbol: String,
+    pub full_hash: FullHash,
+    pub record: RetestRecord,
+}
+
+/// What a refresh did.
+#[derive(Debug, Clone, Default)]
+pub struct RefreshReport {
+    /// One entry per re-tested config, in leaderboard order before the refresh.
+    pub refreshed: Vec<RefreshEntry>,
+    /// Entries whose data already reached the available end date.
+    pub up_to_date: usize,
+    /// Configs whose re-run failed; their previous result is kept.
+    pub failed: Vec<(String, FullHash, String)>,
+}
+
+impl RefreshReport {
+    pub fn degraded(&self) -> impl Iterator<Item = &RefreshEntry> {
+        self.refreshed.iter().filter(|r| r.record.degraded)
+    }
+
+    fn merge(&mut self, other: RefreshReport) {
+        self.refreshed.extend(other.refreshed);
+        self.up_to_date += other.up_to_date;
+        self.failed.extend(other.failed);
+    }
+}
+
+/// Last date with a real (non-void) bar for `symbol` in `data`.
+pub fn available_end(data: &LoadedData, symbol: &str) -> Option<NaiveDate> {
+    let bars = data.aligned.bars.get(symbol)?;
+    data.aligned
+        .dates
+        .iter()
+        .zip(bars)
+        .rev()
+        .find(|(_, bar)| !bar.close.is_nan())
+        .map(|(date, _)| *date)
+}
+
+/// Re-test every entry of `lb` whose result ends before the data in `data`.
+///
+/// Re-tested entries get the new result and fitness, a [`RetestRecord`], and
+/// lose their artifact directory (the saved artifacts are for the old range).
+/// The leaderboard is re-ranked afterwards.
+pub fn refresh_leaderboard(
+    lb: &mut SymbolLeaderboard,
+    data: &LoadedData,
+    config: &RefreshConfig,
+    now: NaiveDateTime,
+) -> RefreshReport {
+    let mut report = RefreshReport::default();
+    let symbol = lb.symbol().to_string();
+    let Some(end) = available_end(data, &symbol) else {
+        return report;
+    };
+    let metric = lb.fitness_metric();
+
+    for entry in lb.entries_mut() {
+        let stale = NaiveDate::parse_from_str(&entry.result.end_date, "%Y-%m-%d")
+            .map_or(true, |previous| previous < end);
+        if !stale {
+            report.up_to_date += 1;
+            continue;
+        }
+
+        let strategy_config = &entry.result.config;
+        let full_hash = strategy_config.full_hash();
+        let rerun = run_backtest_from_data(
+            strategy_config,
+            &data.aligned,
+            &symbol,
+            config.trading_mode,
+            entry.result.initial_capital,
+            config.position_size_pct,
+            decode_execution_preset(&strategy_config.execution_model.params),
+            &data.dataset_hash,
+            data.has_synthetic,
+        );
+        let mut result = match rerun {
+            Ok(result) => result,
+            Err(err) => {
+                report
+                    .failed
+                    .push((symbol.clone(), full_hash, err.to_string()));
+                continue;
+            }
+        };
+        result
+            .data_quality_warnings
+            .extend(data.warnings_for(&symbol).cloned());
+        result.truncated_range = data.truncated_range(&symbol).cloned();
+
+        let fitness_after = metric.extract(&result.metrics);
+        let record = RetestRecord {
+            refreshed_at: now,
+            previous_end_date: entry.result.end_date.clone(),
+            previous_dataset_hash: entry.result.dataset_hash.clone(),
+            end_date: result.end_date.clone(),
+            fitness_before: entry.fitness_score,
+            fitness_after,
+            degraded: config.is_degraded(entry.fitness_score, fitness_after),
+        };
+        report.refreshed.push(RefreshEntry {
+            session_id: entry.session_id.clone(),
+            symbol: symbol.clone(),
+            full_hash,
+            record: record.clone(),
+        });
+
+        entry.result = result;
+        entry.fitness_score = fitness_after;
+        entry.artifact_dir = None;
+        entry.retest = Some(record);
+    }
+
+    lb.rerank();
+    report
+}
+
+impl YoloResult {
+    /// Re-test the leaderboard entries of every symbol in `data`
+    /// (see [`refresh_leaderboard`]) and carry the new results into the
+    /// cross-symbol leaderboard.
+    pub fn refresh(
+        &mut self,
+        data: &LoadedData,
+        config: &RefreshConfig,
+        now: NaiveDateTime,
+    ) -> RefreshReport {
+        let mut report = RefreshReport::default();
+        let mut symbols: Vec<&String> = self.leaderboards.keys().collect();
+        symbols.sort();
+        let symbols: Vec<String> = symbols.into_iter().cloned().collect();
+        for symbol in symbols {
+            if !data.aligned.bars.contains_key(&symbol) {
+                continue;
+            }
+            let lb = self.leaderboards.get_mut(&symbol).unwrap();
+            let symbol_report = refresh_leaderboard(lb, data, config, now);
+            for refreshed in &symbol_report.refreshed {
+                let Some(entry) = lb
+                    .entries()
+                    .iter()
+                    .find(|e| e.result.config.full_hash() == refreshed.full_hash)
+                else {
+                    continue;
+                };
+                self.cross_leaderboard.set_retest(
+                    &refreshed.full_hash,
+                    &symbol,
+                    entry.result.metrics.clone(),
+                    &entry.result.equity_curve,
+                    refreshed.record.clone(),
+                );
+            }
+            report.merge(symbol_report);
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(before: f64, after: f64, degraded: bool) -> RetestRecord {
+        RetestRecord {
+            refreshed_at: NaiveDateTime::parse_from_str("2025-01-01 00:00:00", "%Y-%m-%d %H:%M:%S")
+                .unwrap(),
+            previous_end_date: "2023-12-29".into(),
+            previous_dataset_hash: "old".into(),
+            end_date: "2024-12-31".into(),
+            fitness_before: before,
+            fitness_after: after,
+            degraded,
+        }
+    }
+
+    #[test]
+    fn degradation_is_relative_to_previous_fitness() {
+        let config = RefreshConfig::default();
+        assert!(!config.is_degraded(1.0, 0.75));
+        assert!(config.is_degraded(1.0, 0.65));
+        // Negative fitness: the allowance scales with its magnitude
+        assert!(!config.is_degraded(-1.0, -1.25));
+        assert!(config.is_degraded(-1.0, -1.4));
+        assert!(config.is_degraded(1.0, f64::NAN));
+        assert!(!config.is_degraded(0.5, 2.0));
+    }
+
+    #[test]
+    fn retest_record_roundtrip_and_delta() {
+        let r = record(1.2, 0.9, false);
+        assert!((r.delta() + 0.3).abs() < 1e-12);
+        let json = serde_json::to_string(&r).unwrap();
+        let back: RetestRecord = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, r);
+
+        let missing = record(1.2, f64::NAN, true);
+        let json = serde_json::to_string(&missing).unwrap();
+        assert!(json.contains(r#""fitness_after":null"#));
+    }
+}
@@ -0,0 +1,534 @@
+//! Results retention — prune saved run directories.
+//!
+//! Every single run saves an artifact directory directly under `results/`
+//! (see [`crate::export::save_artifacts`]), and the TUI scans all of them at
+//! start. [`plan_prune`] keeps each such directory under the first
+//! [`RetentionRule`] that applies, or marks it for deletion:
+//!
+//! 1. [`RetentionRule::Referenced`]: its config and symbol appear in a saved
+//!    session (`session.json`), a leaderboard file (`symbol=*.json`) or a
+//!    promotion report (`promotion_report*.json`) anywhere under the results
+//!    directory. Only [`RetentionPolicy::force`] lifts this.
+//! 2. [`RetentionRule::TopK`]: among the best `keep_top` runs of its symbol
+//!    by the policy's metric.
+//! 3. [`RetentionRule::Recent`]: its manifest is younger than `keep_days`.
+//!
+//! [`execute_prune`] deletes the rest. Nothing else is touched: session
+//! directories keep their own `runs/`, and there is no index to rewrite
+//! because readers scan the directory. A reference file that cannot be
+//! read stops the plan unless forced, since the runs it names are unknown.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, File};
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local, NaiveDateTime};
+use thiserror::Error;
+
+use crate::export::load_artifact_summary;
+use crate::fitness::FitnessMetric;
+use crate::leaderboard_store::StoredLeaderboard;
+use crate::promotion_batch::PromotionReport;
+use crate::session::SESSION_FILE;
+use crate::yolo::YoloResult;
+use trendlab_core::domain::FullHash;
+
+/// Prefix of promotion report file names (`promotion_report.json` by default).
+pub const PROMOTION_REPORT_PREFIX: &str = "promotion_report";
+
+/// Which saved runs survive a prune.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetentionPolicy {
+    /// Runs kept per symbol, best first by `metric`.
+    pub keep_top: usize,
+    pub metric: FitnessMetric,
+    /// Keep runs whose manifest was written within this many days.
+    pub keep_days: Option<u64>,
+    /// Delete runs that sessions, leaderboard files or promotion reports
+    /// reference, if no other rule keeps them.
+    pub force: bool,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            keep_top: 10,
+            metric: FitnessMetric::Sharpe,
+            keep_days: Some(30),
+            force: false,
+        }
+    }
+}
+
+/// Why a run was kept, in order of precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RetentionRule {
+    Referenced,
+    TopK,
+    Recent,
+}
+
+impl RetentionRule {
+    pub const ALL: [RetentionRule; 3] = [
+        RetentionRule::Referenced,
+        RetentionRule::TopK,
+        RetentionRule::Recent,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RetentionRule::Referenced => "referenced",
+            RetentionRule::TopK => "top-k",
+            RetentionRule::Recent => "recent",
+        }
+    }
+}
+
+/// A run directory under the results directory.
+#[derive(Debug, Clone)]
+pub struct SavedRun {
+    pub dir: PathBuf,
+    pub symbol: String,
+    /// Full hash of the run's config.
+    pub run_id: FullHash,
+    /// The policy's metric for this run.
+    pub fitness: f64,
+    /// Modification time of the manifest.
+    pub saved_at: NaiveDateTime,
+    /// Size of the directory's files.
+    pub bytes: u64,
+}
+
+/// What [`execute_prune`] would keep and delete.
+#[derive(Debug, Default)]
+pub struct PrunePlan {
+    pub kept: Vec<(SavedRun, RetentionRule)>,
+    pub delete: Vec<SavedRun>,
+    /// Runs in `delete` that a reference would have kept (only when forced).
+    pub forced: usize,
+    /// Directories whose manifest could not be read. They are kept.
+    pub unreadable: Vec<PathBuf>,
+    /// Reference files read.
+    pub reference_files: usize,
+}
+
+impl PrunePlan {
+    /// Runs and bytes kept by `rule`.
+    pub fn kept_by(&self, rule: RetentionRule) -> (usize, u64) {
+        self.kept
+            .iter()
+            .filter(|(_, r)| *r == rule)
+            .fold((0, 0), |(n, bytes), (run, _)| (n + 1, bytes + run.bytes))
+    }
+
+    /// Bytes freed by deleting every run in `delete`.
+    pub fn bytes_to_reclaim(&self) -> u64 {
+        self.delete.iter().map(|r| r.bytes).sum()
+    }
+}
+
+/// What [`execute_prune`] deleted.
+#[derive(Debug, Default)]
+pub struct PruneOutcome {
+    pub deleted: usize,
+    pub bytes_reclaimed: u64,
+    pub failed: Vec<(PathBuf, io::Error)>,
+}
+
+/// Errors from planning a prune.
+#[derive(Debug, Error)]
+pub enum RetentionError {
+    #[error("results directory {path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("cannot read reference file {path}: {reason} (force prunes without it)")]
+    Reference { path: PathBuf, reason: String },
+}
+
+/// Sort the run directories under `results_dir` into kept and deleted.
+///
+/// `now` anchors `keep_days`. Deletes nothing.
+pub fn plan_prune(
+    results_dir: &Path,
+    policy: &RetentionPolicy,
+    now: NaiveDateTime,
+) -> Result<PrunePlan, RetentionError> {
+    let (runs, unreadable) = saved_runs(results_dir, policy.metric)?;
+    let references = References::collect(results_dir);
+    if let (false, Some((path, reason))) = (policy.force, references.errors.first()) {
+        return Err(RetentionError::Reference {
+            path: path.clone(),
+            reason: reason.clone(),
+        });
+    }
+
+    let mut by_symbol: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (i, run) in runs.iter().enumerate() {
+        by_symbol.entry(&run.symbol).or_default().push(i);
+    }
+    let mut top = HashSet::new();
+    for indices in by_symbol.values_mut() {
+        indices.sort_by(|&a, &b| {
+            let (a, b) = (&runs[a], &runs[b]);
+            policy
+                .metric
+                .compare(a.fitness, b.fitness)
+                .then_with(|| a.dir.cmp(&b.dir))
+        });
+        top.extend(indices.iter().take(policy.keep_top).copied());
+    }
+    let cutoff = policy
+        .keep_days
+        .map(|days| now - chrono::Duration::days(days as i64));
+
+    let mut plan = PrunePlan {
+        unreadable,
+        reference_files: references.files,
+        ..PrunePlan::default()
+    };
+    for (i, run) in runs.into_iter().enumerate() {
+        let referenced = references
+            .runs
+            .contains(&(run.run_id.clone(), run.symbol.clone()));
+        let rule = if referenced && !policy.force {
+            Some(RetentionRule::Referenced)
+        } else if top.contains(&i) {
+            Some(RetentionRule::TopK)
+        } else if cutoff.is_some_and(|c| run.saved_at >= c) {
+            Some(RetentionRule::Recent)
+        } else {
+            None
+        };
+        match rule {
+            Some(rule) => plan.kept.push((run, rule)),
+            None => {
+                plan.forced += usize::from(referenced);
+                plan.delete.push(run);
+            }
+        }
+    }
+    Ok(plan)
+}
+
+/// Delete the runs `plan` marks for deletion. A directory that fails to
+/// delete is reported and the rest still go.
+pub fn execute_prune(plan: &PrunePlan) -> PruneOutcome {
+    let mut outcome = PruneOutcome::default();
+    for run in &plan.delete {
+        match fs::remove_dir_all(&run.dir) {
+            Ok(()) => {
+                outcome.deleted += 1;
+                outcome.bytes_reclaimed += run.bytes;
+            }
+            Err(e) => outcome.failed.push((run.dir.clone(), e)),
+        }
+    }
+    outcome
+}
+
+/// Run directories directly under `results_dir`, sorted by path, and those
+/// whose manifest could not be read. A missing directory has none.
+fn saved_runs(
+    results_dir: &Path,
+    metric: FitnessMetric,
+) -> Result<(Vec<SavedRun>, Vec<PathBuf>), RetentionError> {
+    let entries = match fs::read_dir(results_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok((Vec::new(), Vec::new())),
+        Err(source) => {
+            return Err(RetentionError::Io {
+                path: results_dir.to_path_buf(),
+                source,
+            })
+        }
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.join("manifest.json").is_file())
+        .collect();
+    dirs.sort();
+
+    let mut runs = Vec::with_capacity(dirs.len());
+    let mut unreadable = Vec::new();
+    for dir in dirs {
+        let modified = fs::metadata(dir.join("manifest.json")).and_then(|m| m.modified());
+        match (load_artifact_summary(&dir), modified) {
+            (Ok(summary), Ok(modified)) => runs.push(SavedRun {
+                symbol: summary.symbol,
+                run_id: summary.config.full_hash(),
+                fitness: metric.extract(&summary.metrics),
+                saved_at: DateTime::<Local>::from(modified).naive_local(),
+                bytes: dir_size(&dir),
+                dir,
+            }),
+            _ => unreadable.push(dir),
+        }
+    }
+    Ok((runs, unreadable))
+}
+
+/// Runs named by the reference files under a results directory.
+#[derive(Debug, Default)]
+struct References {
+    /// Config hash and symbol of each referenced run.
+    runs: HashSet<(FullHash, String)>,
+    files: usize,
+    errors: Vec<(PathBuf, String)>,
+}
+
+impl References {
+    fn collect(results_dir: &Path) -> Self {
+        let mut references = Self::default();
+        references.walk(results_dir);
+        references
+    }
+
+    /// Read the reference files under `dir`, skipping run directories.
+    fn walk(&mut self, dir: &Path) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        let mut paths: Vec<PathBuf> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+        paths.sort();
+        for path in paths {
+            if path.is_dir() {
+                if !path.join("manifest.json").is_file() {
+                    self.walk(&path);
+                }
+                continue;
+            }
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let read = if name == SESSION_FILE {
+                read_session(&path)
+            } else if name.starts_with("symbol=") && name.ends_with(".json") {
+                read_leaderboard(&path)
+            } else if name.starts_with(PROMOTION_REPORT_PREFIX) && name.ends_with(".json") {
+                read_promotion_report(&path)
+            } else {
+                continue;
+            };
+            match read {
+                Ok(runs) => {
+                    self.files += 1;
+                    self.runs.extend(runs);
+                }
+                Err(reason) => self.errors.push((path, reason)),
+            }
+        }
+    }
+}
+
+fn read_session(path: &Path) -> Result<Vec<(FullHash, String)>, String> {
+    let session = YoloResult::load(path).map_err(|e| e.to_string())?;
+    Ok(session
+        .leaderboards
+        .values()
+        .flat_map(|lb| lb.entries())
+        .map(|e| (e.result.config.full_hash(), e.result.symbol.clone()))
+        .collect())
+}
+
+fn read_leaderboard(path: &Path) -> Result<Vec<(FullHash, String)>, String> {
+    let board: StoredLeaderboard = read_json(path)?;
+    Ok(board
+        .entries()
+        .iter()
+        .map(|e| (e.full_hash(), board.symbol().to_string()))
+        .collect())
+}
+
+fn read_promotion_report(path: &Path) -> Result<Vec<(FullHash, String)>, String> {
+    let report: PromotionReport = read_json(path)?;
+    Ok(report
+        .entries
+        .iter()
+        .map(|e| (e.config.full_hash(), e.symbol.clone()))
+        .collect())
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T, String> {
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    serde_json::from_reader(BufReader::new(file)).map_err(|e| e.to_string())
+}
+
+/// Total size of the files under `path`.
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(t) if t.is_dir() => dir_size(&entry.path()),
+            _ => entry.metadata().map_or(0, |m| m.len()),
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::PerformanceMetrics;
+    use crate::promotion::PromotionConfig;
+    use crate::promotion_batch::PromotionReportEntry;
+    use trendlab_core::components::composition::StrategyPreset;
+    use trendlab_core::fingerprint::StrategyConfig;
+
+    fn temp_results(tag: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("trendlab_retention_{tag}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// A run directory with a manifest `load_artifact_summary` can read.
+    fn write_run(results: &Path, name: &str, symbol: &str, config: &StrategyConfig, sharpe: f64) {
+        let dir = results.join(name);
+        fs::create_dir_all(&dir).unwrap();
+        let metrics = PerformanceMetrics {
+            sharpe,
+            ..PerformanceMetrics::neutral()
+        };
+        let manifest = serde_json::json!({
+            "metrics": metrics,
+            "config": config,
+            "symbol": symbol,
+            "start_date": "2024-01-02",
+            "end_date": "2024-12-31",
+            "initial_capital": 100_000.0,
+            "bar_count": 252,
+            "stickiness": null,
+        });
+        fs::write(dir.join("manifest.json"), manifest.to_string()).unwrap();
+        fs::write(dir.join("trades.csv"), "symbol\n").unwrap();
+    }
+
+    fn names(runs: impl Iterator<Item = PathBuf>) -> Vec<String> {
+        runs.map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    fn later() -> NaiveDateTime {
+        Local::now().naive_local() + chrono::Duration::days(365)
+    }
+
+    #[test]
+    fn keeps_top_k_per_symbol_and_recent_runs() {
+        let results = temp_results("top_k");
+        let config = StrategyPreset::DonchianTrend.to_config();
+        write_run(&results, "SPY_a", "SPY", &config, 0.5);
+        write_run(&results, "SPY_b", "SPY", &config, 1.5);
+        write_run(&results, "SPY_c", "SPY", &config, f64::NAN);
+        write_run(&results, "QQQ_a", "QQQ", &config, -1.0);
+        fs::create_dir_all(results.join("not_a_run")).unwrap();
+
+        let policy = RetentionPolicy {
+            keep_top: 1,
+            keep_days: Some(30),
+            ..RetentionPolicy::default()
+        };
+        let plan = plan_prune(&results, &policy, later()).unwrap();
+        let kept = names(plan.kept.iter().map(|(r, _)| r.dir.clone()));
+        assert_eq!(kept, ["QQQ_a", "SPY_b"]);
+        assert_eq!(
+            names(plan.delete.iter().map(|r| r.dir.clone())),
+            ["SPY_a", "SPY_c"]
+        );
+        assert_eq!(plan.kept_by(RetentionRule::TopK).0, 2);
+        assert!(plan.bytes_to_reclaim() > 0);
+
+        // Everything was written just now, so the age rule keeps it all
+        let now = Local::now().naive_local();
+        let plan = plan_prune(&results, &policy, now).unwrap();
+        assert!(plan.delete.is_empty());
+        assert_eq!(plan.kept_by(RetentionRule::Recent).0, 2);
+
+        // Planning deletes nothing; executing deletes the planned runs
+        let plan = plan_prune(&results, &policy, later()).unwrap();
+        let outcome = execute_prune(&plan);
+        assert_eq!(outcome.deleted, 2);
+        assert_eq!(outcome.bytes_reclaimed, plan.bytes_to_reclaim());
+        assert!(!results.join("SPY_a").exists());
+        assert!(results.join("SPY_b").exists());
+        assert!(results.join("not_a_run").exists());
+
+        let _ = fs::remove_dir_all(&results);
+    }
+
+    #[test]
+    fn referenced_runs_need_force() {
+        let results = temp_results("referenced");
+        let promoted = StrategyPreset::DonchianTrend.to_config();
+        let other = StrategyPreset::MomentumRoc.to_config();
+        write_run(&results, "SPY_promoted", "SPY", &promoted, -2.0);
+        write_run(&results, "QQQ_promoted_elsewhere", "QQQ", &promoted, -2.0);
+        write_run(&results, "SPY_other", "SPY", &other, -1.0);
+
+        let report = PromotionReport {
+            promotion_config: PromotionConfig::default(),
+            entries: vec![PromotionReportEntry {
+                source: results.join("SPY_promoted").display().to_string(),
+                symbol: "SPY".into(),
+                config: promoted.clone(),
+                recorded_sharpe: -2.0,
+                sharpe: None,
+                level: None,
+                failing_criterion: None,
+                fdr_significant: None,
+                robustness: None,
+                from_cache: Vec::new(),
+            }],
+        };
+        let reports = results.join("reports");
+        fs::create_dir_all(&reports).unwrap();
+        fs::write(
+            reports.join("promotion_report.json"),
+            serde_json::to_string(&report).unwrap(),
+        )
+        .unwrap();
+
+        let policy = RetentionPolicy {
+            keep_top: 0,
+            keep_days: None,
+            ..RetentionPolicy::default()
+        };
+        let plan = plan_prune(&results, &policy, later()).unwrap();
+        assert_eq!(plan.reference_files, 1);
+        assert_eq!(plan.kept.len(), 1);
+        assert_eq!(plan.kept[0].1, RetentionRule::Referenced);
+        assert!(plan.kept[0].0.dir.ends_with("SPY_promoted"));
+        // The same config on another symbol is a different run
+        assert_eq!(plan.delete.len(), 2);
+        assert_eq!(plan.forced, 0);
+
+        let forced = RetentionPolicy {
+            force: true,
+            ..policy.clone()
+        };
+        let plan = plan_prune(&results, &forced, later()).unwrap();
+        assert_eq!((plan.delete.len(), plan.forced), (3, 1));
+
+        // An unreadable reference protects nothing, so it stops the plan
+        fs::write(results.join("session.json"), "{").unwrap();
+        let err = plan_prune(&results, &policy, later()).unwrap_err();
+        assert!(matches!(err, RetentionError::Reference { .. }), "{err}");
+        assert_eq!(
+            plan_prune(&results, &forced, later()).unwrap().delete.len(),
+            3
+        );
+
+        let _ = fs::remove_dir_all(&results);
+    }
+
+    #[test]
+    fn missing_results_dir_is_empty() {
+        let dir = std::env::temp_dir().join("trendlab_retention_missing_dir");
+        let plan = plan_prune(&dir, &RetentionPolicy::default(), later()).unwrap();
+        assert!(plan.kept.is_empty() && plan.delete.is_empty());
+    }
+}
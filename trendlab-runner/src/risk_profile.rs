@@ -10,12 +10,19 @@
 //! Rank normalization: before applying weights, raw metric values are replaced
 //! with their percentile rank (0.0 = worst, 1.0 = best) within the current
 //! population. This ensures metrics with different units contribute proportionally.
+//!
+//! A profile can also constrain the backtests themselves:
+//! [`RiskProfile::to_overrides`] gives the engine risk guard and any filter
+//! the profile requires, applied on top of a `StrategyConfig` before it runs.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use serde::{Deserialize, Serialize};
 
+use trendlab_core::components::filter::VolatilityFilter;
 use trendlab_core::domain::FullHash;
+use trendlab_core::engine::RiskGuard;
+use trendlab_core::fingerprint::{ComponentConfig, StrategyConfig};
 
 use crate::cross_leaderboard::CrossSymbolEntry;
 
@@ -37,6 +44,8 @@ pub enum RankingMetric {
     MinSharpe,
     GeoMeanCagr,
     HitRate,
+    /// CVaR at 95% of pooled daily returns (less negative ranks higher).
+    Cvar95,
     /// Stub for Phase 11 walk-forward integration.
     MeanOosSharpe,
     /// Composite score using the active risk profile.
@@ -54,7 +63,98 @@ struct ProfileWeights {
     consistency: f64,
 }
 
+/// Component type of the filter a profile can require.
+const VOLATILITY_FILTER: &str = "volatility_filter";
+
+/// Config overlays a risk profile applies before a backtest runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiskOverrides {
+    /// Position size cap and drawdown kill switch, written into the signal's
+    /// params so they enter `full_hash`.
+    pub guard: RiskGuard,
+    /// Filter the strategy must run with. The config has a single filter
+    /// slot, so any other filter is replaced.
+    pub required_filter: Option<ComponentConfig>,
+}
+
+impl RiskOverrides {
+    /// True when applying the overrides leaves every config unchanged.
+    pub fn is_empty(&self) -> bool {
+        !self.guard.is_active() && self.required_filter.is_none()
+    }
+
+    /// Apply on top of `config`. Applying twice changes nothing further.
+    pub fn apply(&self, config: &mut StrategyConfig) {
+        self.guard.write_params(&mut config.signal.params);
+        if let Some(filter) = &self.required_filter {
+            if config.signal_filter.component_type != filter.component_type {
+                config.signal_filter = filter.clone();
+            }
+        }
+    }
+}
+
 impl RiskProfile {
+    pub const ALL: [RiskProfile; 4] = [
+        RiskProfile::Balanced,
+        RiskProfile::Conservative,
+        RiskProfile::Aggressive,
+        RiskProfile::TrendOptions,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Balanced => "balanced",
+            Self::Conservative => "conservative",
+            Self::Aggressive => "aggressive",
+            Self::TrendOptions => "trend-options",
+        }
+    }
+
+    /// Engine-level constraints for backtests run under this profile.
+    ///
+    /// | Profile | Max position | Drawdown halt | Required filter |
+    /// |---|---|---|---|
+    /// | Balanced | — | — | — |
+    /// | Conservative | 50% | 15% | volatility |
+    /// | Aggressive | — | 40% | — |
+    /// | TrendOptions | 25% | — | — |
+    pub fn to_overrides(self) -> RiskOverrides {
+        let guard = |max_position_pct, max_drawdown| RiskGuard {
+            max_position_pct,
+            max_drawdown,
+        };
+        match self {
+            Self::Balanced => RiskOverrides {
+                guard: RiskGuard::default(),
+                required_filter: None,
+            },
+            Self::Conservative => {
+                let vol = VolatilityFilter::default_params();
+                RiskOverrides {
+                    guard: guard(Some(0.5), Some(0.15)),
+                    required_filter: Some(ComponentConfig {
+                        component_type: VOLATILITY_FILTER.into(),
+                        params: BTreeMap::from([
+                            ("period".to_string(), vol.period as f64),
+                            ("min_pct".to_string(), vol.min_pct),
+                            ("max_pct".to_string(), vol.max_pct),
+                        ]),
+                        children: Vec::new(),
+                    }),
+                }
+            }
+            Self::Aggressive => RiskOverrides {
+                guard: guard(None, Some(0.4)),
+                required_filter: None,
+            },
+            Self::TrendOptions => RiskOverrides {
+                guard: guard(Some(0.25), None),
+                required_filter: None,
+            },
+        }
+    }
+
     fn weights(&self) -> ProfileWeights {
         match self {
             Self::Balanced => ProfileWeights {
@@ -97,6 +197,21 @@ impl RiskProfile {
     }
 }
 
+impl std::str::FromStr for RiskProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_ascii_lowercase().replace('_', "-");
+        Self::ALL
+            .into_iter()
+            .find(|p| p.label() == s || p.label().replace('-', "") == s)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(|p| p.label()).collect();
+                format!("unknown risk profile '{s}' ({})", names.join(", "))
+            })
+    }
+}
+
 /// Compute composite scores for a set of cross-symbol entries under a risk profile.
 ///
 /// Returns a map of `FullHash → composite_score`. Higher is better.
@@ -225,18 +340,22 @@ mod tests {
                     m.insert("lookback".into(), lookback);
                     m
                 },
+                children: Vec::new(),
             },
             position_manager: ComponentConfig {
                 component_type: "atr_trailing".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
             execution_model: ComponentConfig {
                 component_type: "next_bar_open".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
             signal_filter: ComponentConfig {
                 component_type: "no_filter".into(),
                 params: BTreeMap::new(),
+                children: Vec::new(),
             },
         }
     }
@@ -264,17 +383,28 @@ mod tests {
             hit_rate,
             worst_max_drawdown: worst_dd,
             avg_trade_count: 20.0,
+            effective_bets: None,
+            adjusted_sharpe: None,
             tail_metrics: None,
+            equity_sparkline: Vec::new(),
             symbol_count: 3,
             symbol_metrics: HashMap::new(),
             symbol_equity_curves: HashMap::new(),
+            symbol_trade_spans: HashMap::new(),
             avg_stickiness: None,
             symbol_stickiness: HashMap::new(),
+            symbol_retests: HashMap::new(),
+            symbol_holdouts: HashMap::new(),
+            symbol_execution_profiles: HashMap::new(),
+            symbol_fdr: HashMap::new(),
             robustness: None,
+            cluster_id: None,
+            diversification_score: None,
             has_catastrophic: false,
             session_id: "test".into(),
             timestamp: ts,
             iteration: 0,
+            provenance: None,
         }
     }
 
@@ -448,4 +578,35 @@ mod tests {
             "Expected {expected}, got {score}"
         );
     }
+
+    // ── Overrides ──
+
+    #[test]
+    fn parses_labels() {
+        for profile in RiskProfile::ALL {
+            assert_eq!(profile.label().parse::<RiskProfile>(), Ok(profile));
+        }
+        assert_eq!("TrendOptions".parse(), Ok(RiskProfile::TrendOptions));
+        assert!("reckless".parse::<RiskProfile>().is_err());
+    }
+
+    #[test]
+    fn conservative_overrides_guard_and_require_volatility_filter() {
+        assert!(RiskProfile::Balanced.to_overrides().is_empty());
+
+        let base = make_config("donchian", 50.0);
+        let overrides = RiskProfile::Conservative.to_overrides();
+        let mut config = base.clone();
+        overrides.apply(&mut config);
+        assert_eq!(
+            RiskGuard::from_params(&config.signal.params),
+            overrides.guard
+        );
+        assert_eq!(config.signal_filter.component_type, "volatility_filter");
+        assert_ne!(config.full_hash(), base.full_hash());
+
+        let applied = config.clone();
+        overrides.apply(&mut config);
+        assert_eq!(config, applied);
+    }
 }
@@ -0,0 +1,282 @@
+//! Robustness-results cache — reuse promotion-ladder levels across runs.
+//!
+//! Walk-forward, trigger-margin MC, execution MC and bootstrap are
+//! deterministic given the strategy, the data, the level's config and the
+//! run settings, and each can take minutes. Promoting the same config again
+//! (re-testing a batch, or the same strategy on another report) reuses the
+//! stored result instead.
+//!
+//! Each result is one JSON file under `<results>/robustness_cache/`, named by
+//! a hash of its [`LevelKey`]: config hash, dataset hash, level, and a hash of
+//! the level's config plus the run settings. New data or changed level
+//! parameters produce a different key, so stale entries are never read; they
+//! are only left behind.
+//!
+//! Files are written to a uniquely named temp file and renamed into place,
+//! so concurrent promote jobs never see a partial write. Unreadable or
+//! mismatched files count as misses and are overwritten.
+
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Cache directory under a results directory.
+pub const ROBUSTNESS_CACHE_DIR: &str = "robustness_cache";
+
+/// Ladder level whose result the cache stores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CachedLevel {
+    ShuffleTest,
+    Sensitivity,
+    WalkForward,
+    TriggerMargin,
+    ExecutionMc,
+    Bootstrap,
+}
+
+impl CachedLevel {
+    pub const ALL: [Self; 6] = [
+        Self::ShuffleTest,
+        Self::Sensitivity,
+        Self::WalkForward,
+        Self::TriggerMargin,
+        Self::ExecutionMc,
+        Self::Bootstrap,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ShuffleTest => "shuffle_test",
+            Self::Sensitivity => "sensitivity",
+            Self::WalkForward => "walk_forward",
+            Self::TriggerMargin => "trigger_margin",
+            Self::ExecutionMc => "execution_mc",
+            Self::Bootstrap => "bootstrap",
+        }
+    }
+}
+
+/// Identity of one cached level result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelKey {
+    pub config_hash: String,
+    pub dataset_hash: String,
+    pub level: CachedLevel,
+    /// Hash of the level's config and the run settings it depends on.
+    pub level_config_hash: String,
+}
+
+impl LevelKey {
+    /// Key for `level`, hashing `level_config` (anything that changes the
+    /// level's result) to JSON.
+    pub fn new(
+        config_hash: &str,
+        dataset_hash: &str,
+        level: CachedLevel,
+        level_config: &impl Serialize,
+    ) -> Self {
+        let json = serde_json::to_vec(level_config).expect("level config must serialize");
+        Self {
+            config_hash: config_hash.to_string(),
+            dataset_hash: dataset_hash.to_string(),
+            level,
+            level_config_hash: blake3::hash(&json).to_hex().to_string(),
+        }
+    }
+
+    fn file_name(&self) -> String {
+        let mut hasher = blake3::Hasher::new();
+        for part in [
+            self.config_hash.as_str(),
+            self.dataset_hash.as_str(),
+            self.level.label(),
+            self.level_config_hash.as_str(),
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update(&[0]);
+        }
+        format!("{}.json", hasher.finalize().to_hex())
+    }
+}
+
+/// Hits and misses since the cache was opened, across all clones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Levels served from the cache.
+    pub hits: usize,
+    /// Levels computed because no usable entry existed.
+    pub misses: usize,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheFile<T> {
+    key: LevelKey,
+    result: T,
+}
+
+/// File-backed cache of promotion-ladder level results.
+///
+/// Clones share the directory and the hit/miss counters.
+#[derive(Debug, Clone)]
+pub struct RobustnessCache {
+    dir: PathBuf,
+    counters: Arc<Counters>,
+}
+
+impl RobustnessCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            counters: Arc::default(),
+        }
+    }
+
+    /// The cache under `results_dir`.
+    pub fn in_results_dir(results_dir: &Path) -> Self {
+        Self::new(results_dir.join(ROBUSTNESS_CACHE_DIR))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.counters.hits.load(Ordering::Relaxed),
+            misses: self.counters.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Stored result for `key`; `None` if missing, unreadable, or written
+    /// under a different key.
+    pub fn get<T: DeserializeOwned>(&self, key: &LevelKey) -> Option<T> {
+        let file = File::open(self.dir.join(key.file_name())).ok()?;
+        let stored: CacheFile<T> = serde_json::from_reader(BufReader::new(file)).ok()?;
+        (stored.key == *key).then_some(stored.result)
+    }
+
+    /// Store `result` under `key`, replacing any existing entry atomically.
+    pub fn put<T: Serialize>(&self, key: &LevelKey, result: &T) -> io::Result<()> {
+        static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(key.file_name());
+        let tmp = self.dir.join(format!(
+            ".{}.{}-{}.tmp",
+            key.file_name(),
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let write = || -> io::Result<()> {
+            let mut writer = BufWriter::new(File::create(&tmp)?);
+            serde_json::to_writer(
+                &mut writer,
+                &CacheFile {
+                    key: key.clone(),
+                    result,
+                },
+            )?;
+            writer.flush()?;
+            drop(writer);
+            fs::rename(&tmp, &path)
+        };
+        let written = write();
+        if written.is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+        written
+    }
+
+    /// The stored result for `key`, or `compute()` stored under it.
+    ///
+    /// Returns the result and whether it came from the cache. Errors are
+    /// not stored, and a failed write only loses the entry.
+    pub fn get_or_compute<T, E>(
+        &self,
+        key: &LevelKey,
+        compute: impl FnOnce() -> Result<T, E>,
+    ) -> (Result<T, E>, bool)
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        if let Some(result) = self.get(key) {
+            self.counters.hits.fetch_add(1, Ordering::Relaxed);
+            return (Ok(result), true);
+        }
+        self.counters.misses.fetch_add(1, Ordering::Relaxed);
+        let result = compute();
+        if let Ok(ref value) = result {
+            let _ = self.put(key, value);
+        }
+        (result, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(dataset_hash: &str, n_samples: usize) -> LevelKey {
+        LevelKey::new("cfg", dataset_hash, CachedLevel::ExecutionMc, &n_samples)
+    }
+
+    #[test]
+    fn get_or_compute_stores_then_hits() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = RobustnessCache::in_results_dir(dir.path());
+
+        let (first, cached) = cache.get_or_compute(&key("d1", 20), || Ok::<_, ()>(vec![1.5, 2.5]));
+        assert_eq!((first, cached), (Ok(vec![1.5, 2.5]), false));
+
+        let (second, cached) = cache.get_or_compute(&key("d1", 20), || -> Result<Vec<f64>, ()> {
+            panic!("recomputed")
+        });
+        assert_eq!((second, cached), (Ok(vec![1.5, 2.5]), true));
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 1 });
+
+        // No temp files left behind
+        let files: Vec<_> = fs::read_dir(cache.dir()).unwrap().collect();
+        assert_eq!(files.len(), 1);
+    }
+
+    #[test]
+    fn dataset_or_level_config_change_misses() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = RobustnessCache::new(dir.path());
+        cache.put(&key("d1", 20), &1.0).unwrap();
+
+        assert_eq!(cache.get::<f64>(&key("d1", 20)), Some(1.0));
+        assert_eq!(cache.get::<f64>(&key("d2", 20)), None);
+        assert_eq!(cache.get::<f64>(&key("d1", 50)), None);
+        let other_level = LevelKey::new("cfg", "d1", CachedLevel::Bootstrap, &20);
+        assert_eq!(cache.get::<f64>(&other_level), None);
+    }
+
+    #[test]
+    fn errors_are_not_stored_and_corrupt_files_miss() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = RobustnessCache::new(dir.path());
+        let k = key("d1", 20);
+
+        let (result, _) = cache.get_or_compute(&k, || Err::<f64, _>("no data"));
+        assert_eq!(result, Err("no data"));
+        assert_eq!(cache.get::<f64>(&k), None);
+
+        fs::write(dir.path().join(k.file_name()), "{\"key\":").unwrap();
+        let (result, cached) = cache.get_or_compute(&k, || Ok::<_, ()>(3.0));
+        assert_eq!((result, cached), (Ok(3.0), false));
+        assert_eq!(cache.get::<f64>(&k), Some(3.0));
+    }
+}
@@ -1,42 +1,156 @@
 //! Backtest runner — wires together composition, engine, and metrics.
 //!
-//! Three entry points:
+//! Five entry points:
 //! - `run_single_backtest()`: loads data from cache, then runs. Used by CLI.
+//! - `run_modified()`: re-runs a result with another `StrategyConfig` on the
+//!   same data and settings. Used by the TUI's parameter nudging.
 //! - `run_backtest_from_data()`: takes pre-loaded data + execution preset. Used by YOLO mode.
 //! - `run_backtest_with_exec_config()`: takes pre-loaded data + explicit ExecutionConfig.
 //!   Used by execution Monte Carlo.
+//! - `run_backtest_cached()`: pre-loaded data + a shared indicator cache. Used by
+//!   walk-forward optimization.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use trendlab_core::components::composition::build_composition;
-use trendlab_core::components::execution::ExecutionPreset;
+use trendlab_core::components::execution::{ExecutionPreset, ExecutionProfile};
 use trendlab_core::components::factory::FactoryError;
 use trendlab_core::data::align::AlignedData;
-use trendlab_core::data::cache::ParquetCache;
+use trendlab_core::data::cache::{ParquetCache, PartitionVintage};
+use trendlab_core::data::interval::BarInterval;
 use trendlab_core::data::provider::DataProvider;
-use trendlab_core::domain::TradeRecord;
-use trendlab_core::engine::stickiness::StickinessMetrics;
-use trendlab_core::engine::{run_backtest, EngineConfig, ExecutionConfig};
-use trendlab_core::fingerprint::{StrategyConfig, TradingMode};
+use trendlab_core::domain::{Instrument, Quantization, TradeRecord};
+use trendlab_core::engine::stickiness::{PositionStickiness, StickinessMetrics, StickinessReport};
+use trendlab_core::engine::{
+    run_backtest, run_backtest_cached as run_engine_cached, EngineConfig, EngineTimings,
+    EntryGovernance, ExecutionConfig, IndicatorCache, PnlAttribution, RatchetClamp, ReplayLog,
+    RiskGuard, SnapshotLog, SnapshotMode, WarmupPolicy, WarmupReport,
+};
+use trendlab_core::fingerprint::{Provenance, StrategyConfig, TradingMode};
 
-use crate::config::{BacktestConfig, ConfigError};
-use crate::data_loader::{load_bars, LoadError, LoadOptions};
-use crate::metrics::PerformanceMetrics;
+use crate::config::{parse_date, validate_strategy, BacktestConfig, ConfigError, ValidationReport};
+use crate::consistency::ConsistencyCheck;
+use crate::data_loader::{compute_dataset_hash, load_bars, LoadError, LoadOptions, TruncatedRange};
+use crate::data_quality::{deserialize_warnings, AnomalyKind, DataQualityWarning};
+use crate::metrics::{PerformanceMetrics, RDistribution, TradeDistributions};
+use crate::risk_profile::RiskProfile;
+use crate::sparkline::{equity_sparkline, SPARKLINE_POINTS};
+use crate::style::{load_factors, style_analysis, FactorSeries, StyleError, StyleReport};
+use crate::tail_metrics::{compute_tail_metrics, worst_days, TailMetrics, DEFAULT_WORST_DAYS};
+use crate::walk_forward::slice_aligned_data;
 
 /// Errors from the runner.
+///
+/// Grouped by what the caller can do about them: see [`RunError::kind`] and
+/// [`RunError::is_retryable`].
 #[derive(Debug, Error)]
 pub enum RunError {
-    #[error("config error: {0}")]
-    Config(#[from] ConfigError),
-    #[error("data error: {0}")]
-    Data(#[from] LoadError),
-    #[error("composition error: {0}")]
-    Composition(#[from] FactoryError),
-    #[error("symbol '{0}' not found in loaded data")]
-    SymbolNotFound(String),
+    /// Bars for `symbol` (or a factor series its run needed) could not be loaded.
+    #[error("data for '{symbol}': {source}")]
+    DataLoad {
+        symbol: String,
+        #[source]
+        source: LoadError,
+    },
+    /// The strategy or backtest config cannot be built.
+    #[error("invalid config: {0}")]
+    ConfigInvalid(#[from] ConfigError),
+    /// The engine produced output that breaks one of its own guarantees.
+    #[error("engine invariant violated for '{symbol}': {reason}")]
+    EngineInvariant { symbol: String, reason: String },
+    /// A file the run reads or writes (factor CSV, artifact) failed.
+    #[error("artifact I/O at {path}: {reason}")]
+    ArtifactIo { path: String, reason: String },
+    /// The run's trades, fills and equity curve don't reconcile (see
+    /// [`crate::consistency`]). One detail per violation.
+    #[error("inconsistent result for '{symbol}': {}", details.join("; "))]
+    InconsistentResult {
+        symbol: String,
+        details: Vec<String>,
+    },
+}
+
+impl From<FactoryError> for RunError {
+    fn from(err: FactoryError) -> Self {
+        Self::ConfigInvalid(ConfigError::Component(err))
+    }
+}
+
+/// Category of a [`RunError`], for counting and exit codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunErrorKind {
+    DataLoad,
+    ConfigInvalid,
+    EngineInvariant,
+    ArtifactIo,
+    InconsistentResult,
+}
+
+impl RunErrorKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::DataLoad => "data_load",
+            Self::ConfigInvalid => "config_invalid",
+            Self::EngineInvariant => "engine_invariant",
+            Self::ArtifactIo => "artifact_io",
+            Self::InconsistentResult => "inconsistent_result",
+        }
+    }
+}
+
+impl RunError {
+    pub fn kind(&self) -> RunErrorKind {
+        match self {
+            Self::DataLoad { .. } => RunErrorKind::DataLoad,
+            Self::ConfigInvalid(_) => RunErrorKind::ConfigInvalid,
+            Self::EngineInvariant { .. } => RunErrorKind::EngineInvariant,
+            Self::ArtifactIo { .. } => RunErrorKind::ArtifactIo,
+            Self::InconsistentResult { .. } => RunErrorKind::InconsistentResult,
+        }
+    }
+
+    /// True if running the same thing again may succeed: a failed download or
+    /// a file error. Bad data, bad configs, and engine bugs fail every time.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::DataLoad { source, .. } => matches!(source, LoadError::DownloadFailed { .. }),
+            Self::ArtifactIo { .. } => true,
+            Self::ConfigInvalid(_)
+            | Self::EngineInvariant { .. }
+            | Self::InconsistentResult { .. } => false,
+        }
+    }
+
+    /// The symbol the failure is attributable to, if any.
+    ///
+    /// Config errors are the strategy's fault, not the symbol's, so they return None.
+    pub fn symbol(&self) -> Option<&str> {
+        match self {
+            Self::DataLoad { symbol, .. }
+            | Self::EngineInvariant { symbol, .. }
+            | Self::InconsistentResult { symbol, .. } => Some(symbol),
+            Self::ConfigInvalid(_) | Self::ArtifactIo { .. } => None,
+        }
+    }
+
+    fn from_style(err: StyleError, symbol: &str) -> Self {
+        match err {
+            StyleError::Load(source) => Self::DataLoad {
+                symbol: symbol.to_string(),
+                source,
+            },
+            StyleError::Csv { path, reason } => Self::ArtifactIo { path, reason },
+            other => Self::ArtifactIo {
+                path: String::new(),
+                reason: other.to_string(),
+            },
+        }
+    }
 }
 
 /// Current schema version for persisted artifacts.
@@ -49,22 +163,149 @@ pub struct BacktestResult {
     #[serde(default = "default_schema_version")]
     pub schema_version: u32,
     pub metrics: PerformanceMetrics,
+    /// Metrics without the end-of-data trades (positions still open when
+    /// the data ran out). None when every trade was a strategy exit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_position_adjusted: Option<PerformanceMetrics>,
     pub trades: Vec<TradeRecord>,
     pub equity_curve: Vec<f64>,
+    /// Date of each bar of `equity_curve`. Empty for results saved before
+    /// it was recorded, which cannot be sliced by date.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dates: Vec<NaiveDate>,
+    /// Gross exposure at each bar of `equity_curve`, as a fraction of
+    /// equity. Empty for results saved before it was recorded.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exposure_curve: Vec<f64>,
     pub config: StrategyConfig,
     pub symbol: String,
+    /// Bar interval of the run, inferred from its timeline. Decides how the
+    /// metrics are annualized.
+    #[serde(default)]
+    pub interval: BarInterval,
     pub start_date: String,
     pub end_date: String,
     pub initial_capital: f64,
     pub dataset_hash: String,
     pub has_synthetic: bool,
     pub signal_count: usize,
+    /// True when the run closed no trades (a filter that never passes, or a
+    /// warmup covering the whole range); an end-of-data trade alone does not
+    /// count. Its metrics are then neutral: zero
+    /// returns and ratios, which the promotion ladder and YOLO treat as "no
+    /// evidence" rather than as a flat strategy.
+    #[serde(default)]
+    pub zero_trades: bool,
     pub bar_count: usize,
     pub warmup_bars: usize,
-    pub void_bar_rates: HashMap<String, f64>,
-    pub data_quality_warnings: Vec<String>,
+    /// How `warmup_bars` was derived: each indicator's lookback, the
+    /// multiplier or override, and the bars left to trade. None for results
+    /// saved before it was recorded.
+    #[serde(default)]
+    pub warmup: Option<WarmupReport>,
+    pub void_bar_rates: BTreeMap<String, f64>,
+    /// Structured data quality findings (void-bar rate and load-time anomalies).
+    #[serde(default, deserialize_with = "deserialize_warnings")]
+    pub data_quality_warnings: Vec<DataQualityWarning>,
     /// Stickiness diagnostics from the position manager (None if zero trades).
+    /// This is the value the leaderboards aggregate.
     pub stickiness: Option<StickinessMetrics>,
+    /// Per-position stickiness scores behind `stickiness`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub position_stickiness: Vec<PositionStickiness>,
+    /// R-multiple distribution over trades with a recorded initial stop.
+    #[serde(default)]
+    pub r_distribution: RDistribution,
+    /// Binned trade returns and holding periods for the summary histograms.
+    #[serde(default)]
+    pub trade_distributions: TradeDistributions,
+    /// Tail risk of the daily returns, including the worst-days table.
+    #[serde(default)]
+    pub tail_metrics: Option<TailMetrics>,
+    /// Set when the cached data ended well before the requested end date.
+    #[serde(default)]
+    pub truncated_range: Option<TruncatedRange>,
+    /// Factor betas from returns-based style analysis. Buy-and-hold of the
+    /// traded symbol by default; `[style]` factors are added in single runs.
+    #[serde(default)]
+    pub style: Option<StyleReport>,
+    /// Equity curve downsampled to [`SPARKLINE_POINTS`] for leaderboard rows.
+    #[serde(default)]
+    pub equity_sparkline: Vec<f64>,
+    /// PnL per bar split by the decision behind each fill. Sums to the
+    /// trades' net PnL plus any open position marked at the last close.
+    #[serde(default, skip_serializing_if = "PnlAttribution::is_empty")]
+    pub attribution: PnlAttribution,
+    /// Stop updates the engine's ratchet tightened back to the active stop
+    /// (a position manager asked to loosen it). Empty when none were clamped.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ratchet_clamps: Vec<RatchetClamp>,
+    /// What `verify-run` needs to re-run this result. None for runs with a
+    /// custom execution config and for manifests saved before it was recorded.
+    #[serde(default)]
+    pub inputs: Option<RunInputs>,
+    /// Violations found by a lenient consistency check (see
+    /// [`crate::consistency`]). Empty when the run reconciles or wasn't checked.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub consistency_warnings: Vec<String>,
+    /// Vintage of each cache partition the run's bars came from, for
+    /// `verify-run --as-of`. Empty for runs on synthetic or pre-loaded data
+    /// and for manifests saved before it was recorded.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub data_vintage: Vec<PartitionVintage>,
+    /// Parent config and edited params, for manual variants made with
+    /// [`run_modified`]. None for every other run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Provenance>,
+    /// Engine phase timings. Wall-clock noise, so never persisted.
+    #[serde(skip)]
+    pub timings: EngineTimings,
+    /// Bar-by-bar replay of each position, recorded only by
+    /// [`run_backtest_replayed`]. Kept out of the manifest; artifacts store
+    /// it in `diagnostics.json`.
+    #[serde(skip)]
+    pub replay: ReplayLog,
+    /// Portfolio snapshots on the first, last and every fill bar, recorded
+    /// only by [`run_backtest_replayed`]. Kept out of the manifest; artifacts
+    /// store them in `portfolio.parquet`.
+    #[serde(skip)]
+    pub portfolio_snapshots: SnapshotLog,
+}
+
+impl BacktestResult {
+    /// Metrics of the exits the strategy chose: [`Self::open_position_adjusted`]
+    /// when the run ended holding a position, else the headline metrics.
+    pub fn closed_trade_metrics(&self) -> &PerformanceMetrics {
+        self.open_position_adjusted
+            .as_ref()
+            .unwrap_or(&self.metrics)
+    }
+
+    /// Run-level stickiness together with its per-position scores.
+    pub fn stickiness_report(&self) -> StickinessReport {
+        StickinessReport {
+            run: self.stickiness.clone(),
+            positions: self.position_stickiness.clone(),
+        }
+    }
+}
+
+/// Run settings beyond the `StrategyConfig` that decide a backtest's outcome.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunInputs {
+    pub trading_mode: TradingMode,
+    pub position_size_pct: f64,
+    pub execution_preset: ExecutionPreset,
+    /// Symbols loaded alongside the traded one. Their dates together make up
+    /// the run's timeline, and the dataset hash covers all of them.
+    pub symbols: Vec<String>,
+    /// Per-symbol friction override applied on top of `execution_preset`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_profile: Option<ExecutionProfile>,
+    /// Risk profile whose overrides were applied to the config. The config
+    /// already carries them, so a replay doesn't apply them again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk_profile: Option<RiskProfile>,
 }
 
 /// Default schema version for serde deserialization of older JSON without the field.
@@ -75,19 +316,50 @@ fn default_schema_version() -> u32 {
 /// Run a single backtest from a BacktestConfig (loads data from cache).
 ///
 /// This is the high-level entry point used by the CLI. For pre-loaded data
-/// (YOLO mode), use `run_backtest_from_data()` instead.
+/// (YOLO mode), use `run_backtest_from_data()` instead. `provider` is only
+/// consulted on a cache miss, and never when `opts.offline` is set.
+///
+/// The config is validated first; any errors come back together as
+/// [`ConfigError::Invalid`] before data is loaded. Once the bars are loaded,
+/// a warmup that leaves fewer than `min_tradable_bars` of them is
+/// [`ConfigError::InsufficientBars`].
 pub fn run_single_backtest(
     config: &BacktestConfig,
     cache: &ParquetCache,
     provider: Option<&dyn DataProvider>,
     opts: &LoadOptions,
 ) -> Result<BacktestResult, RunError> {
+    // Missing symbols are left to the loader, which reports them as DataLoad
+    config.validate(None).into_result()?;
+
     let symbol = &config.backtest.symbol;
-    let loaded = load_bars(&[symbol.as_str()], cache, provider, None, opts)?;
+    let loaded = load_bars(&[symbol.as_str()], cache, provider, None, opts).map_err(|source| {
+        RunError::DataLoad {
+            symbol: symbol.clone(),
+            source,
+        }
+    })?;
     let strategy_config = config.to_strategy_config();
     let preset = decode_execution_preset(&config.execution_model.params);
 
-    run_backtest_from_data(
+    let composition = build_composition(&strategy_config, config.trading_mode())?;
+    let warmup = WarmupPolicy::from_params(&strategy_config.signal.params).report(
+        &composition.indicators,
+        0,
+        loaded.aligned.dates.len(),
+    );
+    let minimum = config.min_tradable_bars();
+    if warmup.tradable_bars < minimum {
+        return Err(ConfigError::InsufficientBars {
+            bar_count: loaded.aligned.dates.len(),
+            warmup: warmup.applied,
+            tradable_bars: warmup.tradable_bars,
+            minimum,
+        }
+        .into());
+    }
+
+    let mut result = run_profiled(
         &strategy_config,
         &loaded.aligned,
         symbol,
@@ -95,15 +367,124 @@ pub fn run_single_backtest(
         config.backtest.initial_capital,
         config.backtest.position_size_pct,
         preset,
+        None,
         &loaded.dataset_hash,
         loaded.has_synthetic,
-    )
+        None,
+        true,
+        config.consistency_check(),
+    )?;
+    result
+        .data_quality_warnings
+        .extend(loaded.warnings_for(symbol).cloned());
+    result.truncated_range = loaded.truncated_range(symbol).cloned();
+    result.data_vintage = loaded.vintage.clone();
+    if let Some(inputs) = result.inputs.as_mut() {
+        inputs.risk_profile = config.risk_profile;
+    }
+
+    if let Some(style) = config.style.as_ref().filter(|s| !s.factors.is_empty()) {
+        let mut factors = vec![FactorSeries::from_bars(
+            symbol.as_str(),
+            &loaded.aligned.bars[symbol],
+        )];
+        factors.extend(
+            load_factors(&style.factors, cache, provider, opts)
+                .map_err(|err| RunError::from_style(err, symbol))?,
+        );
+        // Keep the buy-and-hold report if the extra factors share too few dates
+        if let Ok(report) = style_analysis(&result, &loaded.aligned.dates, &factors) {
+            result.style = Some(report);
+        }
+    }
+    Ok(result)
+}
+
+/// Re-run `base` with `config` in place of its strategy.
+///
+/// Everything else comes from `base`: its symbols, date range, bar interval,
+/// capital, sizing and execution, with bars read from `cache` and never
+/// downloaded. `config` is checked with [`validate_strategy`] first, so a
+/// broken cross-parameter constraint is [`ConfigError::Invalid`] rather than
+/// a panic in a constructor. The result records `base`'s config as its
+/// parent in `provenance`.
+pub fn run_modified(
+    base: &BacktestResult,
+    config: &StrategyConfig,
+    cache: &ParquetCache,
+) -> Result<BacktestResult, RunError> {
+    validate_strategy(config).into_result()?;
+    let Some(inputs) = &base.inputs else {
+        let mut report = ValidationReport::default();
+        report.error(
+            "inputs",
+            "the base run does not record its run inputs; run it again first".into(),
+        );
+        return Err(ConfigError::Invalid(report).into());
+    };
+    let mut report = ValidationReport::default();
+    let start = parse_date(&mut report, "start_date", &base.start_date);
+    let end = parse_date(&mut report, "end_date", &base.end_date);
+    let (Some(start), Some(end)) = (start, end) else {
+        return Err(ConfigError::Invalid(report).into());
+    };
+
+    let symbols: Vec<&str> = inputs.symbols.iter().map(String::as_str).collect();
+    let opts = LoadOptions {
+        start,
+        end,
+        offline: true,
+        synthetic: false,
+        force: false,
+        strict: false,
+        stale_after_days: None,
+        interval: base.interval,
+    };
+    let loaded =
+        load_bars(&symbols, cache, None, None, &opts).map_err(|source| RunError::DataLoad {
+            symbol: base.symbol.clone(),
+            source,
+        })?;
+    // The cache may have grown past the base run's range
+    let dates = &loaded.aligned.dates;
+    let aligned = slice_aligned_data(
+        &loaded.aligned,
+        dates.partition_point(|d| *d < start),
+        dates.partition_point(|d| *d <= end),
+    );
+
+    let mut result = run_profiled(
+        config,
+        &aligned,
+        &base.symbol,
+        inputs.trading_mode,
+        base.initial_capital,
+        inputs.position_size_pct,
+        inputs.execution_preset,
+        inputs.execution_profile.as_ref(),
+        &compute_dataset_hash(&aligned),
+        loaded.has_synthetic,
+        None,
+        true,
+        ConsistencyCheck::default(),
+    )?;
+    result
+        .data_quality_warnings
+        .extend(loaded.warnings_for(&base.symbol).cloned());
+    result.truncated_range = loaded.truncated_range(&base.symbol).cloned();
+    result.data_vintage = loaded.vintage;
+    if let Some(modified) = result.inputs.as_mut() {
+        modified.risk_profile = inputs.risk_profile;
+    }
+    result.provenance = Some(Provenance::new(&base.config, config));
+    Ok(result)
 }
 
 /// Run a backtest with pre-loaded data — no I/O.
 ///
 /// Used by YOLO mode to avoid re-reading Parquet on every iteration.
 /// The `aligned` data may contain multiple symbols; only `symbol` is used.
+/// Commission params on the execution model override the preset's bps rate.
 #[allow(clippy::too_many_arguments)]
 pub fn run_backtest_from_data(
     strategy_config: &StrategyConfig,
@@ -116,19 +497,171 @@ pub fn run_backtest_from_data(
     dataset_hash: &str,
     has_synthetic: bool,
 ) -> Result<BacktestResult, RunError> {
-    run_backtest_with_exec_config(
+    run_backtest_profiled(
+        strategy_config,
+        aligned,
+        symbol,
+        trading_mode,
+        initial_capital,
+        position_size_pct,
+        execution_preset,
+        None,
+        dataset_hash,
+        has_synthetic,
+    )
+}
+
+/// Run a backtest with pre-loaded data, recording a bar-by-bar replay of
+/// every position in `BacktestResult::replay` and the portfolio on every
+/// fill bar in `BacktestResult::portfolio_snapshots`.
+///
+/// Same as `run_backtest_from_data` otherwise. Used for single runs, whose
+/// trades are inspected one at a time; sweeps skip the recording.
+#[allow(clippy::too_many_arguments)]
+pub fn run_backtest_replayed(
+    strategy_config: &StrategyConfig,
+    aligned: &AlignedData,
+    symbol: &str,
+    trading_mode: TradingMode,
+    initial_capital: f64,
+    position_size_pct: f64,
+    execution_preset: ExecutionPreset,
+    dataset_hash: &str,
+    has_synthetic: bool,
+) -> Result<BacktestResult, RunError> {
+    run_profiled(
+        strategy_config,
+        aligned,
+        symbol,
+        trading_mode,
+        initial_capital,
+        position_size_pct,
+        execution_preset,
+        None,
+        dataset_hash,
+        has_synthetic,
+        None,
+        true,
+        ConsistencyCheck::default(),
+    )
+}
+
+/// Run a backtest with pre-loaded data under a per-symbol execution profile.
+///
+/// Same as `run_backtest_from_data` when `profile` is None. Otherwise the
+/// profile's overrides replace the preset's friction for this run, and the
+/// profile is recorded in the result's `inputs`.
+#[allow(clippy::too_many_arguments)]
+pub fn run_backtest_profiled(
+    strategy_config: &StrategyConfig,
+    aligned: &AlignedData,
+    symbol: &str,
+    trading_mode: TradingMode,
+    initial_capital: f64,
+    position_size_pct: f64,
+    execution_preset: ExecutionPreset,
+    profile: Option<&ExecutionProfile>,
+    dataset_hash: &str,
+    has_synthetic: bool,
+) -> Result<BacktestResult, RunError> {
+    run_profiled(
+        strategy_config,
+        aligned,
+        symbol,
+        trading_mode,
+        initial_capital,
+        position_size_pct,
+        execution_preset,
+        profile,
+        dataset_hash,
+        has_synthetic,
+        None,
+        false,
+        ConsistencyCheck::default(),
+    )
+}
+
+/// Same as [`run_backtest_profiled`], reusing indicator series from `cache`.
+///
+/// Used by YOLO mode, where every iteration runs on the same bars. The cache
+/// must only ever see this `aligned` dataset.
+#[allow(clippy::too_many_arguments)]
+pub fn run_backtest_profiled_cached(
+    strategy_config: &StrategyConfig,
+    aligned: &AlignedData,
+    symbol: &str,
+    trading_mode: TradingMode,
+    initial_capital: f64,
+    position_size_pct: f64,
+    execution_preset: ExecutionPreset,
+    profile: Option<&ExecutionProfile>,
+    dataset_hash: &str,
+    has_synthetic: bool,
+    cache: &mut IndicatorCache,
+) -> Result<BacktestResult, RunError> {
+    run_profiled(
         strategy_config,
         aligned,
         symbol,
         trading_mode,
         initial_capital,
         position_size_pct,
-        ExecutionConfig::from_preset(execution_preset),
+        execution_preset,
+        profile,
         dataset_hash,
         has_synthetic,
+        Some(cache),
+        false,
+        ConsistencyCheck::default(),
     )
 }
 
+#[allow(clippy::too_many_arguments)]
+fn run_profiled(
+    strategy_config: &StrategyConfig,
+    aligned: &AlignedData,
+    symbol: &str,
+    trading_mode: TradingMode,
+    initial_capital: f64,
+    position_size_pct: f64,
+    execution_preset: ExecutionPreset,
+    profile: Option<&ExecutionProfile>,
+    dataset_hash: &str,
+    has_synthetic: bool,
+    cache: Option<&mut IndicatorCache>,
+    record_replay: bool,
+    check: ConsistencyCheck,
+) -> Result<BacktestResult, RunError> {
+    let params = &strategy_config.execution_model.params;
+    let exec_config = match profile {
+        Some(profile) => profile.execution_config(execution_preset, params),
+        None => ExecutionConfig::from_preset(execution_preset).with_params(params),
+    };
+    let mut result = run_backtest_inner(
+        strategy_config,
+        aligned,
+        symbol,
+        trading_mode,
+        initial_capital,
+        position_size_pct,
+        exec_config,
+        dataset_hash,
+        has_synthetic,
+        cache,
+        record_replay,
+        check,
+    )?;
+    result.inputs = Some(RunInputs {
+        trading_mode,
+        position_size_pct,
+        execution_preset,
+        symbols: aligned.symbols.clone(),
+        execution_profile: profile.cloned(),
+        risk_profile: None,
+    });
+    Ok(result)
+}
+
 /// Run a backtest with pre-loaded data and an explicit ExecutionConfig.
 ///
 /// Used by execution Monte Carlo to test varying slippage/commission parameters.
@@ -144,10 +677,112 @@ pub fn run_backtest_with_exec_config(
     exec_config: ExecutionConfig,
     dataset_hash: &str,
     has_synthetic: bool,
+) -> Result<BacktestResult, RunError> {
+    run_backtest_inner(
+        strategy_config,
+        aligned,
+        symbol,
+        trading_mode,
+        initial_capital,
+        position_size_pct,
+        exec_config,
+        dataset_hash,
+        has_synthetic,
+        None,
+        false,
+        ConsistencyCheck::default(),
+    )
+}
+
+/// Run a backtest with pre-loaded data, reusing indicator series from `cache`.
+///
+/// Used by walk-forward optimization, where every grid point runs on the same
+/// slice. The cache must only ever see this `aligned` dataset.
+#[allow(clippy::too_many_arguments)]
+pub fn run_backtest_cached(
+    strategy_config: &StrategyConfig,
+    aligned: &AlignedData,
+    symbol: &str,
+    trading_mode: TradingMode,
+    initial_capital: f64,
+    position_size_pct: f64,
+    execution_preset: ExecutionPreset,
+    dataset_hash: &str,
+    cache: &mut IndicatorCache,
+) -> Result<BacktestResult, RunError> {
+    run_backtest_inner(
+        strategy_config,
+        aligned,
+        symbol,
+        trading_mode,
+        initial_capital,
+        position_size_pct,
+        ExecutionConfig::from_preset(execution_preset)
+            .with_params(&strategy_config.execution_model.params),
+        dataset_hash,
+        false,
+        Some(cache),
+        false,
+        ConsistencyCheck::default(),
+    )
+}
+
+/// Run a backtest with an explicit ExecutionConfig, reusing indicator series
+/// from `cache`.
+///
+/// Used by the friction ramp, where only costs change between runs. The cache
+/// must only ever see this `aligned` dataset.
+#[allow(clippy::too_many_arguments)]
+pub fn run_backtest_with_exec_config_cached(
+    strategy_config: &StrategyConfig,
+    aligned: &AlignedData,
+    symbol: &str,
+    trading_mode: TradingMode,
+    initial_capital: f64,
+    position_size_pct: f64,
+    exec_config: ExecutionConfig,
+    dataset_hash: &str,
+    cache: &mut IndicatorCache,
+) -> Result<BacktestResult, RunError> {
+    run_backtest_inner(
+        strategy_config,
+        aligned,
+        symbol,
+        trading_mode,
+        initial_capital,
+        position_size_pct,
+        exec_config,
+        dataset_hash,
+        false,
+        Some(cache),
+        false,
+        ConsistencyCheck::default(),
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_backtest_inner(
+    strategy_config: &StrategyConfig,
+    aligned: &AlignedData,
+    symbol: &str,
+    trading_mode: TradingMode,
+    initial_capital: f64,
+    position_size_pct: f64,
+    exec_config: ExecutionConfig,
+    dataset_hash: &str,
+    has_synthetic: bool,
+    cache: Option<&mut IndicatorCache>,
+    record_replay: bool,
+    check: ConsistencyCheck,
 ) -> Result<BacktestResult, RunError> {
     // Verify symbol exists in aligned data
     if !aligned.bars.contains_key(symbol) {
-        return Err(RunError::SymbolNotFound(symbol.to_string()));
+        return Err(RunError::DataLoad {
+            symbol: symbol.to_string(),
+            source: LoadError::NotLoaded {
+                symbol: symbol.to_string(),
+            },
+        });
     }
 
     // Extract single-symbol AlignedData for the engine
@@ -164,21 +799,81 @@ pub fn run_backtest_with_exec_config(
     );
     engine_config.trading_mode = trading_mode;
     engine_config.position_size_pct = position_size_pct;
+    engine_config.entry_governance = EntryGovernance::from_params(&strategy_config.signal.params);
+    engine_config.risk_guard = RiskGuard::from_params(&strategy_config.signal.params);
+    engine_config.warmup = WarmupPolicy::from_params(&strategy_config.signal.params);
+    engine_config.record_replay = record_replay;
+    if record_replay {
+        engine_config.portfolio_snapshots = SnapshotMode::OnChange;
+    }
+    if let Some(quantization) = Quantization::from_params(&strategy_config.execution_model.params) {
+        engine_config.instruments.insert(
+            symbol.to_string(),
+            Instrument::us_equity(symbol).with_quantization(quantization),
+        );
+    }
 
     // Run the bar-by-bar event loop
-    let result = run_backtest(
-        &single_aligned,
-        &composition.indicators,
-        &engine_config,
-        composition.signal.as_ref(),
-        composition.filter.as_ref(),
-        composition.execution.as_ref(),
-        composition.pm.as_ref(),
-    );
+    let result = match cache {
+        Some(cache) => run_engine_cached(
+            &single_aligned,
+            &composition.indicators,
+            &engine_config,
+            composition.signal.as_ref(),
+            composition.filter.as_ref(),
+            composition.execution.as_ref(),
+            composition.pm.as_ref(),
+            cache,
+        ),
+        None => run_backtest(
+            &single_aligned,
+            &composition.indicators,
+            &engine_config,
+            composition.signal.as_ref(),
+            composition.filter.as_ref(),
+            composition.execution.as_ref(),
+            composition.pm.as_ref(),
+        ),
+    };
+
+    if result.equity_curve.len() != single_aligned.dates.len() {
+        return Err(RunError::EngineInvariant {
+            symbol: symbol.to_string(),
+            reason: format!(
+                "equity curve has {} points for {} bars",
+                result.equity_curve.len(),
+                single_aligned.dates.len()
+            ),
+        });
+    }
+
+    let inconsistencies = check.apply(
+        symbol,
+        initial_capital,
+        &result.equity_curve,
+        &result.fills,
+        &result.trades,
+    )?;
 
-    // Compute metrics
-    let metrics =
-        PerformanceMetrics::compute(&result.equity_curve, &result.trades, initial_capital);
+    // Compute metrics, annualized for the bar interval. Zero-trade runs
+    // get neutral metrics
+    let interval = BarInterval::infer(&single_aligned.dates);
+    let (metrics, open_position_adjusted, zero_trades) = run_metrics(
+        &result.equity_curve,
+        &result.exposure_curve,
+        &result.trades,
+        initial_capital,
+        interval,
+    );
+    let r_distribution = RDistribution::compute(&result.trades);
+    let trade_distributions = TradeDistributions::compute(&result.trades);
+    let mut tail_metrics = compute_tail_metrics(&result.equity_curve);
+    tail_metrics.worst_days = worst_days(
+        &result.equity_curve,
+        &single_aligned.dates,
+        &result.trades,
+        DEFAULT_WORST_DAYS,
+    );
 
     // Annotate trades with component names
     let mut trades = result.trades;
@@ -201,29 +896,103 @@ pub fn run_backtest_with_exec_config(
         .map(|d| d.to_string())
         .unwrap_or_default();
 
-    Ok(BacktestResult {
+    let sparkline = equity_sparkline(&result.equity_curve, SPARKLINE_POINTS);
+    let mut backtest = BacktestResult {
         schema_version: SCHEMA_VERSION,
         metrics,
+        open_position_adjusted,
         trades,
         equity_curve: result.equity_curve,
+        dates: single_aligned.dates.clone(),
+        exposure_curve: result.exposure_curve,
         config: strategy_config.clone(),
         symbol: symbol.to_string(),
+        interval,
         start_date,
         end_date,
         initial_capital,
         dataset_hash: dataset_hash.to_string(),
         has_synthetic,
         signal_count: result.signal_count,
+        zero_trades,
         bar_count: result.bar_count,
         warmup_bars: result.warmup_bars,
+        warmup: Some(result.warmup),
         void_bar_rates: result.void_bar_rates,
-        data_quality_warnings: result.data_quality_warnings,
-        stickiness: result.stickiness,
-    })
+        data_quality_warnings: result
+            .data_quality_warnings
+            .into_iter()
+            .map(|msg| {
+                // Engine messages are prefixed "{symbol}: "; the struct carries it already
+                let details = msg.strip_prefix(&format!("{symbol}: ")).unwrap_or(&msg);
+                DataQualityWarning::new(AnomalyKind::VoidBarRate, None, symbol, details)
+            })
+            .collect(),
+        stickiness: result.stickiness.run,
+        position_stickiness: result.stickiness.positions,
+        r_distribution,
+        trade_distributions,
+        tail_metrics: Some(tail_metrics),
+        truncated_range: None,
+        style: None,
+        equity_sparkline: sparkline,
+        attribution: result.attribution,
+        ratchet_clamps: result.ratchet_clamps,
+        inputs: None,
+        consistency_warnings: inconsistencies.iter().map(ToString::to_string).collect(),
+        data_vintage: Vec::new(),
+        provenance: None,
+        timings: result.timings,
+        replay: result.replay,
+        portfolio_snapshots: result.portfolio_snapshots,
+    };
+    let buy_and_hold = FactorSeries::from_bars(symbol, &single_aligned.bars[symbol]);
+    backtest.style = style_analysis(&backtest, &single_aligned.dates, &[buy_and_hold]).ok();
+    Ok(backtest)
+}
+
+/// Headline metrics, the metrics without end-of-data trades (when there
+/// are any) and whether the run closed no trades, in which case the
+/// headline metrics are neutral.
+pub(crate) fn run_metrics(
+    equity_curve: &[f64],
+    exposure_curve: &[f64],
+    trades: &[TradeRecord],
+    initial_capital: f64,
+    interval: BarInterval,
+) -> (PerformanceMetrics, Option<PerformanceMetrics>, bool) {
+    let zero_trades = trades.iter().all(|t| t.liquidated_at_end);
+    let metrics = if zero_trades {
+        PerformanceMetrics::neutral()
+    } else {
+        PerformanceMetrics::compute_annualized(
+            equity_curve,
+            trades,
+            initial_capital,
+            interval.periods_per_year(),
+        )
+        .with_exposure(equity_curve, exposure_curve)
+    };
+    let open_position_adjusted =
+        (!zero_trades && trades.iter().any(|t| t.liquidated_at_end)).then(|| {
+            let closed: Vec<TradeRecord> = trades
+                .iter()
+                .filter(|t| !t.liquidated_at_end)
+                .cloned()
+                .collect();
+            PerformanceMetrics::compute_annualized(
+                equity_curve,
+                &closed,
+                initial_capital,
+                interval.periods_per_year(),
+            )
+            .with_exposure(equity_curve, exposure_curve)
+        });
+    (metrics, open_position_adjusted, zero_trades)
 }
 
 /// Extract a single symbol's data from a multi-symbol AlignedData.
-fn extract_single_symbol(aligned: &AlignedData, symbol: &str) -> AlignedData {
+pub(crate) fn extract_single_symbol(aligned: &AlignedData, symbol: &str) -> AlignedData {
     let bars = aligned.bars.get(symbol).cloned().unwrap_or_default();
 
     let mut bar_map = HashMap::new();
@@ -280,4 +1049,41 @@ mod tests {
             ExecutionPreset::Hostile as u8
         );
     }
+
+    #[test]
+    fn run_error_taxonomy() {
+        let download = RunError::DataLoad {
+            symbol: "SPY".into(),
+            source: LoadError::DownloadFailed {
+                symbol: "SPY".into(),
+                reason: "timeout".into(),
+            },
+        };
+        assert_eq!(download.kind(), RunErrorKind::DataLoad);
+        assert!(download.is_retryable());
+        assert_eq!(download.symbol(), Some("SPY"));
+
+        let missing = RunError::DataLoad {
+            symbol: "XYZ".into(),
+            source: LoadError::NotLoaded {
+                symbol: "XYZ".into(),
+            },
+        };
+        assert!(!missing.is_retryable());
+
+        let config = RunError::ConfigInvalid(ConfigError::Parse("bad".into()));
+        assert_eq!(config.kind(), RunErrorKind::ConfigInvalid);
+        assert!(!config.is_retryable());
+        assert_eq!(config.symbol(), None);
+
+        let csv = RunError::from_style(
+            StyleError::Csv {
+                path: "f.csv".into(),
+                reason: "missing column".into(),
+            },
+            "SPY",
+        );
+        assert_eq!(csv.kind(), RunErrorKind::ArtifactIo);
+        assert!(csv.is_retryable());
+    }
 }
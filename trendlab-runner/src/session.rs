@@ -0,0 +1,268 @@
+//! YOLO sessions — persist a finished `YoloResult` and browse past sessions.
+//!
+//! A session directory sits next to the run's JSONL history and contains:
+//! - `session.json` — the `YoloResult` (config, leaderboards, convergence)
+//! - `runs/{symbol}_{rank}/` — full artifacts for the top entries of each
+//!   per-symbol leaderboard (see [`crate::export::save_artifacts`])
+//!
+//! Leaderboard entries in `session.json` are stored without their trades and
+//! equity curves; an entry's `artifact_dir` points at the saved run instead.
+//! On load, artifact directories that no longer exist are cleared, so the
+//! leaderboard metrics stay browsable while drill-down is unavailable.
+//!
+//! When the session kept leaderboard files (see
+//! [`crate::leaderboard_store`]), saving points their entries from this
+//! session at the saved artifacts.
+
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use thiserror::Error;
+
+use crate::export::{load_artifacts, write_artifacts};
+use crate::history::FileLock;
+use crate::leaderboard_store::StoredLeaderboard;
+use crate::yolo::YoloResult;
+
+/// File name of the serialized `YoloResult` inside a session directory.
+pub const SESSION_FILE: &str = "session.json";
+
+/// Subdirectory of a session holding per-entry artifact directories.
+pub const SESSION_RUNS_DIR: &str = "runs";
+
+/// Number of entries per symbol whose full artifacts are saved.
+pub const SESSION_ARTIFACTS_PER_SYMBOL: usize = 10;
+
+/// Errors from saving or loading a session.
+#[derive(Debug, Error)]
+pub enum SessionError {
+    #[error("session I/O on {path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("session file {path}: {source}")]
+    Json {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[error("artifacts for {dir}: {reason}")]
+    Artifacts { dir: PathBuf, reason: String },
+}
+
+impl YoloResult {
+    /// Persist this result as a session under `dir`, normally the directory
+    /// of the session's history file.
+    ///
+    /// Saves full artifacts for the top [`SESSION_ARTIFACTS_PER_SYMBOL`]
+    /// entries of each symbol, then writes [`SESSION_FILE`]. Returns the path
+    /// of the session file.
+    pub fn save(&self, dir: &Path) -> Result<PathBuf, SessionError> {
+        fs::create_dir_all(dir).map_err(|source| SessionError::Io {
+            path: dir.to_path_buf(),
+            source,
+        })?;
+
+        let mut stored = self.clone();
+        for (symbol, lb) in stored.leaderboards.iter_mut() {
+            for (i, entry) in lb.entries_mut().iter_mut().enumerate() {
+                if i < SESSION_ARTIFACTS_PER_SYMBOL {
+                    let relative =
+                        Path::new(SESSION_RUNS_DIR).join(format!("{symbol}_{:03}", i + 1));
+                    let run_dir = dir.join(&relative);
+                    write_artifacts(&entry.result, &run_dir).map_err(|e| {
+                        SessionError::Artifacts {
+                            dir: run_dir.clone(),
+                            reason: format!("{e:#}"),
+                        }
+                    })?;
+                    entry.artifact_dir = Some(relative);
+                } else {
+                    entry.artifact_dir = None;
+                }
+                entry.result.trades = Vec::new();
+                entry.result.equity_curve = Vec::new();
+            }
+        }
+        if let Some(ref lb_dir) = self.config.leaderboard_dir {
+            stored.link_stored_artifacts(dir, lb_dir)?;
+        }
+
+        // Written to a temp file and renamed under the session lock, so
+        // readers and concurrent savers never see a half-written file.
+        let path = dir.join(SESSION_FILE);
+        let io_err = |source| SessionError::Io {
+            path: path.clone(),
+            source,
+        };
+        let _lock = FileLock::acquire(&path).map_err(io_err)?;
+        let tmp = dir.join(format!("{SESSION_FILE}.tmp"));
+        let file = File::create(&tmp).map_err(io_err)?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer(&mut writer, &stored).map_err(|source| SessionError::Json {
+            path: path.clone(),
+            source,
+        })?;
+        writer.flush().map_err(io_err)?;
+        fs::rename(&tmp, &path).map_err(io_err)?;
+        Ok(path)
+    }
+
+    /// Set the artifact directory of this session's entries in the
+    /// leaderboard files under `lb_dir`, for entries saved under `dir`.
+    fn link_stored_artifacts(&self, dir: &Path, lb_dir: &Path) -> Result<(), SessionError> {
+        for (symbol, lb) in &self.leaderboards {
+            let io_err = |source| SessionError::Io {
+                path: lb_dir.to_path_buf(),
+                source,
+            };
+            let Some(mut board) = StoredLeaderboard::load(lb_dir, symbol).map_err(io_err)? else {
+                continue;
+            };
+            let mut linked = false;
+            for entry in lb.entries() {
+                if let Some(ref relative) = entry.artifact_dir {
+                    linked |= board.set_artifact_dir(
+                        &entry.result.config.full_hash(),
+                        &self.session_id,
+                        dir.join(relative),
+                    );
+                }
+            }
+            if linked {
+                board.save(lb_dir).map_err(io_err)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Load a session saved by [`YoloResult::save`].
+    ///
+    /// `path` may be the session directory or its [`SESSION_FILE`]. Entry
+    /// artifact directories are resolved against the session directory;
+    /// those without a readable manifest are cleared.
+    pub fn load(path: &Path) -> Result<Self, SessionError> {
+        let path = if path.is_dir() {
+            path.join(SESSION_FILE)
+        } else {
+            path.to_path_buf()
+        };
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let file = File::open(&path).map_err(|source| SessionError::Io {
+            path: path.clone(),
+            source,
+        })?;
+        let mut result: YoloResult =
+            serde_json::from_reader(BufReader::new(file)).map_err(|source| SessionError::Json {
+                path: path.clone(),
+                source,
+            })?;
+
+        for lb in result.leaderboards.values_mut() {
+            for entry in lb.entries_mut() {
+                entry.artifact_dir = entry
+                    .artifact_dir
+                    .take()
+                    .map(|rel| dir.join(rel))
+                    .filter(|d| d.join("manifest.json").is_file());
+            }
+        }
+        Ok(result)
+    }
+
+    /// Restore the trades and equity curves of loaded entries from their
+    /// artifact directories, so a later [`YoloResult::save`] rewrites them
+    /// in full. Entries without artifacts are left as they are.
+    pub fn rehydrate_artifacts(&mut self) -> Result<(), SessionError> {
+        for lb in self.leaderboards.values_mut() {
+            for entry in lb.entries_mut() {
+                let Some(dir) = &entry.artifact_dir else {
+                    continue;
+                };
+                if !entry.result.equity_curve.is_empty() {
+                    continue;
+                }
+                let saved = load_artifacts(dir).map_err(|e| SessionError::Artifacts {
+                    dir: dir.clone(),
+                    reason: format!("{e:#}"),
+                })?;
+                entry.result.trades = saved.trades;
+                entry.result.equity_curve = saved.equity_curve;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Headline facts of a saved session, for listing.
+#[derive(Debug, Clone)]
+pub struct SessionSummary {
+    /// Session directory the summary was read from.
+    pub dir: PathBuf,
+    pub session_id: String,
+    /// Symbols in the session, sorted.
+    pub symbols: Vec<String>,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub iterations_completed: usize,
+    pub elapsed_secs: f64,
+    pub cross_entry_count: usize,
+    /// Best cross-symbol average Sharpe, if any config was ranked.
+    pub best_avg_sharpe: Option<f64>,
+    /// Leaderboard entries whose artifact directories are still on disk.
+    pub saved_artifacts: usize,
+}
+
+impl SessionSummary {
+    pub fn from_result(dir: PathBuf, result: &YoloResult) -> Self {
+        let mut symbols: Vec<String> = result.leaderboards.keys().cloned().collect();
+        symbols.sort();
+        let best_avg_sharpe = result
+            .cross_leaderboard
+            .entries()
+            .values()
+            .map(|e| e.avg_sharpe)
+            .filter(|s| s.is_finite())
+            .reduce(f64::max);
+        let saved_artifacts = result
+            .leaderboards
+            .values()
+            .flat_map(|lb| lb.entries())
+            .filter(|e| e.artifact_dir.is_some())
+            .count();
+        Self {
+            dir,
+            session_id: result.session_id.clone(),
+            symbols,
+            start_date: result.config.start_date,
+            end_date: result.config.end_date,
+            iterations_completed: result.iterations_completed,
+            elapsed_secs: result.elapsed_secs,
+            cross_entry_count: result.cross_leaderboard.len(),
+            best_avg_sharpe,
+            saved_artifacts,
+        }
+    }
+}
+
+/// Summaries of every session directory directly under `sessions_dir`,
+/// sorted by directory name.
+///
+/// Directories without a readable [`SESSION_FILE`] are skipped. A missing
+/// `sessions_dir` yields an empty list.
+pub fn scan_sessions(sessions_dir: &Path) -> Vec<SessionSummary> {
+    let Ok(entries) = fs::read_dir(sessions_dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.join(SESSION_FILE).is_file())
+        .collect();
+    dirs.sort();
+    dirs.into_iter()
+        .filter_map(|d| {
+            let result = YoloResult::load(&d).ok()?;
+            Some(SessionSummary::from_result(d, &result))
+        })
+        .collect()
+}
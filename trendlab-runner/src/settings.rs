@@ -0,0 +1,521 @@
+//! Layered settings — defaults shared by the CLI and the TUI.
+//!
+//! Each setting resolves, highest precedence first, from:
+//!
+//! 1. an explicit command-line flag ([`Sourced::or_flag`]),
+//! 2. a `TRENDLAB_*` environment variable,
+//! 3. the config file (`$TRENDLAB_CONFIG`, else
+//!    `~/.config/trendlab/config.toml`),
+//! 4. the built-in default.
+//!
+//! ```toml
+//! cache_dir = "/data/trendlab/cache"
+//! results_dir = "/data/trendlab/results"
+//! start_date = "2015-01-02"
+//! end_date = "2024-12-31"
+//! execution_preset = "realistic"
+//! symbols = ["SPY", "QQQ"]
+//! arrow_export = true
+//! drop_partial_last_bar = false
+//! ```
+//!
+//! A missing config file is not an error; a malformed one, or an env var
+//! that does not parse, is. Settings without a built-in value (dates,
+//! execution preset) leave the command's own default in place when unset.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+use thiserror::Error;
+
+use trendlab_core::components::execution::ExecutionPreset;
+
+use crate::config::DATE_FORMAT;
+
+/// Env var naming the config file, overriding the default location.
+pub const CONFIG_ENV: &str = "TRENDLAB_CONFIG";
+pub const CACHE_DIR_ENV: &str = "TRENDLAB_CACHE_DIR";
+pub const RESULTS_DIR_ENV: &str = "TRENDLAB_RESULTS_DIR";
+pub const START_DATE_ENV: &str = "TRENDLAB_START_DATE";
+pub const END_DATE_ENV: &str = "TRENDLAB_END_DATE";
+pub const EXECUTION_PRESET_ENV: &str = "TRENDLAB_EXECUTION_PRESET";
+/// Comma-separated, e.g. `SPY,QQQ`.
+pub const SYMBOLS_ENV: &str = "TRENDLAB_SYMBOLS";
+/// `true`/`false` (or `1`/`0`).
+pub const ARROW_EXPORT_ENV: &str = "TRENDLAB_ARROW_EXPORT";
+/// `true`/`false` (or `1`/`0`).
+pub const DROP_PARTIAL_LAST_BAR_ENV: &str = "TRENDLAB_DROP_PARTIAL_LAST_BAR";
+
+#[derive(Debug, Error)]
+pub enum SettingsError {
+    #[error("reading {path}: {source}")]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("parsing {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    #[error("{var}: {message}")]
+    Env { var: &'static str, message: String },
+}
+
+/// Where a setting's effective value came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettingSource {
+    Default,
+    ConfigFile(PathBuf),
+    Env(&'static str),
+}
+
+impl fmt::Display for SettingSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => f.write_str("default"),
+            Self::ConfigFile(path) => write!(f, "config file {}", path.display()),
+            Self::Env(var) => write!(f, "env {var}"),
+        }
+    }
+}
+
+/// A setting's value and its source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sourced<T> {
+    pub value: T,
+    pub source: SettingSource,
+}
+
+impl<T: Clone> Sourced<T> {
+    fn new(value: T) -> Self {
+        Self {
+            value,
+            source: SettingSource::Default,
+        }
+    }
+
+    fn set(&mut self, value: Option<T>, source: SettingSource) {
+        if let Some(value) = value {
+            self.value = value;
+            self.source = source;
+        }
+    }
+
+    /// The flag's value when one was given, else this setting's.
+    pub fn or_flag(&self, flag: Option<T>) -> T {
+        flag.unwrap_or_else(|| self.value.clone())
+    }
+}
+
+impl<T: Clone> Sourced<Option<T>> {
+    /// The flag's value when one was given, else this setting's (if set).
+    pub fn or_flag_opt(&self, flag: Option<T>) -> Option<T> {
+        flag.or_else(|| self.value.clone())
+    }
+}
+
+/// The config file's contents. Every key is optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SettingsFile {
+    pub cache_dir: Option<PathBuf>,
+    pub results_dir: Option<PathBuf>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    #[serde(default, deserialize_with = "deserialize_preset")]
+    pub execution_preset: Option<ExecutionPreset>,
+    pub symbols: Option<Vec<String>>,
+    pub arrow_export: Option<bool>,
+    pub drop_partial_last_bar: Option<bool>,
+}
+
+fn deserialize_preset<'de, D>(deserializer: D) -> Result<Option<ExecutionPreset>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let label = String::deserialize(deserializer)?;
+    label.parse().map(Some).map_err(serde::de::Error::custom)
+}
+
+/// Effective defaults after merging env vars, the config file and built-ins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    pub cache_dir: Sourced<PathBuf>,
+    pub results_dir: Sourced<PathBuf>,
+    pub start_date: Sourced<Option<NaiveDate>>,
+    pub end_date: Sourced<Option<NaiveDate>>,
+    pub execution_preset: Sourced<Option<ExecutionPreset>>,
+    /// Symbols used when a command is given none.
+    pub symbols: Sourced<Vec<String>>,
+    /// Also save each run's trades and bars as Arrow IPC files (see
+    /// [`crate::interop`]).
+    pub arrow_export: Sourced<bool>,
+    /// Drop a downloaded daily bar from a session still trading at fetch
+    /// time; it changes on the next fetch.
+    pub drop_partial_last_bar: Sourced<bool>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            cache_dir: Sourced::new(PathBuf::from("data")),
+            results_dir: Sourced::new(PathBuf::from("results")),
+            start_date: Sourced::new(None),
+            end_date: Sourced::new(None),
+            execution_preset: Sourced::new(None),
+            symbols: Sourced::new(Vec::new()),
+            arrow_export: Sourced::new(false),
+            drop_partial_last_bar: Sourced::new(true),
+        }
+    }
+}
+
+impl Settings {
+    /// Resolve from the process environment and the config file it points to.
+    pub fn load() -> Result<Self, SettingsError> {
+        let env = |var: &str| std::env::var(var).ok().filter(|v| !v.is_empty());
+        let path = env(CONFIG_ENV)
+            .map(PathBuf::from)
+            .or_else(default_config_path);
+        let file = match &path {
+            Some(path) => read_file(path)?.map(|file| (path.as_path(), file)),
+            None => None,
+        };
+        Self::resolve(file, env)
+    }
+
+    /// Merge `file` (with the path it came from) and `env` over the
+    /// built-in defaults. Env wins over the file.
+    pub fn resolve(
+        file: Option<(&Path, SettingsFile)>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, SettingsError> {
+        let mut settings = Self::default();
+
+        if let Some((path, file)) = file {
+            let source = SettingSource::ConfigFile(path.to_path_buf());
+            settings.cache_dir.set(file.cache_dir, source.clone());
+            settings.results_dir.set(file.results_dir, source.clone());
+            settings
+                .start_date
+                .set(file.start_date.map(Some), source.clone());
+            settings
+                .end_date
+                .set(file.end_date.map(Some), source.clone());
+            settings
+                .execution_preset
+                .set(file.execution_preset.map(Some), source.clone());
+            settings.symbols.set(file.symbols, source.clone());
+            settings.arrow_export.set(file.arrow_export, source.clone());
+            settings
+                .drop_partial_last_bar
+                .set(file.drop_partial_last_bar, source);
+        }
+
+        let var = |name: &'static str| env(name).map(|value| (name, value));
+        if let Some((name, value)) = var(CACHE_DIR_ENV) {
+            settings
+                .cache_dir
+                .set(Some(value.into()), SettingSource::Env(name));
+        }
+        if let Some((name, value)) = var(RESULTS_DIR_ENV) {
+            settings
+                .results_dir
+                .set(Some(value.into()), SettingSource::Env(name));
+        }
+        if let Some((name, value)) = var(START_DATE_ENV) {
+            let date = parse_date(name, &value)?;
+            settings
+                .start_date
+                .set(Some(Some(date)), SettingSource::Env(name));
+        }
+        if let Some((name, value)) = var(END_DATE_ENV) {
+            let date = parse_date(name, &value)?;
+            settings
+                .end_date
+                .set(Some(Some(date)), SettingSource::Env(name));
+        }
+        if let Some((name, value)) = var(EXECUTION_PRESET_ENV) {
+            let preset = value
+                .parse()
+                .map_err(|message| SettingsError::Env { var: name, message })?;
+            settings
+                .execution_preset
+                .set(Some(Some(preset)), SettingSource::Env(name));
+        }
+        if let Some((name, value)) = var(SYMBOLS_ENV) {
+            let symbols = value
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect();
+            settings
+                .symbols
+                .set(Some(symbols), SettingSource::Env(name));
+        }
+        if let Some((name, value)) = var(ARROW_EXPORT_ENV) {
+            let enabled = parse_bool(name, &value)?;
+            settings
+                .arrow_export
+                .set(Some(enabled), SettingSource::Env(name));
+        }
+        if let Some((name, value)) = var(DROP_PARTIAL_LAST_BAR_ENV) {
+            let enabled = parse_bool(name, &value)?;
+            settings
+                .drop_partial_last_bar
+                .set(Some(enabled), SettingSource::Env(name));
+        }
+
+        Ok(settings)
+    }
+
+    /// `(key, effective value, source)` for every setting, in file order.
+    pub fn rows(&self) -> Vec<(&'static str, String, &SettingSource)> {
+        fn or_unset<T: ToString>(value: &Option<T>) -> String {
+            value
+                .as_ref()
+                .map_or_else(|| "(unset)".to_string(), T::to_string)
+        }
+        let symbols = if self.symbols.value.is_empty() {
+            "(none)".to_string()
+        } else {
+            self.symbols.value.join(",")
+        };
+        vec![
+            (
+                "cache_dir",
+                self.cache_dir.value.display().to_string(),
+                &self.cache_dir.source,
+            ),
+            (
+                "results_dir",
+                self.results_dir.value.display().to_string(),
+                &self.results_dir.source,
+            ),
+            (
+                "start_date",
+                or_unset(&self.start_date.value),
+                &self.start_date.source,
+            ),
+            (
+                "end_date",
+                or_unset(&self.end_date.value),
+                &self.end_date.source,
+            ),
+            (
+                "execution_preset",
+                or_unset(&self.execution_preset.value.map(ExecutionPreset::label)),
+                &self.execution_preset.source,
+            ),
+            ("symbols", symbols, &self.symbols.source),
+            (
+                "arrow_export",
+                self.arrow_export.value.to_string(),
+                &self.arrow_export.source,
+            ),
+            (
+                "drop_partial_last_bar",
+                self.drop_partial_last_bar.value.to_string(),
+                &self.drop_partial_last_bar.source,
+            ),
+        ]
+    }
+}
+
+/// `~/.config/trendlab/config.toml` (or the platform's config directory).
+pub fn default_config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("trendlab").join("config.toml"))
+}
+
+/// Read and parse the config file; `None` if it does not exist.
+pub fn read_file(path: &Path) -> Result<Option<SettingsFile>, SettingsError> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(source) => {
+            return Err(SettingsError::Read {
+                path: path.to_path_buf(),
+                source,
+            })
+        }
+    };
+    toml::from_str(&text)
+        .map(Some)
+        .map_err(|source| SettingsError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+fn parse_date(var: &'static str, value: &str) -> Result<NaiveDate, SettingsError> {
+    NaiveDate::parse_from_str(value, DATE_FORMAT).map_err(|err| SettingsError::Env {
+        var,
+        message: format!("'{value}' is not a YYYY-MM-DD date: {err}"),
+    })
+}
+
+fn parse_bool(var: &'static str, value: &str) -> Result<bool, SettingsError> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "1" => Ok(true),
+        "false" | "0" => Ok(false),
+        _ => Err(SettingsError::Env {
+            var,
+            message: format!("'{value}' is not true or false"),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    fn file(text: &str) -> SettingsFile {
+        toml::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn defaults_without_file_or_env() {
+        let settings = Settings::resolve(None, env(&[])).unwrap();
+        assert_eq!(settings, Settings::default());
+        assert_eq!(settings.cache_dir.value, PathBuf::from("data"));
+        assert_eq!(settings.results_dir.value, PathBuf::from("results"));
+        assert_eq!(settings.cache_dir.source, SettingSource::Default);
+    }
+
+    #[test]
+    fn env_beats_file_beats_default() {
+        let path = Path::new("/etc/trendlab.toml");
+        let settings = Settings::resolve(
+            Some((
+                path,
+                file(
+                    r#"
+                    cache_dir = "file-cache"
+                    results_dir = "file-results"
+                    start_date = "2015-01-02"
+                    execution_preset = "hostile"
+                    symbols = ["QQQ"]
+                    arrow_export = true
+                    drop_partial_last_bar = true
+                    "#,
+                ),
+            )),
+            env(&[
+                (CACHE_DIR_ENV, "env-cache"),
+                (EXECUTION_PRESET_ENV, "optimistic"),
+                (SYMBOLS_ENV, "SPY, IWM,"),
+                (DROP_PARTIAL_LAST_BAR_ENV, "0"),
+            ]),
+        )
+        .unwrap();
+
+        let from_file = SettingSource::ConfigFile(path.to_path_buf());
+        assert_eq!(settings.cache_dir.value, PathBuf::from("env-cache"));
+        assert_eq!(settings.cache_dir.source, SettingSource::Env(CACHE_DIR_ENV));
+        assert_eq!(settings.results_dir.value, PathBuf::from("file-results"));
+        assert_eq!(settings.results_dir.source, from_file);
+        assert_eq!(
+            settings.start_date.value,
+            NaiveDate::from_ymd_opt(2015, 1, 2)
+        );
+        assert_eq!(settings.end_date.source, SettingSource::Default);
+        assert_eq!(
+            settings.execution_preset.value,
+            Some(ExecutionPreset::Optimistic)
+        );
+        assert_eq!(settings.symbols.value, vec!["SPY", "IWM"]);
+        assert!(settings.arrow_export.value);
+        assert_eq!(settings.arrow_export.source, from_file);
+        assert!(!settings.drop_partial_last_bar.value);
+        assert_eq!(
+            settings.drop_partial_last_bar.source,
+            SettingSource::Env(DROP_PARTIAL_LAST_BAR_ENV)
+        );
+    }
+
+    #[test]
+    fn flags_beat_everything() {
+        let settings = Settings::resolve(None, env(&[(RESULTS_DIR_ENV, "env-results")])).unwrap();
+        assert_eq!(
+            settings.results_dir.or_flag(Some(PathBuf::from("flag"))),
+            PathBuf::from("flag")
+        );
+        assert_eq!(
+            settings.results_dir.or_flag(None),
+            PathBuf::from("env-results")
+        );
+
+        let settings = Settings::resolve(None, env(&[(START_DATE_ENV, "2018-06-01")])).unwrap();
+        let flag = NaiveDate::from_ymd_opt(2020, 1, 2);
+        assert_eq!(settings.start_date.or_flag_opt(flag), flag);
+        assert_eq!(
+            settings.start_date.or_flag_opt(None),
+            NaiveDate::from_ymd_opt(2018, 6, 1)
+        );
+        assert_eq!(settings.end_date.or_flag_opt(None), None);
+    }
+
+    #[test]
+    fn bad_values_name_their_source() {
+        let err = Settings::resolve(None, env(&[(END_DATE_ENV, "yesterday")])).unwrap_err();
+        assert!(err.to_string().contains(END_DATE_ENV), "{err}");
+
+        let err = Settings::resolve(None, env(&[(EXECUTION_PRESET_ENV, "lenient")])).unwrap_err();
+        assert!(err.to_string().contains("lenient"), "{err}");
+
+        let err = Settings::resolve(None, env(&[(ARROW_EXPORT_ENV, "yes")])).unwrap_err();
+        assert!(err.to_string().contains(ARROW_EXPORT_ENV), "{err}");
+
+        assert!(toml::from_str::<SettingsFile>("cache = \"x\"").is_err());
+        assert!(toml::from_str::<SettingsFile>("execution_preset = \"lenient\"").is_err());
+    }
+
+    #[test]
+    fn missing_file_is_not_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(read_file(&dir.path().join("config.toml"))
+            .unwrap()
+            .is_none());
+
+        let path = dir.path().join("bad.toml");
+        std::fs::write(&path, "symbols = 3").unwrap();
+        let err = read_file(&path).unwrap_err();
+        assert!(matches!(err, SettingsError::Parse { .. }));
+    }
+
+    #[test]
+    fn rows_cover_every_setting() {
+        let settings = Settings::resolve(None, env(&[(SYMBOLS_ENV, "SPY")])).unwrap();
+        let rows = settings.rows();
+        let keys: Vec<&str> = rows.iter().map(|(k, _, _)| *k).collect();
+        assert_eq!(
+            keys,
+            [
+                "cache_dir",
+                "results_dir",
+                "start_date",
+                "end_date",
+                "execution_preset",
+                "symbols",
+                "arrow_export",
+                "drop_partial_last_bar"
+            ]
+        );
+        assert_eq!(rows[2].1, "(unset)");
+        assert_eq!(rows[5].1, "SPY");
+        assert_eq!(rows[5].2, &SettingSource::Env(SYMBOLS_ENV));
+    }
+}
@@ -0,0 +1,534 @@
+//! Shuffle test — the signal against randomized entries on the same prices.
+//!
+//! A strategy's Level 1 numbers mix signal and market: on a rising series
+//! almost any long entry makes money. The shuffle test separates the two by
+//! building a null distribution. Each variant keeps the real run's trades
+//! (count, side and holding period) but places them on random,
+//! non-overlapping entry bars, then runs them through the engine with the
+//! strategy's execution costs. The real run's Sharpe and total return are
+//! reported as percentiles of the variants' ([`SignalValueScore`]).
+//!
+//! Variant entries fill at the next bar's open and exit after their holding
+//! period, so stop and limit entries and the strategy's own exits are not
+//! reproduced; only their timing is.
+
+use std::collections::BTreeMap;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use trendlab_core::components::execution::{ExecutionPreset, NextBarOpenModel};
+use trendlab_core::components::filter::NoFilter;
+use trendlab_core::components::indicator::IndicatorValues;
+use trendlab_core::components::pm::{OrderIntent, PositionManager};
+use trendlab_core::components::signal::{SignalDirection, SignalEvent, SignalGenerator};
+use trendlab_core::data::align::AlignedData;
+use trendlab_core::data::interval::BarInterval;
+use trendlab_core::domain::{
+    Bar, BarSeries, MarketStatus, Position, PositionSide, SignalEventId, TradeRecord,
+};
+use trendlab_core::engine::execution::{CommissionModel, RegulatoryFees};
+use trendlab_core::engine::{run_backtest, EngineConfig, ExecutionConfig};
+use trendlab_core::fingerprint::{StrategyConfig, TradingMode};
+
+use crate::metrics::PerformanceMetrics;
+use crate::runner::{extract_single_symbol, run_metrics, BacktestResult};
+
+// ─── Configuration ───────────────────────────────────────────────────
+
+/// Configuration for the shuffle test.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShuffleTestConfig {
+    /// Number of randomized-entry variants (default 100).
+    pub n_variants: usize,
+    /// RNG seed for reproducibility.
+    pub seed: u64,
+    /// Lowest Sharpe percentile that passes the promotion gate, 0–100.
+    /// None (default) records the score without gating on it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_sharpe_percentile: Option<f64>,
+}
+
+impl Default for ShuffleTestConfig {
+    fn default() -> Self {
+        Self {
+            n_variants: 100,
+            seed: 42,
+            min_sharpe_percentile: None,
+        }
+    }
+}
+
+// ─── Result types ────────────────────────────────────────────────────
+
+/// Where the real run falls within its randomized-entry null distribution.
+///
+/// Percentiles are 0–100: the share of variants the real run beat, with
+/// ties counted half. Near 50 means the entries add nothing over random
+/// timing; near 100 means they beat almost every random placement.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignalValueScore {
+    /// Variants run.
+    pub variants: usize,
+    /// Trades placed in each variant.
+    pub trades: usize,
+    /// Percentile of the real Sharpe among the variants' Sharpes.
+    pub sharpe_percentile: f64,
+    /// Percentile of the real total return among the variants'.
+    pub total_return_percentile: f64,
+    /// Median Sharpe of the variants.
+    pub null_median_sharpe: f64,
+    /// Median total return of the variants.
+    pub null_median_total_return: f64,
+}
+
+/// Errors from the shuffle test.
+#[derive(Debug, Error)]
+pub enum ShuffleTestError {
+    #[error("symbol {0} not in aligned data")]
+    MissingSymbol(String),
+    #[error("no trades to shuffle")]
+    NoTrades,
+    #[error("{trades} trades need {needed} bars but only {available} are tradable")]
+    InsufficientBars {
+        trades: usize,
+        needed: usize,
+        available: usize,
+    },
+}
+
+// ─── Shuffle test ────────────────────────────────────────────────────
+
+/// Run the shuffle test for a finished backtest of `strategy_config`.
+///
+/// Variants use the same trading mode, capital, position sizing and
+/// execution costs as `result`, and only place entries after its warmup.
+#[allow(clippy::too_many_arguments)]
+pub fn run_shuffle_test(
+    result: &BacktestResult,
+    strategy_config: &StrategyConfig,
+    aligned: &AlignedData,
+    symbol: &str,
+    trading_mode: TradingMode,
+    initial_capital: f64,
+    position_size_pct: f64,
+    execution_preset: ExecutionPreset,
+    config: &ShuffleTestConfig,
+) -> Result<SignalValueScore, ShuffleTestError> {
+    if !aligned.bars.contains_key(symbol) {
+        return Err(ShuffleTestError::MissingSymbol(symbol.to_string()));
+    }
+    let params = &strategy_config.execution_model.params;
+    let mut engine_config = EngineConfig::with_execution(
+        initial_capital,
+        0,
+        ExecutionConfig::from_preset(execution_preset).with_params(params),
+    );
+    engine_config.trading_mode = trading_mode;
+    engine_config.position_size_pct = position_size_pct;
+    let execution = NextBarOpenModel::new(execution_preset).with_commission(
+        CommissionModel::from_params(params),
+        RegulatoryFees::from_params(params),
+    );
+    let null = NullRunner {
+        aligned: extract_single_symbol(aligned, symbol),
+        symbol,
+        engine_config,
+        execution,
+    };
+    null.score(&result.metrics, &result.trades, result.warmup_bars, config)
+}
+
+/// Runs trade schedules through the engine on one symbol's bars.
+struct NullRunner<'a> {
+    aligned: AlignedData,
+    symbol: &'a str,
+    engine_config: EngineConfig,
+    execution: NextBarOpenModel,
+}
+
+impl NullRunner<'_> {
+    /// Score `real` against variants of `trades` placed from bar `start`.
+    fn score(
+        &self,
+        real: &PerformanceMetrics,
+        trades: &[TradeRecord],
+        start: usize,
+        config: &ShuffleTestConfig,
+    ) -> Result<SignalValueScore, ShuffleTestError> {
+        if trades.is_empty() {
+            return Err(ShuffleTestError::NoTrades);
+        }
+        let mut slots: Vec<Slot> = trades
+            .iter()
+            .map(|t| Slot {
+                direction: match t.side {
+                    PositionSide::Short => SignalDirection::Short,
+                    _ => SignalDirection::Long,
+                },
+                hold: t.bars_held.max(1),
+            })
+            .collect();
+        // A slot spans its signal bar, its holding period and a bar after
+        // the exit fill before the next signal
+        let needed: usize = slots.iter().map(|s| s.hold + 2).sum();
+        let available = self.aligned.dates.len().saturating_sub(start);
+        if needed > available {
+            return Err(ShuffleTestError::InsufficientBars {
+                trades: slots.len(),
+                needed,
+                available,
+            });
+        }
+
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let mut sharpes = Vec::with_capacity(config.n_variants);
+        let mut returns = Vec::with_capacity(config.n_variants);
+        for _ in 0..config.n_variants {
+            slots.shuffle(&mut rng);
+            let schedule = random_schedule(&slots, start, available - needed, &mut rng);
+            let metrics = self.run(&schedule);
+            sharpes.push(metrics.sharpe);
+            returns.push(metrics.total_return);
+        }
+
+        Ok(SignalValueScore {
+            variants: config.n_variants,
+            trades: slots.len(),
+            sharpe_percentile: percentile_of(real.sharpe, &sharpes),
+            total_return_percentile: percentile_of(real.total_return, &returns),
+            null_median_sharpe: median(&mut sharpes),
+            null_median_total_return: median(&mut returns),
+        })
+    }
+
+    /// Metrics of one schedule: signal bar → slot.
+    fn run(&self, schedule: &BTreeMap<usize, Slot>) -> PerformanceMetrics {
+        let run = run_backtest(
+            &self.aligned,
+            &[],
+            &self.engine_config,
+            &ScheduledSignal {
+                schedule,
+                symbol: self.symbol,
+            },
+            &NoFilter,
+            &self.execution,
+            &ScheduledExit { schedule },
+        );
+        let interval = BarInterval::infer(&self.aligned.dates);
+        let (metrics, _, _) = run_metrics(
+            &run.equity_curve,
+            &run.exposure_curve,
+            &run.trades,
+            self.engine_config.initial_capital,
+            interval,
+        );
+        metrics
+    }
+}
+
+/// One trade to place: its side and how many bars it is held.
+#[derive(Debug, Clone, Copy)]
+struct Slot {
+    direction: SignalDirection,
+    hold: usize,
+}
+
+/// Place `slots` in order from bar `start`, spreading `slack` spare bars
+/// over the gaps before them at random.
+fn random_schedule(
+    slots: &[Slot],
+    start: usize,
+    slack: usize,
+    rng: &mut StdRng,
+) -> BTreeMap<usize, Slot> {
+    let mut offsets: Vec<usize> = (0..slots.len()).map(|_| rng.gen_range(0..=slack)).collect();
+    offsets.sort_unstable();
+    let mut used = 0;
+    slots
+        .iter()
+        .zip(offsets)
+        .map(|(slot, offset)| {
+            let bar = start + offset + used;
+            used += slot.hold + 2;
+            (bar, *slot)
+        })
+        .collect()
+}
+
+/// Share of `null` below `value`, ties counted half, as 0–100.
+fn percentile_of(value: f64, null: &[f64]) -> f64 {
+    if null.is_empty() {
+        return 50.0;
+    }
+    let below = null.iter().filter(|&&v| v < value).count() as f64;
+    let ties = null.iter().filter(|&&v| v == value).count() as f64;
+    (below + ties / 2.0) / null.len() as f64 * 100.0
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Fires on each scheduled signal bar in the slot's direction.
+struct ScheduledSignal<'a> {
+    schedule: &'a BTreeMap<usize, Slot>,
+    symbol: &'a str,
+}
+
+impl SignalGenerator for ScheduledSignal<'_> {
+    fn name(&self) -> &str {
+        "shuffled_entry"
+    }
+
+    fn warmup_bars(&self) -> usize {
+        0
+    }
+
+    fn evaluate(
+        &self,
+        bars: &dyn BarSeries,
+        bar_index: usize,
+        _indicators: &IndicatorValues,
+    ) -> Option<SignalEvent> {
+        let slot = self.schedule.get(&bar_index)?;
+        Some(SignalEvent {
+            id: SignalEventId(bar_index as u64),
+            bar_index,
+            date: bars.bar(bar_index).date,
+            symbol: self.symbol.to_string(),
+            direction: slot.direction,
+            strength: 1.0,
+            metadata: Default::default(),
+        })
+    }
+}
+
+/// Exits each position once it has been held for its slot's period.
+struct ScheduledExit<'a> {
+    schedule: &'a BTreeMap<usize, Slot>,
+}
+
+impl PositionManager for ScheduledExit<'_> {
+    fn name(&self) -> &str {
+        "shuffled_exit"
+    }
+
+    fn on_bar(
+        &self,
+        position: &Position,
+        _bar: &Bar,
+        _bar_index: usize,
+        _market_status: MarketStatus,
+        _indicators: &IndicatorValues,
+    ) -> OrderIntent {
+        // The slot whose signal bar came last before the fill
+        let hold = self
+            .schedule
+            .range(..position.entry_bar)
+            .next_back()
+            .map_or(1, |(_, slot)| slot.hold);
+        // `bars_held` already counts this bar; the exit fills at the next
+        // bar's open, `hold` bars after the entry fill
+        if position.bars_held >= hold {
+            OrderIntent::force_exit()
+        } else {
+            OrderIntent::hold()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::collections::HashMap;
+    use trendlab_core::data::provider::RawBar;
+
+    const HOLD: usize = 5;
+
+    /// 600 daily bars of a driftless random walk for "SYN".
+    fn random_walk() -> AlignedData {
+        let mut rng = StdRng::seed_from_u64(7);
+        let start = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        let mut close: f64 = 100.0;
+        let mut bars = Vec::new();
+        for i in 0..600 {
+            let open = close;
+            close *= 1.0 + rng.gen_range(-0.02..0.02);
+            bars.push(RawBar {
+                date: start + chrono::Duration::days(i),
+                open,
+                high: open.max(close) * 1.002,
+                low: open.min(close) * 0.998,
+                close,
+                volume: 1_000_000,
+                adj_close: close,
+            });
+        }
+        AlignedData {
+            dates: bars.iter().map(|b| b.date).collect(),
+            bars: HashMap::from([("SYN".to_string(), bars)]),
+            symbols: vec!["SYN".to_string()],
+        }
+    }
+
+    fn null_runner(aligned: &AlignedData) -> NullRunner<'static> {
+        NullRunner {
+            aligned: extract_single_symbol(aligned, "SYN"),
+            symbol: "SYN",
+            engine_config: EngineConfig::with_execution(
+                100_000.0,
+                0,
+                ExecutionConfig::from_preset(ExecutionPreset::Realistic),
+            ),
+            execution: NextBarOpenModel::new(ExecutionPreset::Realistic),
+        }
+    }
+
+    /// Goes long whenever the close `HOLD` bars after the entry fill will
+    /// be above the fill bar's open.
+    struct LookAhead;
+
+    impl SignalGenerator for LookAhead {
+        fn name(&self) -> &str {
+            "look_ahead"
+        }
+
+        fn warmup_bars(&self) -> usize {
+            0
+        }
+
+        fn evaluate(
+            &self,
+            bars: &dyn BarSeries,
+            bar_index: usize,
+            _indicators: &IndicatorValues,
+        ) -> Option<SignalEvent> {
+            let fill = bars.get(bar_index + 1)?;
+            let exit = bars.get(bar_index + 1 + HOLD)?;
+            (exit.open > fill.open * 1.01).then(|| SignalEvent {
+                id: SignalEventId(bar_index as u64),
+                bar_index,
+                date: bars.bar(bar_index).date,
+                symbol: "SYN".into(),
+                direction: SignalDirection::Long,
+                strength: 1.0,
+                metadata: Default::default(),
+            })
+        }
+    }
+
+    /// Run `signal` with every trade held `HOLD` bars.
+    fn run_signal(
+        null: &NullRunner,
+        signal: &dyn SignalGenerator,
+    ) -> (PerformanceMetrics, Vec<TradeRecord>) {
+        let schedule = BTreeMap::from([(
+            0,
+            Slot {
+                direction: SignalDirection::Long,
+                hold: HOLD,
+            },
+        )]);
+        let run = run_backtest(
+            &null.aligned,
+            &[],
+            &null.engine_config,
+            signal,
+            &NoFilter,
+            &null.execution,
+            &ScheduledExit {
+                schedule: &schedule,
+            },
+        );
+        let (metrics, _, _) = run_metrics(
+            &run.equity_curve,
+            &run.exposure_curve,
+            &run.trades,
+            100_000.0,
+            BarInterval::infer(&null.aligned.dates),
+        );
+        (metrics, run.trades)
+    }
+
+    #[test]
+    fn random_entries_score_near_the_median() {
+        let aligned = random_walk();
+        let null = null_runner(&aligned);
+        let config = ShuffleTestConfig::default();
+        let slot = Slot {
+            direction: SignalDirection::Long,
+            hold: HOLD,
+        };
+
+        // Random-entry "strategies" drawn with other seeds score anywhere in
+        // the null distribution, but average out near the middle
+        let mut percentiles = Vec::new();
+        for seed in 1..=8 {
+            let mut rng = StdRng::seed_from_u64(1000 + seed);
+            let schedule = random_schedule(&[slot; 30], 0, 600 - 30 * (HOLD + 2), &mut rng);
+            let scheduled = ScheduledSignal {
+                schedule: &schedule,
+                symbol: "SYN",
+            };
+            let (metrics, trades) = run_signal(&null, &scheduled);
+            assert_eq!(trades.len(), 30);
+            assert!(trades.iter().all(|t| t.bars_held == HOLD), "{trades:?}");
+            let score = null.score(&metrics, &trades, 0, &config).unwrap();
+            assert_eq!((score.variants, score.trades), (100, 30));
+            percentiles.push(score.sharpe_percentile);
+        }
+        let mean = percentiles.iter().sum::<f64>() / percentiles.len() as f64;
+        assert!((30.0..=70.0).contains(&mean), "{percentiles:?}");
+    }
+
+    #[test]
+    fn look_ahead_signal_scores_near_the_top() {
+        let aligned = random_walk();
+        let null = null_runner(&aligned);
+        let (metrics, trades) = run_signal(&null, &LookAhead);
+        assert!(trades.len() >= 20, "{} trades", trades.len());
+
+        let score = null
+            .score(&metrics, &trades, 0, &ShuffleTestConfig::default())
+            .unwrap();
+        assert!(score.sharpe_percentile >= 99.0, "{score:?}");
+        assert!(score.total_return_percentile >= 99.0, "{score:?}");
+        assert!(score.null_median_total_return < metrics.total_return);
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_score() {
+        let aligned = random_walk();
+        let null = null_runner(&aligned);
+        let (metrics, trades) = run_signal(&null, &LookAhead);
+        let config = ShuffleTestConfig {
+            n_variants: 20,
+            ..ShuffleTestConfig::default()
+        };
+        let a = null.score(&metrics, &trades, 0, &config).unwrap();
+        let b = null.score(&metrics, &trades, 0, &config).unwrap();
+        assert_eq!(a, b);
+
+        let too_many = vec![trades[0].clone(); 200];
+        assert!(matches!(
+            null.score(&metrics, &too_many, 0, &config),
+            Err(ShuffleTestError::InsufficientBars { .. })
+        ));
+    }
+}
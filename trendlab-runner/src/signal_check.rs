@@ -0,0 +1,349 @@
+//! Signal check — what a config would do on the latest bar, without a backtest.
+//!
+//! [`check_signals`] builds the config's components, precomputes indicators
+//! over the loaded bars and evaluates the signal and filter on each symbol's
+//! most recent valid bar. When an entry would fire it reports the order the
+//! execution model would place and where the position manager's initial stop
+//! would sit for a position entered at the order's reference price. There is
+//! no portfolio loop, so entry governance (cooldowns, entry caps) and any
+//! position already held are not taken into account.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use trendlab_core::components::composition::build_composition;
+use trendlab_core::components::factory::FactoryError;
+use trendlab_core::components::pm::IntentAction;
+use trendlab_core::components::signal::{FilterVerdict, SignalDirection};
+use trendlab_core::data::align::AlignedData;
+use trendlab_core::domain::{Instrument, MarketStatus, OrderSide, OrderType, Position};
+use trendlab_core::engine::{aligned_to_bars, compute_warmup, precompute_indicators};
+use trendlab_core::fingerprint::{StrategyConfig, TradingMode};
+
+/// What the config would do on one symbol's latest bar.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SymbolCheck {
+    pub symbol: String,
+    /// Date of the bar evaluated: the symbol's last bar with prices.
+    pub last_bar: NaiveDate,
+    /// Calendar days from `last_bar` to the check date.
+    pub age_days: i64,
+    /// `age_days` exceeds the staleness threshold.
+    pub stale: bool,
+    pub close: f64,
+    pub outcome: CheckOutcome,
+}
+
+/// Result of evaluating the signal chain on the latest bar.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum CheckOutcome {
+    /// Too few bars for the indicators; nothing was evaluated.
+    Warmup { bars: usize, required: usize },
+    /// The signal did not fire.
+    NoSignal,
+    /// The signal fired in a direction the trading mode does not take.
+    ModeBlocked { direction: SignalDirection },
+    /// The signal fired and the filter rejected it.
+    Filtered {
+        direction: SignalDirection,
+        filter: String,
+        verdict: FilterVerdict,
+        filter_state: BTreeMap<String, f64>,
+    },
+    /// An entry would be placed for the next bar.
+    Entry(EntryPlan),
+}
+
+/// The entry order a firing signal would produce.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EntryPlan {
+    pub direction: SignalDirection,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    /// Trigger or limit price, or the latest close for market orders.
+    pub reference_price: f64,
+    /// The PM's first stop for a position entered at `reference_price`.
+    pub initial_stop: Option<f64>,
+    pub strength: f64,
+}
+
+impl EntryPlan {
+    /// Distance from the reference price to the initial stop.
+    pub fn risk_per_share(&self) -> Option<f64> {
+        self.initial_stop
+            .map(|stop| (self.reference_price - stop).abs())
+    }
+}
+
+impl fmt::Display for CheckOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warmup { bars, required } => {
+                write!(f, "warmup not satisfied ({bars} of {required} bars)")
+            }
+            Self::NoSignal => write!(f, "no signal"),
+            Self::ModeBlocked { direction } => {
+                write!(f, "{direction:?} signal blocked by trading mode")
+            }
+            Self::Filtered {
+                direction,
+                filter,
+                verdict,
+                ..
+            } => write!(f, "{direction:?} signal filtered by {filter} ({verdict:?})"),
+            Self::Entry(plan) => {
+                let order = match &plan.order_type {
+                    OrderType::MarketOnOpen => "market on open".to_string(),
+                    OrderType::MarketOnClose => "market on close".to_string(),
+                    OrderType::MarketImmediate => "market".to_string(),
+                    OrderType::StopMarket { trigger_price } => format!("stop {trigger_price:.2}"),
+                    OrderType::Limit { limit_price } => format!("limit {limit_price:.2}"),
+                    OrderType::StopLimit {
+                        trigger_price,
+                        limit_price,
+                    } => format!("stop {trigger_price:.2} limit {limit_price:.2}"),
+                };
+                let side = match plan.side {
+                    OrderSide::Buy => "BUY",
+                    OrderSide::Sell => "SELL",
+                };
+                write!(f, "{side} {order}")?;
+                if let Some(stop) = plan.initial_stop {
+                    write!(f, ", initial stop {stop:.2}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Evaluate `config` on the latest bar of each of `symbols` in `aligned`.
+///
+/// `today` and `stale_after_days` only feed the staleness fields. Symbols
+/// missing from `aligned` or without a single valid bar are skipped.
+pub fn check_signals(
+    config: &StrategyConfig,
+    trading_mode: TradingMode,
+    aligned: &AlignedData,
+    symbols: &[&str],
+    today: NaiveDate,
+    stale_after_days: u32,
+) -> Result<Vec<SymbolCheck>, FactoryError> {
+    let composition = build_composition(config, trading_mode)?;
+    let bars_by_symbol = aligned_to_bars(aligned);
+    let indicator_values = precompute_indicators(&bars_by_symbol, &composition.indicators);
+    let required = compute_warmup(&composition.indicators);
+
+    let mut checks = Vec::new();
+    for &symbol in symbols {
+        let (Some(bars), Some(indicators)) =
+            (bars_by_symbol.get(symbol), indicator_values.get(symbol))
+        else {
+            continue;
+        };
+        let Some(t) = bars.iter().rposition(|b| !b.is_void()) else {
+            continue;
+        };
+        let bar = &bars[t];
+        let age_days = (today - bar.date).num_days();
+
+        let outcome = if t < required {
+            CheckOutcome::Warmup {
+                bars: t + 1,
+                required: required + 1,
+            }
+        } else {
+            match composition.signal.evaluate(bars, t, indicators) {
+                None => CheckOutcome::NoSignal,
+                Some(signal) if blocked_by_mode(trading_mode, signal.direction) => {
+                    CheckOutcome::ModeBlocked {
+                        direction: signal.direction,
+                    }
+                }
+                Some(signal) => {
+                    let evaluation = composition.filter.evaluate(&signal, bars, t, indicators);
+                    if !evaluation.verdict.is_passed() {
+                        CheckOutcome::Filtered {
+                            direction: signal.direction,
+                            filter: evaluation.filter_name,
+                            verdict: evaluation.verdict,
+                            filter_state: evaluation.filter_state.into_iter().collect(),
+                        }
+                    } else {
+                        let instrument = Instrument::us_equity(symbol);
+                        let order_type =
+                            composition
+                                .execution
+                                .entry_order_type(&signal, bar, &instrument);
+                        let reference_price = match order_type {
+                            OrderType::StopMarket { trigger_price }
+                            | OrderType::StopLimit { trigger_price, .. } => trigger_price,
+                            OrderType::Limit { limit_price } => limit_price,
+                            _ => bar.close,
+                        };
+                        let position = match signal.direction {
+                            SignalDirection::Long => {
+                                Position::new_long(symbol.to_string(), 1.0, reference_price, t)
+                            }
+                            SignalDirection::Short => {
+                                Position::new_short(symbol.to_string(), 1.0, reference_price, t)
+                            }
+                        };
+                        let intent = composition.pm.on_bar(
+                            &position,
+                            bar,
+                            t,
+                            MarketStatus::Open,
+                            indicators,
+                        );
+                        let initial_stop = match intent.action {
+                            IntentAction::AdjustStop => intent.stop_price,
+                            _ => None,
+                        };
+                        CheckOutcome::Entry(EntryPlan {
+                            direction: signal.direction,
+                            side: match signal.direction {
+                                SignalDirection::Long => OrderSide::Buy,
+                                SignalDirection::Short => OrderSide::Sell,
+                            },
+                            order_type,
+                            reference_price,
+                            initial_stop,
+                            strength: signal.strength,
+                        })
+                    }
+                }
+            }
+        };
+
+        checks.push(SymbolCheck {
+            symbol: symbol.to_string(),
+            last_bar: bar.date,
+            age_days,
+            stale: age_days > i64::from(stale_after_days),
+            close: bar.close,
+            outcome,
+        });
+    }
+    Ok(checks)
+}
+
+/// Mirrors the engine's trading-mode gate.
+fn blocked_by_mode(mode: TradingMode, direction: SignalDirection) -> bool {
+    matches!(
+        (mode, direction),
+        (TradingMode::LongOnly, SignalDirection::Short)
+            | (TradingMode::ShortOnly, SignalDirection::Long)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use trendlab_core::components::composition::StrategyPreset;
+    use trendlab_core::data::provider::RawBar;
+
+    fn trending(n: usize, step: f64) -> AlignedData {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let dates: Vec<NaiveDate> = (0..n)
+            .map(|i| start + chrono::Duration::days(i as i64))
+            .collect();
+        let bars = dates
+            .iter()
+            .enumerate()
+            .map(|(i, d)| {
+                let close = 100.0 + step * i as f64;
+                RawBar {
+                    date: *d,
+                    open: close - step / 2.0,
+                    high: close + step.abs() / 2.0,
+                    low: close - step.abs() / 2.0,
+                    close,
+                    volume: 1000,
+                    adj_close: close,
+                }
+            })
+            .collect();
+        AlignedData {
+            dates,
+            bars: HashMap::from([("SPY".to_string(), bars)]),
+            symbols: vec!["SPY".to_string()],
+        }
+    }
+
+    fn check(preset: StrategyPreset, aligned: &AlignedData, mode: TradingMode) -> SymbolCheck {
+        let last = *aligned.dates.last().unwrap();
+        let config = preset.to_config();
+        let mut checks = check_signals(
+            &config,
+            mode,
+            aligned,
+            &["SPY"],
+            last + chrono::Duration::days(10),
+            7,
+        )
+        .unwrap();
+        assert_eq!(checks.len(), 1);
+        checks.remove(0)
+    }
+
+    #[test]
+    fn momentum_reports_entry_order_and_initial_stop() {
+        let check = check(
+            StrategyPreset::MomentumRoc,
+            &trending(120, 1.0),
+            TradingMode::LongOnly,
+        );
+        assert_eq!(check.age_days, 10);
+        assert!(check.stale);
+        let CheckOutcome::Entry(plan) = &check.outcome else {
+            panic!("expected an entry, got {}", check.outcome);
+        };
+        assert_eq!(plan.side, OrderSide::Buy);
+        assert_eq!(plan.order_type, OrderType::MarketOnOpen);
+        assert_eq!(plan.reference_price, check.close);
+        let stop = plan.initial_stop.expect("time decay places a stop");
+        assert!(stop < plan.reference_price);
+        assert_eq!(plan.risk_per_share(), Some(plan.reference_price - stop));
+    }
+
+    #[test]
+    fn short_signal_is_blocked_in_long_only_mode() {
+        let check = check(
+            StrategyPreset::MomentumRoc,
+            &trending(120, -0.5),
+            TradingMode::LongOnly,
+        );
+        assert!(matches!(
+            check.outcome,
+            CheckOutcome::ModeBlocked {
+                direction: SignalDirection::Short
+            }
+        ));
+    }
+
+    #[test]
+    fn refuses_to_evaluate_before_warmup() {
+        let check = check(
+            StrategyPreset::DonchianTrend,
+            &trending(20, 1.0),
+            TradingMode::LongOnly,
+        );
+        let CheckOutcome::Warmup { bars, required } = check.outcome else {
+            panic!("expected warmup, got {}", check.outcome);
+        };
+        assert_eq!(bars, 20);
+        assert!(required > bars);
+    }
+
+    #[test]
+    fn outcome_serializes_with_a_status_tag() {
+        let json = serde_json::to_value(CheckOutcome::NoSignal).unwrap();
+        assert_eq!(json["status"], "no_signal");
+    }
+}
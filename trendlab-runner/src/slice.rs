@@ -0,0 +1,357 @@
+//! Date-window subsetting of a finished run.
+//!
+//! [`BacktestResult::slice`] cuts a run down to the bars dated inside a
+//! window and recomputes its metrics there. The engine is not re-run: the
+//! positions are the full run's, measured over the window only.
+//!
+//! - The equity curve is truncated to the window. As in a full run, returns
+//!   are measured from the close of the first bar.
+//! - A trade is kept when its holding period overlaps the window's returns
+//!   (at least one bar of it lies between the window's first and last bar),
+//!   or when it is opened and closed inside the window.
+//! - A trade spanning a boundary is cut to the window: its bars are clipped,
+//!   and its PnL and costs are pro-rated by the share of its bars held inside
+//!   the window. Prices and excursions stay those of the full trade. Each cut
+//!   trade is listed in [`ResultSlice::prorated`]; one cut by the window's end
+//!   is marked `liquidated_at_end`, as it was still open when the window ran
+//!   out.
+//! - Warmup bars inside the window stay in the curve (flat, like a full run's
+//!   warmup). A window lying entirely in warmup is rejected.
+//!
+//! Run-level diagnostics that can't be recovered per bar (signal count,
+//! void-bar rates, data quality and consistency warnings) carry over from
+//! the full run. Stickiness, style analysis, the warmup breakdown and the
+//! re-run inputs are dropped.
+
+use chrono::NaiveDate;
+use thiserror::Error;
+
+use trendlab_core::domain::TradeRecord;
+use trendlab_core::engine::PnlAttribution;
+
+use crate::metrics::{RDistribution, TradeDistributions};
+use crate::runner::{run_metrics, BacktestResult};
+use crate::sparkline::{equity_sparkline, SPARKLINE_POINTS};
+use crate::tail_metrics::{compute_tail_metrics, worst_days, DEFAULT_WORST_DAYS};
+
+/// Why a run can't be sliced to a window.
+#[derive(Debug, Error, PartialEq)]
+pub enum SliceError {
+    #[error("run has no per-bar dates (saved before they were recorded); re-run it to slice")]
+    NoDates,
+    #[error("run has {dates} dates for {bars} equity bars")]
+    Misaligned { dates: usize, bars: usize },
+    #[error("window starts {from}, after its end {to}")]
+    Inverted { from: NaiveDate, to: NaiveDate },
+    #[error("window {from} to {to} holds no bars of the run ({start} to {end})")]
+    OutOfRange {
+        from: NaiveDate,
+        to: NaiveDate,
+        start: NaiveDate,
+        end: NaiveDate,
+    },
+    #[error(
+        "window {from} to {to} lies entirely in the {warmup_bars}-bar warmup \
+         (trading starts {first_trading})"
+    )]
+    InWarmup {
+        from: NaiveDate,
+        to: NaiveDate,
+        warmup_bars: usize,
+        first_trading: NaiveDate,
+    },
+}
+
+/// A trade cut by a window boundary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProratedTrade {
+    /// Index into the sliced result's trades.
+    pub trade: usize,
+    /// Entry bar of the full trade in the full run.
+    pub full_entry_bar: usize,
+    /// Share of the trade's bars held inside the window, which scales its
+    /// PnL and costs.
+    pub share: f64,
+    /// The trade was entered before the window.
+    pub cut_entry: bool,
+    /// The trade was still open at the window's end.
+    pub cut_exit: bool,
+}
+
+/// A run recomputed over a date window.
+#[derive(Debug, Clone)]
+pub struct ResultSlice {
+    /// The run over the window. Bar indices count from the window's first
+    /// bar, and `initial_capital` is the equity on it.
+    pub result: BacktestResult,
+    /// Index of the window's first bar in the full run.
+    pub first_bar: usize,
+    /// Index of the window's last bar in the full run.
+    pub last_bar: usize,
+    /// Trades cut by a window boundary, with pro-rated PnL.
+    pub prorated: Vec<ProratedTrade>,
+}
+
+impl ResultSlice {
+    /// Whether the window covers the whole run.
+    pub fn is_full(&self, full: &BacktestResult) -> bool {
+        self.first_bar == 0 && self.last_bar + 1 == full.equity_curve.len()
+    }
+}
+
+impl BacktestResult {
+    /// This run over the bars dated `start..=end`, with its metrics and tail
+    /// metrics recomputed there (see [`crate::slice`]).
+    pub fn slice(&self, start: NaiveDate, end: NaiveDate) -> Result<ResultSlice, SliceError> {
+        let (first, last) = self.window_bars(start, end)?;
+        let dates = &self.dates[first..=last];
+        let equity_curve = self.equity_curve[first..=last].to_vec();
+        let exposure_curve = self
+            .exposure_curve
+            .get(first..=last)
+            .map(<[f64]>::to_vec)
+            .unwrap_or_default();
+
+        let mut trades = Vec::new();
+        let mut prorated = Vec::new();
+        for trade in &self.trades {
+            let Some((mut cut, share)) = cut_trade(trade, first, last) else {
+                continue;
+            };
+            let cut_entry = trade.entry_bar < first;
+            let cut_exit = trade.exit_bar > last;
+            cut.entry_date = self.dates[cut.entry_bar];
+            cut.exit_date = self.dates[cut.exit_bar];
+            cut.entry_bar -= first;
+            cut.exit_bar -= first;
+            if cut_entry || cut_exit {
+                prorated.push(ProratedTrade {
+                    trade: trades.len(),
+                    full_entry_bar: trade.entry_bar,
+                    share,
+                    cut_entry,
+                    cut_exit,
+                });
+            }
+            trades.push(cut);
+        }
+
+        let initial_capital = equity_curve[0];
+        let (metrics, open_position_adjusted, zero_trades) = run_metrics(
+            &equity_curve,
+            &exposure_curve,
+            &trades,
+            initial_capital,
+            self.interval,
+        );
+        let mut tail_metrics = compute_tail_metrics(&equity_curve);
+        tail_metrics.worst_days = worst_days(&equity_curve, dates, &trades, DEFAULT_WORST_DAYS);
+        let attribution = PnlAttribution {
+            bars: self
+                .attribution
+                .bars
+                .iter()
+                .filter(|b| (first..=last).contains(&b.bar_index))
+                .map(|b| {
+                    let mut bar = b.clone();
+                    bar.bar_index -= first;
+                    bar
+                })
+                .collect(),
+        };
+        let ratchet_clamps = self
+            .ratchet_clamps
+            .iter()
+            .filter(|c| (first..=last).contains(&c.bar_index))
+            .map(|c| {
+                let mut clamp = c.clone();
+                clamp.bar_index -= first;
+                clamp
+            })
+            .collect();
+
+        let result = BacktestResult {
+            schema_version: self.schema_version,
+            metrics,
+            open_position_adjusted,
+            r_distribution: RDistribution::compute(&trades),
+            trade_distributions: TradeDistributions::compute(&trades),
+            trades,
+            equity_sparkline: equity_sparkline(&equity_curve, SPARKLINE_POINTS),
+            equity_curve,
+            dates: dates.to_vec(),
+            exposure_curve,
+            config: self.config.clone(),
+            symbol: self.symbol.clone(),
+            interval: self.interval,
+            start_date: dates[0].to_string(),
+            end_date: dates[dates.len() - 1].to_string(),
+            initial_capital,
+            dataset_hash: self.dataset_hash.clone(),
+            has_synthetic: self.has_synthetic,
+            signal_count: self.signal_count,
+            zero_trades,
+            bar_count: dates.len(),
+            warmup_bars: self.warmup_bars.saturating_sub(first),
+            warmup: None,
+            void_bar_rates: self.void_bar_rates.clone(),
+            data_quality_warnings: self.data_quality_warnings.clone(),
+            stickiness: None,
+            position_stickiness: Vec::new(),
+            tail_metrics: Some(tail_metrics),
+            truncated_range: None,
+            style: None,
+            attribution,
+            ratchet_clamps,
+            inputs: None,
+            consistency_warnings: self.consistency_warnings.clone(),
+            data_vintage: Vec::new(),
+            provenance: None,
+            timings: Default::default(),
+            replay: Default::default(),
+            portfolio_snapshots: Default::default(),
+        };
+        Ok(ResultSlice {
+            result,
+            first_bar: first,
+            last_bar: last,
+            prorated,
+        })
+    }
+
+    /// First and last bar dated inside `start..=end`.
+    fn window_bars(&self, start: NaiveDate, end: NaiveDate) -> Result<(usize, usize), SliceError> {
+        if self.dates.is_empty() {
+            return Err(SliceError::NoDates);
+        }
+        if self.dates.len() != self.equity_curve.len() {
+            return Err(SliceError::Misaligned {
+                dates: self.dates.len(),
+                bars: self.equity_curve.len(),
+            });
+        }
+        if start > end {
+            return Err(SliceError::Inverted {
+                from: start,
+                to: end,
+            });
+        }
+        let first = self.dates.partition_point(|d| *d < start);
+        let after = self.dates.partition_point(|d| *d <= end);
+        if first >= after {
+            return Err(SliceError::OutOfRange {
+                from: start,
+                to: end,
+                start: self.dates[0],
+                end: self.dates[self.dates.len() - 1],
+            });
+        }
+        let last = after - 1;
+        if last < self.warmup_bars {
+            return Err(SliceError::InWarmup {
+                from: start,
+                to: end,
+                warmup_bars: self.warmup_bars,
+                first_trading: self.dates[self.warmup_bars.min(self.dates.len() - 1)],
+            });
+        }
+        Ok((first, last))
+    }
+}
+
+/// `trade` clipped to bars `first..=last` with its PnL pro-rated, and the
+/// share kept. None when no bar of it is held inside the window.
+fn cut_trade(trade: &TradeRecord, first: usize, last: usize) -> Option<(TradeRecord, f64)> {
+    let entry = trade.entry_bar.max(first);
+    let exit = trade.exit_bar.min(last);
+    let inside = trade.entry_bar >= first && trade.exit_bar <= last;
+    if !inside && entry >= exit {
+        return None;
+    }
+    let share = if inside {
+        1.0
+    } else {
+        (exit - entry) as f64 / trade.bars_held.max(1) as f64
+    };
+    let mut cut = trade.clone();
+    if !inside {
+        cut.entry_bar = entry;
+        cut.exit_bar = exit;
+        cut.bars_held = exit - entry;
+        cut.gross_pnl *= share;
+        cut.commission *= share;
+        cut.slippage *= share;
+        cut.net_pnl *= share;
+        cut.liquidated_at_end |= trade.exit_bar > last;
+    }
+    Some((cut, share))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use trendlab_core::domain::PositionSide;
+
+    fn trade(entry_bar: usize, exit_bar: usize, net_pnl: f64) -> TradeRecord {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        TradeRecord {
+            symbol: "SPY".into(),
+            side: PositionSide::Long,
+            entry_bar,
+            entry_date: date,
+            entry_price: 100.0,
+            entry_trigger_margin: None,
+            exit_bar,
+            exit_date: date,
+            exit_price: 110.0,
+            quantity: 10.0,
+            gross_pnl: net_pnl + 4.0,
+            commission: 3.0,
+            slippage: 1.0,
+            net_pnl,
+            bars_held: exit_bar - entry_bar,
+            mae: -20.0,
+            mfe: 120.0,
+            initial_stop_price: None,
+            entry_source: None,
+            exit_source: None,
+            liquidated_at_end: false,
+            expiry_bar: None,
+            signal_id: None,
+            signal_type: None,
+            pm_type: None,
+            execution_model: None,
+            filter_type: None,
+        }
+    }
+
+    #[test]
+    fn boundary_trades_are_clipped_and_prorated() {
+        // Held 10 bars, 4 of them (bars 16..20) inside the window 16..=30
+        let (cut, share) = cut_trade(&trade(10, 20, 96.0), 16, 30).unwrap();
+        assert!((share - 0.4).abs() < 1e-12);
+        assert_eq!((cut.entry_bar, cut.exit_bar, cut.bars_held), (16, 20, 4));
+        assert!((cut.net_pnl - 38.4).abs() < 1e-9);
+        assert!((cut.gross_pnl - cut.commission - cut.slippage - cut.net_pnl).abs() < 1e-9);
+        assert_eq!((cut.mae, cut.mfe, cut.entry_price), (-20.0, 120.0, 100.0));
+        assert!(!cut.liquidated_at_end);
+
+        // Open past the window's end: clipped there and marked open at end
+        let (cut, share) = cut_trade(&trade(25, 35, 50.0), 16, 30).unwrap();
+        assert!((share - 0.5).abs() < 1e-12);
+        assert!(cut.liquidated_at_end);
+
+        // Inside: untouched, even when opened and closed on one bar
+        let inner = trade(18, 18, 7.0);
+        assert_eq!(cut_trade(&inner, 16, 30).unwrap().0.net_pnl, 7.0);
+    }
+
+    #[test]
+    fn trades_touching_only_a_boundary_bar_are_dropped() {
+        // Exits on the first bar: none of its holding period is a window return
+        assert!(cut_trade(&trade(5, 16, 10.0), 16, 30).is_none());
+        // Enters on the last bar
+        assert!(cut_trade(&trade(30, 40, 10.0), 16, 30).is_none());
+        assert!(cut_trade(&trade(2, 8, 10.0), 16, 30).is_none());
+    }
+}
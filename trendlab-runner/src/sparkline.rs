@@ -0,0 +1,119 @@
+//! Equity sparklines — small downsampled curves for leaderboard rows.
+//!
+//! Uses largest-triangle-three-buckets (LTTB): the first and last points are
+//! kept, and each bucket in between contributes the point that forms the
+//! largest triangle with its neighbours. Unlike bucket means, this keeps the
+//! peaks and troughs that make a "one lucky trade" curve recognizable.
+
+/// Points kept per equity curve for leaderboard sparklines.
+pub const SPARKLINE_POINTS: usize = 40;
+
+/// Downsample `curve` to at most `n_buckets` points with LTTB.
+///
+/// Curves with no more than `n_buckets` points are returned unchanged.
+/// Non-finite values are dropped first.
+pub fn equity_sparkline(curve: &[f64], n_buckets: usize) -> Vec<f64> {
+    let points: Vec<f64> = curve.iter().copied().filter(|v| v.is_finite()).collect();
+    let n = points.len();
+    if n <= n_buckets {
+        return points;
+    }
+    match n_buckets {
+        0 => return Vec::new(),
+        1 => return vec![points[n - 1]],
+        2 => return vec![points[0], points[n - 1]],
+        _ => {}
+    }
+
+    // Interior points [1, n - 1) split into n_buckets - 2 buckets
+    let interior = n - 2;
+    let buckets = n_buckets - 2;
+    let bucket_range = |k: usize| (1 + k * interior / buckets, 1 + (k + 1) * interior / buckets);
+
+    let mut out = Vec::with_capacity(n_buckets);
+    out.push(points[0]);
+    let mut prev = 0usize;
+    for k in 0..buckets {
+        let (start, end) = bucket_range(k);
+
+        // Average of the next bucket (or the last point) as the third vertex
+        let (next_start, next_end) = if k + 1 < buckets {
+            bucket_range(k + 1)
+        } else {
+            (n - 1, n)
+        };
+        let count = (next_end - next_start) as f64;
+        let avg_x = (next_start + next_end - 1) as f64 / 2.0;
+        let avg_y = points[next_start..next_end].iter().sum::<f64>() / count;
+
+        let (px, py) = (prev as f64, points[prev]);
+        let mut best = start;
+        let mut best_area = -1.0;
+        for (i, &y) in points.iter().enumerate().take(end).skip(start) {
+            let area = ((px - avg_x) * (y - py) - (px - i as f64) * (avg_y - py)).abs();
+            if area > best_area {
+                best_area = area;
+                best = i;
+            }
+        }
+        out.push(points[best]);
+        prev = best;
+    }
+    out.push(points[n - 1]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_curve_is_returned_unchanged() {
+        let curve = vec![100.0, 101.0, 99.0];
+        assert_eq!(equity_sparkline(&curve, 40), curve);
+        assert!(equity_sparkline(&[], 40).is_empty());
+    }
+
+    #[test]
+    fn output_has_requested_length_and_endpoints() {
+        let curve: Vec<f64> = (0..500).map(|i| 100.0 + i as f64).collect();
+        let spark = equity_sparkline(&curve, SPARKLINE_POINTS);
+        assert_eq!(spark.len(), SPARKLINE_POINTS);
+        assert_eq!(spark[0], 100.0);
+        assert_eq!(spark[SPARKLINE_POINTS - 1], 599.0);
+    }
+
+    #[test]
+    fn flat_curve_stays_flat() {
+        let curve = vec![100_000.0; 300];
+        let spark = equity_sparkline(&curve, 40);
+        assert_eq!(spark.len(), 40);
+        assert!(spark.iter().all(|&v| v == 100_000.0));
+    }
+
+    #[test]
+    fn keeps_a_single_spike() {
+        let mut curve = vec![100.0; 1000];
+        curve[517] = 180.0;
+        let spark = equity_sparkline(&curve, 40);
+        assert!(
+            spark.contains(&180.0),
+            "a bucket mean would smear the spike"
+        );
+    }
+
+    #[test]
+    fn tiny_bucket_counts() {
+        let curve = vec![1.0, 5.0, 2.0, 3.0];
+        assert!(equity_sparkline(&curve, 0).is_empty());
+        assert_eq!(equity_sparkline(&curve, 1), vec![3.0]);
+        assert_eq!(equity_sparkline(&curve, 2), vec![1.0, 3.0]);
+        assert_eq!(equity_sparkline(&curve, 3), vec![1.0, 5.0, 3.0]);
+    }
+
+    #[test]
+    fn non_finite_points_are_dropped() {
+        let curve = vec![1.0, f64::NAN, 2.0, f64::INFINITY];
+        assert_eq!(equity_sparkline(&curve, 40), vec![1.0, 2.0]);
+    }
+}
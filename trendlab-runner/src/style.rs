@@ -0,0 +1,473 @@
+//! Returns-based style analysis — is a strategy just levered beta?
+//!
+//! Regresses a strategy's daily returns on one or more factor return series
+//! (the symbol's own buy-and-hold, other cached symbols, or user-provided CSV
+//! series such as momentum or bond proxies) and reports the factor betas,
+//! R², annualized alpha and the Sharpe ratio of the residual (idiosyncratic)
+//! returns. A high R² against buy-and-hold with a beta near the strategy's
+//! exposure means there is little left once the market is accounted for.
+//!
+//! Series are matched by date: only dates present in the strategy and in
+//! every factor enter the regression, and fewer than
+//! [`MIN_STYLE_OBSERVATIONS`] shared dates is an error.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use trendlab_core::data::cache::ParquetCache;
+use trendlab_core::data::provider::{DataProvider, RawBar};
+
+use crate::data_loader::{load_bars, LoadError, LoadOptions};
+use crate::metrics::{mean_f64, std_dev};
+use crate::runner::BacktestResult;
+
+/// Minimum number of shared return dates for a style regression.
+pub const MIN_STYLE_OBSERVATIONS: usize = 60;
+
+/// Errors from style analysis.
+#[derive(Debug, Error)]
+pub enum StyleError {
+    #[error("only {found} shared return dates across strategy and factors (need {required})")]
+    TooFewObservations { found: usize, required: usize },
+    #[error("equity curve has {equity} points but {dates} dates")]
+    DateMismatch { equity: usize, dates: usize },
+    #[error("factor returns are collinear; drop one of: {0}")]
+    Collinear(String),
+    #[error("factor file {path}: {reason}")]
+    Csv { path: String, reason: String },
+    #[error("factor data: {0}")]
+    Load(#[from] LoadError),
+}
+
+/// A named series of dated daily returns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FactorSeries {
+    pub name: String,
+    pub returns: Vec<(NaiveDate, f64)>,
+}
+
+impl FactorSeries {
+    /// Buy-and-hold returns from close-to-close moves. A return is dated on
+    /// its second bar; pairs touching a void or non-positive close are skipped.
+    pub fn from_bars(name: impl Into<String>, bars: &[RawBar]) -> Self {
+        let returns = bars
+            .windows(2)
+            .filter(|w| w[0].close > 0.0 && w[1].close.is_finite())
+            .map(|w| (w[1].date, w[1].close / w[0].close - 1.0))
+            .collect();
+        Self {
+            name: name.into(),
+            returns,
+        }
+    }
+
+    /// Growth of 1.0 through the returns: one point before the first return
+    /// and one after each.
+    pub fn equity_curve(&self) -> Vec<f64> {
+        std::iter::once(1.0)
+            .chain(self.returns.iter().scan(1.0, |equity, &(_, r)| {
+                *equity *= 1.0 + r;
+                Some(*equity)
+            }))
+            .collect()
+    }
+
+    /// Load a `date,return` CSV (with a header row). The factor is named
+    /// after the file stem.
+    pub fn from_csv(path: &Path) -> Result<Self, StyleError> {
+        let csv_err = |reason: String| StyleError::Csv {
+            path: path.display().to_string(),
+            reason,
+        };
+        let mut reader = csv::Reader::from_path(path).map_err(|e| csv_err(e.to_string()))?;
+        let mut returns = Vec::new();
+        for (i, record) in reader.records().enumerate() {
+            let record = record.map_err(|e| csv_err(e.to_string()))?;
+            let line = i + 2;
+            let (Some(date), Some(value)) = (record.get(0), record.get(1)) else {
+                return Err(csv_err(format!("line {line}: expected date,return")));
+            };
+            let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+                .map_err(|e| csv_err(format!("line {line}: bad date '{date}': {e}")))?;
+            let value: f64 = value
+                .trim()
+                .parse()
+                .map_err(|_| csv_err(format!("line {line}: bad return '{value}'")))?;
+            if value.is_finite() {
+                returns.push((date, value));
+            }
+        }
+        let name = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        Ok(Self { name, returns })
+    }
+}
+
+/// Load factor series from a list of sources. Entries ending in `.csv` are
+/// read with [`FactorSeries::from_csv`]; everything else is a symbol loaded
+/// through the data loader (cache first, `provider` only on a miss) and
+/// turned into buy-and-hold returns.
+pub fn load_factors(
+    sources: &[String],
+    cache: &ParquetCache,
+    provider: Option<&dyn DataProvider>,
+    opts: &LoadOptions,
+) -> Result<Vec<FactorSeries>, StyleError> {
+    let is_csv = |s: &str| s.to_ascii_lowercase().ends_with(".csv");
+    let symbols: Vec<&str> = sources
+        .iter()
+        .map(String::as_str)
+        .filter(|s| !is_csv(s))
+        .collect();
+    let loaded = if symbols.is_empty() {
+        None
+    } else {
+        Some(load_bars(&symbols, cache, provider, None, opts)?)
+    };
+
+    let mut factors = Vec::with_capacity(sources.len());
+    for source in sources {
+        if is_csv(source) {
+            factors.push(FactorSeries::from_csv(Path::new(source))?);
+        } else if let Some(bars) = loaded.as_ref().and_then(|l| l.aligned.bars.get(source)) {
+            factors.push(FactorSeries::from_bars(source.as_str(), bars));
+        }
+    }
+    Ok(factors)
+}
+
+/// One factor's loading in the style regression.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FactorBeta {
+    pub factor: String,
+    pub beta: f64,
+}
+
+/// Result of a returns-based style regression.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StyleReport {
+    /// Factor betas, in the order the factors were given.
+    pub betas: Vec<FactorBeta>,
+    /// Share of daily return variance explained by the factors.
+    pub r_squared: f64,
+    /// Regression intercept, annualized (252 days).
+    pub alpha: f64,
+    /// Annualized Sharpe of the returns left after removing factor exposure
+    /// (alpha plus residuals).
+    pub residual_sharpe: f64,
+    /// Shared return dates used in the regression.
+    pub observations: usize,
+}
+
+/// Regress a result's daily returns on `factors`.
+///
+/// `dates` is the bar date axis the result was run on (one per equity point);
+/// strategy returns are dated on their second bar, like factor returns.
+pub fn style_analysis(
+    result: &BacktestResult,
+    dates: &[NaiveDate],
+    factors: &[FactorSeries],
+) -> Result<StyleReport, StyleError> {
+    regress_equity(&result.equity_curve, dates, factors)
+}
+
+fn regress_equity(
+    equity: &[f64],
+    dates: &[NaiveDate],
+    factors: &[FactorSeries],
+) -> Result<StyleReport, StyleError> {
+    if equity.len() != dates.len() {
+        return Err(StyleError::DateMismatch {
+            equity: equity.len(),
+            dates: dates.len(),
+        });
+    }
+
+    let lookups: Vec<HashMap<NaiveDate, f64>> = factors
+        .iter()
+        .map(|f| f.returns.iter().copied().collect())
+        .collect();
+
+    // Intersect strategy dates with every factor's dates
+    let mut y = Vec::new();
+    let mut x: Vec<Vec<f64>> = Vec::new();
+    for (i, w) in equity.windows(2).enumerate() {
+        if w[0] <= 0.0 {
+            continue;
+        }
+        let date = dates[i + 1];
+        let row: Option<Vec<f64>> = lookups.iter().map(|l| l.get(&date).copied()).collect();
+        if let Some(row) = row {
+            y.push(w[1] / w[0] - 1.0);
+            x.push(row);
+        }
+    }
+
+    let n = y.len();
+    let required = MIN_STYLE_OBSERVATIONS.max(factors.len() + 2);
+    if n < required {
+        return Err(StyleError::TooFewObservations { found: n, required });
+    }
+
+    let names = || {
+        factors
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let coef = ols(&y, &x).ok_or_else(|| StyleError::Collinear(names()))?;
+    let (intercept, betas) = (coef[0], &coef[1..]);
+
+    // Idiosyncratic return: what the factor exposure doesn't explain
+    let idio: Vec<f64> = y
+        .iter()
+        .zip(&x)
+        .map(|(yi, row)| yi - row.iter().zip(betas).map(|(f, b)| f * b).sum::<f64>())
+        .collect();
+    let residual_var = idio.iter().map(|r| (r - intercept).powi(2)).sum::<f64>();
+    let y_mean = mean_f64(&y);
+    let total_var = y.iter().map(|v| (v - y_mean).powi(2)).sum::<f64>();
+    let r_squared = if total_var > 0.0 {
+        (1.0 - residual_var / total_var).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let idio_std = std_dev(&idio);
+    let residual_sharpe = if idio_std > 0.0 {
+        mean_f64(&idio) / idio_std * 252f64.sqrt()
+    } else {
+        0.0
+    };
+
+    Ok(StyleReport {
+        betas: factors
+            .iter()
+            .zip(betas)
+            .map(|(f, &beta)| FactorBeta {
+                factor: f.name.clone(),
+                beta,
+            })
+            .collect(),
+        r_squared,
+        alpha: intercept * 252.0,
+        residual_sharpe,
+        observations: n,
+    })
+}
+
+/// Ordinary least squares with an intercept, via the normal equations.
+/// Returns `[intercept, b1, .., bk]`, or None when the factors are collinear.
+fn ols(y: &[f64], x: &[Vec<f64>]) -> Option<Vec<f64>> {
+    let k = x.first().map_or(0, Vec::len) + 1;
+    // Augmented [X'X | X'y] with a leading constant column
+    let mut m = vec![vec![0.0; k + 1]; k];
+    for (row, &yi) in x.iter().zip(y) {
+        let full: Vec<f64> = std::iter::once(1.0).chain(row.iter().copied()).collect();
+        for i in 0..k {
+            for j in 0..k {
+                m[i][j] += full[i] * full[j];
+            }
+            m[i][k] += full[i] * yi;
+        }
+    }
+
+    // Gauss-Jordan elimination with partial pivoting. A pivot that is
+    // negligible next to its column's original scale means collinearity.
+    let scale: Vec<f64> = (0..k).map(|i| m[i][i]).collect();
+    for col in 0..k {
+        let pivot = (col..k).max_by(|&a, &b| m[a][col].abs().total_cmp(&m[b][col].abs()))?;
+        if m[pivot][col].abs() <= 1e-10 * scale[col] {
+            return None;
+        }
+        m.swap(col, pivot);
+        let pivot_row = m[col].clone();
+        for (r, row) in m.iter_mut().enumerate() {
+            if r != col {
+                let factor = row[col] / pivot_row[col];
+                for (v, p) in row.iter_mut().zip(&pivot_row).skip(col) {
+                    *v -= factor * p;
+                }
+            }
+        }
+    }
+    Some((0..k).map(|i| m[i][k] / m[i][i]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days(n: usize) -> Vec<NaiveDate> {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        (0..n)
+            .map(|i| start + chrono::Days::new(i as u64))
+            .collect()
+    }
+
+    /// Deterministic, non-trivial market returns.
+    fn market_returns(n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|i| 0.01 * ((i as f64 * 0.7).sin() + 0.3 * (i as f64 * 1.9).cos()))
+            .collect()
+    }
+
+    fn equity_from_returns(returns: &[f64]) -> Vec<f64> {
+        let mut equity = vec![100_000.0];
+        for r in returns {
+            equity.push(equity.last().unwrap() * (1.0 + r));
+        }
+        equity
+    }
+
+    fn series(name: &str, dates: &[NaiveDate], returns: &[f64]) -> FactorSeries {
+        FactorSeries {
+            name: name.into(),
+            returns: dates[1..]
+                .iter()
+                .copied()
+                .zip(returns.iter().copied())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn levered_beta_is_fully_explained() {
+        let market = market_returns(200);
+        let strategy: Vec<f64> = market.iter().map(|r| 1.5 * r).collect();
+        let dates = days(201);
+        let report = regress_equity(
+            &equity_from_returns(&strategy),
+            &dates,
+            &[series("SPY", &dates, &market)],
+        )
+        .unwrap();
+        assert_eq!(report.observations, 200);
+        assert!((report.betas[0].beta - 1.5).abs() < 1e-9);
+        assert!(report.r_squared > 0.999_999);
+        assert!(report.alpha.abs() < 1e-9);
+    }
+
+    #[test]
+    fn two_factor_regression_recovers_betas_and_alpha() {
+        let market = market_returns(300);
+        let bonds: Vec<f64> = (0..300).map(|i| 0.004 * (i as f64 * 0.31).cos()).collect();
+        let strategy: Vec<f64> = market
+            .iter()
+            .zip(&bonds)
+            .map(|(m, b)| 0.0004 + 0.6 * m - 0.8 * b)
+            .collect();
+        let dates = days(301);
+        let report = regress_equity(
+            &equity_from_returns(&strategy),
+            &dates,
+            &[
+                series("SPY", &dates, &market),
+                series("bonds", &dates, &bonds),
+            ],
+        )
+        .unwrap();
+        assert_eq!(report.betas[0].factor, "SPY");
+        assert!((report.betas[0].beta - 0.6).abs() < 1e-9);
+        assert!((report.betas[1].beta + 0.8).abs() < 1e-9);
+        assert!((report.alpha - 0.0004 * 252.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn dates_are_intersected_across_factors() {
+        let market = market_returns(150);
+        let dates = days(151);
+        let strategy: Vec<f64> = market.iter().map(|r| 0.5 * r).collect();
+        // Factor covers only the last 100 return dates
+        let partial = FactorSeries {
+            name: "late".into(),
+            returns: dates[51..]
+                .iter()
+                .copied()
+                .zip(market[50..].iter().copied())
+                .collect(),
+        };
+        let report = regress_equity(&equity_from_returns(&strategy), &dates, &[partial]).unwrap();
+        assert_eq!(report.observations, 100);
+        assert!((report.betas[0].beta - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn too_few_shared_dates_is_an_error() {
+        let market = market_returns(100);
+        let dates = days(101);
+        let partial = FactorSeries {
+            name: "short".into(),
+            returns: dates[71..]
+                .iter()
+                .copied()
+                .zip(market[70..].iter().copied())
+                .collect(),
+        };
+        let err = regress_equity(&equity_from_returns(&market), &dates, &[partial]).unwrap_err();
+        assert!(matches!(
+            err,
+            StyleError::TooFewObservations {
+                found: 30,
+                required: MIN_STYLE_OBSERVATIONS
+            }
+        ));
+    }
+
+    #[test]
+    fn collinear_factors_are_rejected() {
+        let market = market_returns(100);
+        let dates = days(101);
+        let doubled: Vec<f64> = market.iter().map(|r| 2.0 * r).collect();
+        let err = regress_equity(
+            &equity_from_returns(&market),
+            &dates,
+            &[series("a", &dates, &market), series("b", &dates, &doubled)],
+        )
+        .unwrap_err();
+        assert!(matches!(err, StyleError::Collinear(names) if names == "a, b"));
+    }
+
+    #[test]
+    fn csv_factor_loads_dates_and_returns() {
+        let path = std::env::temp_dir().join(format!("momentum_{}.csv", std::process::id()));
+        std::fs::write(&path, "date,return\n2024-01-02,0.01\n2024-01-03,-0.005\n").unwrap();
+        let factor = FactorSeries::from_csv(&path).unwrap();
+        assert!(factor.name.starts_with("momentum_"));
+        assert_eq!(
+            factor.returns,
+            vec![
+                (NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(), 0.01),
+                (NaiveDate::from_ymd_opt(2024, 1, 3).unwrap(), -0.005),
+            ]
+        );
+
+        std::fs::write(&path, "date,return\n2024-01-02,abc\n").unwrap();
+        let err = FactorSeries::from_csv(&path).unwrap_err();
+        assert!(err.to_string().contains("line 2: bad return 'abc'"));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn buy_and_hold_skips_void_bars() {
+        let bar = |day: u32, close: f64| RawBar {
+            date: NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 0,
+            adj_close: close,
+        };
+        let bars = [bar(2, 100.0), bar(3, 110.0), bar(4, f64::NAN), bar(5, 99.0)];
+        let factor = FactorSeries::from_bars("SPY", &bars);
+        assert_eq!(factor.returns.len(), 1);
+        assert!((factor.returns[0].1 - 0.1).abs() < 1e-12);
+    }
+}
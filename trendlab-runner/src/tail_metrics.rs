@@ -1,17 +1,29 @@
-//! Tail risk metrics — CVaR, skewness, kurtosis, downside deviation ratio.
+//! Tail risk metrics — CVaR, skewness, kurtosis, downside deviation ratio,
+//! and the worst-days table.
 //!
 //! These complement the core `PerformanceMetrics` with distribution shape
 //! statistics needed by the cross-symbol leaderboard and risk profile system.
 //! All functions are pure: daily returns in, scalar out.
 
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
+use trendlab_core::domain::TradeRecord;
+
 use crate::metrics::{daily_returns, mean_f64, std_dev};
 
-/// Minimum number of daily return observations required for tail metrics.
-/// Below this threshold, all Optional fields return None.
+/// Minimum number of daily return observations required for the shape
+/// statistics (skewness, kurtosis, downside deviation ratio). Below this
+/// threshold those fields are None.
 pub const MIN_RETURN_OBSERVATIONS: usize = 252;
 
+/// Minimum number of daily return observations required for CVaR. With fewer,
+/// the 99% tail is a single day or less, so both CVaR fields are None.
+pub const MIN_CVAR_OBSERVATIONS: usize = 100;
+
+/// Default number of rows in the worst-days table.
+pub const DEFAULT_WORST_DAYS: usize = 10;
+
 /// Tail risk statistics computed from an equity curve's daily returns.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TailMetrics {
@@ -20,6 +32,10 @@ pub struct TailMetrics {
     /// in the worst 5% of days).
     pub cvar_95: Option<f64>,
 
+    /// Conditional Value at Risk at 99% — average return of the worst 1% of days.
+    #[serde(default)]
+    pub cvar_99: Option<f64>,
+
     /// Skewness of daily returns (third standardized moment).
     /// Negative = left tail heavier (more large losses than gains).
     pub skewness: Option<f64>,
@@ -34,45 +50,92 @@ pub struct TailMetrics {
 
     /// Number of return observations used.
     pub sample_size: usize,
+
+    /// Worst daily returns, worst first. Only filled for single-symbol runs,
+    /// where returns map back to dates (see [`worst_days`]).
+    #[serde(default)]
+    pub worst_days: Vec<WorstDay>,
+}
+
+/// One row of the worst-days table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorstDay {
+    pub date: NaiveDate,
+    /// Daily return of the equity curve on this date.
+    pub daily_return: f64,
+    /// Positions open during the day (entered on or before it, exited on or after it).
+    pub concurrent_positions: usize,
 }
 
 /// Compute all tail risk metrics from an equity curve.
 ///
-/// Returns `TailMetrics` with `None` for all statistical fields if the equity
-/// curve has fewer than `MIN_RETURN_OBSERVATIONS + 1` data points (we need
-/// n+1 prices to compute n returns).
+/// Returns `TailMetrics` with `None` for the CVaR fields below
+/// `MIN_CVAR_OBSERVATIONS` returns, and for the shape statistics below
+/// `MIN_RETURN_OBSERVATIONS` (we need n+1 prices to compute n returns).
+/// The worst-days table is left empty; see [`worst_days`].
 pub fn compute_tail_metrics(equity_curve: &[f64]) -> TailMetrics {
     let returns = daily_returns(equity_curve);
     let n = returns.len();
-
-    if n < MIN_RETURN_OBSERVATIONS {
-        return TailMetrics {
-            cvar_95: None,
-            skewness: None,
-            kurtosis: None,
-            downside_deviation_ratio: None,
-            sample_size: n,
-        };
-    }
+    let has_cvar = n >= MIN_CVAR_OBSERVATIONS;
+    let has_shape = n >= MIN_RETURN_OBSERVATIONS;
 
     TailMetrics {
-        cvar_95: Some(cvar_95(&returns)),
-        skewness: Some(skewness(&returns)),
-        kurtosis: Some(excess_kurtosis(&returns)),
-        downside_deviation_ratio: Some(downside_deviation_ratio(&returns)),
+        cvar_95: has_cvar.then(|| cvar(&returns, 0.05)),
+        cvar_99: has_cvar.then(|| cvar(&returns, 0.01)),
+        skewness: has_shape.then(|| skewness(&returns)),
+        kurtosis: has_shape.then(|| excess_kurtosis(&returns)),
+        downside_deviation_ratio: has_shape.then(|| downside_deviation_ratio(&returns)),
         sample_size: n,
+        worst_days: Vec::new(),
     }
 }
 
-/// Conditional Value at Risk at the 95th percentile.
+/// The `n` worst daily returns of a single-symbol run, worst first.
 ///
-/// Sort returns ascending, take the bottom 5%, and compute their mean.
-/// This represents the expected loss on the worst 5% of trading days.
-fn cvar_95(returns: &[f64]) -> f64 {
+/// `dates[i]` is the date of `equity_curve[i]`, so return `i` (from bar i to
+/// bar i+1) is dated `dates[i + 1]`. Concurrent positions count the trades
+/// open on that bar. Ties keep date order.
+pub fn worst_days(
+    equity_curve: &[f64],
+    dates: &[NaiveDate],
+    trades: &[TradeRecord],
+    n: usize,
+) -> Vec<WorstDay> {
+    let returns = daily_returns(equity_curve);
+    let mut order: Vec<usize> = (0..returns.len().min(dates.len().saturating_sub(1))).collect();
+    order.sort_by(|&a, &b| {
+        returns[a]
+            .partial_cmp(&returns[b])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    order
+        .into_iter()
+        .take(n)
+        .map(|i| {
+            let bar = i + 1;
+            WorstDay {
+                date: dates[bar],
+                daily_return: returns[i],
+                concurrent_positions: trades
+                    .iter()
+                    .filter(|t| t.entry_bar <= bar && bar <= t.exit_bar)
+                    .count(),
+            }
+        })
+        .collect()
+}
+
+/// Conditional Value at Risk: mean of the worst `tail` fraction of returns.
+///
+/// Sort returns ascending, take the bottom `tail` share (at least one
+/// observation), and compute their mean. With `tail = 0.05` this is the
+/// expected loss on the worst 5% of trading days.
+fn cvar(returns: &[f64], tail: f64) -> f64 {
     let mut sorted = returns.to_vec();
     sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
-    let cutoff = (sorted.len() as f64 * 0.05).ceil() as usize;
+    let cutoff = (sorted.len() as f64 * tail).ceil() as usize;
     let cutoff = cutoff.max(1); // at least 1 observation
     let tail = &sorted[..cutoff];
 
@@ -164,10 +227,11 @@ mod tests {
 
     #[test]
     fn insufficient_data_returns_none() {
-        // 100 bars = 99 returns, below 252 threshold
+        // 100 bars = 99 returns, below both thresholds
         let eq = vec![100_000.0; 100];
         let tm = compute_tail_metrics(&eq);
         assert!(tm.cvar_95.is_none());
+        assert!(tm.cvar_99.is_none());
         assert!(tm.skewness.is_none());
         assert!(tm.kurtosis.is_none());
         assert!(tm.downside_deviation_ratio.is_none());
@@ -198,12 +262,101 @@ mod tests {
         let tm = compute_tail_metrics(&eq);
 
         assert!(tm.cvar_95.is_some());
+        assert!(tm.cvar_99.is_some());
         assert!(tm.skewness.is_some());
         assert!(tm.kurtosis.is_some());
         assert!(tm.downside_deviation_ratio.is_some());
         assert_eq!(tm.sample_size, 252);
     }
 
+    #[test]
+    fn short_run_has_cvar_but_no_shape_stats() {
+        // 100 returns: enough for CVaR, not for skewness/kurtosis
+        let returns: Vec<f64> = (0..100).map(|i| (i as f64 - 50.0) / 1000.0).collect();
+        let eq = equity_from_returns(100_000.0, &returns);
+        let tm = compute_tail_metrics(&eq);
+
+        assert!(tm.skewness.is_none());
+        assert!(tm.kurtosis.is_none());
+        // Worst 5 days: -0.050..=-0.046; worst 1 day: -0.050
+        assert!((tm.cvar_95.unwrap() - (-0.048)).abs() < 1e-10);
+        assert!((tm.cvar_99.unwrap() - (-0.050)).abs() < 1e-10);
+    }
+
+    #[test]
+    fn cvar_99_is_at_least_as_bad_as_cvar_95() {
+        let returns: Vec<f64> = (0..300)
+            .map(|i| match i % 50 {
+                0 => -0.08,
+                1..=10 => -0.01,
+                _ => 0.004,
+            })
+            .collect();
+        let eq = equity_from_returns(100_000.0, &returns);
+        let tm = compute_tail_metrics(&eq);
+
+        assert!((tm.cvar_99.unwrap() - (-0.08)).abs() < 1e-10);
+        assert!(tm.cvar_99.unwrap() < tm.cvar_95.unwrap());
+    }
+
+    #[test]
+    fn worst_days_dated_and_counted() {
+        let returns = [0.01, -0.03, 0.02, -0.01, -0.05, 0.0];
+        let eq = equity_from_returns(100_000.0, &returns);
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let dates: Vec<NaiveDate> = (0..eq.len() as u64)
+            .map(|d| start + chrono::Days::new(d))
+            .collect();
+        let mut trade = trendlab_core::domain::TradeRecord {
+            symbol: "SPY".into(),
+            side: trendlab_core::domain::PositionSide::Long,
+            entry_bar: 1,
+            entry_date: dates[1],
+            entry_price: 100.0,
+            entry_trigger_margin: None,
+            exit_bar: 4,
+            exit_date: dates[4],
+            exit_price: 100.0,
+            quantity: 1.0,
+            gross_pnl: 0.0,
+            commission: 0.0,
+            slippage: 0.0,
+            net_pnl: 0.0,
+            bars_held: 3,
+            mae: 0.0,
+            mfe: 0.0,
+            initial_stop_price: None,
+            entry_source: None,
+            exit_source: None,
+            liquidated_at_end: false,
+            expiry_bar: None,
+            signal_id: None,
+            signal_type: None,
+            pm_type: None,
+            execution_model: None,
+            filter_type: None,
+        };
+        let mut trades = vec![trade.clone()];
+        trade.entry_bar = 4;
+        trade.exit_bar = 6;
+        trades.push(trade);
+
+        let worst = worst_days(&eq, &dates, &trades, 3);
+        assert_eq!(worst.len(), 3);
+        // Return 4 (-5%) lands on bar 5, return 1 (-3%) on bar 2
+        assert_eq!(worst[0].date, dates[5]);
+        assert!((worst[0].daily_return - (-0.05)).abs() < 1e-10);
+        assert_eq!(worst[0].concurrent_positions, 1);
+        assert_eq!(worst[1].date, dates[2]);
+        assert_eq!(worst[1].concurrent_positions, 1);
+        assert_eq!(worst[2].date, dates[4]);
+        assert_eq!(worst[2].concurrent_positions, 2);
+
+        // Asking for more rows than returns yields every return
+        assert_eq!(worst_days(&eq, &dates, &[], 50).len(), returns.len());
+        assert!(worst_days(&eq, &[], &[], 5).is_empty());
+    }
+
     #[test]
     fn cvar_95_is_negative_for_mixed_returns() {
         let returns: Vec<f64> = (0..300)
@@ -319,14 +472,27 @@ mod tests {
     fn tail_metrics_serialization_roundtrip() {
         let tm = TailMetrics {
             cvar_95: Some(-0.02),
+            cvar_99: Some(-0.04),
             skewness: Some(-0.5),
             kurtosis: Some(1.2),
             downside_deviation_ratio: Some(0.8),
             sample_size: 300,
+            worst_days: vec![WorstDay {
+                date: NaiveDate::from_ymd_opt(2024, 3, 5).unwrap(),
+                daily_return: -0.031,
+                concurrent_positions: 1,
+            }],
         };
         let json = serde_json::to_string(&tm).unwrap();
         let deser: TailMetrics = serde_json::from_str(&json).unwrap();
         assert_eq!(deser.sample_size, 300);
         assert!((deser.cvar_95.unwrap() - (-0.02)).abs() < 1e-10);
+        assert_eq!(deser.worst_days, tm.worst_days);
+
+        // Older JSON without the new fields still loads
+        let old = r#"{"cvar_95":-0.02,"skewness":null,"kurtosis":null,"downside_deviation_ratio":null,"sample_size":300}"#;
+        let deser: TailMetrics = serde_json::from_str(old).unwrap();
+        assert!(deser.cvar_99.is_none());
+        assert!(deser.worst_days.is_empty());
     }
 }
@@ -0,0 +1,375 @@
+//! Run verification — re-run a saved backtest and check it reproduces.
+//!
+//! A manifest records the strategy config, the dataset hash, and (since
+//! [`RunInputs`] was added) the trading mode, sizing, execution preset, and
+//! symbol universe of the run. [`verify_run`] reloads that universe from the
+//! cache, trims it to the manifest's date range, and refuses to go further if
+//! the bars no longer hash to the recorded dataset hash. Otherwise it re-runs
+//! the config and reports the first place the new result departs from the
+//! saved one: a trade (with the bar it entered on), the trade count, or a
+//! headline metric.
+//!
+//! [`verify_run_as_of`] is stricter: before anything else it checks the
+//! manifest's data vintage against the cache and refuses when any partition
+//! the run read has changed since, naming the symbols. A cache that only grew
+//! a newer tail passes the dataset hash check but fails this one.
+
+use std::fmt;
+use std::path::Path;
+
+use chrono::NaiveDate;
+use thiserror::Error;
+
+use trendlab_core::data::align::AlignedData;
+use trendlab_core::data::cache::ParquetCache;
+use trendlab_core::data::interval::BarInterval;
+use trendlab_core::domain::{SignalEventId, TradeRecord};
+
+use crate::data_loader::{compute_dataset_hash, load_bars, LoadError, LoadOptions};
+use crate::export::load_artifacts;
+use crate::metrics::PerformanceMetrics;
+use crate::runner::{run_backtest_profiled, BacktestResult, RunError, RunInputs};
+use crate::vintage::{format_changes, vintage_changes, VintageChange};
+use crate::walk_forward::slice_aligned_data;
+
+/// Default tolerance for PnL and metric comparisons (relative to the larger
+/// magnitude, or absolute below 1.0).
+pub const DEFAULT_TOLERANCE: f64 = 1e-9;
+
+/// Reasons a saved run could not be re-run at all.
+#[derive(Debug, Error)]
+pub enum VerifyError {
+    #[error("manifest at {path}: {reason}")]
+    Manifest { path: String, reason: String },
+    #[error("manifest does not record its run inputs (saved before verify-run support, or run with a custom execution config)")]
+    MissingInputs,
+    #[error("manifest has an invalid {field} '{value}'")]
+    BadDate { field: &'static str, value: String },
+    #[error("loading {symbols} from the cache: {source}")]
+    DataLoad {
+        symbols: String,
+        #[source]
+        source: LoadError,
+    },
+    #[error(
+        "dataset hash mismatch for {symbols} over {start}..{end}: manifest has {expected}, \
+         the cache now gives {actual}. The cached bars changed since the run"
+    )]
+    DatasetMismatch {
+        symbols: String,
+        start: NaiveDate,
+        end: NaiveDate,
+        expected: String,
+        actual: String,
+    },
+    #[error("manifest records no data vintage (saved before vintages were recorded, or run on synthetic or pre-loaded data)")]
+    MissingVintage,
+    #[error("cached data changed since the run: {}", format_changes(.changes))]
+    VintageMismatch { changes: Vec<VintageChange> },
+    #[error("re-run failed: {0}")]
+    Run(#[from] RunError),
+}
+
+/// First difference between a saved result and its re-run.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Divergence {
+    /// Trade `index` entered or exited on a different bar, or its PnL moved
+    /// beyond the tolerance. `bar` is the saved trade's entry bar.
+    Trade {
+        index: usize,
+        bar: usize,
+        signal_id: Option<SignalEventId>,
+        field: &'static str,
+        saved: f64,
+        rerun: f64,
+    },
+    /// Every common trade matched, but one side has more. `bar` is the entry
+    /// bar of the first unmatched trade.
+    TradeCount {
+        saved: usize,
+        rerun: usize,
+        bar: usize,
+    },
+    /// Trades matched, but a headline metric did not.
+    Metric {
+        name: &'static str,
+        saved: f64,
+        rerun: f64,
+    },
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Trade {
+                index,
+                bar,
+                signal_id,
+                field,
+                saved,
+                rerun,
+            } => {
+                write!(f, "trade #{index} (entry bar {bar}")?;
+                if let Some(id) = signal_id {
+                    write!(f, ", {id}")?;
+                }
+                write!(f, "): {field} {saved} saved, {rerun} on re-run")
+            }
+            Self::TradeCount { saved, rerun, bar } => write!(
+                f,
+                "{saved} trades saved, {rerun} on re-run (first unmatched entry at bar {bar})"
+            ),
+            Self::Metric { name, saved, rerun } => {
+                write!(f, "{name} {saved} saved, {rerun} on re-run")
+            }
+        }
+    }
+}
+
+/// Outcome of re-running one saved result.
+#[derive(Debug, Clone)]
+pub struct VerifyReport {
+    pub symbol: String,
+    pub trade_count: usize,
+    /// None when the re-run matched within the tolerance.
+    pub divergence: Option<Divergence>,
+}
+
+impl VerifyReport {
+    pub fn is_reproduced(&self) -> bool {
+        self.divergence.is_none()
+    }
+}
+
+/// Load the manifest in `dir` and verify it against the cache, as of the
+/// run's data vintage when `as_of` is set.
+pub fn verify_run_dir(
+    dir: &Path,
+    cache: &ParquetCache,
+    tolerance: f64,
+    as_of: bool,
+) -> Result<VerifyReport, VerifyError> {
+    let saved = load_artifacts(dir).map_err(|err| VerifyError::Manifest {
+        path: dir.display().to_string(),
+        reason: format!("{err:#}"),
+    })?;
+    if as_of {
+        verify_run_as_of(&saved, cache, tolerance)
+    } else {
+        verify_run(&saved, cache, tolerance)
+    }
+}
+
+/// [`verify_run`], refusing first if any cache partition the run read no
+/// longer holds what it held at the run.
+pub fn verify_run_as_of(
+    saved: &BacktestResult,
+    cache: &ParquetCache,
+    tolerance: f64,
+) -> Result<VerifyReport, VerifyError> {
+    if saved.data_vintage.is_empty() {
+        return Err(VerifyError::MissingVintage);
+    }
+    let changes = vintage_changes(&saved.data_vintage, cache);
+    if !changes.is_empty() {
+        return Err(VerifyError::VintageMismatch { changes });
+    }
+    verify_run(saved, cache, tolerance)
+}
+
+/// Re-run `saved` from the cache and compare the result with it.
+///
+/// Never touches the network: verification is only meaningful against the
+/// bars the run saw, which a fresh download cannot promise.
+pub fn verify_run(
+    saved: &BacktestResult,
+    cache: &ParquetCache,
+    tolerance: f64,
+) -> Result<VerifyReport, VerifyError> {
+    let inputs = saved.inputs.as_ref().ok_or(VerifyError::MissingInputs)?;
+    let start = parse_date("start_date", &saved.start_date)?;
+    let end = parse_date("end_date", &saved.end_date)?;
+
+    let aligned = load_universe(inputs, cache, saved.interval, start, end)?;
+    let actual = compute_dataset_hash(&aligned);
+    if actual != saved.dataset_hash {
+        return Err(VerifyError::DatasetMismatch {
+            symbols: inputs.symbols.join(", "),
+            start,
+            end,
+            expected: saved.dataset_hash.clone(),
+            actual,
+        });
+    }
+
+    let rerun = run_backtest_profiled(
+        &saved.config,
+        &aligned,
+        &saved.symbol,
+        inputs.trading_mode,
+        saved.initial_capital,
+        inputs.position_size_pct,
+        inputs.execution_preset,
+        inputs.execution_profile.as_ref(),
+        &actual,
+        saved.has_synthetic,
+    )?;
+    Ok(VerifyReport {
+        symbol: saved.symbol.clone(),
+        trade_count: saved.trades.len(),
+        divergence: compare_results(saved, &rerun, tolerance),
+    })
+}
+
+fn parse_date(field: &'static str, value: &str) -> Result<NaiveDate, VerifyError> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| VerifyError::BadDate {
+        field,
+        value: value.to_string(),
+    })
+}
+
+/// Load the run's symbols from the cache and keep only `start..=end`.
+fn load_universe(
+    inputs: &RunInputs,
+    cache: &ParquetCache,
+    interval: BarInterval,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<AlignedData, VerifyError> {
+    let symbols: Vec<&str> = inputs.symbols.iter().map(String::as_str).collect();
+    let opts = LoadOptions {
+        start,
+        end,
+        offline: true,
+        synthetic: false,
+        force: false,
+        strict: false,
+        stale_after_days: None,
+        interval,
+    };
+    let loaded =
+        load_bars(&symbols, cache, None, None, &opts).map_err(|source| VerifyError::DataLoad {
+            symbols: inputs.symbols.join(", "),
+            source,
+        })?;
+    // The cache may have grown since the run; its range is what was hashed
+    let dates = &loaded.aligned.dates;
+    let from = dates.partition_point(|d| *d < start);
+    let to = dates.partition_point(|d| *d <= end);
+    Ok(slice_aligned_data(&loaded.aligned, from, to))
+}
+
+/// First divergence of `rerun` from `saved`: trades in order, then the
+/// trade count, then headline metrics.
+pub fn compare_results(
+    saved: &BacktestResult,
+    rerun: &BacktestResult,
+    tolerance: f64,
+) -> Option<Divergence> {
+    for (index, (a, b)) in saved.trades.iter().zip(&rerun.trades).enumerate() {
+        if let Some((field, saved_value, rerun_value)) = trade_difference(a, b, tolerance) {
+            return Some(Divergence::Trade {
+                index,
+                bar: a.entry_bar,
+                signal_id: a.signal_id,
+                field,
+                saved: saved_value,
+                rerun: rerun_value,
+            });
+        }
+    }
+
+    let common = saved.trades.len().min(rerun.trades.len());
+    if saved.trades.len() != rerun.trades.len() {
+        let first_unmatched = saved.trades.get(common).or(rerun.trades.get(common));
+        return Some(Divergence::TradeCount {
+            saved: saved.trades.len(),
+            rerun: rerun.trades.len(),
+            bar: first_unmatched.map_or(0, |t| t.entry_bar),
+        });
+    }
+
+    metric_values(&saved.metrics)
+        .into_iter()
+        .zip(metric_values(&rerun.metrics))
+        .find(|((_, a), (_, b))| !within(*a, *b, tolerance))
+        .map(|((name, a), (_, b))| Divergence::Metric {
+            name,
+            saved: a,
+            rerun: b,
+        })
+}
+
+fn trade_difference(
+    a: &TradeRecord,
+    b: &TradeRecord,
+    tolerance: f64,
+) -> Option<(&'static str, f64, f64)> {
+    if a.entry_bar != b.entry_bar {
+        return Some(("entry_bar", a.entry_bar as f64, b.entry_bar as f64));
+    }
+    if a.exit_bar != b.exit_bar {
+        return Some(("exit_bar", a.exit_bar as f64, b.exit_bar as f64));
+    }
+    [
+        ("entry_price", a.entry_price, b.entry_price),
+        ("exit_price", a.exit_price, b.exit_price),
+        ("quantity", a.quantity, b.quantity),
+        ("net_pnl", a.net_pnl, b.net_pnl),
+    ]
+    .into_iter()
+    .find(|(_, x, y)| !within(*x, *y, tolerance))
+}
+
+pub(crate) fn metric_values(m: &PerformanceMetrics) -> [(&'static str, f64); 9] {
+    [
+        ("total_return", m.total_return),
+        ("cagr", m.cagr),
+        ("sharpe", m.sharpe),
+        ("sortino", m.sortino),
+        ("calmar", m.calmar),
+        ("max_drawdown", m.max_drawdown),
+        ("win_rate", m.win_rate),
+        ("profit_factor", m.profit_factor),
+        ("turnover", m.turnover),
+    ]
+}
+
+/// Equal within `tolerance`, relative above magnitude 1.0. Non-finite values
+/// (saved as null, loaded as NaN) match any non-finite value.
+fn within(a: f64, b: f64, tolerance: f64) -> bool {
+    if !a.is_finite() || !b.is_finite() {
+        return !a.is_finite() && !b.is_finite();
+    }
+    (a - b).abs() <= tolerance * a.abs().max(b.abs()).max(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tolerance_is_relative_above_one() {
+        assert!(within(1.0, 1.0 + 1e-10, 1e-9));
+        assert!(!within(1.0, 1.0 + 1e-8, 1e-9));
+        assert!(within(1e6, 1e6 + 1e-4, 1e-9));
+        assert!(within(f64::NAN, f64::INFINITY, 1e-9));
+        assert!(!within(f64::NAN, 0.0, 1e-9));
+    }
+
+    #[test]
+    fn divergence_names_the_trade_and_bar() {
+        let d = Divergence::Trade {
+            index: 3,
+            bar: 120,
+            signal_id: Some(SignalEventId(7)),
+            field: "net_pnl",
+            saved: 10.5,
+            rerun: 11.0,
+        };
+        assert_eq!(
+            d.to_string(),
+            "trade #3 (entry bar 120, SignalEventId(7)): net_pnl 10.5 saved, 11 on re-run"
+        );
+    }
+}
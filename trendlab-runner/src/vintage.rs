@@ -0,0 +1,222 @@
+//! Data vintages — which cached bars a run saw, and whether they still exist.
+//!
+//! A saved run records the [`PartitionVintage`] of every cache partition its
+//! bars came from: when the partition was last written and a hash of its
+//! content. The dataset hash only covers the run's date range, so a cache
+//! that has since grown a newer tail still reproduces the run; the vintage
+//! tells the two apart. [`vintage_changes`] lists the partitions whose
+//! content is no longer what the run saw, and [`snapshot_partitions`] copies
+//! the exact partitions into the run's artifact directory so the run can be
+//! re-checked without the shared cache.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDateTime;
+use thiserror::Error;
+
+use trendlab_core::data::cache::{ParquetCache, PartitionVintage};
+
+/// Directory inside a run's artifact directory holding its snapshotted
+/// partitions, laid out as a cache: pass it as the cache directory.
+pub const SNAPSHOT_DIR: &str = "data";
+
+/// Default size limit for [`snapshot_partitions`], in bytes.
+pub const DEFAULT_SNAPSHOT_MAX_BYTES: u64 = 256 * 1024 * 1024;
+
+/// A partition whose content differs from the recorded vintage.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VintageChange {
+    pub recorded: PartitionVintage,
+    /// The partition now; None when it is gone from the cache.
+    pub current: Option<PartitionVintage>,
+}
+
+impl VintageChange {
+    pub fn symbol(&self) -> &str {
+        &self.recorded.symbol
+    }
+}
+
+impl fmt::Display for VintageChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = self.symbol();
+        match &self.current {
+            None => write!(f, "{symbol}: no longer cached"),
+            Some(current) => write!(
+                f,
+                "{symbol}: content changed (cached {} at the run, {} now)",
+                cached_label(self.recorded.cached_at),
+                cached_label(current.cached_at)
+            ),
+        }
+    }
+}
+
+fn cached_label(at: Option<NaiveDateTime>) -> String {
+    at.map_or_else(
+        || "at an unknown time".to_string(),
+        |at| at.format("%Y-%m-%d %H:%M:%S").to_string(),
+    )
+}
+
+/// One line per change, e.g. "SPY: content changed (...); QQQ: no longer cached".
+pub fn format_changes(changes: &[VintageChange]) -> String {
+    changes
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Partitions of `recorded` whose content in `cache` no longer matches, in
+/// recorded order. Empty when every partition is as the run saw it.
+pub fn vintage_changes(recorded: &[PartitionVintage], cache: &ParquetCache) -> Vec<VintageChange> {
+    recorded
+        .iter()
+        .filter_map(|vintage| {
+            let current = cache
+                .with_interval(vintage.interval)
+                .vintage(&vintage.symbol)
+                .ok();
+            let unchanged = current
+                .as_ref()
+                .is_some_and(|c| c.content_hash == vintage.content_hash);
+            (!unchanged).then(|| VintageChange {
+                recorded: vintage.clone(),
+                current,
+            })
+        })
+        .collect()
+}
+
+/// Why a run's partitions could not be snapshotted.
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error(
+        "the run records no data vintage (synthetic data, or saved before vintages were recorded)"
+    )]
+    NoVintage,
+    #[error("the cache changed since the run: {}", format_changes(.changes))]
+    Changed { changes: Vec<VintageChange> },
+    #[error("partitions total {bytes} bytes, over the {max} byte limit")]
+    TooLarge { bytes: u64, max: u64 },
+    #[error("{path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("{0}")]
+    Data(#[from] trendlab_core::data::provider::DataError),
+}
+
+/// What [`snapshot_partitions`] copied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotReport {
+    /// The snapshot cache directory.
+    pub dir: PathBuf,
+    pub partitions: usize,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// Copy the partitions of `recorded` from `cache` into `run_dir`/[`SNAPSHOT_DIR`].
+///
+/// Refuses when any partition changed since the run, since the copy would
+/// not be what the run saw, and when the files add up to more than
+/// `max_bytes`. Nothing is written in either case.
+pub fn snapshot_partitions(
+    recorded: &[PartitionVintage],
+    cache: &ParquetCache,
+    run_dir: &Path,
+    max_bytes: Option<u64>,
+) -> Result<SnapshotReport, SnapshotError> {
+    if recorded.is_empty() {
+        return Err(SnapshotError::NoVintage);
+    }
+    let changes = vintage_changes(recorded, cache);
+    if !changes.is_empty() {
+        return Err(SnapshotError::Changed { changes });
+    }
+
+    let mut copies = Vec::new();
+    let mut bytes = 0;
+    for vintage in recorded {
+        let partition = cache.with_interval(vintage.interval);
+        let dir = partition.partition_dir(&vintage.symbol);
+        let relative = dir.strip_prefix(cache.cache_dir()).unwrap_or(&dir);
+        for file in partition.partition_files(&vintage.symbol)? {
+            bytes += fs::metadata(&file)
+                .map_err(|source| io_error(&file, source))?
+                .len();
+            let dest = run_dir.join(SNAPSHOT_DIR).join(relative);
+            copies.push((file, dest));
+        }
+    }
+    if let Some(max) = max_bytes.filter(|&max| bytes > max) {
+        return Err(SnapshotError::TooLarge { bytes, max });
+    }
+
+    for (file, dest_dir) in &copies {
+        fs::create_dir_all(dest_dir).map_err(|source| io_error(dest_dir, source))?;
+        let name = file.file_name().unwrap_or_default();
+        fs::copy(file, dest_dir.join(name)).map_err(|source| io_error(file, source))?;
+    }
+    Ok(SnapshotReport {
+        dir: run_dir.join(SNAPSHOT_DIR),
+        partitions: recorded.len(),
+        files: copies.len(),
+        bytes,
+    })
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> SnapshotError {
+    SnapshotError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn vintage(symbol: &str, cached_at: Option<NaiveDateTime>) -> PartitionVintage {
+        PartitionVintage {
+            symbol: symbol.into(),
+            interval: Default::default(),
+            cached_at,
+            content_hash: "abc".into(),
+        }
+    }
+
+    #[test]
+    fn changes_name_the_symbol_and_both_vintages() {
+        let then = NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_opt(9, 30, 0)
+            .unwrap();
+        let now = NaiveDate::from_ymd_opt(2024, 6, 3)
+            .unwrap()
+            .and_hms_opt(18, 0, 5)
+            .unwrap();
+        let changes = [
+            VintageChange {
+                recorded: vintage("SPY", Some(then)),
+                current: Some(vintage("SPY", Some(now))),
+            },
+            VintageChange {
+                recorded: vintage("QQQ", None),
+                current: None,
+            },
+        ];
+        assert_eq!(
+            format_changes(&changes),
+            "SPY: content changed (cached 2024-01-01 09:30:00 at the run, \
+             2024-06-03 18:00:05 now); QQQ: no longer cached"
+        );
+    }
+}
@@ -9,7 +9,13 @@
 //! - 756 bars total (3 years)
 //! - 252 bars per IS fold
 //! - 63 bars per OOS fold (one quarter)
+//!
+//! In the calendar boundary modes each OOS window is instead one whole
+//! calendar year or quarter, trained on the `train_periods` whole periods
+//! before it (a rolling window); periods the data only partly covers are
+//! skipped. Every fold carries a label naming its test window.
 
+use chrono::{Datelike, NaiveDate};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -24,8 +30,11 @@ use crate::runner::{run_backtest_from_data, RunError};
 
 /// Configuration for walk-forward validation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct WalkForwardConfig {
-    /// Number of folds (default 5).
+    /// Number of folds (default 5). The bar minimums below also apply to
+    /// bar-count mode only; calendar modes make a fold for every whole period
+    /// they can.
     pub n_folds: usize,
     /// Minimum total bars required (default 756 = 3 years).
     pub min_total_bars: usize,
@@ -33,6 +42,27 @@ pub struct WalkForwardConfig {
     pub min_is_bars: usize,
     /// Minimum out-of-sample bars per fold (default 63 = 1 quarter).
     pub min_oos_bars: usize,
+    /// How fold boundaries are placed (default: by bar count).
+    #[serde(skip_serializing_if = "FoldBoundaryMode::is_bar_count")]
+    pub boundary_mode: FoldBoundaryMode,
+    /// Whole periods in each calendar-mode training window (default 3).
+    /// Unused in bar-count mode.
+    #[serde(skip_serializing_if = "is_default_train_periods")]
+    pub train_periods: usize,
+    /// Bars dropped from the end of each IS window so indicator state and
+    /// open trades do not leak across into the test window (default 0).
+    #[serde(skip_serializing_if = "is_zero")]
+    pub purge_bars: usize,
+}
+
+const DEFAULT_TRAIN_PERIODS: usize = 3;
+
+fn is_default_train_periods(n: &usize) -> bool {
+    *n == DEFAULT_TRAIN_PERIODS
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
 }
 
 impl Default for WalkForwardConfig {
@@ -42,16 +72,41 @@ impl Default for WalkForwardConfig {
             min_total_bars: 756,
             min_is_bars: 252,
             min_oos_bars: 63,
+            boundary_mode: FoldBoundaryMode::BarCount,
+            train_periods: DEFAULT_TRAIN_PERIODS,
+            purge_bars: 0,
         }
     }
 }
 
+/// Where walk-forward fold boundaries fall.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FoldBoundaryMode {
+    /// `n_folds` equal OOS chunks after an expanding IS window.
+    #[default]
+    BarCount,
+    /// One fold per whole calendar year.
+    CalendarYear,
+    /// One fold per whole calendar quarter.
+    CalendarQuarter,
+}
+
+impl FoldBoundaryMode {
+    fn is_bar_count(&self) -> bool {
+        *self == FoldBoundaryMode::BarCount
+    }
+}
+
 // ─── Result types ────────────────────────────────────────────────────
 
 /// Specification of a single walk-forward fold (bar index ranges).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FoldSpec {
     pub fold_index: usize,
+    /// The test window, e.g. "test: 2021" or "test: 2021-Q3".
+    #[serde(default)]
+    pub label: String,
     /// In-sample start bar index (inclusive).
     pub is_start: usize,
     /// In-sample end bar index (exclusive).
@@ -66,10 +121,17 @@ pub struct FoldSpec {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FoldResult {
     pub fold_index: usize,
+    /// The fold's test window (see [`FoldSpec::label`]).
+    #[serde(default)]
+    pub label: String,
     pub is_sharpe: f64,
     pub oos_sharpe: f64,
     pub is_trades: usize,
     pub oos_trades: usize,
+    /// The test window closed no trades, so `oos_sharpe` is the neutral 0.0
+    /// rather than a measurement.
+    #[serde(default)]
+    pub oos_zero_trades: bool,
 }
 
 /// How the degradation ratio was computed (or why it wasn't).
@@ -85,6 +147,9 @@ pub enum DegradationFlag {
     FailedOos,
     /// Not enough bars for walk-forward.
     InsufficientData,
+    /// Walk-forward optimization picked materially different parameters on
+    /// each fold, so the IS optimum is not a stable property of the strategy.
+    ParameterInstability,
 }
 
 /// Complete result of walk-forward validation.
@@ -101,6 +166,20 @@ pub struct WalkForwardResult {
     pub t_test: Option<TTestResult>,
 }
 
+impl WalkForwardResult {
+    /// Folds whose test window closed no trades.
+    pub fn zero_trade_folds(&self) -> usize {
+        self.fold_results.iter().filter(|f| f.oos_zero_trades).count()
+    }
+
+    /// The fold with the lowest OOS Sharpe.
+    pub fn worst_fold(&self) -> Option<&FoldResult> {
+        self.fold_results
+            .iter()
+            .min_by(|a, b| a.oos_sharpe.total_cmp(&b.oos_sharpe))
+    }
+}
+
 /// Errors from walk-forward validation.
 #[derive(Debug, Error)]
 pub enum WalkForwardError {
@@ -108,6 +187,14 @@ pub enum WalkForwardError {
     InsufficientData { total_bars: usize, min_bars: usize },
     #[error("fold creation failed: cannot fit {n_folds} folds in {total_bars} bars")]
     FoldCreationFailed { n_folds: usize, total_bars: usize },
+    #[error(
+        "fold creation failed: no whole calendar period follows {train_periods} whole \
+         periods in {total_bars} bars"
+    )]
+    NoWholePeriods {
+        train_periods: usize,
+        total_bars: usize,
+    },
     #[error("backtest error on fold {fold}: {source}")]
     BacktestFailed {
         fold: usize,
@@ -142,9 +229,9 @@ pub fn create_folds(
     // Each OOS must be at least min_oos_bars
     let n = config.n_folds;
 
-    // Total bars needed: min_is_bars + n * oos_size <= total_bars
-    // oos_size = (total_bars - min_is_bars) / n
-    let available_for_oos = total_bars.saturating_sub(config.min_is_bars);
+    // Total bars needed: min_is_bars + purge + n * oos_size <= total_bars
+    // oos_size = (total_bars - min_is_bars - purge) / n
+    let available_for_oos = total_bars.saturating_sub(config.min_is_bars + config.purge_bars);
     let oos_size = available_for_oos / n;
 
     if oos_size < config.min_oos_bars {
@@ -154,13 +241,13 @@ pub fn create_folds(
         });
     }
 
-    let base_is_end = config.min_is_bars;
+    let base_oos_start = config.min_is_bars + config.purge_bars;
 
     let mut folds = Vec::with_capacity(n);
     for i in 0..n {
         let is_start = 0;
-        let is_end = base_is_end + i * oos_size;
-        let oos_start = is_end;
+        let oos_start = base_oos_start + i * oos_size;
+        let is_end = oos_start - config.purge_bars;
         let oos_end = oos_start + oos_size;
 
         // Don't create fold if OOS goes past data
@@ -170,6 +257,7 @@ pub fn create_folds(
 
         folds.push(FoldSpec {
             fold_index: i,
+            label: format!("test: bars {oos_start}..{oos_end}"),
             is_start,
             is_end,
             oos_start,
@@ -187,6 +275,139 @@ pub fn create_folds(
     Ok(folds)
 }
 
+/// Create folds over bars dated `dates` under the config's boundary mode.
+///
+/// Bar-count folds are labeled with their test window's first and last
+/// dates.
+pub fn create_folds_for_dates(
+    dates: &[NaiveDate],
+    config: &WalkForwardConfig,
+) -> Result<Vec<FoldSpec>, WalkForwardError> {
+    match config.boundary_mode {
+        FoldBoundaryMode::BarCount => {
+            let mut folds = create_folds(dates.len(), config)?;
+            for fold in &mut folds {
+                fold.label = format!(
+                    "test: {}..{}",
+                    dates[fold.oos_start],
+                    dates[fold.oos_end - 1]
+                );
+            }
+            Ok(folds)
+        }
+        FoldBoundaryMode::CalendarYear | FoldBoundaryMode::CalendarQuarter => {
+            create_calendar_folds(dates, config)
+        }
+    }
+}
+
+/// Days a period's first or last bar may sit inside its calendar bounds and
+/// still count as covering the whole period (weekends and holidays).
+const PERIOD_SLACK_DAYS: i64 = 7;
+
+/// A run of bars in one calendar year or quarter.
+struct Period {
+    /// Years, or quarters since year 0: consecutive periods differ by one.
+    ordinal: i64,
+    first: usize,
+    /// Exclusive.
+    end: usize,
+    complete: bool,
+    label: String,
+}
+
+fn calendar_periods(dates: &[NaiveDate], mode: FoldBoundaryMode) -> Vec<Period> {
+    let quarters = mode == FoldBoundaryMode::CalendarQuarter;
+    let ordinal = |d: &NaiveDate| {
+        let year = i64::from(d.year());
+        if quarters {
+            year * 4 + i64::from(d.month0() / 3)
+        } else {
+            year
+        }
+    };
+    let bounds = |ord: i64| {
+        let (year, first_month, months) = if quarters {
+            (ord.div_euclid(4), ord.rem_euclid(4) as u32 * 3 + 1, 3)
+        } else {
+            (ord, 1, 12)
+        };
+        let start = NaiveDate::from_ymd_opt(year as i32, first_month, 1).expect("valid date");
+        let next = start
+            .checked_add_months(chrono::Months::new(months))
+            .expect("valid date");
+        (start, next.pred_opt().expect("valid date"))
+    };
+
+    let mut periods: Vec<Period> = Vec::new();
+    for (i, date) in dates.iter().enumerate() {
+        let ord = ordinal(date);
+        match periods.last_mut() {
+            Some(p) if p.ordinal == ord => p.end = i + 1,
+            _ => periods.push(Period {
+                ordinal: ord,
+                first: i,
+                end: i + 1,
+                complete: false,
+                label: if quarters {
+                    format!("{}-Q{}", ord.div_euclid(4), ord.rem_euclid(4) + 1)
+                } else {
+                    ord.to_string()
+                },
+            }),
+        }
+    }
+    for p in &mut periods {
+        let (start, last) = bounds(p.ordinal);
+        p.complete = (dates[p.first] - start).num_days() <= PERIOD_SLACK_DAYS
+            && (last - dates[p.end - 1]).num_days() <= PERIOD_SLACK_DAYS;
+    }
+    periods
+}
+
+/// One fold per whole calendar period preceded by `train_periods` whole,
+/// consecutive periods. The IS window ends `purge_bars` before the test
+/// period starts.
+fn create_calendar_folds(
+    dates: &[NaiveDate],
+    config: &WalkForwardConfig,
+) -> Result<Vec<FoldSpec>, WalkForwardError> {
+    let periods = calendar_periods(dates, config.boundary_mode);
+    let train = config.train_periods.max(1);
+
+    let mut folds = Vec::new();
+    for i in train..periods.len() {
+        let window = &periods[i - train..=i];
+        let usable = window.iter().all(|p| p.complete)
+            && window.windows(2).all(|w| w[1].ordinal == w[0].ordinal + 1);
+        if !usable {
+            continue;
+        }
+        let test = &periods[i];
+        let is_start = window[0].first;
+        let is_end = test.first.saturating_sub(config.purge_bars);
+        if is_end <= is_start {
+            continue;
+        }
+        folds.push(FoldSpec {
+            fold_index: folds.len(),
+            label: format!("test: {}", test.label),
+            is_start,
+            is_end,
+            oos_start: test.first,
+            oos_end: test.end,
+        });
+    }
+
+    if folds.is_empty() {
+        return Err(WalkForwardError::NoWholePeriods {
+            train_periods: train,
+            total_bars: dates.len(),
+        });
+    }
+    Ok(folds)
+}
+
 /// Slice AlignedData to a bar index range [start, end).
 pub fn slice_aligned_data(full: &AlignedData, start: usize, end: usize) -> AlignedData {
     let end = end.min(full.dates.len());
@@ -222,8 +443,7 @@ pub fn run_walk_forward(
     execution_preset: ExecutionPreset,
     dataset_hash: &str,
 ) -> Result<WalkForwardResult, WalkForwardError> {
-    let total_bars = aligned.dates.len();
-    let folds = create_folds(total_bars, wf_config)?;
+    let folds = create_folds_for_dates(&aligned.dates, wf_config)?;
 
     let mut fold_results = Vec::with_capacity(folds.len());
 
@@ -266,12 +486,20 @@ pub fn run_walk_forward(
             source: e,
         })?;
 
+        // Every split ends at a slice boundary, so a held position shows up
+        // as an end-of-data trade; fold stats use the adjusted metrics
+        let (is_metrics, oos_metrics) = (
+            is_result.closed_trade_metrics(),
+            oos_result.closed_trade_metrics(),
+        );
         fold_results.push(FoldResult {
             fold_index: fold.fold_index,
-            is_sharpe: is_result.metrics.sharpe,
-            oos_sharpe: oos_result.metrics.sharpe,
-            is_trades: is_result.metrics.trade_count,
-            oos_trades: oos_result.metrics.trade_count,
+            label: fold.label.clone(),
+            is_sharpe: is_metrics.sharpe,
+            oos_sharpe: oos_metrics.sharpe,
+            is_trades: is_metrics.trade_count,
+            oos_trades: oos_metrics.trade_count,
+            oos_zero_trades: oos_result.zero_trades,
         });
     }
 
@@ -308,7 +536,7 @@ fn compute_walk_forward_stats(fold_results: Vec<FoldResult>) -> WalkForwardResul
 /// - IS < 0.1 and >= 0: difference = OOS - IS (LowIsSharpe)
 /// - IS < 0: ratio skipped (NegativeIsSharpe)
 /// - IS >= 0.1 but OOS < 0: clamped to 0.0 (FailedOos)
-fn compute_degradation_ratio(
+pub(crate) fn compute_degradation_ratio(
     mean_is_sharpe: f64,
     mean_oos_sharpe: f64,
 ) -> (Option<f64>, DegradationFlag) {
@@ -438,6 +666,128 @@ mod tests {
         }
     }
 
+    // ─── Calendar fold tests ─────────────────────────────────────
+
+    /// Weekdays from 2014-01-01 through 2023-12-31: ten whole years.
+    fn ten_years_of_weekdays() -> Vec<chrono::NaiveDate> {
+        use chrono::{Datelike, NaiveDate, Weekday};
+        let mut d = NaiveDate::from_ymd_opt(2014, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2023, 12, 31).unwrap();
+        let mut dates = Vec::new();
+        while d <= end {
+            if !matches!(d.weekday(), Weekday::Sat | Weekday::Sun) {
+                dates.push(d);
+            }
+            d = d.succ_opt().unwrap();
+        }
+        dates
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> chrono::NaiveDate {
+        chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn calendar_year_folds_test_each_whole_year() {
+        let dates = ten_years_of_weekdays();
+        let config = WalkForwardConfig {
+            boundary_mode: FoldBoundaryMode::CalendarYear,
+            train_periods: 3,
+            purge_bars: 5,
+            ..Default::default()
+        };
+        let folds = create_folds_for_dates(&dates, &config).unwrap();
+
+        // 2017 through 2023, each trained on the three years before it
+        assert_eq!(folds.len(), 7);
+        let first = &folds[0];
+        assert_eq!(first.label, "test: 2017");
+        assert_eq!(dates[first.is_start], date(2014, 1, 1));
+        assert_eq!(dates[first.oos_start], date(2017, 1, 2));
+        assert_eq!(dates[first.oos_end - 1], date(2017, 12, 29));
+        // The purge drops the last 5 bars of 2016 from training
+        assert_eq!(first.oos_start - first.is_end, 5);
+        assert_eq!(dates[first.is_end - 1], date(2016, 12, 23));
+
+        let last = folds.last().unwrap();
+        assert_eq!(last.label, "test: 2023");
+        assert_eq!(dates[last.is_start], date(2020, 1, 1));
+        assert_eq!(dates[last.oos_end - 1], date(2023, 12, 29));
+        assert_eq!(last.oos_end, dates.len());
+        for w in folds.windows(2) {
+            assert_eq!(w[1].oos_start, w[0].oos_end);
+        }
+    }
+
+    #[test]
+    fn calendar_quarter_folds_skip_partial_periods() {
+        // Start mid-way through 2014-Q1 and stop mid-way through 2023-Q4
+        let all = ten_years_of_weekdays();
+        let from = all.iter().position(|d| *d >= date(2014, 2, 15)).unwrap();
+        let to = all.iter().position(|d| *d >= date(2023, 11, 15)).unwrap();
+        let dates = &all[from..to];
+        let config = WalkForwardConfig {
+            boundary_mode: FoldBoundaryMode::CalendarQuarter,
+            train_periods: 4,
+            ..Default::default()
+        };
+        let folds = create_folds_for_dates(dates, &config).unwrap();
+
+        // Whole quarters run 2014-Q2 to 2023-Q3: 38 of them, the first
+        // four only train
+        assert_eq!(folds.len(), 34);
+        let first = &folds[0];
+        assert_eq!(first.label, "test: 2015-Q2");
+        assert_eq!(dates[first.is_start], date(2014, 4, 1));
+        assert_eq!(first.is_end, first.oos_start);
+        assert_eq!(dates[first.oos_start], date(2015, 4, 1));
+        assert_eq!(dates[first.oos_end - 1], date(2015, 6, 30));
+        let last = folds.last().unwrap();
+        assert_eq!(last.label, "test: 2023-Q3");
+        assert_eq!(dates[last.is_start], date(2022, 7, 1));
+        assert_eq!(dates[last.oos_end - 1], date(2023, 9, 29));
+    }
+
+    #[test]
+    fn calendar_folds_need_enough_whole_periods() {
+        let dates: Vec<_> = ten_years_of_weekdays()
+            .into_iter()
+            .filter(|d| *d < date(2016, 1, 1))
+            .collect();
+        let config = WalkForwardConfig {
+            boundary_mode: FoldBoundaryMode::CalendarYear,
+            train_periods: 2,
+            ..Default::default()
+        };
+        assert!(matches!(
+            create_folds_for_dates(&dates, &config),
+            Err(WalkForwardError::NoWholePeriods { .. })
+        ));
+    }
+
+    #[test]
+    fn bar_count_folds_are_labeled_with_dates() {
+        let dates = ten_years_of_weekdays();
+        let folds = create_folds_for_dates(&dates, &WalkForwardConfig::default()).unwrap();
+        let expected: Vec<FoldSpec> = create_folds(dates.len(), &WalkForwardConfig::default())
+            .unwrap()
+            .into_iter()
+            .map(|f| FoldSpec {
+                label: format!("test: {}..{}", dates[f.oos_start], dates[f.oos_end - 1]),
+                ..f
+            })
+            .collect();
+        assert_eq!(folds, expected);
+        assert!(folds[0].label.starts_with("test: 2014-12-"));
+    }
+
+    #[test]
+    fn default_config_serializes_without_the_new_fields() {
+        // Robustness cache keys hash the serialized config
+        let json = serde_json::to_value(WalkForwardConfig::default()).unwrap();
+        assert_eq!(json.as_object().unwrap().len(), 4);
+    }
+
     // ─── Slice tests ─────────────────────────────────────────────
 
     #[test]
@@ -0,0 +1,450 @@
+//! Walk-forward optimization — re-fit parameters on every fold, evaluate OOS.
+//!
+//! `run_walk_forward` evaluates one fixed config on each fold. The optimizer
+//! instead searches a `ParamGrid` on every in-sample window, keeps the best
+//! config by `FitnessMetric`, and runs only that config on the following OOS
+//! window. The OOS segments are stitched into a single equity curve: the curve
+//! a trader re-optimizing on that schedule would actually have lived through.
+//!
+//! Outputs:
+//! - Per-fold chosen parameters, and per-axis dispersion of those choices
+//! - Walk-forward efficiency: annualized OOS return / annualized IS return
+//! - Sharpe degradation flag, upgraded to `ParameterInstability` when the
+//!   chosen parameters wander across most of the grid
+//!
+//! The grid runs many backtests per fold, so the symbol is extracted once,
+//! each IS slice is built once, and indicator series are shared across grid
+//! points through an `IndicatorCache`.
+
+use serde::{Deserialize, Serialize};
+
+use trendlab_core::components::execution::ExecutionPreset;
+use trendlab_core::components::sampler::{ComponentSlot, ParamGrid};
+use trendlab_core::data::align::AlignedData;
+use trendlab_core::engine::IndicatorCache;
+use trendlab_core::fingerprint::{StrategyConfig, TradingMode};
+
+use crate::data_loader::LoadError;
+use crate::fdr::TTestResult;
+use crate::fitness::FitnessMetric;
+use crate::runner::{
+    extract_single_symbol, run_backtest_cached, run_backtest_from_data, BacktestResult, RunError,
+};
+use crate::walk_forward::{
+    compute_degradation_ratio, create_folds_for_dates, slice_aligned_data, DegradationFlag,
+    WalkForwardConfig, WalkForwardError,
+};
+
+// ─── Configuration ───────────────────────────────────────────────────
+
+/// Configuration for walk-forward optimization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WfoConfig {
+    /// Fold layout (same expanding-window scheme as plain walk-forward).
+    pub walk_forward: WalkForwardConfig,
+    /// Metric used to pick the best IS config on each fold.
+    pub fitness_metric: FitnessMetric,
+    /// Max normalized dispersion of chosen values on any axis before the
+    /// result is flagged `ParameterInstability` (default 0.25).
+    pub instability_threshold: f64,
+}
+
+impl Default for WfoConfig {
+    fn default() -> Self {
+        Self {
+            walk_forward: WalkForwardConfig::default(),
+            fitness_metric: FitnessMetric::Sharpe,
+            instability_threshold: 0.25,
+        }
+    }
+}
+
+// ─── Result types ────────────────────────────────────────────────────
+
+/// Outcome of one optimization fold.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WfoFold {
+    pub fold_index: usize,
+    /// The fold's test window (see [`crate::walk_forward::FoldSpec::label`]).
+    #[serde(default)]
+    pub label: String,
+    /// Config that won the IS grid search and was traded OOS.
+    pub chosen_config: StrategyConfig,
+    /// Fitness of the winner on the IS window.
+    pub is_fitness: f64,
+    /// Fitness of the winner on the OOS window.
+    pub oos_fitness: f64,
+    pub is_sharpe: f64,
+    pub oos_sharpe: f64,
+    pub is_cagr: f64,
+    pub oos_cagr: f64,
+    pub is_trades: usize,
+    pub oos_trades: usize,
+    /// The winner closed no trades on the OOS window; its OOS metrics are
+    /// the neutral zeros.
+    #[serde(default)]
+    pub oos_zero_trades: bool,
+}
+
+/// How consistently one grid axis was chosen across folds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParamStability {
+    pub slot: ComponentSlot,
+    pub param: String,
+    /// Chosen value per fold, in fold order.
+    pub chosen: Vec<f64>,
+    /// Population std of `chosen` divided by the axis span (0.0 = identical
+    /// every fold, 0.5 = alternating between the grid extremes).
+    pub dispersion: f64,
+}
+
+/// Complete result of walk-forward optimization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WfoResult {
+    pub folds: Vec<WfoFold>,
+    /// OOS equity segments chained end-to-end, starting at initial capital.
+    pub oos_equity_curve: Vec<f64>,
+    pub param_stability: Vec<ParamStability>,
+    /// Walk-forward efficiency: mean OOS CAGR / mean IS CAGR.
+    /// None when IS CAGR is not positive.
+    pub efficiency_ratio: Option<f64>,
+    pub mean_is_sharpe: f64,
+    pub mean_oos_sharpe: f64,
+    /// Mean OOS Sharpe / mean IS Sharpe (see `DegradationFlag` for edge cases).
+    pub degradation_ratio: Option<f64>,
+    pub degradation_flag: DegradationFlag,
+    /// t-test on fold-level OOS Sharpe values (H0: mean = 0, H1: mean > 0).
+    pub t_test: Option<TTestResult>,
+    /// Distinct configs evaluated per fold.
+    pub grid_size: usize,
+}
+
+impl WfoResult {
+    /// Largest per-axis dispersion (0.0 when no axis varies).
+    pub fn max_dispersion(&self) -> f64 {
+        self.param_stability
+            .iter()
+            .map(|p| p.dispersion)
+            .fold(0.0, f64::max)
+    }
+}
+
+// ─── Optimizer ───────────────────────────────────────────────────────
+
+/// Walk-forward optimizer over a base config and a parameter grid.
+#[derive(Debug, Clone)]
+pub struct WalkForwardOptimizer {
+    base: StrategyConfig,
+    grid: ParamGrid,
+    config: WfoConfig,
+}
+
+impl WalkForwardOptimizer {
+    pub fn new(base: StrategyConfig, grid: ParamGrid, config: WfoConfig) -> Self {
+        Self { base, grid, config }
+    }
+
+    /// Run the optimization on pre-loaded data.
+    ///
+    /// Every grid point is backtested on every IS window, so cost scales as
+    /// `folds × grid points`. Keep grids small.
+    #[allow(clippy::too_many_arguments)]
+    pub fn run(
+        &self,
+        aligned: &AlignedData,
+        symbol: &str,
+        trading_mode: TradingMode,
+        initial_capital: f64,
+        position_size_pct: f64,
+        execution_preset: ExecutionPreset,
+        dataset_hash: &str,
+    ) -> Result<WfoResult, WalkForwardError> {
+        let folds = create_folds_for_dates(&aligned.dates, &self.config.walk_forward)?;
+        if !aligned.bars.contains_key(symbol) {
+            return Err(WalkForwardError::BacktestFailed {
+                fold: 0,
+                source: RunError::DataLoad {
+                    symbol: symbol.to_string(),
+                    source: LoadError::NotLoaded {
+                        symbol: symbol.to_string(),
+                    },
+                },
+            });
+        }
+
+        // Slice one symbol once instead of per grid point
+        let single = extract_single_symbol(aligned, symbol);
+        let candidates = self.grid.expand(&self.base);
+        let metric = self.config.fitness_metric;
+
+        let mut fold_results = Vec::with_capacity(folds.len());
+        let mut oos_segments = Vec::with_capacity(folds.len());
+
+        for fold in &folds {
+            let backtest_failed = |source| WalkForwardError::BacktestFailed {
+                fold: fold.fold_index,
+                source,
+            };
+            let is_data = slice_aligned_data(&single, fold.is_start, fold.is_end);
+            let oos_data = slice_aligned_data(&single, fold.oos_start, fold.oos_end);
+            let mut cache = IndicatorCache::new();
+
+            // In-sample grid search; ties keep the earlier grid point
+            let mut best: Option<(f64, &StrategyConfig, BacktestResult)> = None;
+            for candidate in &candidates {
+                let result = run_backtest_cached(
+                    candidate,
+                    &is_data,
+                    symbol,
+                    trading_mode,
+                    initial_capital,
+                    position_size_pct,
+                    execution_preset,
+                    dataset_hash,
+                    &mut cache,
+                )
+                .map_err(backtest_failed)?;
+                let fitness = metric.extract(&result.metrics);
+                let replace = match &best {
+                    None => true,
+                    Some((best_fitness, _, _)) => {
+                        best_fitness.is_nan()
+                            || (!fitness.is_nan() && metric.is_better(fitness, *best_fitness))
+                    }
+                };
+                if replace {
+                    best = Some((fitness, candidate, result));
+                }
+            }
+            let Some((is_fitness, chosen, is_result)) = best else {
+                continue;
+            };
+
+            let oos_result = run_backtest_from_data(
+                chosen,
+                &oos_data,
+                symbol,
+                trading_mode,
+                initial_capital,
+                position_size_pct,
+                execution_preset,
+                dataset_hash,
+                false,
+            )
+            .map_err(backtest_failed)?;
+
+            fold_results.push(WfoFold {
+                fold_index: fold.fold_index,
+                label: fold.label.clone(),
+                chosen_config: chosen.clone(),
+                is_fitness,
+                oos_fitness: metric.extract(&oos_result.metrics),
+                is_sharpe: is_result.metrics.sharpe,
+                oos_sharpe: oos_result.metrics.sharpe,
+                is_cagr: is_result.metrics.cagr,
+                oos_cagr: oos_result.metrics.cagr,
+                is_trades: is_result.metrics.trade_count,
+                oos_trades: oos_result.metrics.trade_count,
+                oos_zero_trades: oos_result.zero_trades,
+            });
+            oos_segments.push(oos_result.equity_curve);
+        }
+
+        Ok(self.summarize(
+            fold_results,
+            &oos_segments,
+            initial_capital,
+            candidates.len(),
+        ))
+    }
+
+    fn summarize(
+        &self,
+        folds: Vec<WfoFold>,
+        oos_segments: &[Vec<f64>],
+        initial_capital: f64,
+        grid_size: usize,
+    ) -> WfoResult {
+        let n = folds.len().max(1) as f64;
+        let mean_is_sharpe = folds.iter().map(|f| f.is_sharpe).sum::<f64>() / n;
+        let mean_oos_sharpe = folds.iter().map(|f| f.oos_sharpe).sum::<f64>() / n;
+        let mean_is_cagr = folds.iter().map(|f| f.is_cagr).sum::<f64>() / n;
+        let mean_oos_cagr = folds.iter().map(|f| f.oos_cagr).sum::<f64>() / n;
+
+        let efficiency_ratio = if mean_is_cagr > 0.0 {
+            Some(mean_oos_cagr / mean_is_cagr)
+        } else {
+            None
+        };
+
+        let param_stability = param_stability(&self.grid, &folds);
+        let max_dispersion = param_stability
+            .iter()
+            .map(|p| p.dispersion)
+            .fold(0.0, f64::max);
+
+        let (degradation_ratio, mut degradation_flag) =
+            compute_degradation_ratio(mean_is_sharpe, mean_oos_sharpe);
+        // Instability only overrides flags that would otherwise look healthy
+        if max_dispersion > self.config.instability_threshold
+            && matches!(
+                degradation_flag,
+                DegradationFlag::Normal | DegradationFlag::LowIsSharpe
+            )
+        {
+            degradation_flag = DegradationFlag::ParameterInstability;
+        }
+
+        let oos_sharpes: Vec<f64> = folds.iter().map(|f| f.oos_sharpe).collect();
+        let t_test = crate::fdr::one_sided_t_test(&oos_sharpes);
+
+        WfoResult {
+            oos_equity_curve: stitch_equity(oos_segments, initial_capital),
+            folds,
+            param_stability,
+            efficiency_ratio,
+            mean_is_sharpe,
+            mean_oos_sharpe,
+            degradation_ratio,
+            degradation_flag,
+            t_test,
+            grid_size,
+        }
+    }
+}
+
+/// Chain OOS equity segments so each one starts where the previous ended.
+///
+/// Every segment is a fresh backtest starting at `initial_capital`, so it is
+/// rescaled by the cumulative growth of the segments before it.
+fn stitch_equity(segments: &[Vec<f64>], initial_capital: f64) -> Vec<f64> {
+    let mut stitched = Vec::with_capacity(segments.iter().map(Vec::len).sum());
+    let mut scale = 1.0;
+    for segment in segments {
+        stitched.extend(segment.iter().map(|v| v * scale));
+        if let Some(&last) = stitched.last() {
+            scale = last / initial_capital;
+        }
+    }
+    stitched
+}
+
+/// Per-axis dispersion of the chosen values across folds.
+fn param_stability(grid: &ParamGrid, folds: &[WfoFold]) -> Vec<ParamStability> {
+    grid.axes
+        .iter()
+        .map(|axis| {
+            let chosen: Vec<f64> = folds
+                .iter()
+                .filter_map(|f| {
+                    let component = match axis.slot {
+                        ComponentSlot::Signal => &f.chosen_config.signal,
+                        ComponentSlot::PositionManager => &f.chosen_config.position_manager,
+                        ComponentSlot::ExecutionModel => &f.chosen_config.execution_model,
+                        ComponentSlot::SignalFilter => &f.chosen_config.signal_filter,
+                    };
+                    component.params.get(&axis.param).copied()
+                })
+                .collect();
+
+            let lo = axis.values.iter().copied().fold(f64::INFINITY, f64::min);
+            let hi = axis
+                .values
+                .iter()
+                .copied()
+                .fold(f64::NEG_INFINITY, f64::max);
+            let span = hi - lo;
+            let dispersion = if chosen.len() < 2 || span.is_nan() || span <= 0.0 {
+                0.0
+            } else {
+                let n = chosen.len() as f64;
+                let mean = chosen.iter().sum::<f64>() / n;
+                let var = chosen.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+                var.sqrt() / span
+            };
+
+            ParamStability {
+                slot: axis.slot,
+                param: axis.param.clone(),
+                chosen,
+                dispersion,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use trendlab_core::components::composition::StrategyPreset;
+
+    fn fold_with_lookback(fold_index: usize, lookback: f64) -> WfoFold {
+        let mut chosen_config = StrategyPreset::DonchianTrend.to_config();
+        chosen_config
+            .signal
+            .params
+            .insert("entry_lookback".into(), lookback);
+        WfoFold {
+            fold_index,
+            label: String::new(),
+            chosen_config,
+            is_fitness: 1.0,
+            oos_fitness: 0.5,
+            is_sharpe: 1.0,
+            oos_sharpe: 0.5,
+            is_cagr: 0.2,
+            oos_cagr: 0.1,
+            is_trades: 10,
+            oos_trades: 3,
+            oos_zero_trades: false,
+        }
+    }
+
+    fn lookback_grid() -> ParamGrid {
+        ParamGrid::new().with_axis(
+            ComponentSlot::Signal,
+            "entry_lookback",
+            vec![20.0, 50.0, 100.0],
+        )
+    }
+
+    #[test]
+    fn stitch_chains_segment_growth() {
+        let segments = vec![vec![100.0, 110.0], vec![100.0, 120.0]];
+        let stitched = stitch_equity(&segments, 100.0);
+        assert_eq!(stitched.len(), 4);
+        assert!((stitched[2] - 110.0).abs() < 1e-10);
+        assert!((stitched[3] - 132.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn stable_choices_have_zero_dispersion() {
+        let folds: Vec<WfoFold> = (0..4).map(|i| fold_with_lookback(i, 50.0)).collect();
+        let stability = param_stability(&lookback_grid(), &folds);
+        assert_eq!(stability.len(), 1);
+        assert_eq!(stability[0].chosen, vec![50.0; 4]);
+        assert!(stability[0].dispersion.abs() < 1e-12);
+    }
+
+    #[test]
+    fn alternating_extremes_flag_instability() {
+        let folds: Vec<WfoFold> = (0..4)
+            .map(|i| fold_with_lookback(i, if i % 2 == 0 { 20.0 } else { 100.0 }))
+            .collect();
+        let optimizer = WalkForwardOptimizer::new(
+            StrategyPreset::DonchianTrend.to_config(),
+            lookback_grid(),
+            WfoConfig::default(),
+        );
+        let segments = vec![vec![100.0, 101.0]; 4];
+        let result = optimizer.summarize(folds, &segments, 100.0, 3);
+
+        assert!((result.max_dispersion() - 0.5).abs() < 1e-12);
+        assert_eq!(
+            result.degradation_flag,
+            DegradationFlag::ParameterInstability
+        );
+        assert!((result.efficiency_ratio.unwrap() - 0.5).abs() < 1e-12);
+        assert_eq!(result.oos_equity_curve.len(), 8);
+    }
+}
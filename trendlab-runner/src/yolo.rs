@@ -7,30 +7,99 @@
 //! Two controls:
 //! - `jitter_pct` (0.0–1.0): parameter variation within known structures.
 //! - `structural_explore` (0.0–1.0): probability of trying novel component combos.
+//!
+//! With `exploit_pct` > 0, some iterations instead mutate one of the top
+//! cross-symbol champions (jittered by `jitter_pct`); those configs record
+//! their parent in the history and the cross-symbol leaderboard.
+//!
+//! A symbol that fails `quarantine_after` times in a row is skipped for the
+//! rest of the session instead of failing every iteration.
+//!
+//! `execution_profiles` gives symbols their own friction (by name, or by the
+//! liquidity bucket of their average dollar volume), so illiquid names are
+//! not ranked at ETF slippage.
+//!
+//! With a `risk_profile`, every sampled or mutated config gets the profile's
+//! guards and required filter before it runs (see
+//! [`RiskProfile::to_overrides`]), so the leaderboards only hold configs that
+//! trade under the profile's constraints.
+//!
+//! Sampled configs must pass the incompatible rules of the compatibility
+//! table, with `min_post_warmup_bars` of the loaded bars left after warmup.
+//! How often each rule forced a resample is reported per rule.
+//!
+//! With `adaptive` set, an [`AdaptiveController`] moves the sliders and the
+//! exploit probability between baseline, explore and exploit modes as the
+//! session plateaus or keeps finding leaderboard entries (see
+//! [`crate::adaptive`]).
+//!
+//! With a `holdout` window configured, the loaded bars are split before the
+//! first iteration and discovery only ever runs on the bars before the
+//! boundary; the final leaderboards are evaluated once on the rest (see
+//! [`crate::holdout`]).
+//!
+//! Every backtest is a trial in its symbol's FDR family. Leaderboard entries
+//! carry their q-value in the family, refreshed each iteration, and with a
+//! history the trials are persisted so later sessions can merge them (see
+//! [`crate::fdr`]).
+//!
+//! With a `leaderboard_dir`, each symbol's leaderboard is also kept in a file
+//! that outlives the session: loaded and merged with a replay of the history
+//! at start, and rewritten on every insertion (see
+//! [`crate::leaderboard_store`]).
+//!
+//! Each symbol keeps an indicator cache for the session, and every run's bars,
+//! engine time and cache lookups are added to a [`ComputeBudget`]. With
+//! `compute_limits` set, the session ends once it has simulated enough bars
+//! or run long enough; why it ended is recorded as a [`StopReason`].
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, RwLock};
 use std::time::Instant;
 
 use chrono::NaiveDate;
+use rand::Rng;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use trendlab_core::components::sampler::{sample_composition, ComponentPool};
+use trendlab_core::components::composition::CompatibilityContext;
+use trendlab_core::components::execution::ExecutionProfiles;
+use trendlab_core::components::sampler::{
+    mutate_composition, sample_compatible_composition, ComponentPool,
+};
+use trendlab_core::data::cache::ParquetCache;
+use trendlab_core::data::provider::DataProvider;
 use trendlab_core::domain::{DatasetHash, RunId};
+use trendlab_core::engine::{EngineTimings, IndicatorCache};
 use trendlab_core::fingerprint::{RunFingerprint, TradingMode};
 use trendlab_core::rng::RngHierarchy;
 
-use crate::cross_leaderboard::CrossSymbolLeaderboard;
-use crate::data_loader::LoadedData;
-use crate::fdr::FdrFamily;
+use crate::adaptive::{AdaptiveConfig, AdaptiveController, ExplorationMode, ModeTransition};
+use crate::compute_budget::{ComputeBudget, ComputeLimits, ComputeUsage, StopReason};
+use crate::convergence::{
+    ConvergenceConfig, ConvergencePoint, ConvergenceState, ConvergenceTracker, PlateauAction,
+};
+use crate::cross_leaderboard::{CrossAggregation, CrossSymbolLeaderboard};
+use crate::data_loader::{load_bars, LoadError, LoadOptions, LoadedData};
+use crate::execution_mc::{run_friction_ramp, FrictionRampConfig};
+use crate::fdr::{returns_p_value, FdrConfig, FdrFamily, FdrRecord};
 use crate::fitness::FitnessMetric;
-use crate::history::{HistoryEntry, WriteFilter, YoloHistory};
-use crate::leaderboard::{LeaderboardEntry, SymbolLeaderboard};
+use crate::history::{
+    EntryDetail, EvalWindow, FitnessQuartiles, HistoryCheckpoint, HistoryEntry, RunDetail,
+    RunErrorEntry, WriteFilter, WritePreset, YoloHistory,
+};
+use crate::holdout::{HoldoutConfig, HoldoutError, HoldoutReport};
+use crate::leaderboard::{InsertResult, LeaderboardEntry, SymbolLeaderboard};
+use crate::leaderboard_store::{resume, LeaderboardDivergence, StoredEntry, StoredLeaderboard};
+use crate::overlap::{downsample_returns, OverlapConfig, RETURN_SAMPLE_POINTS};
 use crate::promotion::{promote, PromotionConfig, PromotionLevel};
-use crate::runner::{decode_execution_preset, run_backtest_from_data, RunError};
+use crate::risk_profile::{RankingMetric, RiskProfile};
+use crate::runner::{
+    decode_execution_preset, run_backtest_profiled_cached, BacktestResult, RunError, RunErrorKind,
+};
 
 // ─── Config types ────────────────────────────────────────────────────
 
@@ -42,6 +111,17 @@ pub enum SweepDepth {
     Deep,
 }
 
+impl SweepDepth {
+    /// Points per parameter axis when expanding a `ParamGrid`.
+    pub fn grid_steps(&self) -> usize {
+        match self {
+            Self::Quick => 3,
+            Self::Normal => 5,
+            Self::Deep => 9,
+        }
+    }
+}
+
 /// Combo mode: controls multi-strategy composition (stub for now).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ComboMode {
@@ -57,6 +137,19 @@ pub struct YoloConfig {
     // ── Dual sliders ──
     pub jitter_pct: f64,
     pub structural_explore: f64,
+    /// Probability that an iteration mutates one of the top
+    /// [`EXPLOIT_CHAMPIONS`] cross-symbol champions instead of sampling from
+    /// scratch (default 0.0).
+    #[serde(default)]
+    pub exploit_pct: f64,
+    /// Let the session adjust the sliders and `exploit_pct` itself. The
+    /// values above are the baseline. If None, they stay as configured.
+    #[serde(default)]
+    pub adaptive: Option<AdaptiveConfig>,
+    /// Also sample 2-of-2 signal ensembles (see
+    /// `ComponentPool::with_signal_ensembles`), at a low weight.
+    #[serde(default)]
+    pub signal_ensembles: bool,
 
     // ── Backtest parameters ──
     pub start_date: NaiveDate,
@@ -64,10 +157,29 @@ pub struct YoloConfig {
     pub initial_capital: f64,
     pub position_size_pct: f64,
     pub trading_mode: TradingMode,
+    /// Per-symbol friction overrides, by symbol or by liquidity bucket.
+    /// Symbols without a profile run at the sampled execution preset.
+    #[serde(default)]
+    pub execution_profiles: ExecutionProfiles,
+    /// Risk profile applied to every config before it runs. If None,
+    /// configs run as sampled.
+    #[serde(default)]
+    pub risk_profile: Option<RiskProfile>,
+    /// Trailing window withheld from discovery and evaluated once at the
+    /// end. If None, every loaded bar is searched.
+    #[serde(default)]
+    pub holdout: Option<HoldoutConfig>,
 
     // ── Robustness (Phase 11) ──
     /// Promotion ladder configuration. If None, promotion is disabled.
     pub promotion_config: Option<PromotionConfig>,
+    /// Slippage ramp run on every per-symbol leaderboard insertion. If None,
+    /// friction sensitivity is not computed.
+    #[serde(default)]
+    pub friction_ramp: Option<FrictionRampConfig>,
+    /// Per-symbol FDR families over every trial of the session.
+    #[serde(default)]
+    pub fdr: FdrConfig,
 
     // ── Sweep settings ──
     pub sweep_depth: SweepDepth,
@@ -81,6 +193,17 @@ pub struct YoloConfig {
     // ── Limits ──
     pub max_iterations: Option<usize>,
     pub leaderboard_max_size: usize,
+    /// Consecutive failures after which a symbol is skipped for the rest of
+    /// the session. Only failures attributable to the symbol count; 0 disables.
+    #[serde(default = "default_quarantine_after")]
+    pub quarantine_after: usize,
+    /// Bars a sampled config must leave after its warmup; lookback ranges
+    /// are capped to fit the loaded data.
+    #[serde(default = "default_min_post_warmup_bars")]
+    pub min_post_warmup_bars: usize,
+    /// Bar and wall-time budgets that end the session early.
+    #[serde(default)]
+    pub compute_limits: ComputeLimits,
 
     // ── Fitness & seeding ──
     pub fitness_metric: FitnessMetric,
@@ -91,8 +214,34 @@ pub struct YoloConfig {
     pub history_path: Option<PathBuf>,
     /// Write filter for history persistence.
     pub write_filter: WriteFilter,
+    /// Named write policy. Replaces `write_filter` when set.
+    #[serde(default)]
+    pub history_preset: Option<WritePreset>,
+    /// Embed each entry's full equity curve and trades in the history.
+    #[serde(default)]
+    pub history_detail: bool,
+    /// Entries larger than this many kilobytes move their embedded detail
+    /// to a side file. None lets entries grow without bound.
+    #[serde(default = "default_history_entry_budget_kb")]
+    pub history_entry_budget_kb: Option<usize>,
+    /// Sync each history append to disk (slower; survives power loss).
+    #[serde(default)]
+    pub history_fsync: bool,
+    /// Directory of per-symbol leaderboard files shared across sessions.
+    /// None keeps the leaderboards in memory only.
+    #[serde(default)]
+    pub leaderboard_dir: Option<PathBuf>,
     /// Catastrophic loss threshold for cross-symbol flagging (e.g., -0.5 = -50%).
     pub catastrophic_threshold: f64,
+    /// Whether cross-symbol ranking uses the plain mean Sharpe or the one
+    /// discounted for correlated symbols.
+    #[serde(default)]
+    pub cross_aggregation: CrossAggregation,
+
+    // ── Convergence ──
+    /// Convergence sampling and plateau handling.
+    #[serde(default)]
+    pub convergence: ConvergenceConfig,
 }
 
 impl Default for YoloConfig {
@@ -100,12 +249,20 @@ impl Default for YoloConfig {
         Self {
             jitter_pct: 0.5,
             structural_explore: 0.3,
+            exploit_pct: 0.0,
+            signal_ensembles: false,
+            adaptive: None,
             start_date: NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
             end_date: NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
             initial_capital: 100_000.0,
             position_size_pct: 1.0,
             trading_mode: TradingMode::LongOnly,
+            execution_profiles: ExecutionProfiles::default(),
+            risk_profile: None,
+            holdout: None,
             promotion_config: None,
+            friction_ramp: None,
+            fdr: FdrConfig::default(),
             sweep_depth: SweepDepth::Normal,
             warmup_iterations: 10,
             combo_mode: ComboMode::None,
@@ -113,15 +270,37 @@ impl Default for YoloConfig {
             outer_thread_cap: 1,
             max_iterations: None,
             leaderboard_max_size: 500,
+            quarantine_after: default_quarantine_after(),
+            min_post_warmup_bars: default_min_post_warmup_bars(),
+            compute_limits: ComputeLimits::default(),
             fitness_metric: FitnessMetric::Sharpe,
             master_seed: 42,
             history_path: None,
             write_filter: WriteFilter::default(),
+            history_preset: None,
+            history_detail: false,
+            history_entry_budget_kb: default_history_entry_budget_kb(),
+            history_fsync: false,
+            leaderboard_dir: None,
             catastrophic_threshold: -0.5,
+            cross_aggregation: CrossAggregation::default(),
+            convergence: ConvergenceConfig::default(),
         }
     }
 }
 
+fn default_quarantine_after() -> usize {
+    5
+}
+
+fn default_min_post_warmup_bars() -> usize {
+    50
+}
+
+fn default_history_entry_budget_kb() -> Option<usize> {
+    Some(64)
+}
+
 impl YoloConfig {
     /// Enforce the threading mutual exclusion rule:
     /// if outer_thread_cap > 1, force polars_thread_cap = 1.
@@ -136,8 +315,62 @@ impl YoloConfig {
     }
 }
 
+/// The two slider values, adjustable while a session is running.
+///
+/// Passed to [`run_yolo_live`] behind a `RwLock`; the loop re-reads them at
+/// the start of every iteration, so a change takes effect on the next sample.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct YoloTunables {
+    pub jitter_pct: f64,
+    pub structural_explore: f64,
+}
+
+impl YoloTunables {
+    pub fn from_config(config: &YoloConfig) -> Self {
+        Self {
+            jitter_pct: config.jitter_pct,
+            structural_explore: config.structural_explore,
+        }
+    }
+
+    /// Shift `structural_explore` by `delta`, clamped to 0.0–1.0.
+    pub fn nudge_explore(&mut self, delta: f64) {
+        self.structural_explore = (self.structural_explore + delta).clamp(0.0, 1.0);
+    }
+
+    /// Shift `jitter_pct` by `delta`, clamped to 0.0–1.0.
+    pub fn nudge_jitter(&mut self, delta: f64) {
+        self.jitter_pct = (self.jitter_pct + delta).clamp(0.0, 1.0);
+    }
+}
+
 // ─── Progress & result types ─────────────────────────────────────────
 
+/// Cross-symbol champions (by average Sharpe) that exploiting iterations mutate.
+pub const EXPLOIT_CHAMPIONS: usize = 5;
+
+/// Number of recent leaderboard insertions carried in each progress update.
+pub const RECENT_INSERTIONS_CAP: usize = 10;
+
+/// Trials a symbol's FDR batch collects before it is written to the history.
+const FDR_BATCH_TRIALS: usize = 100;
+
+/// Indicator series a symbol's cache may hold before it is emptied. Jittered
+/// multipliers make new series names without end, so the cache is bounded.
+const INDICATOR_CACHE_SERIES: usize = 500;
+
+/// A strategy that entered (or improved its slot on) a per-symbol leaderboard.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeaderboardInsertion {
+    pub iteration: usize,
+    pub symbol: String,
+    pub signal_type: String,
+    pub pm_type: String,
+    pub fitness_score: f64,
+    /// True if an existing entry with the same full_hash was replaced.
+    pub replaced: bool,
+}
+
 /// Progress update sent during YOLO execution.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct YoloProgress {
@@ -146,6 +379,11 @@ pub struct YoloProgress {
     pub symbols_complete: usize,
     pub symbols_total: usize,
     pub success_count: usize,
+    /// Successful backtests that closed no trades (included in
+    /// `success_count`). A high share means the filters or warmups leave
+    /// nothing to explore.
+    #[serde(default)]
+    pub zero_trade_count: usize,
     pub error_count: usize,
     pub throughput_per_min: f64,
     pub leaderboard_entries: usize,
@@ -155,14 +393,69 @@ pub struct YoloProgress {
     pub promoted_l2_count: usize,
     pub promoted_l3_count: usize,
     pub fdr_family_size: usize,
+    /// Data quality anomalies found when the session's bars were loaded.
+    #[serde(default)]
+    pub data_quality_warnings: usize,
+    /// Slider values in effect for the latest iteration.
+    #[serde(default)]
+    pub jitter_pct: f64,
+    #[serde(default)]
+    pub structural_explore: f64,
+    /// Best fitness score per symbol (symbols with an empty leaderboard are omitted).
+    #[serde(default)]
+    pub best_fitness: BTreeMap<String, f64>,
+    /// Most recent leaderboard insertions, oldest first.
+    #[serde(default)]
+    pub recent_insertions: Vec<LeaderboardInsertion>,
+    /// Best-fitness convergence status (improving or plateaued).
+    #[serde(default)]
+    pub convergence: ConvergenceState,
+    /// Symbols skipped for the rest of the session after repeated failures.
+    #[serde(default)]
+    pub quarantined: Vec<String>,
+    /// Failed backtests so far, by error category.
+    #[serde(default)]
+    pub errors_by_kind: BTreeMap<RunErrorKind, usize>,
+    /// Sampled configs rejected so far, by compatibility rule id.
+    #[serde(default)]
+    pub rejections_by_rule: BTreeMap<String, usize>,
+    /// Adaptive exploration mode, when the session has a controller.
+    #[serde(default)]
+    pub exploration_mode: Option<ExplorationMode>,
+    /// Compute spent so far, over all symbols.
+    #[serde(default)]
+    pub compute: ComputeUsage,
+}
+
+/// A symbol dropped from a YOLO session after `quarantine_after` consecutive failures.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuarantinedSymbol {
+    pub symbol: String,
+    /// Iteration of the failure that triggered the quarantine.
+    pub iteration: usize,
+    pub consecutive_failures: usize,
+    pub kind: RunErrorKind,
+    pub retryable: bool,
+    pub last_error: String,
 }
 
 /// Final result of a YOLO run.
+///
+/// Persisted as a session with [`YoloResult::save`] and reloaded with
+/// [`YoloResult::load`] (see [`crate::session`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct YoloResult {
+    /// Session id stamped on every leaderboard entry of the run.
+    pub session_id: String,
+    /// Configuration the session ran with, after thread constraints.
+    pub config: YoloConfig,
     pub leaderboards: HashMap<String, SymbolLeaderboard>,
     pub cross_leaderboard: CrossSymbolLeaderboard,
     pub iterations_completed: usize,
     pub success_count: usize,
+    /// Successful backtests that closed no trades (included in `success_count`).
+    #[serde(default)]
+    pub zero_trade_count: usize,
     pub error_count: usize,
     pub elapsed_secs: f64,
     pub history_entries_written: usize,
@@ -170,6 +463,45 @@ pub struct YoloResult {
     pub promoted_l2_count: usize,
     pub promoted_l3_count: usize,
     pub fdr_family_size: usize,
+    /// Trials in each symbol's FDR family at the end of the session,
+    /// including trials merged from earlier sessions.
+    #[serde(default)]
+    pub fdr_trials: BTreeMap<String, usize>,
+    /// Symbols whose leaderboard file disagreed with the history replay at
+    /// session start.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub leaderboard_divergence: BTreeMap<String, LeaderboardDivergence>,
+    /// Per-symbol best fitness sampled over the session.
+    pub convergence: Vec<ConvergencePoint>,
+    /// True if the session ended because of `PlateauAction::Stop`.
+    pub stopped_on_plateau: bool,
+    /// Why the session ended. None for sessions saved before it was recorded.
+    #[serde(default)]
+    pub stop_reason: Option<StopReason>,
+    /// Engine phase timings summed over every completed backtest.
+    pub timings: EngineTimings,
+    /// Bars simulated, engine time and indicator-cache lookups, per symbol
+    /// and in total.
+    #[serde(default)]
+    pub compute: ComputeBudget,
+    /// Symbols skipped after repeated failures, in the order they were dropped.
+    #[serde(default)]
+    pub quarantined: Vec<QuarantinedSymbol>,
+    /// Failed backtests by error category.
+    #[serde(default)]
+    pub errors_by_kind: BTreeMap<RunErrorKind, usize>,
+    /// Sampled configs rejected and resampled, by compatibility rule id.
+    #[serde(default)]
+    pub rejections_by_rule: BTreeMap<String, usize>,
+    /// Adaptive exploration mode changes, in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mode_transitions: Vec<ModeTransition>,
+    /// First date of the holdout window, when the session held one out.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holdout_start: Option<NaiveDate>,
+    /// The session's one evaluation on the holdout window.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holdout: Option<HoldoutReport>,
 }
 
 /// Errors from the YOLO engine.
@@ -179,15 +511,10 @@ pub enum YoloError {
     NoSymbols,
     #[error("data error: {0}")]
     Data(String),
-}
-
-/// Record of a failed iteration for diagnostics.
-#[derive(Debug)]
-#[allow(dead_code)]
-struct FailedIteration {
-    iteration: usize,
-    symbol: String,
-    error: String,
+    #[error("load error: {0}")]
+    Load(#[from] LoadError),
+    #[error("holdout: {0}")]
+    Holdout(#[from] HoldoutError),
 }
 
 // ─── Core YOLO loop ──────────────────────────────────────────────────
@@ -206,6 +533,45 @@ pub fn run_yolo(
     symbols: &[String],
     progress_cb: Option<&dyn Fn(&YoloProgress)>,
     cancel: Option<&AtomicBool>,
+) -> Result<YoloResult, YoloError> {
+    run_yolo_live(config, data, symbols, progress_cb, cancel, None)
+}
+
+/// Load bars for `symbols`, then run YOLO mode on them.
+///
+/// Takes the same `cache`/`provider`/`opts` triple as
+/// [`run_single_backtest`](crate::runner::run_single_backtest): `provider` is
+/// only consulted on a cache miss, and never when `opts.offline` is set.
+#[allow(clippy::too_many_arguments)]
+pub fn run_yolo_from_cache(
+    config: &YoloConfig,
+    symbols: &[String],
+    cache: &ParquetCache,
+    provider: Option<&dyn DataProvider>,
+    opts: &LoadOptions,
+    progress_cb: Option<&dyn Fn(&YoloProgress)>,
+    cancel: Option<&AtomicBool>,
+    tunables: Option<&RwLock<YoloTunables>>,
+) -> Result<YoloResult, YoloError> {
+    if symbols.is_empty() {
+        return Err(YoloError::NoSymbols);
+    }
+    let sym_refs: Vec<&str> = symbols.iter().map(String::as_str).collect();
+    let loaded = load_bars(&sym_refs, cache, provider, None, opts)?;
+    run_yolo_live(config, &loaded, symbols, progress_cb, cancel, tunables)
+}
+
+/// Run YOLO mode with sliders that can be adjusted mid-session.
+///
+/// Identical to [`run_yolo`], except that `jitter_pct` and `structural_explore`
+/// are re-read from `tunables` (when provided) before each iteration's sample.
+pub fn run_yolo_live(
+    config: &YoloConfig,
+    data: &LoadedData,
+    symbols: &[String],
+    progress_cb: Option<&dyn Fn(&YoloProgress)>,
+    cancel: Option<&AtomicBool>,
+    tunables: Option<&RwLock<YoloTunables>>,
 ) -> Result<YoloResult, YoloError> {
     if symbols.is_empty() {
         return Err(YoloError::NoSymbols);
@@ -214,8 +580,38 @@ pub fn run_yolo(
     let mut config = config.clone();
     config.enforce_thread_constraints();
 
+    // Cut the holdout off before anything runs: discovery never sees its bars
+    let split = match config.holdout {
+        Some(ref holdout) => Some(data.split_at(holdout.boundary(&data.aligned.dates)?)),
+        None => None,
+    };
+    let (data, window) = match split {
+        Some((ref discovery, _)) => (discovery, EvalWindow::Discovery),
+        None => (data, EvalWindow::Full),
+    };
+    let holdout_start = split
+        .as_ref()
+        .and_then(|(_, holdout)| holdout.aligned.dates.first().copied());
+    let (window_start, window_end) = match window {
+        EvalWindow::Discovery => (
+            data.aligned.dates[0],
+            data.aligned.dates[data.aligned.dates.len() - 1],
+        ),
+        EvalWindow::Full => (config.start_date, config.end_date),
+    };
+
     let start_time = Instant::now();
-    let pool = ComponentPool::default_pool();
+    let compat = CompatibilityContext {
+        bar_count: Some(data.aligned.dates.len()),
+        min_post_warmup_bars: config.min_post_warmup_bars,
+    };
+    // Mutations draw from the same capped ranges as fresh samples
+    let mut pool = ComponentPool::default_pool();
+    if config.signal_ensembles {
+        pool = pool.with_signal_ensembles();
+    }
+    let pool = pool.conditioned_on(&compat);
+    let risk_overrides = config.risk_profile.map(RiskProfile::to_overrides);
     let run_id = RunId::from_bytes(format!("yolo-{}", config.master_seed).as_bytes());
     let rng_hierarchy = RngHierarchy::new(config.master_seed);
     let session_id = format!(
@@ -241,24 +637,101 @@ pub fn run_yolo(
 
     // Initialize cross-symbol leaderboard
     let mut cross_leaderboard =
-        CrossSymbolLeaderboard::new(config.leaderboard_max_size, config.catastrophic_threshold);
+        CrossSymbolLeaderboard::new(config.leaderboard_max_size, config.catastrophic_threshold)
+            .with_aggregation(config.cross_aggregation);
 
     // Initialize history if path is configured
-    let history = config
-        .history_path
-        .as_ref()
-        .map(|p| YoloHistory::new(p.clone(), config.write_filter.clone()));
+    let history = config.history_path.as_ref().map(|p| {
+        let filter = match config.history_preset {
+            Some(_) => WriteFilter::unfiltered(),
+            None => config.write_filter.clone(),
+        };
+        YoloHistory::new(p.clone(), filter)
+            .with_fsync(config.history_fsync)
+            .with_entry_budget(config.history_entry_budget_kb.map(|kb| kb * 1024))
+    });
     let mut history_entries_written: usize = 0;
+    let mut fitness_quartiles = FitnessQuartiles::default();
 
     // Initialize FDR family for promotion ladder
     let mut fdr_family = FdrFamily::new();
+
+    // Per-symbol FDR families over every trial, continuing earlier
+    // sessions' on the same dataset when merging
+    let mut symbol_families: HashMap<String, FdrFamily> = symbols
+        .iter()
+        .map(|s| (s.clone(), FdrFamily::new()))
+        .collect();
+    if let (true, Some(hist)) = (config.fdr.merge_sessions, &history) {
+        for record in hist.read_fdr().unwrap_or_default() {
+            if record.dataset_hash != data.dataset_hash {
+                continue;
+            }
+            if let Some(family) = symbol_families.get_mut(&record.symbol) {
+                family.absorb(&record);
+            }
+        }
+    }
+    // Leaderboard files, resumed from disk and the history
+    let mut stored_boards: HashMap<String, StoredLeaderboard> = HashMap::new();
+    let mut leaderboard_divergence: BTreeMap<String, LeaderboardDivergence> = BTreeMap::new();
+    if let Some(ref dir) = config.leaderboard_dir {
+        let replay = history
+            .as_ref()
+            .and_then(|h| h.read_all().ok())
+            .unwrap_or_default();
+        for symbol in symbols {
+            let resumed = resume(
+                dir,
+                symbol,
+                &replay,
+                config.leaderboard_max_size,
+                config.fitness_metric,
+            );
+            // A file that cannot be read is left alone for the session
+            let Ok(resumed) = resumed else {
+                continue;
+            };
+            if let Some(divergence) = resumed.divergence.filter(|d| !d.is_consistent()) {
+                leaderboard_divergence.insert(symbol.clone(), divergence);
+            }
+            stored_boards.insert(symbol.clone(), resumed.board);
+        }
+    }
+    // Trials not yet written to the history, per symbol
+    let mut unlogged_fdr: HashMap<String, FdrRecord> = HashMap::new();
     let mut promoted_l2_count: usize = 0;
     let mut promoted_l3_count: usize = 0;
 
     let mut success_count: usize = 0;
+    let mut zero_trade_count: usize = 0;
     let mut error_count: usize = 0;
-    let mut failed_log: Vec<FailedIteration> = Vec::new();
+    let mut errors_by_kind: BTreeMap<RunErrorKind, usize> = BTreeMap::new();
+    let mut rejections_by_rule: BTreeMap<String, usize> = BTreeMap::new();
+    let mut consecutive_failures: HashMap<String, usize> = HashMap::new();
+    let mut quarantined: Vec<QuarantinedSymbol> = Vec::new();
+    let mut recent_insertions: VecDeque<LeaderboardInsertion> =
+        VecDeque::with_capacity(RECENT_INSERTIONS_CAP);
     let mut last_progress = Instant::now();
+    let mut tracker = ConvergenceTracker::new(config.convergence.clone());
+    let mut stopped_on_plateau = false;
+    let mut timings = EngineTimings::default();
+    let mut compute = ComputeBudget::new();
+    // One indicator cache per symbol; every iteration runs on the same bars
+    let indicator_caches: HashMap<&str, Mutex<IndicatorCache>> = symbols
+        .iter()
+        .map(|s| (s.as_str(), Mutex::new(IndicatorCache::new())))
+        .collect();
+    let mut controller = config.adaptive.clone().map(|adaptive| {
+        AdaptiveController::new(
+            adaptive,
+            YoloTunables::from_config(&config),
+            &data.dataset_hash,
+        )
+    });
+    let mut mode_transitions: Vec<ModeTransition> = Vec::new();
+    // Transitions not yet written to a history checkpoint
+    let mut unlogged_transitions = 0;
 
     // Build Rayon thread pool if outer_thread_cap > 1
     let thread_pool = if config.outer_thread_cap > 1 {
@@ -274,89 +747,198 @@ pub fn run_yolo(
 
     let mut iteration: usize = 0;
 
-    loop {
+    let stop_reason = loop {
         // Check cancellation
         if cancel.is_some_and(|f| f.load(Ordering::Relaxed)) {
-            break;
+            break StopReason::Cancelled;
         }
 
         // Check iteration limit
         if let Some(max) = config.max_iterations {
             if iteration >= max {
-                break;
+                break StopReason::MaxIterations;
             }
         }
 
-        // Sample a strategy config using the iteration-specific RNG
+        // Check the compute budget
+        if let Some(reason) = compute.exhausted(&config.compute_limits, start_time.elapsed()) {
+            break reason;
+        }
+
+        // Quarantined symbols sit out the rest of the session
+        let active: Vec<&String> = symbols
+            .iter()
+            .filter(|s| !quarantined.iter().any(|q| &q.symbol == *s))
+            .collect();
+        if active.is_empty() {
+            break StopReason::AllQuarantined;
+        }
+
+        // Pick up slider changes made since the last iteration
+        if let Some(lock) = tunables {
+            let live = match lock.read() {
+                Ok(guard) => *guard,
+                Err(poisoned) => *poisoned.into_inner(),
+            };
+            config.jitter_pct = live.jitter_pct;
+            config.structural_explore = live.structural_explore;
+        }
+
+        // The controller takes slider moves as its new baseline and
+        // overrides the values sampled with
+        if let Some(ref mut controller) = controller {
+            let baseline = YoloTunables::from_config(&config);
+            mode_transitions.extend(controller.set_baseline(iteration, baseline));
+            mode_transitions.extend(controller.observe_dataset(iteration, &data.dataset_hash));
+            let tunables = controller.tunables();
+            config.jitter_pct = tunables.jitter_pct;
+            config.structural_explore = tunables.structural_explore;
+        }
+        let exploit_pct = controller
+            .as_ref()
+            .map_or(config.exploit_pct, |c| c.exploit_pct(config.exploit_pct));
+
+        // Sample a strategy config using the iteration-specific RNG, or
+        // mutate a champion when exploiting
         let mut sampler_rng = rng_hierarchy.rng_for(&run_id, "sampler", iteration as u64);
-        let strategy_config = sample_composition(
-            &pool,
-            &mut sampler_rng,
-            config.jitter_pct,
-            config.structural_explore,
-        );
-
-        // Decode execution preset from the sampled config
+        let mut exploit_rng = rng_hierarchy.rng_for(&run_id, "exploit", iteration as u64);
+        let champion = if exploit_pct > 0.0 && exploit_rng.gen::<f64>() < exploit_pct {
+            let top = cross_leaderboard.get_ranked(RankingMetric::AvgSharpe);
+            let n = top.len().min(EXPLOIT_CHAMPIONS);
+            (n > 0).then(|| top[exploit_rng.gen_range(0..n)].config.clone())
+        } else {
+            None
+        };
+        let (mut strategy_config, provenance) = match champion {
+            Some(parent) => {
+                let (child, provenance) =
+                    mutate_composition(&pool, &parent, &mut sampler_rng, config.jitter_pct);
+                let provenance = (!provenance.changes.is_empty()).then_some(provenance);
+                (child, provenance)
+            }
+            None => {
+                let sampled = sample_compatible_composition(
+                    &pool,
+                    &mut sampler_rng,
+                    config.jitter_pct,
+                    config.structural_explore,
+                    &compat,
+                );
+                for rule in sampled.rejections {
+                    *rejections_by_rule.entry(rule.to_string()).or_default() += 1;
+                }
+                (sampled.config, None)
+            }
+        };
+        if let Some(overrides) = &risk_overrides {
+            overrides.apply(&mut strategy_config);
+        }
+
+        // Decode execution preset from the sampled config; per-symbol
+        // profiles adjust it for each run
         let iter_preset = decode_execution_preset(&strategy_config.execution_model.params);
+        let profile_for = |symbol: &str| {
+            config
+                .execution_profiles
+                .resolve(symbol, data.avg_dollar_volume(symbol))
+        };
 
-        // Run backtests for each symbol
-        let iter_results: Vec<(String, Result<crate::runner::BacktestResult, RunError>)> =
-            if let Some(ref tp) = thread_pool {
-                tp.install(|| {
-                    symbols
-                        .par_iter()
-                        .map(|symbol| {
-                            let result = run_backtest_from_data(
-                                &strategy_config,
-                                &data.aligned,
-                                symbol,
-                                config.trading_mode,
-                                config.initial_capital,
-                                config.position_size_pct,
-                                iter_preset,
-                                &data.dataset_hash,
-                                data.has_synthetic,
-                            );
-                            (symbol.clone(), result)
-                        })
-                        .collect()
-                })
-            } else {
-                symbols
-                    .iter()
-                    .map(|symbol| {
-                        let result = run_backtest_from_data(
-                            &strategy_config,
-                            &data.aligned,
-                            symbol,
-                            config.trading_mode,
-                            config.initial_capital,
-                            config.position_size_pct,
-                            iter_preset,
-                            &data.dataset_hash,
-                            data.has_synthetic,
-                        );
-                        (symbol.clone(), result)
-                    })
-                    .collect()
+        // Run backtests for each symbol, through its indicator cache
+        let run_symbol = |symbol: &&String| {
+            let mut cache = match indicator_caches[symbol.as_str()].lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            let (hits, misses) = (cache.hits(), cache.misses());
+            let result = run_backtest_profiled_cached(
+                &strategy_config,
+                &data.aligned,
+                symbol,
+                config.trading_mode,
+                config.initial_capital,
+                config.position_size_pct,
+                iter_preset,
+                profile_for(symbol),
+                &data.dataset_hash,
+                data.has_synthetic,
+                &mut cache,
+            );
+            let (hits, misses) = (cache.hits() - hits, cache.misses() - misses);
+            if cache.len() > INDICATOR_CACHE_SERIES {
+                cache.clear();
+            }
+            let usage = match result {
+                Ok(ref r) => ComputeUsage::of_run(r, hits, misses),
+                Err(_) => ComputeUsage {
+                    cache_hits: hits,
+                    cache_misses: misses,
+                    ..ComputeUsage::default()
+                },
+            };
+            (symbol.to_string(), result, usage)
+        };
+        let iter_results: Vec<(String, Result<BacktestResult, RunError>, ComputeUsage)> =
+            match thread_pool {
+                Some(ref tp) => tp.install(|| active.par_iter().map(run_symbol).collect()),
+                None => active.iter().map(run_symbol).collect(),
             };
 
         // Process results
         let now = chrono::Utc::now().naive_utc();
-        for (symbol, result) in iter_results {
+        let mut inserted = false;
+        for (symbol, result, usage) in iter_results {
+            compute.record(&symbol, &usage);
             match result {
-                Ok(backtest_result) => {
+                Ok(mut backtest_result) => {
+                    consecutive_failures.remove(&symbol);
+                    timings.merge(&backtest_result.timings);
+                    backtest_result
+                        .data_quality_warnings
+                        .extend(data.warnings_for(&symbol).cloned());
+                    backtest_result.truncated_range = data.truncated_range(&symbol).cloned();
+                    if let Some(inputs) = backtest_result.inputs.as_mut() {
+                        inputs.risk_profile = config.risk_profile;
+                    }
+
                     let fitness = config.fitness_metric.extract(&backtest_result.metrics);
 
+                    // Every completed run is a trial in the symbol's FDR
+                    // family; only leaderboard-worthy runs get a p-value
+                    let worthy = !backtest_result.zero_trades
+                        && !backtest_result.trades.is_empty()
+                        && fitness.is_finite()
+                        && backtest_result.metrics.sharpe.is_finite()
+                        && backtest_result.metrics.cagr.is_finite();
+                    let p_value = worthy
+                        .then(|| returns_p_value(&backtest_result.equity_curve))
+                        .flatten();
+                    let family = symbol_families.entry(symbol.clone()).or_default();
+                    let trial = p_value.map(|p| (strategy_config.full_hash().as_hex(), p));
+                    if history.is_some() {
+                        let unlogged = unlogged_fdr.entry(symbol.clone()).or_insert_with(|| {
+                            FdrRecord::new(&session_id, &symbol, &data.dataset_hash)
+                        });
+                        unlogged.iteration = iteration;
+                        unlogged.trials += 1;
+                        unlogged.p_values.extend(trial.clone());
+                    }
+                    match trial {
+                        Some((config_id, p)) => family.add(config_id, p),
+                        None => family.add_untested(1),
+                    }
+
                     // Filter: at least 1 trade and finite metrics
-                    if backtest_result.trades.is_empty()
-                        || !fitness.is_finite()
-                        || !backtest_result.metrics.sharpe.is_finite()
-                        || !backtest_result.metrics.cagr.is_finite()
-                    {
+                    if backtest_result.zero_trades {
+                        success_count += 1;
+                        zero_trade_count += 1;
+                        continue;
+                    }
+                    if !worthy {
                         success_count += 1; // Counts as successful execution, just not leaderboard-worthy
                         continue;
                     }
+                    tracker.observe(&symbol, fitness);
+                    let profile = profile_for(&symbol);
 
                     // Insert into cross-symbol leaderboard
                     cross_leaderboard.insert_result(
@@ -369,14 +951,23 @@ pub fn run_yolo(
                         now,
                     );
 
-                    // Thread stickiness into cross-symbol leaderboard
+                    // Thread stickiness and trades into cross-symbol leaderboard
                     let full_hash = strategy_config.full_hash();
+                    cross_leaderboard.set_trades(&full_hash, &symbol, &backtest_result.trades);
+                    if let Some(ref provenance) = provenance {
+                        cross_leaderboard.set_provenance(&full_hash, provenance.clone());
+                    }
+                    cross_leaderboard.set_execution_profile(
+                        &full_hash,
+                        &symbol,
+                        profile.map(|p| p.name.as_str()),
+                    );
                     if let Some(ref stickiness) = backtest_result.stickiness {
-                        cross_leaderboard.set_stickiness(
-                            &full_hash,
-                            &symbol,
-                            stickiness.clone(),
-                        );
+                        cross_leaderboard.set_stickiness(&full_hash, &symbol, stickiness.clone());
+                    }
+                    let fdr = p_value.map(|p| family.q_values().standing(p, config.fdr.alpha));
+                    if let Some(standing) = fdr {
+                        cross_leaderboard.set_fdr(&full_hash, &symbol, standing);
                     }
 
                     // Run promotion ladder if configured
@@ -389,7 +980,7 @@ pub fn run_yolo(
                             config.trading_mode,
                             config.initial_capital,
                             config.position_size_pct,
-                            iter_preset,
+                            profile.map_or(iter_preset, |p| p.preset_for(iter_preset)),
                             &data.dataset_hash,
                             promo_config,
                             &mut fdr_family,
@@ -407,8 +998,10 @@ pub fn run_yolo(
                         cross_leaderboard.set_robustness(&full_hash, robustness);
                     }
 
-                    // Build and persist fingerprint if history is enabled
-                    if let Some(ref hist) = history {
+                    // Build the history entry if history is enabled; it is
+                    // written once the leaderboard outcome is known
+                    let mut history_entry = None;
+                    if history.is_some() {
                         let fingerprint = RunFingerprint {
                             run_id: RunId::from_bytes(
                                 format!("yolo-{}-{}-{}", config.master_seed, iteration, symbol)
@@ -417,11 +1010,13 @@ pub fn run_yolo(
                             timestamp: now,
                             seed: config.master_seed,
                             symbol: symbol.clone(),
-                            start_date: config.start_date,
-                            end_date: config.end_date,
+                            start_date: window_start,
+                            end_date: window_end,
                             trading_mode: config.trading_mode,
                             initial_capital: config.initial_capital,
                             strategy_config: strategy_config.clone(),
+                            execution_profile: profile.cloned(),
+                            risk_profile: config.risk_profile.map(|p| p.label().to_string()),
                             config_hash: strategy_config.config_hash(),
                             full_hash: strategy_config.full_hash(),
                             dataset_hash: DatasetHash::from_bytes(data.dataset_hash.as_bytes()),
@@ -432,11 +1027,21 @@ pub fn run_yolo(
                             metrics: backtest_result.metrics.clone(),
                             trade_count: backtest_result.trades.len(),
                             fitness_score: fitness,
+                            return_sample: downsample_returns(
+                                &backtest_result.equity_curve,
+                                RETURN_SAMPLE_POINTS,
+                            ),
+                            provenance: provenance.clone(),
+                            window,
+                            data_warnings: backtest_result.data_quality_warnings.len(),
+                            detail: config.history_detail.then(|| {
+                                EntryDetail::Embedded(RunDetail {
+                                    equity_curve: backtest_result.equity_curve.clone(),
+                                    trades: backtest_result.trades.clone(),
+                                })
+                            }),
                         };
-
-                        if let Ok(true) = hist.append(&entry) {
-                            history_entries_written += 1;
-                        }
+                        history_entry = Some(entry);
                     }
 
                     // Insert into per-symbol leaderboard
@@ -446,27 +1051,174 @@ pub fn run_yolo(
                         iteration,
                         session_id: session_id.clone(),
                         timestamp: now,
+                        artifact_dir: None,
+                        retest: None,
+                        holdout: None,
+                        fdr,
                     };
+                    let stored_entry = stored_boards
+                        .contains_key(&symbol)
+                        .then(|| StoredEntry::from_entry(&entry));
 
+                    let mut ranked = false;
                     if let Some(lb) = leaderboards.get_mut(&symbol) {
-                        lb.insert(entry);
+                        let outcome = lb.insert(entry);
+                        if matches!(outcome, InsertResult::Inserted | InsertResult::Replaced) {
+                            inserted = true;
+                            ranked = true;
+                            if let (Some(board), Some(stored), Some(dir)) = (
+                                stored_boards.get_mut(&symbol),
+                                stored_entry,
+                                &config.leaderboard_dir,
+                            ) {
+                                if matches!(
+                                    board.insert(stored),
+                                    InsertResult::Inserted | InsertResult::Replaced
+                                ) {
+                                    let _ = board.save(dir);
+                                }
+                            }
+                            if let Some(ref ramp) = config.friction_ramp {
+                                if let Ok(friction) = run_friction_ramp(
+                                    &strategy_config,
+                                    &data.aligned,
+                                    &symbol,
+                                    ramp,
+                                    config.trading_mode,
+                                    config.initial_capital,
+                                    config.position_size_pct,
+                                    &data.dataset_hash,
+                                ) {
+                                    cross_leaderboard.set_friction(&full_hash, friction);
+                                }
+                            }
+                            if recent_insertions.len() == RECENT_INSERTIONS_CAP {
+                                recent_insertions.pop_front();
+                            }
+                            recent_insertions.push_back(LeaderboardInsertion {
+                                iteration,
+                                symbol: symbol.clone(),
+                                signal_type: strategy_config.signal.component_type.clone(),
+                                pm_type: strategy_config.position_manager.component_type.clone(),
+                                fitness_score: fitness,
+                                replaced: outcome == InsertResult::Replaced,
+                            });
+                        }
+                    }
+
+                    if let (Some(hist), Some(entry)) = (&history, history_entry) {
+                        let selected = match config.history_preset {
+                            None | Some(WritePreset::Everything) => true,
+                            Some(WritePreset::LeaderboardOnly) => ranked,
+                            Some(WritePreset::TopQuartileByFitness) => {
+                                fitness_quartiles.observe(&symbol, fitness)
+                            }
+                            Some(WritePreset::ErrorsAndWarnings) => entry.data_warnings > 0,
+                        };
+                        if selected {
+                            if let Ok(true) = hist.append(&entry) {
+                                history_entries_written += 1;
+                            }
+                        }
                     }
                     success_count += 1;
                 }
                 Err(e) => {
-                    failed_log.push(FailedIteration {
-                        iteration,
-                        symbol: symbol.clone(),
-                        error: e.to_string(),
-                    });
                     error_count += 1;
+                    *errors_by_kind.entry(e.kind()).or_default() += 1;
+                    if let Some(ref hist) = history {
+                        if config.history_preset.is_some_and(WritePreset::keeps_errors) {
+                            let _ = hist.append_error(&RunErrorEntry {
+                                session_id: session_id.clone(),
+                                iteration,
+                                symbol: symbol.clone(),
+                                full_hash: strategy_config.full_hash(),
+                                kind: e.kind(),
+                                message: e.to_string(),
+                            });
+                        }
+                    }
+                    if e.symbol().is_none() {
+                        continue;
+                    }
+                    let failures = consecutive_failures.entry(symbol.clone()).or_default();
+                    *failures += 1;
+                    if config.quarantine_after > 0 && *failures >= config.quarantine_after {
+                        quarantined.push(QuarantinedSymbol {
+                            symbol,
+                            iteration,
+                            consecutive_failures: *failures,
+                            kind: e.kind(),
+                            retryable: e.is_retryable(),
+                            last_error: e.to_string(),
+                        });
+                    }
                 }
             }
         }
 
+        // The families grew: refresh every standing against them, and write
+        // trial batches once they are large enough
+        for symbol in &active {
+            let Some(family) = symbol_families.get(symbol.as_str()) else {
+                continue;
+            };
+            let q_values = family.q_values();
+            if let Some(lb) = leaderboards.get_mut(symbol.as_str()) {
+                lb.refresh_fdr(&q_values, config.fdr.alpha);
+            }
+            cross_leaderboard.refresh_fdr(symbol, &q_values, config.fdr.alpha);
+        }
+        if let Some(ref hist) = history {
+            flush_fdr(hist, &mut unlogged_fdr, FDR_BATCH_TRIALS);
+        }
+
+        compute.end_iteration();
+
+        // Convergence: sample, checkpoint to history, and act on plateaus
+        match tracker.end_iteration(iteration, inserted) {
+            Some(PlateauAction::Stop) => stopped_on_plateau = true,
+            Some(PlateauAction::BoostExplore { step }) => {
+                if let Some(lock) = tunables {
+                    match lock.write() {
+                        Ok(mut guard) => guard.nudge_explore(step),
+                        Err(poisoned) => poisoned.into_inner().nudge_explore(step),
+                    }
+                }
+                config.structural_explore = (config.structural_explore + step).clamp(0.0, 1.0);
+                if let Some(ref mut controller) = controller {
+                    let mut baseline = controller.baseline();
+                    baseline.nudge_explore(step);
+                    mode_transitions.extend(controller.set_baseline(iteration, baseline));
+                }
+            }
+            Some(PlateauAction::Report) | None => {}
+        }
+        if let Some(ref mut controller) = controller {
+            mode_transitions.extend(controller.end_iteration(
+                iteration,
+                inserted,
+                tracker.state().iterations_since_improvement,
+                cross_leaderboard.len().min(EXPLOIT_CHAMPIONS),
+            ));
+        }
+        if let (Some(hist), Some(point)) = (&history, tracker.last_point()) {
+            if point.iteration == iteration {
+                let _ = hist.append_checkpoint(&HistoryCheckpoint {
+                    session_id: session_id.clone(),
+                    point: point.clone(),
+                    iterations_since_improvement: tracker.state().iterations_since_improvement,
+                    mode: controller.as_ref().map(AdaptiveController::mode),
+                    transitions: mode_transitions[unlogged_transitions..].to_vec(),
+                    compute: Some(compute.total),
+                });
+                unlogged_transitions = mode_transitions.len();
+            }
+        }
+
         // Progress callback (throttled to 500ms)
         if let Some(cb) = progress_cb {
-            if last_progress.elapsed().as_millis() >= 500 || iteration == 0 {
+            if last_progress.elapsed().as_millis() >= 500 || iteration == 0 || stopped_on_plateau {
                 let elapsed = start_time.elapsed().as_secs_f64();
                 let total_lb_entries: usize = leaderboards.values().map(|lb| lb.len()).sum();
                 let throughput = if elapsed > 0.0 {
@@ -481,12 +1233,20 @@ pub fn run_yolo(
                     .unwrap_or(0) as f64
                     / (1024.0 * 1024.0);
 
+                let best_fitness: BTreeMap<String, f64> = leaderboards
+                    .iter()
+                    .filter_map(|(sym, lb)| {
+                        lb.entries().first().map(|e| (sym.clone(), e.fitness_score))
+                    })
+                    .collect();
+
                 cb(&YoloProgress {
                     iteration,
                     current_symbol: String::new(),
                     symbols_complete: symbols.len(),
                     symbols_total: symbols.len(),
                     success_count,
+                    zero_trade_count,
                     error_count,
                     throughput_per_min: throughput,
                     leaderboard_entries: total_lb_entries,
@@ -496,26 +1256,66 @@ pub fn run_yolo(
                     promoted_l2_count,
                     promoted_l3_count,
                     fdr_family_size: fdr_family.len(),
+                    data_quality_warnings: data.data_quality_warnings.len(),
+                    jitter_pct: config.jitter_pct,
+                    structural_explore: config.structural_explore,
+                    best_fitness,
+                    recent_insertions: recent_insertions.iter().cloned().collect(),
+                    convergence: tracker.state(),
+                    quarantined: quarantined.iter().map(|q| q.symbol.clone()).collect(),
+                    errors_by_kind: errors_by_kind.clone(),
+                    rejections_by_rule: rejections_by_rule.clone(),
+                    exploration_mode: controller.as_ref().map(AdaptiveController::mode),
+                    compute: compute.total,
                 });
                 last_progress = Instant::now();
             }
         }
 
         iteration += 1;
-    }
+        if stopped_on_plateau {
+            break StopReason::Plateau;
+        }
+    };
 
     let elapsed = start_time.elapsed().as_secs_f64();
 
+    // Cluster the champions so callers can offer a diversified view
+    cross_leaderboard.analyze_overlap(RankingMetric::AvgSharpe, &OverlapConfig::default());
+
+    // Log transitions since the last checkpoint against the final sample
+    let since_improvement = tracker.state().iterations_since_improvement;
+    let convergence = tracker.finish(iteration.checked_sub(1));
+    if let (Some(hist), Some(point)) = (&history, convergence.last()) {
+        if unlogged_transitions < mode_transitions.len() {
+            let _ = hist.append_checkpoint(&HistoryCheckpoint {
+                session_id: session_id.clone(),
+                point: point.clone(),
+                iterations_since_improvement: since_improvement,
+                mode: controller.as_ref().map(AdaptiveController::mode),
+                transitions: mode_transitions[unlogged_transitions..].to_vec(),
+                compute: Some(compute.total),
+            });
+        }
+    }
+
+    if let Some(ref hist) = history {
+        flush_fdr(hist, &mut unlogged_fdr, 1);
+    }
+
     let history_file_size_bytes = history
         .as_ref()
         .and_then(|h| h.file_size_bytes().ok())
         .unwrap_or(0);
 
-    Ok(YoloResult {
+    let mut result = YoloResult {
+        session_id,
+        config,
         leaderboards,
         cross_leaderboard,
         iterations_completed: iteration,
         success_count,
+        zero_trade_count,
         error_count,
         elapsed_secs: elapsed,
         history_entries_written,
@@ -523,7 +1323,46 @@ pub fn run_yolo(
         promoted_l2_count,
         promoted_l3_count,
         fdr_family_size: fdr_family.len(),
-    })
+        fdr_trials: symbol_families
+            .iter()
+            .map(|(symbol, family)| (symbol.clone(), family.trials()))
+            .collect(),
+        leaderboard_divergence,
+        convergence,
+        stopped_on_plateau,
+        stop_reason: Some(stop_reason),
+        timings,
+        compute,
+        quarantined,
+        errors_by_kind,
+        rejections_by_rule,
+        mode_transitions,
+        holdout_start,
+        holdout: None,
+    };
+
+    // The holdout is evaluated once, on the final leaderboards
+    let evaluate_at_end = result
+        .config
+        .holdout
+        .as_ref()
+        .is_some_and(|h| h.evaluate_at_end);
+    if let Some((_, holdout)) = split.as_ref().filter(|_| evaluate_at_end) {
+        let report = result.evaluate_holdout(holdout, chrono::Utc::now().naive_utc())?;
+        if let Some(ref hist) = history {
+            for entry in &report.evaluated {
+                let _ = hist.append_holdout(entry);
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Append the FDR batches holding at least `min_trials` trials and drop
+/// them from `unlogged`. A failed write keeps the batch for the next flush.
+fn flush_fdr(hist: &YoloHistory, unlogged: &mut HashMap<String, FdrRecord>, min_trials: usize) {
+    unlogged.retain(|_, record| record.trials < min_trials || hist.append_fdr(record).is_err());
 }
 
 /// Check if a backtest result has valid metrics for leaderboard consideration.
@@ -588,11 +1427,40 @@ mod tests {
             sources: HashMap::new(),
             dataset_hash: "empty".into(),
             has_synthetic: false,
+            data_quality_warnings: vec![],
+            truncated_ranges: Default::default(),
+            avg_dollar_volume: HashMap::new(),
+            vintage: Vec::new(),
         };
         let result = run_yolo(&config, &data, &[], None, None);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn tunables_nudge_clamps_to_unit_range() {
+        let mut t = YoloTunables::from_config(&YoloConfig::default());
+        assert_eq!(t.structural_explore, 0.3);
+        t.nudge_explore(0.05);
+        assert!((t.structural_explore - 0.35).abs() < 1e-12);
+        t.nudge_explore(5.0);
+        assert_eq!(t.structural_explore, 1.0);
+        t.nudge_jitter(-5.0);
+        assert_eq!(t.jitter_pct, 0.0);
+    }
+
+    #[test]
+    fn progress_without_live_fields_deserializes() {
+        let json = r#"{"iteration":3,"current_symbol":"","symbols_complete":1,
+            "symbols_total":1,"success_count":3,"error_count":0,"throughput_per_min":60.0,
+            "leaderboard_entries":2,"elapsed_secs":3.0,"cross_leaderboard_entries":2,
+            "history_file_size_mb":0.0,"promoted_l2_count":0,"promoted_l3_count":0,
+            "fdr_family_size":0}"#;
+        let p: YoloProgress = serde_json::from_str(json).unwrap();
+        assert_eq!(p.iteration, 3);
+        assert!(p.best_fitness.is_empty());
+        assert!(p.recent_insertions.is_empty());
+    }
+
     #[test]
     fn sweep_depth_serialization() {
         let depth = SweepDepth::Deep;
@@ -625,6 +1493,11 @@ mod tests {
             max_consecutive_wins: 0,
             max_consecutive_losses: 0,
             avg_losing_streak: 0.0,
+            time_in_market: 0.0,
+            avg_exposure: 0.0,
+            invested_return: 0.0,
+            idle_return: 0.0,
+            exposure_adjusted_cagr: 0.0,
         };
         assert!(!is_valid_for_leaderboard(&metrics, 0));
     }
@@ -645,6 +1518,11 @@ mod tests {
             max_consecutive_wins: 3,
             max_consecutive_losses: 2,
             avg_losing_streak: 1.5,
+            time_in_market: 0.0,
+            avg_exposure: 0.0,
+            invested_return: 0.0,
+            idle_return: 0.0,
+            exposure_adjusted_cagr: 0.0,
         };
         assert!(!is_valid_for_leaderboard(&metrics, 5));
     }
@@ -665,6 +1543,11 @@ mod tests {
             max_consecutive_wins: 3,
             max_consecutive_losses: 2,
             avg_losing_streak: 1.5,
+            time_in_market: 0.0,
+            avg_exposure: 0.0,
+            invested_return: 0.0,
+            idle_return: 0.0,
+            exposure_adjusted_cagr: 0.0,
         };
         assert!(is_valid_for_leaderboard(&metrics, 10));
     }
@@ -0,0 +1,177 @@
+//! Integration tests for the embedding API (`TrendLab`).
+//!
+//! Every test runs offline against synthetic bars in a temp directory.
+
+use chrono::NaiveDate;
+
+use trendlab_runner::config::ConfigError;
+use trendlab_runner::promotion::PromotionConfig;
+use trendlab_runner::runner::RunError;
+use trendlab_runner::yolo::YoloConfig;
+use trendlab_runner::{
+    diff_corpora, load_snapshot, read_arrow, save_snapshot, CorpusOutcome, Settings, TrendLab,
+    RESULTS_ARROW, TRADES_ARROW,
+};
+
+fn offline_lab(dir: &tempfile::TempDir) -> TrendLab {
+    TrendLab::new(&Settings::default())
+        .with_cache_dir(dir.path().join("data"))
+        .with_results_dir(dir.path().join("results"))
+        .with_offline(true)
+        .with_synthetic(true)
+        .with_jobs(1)
+}
+
+fn range() -> std::ops::RangeInclusive<NaiveDate> {
+    NaiveDate::from_ymd_opt(2021, 1, 4).unwrap()..=NaiveDate::from_ymd_opt(2023, 12, 29).unwrap()
+}
+
+#[test]
+fn run_preset_matches_run_of_its_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let lab = offline_lab(&dir);
+
+    let config = lab.preset_config("momentum_roc", "SPY", range()).unwrap();
+    let direct = lab.run(&config).unwrap();
+    let preset = lab.run_preset("momentum_roc", "SPY", range()).unwrap();
+    assert_eq!(direct.config, preset.config);
+    assert_eq!(direct.equity_curve, preset.equity_curve);
+    assert_eq!(direct.start_date, "2021-01-04");
+}
+
+#[test]
+fn unknown_preset_is_a_config_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let err = offline_lab(&dir)
+        .run_preset("turtle", "SPY", range())
+        .unwrap_err();
+    let RunError::ConfigInvalid(ConfigError::UnknownPreset(message)) = err else {
+        panic!("expected UnknownPreset, got {err:?}");
+    };
+    assert!(message.contains("donchian_trend"), "{message}");
+}
+
+#[test]
+fn promote_reuses_cached_levels() {
+    let dir = tempfile::tempdir().unwrap();
+    let lab = offline_lab(&dir);
+    // Long enough for the default walk-forward's minimum bar count
+    let start = NaiveDate::from_ymd_opt(2018, 1, 2).unwrap();
+    let result = lab
+        .run_preset("momentum_roc", "SPY", start..=*range().end())
+        .unwrap();
+    let promotion = PromotionConfig {
+        wf_sharpe_threshold: f64::NEG_INFINITY,
+        ..PromotionConfig::default()
+    };
+
+    let first = lab.promote(&result, &promotion).unwrap();
+    let computed = lab.robustness_cache().unwrap().stats().misses;
+    assert!(computed > 0);
+    let second = lab.promote(&result, &promotion).unwrap();
+    let stats = lab.robustness_cache().unwrap().stats();
+    assert_eq!(
+        stats.misses, computed,
+        "second promotion recomputed a level"
+    );
+    assert_eq!(first.level_reached, second.level_reached);
+}
+
+#[test]
+fn discover_needs_symbols_from_caller_or_settings() {
+    let dir = tempfile::tempdir().unwrap();
+    let lab = offline_lab(&dir);
+    let config = YoloConfig {
+        start_date: *range().start(),
+        end_date: *range().end(),
+        max_iterations: Some(2),
+        ..YoloConfig::default()
+    };
+
+    // Default settings have no symbols
+    assert!(lab.discover(&config, &[]).is_err());
+    let result = lab.discover(&config, &["SPY".to_string()]).unwrap();
+    assert_eq!(result.iterations_completed, 2);
+}
+
+#[test]
+fn save_writes_arrow_files_only_when_enabled() {
+    let dir = tempfile::tempdir().unwrap();
+    let lab = offline_lab(&dir);
+    let result = lab.run_preset("momentum_roc", "SPY", range()).unwrap();
+
+    let plain = lab.save(&result).unwrap();
+    assert!(!plain.join(RESULTS_ARROW).exists());
+
+    let saved = lab.with_arrow_export(true).save(&result).unwrap();
+    assert!(saved.join(TRADES_ARROW).exists());
+    let bars = read_arrow(&saved.join(RESULTS_ARROW)).unwrap();
+    assert_eq!(bars.height(), result.equity_curve.len());
+}
+
+fn write_corpus(dir: &std::path::Path, periods: &[(&str, f64)]) {
+    std::fs::create_dir_all(dir.join("breakouts")).unwrap();
+    for (name, period) in periods {
+        let toml = format!(
+            r#"
+[backtest]
+symbol = "SPY"
+start_date = "2021-01-04"
+end_date = "2023-12-29"
+
+[signal]
+type = "roc_momentum"
+params = {{ period = {period:.1}, threshold_pct = 0.0 }}
+
+[position_manager]
+type = "time_decay"
+params = {{ initial_pct = 0.10, decay_per_bar = 0.005, min_pct = 0.02 }}
+
+[execution_model]
+type = "next_bar_open"
+"#
+        );
+        std::fs::write(dir.join("breakouts").join(name), toml).unwrap();
+    }
+    std::fs::write(dir.join("broken.toml"), "[backtest]\nsymbol = 1\n").unwrap();
+}
+
+#[test]
+fn corpus_diff_localizes_a_changed_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let lab = offline_lab(&dir);
+    let configs = [("fast.toml", 12.0), ("mid.toml", 20.0), ("slow.toml", 30.0)];
+    write_corpus(&dir.path().join("configs_a"), &configs);
+    let mut changed = configs;
+    changed[1].1 = 15.0;
+    write_corpus(&dir.path().join("configs_b"), &changed);
+
+    let a = lab.run_corpus(&dir.path().join("configs_a")).unwrap();
+    let names: Vec<_> = a.entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "breakouts/fast.toml",
+            "breakouts/mid.toml",
+            "breakouts/slow.toml",
+            "broken.toml"
+        ]
+    );
+    assert_eq!(a.failed(), 1);
+    assert!(matches!(a.entries[3].outcome, CorpusOutcome::Failed { .. }));
+    assert!(matches!(&a.entries[1].outcome, CorpusOutcome::Ran(run) if run.trade_count > 0));
+
+    // Re-running the same configs reproduces the snapshot exactly
+    let path = save_snapshot(&a, &dir.path().join("corpus_A")).unwrap();
+    let again = lab.run_corpus(&dir.path().join("configs_a")).unwrap();
+    assert_eq!(load_snapshot(&path).unwrap(), again);
+    assert!(!diff_corpora(&a, &again, 0.0).has_drift());
+
+    let b = lab.run_corpus(&dir.path().join("configs_b")).unwrap();
+    let diff = diff_corpora(&a, &b, 1e-9);
+    assert_eq!(diff.compared, 4);
+    let changed: Vec<_> = diff.changed.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(changed, ["breakouts/mid.toml"]);
+    assert!(!diff.changed[0].data_changed);
+    assert!(diff.max_drift.values().any(|&d| d > 0.0));
+}
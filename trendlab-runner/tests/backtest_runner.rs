@@ -1,16 +1,21 @@
 //! Integration tests for the runner: real strategies on real SPY data.
 //!
 //! Uses the frozen SPY 2024 fixture to run all five presets
-//! and verify trade extraction, metrics, and end-to-end correctness.
+//! and verify trade extraction, metrics, end-to-end correctness, and the
+//! Arrow export of bars and trades.
 
 use chrono::NaiveDate;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 use trendlab_core::components::composition::StrategyPreset;
 use trendlab_core::data::cache::ParquetCache;
-use trendlab_runner::config::BacktestConfig;
+use trendlab_core::data::BarInterval;
+use trendlab_core::engine::{WarmupContribution, WarmupPolicy};
+use trendlab_runner::config::{BacktestConfig, ConfigError, WarmupSection};
 use trendlab_runner::data_loader::LoadOptions;
-use trendlab_runner::runner::run_single_backtest;
+use trendlab_runner::metrics::PerformanceMetrics;
+use trendlab_runner::runner::{run_single_backtest, BacktestResult, RunError, RunErrorKind};
+use trendlab_runner::{load_artifacts, load_diagnostics, save_artifacts, RiskProfile};
 
 static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
 
@@ -50,6 +55,9 @@ fn load_opts() -> LoadOptions {
         offline: true,
         synthetic: false,
         force: false,
+        strict: false,
+        stale_after_days: None,
+        interval: Default::default(),
     }
 }
 
@@ -218,6 +226,67 @@ fn preset_supertrend_on_spy() {
 
 // ── Cross-preset distinctness ────────────────────────────────────
 
+#[test]
+fn preset_donchian_trend_on_weekly_spy() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(&cache_dir);
+    let mut config = config_from_preset(StrategyPreset::DonchianTrend);
+    config.backtest.interval = BarInterval::Weekly;
+    // A 50-bar lookback would be a whole year of weekly bars
+    config.signal.params.insert("entry_lookback".into(), 10.0);
+    config
+        .position_manager
+        .params
+        .insert("atr_period".into(), 5.0);
+    // 52 weekly bars leave fewer than the default 50 after the warmup
+    config.warmup = Some(WarmupSection {
+        policy: WarmupPolicy::default(),
+        min_tradable_bars: 30,
+    });
+    let opts = LoadOptions {
+        interval: BarInterval::Weekly,
+        ..load_opts()
+    };
+
+    // Only daily bars are cached: the weekly series is built from them
+    let result = run_single_backtest(&config, &cache, None, &opts).unwrap();
+    assert_eq!(result.interval, BarInterval::Weekly);
+    assert!(
+        (52..=53).contains(&result.bar_count),
+        "a year of weekly bars, got {}",
+        result.bar_count
+    );
+    assert_eq!(result.equity_curve.len(), result.bar_count);
+    for trade in &result.trades {
+        assert!(trade.exit_bar < result.bar_count);
+        assert!(trade.exit_date >= trade.entry_date);
+    }
+
+    // Metrics are annualized over 52 periods, not 252
+    let weekly = PerformanceMetrics::compute_annualized(
+        &result.equity_curve,
+        &result.trades,
+        result.initial_capital,
+        52.0,
+    );
+    assert_eq!(result.metrics.sharpe, weekly.sharpe);
+    assert_eq!(result.metrics.cagr, weekly.cagr);
+
+    // The daily run on the same cache is unaffected
+    let daily = run_single_backtest(
+        &config_from_preset(StrategyPreset::DonchianTrend),
+        &cache,
+        None,
+        &load_opts(),
+    )
+    .unwrap();
+    assert_eq!(daily.interval, BarInterval::Daily);
+    assert!(daily.bar_count > 250);
+    assert_ne!(daily.dataset_hash, result.dataset_hash);
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
 #[test]
 fn presets_produce_distinct_results() {
     let cache_dir = setup_fixture_cache();
@@ -272,6 +341,9 @@ type = "no_op"
 
 [execution_model]
 type = "next_bar_open"
+
+[warmup]
+min_tradable_bars = 0
 "#;
 
     let config = BacktestConfig::from_toml(toml_str).unwrap();
@@ -279,6 +351,7 @@ type = "next_bar_open"
 
     // With lookback 300 on 252 bars of data, no breakout signal should fire
     assert_eq!(result.trades.len(), 0, "expected zero trades");
+    assert!(result.zero_trades);
     assert_eq!(result.metrics.trade_count, 0);
     assert!((result.metrics.total_return - 0.0).abs() < 1e-10);
     assert!((result.metrics.sharpe - 0.0).abs() < 1e-10);
@@ -308,3 +381,483 @@ fn backtest_result_serializes_to_json() {
 
     let _ = std::fs::remove_dir_all(&cache_dir);
 }
+
+// ── Pre-flight validation ────────────────────────────────────────
+
+#[test]
+fn invalid_config_reports_every_error_before_loading() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(&cache_dir);
+    let opts = load_opts();
+    let mut config = config_from_preset(StrategyPreset::DonchianTrend);
+    config.backtest.end_date = "2024-02-30".into();
+    config.signal.component_type = "donchian".into();
+
+    let err = run_single_backtest(&config, &cache, None, &opts).unwrap_err();
+    assert_eq!(err.kind(), RunErrorKind::ConfigInvalid);
+    let RunError::ConfigInvalid(ConfigError::Invalid(report)) = err else {
+        panic!("expected a validation report, got {err}");
+    };
+    let fields: Vec<&str> = report.errors().map(|i| i.field.as_str()).collect();
+    assert_eq!(fields, vec!["backtest.end_date", "signal"]);
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+// ── Warmup ───────────────────────────────────────────────────────
+
+const EMA_200_TOML: &str = r#"
+[backtest]
+symbol = "SPY"
+start_date = "2024-01-02"
+end_date = "2024-12-31"
+
+[signal]
+type = "ma_crossover"
+[signal.params]
+fast_period = 50.0
+slow_period = 200.0
+ma_type = 1.0
+
+[position_manager]
+type = "no_op"
+
+[execution_model]
+type = "next_bar_open"
+"#;
+
+#[test]
+fn ema_200_warmup_leaving_too_few_bars_is_a_config_error() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(&cache_dir);
+    let opts = load_opts();
+
+    // 252 bars less a 199-bar warmup: 53 left, above the default floor
+    let config = BacktestConfig::from_toml(EMA_200_TOML).unwrap();
+    let result = run_single_backtest(&config, &cache, None, &opts).unwrap();
+    let warmup = result.warmup.as_ref().unwrap();
+    assert_eq!(warmup.applied, 199);
+    assert_eq!(warmup.tradable_bars, result.bar_count - 199);
+
+    // ...but under a stricter floor
+    let strict = format!("{EMA_200_TOML}\n[warmup]\nmin_tradable_bars = 100\n");
+    let config = BacktestConfig::from_toml(&strict).unwrap();
+    let err = run_single_backtest(&config, &cache, None, &opts).unwrap_err();
+    assert_eq!(err.kind(), RunErrorKind::ConfigInvalid);
+    let RunError::ConfigInvalid(ConfigError::InsufficientBars {
+        bar_count,
+        warmup,
+        tradable_bars,
+        minimum,
+    }) = &err
+    else {
+        panic!("expected InsufficientBars, got {err}");
+    };
+    assert_eq!((*warmup, *minimum), (199, 100));
+    assert_eq!(*tradable_bars, bar_count - 199);
+    assert!(err.to_string().contains("after a 199-bar warmup"));
+
+    // A convergence multiplier pushes the warmup past the data entirely
+    let scaled = format!("{EMA_200_TOML}\n[warmup]\nmultiplier = 1.5\n");
+    let config = BacktestConfig::from_toml(&scaled).unwrap();
+    let err = run_single_backtest(&config, &cache, None, &opts).unwrap_err();
+    assert!(matches!(
+        err,
+        RunError::ConfigInvalid(ConfigError::InsufficientBars {
+            warmup: 299,
+            tradable_bars: 0,
+            ..
+        })
+    ));
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+#[test]
+fn ema_200_warmup_breakdown_on_long_series() {
+    let cache_dir = std::env::temp_dir().join(format!(
+        "trendlab_runner_backtest_warmup_{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&cache_dir);
+    std::fs::create_dir_all(&cache_dir).unwrap();
+    let cache = ParquetCache::new(&cache_dir);
+    // 400 weeks of weekdays: about 2,000 synthetic bars
+    let opts = LoadOptions {
+        start: NaiveDate::from_ymd_opt(2016, 1, 1).unwrap(),
+        end: NaiveDate::from_ymd_opt(2023, 8, 24).unwrap(),
+        offline: false,
+        synthetic: true,
+        ..load_opts()
+    };
+
+    let toml = format!("{EMA_200_TOML}\n[warmup]\nmultiplier = 1.5\n");
+    let config = BacktestConfig::from_toml(&toml).unwrap();
+    let result = run_single_backtest(&config, &cache, None, &opts).unwrap();
+
+    assert!((1990..=2000).contains(&result.bar_count));
+    assert_eq!(result.warmup_bars, 299);
+    let warmup = result.warmup.as_ref().unwrap();
+    assert_eq!(
+        warmup.contributions,
+        vec![
+            WarmupContribution {
+                indicator: "ema_50".into(),
+                lookback: 49,
+                bars: 74,
+            },
+            WarmupContribution {
+                indicator: "ema_200".into(),
+                lookback: 199,
+                bars: 299,
+            },
+        ]
+    );
+    assert_eq!(warmup.multiplier, 1.5);
+    assert_eq!(warmup.applied, 299);
+    assert_eq!(warmup.tradable_bars, result.bar_count - 299);
+    assert_eq!(warmup.binding().unwrap().indicator, "ema_200");
+    // The multiplier is part of the config, so it changes the fingerprint
+    assert_eq!(result.config.signal.params["warmup_multiplier"], 1.5);
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+// ── Risk profiles ────────────────────────────────────────────────
+
+#[test]
+fn risk_profiles_change_fills_and_are_recorded_in_the_manifest() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(&cache_dir);
+    let opts = load_opts();
+
+    let run = |profile: RiskProfile| {
+        let mut config = config_from_preset(StrategyPreset::MomentumRoc);
+        config.risk_profile = Some(profile);
+        run_single_backtest(&config, &cache, None, &opts).unwrap()
+    };
+    let conservative = run(RiskProfile::Conservative);
+    let aggressive = run(RiskProfile::Aggressive);
+
+    // Half-size entries under the conservative cap
+    assert!(!conservative.trades.is_empty());
+    let fills = |r: &BacktestResult| {
+        r.trades
+            .iter()
+            .map(|t| (t.entry_bar, t.quantity))
+            .collect::<Vec<_>>()
+    };
+    assert_ne!(fills(&conservative), fills(&aggressive));
+    assert!(conservative.trades[0].quantity < aggressive.trades[0].quantity);
+    assert_eq!(
+        conservative.config.signal_filter.component_type,
+        "volatility_filter"
+    );
+    assert_ne!(
+        conservative.config.full_hash(),
+        aggressive.config.full_hash()
+    );
+
+    // The manifest records the profile, and the config it carries replays as is
+    let out_dir = cache_dir.join("results");
+    let run_dir = save_artifacts(&conservative, &out_dir).unwrap();
+    let saved = load_artifacts(&run_dir).unwrap();
+    let inputs = saved.inputs.as_ref().unwrap();
+    assert_eq!(inputs.risk_profile, Some(RiskProfile::Conservative));
+    assert_eq!(saved.config, conservative.config);
+    assert_eq!(
+        aggressive.inputs.as_ref().unwrap().risk_profile,
+        Some(RiskProfile::Aggressive)
+    );
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+// ── Ratchet diagnostics ──────────────────────────────────────────
+
+#[test]
+fn ratchet_clamps_reach_the_run_diagnostics() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(&cache_dir);
+    // ATR trailing asks for a looser stop whenever the close dips or ATR widens
+    let mut config = config_from_preset(StrategyPreset::MomentumRoc);
+    config.position_manager.component_type = "atr_trailing".into();
+    config.position_manager.params = [
+        ("atr_period".to_string(), 14.0),
+        ("multiplier".to_string(), 3.0),
+    ]
+    .into();
+    let result = run_single_backtest(&config, &cache, None, &load_opts()).unwrap();
+    assert!(!result.ratchet_clamps.is_empty());
+    for clamp in &result.ratchet_clamps {
+        assert_eq!(clamp.symbol, "SPY");
+        assert!(clamp.bar_index < result.bar_count);
+        assert_ne!(clamp.requested, clamp.applied);
+    }
+
+    let out_dir = cache_dir.join("results");
+    let run_dir = save_artifacts(&result, &out_dir).unwrap();
+    let diagnostics = load_diagnostics(&run_dir).unwrap();
+    assert_eq!(diagnostics.ratchet_clamps, result.ratchet_clamps);
+    let saved = load_artifacts(&run_dir).unwrap();
+    assert_eq!(saved.ratchet_clamps, result.ratchet_clamps);
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+// ── Date-window slicing ──────────────────────────────────────────
+
+fn momentum_result() -> (BacktestResult, PathBuf) {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(&cache_dir);
+    let config = config_from_preset(StrategyPreset::MomentumRoc);
+    let result = run_single_backtest(&config, &cache, None, &load_opts()).unwrap();
+    (result, cache_dir)
+}
+
+#[test]
+fn slice_over_the_whole_run_reproduces_it() {
+    let (result, cache_dir) = momentum_result();
+    assert_eq!(result.dates.len(), result.bar_count);
+
+    let whole = result
+        .slice(result.dates[0], *result.dates.last().unwrap())
+        .unwrap();
+    assert!(whole.is_full(&result));
+    assert!(whole.prorated.is_empty());
+    assert_eq!(whole.result.equity_curve, result.equity_curve);
+    assert_eq!(whole.result.trades.len(), result.trades.len());
+    assert_eq!(whole.result.warmup_bars, result.warmup_bars);
+    let (a, b) = (&whole.result.metrics, &result.metrics);
+    assert_eq!(a.trade_count, b.trade_count);
+    assert!((a.total_return - b.total_return).abs() < 1e-12);
+    assert!((a.sharpe - b.sharpe).abs() < 1e-12);
+    assert!((a.max_drawdown - b.max_drawdown).abs() < 1e-12);
+    let (ta, tb) = (
+        whole.result.tail_metrics.as_ref().unwrap(),
+        result.tail_metrics.as_ref().unwrap(),
+    );
+    assert_eq!(ta.cvar_95, tb.cvar_95);
+    assert_eq!(ta.worst_days, tb.worst_days);
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+#[test]
+fn slice_prorates_trades_spanning_the_boundary() {
+    let (result, cache_dir) = momentum_result();
+    let spanning = result
+        .trades
+        .iter()
+        .find(|t| t.bars_held >= 4)
+        .expect("a trade held at least 4 bars");
+    // Split the run on a bar inside the trade
+    let split = spanning.entry_bar + spanning.bars_held / 2;
+    let (start, end) = (result.dates[0], *result.dates.last().unwrap());
+    let before = result.slice(start, result.dates[split]).unwrap();
+    let after = result.slice(result.dates[split], end).unwrap();
+
+    assert_eq!(before.last_bar, split);
+    assert_eq!(after.first_bar, split);
+    assert_eq!(after.result.warmup_bars, 0);
+    assert_eq!(after.result.equity_curve.len(), result.bar_count - split);
+    assert_eq!(after.result.initial_capital, result.equity_curve[split]);
+
+    let find = |slice: &trendlab_runner::ResultSlice| {
+        slice
+            .prorated
+            .iter()
+            .find(|p| p.full_entry_bar == spanning.entry_bar)
+            .cloned()
+            .expect("boundary trade flagged")
+    };
+    let (head, tail) = (find(&before), find(&after));
+    assert!(head.cut_exit && !head.cut_entry);
+    assert!(tail.cut_entry && !tail.cut_exit);
+    assert!((head.share + tail.share - 1.0).abs() < 1e-12);
+
+    // The two halves add back up to the full trade, and the first half is
+    // open at the window's end
+    let head_trade = &before.result.trades[head.trade];
+    let tail_trade = &after.result.trades[tail.trade];
+    assert!(head_trade.liquidated_at_end);
+    assert_eq!(tail_trade.entry_bar, 0);
+    assert_eq!(tail_trade.entry_date, result.dates[split]);
+    assert!((head_trade.net_pnl + tail_trade.net_pnl - spanning.net_pnl).abs() < 1e-6);
+    assert!(before.result.metrics.is_finite() && after.result.metrics.is_finite());
+
+    // Every trade of the run is in exactly one half or split across both
+    let split_count = before.prorated.len().min(after.prorated.len());
+    assert_eq!(
+        before.result.trades.len() + after.result.trades.len(),
+        result.trades.len() + split_count
+    );
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+#[test]
+fn slice_windows_in_warmup_or_outside_the_run_are_rejected() {
+    use trendlab_runner::SliceError;
+
+    let (result, cache_dir) = momentum_result();
+    let warmup = result.warmup_bars;
+    assert!(warmup > 2);
+    let err = result
+        .slice(result.dates[0], result.dates[warmup - 1])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        SliceError::InWarmup {
+            from: result.dates[0],
+            to: result.dates[warmup - 1],
+            warmup_bars: warmup,
+            first_trading: result.dates[warmup],
+        }
+    );
+
+    // A window reaching one bar past warmup keeps the warmup it covers
+    let partial = result.slice(result.dates[1], result.dates[warmup]).unwrap();
+    assert_eq!(partial.result.warmup_bars, warmup - 1);
+    assert_eq!(partial.result.bar_count, warmup);
+    assert!(partial.result.trades.is_empty());
+    assert!(partial.result.zero_trades);
+
+    let day = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    assert!(matches!(
+        result.slice(day(2023, 1, 1), day(2023, 12, 31)),
+        Err(SliceError::OutOfRange { .. })
+    ));
+    // Only a weekend
+    assert!(matches!(
+        result.slice(day(2024, 6, 8), day(2024, 6, 9)),
+        Err(SliceError::OutOfRange { .. })
+    ));
+    assert!(matches!(
+        result.slice(day(2024, 6, 1), day(2024, 3, 1)),
+        Err(SliceError::Inverted { .. })
+    ));
+
+    // Results saved before per-bar dates were recorded
+    let mut old = result.clone();
+    old.dates.clear();
+    assert_eq!(
+        old.slice(day(2024, 6, 1), day(2024, 9, 1)).unwrap_err(),
+        SliceError::NoDates
+    );
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+// ── Arrow interop ────────────────────────────────────────────────
+
+#[test]
+fn arrow_files_round_trip_bars_and_trades() {
+    use polars::prelude::*;
+    use trendlab_core::domain::DecisionSource;
+    use trendlab_runner::interop::{
+        pnl_column, read_arrow, write_results_arrow, BAR_COLUMNS, RESULTS_ARROW, TRADES_ARROW,
+        TRADE_COLUMNS,
+    };
+
+    let (result, cache_dir) = momentum_result();
+    assert!(!result.trades.is_empty());
+    let run_dir = cache_dir.join("run");
+    std::fs::create_dir_all(&run_dir).unwrap();
+    write_results_arrow(&result, &run_dir).unwrap();
+
+    let days = |c: &Column| -> Vec<NaiveDate> {
+        c.date()
+            .unwrap()
+            .as_date_iter()
+            .map(Option::unwrap)
+            .collect()
+    };
+    let f64s = |c: &Column| -> Vec<f64> { c.f64().unwrap().into_no_null_iter().collect() };
+    let u64s = |c: &Column| -> Vec<usize> {
+        c.u64()
+            .unwrap()
+            .into_no_null_iter()
+            .map(|v| v as usize)
+            .collect()
+    };
+
+    // One row per bar; dates come back as Date32
+    let bars = read_arrow(&run_dir.join(RESULTS_ARROW)).unwrap();
+    let names: Vec<&str> = bars.get_column_names().iter().map(|n| n.as_str()).collect();
+    assert_eq!(names[..BAR_COLUMNS.len()], BAR_COLUMNS);
+    assert_eq!(bars.column("date").unwrap().dtype(), &DataType::Date);
+    assert_eq!(days(bars.column("date").unwrap()), result.dates);
+    assert_eq!(f64s(bars.column("equity").unwrap()), result.equity_curve);
+    assert_eq!(
+        f64s(bars.column("exposure").unwrap()),
+        result.exposure_curve
+    );
+    let attributed: f64 = DecisionSource::ALL
+        .iter()
+        .map(|&s| {
+            f64s(bars.column(&pnl_column(s)).unwrap())
+                .iter()
+                .sum::<f64>()
+        })
+        .sum();
+    assert!((attributed - result.attribution.total()).abs() < 1e-6);
+
+    // One row per trade, typed like the structs
+    let trades = read_arrow(&run_dir.join(TRADES_ARROW)).unwrap();
+    let names: Vec<&str> = trades
+        .get_column_names()
+        .iter()
+        .map(|n| n.as_str())
+        .collect();
+    assert_eq!(names, TRADE_COLUMNS);
+    let col = |name: &str| trades.column(name).unwrap();
+    let t = &result.trades;
+    assert_eq!(
+        u64s(col("entry_bar")),
+        t.iter().map(|t| t.entry_bar).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        days(col("exit_date")),
+        t.iter().map(|t| t.exit_date).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        f64s(col("net_pnl")),
+        t.iter().map(|t| t.net_pnl).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        u64s(col("bars_held")),
+        t.iter().map(|t| t.bars_held).collect::<Vec<_>>()
+    );
+    let exit_sources: Vec<Option<String>> = col("exit_source")
+        .str()
+        .unwrap()
+        .into_iter()
+        .map(|s| s.map(str::to_string))
+        .collect();
+    assert_eq!(
+        exit_sources,
+        t.iter()
+            .map(|t| t.exit_source.map(|s| s.to_string()))
+            .collect::<Vec<_>>()
+    );
+    let liquidated: Vec<bool> = col("liquidated_at_end")
+        .bool()
+        .unwrap()
+        .into_no_null_iter()
+        .collect();
+    assert_eq!(
+        liquidated,
+        t.iter().map(|t| t.liquidated_at_end).collect::<Vec<_>>()
+    );
+
+    // Files from an older writer without the version stamp are refused
+    let mut plain = trades.clone();
+    let path = run_dir.join("unstamped.arrow");
+    IpcWriter::new(std::fs::File::create(&path).unwrap())
+        .finish(&mut plain)
+        .unwrap();
+    assert!(read_arrow(&path).is_err());
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
@@ -0,0 +1,220 @@
+//! Capital-scaling invariance.
+//!
+//! Runs the same strategy on the frozen SPY 2024 fixture at 10k, 100k and
+//! 1M initial capital with fractional sizing. Return-based metrics, turnover
+//! and promotion outcomes must not depend on the account size; absolute PnL
+//! must scale linearly with it.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::NaiveDate;
+
+use trendlab_core::components::composition::StrategyPreset;
+use trendlab_core::components::execution::ExecutionPreset;
+use trendlab_core::data::cache::ParquetCache;
+use trendlab_core::domain::Quantization;
+use trendlab_core::fingerprint::TradingMode;
+
+use trendlab_runner::data_loader::{load_bars, LoadOptions};
+use trendlab_runner::fdr::FdrFamily;
+use trendlab_runner::promotion::{promote, PromotionConfig};
+use trendlab_runner::runner::{run_backtest_from_data, BacktestResult};
+use trendlab_runner::walk_forward::WalkForwardConfig;
+
+const CAPITALS: [f64; 3] = [10_000.0, 100_000.0, 1_000_000.0];
+
+static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+fn core_fixture_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join("trendlab-core/tests/fixtures")
+}
+
+fn setup_fixture_cache() -> PathBuf {
+    let id = TEST_COUNTER.fetch_add(1, Ordering::SeqCst);
+    let cache_dir = std::env::temp_dir().join(format!(
+        "trendlab_runner_capital_scaling_{}_{id}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&cache_dir);
+
+    let sym_dir = cache_dir.join("symbol=SPY");
+    std::fs::create_dir_all(&sym_dir).unwrap();
+    std::fs::copy(
+        core_fixture_dir().join("spy_2024.parquet"),
+        sym_dir.join("2024.parquet"),
+    )
+    .unwrap();
+
+    let meta = r#"{"symbol":"SPY","start_date":"2024-01-02","end_date":"2024-12-31","bar_count":252,"data_hash":"fixture","source":"fixture","cached_at":"2024-01-01T00:00:00"}"#;
+    std::fs::write(sym_dir.join("meta.json"), meta).unwrap();
+
+    cache_dir
+}
+
+fn load_opts() -> LoadOptions {
+    LoadOptions {
+        start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        end: NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+        offline: true,
+        synthetic: false,
+        force: false,
+        strict: false,
+        stale_after_days: None,
+        interval: Default::default(),
+    }
+}
+
+fn assert_close(label: &str, capital: f64, actual: f64, expected: f64) {
+    let tolerance = 1e-9 * expected.abs().max(1.0);
+    assert!(
+        (actual - expected).abs() <= tolerance,
+        "{label} at {capital}: {actual} vs {expected} at 100k"
+    );
+}
+
+#[test]
+fn return_metrics_and_promotion_are_invariant_to_initial_capital() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(cache_dir.clone());
+    let loaded = load_bars(&["SPY"], &cache, None, None, &load_opts()).unwrap();
+
+    // Whole-share rounding would make fills depend on account size
+    let mut strategy_config = StrategyPreset::MomentumRoc.to_config();
+    Quantization::Fractional.write_params(&mut strategy_config.execution_model.params);
+
+    let promo_config = PromotionConfig {
+        wf_sharpe_threshold: 0.0,
+        wf_config: WalkForwardConfig {
+            n_folds: 3,
+            min_total_bars: 100,
+            min_is_bars: 50,
+            min_oos_bars: 25,
+            ..Default::default()
+        },
+        ..PromotionConfig::default()
+    };
+
+    let runs: Vec<(f64, BacktestResult)> = CAPITALS
+        .iter()
+        .map(|&capital| {
+            let result = run_backtest_from_data(
+                &strategy_config,
+                &loaded.aligned,
+                "SPY",
+                TradingMode::LongOnly,
+                capital,
+                1.0,
+                ExecutionPreset::Realistic,
+                &loaded.dataset_hash,
+                false,
+            )
+            .expect("Backtest should succeed");
+            (capital, result)
+        })
+        .collect();
+
+    let (base_capital, base) = &runs[1];
+    assert!(
+        base.metrics.trade_count > 0,
+        "Fixture strategy must trade for the comparison to mean anything"
+    );
+
+    let base_promotion = promote(
+        base,
+        &strategy_config,
+        &loaded.aligned,
+        "SPY",
+        TradingMode::LongOnly,
+        *base_capital,
+        1.0,
+        ExecutionPreset::Realistic,
+        &loaded.dataset_hash,
+        &promo_config,
+        &mut FdrFamily::new(),
+    );
+
+    for (capital, run) in &runs {
+        let (m, b) = (&run.metrics, &base.metrics);
+        assert_eq!(m.trade_count, b.trade_count, "trade_count at {capital}");
+        assert_close("total_return", *capital, m.total_return, b.total_return);
+        assert_close("cagr", *capital, m.cagr, b.cagr);
+        assert_close("sharpe", *capital, m.sharpe, b.sharpe);
+        assert_close("sortino", *capital, m.sortino, b.sortino);
+        assert_close("max_drawdown", *capital, m.max_drawdown, b.max_drawdown);
+        assert_close("win_rate", *capital, m.win_rate, b.win_rate);
+        assert_close("profit_factor", *capital, m.profit_factor, b.profit_factor);
+        assert_close("turnover", *capital, m.turnover, b.turnover);
+
+        // Absolute PnL scales linearly with capital
+        let scale = capital / base_capital;
+        for (trade, base_trade) in run.trades.iter().zip(&base.trades) {
+            assert_eq!(trade.entry_bar, base_trade.entry_bar);
+            assert_close(
+                "net_pnl",
+                *capital,
+                trade.net_pnl / scale,
+                base_trade.net_pnl,
+            );
+        }
+        assert_close(
+            "final_equity",
+            *capital,
+            run.equity_curve.last().unwrap() / scale,
+            *base.equity_curve.last().unwrap(),
+        );
+
+        let promotion = promote(
+            run,
+            &strategy_config,
+            &loaded.aligned,
+            "SPY",
+            TradingMode::LongOnly,
+            *capital,
+            1.0,
+            ExecutionPreset::Realistic,
+            &loaded.dataset_hash,
+            &promo_config,
+            &mut FdrFamily::new(),
+        );
+        assert_eq!(
+            promotion.level_reached, base_promotion.level_reached,
+            "promotion level at {capital}"
+        );
+        assert_eq!(
+            format!("{:?}", promotion.gate_failure),
+            format!("{:?}", base_promotion.gate_failure),
+            "gate failure at {capital}"
+        );
+        match (&promotion.walk_forward, &base_promotion.walk_forward) {
+            (Some(wf), Some(base_wf)) => {
+                assert_close(
+                    "wf_oos_sharpe",
+                    *capital,
+                    wf.mean_oos_sharpe,
+                    base_wf.mean_oos_sharpe,
+                );
+            }
+            (None, None) => {}
+            _ => panic!("walk-forward ran at only one capital ({capital})"),
+        }
+        match (&promotion.bootstrap, &base_promotion.bootstrap) {
+            (Some(bs), Some(base_bs)) => {
+                assert_eq!(bs.grade, base_bs.grade, "bootstrap grade at {capital}");
+                assert_close(
+                    "bootstrap_median",
+                    *capital,
+                    bs.sharpe_median,
+                    base_bs.sharpe_median,
+                );
+            }
+            (None, None) => {}
+            _ => panic!("bootstrap ran at only one capital ({capital})"),
+        }
+    }
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
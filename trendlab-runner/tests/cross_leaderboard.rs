@@ -15,7 +15,9 @@ use trendlab_core::data::cache::ParquetCache;
 use trendlab_core::domain::FullHash;
 use trendlab_core::fingerprint::StrategyConfig;
 
-use trendlab_runner::cross_leaderboard::{CrossSymbolEntry, CrossSymbolLeaderboard};
+use trendlab_runner::cross_leaderboard::{
+    AggregatedStickiness, CrossSymbolEntry, CrossSymbolLeaderboard,
+};
 use trendlab_runner::data_loader::{LoadOptions, LoadedData};
 use trendlab_runner::history::{WriteFilter, YoloHistory};
 use trendlab_runner::metrics::PerformanceMetrics;
@@ -64,6 +66,9 @@ fn load_spy_data() -> LoadedData {
         offline: true,
         synthetic: false,
         force: false,
+        strict: false,
+        stale_after_days: None,
+        interval: Default::default(),
     };
     trendlab_runner::load_bars(&["SPY"], &cache, None, None, &opts).unwrap()
 }
@@ -101,6 +106,11 @@ fn make_metrics(sharpe: f64, cagr: f64, total_return: f64, max_dd: f64) -> Perfo
         max_consecutive_wins: 4,
         max_consecutive_losses: 3,
         avg_losing_streak: 1.5,
+        time_in_market: 0.0,
+        avg_exposure: 0.0,
+        invested_return: 0.0,
+        idle_return: 0.0,
+        exposure_adjusted_cagr: 0.0,
     }
 }
 
@@ -116,18 +126,22 @@ fn make_config(signal_type: &str, lookback: f64) -> StrategyConfig {
                 m.insert("lookback".into(), lookback);
                 m
             },
+            children: Vec::new(),
         },
         position_manager: ComponentConfig {
             component_type: "atr_trailing".into(),
             params: BTreeMap::new(),
+            children: Vec::new(),
         },
         execution_model: ComponentConfig {
             component_type: "next_bar_open".into(),
             params: BTreeMap::new(),
+            children: Vec::new(),
         },
         signal_filter: ComponentConfig {
             component_type: "no_filter".into(),
             params: BTreeMap::new(),
+            children: Vec::new(),
         },
     }
 }
@@ -304,7 +318,7 @@ fn yolo_writes_history_when_enabled() {
     let config = YoloConfig {
         history_path: Some(history_path.clone()),
         write_filter: WriteFilter::default(),
-        ..base_yolo_config(50)
+        ..base_yolo_config(100)
     };
 
     let result = run_yolo(&config, &data, &symbols, None, None).unwrap();
@@ -412,7 +426,7 @@ fn history_fingerprints_match_strategy_config() {
 
     let config = YoloConfig {
         history_path: Some(history_path.clone()),
-        ..base_yolo_config(30)
+        ..base_yolo_config(100)
     };
 
     let result = run_yolo(&config, &data, &symbols, None, None).unwrap();
@@ -690,3 +704,37 @@ fn yolo_no_history_when_path_is_none() {
     assert_eq!(result.history_entries_written, 0);
     assert_eq!(result.history_file_size_bytes, 0);
 }
+
+// ─── Test 11: Stickiness is reproducible from per-run reports ───────
+
+#[test]
+fn avg_stickiness_is_reproducible_from_run_reports() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let result = run_yolo(&base_yolo_config(30), &data, &symbols, None, None).unwrap();
+
+    let mut checked = 0;
+    for lb_entry in result.leaderboards["SPY"].entries() {
+        let report = lb_entry.result.stickiness_report();
+        let Some(run) = report.run.as_ref() else {
+            continue;
+        };
+        let hash = lb_entry.result.config.full_hash();
+        let entry = &result.cross_leaderboard.entries()[&hash];
+        assert_eq!(
+            entry.avg_stickiness,
+            AggregatedStickiness::from_runs(&[run]),
+            "cross-symbol stickiness of {}",
+            hash.as_hex()
+        );
+
+        // The run's exit trigger rate is its positions' PM calls summed
+        let calls = report.pm_calls();
+        if calls.total > 0 {
+            let rate = calls.active as f64 / calls.total as f64;
+            assert!((run.exit_trigger_rate - rate).abs() < 1e-12);
+        }
+        checked += 1;
+    }
+    assert!(checked > 0, "no leaderboard entry had completed trades");
+}
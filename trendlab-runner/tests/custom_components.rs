@@ -0,0 +1,208 @@
+//! Custom components registered from outside trendlab-core.
+//!
+//! Registers a trivial "close above its SMA" signal through
+//! `ComponentRegistry`, runs it end-to-end on the frozen SPY 2024 fixture,
+//! and checks that configs naming it hash distinctly and that an unregistered
+//! type fails with the registered types listed.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::Once;
+
+use chrono::NaiveDate;
+
+use trendlab_core::components::composition::StrategyPreset;
+use trendlab_core::components::execution::ExecutionPreset;
+use trendlab_core::components::factory::{create_signal, FactoryError};
+use trendlab_core::components::signal::{SignalDirection, SignalEvent, SignalGenerator};
+use trendlab_core::components::{ComponentRegistry, CustomSignal, IndicatorValues, ParamRange};
+use trendlab_core::data::cache::ParquetCache;
+use trendlab_core::domain::{BarSeries, SignalEventId};
+use trendlab_core::fingerprint::{ComponentConfig, TradingMode};
+use trendlab_core::indicators::Sma;
+
+use trendlab_runner::data_loader::{load_bars, LoadOptions};
+use trendlab_runner::runner::run_backtest_from_data;
+
+const CUSTOM_TYPE: &str = "close_above_sma";
+
+/// Long while the close is above SMA(window), short while below.
+struct CloseAboveSma {
+    window: usize,
+    key: String,
+}
+
+impl SignalGenerator for CloseAboveSma {
+    fn name(&self) -> &str {
+        CUSTOM_TYPE
+    }
+
+    fn warmup_bars(&self) -> usize {
+        self.window
+    }
+
+    fn evaluate(
+        &self,
+        bars: &dyn BarSeries,
+        bar_index: usize,
+        indicators: &IndicatorValues,
+    ) -> Option<SignalEvent> {
+        let sma = indicators.get(&self.key, bar_index)?;
+        let bar = bars.bar(bar_index);
+        if sma.is_nan() || bar.close.is_nan() {
+            return None;
+        }
+        let direction = if bar.close > sma {
+            SignalDirection::Long
+        } else {
+            SignalDirection::Short
+        };
+        Some(SignalEvent {
+            id: SignalEventId(0),
+            bar_index,
+            date: bar.date,
+            symbol: bar.symbol.to_string(),
+            direction,
+            strength: 1.0,
+            metadata: HashMap::new(),
+        })
+    }
+}
+
+fn window(config: &ComponentConfig) -> usize {
+    config.params.get("window").copied().unwrap_or(20.0) as usize
+}
+
+fn register_custom_signal() {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(|| {
+        let range = ParamRange {
+            name: "window".into(),
+            default: 20.0,
+            min: 5.0,
+            max: 60.0,
+        };
+        ComponentRegistry::register_signal(
+            CustomSignal::new(CUSTOM_TYPE, vec![range], |config| {
+                let window = window(config);
+                Box::new(CloseAboveSma {
+                    window,
+                    key: format!("sma_{window}"),
+                })
+            })
+            .with_indicators(|config| vec![Box::new(Sma::new(window(config)))]),
+        )
+        .expect("custom signal registers once");
+    });
+}
+
+fn custom_signal(window: f64) -> ComponentConfig {
+    ComponentConfig {
+        component_type: CUSTOM_TYPE.into(),
+        params: BTreeMap::from([("window".to_string(), window)]),
+        children: vec![],
+    }
+}
+
+fn setup_fixture_cache() -> PathBuf {
+    let fixture = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join("trendlab-core/tests/fixtures/spy_2024.parquet");
+    let cache_dir = std::env::temp_dir().join(format!(
+        "trendlab_runner_custom_components_{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&cache_dir);
+
+    let sym_dir = cache_dir.join("symbol=SPY");
+    std::fs::create_dir_all(&sym_dir).unwrap();
+    std::fs::copy(fixture, sym_dir.join("2024.parquet")).unwrap();
+
+    let meta = r#"{"symbol":"SPY","start_date":"2024-01-02","end_date":"2024-12-31","bar_count":252,"data_hash":"fixture","source":"fixture","cached_at":"2024-01-01T00:00:00"}"#;
+    std::fs::write(sym_dir.join("meta.json"), meta).unwrap();
+
+    cache_dir
+}
+
+fn load_opts() -> LoadOptions {
+    LoadOptions {
+        start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        end: NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+        offline: true,
+        synthetic: false,
+        force: false,
+        strict: false,
+        stale_after_days: None,
+        interval: Default::default(),
+    }
+}
+
+#[test]
+fn registered_signal_runs_end_to_end_and_hashes_distinctly() {
+    register_custom_signal();
+
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(&cache_dir);
+    let loaded = load_bars(&["SPY"], &cache, None, None, &load_opts()).unwrap();
+
+    let mut config = StrategyPreset::MomentumRoc.to_config();
+    let builtin_hash = config.full_hash();
+    config.signal = custom_signal(10.0);
+
+    let result = run_backtest_from_data(
+        &config,
+        &loaded.aligned,
+        "SPY",
+        TradingMode::LongOnly,
+        100_000.0,
+        1.0,
+        ExecutionPreset::Frictionless,
+        &loaded.dataset_hash,
+        false,
+    )
+    .expect("custom signal backtest should succeed");
+
+    assert!(result.signal_count > 0, "custom signal never fired");
+    assert!(
+        !result.trades.is_empty(),
+        "custom signal produced no trades"
+    );
+    assert_eq!(result.config.signal.component_type, CUSTOM_TYPE);
+
+    // The type name and its params enter both hashes.
+    assert_ne!(config.full_hash(), builtin_hash);
+    let mut other = config.clone();
+    other.signal = custom_signal(30.0);
+    assert_eq!(other.config_hash(), config.config_hash());
+    assert_ne!(other.full_hash(), config.full_hash());
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+#[test]
+fn unregistered_type_lists_registered_types() {
+    register_custom_signal();
+
+    let err = match create_signal(&ComponentConfig {
+        component_type: "not_registered_anywhere".into(),
+        params: BTreeMap::new(),
+        children: vec![],
+    }) {
+        Err(err) => err,
+        Ok(_) => panic!("unregistered signal type must not build"),
+    };
+    assert!(matches!(err, FactoryError::UnknownSignal { .. }));
+    let message = err.to_string();
+    assert!(message.contains("not_registered_anywhere"), "{message}");
+    assert!(message.contains("donchian_breakout"), "{message}");
+    assert!(message.contains(CUSTOM_TYPE), "{message}");
+
+    // Params are validated against the registered ranges.
+    let mut bad = custom_signal(10.0);
+    bad.params.insert("lookback".into(), 5.0);
+    assert!(matches!(
+        create_signal(&bad),
+        Err(FactoryError::UnknownParam { .. })
+    ));
+}
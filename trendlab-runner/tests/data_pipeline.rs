@@ -54,6 +54,9 @@ fn runner_loads_real_spy_from_fixture() {
         offline: true,
         synthetic: false,
         force: false,
+        strict: false,
+        stale_after_days: None,
+        interval: Default::default(),
     };
 
     let loaded = load_bars(&["SPY"], &cache, None, None, &opts).unwrap();
@@ -95,6 +98,9 @@ fn runner_trivial_backtest_on_real_data() {
         offline: true,
         synthetic: false,
         force: false,
+        strict: false,
+        stale_after_days: None,
+        interval: Default::default(),
     };
 
     let loaded = load_bars(&["SPY"], &cache, None, None, &opts).unwrap();
@@ -130,6 +136,9 @@ fn offline_no_cache_no_synthetic_fails_cleanly() {
         offline: true,
         synthetic: false,
         force: false,
+        strict: false,
+        stale_after_days: None,
+        interval: Default::default(),
     };
 
     let result = load_bars(&["NONEXISTENT"], &cache, None, None, &opts);
@@ -159,6 +168,9 @@ fn synthetic_fallback_tagged_not_real() {
         offline: false,
         synthetic: true,
         force: false,
+        strict: false,
+        stale_after_days: None,
+        interval: Default::default(),
     };
 
     let loaded = load_bars(&["FAKE_TICKER"], &cache, None, None, &opts).unwrap();
@@ -190,6 +202,9 @@ fn dataset_hash_changes_with_different_data() {
         offline: true,
         synthetic: false,
         force: false,
+        strict: false,
+        stale_after_days: None,
+        interval: Default::default(),
     };
 
     let loaded_real = load_bars(&["SPY"], &cache, None, None, &opts_real).unwrap();
@@ -208,6 +223,9 @@ fn dataset_hash_changes_with_different_data() {
         offline: false,
         synthetic: true,
         force: false,
+        strict: false,
+        stale_after_days: None,
+        interval: Default::default(),
     };
 
     let loaded_synth = load_bars(&["FAKE"], &cache2, None, None, &opts_synth).unwrap();
@@ -0,0 +1,202 @@
+//! Property tests for degenerate runs through metrics → leaderboard.
+//!
+//! Zero-trade, one-trade and all-winner runs must produce finite metrics,
+//! insert cleanly for every fitness metric, and serialize without NaN/Inf.
+
+use std::collections::BTreeMap;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use proptest::prelude::*;
+use trendlab_core::components::composition::StrategyPreset;
+use trendlab_core::domain::{PositionSide, TradeRecord};
+use trendlab_runner::fitness::FitnessMetric;
+use trendlab_runner::leaderboard::{
+    InsertResult, LeaderboardEntry, RejectReason, SymbolLeaderboard,
+};
+use trendlab_runner::metrics::PerformanceMetrics;
+use trendlab_runner::runner::BacktestResult;
+
+const INITIAL_CAPITAL: f64 = 100_000.0;
+
+const ALL_METRICS: [FitnessMetric; 7] = [
+    FitnessMetric::Sharpe,
+    FitnessMetric::Sortino,
+    FitnessMetric::Calmar,
+    FitnessMetric::Cagr,
+    FitnessMetric::WinRate,
+    FitnessMetric::ProfitFactor,
+    FitnessMetric::MaxDrawdown,
+];
+
+fn make_trade(exit_bar: usize, net_pnl: f64) -> TradeRecord {
+    let date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+    TradeRecord {
+        symbol: "SPY".into(),
+        side: PositionSide::Long,
+        entry_bar: exit_bar.saturating_sub(1),
+        entry_date: date,
+        entry_price: 100.0,
+        entry_trigger_margin: None,
+        exit_bar,
+        exit_date: date,
+        exit_price: 100.0 + net_pnl / 100.0,
+        quantity: 100.0,
+        gross_pnl: net_pnl,
+        commission: 0.0,
+        slippage: 0.0,
+        net_pnl,
+        bars_held: 1,
+        mae: 0.0,
+        mfe: 0.0,
+        initial_stop_price: None,
+        entry_source: None,
+        exit_source: None,
+        liquidated_at_end: false,
+        expiry_bar: None,
+        signal_id: None,
+        signal_type: None,
+        pm_type: None,
+        execution_model: None,
+        filter_type: None,
+    }
+}
+
+/// Flat equity with each trade's PnL booked on its exit bar.
+fn equity_curve(bars: usize, trades: &[TradeRecord]) -> Vec<f64> {
+    let mut equity = INITIAL_CAPITAL;
+    (0..bars)
+        .map(|bar| {
+            equity += trades
+                .iter()
+                .filter(|t| t.exit_bar == bar)
+                .map(|t| t.net_pnl)
+                .sum::<f64>();
+            equity
+        })
+        .collect()
+}
+
+fn make_entry(metrics: PerformanceMetrics, fitness_score: f64) -> LeaderboardEntry {
+    LeaderboardEntry {
+        result: BacktestResult {
+            schema_version: 1,
+            metrics,
+            open_position_adjusted: None,
+            trades: vec![],
+            equity_curve: vec![],
+            dates: Vec::new(),
+            exposure_curve: Vec::new(),
+            config: StrategyPreset::DonchianTrend.to_config(),
+            symbol: "SPY".into(),
+            interval: Default::default(),
+            start_date: "2024-01-02".into(),
+            end_date: "2024-12-31".into(),
+            initial_capital: INITIAL_CAPITAL,
+            dataset_hash: "test".into(),
+            has_synthetic: false,
+            signal_count: 0,
+            zero_trades: false,
+            bar_count: 0,
+            warmup_bars: 0,
+            warmup: None,
+            void_bar_rates: BTreeMap::new(),
+            data_quality_warnings: vec![],
+            stickiness: None,
+            position_stickiness: Vec::new(),
+            r_distribution: Default::default(),
+            trade_distributions: Default::default(),
+            tail_metrics: None,
+            truncated_range: None,
+            style: None,
+            timings: Default::default(),
+            replay: Default::default(),
+            portfolio_snapshots: Default::default(),
+            equity_sparkline: Vec::new(),
+            attribution: Default::default(),
+            ratchet_clamps: Vec::new(),
+            inputs: None,
+            consistency_warnings: Vec::new(),
+            data_vintage: Vec::new(),
+            provenance: None,
+        },
+        fitness_score,
+        iteration: 0,
+        session_id: "test".into(),
+        timestamp: NaiveDateTime::parse_from_str("2024-01-01 00:00:00", "%Y-%m-%d %H:%M:%S")
+            .unwrap(),
+        artifact_dir: None,
+        retest: None,
+        holdout: None,
+        fdr: None,
+    }
+}
+
+/// Bar count plus 0, 1 or several winning trades.
+fn arb_degenerate_run() -> impl Strategy<Value = (usize, Vec<TradeRecord>)> {
+    (1usize..300).prop_flat_map(|bars| {
+        let trade = (0..bars, 0.01..50_000.0_f64).prop_map(|(bar, pnl)| make_trade(bar, pnl));
+        (Just(bars), prop::collection::vec(trade, 0..4))
+    })
+}
+
+proptest! {
+    #[test]
+    fn degenerate_runs_have_finite_metrics((bars, trades) in arb_degenerate_run()) {
+        let curve = equity_curve(bars, &trades);
+        let metrics = PerformanceMetrics::compute(&curve, &trades, INITIAL_CAPITAL);
+        prop_assert!(metrics.is_finite(), "{metrics:?}");
+        prop_assert!(metrics.profit_factor <= 100.0);
+    }
+
+    #[test]
+    fn degenerate_runs_insert_for_every_metric((bars, trades) in arb_degenerate_run()) {
+        let curve = equity_curve(bars, &trades);
+        let metrics = PerformanceMetrics::compute(&curve, &trades, INITIAL_CAPITAL);
+        for metric in ALL_METRICS {
+            let fitness = metric.extract(&metrics);
+            let mut lb = SymbolLeaderboard::new("SPY".into(), 10, metric);
+            prop_assert_eq!(lb.insert(make_entry(metrics.clone(), fitness)), InsertResult::Inserted);
+        }
+    }
+
+    #[test]
+    fn degenerate_runs_serialize_without_non_finite((bars, trades) in arb_degenerate_run()) {
+        let curve = equity_curve(bars, &trades);
+        let metrics = PerformanceMetrics::compute(&curve, &trades, INITIAL_CAPITAL);
+        let json = serde_json::to_string(&metrics).unwrap();
+        prop_assert!(!json.contains("null"), "{json}");
+        let back: PerformanceMetrics = serde_json::from_str(&json).unwrap();
+        prop_assert!(back.is_finite());
+    }
+}
+
+#[test]
+fn all_winners_cap_profit_factor() {
+    let trades = vec![make_trade(3, 500.0), make_trade(7, 250.0)];
+    let metrics = PerformanceMetrics::compute(&equity_curve(10, &trades), &trades, INITIAL_CAPITAL);
+    assert_eq!(metrics.profit_factor, 100.0);
+    assert_eq!(metrics.win_rate, 1.0);
+    assert_eq!(metrics.sortino, 0.0);
+}
+
+#[test]
+fn non_finite_metrics_round_trip_as_missing() {
+    let mut metrics = PerformanceMetrics::compute(&[INITIAL_CAPITAL], &[], INITIAL_CAPITAL);
+    metrics.sharpe = f64::INFINITY;
+    metrics.profit_factor = f64::NAN;
+    let entry = make_entry(metrics, f64::NAN);
+
+    let json = serde_json::to_string(&entry).unwrap();
+    assert!(json.contains(r#""sharpe":null"#));
+    assert!(json.contains(r#""fitness_score":null"#));
+
+    let back: LeaderboardEntry = serde_json::from_str(&json).unwrap();
+    assert!(back.result.metrics.sharpe.is_nan());
+    assert!(back.fitness_score.is_nan());
+
+    let mut lb = SymbolLeaderboard::new("SPY".into(), 10, FitnessMetric::Sharpe);
+    assert_eq!(
+        lb.insert(back),
+        InsertResult::Rejected(RejectReason::NonFinite)
+    );
+}
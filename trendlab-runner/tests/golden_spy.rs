@@ -6,6 +6,7 @@
 //! - Exact equity curve values at specific bars
 //! - Exact trade list (entry/exit bars, PnL)
 //! - Exact performance metrics
+//! - PnL attribution consistent with the trade list
 //!
 //! Strategy: MomentumRoc (period=12, threshold=0%) with time_decay PM,
 //!           next_bar_open execution, volatility_filter.
@@ -18,6 +19,7 @@ use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 use trendlab_core::components::composition::StrategyPreset;
 use trendlab_core::data::cache::ParquetCache;
+use trendlab_core::domain::DecisionSource;
 use trendlab_runner::config::BacktestConfig;
 use trendlab_runner::data_loader::LoadOptions;
 use trendlab_runner::runner::run_single_backtest;
@@ -33,10 +35,8 @@ fn core_fixture_dir() -> PathBuf {
 
 fn setup_fixture_cache() -> PathBuf {
     let id = TEST_COUNTER.fetch_add(1, Ordering::SeqCst);
-    let cache_dir = std::env::temp_dir().join(format!(
-        "trendlab_golden_{}_{id}",
-        std::process::id()
-    ));
+    let cache_dir =
+        std::env::temp_dir().join(format!("trendlab_golden_{}_{id}", std::process::id()));
     let _ = std::fs::remove_dir_all(&cache_dir);
 
     let sym_dir = cache_dir.join("symbol=SPY");
@@ -60,6 +60,9 @@ fn load_opts() -> LoadOptions {
         offline: true,
         synthetic: false,
         force: false,
+        strict: false,
+        stale_after_days: None,
+        interval: Default::default(),
     }
 }
 
@@ -139,7 +142,14 @@ fn approx_eq(a: f64, b: f64, tolerance: f64) -> bool {
 #[test]
 fn golden_trade_count() {
     let result = run_golden_strategy();
-    assert_eq!(result.trades.len(), 8, "trade count changed");
+    assert_eq!(result.trades.len(), 9, "trade count changed");
+    // The last is the position still open on the final bar
+    let closed = result
+        .trades
+        .iter()
+        .filter(|t| !t.liquidated_at_end)
+        .count();
+    assert_eq!(closed, 8, "closed trade count changed");
 }
 
 #[test]
@@ -160,8 +170,8 @@ fn golden_final_equity() {
     let result = run_golden_strategy();
     let final_eq = *result.equity_curve.last().unwrap();
     assert!(
-        approx_eq(final_eq, 283396.707492, EPS),
-        "final equity changed: got {final_eq:.6}, expected 283396.707492"
+        approx_eq(final_eq, 94552.273532, EPS),
+        "final equity changed: got {final_eq:.6}, expected 94552.273532"
     );
 }
 
@@ -174,9 +184,9 @@ fn golden_equity_curve_checkpoints() {
         (10, 100000.0),
         (50, 105437.580408),
         (100, 107480.027785),
-        (150, 311739.331062),
+        (150, 103921.229011),
         (200, 92447.876096),
-        (251, 283396.707492),
+        (251, 94552.273532),
     ];
 
     for &(bar, expected) in checks {
@@ -194,41 +204,56 @@ fn golden_performance_metrics() {
     let m = &result.metrics;
 
     assert!(
-        approx_eq(m.total_return, 1.8339670749, EPS),
+        approx_eq(m.total_return, -0.0544772647, EPS),
         "total_return changed: {:.10}",
         m.total_return
     );
     assert!(
-        approx_eq(m.sharpe, 1.4550805886, 0.001),
+        approx_eq(m.sharpe, -0.3778415227, 0.001),
         "sharpe changed: {:.10}",
         m.sharpe
     );
     assert!(
-        approx_eq(m.sortino, 5.1240484664, 0.01),
+        approx_eq(m.sortino, -0.4982014797, 0.01),
         "sortino changed: {:.10}",
         m.sortino
     );
     assert!(
-        approx_eq(m.max_drawdown, -0.7185625354, 0.001),
+        approx_eq(m.max_drawdown, -0.2154429285, 0.001),
         "max_drawdown changed: {:.10}",
         m.max_drawdown
     );
     assert!(
-        approx_eq(m.win_rate, 0.625, EPS),
+        approx_eq(m.win_rate, 0.6666666667, EPS),
         "win_rate changed: {:.10}",
         m.win_rate
     );
     assert!(
-        approx_eq(m.profit_factor, 0.7107852113, 0.001),
+        approx_eq(m.profit_factor, 0.7299809397, 0.001),
         "profit_factor changed: {:.10}",
         m.profit_factor
     );
+
+    // Without the end-of-data trade: same curve, trade stats of the 8 exits
+    let adjusted = result.open_position_adjusted.as_ref().unwrap();
+    assert_eq!(adjusted.trade_count, 8);
+    assert_eq!(adjusted.sharpe, m.sharpe);
+    assert!(
+        approx_eq(adjusted.win_rate, 0.625, EPS),
+        "adjusted win_rate changed: {:.10}",
+        adjusted.win_rate
+    );
+    assert!(
+        approx_eq(adjusted.profit_factor, 0.7107852113, 0.001),
+        "adjusted profit_factor changed: {:.10}",
+        adjusted.profit_factor
+    );
 }
 
 #[test]
 fn golden_trade_details() {
     let result = run_golden_strategy();
-    assert_eq!(result.trades.len(), 8);
+    assert_eq!(result.trades.len(), 9);
 
     // Trade 0: Long entry_bar=15, exit_bar=68
     assert_eq!(result.trades[0].entry_bar, 15);
@@ -246,23 +271,51 @@ fn golden_trade_details() {
     assert_eq!(result.trades[7].exit_bar, 243);
     assert!(result.trades[7].net_pnl > 0.0, "trade 7 should be a winner");
 
+    // Trade 8: Long entry_bar=244, still open, marked at the last close
+    let open = &result.trades[8];
+    assert!(open.liquidated_at_end);
+    assert_eq!((open.entry_bar, open.exit_bar), (244, 251));
+    assert!(approx_eq(open.exit_price, 579.28, 0.01));
+    assert_eq!(open.exit_source, Some(DecisionSource::EndOfRunLiquidation));
+
     // Signal traceability: all trades should have component names
     for (i, t) in result.trades.iter().enumerate() {
-        assert!(
-            t.signal_type.is_some(),
-            "trade {i} missing signal_type"
-        );
-        assert!(
-            t.pm_type.is_some(),
-            "trade {i} missing pm_type"
-        );
+        assert!(t.signal_type.is_some(), "trade {i} missing signal_type");
+        assert!(t.pm_type.is_some(), "trade {i} missing pm_type");
         assert!(
             t.execution_model.is_some(),
             "trade {i} missing execution_model"
         );
-        assert!(
-            t.filter_type.is_some(),
-            "trade {i} missing filter_type"
-        );
+        assert!(t.filter_type.is_some(), "trade {i} missing filter_type");
     }
 }
+
+#[test]
+fn golden_attribution_matches_closed_trades() {
+    let result = run_golden_strategy();
+    let (open, closed_trades) = result.trades.split_last().unwrap();
+    let last_exit = closed_trades.last().unwrap().exit_bar;
+    let closed: f64 = result
+        .attribution
+        .bars
+        .iter()
+        .filter(|b| b.bar_index <= last_exit)
+        .flat_map(|b| b.pnl.values())
+        .sum();
+    let net: f64 = closed_trades.iter().map(|t| t.net_pnl).sum();
+    assert!(
+        approx_eq(closed, net, EPS),
+        "attribution through bar {last_exit} is {closed}, trades net {net}"
+    );
+
+    // time_decay exits through its stop; the position open at the end is
+    // attributed to end-of-run liquidation, like its end-of-data trade
+    let totals = result.attribution.totals();
+    assert!(closed_trades
+        .iter()
+        .all(|t| t.exit_source == Some(DecisionSource::PmStopExit)));
+    assert!(open.liquidated_at_end);
+    let all_net: f64 = result.trades.iter().map(|t| t.net_pnl).sum();
+    assert!(approx_eq(result.attribution.total(), all_net, EPS));
+    assert!(totals.contains_key(&DecisionSource::EndOfRunLiquidation));
+}
@@ -0,0 +1,182 @@
+//! Property tests: `PerformanceMetrics::compute` against the per-metric
+//! functions it replaces with a single pass.
+//!
+//! Every field matches exactly except Sharpe, whose variance is accumulated
+//! with running moments instead of a second pass over the returns.
+
+use chrono::NaiveDate;
+use proptest::prelude::*;
+use trendlab_core::domain::{PositionSide, TradeRecord};
+use trendlab_runner::metrics::{self, PerformanceMetrics};
+
+const PERIODS: [f64; 3] = [252.0, 52.0, 12.0];
+
+fn make_trade(net_pnl: f64, price: f64, quantity: f64) -> TradeRecord {
+    let date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+    TradeRecord {
+        symbol: "SPY".into(),
+        side: PositionSide::Long,
+        entry_bar: 0,
+        entry_date: date,
+        entry_price: price,
+        entry_trigger_margin: None,
+        exit_bar: 5,
+        exit_date: date,
+        exit_price: price + net_pnl / quantity,
+        quantity,
+        gross_pnl: net_pnl,
+        commission: 0.0,
+        slippage: 0.0,
+        net_pnl,
+        bars_held: 5,
+        mae: 0.0,
+        mfe: 0.0,
+        initial_stop_price: None,
+        entry_source: None,
+        exit_source: None,
+        liquidated_at_end: false,
+        expiry_bar: None,
+        signal_id: None,
+        signal_type: None,
+        pm_type: None,
+        execution_model: None,
+        filter_type: None,
+    }
+}
+
+/// The metrics assembled from the individual functions.
+fn reference(
+    curve: &[f64],
+    trades: &[TradeRecord],
+    capital: f64,
+    periods: f64,
+) -> PerformanceMetrics {
+    let finite = |v: f64| if v.is_finite() { v } else { 0.0 };
+    let days = curve.len();
+    PerformanceMetrics {
+        total_return: finite(metrics::total_return(curve)),
+        cagr: finite(metrics::cagr(curve, days, periods)),
+        sharpe: finite(metrics::sharpe_ratio(curve, 0.0, periods)),
+        sortino: finite(metrics::sortino_ratio(curve, 0.0, periods)),
+        calmar: finite(metrics::calmar_ratio(curve, days, periods)),
+        max_drawdown: finite(metrics::max_drawdown(curve)),
+        win_rate: metrics::win_rate(trades),
+        profit_factor: finite(metrics::profit_factor(trades)),
+        trade_count: trades.len(),
+        turnover: finite(metrics::turnover(
+            trades,
+            metrics::average_equity(curve).unwrap_or(capital),
+            days,
+            periods,
+        )),
+        max_consecutive_wins: metrics::max_consecutive_wins(trades),
+        max_consecutive_losses: metrics::max_consecutive_losses(trades),
+        avg_losing_streak: metrics::avg_losing_streak(trades),
+        time_in_market: 0.0,
+        avg_exposure: 0.0,
+        invested_return: 0.0,
+        idle_return: 0.0,
+        exposure_adjusted_cagr: 0.0,
+    }
+}
+
+/// Fields other than Sharpe that differ between `got` and `want`.
+fn mismatches(got: &PerformanceMetrics, want: &PerformanceMetrics) -> Vec<String> {
+    let floats = [
+        ("total_return", got.total_return, want.total_return),
+        ("cagr", got.cagr, want.cagr),
+        ("sortino", got.sortino, want.sortino),
+        ("calmar", got.calmar, want.calmar),
+        ("max_drawdown", got.max_drawdown, want.max_drawdown),
+        ("win_rate", got.win_rate, want.win_rate),
+        ("profit_factor", got.profit_factor, want.profit_factor),
+        ("turnover", got.turnover, want.turnover),
+        (
+            "avg_losing_streak",
+            got.avg_losing_streak,
+            want.avg_losing_streak,
+        ),
+    ];
+    let counts = [
+        ("trade_count", got.trade_count, want.trade_count),
+        (
+            "max_consecutive_wins",
+            got.max_consecutive_wins,
+            want.max_consecutive_wins,
+        ),
+        (
+            "max_consecutive_losses",
+            got.max_consecutive_losses,
+            want.max_consecutive_losses,
+        ),
+    ];
+    floats
+        .iter()
+        .filter(|(_, g, w)| g != w)
+        .map(|(name, g, w)| format!("{name}: {g} vs {w}"))
+        .chain(
+            counts
+                .iter()
+                .filter(|(_, g, w)| g != w)
+                .map(|(name, g, w)| format!("{name}: {g} vs {w}")),
+        )
+        .collect()
+}
+
+/// Random walks from 100k, with the odd flat stretch and wipe-out to zero.
+fn arb_curve() -> impl Strategy<Value = Vec<f64>> {
+    let step = prop_oneof![
+        8 => -0.05..0.05_f64,
+        1 => Just(0.0),
+        1 => Just(-1.0),
+    ];
+    prop::collection::vec(step, 0..400).prop_map(|steps| {
+        let mut equity = 100_000.0;
+        steps
+            .into_iter()
+            .map(|r| {
+                equity *= 1.0 + r;
+                equity
+            })
+            .collect()
+    })
+}
+
+fn arb_trades() -> impl Strategy<Value = Vec<TradeRecord>> {
+    let pnl = prop_oneof![
+        4 => -5_000.0..5_000.0_f64,
+        1 => Just(0.0),
+    ];
+    let trade = (pnl, 10.0..500.0_f64, 1.0..1_000.0_f64)
+        .prop_map(|(pnl, price, qty)| make_trade(pnl, price, qty));
+    prop::collection::vec(trade, 0..60)
+}
+
+proptest! {
+    #[test]
+    fn single_pass_matches_reference(
+        curve in arb_curve(),
+        trades in arb_trades(),
+        periods in prop::sample::select(PERIODS.to_vec()),
+    ) {
+        let got = PerformanceMetrics::compute_annualized(&curve, &trades, 100_000.0, periods);
+        let want = reference(&curve, &trades, 100_000.0, periods);
+
+        let tolerance = 1e-9 * want.sharpe.abs().max(1.0);
+        prop_assert!((got.sharpe - want.sharpe).abs() <= tolerance, "{} vs {}", got.sharpe, want.sharpe);
+        let diff = mismatches(&got, &want);
+        prop_assert!(diff.is_empty(), "{diff:?}");
+    }
+}
+
+#[test]
+fn flat_and_constant_growth_curves_match_reference() {
+    let flat = vec![100_000.0; 50];
+    let growth: Vec<f64> = (0..253).map(|i| 100_000.0 * 1.001f64.powi(i)).collect();
+    for curve in [flat, growth] {
+        let got = PerformanceMetrics::compute(&curve, &[], 100_000.0);
+        let want = reference(&curve, &[], 100_000.0, 252.0);
+        assert_eq!(mismatches(&got, &want), Vec::<String>::new());
+        assert_eq!(got.sharpe, want.sharpe);
+    }
+}
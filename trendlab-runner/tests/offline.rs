@@ -0,0 +1,437 @@
+//! Integration tests for offline mode.
+//!
+//! A provider that panics on any use is passed to every loading entry point
+//! with `offline` set, proving that no fetch (or availability probe) is ever
+//! attempted and that cache misses (and stale caches) are reported without
+//! touching the network.
+
+use chrono::NaiveDate;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use trendlab_core::data::cache::ParquetCache;
+use trendlab_core::data::provider::{DataError, DataProvider, DataSource, FetchResult};
+use trendlab_core::fingerprint::TradingMode;
+use trendlab_runner::config::BacktestConfig;
+use trendlab_runner::data_loader::{
+    load_bars, CacheMiss, LoadError, LoadOptions, DEFAULT_STALE_AFTER_DAYS,
+};
+use trendlab_runner::data_quality::AnomalyKind;
+use trendlab_runner::promotion::{PromotionConfig, PromotionLevel};
+use trendlab_runner::promotion_batch::{run_promotion_batch, BatchSettings, PromotionCandidate};
+use trendlab_runner::runner::{run_single_backtest, RunError};
+use trendlab_runner::yolo::{run_yolo_from_cache, YoloConfig, YoloError};
+
+static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Provider that fails the test if the loader ever consults it.
+struct PanickingProvider;
+
+impl DataProvider for PanickingProvider {
+    fn name(&self) -> &str {
+        "panicking"
+    }
+
+    fn fetch(
+        &self,
+        symbol: &str,
+        _start: NaiveDate,
+        _end: NaiveDate,
+    ) -> Result<FetchResult, DataError> {
+        panic!("offline load attempted to fetch {symbol}");
+    }
+
+    fn is_available(&self) -> bool {
+        panic!("offline load probed provider availability");
+    }
+}
+
+fn core_fixture_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join("trendlab-core/tests/fixtures")
+}
+
+/// Cache holding only the frozen SPY 2024 fixture.
+fn setup_fixture_cache() -> PathBuf {
+    let id = TEST_COUNTER.fetch_add(1, Ordering::SeqCst);
+    let cache_dir = std::env::temp_dir().join(format!(
+        "trendlab_runner_offline_{}_{id}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&cache_dir);
+
+    let sym_dir = cache_dir.join("symbol=SPY");
+    std::fs::create_dir_all(&sym_dir).unwrap();
+    std::fs::copy(
+        core_fixture_dir().join("spy_2024.parquet"),
+        sym_dir.join("2024.parquet"),
+    )
+    .unwrap();
+
+    let meta = r#"{"symbol":"SPY","start_date":"2024-01-02","end_date":"2024-12-31","bar_count":252,"data_hash":"fixture","source":"fixture","cached_at":"2024-01-01T00:00:00"}"#;
+    std::fs::write(sym_dir.join("meta.json"), meta).unwrap();
+
+    cache_dir
+}
+
+fn offline_opts(synthetic: bool) -> LoadOptions {
+    LoadOptions {
+        start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        end: NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+        offline: true,
+        synthetic,
+        force: false,
+        strict: false,
+        stale_after_days: None,
+        interval: Default::default(),
+    }
+}
+
+fn expect_misses(err: LoadError, symbols: &[&str]) -> Vec<CacheMiss> {
+    match err {
+        LoadError::OfflineCacheMiss { missing } => {
+            let got: Vec<&str> = missing.iter().map(|m| m.symbol.as_str()).collect();
+            assert_eq!(got, symbols);
+            missing
+        }
+        other => panic!("expected OfflineCacheMiss, got {other}"),
+    }
+}
+
+#[test]
+fn offline_cache_hit_never_touches_provider() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(&cache_dir);
+
+    let loaded = load_bars(
+        &["SPY"],
+        &cache,
+        Some(&PanickingProvider),
+        None,
+        &offline_opts(false),
+    )
+    .unwrap();
+    assert_eq!(loaded.sources["SPY"], DataSource::Cache);
+    assert!(!loaded.has_synthetic);
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+#[test]
+fn offline_miss_lists_every_missing_symbol_and_range() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(&cache_dir);
+    let opts = offline_opts(false);
+
+    let err = load_bars(
+        &["QQQ", "SPY", "IWM"],
+        &cache,
+        Some(&PanickingProvider),
+        None,
+        &opts,
+    )
+    .unwrap_err();
+    let missing = expect_misses(err, &["QQQ", "IWM"]);
+    for miss in &missing {
+        assert_eq!(miss.start, opts.start);
+        assert_eq!(miss.end, opts.end);
+    }
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+#[test]
+fn offline_synthetic_fallback_only_when_allowed() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(&cache_dir);
+
+    let loaded = load_bars(
+        &["SPY", "FAKE"],
+        &cache,
+        Some(&PanickingProvider),
+        None,
+        &offline_opts(true),
+    )
+    .unwrap();
+    assert_eq!(loaded.sources["SPY"], DataSource::Cache);
+    assert_eq!(loaded.sources["FAKE"], DataSource::Synthetic);
+    assert!(loaded.has_synthetic);
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+#[test]
+fn offline_single_backtest_never_touches_provider() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(&cache_dir);
+    let toml = |symbol: &str| {
+        format!(
+            r#"
+[backtest]
+symbol = "{symbol}"
+start_date = "2024-01-02"
+end_date = "2024-12-31"
+
+[signal]
+type = "donchian_breakout"
+params = {{ entry_lookback = 20.0 }}
+
+[position_manager]
+type = "atr_trailing"
+params = {{ atr_period = 14.0 }}
+
+[execution_model]
+type = "next_bar_open"
+
+[signal_filter]
+type = "no_filter"
+"#
+        )
+    };
+    let opts = offline_opts(false);
+
+    let config = BacktestConfig::from_toml(&toml("SPY")).unwrap();
+    run_single_backtest(&config, &cache, Some(&PanickingProvider), &opts).unwrap();
+
+    let config = BacktestConfig::from_toml(&toml("QQQ")).unwrap();
+    match run_single_backtest(&config, &cache, Some(&PanickingProvider), &opts) {
+        Err(RunError::DataLoad { symbol, source }) => {
+            assert_eq!(symbol, "QQQ");
+            expect_misses(source, &["QQQ"]);
+        }
+        other => panic!("expected offline cache miss, got {other:?}"),
+    }
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+#[test]
+fn offline_yolo_never_touches_provider() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(&cache_dir);
+    let opts = offline_opts(false);
+    let config = YoloConfig {
+        start_date: opts.start,
+        end_date: opts.end,
+        max_iterations: Some(3),
+        master_seed: 7,
+        ..YoloConfig::default()
+    };
+
+    let symbols = vec!["SPY".to_string()];
+    let result = run_yolo_from_cache(
+        &config,
+        &symbols,
+        &cache,
+        Some(&PanickingProvider),
+        &opts,
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(result.iterations_completed, 3);
+
+    let symbols = vec!["SPY".to_string(), "QQQ".to_string()];
+    let result = run_yolo_from_cache(
+        &config,
+        &symbols,
+        &cache,
+        Some(&PanickingProvider),
+        &opts,
+        None,
+        None,
+        None,
+    );
+    match result {
+        Err(YoloError::Load(err)) => {
+            expect_misses(err, &["QQQ"]);
+        }
+        Err(other) => panic!("expected offline cache miss, got {other}"),
+        Ok(_) => panic!("expected offline cache miss, got a result"),
+    }
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+#[test]
+fn offline_stale_cache_marks_result_truncated() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(&cache_dir);
+    let config = BacktestConfig::from_toml(
+        r#"
+[backtest]
+symbol = "SPY"
+start_date = "2024-01-02"
+end_date = "2025-03-31"
+
+[signal]
+type = "donchian_breakout"
+params = { entry_lookback = 20.0 }
+
+[position_manager]
+type = "atr_trailing"
+params = { atr_period = 14.0 }
+
+[execution_model]
+type = "next_bar_open"
+
+[signal_filter]
+type = "no_filter"
+"#,
+    )
+    .unwrap();
+    let opts = LoadOptions {
+        end: NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(),
+        stale_after_days: Some(DEFAULT_STALE_AFTER_DAYS),
+        ..offline_opts(false)
+    };
+
+    let result = run_single_backtest(&config, &cache, Some(&PanickingProvider), &opts).unwrap();
+    let truncated = result.truncated_range.expect("stale cache must be flagged");
+    assert_eq!(
+        truncated.to_string(),
+        "data ends 2024-12-31 (requested 2025-03-31)"
+    );
+    assert!(result
+        .data_quality_warnings
+        .iter()
+        .any(|w| w.kind == AnomalyKind::TruncatedRange));
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+#[test]
+fn offline_style_analysis_adds_csv_factor_to_buy_and_hold() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(&cache_dir);
+    let factor_path = cache_dir.join("momentum.csv");
+    let mut csv = String::from("date,return\n");
+    let mut date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+    for i in 0..365 {
+        csv.push_str(&format!("{date},{}\n", 0.01 * (i as f64 * 0.9).sin()));
+        date = date.succ_opt().unwrap();
+    }
+    std::fs::write(&factor_path, csv).unwrap();
+
+    let config = BacktestConfig::from_toml(&format!(
+        r#"
+[backtest]
+symbol = "SPY"
+start_date = "2024-01-02"
+end_date = "2024-12-31"
+
+[signal]
+type = "donchian_breakout"
+params = {{ entry_lookback = 20.0 }}
+
+[position_manager]
+type = "atr_trailing"
+params = {{ atr_period = 14.0 }}
+
+[execution_model]
+type = "next_bar_open"
+
+[style]
+factors = ["{}"]
+"#,
+        factor_path.display()
+    ))
+    .unwrap();
+
+    let result = run_single_backtest(
+        &config,
+        &cache,
+        Some(&PanickingProvider),
+        &offline_opts(false),
+    )
+    .unwrap();
+    let style = result.style.expect("style report attached");
+    let factors: Vec<&str> = style.betas.iter().map(|b| b.factor.as_str()).collect();
+    assert_eq!(factors, ["SPY", "momentum"]);
+    assert!(style.observations >= 200);
+    assert!((0.0..=1.0).contains(&style.r_squared));
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+#[test]
+fn offline_promotion_batch_shares_loads_and_reports_failures() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(&cache_dir);
+    let config = BacktestConfig::from_toml(
+        r#"
+[backtest]
+symbol = "SPY"
+start_date = "2024-01-02"
+end_date = "2024-12-31"
+
+[signal]
+type = "roc_momentum"
+params = { period = 12.0 }
+
+[position_manager]
+type = "atr_trailing"
+params = { atr_period = 14.0 }
+
+[execution_model]
+type = "next_bar_open"
+"#,
+    )
+    .unwrap()
+    .to_strategy_config();
+    let opts = offline_opts(false);
+    let candidate = |source: &str, symbol: &str| PromotionCandidate {
+        source: source.into(),
+        config: config.clone(),
+        symbol: symbol.into(),
+        start: opts.start,
+        end: opts.end,
+        trading_mode: None,
+        initial_capital: 100_000.0,
+        sharpe: 1.0,
+    };
+    let settings = BatchSettings {
+        promotion: PromotionConfig {
+            wf_sharpe_threshold: f64::NEG_INFINITY,
+            ..PromotionConfig::default()
+        },
+        trading_mode: TradingMode::LongOnly,
+        position_size_pct: 1.0,
+        jobs: 2,
+        robustness_cache: None,
+    };
+
+    let report = run_promotion_batch(
+        &[
+            candidate("a", "SPY"),
+            candidate("b", "SPY"),
+            candidate("c", "QQQ"),
+        ],
+        &cache,
+        Some(&PanickingProvider),
+        &opts,
+        &settings,
+    );
+    assert_eq!(report.entries.len(), 3);
+    for entry in &report.entries[..2] {
+        // A single year of bars is too short for walk-forward
+        assert_eq!(entry.level, Some(PromotionLevel::Level1CheapPass));
+        assert!(entry.sharpe.is_some());
+        assert!(entry
+            .failing_criterion
+            .as_deref()
+            .unwrap()
+            .starts_with("walk-forward error"));
+    }
+    assert_eq!(report.entries[0].sharpe, report.entries[1].sharpe);
+    assert_eq!(report.entries[2].level, None);
+    assert!(report.entries[2]
+        .failing_criterion
+        .as_deref()
+        .unwrap()
+        .contains("QQQ"));
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
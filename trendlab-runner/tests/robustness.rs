@@ -11,16 +11,24 @@ use chrono::NaiveDate;
 
 use trendlab_core::components::composition::StrategyPreset;
 use trendlab_core::components::execution::ExecutionPreset;
+use trendlab_core::components::sampler::{ComponentSlot, ParamGrid};
 use trendlab_core::data::cache::ParquetCache;
 use trendlab_core::fingerprint::TradingMode;
 
 use trendlab_runner::bootstrap::{stationary_block_bootstrap, BootstrapConfig};
 use trendlab_runner::data_loader::{load_bars, LoadOptions};
-use trendlab_runner::execution_mc::ExecutionMcConfig;
+use trendlab_runner::execution_mc::{
+    run_friction_ramp, run_trigger_margin_mc, ExecutionMcConfig, FrictionRampConfig,
+    TriggerMarginMcConfig,
+};
 use trendlab_runner::fdr::{benjamini_hochberg, FdrFamily};
-use trendlab_runner::promotion::{PromotionConfig, PromotionLevel};
+use trendlab_runner::param_sensitivity::SensitivityConfig;
+use trendlab_runner::promotion::{promote_cached, GateFailure, PromotionConfig, PromotionLevel};
+use trendlab_runner::robustness_cache::{CachedLevel, RobustnessCache};
 use trendlab_runner::runner::run_backtest_from_data;
+use trendlab_runner::shuffle_test::ShuffleTestConfig;
 use trendlab_runner::walk_forward::{run_walk_forward, WalkForwardConfig};
+use trendlab_runner::wfo::{WalkForwardOptimizer, WfoConfig};
 use trendlab_runner::yolo::{run_yolo, YoloConfig};
 
 static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
@@ -61,6 +69,9 @@ fn load_opts() -> LoadOptions {
         offline: true,
         synthetic: false,
         force: false,
+        strict: false,
+        stale_after_days: None,
+        interval: Default::default(),
     }
 }
 
@@ -107,6 +118,7 @@ fn walk_forward_with_relaxed_config() {
         min_total_bars: 100,
         min_is_bars: 50,
         min_oos_bars: 25,
+        ..Default::default()
     };
 
     let result = run_walk_forward(
@@ -132,6 +144,62 @@ fn walk_forward_with_relaxed_config() {
     assert!(wf.mean_oos_sharpe.is_finite());
 }
 
+#[test]
+fn walk_forward_optimizer_refits_each_fold() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(cache_dir.clone());
+    let loaded = load_bars(&["SPY"], &cache, None, None, &load_opts()).unwrap();
+
+    let base = StrategyPreset::DonchianTrend.to_config();
+    let grid = ParamGrid::new()
+        .with_axis(
+            ComponentSlot::Signal,
+            "entry_lookback",
+            vec![10.0, 20.0, 40.0],
+        )
+        .with_axis(ComponentSlot::PositionManager, "multiplier", vec![2.0, 3.0]);
+    let config = WfoConfig {
+        walk_forward: WalkForwardConfig {
+            n_folds: 3,
+            min_total_bars: 100,
+            min_is_bars: 75,
+            min_oos_bars: 25,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let optimizer = WalkForwardOptimizer::new(base, grid, config);
+    let result = optimizer
+        .run(
+            &loaded.aligned,
+            "SPY",
+            TradingMode::LongOnly,
+            100_000.0,
+            1.0,
+            ExecutionPreset::Realistic,
+            &loaded.dataset_hash,
+        )
+        .expect("WFO should succeed with relaxed config");
+
+    assert_eq!(result.grid_size, 6);
+    assert_eq!(result.folds.len(), 3);
+    assert_eq!(result.param_stability.len(), 2);
+    for stability in &result.param_stability {
+        assert_eq!(stability.chosen.len(), 3);
+        assert!((0.0..=0.5).contains(&stability.dispersion));
+    }
+    // 252 fixture bars: OOS chunk = (252 - 75) / 3 = 59 bars per fold
+    assert_eq!(result.oos_equity_curve.len(), 3 * 59);
+    assert!((result.oos_equity_curve[0] - 100_000.0).abs() < 1e-6);
+    for fold in &result.folds {
+        assert!(fold.is_fitness.is_finite());
+        assert!(fold.oos_sharpe.is_finite());
+    }
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
 // ── Execution MC on real data ──────────────────────────────────────────
 
 #[test]
@@ -146,11 +214,14 @@ fn execution_mc_produces_non_identical_samples() {
         n_samples: 20,
         slippage_range: (0.0, 30.0),
         commission_range: (0.0, 20.0),
+        commission_scale_range: (0.5, 2.0),
         path_policies: vec![
             trendlab_core::components::execution::PathPolicy::Deterministic,
             trendlab_core::components::execution::PathPolicy::WorstCase,
             trendlab_core::components::execution::PathPolicy::BestCase,
         ],
+        limit_fill_policies: Vec::new(),
+        volume_shock: None,
         seed: 42,
     };
 
@@ -231,7 +302,7 @@ fn fdr_family_accumulation_and_correction() {
     // Simulate 20 strategy tests with varying p-values
     for i in 0..20 {
         let p = match i {
-            0..=2 => 0.001 * (i + 1) as f64, // strongly significant
+            0..=2 => 0.001 * (i + 1) as f64,  // strongly significant
             3..=5 => 0.02 + 0.005 * i as f64, // borderline
             _ => 0.1 + 0.04 * i as f64,       // not significant
         };
@@ -246,7 +317,10 @@ fn fdr_family_accumulation_and_correction() {
     // Some should be significant, some not
     let significant_count = results.iter().filter(|r| r.significant).count();
     let not_significant = results.iter().filter(|r| !r.significant).count();
-    assert!(significant_count > 0, "Should have some significant results");
+    assert!(
+        significant_count > 0,
+        "Should have some significant results"
+    );
     assert!(
         not_significant > 0,
         "Should have some non-significant results"
@@ -324,6 +398,7 @@ fn promotion_gate_low_sharpe_stops_at_level1() {
             min_total_bars: 100,
             min_is_bars: 50,
             min_oos_bars: 25,
+            ..Default::default()
         },
         ..PromotionConfig::default()
     };
@@ -354,13 +429,98 @@ fn promotion_gate_low_sharpe_stops_at_level1() {
     assert!(robustness.gate_failure.is_some());
 }
 
+#[test]
+fn zero_trade_config_short_circuits_the_ladder() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(cache_dir.clone());
+    let loaded = load_bars(&["SPY"], &cache, None, None, &load_opts()).unwrap();
+
+    // A signal that never fires: the entry lookback is longer than the data
+    let mut strategy_config = StrategyPreset::DonchianTrend.to_config();
+    strategy_config
+        .signal
+        .params
+        .insert("entry_lookback".into(), 300.0);
+
+    let result = run_backtest_from_data(
+        &strategy_config,
+        &loaded.aligned,
+        "SPY",
+        TradingMode::LongOnly,
+        100_000.0,
+        1.0,
+        ExecutionPreset::Realistic,
+        &loaded.dataset_hash,
+        false,
+    )
+    .expect("Backtest should succeed");
+    assert!(result.zero_trades);
+    assert_eq!(result.metrics.sharpe, 0.0);
+    assert_eq!(result.metrics.total_return, 0.0);
+    assert!(result.metrics.profit_factor.is_finite());
+
+    let wf_config = WalkForwardConfig {
+        n_folds: 3,
+        min_total_bars: 100,
+        min_is_bars: 50,
+        min_oos_bars: 25,
+        ..Default::default()
+    };
+    let promo_config = PromotionConfig {
+        wf_sharpe_threshold: 0.0,
+        wf_config: wf_config.clone(),
+        ..PromotionConfig::default()
+    };
+    let mut fdr_family = FdrFamily::new();
+    let robustness = trendlab_runner::promotion::promote(
+        &result,
+        &strategy_config,
+        &loaded.aligned,
+        "SPY",
+        TradingMode::LongOnly,
+        100_000.0,
+        1.0,
+        ExecutionPreset::Realistic,
+        &loaded.dataset_hash,
+        &promo_config,
+        &mut fdr_family,
+    );
+    assert_eq!(robustness.level_reached, PromotionLevel::Level1CheapPass);
+    assert!(matches!(
+        robustness.gate_failure,
+        Some(GateFailure::ZeroTrades)
+    ));
+    assert!(robustness.walk_forward.is_none());
+    assert!(robustness.execution_mc.is_none());
+    assert!(robustness.bootstrap.is_none());
+
+    // Walk-forward run directly flags every empty test window
+    let wf = run_walk_forward(
+        &strategy_config,
+        &loaded.aligned,
+        "SPY",
+        &wf_config,
+        TradingMode::LongOnly,
+        100_000.0,
+        1.0,
+        ExecutionPreset::Realistic,
+        &loaded.dataset_hash,
+    )
+    .expect("Walk-forward should succeed with relaxed config");
+    assert!(wf.fold_results.iter().all(|f| f.oos_zero_trades));
+    assert_eq!(wf.zero_trade_folds(), 3);
+    assert!(wf.mean_oos_sharpe.is_finite());
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
 #[test]
 fn promotion_real_strategy_reaches_level2_or_beyond() {
     let cache_dir = setup_fixture_cache();
     let cache = ParquetCache::new(cache_dir.clone());
     let loaded = load_bars(&["SPY"], &cache, None, None, &load_opts()).unwrap();
 
-    let preset = StrategyPreset::DonchianTrend;
+    let preset = StrategyPreset::MomentumRoc;
     let strategy_config = preset.to_config();
 
     let result = run_backtest_from_data(
@@ -384,17 +544,28 @@ fn promotion_real_strategy_reaches_level2_or_beyond() {
             min_total_bars: 50,
             min_is_bars: 25,
             min_oos_bars: 15,
+            ..Default::default()
         },
         wf_degradation_threshold: -10.0, // always passes gate 2
         mc_config: ExecutionMcConfig {
             n_samples: 5,
             ..ExecutionMcConfig::default()
         },
+        trigger_margin_config: TriggerMarginMcConfig {
+            n_trials: 20,
+            ..TriggerMarginMcConfig::default()
+        },
+        max_borderline_profit_fraction: f64::INFINITY, // always passes borderline gate
         bootstrap_config: BootstrapConfig {
             n_resamples: 100,
             ..BootstrapConfig::default()
         },
         fdr_alpha: 0.05,
+        benchmark_metric: None,
+        benchmark_margin: 0.0,
+        benchmark_lower_drawdown: false,
+        shuffle_test: None,
+        sensitivity: None,
     };
 
     let mut fdr_family = FdrFamily::new();
@@ -421,6 +592,108 @@ fn promotion_real_strategy_reaches_level2_or_beyond() {
     assert!(robustness.walk_forward.is_some());
 }
 
+#[test]
+fn cached_promotion_reruns_no_monte_carlo() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(cache_dir.clone());
+    let loaded = load_bars(&["SPY"], &cache, None, None, &load_opts()).unwrap();
+    let strategy_config = StrategyPreset::MomentumRoc.to_config();
+    let result = run_backtest_from_data(
+        &strategy_config,
+        &loaded.aligned,
+        "SPY",
+        TradingMode::LongOnly,
+        100_000.0,
+        1.0,
+        ExecutionPreset::Realistic,
+        &loaded.dataset_hash,
+        false,
+    )
+    .unwrap();
+    let promo_config = PromotionConfig {
+        wf_sharpe_threshold: -10.0,
+        wf_config: WalkForwardConfig {
+            n_folds: 2,
+            min_total_bars: 50,
+            min_is_bars: 25,
+            min_oos_bars: 15,
+            ..Default::default()
+        },
+        wf_degradation_threshold: -10.0,
+        mc_config: ExecutionMcConfig {
+            n_samples: 5,
+            ..ExecutionMcConfig::default()
+        },
+        trigger_margin_config: TriggerMarginMcConfig {
+            n_trials: 20,
+            ..TriggerMarginMcConfig::default()
+        },
+        max_borderline_profit_fraction: f64::INFINITY,
+        bootstrap_config: BootstrapConfig {
+            n_resamples: 100,
+            ..BootstrapConfig::default()
+        },
+        fdr_alpha: 0.05,
+        benchmark_metric: None,
+        benchmark_margin: 0.0,
+        benchmark_lower_drawdown: false,
+        shuffle_test: Some(ShuffleTestConfig {
+            n_variants: 10,
+            ..ShuffleTestConfig::default()
+        }),
+        sensitivity: Some(SensitivityConfig::default()),
+    };
+
+    let results_dir = tempfile::tempdir().unwrap();
+    let ladder = |promo_config: &PromotionConfig| {
+        let robustness_cache = RobustnessCache::in_results_dir(results_dir.path());
+        let (robustness, from_cache) = promote_cached(
+            &result,
+            &strategy_config,
+            &loaded.aligned,
+            "SPY",
+            TradingMode::LongOnly,
+            100_000.0,
+            1.0,
+            ExecutionPreset::Realistic,
+            &loaded.dataset_hash,
+            promo_config,
+            &mut FdrFamily::new(),
+            &robustness_cache,
+        );
+        (robustness, from_cache, robustness_cache.stats())
+    };
+
+    let (first, first_cached, first_stats) = ladder(&promo_config);
+    assert!(first_cached.is_empty());
+    assert_eq!(first_stats.hits, 0);
+    assert_eq!(first.level_reached, PromotionLevel::Level3ExecutionMc);
+    assert_eq!(first_stats.misses, CachedLevel::ALL.len());
+
+    // Every level the first run computed is reused: no walk-forward
+    // backtests, no MC trials, no bootstrap resamples
+    let (second, second_cached, second_stats) = ladder(&promo_config);
+    assert_eq!(second_stats.misses, 0);
+    assert_eq!(second_stats.hits, CachedLevel::ALL.len());
+    assert_eq!(second_cached, CachedLevel::ALL);
+    assert_eq!(
+        serde_json::to_string(&second).unwrap(),
+        serde_json::to_string(&first).unwrap()
+    );
+
+    // Changing one level's parameters recomputes that level only
+    let changed = PromotionConfig {
+        wf_config: WalkForwardConfig {
+            n_folds: 3,
+            ..promo_config.wf_config.clone()
+        },
+        ..promo_config.clone()
+    };
+    let (_, third_cached, third_stats) = ladder(&changed);
+    assert!(!third_cached.contains(&CachedLevel::WalkForward));
+    assert!(third_stats.misses >= 1);
+}
+
 // ── Stickiness Integration ─────────────────────────────────────────────
 
 #[test]
@@ -476,24 +749,34 @@ fn yolo_with_promotion_tracks_counts() {
                 min_total_bars: 50,
                 min_is_bars: 25,
                 min_oos_bars: 15,
+                ..Default::default()
             },
             wf_degradation_threshold: -10.0, // always pass WF gate
             mc_config: ExecutionMcConfig {
                 n_samples: 3,
                 ..ExecutionMcConfig::default()
             },
+            trigger_margin_config: TriggerMarginMcConfig {
+                n_trials: 10,
+                ..TriggerMarginMcConfig::default()
+            },
+            max_borderline_profit_fraction: f64::INFINITY,
             bootstrap_config: BootstrapConfig {
                 n_resamples: 50,
                 ..BootstrapConfig::default()
             },
             fdr_alpha: 0.05,
+            benchmark_metric: None,
+            benchmark_margin: 0.0,
+            benchmark_lower_drawdown: false,
+            shuffle_test: None,
+            sensitivity: None,
         }),
         ..YoloConfig::default()
     };
 
     let symbols = vec!["SPY".to_string()];
-    let result = run_yolo(&config, &loaded, &symbols, None, None)
-        .expect("YOLO should succeed");
+    let result = run_yolo(&config, &loaded, &symbols, None, None).expect("YOLO should succeed");
 
     assert_eq!(result.iterations_completed, 10);
     assert!(result.success_count > 0);
@@ -537,3 +820,108 @@ fn execution_mc_stability_ratio_is_finite_on_real_data() {
     assert!(result.stability.iqr_sharpe >= 0.0, "IQR cannot be negative");
     assert!(result.stability.p10_sharpe <= result.stability.median_sharpe);
 }
+
+// ── Trigger-Margin MC ──────────────────────────────────────────────────
+
+#[test]
+fn trigger_margin_mc_drops_borderline_stop_entries() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(cache_dir.clone());
+    let loaded = load_bars(&["SPY"], &cache, None, None, &load_opts()).unwrap();
+
+    // Breakout entries on stop orders: every entry records a trigger margin
+    let mut strategy_config = StrategyPreset::BollingerBreakout.to_config();
+    strategy_config.execution_model.component_type = "stop_entry".into();
+    let result = run_backtest_from_data(
+        &strategy_config,
+        &loaded.aligned,
+        "SPY",
+        TradingMode::LongOnly,
+        100_000.0,
+        1.0,
+        ExecutionPreset::Realistic,
+        &loaded.dataset_hash,
+        false,
+    )
+    .expect("Backtest should succeed");
+    assert!(!result.trades.is_empty());
+    assert!(result
+        .trades
+        .iter()
+        .all(|t| t.entry_trigger_margin.is_some_and(|m| m >= 0.0)));
+
+    let closes: Vec<f64> = loaded.aligned.bars["SPY"].iter().map(|b| b.close).collect();
+
+    // An epsilon above every margin makes every entry borderline
+    let config = TriggerMarginMcConfig {
+        epsilon: 1.0,
+        n_trials: 30,
+        seed: 7,
+    };
+    let mc = run_trigger_margin_mc(&result, &closes, &config).unwrap();
+    assert_eq!(mc.trade_count, result.trades.len());
+    assert_eq!(mc.borderline_count, result.trades.len());
+    assert_eq!(mc.trials.len(), 30);
+    assert!(mc.trials.iter().any(|t| t.dropped > 0));
+    for trial in &mc.trials {
+        assert_eq!(trial.trade_count + trial.dropped, mc.trade_count);
+        assert!(trial.sharpe.is_finite());
+    }
+
+    // A zero epsilon leaves every trial identical to the baseline
+    let none = TriggerMarginMcConfig {
+        epsilon: 0.0,
+        ..config
+    };
+    let mc = run_trigger_margin_mc(&result, &closes, &none).unwrap();
+    assert_eq!(mc.borderline_count, 0);
+    assert!(mc
+        .trials
+        .iter()
+        .all(|t| (t.sharpe - result.metrics.sharpe).abs() < 1e-9));
+}
+
+// ── Friction Ramp ──────────────────────────────────────────────────────
+
+#[test]
+fn friction_ramp_sharpe_falls_with_slippage() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(cache_dir.clone());
+    let loaded = load_bars(&["SPY"], &cache, None, None, &load_opts()).unwrap();
+
+    let strategy_config = StrategyPreset::MomentumRoc.to_config();
+    let friction = run_friction_ramp(
+        &strategy_config,
+        &loaded.aligned,
+        "SPY",
+        &FrictionRampConfig::default(),
+        TradingMode::LongOnly,
+        100_000.0,
+        1.0,
+        &loaded.dataset_hash,
+    )
+    .expect("friction ramp should succeed");
+
+    assert_eq!(friction.points.len(), 11);
+    assert_eq!(friction.preset_slippage_bps, 5.0);
+    assert!(
+        friction
+            .points
+            .windows(2)
+            .all(|w| w[1].sharpe < w[0].sharpe),
+        "Sharpe should fall at every step of the ramp"
+    );
+    assert!(friction.points[0].sharpe > 0.0);
+    assert!(friction.fit_slope < 0.0);
+    assert!(friction.slope_at_preset < 0.0);
+
+    // The ramp crosses zero, so break-even lies between two ramp points
+    let break_even = friction.break_even_bps.expect("Sharpe crosses zero");
+    let crossing = friction
+        .points
+        .iter()
+        .position(|p| p.sharpe <= 0.0)
+        .unwrap();
+    assert!(break_even > friction.points[crossing - 1].slippage_bps);
+    assert!(break_even <= friction.points[crossing].slippage_bps);
+}
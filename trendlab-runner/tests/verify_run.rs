@@ -0,0 +1,364 @@
+//! Integration tests for `verify_run`: re-running saved manifests against the
+//! frozen SPY 2024 cache, and checking them as of their data vintage. Also
+//! `run_modified`, which re-runs a result with edited params the same way.
+
+use chrono::NaiveDate;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use trendlab_core::components::composition::StrategyPreset;
+use trendlab_core::components::execution::ExecutionPreset;
+use trendlab_core::data::cache::ParquetCache;
+use trendlab_core::data::provider::RawBar;
+use trendlab_core::fingerprint::TradingMode;
+use trendlab_runner::config::ConfigError;
+use trendlab_runner::data_loader::{load_bars, LoadOptions};
+use trendlab_runner::export::write_artifacts;
+use trendlab_runner::runner::{run_backtest_from_data, run_modified, BacktestResult, RunError};
+use trendlab_runner::verify::{
+    compare_results, verify_run, verify_run_as_of, verify_run_dir, Divergence, VerifyError,
+    DEFAULT_TOLERANCE,
+};
+use trendlab_runner::vintage::{snapshot_partitions, SnapshotError, SNAPSHOT_DIR};
+
+static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+fn core_fixture_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join("trendlab-core/tests/fixtures")
+}
+
+fn temp_dir(label: &str) -> PathBuf {
+    let id = TEST_COUNTER.fetch_add(1, Ordering::SeqCst);
+    let dir = std::env::temp_dir().join(format!(
+        "trendlab_verify_{label}_{}_{id}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+/// Cache holding only the frozen SPY 2024 fixture.
+fn setup_fixture_cache() -> PathBuf {
+    setup_fixture_cache_with(&["SPY"])
+}
+
+/// Cache holding the frozen SPY 2024 fixture under each of `symbols`.
+fn setup_fixture_cache_with(symbols: &[&str]) -> PathBuf {
+    let cache_dir = temp_dir("cache");
+    for symbol in symbols {
+        let sym_dir = cache_dir.join(format!("symbol={symbol}"));
+        std::fs::create_dir_all(&sym_dir).unwrap();
+        std::fs::copy(
+            core_fixture_dir().join("spy_2024.parquet"),
+            sym_dir.join("2024.parquet"),
+        )
+        .unwrap();
+
+        let meta = format!(
+            r#"{{"symbol":"{symbol}","start_date":"2024-01-02","end_date":"2024-12-31","bar_count":252,"data_hash":"fixture","source":"fixture","cached_at":"2024-01-01T00:00:00"}}"#
+        );
+        std::fs::write(sym_dir.join("meta.json"), meta).unwrap();
+    }
+
+    cache_dir
+}
+
+/// The golden-test strategy (MomentumRoc, long/short), run as YOLO mode does.
+fn saved_run(cache: &ParquetCache) -> BacktestResult {
+    saved_universe_run(cache, &["SPY"])
+}
+
+/// [`saved_run`] on SPY with `symbols` loaded alongside, recording the
+/// vintage of the partitions it read.
+fn saved_universe_run(cache: &ParquetCache, symbols: &[&str]) -> BacktestResult {
+    let opts = LoadOptions {
+        start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        end: NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+        offline: true,
+        synthetic: false,
+        force: false,
+        strict: false,
+        stale_after_days: None,
+        interval: Default::default(),
+    };
+    let loaded = load_bars(symbols, cache, None, None, &opts).unwrap();
+    let mut result = run_backtest_from_data(
+        &StrategyPreset::MomentumRoc.to_config(),
+        &loaded.aligned,
+        "SPY",
+        TradingMode::LongShort,
+        100_000.0,
+        1.0,
+        ExecutionPreset::Realistic,
+        &loaded.dataset_hash,
+        loaded.has_synthetic,
+    )
+    .unwrap();
+    assert!(result.trades.len() >= 2, "fixture run should trade");
+    result.data_vintage = loaded.vintage;
+    result
+}
+
+/// Rewrite `symbol`'s bar on `date` with its close moved by `delta`, or
+/// append a copy of the last bar on `date` when there is none.
+fn mutate_cache(cache: &ParquetCache, symbol: &str, date: NaiveDate, delta: f64) {
+    let bars = cache.load(symbol).unwrap();
+    let mut bar: RawBar = bars
+        .iter()
+        .find(|b| b.date == date)
+        .unwrap_or_else(|| bars.last().unwrap())
+        .clone();
+    bar.date = date;
+    bar.close += delta;
+    bar.high = bar.high.max(bar.close);
+    cache.write(symbol, &[bar]).unwrap();
+}
+
+#[test]
+fn saved_manifest_reproduces() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(&cache_dir);
+    let result = saved_run(&cache);
+    let inputs = result.inputs.as_ref().expect("runs record their inputs");
+    assert_eq!(inputs.symbols, vec!["SPY".to_string()]);
+    assert_eq!(inputs.trading_mode, TradingMode::LongShort);
+
+    let run_dir = temp_dir("run");
+    write_artifacts(&result, &run_dir).unwrap();
+    let report = verify_run_dir(&run_dir, &cache, DEFAULT_TOLERANCE, true).unwrap();
+    assert!(report.is_reproduced(), "{:?}", report.divergence);
+    assert_eq!(report.trade_count, result.trades.len());
+
+    let _ = std::fs::remove_dir_all(&run_dir);
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+#[test]
+fn first_divergent_trade_is_reported() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(&cache_dir);
+    let mut result = saved_run(&cache);
+
+    result.trades[1].net_pnl += 1.0;
+    let expected_bar = result.trades[1].entry_bar;
+    match verify_run(&result, &cache, DEFAULT_TOLERANCE)
+        .unwrap()
+        .divergence
+    {
+        Some(Divergence::Trade {
+            index, bar, field, ..
+        }) => {
+            assert_eq!(index, 1);
+            assert_eq!(bar, expected_bar);
+            assert_eq!(field, "net_pnl");
+        }
+        other => panic!("expected a trade divergence, got {other:?}"),
+    }
+    // A looser tolerance accepts the same difference
+    assert!(verify_run(&result, &cache, 1.0).unwrap().is_reproduced());
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+#[test]
+fn missing_trades_are_a_count_divergence() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(&cache_dir);
+    let mut result = saved_run(&cache);
+
+    let last = result.trades.pop().unwrap();
+    let saved = result.trades.len();
+    match verify_run(&result, &cache, DEFAULT_TOLERANCE)
+        .unwrap()
+        .divergence
+    {
+        Some(Divergence::TradeCount {
+            saved: s,
+            rerun,
+            bar,
+        }) => {
+            assert_eq!(s, saved);
+            assert_eq!(rerun, saved + 1);
+            assert_eq!(bar, last.entry_bar);
+        }
+        other => panic!("expected a trade count divergence, got {other:?}"),
+    }
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+#[test]
+fn changed_dataset_fails_before_rerunning() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(&cache_dir);
+    let mut result = saved_run(&cache);
+
+    result.dataset_hash = "0".repeat(64);
+    match verify_run(&result, &cache, DEFAULT_TOLERANCE) {
+        Err(err @ VerifyError::DatasetMismatch { .. }) => {
+            let msg = err.to_string();
+            assert!(msg.contains("SPY"), "{msg}");
+            assert!(msg.contains("2024-01-02..2024-12-31"), "{msg}");
+        }
+        other => panic!("expected a dataset mismatch, got {other:?}"),
+    }
+
+    result.inputs = None;
+    assert!(matches!(
+        verify_run(&result, &cache, DEFAULT_TOLERANCE),
+        Err(VerifyError::MissingInputs)
+    ));
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+#[test]
+fn cache_mutation_is_reported_per_symbol() {
+    let cache_dir = setup_fixture_cache_with(&["SPY", "QQQ"]);
+    let cache = ParquetCache::new(&cache_dir);
+    let result = saved_universe_run(&cache, &["SPY", "QQQ"]);
+    let recorded: Vec<&str> = result
+        .data_vintage
+        .iter()
+        .map(|v| v.symbol.as_str())
+        .collect();
+    assert_eq!(recorded, ["SPY", "QQQ"]);
+    assert!(result.data_vintage.iter().all(|v| v.cached_at.is_some()));
+    assert!(verify_run_as_of(&result, &cache, DEFAULT_TOLERANCE)
+        .unwrap()
+        .is_reproduced());
+
+    // QQQ grows a bar past the run's range: the dataset hash still matches
+    mutate_cache(
+        &cache,
+        "QQQ",
+        NaiveDate::from_ymd_opt(2025, 1, 2).unwrap(),
+        1.0,
+    );
+    assert!(verify_run(&result, &cache, DEFAULT_TOLERANCE)
+        .unwrap()
+        .is_reproduced());
+    match verify_run_as_of(&result, &cache, DEFAULT_TOLERANCE) {
+        Err(err @ VerifyError::VintageMismatch { .. }) => {
+            let VerifyError::VintageMismatch { changes } = &err else {
+                unreachable!()
+            };
+            assert_eq!(changes.len(), 1);
+            assert_eq!(changes[0].symbol(), "QQQ");
+            let current = changes[0].current.as_ref().unwrap();
+            assert!(current.cached_at > changes[0].recorded.cached_at);
+            let msg = err.to_string();
+            assert!(msg.contains("QQQ: content changed"), "{msg}");
+            assert!(!msg.contains("SPY"), "{msg}");
+        }
+        other => panic!("expected a vintage mismatch, got {other:?}"),
+    }
+
+    // Removing a partition is reported too
+    std::fs::remove_dir_all(cache_dir.join("symbol=SPY")).unwrap();
+    match verify_run_as_of(&result, &cache, DEFAULT_TOLERANCE) {
+        Err(err @ VerifyError::VintageMismatch { .. }) => {
+            let msg = err.to_string();
+            assert!(msg.contains("SPY: no longer cached"), "{msg}");
+            assert!(msg.contains("QQQ: content changed"), "{msg}");
+        }
+        other => panic!("expected a vintage mismatch, got {other:?}"),
+    }
+
+    let mut unrecorded = result;
+    unrecorded.data_vintage.clear();
+    assert!(matches!(
+        verify_run_as_of(&unrecorded, &cache, DEFAULT_TOLERANCE),
+        Err(VerifyError::MissingVintage)
+    ));
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+#[test]
+fn snapshot_keeps_the_run_verifiable_after_the_cache_changes() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(&cache_dir);
+    let result = saved_run(&cache);
+    let run_dir = temp_dir("snapshot_run");
+    write_artifacts(&result, &run_dir).unwrap();
+
+    assert!(matches!(
+        snapshot_partitions(&result.data_vintage, &cache, &run_dir, Some(1)),
+        Err(SnapshotError::TooLarge { max: 1, .. })
+    ));
+    assert!(!run_dir.join(SNAPSHOT_DIR).exists());
+
+    let report = snapshot_partitions(&result.data_vintage, &cache, &run_dir, None).unwrap();
+    assert_eq!(report.partitions, 1);
+    assert_eq!(report.files, 2, "the year file and meta.json");
+    assert!(run_dir
+        .join(SNAPSHOT_DIR)
+        .join("symbol=SPY/meta.json")
+        .exists());
+
+    // A revised close inside the range breaks the shared cache...
+    mutate_cache(
+        &cache,
+        "SPY",
+        NaiveDate::from_ymd_opt(2024, 6, 3).unwrap(),
+        5.0,
+    );
+    match verify_run_dir(&run_dir, &cache, DEFAULT_TOLERANCE, true) {
+        Err(err @ VerifyError::VintageMismatch { .. }) => {
+            assert!(err.to_string().contains("SPY: content changed"), "{err}");
+        }
+        other => panic!("expected a vintage mismatch, got {other:?}"),
+    }
+    assert!(matches!(
+        snapshot_partitions(&result.data_vintage, &cache, &temp_dir("late"), None),
+        Err(SnapshotError::Changed { .. })
+    ));
+
+    // ...but not the snapshot
+    let snapshot = ParquetCache::new(run_dir.join(SNAPSHOT_DIR));
+    let report = verify_run_dir(&run_dir, &snapshot, DEFAULT_TOLERANCE, true).unwrap();
+    assert!(report.is_reproduced(), "{:?}", report.divergence);
+
+    let _ = std::fs::remove_dir_all(&run_dir);
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+#[test]
+fn modified_run_keeps_the_base_settings_and_records_its_parent() {
+    let cache_dir = setup_fixture_cache();
+    let cache = ParquetCache::new(&cache_dir);
+    let base = saved_run(&cache);
+
+    // Same config: the base run, reproduced from its recorded inputs
+    let same = run_modified(&base, &base.config, &cache).unwrap();
+    assert_eq!(compare_results(&base, &same, DEFAULT_TOLERANCE), None);
+    assert_eq!(same.dataset_hash, base.dataset_hash);
+    assert_eq!(same.inputs, base.inputs);
+    assert!(same.provenance.unwrap().changes.is_empty());
+    assert!(!same.replay.is_empty(), "manual variants keep their replay");
+
+    let mut edited = base.config.clone();
+    edited.signal.params.insert("period".into(), 20.0);
+    let variant = run_modified(&base, &edited, &cache).unwrap();
+    let provenance = variant.provenance.as_ref().unwrap();
+    assert_eq!(provenance.parent_full_hash, base.config.full_hash());
+    let changes: Vec<String> = provenance.changes.iter().map(ToString::to_string).collect();
+    assert_eq!(changes, vec!["roc_momentum period 12.0 → 20.0"]);
+    assert_eq!(variant.start_date, base.start_date);
+
+    // A broken constraint is refused before anything is built
+    edited.position_manager.params.insert("min_pct".into(), 0.2);
+    match run_modified(&base, &edited, &cache) {
+        Err(RunError::ConfigInvalid(ConfigError::Invalid(report))) => {
+            assert!(report
+                .to_string()
+                .contains("min_pct must be below initial_pct"))
+        }
+        other => panic!("expected a constraint error, got {other:?}"),
+    }
+
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
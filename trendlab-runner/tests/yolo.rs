@@ -2,15 +2,38 @@
 //!
 //! Uses the frozen SPY 2024 fixture to run real YOLO sweeps.
 //! Tests: determinism across thread counts, 100+ iterations,
-//! dual slider behavior, error resilience, thread constraint enforcement.
+//! dual slider behavior, error resilience, thread constraint enforcement,
+//! convergence tracking, plateau handling, session persistence, per-symbol
+//! execution profiles, champion exploitation, adaptive exploration,
+//! compatibility resampling, the holdout embargo, results pruning,
+//! discovery under a risk profile, and compute accounting and budgets.
 
 use chrono::NaiveDate;
 use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+use trendlab_core::components::composition::{
+    check_compatibility, CompatibilityContext, StrategyPreset, COMPATIBILITY_RULES,
+};
+use trendlab_core::components::execution::{ExecutionProfile, ExecutionProfiles, LiquidityBucket};
 use trendlab_core::data::cache::ParquetCache;
+use trendlab_core::engine::RiskGuard;
+use trendlab_runner::adaptive::{AdaptiveConfig, ExplorationMode};
+use trendlab_runner::compute_budget::{ComputeLimits, ComputeUsage, StopReason};
+use trendlab_runner::convergence::{ConvergenceConfig, PlateauAction};
 use trendlab_runner::data_loader::{LoadOptions, LoadedData};
-use trendlab_runner::yolo::{run_yolo, YoloConfig, YoloProgress};
+use trendlab_runner::execution_mc::FrictionRampConfig;
+use trendlab_runner::history::{lineage, EvalWindow, WriteFilter, YoloHistory};
+use trendlab_runner::holdout::{HoldoutConfig, HoldoutError};
+use trendlab_runner::refresh::{RefreshConfig, RetestStatus};
+use trendlab_runner::risk_profile::RiskProfile;
+use trendlab_runner::runner::RunErrorKind;
+use trendlab_runner::session::{scan_sessions, SESSION_ARTIFACTS_PER_SYMBOL, SESSION_FILE};
+use trendlab_runner::yolo::{
+    run_yolo, run_yolo_live, YoloConfig, YoloProgress, YoloResult, YoloTunables,
+    RECENT_INSERTIONS_CAP,
+};
 
 static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
 
@@ -50,6 +73,9 @@ fn load_spy_data() -> LoadedData {
         offline: true,
         synthetic: false,
         force: false,
+        strict: false,
+        stale_after_days: None,
+        interval: Default::default(),
     };
     trendlab_runner::load_bars(&["SPY"], &cache, None, None, &opts).unwrap()
 }
@@ -178,6 +204,9 @@ fn yolo_100_iterations_populates_leaderboard() {
         100, // 100 iterations * 1 symbol
         "success + error should equal total"
     );
+    // Engine timings cover every completed backtest
+    assert_eq!(result.timings.runs, result.success_count);
+    assert!(result.timings.total() > std::time::Duration::ZERO);
 
     let lb = &result.leaderboards["SPY"];
     assert!(
@@ -266,6 +295,35 @@ fn dual_sliders_affect_exploration() {
     );
 }
 
+#[test]
+fn sampled_configs_respect_compatibility_rules() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let bars = data.aligned.dates.len();
+
+    let mut config = base_yolo_config(40);
+    config.jitter_pct = 1.0;
+    config.structural_explore = 1.0;
+    config.min_post_warmup_bars = 150;
+
+    let result = run_yolo(&config, &data, &symbols, None, None).unwrap();
+    let context = CompatibilityContext {
+        bar_count: Some(bars),
+        min_post_warmup_bars: 150,
+    };
+    for entry in result.leaderboards["SPY"].entries() {
+        let check = check_compatibility(&entry.result.config, &context);
+        assert!(check.is_compatible(), "{:?}", check.warnings);
+    }
+    assert!(
+        !result.rejections_by_rule.is_empty(),
+        "full exploration on 252 bars should hit a rule"
+    );
+    for rule in result.rejections_by_rule.keys() {
+        assert!(COMPATIBILITY_RULES.iter().any(|r| r.id == rule));
+    }
+}
+
 // ─── Cancellation ──────────────────────────────────────────────────
 
 #[test]
@@ -299,6 +357,7 @@ fn yolo_cancellation_via_flag() {
 
     // Should stop immediately (0 iterations)
     assert_eq!(result.iterations_completed, 0);
+    assert_eq!(result.stop_reason, Some(StopReason::Cancelled));
 }
 
 // ─── Progress reporting ────────────────────────────────────────────
@@ -334,6 +393,861 @@ fn yolo_progress_callback_fires() {
     );
 }
 
+// ─── Live tunables ─────────────────────────────────────────────────
+
+#[test]
+fn live_tunables_override_config_sliders() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let mut config = base_yolo_config(20);
+    config.jitter_pct = 0.1;
+    config.structural_explore = 0.1;
+
+    let tunables = RwLock::new(YoloTunables {
+        jitter_pct: 0.8,
+        structural_explore: 0.9,
+    });
+    let last: Mutex<Option<YoloProgress>> = Mutex::new(None);
+    let progress_cb = |progress: &YoloProgress| {
+        *last.lock().unwrap() = Some(progress.clone());
+    };
+
+    let result = run_yolo_live(
+        &config,
+        &data,
+        &symbols,
+        Some(&progress_cb),
+        None,
+        Some(&tunables),
+    )
+    .unwrap();
+    assert_eq!(result.iterations_completed, 20);
+
+    let progress = last
+        .into_inner()
+        .unwrap()
+        .expect("progress should have fired");
+    assert_eq!(progress.jitter_pct, 0.8);
+    assert_eq!(progress.structural_explore, 0.9);
+    assert!(progress.recent_insertions.len() <= RECENT_INSERTIONS_CAP);
+    for (symbol, best) in &progress.best_fitness {
+        assert_eq!(symbol, "SPY");
+        assert!(best.is_finite());
+    }
+}
+
+#[test]
+fn live_tunables_matching_config_reproduce_run_yolo() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let config = base_yolo_config(30);
+    let tunables = RwLock::new(YoloTunables::from_config(&config));
+
+    let plain = run_yolo(&config, &data, &symbols, None, None).unwrap();
+    let live = run_yolo_live(&config, &data, &symbols, None, None, Some(&tunables)).unwrap();
+
+    let hashes = |r: &trendlab_runner::yolo::YoloResult| -> Vec<String> {
+        r.leaderboards["SPY"]
+            .entries()
+            .iter()
+            .map(|e| e.result.config.full_hash().as_hex())
+            .collect()
+    };
+    assert_eq!(hashes(&plain), hashes(&live));
+}
+
+// ─── Convergence ───────────────────────────────────────────────────
+
+fn plateau_config(window: usize, action: PlateauAction) -> YoloConfig {
+    YoloConfig {
+        convergence: ConvergenceConfig {
+            sample_every: 10,
+            plateau_window: Some(window),
+            on_plateau: action,
+            ..ConvergenceConfig::default()
+        },
+        ..base_yolo_config(2_000)
+    }
+}
+
+#[test]
+fn convergence_series_tracks_best_fitness() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let mut config = base_yolo_config(35);
+    config.convergence.sample_every = 10;
+
+    let result = run_yolo(&config, &data, &symbols, None, None).unwrap();
+    let iterations: Vec<usize> = result.convergence.iter().map(|p| p.iteration).collect();
+    assert_eq!(iterations, vec![0, 10, 20, 30, 34]);
+    assert!(!result.stopped_on_plateau);
+
+    // Best fitness so far never decreases
+    for pair in result.convergence.windows(2) {
+        if let Some(&prev) = pair[0].best_fitness.get("SPY") {
+            assert!(pair[1].best_fitness["SPY"] >= prev);
+        }
+    }
+    let last = result.convergence.last().unwrap();
+    assert_eq!(
+        last.best_fitness.get("SPY").copied(),
+        result.leaderboards["SPY"]
+            .entries()
+            .first()
+            .map(|e| e.fitness_score)
+    );
+}
+
+#[test]
+fn plateau_stops_session_early() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let config = plateau_config(5, PlateauAction::Stop);
+
+    let last: Mutex<Option<YoloProgress>> = Mutex::new(None);
+    let progress_cb = |progress: &YoloProgress| {
+        *last.lock().unwrap() = Some(progress.clone());
+    };
+    let result = run_yolo(&config, &data, &symbols, Some(&progress_cb), None).unwrap();
+
+    assert!(result.stopped_on_plateau);
+    assert_eq!(result.stop_reason, Some(StopReason::Plateau));
+    assert!(result.iterations_completed < 2_000);
+    let progress = last.into_inner().unwrap().unwrap();
+    assert!(progress.convergence.plateaued);
+    assert_eq!(progress.convergence.iterations_since_improvement, 5);
+    assert_eq!(progress.iteration + 1, result.iterations_completed);
+}
+
+#[test]
+fn plateau_boosts_structural_explore() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let mut config = plateau_config(3, PlateauAction::BoostExplore { step: 0.05 });
+    config.max_iterations = Some(200);
+    config.structural_explore = 0.0;
+    let tunables = RwLock::new(YoloTunables::from_config(&config));
+
+    let result = run_yolo_live(&config, &data, &symbols, None, None, Some(&tunables)).unwrap();
+    assert_eq!(result.iterations_completed, 200);
+    assert!(!result.stopped_on_plateau);
+    assert!(tunables.read().unwrap().structural_explore > 0.0);
+}
+
+#[test]
+fn convergence_checkpoints_written_to_history() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let dir = std::env::temp_dir().join(format!(
+        "trendlab_yolo_convergence_{}_{}",
+        std::process::id(),
+        TEST_COUNTER.fetch_add(1, Ordering::SeqCst)
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    let path = dir.join("history.jsonl");
+
+    let mut config = base_yolo_config(25);
+    config.convergence.sample_every = 10;
+    config.history_path = Some(path.clone());
+    run_yolo(&config, &data, &symbols, None, None).unwrap();
+
+    let history = trendlab_runner::history::YoloHistory::new(path, config.write_filter.clone());
+    let checkpoints = history.read_checkpoints().unwrap();
+    let iterations: Vec<usize> = checkpoints.iter().map(|c| c.point.iteration).collect();
+    assert_eq!(iterations, vec![0, 10, 20]);
+    // Each checkpoint carries the compute spent up to it
+    let bars: Vec<u64> = checkpoints
+        .iter()
+        .map(|c| c.compute.unwrap().bars)
+        .collect();
+    assert!(bars[0] > 0);
+    assert!(bars.windows(2).all(|w| w[0] < w[1]));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+// ─── Compute accounting ────────────────────────────────────────────
+
+#[test]
+fn compute_budget_sums_every_run() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let config = base_yolo_config(20);
+
+    let result = run_yolo(&config, &data, &symbols, None, None).unwrap();
+    let compute = &result.compute;
+    assert_eq!(result.stop_reason, Some(StopReason::MaxIterations));
+    assert_eq!(compute.iterations, 20);
+    assert_eq!(compute.total.runs, result.success_count);
+    assert_eq!(
+        compute.total.bars,
+        (result.success_count * data.aligned.dates.len()) as u64
+    );
+    assert_eq!(compute.by_symbol["SPY"], compute.total);
+    let engine_secs = result.timings.total().as_secs_f64();
+    assert!((compute.total.engine_secs - engine_secs).abs() < 1e-6);
+    // Repeated indicators across iterations come from the symbol's cache
+    assert!(compute.total.cache_misses > 0);
+    assert!(compute.total.cache_hits > 0);
+}
+
+#[test]
+fn indicator_cache_leaves_results_unchanged() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let result = run_yolo(&base_yolo_config(15), &data, &symbols, None, None).unwrap();
+
+    for entry in result.leaderboards["SPY"].entries() {
+        let fresh = trendlab_runner::run_backtest_profiled(
+            &entry.result.config,
+            &data.aligned,
+            "SPY",
+            result.config.trading_mode,
+            result.config.initial_capital,
+            result.config.position_size_pct,
+            entry.result.inputs.as_ref().unwrap().execution_preset,
+            None,
+            &data.dataset_hash,
+            data.has_synthetic,
+        )
+        .unwrap();
+        assert_eq!(fresh.equity_curve, entry.result.equity_curve);
+    }
+}
+
+#[test]
+fn bar_budget_stops_after_the_iteration_that_spends_it() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let bars_per_run = data.aligned.dates.len() as u64;
+    let config = YoloConfig {
+        compute_limits: ComputeLimits {
+            max_bars: Some(bars_per_run * 5 / 2),
+            max_minutes: None,
+        },
+        ..base_yolo_config(1_000)
+    };
+
+    let last: Mutex<ComputeUsage> = Mutex::new(ComputeUsage::default());
+    let progress_cb = |progress: &YoloProgress| {
+        *last.lock().unwrap() = progress.compute;
+    };
+    let result = run_yolo(&config, &data, &symbols, Some(&progress_cb), None).unwrap();
+
+    assert_eq!(result.stop_reason, Some(StopReason::BarBudget));
+    let spent = result.compute.total.bars;
+    assert!(spent >= bars_per_run * 5 / 2);
+    // The budget was not yet spent before the final iteration
+    assert!(spent - result.compute.last_iteration.bars < bars_per_run * 5 / 2);
+    assert_eq!(result.compute.iterations, result.iterations_completed);
+    assert!(last.into_inner().unwrap().bars <= spent);
+}
+
+#[test]
+fn time_budget_is_checked_before_each_iteration() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let config = YoloConfig {
+        compute_limits: ComputeLimits {
+            max_bars: None,
+            max_minutes: Some(0.0),
+        },
+        ..base_yolo_config(100)
+    };
+
+    let result = run_yolo(&config, &data, &symbols, None, None).unwrap();
+    assert_eq!(result.stop_reason, Some(StopReason::TimeBudget));
+    assert_eq!(result.iterations_completed, 0);
+    assert_eq!(result.compute.total, ComputeUsage::default());
+}
+
+#[test]
+fn adaptive_controller_is_deterministic_and_logged_to_history() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let dir = session_dir("adaptive");
+    let path = dir.join("history.jsonl");
+
+    let mut config = base_yolo_config(300);
+    config.convergence.sample_every = 50;
+    config.adaptive = Some(AdaptiveConfig {
+        plateau_iterations: 20,
+        insertion_window: 10,
+        exploit_insertion_rate: 0.1,
+        ..AdaptiveConfig::default()
+    });
+    config.history_path = Some(path.clone());
+
+    let last: Mutex<Option<YoloProgress>> = Mutex::new(None);
+    let progress_cb = |progress: &YoloProgress| {
+        *last.lock().unwrap() = Some(progress.clone());
+    };
+    let result = run_yolo(&config, &data, &symbols, Some(&progress_cb), None).unwrap();
+    let transitions = &result.mode_transitions;
+    assert!(!transitions.is_empty());
+    assert!(transitions
+        .iter()
+        .any(|t| matches!(t.to, ExplorationMode::Explore { .. })));
+
+    // Same seed, same outcomes, same transitions
+    config.history_path = None;
+    let rerun = run_yolo(&config, &data, &symbols, None, None).unwrap();
+    assert_eq!(&rerun.mode_transitions, transitions);
+
+    // Every transition lands in exactly one checkpoint
+    let history = YoloHistory::new(path, config.write_filter.clone());
+    let logged: Vec<_> = history
+        .read_checkpoints()
+        .unwrap()
+        .into_iter()
+        .flat_map(|c| c.transitions)
+        .collect();
+    assert_eq!(&logged, transitions);
+
+    let progress = last.into_inner().unwrap().unwrap();
+    assert!(progress.exploration_mode.is_some());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn sessions_without_adaptive_config_keep_their_sliders() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let result = run_yolo(&base_yolo_config(30), &data, &symbols, None, None).unwrap();
+    assert!(result.mode_transitions.is_empty());
+    assert_eq!(result.config.structural_explore, 0.5);
+}
+
+// ─── Session persistence ──────────────────────────────────────────
+
+fn session_dir(tag: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "trendlab_yolo_{tag}_{}_{}",
+        std::process::id(),
+        TEST_COUNTER.fetch_add(1, Ordering::SeqCst)
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn session_roundtrip_keeps_leaderboards_and_artifacts() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let dir = session_dir("session");
+    let mut config = base_yolo_config(40);
+    config.history_path = Some(dir.join("history.jsonl"));
+    let result = run_yolo(&config, &data, &symbols, None, None).unwrap();
+
+    let path = result.save(&dir).unwrap();
+    assert_eq!(path, dir.join(SESSION_FILE));
+
+    let loaded = YoloResult::load(&dir).unwrap();
+    assert_eq!(loaded.session_id, result.session_id);
+    assert_eq!(loaded.config.master_seed, 42);
+    assert_eq!(loaded.iterations_completed, 40);
+    assert_eq!(loaded.convergence, result.convergence);
+    assert_eq!(
+        loaded.cross_leaderboard.len(),
+        result.cross_leaderboard.len()
+    );
+
+    let lb = &result.leaderboards["SPY"];
+    let reloaded = &loaded.leaderboards["SPY"];
+    assert!(!lb.is_empty());
+    assert_eq!(reloaded.len(), lb.len());
+    for (i, (orig, back)) in lb.entries().iter().zip(reloaded.entries()).enumerate() {
+        assert_eq!(
+            back.result.config.full_hash(),
+            orig.result.config.full_hash()
+        );
+        assert_eq!(back.fitness_score, orig.fitness_score);
+        assert!(
+            back.result.equity_curve.is_empty(),
+            "curves live in artifacts"
+        );
+        assert_eq!(
+            back.artifact_dir.is_some(),
+            i < SESSION_ARTIFACTS_PER_SYMBOL
+        );
+        if let Some(artifact_dir) = &back.artifact_dir {
+            let curve = trendlab_runner::load_equity_curve(artifact_dir).unwrap();
+            assert_eq!(curve, orig.result.equity_curve);
+            let full = trendlab_runner::load_artifacts(artifact_dir).unwrap();
+            assert_eq!(full.trades.len(), orig.result.trades.len());
+        }
+    }
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn pruning_keeps_runs_a_saved_session_references() {
+    use trendlab_runner::retention::{execute_prune, plan_prune, RetentionPolicy, RetentionRule};
+
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let results = session_dir("prune");
+    let result = run_yolo(&base_yolo_config(20), &data, &symbols, None, None).unwrap();
+    result.save(&results.join("sessions").join("s1")).unwrap();
+
+    // A single run of the session's best config, and one of a config it
+    // did not keep
+    let best = &result.leaderboards["SPY"].entries()[0].result;
+    let referenced = trendlab_runner::save_artifacts(best, &results).unwrap();
+    let mut unrelated = best.clone();
+    unrelated.config = StrategyPreset::SupertrendSystem.to_config();
+    assert!(result.leaderboards["SPY"]
+        .entries()
+        .iter()
+        .all(|e| e.result.config.full_hash() != unrelated.config.full_hash()));
+    let orphan = results.join("SPY_orphan");
+    trendlab_runner::write_artifacts(&unrelated, &orphan).unwrap();
+
+    let policy = RetentionPolicy {
+        keep_top: 0,
+        keep_days: None,
+        ..RetentionPolicy::default()
+    };
+    let later = chrono::Local::now().naive_local() + chrono::Duration::days(1);
+    let plan = plan_prune(&results, &policy, later).unwrap();
+    assert_eq!(plan.reference_files, 1);
+    assert_eq!(plan.kept.len(), 1);
+    assert_eq!(plan.kept[0].1, RetentionRule::Referenced);
+    assert_eq!(plan.kept[0].0.dir, referenced);
+    assert_eq!(plan.delete.len(), 1);
+
+    let outcome = execute_prune(&plan);
+    assert_eq!(outcome.deleted, 1);
+    assert!(!orphan.exists());
+    assert!(referenced.exists());
+    // The session and its own artifacts are not candidates
+    assert!(YoloResult::load(&results.join("sessions").join("s1")).is_ok());
+
+    let forced = RetentionPolicy {
+        force: true,
+        ..policy
+    };
+    let plan = plan_prune(&results, &forced, later).unwrap();
+    assert_eq!((plan.delete.len(), plan.forced), (1, 1));
+
+    let _ = std::fs::remove_dir_all(&results);
+}
+
+#[test]
+fn session_without_artifacts_still_lists_metrics() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let root = session_dir("sessions");
+    let result = run_yolo(&base_yolo_config(20), &data, &symbols, None, None).unwrap();
+    result.save(&root.join("b")).unwrap();
+    result.save(&root.join("a")).unwrap();
+    std::fs::remove_dir_all(root.join("a").join("runs")).unwrap();
+    std::fs::create_dir_all(root.join("not_a_session")).unwrap();
+
+    let sessions = scan_sessions(&root);
+    assert_eq!(sessions.len(), 2);
+    assert!(sessions[0].dir.ends_with("a"));
+    assert_eq!(sessions[0].symbols, vec!["SPY".to_string()]);
+    assert_eq!(sessions[0].saved_artifacts, 0);
+    assert!(sessions[1].saved_artifacts > 0);
+
+    let loaded = YoloResult::load(&root.join("a").join(SESSION_FILE)).unwrap();
+    let entries = loaded.leaderboards["SPY"].entries();
+    assert!(!entries.is_empty());
+    assert!(entries.iter().all(|e| e.artifact_dir.is_none()));
+    assert!(entries.iter().all(|e| e.result.metrics.sharpe.is_finite()));
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn refresh_retests_entries_on_extended_data() {
+    // Session data ends mid-year; the refresh sees the full year
+    let mut data = load_spy_data();
+    let keep = data
+        .aligned
+        .dates
+        .iter()
+        .take_while(|d| **d <= NaiveDate::from_ymd_opt(2024, 6, 28).unwrap())
+        .count();
+    data.aligned.dates.truncate(keep);
+    data.aligned.bars.get_mut("SPY").unwrap().truncate(keep);
+    let symbols = vec!["SPY".to_string()];
+    let dir = session_dir("refresh");
+    let result = run_yolo(&base_yolo_config(60), &data, &symbols, None, None).unwrap();
+    result.save(&dir).unwrap();
+
+    let mut loaded = YoloResult::load(&dir).unwrap();
+    loaded.rehydrate_artifacts().unwrap();
+    let before = loaded.leaderboards["SPY"].len();
+    assert!(before > 0);
+    assert!(loaded.leaderboards["SPY"]
+        .entries()
+        .iter()
+        .all(|e| e.retest_status() == RetestStatus::NeverRetested));
+
+    let full = load_spy_data();
+    let now = chrono::Utc::now().naive_utc();
+    let config = RefreshConfig::from_yolo(&loaded.config);
+    let report = loaded.refresh(&full, &config, now);
+    assert_eq!(report.refreshed.len(), before);
+    assert!(report.failed.is_empty());
+
+    let lb = &loaded.leaderboards["SPY"];
+    assert_eq!(lb.len(), before);
+    for entry in lb.entries() {
+        let record = entry.retest.as_ref().unwrap();
+        assert!(record.previous_end_date.as_str() <= "2024-06-28");
+        assert_eq!(record.end_date, entry.result.end_date);
+        assert!(record.end_date.as_str() > "2024-06-28");
+        assert_eq!(record.fitness_after, entry.fitness_score);
+        assert_ne!(entry.retest_status(), RetestStatus::NeverRetested);
+    }
+
+    for entry in loaded.cross_leaderboard.entries().values() {
+        assert!(entry.symbol_retests.contains_key("SPY"));
+        assert_ne!(entry.retest_status(), RetestStatus::NeverRetested);
+    }
+
+    // A second pass finds nothing stale
+    let again = loaded.refresh(&full, &config, now);
+    assert!(again.refreshed.is_empty());
+    assert_eq!(again.up_to_date, before);
+
+    let history = YoloHistory::new(dir.join("history.jsonl"), WriteFilter::default());
+    for refreshed in &report.refreshed {
+        history.append_refresh(refreshed).unwrap();
+    }
+    assert_eq!(history.read_refreshes().unwrap(), report.refreshed);
+    assert!(history.read_all().unwrap().is_empty());
+
+    loaded.save(&dir).unwrap();
+    let reloaded = YoloResult::load(&dir).unwrap();
+    for (back, entry) in reloaded.leaderboards["SPY"]
+        .entries()
+        .iter()
+        .zip(loaded.leaderboards["SPY"].entries())
+    {
+        assert_eq!(back.retest, entry.retest);
+        if let Some(artifact_dir) = &back.artifact_dir {
+            let curve = trendlab_runner::load_equity_curve(artifact_dir).unwrap();
+            assert_eq!(curve, entry.result.equity_curve);
+        }
+    }
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+// ─── Holdout embargo ───────────────────────────────────────────────
+
+#[test]
+fn holdout_is_withheld_from_discovery_and_evaluated_once() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let dir = session_dir("holdout");
+    let boundary = NaiveDate::from_ymd_opt(2024, 10, 1).unwrap();
+    let config = YoloConfig {
+        holdout: Some(HoldoutConfig {
+            start: Some(boundary),
+            ..HoldoutConfig::default()
+        }),
+        history_path: Some(dir.join("history.jsonl")),
+        write_filter: WriteFilter {
+            min_trades: 1,
+            min_cagr: None,
+            min_sharpe: None,
+        },
+        ..base_yolo_config(30)
+    };
+    let mut result = run_yolo(&config, &data, &symbols, None, None).unwrap();
+
+    let holdout_start = result.holdout_start.unwrap();
+    assert!(holdout_start >= boundary);
+    let lb = &result.leaderboards["SPY"];
+    assert!(!lb.is_empty());
+    for entry in lb.entries() {
+        assert!(entry.result.end_date.as_str() < "2024-10-01");
+        let record = entry.holdout.as_ref().unwrap();
+        assert_eq!(record.holdout_start, holdout_start);
+        assert_eq!(record.discovery_fitness, entry.fitness_score);
+    }
+    let report = result.holdout.clone().unwrap();
+    assert_eq!(report.evaluated.len() + report.failed.len(), lb.len());
+    for entry in result.cross_leaderboard.entries().values() {
+        assert!(entry.symbol_holdouts.contains_key("SPY"));
+    }
+
+    let history = YoloHistory::new(dir.join("history.jsonl"), WriteFilter::default());
+    let entries = history.read_all().unwrap();
+    assert!(!entries.is_empty());
+    assert!(entries
+        .iter()
+        .all(|e| e.window == EvalWindow::Discovery && e.fingerprint.end_date < holdout_start));
+    assert_eq!(
+        history.read_holdouts().unwrap().len(),
+        report.evaluated.len()
+    );
+
+    // Looked at once, the holdout is spent
+    let (_, holdout) = data.split_at(data.aligned.dates.len() - 10);
+    assert!(matches!(
+        result.evaluate_holdout(&holdout, chrono::Utc::now().naive_utc()),
+        Err(HoldoutError::AlreadyEvaluated(_))
+    ));
+
+    result.save(&dir).unwrap();
+    let loaded = YoloResult::load(&dir).unwrap();
+    assert_eq!(loaded.holdout_start, Some(holdout_start));
+    assert_eq!(
+        loaded.holdout.unwrap().evaluated.len(),
+        report.evaluated.len()
+    );
+    assert!(loaded.leaderboards["SPY"]
+        .entries()
+        .iter()
+        .all(|e| e.holdout.is_some()));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn deferred_holdout_refuses_discovery_bars() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let config = YoloConfig {
+        holdout: Some(HoldoutConfig {
+            fraction: 0.25,
+            evaluate_at_end: false,
+            ..HoldoutConfig::default()
+        }),
+        ..base_yolo_config(20)
+    };
+    let mut result = run_yolo(&config, &data, &symbols, None, None).unwrap();
+    assert!(result.holdout.is_none());
+    let now = chrono::Utc::now().naive_utc();
+
+    assert!(matches!(
+        result.evaluate_holdout(&data, now),
+        Err(HoldoutError::Overlap { .. })
+    ));
+    assert!(result.holdout.is_none());
+
+    let boundary = config
+        .holdout
+        .as_ref()
+        .unwrap()
+        .boundary(&data.aligned.dates)
+        .unwrap();
+    let (_, holdout) = data.split_at(boundary);
+    let holdout_start = result.holdout_start.unwrap();
+    assert_eq!(holdout.aligned.dates[0], holdout_start);
+    let report = result.evaluate_holdout(&holdout, now).unwrap();
+    assert_eq!(report.holdout_start, holdout_start);
+}
+
+// ─── Friction ramp ─────────────────────────────────────────────────
+
+#[test]
+fn friction_ramp_attaches_to_inserted_configs() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+
+    let config = YoloConfig {
+        friction_ramp: Some(FrictionRampConfig {
+            max_slippage_bps: 20.0,
+            step_bps: 10.0,
+        }),
+        ..base_yolo_config(30)
+    };
+    let result = run_yolo(&config, &data, &symbols, None, None).unwrap();
+
+    let lb = &result.leaderboards["SPY"];
+    assert!(!lb.entries().is_empty());
+    for entry in lb.entries() {
+        let cross = &result.cross_leaderboard.entries()[&entry.result.config.full_hash()];
+        let friction = cross
+            .robustness
+            .as_ref()
+            .and_then(|r| r.friction.as_ref())
+            .expect("inserted configs carry a friction ramp");
+        assert_eq!(friction.symbol, "SPY");
+        assert_eq!(friction.points.len(), 3);
+        assert_eq!(friction.points[0].slippage_bps, 0.0);
+    }
+
+    // Disabled by default
+    let plain = run_yolo(&base_yolo_config(30), &data, &symbols, None, None).unwrap();
+    assert!(plain
+        .cross_leaderboard
+        .entries()
+        .values()
+        .all(|e| e.robustness.is_none()));
+}
+
+// ─── Per-symbol execution profiles ─────────────────────────────────
+
+#[test]
+fn execution_profiles_give_symbols_their_own_friction() {
+    // The same bars under a second name, loaded as if it were a small cap
+    let mut data = load_spy_data();
+    let spy_bars = data.aligned.bars["SPY"].clone();
+    data.aligned.bars.insert("TINY".into(), spy_bars);
+    data.aligned.symbols.push("TINY".into());
+    data.avg_dollar_volume.insert("TINY".into(), 2e6);
+    let symbols = vec!["SPY".to_string(), "TINY".to_string()];
+
+    let profile = |name: &str, slippage_bps: f64| ExecutionProfile {
+        name: name.into(),
+        preset: None,
+        slippage_bps: Some(slippage_bps),
+        commission: None,
+    };
+    let dir = session_dir("profiles");
+    let path = dir.join("history.jsonl");
+    let mut config = base_yolo_config(60);
+    config.execution_profiles = ExecutionProfiles {
+        symbols: [("SPY".to_string(), profile("etf", 1.0))].into(),
+        buckets: vec![LiquidityBucket {
+            min_avg_dollar_volume: 0.0,
+            profile: profile("small_cap", 100.0),
+        }],
+    };
+    config.history_path = Some(path.clone());
+    let result = run_yolo(&config, &data, &symbols, None, None).unwrap();
+
+    // Same config, same bars: only the profile separates the fills
+    let tiny = &result.leaderboards["TINY"];
+    let pair = result.leaderboards["SPY"].entries().iter().find_map(|spy| {
+        let hash = spy.result.config.full_hash();
+        let other = tiny
+            .entries()
+            .iter()
+            .find(|e| e.result.config.full_hash() == hash)?;
+        Some((spy, other))
+    });
+    let (spy, tiny) = pair.expect("a config ranked on both symbols");
+    let profile_name = |e: &trendlab_runner::LeaderboardEntry| {
+        e.result
+            .inputs
+            .as_ref()
+            .and_then(|i| i.execution_profile.as_ref())
+            .map(|p| p.name.clone())
+    };
+    assert_eq!(profile_name(spy).as_deref(), Some("etf"));
+    assert_eq!(profile_name(tiny).as_deref(), Some("small_cap"));
+
+    let (a, b) = (&spy.result.trades[0], &tiny.result.trades[0]);
+    assert_eq!(a.entry_bar, b.entry_bar);
+    assert!(
+        b.entry_price > a.entry_price,
+        "100 bps buys fill above 1 bps buys: {} vs {}",
+        b.entry_price,
+        a.entry_price
+    );
+    assert!(tiny.result.metrics.total_return < spy.result.metrics.total_return);
+
+    let cross = &result.cross_leaderboard.entries()[&spy.result.config.full_hash()];
+    assert!(cross.has_mixed_execution_profiles());
+    assert_eq!(cross.symbol_execution_profiles["TINY"], "small_cap");
+
+    // Every history fingerprint names the profile its run used
+    let history = YoloHistory::new(path, config.write_filter.clone());
+    let entries = history.read_all().unwrap();
+    assert!(!entries.is_empty());
+    for entry in &entries {
+        let fp = &entry.fingerprint;
+        let expected = match fp.symbol.as_str() {
+            "SPY" => "etf",
+            _ => "small_cap",
+        };
+        assert_eq!(fp.execution_profile.as_ref().unwrap().name, expected);
+    }
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+// ─── Risk profile discovery ────────────────────────────────────────
+
+#[test]
+fn risk_profile_constrains_every_discovered_config() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let dir = session_dir("risk_profile");
+    let path = dir.join("history.jsonl");
+    let mut config = base_yolo_config(40);
+    config.risk_profile = Some(RiskProfile::Conservative);
+    config.write_filter = WriteFilter {
+        min_trades: 0,
+        min_cagr: None,
+        min_sharpe: None,
+    };
+    config.history_path = Some(path.clone());
+    let result = run_yolo(&config, &data, &symbols, None, None).unwrap();
+
+    let overrides = RiskProfile::Conservative.to_overrides();
+    let lb = &result.leaderboards["SPY"];
+    assert!(!lb.is_empty());
+    for entry in lb.entries() {
+        let config = &entry.result.config;
+        assert_eq!(
+            RiskGuard::from_params(&config.signal.params),
+            overrides.guard
+        );
+        assert_eq!(config.signal_filter.component_type, "volatility_filter");
+        let inputs = entry.result.inputs.as_ref().unwrap();
+        assert_eq!(inputs.risk_profile, Some(RiskProfile::Conservative));
+    }
+
+    let history = YoloHistory::new(path, config.write_filter.clone());
+    let entries = history.read_all().unwrap();
+    assert!(!entries.is_empty());
+    assert!(entries
+        .iter()
+        .all(|e| e.fingerprint.risk_profile.as_deref() == Some("conservative")));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+// ─── Champion exploitation ─────────────────────────────────────────
+
+#[test]
+fn exploited_configs_record_provenance() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let dir = session_dir("exploit");
+    let path = dir.join("history.jsonl");
+
+    let mut config = YoloConfig {
+        exploit_pct: 1.0,
+        ..base_yolo_config(40)
+    };
+    config.write_filter = WriteFilter {
+        min_trades: 0,
+        min_cagr: None,
+        min_sharpe: None,
+    };
+    config.history_path = Some(path.clone());
+    run_yolo(&config, &data, &symbols, None, None).unwrap();
+
+    let history = YoloHistory::new(path, config.write_filter.clone());
+    let entries = history.read_all().unwrap();
+    let mutated: Vec<_> = entries.iter().filter(|e| e.provenance.is_some()).collect();
+    assert!(!mutated.is_empty(), "exploit_pct 1.0 mutates champions");
+
+    for entry in mutated {
+        let provenance = entry.provenance.as_ref().unwrap();
+        assert!(!provenance.changes.is_empty());
+        let chain = lineage(&entries, &entry.fingerprint.strategy_config.full_hash());
+        assert!(chain.len() >= 2);
+        let root = chain.last().unwrap();
+        assert!(root.entry.map_or(true, |e| e.provenance.is_none()));
+    }
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
 // ─── Error resilience ──────────────────────────────────────────────
 
 #[test]
@@ -342,7 +1256,10 @@ fn yolo_handles_missing_symbol_gracefully() {
     // Include a symbol not in the data — should produce errors, not crash
     let symbols = vec!["SPY".to_string(), "NONEXISTENT".to_string()];
 
-    let config = base_yolo_config(20);
+    let config = YoloConfig {
+        quarantine_after: 0,
+        ..base_yolo_config(20)
+    };
 
     let result = run_yolo(&config, &data, &symbols, None, None).unwrap();
 
@@ -368,6 +1285,66 @@ fn yolo_handles_missing_symbol_gracefully() {
     assert!(lb_spy.len() > 0, "SPY leaderboard should have entries");
 }
 
+#[test]
+fn yolo_quarantines_always_failing_symbol() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string(), "NONEXISTENT".to_string()];
+    let config = YoloConfig {
+        quarantine_after: 3,
+        ..base_yolo_config(30)
+    };
+
+    let first_progress = Mutex::new(None);
+    let progress_cb = |progress: &YoloProgress| {
+        first_progress
+            .lock()
+            .unwrap()
+            .get_or_insert_with(|| progress.clone());
+    };
+    let result = run_yolo(&config, &data, &symbols, Some(&progress_cb), None).unwrap();
+
+    // The session runs to completion on SPY alone
+    assert_eq!(result.iterations_completed, 30);
+    assert_eq!(result.success_count, 30);
+    assert_eq!(
+        result.error_count, 3,
+        "NONEXISTENT is skipped once quarantined"
+    );
+    assert_eq!(result.errors_by_kind.get(&RunErrorKind::DataLoad), Some(&3));
+
+    assert_eq!(result.quarantined.len(), 1);
+    let q = &result.quarantined[0];
+    assert_eq!(q.symbol, "NONEXISTENT");
+    assert_eq!(q.iteration, 2);
+    assert_eq!(q.consecutive_failures, 3);
+    assert_eq!(q.kind, RunErrorKind::DataLoad);
+    assert!(
+        !q.retryable,
+        "a symbol missing from the data won't appear on retry"
+    );
+
+    let first = first_progress.into_inner().unwrap().unwrap();
+    assert_eq!(first.errors_by_kind.get(&RunErrorKind::DataLoad), Some(&1));
+    assert!(first.quarantined.is_empty());
+}
+
+#[test]
+fn yolo_stops_when_every_symbol_is_quarantined() {
+    let data = load_spy_data();
+    let symbols = vec!["NONEXISTENT".to_string()];
+    let config = YoloConfig {
+        quarantine_after: 2,
+        ..base_yolo_config(50)
+    };
+
+    let result = run_yolo(&config, &data, &symbols, None, None).unwrap();
+
+    assert_eq!(result.iterations_completed, 2);
+    assert_eq!(result.error_count, 2);
+    assert_eq!(result.quarantined[0].symbol, "NONEXISTENT");
+    assert_eq!(result.stop_reason, Some(StopReason::AllQuarantined));
+}
+
 // ─── Thread constraint enforcement ─────────────────────────────────
 
 #[test]
@@ -463,3 +1440,100 @@ fn different_seeds_produce_different_results() {
         );
     }
 }
+
+#[test]
+fn fdr_families_persist_and_merge_across_sessions() {
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let dir = session_dir("fdr");
+    let path = dir.join("history.jsonl");
+
+    let mut config = base_yolo_config(30);
+    config.history_path = Some(path.clone());
+    let first = run_yolo(&config, &data, &symbols, None, None).unwrap();
+    let first_trials = first.fdr_trials["SPY"];
+    assert_eq!(first_trials, first.success_count);
+
+    let history = YoloHistory::new(path, config.write_filter.clone());
+    let records = history.read_fdr().unwrap();
+    assert_eq!(
+        records.iter().map(|r| r.trials).sum::<usize>(),
+        first_trials
+    );
+    assert!(records.iter().all(|r| r.session_id == first.session_id));
+
+    // Every standing is computed over the final family
+    let lb = &first.leaderboards["SPY"];
+    assert!(lb.entries().iter().any(|e| e.fdr.is_some()));
+    for standing in lb.entries().iter().filter_map(|e| e.fdr) {
+        assert_eq!(standing.family_trials, first_trials);
+        assert!(standing.q_value >= standing.p_value);
+    }
+
+    // A separate session starts its own family unless merging
+    config.master_seed = 7;
+    let separate = run_yolo(&config, &data, &symbols, None, None).unwrap();
+    assert_eq!(separate.fdr_trials["SPY"], separate.success_count);
+
+    config.master_seed = 8;
+    config.fdr.merge_sessions = true;
+    let merged = run_yolo(&config, &data, &symbols, None, None).unwrap();
+    assert_eq!(
+        merged.fdr_trials["SPY"],
+        first_trials + separate.success_count + merged.success_count
+    );
+    for entry in merged.leaderboards["SPY"].entries() {
+        if let Some(standing) = entry.fdr {
+            assert_eq!(standing.family_trials, merged.fdr_trials["SPY"]);
+        }
+    }
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn leaderboard_files_outlive_sessions_and_link_artifacts() {
+    use trendlab_runner::leaderboard_store::{stored_leaderboard_path, StoredLeaderboard};
+
+    let data = load_spy_data();
+    let symbols = vec!["SPY".to_string()];
+    let dir = session_dir("lb_store");
+    let lb_dir = dir.join("leaderboards");
+
+    let mut config = base_yolo_config(20);
+    config.leaderboard_dir = Some(lb_dir.clone());
+    let first = run_yolo(&config, &data, &symbols, None, None).unwrap();
+    let session_lb = &first.leaderboards["SPY"];
+    assert!(!session_lb.is_empty());
+    assert!(stored_leaderboard_path(&lb_dir, "SPY").is_file());
+    let stored = StoredLeaderboard::load(&lb_dir, "SPY").unwrap().unwrap();
+    let stored_hashes: Vec<_> = stored.entries().iter().map(|e| e.full_hash()).collect();
+    let session_hashes: Vec<_> = session_lb
+        .entries()
+        .iter()
+        .map(|e| e.result.config.full_hash())
+        .collect();
+    assert_eq!(stored_hashes, session_hashes);
+
+    // Saving the session points the file at the saved artifacts
+    first.save(&dir.join("first")).unwrap();
+    let stored = StoredLeaderboard::load(&lb_dir, "SPY").unwrap().unwrap();
+    let best = &stored.entries()[0];
+    let best_dir = best.artifact_dir.as_ref().unwrap();
+    assert!(best_dir.join("manifest.json").is_file());
+
+    // A later session resumes the file and keeps the first session's entries
+    config.master_seed = 9;
+    config.history_path = Some(dir.join("history.jsonl"));
+    let second = run_yolo(&config, &data, &symbols, None, None).unwrap();
+    assert!(second.leaderboard_divergence.is_empty());
+    let stored = StoredLeaderboard::load(&lb_dir, "SPY").unwrap().unwrap();
+    assert!(stored.len() >= session_lb.len());
+    assert!(stored
+        .entries()
+        .iter()
+        .any(|e| e.origin.as_ref().unwrap().session_id == first.session_id));
+    assert!(stored.entries()[0].fitness_score >= session_lb.entries()[0].fitness_score);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
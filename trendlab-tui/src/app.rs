@@ -2,21 +2,41 @@
 //!
 //! All TUI state lives here. The worker thread communicates via channels.
 
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::{Receiver, Sender};
-use std::sync::atomic::AtomicBool;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
 
-use chrono::NaiveDateTime;
+use chrono::{Months, NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 
-use trendlab_core::components::sampler::{ComponentPool, ComponentVariant};
+use trendlab_core::components::sampler::{
+    constraint_violations, default_composite_children, ComponentPool, ComponentSlot,
+    ComponentVariant, ParamRange,
+};
+use trendlab_core::data::cache::{CacheEntry, ParquetCache};
 use trendlab_core::data::universe::Universe;
-use trendlab_core::fingerprint::{ComponentConfig, StrategyConfig, TradingMode};
-use trendlab_runner::{PerformanceMetrics, RiskProfile, YoloConfig, YoloProgress};
+use trendlab_core::domain::{DecisionSource, TradeRecord};
+use trendlab_core::engine::stickiness::StickinessReport;
+use trendlab_core::engine::{PositionReplay, ReplayLog};
+use trendlab_core::fingerprint::{
+    ComponentConfig, ConfigChange, Provenance, StrategyConfig, TradingMode,
+};
+use trendlab_runner::{
+    scan_sessions, Annotation, AnnotationTag, Annotations, ArtifactScan, ArtifactSummary, BenchmarkComparison, CrossSymbolEntry, FdrStanding, FrictionSensitivity,
+    LeaderboardEntry, PerformanceMetrics, RiskProfile, SessionSummary, StyleReport, TailMetrics, TruncatedRange,
+    YoloConfig, YoloProgress, YoloResult, YoloTunables, load_artifacts, load_diagnostics,
+    ParameterSensitivity, SignalValueScore,
+    BacktestResult, ResultSlice, SliceError, ANNOTATIONS_FILE,
+};
 
-use crate::worker::{WorkerCommand, WorkerResponse};
+use crate::export;
+use crate::persistence;
+use crate::query::ResultsQuery;
+use crate::theme;
+use crate::worker::{champion_entries, EquityLoadRequest, WorkerCommand, WorkerResponse};
 
 /// Which panel is active.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -131,6 +151,33 @@ pub struct DataPanelState {
     pub fetch_current_symbol: Option<String>,
     pub fetch_done: usize,
     pub fetch_total: usize,
+    /// Symbols found in the cache, rescanned after every download batch.
+    pub cache_entries: Vec<CacheEntry>,
+    /// Per-symbol state of the current or last download batch.
+    pub downloads: BTreeMap<String, DownloadStatus>,
+    /// Date range of the last download batch, reused by retries.
+    pub last_fetch_range: Option<(NaiveDate, NaiveDate)>,
+    pub manager: CacheManagerState,
+}
+
+/// Download state of one symbol, streamed from the worker.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DownloadStatus {
+    Pending,
+    /// `attempt` counts from 1 and rises on each transient-error retry.
+    Downloading { attempt: u32 },
+    Done,
+    Failed(String),
+}
+
+/// Cache manager drill-down: cursor, symbols marked for download, and the
+/// range they are fetched over.
+#[derive(Debug, Default)]
+pub struct CacheManagerState {
+    pub cursor: usize,
+    pub marked: BTreeSet<String>,
+    /// None follows the sweep config's date range.
+    pub range: Option<(NaiveDate, NaiveDate)>,
 }
 
 impl DataPanelState {
@@ -146,9 +193,25 @@ impl DataPanelState {
             fetch_current_symbol: None,
             fetch_done: 0,
             fetch_total: 0,
+            cache_entries: Vec::new(),
+            downloads: BTreeMap::new(),
+            last_fetch_range: None,
+            manager: CacheManagerState::default(),
         }
     }
 
+    /// Rows of the cache manager: cached symbols and symbols being
+    /// downloaded, sorted.
+    pub fn cache_rows(&self) -> Vec<String> {
+        let rows: BTreeSet<&String> = self
+            .cache_entries
+            .iter()
+            .map(|e| &e.symbol)
+            .chain(self.downloads.keys())
+            .collect();
+        rows.into_iter().cloned().collect()
+    }
+
     /// Count total visible rows (sectors + visible tickers).
     pub fn visible_row_count(&self) -> usize {
         let mut count = 0;
@@ -263,15 +326,19 @@ impl StrategyPanelState {
             ComponentConfig {
                 component_type: variant.component_type.clone(),
                 params: map,
+                children: Vec::new(),
             }
         }
 
+        let mut position_manager =
+            build_config(&self.pool.position_managers[self.pm_idx], &self.pm_params);
+        if position_manager.component_type == "composite" {
+            position_manager.children = default_composite_children(&self.pool.position_managers);
+        }
+
         StrategyConfig {
             signal: build_config(&self.pool.signals[self.signal_idx], &self.signal_params),
-            position_manager: build_config(
-                &self.pool.position_managers[self.pm_idx],
-                &self.pm_params,
-            ),
+            position_manager,
             execution_model: build_config(
                 &self.pool.execution_models[self.exec_idx],
                 &self.exec_params,
@@ -373,10 +440,252 @@ pub struct LeaderboardDisplayEntry {
     pub session_id: String,
     /// Full metrics for drill-down.
     pub metrics: PerformanceMetrics,
-    /// Stickiness metrics (if available).
-    pub stickiness: Option<trendlab_core::engine::stickiness::StickinessMetrics>,
+    /// Stickiness metrics (if available) and per-position scores.
+    pub stickiness: StickinessReport,
+    /// Number of data quality warnings attached to the result.
+    pub data_warnings: usize,
+    /// Set when the backtest's data ended before the requested end date.
+    pub truncated_range: Option<TruncatedRange>,
+    /// R-multiple distribution (single backtests only).
+    pub r_distribution: Option<trendlab_runner::RDistribution>,
+    /// Trade return and holding-period histograms (single backtests only).
+    pub trade_distributions: Option<trendlab_runner::TradeDistributions>,
+    /// CVaR, moments, and (single backtests only) the worst-days table.
+    pub tail_metrics: Option<TailMetrics>,
+    /// Factor betas from style analysis.
+    pub style: Option<StyleReport>,
+    /// Sharpe-vs-slippage ramp of a YOLO champion, if the ramp was enabled.
+    pub friction: Option<FrictionSensitivity>,
+    /// Comparison with buy-and-hold over the same window, for YOLO champions
+    /// that reached the promotion ladder.
+    pub benchmark: Option<BenchmarkComparison>,
+    /// Shuffle-test percentiles against randomized entries, for YOLO
+    /// champions promoted with the shuffle test on.
+    pub signal_value: Option<SignalValueScore>,
+    /// Fitness with the primary params nudged, for YOLO champions promoted
+    /// with the sensitivity probe on; carries the parameter-cliff flag.
+    pub sensitivity: Option<ParameterSensitivity>,
+    /// Standing in the symbol's FDR family (YOLO entries; for champions,
+    /// their weakest symbol).
+    pub fdr: Option<FdrStanding>,
+    /// Downsampled equity curve for the leaderboard sparkline column.
+    pub equity_sparkline: Vec<f64>,
+    /// Saved artifact directory; its equity curve is loaded on first drill-down.
+    pub artifact_dir: Option<PathBuf>,
+    /// Overlap cluster of a YOLO champion (see `CrossSymbolLeaderboard::analyze_overlap`).
+    pub cluster_id: Option<usize>,
+    pub diversification: Option<f64>,
+    /// Best entry of its cluster; unclustered entries are their own leader.
+    pub cluster_leader: bool,
+    /// Parent config and changes, for YOLO champions mutated from another.
+    pub provenance: Option<Provenance>,
+    /// Run PnL by decision source (empty for cross-symbol champions).
+    pub attribution: BTreeMap<DecisionSource, f64>,
+    /// Bar-by-bar position replay. Single backtests carry theirs; saved runs
+    /// load it from `diagnostics.json` on first replay.
+    pub replay: Option<Arc<ReplayLog>>,
+    /// Trade list. Single backtests carry theirs; saved runs read it from
+    /// the manifest on export.
+    pub trades: Option<Arc<Vec<TradeRecord>>>,
+    /// Full result, for date-window slicing. Single backtests carry theirs;
+    /// saved runs read it from the manifest when a window is first applied.
+    pub result: Option<Arc<BacktestResult>>,
+}
+
+impl LeaderboardDisplayEntry {
+    /// Entry for a saved run, built from its manifest headline stats.
+    pub fn from_summary(rank: usize, summary: ArtifactSummary) -> Self {
+        Self {
+            rank,
+            signal_type: summary.config.signal.component_type.clone(),
+            pm_type: summary.config.position_manager.component_type.clone(),
+            exec_type: summary.config.execution_model.component_type.clone(),
+            filter_type: summary.config.signal_filter.component_type.clone(),
+            symbol: summary.symbol,
+            sharpe: summary.metrics.sharpe,
+            cagr: summary.metrics.cagr,
+            max_drawdown: summary.metrics.max_drawdown,
+            win_rate: summary.metrics.win_rate,
+            profit_factor: summary.metrics.profit_factor,
+            trade_count: summary.metrics.trade_count,
+            config: summary.config,
+            fitness_score: summary.metrics.sharpe,
+            session_id: SAVED_RUNS_SESSION.to_string(),
+            metrics: summary.metrics,
+            stickiness: StickinessReport {
+                run: summary.stickiness,
+                positions: summary.position_stickiness,
+            },
+            data_warnings: summary.data_warning_count,
+            truncated_range: None,
+            r_distribution: Some(summary.r_distribution),
+            trade_distributions: Some(summary.trade_distributions),
+            tail_metrics: summary.tail_metrics,
+            style: summary.style,
+            friction: None,
+            benchmark: None,
+            signal_value: None,
+            sensitivity: None,
+            fdr: None,
+            equity_sparkline: summary.equity_sparkline,
+            artifact_dir: Some(summary.dir),
+            cluster_id: None,
+            diversification: None,
+            cluster_leader: true,
+            provenance: None,
+            attribution: summary.attribution.totals(),
+            replay: None,
+            trades: None,
+            result: None,
+        }
+    }
+
+    /// Entry for a YOLO cross-symbol champion, with metrics aggregated across
+    /// its symbols.
+    pub fn from_champion(rank: usize, entry: &CrossSymbolEntry, cluster_leader: bool) -> Self {
+        let metrics = aggregate_metrics(entry);
+        Self {
+            rank,
+            signal_type: entry.config.signal.component_type.clone(),
+            pm_type: entry.config.position_manager.component_type.clone(),
+            exec_type: entry.config.execution_model.component_type.clone(),
+            filter_type: entry.config.signal_filter.component_type.clone(),
+            symbol: format!("{} sym", entry.symbol_count),
+            sharpe: metrics.sharpe,
+            cagr: metrics.cagr,
+            max_drawdown: metrics.max_drawdown,
+            win_rate: metrics.win_rate,
+            profit_factor: metrics.profit_factor,
+            trade_count: metrics.trade_count,
+            config: entry.config.clone(),
+            fitness_score: entry.avg_sharpe,
+            session_id: entry.session_id.clone(),
+            metrics,
+            stickiness: StickinessReport::default(),
+            data_warnings: 0,
+            truncated_range: None,
+            r_distribution: None,
+            trade_distributions: None,
+            tail_metrics: entry.tail_metrics.clone(),
+            style: None,
+            friction: entry.robustness.as_ref().and_then(|r| r.friction.clone()),
+            benchmark: entry.robustness.as_ref().and_then(|r| r.benchmark.clone()),
+            signal_value: entry.robustness.as_ref().and_then(|r| r.signal_value.clone()),
+            sensitivity: entry.robustness.as_ref().and_then(|r| r.sensitivity.clone()),
+            fdr: entry.weakest_fdr().copied(),
+            equity_sparkline: entry.equity_sparkline.clone(),
+            artifact_dir: None,
+            cluster_id: entry.cluster_id,
+            diversification: entry.diversification_score,
+            cluster_leader,
+            provenance: entry.provenance.clone(),
+            attribution: BTreeMap::new(),
+            replay: None,
+            trades: None,
+            result: None,
+        }
+    }
+
+    /// Entry for a per-symbol leaderboard row of a saved YOLO session.
+    ///
+    /// Drill-down needs `entry.artifact_dir`; without it only metrics show.
+    pub fn from_session_entry(rank: usize, entry: &LeaderboardEntry) -> Self {
+        let result = &entry.result;
+        Self {
+            rank,
+            signal_type: result.config.signal.component_type.clone(),
+            pm_type: result.config.position_manager.component_type.clone(),
+            exec_type: result.config.execution_model.component_type.clone(),
+            filter_type: result.config.signal_filter.component_type.clone(),
+            symbol: result.symbol.clone(),
+            sharpe: result.metrics.sharpe,
+            cagr: result.metrics.cagr,
+            max_drawdown: result.metrics.max_drawdown,
+            win_rate: result.metrics.win_rate,
+            profit_factor: result.metrics.profit_factor,
+            trade_count: result.metrics.trade_count,
+            config: result.config.clone(),
+            fitness_score: entry.fitness_score,
+            session_id: entry.session_id.clone(),
+            metrics: result.metrics.clone(),
+            stickiness: result.stickiness_report(),
+            data_warnings: result.data_quality_warnings.len(),
+            truncated_range: result.truncated_range.clone(),
+            r_distribution: Some(result.r_distribution.clone()),
+            trade_distributions: Some(result.trade_distributions.clone()),
+            tail_metrics: result.tail_metrics.clone(),
+            style: result.style.clone(),
+            friction: None,
+            benchmark: None,
+            signal_value: None,
+            sensitivity: None,
+            fdr: entry.fdr,
+            equity_sparkline: result.equity_sparkline.clone(),
+            artifact_dir: entry.artifact_dir.clone(),
+            cluster_id: None,
+            diversification: None,
+            cluster_leader: true,
+            provenance: None,
+            attribution: result.attribution.totals(),
+            replay: None,
+            trades: None,
+            result: None,
+        }
+    }
+
+    /// Re-run of another entry with hand-edited params (see `NudgeState`).
+    pub fn is_manual_variant(&self) -> bool {
+        self.result.as_ref().is_some_and(|r| r.provenance.is_some())
+    }
+
+    /// Chart title for this entry.
+    pub fn chart_label(&self) -> String {
+        format!(
+            "{} | {} | Sharpe: {:.2}",
+            self.symbol, self.signal_type, self.sharpe
+        )
+    }
+}
+
+/// Cross-symbol aggregate: avg Sharpe, geometric-mean CAGR, worst drawdown,
+/// and per-symbol means for everything else.
+fn aggregate_metrics(entry: &CrossSymbolEntry) -> PerformanceMetrics {
+    let per_symbol: Vec<&PerformanceMetrics> = entry.symbol_metrics.values().collect();
+    let n = per_symbol.len().max(1) as f64;
+    let mean = |f: fn(&PerformanceMetrics) -> f64| per_symbol.iter().map(|m| f(m)).sum::<f64>() / n;
+    PerformanceMetrics {
+        total_return: mean(|m| m.total_return),
+        cagr: entry.geo_mean_cagr,
+        sharpe: entry.avg_sharpe,
+        sortino: mean(|m| m.sortino),
+        calmar: mean(|m| m.calmar),
+        max_drawdown: entry.worst_max_drawdown,
+        win_rate: mean(|m| m.win_rate),
+        profit_factor: mean(|m| m.profit_factor),
+        trade_count: entry.avg_trade_count.round() as usize,
+        turnover: mean(|m| m.turnover),
+        max_consecutive_wins: per_symbol
+            .iter()
+            .map(|m| m.max_consecutive_wins)
+            .max()
+            .unwrap_or(0),
+        max_consecutive_losses: per_symbol
+            .iter()
+            .map(|m| m.max_consecutive_losses)
+            .max()
+            .unwrap_or(0),
+        avg_losing_streak: mean(|m| m.avg_losing_streak),
+        time_in_market: mean(|m| m.time_in_market),
+        avg_exposure: mean(|m| m.avg_exposure),
+        invested_return: mean(|m| m.invested_return),
+        idle_return: mean(|m| m.idle_return),
+        exposure_adjusted_cagr: mean(|m| m.exposure_adjusted_cagr),
+    }
 }
 
+/// Session id given to runs loaded from saved artifacts.
+pub const SAVED_RUNS_SESSION: &str = "saved";
+
 /// Results panel state.
 pub struct ResultsPanelState {
     pub entries: Vec<LeaderboardDisplayEntry>,
@@ -385,6 +694,30 @@ pub struct ResultsPanelState {
     pub risk_profile: RiskProfile,
     pub scroll_offset: usize,
     pub current_session_id: String,
+    /// Show only the best entry of each overlap cluster.
+    pub diversified_view: bool,
+    /// Worst-days table expanded in the detail overlay (collapsed on open).
+    pub worst_days_expanded: bool,
+    /// Text in the filter overlay, parsed on every keystroke.
+    pub filter_input: String,
+    /// Last filter that parsed; rows must match it to be shown.
+    pub filter: Option<ResultsQuery>,
+    /// Why `filter_input` does not parse, if it doesn't.
+    pub filter_error: Option<String>,
+    /// Text in the date-window prompt (`FROM..TO`).
+    pub window_input: String,
+    /// Why the last window couldn't be applied.
+    pub window_error: Option<String>,
+    /// Detail metrics recomputed over a date window, until the detail view
+    /// closes.
+    pub window: Option<DateWindow>,
+}
+
+/// A results entry's run sliced to a date window (see `BacktestResult::slice`).
+#[derive(Debug, Clone)]
+pub struct DateWindow {
+    pub entry: usize,
+    pub slice: ResultSlice,
 }
 
 impl ResultsPanelState {
@@ -396,28 +729,111 @@ impl ResultsPanelState {
             risk_profile: RiskProfile::default(),
             scroll_offset: 0,
             current_session_id: session_id,
+            diversified_view: false,
+            worst_days_expanded: false,
+            filter_input: String::new(),
+            filter: None,
+            filter_error: None,
+            window_input: String::new(),
+            window_error: None,
+            window: None,
+        }
+    }
+
+    /// Indices into `entries` of the rows currently shown.
+    pub fn visible_indices(&self) -> Vec<usize> {
+        (0..self.entries.len())
+            .filter(|&i| !self.diversified_view || self.entries[i].cluster_leader)
+            .filter(|&i| self.filter.as_ref().map_or(true, |q| q.matches(&self.entries[i])))
+            .collect()
+    }
+
+    /// Indices of the manual variants of entry `index`, oldest first.
+    pub fn variants_of(&self, index: usize) -> Vec<usize> {
+        let Some(base) = self.entries.get(index) else {
+            return Vec::new();
+        };
+        let parent = base.config.full_hash();
+        (0..self.entries.len())
+            .filter(|&i| {
+                let entry = &self.entries[i];
+                entry.is_manual_variant()
+                    && entry.symbol == base.symbol
+                    && entry.provenance.as_ref().is_some_and(|p| p.parent_full_hash == parent)
+            })
+            .collect()
+    }
+
+    /// Replace the filter text and apply it if it parses. A query that does
+    /// not parse leaves the previous filter in place.
+    pub fn set_filter_input(&mut self, text: String) {
+        match ResultsQuery::parse(&text) {
+            Ok(query) => {
+                let query = (!query.is_empty()).then_some(query);
+                if query != self.filter {
+                    self.filter = query;
+                    self.cursor = 0;
+                    self.scroll_offset = 0;
+                }
+                self.filter_error = None;
+            }
+            Err(e) => self.filter_error = Some(e.to_string()),
         }
+        self.filter_input = text;
+    }
+
+    /// Drop the filter and show every row again.
+    pub fn clear_filter(&mut self) {
+        self.set_filter_input(String::new());
+    }
+
+    /// Index into `entries` of the row under the cursor.
+    pub fn selected_index(&self) -> Option<usize> {
+        self.visible_indices().get(self.cursor).copied()
     }
 }
 
+/// Step applied by the dashboard's `+`/`-` explore nudges.
+pub const EXPLORE_NUDGE: f64 = 0.05;
+
 /// Sweep panel state — YOLO configuration.
 pub struct SweepPanelState {
     pub config: YoloConfig,
     pub cursor: usize,
     pub yolo_running: bool,
     pub last_progress: Option<YoloProgress>,
+    /// Sliders shared with the worker; the YOLO loop re-reads them each iteration.
+    pub tunables: Arc<RwLock<YoloTunables>>,
 }
 
 impl SweepPanelState {
     pub fn new() -> Self {
+        let config = YoloConfig::default();
+        let tunables = Arc::new(RwLock::new(YoloTunables::from_config(&config)));
         Self {
-            config: YoloConfig::default(),
+            config,
             cursor: 0,
             yolo_running: false,
             last_progress: None,
+            tunables,
+        }
+    }
+
+    /// Push the config's slider values to the shared tunables.
+    pub fn sync_tunables(&self) {
+        if let Ok(mut t) = self.tunables.write() {
+            *t = YoloTunables::from_config(&self.config);
         }
     }
 
+    /// Nudge `structural_explore` in both the config and the live tunables.
+    pub fn nudge_explore(&mut self, delta: f64) {
+        let mut t = YoloTunables::from_config(&self.config);
+        t.nudge_explore(delta);
+        self.config.structural_explore = t.structural_explore;
+        self.sync_tunables();
+    }
+
     /// Number of configurable settings.
     pub fn setting_count(&self) -> usize {
         12 // jitter, structural, start_date, end_date, initial_capital,
@@ -430,6 +846,12 @@ impl SweepPanelState {
 pub struct ChartPanelState {
     pub equity_curve: Option<Vec<f64>>,
     pub label: String,
+    /// Results entry shown (or being loaded) in the chart.
+    pub viewing: Option<usize>,
+    /// Set while the viewed entry's curve loads in the background.
+    pub loading_since: Option<Instant>,
+    /// Draw the drawdown strip under the curve (when the panel is tall enough).
+    pub show_drawdown: bool,
 }
 
 impl ChartPanelState {
@@ -437,6 +859,147 @@ impl ChartPanelState {
         Self {
             equity_curve: None,
             label: String::new(),
+            viewing: None,
+            loading_since: None,
+            show_drawdown: true,
+        }
+    }
+}
+
+/// Maximum number of equity curves held in memory.
+pub const EQUITY_CACHE_CAP: usize = 8;
+
+/// LRU cache of equity curves keyed by results entry index.
+pub struct EquityCache {
+    cap: usize,
+    curves: HashMap<usize, Vec<f64>>,
+    /// Entry indices, least recently used first.
+    order: VecDeque<usize>,
+}
+
+impl EquityCache {
+    pub fn new(cap: usize) -> Self {
+        Self {
+            cap,
+            curves: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.curves.len()
+    }
+
+    #[cfg(test)]
+    fn contains(&self, index: usize) -> bool {
+        self.curves.contains_key(&index)
+    }
+
+    /// Get a curve, marking it most recently used.
+    pub fn get(&mut self, index: usize) -> Option<&Vec<f64>> {
+        if self.curves.contains_key(&index) {
+            self.touch(index);
+        }
+        self.curves.get(&index)
+    }
+
+    /// Insert a curve, then evict least recently used curves while over the
+    /// cap. Only indices for which `evictable` returns true are dropped, so
+    /// the cache can exceed the cap when everything else is pinned.
+    pub fn insert(&mut self, index: usize, curve: Vec<f64>, evictable: impl Fn(usize) -> bool) {
+        self.curves.insert(index, curve);
+        self.touch(index);
+        while self.curves.len() > self.cap {
+            let Some(pos) = self.order.iter().position(|&i| i != index && evictable(i)) else {
+                break;
+            };
+            if let Some(victim) = self.order.remove(pos) {
+                self.curves.remove(&victim);
+            }
+        }
+    }
+
+    fn touch(&mut self, index: usize) {
+        self.order.retain(|&i| i != index);
+        self.order.push_back(index);
+    }
+}
+
+/// One leaderboard of an opened session.
+pub struct SessionBoard {
+    pub title: String,
+    pub entries: Vec<LeaderboardDisplayEntry>,
+}
+
+/// A past YOLO session opened in the session browser.
+pub struct OpenSession {
+    pub session_id: String,
+    /// Cross-symbol champions first, then one board per symbol.
+    pub boards: Vec<SessionBoard>,
+    pub board: usize,
+    pub cursor: usize,
+}
+
+impl OpenSession {
+    pub fn from_result(result: &YoloResult) -> Self {
+        let mut boards = vec![SessionBoard {
+            title: "Cross-symbol".to_string(),
+            entries: champion_entries(&result.cross_leaderboard),
+        }];
+        let mut symbols: Vec<&String> = result.leaderboards.keys().collect();
+        symbols.sort();
+        for symbol in symbols {
+            let entries = result.leaderboards[symbol]
+                .entries()
+                .iter()
+                .enumerate()
+                .map(|(i, e)| LeaderboardDisplayEntry::from_session_entry(i + 1, e))
+                .collect();
+            boards.push(SessionBoard {
+                title: symbol.clone(),
+                entries,
+            });
+        }
+        Self {
+            session_id: result.session_id.clone(),
+            boards,
+            board: 0,
+            cursor: 0,
+        }
+    }
+
+    /// Switch to the next (`step > 0`) or previous board, wrapping around.
+    pub fn cycle_board(&mut self, step: isize) {
+        let n = self.boards.len() as isize;
+        self.board = (self.board as isize + step).rem_euclid(n) as usize;
+        self.cursor = 0;
+    }
+
+    pub fn current(&self) -> &SessionBoard {
+        &self.boards[self.board]
+    }
+
+    /// Row under the cursor on the current board.
+    pub fn selected(&self) -> Option<&LeaderboardDisplayEntry> {
+        self.current().entries.get(self.cursor)
+    }
+}
+
+/// Session browser state: the list of saved sessions and the opened one.
+pub struct SessionBrowserState {
+    /// Sessions found under the sessions directory, oldest first.
+    pub sessions: Vec<SessionSummary>,
+    pub cursor: usize,
+    pub open: Option<OpenSession>,
+}
+
+impl SessionBrowserState {
+    pub fn new() -> Self {
+        Self {
+            sessions: Vec::new(),
+            cursor: 0,
+            open: None,
         }
     }
 }
@@ -449,6 +1012,257 @@ pub enum Overlay {
     Detail(usize),     // index into results entries
     ErrorHistory,
     Search,
+    YoloDashboard,
+    Sessions,
+    CacheManager,
+    /// Bar replay of a position of results entry `entry` (see `AppState::replay`).
+    Replay,
+    /// Leaderboard filter query (see `crate::query`).
+    Filter,
+    /// Note and tag editor (see `AppState::note_editor`).
+    Note,
+    /// Date-window prompt over the detail view of results entry `idx` (see
+    /// `ResultsPanelState::window`).
+    DateWindow(usize),
+    /// Parameter nudging over a detail view (see `AppState::nudge`).
+    Nudge,
+}
+
+/// What a note edit applies to: a results entry's run, or one of its trades.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoteTarget {
+    pub entry: usize,
+    /// Entry bar of the trade; None for the run itself.
+    pub trade: Option<usize>,
+}
+
+/// Note and tag editor (`n` on a run, `N` on a replayed trade).
+#[derive(Debug, Clone)]
+pub struct NoteEditor {
+    pub target: NoteTarget,
+    pub text: String,
+    pub tags: BTreeSet<AnnotationTag>,
+    /// Keys go to the tag picker instead of the text.
+    pub tags_focused: bool,
+    /// Index into `AnnotationTag::ALL`.
+    pub tag_cursor: usize,
+    /// Overlay the editor was opened from, shown again when it closes.
+    pub return_to: Overlay,
+}
+
+impl NoteEditor {
+    pub fn toggle_tag(&mut self) {
+        let tag = AnnotationTag::ALL[self.tag_cursor];
+        if !self.tags.remove(&tag) {
+            self.tags.insert(tag);
+        }
+    }
+
+    pub fn move_tag_cursor(&mut self, delta: isize) {
+        let len = AnnotationTag::ALL.len() as isize;
+        self.tag_cursor = (self.tag_cursor as isize + delta).rem_euclid(len) as usize;
+    }
+}
+
+/// Replay drill-down: steps through one position's recorded bars.
+#[derive(Debug, Clone)]
+pub struct ReplayState {
+    /// Results entry the replay belongs to; Esc returns to its detail view.
+    pub entry: usize,
+    pub log: Arc<ReplayLog>,
+    /// Index into `log.positions`.
+    pub position: usize,
+    /// Index into the position's bars; bars after it are not drawn yet.
+    pub cursor: usize,
+    /// Indicator drawn over the price pane, indexing `indicator_names()`.
+    pub indicator: Option<usize>,
+}
+
+impl ReplayState {
+    /// Opens on the first losing position, if any, at its entry bar.
+    pub fn new(entry: usize, log: Arc<ReplayLog>) -> Self {
+        let position = log.positions.iter().position(|p| p.is_loser()).unwrap_or(0);
+        Self {
+            entry,
+            log,
+            position,
+            cursor: 0,
+            indicator: None,
+        }
+    }
+
+    pub fn current(&self) -> &PositionReplay {
+        &self.log.positions[self.position]
+    }
+
+    /// Move the cursor by `delta` bars, clamped to the holding window.
+    pub fn step(&mut self, delta: isize) {
+        let last = self.current().bars.len().saturating_sub(1);
+        self.cursor = self.cursor.saturating_add_signed(delta).min(last);
+    }
+
+    pub fn jump_to_entry(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn jump_to_exit(&mut self) {
+        self.cursor = self.current().exit_index();
+    }
+
+    /// Switch to the previous/next position, starting at its entry bar.
+    pub fn cycle_position(&mut self, delta: isize) {
+        let n = self.log.positions.len() as isize;
+        self.position = (self.position as isize + delta).rem_euclid(n) as usize;
+        self.cursor = 0;
+        self.indicator = None;
+    }
+
+    /// Switch to the next losing position after the current one, wrapping.
+    /// Returns false if the run has no losing position.
+    pub fn next_loser(&mut self) -> bool {
+        let n = self.log.positions.len();
+        let Some(offset) = (1..=n).find(|i| self.log.positions[(self.position + i) % n].is_loser())
+        else {
+            return false;
+        };
+        self.position = (self.position + offset) % n;
+        self.cursor = 0;
+        self.indicator = None;
+        true
+    }
+
+    /// Cycle the indicator overlay: none → each recorded indicator → none.
+    pub fn cycle_indicator(&mut self) {
+        let count = self.indicator_names().len();
+        self.indicator = match self.indicator {
+            None if count > 0 => Some(0),
+            Some(i) if i + 1 < count => Some(i + 1),
+            _ => None,
+        };
+    }
+
+    pub fn indicator_names(&self) -> Vec<String> {
+        self.current().indicator_names()
+    }
+
+    /// Name of the indicator drawn over the price pane, if any.
+    pub fn indicator_name(&self) -> Option<String> {
+        self.indicator.and_then(|i| self.indicator_names().get(i).cloned())
+    }
+}
+
+/// A nudgeable param of a [`NudgeState`]: one of the top-level component's
+/// params that has a range in the pool.
+#[derive(Debug, Clone)]
+pub struct NudgeRow {
+    pub slot: ComponentSlot,
+    pub component: String,
+    pub range: ParamRange,
+}
+
+/// Parameter nudging (`p` in the detail view): step the params of a run up
+/// and down, then re-run it as a manual variant.
+///
+/// Steps come from the pool's `ParamRange`s. A nudge past the range or one
+/// that breaks a cross-parameter constraint is refused, with the reason in
+/// `error`. Composite children keep their params.
+#[derive(Debug, Clone)]
+pub struct NudgeState {
+    /// Results entry being varied; Esc returns to its detail view.
+    pub entry: usize,
+    /// Run the variant re-runs with the edited config.
+    pub base: Arc<BacktestResult>,
+    /// The base config with the edits so far.
+    pub config: StrategyConfig,
+    pub rows: Vec<NudgeRow>,
+    pub cursor: usize,
+    /// Why the last nudge or re-run was refused.
+    pub error: Option<String>,
+}
+
+impl NudgeState {
+    pub fn new(entry: usize, base: Arc<BacktestResult>, pool: &ComponentPool) -> Self {
+        let config = base.config.clone();
+        let rows = ComponentSlot::ALL
+            .into_iter()
+            .flat_map(|slot| {
+                let component = slot.component(&config);
+                component.params.keys().filter_map(move |param| {
+                    let range = pool.param_range(slot, &component.component_type, param)?;
+                    Some(NudgeRow {
+                        slot,
+                        component: component.component_type.clone(),
+                        range: range.clone(),
+                    })
+                })
+            })
+            .collect();
+        Self {
+            entry,
+            base,
+            config,
+            rows,
+            cursor: 0,
+            error: None,
+        }
+    }
+
+    /// Value of `row`'s param in the edited config.
+    pub fn value(&self, row: &NudgeRow) -> f64 {
+        row.slot.component(&self.config).params[&row.range.name]
+    }
+
+    /// Value of `row`'s param in the base run.
+    pub fn base_value(&self, row: &NudgeRow) -> f64 {
+        row.slot.component(&self.base.config).params[&row.range.name]
+    }
+
+    pub fn move_cursor(&mut self, delta: isize) {
+        let last = self.rows.len().saturating_sub(1);
+        self.cursor = self.cursor.saturating_add_signed(delta).min(last);
+    }
+
+    /// Step the param under the cursor up (1) or down (-1) by its nudge step,
+    /// stopping at the range. Refused, with the reason in `error`, when the
+    /// param is already at the end of its range or the new value breaks a
+    /// cross-parameter constraint.
+    pub fn nudge(&mut self, direction: i32) {
+        let Some(row) = self.rows.get(self.cursor) else {
+            return;
+        };
+        let range = &row.range;
+        let current = self.value(row);
+        let stepped = current + range.nudge_step() * direction as f64;
+        // Trim float noise from repeated steps
+        let value = ((stepped * 1e6).round() / 1e6).clamp(range.min, range.max);
+        if value == current {
+            let end = if direction > 0 { "maximum" } else { "minimum" };
+            self.error = Some(format!(
+                "{} {} is at its {end} (range {}..={})",
+                row.component, range.name, range.min, range.max
+            ));
+            return;
+        }
+
+        let mut config = self.config.clone();
+        row.slot
+            .component_mut(&mut config)
+            .params
+            .insert(range.name.clone(), value);
+        let violations = constraint_violations(&config);
+        if violations.is_empty() {
+            self.config = config;
+            self.error = None;
+        } else {
+            let messages: Vec<String> = violations.iter().map(|v| v.message.clone()).collect();
+            self.error = Some(messages.join("; "));
+        }
+    }
+
+    /// Params edited so far, against the base run.
+    pub fn edits(&self) -> Vec<ConfigChange> {
+        self.base.config.diff(&self.config)
+    }
 }
 
 /// Top-level application state.
@@ -463,23 +1277,51 @@ pub struct AppState {
     pub sweep: SweepPanelState,
     pub results: ResultsPanelState,
     pub chart: ChartPanelState,
+    pub equity_cache: EquityCache,
+    pub sessions: SessionBrowserState,
+    /// Open bar replay (shown by `Overlay::Replay`).
+    pub replay: Option<ReplayState>,
+    /// Open note editor (shown by `Overlay::Note`).
+    pub note_editor: Option<NoteEditor>,
+    /// Open parameter nudging (shown by `Overlay::Nudge`).
+    pub nudge: Option<NudgeState>,
+    /// Notes and tags on runs and trades, from the results directory.
+    pub annotations: Annotations,
 
     // Worker communication
     pub worker_tx: Sender<WorkerCommand>,
     pub worker_rx: Receiver<WorkerResponse>,
     pub cancel: Arc<AtomicBool>,
+    /// Equity loader thread (see `worker::spawn_equity_loader`).
+    pub equity_tx: Option<Sender<EquityLoadRequest>>,
 
     // Cross-cutting
     pub status_message: Option<(String, StatusLevel)>,
     pub error_history: VecDeque<ErrorRecord>,
     pub error_scroll: usize,
     pub overlay: Overlay,
+    /// Cheat sheet for the current context (`?`), drawn over any overlay.
+    pub show_help: bool,
     pub search_input: String,
+    /// Something drawn changed since the last frame. The main loop marks it
+    /// after every handled event and skips `terminal.draw` while it is clear.
+    pub dirty: bool,
 
     // Paths
     pub cache_dir: PathBuf,
+    /// Each YOLO session writes its history and `session.json` to a
+    /// subdirectory of this.
+    pub sessions_dir: PathBuf,
     #[allow(dead_code)]
     pub state_path: PathBuf,
+    /// Where a theme switched with `T` is saved; None keeps it in memory.
+    pub tui_config_path: Option<PathBuf>,
+    /// Trade and leaderboard CSV exports go here (`exports_dir` in `tui.toml`).
+    pub exports_dir: PathBuf,
+    /// Results directory whose annotations file note edits are saved to.
+    /// None until loaded, or when the file could not be read, so a damaged
+    /// file is never overwritten.
+    pub annotations_dir: Option<PathBuf>,
 }
 
 impl AppState {
@@ -500,16 +1342,29 @@ impl AppState {
             sweep: SweepPanelState::new(),
             results: ResultsPanelState::new(session_id),
             chart: ChartPanelState::new(),
+            equity_cache: EquityCache::new(EQUITY_CACHE_CAP),
+            sessions: SessionBrowserState::new(),
+            replay: None,
+            note_editor: None,
+            nudge: None,
+            annotations: Annotations::default(),
             worker_tx,
             worker_rx,
             cancel,
+            equity_tx: None,
             status_message: None,
             error_history: VecDeque::with_capacity(50),
             error_scroll: 0,
             overlay: Overlay::None,
+            show_help: false,
             search_input: String::new(),
+            dirty: true,
             cache_dir,
+            sessions_dir: PathBuf::from("sessions"),
             state_path,
+            tui_config_path: None,
+            exports_dir: PathBuf::from("exports"),
+            annotations_dir: None,
         }
     }
 
@@ -528,6 +1383,17 @@ impl AppState {
         self.status_message = Some((message, StatusLevel::Error));
     }
 
+    /// Request a redraw on the next tick.
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Whether the next tick must draw: state changed, or the chart spinner
+    /// is animating.
+    pub fn needs_redraw(&self) -> bool {
+        self.dirty || self.chart.loading_since.is_some()
+    }
+
     /// Set an info status message.
     pub fn set_status(&mut self, msg: impl Into<String>) {
         self.status_message = Some((msg.into(), StatusLevel::Info));
@@ -537,82 +1403,1645 @@ impl AppState {
     pub fn set_warning(&mut self, msg: impl Into<String>) {
         self.status_message = Some((msg.into(), StatusLevel::Warning));
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    #[test]
-    fn panel_cycle() {
-        assert_eq!(Panel::Data.next(), Panel::Strategy);
-        assert_eq!(Panel::Help.next(), Panel::Data);
-        assert_eq!(Panel::Data.prev(), Panel::Help);
-        assert_eq!(Panel::Strategy.prev(), Panel::Data);
+    /// Switch to the next built-in theme and remember it in `tui.toml`.
+    pub fn cycle_theme(&mut self) {
+        let next = theme::current().next();
+        theme::set(next);
+        if let Some(path) = &self.tui_config_path {
+            let mut config = persistence::load_tui_config(path);
+            config.theme = Some(next);
+            if let Err(err) = persistence::save_tui_config(path, &config) {
+                self.set_warning(format!("Theme: {next} (not saved: {err})"));
+                return;
+            }
+        }
+        self.set_status(format!("Theme: {next}"));
     }
 
-    #[test]
-    fn panel_from_index() {
-        for i in 0..6 {
-            let p = Panel::from_index(i).unwrap();
-            assert_eq!(p.index(), i);
+    /// Download `symbols` over `start..=end` on the worker. Each symbol's
+    /// progress streams into `data.downloads`.
+    pub fn fetch_symbols(&mut self, symbols: Vec<String>, start: NaiveDate, end: NaiveDate) {
+        if symbols.is_empty() {
+            return;
         }
-        assert!(Panel::from_index(6).is_none());
+        if self.data.fetch_in_progress {
+            self.set_warning("A download is already running");
+            return;
+        }
+        for symbol in &symbols {
+            self.data.downloads.insert(symbol.clone(), DownloadStatus::Pending);
+        }
+        self.data.fetch_in_progress = true;
+        self.data.fetch_done = 0;
+        self.data.fetch_total = symbols.len();
+        self.data.last_fetch_range = Some((start, end));
+        let _ = self.worker_tx.send(WorkerCommand::FetchData {
+            symbols,
+            start,
+            end,
+            cache_dir: self.cache_dir.clone(),
+        });
+        self.set_status("Fetching data...");
     }
 
-    #[test]
-    fn error_history_caps_at_50() {
-        let (tx, _rx) = std::sync::mpsc::channel();
-        let (_tx2, rx2) = std::sync::mpsc::channel();
-        let cancel = Arc::new(AtomicBool::new(false));
-        let mut app = AppState::new(
-            tx,
-            rx2,
-            cancel,
-            PathBuf::from("."),
-            PathBuf::from("."),
-        );
-        for i in 0..60 {
-            app.push_error(ErrorCategory::Other, format!("error {i}"), String::new());
+    /// Rescan the cache directory for the cache manager and the tree's
+    /// cached markers.
+    pub fn refresh_cache_listing(&mut self) {
+        let cache = ParquetCache::new(&self.cache_dir);
+        match cache.entries() {
+            Ok(entries) => self.data.cache_entries = entries,
+            Err(e) => self.push_error(
+                ErrorCategory::Data,
+                format!("Failed to list cache: {e}"),
+                self.cache_dir.display().to_string(),
+            ),
+        }
+        let tickers = self.data.universe.all_tickers();
+        for status in cache.status(&tickers) {
+            self.data.cache_status.insert(status.symbol, status.cached);
         }
-        assert_eq!(app.error_history.len(), 50);
-        assert!(app.error_history[0].message.contains("59"));
     }
 
-    #[test]
-    fn strategy_builds_valid_config() {
-        let state = StrategyPanelState::new();
-        let config = state.to_strategy_config();
-        assert!(!config.signal.component_type.is_empty());
-        assert!(!config.position_manager.component_type.is_empty());
-        assert!(!config.execution_model.component_type.is_empty());
-        assert!(!config.signal_filter.component_type.is_empty());
+    /// Open the cache manager on a fresh scan of the cache.
+    pub fn open_cache_manager(&mut self) {
+        self.refresh_cache_listing();
+        let rows = self.data.cache_rows().len();
+        let manager = &mut self.data.manager;
+        manager.cursor = manager.cursor.min(rows.saturating_sub(1));
+        self.overlay = Overlay::CacheManager;
     }
 
-    #[test]
-    fn data_panel_visible_rows() {
-        let universe = Universe::default_us();
-        let state = DataPanelState::new(universe);
-        // All sectors expanded: 6 sectors + all their tickers
-        let total = state.visible_row_count();
-        assert!(total > 30); // 6 sectors + ~54 tickers
+    /// Range the cache manager downloads over.
+    pub fn download_range(&self) -> (NaiveDate, NaiveDate) {
+        self.data
+            .manager
+            .range
+            .unwrap_or((self.sweep.config.start_date, self.sweep.config.end_date))
     }
 
-    #[test]
-    fn data_panel_cursor_item() {
-        let universe = Universe::default_us();
-        let mut state = DataPanelState::new(universe);
-        // First row should be a sector
-        state.cursor.row = 0;
-        match state.cursor_item() {
-            Some(TreeItem::Sector(_)) => {}
-            other => panic!("Expected Sector, got {:?}", other),
+    /// Move the download range's start (`end == false`) or end by whole
+    /// years, keeping start before end.
+    pub fn shift_download_range(&mut self, end: bool, years: i32) {
+        let (mut start_date, mut end_date) = self.download_range();
+        let date = if end { &mut end_date } else { &mut start_date };
+        let months = Months::new(12 * years.unsigned_abs());
+        let shifted = if years < 0 {
+            date.checked_sub_months(months)
+        } else {
+            date.checked_add_months(months)
+        };
+        if let Some(shifted) = shifted {
+            *date = shifted;
         }
-        // Second row (sector expanded) should be a ticker
-        state.cursor.row = 1;
-        match state.cursor_item() {
-            Some(TreeItem::Ticker(_, _)) => {}
-            other => panic!("Expected Ticker, got {:?}", other),
+        if start_date < end_date {
+            self.data.manager.range = Some((start_date, end_date));
         }
     }
+
+    /// Download the marked symbols, or the one under the cursor if none are
+    /// marked.
+    pub fn download_marked(&mut self) {
+        if self.data.fetch_in_progress {
+            self.set_warning("A download is already running");
+            return;
+        }
+        let mut symbols: Vec<String> = self.data.manager.marked.iter().cloned().collect();
+        if symbols.is_empty() {
+            symbols.extend(self.data.cache_rows().into_iter().nth(self.data.manager.cursor));
+        }
+        let (start, end) = self.download_range();
+        self.data.manager.marked.clear();
+        self.fetch_symbols(symbols, start, end);
+    }
+
+    /// Re-download the symbols that failed in the last batch, over its range.
+    pub fn retry_failed_downloads(&mut self) {
+        let failed: Vec<String> = self
+            .data
+            .downloads
+            .iter()
+            .filter(|(_, s)| matches!(s, DownloadStatus::Failed(_)))
+            .map(|(symbol, _)| symbol.clone())
+            .collect();
+        if failed.is_empty() {
+            self.set_status("No failed downloads to retry");
+            return;
+        }
+        let (start, end) = self.data.last_fetch_range.unwrap_or_else(|| self.download_range());
+        self.fetch_symbols(failed, start, end);
+    }
+
+    /// Launch a YOLO session on the selected tickers.
+    pub fn start_yolo(&mut self) {
+        if self.sweep.yolo_running {
+            return;
+        }
+        if self.data.selected.is_empty() {
+            self.set_warning("Select tickers in Data panel first");
+            return;
+        }
+        let symbols: Vec<String> = self.data.selected.iter().cloned().collect();
+        let mut config = self.sweep.config.clone();
+        config.enforce_thread_constraints();
+        let session_dir = self.sessions_dir.join(format!(
+            "yolo_{}",
+            chrono::Local::now().format("%Y%m%d_%H%M%S")
+        ));
+        config.history_path = Some(session_dir.join("history.jsonl"));
+        // Leaderboard files are shared by every session
+        config.leaderboard_dir = Some(self.sessions_dir.join(trendlab_runner::LEADERBOARDS_DIR));
+        self.sweep.sync_tunables();
+        let _ = self.worker_tx.send(WorkerCommand::StartYolo {
+            config: Box::new(config),
+            symbols,
+            cache_dir: self.cache_dir.clone(),
+            tunables: self.sweep.tunables.clone(),
+        });
+        self.sweep.yolo_running = true;
+        self.sweep.last_progress = None;
+        self.set_status("YOLO mode started");
+    }
+
+    /// Ask a running YOLO session to stop after its current iteration.
+    pub fn stop_yolo(&mut self) {
+        if self.sweep.yolo_running {
+            self.cancel.store(true, Ordering::Relaxed);
+            self.set_warning("Stopping YOLO...");
+        }
+    }
+
+    /// Add saved runs to the results list, from their manifest headline stats.
+    ///
+    /// Equity curves are not read here; see `show_equity`.
+    pub fn add_saved_runs(&mut self, summaries: Vec<ArtifactSummary>) {
+        for summary in summaries {
+            let rank = self.results.entries.len() + 1;
+            self.results
+                .entries
+                .push(LeaderboardDisplayEntry::from_summary(rank, summary));
+        }
+    }
+
+    /// Add the runs of a startup scan and report the ones that failed to
+    /// load: one error-history entry per skipped path, then a notice
+    /// summarizing the counts.
+    pub fn load_saved_runs(&mut self, scan: ArtifactScan) {
+        let line = scan.summary_line();
+        for skip in &scan.skipped {
+            self.push_error(
+                ErrorCategory::Data,
+                format!(
+                    "Skipped saved run ({}): {}",
+                    skip.reason.label(),
+                    skip.dir.display()
+                ),
+                skip.error.clone(),
+            );
+        }
+        if !scan.skipped.is_empty() {
+            self.set_warning(format!("Saved runs: {line} — see error history"));
+        } else if !scan.summaries.is_empty() {
+            self.set_status(format!("Saved runs: {line}"));
+        }
+        self.add_saved_runs(scan.summaries);
+    }
+
+    /// Add YOLO cross-symbol champions to the results list, ranked after
+    /// existing entries.
+    pub fn add_yolo_champions(&mut self, champions: Vec<LeaderboardDisplayEntry>) {
+        for mut entry in champions {
+            entry.rank = self.results.entries.len() + 1;
+            self.results.entries.push(entry);
+        }
+    }
+
+    /// Open the session browser on a fresh scan of the sessions directory.
+    pub fn open_session_browser(&mut self) {
+        self.sessions.sessions = scan_sessions(&self.sessions_dir);
+        self.sessions.cursor = self
+            .sessions
+            .cursor
+            .min(self.sessions.sessions.len().saturating_sub(1));
+        self.sessions.open = None;
+        self.overlay = Overlay::Sessions;
+    }
+
+    /// Load the session under the browser cursor and show its leaderboards.
+    pub fn open_selected_session(&mut self) {
+        let Some(summary) = self.sessions.sessions.get(self.sessions.cursor) else {
+            return;
+        };
+        let dir = summary.dir.clone();
+        match YoloResult::load(&dir) {
+            Ok(result) => self.sessions.open = Some(OpenSession::from_result(&result)),
+            Err(e) => self.push_error(
+                ErrorCategory::Data,
+                format!("Failed to load session: {e}"),
+                dir.display().to_string(),
+            ),
+        }
+    }
+
+    /// Open the detail drill-down for the selected row of the opened session.
+    ///
+    /// The row is added to the results list (once) so the chart can load its
+    /// equity curve. Rows without saved artifacts stay metrics-only.
+    pub fn drill_into_session_entry(&mut self) {
+        let Some(entry) = self.sessions.open.as_ref().and_then(|s| s.selected()) else {
+            return;
+        };
+        let Some(dir) = entry.artifact_dir.clone() else {
+            self.set_warning("No saved artifacts for this entry; drill-down unavailable");
+            return;
+        };
+        let index = match self
+            .results
+            .entries
+            .iter()
+            .position(|e| e.artifact_dir.as_ref() == Some(&dir))
+        {
+            Some(index) => index,
+            None => {
+                let mut entry = entry.clone();
+                entry.rank = self.results.entries.len() + 1;
+                self.results.entries.push(entry);
+                self.results.entries.len() - 1
+            }
+        };
+        self.overlay = Overlay::Detail(index);
+        self.results.worst_days_expanded = false;
+        self.show_equity(index);
+    }
+
+    /// Open the bar replay of results entry `index`.
+    ///
+    /// Saved runs load their replay from `diagnostics.json` once. Sweep runs
+    /// record none, so their entries stay in the detail view with a warning.
+    pub fn open_replay(&mut self, index: usize) {
+        let Some(entry) = self.results.entries.get_mut(index) else {
+            return;
+        };
+        if let (None, Some(dir)) = (&entry.replay, &entry.artifact_dir) {
+            match load_diagnostics(dir) {
+                Ok(diagnostics) => entry.replay = Some(Arc::new(diagnostics.replay)),
+                Err(e) => {
+                    let context = "bar replay load".to_string();
+                    self.push_error(ErrorCategory::Data, format!("{e:#}"), context);
+                    return;
+                }
+            }
+        }
+        match entry.replay.clone().filter(|log| !log.is_empty()) {
+            Some(log) => {
+                self.replay = Some(ReplayState::new(index, log));
+                self.overlay = Overlay::Replay;
+            }
+            None => self.set_warning(
+                "No bar replay recorded for this run; re-run it as a single backtest",
+            ),
+        }
+    }
+
+    /// Open the date-window prompt over entry `index`'s detail view.
+    pub fn open_date_window(&mut self, index: usize) {
+        let r = &mut self.results;
+        if index >= r.entries.len() {
+            return;
+        }
+        r.window_error = None;
+        r.window_input = match r.window.as_ref().filter(|w| w.entry == index) {
+            Some(w) => format!("{}..{}", w.slice.result.start_date, w.slice.result.end_date),
+            None => String::new(),
+        };
+        self.overlay = Overlay::DateWindow(index);
+    }
+
+    /// Recompute entry `index`'s detail metrics over the window typed in the
+    /// prompt and return to the detail view. An empty prompt goes back to
+    /// the whole run; a window that can't be applied keeps the prompt open
+    /// with the reason.
+    pub fn apply_date_window(&mut self, index: usize) {
+        let input = self.results.window_input.trim().to_string();
+        if input.is_empty() {
+            self.results.window = None;
+            self.overlay = Overlay::Detail(index);
+            return;
+        }
+        let result = match self.full_result(index) {
+            Ok(result) => result,
+            Err(e) => {
+                self.results.window_error = Some(e);
+                return;
+            }
+        };
+        let sliced = match (result.dates.first(), result.dates.last()) {
+            (Some(&first), Some(&last)) => parse_date_window(&input, first, last)
+                .and_then(|(from, to)| result.slice(from, to).map_err(|e| e.to_string())),
+            _ => Err(SliceError::NoDates.to_string()),
+        };
+        match sliced {
+            Ok(slice) => {
+                let cut = slice.prorated.len();
+                self.set_status(match cut {
+                    0 => format!(
+                        "Metrics over {} to {}",
+                        slice.result.start_date, slice.result.end_date
+                    ),
+                    _ => format!(
+                        "Metrics over {} to {}; {cut} boundary trades pro-rated",
+                        slice.result.start_date, slice.result.end_date
+                    ),
+                });
+                self.results.window = Some(DateWindow { entry: index, slice });
+                self.results.window_error = None;
+                self.overlay = Overlay::Detail(index);
+            }
+            Err(e) => self.results.window_error = Some(e),
+        }
+    }
+
+    /// Open parameter nudging over entry `index`'s detail view.
+    pub fn open_nudge(&mut self, index: usize) {
+        let Some(entry) = self.results.entries.get(index) else {
+            return;
+        };
+        if entry.result.is_none() && entry.artifact_dir.is_none() {
+            self.set_warning("Only single backtests and saved runs can be re-run with nudged params");
+            return;
+        }
+        let base = match self.full_result(index) {
+            Ok(base) => base,
+            Err(e) => {
+                self.set_warning(format!("Cannot nudge this run: {e}"));
+                return;
+            }
+        };
+        if base.inputs.is_none() {
+            self.set_warning("This run does not record its run inputs; run it again first");
+            return;
+        }
+        let nudge = NudgeState::new(index, base, &self.strategy.pool);
+        if nudge.rows.is_empty() {
+            self.set_warning("No params with a known range to nudge");
+            return;
+        }
+        self.nudge = Some(nudge);
+        self.overlay = Overlay::Nudge;
+    }
+
+    /// Re-run the nudged config in the background. Its result is added to
+    /// the leaderboard as a manual variant of the base run.
+    pub fn rerun_nudge(&mut self) {
+        let Some(nudge) = self.nudge.as_mut() else {
+            return;
+        };
+        let edits = nudge.edits();
+        if edits.is_empty() {
+            nudge.error = Some("No params edited yet".into());
+            return;
+        }
+        nudge.error = None;
+        let _ = self.worker_tx.send(WorkerCommand::RunModified {
+            base: nudge.base.clone(),
+            config: Box::new(nudge.config.clone()),
+            cache_dir: self.cache_dir.clone(),
+        });
+        let edits: Vec<String> = edits.iter().map(ToString::to_string).collect();
+        self.set_status(format!("Re-running with {}...", edits.join(", ")));
+    }
+
+    /// Back to the detail view the nudging was opened from.
+    pub fn close_nudge(&mut self) {
+        self.overlay = match self.nudge.take() {
+            Some(nudge) => Overlay::Detail(nudge.entry),
+            None => Overlay::None,
+        };
+    }
+
+    /// Leave the detail view, dropping any date window.
+    pub fn close_detail(&mut self) {
+        self.results.window = None;
+        self.overlay = Overlay::None;
+    }
+
+    /// Entry `index`'s full result, read from its manifest on first use.
+    fn full_result(&mut self, index: usize) -> Result<Arc<BacktestResult>, String> {
+        let entry = self
+            .results
+            .entries
+            .get_mut(index)
+            .ok_or_else(|| "entry not found".to_string())?;
+        if let Some(result) = &entry.result {
+            return Ok(result.clone());
+        }
+        let Some(dir) = &entry.artifact_dir else {
+            return Err("no saved run to slice; re-run it as a single backtest".into());
+        };
+        let result = Arc::new(load_artifacts(dir).map_err(|e| format!("{e:#}"))?);
+        entry.result = Some(result.clone());
+        Ok(result)
+    }
+
+    /// Leave the bar replay for its entry's detail view.
+    pub fn close_replay(&mut self) {
+        self.overlay = match self.replay.take() {
+            Some(replay) => Overlay::Detail(replay.entry),
+            None => Overlay::None,
+        };
+    }
+
+    /// Load the annotations of results directory `dir`; note edits are
+    /// saved back there.
+    pub fn load_annotations(&mut self, dir: &Path) {
+        match Annotations::load(dir) {
+            Ok(annotations) => {
+                self.annotations = annotations;
+                self.annotations_dir = Some(dir.to_path_buf());
+            }
+            Err(e) => self.push_error(
+                ErrorCategory::Data,
+                format!("Failed to load notes (edits will not be saved): {e}"),
+                dir.join(ANNOTATIONS_FILE).display().to_string(),
+            ),
+        }
+    }
+
+    /// Annotation of results entry `index`'s run, or of its trade entered on
+    /// `trade`.
+    pub fn annotation(&self, index: usize, trade: Option<usize>) -> Option<&Annotation> {
+        let entry = self.results.entries.get(index)?;
+        let run_id = entry.config.full_hash();
+        match trade {
+            Some(entry_bar) => self.annotations.trade(&run_id, &entry.symbol, entry_bar),
+            None => self.annotations.run(&run_id, &entry.symbol),
+        }
+    }
+
+    /// Whether results entry `entry`'s run or any of its trades has a note.
+    pub fn is_annotated(&self, entry: &LeaderboardDisplayEntry) -> bool {
+        self.annotations
+            .is_annotated(&entry.config.full_hash(), &entry.symbol)
+    }
+
+    /// Open the note editor on results entry `index`'s run, or on its trade
+    /// entered on `trade`.
+    pub fn open_note_editor(&mut self, index: usize, trade: Option<usize>) {
+        if index >= self.results.entries.len() {
+            return;
+        }
+        let current = self.annotation(index, trade).cloned().unwrap_or_default();
+        self.note_editor = Some(NoteEditor {
+            target: NoteTarget {
+                entry: index,
+                trade,
+            },
+            text: current.note,
+            tags: current.tags,
+            tags_focused: false,
+            tag_cursor: 0,
+            return_to: self.overlay.clone(),
+        });
+        self.overlay = Overlay::Note;
+    }
+
+    /// Close the note editor without saving.
+    pub fn close_note_editor(&mut self) {
+        self.overlay = match self.note_editor.take() {
+            Some(editor) => editor.return_to,
+            None => Overlay::None,
+        };
+    }
+
+    /// Apply the note editor's note and tags, write the annotations file and
+    /// close the editor.
+    pub fn save_note(&mut self) {
+        let Some(editor) = self.note_editor.clone() else {
+            return;
+        };
+        self.close_note_editor();
+        let Some(entry) = self.results.entries.get(editor.target.entry) else {
+            return;
+        };
+        let run_id = entry.config.full_hash();
+        let symbol = entry.symbol.clone();
+        let annotation = Annotation {
+            note: editor.text.trim().to_string(),
+            tags: editor.tags,
+        };
+        match editor.target.trade {
+            Some(entry_bar) => self.annotations.set_trade(&run_id, &symbol, entry_bar, annotation),
+            None => self.annotations.set_run(&run_id, &symbol, annotation),
+        }
+        let Some(dir) = self.annotations_dir.clone() else {
+            self.set_warning("Note kept for this session only; the notes file could not be read");
+            return;
+        };
+        match self.annotations.save(&dir) {
+            Ok(_) => self.set_status("Note saved"),
+            Err(e) => self.push_error(
+                ErrorCategory::Other,
+                format!("Failed to save notes: {e}"),
+                dir.join(ANNOTATIONS_FILE).display().to_string(),
+            ),
+        }
+    }
+
+    /// Write the trades of results entry `index` to a CSV in `exports_dir`.
+    ///
+    /// Saved runs read their trades from the manifest. YOLO entries keep
+    /// none, so they get a warning instead.
+    pub fn export_trades(&mut self, index: usize) {
+        let Some(entry) = self.results.entries.get(index) else {
+            return;
+        };
+        let trades = match (entry.trades.clone(), entry.artifact_dir.clone()) {
+            (Some(trades), _) => trades,
+            (None, Some(dir)) => match load_artifacts(&dir) {
+                Ok(result) => Arc::new(result.trades),
+                Err(e) => {
+                    self.push_error(ErrorCategory::Data, format!("{e:#}"), "trade export".into());
+                    return;
+                }
+            },
+            (None, None) => {
+                self.set_warning("No trade list kept for this run; re-run it as a single backtest");
+                return;
+            }
+        };
+        let entry = &self.results.entries[index];
+        let notes = self
+            .annotations
+            .get(&entry.config.full_hash(), &entry.symbol)
+            .cloned()
+            .unwrap_or_default();
+        let now = chrono::Local::now().naive_local();
+        match export::export_trades(&self.exports_dir, entry, &trades, &notes, now) {
+            Ok(path) => {
+                self.set_status(format!("Exported {} trades to {}", trades.len(), path.display()))
+            }
+            Err(e) => self.push_error(ErrorCategory::Other, format!("{e:#}"), "trade export".into()),
+        }
+    }
+
+    /// Write the leaderboard rows currently shown (view and filter applied)
+    /// to a CSV in `exports_dir`.
+    pub fn export_leaderboard(&mut self) {
+        let rows: Vec<&LeaderboardDisplayEntry> = self
+            .results
+            .visible_indices()
+            .into_iter()
+            .map(|i| &self.results.entries[i])
+            .collect();
+        if rows.is_empty() {
+            self.set_warning("No leaderboard rows to export");
+            return;
+        }
+        let count = rows.len();
+        let now = chrono::Local::now().naive_local();
+        match export::export_leaderboard(&self.exports_dir, &rows, &self.annotations, now) {
+            Ok(path) => self.set_status(format!("Exported {count} rows to {}", path.display())),
+            Err(e) => {
+                let context = "leaderboard export".to_string();
+                self.push_error(ErrorCategory::Other, format!("{e:#}"), context)
+            }
+        }
+    }
+
+    /// Show results entry `index` in the chart.
+    ///
+    /// Cached curves display immediately; saved runs not in memory are loaded
+    /// in the background and the chart shows a spinner until they arrive.
+    pub fn show_equity(&mut self, index: usize) {
+        let Some(entry) = self.results.entries.get(index) else {
+            return;
+        };
+        let label = entry.chart_label();
+        let artifact_dir = entry.artifact_dir.clone();
+
+        self.chart.viewing = Some(index);
+        self.chart.label = label;
+        if let Some(curve) = self.equity_cache.get(index) {
+            self.chart.equity_curve = Some(curve.clone());
+            self.chart.loading_since = None;
+            return;
+        }
+
+        self.chart.equity_curve = None;
+        match (artifact_dir, &self.equity_tx) {
+            (Some(artifact_dir), Some(tx)) => {
+                let _ = tx.send(EquityLoadRequest {
+                    index,
+                    artifact_dir,
+                });
+                self.chart.loading_since = Some(Instant::now());
+            }
+            _ => self.chart.loading_since = None,
+        }
+    }
+
+    /// Store a loaded equity curve, displaying it if its entry is being viewed.
+    pub fn receive_equity(&mut self, index: usize, curve: Vec<f64>) {
+        if self.chart.viewing == Some(index) {
+            self.chart.equity_curve = Some(curve.clone());
+            self.chart.loading_since = None;
+        }
+        // Only curves that can be reloaded from disk are evicted
+        let entries = &self.results.entries;
+        let viewing = self.chart.viewing;
+        self.equity_cache.insert(index, curve, |i| {
+            Some(i) != viewing && entries.get(i).is_some_and(|e| e.artifact_dir.is_some())
+        });
+    }
+
+    /// Clear the chart's loading state after a failed background load.
+    pub fn equity_load_failed(&mut self, index: usize) {
+        if self.chart.viewing == Some(index) {
+            self.chart.loading_since = None;
+        }
+    }
+}
+
+/// Parse a date-window prompt: `FROM..TO`, where an omitted side defaults
+/// to the run's `first` or `last` date.
+pub fn parse_date_window(
+    input: &str,
+    first: NaiveDate,
+    last: NaiveDate,
+) -> Result<(NaiveDate, NaiveDate), String> {
+    let Some((from, to)) = input.split_once("..") else {
+        return Err("expected FROM..TO, e.g. 2022-01-01..2022-12-31".into());
+    };
+    let date = |s: &str, default: NaiveDate| match s.trim() {
+        "" => Ok(default),
+        s => NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|_| format!("bad date {s:?}")),
+    };
+    Ok((date(from, first)?, date(to, last)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panel_cycle() {
+        assert_eq!(Panel::Data.next(), Panel::Strategy);
+        assert_eq!(Panel::Help.next(), Panel::Data);
+        assert_eq!(Panel::Data.prev(), Panel::Help);
+        assert_eq!(Panel::Strategy.prev(), Panel::Data);
+    }
+
+    #[test]
+    fn panel_from_index() {
+        for i in 0..6 {
+            let p = Panel::from_index(i).unwrap();
+            assert_eq!(p.index(), i);
+        }
+        assert!(Panel::from_index(6).is_none());
+    }
+
+    #[test]
+    fn error_history_caps_at_50() {
+        let (tx, _rx) = std::sync::mpsc::channel();
+        let (_tx2, rx2) = std::sync::mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut app = AppState::new(
+            tx,
+            rx2,
+            cancel,
+            PathBuf::from("."),
+            PathBuf::from("."),
+        );
+        for i in 0..60 {
+            app.push_error(ErrorCategory::Other, format!("error {i}"), String::new());
+        }
+        assert_eq!(app.error_history.len(), 50);
+        assert!(app.error_history[0].message.contains("59"));
+    }
+
+    #[test]
+    fn nudge_explore_updates_config_and_shared_tunables() {
+        let mut sweep = SweepPanelState::new();
+        let shared = sweep.tunables.clone();
+        sweep.nudge_explore(EXPLORE_NUDGE);
+        assert!((sweep.config.structural_explore - 0.35).abs() < 1e-12);
+        assert_eq!(shared.read().unwrap().structural_explore, sweep.config.structural_explore);
+
+        sweep.nudge_explore(-10.0);
+        assert_eq!(sweep.config.structural_explore, 0.0);
+        assert_eq!(shared.read().unwrap().structural_explore, 0.0);
+    }
+
+    #[test]
+    fn start_yolo_shares_tunables_with_worker() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let (_tx2, rx2) = std::sync::mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut app = AppState::new(
+            tx,
+            rx2,
+            cancel.clone(),
+            PathBuf::from("."),
+            PathBuf::from("."),
+        );
+
+        app.start_yolo();
+        assert!(!app.sweep.yolo_running, "no tickers selected");
+        assert!(rx.try_recv().is_err());
+
+        app.data.selected.insert("SPY".into());
+        app.start_yolo();
+        assert!(app.sweep.yolo_running);
+        let (config, tunables) = match rx.try_recv() {
+            Ok(WorkerCommand::StartYolo {
+                config, tunables, ..
+            }) => (config, tunables),
+            other => panic!("Expected StartYolo, got {:?}", other),
+        };
+        // Each session records its history in its own directory
+        let history = config.history_path.unwrap();
+        assert!(history.starts_with(&app.sessions_dir));
+        assert!(history.ends_with("history.jsonl"));
+        assert_eq!(
+            config.leaderboard_dir,
+            Some(app.sessions_dir.join(trendlab_runner::LEADERBOARDS_DIR))
+        );
+
+        app.sweep.nudge_explore(EXPLORE_NUDGE);
+        assert_eq!(
+            tunables.read().unwrap().structural_explore,
+            app.sweep.config.structural_explore
+        );
+
+        app.stop_yolo();
+        assert!(cancel.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn equity_cache_evicts_least_recently_used() {
+        let mut cache = EquityCache::new(2);
+        cache.insert(0, vec![0.0], |_| true);
+        cache.insert(1, vec![1.0], |_| true);
+        assert!(cache.get(0).is_some()); // 1 is now least recently used
+        cache.insert(2, vec![2.0], |_| true);
+        assert_eq!(cache.len(), 2);
+        assert!(cache.contains(0));
+        assert!(!cache.contains(1));
+        assert!(cache.contains(2));
+    }
+
+    #[test]
+    fn equity_cache_keeps_pinned_curves() {
+        let mut cache = EquityCache::new(1);
+        cache.insert(0, vec![0.0], |_| true);
+        // Index 0 pinned (e.g. on screen): the cache grows past its cap
+        cache.insert(1, vec![1.0], |i| i != 0);
+        assert_eq!(cache.len(), 2);
+        cache.insert(2, vec![2.0], |i| i != 0);
+        assert!(cache.contains(0));
+        assert!(!cache.contains(1));
+        assert!(cache.contains(2));
+    }
+
+    fn saved_entry(dir: &str) -> LeaderboardDisplayEntry {
+        let curve = vec![100_000.0, 101_000.0, 102_000.0];
+        let config = StrategyPanelState::new().to_strategy_config();
+        LeaderboardDisplayEntry::from_summary(
+            1,
+            ArtifactSummary {
+                dir: PathBuf::from(dir),
+                schema_version: 1,
+                metrics: PerformanceMetrics::compute(&curve, &[], 100_000.0),
+                config,
+                symbol: "SPY".into(),
+                start_date: "2020-01-01".into(),
+                end_date: "2020-12-31".into(),
+                initial_capital: 100_000.0,
+                bar_count: curve.len(),
+                data_warning_count: 0,
+                stickiness: None,
+                position_stickiness: Vec::new(),
+                r_distribution: Default::default(),
+                trade_distributions: Default::default(),
+                tail_metrics: None,
+                style: None,
+                equity_sparkline: Vec::new(),
+                attribution: Default::default(),
+                unknown_fields: Vec::new(),
+            },
+        )
+    }
+
+    #[test]
+    fn show_equity_loads_saved_runs_lazily() {
+        let (tx, _rx) = std::sync::mpsc::channel();
+        let (_tx2, rx2) = std::sync::mpsc::channel();
+        let (equity_tx, equity_rx) = std::sync::mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut app = AppState::new(
+            tx,
+            rx2,
+            cancel,
+            PathBuf::from("."),
+            PathBuf::from("."),
+        );
+        app.equity_tx = Some(equity_tx);
+        app.results.entries.push(saved_entry("results/SPY_1"));
+
+        // First drill-down: background load with spinner
+        app.show_equity(0);
+        let req = equity_rx.try_recv().unwrap();
+        assert_eq!(req.index, 0);
+        assert_eq!(req.artifact_dir, PathBuf::from("results/SPY_1"));
+        assert!(app.chart.loading_since.is_some());
+        assert!(app.chart.equity_curve.is_none());
+
+        app.receive_equity(0, vec![1.0, 2.0]);
+        assert!(app.chart.loading_since.is_none());
+        assert_eq!(app.chart.equity_curve, Some(vec![1.0, 2.0]));
+
+        // Second drill-down: served from memory
+        app.chart = ChartPanelState::new();
+        app.show_equity(0);
+        assert!(equity_rx.try_recv().is_err());
+        assert_eq!(app.chart.equity_curve, Some(vec![1.0, 2.0]));
+    }
+
+    #[test]
+    fn failed_equity_load_clears_spinner() {
+        let (tx, _rx) = std::sync::mpsc::channel();
+        let (_tx2, rx2) = std::sync::mpsc::channel();
+        let (equity_tx, _equity_rx) = std::sync::mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut app = AppState::new(
+            tx,
+            rx2,
+            cancel,
+            PathBuf::from("."),
+            PathBuf::from("."),
+        );
+        app.equity_tx = Some(equity_tx);
+        app.results.entries.push(saved_entry("results/SPY_1"));
+
+        app.show_equity(0);
+        assert!(app.chart.loading_since.is_some());
+        app.equity_load_failed(0);
+        assert!(app.chart.loading_since.is_none());
+    }
+
+    #[test]
+    fn session_drill_down_needs_saved_artifacts() {
+        let (tx, _rx) = std::sync::mpsc::channel();
+        let (_tx2, rx2) = std::sync::mpsc::channel();
+        let (equity_tx, equity_rx) = std::sync::mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut app = AppState::new(
+            tx,
+            rx2,
+            cancel,
+            PathBuf::from("."),
+            PathBuf::from("."),
+        );
+        app.equity_tx = Some(equity_tx);
+        let mut missing = saved_entry("unused");
+        missing.artifact_dir = None;
+        app.overlay = Overlay::Sessions;
+        app.sessions.open = Some(OpenSession {
+            session_id: "yolo-42-1".into(),
+            boards: vec![
+                SessionBoard {
+                    title: "Cross-symbol".into(),
+                    entries: Vec::new(),
+                },
+                SessionBoard {
+                    title: "SPY".into(),
+                    entries: vec![saved_entry("sessions/a/runs/SPY_001"), missing],
+                },
+            ],
+            board: 0,
+            cursor: 0,
+        });
+
+        let open = app.sessions.open.as_mut().unwrap();
+        open.cycle_board(-1);
+        assert_eq!(open.current().title, "SPY");
+        open.cursor = 1;
+        app.drill_into_session_entry();
+        assert_eq!(
+            app.overlay,
+            Overlay::Sessions,
+            "no artifacts, no drill-down"
+        );
+        assert!(app.results.entries.is_empty());
+
+        app.sessions.open.as_mut().unwrap().cursor = 0;
+        app.drill_into_session_entry();
+        assert_eq!(app.overlay, Overlay::Detail(0));
+        assert_eq!(
+            equity_rx.try_recv().unwrap().artifact_dir,
+            PathBuf::from("sessions/a/runs/SPY_001")
+        );
+
+        // Drilling into the same run again reuses its results entry
+        app.overlay = Overlay::Sessions;
+        app.drill_into_session_entry();
+        assert_eq!(app.overlay, Overlay::Detail(0));
+        assert_eq!(app.results.entries.len(), 1);
+    }
+
+    fn replay_log() -> ReplayLog {
+        use trendlab_core::domain::PositionSide;
+        use trendlab_core::engine::ReplayBar;
+
+        let position = |entry_bar: usize, net_pnl: f64, stops: &[f64]| PositionReplay {
+            symbol: "SPY".into(),
+            side: PositionSide::Long,
+            entry_bar,
+            net_pnl: Some(net_pnl),
+            bars: stops
+                .iter()
+                .enumerate()
+                .map(|(i, &stop)| ReplayBar {
+                    bar_index: entry_bar + i,
+                    date: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+                    open: 100.0,
+                    high: 101.0,
+                    low: 99.0,
+                    close: 100.5,
+                    stop: Some(stop),
+                    orders: Vec::new(),
+                    equity: 100_000.0,
+                    indicators: [("atr_14".to_string(), 1.5), ("sma_50".to_string(), 98.0)]
+                        .into(),
+                    exit: i + 1 == stops.len(),
+                })
+                .collect(),
+        };
+        ReplayLog {
+            positions: vec![
+                position(10, 250.0, &[95.0, 96.0, 96.0]),
+                position(20, -120.0, &[95.0, 96.5, 97.0, 97.0]),
+                position(30, 80.0, &[94.0, 94.0]),
+            ],
+        }
+    }
+
+    #[test]
+    fn replay_steps_through_a_losing_position() {
+        let mut replay = ReplayState::new(0, Arc::new(replay_log()));
+        // Opens on the first loser, at its entry bar
+        assert_eq!(replay.position, 1);
+        assert_eq!(replay.cursor, 0);
+
+        replay.step(-1);
+        assert_eq!(replay.cursor, 0);
+        replay.step(2);
+        assert_eq!(replay.current().bars[replay.cursor].stop, Some(97.0));
+        replay.step(10);
+        assert_eq!(replay.cursor, 3, "clamped to the exit bar");
+        replay.jump_to_entry();
+        assert_eq!(replay.cursor, 0);
+        replay.jump_to_exit();
+        assert!(replay.current().bars[replay.cursor].exit);
+
+        replay.cycle_indicator();
+        assert_eq!(replay.indicator_name().as_deref(), Some("atr_14"));
+        replay.cycle_indicator();
+        assert_eq!(replay.indicator_name().as_deref(), Some("sma_50"));
+        replay.cycle_indicator();
+        assert_eq!(replay.indicator_name(), None);
+
+        // Switching positions starts over at the entry bar
+        replay.cycle_position(1);
+        assert_eq!((replay.position, replay.cursor), (2, 0));
+        replay.cycle_position(1);
+        assert_eq!(replay.position, 0);
+        assert!(replay.next_loser());
+        assert_eq!(replay.position, 1);
+        assert!(replay.next_loser(), "wraps back to the only loser");
+        assert_eq!(replay.position, 1);
+    }
+
+    #[test]
+    fn replay_loads_saved_diagnostics_or_warns() {
+        let (tx, _rx) = std::sync::mpsc::channel();
+        let (_tx2, rx2) = std::sync::mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut app = AppState::new(
+            tx,
+            rx2,
+            cancel,
+            PathBuf::from("."),
+            PathBuf::from("."),
+        );
+        let dir = std::env::temp_dir().join(format!("trendlab_tui_replay_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let diagnostics = trendlab_runner::RunDiagnostics {
+            stickiness: StickinessReport::default(),
+            attribution: Default::default(),
+            ratchet_clamps: Vec::new(),
+            replay: replay_log(),
+        };
+        std::fs::write(
+            dir.join(trendlab_runner::export::DIAGNOSTICS_JSON),
+            serde_json::to_string(&diagnostics).unwrap(),
+        )
+        .unwrap();
+
+        app.results.entries.push(saved_entry(dir.to_str().unwrap()));
+        let mut sweep_run = saved_entry("unused");
+        sweep_run.artifact_dir = None;
+        app.results.entries.push(sweep_run);
+
+        app.overlay = Overlay::Detail(1);
+        app.open_replay(1);
+        assert_eq!(app.overlay, Overlay::Detail(1), "nothing recorded, no replay");
+        assert!(matches!(app.status_message, Some((_, StatusLevel::Warning))));
+
+        app.overlay = Overlay::Detail(0);
+        app.open_replay(0);
+        assert_eq!(app.overlay, Overlay::Replay);
+        assert_eq!(app.replay.as_ref().unwrap().log.positions.len(), 3);
+        assert!(app.results.entries[0].replay.is_some(), "loaded once, kept");
+
+        app.close_replay();
+        assert_eq!(app.overlay, Overlay::Detail(0));
+        assert!(app.replay.is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn diversified_view_shows_cluster_leaders() {
+        let mut results = ResultsPanelState::new("s1".into());
+        for (i, leader) in [true, false, true].into_iter().enumerate() {
+            let mut entry = saved_entry(&format!("results/SPY_{i}"));
+            entry.cluster_leader = leader;
+            results.entries.push(entry);
+        }
+
+        assert_eq!(results.visible_indices(), vec![0, 1, 2]);
+        results.diversified_view = true;
+        assert_eq!(results.visible_indices(), vec![0, 2]);
+        results.cursor = 1;
+        assert_eq!(results.selected_index(), Some(2));
+        results.cursor = 2;
+        assert_eq!(results.selected_index(), None);
+    }
+
+    #[test]
+    fn date_window_prompt_parses_open_ended_ranges() {
+        let day = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+        let (first, last) = (day(1, 3), day(12, 30));
+        let parse = |s| parse_date_window(s, first, last);
+        assert_eq!(parse("2022-03-01..2022-06-30"), Ok((day(3, 1), day(6, 30))));
+        assert_eq!(parse("2022-03-01.."), Ok((day(3, 1), last)));
+        assert_eq!(parse(" .. 2022-06-30"), Ok((first, day(6, 30))));
+        assert!(parse("2022-03-01").is_err());
+        assert!(parse("2022-13-01..").unwrap_err().contains("2022-13-01"));
+    }
+
+    #[test]
+    fn nudging_refuses_range_and_constraint_breaks_and_sends_the_variant() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let (_tx2, rx2) = std::sync::mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut app = AppState::new(tx, rx2, cancel, PathBuf::from("."), PathBuf::from("."));
+        let mut entry = saved_entry("results/missing");
+        entry.config.signal = ComponentConfig {
+            component_type: "ma_crossover".into(),
+            params: BTreeMap::from([("fast_period".into(), 5.0), ("slow_period".into(), 20.0)]),
+            children: Vec::new(),
+        };
+        let curve = vec![100_000.0, 101_000.0, 102_000.0];
+        let result: BacktestResult = serde_json::from_value(serde_json::json!({
+            "metrics": PerformanceMetrics::compute(&curve, &[], 100_000.0),
+            "trades": [],
+            "equity_curve": &curve,
+            "config": entry.config,
+            "symbol": "SPY",
+            "start_date": "2020-01-01",
+            "end_date": "2020-12-31",
+            "initial_capital": 100_000.0,
+            "dataset_hash": "",
+            "has_synthetic": false,
+            "signal_count": 0,
+            "bar_count": 3,
+            "warmup_bars": 0,
+            "void_bar_rates": {},
+            "stickiness": null,
+            "inputs": {
+                "trading_mode": "LongOnly",
+                "position_size_pct": 1.0,
+                "execution_preset": "Realistic",
+                "symbols": ["SPY"],
+            },
+        }))
+        .unwrap();
+        entry.result = Some(Arc::new(result));
+        app.results.entries.push(entry);
+        let press = |app: &mut AppState, code| {
+            crate::input::handle_key(app, KeyEvent::new(code, KeyModifiers::NONE));
+        };
+
+        app.overlay = Overlay::Detail(0);
+        press(&mut app, KeyCode::Char('p'));
+        assert_eq!(app.overlay, Overlay::Nudge);
+        let nudge = app.nudge.as_ref().unwrap();
+        let params: Vec<_> = nudge.rows.iter().map(|r| r.range.name.as_str()).collect();
+        assert_eq!(params[..2], ["fast_period", "slow_period"]);
+
+        // Nothing edited yet
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.nudge.as_ref().unwrap().error.as_deref(), Some("No params edited yet"));
+
+        // fast_period starts at its minimum
+        press(&mut app, KeyCode::Char('-'));
+        let nudge = app.nudge.as_ref().unwrap();
+        assert!(nudge.error.as_ref().unwrap().contains("at its minimum"));
+        assert!(nudge.edits().is_empty());
+
+        // Raising it up to slow_period is refused, keeping the last valid value
+        for _ in 0..15 {
+            press(&mut app, KeyCode::Char('+'));
+        }
+        let nudge = app.nudge.as_ref().unwrap();
+        assert!(nudge
+            .error
+            .as_ref()
+            .unwrap()
+            .contains("slow_period must be greater than fast_period"));
+        assert_eq!(nudge.value(&nudge.rows[0]), 19.0);
+
+        press(&mut app, KeyCode::Enter);
+        match rx.try_recv() {
+            Ok(WorkerCommand::RunModified { config, .. }) => {
+                assert_eq!(config.signal.params["fast_period"], 19.0);
+                assert_eq!(config.signal.params["slow_period"], 20.0);
+            }
+            _ => panic!("expected a RunModified command"),
+        }
+        assert!(app.status_message.as_ref().unwrap().0.contains("fast_period 5.0 → 19.0"));
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.overlay, Overlay::Detail(0));
+        assert!(app.nudge.is_none());
+    }
+
+    #[test]
+    fn date_window_rerenders_detail_metrics_until_closed() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let (tx, _rx) = std::sync::mpsc::channel();
+        let (_tx2, rx2) = std::sync::mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut app = AppState::new(tx, rx2, cancel, PathBuf::from("."), PathBuf::from("."));
+        // Ten daily bars, the first three warmup, rising 1% a bar after it
+        let dates: Vec<NaiveDate> = (0..10)
+            .map(|i| NaiveDate::from_ymd_opt(2022, 3, 1).unwrap() + chrono::Duration::days(i))
+            .collect();
+        let curve: Vec<f64> = (0..10)
+            .map(|i: i32| 100_000.0 * 1.01_f64.powi((i - 2).max(0)))
+            .collect();
+        let mut entry = saved_entry("results/missing");
+        let result: BacktestResult = serde_json::from_value(serde_json::json!({
+            "metrics": PerformanceMetrics::compute(&curve, &[], 100_000.0),
+            "trades": [],
+            "equity_curve": &curve,
+            "dates": dates,
+            "config": entry.config,
+            "symbol": "SPY",
+            "start_date": "2022-03-01",
+            "end_date": "2022-03-10",
+            "initial_capital": 100_000.0,
+            "dataset_hash": "",
+            "has_synthetic": false,
+            "signal_count": 0,
+            "bar_count": 10,
+            "warmup_bars": 3,
+            "void_bar_rates": {},
+            "stickiness": null,
+        }))
+        .unwrap();
+        entry.result = Some(Arc::new(result));
+        app.results.entries.push(saved_entry("results/missing"));
+        app.results.entries.push(entry);
+        let press = |app: &mut AppState, code| {
+            crate::input::handle_key(app, KeyEvent::new(code, KeyModifiers::NONE));
+        };
+        let type_text = |app: &mut AppState, text: &str| {
+            for c in text.chars() {
+                press(app, KeyCode::Char(c));
+            }
+        };
+
+        // Saved run whose manifest is gone: the prompt stays open with the reason
+        app.overlay = Overlay::Detail(0);
+        press(&mut app, KeyCode::Char('d'));
+        assert_eq!(app.overlay, Overlay::DateWindow(0));
+        type_text(&mut app, "2022-03-05..");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.overlay, Overlay::DateWindow(0));
+        assert!(app.results.window_error.is_some());
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.overlay, Overlay::Detail(0));
+        press(&mut app, KeyCode::Esc);
+
+        // A window entirely in warmup is refused
+        app.overlay = Overlay::Detail(1);
+        press(&mut app, KeyCode::Char('d'));
+        type_text(&mut app, "..2022-03-02");
+        press(&mut app, KeyCode::Enter);
+        assert!(app.results.window_error.as_ref().unwrap().contains("warmup"));
+        assert!(app.results.window.is_none());
+
+        for _ in 0.."..2022-03-02".len() {
+            press(&mut app, KeyCode::Backspace);
+        }
+        type_text(&mut app, "2022-03-06..2022-03-08");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.overlay, Overlay::Detail(1));
+        let window = app.results.window.as_ref().unwrap();
+        assert_eq!(window.entry, 1);
+        let sliced = &window.slice.result;
+        assert_eq!(sliced.bar_count, 3);
+        assert_eq!(sliced.equity_curve[0], curve[5]);
+        assert_eq!(sliced.tail_metrics.as_ref().unwrap().sample_size, 2);
+        // No trades in the window: neutral metrics, as for a full run
+        assert!(sliced.zero_trades);
+
+        // Reopening the prompt shows the window; clearing it restores the run
+        press(&mut app, KeyCode::Char('d'));
+        assert_eq!(app.results.window_input, "2022-03-06..2022-03-08");
+        press(&mut app, KeyCode::Esc);
+        assert!(app.results.window.is_some());
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.overlay, Overlay::None);
+        assert!(app.results.window.is_none());
+    }
+
+    #[test]
+    fn leaderboard_filter_survives_drill_down_and_clears_on_esc() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let (tx, _rx) = std::sync::mpsc::channel();
+        let (_tx2, rx2) = std::sync::mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut app = AppState::new(tx, rx2, cancel, PathBuf::from("."), PathBuf::from("."));
+        let rows = [("donchian_breakout", 2.0), ("supertrend", 1.8), ("donchian_breakout", 0.9)];
+        for (i, (signal, sharpe)) in rows.into_iter().enumerate() {
+            let mut entry = saved_entry(&format!("results/SPY_{i}"));
+            entry.signal_type = signal.to_string();
+            entry.sharpe = sharpe;
+            app.results.entries.push(entry);
+        }
+        app.active_panel = Panel::Results;
+        let press = |app: &mut AppState, code| {
+            crate::input::handle_key(app, KeyEvent::new(code, KeyModifiers::NONE));
+        };
+
+        press(&mut app, KeyCode::Char('/'));
+        assert_eq!(app.overlay, Overlay::Filter);
+        for c in "sharpe>1.5 donch".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        assert_eq!(app.results.visible_indices(), vec![0]);
+        // A half-typed comparison keeps the last filter that parsed
+        for c in " sharpe".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        let before = app.results.filter.clone();
+        press(&mut app, KeyCode::Char('<'));
+        assert!(app.results.filter_error.is_some());
+        assert_eq!(app.results.filter, before);
+        for _ in " sharpe<".chars() {
+            press(&mut app, KeyCode::Backspace);
+        }
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.overlay, Overlay::None);
+        assert_eq!(app.results.filter_input, "sharpe>1.5 donch");
+
+        // Drill into the row and back out: still filtered
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.overlay, Overlay::Detail(0));
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.overlay, Overlay::None);
+        assert_eq!(app.results.visible_indices(), vec![0]);
+
+        press(&mut app, KeyCode::Esc);
+        assert!(app.results.filter.is_none());
+        assert_eq!(app.results.visible_indices(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn notes_are_edited_from_the_keyboard_and_survive_reload() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let new_app = || {
+            let (tx, _rx) = std::sync::mpsc::channel();
+            let (_tx2, rx2) = std::sync::mpsc::channel();
+            let cancel = Arc::new(AtomicBool::new(false));
+            let mut app = AppState::new(tx, rx2, cancel, PathBuf::from("."), PathBuf::from("."));
+            app.results.entries.push(saved_entry("results/SPY_0"));
+            app.active_panel = Panel::Results;
+            app
+        };
+        let press = |app: &mut AppState, code| {
+            crate::input::handle_key(app, KeyEvent::new(code, KeyModifiers::NONE));
+        };
+        let dir = std::env::temp_dir().join(format!("trendlab_tui_notes_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut app = new_app();
+        app.load_annotations(&dir);
+        press(&mut app, KeyCode::Char('n'));
+        assert_eq!(app.overlay, Overlay::Note);
+        for c in "one lucky trade".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        // Tab moves to the tag picker, where Space toggles instead of typing
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Char(' '));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.overlay, Overlay::None);
+        assert_eq!(app.status_message.clone().unwrap().1, StatusLevel::Info);
+        assert!(app.is_annotated(&app.results.entries[0]));
+
+        // Esc discards an edit, and returns to the view the editor was opened from
+        app.overlay = Overlay::Detail(0);
+        press(&mut app, KeyCode::Char('n'));
+        press(&mut app, KeyCode::Char('!'));
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.overlay, Overlay::Detail(0));
+        assert_eq!(app.annotation(0, None).unwrap().note, "one lucky trade");
+
+        let mut reloaded = new_app();
+        reloaded.load_annotations(&dir);
+        let note = reloaded.annotation(0, None).unwrap();
+        assert_eq!(note.note, "one lucky trade");
+        assert_eq!(note.tags, BTreeSet::from([AnnotationTag::Suspicious]));
+        assert!(reloaded.annotation(0, Some(5)).is_none());
+
+        // Same config on another symbol is a different run
+        let mut qqq = saved_entry("results/QQQ_0");
+        qqq.symbol = "QQQ".into();
+        assert!(!reloaded.is_annotated(&qqq));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn champion_entry_aggregates_symbols() {
+        let config = StrategyPanelState::new().to_strategy_config();
+        let ts = NaiveDateTime::parse_from_str("2024-01-01 00:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let mut lb = trendlab_runner::CrossSymbolLeaderboard::new(10, -0.5);
+        for (symbol, step) in [("SPY", 500.0), ("QQQ", 1_000.0)] {
+            let curve: Vec<f64> = (0..50).map(|i| 100_000.0 + step * i as f64).collect();
+            let metrics = PerformanceMetrics::compute(&curve, &[], 100_000.0);
+            lb.insert_result(symbol, metrics, &curve, &config, "yolo", 0, ts);
+        }
+        lb.analyze_overlap(
+            trendlab_runner::RankingMetric::AvgSharpe,
+            &trendlab_runner::OverlapConfig::default(),
+        );
+
+        let cross = &lb.entries()[&config.full_hash()];
+        let entry = LeaderboardDisplayEntry::from_champion(1, cross, true);
+        assert_eq!(entry.symbol, "2 sym");
+        assert_eq!(entry.sharpe, cross.avg_sharpe);
+        assert_eq!(entry.max_drawdown, cross.worst_max_drawdown);
+        assert_eq!(entry.cluster_id, Some(0));
+        assert_eq!(entry.diversification, Some(1.0));
+    }
+
+    #[test]
+    fn strategy_builds_valid_config() {
+        let state = StrategyPanelState::new();
+        let config = state.to_strategy_config();
+        assert!(!config.signal.component_type.is_empty());
+        assert!(!config.position_manager.component_type.is_empty());
+        assert!(!config.execution_model.component_type.is_empty());
+        assert!(!config.signal_filter.component_type.is_empty());
+    }
+
+    #[test]
+    fn cache_manager_downloads_marked_symbols_and_retries_failures() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let (_tx2, rx2) = std::sync::mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut app = AppState::new(
+            tx,
+            rx2,
+            cancel,
+            PathBuf::from("."),
+            PathBuf::from("."),
+        );
+        let start = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        app.data.manager.range = Some((start, end));
+        app.shift_download_range(false, -2);
+        app.shift_download_range(true, -10);
+        assert_eq!(
+            app.download_range(),
+            (NaiveDate::from_ymd_opt(2018, 1, 1).unwrap(), end),
+            "end may not move before start"
+        );
+
+        app.data.manager.marked.extend(["QQQ".to_string(), "SPY".to_string()]);
+        app.download_marked();
+        match rx.try_recv() {
+            Ok(WorkerCommand::FetchData { symbols, start, .. }) => {
+                assert_eq!(symbols, ["QQQ", "SPY"]);
+                assert_eq!(start, NaiveDate::from_ymd_opt(2018, 1, 1).unwrap());
+            }
+            other => panic!("Expected FetchData, got {:?}", other),
+        }
+        assert!(app.data.manager.marked.is_empty());
+        assert_eq!(app.data.downloads["SPY"], DownloadStatus::Pending);
+        assert_eq!(app.data.cache_rows(), ["QQQ", "SPY"]);
+
+        // A second batch waits for the first
+        app.data.manager.marked.insert("IWM".into());
+        app.download_marked();
+        assert!(rx.try_recv().is_err());
+
+        app.data.fetch_in_progress = false;
+        app.data.downloads.insert("QQQ".into(), DownloadStatus::Done);
+        app.data
+            .downloads
+            .insert("SPY".into(), DownloadStatus::Failed("HTTP 500".into()));
+        app.retry_failed_downloads();
+        match rx.try_recv() {
+            Ok(WorkerCommand::FetchData { symbols, start, .. }) => {
+                assert_eq!(symbols, ["SPY"]);
+                assert_eq!(start, NaiveDate::from_ymd_opt(2018, 1, 1).unwrap());
+            }
+            other => panic!("Expected FetchData, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn refresh_cache_listing_reads_the_cache_dir() {
+        let dir = std::env::temp_dir().join(format!("trendlab_tui_cache_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        let bar = trendlab_core::data::RawBar {
+            date,
+            open: 1.0,
+            high: 1.0,
+            low: 1.0,
+            close: 1.0,
+            volume: 1,
+            adj_close: 1.0,
+        };
+        ParquetCache::new(&dir).write("SPY", &[bar]).unwrap();
+
+        let (tx, _rx) = std::sync::mpsc::channel();
+        let (_tx2, rx2) = std::sync::mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut app = AppState::new(tx, rx2, cancel, dir.clone(), PathBuf::from("."));
+        app.open_cache_manager();
+        assert_eq!(app.overlay, Overlay::CacheManager);
+        assert_eq!(app.data.cache_rows(), ["SPY"]);
+        assert_eq!(app.data.cache_entries[0].bar_count(), 1);
+        assert_eq!(app.data.cache_status.get("SPY"), Some(&true));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn data_panel_visible_rows() {
+        let universe = Universe::default_us();
+        let state = DataPanelState::new(universe);
+        // All sectors expanded: 6 sectors + all their tickers
+        let total = state.visible_row_count();
+        assert!(total > 30); // 6 sectors + ~54 tickers
+    }
+
+    #[test]
+    fn data_panel_cursor_item() {
+        let universe = Universe::default_us();
+        let mut state = DataPanelState::new(universe);
+        // First row should be a sector
+        state.cursor.row = 0;
+        match state.cursor_item() {
+            Some(TreeItem::Sector(_)) => {}
+            other => panic!("Expected Sector, got {:?}", other),
+        }
+        // Second row (sector expanded) should be a ticker
+        state.cursor.row = 1;
+        match state.cursor_item() {
+            Some(TreeItem::Ticker(_, _)) => {}
+            other => panic!("Expected Ticker, got {:?}", other),
+        }
+    }
+
+    fn export_trade(symbol: &str, net_pnl: f64) -> TradeRecord {
+        TradeRecord {
+            symbol: symbol.into(),
+            side: trendlab_core::domain::PositionSide::Long,
+            entry_bar: 5,
+            entry_date: NaiveDate::from_ymd_opt(2020, 1, 8).unwrap(),
+            entry_price: 100.0,
+            entry_trigger_margin: None,
+            exit_bar: 9,
+            exit_date: NaiveDate::from_ymd_opt(2020, 1, 14).unwrap(),
+            exit_price: 100.0 + net_pnl / 10.0,
+            quantity: 10.0,
+            gross_pnl: net_pnl,
+            commission: 0.0,
+            slippage: 0.0,
+            net_pnl,
+            bars_held: 4,
+            mae: -20.0,
+            mfe: 40.0,
+            initial_stop_price: None,
+            entry_source: None,
+            exit_source: None,
+            liquidated_at_end: false,
+            expiry_bar: None,
+            signal_id: None,
+            signal_type: None,
+            pm_type: None,
+            execution_model: None,
+            filter_type: None,
+        }
+    }
+
+    #[test]
+    fn export_writes_trades_and_visible_leaderboard_rows() {
+        let (tx, _rx) = std::sync::mpsc::channel();
+        let (_tx2, rx2) = std::sync::mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut app = AppState::new(
+            tx,
+            rx2,
+            cancel,
+            PathBuf::from("."),
+            PathBuf::from("."),
+        );
+        let dir = std::env::temp_dir().join(format!("trendlab_tui_export_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        app.exports_dir = dir.join("exports");
+
+        let mut spy = saved_entry("unused");
+        spy.artifact_dir = None;
+        spy.trades = Some(Arc::new(vec![export_trade("SPY", 40.0), export_trade("SPY", -15.0)]));
+        let mut qqq = saved_entry("unused");
+        qqq.artifact_dir = None;
+        qqq.symbol = "QQQ".into();
+        app.results.entries.push(spy);
+        app.results.entries.push(qqq);
+
+        let run_id = app.results.entries[0].config.full_hash();
+        let gap = Annotation {
+            note: "gap entry".into(),
+            tags: BTreeSet::from([AnnotationTag::GapEntry]),
+        };
+        app.annotations.set_trade(&run_id, "SPY", 5, gap.clone());
+        app.annotations.set_run(&run_id, "QQQ", gap);
+
+        app.export_trades(0);
+        let (msg, level) = app.status_message.clone().unwrap();
+        assert_eq!(level, StatusLevel::Info, "{msg}");
+        let files: Vec<PathBuf> = std::fs::read_dir(&app.exports_dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        assert_eq!(files.len(), 1);
+        assert!(msg.contains(&files[0].display().to_string()));
+        let csv = std::fs::read_to_string(&files[0]).unwrap();
+        assert!(csv.lines().next().unwrap().contains("mae"));
+        assert_eq!(csv.lines().count(), 3, "header + two trades");
+        assert!(csv.lines().nth(1).unwrap().ends_with(",gap entry,gap-entry"));
+
+        // No trades in memory and nothing on disk: a warning, no file
+        app.export_trades(1);
+        assert!(matches!(app.status_message, Some((_, StatusLevel::Warning))));
+
+        // Only rows that pass the filter are written
+        app.results.set_filter_input("symbol=QQQ".into());
+        app.export_leaderboard();
+        let (msg, _) = app.status_message.clone().unwrap();
+        assert!(msg.starts_with("Exported 1 rows"), "{msg}");
+        let board = std::fs::read_dir(&app.exports_dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .find(|p| p.file_name().unwrap().to_string_lossy().starts_with("leaderboard_"))
+            .unwrap();
+        let csv = std::fs::read_to_string(board).unwrap();
+        assert_eq!(csv.lines().count(), 2);
+        assert!(csv.lines().next().unwrap().ends_with(",note,tags"));
+        assert!(csv.lines().nth(1).unwrap().contains("QQQ"));
+        assert!(csv.lines().nth(1).unwrap().ends_with(",gap entry,gap-entry"));
+
+        // An unwritable directory is reported, not fatal
+        app.exports_dir = files[0].join("nested");
+        app.export_leaderboard();
+        assert!(matches!(app.status_message, Some((_, StatusLevel::Error))));
+        assert!(!app.error_history.is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
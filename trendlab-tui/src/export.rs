@@ -0,0 +1,130 @@
+//! CSV exports from the results panel.
+//!
+//! A run's trade tape (every `trades.csv` column, including MAE/MFE and
+//! slippage) and the leaderboard rows currently shown, each with the note
+//! and tags from the trade journal, are written to
+//! `<name>_<YYYYmmdd_HHMMSS>.csv` in the exports directory. Key handling
+//! lives in `input`; these functions only build and write the files.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::NaiveDateTime;
+use trendlab_core::domain::TradeRecord;
+use trendlab_runner::{export_annotated_trades_csv, Annotations, RunAnnotations};
+
+use crate::app::LeaderboardDisplayEntry;
+
+/// Leaderboard CSV columns: the table's own plus the full identity of each
+/// row and its run note.
+const LEADERBOARD_COLUMNS: [&str; 19] = [
+    "rank",
+    "run_id",
+    "signal",
+    "pm",
+    "execution",
+    "filter",
+    "symbol",
+    "sharpe",
+    "cagr",
+    "max_drawdown",
+    "win_rate",
+    "profit_factor",
+    "trades",
+    "fitness",
+    "cluster",
+    "q_value",
+    "session",
+    "note",
+    "tags",
+];
+
+/// Write `trades` of `entry`, with the run's trade `notes`, as
+/// `trades_<symbol>_<run id>_<time>.csv`.
+pub fn export_trades(
+    dir: &Path,
+    entry: &LeaderboardDisplayEntry,
+    trades: &[TradeRecord],
+    notes: &RunAnnotations,
+    now: NaiveDateTime,
+) -> Result<PathBuf> {
+    let run_id = entry.config.full_hash().as_hex();
+    let stem = format!(
+        "trades_{}_{}",
+        file_safe(&entry.symbol),
+        &run_id[..run_id.len().min(8)]
+    );
+    write_export(dir, &stem, now, &export_annotated_trades_csv(trades, notes)?)
+}
+
+/// Write the leaderboard rows `entries`, in display order, as
+/// `leaderboard_<time>.csv`.
+pub fn export_leaderboard(
+    dir: &Path,
+    entries: &[&LeaderboardDisplayEntry],
+    annotations: &Annotations,
+    now: NaiveDateTime,
+) -> Result<PathBuf> {
+    write_export(dir, "leaderboard", now, &leaderboard_csv(entries, annotations)?)
+}
+
+fn leaderboard_csv(
+    entries: &[&LeaderboardDisplayEntry],
+    annotations: &Annotations,
+) -> Result<String> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record(LEADERBOARD_COLUMNS)?;
+    for e in entries {
+        let note = annotations.run(&e.config.full_hash(), &e.symbol);
+        wtr.write_record([
+            e.rank.to_string(),
+            e.config.full_hash().as_hex(),
+            e.signal_type.clone(),
+            e.pm_type.clone(),
+            e.exec_type.clone(),
+            e.filter_type.clone(),
+            e.symbol.clone(),
+            format!("{:.4}", e.sharpe),
+            format!("{:.6}", e.cagr),
+            format!("{:.6}", e.max_drawdown),
+            format!("{:.4}", e.win_rate),
+            format!("{:.4}", e.profit_factor),
+            e.trade_count.to_string(),
+            format!("{:.6}", e.fitness_score),
+            e.cluster_id.map(|c| (c + 1).to_string()).unwrap_or_default(),
+            e.fdr
+                .as_ref()
+                .map(|f| format!("{:.6}", f.q_value))
+                .unwrap_or_default(),
+            e.session_id.clone(),
+            note.map(|n| n.note.clone()).unwrap_or_default(),
+            note.map(|n| n.tags_label()).unwrap_or_default(),
+        ])?;
+    }
+    let data = wtr.into_inner().context("failed to flush CSV writer")?;
+    String::from_utf8(data).context("CSV output is not valid UTF-8")
+}
+
+/// Write `contents` to a new timestamped file, creating `dir` if needed.
+/// A second export within the same second gets a numeric suffix.
+fn write_export(dir: &Path, stem: &str, now: NaiveDateTime, contents: &str) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("cannot create exports directory {}", dir.display()))?;
+    let base = format!("{stem}_{}", now.format("%Y%m%d_%H%M%S"));
+    let mut path = dir.join(format!("{base}.csv"));
+    let mut n = 1;
+    while path.exists() {
+        n += 1;
+        path = dir.join(format!("{base}_{n}.csv"));
+    }
+    std::fs::write(&path, contents)
+        .with_context(|| format!("cannot write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Keep symbols like `BRK.B` or `^GSPC` usable as file name parts.
+fn file_safe(s: &str) -> String {
+    s.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect()
+}
@@ -1,103 +1,100 @@
-//! Keyboard input dispatch — global keys → overlays → panel-specific handlers.
+//! Keyboard input dispatch — key → [`keymap`](crate::keymap) binding →
+//! the handler for the context it is bound in.
 
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
 
 use trendlab_runner::RiskProfile;
 
 use crate::app::{
-    AppState, Overlay, Panel, SessionFilter, TreeItem,
+    AppState, Overlay, SessionFilter, TreeItem, EXPLORE_NUDGE,
 };
+use crate::keymap::{self, Action, KeyContext};
 
-/// Handle a key event. Returns true if the app should continue running.
+/// Handle a key event.
 pub fn handle_key(app: &mut AppState, key: KeyEvent) {
     // Only handle key press events (Windows sends both Press and Release).
     if key.kind != KeyEventKind::Press {
         return;
     }
+    let Some(binding) = keymap::lookup(KeyContext::current(app), &key) else {
+        return;
+    };
 
-    // 1. Overlays consume input first.
-    match &app.overlay {
-        Overlay::Welcome => {
-            app.overlay = Overlay::None;
-            return;
-        }
-        Overlay::ErrorHistory => {
-            handle_error_overlay(app, key);
-            return;
-        }
-        Overlay::Search => {
-            handle_search_overlay(app, key);
-            return;
+    let action = binding.action;
+    match binding.context {
+        KeyContext::Always | KeyContext::Global => handle_global(app, action),
+        KeyContext::HelpOverlay => app.show_help = false,
+        KeyContext::Welcome => app.overlay = Overlay::None,
+        KeyContext::ErrorHistory => handle_error_overlay(app, action),
+        KeyContext::Search => handle_search_overlay(app, action, key),
+        KeyContext::Filter => handle_filter_overlay(app, action, key),
+        KeyContext::Detail => {
+            if let Overlay::Detail(idx) = app.overlay {
+                handle_detail_overlay(app, action, idx);
+            }
         }
-        Overlay::Detail(_) => {
-            handle_detail_overlay(app, key);
-            return;
+        KeyContext::Replay => handle_replay_overlay(app, action),
+        KeyContext::Note | KeyContext::NoteTags => handle_note_editor(app, action, key),
+        KeyContext::DateWindow => {
+            if let Overlay::DateWindow(idx) = app.overlay {
+                handle_date_window(app, action, key, idx);
+            }
         }
-        Overlay::None => {}
+        KeyContext::Nudge => handle_nudge(app, action),
+        KeyContext::YoloDashboard => handle_yolo_dashboard(app, action),
+        KeyContext::Sessions | KeyContext::SessionBoard => handle_sessions_overlay(app, action),
+        KeyContext::CacheManager => handle_cache_manager(app, action),
+        KeyContext::Data => handle_data_key(app, action),
+        KeyContext::Strategy => handle_strategy_key(app, action),
+        KeyContext::Sweep => handle_sweep_key(app, action),
+        KeyContext::Results => handle_results_key(app, action),
+        KeyContext::Chart => handle_chart_key(app, action),
+        KeyContext::HelpPanel => handle_help_key(app, action),
     }
+}
 
-    // 2. Global keys (always available).
-    match key.code {
-        KeyCode::Char('q') => {
-            app.running = false;
-            return;
-        }
-        KeyCode::Char('1') => { app.active_panel = Panel::Data; return; }
-        KeyCode::Char('2') => { app.active_panel = Panel::Strategy; return; }
-        KeyCode::Char('3') => { app.active_panel = Panel::Sweep; return; }
-        KeyCode::Char('4') => { app.active_panel = Panel::Results; return; }
-        KeyCode::Char('5') => { app.active_panel = Panel::Chart; return; }
-        KeyCode::Char('6') => { app.active_panel = Panel::Help; return; }
-        KeyCode::Tab => {
-            if key.modifiers.contains(KeyModifiers::SHIFT) {
-                app.active_panel = app.active_panel.prev();
-            } else {
-                app.active_panel = app.active_panel.next();
-            }
-            return;
-        }
-        KeyCode::BackTab => {
-            app.active_panel = app.active_panel.prev();
-            return;
-        }
+fn handle_global(app: &mut AppState, action: Action) {
+    match action {
+        Action::ShowHelp => app.show_help = true,
+        Action::Quit => app.running = false,
+        Action::FocusPanel(panel) => app.active_panel = panel,
+        Action::NextPanel => app.active_panel = app.active_panel.next(),
+        Action::PrevPanel => app.active_panel = app.active_panel.prev(),
+        Action::OpenYolo => app.overlay = Overlay::YoloDashboard,
+        Action::OpenSessions => app.open_session_browser(),
+        Action::CycleTheme => app.cycle_theme(),
         _ => {}
     }
+}
 
-    // 3. Panel-specific keys.
-    match app.active_panel {
-        Panel::Data => handle_data_key(app, key),
-        Panel::Strategy => handle_strategy_key(app, key),
-        Panel::Sweep => handle_sweep_key(app, key),
-        Panel::Results => handle_results_key(app, key),
-        Panel::Chart => {} // display only
-        Panel::Help => handle_help_key(app, key),
+fn handle_chart_key(app: &mut AppState, action: Action) {
+    if action == Action::ToggleDrawdown {
+        app.chart.show_drawdown = !app.chart.show_drawdown;
     }
 }
 
-fn handle_error_overlay(app: &mut AppState, key: KeyEvent) {
-    match key.code {
-        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('e') => {
+fn handle_error_overlay(app: &mut AppState, action: Action) {
+    match action {
+        Action::Close => {
             app.overlay = Overlay::None;
         }
-        KeyCode::Char('j') | KeyCode::Down => {
-            if app.error_scroll + 1 < app.error_history.len() {
-                app.error_scroll += 1;
-            }
+        Action::Down if app.error_scroll + 1 < app.error_history.len() => {
+            app.error_scroll += 1;
         }
-        KeyCode::Char('k') | KeyCode::Up => {
+        Action::Up => {
             app.error_scroll = app.error_scroll.saturating_sub(1);
         }
         _ => {}
     }
 }
 
-fn handle_search_overlay(app: &mut AppState, key: KeyEvent) {
-    match key.code {
-        KeyCode::Esc => {
+fn handle_search_overlay(app: &mut AppState, action: Action, key: KeyEvent) {
+    match action {
+        Action::Close => {
             app.overlay = Overlay::None;
             app.search_input.clear();
         }
-        KeyCode::Enter => {
+        Action::Confirm => {
             let symbol = app.search_input.trim().to_uppercase();
             if !symbol.is_empty() {
                 // Add to universe under "Custom" sector and select it.
@@ -114,51 +111,256 @@ fn handle_search_overlay(app: &mut AppState, key: KeyEvent) {
             app.search_input.clear();
             app.overlay = Overlay::None;
         }
-        KeyCode::Backspace => {
+        Action::DeleteChar => {
             app.search_input.pop();
         }
-        KeyCode::Char(c) => {
-            app.search_input.push(c);
+        Action::TypeChar => {
+            if let KeyCode::Char(c) = key.code {
+                app.search_input.push(c);
+            }
         }
         _ => {}
     }
 }
 
-fn handle_detail_overlay(app: &mut AppState, key: KeyEvent) {
-    match key.code {
-        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
+fn handle_filter_overlay(app: &mut AppState, action: Action, key: KeyEvent) {
+    match action {
+        Action::ClearFilter => {
+            app.results.clear_filter();
+            app.overlay = Overlay::None;
+        }
+        Action::Confirm => {
+            // Keep the last query that parsed
+            if app.results.filter_error.is_some() {
+                let text = app.results.filter.as_ref().map(|q| q.text.clone());
+                app.results.set_filter_input(text.unwrap_or_default());
+            }
             app.overlay = Overlay::None;
         }
+        Action::DeleteChar => {
+            let mut text = app.results.filter_input.clone();
+            text.pop();
+            app.results.set_filter_input(text);
+        }
+        Action::TypeChar => {
+            if let KeyCode::Char(c) = key.code {
+                let mut text = app.results.filter_input.clone();
+                text.push(c);
+                app.results.set_filter_input(text);
+            }
+        }
         _ => {}
     }
 }
 
-fn handle_data_key(app: &mut AppState, key: KeyEvent) {
-    let row_count = app.data.visible_row_count();
+fn handle_detail_overlay(app: &mut AppState, action: Action, idx: usize) {
+    match action {
+        Action::Close => app.close_detail(),
+        Action::ToggleWorstDays => {
+            app.results.worst_days_expanded = !app.results.worst_days_expanded;
+        }
+        Action::OpenReplay => app.open_replay(idx),
+        Action::Export => app.export_trades(idx),
+        Action::EditNote => app.open_note_editor(idx, None),
+        Action::OpenDateWindow => app.open_date_window(idx),
+        Action::OpenNudge => app.open_nudge(idx),
+        _ => {}
+    }
+}
 
-    match key.code {
-        KeyCode::Char('j') | KeyCode::Down => {
-            if row_count > 0 && app.data.cursor.row + 1 < row_count {
-                app.data.cursor.row += 1;
+fn handle_date_window(app: &mut AppState, action: Action, key: KeyEvent, idx: usize) {
+    match action {
+        Action::Close => app.overlay = Overlay::Detail(idx),
+        Action::Confirm => app.apply_date_window(idx),
+        Action::TypeChar => {
+            if let KeyCode::Char(c) = key.code {
+                app.results.window_input.push(c);
             }
         }
-        KeyCode::Char('k') | KeyCode::Up => {
+        Action::DeleteChar => {
+            app.results.window_input.pop();
+        }
+        _ => {}
+    }
+}
+
+fn handle_nudge(app: &mut AppState, action: Action) {
+    let Some(nudge) = app.nudge.as_mut() else {
+        app.overlay = Overlay::None;
+        return;
+    };
+    match action {
+        Action::Close => app.close_nudge(),
+        Action::Down => nudge.move_cursor(1),
+        Action::Up => nudge.move_cursor(-1),
+        Action::Right => nudge.nudge(1),
+        Action::Left => nudge.nudge(-1),
+        Action::Confirm => app.rerun_nudge(),
+        _ => {}
+    }
+}
+
+fn handle_replay_overlay(app: &mut AppState, action: Action) {
+    let Some(replay) = app.replay.as_mut() else {
+        app.overlay = Overlay::None;
+        return;
+    };
+    match action {
+        Action::Close => app.close_replay(),
+        Action::Step(bars) => replay.step(bars as isize),
+        Action::JumpToEntry => replay.jump_to_entry(),
+        Action::JumpToExit => replay.jump_to_exit(),
+        Action::CyclePosition(direction) => replay.cycle_position(direction as isize),
+        Action::CycleIndicator => replay.cycle_indicator(),
+        Action::NextLoser => {
+            let found = replay.next_loser();
+            if !found {
+                app.set_status("No losing trades in this run");
+            }
+        }
+        Action::EditNote => {
+            let (entry, entry_bar) = (replay.entry, replay.current().entry_bar);
+            app.open_note_editor(entry, Some(entry_bar));
+        }
+        _ => {}
+    }
+}
+
+fn handle_note_editor(app: &mut AppState, action: Action, key: KeyEvent) {
+    let Some(editor) = app.note_editor.as_mut() else {
+        app.overlay = Overlay::None;
+        return;
+    };
+    match action {
+        Action::Close => app.close_note_editor(),
+        Action::Confirm => app.save_note(),
+        Action::SwitchField => editor.tags_focused = !editor.tags_focused,
+        Action::TypeChar => {
+            if let KeyCode::Char(c) = key.code {
+                editor.text.push(c);
+            }
+        }
+        Action::DeleteChar => {
+            editor.text.pop();
+        }
+        Action::Left => editor.move_tag_cursor(-1),
+        Action::Right => editor.move_tag_cursor(1),
+        Action::Toggle => editor.toggle_tag(),
+        _ => {}
+    }
+}
+
+fn handle_yolo_dashboard(app: &mut AppState, action: Action) {
+    match action {
+        Action::Close => {
+            app.overlay = Overlay::None;
+        }
+        Action::ToggleYolo => {
+            if app.sweep.yolo_running {
+                app.stop_yolo();
+            } else {
+                app.start_yolo();
+            }
+        }
+        Action::NudgeExplore(direction) => {
+            app.sweep.nudge_explore(EXPLORE_NUDGE * direction as f64);
+        }
+        _ => {}
+    }
+}
+
+fn handle_sessions_overlay(app: &mut AppState, action: Action) {
+    // Opened session: browse its leaderboards
+    if let Some(open) = &mut app.sessions.open {
+        let row_count = open.current().entries.len();
+        match action {
+            Action::Close => app.sessions.open = None,
+            Action::Down if open.cursor + 1 < row_count => {
+                open.cursor += 1;
+            }
+            Action::Up => {
+                open.cursor = open.cursor.saturating_sub(1);
+            }
+            Action::Right => open.cycle_board(1),
+            Action::Left => open.cycle_board(-1),
+            Action::Confirm => app.drill_into_session_entry(),
+            _ => {}
+        }
+        return;
+    }
+
+    match action {
+        Action::Close => {
+            app.overlay = Overlay::None;
+        }
+        Action::Down if app.sessions.cursor + 1 < app.sessions.sessions.len() => {
+            app.sessions.cursor += 1;
+        }
+        Action::Up => {
+            app.sessions.cursor = app.sessions.cursor.saturating_sub(1);
+        }
+        Action::Confirm => app.open_selected_session(),
+        _ => {}
+    }
+}
+
+fn handle_cache_manager(app: &mut AppState, action: Action) {
+    let rows = app.data.cache_rows();
+    let manager = &mut app.data.manager;
+    match action {
+        Action::Close => {
+            app.overlay = Overlay::None;
+        }
+        Action::Down if manager.cursor + 1 < rows.len() => {
+            manager.cursor += 1;
+        }
+        Action::Up => {
+            manager.cursor = manager.cursor.saturating_sub(1);
+        }
+        Action::Toggle => {
+            if let Some(symbol) = rows.get(manager.cursor) {
+                if !manager.marked.remove(symbol) {
+                    manager.marked.insert(symbol.clone());
+                }
+            }
+        }
+        Action::SelectAll => {
+            manager.marked = rows.into_iter().collect();
+        }
+        Action::ShiftRange { end, years } => app.shift_download_range(end, years),
+        Action::Confirm => app.download_marked(),
+        Action::RetryFailed => app.retry_failed_downloads(),
+        Action::CancelFetch if app.data.fetch_in_progress => {
+            app.cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+            app.set_warning("Cancelling fetch...");
+        }
+        _ => {}
+    }
+}
+
+fn handle_data_key(app: &mut AppState, action: Action) {
+    let row_count = app.data.visible_row_count();
+
+    match action {
+        Action::Down if row_count > 0 && app.data.cursor.row + 1 < row_count => {
+            app.data.cursor.row += 1;
+        }
+        Action::Up => {
             app.data.cursor.row = app.data.cursor.row.saturating_sub(1);
         }
-        KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter => {
+        Action::Right => {
             // Expand sector
             if let Some(TreeItem::Sector(name)) = app.data.cursor_item() {
                 app.data.expanded_sectors.insert(name);
             }
         }
-        KeyCode::Char('h') | KeyCode::Left => {
+        Action::Left => {
             // Collapse sector
             if let Some(TreeItem::Sector(name)) = app.data.cursor_item() {
                 app.data.expanded_sectors.remove(&name);
             }
         }
-        KeyCode::Char(' ') => {
-            // Toggle selection
+        Action::Toggle => {
             match app.data.cursor_item() {
                 Some(TreeItem::Sector(sector)) => {
                     if let Some(tickers) = app.data.universe.sector_tickers(&sector) {
@@ -182,51 +384,39 @@ fn handle_data_key(app: &mut AppState, key: KeyEvent) {
                 None => {}
             }
         }
-        KeyCode::Char('a') => {
+        Action::SelectAll => {
             // Select all
             for ticker in app.data.universe.all_tickers() {
                 app.data.selected.insert(ticker.to_string());
             }
         }
-        KeyCode::Char('d') => {
+        Action::DeselectAll => {
             // Deselect all
             app.data.selected.clear();
         }
-        KeyCode::Char('f') => {
+        Action::Fetch => {
             // Fetch selected tickers
-            if !app.data.selected.is_empty() && !app.data.fetch_in_progress {
-                let symbols: Vec<String> = app.data.selected.iter().cloned().collect();
-                let start = app.sweep.config.start_date;
-                let end = app.sweep.config.end_date;
-                app.data.fetch_in_progress = true;
-                app.data.fetch_done = 0;
-                app.data.fetch_total = symbols.len();
-                let _ = app.worker_tx.send(crate::worker::WorkerCommand::FetchData {
-                    symbols,
-                    start,
-                    end,
-                    cache_dir: app.cache_dir.clone(),
-                });
-                app.set_status("Fetching data...");
-            }
+            let symbols: Vec<String> = app.data.selected.iter().cloned().collect();
+            let start = app.sweep.config.start_date;
+            let end = app.sweep.config.end_date;
+            app.fetch_symbols(symbols, start, end);
         }
-        KeyCode::Char('s') => {
+        Action::OpenCacheManager => app.open_cache_manager(),
+        Action::OpenSearch => {
             app.overlay = Overlay::Search;
             app.search_input.clear();
         }
-        KeyCode::Esc => {
-            if app.data.fetch_in_progress {
-                app.cancel.store(true, std::sync::atomic::Ordering::Relaxed);
-                app.set_warning("Cancelling fetch...");
-            }
+        Action::CancelFetch if app.data.fetch_in_progress => {
+            app.cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+            app.set_warning("Cancelling fetch...");
         }
         _ => {}
     }
 }
 
-fn handle_strategy_key(app: &mut AppState, key: KeyEvent) {
-    match key.code {
-        KeyCode::Char('j') | KeyCode::Down => {
+fn handle_strategy_key(app: &mut AppState, action: Action) {
+    match action {
+        Action::Down => {
             // Move to next component or param
             let param_count = app
                 .strategy
@@ -240,7 +430,7 @@ fn handle_strategy_key(app: &mut AppState, key: KeyEvent) {
                 app.strategy.active_param = 0;
             }
         }
-        KeyCode::Char('k') | KeyCode::Up => {
+        Action::Up => {
             if app.strategy.active_param > 0 {
                 app.strategy.active_param -= 1;
             } else if app.strategy.active_component > 0 {
@@ -250,14 +440,14 @@ fn handle_strategy_key(app: &mut AppState, key: KeyEvent) {
                     prev_variant.param_ranges.len().saturating_sub(1);
             }
         }
-        KeyCode::Char('h') | KeyCode::Left => {
+        Action::Left => {
             // If on param row: decrease value. If on component header: cycle type left.
             adjust_strategy(app, -1);
         }
-        KeyCode::Char('l') | KeyCode::Right => {
+        Action::Right => {
             adjust_strategy(app, 1);
         }
-        KeyCode::Enter => {
+        Action::Confirm => {
             // Launch single backtest
             if app.data.selected.is_empty() {
                 app.set_warning("Select tickers in Data panel first");
@@ -266,7 +456,7 @@ fn handle_strategy_key(app: &mut AppState, key: KeyEvent) {
             let config = app.strategy.to_strategy_config();
             let symbols: Vec<String> = app.data.selected.iter().cloned().collect();
             let _ = app.worker_tx.send(crate::worker::WorkerCommand::RunSingleBacktest {
-                config,
+                config: Box::new(config),
                 symbols,
                 trading_mode: app.strategy.trading_mode,
                 initial_capital: app.strategy.initial_capital,
@@ -332,48 +522,24 @@ fn adjust_strategy(app: &mut AppState, direction: i32) {
     }
 }
 
-fn handle_sweep_key(app: &mut AppState, key: KeyEvent) {
+fn handle_sweep_key(app: &mut AppState, action: Action) {
     let setting_count = app.sweep.setting_count();
 
-    match key.code {
-        KeyCode::Char('j') | KeyCode::Down => {
-            if app.sweep.cursor + 1 < setting_count {
-                app.sweep.cursor += 1;
-            }
+    match action {
+        Action::Down if app.sweep.cursor + 1 < setting_count => {
+            app.sweep.cursor += 1;
         }
-        KeyCode::Char('k') | KeyCode::Up => {
+        Action::Up => {
             app.sweep.cursor = app.sweep.cursor.saturating_sub(1);
         }
-        KeyCode::Char('h') | KeyCode::Left => {
+        Action::Left => {
             adjust_sweep_setting(app, -1);
         }
-        KeyCode::Char('l') | KeyCode::Right => {
+        Action::Right => {
             adjust_sweep_setting(app, 1);
         }
-        KeyCode::Enter => {
-            if !app.sweep.yolo_running {
-                if app.data.selected.is_empty() {
-                    app.set_warning("Select tickers in Data panel first");
-                    return;
-                }
-                let symbols: Vec<String> = app.data.selected.iter().cloned().collect();
-                let mut config = app.sweep.config.clone();
-                config.enforce_thread_constraints();
-                let _ = app.worker_tx.send(crate::worker::WorkerCommand::StartYolo {
-                    config,
-                    symbols,
-                    cache_dir: app.cache_dir.clone(),
-                });
-                app.sweep.yolo_running = true;
-                app.set_status("YOLO mode started");
-            }
-        }
-        KeyCode::Esc => {
-            if app.sweep.yolo_running {
-                app.cancel.store(true, std::sync::atomic::Ordering::Relaxed);
-                app.set_warning("Stopping YOLO...");
-            }
-        }
+        Action::StartYolo => app.start_yolo(),
+        Action::StopYolo => app.stop_yolo(),
         _ => {}
     }
 }
@@ -401,27 +567,27 @@ fn adjust_sweep_setting(app: &mut AppState, direction: i32) {
     }
     // Enforce thread constraints
     c.enforce_thread_constraints();
+    // Slider changes reach a running session on its next iteration
+    app.sweep.sync_tunables();
 }
 
-fn handle_results_key(app: &mut AppState, key: KeyEvent) {
-    let entry_count = app.results.entries.len();
+fn handle_results_key(app: &mut AppState, action: Action) {
+    let entry_count = app.results.visible_indices().len();
 
-    match key.code {
-        KeyCode::Char('j') | KeyCode::Down => {
-            if entry_count > 0 && app.results.cursor + 1 < entry_count {
-                app.results.cursor += 1;
-            }
+    match action {
+        Action::Down if entry_count > 0 && app.results.cursor + 1 < entry_count => {
+            app.results.cursor += 1;
         }
-        KeyCode::Char('k') | KeyCode::Up => {
+        Action::Up => {
             app.results.cursor = app.results.cursor.saturating_sub(1);
         }
-        KeyCode::Char('t') => {
+        Action::ToggleSessionFilter => {
             app.results.session_filter = match app.results.session_filter {
                 SessionFilter::Session => SessionFilter::AllTime,
                 SessionFilter::AllTime => SessionFilter::Session,
             };
         }
-        KeyCode::Char('p') => {
+        Action::CycleRiskProfile => {
             app.results.risk_profile = match app.results.risk_profile {
                 RiskProfile::Balanced => RiskProfile::Conservative,
                 RiskProfile::Conservative => RiskProfile::Aggressive,
@@ -429,19 +595,33 @@ fn handle_results_key(app: &mut AppState, key: KeyEvent) {
                 RiskProfile::TrendOptions => RiskProfile::Balanced,
             };
         }
-        KeyCode::Enter => {
-            if !app.results.entries.is_empty() {
+        Action::ToggleDiversified => {
+            app.results.diversified_view = !app.results.diversified_view;
+            app.results.cursor = 0;
+            app.results.scroll_offset = 0;
+        }
+        Action::OpenFilter => app.overlay = Overlay::Filter,
+        Action::Export => app.export_leaderboard(),
+        Action::EditNote => {
+            if let Some(idx) = app.results.selected_index() {
+                app.open_note_editor(idx, None);
+            }
+        }
+        Action::ClearFilter if app.results.filter.is_some() => app.results.clear_filter(),
+        Action::Confirm => {
+            if let Some(idx) = app.results.selected_index() {
                 // Open detail overlay and populate chart
-                let idx = app.results.cursor;
                 app.overlay = Overlay::Detail(idx);
+                app.results.worst_days_expanded = false;
+                app.show_equity(idx);
             }
         }
         _ => {}
     }
 }
 
-fn handle_help_key(app: &mut AppState, key: KeyEvent) {
-    if let KeyCode::Char('e') = key.code {
+fn handle_help_key(app: &mut AppState, action: Action) {
+    if action == Action::OpenErrorHistory {
         app.overlay = Overlay::ErrorHistory;
         app.error_scroll = 0;
     }
@@ -0,0 +1,537 @@
+//! Declarative keymap — every key binding, by context, in one table.
+//!
+//! `input::handle_key` resolves a key press to a [`Binding`] in [`KEYMAP`]
+//! and dispatches its [`Action`]; the help panel and the `?` cheat sheet
+//! render the same table, so the documented keys are the keys that work.
+//!
+//! A key press is looked up through the current context's
+//! [`KeyContext::layers`]: `?` first, then (on panels) the global keys, then
+//! the context's own bindings. A key bound twice across one context's layers
+//! would leave one binding dead, which the tests reject.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use KeyCode::{BackTab, Backspace, Char, Down, End, Enter, Esc, Home, Left, Right, Tab, Up};
+
+use crate::app::{AppState, Overlay, Panel};
+use Action as A;
+use KeyContext as C;
+
+/// Where a key press lands: an open overlay, else the active panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyContext {
+    /// Bound in every context (except the cheat sheet itself).
+    Always,
+    /// Bound on every panel while no overlay is open.
+    Global,
+    Data,
+    Strategy,
+    Sweep,
+    Results,
+    Chart,
+    HelpPanel,
+    Welcome,
+    CacheManager,
+    Search,
+    Filter,
+    Detail,
+    Replay,
+    /// Note text of the note editor.
+    Note,
+    /// Tag picker of the note editor.
+    NoteTags,
+    /// Date-window prompt of the detail view.
+    DateWindow,
+    /// Parameter nudging of the detail view.
+    Nudge,
+    YoloDashboard,
+    /// Session list of the session browser.
+    Sessions,
+    /// An opened session's leaderboards.
+    SessionBoard,
+    ErrorHistory,
+    /// The `?` cheat sheet.
+    HelpOverlay,
+}
+
+impl KeyContext {
+    pub const ALL: [KeyContext; 23] = [
+        KeyContext::Always,
+        KeyContext::Global,
+        KeyContext::Data,
+        KeyContext::Strategy,
+        KeyContext::Sweep,
+        KeyContext::Results,
+        KeyContext::Chart,
+        KeyContext::HelpPanel,
+        KeyContext::Welcome,
+        KeyContext::CacheManager,
+        KeyContext::Search,
+        KeyContext::Filter,
+        KeyContext::Detail,
+        KeyContext::Replay,
+        KeyContext::Note,
+        KeyContext::NoteTags,
+        KeyContext::DateWindow,
+        KeyContext::Nudge,
+        KeyContext::YoloDashboard,
+        KeyContext::Sessions,
+        KeyContext::SessionBoard,
+        KeyContext::ErrorHistory,
+        KeyContext::HelpOverlay,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            KeyContext::Always => "Everywhere",
+            KeyContext::Global => "Global Navigation",
+            KeyContext::Data => "Panel 1 — Data",
+            KeyContext::Strategy => "Panel 2 — Strategy",
+            KeyContext::Sweep => "Panel 3 — Sweep (YOLO)",
+            KeyContext::Results => "Panel 4 — Results",
+            KeyContext::Chart => "Panel 5 — Chart",
+            KeyContext::HelpPanel => "Panel 6 — Help",
+            KeyContext::Welcome => "Welcome",
+            KeyContext::CacheManager => "Cache Manager (c in Data)",
+            KeyContext::Search => "Symbol Search (s in Data)",
+            KeyContext::Filter => "Leaderboard Filter (/ in Results)",
+            KeyContext::Detail => "Strategy Detail (Enter in Results)",
+            KeyContext::Replay => "Bar Replay (r in detail)",
+            KeyContext::Note => "Note Editor (n on a run, N in replay)",
+            KeyContext::NoteTags => "Note Tags (Tab in the note editor)",
+            KeyContext::DateWindow => "Date Window (d in detail)",
+            KeyContext::Nudge => "Parameter Nudge (p in detail)",
+            KeyContext::YoloDashboard => "YOLO Dashboard (Y)",
+            KeyContext::Sessions => "Session Browser (S)",
+            KeyContext::SessionBoard => "Session Leaderboards (Enter on a session)",
+            KeyContext::ErrorHistory => "Error History (e in Help)",
+            KeyContext::HelpOverlay => "Cheat Sheet (?)",
+        }
+    }
+
+    /// Contexts searched for a key press in this context, first match wins.
+    pub fn layers(self) -> &'static [KeyContext] {
+        use KeyContext::*;
+        match self {
+            Always => &[Always],
+            Global => &[Always, Global],
+            Data => &[Always, Global, Data],
+            Strategy => &[Always, Global, Strategy],
+            Sweep => &[Always, Global, Sweep],
+            Results => &[Always, Global, Results],
+            Chart => &[Always, Global, Chart],
+            HelpPanel => &[Always, Global, HelpPanel],
+            Welcome => &[Always, Welcome],
+            CacheManager => &[Always, CacheManager],
+            Search => &[Always, Search],
+            Filter => &[Always, Filter],
+            Detail => &[Always, Detail],
+            Replay => &[Always, Replay],
+            Note => &[Always, Note],
+            NoteTags => &[Always, NoteTags],
+            DateWindow => &[Always, DateWindow],
+            Nudge => &[Always, Nudge],
+            YoloDashboard => &[Always, YoloDashboard],
+            Sessions => &[Always, Sessions],
+            SessionBoard => &[Always, SessionBoard],
+            ErrorHistory => &[Always, ErrorHistory],
+            HelpOverlay => &[HelpOverlay],
+        }
+    }
+
+    /// The context key presses currently go to.
+    pub fn current(app: &AppState) -> Self {
+        if app.show_help {
+            return KeyContext::HelpOverlay;
+        }
+        Self::beneath_help(app)
+    }
+
+    /// The context under the cheat sheet — the one it documents.
+    pub fn beneath_help(app: &AppState) -> Self {
+        match app.overlay {
+            Overlay::None => match app.active_panel {
+                Panel::Data => KeyContext::Data,
+                Panel::Strategy => KeyContext::Strategy,
+                Panel::Sweep => KeyContext::Sweep,
+                Panel::Results => KeyContext::Results,
+                Panel::Chart => KeyContext::Chart,
+                Panel::Help => KeyContext::HelpPanel,
+            },
+            Overlay::Welcome => KeyContext::Welcome,
+            Overlay::Detail(_) => KeyContext::Detail,
+            Overlay::ErrorHistory => KeyContext::ErrorHistory,
+            Overlay::Search => KeyContext::Search,
+            Overlay::YoloDashboard => KeyContext::YoloDashboard,
+            Overlay::Sessions if app.sessions.open.is_some() => KeyContext::SessionBoard,
+            Overlay::Sessions => KeyContext::Sessions,
+            Overlay::CacheManager => KeyContext::CacheManager,
+            Overlay::Replay => KeyContext::Replay,
+            Overlay::Filter => KeyContext::Filter,
+            Overlay::Note if app.note_editor.as_ref().is_some_and(|e| e.tags_focused) => {
+                KeyContext::NoteTags
+            }
+            Overlay::Note => KeyContext::Note,
+            Overlay::DateWindow(_) => KeyContext::DateWindow,
+            Overlay::Nudge => KeyContext::Nudge,
+        }
+    }
+
+    /// This context's own bindings, in table order.
+    pub fn bindings(self) -> impl Iterator<Item = &'static Binding> {
+        KEYMAP.iter().filter(move |b| b.context == self)
+    }
+}
+
+/// What a binding does. Handlers in `input` interpret an action for the
+/// context it is bound in (`Confirm` runs a backtest on the Strategy panel
+/// and opens a session in the session browser).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    ShowHelp,
+    CloseHelp,
+    Quit,
+    FocusPanel(Panel),
+    NextPanel,
+    PrevPanel,
+    OpenYolo,
+    OpenSessions,
+    CycleTheme,
+    Close,
+    Down,
+    Up,
+    Left,
+    Right,
+    Confirm,
+    Toggle,
+    SelectAll,
+    DeselectAll,
+    Fetch,
+    CancelFetch,
+    OpenCacheManager,
+    OpenSearch,
+    /// Move the download range's start (or `end`) by `years`.
+    ShiftRange { end: bool, years: i32 },
+    RetryFailed,
+    StartYolo,
+    StopYolo,
+    ToggleYolo,
+    /// Nudge structural explore up (1) or down (-1).
+    NudgeExplore(i32),
+    ToggleSessionFilter,
+    CycleRiskProfile,
+    ToggleDiversified,
+    OpenFilter,
+    ClearFilter,
+    Export,
+    OpenErrorHistory,
+    TypeChar,
+    DeleteChar,
+    ToggleWorstDays,
+    OpenReplay,
+    Step(i32),
+    JumpToEntry,
+    JumpToExit,
+    CyclePosition(i32),
+    CycleIndicator,
+    NextLoser,
+    /// Open the note editor on the run (or replayed trade).
+    EditNote,
+    /// Move between the note text and the tag picker.
+    SwitchField,
+    /// Open the date-window prompt on the detail view.
+    OpenDateWindow,
+    /// Open parameter nudging on the detail view.
+    OpenNudge,
+    /// Show or hide the chart's drawdown strip.
+    ToggleDrawdown,
+}
+
+/// Which key presses a binding matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keys {
+    Codes(&'static [KeyCode]),
+    /// Any character key not bound otherwise (text entry).
+    AnyChar,
+    /// Any key not bound otherwise.
+    AnyKey,
+}
+
+impl Keys {
+    pub fn label(self) -> String {
+        match self {
+            Keys::Codes(codes) => codes.iter().map(|&c| code_label(c)).collect::<Vec<_>>().join(" / "),
+            Keys::AnyChar => "type".to_string(),
+            Keys::AnyKey => "any key".to_string(),
+        }
+    }
+}
+
+/// One row of the keymap.
+#[derive(Debug, Clone, Copy)]
+pub struct Binding {
+    pub context: KeyContext,
+    pub keys: Keys,
+    pub action: Action,
+    pub description: &'static str,
+}
+
+const fn bind(
+    context: KeyContext,
+    codes: &'static [KeyCode],
+    action: Action,
+    description: &'static str,
+) -> Binding {
+    Binding { context, keys: Keys::Codes(codes), action, description }
+}
+
+/// Every key binding of the TUI.
+pub const KEYMAP: &[Binding] = &[
+    bind(C::Always, &[Char('?')], A::ShowHelp, "Show the keys for the current view"),
+    // Global
+    bind(C::Global, &[Char('1')], A::FocusPanel(Panel::Data), "Data panel"),
+    bind(C::Global, &[Char('2')], A::FocusPanel(Panel::Strategy), "Strategy panel"),
+    bind(C::Global, &[Char('3')], A::FocusPanel(Panel::Sweep), "Sweep panel"),
+    bind(C::Global, &[Char('4')], A::FocusPanel(Panel::Results), "Results panel"),
+    bind(C::Global, &[Char('5')], A::FocusPanel(Panel::Chart), "Chart panel"),
+    bind(C::Global, &[Char('6')], A::FocusPanel(Panel::Help), "Help panel"),
+    bind(C::Global, &[Tab], A::NextPanel, "Next panel"),
+    bind(C::Global, &[BackTab], A::PrevPanel, "Previous panel"),
+    bind(C::Global, &[Char('Y')], A::OpenYolo, "Open YOLO dashboard"),
+    bind(C::Global, &[Char('S')], A::OpenSessions, "Browse saved YOLO sessions"),
+    bind(C::Global, &[Char('T')], A::CycleTheme, "Cycle theme (dark → light → high-contrast → colorblind)"),
+    bind(C::Global, &[Char('q')], A::Quit, "Quit"),
+    // Data
+    bind(C::Data, &[Char('j'), Down], A::Down, "Move cursor down"),
+    bind(C::Data, &[Char('k'), Up], A::Up, "Move cursor up"),
+    bind(C::Data, &[Char('l'), Right, Enter], A::Right, "Expand sector"),
+    bind(C::Data, &[Char('h'), Left], A::Left, "Collapse sector"),
+    bind(C::Data, &[Char(' ')], A::Toggle, "Toggle ticker (or whole sector) selection"),
+    bind(C::Data, &[Char('a')], A::SelectAll, "Select all tickers"),
+    bind(C::Data, &[Char('d')], A::DeselectAll, "Deselect all tickers"),
+    bind(C::Data, &[Char('f')], A::Fetch, "Fetch data for selected tickers"),
+    bind(C::Data, &[Char('s')], A::OpenSearch, "Search: add a custom symbol"),
+    bind(C::Data, &[Char('c')], A::OpenCacheManager, "Open the cache manager"),
+    bind(C::Data, &[Esc], A::CancelFetch, "Cancel in-progress fetch"),
+    // Strategy
+    bind(C::Strategy, &[Char('j'), Down], A::Down, "Next component or parameter"),
+    bind(C::Strategy, &[Char('k'), Up], A::Up, "Previous component or parameter"),
+    bind(C::Strategy, &[Char('h'), Left], A::Left, "Previous component type / decrease param"),
+    bind(C::Strategy, &[Char('l'), Right], A::Right, "Next component type / increase param"),
+    bind(C::Strategy, &[Enter], A::Confirm, "Run single backtest"),
+    // Sweep
+    bind(C::Sweep, &[Char('j'), Down], A::Down, "Next setting"),
+    bind(C::Sweep, &[Char('k'), Up], A::Up, "Previous setting"),
+    bind(C::Sweep, &[Char('h'), Left], A::Left, "Decrease setting"),
+    bind(C::Sweep, &[Char('l'), Right], A::Right, "Increase setting"),
+    bind(C::Sweep, &[Enter], A::StartYolo, "Start YOLO mode"),
+    bind(C::Sweep, &[Esc], A::StopYolo, "Stop YOLO mode"),
+    // Results
+    bind(C::Results, &[Char('j'), Down], A::Down, "Scroll leaderboard down"),
+    bind(C::Results, &[Char('k'), Up], A::Up, "Scroll leaderboard up"),
+    bind(C::Results, &[Char('t')], A::ToggleSessionFilter, "Toggle session / all-time"),
+    bind(C::Results, &[Char('p')], A::CycleRiskProfile, "Cycle risk profile (Balanced → Conservative → Aggressive → TrendOptions)"),
+    bind(C::Results, &[Char('d')], A::ToggleDiversified, "Toggle diversified view (best config per overlap cluster)"),
+    bind(C::Results, &[Char('/')], A::OpenFilter, "Filter: text, field=value, metric<value (sharpe>1.5 dd<0.2)"),
+    bind(C::Results, &[Esc], A::ClearFilter, "Clear the active filter"),
+    bind(C::Results, &[Char('e')], A::Export, "Export the visible leaderboard to CSV (results/exports)"),
+    bind(C::Results, &[Char('n')], A::EditNote, "Edit the note and tags of the selected run"),
+    bind(C::Results, &[Enter], A::Confirm, "Open detail drill-down + chart"),
+    // Chart
+    bind(C::Chart, &[Char('u')], A::ToggleDrawdown, "Show / hide the drawdown (underwater) strip"),
+    // Help panel
+    bind(C::HelpPanel, &[Char('e')], A::OpenErrorHistory, "Open error history"),
+    // Welcome
+    Binding { context: C::Welcome, keys: Keys::AnyKey, action: A::Close, description: "Dismiss" },
+    // Cache manager
+    bind(C::CacheManager, &[Char('j'), Down], A::Down, "Next cached or downloading symbol"),
+    bind(C::CacheManager, &[Char('k'), Up], A::Up, "Previous cached or downloading symbol"),
+    bind(C::CacheManager, &[Char(' ')], A::Toggle, "Mark symbol for download"),
+    bind(C::CacheManager, &[Char('a')], A::SelectAll, "Mark all symbols"),
+    bind(C::CacheManager, &[Char('h')], A::ShiftRange { end: false, years: -1 }, "Download range starts a year earlier"),
+    bind(C::CacheManager, &[Char('l')], A::ShiftRange { end: false, years: 1 }, "Download range starts a year later"),
+    bind(C::CacheManager, &[Char('H')], A::ShiftRange { end: true, years: -1 }, "Download range ends a year earlier"),
+    bind(C::CacheManager, &[Char('L')], A::ShiftRange { end: true, years: 1 }, "Download range ends a year later"),
+    bind(C::CacheManager, &[Char('r'), Enter], A::Confirm, "Download marked symbols (or the one under the cursor)"),
+    bind(C::CacheManager, &[Char('R')], A::RetryFailed, "Retry failed downloads"),
+    bind(C::CacheManager, &[Char('x')], A::CancelFetch, "Cancel in-progress download"),
+    bind(C::CacheManager, &[Esc, Char('q'), Char('c')], A::Close, "Close"),
+    // Search
+    Binding { context: C::Search, keys: Keys::AnyChar, action: A::TypeChar, description: "Type the symbol" },
+    bind(C::Search, &[Backspace], A::DeleteChar, "Delete last character"),
+    bind(C::Search, &[Enter], A::Confirm, "Add the symbol to Custom and select it"),
+    bind(C::Search, &[Esc], A::Close, "Cancel"),
+    // Filter
+    Binding { context: C::Filter, keys: Keys::AnyChar, action: A::TypeChar, description: "Type the query" },
+    bind(C::Filter, &[Backspace], A::DeleteChar, "Delete last character"),
+    bind(C::Filter, &[Enter], A::Confirm, "Keep the filter (last query that parsed)"),
+    bind(C::Filter, &[Esc], A::ClearFilter, "Clear the filter and close"),
+    // Detail
+    bind(C::Detail, &[Char('w')], A::ToggleWorstDays, "Expand / collapse worst days"),
+    bind(C::Detail, &[Char('r')], A::OpenReplay, "Open bar replay of the run's positions"),
+    bind(C::Detail, &[Char('e')], A::Export, "Export the run's trades to CSV (exports_dir in tui.toml)"),
+    bind(C::Detail, &[Char('n')], A::EditNote, "Edit the run's note and tags"),
+    bind(C::Detail, &[Char('d')], A::OpenDateWindow, "Recompute metrics over a date window"),
+    bind(C::Detail, &[Char('p')], A::OpenNudge, "Nudge the run's params and re-run it"),
+    bind(C::Detail, &[Esc, Enter, Char('q')], A::Close, "Close"),
+    // Replay
+    bind(C::Replay, &[Char('l'), Right], A::Step(1), "Forward one bar"),
+    bind(C::Replay, &[Char('h'), Left], A::Step(-1), "Back one bar"),
+    bind(C::Replay, &[Char('L')], A::Step(10), "Forward ten bars"),
+    bind(C::Replay, &[Char('H')], A::Step(-10), "Back ten bars"),
+    bind(C::Replay, &[Char('e'), Home], A::JumpToEntry, "Jump to entry bar"),
+    bind(C::Replay, &[Char('x'), End], A::JumpToExit, "Jump to exit bar"),
+    bind(C::Replay, &[Char('n'), Char(']')], A::CyclePosition(1), "Next position"),
+    bind(C::Replay, &[Char('p'), Char('[')], A::CyclePosition(-1), "Previous position"),
+    bind(C::Replay, &[Char('f')], A::NextLoser, "Next losing position"),
+    bind(C::Replay, &[Char('i')], A::CycleIndicator, "Cycle indicator overlay"),
+    bind(C::Replay, &[Char('N')], A::EditNote, "Edit the note and tags of this position's trade"),
+    bind(C::Replay, &[Esc, Char('q')], A::Close, "Back to detail"),
+    // Note editor
+    Binding { context: C::Note, keys: Keys::AnyChar, action: A::TypeChar, description: "Type the note" },
+    bind(C::Note, &[Backspace], A::DeleteChar, "Delete last character"),
+    bind(C::Note, &[Tab], A::SwitchField, "Pick tags"),
+    bind(C::Note, &[Enter], A::Confirm, "Save the note and tags (an empty note and no tags removes them)"),
+    bind(C::Note, &[Esc], A::Close, "Discard changes"),
+    bind(C::NoteTags, &[Char('h'), Left], A::Left, "Previous tag"),
+    bind(C::NoteTags, &[Char('l'), Right], A::Right, "Next tag"),
+    bind(C::NoteTags, &[Char(' ')], A::Toggle, "Toggle tag"),
+    bind(C::NoteTags, &[Tab, BackTab], A::SwitchField, "Back to the note text"),
+    bind(C::NoteTags, &[Enter], A::Confirm, "Save the note and tags"),
+    bind(C::NoteTags, &[Esc, Char('q')], A::Close, "Discard changes"),
+    // Date window
+    Binding { context: C::DateWindow, keys: Keys::AnyChar, action: A::TypeChar, description: "Type FROM..TO (either side may be left out)" },
+    bind(C::DateWindow, &[Backspace], A::DeleteChar, "Delete last character"),
+    bind(C::DateWindow, &[Enter], A::Confirm, "Apply the window (empty: whole run)"),
+    bind(C::DateWindow, &[Esc], A::Close, "Back to detail"),
+    // Parameter nudge
+    bind(C::Nudge, &[Char('j'), Down], A::Down, "Next param"),
+    bind(C::Nudge, &[Char('k'), Up], A::Up, "Previous param"),
+    bind(C::Nudge, &[Char('+'), Char('='), Char('l'), Right], A::Right, "Raise the param one step"),
+    bind(C::Nudge, &[Char('-'), Char('h'), Left], A::Left, "Lower the param one step"),
+    bind(C::Nudge, &[Enter], A::Confirm, "Re-run with the edited params (added as a manual variant)"),
+    bind(C::Nudge, &[Esc, Char('q')], A::Close, "Back to detail"),
+    // YOLO dashboard
+    bind(C::YoloDashboard, &[Char('s')], A::ToggleYolo, "Start / stop YOLO session"),
+    bind(C::YoloDashboard, &[Char('+'), Char('=')], A::NudgeExplore(1), "Raise structural explore (applies next iteration)"),
+    bind(C::YoloDashboard, &[Char('-')], A::NudgeExplore(-1), "Lower structural explore (applies next iteration)"),
+    bind(C::YoloDashboard, &[Esc, Char('q'), Char('Y')], A::Close, "Close dashboard"),
+    // Session browser
+    bind(C::Sessions, &[Char('j'), Down], A::Down, "Next session"),
+    bind(C::Sessions, &[Char('k'), Up], A::Up, "Previous session"),
+    bind(C::Sessions, &[Enter], A::Confirm, "Open session"),
+    bind(C::Sessions, &[Esc, Char('q'), Char('S')], A::Close, "Close"),
+    bind(C::SessionBoard, &[Char('j'), Down], A::Down, "Next row"),
+    bind(C::SessionBoard, &[Char('k'), Up], A::Up, "Previous row"),
+    bind(C::SessionBoard, &[Char('l'), Right, Tab], A::Right, "Next leaderboard"),
+    bind(C::SessionBoard, &[Char('h'), Left, BackTab], A::Left, "Previous leaderboard"),
+    bind(C::SessionBoard, &[Enter], A::Confirm, "Drill into the saved run"),
+    bind(C::SessionBoard, &[Esc, Char('q')], A::Close, "Back to session list"),
+    // Error history
+    bind(C::ErrorHistory, &[Char('j'), Down], A::Down, "Older error"),
+    bind(C::ErrorHistory, &[Char('k'), Up], A::Up, "Newer error"),
+    bind(C::ErrorHistory, &[Esc, Char('q'), Char('e')], A::Close, "Close"),
+    // Cheat sheet
+    bind(C::HelpOverlay, &[Esc, Char('q'), Char('?')], A::CloseHelp, "Close"),
+];
+
+/// The binding a key press triggers in `context`, if any.
+pub fn lookup(context: KeyContext, key: &KeyEvent) -> Option<&'static Binding> {
+    let code = match key.code {
+        // Terminals report Shift+Tab either way
+        Tab if key.modifiers.contains(KeyModifiers::SHIFT) => BackTab,
+        code => code,
+    };
+    context.layers().iter().find_map(|&layer| {
+        let mut fallback = None;
+        for binding in layer.bindings() {
+            match binding.keys {
+                Keys::Codes(codes) if codes.contains(&code) => return Some(binding),
+                Keys::AnyChar if matches!(code, Char(_)) => fallback = Some(binding),
+                Keys::AnyKey => fallback = Some(binding),
+                _ => {}
+            }
+        }
+        fallback
+    })
+}
+
+fn code_label(code: KeyCode) -> String {
+    match code {
+        Char(' ') => "Space".to_string(),
+        Char(c) => c.to_string(),
+        Up => "↑".to_string(),
+        Down => "↓".to_string(),
+        Left => "←".to_string(),
+        Right => "→".to_string(),
+        BackTab => "Shift+Tab".to_string(),
+        other => format!("{other:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn no_key_is_bound_twice_in_a_context() {
+        for context in KeyContext::ALL {
+            let mut seen: Vec<(KeyCode, &str)> = Vec::new();
+            for binding in context.layers().iter().flat_map(|l| l.bindings()) {
+                let Keys::Codes(codes) = binding.keys else { continue };
+                for &code in codes {
+                    if let Some((_, other)) = seen.iter().find(|(c, _)| *c == code) {
+                        panic!(
+                            "{}: {} bound to both {other:?} and {:?}",
+                            context.title(),
+                            code_label(code),
+                            binding.description
+                        );
+                    }
+                    seen.push((code, binding.description));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn at_most_one_fallback_per_context() {
+        for context in KeyContext::ALL {
+            let fallbacks = context
+                .layers()
+                .iter()
+                .flat_map(|l| l.bindings())
+                .filter(|b| !matches!(b.keys, Keys::Codes(_)))
+                .count();
+            assert!(fallbacks <= 1, "{}: {fallbacks} fallback bindings", context.title());
+        }
+    }
+
+    #[test]
+    fn question_mark_opens_help_everywhere_and_closes_it() {
+        for context in KeyContext::ALL {
+            let action = lookup(context, &press(Char('?'))).map(|b| b.action);
+            let expected = if context == KeyContext::HelpOverlay { A::CloseHelp } else { A::ShowHelp };
+            assert_eq!(action, Some(expected), "{}", context.title());
+        }
+    }
+
+    #[test]
+    fn layers_resolve_in_order() {
+        // Global keys apply on panels but not inside overlays
+        assert_eq!(lookup(C::Data, &press(Char('q'))).unwrap().action, A::Quit);
+        assert_eq!(lookup(C::Detail, &press(Char('q'))).unwrap().action, A::Close);
+        // Text entry takes unbound characters but not Enter
+        assert_eq!(lookup(C::Search, &press(Char('q'))).unwrap().action, A::TypeChar);
+        assert_eq!(lookup(C::Search, &press(Enter)).unwrap().action, A::Confirm);
+        assert_eq!(lookup(C::Welcome, &press(Enter)).unwrap().action, A::Close);
+        assert!(lookup(C::Chart, &press(Char('x'))).is_none());
+
+        let mut shift_tab = press(Tab);
+        shift_tab.modifiers = KeyModifiers::SHIFT;
+        assert_eq!(lookup(C::Results, &shift_tab).unwrap().action, A::PrevPanel);
+    }
+}
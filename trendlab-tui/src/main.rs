@@ -7,36 +7,64 @@
 //! 4. Results — leaderboard display with rankings
 //! 5. Chart — equity curve visualization
 //! 6. Help — keyboard shortcuts and documentation
+//!
+//! Every key binding lives in [`keymap`]; `?` shows the ones valid in the
+//! current view.
 
 mod app;
+mod export;
 mod input;
+mod keymap;
 mod persistence;
+mod query;
 mod theme;
 mod ui;
 mod worker;
 
 use std::io::{self, stdout};
 use std::path::PathBuf;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::Result;
+use clap::Parser;
 use crossterm::event::{self, Event};
 use crossterm::execute;
 use crossterm::terminal::{
     EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
 };
 use ratatui::Terminal;
-use ratatui::backend::CrosstermBackend;
+use ratatui::backend::{Backend, CrosstermBackend};
+use trendlab_runner::Settings;
 
-use trendlab_core::data::cache::ParquetCache;
 
-use crate::app::{AppState, ErrorCategory};
+use crate::app::{AppState, DownloadStatus, ErrorCategory};
+use crate::theme::ThemeName;
 use crate::worker::{WorkerCommand, WorkerResponse};
 
+#[derive(Parser)]
+#[command(name = "trendlab-tui", about = "TrendLab terminal interface")]
+struct Args {
+    /// Color theme: dark, light, high-contrast, colorblind. Overrides the
+    /// theme saved in tui.toml for this run.
+    #[arg(long)]
+    theme: Option<ThemeName>,
+
+    /// Cache directory. Defaults to the cache_dir setting (./data).
+    #[arg(long)]
+    cache_dir: Option<PathBuf>,
+
+    /// Results directory. Defaults to the results_dir setting (./results).
+    #[arg(long)]
+    results_dir: Option<PathBuf>,
+}
+
 fn main() -> Result<()> {
+    let args = Args::parse();
+    let settings = Settings::load()?;
+
     // Install a panic hook that restores the terminal before printing the panic.
     let default_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
@@ -45,22 +73,28 @@ fn main() -> Result<()> {
         default_hook(info);
     }));
 
-    // Paths
-    let cache_dir = PathBuf::from("data");
-    let state_path = dirs::config_dir()
+    // Paths: flags, then TRENDLAB_* env vars, then config.toml
+    let cache_dir = settings.cache_dir.or_flag(args.cache_dir);
+    let results_dir = settings.results_dir.or_flag(args.results_dir);
+    let config_dir = dirs::config_dir()
         .unwrap_or_else(|| PathBuf::from("."))
-        .join("trendlab")
-        .join("state.json");
+        .join("trendlab");
+    let state_path = config_dir.join("state.json");
+    let tui_config_path = config_dir.join("tui.toml");
 
     // Load persisted state
     let persisted = persistence::load(&state_path);
+    let tui_config = persistence::load_tui_config(&tui_config_path);
+    theme::set(args.theme.or(tui_config.theme).unwrap_or_default());
 
     // Worker channels
     let (cmd_tx, cmd_rx) = mpsc::channel();
     let (resp_tx, resp_rx) = mpsc::channel();
     let cancel = Arc::new(AtomicBool::new(false));
 
-    // Spawn worker
+    // Spawn worker and equity loader
+    let (equity_tx, equity_rx) = mpsc::channel();
+    let _loader_handle = worker::spawn_equity_loader(equity_rx, resp_tx.clone());
     let worker_handle = worker::spawn_worker(cmd_rx, resp_tx, cancel.clone());
 
     // Build app state
@@ -72,11 +106,23 @@ fn main() -> Result<()> {
         state_path.clone(),
     );
 
+    app.equity_tx = Some(equity_tx);
+    app.sessions_dir = results_dir.join("sessions");
+    app.exports_dir = tui_config
+        .exports_dir
+        .clone()
+        .unwrap_or_else(|| results_dir.join("exports"));
+    app.tui_config_path = Some(tui_config_path);
+
     // Apply persisted state
     persistence::apply(&mut app, persisted);
 
+    // List saved runs from manifest headline stats; curves load on drill-down
+    app.load_saved_runs(trendlab_runner::scan_artifacts_report(&results_dir));
+    app.load_annotations(&results_dir);
+
     // Scan cache for existing data
-    scan_cache_status(&mut app, &cache_dir);
+    app.refresh_cache_listing();
 
     // Setup terminal
     enable_raw_mode()?;
@@ -93,7 +139,12 @@ fn main() -> Result<()> {
     let persisted = persistence::extract(&app);
     let _ = persistence::save(&state_path, &persisted);
 
-    // Shutdown worker
+    // Shutdown worker. A running YOLO session is cancelled first so the worker
+    // finishes its current iteration (history appends are flushed per entry)
+    // and returns to the command loop before the join.
+    if app.sweep.yolo_running {
+        cancel.store(true, Ordering::Relaxed);
+    }
     let _ = cmd_tx.send(WorkerCommand::Shutdown);
     let _ = worker_handle.join();
 
@@ -105,25 +156,28 @@ fn main() -> Result<()> {
     result
 }
 
+/// Longest wait for input per tick (~20 FPS while the spinner animates).
+const TICK: Duration = Duration::from_millis(50);
+
+/// Events handled per tick at most, so a key flood cannot starve rendering.
+const MAX_EVENTS_PER_TICK: usize = 64;
+
 fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut AppState,
 ) -> Result<()> {
     loop {
-        // 1. Render
-        terminal.draw(|f| ui::draw(f, app))?;
+        // 1. Render, only if something changed
+        render_if_needed(terminal, app)?;
 
         // 2. Drain worker responses (non-blocking)
         while let Ok(resp) = app.worker_rx.try_recv() {
             handle_worker_response(app, resp);
+            app.mark_dirty();
         }
 
-        // 3. Poll for input events (50ms timeout for ~20 FPS tick)
-        if event::poll(Duration::from_millis(50))? {
-            if let Event::Key(key) = event::read()? {
-                input::handle_key(app, key);
-            }
-        }
+        // 3. Handle every input event already queued, waiting up to one tick
+        handle_events(app, read_ready_events()?);
 
         // 4. Check quit
         if !app.running {
@@ -133,6 +187,47 @@ fn run_app(
     Ok(())
 }
 
+/// Draw a frame if the app needs one. Returns whether it drew.
+fn render_if_needed<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut AppState,
+) -> io::Result<bool> {
+    if !app.needs_redraw() {
+        return Ok(false);
+    }
+    terminal.draw(|f| ui::draw(f, app))?;
+    app.dirty = false;
+    Ok(true)
+}
+
+/// Wait up to one tick for input, then take everything else already queued,
+/// so holding j/k moves one row per key repeat rather than one per frame.
+fn read_ready_events() -> io::Result<Vec<Event>> {
+    let mut events = Vec::new();
+    if event::poll(TICK)? {
+        events.push(event::read()?);
+        while events.len() < MAX_EVENTS_PER_TICK && event::poll(Duration::ZERO)? {
+            events.push(event::read()?);
+        }
+    }
+    Ok(events)
+}
+
+/// Apply a batch of input events, marking the app dirty for keys and resizes.
+fn handle_events(app: &mut AppState, events: impl IntoIterator<Item = Event>) {
+    for event in events {
+        match event {
+            Event::Key(key) => input::handle_key(app, key),
+            Event::Resize(..) => {}
+            _ => continue,
+        }
+        app.mark_dirty();
+        if !app.running {
+            break;
+        }
+    }
+}
+
 fn handle_worker_response(app: &mut AppState, resp: WorkerResponse) {
     match resp {
         WorkerResponse::FetchProgress {
@@ -140,18 +235,36 @@ fn handle_worker_response(app: &mut AppState, resp: WorkerResponse) {
             index,
             total,
         } => {
+            app.data
+                .downloads
+                .insert(symbol.clone(), DownloadStatus::Downloading { attempt: 1 });
             app.data.fetch_current_symbol = Some(symbol);
             app.data.fetch_done = index;
             app.data.fetch_total = total;
         }
+        WorkerResponse::FetchRetry {
+            symbol,
+            attempt,
+            error,
+        } => {
+            app.data
+                .downloads
+                .insert(symbol.clone(), DownloadStatus::Downloading { attempt });
+            app.set_warning(format!("{symbol}: retry {attempt} after {error}"));
+        }
         WorkerResponse::FetchSymbolDone {
             symbol,
             success,
             error,
         } => {
             if success {
+                app.data.downloads.insert(symbol.clone(), DownloadStatus::Done);
                 app.data.cache_status.insert(symbol, true);
-            } else if let Some(err) = error {
+            } else {
+                let err = error.unwrap_or_else(|| "unknown error".into());
+                app.data
+                    .downloads
+                    .insert(symbol.clone(), DownloadStatus::Failed(err.clone()));
                 app.push_error(
                     ErrorCategory::Network,
                     format!("Failed to fetch: {err}"),
@@ -163,6 +276,13 @@ fn handle_worker_response(app: &mut AppState, resp: WorkerResponse) {
         WorkerResponse::FetchBatchDone { succeeded, failed } => {
             app.data.fetch_in_progress = false;
             app.data.fetch_current_symbol = None;
+            // Symbols a cancel skipped can be retried like failures
+            for status in app.data.downloads.values_mut() {
+                if *status == DownloadStatus::Pending {
+                    *status = DownloadStatus::Failed("cancelled".into());
+                }
+            }
+            app.refresh_cache_listing();
             if failed == 0 {
                 app.set_status(format!("Fetch complete: {succeeded} symbols downloaded"));
             } else {
@@ -170,13 +290,24 @@ fn handle_worker_response(app: &mut AppState, resp: WorkerResponse) {
             }
         }
         WorkerResponse::BacktestComplete { result } => {
+            // Move the large parts (curve, trades, replay, distributions) into
+            // the entry instead of cloning them. A copy without the replay is
+            // kept for date-window slicing
+            let mut result = *result;
+            let replay = std::mem::take(&mut result.replay);
+            let full = Arc::new(result.clone());
+            let stickiness = result.stickiness_report();
+            let attribution = result.attribution.totals();
+            let (trade_count, sharpe) = (result.metrics.trade_count, result.metrics.sharpe);
+            let equity_curve = result.equity_curve;
+            let provenance = result.provenance;
             let entry = app::LeaderboardDisplayEntry {
                 rank: app.results.entries.len() + 1,
                 signal_type: result.config.signal.component_type.clone(),
                 pm_type: result.config.position_manager.component_type.clone(),
                 exec_type: result.config.execution_model.component_type.clone(),
                 filter_type: result.config.signal_filter.component_type.clone(),
-                symbol: result.symbol.clone(),
+                symbol: result.symbol,
                 sharpe: result.metrics.sharpe,
                 cagr: result.metrics.cagr,
                 max_drawdown: result.metrics.max_drawdown,
@@ -185,39 +316,61 @@ fn handle_worker_response(app: &mut AppState, resp: WorkerResponse) {
                 trade_count: result.metrics.trade_count,
                 fitness_score: result.metrics.sharpe,
                 session_id: app.results.current_session_id.clone(),
-                config: result.config.clone(),
-                metrics: result.metrics.clone(),
-                stickiness: result.stickiness.clone(),
+                config: result.config,
+                metrics: result.metrics,
+                stickiness,
+                data_warnings: result.data_quality_warnings.len(),
+                truncated_range: result.truncated_range,
+                r_distribution: Some(result.r_distribution),
+                trade_distributions: Some(result.trade_distributions),
+                tail_metrics: result.tail_metrics,
+                style: result.style,
+                friction: None,
+                benchmark: None,
+                signal_value: None,
+                sensitivity: None,
+                fdr: None,
+                equity_sparkline: result.equity_sparkline,
+                artifact_dir: None,
+                cluster_id: None,
+                diversification: None,
+                cluster_leader: true,
+                provenance,
+                attribution,
+                replay: Some(Arc::new(replay)),
+                trades: Some(Arc::new(result.trades)),
+                result: Some(full),
             };
 
             // Populate chart with equity curve
-            app.chart.equity_curve = Some(result.equity_curve.clone());
-            app.chart.label = format!(
-                "{} | {} | Sharpe: {:.2}",
-                entry.symbol, entry.signal_type, entry.sharpe
-            );
-
             app.results.entries.push(entry);
-            app.set_status(format!(
-                "Backtest complete: {} trades, Sharpe {:.2}",
-                result.metrics.trade_count, result.metrics.sharpe
-            ));
+            let index = app.results.entries.len() - 1;
+            app.show_equity(index);
+            app.receive_equity(index, equity_curve);
+            app.set_status(match &app.results.entries[index].provenance {
+                Some(_) => format!("Variant complete: {trade_count} trades, Sharpe {sharpe:.2}"),
+                None => format!("Backtest complete: {trade_count} trades, Sharpe {sharpe:.2}"),
+            });
         }
         WorkerResponse::BacktestError { error } => {
             app.push_error(ErrorCategory::Engine, error, "single backtest".into());
         }
         WorkerResponse::YoloProgress(progress) => {
-            app.sweep.last_progress = Some(progress);
+            app.sweep.last_progress = Some(*progress);
         }
         WorkerResponse::YoloDone { result } => {
             app.sweep.yolo_running = false;
             app.sweep.last_progress = None;
+            app.add_yolo_champions(result.champions);
             app.set_status(format!(
-                "YOLO complete: {} iterations, {} ok, {} errors in {:.1}s",
+                "YOLO complete ({}): {} iterations, {} ok ({} no trades), {} errors in {:.1}s; {}",
+                result.stop_reason.map_or("stopped", |r| r.describe()),
                 result.iterations_completed,
                 result.success_count,
+                result.zero_trade_count,
                 result.error_count,
                 result.elapsed_secs,
+                result.compute.describe(),
             ));
         }
         WorkerResponse::YoloError { error } => {
@@ -225,13 +378,12 @@ fn handle_worker_response(app: &mut AppState, resp: WorkerResponse) {
             app.sweep.last_progress = None;
             app.push_error(ErrorCategory::Engine, error, "YOLO mode".into());
         }
-        WorkerResponse::EquityCurve {
-            index: _,
-            curve,
-            label,
-        } => {
-            app.chart.equity_curve = Some(curve);
-            app.chart.label = label;
+        WorkerResponse::EquityCurve { index, curve } => {
+            app.receive_equity(index, curve);
+        }
+        WorkerResponse::EquityCurveFailed { index, error } => {
+            app.equity_load_failed(index);
+            app.push_error(ErrorCategory::Data, error, "equity curve load".into());
         }
         WorkerResponse::Error {
             category,
@@ -250,13 +402,95 @@ fn handle_worker_response(app: &mut AppState, resp: WorkerResponse) {
     }
 }
 
-fn scan_cache_status(app: &mut AppState, cache_dir: &PathBuf) {
-    let cache = ParquetCache::new(cache_dir);
-    let all_tickers: Vec<&str> = app.data.universe.all_tickers();
-    let statuses = cache.status(&all_tickers);
-    for status in statuses {
-        app.data
-            .cache_status
-            .insert(status.symbol.clone(), status.cached);
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+    use std::time::Instant;
+
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::backend::TestBackend;
+
+    use super::*;
+    use crate::app::Panel;
+
+    fn app() -> AppState {
+        let (tx, _rx) = mpsc::channel();
+        let (_tx2, rx2) = mpsc::channel();
+        AppState::new(
+            tx,
+            rx2,
+            Arc::new(AtomicBool::new(false)),
+            PathBuf::from("."),
+            PathBuf::from("."),
+        )
+    }
+
+    fn key(c: char) -> Event {
+        Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn draw_is_skipped_until_state_changes() {
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        let mut app = app();
+
+        assert!(render_if_needed(&mut terminal, &mut app).unwrap());
+        assert!(!render_if_needed(&mut terminal, &mut app).unwrap());
+        handle_events(&mut app, []);
+        assert!(!render_if_needed(&mut terminal, &mut app).unwrap());
+
+        handle_events(&mut app, [key('j')]);
+        assert!(render_if_needed(&mut terminal, &mut app).unwrap());
+        assert!(!render_if_needed(&mut terminal, &mut app).unwrap());
+
+        // The chart spinner animates without state changes
+        app.chart.loading_since = Some(Instant::now());
+        assert!(render_if_needed(&mut terminal, &mut app).unwrap());
+        assert!(render_if_needed(&mut terminal, &mut app).unwrap());
+    }
+
+    #[test]
+    fn one_tick_applies_every_queued_key() {
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        let mut app = app();
+        app.active_panel = Panel::Sweep;
+        render_if_needed(&mut terminal, &mut app).unwrap();
+
+        handle_events(&mut app, vec![key('j'); 5]);
+        assert_eq!(app.sweep.cursor, 5);
+        handle_events(&mut app, vec![key('k'); 2]);
+        assert_eq!(app.sweep.cursor, 3);
+        assert!(render_if_needed(&mut terminal, &mut app).unwrap());
+    }
+
+    #[test]
+    fn startup_scan_skips_bad_manifests_and_renders_the_rest() {
+        let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../trendlab-runner/tests/fixtures/artifacts");
+        let mut app = app();
+        app.load_saved_runs(trendlab_runner::scan_artifacts_report(&fixtures));
+
+        assert_eq!(app.results.entries.len(), 2);
+        assert_eq!(app.error_history.len(), 3);
+        assert!(app
+            .error_history
+            .iter()
+            .any(|e| e.message.ends_with("run_05_truncated") && e.message.contains("corrupt")));
+        let (status, _) = app.status_message.clone().unwrap();
+        assert!(status.contains("2 loaded, 2 skipped (schema), 1 skipped (corrupt)"));
+
+        app.active_panel = Panel::Results;
+        let mut terminal = Terminal::new(TestBackend::new(160, 40)).unwrap();
+        assert!(render_if_needed(&mut terminal, &mut app).unwrap());
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(screen.contains("SPY"));
     }
 }
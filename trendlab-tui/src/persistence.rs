@@ -1,6 +1,7 @@
-//! App state persistence — JSON save/load across restarts.
+//! App state persistence — JSON save/load across restarts, plus the
+//! hand-editable `tui.toml` for display preferences.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
@@ -8,6 +9,7 @@ use trendlab_core::fingerprint::TradingMode;
 use trendlab_runner::{RiskProfile, YoloConfig};
 
 use crate::app::{Panel, SessionFilter};
+use crate::theme::ThemeName;
 
 /// Serializable subset of app state that persists across restarts.
 #[derive(Debug, Serialize, Deserialize)]
@@ -55,6 +57,34 @@ pub fn save(path: &Path, state: &PersistedState) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Display preferences in `~/.config/trendlab/tui.toml`.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TuiConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<ThemeName>,
+    /// Directory for trade and leaderboard CSV exports (default
+    /// `results/exports`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exports_dir: Option<PathBuf>,
+}
+
+/// Load the TUI config. Returns defaults if the file is missing or corrupt.
+pub fn load_tui_config(path: &Path) -> TuiConfig {
+    match std::fs::read_to_string(path) {
+        Ok(content) => toml::from_str(&content).unwrap_or_default(),
+        Err(_) => TuiConfig::default(),
+    }
+}
+
+/// Save the TUI config. Creates parent directories if needed.
+pub fn save_tui_config(path: &Path, config: &TuiConfig) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, toml::to_string(config)?)?;
+    Ok(())
+}
+
 /// Extract persisted state from AppState.
 pub fn extract(app: &crate::app::AppState) -> PersistedState {
     PersistedState {
@@ -117,6 +147,29 @@ mod tests {
         assert!(!loaded.welcome_dismissed);
     }
 
+    #[test]
+    fn tui_config_roundtrip() {
+        let dir = std::env::temp_dir().join("trendlab_tui_config_test");
+        let path = dir.join("tui.toml");
+
+        let config = TuiConfig {
+            theme: Some(ThemeName::HighContrast),
+            exports_dir: None,
+        };
+        save_tui_config(&path, &config).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap().trim(),
+            r#"theme = "high-contrast""#
+        );
+        assert_eq!(load_tui_config(&path), config);
+        assert_eq!(
+            load_tui_config(Path::new("/nonexistent/tui.toml")),
+            TuiConfig::default()
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn corrupt_file_returns_defaults() {
         let dir = std::env::temp_dir().join("trendlab_persist_corrupt");
@@ -0,0 +1,297 @@
+//! Leaderboard filter queries.
+//!
+//! A query is whitespace-separated terms; an entry is shown when every term
+//! matches. Matching is case-insensitive.
+//!
+//! - `sharpe>1.5`, `dd<0.2`, `trades>=20`: metric comparisons (`<`, `<=`,
+//!   `>`, `>=`, `=`). Drawdown compares its magnitude; a trailing `%`
+//!   divides the value by 100 (`dd<20%`).
+//! - `signal=donchian`, `pm=atr`, `symbol=spy`: substring match on one field.
+//! - Anything else: substring match on the run id (config hash), symbol, or
+//!   any component name.
+
+use std::fmt;
+
+use crate::app::LeaderboardDisplayEntry;
+
+/// A metric a query can compare against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    Sharpe,
+    Cagr,
+    Drawdown,
+    WinRate,
+    ProfitFactor,
+    Trades,
+    Fitness,
+    QValue,
+}
+
+impl Metric {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "sharpe" => Self::Sharpe,
+            "cagr" => Self::Cagr,
+            "dd" | "maxdd" | "drawdown" => Self::Drawdown,
+            "wr" | "winrate" => Self::WinRate,
+            "pf" => Self::ProfitFactor,
+            "trades" => Self::Trades,
+            "fitness" => Self::Fitness,
+            "q" => Self::QValue,
+            _ => return None,
+        })
+    }
+
+    /// The entry's value, or None when it has none (q-value of a run
+    /// without a p-value).
+    fn value(self, entry: &LeaderboardDisplayEntry) -> Option<f64> {
+        Some(match self {
+            Self::Sharpe => entry.sharpe,
+            Self::Cagr => entry.cagr,
+            Self::Drawdown => entry.max_drawdown.abs(),
+            Self::WinRate => entry.win_rate,
+            Self::ProfitFactor => entry.profit_factor,
+            Self::Trades => entry.trade_count as f64,
+            Self::Fitness => entry.fitness_score,
+            Self::QValue => return entry.fdr.map(|f| f.q_value),
+        })
+    }
+}
+
+/// A text field a query can match on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Signal,
+    Pm,
+    Exec,
+    Filter,
+    Symbol,
+    Id,
+}
+
+impl Field {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "signal" => Self::Signal,
+            "pm" => Self::Pm,
+            "exec" => Self::Exec,
+            "filter" => Self::Filter,
+            "symbol" => Self::Symbol,
+            "id" => Self::Id,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+}
+
+impl Comparison {
+    fn holds(self, lhs: f64, rhs: f64) -> bool {
+        match self {
+            Self::Lt => lhs < rhs,
+            Self::Le => lhs <= rhs,
+            Self::Gt => lhs > rhs,
+            Self::Ge => lhs >= rhs,
+            Self::Eq => (lhs - rhs).abs() < 1e-9,
+        }
+    }
+}
+
+/// One parsed term of a query.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term {
+    Text(String),
+    Field(Field, String),
+    Compare(Metric, Comparison, f64),
+}
+
+/// Why a query did not parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    /// `name<value` where `name` is not a metric.
+    UnknownMetric(String),
+    /// `name=value` where `name` is neither a metric nor a field.
+    UnknownField(String),
+    /// A comparison whose value is not a number.
+    BadNumber(String),
+    /// A comparison or field match with nothing after the operator.
+    MissingValue(String),
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownMetric(name) => write!(f, "unknown metric '{name}'"),
+            Self::UnknownField(name) => write!(f, "unknown field '{name}'"),
+            Self::BadNumber(term) => write!(f, "'{term}' needs a number"),
+            Self::MissingValue(term) => write!(f, "'{term}' has no value"),
+        }
+    }
+}
+
+/// A parsed leaderboard filter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultsQuery {
+    /// The query as typed.
+    pub text: String,
+    pub terms: Vec<Term>,
+}
+
+impl ResultsQuery {
+    /// Parse a query. An empty query parses to no terms (matches everything).
+    pub fn parse(text: &str) -> Result<Self, QueryError> {
+        let terms = text
+            .split_whitespace()
+            .map(|t| parse_term(&t.to_lowercase()))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            text: text.trim().to_string(),
+            terms,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Whether `entry` satisfies every term.
+    pub fn matches(&self, entry: &LeaderboardDisplayEntry) -> bool {
+        // The run id is only computed when a term needs it
+        let mut run_id: Option<String> = None;
+        let mut id = || {
+            run_id
+                .get_or_insert_with(|| entry.config.full_hash().as_hex())
+                .clone()
+        };
+        self.terms.iter().all(|term| match term {
+            Term::Compare(metric, cmp, rhs) => {
+                metric.value(entry).is_some_and(|v| cmp.holds(v, *rhs))
+            }
+            Term::Field(field, needle) => {
+                let haystack = match field {
+                    Field::Signal => entry.signal_type.clone(),
+                    Field::Pm => entry.pm_type.clone(),
+                    Field::Exec => entry.exec_type.clone(),
+                    Field::Filter => entry.filter_type.clone(),
+                    Field::Symbol => entry.symbol.clone(),
+                    Field::Id => id(),
+                };
+                haystack.to_lowercase().contains(needle.as_str())
+            }
+            Term::Text(needle) => {
+                [
+                    &entry.symbol,
+                    &entry.signal_type,
+                    &entry.pm_type,
+                    &entry.exec_type,
+                    &entry.filter_type,
+                ]
+                .iter()
+                .any(|s| s.to_lowercase().contains(needle.as_str()))
+                    || id().contains(needle.as_str())
+            }
+        })
+    }
+}
+
+fn parse_term(term: &str) -> Result<Term, QueryError> {
+    let Some(at) = term.find(['<', '>', '=']) else {
+        return Ok(Term::Text(term.to_string()));
+    };
+    let (name, rest) = term.split_at(at);
+    let (cmp, value) = if let Some(v) = rest.strip_prefix("<=") {
+        (Comparison::Le, v)
+    } else if let Some(v) = rest.strip_prefix(">=") {
+        (Comparison::Ge, v)
+    } else if let Some(v) = rest.strip_prefix('<') {
+        (Comparison::Lt, v)
+    } else if let Some(v) = rest.strip_prefix('>') {
+        (Comparison::Gt, v)
+    } else {
+        (Comparison::Eq, &rest[1..])
+    };
+    if value.is_empty() {
+        return Err(QueryError::MissingValue(term.to_string()));
+    }
+
+    if let Some(metric) = Metric::parse(name) {
+        let (number, scale) = match value.strip_suffix('%') {
+            Some(v) => (v, 0.01),
+            None => (value, 1.0),
+        };
+        return number
+            .parse::<f64>()
+            .map(|v| Term::Compare(metric, cmp, v * scale))
+            .map_err(|_| QueryError::BadNumber(term.to_string()));
+    }
+    match (cmp, Field::parse(name)) {
+        (Comparison::Eq, Some(field)) => Ok(Term::Field(field, value.to_string())),
+        (Comparison::Eq, None) => Err(QueryError::UnknownField(name.to_string())),
+        _ => Err(QueryError::UnknownMetric(name.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_metric_field_and_text_terms() {
+        let query = ResultsQuery::parse("Sharpe>1.5 dd<20% signal=Donchian spy").unwrap();
+        assert_eq!(
+            query.terms,
+            vec![
+                Term::Compare(Metric::Sharpe, Comparison::Gt, 1.5),
+                Term::Compare(Metric::Drawdown, Comparison::Lt, 0.2),
+                Term::Field(Field::Signal, "donchian".into()),
+                Term::Text("spy".into()),
+            ]
+        );
+        assert_eq!(query.text, "Sharpe>1.5 dd<20% signal=Donchian spy");
+    }
+
+    #[test]
+    fn parses_two_character_operators() {
+        let query = ResultsQuery::parse("trades>=20 pf<=1.2 q=0.05").unwrap();
+        assert_eq!(
+            query.terms,
+            vec![
+                Term::Compare(Metric::Trades, Comparison::Ge, 20.0),
+                Term::Compare(Metric::ProfitFactor, Comparison::Le, 1.2),
+                Term::Compare(Metric::QValue, Comparison::Eq, 0.05),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_query_has_no_terms() {
+        assert!(ResultsQuery::parse("   ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_terms() {
+        assert_eq!(
+            ResultsQuery::parse("sharpe>abc"),
+            Err(QueryError::BadNumber("sharpe>abc".into()))
+        );
+        assert_eq!(
+            ResultsQuery::parse("colour=red"),
+            Err(QueryError::UnknownField("colour".into()))
+        );
+        assert_eq!(
+            ResultsQuery::parse("signal>1"),
+            Err(QueryError::UnknownMetric("signal".into()))
+        );
+        assert_eq!(
+            ResultsQuery::parse("sharpe>"),
+            Err(QueryError::MissingValue("sharpe>".into()))
+        );
+    }
+}
@@ -1,64 +1,191 @@
-//! Semantic color tokens for the parrot/neon TUI theme.
+//! Semantic color tokens and the selectable TUI themes.
 //!
 //! All widgets reference these tokens — no hardcoded colors in panel code.
+//! The tokens resolve against the active [`ThemeName`], so switching themes
+//! at runtime restyles every panel on the next frame.
+
+use std::cell::Cell;
+use std::fmt;
+use std::str::FromStr;
 
 use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
 
-/// Near-black / deep charcoal background.
-#[allow(dead_code)]
-pub const BG: Color = Color::Rgb(26, 26, 46);
+/// Built-in themes, in the order the `T` key cycles through them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    /// The parrot/neon palette on a dark terminal.
+    #[default]
+    Dark,
+    /// Darker tones that stay readable on a light background.
+    Light,
+    /// The terminal's own bright ANSI colors.
+    HighContrast,
+    /// Blue/orange PnL coloring instead of green/red.
+    Colorblind,
+}
 
-/// Electric cyan — accent, active panel borders, highlights.
-pub const ACCENT: Color = Color::Rgb(0, 255, 255);
+impl ThemeName {
+    pub const ALL: [ThemeName; 4] = [
+        ThemeName::Dark,
+        ThemeName::Light,
+        ThemeName::HighContrast,
+        ThemeName::Colorblind,
+    ];
+
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|&t| t == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
 
-/// Neon green — positive values (profit, good Sharpe).
-pub const POSITIVE: Color = Color::Rgb(57, 255, 20);
+    pub fn label(self) -> &'static str {
+        match self {
+            ThemeName::Dark => "dark",
+            ThemeName::Light => "light",
+            ThemeName::HighContrast => "high-contrast",
+            ThemeName::Colorblind => "colorblind",
+        }
+    }
+
+    pub fn palette(self) -> Palette {
+        match self {
+            ThemeName::Dark => DARK,
+            ThemeName::Light => LIGHT,
+            ThemeName::HighContrast => HIGH_CONTRAST,
+            ThemeName::Colorblind => COLORBLIND,
+        }
+    }
+}
 
-/// Hot pink — negative values (loss, drawdown).
-pub const NEGATIVE: Color = Color::Rgb(255, 16, 100);
+impl fmt::Display for ThemeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for ThemeName {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|t| t.label() == s.replace('_', "-"))
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(|t| t.label()).collect();
+                format!("unknown theme '{s}' ({})", names.join(", "))
+            })
+    }
+}
+
+/// The colors behind the semantic tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    /// Accent, active panel borders, highlights.
+    pub accent: Color,
+    /// Positive values (profit, good Sharpe).
+    pub positive: Color,
+    /// Negative values (loss, drawdown).
+    pub negative: Color,
+    /// Warnings, incompatible configs.
+    pub warning: Color,
+    /// Neutral/decorative.
+    pub neutral: Color,
+    /// Muted text, inactive borders.
+    pub muted: Color,
+}
 
-/// Neon orange — warnings, incompatible configs.
-pub const WARNING: Color = Color::Rgb(255, 165, 0);
+/// Electric cyan, neon green, hot pink, neon orange, cool purple, steel blue.
+const DARK: Palette = Palette {
+    accent: Color::Rgb(0, 255, 255),
+    positive: Color::Rgb(57, 255, 20),
+    negative: Color::Rgb(255, 16, 100),
+    warning: Color::Rgb(255, 165, 0),
+    neutral: Color::Rgb(170, 130, 255),
+    muted: Color::Rgb(100, 130, 180),
+};
 
-/// Cool purple — neutral/decorative.
-pub const NEUTRAL: Color = Color::Rgb(170, 130, 255);
+const LIGHT: Palette = Palette {
+    accent: Color::Rgb(0, 95, 135),
+    positive: Color::Rgb(0, 125, 40),
+    negative: Color::Rgb(190, 0, 50),
+    warning: Color::Rgb(175, 90, 0),
+    neutral: Color::Rgb(105, 55, 170),
+    muted: Color::Rgb(80, 80, 100),
+};
 
-/// Steel blue — muted text, inactive borders.
-pub const MUTED: Color = Color::Rgb(100, 130, 180);
+/// Named ANSI colors, so the terminal's own scheme decides the exact shade.
+const HIGH_CONTRAST: Palette = Palette {
+    accent: Color::LightCyan,
+    positive: Color::LightGreen,
+    negative: Color::LightRed,
+    warning: Color::LightYellow,
+    neutral: Color::White,
+    muted: Color::Gray,
+};
 
-/// Accent style (electric cyan foreground).
+/// Okabe–Ito sky blue and orange for PnL; yellow for warnings.
+const COLORBLIND: Palette = Palette {
+    accent: Color::Rgb(235, 235, 235),
+    positive: Color::Rgb(86, 180, 233),
+    negative: Color::Rgb(230, 159, 0),
+    warning: Color::Rgb(240, 228, 66),
+    neutral: Color::Rgb(204, 121, 167),
+    muted: Color::Rgb(150, 150, 150),
+};
+
+thread_local! {
+    // Rendering happens on the UI thread only.
+    static ACTIVE: Cell<ThemeName> = const { Cell::new(ThemeName::Dark) };
+}
+
+/// Theme the tokens currently resolve against.
+pub fn current() -> ThemeName {
+    ACTIVE.with(Cell::get)
+}
+
+/// Switch the active theme.
+pub fn set(name: ThemeName) {
+    ACTIVE.with(|t| t.set(name));
+}
+
+fn palette() -> Palette {
+    current().palette()
+}
+
+/// Accent style.
 pub fn accent() -> Style {
-    Style::default().fg(ACCENT)
+    Style::default().fg(palette().accent)
 }
 
-/// Positive style (neon green foreground).
+/// Positive style.
 pub fn positive() -> Style {
-    Style::default().fg(POSITIVE)
+    Style::default().fg(palette().positive)
 }
 
-/// Negative style (hot pink foreground).
+/// Negative style.
 pub fn negative() -> Style {
-    Style::default().fg(NEGATIVE)
+    Style::default().fg(palette().negative)
 }
 
-/// Warning style (neon orange foreground).
+/// Warning style.
 pub fn warning() -> Style {
-    Style::default().fg(WARNING)
+    Style::default().fg(palette().warning)
 }
 
-/// Neutral style (cool purple foreground).
+/// Neutral style.
 pub fn neutral() -> Style {
-    Style::default().fg(NEUTRAL)
+    Style::default().fg(palette().neutral)
 }
 
-/// Muted style (steel blue foreground).
+/// Muted style.
 pub fn muted() -> Style {
-    Style::default().fg(MUTED)
+    Style::default().fg(palette().muted)
 }
 
 /// Bold accent (for titles).
 pub fn accent_bold() -> Style {
-    Style::default().fg(ACCENT).add_modifier(Modifier::BOLD)
+    accent().add_modifier(Modifier::BOLD)
 }
 
 /// Bold style on any color.
@@ -70,9 +197,9 @@ pub fn bold() -> Style {
 /// Panel border style — accent if active, muted otherwise.
 pub fn panel_border(is_active: bool) -> Style {
     if is_active {
-        Style::default().fg(ACCENT)
+        accent()
     } else {
-        Style::default().fg(MUTED)
+        muted()
     }
 }
 
@@ -85,7 +212,7 @@ pub fn panel_title(is_active: bool) -> Style {
     }
 }
 
-/// Style a metric value: green if positive, red if negative.
+/// Style a metric value: positive if above zero, negative if below.
 pub fn metric_color(value: f64) -> Style {
     if value > 0.0 {
         positive()
@@ -96,7 +223,7 @@ pub fn metric_color(value: f64) -> Style {
     }
 }
 
-/// Style for Sharpe ratio: green if >1, muted if 0-1, red if <0.
+/// Style for Sharpe ratio: positive if >1, muted if 0-1, negative if <0.
 pub fn sharpe_style(sharpe: f64) -> Style {
     if sharpe >= 1.0 {
         positive()
@@ -106,3 +233,30 @@ pub fn sharpe_style(sharpe: f64) -> Style {
         negative()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn theme_names_parse_and_cycle() {
+        assert_eq!("high-contrast".parse(), Ok(ThemeName::HighContrast));
+        assert_eq!("high_contrast".parse(), Ok(ThemeName::HighContrast));
+        assert!("solarized".parse::<ThemeName>().is_err());
+        assert_eq!(ThemeName::Colorblind.next(), ThemeName::Dark);
+        for name in ThemeName::ALL {
+            assert_eq!(name.to_string().parse(), Ok(name));
+        }
+    }
+
+    #[test]
+    fn colorblind_pnl_avoids_green_and_red() {
+        let cb = ThemeName::Colorblind.palette();
+        assert_ne!(cb.positive, DARK.positive);
+        assert_ne!(cb.negative, DARK.negative);
+        set(ThemeName::Colorblind);
+        assert_eq!(metric_color(1.0), Style::default().fg(cb.positive));
+        assert_eq!(metric_color(-1.0), Style::default().fg(cb.negative));
+        set(ThemeName::Dark);
+    }
+}
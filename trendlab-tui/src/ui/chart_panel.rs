@@ -1,24 +1,64 @@
-//! Panel 5 — Chart: MVP equity curve line chart.
+//! Panel 5 — Chart: equity curve line chart, with its drawdown strip below.
 
 use ratatui::Frame;
-use ratatui::layout::Rect;
-use ratatui::style::Style;
+use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::symbols;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Axis, Chart, Dataset, GraphType, Paragraph};
 
 use crate::app::AppState;
 use crate::theme;
+use crate::ui::widgets::drawdown;
+
+/// Braille spinner frames, advanced every 100ms while a curve loads.
+const SPINNER: [&str; 8] = ["⣾", "⣽", "⣻", "⢿", "⡿", "⣟", "⣯", "⣷"];
+
+/// Shortest panel that still fits the drawdown strip under the equity chart.
+const MIN_HEIGHT_WITH_DRAWDOWN: u16 = 16;
+
+/// Width of the widest drawdown label ("-100.0%").
+const DRAWDOWN_LABEL_WIDTH: usize = 7;
 
 pub fn render(f: &mut Frame, area: Rect, app: &AppState) {
     let chart_state = &app.chart;
 
+    if let Some(since) = chart_state.loading_since {
+        render_loading(f, area, since.elapsed().as_millis(), &chart_state.label);
+        return;
+    }
+
     match &chart_state.equity_curve {
-        Some(curve) if !curve.is_empty() => render_chart(f, area, curve, &chart_state.label),
+        Some(curve) if !curve.is_empty() => {
+            if chart_state.show_drawdown && area.height >= MIN_HEIGHT_WITH_DRAWDOWN {
+                let [top, bottom] =
+                    Layout::vertical([Constraint::Percentage(70), Constraint::Percentage(30)])
+                        .areas(area);
+                let label_width = render_chart(f, top, curve, &chart_state.label);
+                let x_bounds = [0.0, curve.len().saturating_sub(1).max(1) as f64];
+                drawdown::render(f, bottom, curve, x_bounds, label_width);
+            } else {
+                render_chart(f, area, curve, &chart_state.label);
+            }
+        }
         _ => render_empty(f, area),
     }
 }
 
+fn render_loading(f: &mut Frame, area: Rect, elapsed_ms: u128, label: &str) {
+    let frame = SPINNER[(elapsed_ms / 100) as usize % SPINNER.len()];
+    let lines = vec![
+        Line::from(""),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(format!("{frame} "), theme::accent()),
+            Span::styled("Loading equity curve...", theme::muted()),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(label.to_string(), theme::muted())),
+    ];
+    f.render_widget(Paragraph::new(lines), area);
+}
+
 fn render_empty(f: &mut Frame, area: Rect) {
     let lines = vec![
         Line::from(""),
@@ -36,7 +76,9 @@ fn render_empty(f: &mut Frame, area: Rect) {
     f.render_widget(Paragraph::new(lines), area);
 }
 
-fn render_chart(f: &mut Frame, area: Rect, curve: &[f64], label: &str) {
+/// Draw the equity chart; returns the width its y labels are padded to,
+/// which the drawdown strip matches to share the x axis.
+fn render_chart(f: &mut Frame, area: Rect, curve: &[f64], label: &str) -> usize {
     let min_y = curve
         .iter()
         .copied()
@@ -61,10 +103,13 @@ fn render_chart(f: &mut Frame, area: Rect, curve: &[f64], label: &str) {
     let dataset = Dataset::default()
         .name(label)
         .marker(symbols::Marker::Braille)
-        .style(Style::default().fg(theme::ACCENT))
+        .style(theme::accent())
         .graph_type(GraphType::Line)
         .data(&data);
 
+    let (low, high) = (format!("{:.0}", y_min), format!("{:.0}", y_max));
+    let label_width = low.len().max(high.len()).max(DRAWDOWN_LABEL_WIDTH);
+
     let chart = Chart::new(vec![dataset])
         .x_axis(
             Axis::default()
@@ -82,10 +127,11 @@ fn render_chart(f: &mut Frame, area: Rect, curve: &[f64], label: &str) {
                 .style(theme::muted())
                 .bounds([y_min, y_max])
                 .labels(vec![
-                    Span::styled(format!("{:.0}", y_min), theme::muted()),
-                    Span::styled(format!("{:.0}", y_max), theme::muted()),
+                    Span::styled(format!("{low:>label_width$}"), theme::muted()),
+                    Span::styled(format!("{high:>label_width$}"), theme::muted()),
                 ]),
         );
 
     f.render_widget(chart, area);
+    label_width
 }
@@ -1,4 +1,5 @@
-//! Panel 1 — Data: sector/ticker tree, fetch progress, cache status indicators.
+//! Panel 1 — Data: sector/ticker tree, fetch progress, per-symbol download
+//! and cache status indicators.
 
 use ratatui::Frame;
 use ratatui::layout::Rect;
@@ -6,7 +7,7 @@ use ratatui::style::Modifier;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 
-use crate::app::AppState;
+use crate::app::{AppState, DownloadStatus};
 use crate::theme;
 
 pub fn render(f: &mut Frame, area: Rect, app: &AppState) {
@@ -23,7 +24,7 @@ pub fn render(f: &mut Frame, area: Rect, app: &AppState) {
             theme::accent(),
         ),
         Span::styled(
-            "  [Space]toggle [a]ll [d]eselect [f]etch [s]earch",
+            "  [Space]toggle [a]ll [d]eselect [f]etch [s]earch [c]ache",
             theme::muted(),
         ),
     ]));
@@ -84,7 +85,13 @@ pub fn render(f: &mut Frame, area: Rect, app: &AppState) {
                     .unwrap_or(false);
 
                 let check = if is_selected { "[x]" } else { "[ ]" };
-                let dot = if is_cached { " ●" } else { " ○" };
+                let (dot, dot_style) = match data.downloads.get(ticker) {
+                    Some(DownloadStatus::Pending) => (" …", theme::muted()),
+                    Some(DownloadStatus::Downloading { .. }) => (" ↓", theme::warning()),
+                    Some(DownloadStatus::Failed(_)) => (" ✗", theme::negative()),
+                    _ if is_cached => (" ●", theme::positive()),
+                    _ => (" ○", theme::muted()),
+                };
 
                 let mut spans = vec![
                     Span::raw("  "),
@@ -100,12 +107,6 @@ pub fn render(f: &mut Frame, area: Rect, app: &AppState) {
                     theme::muted()
                 };
                 spans.push(Span::styled(ticker.as_str(), ticker_style));
-
-                let dot_style = if is_cached {
-                    theme::positive()
-                } else {
-                    theme::muted()
-                };
                 spans.push(Span::styled(dot, dot_style));
 
                 lines.push(Line::from(spans));
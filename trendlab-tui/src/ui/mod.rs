@@ -35,11 +35,40 @@ pub fn draw(f: &mut Frame, app: &AppState) {
     status_bar::render(f, status_area, app);
 
     // Draw overlays on top.
-    match &app.overlay {
-        Overlay::Welcome => overlays::render_welcome(f, main_area),
-        Overlay::ErrorHistory => overlays::render_error_history(f, main_area, app),
-        Overlay::Search => overlays::render_search(f, main_area, &app.search_input),
-        Overlay::Detail(idx) => overlays::render_detail(f, main_area, app, *idx),
+    draw_overlay(f, main_area, app, &app.overlay);
+    if app.show_help {
+        overlays::render_key_help(f, main_area, app);
+    }
+}
+
+fn draw_overlay(f: &mut Frame, area: Rect, app: &AppState, overlay: &Overlay) {
+    match overlay {
+        Overlay::Welcome => overlays::render_welcome(f, area),
+        Overlay::ErrorHistory => overlays::render_error_history(f, area, app),
+        Overlay::Search => overlays::render_search(f, area, &app.search_input),
+        Overlay::Detail(idx) => overlays::render_detail(f, area, app, *idx),
+        Overlay::YoloDashboard => overlays::render_yolo_dashboard(f, area, app),
+        Overlay::Sessions => overlays::render_sessions(f, area, app),
+        Overlay::CacheManager => overlays::render_cache_manager(f, area, app),
+        Overlay::Replay => overlays::render_replay(f, area, app),
+        Overlay::Filter => overlays::render_filter(f, area, app),
+        Overlay::Note => {
+            // The editor sits over the view it was opened from
+            if let Some(editor) = &app.note_editor {
+                draw_overlay(f, area, app, &editor.return_to);
+            }
+            overlays::render_note(f, area, app);
+        }
+        Overlay::DateWindow(idx) => {
+            overlays::render_detail(f, area, app, *idx);
+            overlays::render_date_window(f, area, app);
+        }
+        Overlay::Nudge => {
+            if let Some(nudge) = &app.nudge {
+                overlays::render_detail(f, area, app, nudge.entry);
+            }
+            overlays::render_nudge(f, area, app);
+        }
         Overlay::None => {}
     }
 }
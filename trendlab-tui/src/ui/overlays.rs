@@ -1,14 +1,33 @@
-//! Overlay widgets — welcome, detail drill-down, error history, search.
+//! Overlay widgets — welcome, detail drill-down, parameter nudging, bar
+//! replay, note editor, error history, search, YOLO dashboard, session
+//! browser, cache manager.
 
 use ratatui::Frame;
-use ratatui::layout::Rect;
-use ratatui::style::Modifier;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
 
-use crate::app::AppState;
+use trendlab_core::data::cache::format_size;
+use trendlab_runner::{Annotation, AnnotationTag};
+
+use crate::app::{AppState, DownloadStatus};
+use crate::keymap::KeyContext;
 use crate::theme;
 use crate::ui::centered_rect;
+use crate::ui::help_panel;
+use crate::ui::results_panel::{truncate, NOTE_MARKER};
+use crate::ui::widgets::candles::{self, Candle, PriceLine};
+use crate::ui::widgets::histogram::HistogramWidget;
+use crate::ui::widgets::sparkline;
+
+/// Chasing positions listed under the detail view's stickiness summary.
+const MAX_CHASING_ROWS: usize = 5;
+
+/// Rows of the detail view's trade histograms, and the least inner height
+/// that still shows them.
+const HISTOGRAM_ROWS: u16 = 7;
+const MIN_HEIGHT_WITH_HISTOGRAMS: u16 = 24;
 
 /// First-run welcome overlay.
 pub fn render_welcome(f: &mut Frame, area: Rect) {
@@ -55,6 +74,36 @@ pub fn render_welcome(f: &mut Frame, area: Rect) {
     f.render_widget(para, popup);
 }
 
+/// Cheat sheet (`?`): the bindings valid in the context under it.
+pub fn render_key_help(f: &mut Frame, area: Rect, app: &AppState) {
+    let context = KeyContext::beneath_help(app);
+    let mut lines: Vec<Line> = Vec::new();
+    // Most specific first
+    for &layer in context.layers().iter().rev() {
+        if help_panel::context_lines(&mut lines, layer) {
+            lines.push(Line::from(""));
+        }
+    }
+    lines.pop();
+
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let width = 80.min(area.width);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+    f.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme::accent())
+        .title(" Keys [?/Esc]close ")
+        .title_style(theme::accent_bold());
+    f.render_widget(Paragraph::new(lines).block(block), popup);
+}
+
 /// Error history overlay.
 pub fn render_error_history(f: &mut Frame, area: Rect, app: &AppState) {
     let popup = centered_rect(80, 70, area);
@@ -145,6 +194,247 @@ pub fn render_search(f: &mut Frame, area: Rect, input: &str) {
     f.render_widget(para, inner);
 }
 
+/// Leaderboard filter prompt, docked at the bottom so the filtered rows stay
+/// visible while typing.
+pub fn render_filter(f: &mut Frame, area: Rect, app: &AppState) {
+    let r = &app.results;
+    let height = 4.min(area.height);
+    let popup = Rect::new(area.x, area.y + area.height - height, area.width, height);
+    f.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme::accent())
+        .title(" Filter [Enter]keep [Esc]clear ")
+        .title_style(theme::accent_bold());
+    let inner = block.inner(popup);
+    f.render_widget(block, popup);
+
+    let status = match &r.filter_error {
+        Some(e) => Span::styled(e.clone(), theme::warning()),
+        None => Span::styled(
+            format!("{} of {} entries", r.visible_indices().len(), r.entries.len()),
+            theme::muted(),
+        ),
+    };
+    let text = vec![
+        Line::from(vec![
+            Span::styled("/ ", theme::accent()),
+            Span::styled(r.filter_input.as_str(), theme::accent_bold()),
+            Span::styled("_", theme::accent()),
+        ]),
+        Line::from(vec![
+            status,
+            Span::styled(
+                "  e.g. sharpe>1.5 dd<0.2 signal=donchian spy",
+                theme::muted(),
+            ),
+        ]),
+    ];
+    f.render_widget(Paragraph::new(text), inner);
+}
+
+/// Date-window prompt, docked at the bottom of the detail view.
+pub fn render_date_window(f: &mut Frame, area: Rect, app: &AppState) {
+    let r = &app.results;
+    let height = 4.min(area.height);
+    let popup = Rect::new(area.x, area.y + area.height - height, area.width, height);
+    f.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme::accent())
+        .title(" Date Window [Enter]apply [Esc]back ")
+        .title_style(theme::accent_bold());
+    let inner = block.inner(popup);
+    f.render_widget(block, popup);
+
+    let status = match &r.window_error {
+        Some(e) => Span::styled(e.clone(), theme::warning()),
+        None => Span::styled(
+            "FROM..TO, e.g. 2022-01-01..2022-12-31 or 2022-01-01..  (empty: whole run)",
+            theme::muted(),
+        ),
+    };
+    let text = vec![
+        Line::from(vec![
+            Span::styled("> ", theme::accent()),
+            Span::styled(r.window_input.as_str(), theme::accent_bold()),
+            Span::styled("_", theme::accent()),
+        ]),
+        Line::from(status),
+    ];
+    f.render_widget(Paragraph::new(text), inner);
+}
+
+/// Parameter nudging, docked at the bottom of the detail view: the run's
+/// params with their edits, then its manual variants against it.
+pub fn render_nudge(f: &mut Frame, area: Rect, app: &AppState) {
+    let Some(nudge) = &app.nudge else {
+        return;
+    };
+    let r = &app.results;
+    let variants = r.variants_of(nudge.entry);
+    let wanted = nudge.rows.len() + variants.len() + if variants.is_empty() { 4 } else { 6 };
+    let height = (wanted as u16).min(area.height);
+    let popup = Rect::new(area.x, area.y + area.height - height, area.width, height);
+    f.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme::accent())
+        .title(" Parameter Nudge [j/k]param [+/-]step [Enter]re-run [Esc]back ")
+        .title_style(theme::accent_bold());
+    let inner = block.inner(popup);
+    f.render_widget(block, popup);
+
+    let mut lines = Vec::new();
+    for (i, row) in nudge.rows.iter().enumerate() {
+        let (value, base) = (nudge.value(row), nudge.base_value(row));
+        let style = if i == nudge.cursor {
+            theme::accent().add_modifier(Modifier::REVERSED)
+        } else if value != base {
+            theme::accent_bold()
+        } else {
+            theme::muted()
+        };
+        let mut spans = vec![Span::styled(
+            format!(
+                "  {:<16} {:<18} {:<16} {:>8}",
+                row.slot.name(),
+                truncate(&row.component, 18),
+                truncate(&row.range.name, 16),
+                param_value(value)
+            ),
+            style,
+        )];
+        if value != base {
+            spans.push(Span::styled(format!(" (was {})", param_value(base)), theme::accent()));
+        }
+        spans.push(Span::styled(
+            format!(
+                "  step {}, {}..={}",
+                param_value(row.range.nudge_step()),
+                param_value(row.range.min),
+                param_value(row.range.max)
+            ),
+            theme::muted(),
+        ));
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(match &nudge.error {
+        Some(e) => Span::styled(format!("  {e}"), theme::warning()),
+        None => Span::styled(
+            "  Enter re-runs on the run's data and settings; the result joins the leaderboard",
+            theme::muted(),
+        ),
+    }));
+
+    if !variants.is_empty() {
+        let base = &r.entries[nudge.entry].metrics;
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Variants vs this run", theme::accent_bold())));
+        for i in variants {
+            let entry = &r.entries[i];
+            let m = &entry.metrics;
+            let changes = entry.provenance.as_ref().map_or_else(String::new, |p| {
+                p.changes.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+            });
+            let delta = |label: &str, value: f64, text: String| {
+                Span::styled(format!("  {label} {text}"), theme::metric_color(value))
+            };
+            let sharpe = m.sharpe - base.sharpe;
+            let cagr = (m.cagr - base.cagr) * 100.0;
+            // Shallower drawdowns are improvements
+            let drawdown = (m.max_drawdown - base.max_drawdown) * 100.0;
+            let trades = m.trade_count as i64 - base.trade_count as i64;
+            lines.push(Line::from(vec![
+                Span::styled(format!("  #{:<3} {changes}", entry.rank), theme::accent()),
+                delta("Sharpe", sharpe, format!("{sharpe:+.2}")),
+                delta("CAGR", cagr, format!("{cagr:+.1}%")),
+                delta("MaxDD", -drawdown, format!("{drawdown:+.1}%")),
+                Span::styled(format!("  Trades {trades:+}"), theme::muted()),
+            ]));
+        }
+    }
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Whole numbers without decimals; others trimmed to four.
+fn param_value(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.4}").trim_end_matches('0').to_string()
+    }
+}
+
+/// Note and tag editor for a run or a replayed trade.
+pub fn render_note(f: &mut Frame, area: Rect, app: &AppState) {
+    let Some(editor) = &app.note_editor else {
+        return;
+    };
+    let popup = centered_rect(60, 30, area);
+    f.render_widget(Clear, popup);
+
+    let subject = match app.results.entries.get(editor.target.entry) {
+        Some(entry) => format!("{} {}", entry.symbol, entry.signal_type),
+        None => "run".to_string(),
+    };
+    let title = match editor.target.trade {
+        Some(entry_bar) => format!(" Note: {subject}, trade entered bar {entry_bar} "),
+        None => format!(" Note: {subject} "),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme::accent())
+        .title(format!("{title}[Tab]tags [Enter]save [Esc]cancel "))
+        .title_style(theme::accent_bold());
+    let inner = block.inner(popup);
+    f.render_widget(block, popup);
+
+    let focus = |focused: bool| if focused { theme::accent_bold() } else { theme::muted() };
+    let mut lines = vec![
+        Line::from(Span::styled("Note", focus(!editor.tags_focused))),
+        Line::from(vec![
+            Span::styled("> ", theme::accent()),
+            Span::styled(editor.text.as_str(), theme::accent_bold()),
+            Span::styled(if editor.tags_focused { "" } else { "_" }, theme::accent()),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled("Tags [h/l]move [Space]toggle", focus(editor.tags_focused))),
+    ];
+    let mut tags = vec![Span::raw("  ")];
+    for (i, tag) in AnnotationTag::ALL.iter().enumerate() {
+        let mark = if editor.tags.contains(tag) { "x" } else { " " };
+        let style = if editor.tags_focused && i == editor.tag_cursor {
+            theme::accent().add_modifier(Modifier::REVERSED)
+        } else if editor.tags.contains(tag) {
+            theme::accent()
+        } else {
+            theme::muted()
+        };
+        tags.push(Span::styled(format!("[{mark}] {tag}"), style));
+        tags.push(Span::raw("  "));
+    }
+    lines.push(Line::from(tags));
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+}
+
+/// Note and tags as detail lines, under a section heading.
+fn annotation_lines(lines: &mut Vec<Line<'_>>, heading: &str, annotation: &Annotation) {
+    lines.push(Line::from(Span::styled(
+        format!("{NOTE_MARKER} {heading}"),
+        theme::accent_bold(),
+    )));
+    if !annotation.note.is_empty() {
+        metric_line(lines, "Note", &annotation.note);
+    }
+    if !annotation.tags.is_empty() {
+        metric_line(lines, "Tags", &annotation.tags_label().replace(';', ", "));
+    }
+}
+
 /// Detail drill-down overlay for a leaderboard entry.
 pub fn render_detail(f: &mut Frame, area: Rect, app: &AppState, idx: usize) {
     let popup = centered_rect(80, 80, area);
@@ -153,7 +443,7 @@ pub fn render_detail(f: &mut Frame, area: Rect, app: &AppState, idx: usize) {
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(theme::accent())
-        .title(" Strategy Detail [Esc]close ")
+        .title(" Strategy Detail [w]worst days [r]replay [e]xport trades [n]ote [d]ates [p]arams [Esc]close ")
         .title_style(theme::accent_bold());
 
     let inner = block.inner(popup);
@@ -166,7 +456,44 @@ pub fn render_detail(f: &mut Frame, area: Rect, app: &AppState, idx: usize) {
     }
 
     let entry = &app.results.entries[idx];
-    let m = &entry.metrics;
+    // A date window replaces the performance and tail sections
+    let window = app.results.window.as_ref().filter(|w| w.entry == idx);
+    let m = window.map_or(&entry.metrics, |w| &w.slice.result.metrics);
+    let tail_metrics = match window {
+        Some(w) => w.slice.result.tail_metrics.as_ref(),
+        None => entry.tail_metrics.as_ref(),
+    };
+    let distributions = match window {
+        Some(w) => Some(&w.slice.result.trade_distributions),
+        None => entry.trade_distributions.as_ref(),
+    };
+
+    // Trade histograms along the bottom, when there is room
+    let inner = match distributions {
+        Some(d) if inner.height >= MIN_HEIGHT_WITH_HISTOGRAMS => {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(HISTOGRAM_ROWS)])
+                .split(inner);
+            let cols = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .spacing(2)
+                .split(rows[1]);
+            f.render_widget(
+                HistogramWidget::new("Trade Return", &d.return_pct)
+                    .format(|v| format!("{:.1}%", v * 100.0))
+                    .signed(true),
+                cols[0],
+            );
+            f.render_widget(
+                HistogramWidget::new("Bars Held", &d.holding_bars),
+                cols[1],
+            );
+            rows[0]
+        }
+        _ => inner,
+    };
     let mut lines: Vec<Line> = Vec::new();
 
     // Composition
@@ -176,10 +503,64 @@ pub fn render_detail(f: &mut Frame, area: Rect, app: &AppState, idx: usize) {
     metric_line(&mut lines, "Execution", &entry.exec_type);
     metric_line(&mut lines, "Filter", &entry.filter_type);
     metric_line(&mut lines, "Symbol", &entry.symbol);
+    if let Some(truncated) = &entry.truncated_range {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:>20}: ", "Data"), theme::muted()),
+            Span::styled(truncated.to_string(), theme::warning()),
+        ]));
+    }
+    if let Some(provenance) = &entry.provenance {
+        let parent = &provenance.parent_full_hash.as_hex()[..12];
+        if entry.is_manual_variant() {
+            metric_line(&mut lines, "Manual Variant of", parent);
+        } else {
+            metric_line(&mut lines, "Parent", parent);
+        }
+        for (i, change) in provenance.changes.iter().enumerate() {
+            let label = if i == 0 {
+                format!("  {:>20}: ", "Differs from Parent")
+            } else {
+                " ".repeat(24)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(label, theme::muted()),
+                Span::styled(change.to_string(), theme::accent()),
+            ]));
+        }
+    }
     lines.push(Line::from(""));
 
+    if let Some(annotation) = app.annotation(idx, None) {
+        annotation_lines(&mut lines, "Journal", annotation);
+        lines.push(Line::from(""));
+    }
+
     // Performance
-    lines.push(Line::from(Span::styled("Performance", theme::accent_bold())));
+    match window {
+        Some(w) => {
+            let sliced = &w.slice.result;
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "Performance {} to {} (window; [d] to change)",
+                    sliced.start_date, sliced.end_date
+                ),
+                theme::accent_bold(),
+            )));
+            if sliced.warmup_bars > 0 {
+                metric_line(&mut lines, "Warmup in Window", &format!("{} bars", sliced.warmup_bars));
+            }
+            if !w.slice.prorated.is_empty() {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {:>20}: ", "Boundary Trades"), theme::muted()),
+                    Span::styled(
+                        format!("{} with PnL pro-rated by bars in window", w.slice.prorated.len()),
+                        theme::warning(),
+                    ),
+                ]));
+            }
+        }
+        None => lines.push(Line::from(Span::styled("Performance", theme::accent_bold()))),
+    }
     metric_num(&mut lines, "Sharpe", m.sharpe, false);
     metric_num(&mut lines, "CAGR", m.cagr * 100.0, true);
     metric_num(&mut lines, "Max Drawdown", m.max_drawdown * 100.0, true);
@@ -188,13 +569,35 @@ pub fn render_detail(f: &mut Frame, area: Rect, app: &AppState, idx: usize) {
     metric_num(&mut lines, "Sortino", m.sortino, false);
     metric_num(&mut lines, "Calmar", m.calmar, false);
     metric_num(&mut lines, "Total Return", m.total_return * 100.0, true);
+    if m.time_in_market > 0.0 {
+        metric_line(
+            &mut lines,
+            "Time in Market",
+            &format!(
+                "{:.1}% at {:.0}% avg exposure (idle return {:+.2}%)",
+                m.time_in_market * 100.0,
+                m.avg_exposure * 100.0,
+                m.idle_return * 100.0
+            ),
+        );
+        metric_num(&mut lines, "Exposure-Adj CAGR", m.exposure_adjusted_cagr * 100.0, true);
+    }
     metric_line(&mut lines, "Trade Count", &m.trade_count.to_string());
     metric_line(&mut lines, "Max Consec Wins", &m.max_consecutive_wins.to_string());
     metric_line(&mut lines, "Max Consec Losses", &m.max_consecutive_losses.to_string());
+    if window.is_none() && !entry.attribution.is_empty() {
+        let mut spans = vec![Span::styled(format!("  {:>20}: ", "PnL by Source"), theme::muted())];
+        for (source, pnl) in &entry.attribution {
+            spans.push(Span::styled(format!("{} ", source.label()), theme::muted()));
+            spans.push(Span::styled(format!("{pnl:+.0}  "), theme::metric_color(*pnl)));
+        }
+        lines.push(Line::from(spans));
+    }
     lines.push(Line::from(""));
 
     // Stickiness
-    if let Some(stick) = &entry.stickiness {
+    if let Some(stick) = &entry.stickiness.run {
+        let report = &entry.stickiness;
         lines.push(Line::from(Span::styled("Stickiness", theme::accent_bold())));
         metric_num(&mut lines, "Median Hold (bars)", stick.median_holding_bars, false);
         metric_num(&mut lines, "P95 Hold (bars)", stick.p95_holding_bars, false);
@@ -202,12 +605,788 @@ pub fn render_detail(f: &mut Frame, area: Rect, app: &AppState, idx: usize) {
         metric_num(&mut lines, "% Over 120 bars", stick.pct_over_120_bars * 100.0, true);
         metric_num(&mut lines, "Exit Trigger Rate", stick.exit_trigger_rate * 100.0, true);
         metric_num(&mut lines, "Chase Ratio", stick.reference_chase_ratio, false);
+        if !report.positions.is_empty() {
+            metric_num(
+                &mut lines,
+                "Frozen References",
+                report.frozen_reference_rate() * 100.0,
+                true,
+            );
+            let mut chasing: Vec<_> = report.chasing().collect();
+            metric_line(
+                &mut lines,
+                "Chasing Positions",
+                &format!("{} of {}", chasing.len(), report.positions.len()),
+            );
+            // The positions behind the number, longest held first
+            chasing.sort_by_key(|pos| std::cmp::Reverse(pos.bars_held));
+            for pos in chasing.iter().take(MAX_CHASING_ROWS) {
+                let exit = pos
+                    .exit_bar
+                    .map_or_else(|| "open".to_string(), |bar| bar.to_string());
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "  {:>20}  {} bars {}-{}, held {}, chase {:.1}",
+                        "",
+                        pos.symbol,
+                        pos.entry_bar,
+                        exit,
+                        pos.bars_held,
+                        pos.reference_chase_ratio
+                    ),
+                    theme::warning(),
+                )));
+            }
+        }
+        lines.push(Line::from(""));
+    }
+
+    // R-multiple footer
+    if let Some(r) = &entry.r_distribution {
+        lines.push(Line::from(Span::styled("R-Multiples", theme::accent_bold())));
+        if r.trade_count == 0 {
+            metric_line(&mut lines, "Excluded (no stop)", &r.excluded_trades.to_string());
+        } else {
+            metric_num(&mut lines, "Mean R", r.mean_r, false);
+            metric_num(&mut lines, "Median R", r.median_r, false);
+            metric_num(&mut lines, "Expectancy (R)", r.expectancy_r, false);
+            metric_num(&mut lines, "% Near -1R", r.pct_near_minus_one * 100.0, true);
+            metric_num(&mut lines, "% Scratch", r.pct_scratch * 100.0, true);
+            metric_line(&mut lines, "Excluded (no stop)", &r.excluded_trades.to_string());
+            let histogram = r
+                .histogram
+                .iter()
+                .enumerate()
+                .map(|(i, c)| format!("{} {c}", trendlab_runner::RDistribution::bin_label(i)))
+                .collect::<Vec<_>>()
+                .join("  ");
+            lines.push(Line::from(Span::styled(format!("  {histogram}"), theme::muted())));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Style analysis: how much of the return is factor exposure
+    if let Some(style) = &entry.style {
+        lines.push(Line::from(Span::styled("Style", theme::accent_bold())));
+        for b in &style.betas {
+            metric_num(&mut lines, &format!("Beta {}", b.factor), b.beta, false);
+        }
+        metric_num(&mut lines, "R²", style.r_squared, false);
+        metric_num(&mut lines, "Alpha (ann.)", style.alpha * 100.0, true);
+        metric_num(&mut lines, "Residual Sharpe", style.residual_sharpe, false);
+        lines.push(Line::from(""));
+    }
+
+    // Multiple testing: the entry's place in its symbol's FDR family
+    if let Some(fdr) = &entry.fdr {
+        lines.push(Line::from(Span::styled("False Discovery Rate", theme::accent_bold())));
+        metric_line(&mut lines, "p-value", &format!("{:.4}", fdr.p_value));
+        metric_line(&mut lines, "q-value", &format!("{:.4}", fdr.q_value));
+        metric_line(&mut lines, "Family Trials", &fdr.family_trials.to_string());
+        let verdict = if fdr.significant { "survives" } else { "does not survive" };
+        metric_line(&mut lines, "At FDR Level", verdict);
+        lines.push(Line::from(""));
+    }
+
+    // Friction ramp: where the edge disappears as slippage rises
+    if let Some(fr) = &entry.friction {
+        lines.push(Line::from(Span::styled("Friction", theme::accent_bold())));
+        if let (Some(first), Some(last)) = (fr.points.first(), fr.points.last()) {
+            let sharpes: Vec<f64> = fr.points.iter().map(|p| p.sharpe).collect();
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:>20}: ", "Sharpe vs Slippage"), theme::muted()),
+                Span::styled(sparkline::blocks(&sharpes), theme::accent()),
+                Span::styled(
+                    format!(
+                        "  {:.0}-{:.0} bps, {}",
+                        first.slippage_bps, last.slippage_bps, fr.symbol
+                    ),
+                    theme::muted(),
+                ),
+            ]));
+        }
+        match fr.break_even_bps {
+            Some(bps) => metric_line(&mut lines, "Break-even Slippage", &format!("{bps:.1} bps")),
+            None => metric_line(&mut lines, "Break-even Slippage", "none"),
+        }
+        metric_num(
+            &mut lines,
+            &format!("Slope @ {:.0} bps", fr.preset_slippage_bps),
+            fr.slope_at_preset,
+            false,
+        );
+        lines.push(Line::from(""));
+    }
+
+    // Buy-and-hold of the same symbol over the same window
+    if let Some(b) = &entry.benchmark {
+        lines.push(Line::from(Span::styled("vs Buy-and-Hold", theme::accent_bold())));
+        let (run, held) = (b.strategy_value(), b.metric.value(&b.buy_and_hold));
+        metric_line(
+            &mut lines,
+            b.metric.label(),
+            &format!("{run:.4} vs {held:.4} held"),
+        );
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:>20}: ", "Margin"), theme::muted()),
+            Span::styled(b.metric.format_margin(b.margin), theme::metric_color(b.margin)),
+        ]));
+        metric_num(&mut lines, "Held Max Drawdown", b.buy_and_hold.max_drawdown * 100.0, true);
+        metric_num(&mut lines, "Drawdown Margin", b.drawdown_margin * 100.0, true);
+        lines.push(Line::from(""));
+    }
+
+    // Percentile of the run among randomized-entry variants
+    if let Some(sv) = &entry.signal_value {
+        lines.push(Line::from(Span::styled("vs Shuffled Entries", theme::accent_bold())));
+        metric_line(&mut lines, "Variants", &format!("{} x {} trades", sv.variants, sv.trades));
+        for (label, percentile) in [
+            ("Sharpe Percentile", sv.sharpe_percentile),
+            ("Return Percentile", sv.total_return_percentile),
+        ] {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {label:>20}: "), theme::muted()),
+                Span::styled(format!("{percentile:.0}"), theme::metric_color(percentile - 50.0)),
+            ]));
+        }
+        metric_num(&mut lines, "Null Median Sharpe", sv.null_median_sharpe, false);
+        metric_num(&mut lines, "Null Median Return", sv.null_median_total_return * 100.0, true);
+        lines.push(Line::from(""));
+    }
+
+    // Fitness with the primary stop and lookback nudged
+    if let Some(ps) = &entry.sensitivity {
+        lines.push(Line::from(Span::styled("Parameter Sensitivity", theme::accent_bold())));
+        match &ps.cliff {
+            Some(cliff) => lines.push(Line::from(vec![
+                Span::styled(format!("  {:>20}: ", "Cliff"), theme::muted()),
+                Span::styled(cliff.to_string(), theme::warning()),
+            ])),
+            None => metric_line(&mut lines, "Cliff", "none"),
+        }
+        metric_num(&mut lines, &format!("Base {}", ps.metric.label()), ps.base_fitness, false);
+        for p in &ps.probes {
+            let label = format!("{} {:+.1}%", p.param, p.perturbation * 100.0);
+            let change = p
+                .fitness_change
+                .map_or("-".to_string(), |c| format!("{:+.0}%", c * 100.0));
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {label:>20}: "), theme::muted()),
+                Span::styled(
+                    format!("{:.4} ({change}), {} trades", p.fitness, p.trade_count),
+                    theme::metric_color(p.fitness_change.unwrap_or(0.0)),
+                ),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Tail risk, with the worst-days table collapsed by default
+    if let Some(t) = tail_metrics {
+        let heading = if window.is_some() { "Tail Risk (window)" } else { "Tail Risk" };
+        lines.push(Line::from(Span::styled(heading, theme::accent_bold())));
+        match (t.cvar_95, t.cvar_99) {
+            (Some(c95), Some(c99)) => {
+                metric_num(&mut lines, "CVaR 95%", c95 * 100.0, true);
+                metric_num(&mut lines, "CVaR 99%", c99 * 100.0, true);
+            }
+            _ => metric_line(
+                &mut lines,
+                "CVaR",
+                &format!("n/a ({} returns)", t.sample_size),
+            ),
+        }
+        if let (Some(skew), Some(kurt)) = (t.skewness, t.kurtosis) {
+            metric_num(&mut lines, "Skewness", skew, false);
+            metric_num(&mut lines, "Excess Kurtosis", kurt, false);
+        }
+        if !t.worst_days.is_empty() {
+            if app.results.worst_days_expanded {
+                lines.push(Line::from(Span::styled(
+                    format!("  {:>10}  {:>8}  {:>9}", "Date", "Return", "Positions"),
+                    theme::muted(),
+                )));
+                for day in &t.worst_days {
+                    lines.push(Line::from(vec![
+                        Span::styled(format!("  {:>10}  ", day.date), theme::muted()),
+                        Span::styled(
+                            format!("{:>7.2}%", day.daily_return * 100.0),
+                            theme::metric_color(day.daily_return),
+                        ),
+                        Span::styled(
+                            format!("  {:>9}", day.concurrent_positions),
+                            theme::accent(),
+                        ),
+                    ]));
+                }
+            } else {
+                lines.push(Line::from(Span::styled(
+                    format!("  Worst {} days hidden [w]", t.worst_days.len()),
+                    theme::muted(),
+                )));
+            }
+        }
     }
 
     let para = Paragraph::new(lines);
     f.render_widget(para, inner);
 }
 
+/// Live YOLO dashboard: throughput, sliders, best fitness, recent insertions.
+pub fn render_yolo_dashboard(f: &mut Frame, area: Rect, app: &AppState) {
+    let popup = centered_rect(80, 80, area);
+    f.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme::accent())
+        .title(" YOLO Dashboard [s]start/stop [+/-]explore [Esc]close ")
+        .title_style(theme::accent_bold());
+
+    let inner = block.inner(popup);
+    f.render_widget(block, popup);
+
+    let s = &app.sweep;
+    let live = s
+        .tunables
+        .read()
+        .map(|t| *t)
+        .unwrap_or_else(|p| *p.into_inner());
+    let mut lines: Vec<Line> = Vec::new();
+
+    // Session
+    lines.push(Line::from(if s.yolo_running {
+        Span::styled("RUNNING", theme::positive().add_modifier(Modifier::BOLD))
+    } else {
+        Span::styled("IDLE", theme::muted().add_modifier(Modifier::BOLD))
+    }));
+    metric_line(&mut lines, "Jitter", &format!("{:.0}%", live.jitter_pct * 100.0));
+    metric_line(
+        &mut lines,
+        "Structural Explore",
+        &format!("{:.0}%", live.structural_explore * 100.0),
+    );
+
+    let Some(p) = &s.last_progress else {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            if s.yolo_running {
+                "Waiting for first iteration..."
+            } else {
+                "Select tickers in the Data panel, then press s to start."
+            },
+            theme::muted(),
+        )));
+        f.render_widget(Paragraph::new(lines), inner);
+        return;
+    };
+
+    metric_line(&mut lines, "Iteration", &p.iteration.to_string());
+    metric_line(&mut lines, "Rate", &format!("{:.0} iter/min", p.throughput_per_min));
+    metric_line(&mut lines, "Elapsed", &format!("{:.0}s", p.elapsed_secs));
+    metric_line(
+        &mut lines,
+        "Runs",
+        &format!(
+            "{} ok ({} no trades) / {} err",
+            p.success_count, p.zero_trade_count, p.error_count
+        ),
+    );
+    metric_line(&mut lines, "Compute", &p.compute.describe());
+    metric_line(
+        &mut lines,
+        "Leaderboard",
+        &format!(
+            "{} entries | Cross: {} | L2: {} L3: {}",
+            p.leaderboard_entries,
+            p.cross_leaderboard_entries,
+            p.promoted_l2_count,
+            p.promoted_l3_count,
+        ),
+    );
+    lines.push(Line::from(vec![
+        Span::styled(format!("  {:>20}: ", "Convergence"), theme::muted()),
+        Span::styled(
+            p.convergence.describe(),
+            if p.convergence.plateaued {
+                theme::warning()
+            } else {
+                theme::accent()
+            },
+        ),
+    ]));
+    if let Some(mode) = p.exploration_mode {
+        metric_line(&mut lines, "Exploration", &mode.describe());
+    }
+    if p.data_quality_warnings > 0 {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:>20}: ", "Data Warnings"), theme::muted()),
+            Span::styled(
+                format!("{} (flagged with ! in Results)", p.data_quality_warnings),
+                theme::warning(),
+            ),
+        ]));
+    }
+    if !p.errors_by_kind.is_empty() {
+        let by_kind: Vec<String> = p
+            .errors_by_kind
+            .iter()
+            .map(|(kind, n)| format!("{} {n}", kind.label()))
+            .collect();
+        metric_line(&mut lines, "Errors", &by_kind.join(" | "));
+    }
+    if !p.rejections_by_rule.is_empty() {
+        let by_rule: Vec<String> = p
+            .rejections_by_rule
+            .iter()
+            .map(|(rule, n)| format!("{rule} {n}"))
+            .collect();
+        metric_line(&mut lines, "Resampled", &by_rule.join(" | "));
+    }
+    if !p.quarantined.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:>20}: ", "Quarantined"), theme::muted()),
+            Span::styled(p.quarantined.join(", "), theme::warning()),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    // Best fitness per symbol, best first
+    lines.push(Line::from(Span::styled("Best Fitness", theme::accent_bold())));
+    let mut best: Vec<(&String, &f64)> = p.best_fitness.iter().collect();
+    best.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap_or(std::cmp::Ordering::Equal));
+    if best.is_empty() {
+        lines.push(Line::from(Span::styled("  none yet", theme::muted())));
+    }
+    for (symbol, fitness) in best {
+        metric_num(&mut lines, symbol, *fitness, false);
+    }
+    lines.push(Line::from(""));
+
+    // Recent leaderboard insertions, newest first
+    lines.push(Line::from(Span::styled("Recent Insertions", theme::accent_bold())));
+    if p.recent_insertions.is_empty() {
+        lines.push(Line::from(Span::styled("  none yet", theme::muted())));
+    }
+    for ins in p.recent_insertions.iter().rev() {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  #{:<6} ", ins.iteration), theme::muted()),
+            Span::styled(format!("{:<6} ", ins.symbol), theme::neutral()),
+            Span::styled(format!("{} + {} ", ins.signal_type, ins.pm_type), theme::accent()),
+            Span::styled(format!("{:.4}", ins.fitness_score), theme::metric_color(ins.fitness_score)),
+            Span::styled(if ins.replaced { " (replaced)" } else { "" }, theme::muted()),
+        ]));
+    }
+
+    let para = Paragraph::new(lines);
+    f.render_widget(para, inner);
+}
+
+/// Saved YOLO sessions, or the leaderboards of the opened one.
+pub fn render_sessions(f: &mut Frame, area: Rect, app: &AppState) {
+    let popup = centered_rect(85, 80, area);
+    f.render_widget(Clear, popup);
+
+    let b = &app.sessions;
+    let title = match &b.open {
+        Some(open) => format!(
+            " Session {} [h/l]board [Enter]detail [Esc]back ",
+            open.session_id
+        ),
+        None => format!(" Sessions ({}) [Enter]open [Esc]close ", b.sessions.len()),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme::accent())
+        .title(title)
+        .title_style(theme::accent_bold());
+
+    let inner = block.inner(popup);
+    f.render_widget(block, popup);
+
+    let visible_height = inner.height.saturating_sub(3) as usize;
+    let mut lines: Vec<Line> = Vec::new();
+
+    let Some(open) = &b.open else {
+        if b.sessions.is_empty() {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "No saved sessions in {}. Sessions are saved when YOLO stops.",
+                    app.sessions_dir.display()
+                ),
+                theme::muted(),
+            )));
+            f.render_widget(Paragraph::new(lines), inner);
+            return;
+        }
+        lines.push(Line::from(Span::styled(
+            format!(
+                "{:<28} {:>23} {:>5} {:>8} {:>8} {:>6}",
+                "Session", "Range", "Syms", "Iters", "Best", "Runs"
+            ),
+            theme::accent_bold(),
+        )));
+        let start = b.cursor.saturating_sub(visible_height.saturating_sub(1));
+        for (i, s) in b
+            .sessions
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(visible_height)
+        {
+            let style = if i == b.cursor {
+                theme::accent().add_modifier(Modifier::REVERSED)
+            } else {
+                theme::muted()
+            };
+            let best = s
+                .best_avg_sharpe
+                .map_or_else(|| "-".to_string(), |v| format!("{v:.2}"));
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{:<28} {} → {} {:>5} {:>8} {:>8} {:>6}",
+                    s.session_id,
+                    s.start_date,
+                    s.end_date,
+                    s.symbols.len(),
+                    s.iterations_completed,
+                    best,
+                    s.saved_artifacts
+                ),
+                style,
+            )));
+        }
+        f.render_widget(Paragraph::new(lines), inner);
+        return;
+    };
+
+    // Board tabs, current one highlighted
+    let mut tabs: Vec<Span> = Vec::new();
+    for (i, board) in open.boards.iter().enumerate() {
+        let style = if i == open.board {
+            theme::accent_bold().add_modifier(Modifier::REVERSED)
+        } else {
+            theme::muted()
+        };
+        tabs.push(Span::styled(format!(" {} ", board.title), style));
+        tabs.push(Span::raw(" "));
+    }
+    lines.push(Line::from(tabs));
+
+    let board = open.current();
+    if board.entries.is_empty() {
+        lines.push(Line::from(Span::styled("  No entries.", theme::muted())));
+        f.render_widget(Paragraph::new(lines), inner);
+        return;
+    }
+    lines.push(Line::from(Span::styled(
+        format!(
+            "{:>3} {:>14} {:>12} {:>8} {:>7} {:>7} {:>6} {:>6}  {}",
+            "#", "Signal", "PM", "Symbol", "Sharpe", "CAGR", "MaxDD", "Trades", "Run"
+        ),
+        theme::accent_bold(),
+    )));
+    let start = open.cursor.saturating_sub(visible_height.saturating_sub(1));
+    for (i, e) in board
+        .entries
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(visible_height)
+    {
+        let is_cursor = i == open.cursor;
+        let style = if is_cursor {
+            theme::accent().add_modifier(Modifier::REVERSED)
+        } else {
+            theme::muted()
+        };
+        let run = if e.artifact_dir.is_some() {
+            Span::styled("saved", if is_cursor { style } else { theme::positive() })
+        } else {
+            Span::styled("-", style)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    "{:>3} {:>14} {:>12} {:>8} ",
+                    e.rank,
+                    truncate(&e.signal_type, 14),
+                    truncate(&e.pm_type, 12),
+                    truncate(&e.symbol, 8)
+                ),
+                style,
+            ),
+            Span::styled(
+                format!("{:>7.2} ", e.sharpe),
+                if is_cursor {
+                    style
+                } else {
+                    theme::sharpe_style(e.sharpe)
+                },
+            ),
+            Span::styled(
+                format!("{:>6.1}% ", e.cagr * 100.0),
+                if is_cursor {
+                    style
+                } else {
+                    theme::metric_color(e.cagr)
+                },
+            ),
+            Span::styled(format!("{:>5.1}% ", e.max_drawdown * 100.0), style),
+            Span::styled(format!("{:>6}  ", e.trade_count), style),
+            run,
+        ]));
+    }
+
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Cached symbols with their ranges and sizes, and per-symbol download state.
+pub fn render_cache_manager(f: &mut Frame, area: Rect, app: &AppState) {
+    let popup = centered_rect(75, 80, area);
+    f.render_widget(Clear, popup);
+
+    let data = &app.data;
+    let total_size: u64 = data.cache_entries.iter().map(|e| e.size_bytes).sum();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme::accent())
+        .title(format!(
+            " Cache ({} symbols, {}) [Space]mark [r]download [R]retry failed [x]cancel [Esc]close ",
+            data.cache_entries.len(),
+            format_size(total_size)
+        ))
+        .title_style(theme::accent_bold());
+
+    let inner = block.inner(popup);
+    f.render_widget(block, popup);
+
+    let (start, end) = app.download_range();
+    let mut lines: Vec<Line> = vec![Line::from(vec![
+        Span::styled("Download range: ", theme::muted()),
+        Span::styled(format!("{start} → {end}"), theme::accent()),
+        Span::styled("  [h/l]start [H/L]end  ", theme::muted()),
+        Span::styled(format!("Marked: {}", data.manager.marked.len()), theme::accent()),
+    ])];
+
+    let rows = data.cache_rows();
+    if rows.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(
+                "Nothing cached in {}. Mark tickers in the Data panel and press [f] to fetch.",
+                app.cache_dir.display()
+            ),
+            theme::muted(),
+        )));
+        f.render_widget(Paragraph::new(lines), inner);
+        return;
+    }
+
+    lines.push(Line::from(Span::styled(
+        format!(
+            "    {:<8} {:<25} {:>8} {:>10}  {}",
+            "Symbol", "Date Range", "Bars", "Size", "Status"
+        ),
+        theme::accent_bold(),
+    )));
+    let visible_height = inner.height.saturating_sub(3) as usize;
+    let cursor = data.manager.cursor;
+    let first = cursor.saturating_sub(visible_height.saturating_sub(1));
+    for (i, symbol) in rows.iter().enumerate().skip(first).take(visible_height) {
+        let entry = data.cache_entries.iter().find(|e| &e.symbol == symbol);
+        let (range, bars, size) = match entry {
+            Some(e) => (
+                e.date_range_label(),
+                e.bar_count().to_string(),
+                format_size(e.size_bytes),
+            ),
+            None => ("(not cached)".to_string(), "-".into(), "-".into()),
+        };
+        let mark = if data.manager.marked.contains(symbol) { "[x]" } else { "[ ]" };
+        let style = if i == cursor {
+            theme::accent().add_modifier(Modifier::REVERSED)
+        } else {
+            theme::muted()
+        };
+        let status = match data.downloads.get(symbol) {
+            None => Span::styled("", style),
+            Some(DownloadStatus::Pending) => Span::styled("pending", theme::muted()),
+            Some(DownloadStatus::Downloading { attempt: 1 }) => {
+                Span::styled("downloading", theme::warning())
+            }
+            Some(DownloadStatus::Downloading { attempt }) => {
+                Span::styled(format!("downloading (attempt {attempt})"), theme::warning())
+            }
+            Some(DownloadStatus::Done) => Span::styled("done", theme::positive()),
+            Some(DownloadStatus::Failed(err)) => {
+                Span::styled(format!("error: {err}"), theme::negative())
+            }
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    "{mark} {:<8} {:<25} {:>8} {:>10}  ",
+                    truncate(symbol, 8),
+                    range,
+                    bars,
+                    size
+                ),
+                style,
+            ),
+            status,
+        ]));
+    }
+
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Bar replay: the position's candles up to the cursor with its stop (and
+/// optionally one indicator) drawn over them, plus the cursor bar's details.
+pub fn render_replay(f: &mut Frame, area: Rect, app: &AppState) {
+    let Some(replay) = &app.replay else {
+        return;
+    };
+    let popup = centered_rect(90, 90, area);
+    f.render_widget(Clear, popup);
+
+    let pos = replay.current();
+    let side = format!("{:?}", pos.side).to_lowercase();
+    let note = app.annotation(replay.entry, Some(pos.entry_bar));
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme::accent())
+        .title(format!(
+            " Replay {} {} {}/{}{} [h/l]step [e/x]entry/exit [p/n]position [f]next loser \
+             [i]indicator [N]ote [Esc]back ",
+            pos.symbol,
+            side,
+            replay.position + 1,
+            replay.log.positions.len(),
+            if note.is_some() { format!(" {NOTE_MARKER}") } else { String::new() },
+        ))
+        .title_style(theme::accent_bold());
+    let inner = block.inner(popup);
+    f.render_widget(block, popup);
+
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(20), Constraint::Length(36)])
+        .split(inner);
+
+    // Price pane
+    let candles: Vec<Candle> = pos
+        .bars
+        .iter()
+        .map(|b| Candle {
+            open: b.open,
+            high: b.high,
+            low: b.low,
+            close: b.close,
+        })
+        .collect();
+    let mut price_lines = vec![PriceLine {
+        values: pos.bars.iter().map(|b| b.stop).collect(),
+        color: theme::warning().fg.unwrap_or(Color::Yellow),
+        stepped: true,
+    }];
+    let indicator = replay.indicator_name();
+    if let Some(name) = &indicator {
+        price_lines.push(PriceLine {
+            values: pos.bars.iter().map(|b| b.indicators.get(name).copied()).collect(),
+            color: theme::accent().fg.unwrap_or(Color::Cyan),
+            stepped: false,
+        });
+    }
+    candles::render(f, panes[0], &candles, replay.cursor + 1, &price_lines);
+
+    // Sidebar
+    let bar = &pos.bars[replay.cursor];
+    let mut lines: Vec<Line> = Vec::new();
+    let row = |lines: &mut Vec<Line>, label: &str, value: String, style| {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{label:>9}: "), theme::muted()),
+            Span::styled(value, style),
+        ]));
+    };
+    let marker = if bar.exit {
+        "  EXIT"
+    } else if replay.cursor == 0 {
+        "  ENTRY"
+    } else {
+        ""
+    };
+    lines.push(Line::from(vec![
+        Span::styled(format!("Bar {}/{}", replay.cursor + 1, pos.bars.len()), theme::accent_bold()),
+        Span::styled(marker, theme::warning()),
+    ]));
+    row(&mut lines, "Date", bar.date.to_string(), theme::accent());
+    row(&mut lines, "Bar #", bar.bar_index.to_string(), theme::muted());
+    row(&mut lines, "Open", format!("{:.2}", bar.open), theme::accent());
+    row(&mut lines, "High", format!("{:.2}", bar.high), theme::accent());
+    row(&mut lines, "Low", format!("{:.2}", bar.low), theme::accent());
+    row(&mut lines, "Close", format!("{:.2}", bar.close), theme::accent());
+    lines.push(Line::from(""));
+
+    let prev_stop = replay
+        .cursor
+        .checked_sub(1)
+        .and_then(|i| pos.bars[i].stop);
+    match bar.stop {
+        Some(stop) => {
+            row(&mut lines, "Stop", format!("{stop:.2}"), theme::warning());
+            if let Some(prev) = prev_stop {
+                let moved = stop - prev;
+                let style = if moved.abs() < 1e-9 { theme::muted() } else { theme::accent() };
+                row(&mut lines, "Ratchet", format!("{moved:+.2}"), style);
+            }
+            row(&mut lines, "Cushion", format!("{:.2}", (bar.close - stop).abs()), theme::muted());
+        }
+        None => row(&mut lines, "Stop", "none".into(), theme::muted()),
+    }
+    row(&mut lines, "Equity", format!("{:.0}", bar.equity), theme::accent());
+    if let Some(pnl) = pos.net_pnl {
+        row(&mut lines, "Trade PnL", format!("{pnl:+.2}"), theme::metric_color(pnl));
+    }
+    lines.push(Line::from(""));
+
+    if let Some(annotation) = note {
+        annotation_lines(&mut lines, "Trade note", annotation);
+        lines.push(Line::from(""));
+    }
+
+    lines.push(Line::from(Span::styled("Working orders", theme::accent_bold())));
+    if bar.orders.is_empty() {
+        lines.push(Line::from(Span::styled("  none", theme::muted())));
+    }
+    for order in &bar.orders {
+        lines.push(Line::from(Span::styled(
+            format!("  {} x{:.0}", order.describe(), order.quantity),
+            theme::muted(),
+        )));
+    }
+    lines.push(Line::from(""));
+
+    lines.push(Line::from(Span::styled("Indicators", theme::accent_bold())));
+    if bar.indicators.is_empty() {
+        lines.push(Line::from(Span::styled("  none recorded", theme::muted())));
+    }
+    for (name, value) in &bar.indicators {
+        let style = if indicator.as_deref() == Some(name.as_str()) {
+            theme::accent().add_modifier(Modifier::REVERSED)
+        } else {
+            theme::muted()
+        };
+        lines.push(Line::from(Span::styled(
+            format!("  {:<18} {value:>12.2}", truncate(name, 18)),
+            style,
+        )));
+    }
+
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), panes[1]);
+}
+
+/// One block character per value, scaled between the slice's min and max.
 fn metric_line<'a>(lines: &mut Vec<Line<'a>>, label: &str, value: &str) {
     lines.push(Line::from(vec![
         Span::styled(format!("  {:>20}: ", label), theme::muted()),
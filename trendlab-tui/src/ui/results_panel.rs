@@ -2,15 +2,32 @@
 
 use ratatui::Frame;
 use ratatui::layout::Rect;
-use ratatui::style::Modifier;
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 
+use trendlab_runner::FdrStanding;
+
 use crate::app::AppState;
 use crate::theme;
+use crate::ui::widgets::sparkline;
+
+/// Cells in the equity sparkline column.
+const SPARKLINE_WIDTH: usize = 20;
+
+/// Marks runs and trades that carry a journal note or tag.
+pub const NOTE_MARKER: &str = "✎";
+
+/// Marks manual variants: runs re-run with nudged params (see `NudgeState`).
+pub const VARIANT_MARKER: &str = "±";
+
+/// Marks entries whose fitness falls off a cliff when a primary param is
+/// nudged (see `ParameterSensitivity`).
+pub const CLIFF_MARKER: &str = "↯";
 
 pub fn render(f: &mut Frame, area: Rect, app: &AppState) {
     let r = &app.results;
+    let visible = r.visible_indices();
     let mut lines: Vec<Line> = Vec::new();
 
     // Header
@@ -23,14 +40,33 @@ pub fn render(f: &mut Frame, area: Rect, app: &AppState) {
             theme::muted(),
         ),
         Span::styled(
-            format!("{} entries", r.entries.len()),
+            if let Some(query) = &r.filter {
+                format!(
+                    "{} of {} entries (filter: {}{})",
+                    visible.len(),
+                    r.entries.len(),
+                    query.text,
+                    if r.diversified_view { ", diversified" } else { "" }
+                )
+            } else if r.diversified_view {
+                format!(
+                    "{} of {} entries (diversified)",
+                    visible.len(),
+                    r.entries.len()
+                )
+            } else {
+                format!("{} entries", r.entries.len())
+            },
             theme::accent(),
         ),
-        Span::styled("  [j/k]scroll [t]oggle [p]rofile [Enter]detail", theme::muted()),
+        Span::styled(
+            "  [j/k]scroll [t]oggle [p]rofile [d]iversified [/]filter [e]xport [n]ote [Enter]detail",
+            theme::muted(),
+        ),
     ]));
     lines.push(Line::from(""));
 
-    if r.entries.is_empty() {
+    if visible.is_empty() {
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
             "No results yet. Run a backtest from Panel 2 or start YOLO from Panel 3.",
@@ -41,8 +77,10 @@ pub fn render(f: &mut Frame, area: Rect, app: &AppState) {
         lines.push(Line::from(vec![
             Span::styled(
                 format!(
-                    "{:>3} {:>14} {:>12} {:>8} {:>7} {:>7} {:>6} {:>5} {:>5}",
-                    "#", "Signal", "PM", "Symbol", "Sharpe", "CAGR", "MaxDD", "WR%", "Trades"
+                    "{:>3} {:>14} {:>12} {:>8}  {:>7} {:>7} {:>6} {:>5} {:>5} {:>4} {:>6}  {:<w$}",
+                    "#", "Signal", "PM", "Symbol", "Sharpe", "CAGR", "MaxDD", "WR%", "Trades", "Clu",
+                    "q", "Equity",
+                    w = SPARKLINE_WIDTH
                 ),
                 theme::accent_bold(),
             ),
@@ -51,11 +89,11 @@ pub fn render(f: &mut Frame, area: Rect, app: &AppState) {
         // Visible rows
         let visible_height = area.height.saturating_sub(4) as usize;
         let start = r.scroll_offset;
-        let end = (start + visible_height).min(r.entries.len());
+        let end = (start + visible_height).min(visible.len());
 
-        for i in start..end {
+        for (row, &i) in visible.iter().enumerate().take(end).skip(start) {
             let entry = &r.entries[i];
-            let is_cursor = i == r.cursor;
+            let is_cursor = row == r.cursor;
 
             let style = if is_cursor {
                 theme::accent().add_modifier(Modifier::REVERSED)
@@ -81,17 +119,52 @@ pub fn render(f: &mut Frame, area: Rect, app: &AppState) {
                 theme::negative()
             };
 
-            lines.push(Line::from(vec![
-                Span::styled(format!("{:>3} ", entry.rank), style),
-                Span::styled(format!("{:>14} ", truncate(&entry.signal_type, 14)), style),
+            let mut spans = vec![
+                Span::styled(format!("{:>3}", entry.rank), style),
+                if app.is_annotated(entry) {
+                    Span::styled(NOTE_MARKER, if is_cursor { style } else { theme::accent() })
+                } else {
+                    Span::styled(" ", style)
+                },
+                if entry.is_manual_variant() {
+                    Span::styled(VARIANT_MARKER, if is_cursor { style } else { theme::accent() })
+                } else {
+                    Span::styled(" ", style)
+                },
+                Span::styled(format!("{:>13} ", truncate(&entry.signal_type, 13)), style),
                 Span::styled(format!("{:>12} ", truncate(&entry.pm_type, 12)), style),
-                Span::styled(format!("{:>8} ", truncate(&entry.symbol, 8)), style),
+                Span::styled(format!("{:>8}", truncate(&entry.symbol, 8)), style),
+                if entry.data_warnings > 0 {
+                    Span::styled("!", if is_cursor { style } else { theme::warning() })
+                } else {
+                    Span::styled(" ", style)
+                },
+                if entry.sensitivity.as_ref().is_some_and(|s| s.cliff.is_some()) {
+                    Span::styled(CLIFF_MARKER, if is_cursor { style } else { theme::warning() })
+                } else {
+                    Span::styled(" ", style)
+                },
                 Span::styled(format!("{:>7.2} ", entry.sharpe), sharpe_style),
                 Span::styled(format!("{:>6.1}% ", entry.cagr * 100.0), cagr_style),
                 Span::styled(format!("{:>5.1}% ", entry.max_drawdown * 100.0), dd_style),
                 Span::styled(format!("{:>4.0}% ", entry.win_rate * 100.0), style),
-                Span::styled(format!("{:>5}", entry.trade_count), style),
-            ]));
+                Span::styled(format!("{:>5} ", entry.trade_count), style),
+                Span::styled(
+                    entry
+                        .cluster_id
+                        .map_or_else(|| format!("{:>4}", "-"), |c| format!("{:>4}", c + 1)),
+                    style,
+                ),
+                fdr_span(entry.fdr.as_ref(), is_cursor, style),
+                Span::styled("  ", style),
+            ];
+            spans.extend(sparkline::equity_spans(
+                &entry.equity_sparkline,
+                SPARKLINE_WIDTH,
+                if is_cursor { style } else { theme::accent() },
+                if is_cursor { style } else { theme::warning() },
+            ));
+            lines.push(Line::from(spans));
         }
     }
 
@@ -99,10 +172,166 @@ pub fn render(f: &mut Frame, area: Rect, app: &AppState) {
     f.render_widget(para, area);
 }
 
-fn truncate(s: &str, max: usize) -> String {
+/// FDR q-value cell: starred and positive when the entry survives.
+fn fdr_span(fdr: Option<&FdrStanding>, is_cursor: bool, style: Style) -> Span<'static> {
+    match fdr {
+        None => Span::styled(format!(" {:>6}", "-"), style),
+        Some(f) if f.significant => Span::styled(
+            format!(" {:>5.3}*", f.q_value),
+            if is_cursor { style } else { theme::positive() },
+        ),
+        Some(f) => Span::styled(format!(" {:>5.3} ", f.q_value), style),
+    }
+}
+
+pub fn truncate(s: &str, max: usize) -> String {
     if s.len() <= max {
         s.to_string()
     } else {
         format!("{}.", &s[..max - 1])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+
+    use ratatui::backend::TestBackend;
+    use ratatui::buffer::Buffer;
+    use ratatui::style::Color;
+    use ratatui::Terminal;
+    use trendlab_runner::{
+        ArtifactSummary, FitnessMetric, ParameterCliff, ParameterSensitivity, PerformanceMetrics,
+        ProbedComponent,
+    };
+
+    use super::*;
+    use crate::app::{LeaderboardDisplayEntry, StrategyPanelState};
+    use crate::theme::ThemeName;
+
+    fn entry(rank: usize, sharpe: f64) -> LeaderboardDisplayEntry {
+        let curve = vec![100_000.0, 101_000.0, 102_000.0];
+        let mut entry = LeaderboardDisplayEntry::from_summary(
+            rank,
+            ArtifactSummary {
+                dir: PathBuf::from("results/SPY"),
+                schema_version: 1,
+                metrics: PerformanceMetrics::compute(&curve, &[], 100_000.0),
+                config: StrategyPanelState::new().to_strategy_config(),
+                symbol: "SPY".into(),
+                start_date: "2020-01-01".into(),
+                end_date: "2020-12-31".into(),
+                initial_capital: 100_000.0,
+                bar_count: curve.len(),
+                data_warning_count: 0,
+                stickiness: None,
+                position_stickiness: Vec::new(),
+                r_distribution: Default::default(),
+                trade_distributions: Default::default(),
+                tail_metrics: None,
+                style: None,
+                equity_sparkline: Vec::new(),
+                attribution: Default::default(),
+                unknown_fields: Vec::new(),
+            },
+        );
+        entry.sharpe = sharpe;
+        entry
+    }
+
+    fn render_leaderboard(theme_name: ThemeName) -> Buffer {
+        let (tx, _rx) = std::sync::mpsc::channel();
+        let (_tx2, rx2) = std::sync::mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut app = AppState::new(tx, rx2, cancel, PathBuf::from("."), PathBuf::from("."));
+        app.results.entries = vec![entry(1, 2.0), entry(2, 1.5)];
+
+        theme::set(theme_name);
+        let mut terminal = Terminal::new(TestBackend::new(120, 8)).unwrap();
+        terminal.draw(|f| render(f, f.area(), &app)).unwrap();
+        theme::set(ThemeName::Dark);
+        terminal.backend().buffer().clone()
+    }
+
+    /// Foreground of the first cell of `text` on row `y`.
+    fn fg_of(buf: &Buffer, y: u16, text: &str) -> Color {
+        let row: String = (0..buf.area.width)
+            .map(|x| buf[(x, y)].symbol().to_string())
+            .collect();
+        let x = row
+            .find(text)
+            .unwrap_or_else(|| panic!("{text:?} not in {row:?}"));
+        buf[(x as u16, y)].fg
+    }
+
+    #[test]
+    fn leaderboard_follows_the_active_theme() {
+        let dark = render_leaderboard(ThemeName::Dark);
+        let colorblind = render_leaderboard(ThemeName::Colorblind);
+
+        // Row 4 is the second entry, off the cursor: its Sharpe is styled
+        // as a positive value
+        let dark_sharpe = fg_of(&dark, 4, "1.50");
+        let colorblind_sharpe = fg_of(&colorblind, 4, "1.50");
+        assert_eq!(dark_sharpe, ThemeName::Dark.palette().positive);
+        assert_eq!(colorblind_sharpe, ThemeName::Colorblind.palette().positive);
+        assert_ne!(dark_sharpe, colorblind_sharpe);
+
+        assert_eq!(fg_of(&dark, 4, "SPY"), ThemeName::Dark.palette().muted);
+        assert_ne!(fg_of(&dark, 4, "SPY"), fg_of(&colorblind, 4, "SPY"));
+
+        // Cursor row and column headers use the accent in both themes
+        assert_eq!(fg_of(&dark, 3, "2.00"), ThemeName::Dark.palette().accent);
+        assert_eq!(
+            fg_of(&colorblind, 2, "Sharpe"),
+            ThemeName::Colorblind.palette().accent
+        );
+    }
+
+    #[test]
+    fn cliff_entries_are_marked() {
+        let (tx, _rx) = std::sync::mpsc::channel();
+        let (_tx2, rx2) = std::sync::mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut app = AppState::new(tx, rx2, cancel, PathBuf::from("."), PathBuf::from("."));
+        let mut cliff = entry(2, 1.5);
+        cliff.sensitivity = Some(ParameterSensitivity {
+            metric: FitnessMetric::Sharpe,
+            base_fitness: 1.5,
+            probes: Vec::new(),
+            cliff: Some(ParameterCliff {
+                component: ProbedComponent::PositionManager,
+                param: "multiplier".into(),
+                metric: FitnessMetric::Sharpe,
+                perturbation: -0.02,
+                fitness_change: -0.6,
+            }),
+        });
+        app.results.entries = vec![entry(1, 2.0), cliff];
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 8)).unwrap();
+        terminal.draw(|f| render(f, f.area(), &app)).unwrap();
+        let buf = terminal.backend().buffer();
+        let row = |y: u16| -> String { (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect() };
+        assert!(!row(3).contains(CLIFF_MARKER), "{}", row(3));
+        assert!(row(4).contains(CLIFF_MARKER), "{}", row(4));
+    }
+
+    #[test]
+    fn q_value_cell_marks_survivors() {
+        let standing = |q_value, significant| FdrStanding {
+            p_value: 0.001,
+            q_value,
+            significant,
+            family_trials: 40,
+        };
+        let survivor = fdr_span(Some(&standing(0.012, true)), false, theme::muted());
+        assert_eq!(survivor.content, " 0.012*");
+        assert_eq!(survivor.style, theme::positive());
+        let rejected = fdr_span(Some(&standing(0.2, false)), false, theme::muted());
+        assert_eq!(rejected.content, " 0.200 ");
+        assert_eq!(fdr_span(None, false, theme::muted()).content, "      -");
+    }
+}
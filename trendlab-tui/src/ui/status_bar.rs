@@ -13,7 +13,7 @@ pub fn render(f: &mut Frame, area: Rect, app: &AppState) {
 
     // Panel hints
     spans.push(Span::styled(
-        " 1:Data 2:Strategy 3:Sweep 4:Results 5:Chart 6:Help",
+        " 1:Data 2:Strategy 3:Sweep 4:Results 5:Chart 6:Help ?:Keys",
         theme::muted(),
     ));
 
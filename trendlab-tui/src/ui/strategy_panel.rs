@@ -6,6 +6,8 @@ use ratatui::style::Modifier;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 
+use trendlab_core::components::composition::{check_compatibility, CompatibilityContext};
+
 use crate::app::AppState;
 use crate::theme;
 
@@ -92,6 +94,12 @@ pub fn render(f: &mut Frame, area: Rect, app: &AppState) {
         ),
     ]));
 
+    // Compatibility rules the current composition breaks
+    let compat = check_compatibility(&s.to_strategy_config(), &CompatibilityContext::default());
+    for warning in compat.warnings {
+        lines.push(Line::from(Span::styled(format!("! {warning}"), theme::warning())));
+    }
+
     let para = Paragraph::new(lines);
     f.render_widget(para, area);
 }
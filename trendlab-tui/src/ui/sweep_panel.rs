@@ -32,7 +32,7 @@ pub fn render(f: &mut Frame, area: Rect, app: &AppState) {
     if s.yolo_running {
         lines.push(Line::from(vec![
             Span::styled("YOLO RUNNING ", theme::positive()),
-            Span::styled("[Esc]stop", theme::muted()),
+            Span::styled("[Esc]stop [Y]dashboard", theme::muted()),
         ]));
 
         if let Some(p) = &s.last_progress {
@@ -41,8 +41,8 @@ pub fn render(f: &mut Frame, area: Rect, app: &AppState) {
                 Span::styled(format!("| {} ", p.current_symbol), theme::neutral()),
                 Span::styled(
                     format!(
-                        "| {:.0} iter/min | {} ok / {} err",
-                        p.throughput_per_min, p.success_count, p.error_count
+                        "| {:.0} iter/min | {} ok ({} no trades) / {} err",
+                        p.throughput_per_min, p.success_count, p.zero_trade_count, p.error_count
                     ),
                     theme::muted(),
                 ),
@@ -57,6 +57,24 @@ pub fn render(f: &mut Frame, area: Rect, app: &AppState) {
                     theme::muted(),
                 ),
             ]));
+            if p.convergence.plateaued {
+                lines.push(Line::from(Span::styled(
+                    format!("Convergence: {}", p.convergence.describe()),
+                    theme::warning(),
+                )));
+            }
+            if let Some(mode) = p.exploration_mode {
+                lines.push(Line::from(Span::styled(
+                    format!("Mode: {mode}"),
+                    theme::muted(),
+                )));
+            }
+            if p.data_quality_warnings > 0 {
+                lines.push(Line::from(Span::styled(
+                    format!("Data warnings: {} (flagged with ! in Results)", p.data_quality_warnings),
+                    theme::warning(),
+                )));
+            }
         }
 
         lines.push(Line::from(""));
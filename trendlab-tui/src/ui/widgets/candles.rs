@@ -0,0 +1,96 @@
+//! OHLC candle chart drawn on a braille canvas.
+//!
+//! Used by the bar replay overlay. Bounds cover every candle and line passed
+//! in, so revealing bars one at a time never rescales the chart.
+
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use ratatui::symbols::Marker;
+use ratatui::text::Span;
+use ratatui::widgets::canvas::{Canvas, Line as CanvasLine};
+
+use crate::theme;
+
+/// One bar's prices.
+#[derive(Debug, Clone, Copy)]
+pub struct Candle {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+/// A series drawn over the candles, one optional value per candle.
+pub struct PriceLine {
+    pub values: Vec<Option<f64>>,
+    pub color: Color,
+    /// Hold each value flat across its bar and step between bars (stops),
+    /// instead of joining bar centres (indicators).
+    pub stepped: bool,
+}
+
+/// Draw `candles[..shown]` and the lines' values for those bars, with a
+/// cursor on the last shown bar. Bounds span all candles and values.
+pub fn render(f: &mut Frame, area: Rect, candles: &[Candle], shown: usize, lines: &[PriceLine]) {
+    let shown = shown.min(candles.len());
+    let values = candles
+        .iter()
+        .flat_map(|c| [c.low, c.high])
+        .chain(lines.iter().flat_map(|l| l.values.iter().flatten().copied()));
+    let (lo, hi) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+        (lo.min(v), hi.max(v))
+    });
+    if !lo.is_finite() || !hi.is_finite() {
+        return;
+    }
+    let pad = ((hi - lo) * 0.05).max(hi.abs() * 1e-4).max(1e-9);
+    let (y_min, y_max) = (lo - pad, hi + pad);
+    let x_max = candles.len() as f64;
+
+    let up = theme::positive().fg.unwrap_or(Color::Green);
+    let down = theme::negative().fg.unwrap_or(Color::Red);
+    let muted = theme::muted().fg.unwrap_or(Color::DarkGray);
+
+    let canvas = Canvas::default()
+        .marker(Marker::Braille)
+        .x_bounds([-1.0, x_max])
+        .y_bounds([y_min, y_max])
+        .paint(move |ctx| {
+            let cursor = shown.saturating_sub(1) as f64;
+            ctx.draw(&CanvasLine::new(cursor, y_min, cursor, y_max, muted));
+            ctx.layer();
+
+            for (i, c) in candles[..shown].iter().enumerate() {
+                let x = i as f64;
+                let color = if c.close >= c.open { up } else { down };
+                ctx.draw(&CanvasLine::new(x, c.low, x, c.high, color));
+                ctx.draw(&CanvasLine::new(x - 0.35, c.open, x, c.open, color));
+                ctx.draw(&CanvasLine::new(x, c.close, x + 0.35, c.close, color));
+            }
+
+            for line in lines {
+                let mut prev: Option<(f64, f64)> = None;
+                for (i, value) in line.values.iter().take(shown).enumerate() {
+                    let x = i as f64;
+                    let Some(y) = *value else {
+                        prev = None;
+                        continue;
+                    };
+                    if line.stepped {
+                        if let Some((px, py)) = prev {
+                            ctx.draw(&CanvasLine::new(px + 0.5, py, x - 0.5, y, line.color));
+                        }
+                        ctx.draw(&CanvasLine::new(x - 0.5, y, x + 0.5, y, line.color));
+                    } else if let Some((px, py)) = prev {
+                        ctx.draw(&CanvasLine::new(px, py, x, y, line.color));
+                    }
+                    prev = Some((x, y));
+                }
+            }
+
+            ctx.print(-1.0, y_max, Span::styled(format!("{hi:.2}"), theme::muted()));
+            ctx.print(-1.0, y_min, Span::styled(format!("{lo:.2}"), theme::muted()));
+        });
+    f.render_widget(canvas, area);
+}
@@ -0,0 +1,108 @@
+//! Underwater (drawdown) strip drawn beneath an equity chart.
+//!
+//! Used by the Chart panel. The x bounds match the equity chart's, so with
+//! equally wide y labels a drawdown episode sits under the bars it spans.
+
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::symbols;
+use ratatui::text::Span;
+use ratatui::widgets::{Axis, Chart, Dataset, GraphType};
+
+use crate::theme;
+
+/// Depth below the running peak at each point, as a fraction (0.0 at a new
+/// high, -0.2 twenty percent under it). Points before the first positive
+/// peak read 0.0.
+pub fn underwater(curve: &[f64]) -> Vec<f64> {
+    let mut peak = f64::NEG_INFINITY;
+    curve
+        .iter()
+        .map(|&v| {
+            peak = peak.max(v);
+            if peak > 0.0 { v / peak - 1.0 } else { 0.0 }
+        })
+        .collect()
+}
+
+/// Index and depth of the deepest point; None when the curve never dips.
+pub fn deepest(drawdown: &[f64]) -> Option<(usize, f64)> {
+    drawdown
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|&(_, d)| d < 0.0)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Draw the drawdown of `curve` as a filled area over `x_bounds`, with the
+/// deepest point marked and labeled. Y labels are right-aligned to
+/// `label_width` cells so the plot lines up with the chart above.
+pub fn render(f: &mut Frame, area: Rect, curve: &[f64], x_bounds: [f64; 2], label_width: usize) {
+    let drawdown = underwater(curve);
+    let floor = deepest(&drawdown).map_or(-0.01, |(_, d)| d);
+
+    let area_data: Vec<(f64, f64)> = drawdown
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| (i as f64, d))
+        .collect();
+    let max_point: Vec<(f64, f64)> = deepest(&drawdown)
+        .map(|(i, d)| vec![(i as f64, d)])
+        .unwrap_or_default();
+
+    let mut datasets = vec![Dataset::default()
+        .marker(symbols::Marker::Braille)
+        .style(theme::warning())
+        .graph_type(GraphType::Bar)
+        .data(&area_data)];
+    if let Some(&(i, d)) = max_point.first() {
+        datasets.push(
+            Dataset::default()
+                .name(format!("max {:.1}% @ bar {}", d * 100.0, i as usize))
+                .marker(symbols::Marker::Dot)
+                .style(theme::negative())
+                .graph_type(GraphType::Scatter)
+                .data(&max_point),
+        );
+    }
+
+    let chart = Chart::new(datasets)
+        .x_axis(Axis::default().style(theme::muted()).bounds(x_bounds))
+        .y_axis(
+            Axis::default()
+                .title(Span::styled("Drawdown", theme::muted()))
+                .style(theme::muted())
+                .bounds([floor * 1.05, 0.0])
+                .labels(vec![
+                    Span::styled(
+                        format!("{:>label_width$}", format!("{:.1}%", floor * 100.0)),
+                        theme::muted(),
+                    ),
+                    Span::styled(format!("{:>label_width$}", "0%"), theme::muted()),
+                ]),
+        );
+
+    f.render_widget(chart, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn underwater_measures_depth_below_the_running_peak() {
+        let dd = underwater(&[100.0, 110.0, 99.0, 88.0, 120.0, 114.0]);
+        let want = [0.0, 0.0, -0.1, -0.2, 0.0, -0.05];
+        for (got, want) in dd.iter().zip(want) {
+            assert!((got - want).abs() < 1e-12, "{dd:?}");
+        }
+        assert_eq!(deepest(&dd).unwrap().0, 3);
+    }
+
+    #[test]
+    fn rising_curve_has_no_deepest_point() {
+        assert!(deepest(&underwater(&[1.0, 2.0, 3.0])).is_none());
+        assert!(underwater(&[]).is_empty());
+    }
+}
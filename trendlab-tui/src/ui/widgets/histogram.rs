@@ -0,0 +1,220 @@
+//! Mini histogram of a binned distribution, with the median marked.
+//!
+//! Used by the detail overlay for trade returns and holding periods. The
+//! bins come precomputed from the runner ([`Histogram`]); this only draws
+//! them: a title row, the bars, a marker row with `▲` under the median's bin
+//! and a row of axis labels.
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::widgets::Widget;
+use trendlab_runner::Histogram;
+
+use crate::theme;
+
+const EIGHTHS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Rows besides the bars: title, median marker and axis labels.
+const CHROME_ROWS: u16 = 3;
+
+pub struct HistogramWidget<'a> {
+    title: &'a str,
+    histogram: &'a Histogram,
+    format: fn(f64) -> String,
+    signed: bool,
+}
+
+impl<'a> HistogramWidget<'a> {
+    pub fn new(title: &'a str, histogram: &'a Histogram) -> Self {
+        Self {
+            title,
+            histogram,
+            format: |v| format!("{v:.0}"),
+            signed: false,
+        }
+    }
+
+    /// How axis and median values are printed.
+    pub fn format(mut self, format: fn(f64) -> String) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Style bins below zero as losses and above as gains.
+    pub fn signed(mut self, signed: bool) -> Self {
+        self.signed = signed;
+        self
+    }
+
+    fn bar_style(&self, bin: usize) -> Style {
+        if !self.signed {
+            return theme::accent();
+        }
+        let edges = &self.histogram.edges;
+        let mid = (edges[bin] + edges[bin + 1]) / 2.0;
+        theme::metric_color(mid)
+    }
+}
+
+impl Widget for HistogramWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+        let h = self.histogram;
+        let title = format!("{} (n={})", self.title, h.sample_size);
+        buf.set_stringn(
+            area.x,
+            area.y,
+            &title,
+            area.width as usize,
+            theme::accent_bold(),
+        );
+
+        if h.is_empty() {
+            if area.height > 1 {
+                buf.set_stringn(
+                    area.x,
+                    area.y + 1,
+                    "too few trades to plot",
+                    area.width as usize,
+                    theme::muted(),
+                );
+            }
+            return;
+        }
+        if area.height <= CHROME_ROWS {
+            return;
+        }
+
+        let bar_rows = area.height - CHROME_ROWS;
+        let bins = h.counts.len();
+        let col_width = (area.width as usize / bins).max(1);
+        let plot_width = (col_width * bins).min(area.width as usize) as u16;
+        let max = h.counts.iter().copied().max().unwrap_or(0).max(1);
+        let top = area.y + 1;
+
+        for (bin, &count) in h.counts.iter().enumerate() {
+            let x0 = area.x + (bin * col_width) as u16;
+            if x0 >= area.x + area.width {
+                break;
+            }
+            let eighths = (count * bar_rows as usize * 8).div_ceil(max);
+            let style = self.bar_style(bin);
+            for row in 0..bar_rows {
+                // Rows count up from the bottom
+                let filled = eighths.saturating_sub(row as usize * 8).min(8);
+                if filled == 0 {
+                    continue;
+                }
+                let y = top + bar_rows - 1 - row;
+                for dx in 0..col_width.saturating_sub(1).max(1) as u16 {
+                    if x0 + dx < area.x + area.width {
+                        buf[(x0 + dx, y)]
+                            .set_char(EIGHTHS[filled - 1])
+                            .set_style(style);
+                    }
+                }
+            }
+        }
+
+        // Median marker under its bin
+        let marker_y = top + bar_rows;
+        let median_x = h.bin_of(h.median).map(|bin| {
+            let x = area.x + (bin * col_width + col_width.saturating_sub(1) / 2) as u16;
+            x.min(area.x + plot_width.saturating_sub(1))
+        });
+        if let Some(x) = median_x {
+            buf[(x, marker_y)].set_char('▲').set_style(theme::warning());
+        }
+
+        // Axis labels: low edge, median, high edge
+        let label_y = marker_y + 1;
+        let lo = (self.format)(h.edges[0]);
+        let hi = (self.format)(h.edges[bins]);
+        let med = format!("med {}", (self.format)(h.median));
+        let right = area.x + plot_width;
+        buf.set_stringn(area.x, label_y, &lo, plot_width as usize, theme::muted());
+        let hi_x = right.saturating_sub(hi.len() as u16).max(area.x);
+        buf.set_stringn(hi_x, label_y, &hi, (right - hi_x) as usize, theme::muted());
+        if let Some(x) = median_x {
+            // Centered on the marker when it fits between the edge labels
+            let want = x.saturating_sub(med.len() as u16 / 2);
+            let min_x = area.x + lo.len() as u16 + 1;
+            let max_x = hi_x.saturating_sub(med.len() as u16 + 1);
+            if min_x <= max_x {
+                buf.set_string(want.clamp(min_x, max_x), label_y, &med, theme::warning());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn draw(widget: HistogramWidget, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|f| f.render_widget(widget, f.area()))
+            .unwrap();
+        let buf = terminal.backend().buffer();
+        (0..height)
+            .map(|y| (0..width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn bars_scale_to_the_tallest_bin_and_mark_the_median() {
+        let h = Histogram {
+            edges: vec![0.0, 10.0, 20.0, 30.0],
+            counts: vec![4, 2, 1],
+            median: 8.0,
+            sample_size: 7,
+        };
+        let rows = draw(HistogramWidget::new("Hold", &h), 30, 6);
+        assert!(rows[0].starts_with("Hold (n=7)"));
+        // 3 bar rows of 10-column bins, 9 drawn: 24, 12 and 6 eighths
+        let cell = |y: usize, x: usize| rows[y].chars().nth(x).unwrap();
+        assert_eq!(rows[1].trim_end(), "█".repeat(9));
+        assert_eq!((cell(2, 8), cell(2, 10), cell(2, 20)), ('█', '▄', ' '));
+        assert_eq!((cell(3, 10), cell(3, 20), cell(3, 29)), ('█', '▆', ' '));
+        // Median 8.0 sits in the first bin
+        assert_eq!(rows[4].chars().position(|c| c == '▲'), Some(4));
+        assert!(rows[5].starts_with("0 "));
+        assert!(rows[5].trim_end().ends_with("30"));
+        assert!(rows[5].contains("med 8"));
+    }
+
+    #[test]
+    fn too_few_trades_render_a_placeholder() {
+        let h = Histogram {
+            sample_size: 2,
+            ..Histogram::default()
+        };
+        let rows = draw(HistogramWidget::new("Returns", &h), 30, 6);
+        assert!(rows[0].starts_with("Returns (n=2)"));
+        assert!(rows[1].starts_with("too few trades to plot"));
+        assert!(rows[2..].iter().all(|r| r.trim().is_empty()));
+    }
+
+    #[test]
+    fn signed_bins_color_losses_and_gains() {
+        let h = Histogram {
+            edges: vec![-0.1, 0.0, 0.1],
+            counts: vec![1, 2],
+            median: 0.02,
+            sample_size: 3,
+        };
+        let mut terminal = Terminal::new(TestBackend::new(20, 6)).unwrap();
+        terminal
+            .draw(|f| f.render_widget(HistogramWidget::new("PnL", &h).signed(true), f.area()))
+            .unwrap();
+        let buf = terminal.backend().buffer();
+        assert_eq!(buf[(0, 3)].fg, theme::negative().fg.unwrap());
+        assert_eq!(buf[(10, 3)].fg, theme::positive().fg.unwrap());
+    }
+}
@@ -1,4 +1,8 @@
 //! Reusable TUI widgets.
 
+pub mod candles;
+pub mod drawdown;
+pub mod histogram;
 pub mod slider;
+pub mod sparkline;
 pub mod tree;
@@ -0,0 +1,101 @@
+//! Block-character sparklines.
+//!
+//! Used by the Results panel's equity column and the detail overlay.
+
+use ratatui::style::Style;
+use ratatui::text::Span;
+use trendlab_runner::equity_sparkline;
+
+const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Depth below the running peak at which equity cells switch to the drawdown style.
+pub const DRAWDOWN_HIGHLIGHT: f64 = 0.05;
+
+/// One block per value, scaled between the series' min and max.
+///
+/// A flat series renders at mid height.
+pub fn blocks(values: &[f64]) -> String {
+    let lo = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let hi = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let span = hi - lo;
+    values
+        .iter()
+        .map(|v| {
+            let level = if span > 1e-12 {
+                ((v - lo) / span * 7.0).round() as usize
+            } else {
+                3
+            };
+            BLOCKS[level.min(7)]
+        })
+        .collect()
+}
+
+/// An equity sparkline exactly `width` cells wide, padded with spaces.
+///
+/// Cells more than [`DRAWDOWN_HIGHLIGHT`] below the running peak use
+/// `drawdown`; the rest use `base`.
+pub fn equity_spans(
+    values: &[f64],
+    width: usize,
+    base: Style,
+    drawdown: Style,
+) -> Vec<Span<'static>> {
+    let points = equity_sparkline(values, width);
+    let chars: Vec<char> = blocks(&points).chars().collect();
+
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_in_dd = false;
+    let mut peak = f64::NEG_INFINITY;
+    for (&v, c) in points.iter().zip(chars) {
+        peak = peak.max(v);
+        let in_dd = peak > 0.0 && v < peak * (1.0 - DRAWDOWN_HIGHLIGHT);
+        if in_dd != run_in_dd && !run.is_empty() {
+            let style = if run_in_dd { drawdown } else { base };
+            spans.push(Span::styled(std::mem::take(&mut run), style));
+        }
+        run_in_dd = in_dd;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        spans.push(Span::styled(run, if run_in_dd { drawdown } else { base }));
+    }
+    if points.len() < width {
+        spans.push(Span::styled(" ".repeat(width - points.len()), base));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme;
+
+    fn text(spans: &[Span]) -> String {
+        spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn pads_short_and_empty_curves() {
+        let spans = equity_spans(&[], 6, theme::muted(), theme::warning());
+        assert_eq!(text(&spans), "      ");
+
+        let spans = equity_spans(&[1.0, 1.0], 6, theme::muted(), theme::warning());
+        assert_eq!(text(&spans), "▄▄    ");
+    }
+
+    #[test]
+    fn drawdown_cells_use_drawdown_style() {
+        let curve = [100.0, 110.0, 90.0, 95.0, 120.0];
+        let spans = equity_spans(&curve, 5, theme::muted(), theme::warning());
+        let styles: Vec<(String, Style)> = spans
+            .iter()
+            .map(|s| (s.content.to_string(), s.style))
+            .collect();
+        assert_eq!(styles.len(), 3);
+        assert_eq!(styles[0].1, theme::muted());
+        assert_eq!(styles[1], ("▁▂".to_string(), theme::warning()));
+        assert_eq!(styles[2].1, theme::muted());
+    }
+}
@@ -2,26 +2,36 @@
 //!
 //! Communication with the TUI main thread is via `mpsc` channels.
 //! The worker creates a private rayon::ThreadPool (not the global pool).
+//!
+//! Saved equity curves are read on a separate loader thread so a chart
+//! drill-down never queues behind a running YOLO session.
 
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{Receiver, Sender};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::thread::{self, JoinHandle};
+use std::time::Duration;
 
 use chrono::NaiveDate;
 
 use trendlab_core::data::cache::ParquetCache;
 use trendlab_core::data::circuit_breaker::CircuitBreaker;
-use trendlab_core::data::provider::{DataError, DataProvider, DownloadProgress};
+use trendlab_core::data::ingest::{ingest_with, IngestOptions};
+use trendlab_core::data::provider::{fetch_with_retry, DataError, DownloadProgress};
 use trendlab_core::data::yahoo::YahooProvider;
+use trendlab_core::domain::FullHash;
 use trendlab_core::fingerprint::{StrategyConfig, TradingMode};
-use trendlab_runner::data_loader::LoadOptions;
+use trendlab_runner::data_loader::{LoadOptions, DEFAULT_STALE_AFTER_DAYS};
+use trendlab_runner::overlap::DEFAULT_OVERLAP_TOP_N;
 use trendlab_runner::{
-    BacktestResult, YoloConfig, YoloProgress,
-    run_backtest_from_data,
+    BacktestResult, ComputeUsage, CrossSymbolLeaderboard, RankingMetric, StopReason, YoloConfig,
+    YoloProgress, YoloTunables, run_backtest_replayed, run_modified,
 };
 
+use crate::app::LeaderboardDisplayEntry;
+
 /// Commands sent from the TUI to the worker.
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -33,7 +43,7 @@ pub enum WorkerCommand {
         cache_dir: PathBuf,
     },
     RunSingleBacktest {
-        config: StrategyConfig,
+        config: Box<StrategyConfig>,
         symbols: Vec<String>,
         trading_mode: TradingMode,
         initial_capital: f64,
@@ -42,15 +52,21 @@ pub enum WorkerCommand {
         end: NaiveDate,
         cache_dir: PathBuf,
     },
+    /// Re-run `base` with `config`, a hand-edited copy of its strategy (see
+    /// `trendlab_runner::run_modified`).
+    RunModified {
+        base: Arc<BacktestResult>,
+        config: Box<StrategyConfig>,
+        cache_dir: PathBuf,
+    },
     StartYolo {
-        config: YoloConfig,
+        config: Box<YoloConfig>,
         symbols: Vec<String>,
         cache_dir: PathBuf,
+        /// Live sliders, re-read by the YOLO loop before every iteration.
+        tunables: Arc<RwLock<YoloTunables>>,
     },
     StopYolo,
-    RequestEquityCurve {
-        index: usize,
-    },
     Shutdown,
 }
 
@@ -64,6 +80,11 @@ pub enum WorkerResponse {
         index: usize,
         total: usize,
     },
+    FetchRetry {
+        symbol: String,
+        attempt: u32,
+        error: String,
+    },
     FetchSymbolDone {
         symbol: String,
         success: bool,
@@ -74,7 +95,7 @@ pub enum WorkerResponse {
         failed: usize,
     },
 
-    // Single backtest
+    // Single backtest (or a manual variant, whose result carries provenance)
     BacktestComplete {
         result: Box<BacktestResult>,
     },
@@ -83,7 +104,7 @@ pub enum WorkerResponse {
     },
 
     // YOLO mode
-    YoloProgress(YoloProgress),
+    YoloProgress(Box<YoloProgress>),
     YoloDone {
         result: YoloResultSummary,
     },
@@ -91,11 +112,14 @@ pub enum WorkerResponse {
         error: String,
     },
 
-    // Equity curve (on demand)
+    // Equity curve (on demand, from the loader thread)
     EquityCurve {
         index: usize,
         curve: Vec<f64>,
-        label: String,
+    },
+    EquityCurveFailed {
+        index: usize,
+        error: String,
     },
 
     // General errors
@@ -111,8 +135,35 @@ pub enum WorkerResponse {
 pub struct YoloResultSummary {
     pub iterations_completed: usize,
     pub success_count: usize,
+    pub zero_trade_count: usize,
     pub error_count: usize,
     pub elapsed_secs: f64,
+    /// Compute spent over all symbols.
+    pub compute: ComputeUsage,
+    pub stop_reason: Option<StopReason>,
+    /// Top cross-symbol configs, best first, with overlap clusters.
+    pub champions: Vec<LeaderboardDisplayEntry>,
+}
+
+/// Number of cross-symbol champions sent back when a YOLO session ends.
+const YOLO_CHAMPIONS: usize = DEFAULT_OVERLAP_TOP_N;
+
+/// Top cross-symbol entries as display rows, marking each cluster's best entry.
+pub fn champion_entries(leaderboard: &CrossSymbolLeaderboard) -> Vec<LeaderboardDisplayEntry> {
+    let leaders: HashSet<&FullHash> = leaderboard
+        .diversified_top(YOLO_CHAMPIONS)
+        .into_iter()
+        .map(|e| &e.full_hash)
+        .collect();
+    leaderboard
+        .get_ranked(RankingMetric::AvgSharpe)
+        .into_iter()
+        .take(YOLO_CHAMPIONS)
+        .enumerate()
+        .map(|(i, e)| {
+            LeaderboardDisplayEntry::from_champion(i + 1, e, leaders.contains(&e.full_hash))
+        })
+        .collect()
 }
 
 /// Spawn the background worker thread.
@@ -129,6 +180,40 @@ pub fn spawn_worker(
         .expect("failed to spawn worker thread")
 }
 
+/// Request to load a saved run's equity curve for results entry `index`.
+#[derive(Debug)]
+pub struct EquityLoadRequest {
+    pub index: usize,
+    pub artifact_dir: PathBuf,
+}
+
+/// Spawn the equity loader thread. Exits when the request sender is dropped.
+pub fn spawn_equity_loader(
+    rx: Receiver<EquityLoadRequest>,
+    tx: Sender<WorkerResponse>,
+) -> JoinHandle<()> {
+    thread::Builder::new()
+        .name("trendlab-equity-loader".into())
+        .spawn(move || {
+            for req in rx {
+                let resp = match trendlab_runner::load_equity_curve(&req.artifact_dir) {
+                    Ok(curve) => WorkerResponse::EquityCurve {
+                        index: req.index,
+                        curve,
+                    },
+                    Err(e) => WorkerResponse::EquityCurveFailed {
+                        index: req.index,
+                        error: format!("{}: {e:#}", req.artifact_dir.display()),
+                    },
+                };
+                if tx.send(resp).is_err() {
+                    break;
+                }
+            }
+        })
+        .expect("failed to spawn equity loader thread")
+}
+
 fn worker_loop(
     rx: Receiver<WorkerCommand>,
     tx: Sender<WorkerResponse>,
@@ -166,19 +251,23 @@ fn handle_command(
             position_size_pct, start, end, cache_dir,
         } => {
             handle_single_backtest(
-                config, symbols, trading_mode, initial_capital,
+                *config, symbols, trading_mode, initial_capital,
                 position_size_pct, start, end, cache_dir, tx,
             );
         }
-        WorkerCommand::StartYolo { config, symbols, cache_dir } => {
-            handle_yolo(config, symbols, cache_dir, tx, cancel);
+        WorkerCommand::RunModified { base, config, cache_dir } => {
+            let response = match run_modified(&base, &config, &ParquetCache::new(&cache_dir)) {
+                Ok(result) => WorkerResponse::BacktestComplete { result: Box::new(result) },
+                Err(e) => WorkerResponse::BacktestError { error: e.to_string() },
+            };
+            let _ = tx.send(response);
+        }
+        WorkerCommand::StartYolo { config, symbols, cache_dir, tunables } => {
+            handle_yolo(*config, symbols, cache_dir, &tunables, tx, cancel);
         }
         WorkerCommand::StopYolo => {
             cancel.store(true, Ordering::Relaxed);
         }
-        WorkerCommand::RequestEquityCurve { .. } => {
-            // Will be implemented when results are stored on worker side
-        }
         WorkerCommand::Shutdown => {} // handled in loop
     }
 }
@@ -192,7 +281,7 @@ fn handle_fetch(
     cancel: &Arc<AtomicBool>,
 ) {
     let cache = ParquetCache::new(&cache_dir);
-    let circuit_breaker = Arc::new(CircuitBreaker::new(std::time::Duration::from_secs(1800)));
+    let circuit_breaker = Arc::new(CircuitBreaker::new(Duration::from_secs(1800)));
     let provider = YahooProvider::new(circuit_breaker);
     let progress = ChannelProgress { tx: tx.clone() };
 
@@ -207,9 +296,16 @@ fn handle_fetch(
 
         progress.on_start(symbol, i, total);
 
-        match provider.fetch(symbol, start, end) {
+        let (fetched, _attempts) = fetch_with_retry(&provider, symbol, start, end, Some(&progress));
+        let ingested = fetched.and_then(|result| {
+            ingest_with(result.bars, &IngestOptions::daily(start, end))
+        });
+        match ingested {
             Ok(result) => {
-                if let Err(e) = cache.write(symbol, &result.bars) {
+                let written = cache
+                    .write(symbol, &result.bars)
+                    .and_then(|()| cache.write_ingest_report(symbol, &result.report));
+                if let Err(e) = written {
                     let err_msg = format!("cache write failed: {e}");
                     progress.on_complete(symbol, i, total, &Err(DataError::CacheError(err_msg.clone())));
                     failed += 1;
@@ -241,12 +337,16 @@ fn handle_single_backtest(
     tx: &Sender<WorkerResponse>,
 ) {
     let cache = ParquetCache::new(&cache_dir);
+    // Downloads happen only from the Data panel; backtests read the cache
     let opts = LoadOptions {
         start,
         end,
-        offline: false,
+        offline: true,
         synthetic: false,
         force: false,
+        strict: false,
+        stale_after_days: Some(DEFAULT_STALE_AFTER_DAYS),
+        interval: Default::default(),
     };
 
     let sym_refs: Vec<&str> = symbols.iter().map(|s| s.as_str()).collect();
@@ -255,7 +355,7 @@ fn handle_single_backtest(
         Ok(loaded) => {
             // Run on first symbol
             let symbol = symbols.first().map(|s| s.as_str()).unwrap_or("SPY");
-            match run_backtest_from_data(
+            match run_backtest_replayed(
                 &config,
                 &loaded.aligned,
                 symbol,
@@ -266,7 +366,11 @@ fn handle_single_backtest(
                 &loaded.dataset_hash,
                 loaded.has_synthetic,
             ) {
-                Ok(result) => {
+                Ok(mut result) => {
+                    result
+                        .data_quality_warnings
+                        .extend(loaded.warnings_for(symbol).cloned());
+                    result.truncated_range = loaded.truncated_range(symbol).cloned();
                     let _ = tx.send(WorkerResponse::BacktestComplete { result: Box::new(result) });
                 }
                 Err(e) => {
@@ -288,50 +392,62 @@ fn handle_yolo(
     config: YoloConfig,
     symbols: Vec<String>,
     cache_dir: PathBuf,
+    tunables: &RwLock<YoloTunables>,
     tx: &Sender<WorkerResponse>,
     cancel: &Arc<AtomicBool>,
 ) {
     let cache = ParquetCache::new(&cache_dir);
+    // Downloads happen only from the Data panel; YOLO reads the cache
     let opts = LoadOptions {
         start: config.start_date,
         end: config.end_date,
-        offline: false,
+        offline: true,
         synthetic: false,
         force: false,
+        strict: false,
+        stale_after_days: Some(DEFAULT_STALE_AFTER_DAYS),
+        interval: Default::default(),
     };
 
-    let sym_refs: Vec<&str> = symbols.iter().map(|s| s.as_str()).collect();
-
-    match trendlab_runner::load_bars(&sym_refs, &cache, None, None, &opts) {
-        Ok(loaded) => {
-            let tx_clone = tx.clone();
-            let progress_cb = move |progress: &YoloProgress| {
-                let _ = tx_clone.send(WorkerResponse::YoloProgress(progress.clone()));
-            };
+    let tx_clone = tx.clone();
+    let progress_cb = move |progress: &YoloProgress| {
+        let _ = tx_clone.send(WorkerResponse::YoloProgress(Box::new(progress.clone())));
+    };
 
-            match trendlab_runner::run_yolo(
-                &config,
-                &loaded,
-                &symbols,
-                Some(&progress_cb),
-                Some(cancel.as_ref()),
-            ) {
-                Ok(result) => {
-                    let _ = tx.send(WorkerResponse::YoloDone {
-                        result: YoloResultSummary {
-                            iterations_completed: result.iterations_completed,
-                            success_count: result.success_count,
-                            error_count: result.error_count,
-                            elapsed_secs: result.elapsed_secs,
-                        },
-                    });
-                }
-                Err(e) => {
-                    let _ = tx.send(WorkerResponse::YoloError {
-                        error: e.to_string(),
+    match trendlab_runner::run_yolo_from_cache(
+        &config,
+        &symbols,
+        &cache,
+        None,
+        &opts,
+        Some(&progress_cb),
+        Some(cancel.as_ref()),
+        Some(tunables),
+    ) {
+        Ok(result) => {
+            // The session is saved next to its history, including after a
+            // cancel on quit (the main thread joins the worker before exit)
+            if let Some(dir) = config.history_path.as_deref().and_then(Path::parent) {
+                if let Err(e) = result.save(dir) {
+                    let _ = tx.send(WorkerResponse::Error {
+                        category: "data".into(),
+                        message: format!("Failed to save YOLO session: {e}"),
+                        context: dir.display().to_string(),
                     });
                 }
             }
+            let _ = tx.send(WorkerResponse::YoloDone {
+                result: YoloResultSummary {
+                    iterations_completed: result.iterations_completed,
+                    success_count: result.success_count,
+                    zero_trade_count: result.zero_trade_count,
+                    error_count: result.error_count,
+                    elapsed_secs: result.elapsed_secs,
+                    compute: result.compute.total,
+                    stop_reason: result.stop_reason,
+                    champions: champion_entries(&result.cross_leaderboard),
+                },
+            });
         }
         Err(e) => {
             let _ = tx.send(WorkerResponse::YoloError {
@@ -375,6 +491,14 @@ impl DownloadProgress for ChannelProgress {
             failed,
         });
     }
+
+    fn on_retry(&self, symbol: &str, attempt: u32, _delay: Duration, error: &DataError) {
+        let _ = self.tx.send(WorkerResponse::FetchRetry {
+            symbol: symbol.to_string(),
+            attempt,
+            error: error.to_string(),
+        });
+    }
 }
 
 #[cfg(test)]